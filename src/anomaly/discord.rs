@@ -0,0 +1,108 @@
+//! # Matrix Profile Discords
+//!
+//! Each new window of closes is matched against the recent history; one farther
+//! from its closest match than the configured quantile of historical
+//! nearest-match distances has no precedent.
+
+use chrono::{DateTime, Utc};
+
+use crate::data::ForexDataPoint;
+use crate::patterns::matrix_profile::{MatrixProfile, MatrixProfileConfig, SubsequenceIndex};
+
+/// Outcome of observing one window
+#[derive(Debug, Clone)]
+pub struct DiscordObservation {
+    /// z-normalized distance to the closest historical window
+    pub distance: f64,
+    pub threshold: f64,
+    /// Fraction of historical windows closer to their own match than this one
+    pub rank: f64,
+    /// First bar of the closest historical window
+    pub nearest_start: DateTime<Utc>,
+    pub is_discord: bool,
+}
+
+/// Historical windows and the distribution of their nearest-match distances
+#[derive(Debug, Clone)]
+pub struct DiscordDetector {
+    config: MatrixProfileConfig,
+    reference: SubsequenceIndex,
+    /// Timestamps of the reference bars
+    timestamps: Vec<DateTime<Utc>>,
+    /// Sorted matrix profile of the reference
+    historical_distances: Vec<f64>,
+    /// Observations since the last discord, which suppresses the overlapping windows after it
+    since_discord: usize,
+}
+
+impl DiscordDetector {
+    /// Self-join the last `max_reference_bars` of `history`; `None` when it holds
+    /// too few windows
+    pub fn fit(history: &[ForexDataPoint], config: MatrixProfileConfig) -> Option<Self> {
+        let reference = &history[history.len().saturating_sub(config.max_reference_bars)..];
+        let profile = MatrixProfile::self_join(reference, &config)?;
+        let mut historical_distances: Vec<f64> = profile.profile.into_iter().filter(|d| d.is_finite()).collect();
+        if historical_distances.is_empty() {
+            return None;
+        }
+        historical_distances.sort_by(|a, b| a.total_cmp(b));
+        let closes: Vec<f64> = reference.iter().map(|point| point.close).collect();
+        Some(Self {
+            reference: SubsequenceIndex::new(&closes, config.window)?,
+            timestamps: reference.iter().map(|point| point.timestamp).collect(),
+            historical_distances,
+            since_discord: usize::MAX,
+            config,
+        })
+    }
+
+    /// Bars needed to form one window
+    pub fn bars_needed(&self) -> usize {
+        self.config.window
+    }
+
+    /// Distance beyond which a window is a discord
+    pub fn threshold(&self) -> f64 {
+        let distances = &self.historical_distances;
+        let q = self.config.discord_quantile.clamp(0.0, 1.0);
+        distances[((q * (distances.len() - 1) as f64).round() as usize).min(distances.len() - 1)]
+    }
+
+    /// Score the window ending the last bars of `bars`. Historical windows
+    /// overlapping it (within the exclusion zone) are not matches, and the windows
+    /// after a discord that still overlap it are not reported again.
+    pub fn observe(&mut self, bars: &[ForexDataPoint]) -> Option<DiscordObservation> {
+        let window = self.config.window;
+        if bars.len() < window {
+            return None;
+        }
+        let query = &bars[bars.len() - window..];
+        let closes: Vec<f64> = query.iter().map(|point| point.close).collect();
+        let (first, last) = (query[0].timestamp, query[window - 1].timestamp);
+
+        // A historical window is trivial when it overlaps the query once widened
+        // by the exclusion zone
+        let zone = self.config.exclusion_zone();
+        let end = self.timestamps.len() - 1;
+        let trivial = |j: usize| {
+            self.timestamps[j.saturating_sub(zone)] <= last && self.timestamps[(j + window - 1 + zone).min(end)] >= first
+        };
+        let (nearest, distance) = self.reference.nearest(&closes, trivial)?;
+
+        let threshold = self.threshold();
+        self.since_discord = self.since_discord.saturating_add(1);
+        let is_discord = distance > threshold && self.since_discord > window;
+        if is_discord {
+            self.since_discord = 0;
+        }
+
+        let distances = &self.historical_distances;
+        Some(DiscordObservation {
+            distance,
+            threshold,
+            rank: distances.partition_point(|d| *d < distance) as f64 / distances.len() as f64,
+            nearest_start: self.timestamps[nearest],
+            is_discord,
+        })
+    }
+}
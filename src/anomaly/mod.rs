@@ -1,17 +1,37 @@
 //! # Anomaly Detection from Temporal Symmetries
 //! 
-//! Detect deviations from discovered temporal symmetries in synthetic forex data
+//! Detect deviations from discovered temporal symmetries in historical, live or synthetic forex data
 
-use anyhow::Result;
-use chrono::{DateTime, Utc, Timelike};
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
 use nalgebra::{DVector, DMatrix};
 
-use crate::data::ForexDataPoint;
-use crate::synthetic::SyntheticForexPoint;
-use crate::symmetry::TemporalSymmetry;
+use crate::calendar::economic::EconomicCalendar;
+use crate::calendar::{HolidayCalendar, ThinMarketPolicy, THIN_MARKET_EVENT};
+use crate::correlation::CorrelationRegimeChange;
+use crate::data::{ForexDataPoint, MarketPoint};
+use crate::embedded_db::EmbeddedForexDB;
+use crate::ids::{AnomalyId, CycleId, SymmetryId};
+use crate::symmetry::{SymmetryDetector, SymmetryDetectorConfig, TemporalSymmetry};
 use crate::patterns::HiddenCycle;
+use crate::patterns::matrix_profile::MatrixProfileConfig;
+use crate::regimes::{RegimeConfig, RegimeModel, RegimeProbabilities};
+use crate::sessions::{SessionConfig, SessionVolatility, TradingSessions};
+use crate::stats::momentum::{cumulative_return, MomentumSurface, MomentumSurfaceConfig};
+use crate::stats::vol_surface::{bar_volatility, VolSurface, VolSurfaceConfig, VolatilityBucket};
+use crate::stats::volatility::{VolatilityConfig, VolatilityModel};
+use crate::stats::volume_profile::VolumeProfile;
+
+pub mod discord;
+pub mod novelty;
+pub mod signal_policy;
+pub mod suppression;
+
+use discord::DiscordDetector;
+use novelty::{NoveltyConfig, PatternClusters};
+use signal_policy::{SignalPolicy, SignalPolicyConfig};
 
 /// Anomaly detection engine for temporal symmetry deviations
 pub struct TemporalAnomalyDetector {
@@ -29,6 +49,33 @@ pub struct TemporalAnomalyDetector {
     
     /// Recent anomaly history for pattern learning
     anomaly_history: VecDeque<DetectedAnomaly>,
+    
+    /// Most recent sensitivity calibration, if auto-tuning is enabled
+    calibration: Option<SensitivityCalibration>,
+    
+    /// Return-shape clusters for novel pattern discovery, updated online
+    pattern_clusters: PatternClusters,
+    
+    /// Historical windows new ones are matched against, when matrix profile discords are enabled
+    discord_detector: Option<DiscordDetector>,
+    
+    /// Pair whose holidays and releases apply, set together with either calendar
+    pair: Option<String>,
+    
+    /// Holidays whose thin-market bars are skipped or down-weighted
+    holiday_calendar: HolidayCalendar,
+    
+    /// Scheduled releases listed in market contexts and discounting price shocks
+    economic_calendar: EconomicCalendar,
+    
+    /// Trading sessions in their local time zones
+    sessions: TradingSessions,
+    
+    /// Measures how strongly each expected symmetry still holds
+    symmetry_detector: SymmetryDetector,
+    
+    /// Decides the trading signal of each anomaly
+    signal_policy: Box<dyn SignalPolicy>,
 }
 
 /// Configuration for anomaly detection
@@ -54,6 +101,92 @@ pub struct AnomalyDetectionConfig {
     
     /// Price volatility anomaly weight
     pub volatility_anomaly_weight: f64,
+    
+    /// Target anomaly rate per day for automatic sensitivity calibration (None = fixed threshold)
+    #[serde(default)]
+    pub target_anomalies_per_day: Option<f64>,
+    
+    /// Days between automatic recalibrations
+    #[serde(default = "default_recalibration_interval_days")]
+    pub recalibration_interval_days: u32,
+    
+    /// Bars in the cumulative return checked by the momentum detector
+    #[serde(default = "default_momentum_lookback_bars")]
+    pub momentum_lookback_bars: usize,
+    
+    /// Quantile of historical returns (same hour of the week) a move must exceed to be a shock
+    #[serde(default = "default_momentum_quantile")]
+    pub momentum_quantile: f64,
+    
+    /// Correlation with a cycle template that counts as following it (and its negative as inverted)
+    #[serde(default = "default_inversion_correlation")]
+    pub inversion_correlation: f64,
+    
+    /// Online clustering settings for novel pattern discovery
+    #[serde(default)]
+    pub novelty: NoveltyConfig,
+    
+    /// Matrix profile discords reported as novel patterns (None = disabled)
+    #[serde(default)]
+    pub matrix_profile: Option<MatrixProfileConfig>,
+    
+    /// A bar range below this fraction of the expected range for its hour counts as illiquid
+    #[serde(default = "default_liquidity_range_ratio")]
+    pub liquidity_range_ratio: f64,
+    
+    /// Volume below this fraction of the expected volume for its hour counts as illiquid
+    #[serde(default = "default_liquidity_volume_ratio")]
+    pub liquidity_volume_ratio: f64,
+    
+    /// Rules (or a trained agent) turning anomalies into trading signals
+    #[serde(default)]
+    pub signal_policy: SignalPolicyConfig,
+    
+    /// Trading sessions labelling market contexts and stratifying volatility baselines
+    #[serde(default)]
+    pub sessions: SessionConfig,
+    
+    /// Conditional volatility model scaling the baseline for bars after the history
+    #[serde(default)]
+    pub volatility: VolatilityConfig,
+    
+    /// Market regime model conditioning the volatility baseline of history bars
+    #[serde(default)]
+    pub regimes: RegimeConfig,
+}
+
+fn default_recalibration_interval_days() -> u32 {
+    7
+}
+
+fn default_momentum_lookback_bars() -> usize {
+    MomentumSurfaceConfig::default().lookback_bars
+}
+
+fn default_momentum_quantile() -> f64 {
+    0.99
+}
+
+fn default_inversion_correlation() -> f64 {
+    0.5
+}
+
+fn default_liquidity_range_ratio() -> f64 {
+    0.3
+}
+
+fn default_liquidity_volume_ratio() -> f64 {
+    0.25
+}
+
+/// Result of calibrating the sensitivity threshold against a pair's history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SensitivityCalibration {
+    pub sensitivity_threshold: f64,
+    pub target_rate_per_day: f64,
+    pub estimated_rate_per_day: f64,
+    pub samples: usize,
+    pub calibrated_at: DateTime<Utc>,
 }
 
 /// Baseline statistics from historical data
@@ -63,39 +196,58 @@ pub struct BaselineStatistics {
     pub price_std_dev: f64,
     pub mean_volatility: f64,
     pub volatility_std_dev: f64,
+    /// Expected bar volatility by hour of the week
+    pub volatility_surface: VolSurface,
+    /// Expected bar volatility by trading session, in each session's local hours
+    pub session_volatility: SessionVolatility,
+    /// Distribution of N-bar cumulative returns by hour of the week
+    pub momentum_surface: MomentumSurface,
+    /// Expected bar volume by hour of the week (empty when the history has no volume)
+    pub volume_profile: VolumeProfile,
+    /// Conditional volatility fitted on the history and updated with each later bar
+    /// (None when the history is too short)
+    pub volatility_model: Option<VolatilityModel>,
+    /// Market regimes fitted on the history (None when it is too short)
+    pub regime_model: Option<RegimeModel>,
+    /// Regime probabilities of each history bar, judged from the bars before it
+    pub regime_path: Vec<RegimeProbabilities>,
+    /// Bar volatility in each regime state relative to the unconditional baseline
+    pub regime_volatility_scales: Vec<f64>,
+    /// Median spacing between bars in seconds, the time unit of cycle periods and phases
+    pub bar_seconds: f64,
     pub symmetry_strength_distribution: Vec<f64>,
     pub cycle_strength_distribution: Vec<f64>,
     pub temporal_correlation_matrix: DMatrix<f64>,
 }
 
 /// Detected anomaly structure
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DetectedAnomaly {
-    pub id: String,
+    pub id: AnomalyId,
     pub timestamp: DateTime<Utc>,
     pub anomaly_type: AnomalyType,
     pub severity: AnomalySeverity,
     pub confidence: f64,
     pub deviation_magnitude: f64,
-    pub affected_symmetries: Vec<String>,
-    pub affected_cycles: Vec<String>,
+    pub affected_symmetries: Vec<SymmetryId>,
+    pub affected_cycles: Vec<CycleId>,
     pub market_context: MarketContext,
     pub trading_signal: Option<AnomalyTradingSignal>,
 }
 
 /// Types of anomalies detected
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AnomalyType {
     /// Temporal symmetry broken or significantly weakened
     SymmetryBreakdown {
-        symmetry_id: String,
+        symmetry_id: SymmetryId,
         expected_strength: f64,
         actual_strength: f64,
     },
     
     /// Hidden cycle disrupted or phase-shifted
     CycleDisruption {
-        cycle_id: String,
+        cycle_id: CycleId,
         expected_phase: f64,
         actual_phase: f64,
     },
@@ -124,10 +276,99 @@ pub enum AnomalyType {
         pattern_signature: String,
         emergence_confidence: f64,
     },
+    
+    /// Abnormally thin market: small range and low or missing volume in a normally active hour
+    LiquidityGap {
+        expected_volatility: f64,
+        actual_volatility: f64,
+        /// `None` when the history carries no volume
+        expected_volume: Option<f64>,
+        actual_volume: Option<f64>,
+    },
+    
+    /// Directional move beyond the usual N-bar return for this time of the week
+    MomentumShock {
+        lookback_bars: usize,
+        /// Signed return over the lookback
+        cumulative_return: f64,
+        /// Absolute return at `quantile` of the time-conditional history
+        threshold_return: f64,
+        quantile: f64,
+    },
 }
 
-/// Severity levels for anomalies
-#[derive(Debug, Clone, Serialize)]
+impl AnomalyType {
+    /// Variant name, as used in suppression rules and reports
+    pub fn name(&self) -> &'static str {
+        match self {
+            AnomalyType::SymmetryBreakdown { .. } => "SymmetryBreakdown",
+            AnomalyType::CycleDisruption { .. } => "CycleDisruption",
+            AnomalyType::VolatilitySpike { .. } => "VolatilitySpike",
+            AnomalyType::PatternInversion { .. } => "PatternInversion",
+            AnomalyType::CorrelationBreakdown { .. } => "CorrelationBreakdown",
+            AnomalyType::NovelPattern { .. } => "NovelPattern",
+            AnomalyType::MomentumShock { .. } => "MomentumShock",
+            AnomalyType::LiquidityGap { .. } => "LiquidityGap",
+        }
+    }
+    
+    /// Whether the anomaly is a sudden price move that a scheduled release explains
+    pub fn is_price_shock(&self) -> bool {
+        matches!(self, AnomalyType::VolatilitySpike { .. } | AnomalyType::MomentumShock { .. })
+    }
+}
+
+impl DetectedAnomaly {
+    /// Symmetry or cycle the anomaly concerns, or `*` when it is not tied to one
+    pub fn pattern_id(&self) -> &str {
+        match &self.anomaly_type {
+            AnomalyType::SymmetryBreakdown { symmetry_id, .. } => symmetry_id,
+            AnomalyType::CycleDisruption { cycle_id, .. } => cycle_id,
+            _ => self.affected_symmetries.first().map(SymmetryId::as_str)
+                .or(self.affected_cycles.first().map(CycleId::as_str))
+                .unwrap_or(suppression::WILDCARD),
+        }
+    }
+}
+
+impl From<&CorrelationRegimeChange> for DetectedAnomaly {
+    /// A correlation regime change as a breakdown of the old correlation, at the bar it was detected
+    fn from(change: &CorrelationRegimeChange) -> Self {
+        let shift = (change.correlation_after - change.correlation_before).abs();
+        DetectedAnomaly {
+            id: AnomalyId::new(),
+            timestamp: change.detected_at,
+            anomaly_type: AnomalyType::CorrelationBreakdown {
+                correlation_pair: (change.pair1.clone(), change.pair2.clone()),
+                expected_correlation: change.correlation_before,
+                actual_correlation: change.correlation_after,
+            },
+            severity: match shift {
+                x if x < 0.2 => AnomalySeverity::Low,
+                x if x < 0.4 => AnomalySeverity::Medium,
+                x if x < 0.7 => AnomalySeverity::High,
+                _ => AnomalySeverity::Critical,
+            },
+            confidence: change.confidence(),
+            deviation_magnitude: shift,
+            affected_symmetries: Vec::new(),
+            affected_cycles: Vec::new(),
+            market_context: MarketContext {
+                session: TradingSessions::default().label(change.detected_at),
+                volatility_regime: "Unknown".to_string(),
+                trend_direction: "Unknown".to_string(),
+                recent_events: vec![format!("{}/{} correlation regime change from {}", change.pair1, change.pair2,
+                                            change.change_point.format("%Y-%m-%d %H:%M"))],
+                event_proximity: 0.0,
+            },
+            // A hedge that stopped hedging says nothing about direction
+            trading_signal: None,
+        }
+    }
+}
+
+/// Severity levels for anomalies, in increasing order
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum AnomalySeverity {
     Low,      // Minor deviation, likely noise
     Medium,   // Significant deviation, potential trading opportunity
@@ -136,16 +377,18 @@ pub enum AnomalySeverity {
 }
 
 /// Market context during anomaly
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MarketContext {
     pub session: String,           // London, NY, Asian, etc.
     pub volatility_regime: String, // Low, Normal, High, Crisis
     pub trend_direction: String,   // Bullish, Bearish, Sideways
     pub recent_events: Vec<String>, // Economic events, news, etc.
+    #[serde(default)]
+    pub event_proximity: f64,       // 0 far from scheduled releases, 1 at a high-impact one
 }
 
 /// Trading signal generated from anomaly
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnomalyTradingSignal {
     pub signal_type: String,       // Buy, Sell, Hold
     pub strength: f64,             // Signal strength (0.0-1.0)
@@ -165,6 +408,19 @@ impl Default for AnomalyDetectionConfig {
             symmetry_deviation_weight: 0.4,
             cycle_deviation_weight: 0.3,
             volatility_anomaly_weight: 0.3,
+            target_anomalies_per_day: None,
+            recalibration_interval_days: default_recalibration_interval_days(),
+            momentum_lookback_bars: default_momentum_lookback_bars(),
+            momentum_quantile: default_momentum_quantile(),
+            inversion_correlation: default_inversion_correlation(),
+            novelty: NoveltyConfig::default(),
+            matrix_profile: None,
+            liquidity_range_ratio: default_liquidity_range_ratio(),
+            liquidity_volume_ratio: default_liquidity_volume_ratio(),
+            signal_policy: SignalPolicyConfig::default(),
+            sessions: SessionConfig::default(),
+            volatility: VolatilityConfig::default(),
+            regimes: RegimeConfig::default(),
         }
     }
 }
@@ -177,19 +433,264 @@ impl TemporalAnomalyDetector {
         historical_data: &[ForexDataPoint],
         config: AnomalyDetectionConfig,
     ) -> Result<Self> {
+        let sessions = TradingSessions::new(config.sessions.clone())?;
         let baseline_statistics = Self::calculate_baseline_statistics(
             historical_data,
             &expected_symmetries,
             &expected_cycles,
+            &config,
+            &sessions,
         )?;
         
-        Ok(Self {
+        let pattern_clusters = PatternClusters::fit(historical_data, config.novelty.clone());
+        let discord_detector = config.matrix_profile.clone()
+            .and_then(|matrix_profile| DiscordDetector::fit(historical_data, matrix_profile));
+        let signal_policy = config.signal_policy.build()?;
+        
+        let mut detector = Self {
             expected_symmetries,
             expected_cycles,
             config,
             baseline_statistics,
             anomaly_history: VecDeque::with_capacity(1000),
-        })
+            calibration: None,
+            pattern_clusters,
+            discord_detector,
+            pair: None,
+            holiday_calendar: HolidayCalendar::disabled(),
+            economic_calendar: EconomicCalendar::default(),
+            sessions,
+            symmetry_detector: SymmetryDetector::new(SymmetryDetectorConfig::default())?,
+            signal_policy,
+        };
+        
+        if detector.config.target_anomalies_per_day.is_some()
+            && historical_data.len() > 1
+            && detector.baseline_statistics.volatility_surface.global().std_dev > 0.0 {
+            detector.calibrate_sensitivity(historical_data)?;
+        }
+        
+        Ok(detector)
+    }
+    
+    /// Create a detector expecting the symmetries and cycles of the latest run
+    /// recorded in `db` for `pair` and `timeframe`
+    pub fn from_db(
+        db: &EmbeddedForexDB,
+        pair: &str,
+        timeframe: &str,
+        historical_data: &[ForexDataPoint],
+        config: AnomalyDetectionConfig,
+    ) -> Result<Self> {
+        let symmetries = db.latest_symmetries(pair, timeframe)?;
+        let cycles = db.latest_cycles(pair, timeframe)?;
+        if symmetries.is_empty() && cycles.is_empty() {
+            bail!("no symmetries or cycles recorded for {} {}", pair, timeframe);
+        }
+        Self::new(symmetries, cycles, historical_data, config)
+    }
+    
+    /// Skip or down-weight bars of `pair` that fall in thin holiday markets
+    pub fn with_holiday_calendar(mut self, pair: &str, holiday_calendar: HolidayCalendar) -> Self {
+        self.pair = Some(pair.to_string());
+        self.holiday_calendar = holiday_calendar;
+        self
+    }
+    
+    /// List `pair`'s scheduled releases in the market context of nearby anomalies
+    /// and trust price shocks around them less
+    pub fn with_economic_calendar(mut self, pair: &str, economic_calendar: EconomicCalendar) -> Self {
+        self.pair = Some(pair.to_string());
+        self.economic_calendar = economic_calendar;
+        self
+    }
+    
+    /// Event proximity of the bar at `timestamp`, 0 without a calendar
+    pub fn event_proximity(&self, timestamp: DateTime<Utc>) -> f64 {
+        match &self.pair {
+            Some(pair) if !self.economic_calendar.is_empty() => self.economic_calendar.proximity(pair, timestamp),
+            _ => 0.0,
+        }
+    }
+    
+    /// Decide trading signals with `signal_policy` instead of the configured one
+    pub fn with_signal_policy(mut self, signal_policy: Box<dyn SignalPolicy>) -> Self {
+        self.signal_policy = signal_policy;
+        self
+    }
+    
+    /// Name of the policy deciding trading signals
+    pub fn signal_policy(&self) -> &str {
+        self.signal_policy.name()
+    }
+    
+    /// Trading sessions open at `timestamp`, as the market context labels them
+    pub fn session(&self, timestamp: DateTime<Utc>) -> String {
+        self.sessions.label(timestamp)
+    }
+    
+    /// Expected volatility of a bar at `timestamp`: its hour of the week when well sampled,
+    /// otherwise its session, which follows local opening hours across DST changes
+    pub fn expected_volatility(&self, timestamp: DateTime<Utc>) -> VolatilityBucket {
+        let baseline = &self.baseline_statistics;
+        unconditional_volatility(&baseline.volatility_surface, &baseline.session_volatility, &self.sessions, timestamp)
+    }
+    
+    /// [`Self::expected_volatility`] scaled by the volatility model's forecast over its
+    /// long-run level, for bars after the history; bars the model was fitted on keep
+    /// the unconditional baseline
+    pub fn conditional_expected_volatility(&self, timestamp: DateTime<Utc>) -> VolatilityBucket {
+        let expected = self.expected_volatility(timestamp);
+        match &self.baseline_statistics.volatility_model {
+            Some(model) if timestamp > model.as_of => {
+                let scale = model.volatility_ratio();
+                VolatilityBucket { mean: expected.mean * scale, std_dev: expected.std_dev * scale, ..expected }
+            }
+            _ => expected,
+        }
+    }
+    
+    /// Baseline the volatility detector compares a bar with: bars after the history follow
+    /// [`Self::conditional_expected_volatility`], whose forecast already tracks the regime
+    /// bar by bar, and bars of the history are scaled by how volatile the regimes they
+    /// were likely in (judged from the bars before them) ran
+    pub fn regime_expected_volatility(&self, timestamp: DateTime<Utc>) -> VolatilityBucket {
+        let baseline = &self.baseline_statistics;
+        let expected = self.conditional_expected_volatility(timestamp);
+        if baseline.volatility_model.as_ref().is_some_and(|model| timestamp > model.as_of) {
+            return expected;
+        }
+        match baseline.regime_path.binary_search_by_key(&timestamp, |p| p.timestamp) {
+            Ok(i) => {
+                let scale: f64 = baseline.regime_path[i].states.iter().zip(&baseline.regime_volatility_scales).map(|(p, s)| p * s).sum();
+                VolatilityBucket { mean: expected.mean * scale, std_dev: expected.std_dev * scale, ..expected }
+            }
+            Err(_) => expected,
+        }
+    }
+    
+    /// Regime model fitted on the history
+    pub fn regime_model(&self) -> Option<&RegimeModel> {
+        self.baseline_statistics.regime_model.as_ref()
+    }
+    
+    /// Regime probabilities of each history bar, from the bars before it
+    pub fn regime_path(&self) -> &[RegimeProbabilities] {
+        &self.baseline_statistics.regime_path
+    }
+    
+    /// Volatility model fitted on the history, as of the last bar seen
+    pub fn volatility_model(&self) -> Option<&VolatilityModel> {
+        self.baseline_statistics.volatility_model.as_ref()
+    }
+    
+    /// Why the bar at `timestamp` trades in a thin market, if it does
+    pub fn thin_market(&self, timestamp: DateTime<Utc>) -> Option<String> {
+        self.holiday_calendar.check(self.pair.as_deref()?, timestamp)
+    }
+    
+    /// Current sensitivity threshold (calibrated or configured)
+    pub fn sensitivity_threshold(&self) -> f64 {
+        self.config.sensitivity_threshold
+    }
+    
+    /// Symmetries deviations are measured against
+    pub fn expected_symmetries(&self) -> &[TemporalSymmetry] {
+        &self.expected_symmetries
+    }
+    
+    /// Cycles deviations are measured against
+    pub fn expected_cycles(&self) -> &[HiddenCycle] {
+        &self.expected_cycles
+    }
+    
+    /// Most recent sensitivity calibration
+    pub fn calibration(&self) -> Option<&SensitivityCalibration> {
+        self.calibration.as_ref()
+    }
+    
+    /// Use a calibration made earlier, e.g. one saved with a pair model, instead of
+    /// the one fitted to the current history
+    pub fn with_calibration(mut self, calibration: SensitivityCalibration) -> Self {
+        self.config.sensitivity_threshold = calibration.sensitivity_threshold;
+        self.calibration = Some(calibration);
+        self
+    }
+    
+    /// Calibrate the sensitivity threshold so the volatility detector fires at the
+    /// configured target rate on this pair's historical distribution
+    pub fn calibrate_sensitivity(&mut self, historical_data: &[ForexDataPoint]) -> Result<SensitivityCalibration> {
+        let target_rate_per_day = self.config.target_anomalies_per_day
+            .ok_or_else(|| anyhow::anyhow!("No target anomaly rate configured"))?;
+        
+        if historical_data.len() < 2 || self.baseline_statistics.volatility_surface.global().std_dev <= 0.0 {
+            return Err(anyhow::anyhow!("Not enough historical data to calibrate sensitivity"));
+        }
+        
+        // Estimate sampling frequency from the median spacing between points
+        let mut spacings: Vec<i64> = historical_data.windows(2)
+            .map(|w| (w[1].timestamp - w[0].timestamp).num_seconds())
+            .filter(|s| *s > 0)
+            .collect();
+        spacings.sort_unstable();
+        let median_spacing = spacings.get(spacings.len() / 2).copied().unwrap_or(86400).max(1);
+        let points_per_day = 86400.0 / median_spacing as f64;
+        
+        // Deviation scores exactly as the volatility detector computes them
+        let mut scores: Vec<f64> = historical_data.iter()
+            .filter(|p| p.close > 0.0)
+            .filter_map(|p| {
+                let expected = self.regime_expected_volatility(p.timestamp);
+                (expected.std_dev > 0.0).then(|| (bar_volatility(p) - expected.mean) / expected.std_dev)
+            })
+            .collect();
+        if scores.is_empty() {
+            return Err(anyhow::anyhow!("Not enough historical data to calibrate sensitivity"));
+        }
+        scores.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        
+        let target_fraction = (target_rate_per_day / points_per_day).clamp(0.0, 1.0);
+        let index = ((1.0 - target_fraction) * (scores.len() - 1) as f64).round() as usize;
+        let sensitivity_threshold = scores[index.min(scores.len() - 1)].clamp(0.05, 10.0);
+        
+        // Confidence gating means nothing below min_anomaly_confidence can fire
+        let effective_threshold = sensitivity_threshold.max(self.config.min_anomaly_confidence);
+        let firing = scores.iter().filter(|z| **z > effective_threshold).count();
+        let days = scores.len() as f64 / points_per_day;
+        let estimated_rate_per_day = if days > 0.0 { firing as f64 / days } else { 0.0 };
+        
+        self.config.sensitivity_threshold = sensitivity_threshold;
+        let calibration = SensitivityCalibration {
+            sensitivity_threshold,
+            target_rate_per_day,
+            estimated_rate_per_day,
+            samples: scores.len(),
+            calibrated_at: Utc::now(),
+        };
+        self.calibration = Some(calibration.clone());
+        
+        Ok(calibration)
+    }
+    
+    /// Recalibrate if auto-tuning is enabled and the last calibration is older than the refresh interval
+    pub fn maybe_recalibrate(
+        &mut self,
+        historical_data: &[ForexDataPoint],
+        now: DateTime<Utc>,
+    ) -> Result<Option<SensitivityCalibration>> {
+        if self.config.target_anomalies_per_day.is_none() || historical_data.len() < 2 {
+            return Ok(None);
+        }
+        
+        let due = match &self.calibration {
+            Some(c) => now - c.calibrated_at >= chrono::Duration::days(self.config.recalibration_interval_days as i64),
+            None => true,
+        };
+        if !due {
+            return Ok(None);
+        }
+        
+        self.calibrate_sensitivity(historical_data).map(Some)
     }
     
     /// Calculate baseline statistics from historical data
@@ -197,6 +698,8 @@ impl TemporalAnomalyDetector {
         historical_data: &[ForexDataPoint],
         symmetries: &[TemporalSymmetry],
         cycles: &[HiddenCycle],
+        config: &AnomalyDetectionConfig,
+        sessions: &TradingSessions,
     ) -> Result<BaselineStatistics> {
         let prices: Vec<f64> = historical_data.iter().map(|d| d.close).collect();
         let mean_price = prices.iter().sum::<f64>() / prices.len() as f64;
@@ -214,6 +717,25 @@ impl TemporalAnomalyDetector {
             .map(|v| (v - mean_volatility).powi(2))
             .sum::<f64>() / volatilities.len() as f64;
         let volatility_std_dev = volatility_variance.sqrt();
+        let volatility_surface = VolSurface::from_history(historical_data, VolSurfaceConfig::default());
+        let session_volatility = SessionVolatility::from_history(historical_data, sessions);
+        let momentum_surface = MomentumSurface::from_history(historical_data, MomentumSurfaceConfig {
+            lookback_bars: config.momentum_lookback_bars,
+            ..MomentumSurfaceConfig::default()
+        });
+        let volume_profile = VolumeProfile::from_history(historical_data, VolSurfaceConfig::default().min_samples_per_bucket);
+        let volatility_model = VolatilityModel::fit(historical_data, &config.volatility).ok();
+        let regime_model = RegimeModel::fit(historical_data, &config.regimes).ok();
+        let regime_path = regime_model.as_ref().map(|model| model.predicted_probabilities(historical_data)).unwrap_or_default();
+        let regime_volatility_scales = regime_volatility_scales(historical_data, &regime_path, |timestamp| {
+            unconditional_volatility(&volatility_surface, &session_volatility, sessions, timestamp)
+        });
+        let mut spacings: Vec<i64> = historical_data.windows(2)
+            .map(|w| (w[1].timestamp - w[0].timestamp).num_seconds())
+            .filter(|s| *s > 0)
+            .collect();
+        spacings.sort_unstable();
+        let bar_seconds = spacings.get(spacings.len() / 2).copied().unwrap_or(86400) as f64;
         
         // Extract symmetry and cycle strength distributions
         let symmetry_strength_distribution: Vec<f64> = symmetries.iter()
@@ -240,44 +762,99 @@ impl TemporalAnomalyDetector {
             price_std_dev,
             mean_volatility,
             volatility_std_dev,
+            volatility_surface,
+            session_volatility,
+            momentum_surface,
+            volume_profile,
+            volatility_model,
+            regime_model,
+            regime_path,
+            regime_volatility_scales,
+            bar_seconds,
             symmetry_strength_distribution,
             cycle_strength_distribution,
             temporal_correlation_matrix: correlation_matrix,
         })
     }
     
-    /// Detect anomalies in synthetic forex data
-    pub async fn detect_anomalies(
+    /// Detect anomalies in a series of historical, live or synthetic bars
+    pub async fn detect_anomalies<P: MarketPoint>(
         &mut self,
-        synthetic_data: &[SyntheticForexPoint],
+        points: &[P],
     ) -> Result<Vec<DetectedAnomaly>> {
+        let bars = P::data_points(points);
         let mut detected_anomalies = Vec::new();
         
-        for (i, synthetic_point) in synthetic_data.iter().enumerate() {
+        for (i, point) in bars.iter().enumerate() {
             // Get detection window
             let window_start = i.saturating_sub(self.config.detection_window_size);
-            let window_data = &synthetic_data[window_start..=i];
+            let window_data = &bars[window_start..=i];
+            
+            let thin_market = self.thin_market(point.timestamp);
+            if thin_market.is_some() && self.holiday_calendar.policy == ThinMarketPolicy::Skip {
+                continue;
+            }
+            let first_new = detected_anomalies.len();
             
             // Detect different types of anomalies
-            if let Some(anomaly) = self.detect_symmetry_anomaly(synthetic_point, window_data).await? {
+            if let Some(anomaly) = self.detect_symmetry_anomaly(point, window_data).await? {
                 detected_anomalies.push(anomaly);
             }
             
-            if let Some(anomaly) = self.detect_cycle_anomaly(synthetic_point, window_data).await? {
+            if let Some(anomaly) = self.detect_volatility_anomaly(point, window_data).await? {
                 detected_anomalies.push(anomaly);
             }
             
-            if let Some(anomaly) = self.detect_volatility_anomaly(synthetic_point, window_data).await? {
+            if let Some(anomaly) = self.detect_liquidity_gap(point).await? {
                 detected_anomalies.push(anomaly);
             }
             
-            if let Some(anomaly) = self.detect_pattern_inversion(synthetic_point, window_data).await? {
+            if let Some(anomaly) = self.detect_momentum_anomaly(point, window_data).await? {
                 detected_anomalies.push(anomaly);
             }
             
-            if let Some(anomaly) = self.detect_novel_pattern(synthetic_point, window_data).await? {
+            if let Some(anomaly) = self.detect_pattern_inversion(point, window_data).await? {
                 detected_anomalies.push(anomaly);
             }
+            
+            if let Some(anomaly) = self.detect_novel_pattern(point, window_data).await? {
+                detected_anomalies.push(anomaly);
+            }
+            
+            if let Some(anomaly) = self.detect_matrix_profile_discord(point, &bars[..=i]).await? {
+                detected_anomalies.push(anomaly);
+            }
+            
+            // Holiday bars keep their anomalies, labelled and at reduced confidence
+            if let Some(reason) = thin_market {
+                let weight = self.holiday_calendar.weight(self.pair.as_deref().unwrap_or_default(), point.timestamp);
+                for anomaly in &mut detected_anomalies[first_new..] {
+                    anomaly.confidence *= weight;
+                    anomaly.market_context.recent_events.push(format!("{}: {}", THIN_MARKET_EVENT, reason));
+                }
+            }
+            
+            // Price shocks around a release are news, not a broken pattern
+            let proximity = self.event_proximity(point.timestamp);
+            if proximity > 0.0 {
+                let events = self.economic_calendar.recent_events(self.pair.as_deref().unwrap_or_default(), point.timestamp);
+                let discount = 1.0 - self.economic_calendar.config.news_discount.clamp(0.0, 1.0) * proximity;
+                for anomaly in &mut detected_anomalies[first_new..] {
+                    if anomaly.anomaly_type.is_price_shock() {
+                        anomaly.confidence *= discount;
+                    }
+                    anomaly.market_context.event_proximity = proximity;
+                    anomaly.market_context.recent_events.extend(events.iter().cloned());
+                }
+            }
+            
+            for anomaly in &mut detected_anomalies[first_new..] {
+                anomaly.trading_signal = self.signal_policy.signal(anomaly, point)?;
+            }
+            
+            if let Some(model) = &mut self.baseline_statistics.volatility_model {
+                model.observe(point);
+            }
         }
         
         // Filter anomalies by confidence threshold
@@ -297,16 +874,18 @@ impl TemporalAnomalyDetector {
     /// Detect temporal symmetry anomalies
     async fn detect_symmetry_anomaly(
         &self,
-        synthetic_point: &SyntheticForexPoint,
-        window_data: &[SyntheticForexPoint],
+        point: &ForexDataPoint,
+        window_data: &[ForexDataPoint],
     ) -> Result<Option<DetectedAnomaly>> {
-        // Check if expected symmetries are present in synthetic data
+        // Check if expected symmetries are present in the data
         for expected_symmetry in &self.expected_symmetries {
-            let actual_strength = self.calculate_actual_symmetry_strength(
+            let Some(actual_strength) = self.calculate_actual_symmetry_strength(
                 expected_symmetry,
-                synthetic_point,
+                point,
                 window_data,
-            )?;
+            )? else {
+                continue;
+            };
             
             let deviation = (expected_symmetry.strength - actual_strength).abs();
             let threshold = self.config.sensitivity_threshold * expected_symmetry.strength;
@@ -316,8 +895,8 @@ impl TemporalAnomalyDetector {
                 
                 if confidence >= self.config.min_anomaly_confidence {
                     let anomaly = DetectedAnomaly {
-                        id: format!("symmetry_anomaly_{}", uuid::Uuid::new_v4()),
-                        timestamp: synthetic_point.data_point.timestamp,
+                        id: AnomalyId::new(),
+                        timestamp: point.timestamp,
                         anomaly_type: AnomalyType::SymmetryBreakdown {
                             symmetry_id: expected_symmetry.id.clone(),
                             expected_strength: expected_symmetry.strength,
@@ -328,7 +907,7 @@ impl TemporalAnomalyDetector {
                         deviation_magnitude: deviation,
                         affected_symmetries: vec![expected_symmetry.id.clone()],
                         affected_cycles: Vec::new(),
-                        market_context: self.analyze_market_context(synthetic_point),
+                        market_context: self.analyze_market_context(point),
                         trading_signal: self.generate_trading_signal_from_symmetry_anomaly(
                             expected_symmetry,
                             actual_strength,
@@ -344,87 +923,61 @@ impl TemporalAnomalyDetector {
         Ok(None)
     }
     
-    /// Calculate actual symmetry strength in synthetic data
+    /// Calculate actual symmetry strength in the window: mirror, rotational and
+    /// translational symmetries are re-measured by the symmetry detector, or skipped
+    /// when the window is shorter than they need; others by the price
+    /// autocorrelation at their period against the baseline
     fn calculate_actual_symmetry_strength(
         &self,
         expected_symmetry: &TemporalSymmetry,
-        synthetic_point: &SyntheticForexPoint,
-        window_data: &[SyntheticForexPoint],
-    ) -> Result<f64> {
-        // Simplified symmetry strength calculation
-        // In practice, this would involve complex temporal correlation analysis
-        
+        _point: &ForexDataPoint,
+        window_data: &[ForexDataPoint],
+    ) -> Result<Option<f64>> {
+        if SymmetryDetector::detects(&expected_symmetry.symmetry_type) {
+            return Ok(self.symmetry_detector.measure(expected_symmetry, window_data));
+        }
         let prices: Vec<f64> = window_data.iter()
-            .map(|p| p.data_point.close)
+            .map(|p| p.close)
             .collect();
-        
-        if prices.len() < 2 {
-            return Ok(0.0);
-        }
-        
-        // Calculate autocorrelation at expected period
-        let period = expected_symmetry.period_days as usize;
-        if prices.len() <= period {
-            return Ok(0.0);
-        }
-        
-        let mut correlation_sum = 0.0;
-        let mut count = 0;
-        
-        for i in 0..(prices.len() - period) {
-            correlation_sum += prices[i] * prices[i + period];
-            count += 1;
-        }
-        
-        if count == 0 {
-            return Ok(0.0);
-        }
-        
-        let correlation = correlation_sum / count as f64;
-        let normalized_correlation = (correlation - self.baseline_statistics.mean_price.powi(2))
-            / self.baseline_statistics.price_std_dev.powi(2);
-        
-        Ok(normalized_correlation.abs().min(1.0))
-    }
-    
-    /// Detect cycle anomalies
-    async fn detect_cycle_anomaly(
-        &self,
-        synthetic_point: &SyntheticForexPoint,
-        window_data: &[SyntheticForexPoint],
-    ) -> Result<Option<DetectedAnomaly>> {
-        // Implementation for cycle anomaly detection
-        // This would check if expected cycles are disrupted or phase-shifted
-        Ok(None) // Placeholder
+        Ok(Some(SymmetryDetector::lag_correlation(
+            &prices,
+            expected_symmetry.period_days as usize,
+            self.baseline_statistics.mean_price,
+            self.baseline_statistics.price_std_dev,
+        )))
     }
     
     /// Detect volatility anomalies
     async fn detect_volatility_anomaly(
         &self,
-        synthetic_point: &SyntheticForexPoint,
-        window_data: &[SyntheticForexPoint],
+        point: &ForexDataPoint,
+        window_data: &[ForexDataPoint],
     ) -> Result<Option<DetectedAnomaly>> {
         if window_data.len() < 2 {
             return Ok(None);
         }
         
         // Calculate current volatility
-        let current_volatility = (synthetic_point.data_point.high - synthetic_point.data_point.low)
-            / synthetic_point.data_point.close;
+        let current_volatility = bar_volatility(point);
         
-        // Compare with baseline
-        let expected_volatility = self.baseline_statistics.mean_volatility;
+        // Compare with the baseline for this hour of the week, or session when that is sparse,
+        // scaled to the current volatility regime
+        let expected = self.regime_expected_volatility(point.timestamp);
+        if expected.std_dev <= 0.0 {
+            return Ok(None);
+        }
+        let expected_volatility = expected.mean;
         let volatility_threshold = expected_volatility + 
-            (self.config.sensitivity_threshold * self.baseline_statistics.volatility_std_dev);
+            (self.config.sensitivity_threshold * expected.std_dev);
         
         if current_volatility > volatility_threshold {
             let deviation = current_volatility - expected_volatility;
-            let confidence = (deviation / self.baseline_statistics.volatility_std_dev).min(1.0);
+            let confidence = (deviation / expected.std_dev).min(1.0);
             
             if confidence >= self.config.min_anomaly_confidence {
                 let anomaly = DetectedAnomaly {
-                    id: format!("volatility_anomaly_{}", uuid::Uuid::new_v4()),
-                    timestamp: synthetic_point.data_point.timestamp,
+                    id: AnomalyId::new(),
+                    timestamp: point.timestamp,
                     anomaly_type: AnomalyType::VolatilitySpike {
                         expected_volatility,
                         actual_volatility: current_volatility,
@@ -434,7 +987,7 @@ impl TemporalAnomalyDetector {
                     deviation_magnitude: deviation,
                     affected_symmetries: Vec::new(),
                     affected_cycles: Vec::new(),
-                    market_context: self.analyze_market_context(synthetic_point),
+                    market_context: self.analyze_market_context(point),
                     trading_signal: self.generate_trading_signal_from_volatility_anomaly(
                         current_volatility,
                         expected_volatility,
@@ -449,24 +1002,290 @@ impl TemporalAnomalyDetector {
         Ok(None)
     }
     
-    /// Detect pattern inversions
+    /// Detect liquidity gaps: a range far below the usual one for an hour that is normally
+    /// at least as active as average, with low or absent volume. Without volume in the
+    /// history the range alone decides.
+    async fn detect_liquidity_gap(
+        &self,
+        point: &ForexDataPoint,
+    ) -> Result<Option<DetectedAnomaly>> {
+        if point.close <= 0.0 {
+            return Ok(None);
+        }
+        let surface = &self.baseline_statistics.volatility_surface;
+        let expected = self.expected_volatility(point.timestamp);
+        if expected.mean <= 0.0 || expected.mean < surface.global().mean {
+            return Ok(None); // quiet hours are expected to be thin
+        }
+        
+        let actual_volatility = bar_volatility(point);
+        let range_ratio = actual_volatility / expected.mean;
+        if range_ratio >= self.config.liquidity_range_ratio {
+            return Ok(None);
+        }
+        
+        let profile = &self.baseline_statistics.volume_profile;
+        let (expected_volume, volume_ratio) = if profile.has_volume() {
+            let expected_volume = profile.expected(point.timestamp).mean;
+            let ratio = match point.volume {
+                Some(volume) if expected_volume > 0.0 => volume / expected_volume,
+                Some(_) => 1.0,
+                None => 0.0, // volume missing where the history has it
+            };
+            if ratio >= self.config.liquidity_volume_ratio {
+                return Ok(None);
+            }
+            (Some(expected_volume), Some(ratio))
+        } else {
+            (None, None)
+        };
+        
+        // Shortfall of range and, when known, volume relative to the norm for this hour
+        let confidence = match volume_ratio {
+            Some(volume_ratio) => 1.0 - (range_ratio + volume_ratio.min(1.0)) / 2.0,
+            None => 1.0 - range_ratio,
+        };
+        if confidence < self.config.min_anomaly_confidence {
+            return Ok(None);
+        }
+        let severity = match confidence {
+            x if x < 0.8 => AnomalySeverity::Medium,
+            x if x < 0.9 => AnomalySeverity::High,
+            _ => AnomalySeverity::Critical,
+        };
+        
+        Ok(Some(DetectedAnomaly {
+            id: AnomalyId::new(),
+            timestamp: point.timestamp,
+            anomaly_type: AnomalyType::LiquidityGap {
+                expected_volatility: expected.mean,
+                actual_volatility,
+                expected_volume,
+                actual_volume: point.volume,
+            },
+            severity,
+            confidence,
+            deviation_magnitude: expected.mean - actual_volatility,
+            affected_symmetries: Vec::new(),
+            affected_cycles: Vec::new(),
+            market_context: self.analyze_market_context(point),
+            trading_signal: Some(AnomalyTradingSignal {
+                signal_type: "Hold".to_string(), // Fills in a thin market are unreliable
+                strength: confidence,
+                confidence,
+                time_horizon: "Short".to_string(),
+                risk_level: "High".to_string(),
+                expected_duration: (self.baseline_statistics.bar_seconds / 60.0).max(1.0) as u32,
+            }),
+        }))
+    }
+    
+    /// Detect momentum shocks: N-bar cumulative returns beyond the quantile usual for this hour of the week
+    async fn detect_momentum_anomaly(
+        &self,
+        point: &ForexDataPoint,
+        window_data: &[ForexDataPoint],
+    ) -> Result<Option<DetectedAnomaly>> {
+        let surface = &self.baseline_statistics.momentum_surface;
+        let lookback = surface.lookback_bars();
+        if window_data.len() <= lookback {
+            return Ok(None);
+        }
+        
+        let window = &window_data[window_data.len() - lookback - 1..];
+        let Some(current_return) = cumulative_return(window) else {
+            return Ok(None);
+        };
+        let timestamp = point.timestamp;
+        let quantile = self.config.momentum_quantile;
+        let threshold = match surface.quantile(timestamp, quantile) {
+            Some(threshold) if threshold > 0.0 => threshold,
+            _ => return Ok(None),
+        };
+        if current_return.abs() <= threshold {
+            return Ok(None);
+        }
+        
+        // Confidence is the share of comparable historical moves this one exceeds
+        let confidence = surface.rank(timestamp, current_return.abs());
+        if confidence < self.config.min_anomaly_confidence {
+            return Ok(None);
+        }
+        let span_minutes = (timestamp - window[0].timestamp).num_minutes().max(1) as u32;
+        
+        Ok(Some(DetectedAnomaly {
+            id: AnomalyId::new(),
+            timestamp,
+            anomaly_type: AnomalyType::MomentumShock {
+                lookback_bars: lookback,
+                cumulative_return: current_return,
+                threshold_return: threshold,
+                quantile,
+            },
+            severity: Self::classify_momentum_severity(current_return.abs() / threshold),
+            confidence,
+            deviation_magnitude: current_return.abs() - threshold,
+            affected_symmetries: Vec::new(),
+            affected_cycles: Vec::new(),
+            market_context: self.analyze_market_context(point),
+            trading_signal: self.generate_trading_signal_from_momentum_anomaly(
+                current_return,
+                threshold,
+                confidence,
+                span_minutes,
+            ),
+        }))
+    }
+    
+    /// Detect pattern inversions: prices that followed an expected cycle over the
+    /// previous segment and now correlate negatively with it
     async fn detect_pattern_inversion(
         &self,
-        synthetic_point: &SyntheticForexPoint,
-        window_data: &[SyntheticForexPoint],
+        point: &ForexDataPoint,
+        window_data: &[ForexDataPoint],
     ) -> Result<Option<DetectedAnomaly>> {
-        // Implementation for pattern inversion detection
-        Ok(None) // Placeholder
+        let threshold = self.config.inversion_correlation;
+        let bar_seconds = self.baseline_statistics.bar_seconds.max(1.0);
+        let times: Vec<f64> = window_data.iter()
+            .map(|p| p.timestamp.timestamp() as f64 / bar_seconds)
+            .collect();
+        let closes: Vec<f64> = window_data.iter().map(|p| p.close).collect();
+        let n = closes.len();
+        
+        for cycle in self.expected_cycles.iter().filter(|c| c.period > 0 && c.amplitude > 0.0) {
+            // Compare one period (at most half the window) against the one before it
+            let segment = (cycle.period as usize).min(n / 2);
+            if segment < 8 {
+                continue;
+            }
+            let omega = 2.0 * std::f64::consts::PI / cycle.period as f64;
+            let template: Vec<f64> = times.iter().map(|t| (omega * t + cycle.phase).sin()).collect();
+            let correlation_at = |end: usize| {
+                detrended_correlation(&times[end - segment..end], &closes[end - segment..end], &template[end - segment..end])
+            };
+            
+            let current = correlation_at(n);
+            let previous_bar = correlation_at(n - 1);
+            let prior = correlation_at(n - segment);
+            // Report the flip once, on the bar where the correlation crosses below -threshold
+            if prior < threshold || current > -threshold || previous_bar <= -threshold {
+                continue;
+            }
+            
+            let confidence = ((prior - current) / 2.0).min(1.0);
+            if confidence < self.config.min_anomaly_confidence {
+                continue;
+            }
+            let severity = match current {
+                x if x > -0.6 => AnomalySeverity::Medium,
+                x if x > -0.8 => AnomalySeverity::High,
+                _ => AnomalySeverity::Critical,
+            };
+            // The inverted cycle's slope is the negative of the template's
+            let t = times[n - 1];
+            let inverted_slope = -(omega * t + cycle.phase).cos();
+            
+            return Ok(Some(DetectedAnomaly {
+                id: AnomalyId::new(),
+                timestamp: point.timestamp,
+                anomaly_type: AnomalyType::PatternInversion {
+                    original_pattern: cycle.name.clone(),
+                    inverted_pattern: format!("Inverted {}", cycle.name),
+                },
+                severity,
+                confidence,
+                deviation_magnitude: prior - current,
+                affected_symmetries: Vec::new(),
+                affected_cycles: vec![cycle.id.clone()],
+                market_context: self.analyze_market_context(point),
+                trading_signal: Some(AnomalyTradingSignal {
+                    signal_type: if inverted_slope > 0.0 { "Buy" } else { "Sell" }.to_string(),
+                    strength: inverted_slope.abs(),
+                    confidence,
+                    time_horizon: "Medium".to_string(),
+                    risk_level: "Medium".to_string(),
+                    expected_duration: (cycle.period as f64 * bar_seconds / 60.0 / 4.0) as u32, // Quarter of cycle
+                }),
+            }));
+        }
+        
+        Ok(None)
     }
     
-    /// Detect novel patterns
+    /// Detect novel patterns: return windows far from every learned cluster
     async fn detect_novel_pattern(
-        &self,
-        synthetic_point: &SyntheticForexPoint,
-        window_data: &[SyntheticForexPoint],
+        &mut self,
+        point: &ForexDataPoint,
+        window_data: &[ForexDataPoint],
     ) -> Result<Option<DetectedAnomaly>> {
-        // Implementation for novel pattern detection
-        Ok(None) // Placeholder
+        let needed = self.pattern_clusters.bars_needed();
+        if window_data.len() < needed {
+            return Ok(None);
+        }
+        let Some(novelty) = self.pattern_clusters.observe(&window_data[window_data.len() - needed..]) else {
+            return Ok(None);
+        };
+        if !novelty.is_novel || novelty.rank < self.config.min_anomaly_confidence {
+            return Ok(None);
+        }
+        
+        let mut market_context = self.analyze_market_context(point);
+        if let Some(analog) = &novelty.nearest_historical {
+            market_context.recent_events.push(format!("Closest historical window ended {} ({:.2}σ away)",
+                                                      analog.window_end.format("%Y-%m-%d %H:%M"), analog.distance));
+        }
+        Ok(Some(DetectedAnomaly {
+            id: AnomalyId::new(),
+            timestamp: point.timestamp,
+            anomaly_type: AnomalyType::NovelPattern {
+                pattern_signature: novelty.signature,
+                emergence_confidence: novelty.rank,
+            },
+            severity: self.classify_severity(novelty.distance - novelty.threshold, novelty.threshold),
+            confidence: novelty.rank,
+            deviation_magnitude: novelty.distance - novelty.threshold,
+            affected_symmetries: Vec::new(),
+            affected_cycles: Vec::new(),
+            market_context,
+            trading_signal: None, // Nothing in history says how an unseen shape resolves
+        }))
+    }
+    
+    /// Detect matrix profile discords: windows of closes unlike any window of the history
+    async fn detect_matrix_profile_discord(
+        &mut self,
+        point: &ForexDataPoint,
+        bars: &[ForexDataPoint],
+    ) -> Result<Option<DetectedAnomaly>> {
+        let Some(detector) = &mut self.discord_detector else {
+            return Ok(None);
+        };
+        let window = detector.bars_needed();
+        let Some(discord) = detector.observe(bars) else {
+            return Ok(None);
+        };
+        if !discord.is_discord || discord.rank < self.config.min_anomaly_confidence {
+            return Ok(None);
+        }
+        
+        let mut market_context = self.analyze_market_context(point);
+        market_context.recent_events.push(format!("Closest historical {}-bar shape started {} ({:.2} z-normalized distance)",
+                                                  window, discord.nearest_start.format("%Y-%m-%d %H:%M"), discord.distance));
+        Ok(Some(DetectedAnomaly {
+            id: AnomalyId::new(),
+            timestamp: point.timestamp,
+            anomaly_type: AnomalyType::NovelPattern {
+                pattern_signature: format!("discord-{}bar", window),
+                emergence_confidence: discord.rank,
+            },
+            severity: self.classify_severity(discord.distance - discord.threshold, discord.threshold),
+            confidence: discord.rank,
+            deviation_magnitude: discord.distance - discord.threshold,
+            affected_symmetries: Vec::new(),
+            affected_cycles: Vec::new(),
+            market_context,
+            trading_signal: None, // A shape without precedent has no historical outcome
+        }))
     }
     
     /// Classify anomaly severity
@@ -481,32 +1300,35 @@ impl TemporalAnomalyDetector {
         }
     }
     
+    /// Classify a momentum shock by how far the move exceeds its quantile threshold
+    fn classify_momentum_severity(threshold_ratio: f64) -> AnomalySeverity {
+        match threshold_ratio {
+            x if x < 1.25 => AnomalySeverity::Low,
+            x if x < 1.75 => AnomalySeverity::Medium,
+            x if x < 2.5 => AnomalySeverity::High,
+            _ => AnomalySeverity::Critical,
+        }
+    }
+    
     /// Analyze market context
-    fn analyze_market_context(&self, synthetic_point: &SyntheticForexPoint) -> MarketContext {
-        let hour = synthetic_point.data_point.timestamp.hour();
-        let session = match hour {
-            0..=7 => "Asian",
-            8..=12 => "London",
-            13..=17 => "Overlap",
-            18..=22 => "NewYork",
-            _ => "Closed",
-        }.to_string();
+    fn analyze_market_context(&self, point: &ForexDataPoint) -> MarketContext {
+        let session = self.sessions.label(point.timestamp);
         
-        let volatility = (synthetic_point.data_point.high - synthetic_point.data_point.low)
-            / synthetic_point.data_point.close;
-        let volatility_regime = if volatility > self.baseline_statistics.mean_volatility * 2.0 {
+        let volatility = bar_volatility(point);
+        let expected_volatility = self.expected_volatility(point.timestamp).mean;
+        let volatility_regime = if volatility > expected_volatility * 2.0 {
             "Crisis"
-        } else if volatility > self.baseline_statistics.mean_volatility * 1.5 {
+        } else if volatility > expected_volatility * 1.5 {
             "High"
-        } else if volatility < self.baseline_statistics.mean_volatility * 0.5 {
+        } else if volatility < expected_volatility * 0.5 {
             "Low"
         } else {
             "Normal"
         }.to_string();
         
-        let trend_direction = if synthetic_point.data_point.close > synthetic_point.data_point.open {
+        let trend_direction = if point.close > point.open {
             "Bullish"
-        } else if synthetic_point.data_point.close < synthetic_point.data_point.open {
+        } else if point.close < point.open {
             "Bearish"
         } else {
             "Sideways"
@@ -516,7 +1338,8 @@ impl TemporalAnomalyDetector {
             session,
             volatility_regime,
             trend_direction,
-            recent_events: Vec::new(), // Would be populated with actual events
+            recent_events: Vec::new(),
+            event_proximity: 0.0,
         }
     }
     
@@ -578,6 +1401,26 @@ impl TemporalAnomalyDetector {
         })
     }
     
+    /// Generate trading signal from momentum shock: follow the move over a horizon like its own span
+    fn generate_trading_signal_from_momentum_anomaly(
+        &self,
+        cumulative_return: f64,
+        threshold_return: f64,
+        confidence: f64,
+        span_minutes: u32,
+    ) -> Option<AnomalyTradingSignal> {
+        let threshold_ratio = cumulative_return.abs() / threshold_return;
+        
+        Some(AnomalyTradingSignal {
+            signal_type: if cumulative_return > 0.0 { "Buy" } else { "Sell" }.to_string(),
+            strength: (threshold_ratio - 1.0).min(1.0),
+            confidence,
+            time_horizon: "Short".to_string(),
+            risk_level: if threshold_ratio >= 2.5 { "High" } else { "Medium" }.to_string(),
+            expected_duration: span_minutes,
+        })
+    }
+    
     /// Get anomaly statistics
     pub fn get_anomaly_statistics(&self) -> AnomalyStatistics {
         let total_anomalies = self.anomaly_history.len();
@@ -585,15 +1428,7 @@ impl TemporalAnomalyDetector {
         let mut severity_counts = HashMap::new();
         
         for anomaly in &self.anomaly_history {
-            let type_name = match &anomaly.anomaly_type {
-                AnomalyType::SymmetryBreakdown { .. } => "SymmetryBreakdown",
-                AnomalyType::CycleDisruption { .. } => "CycleDisruption",
-                AnomalyType::VolatilitySpike { .. } => "VolatilitySpike",
-                AnomalyType::PatternInversion { .. } => "PatternInversion",
-                AnomalyType::CorrelationBreakdown { .. } => "CorrelationBreakdown",
-                AnomalyType::NovelPattern { .. } => "NovelPattern",
-            };
-            *type_counts.entry(type_name.to_string()).or_insert(0) += 1;
+            *type_counts.entry(anomaly.anomaly_type.name().to_string()).or_insert(0) += 1;
             
             let severity_name = match anomaly.severity {
                 AnomalySeverity::Low => "Low",
@@ -615,6 +1450,71 @@ impl TemporalAnomalyDetector {
     }
 }
 
+/// Correlation between `values` and `template` after removing a linear trend in `times` from both
+/// Expected volatility of a bar at `timestamp`: its hour of the week when well sampled,
+/// otherwise its session
+fn unconditional_volatility(
+    volatility_surface: &VolSurface,
+    session_volatility: &SessionVolatility,
+    sessions: &TradingSessions,
+    timestamp: DateTime<Utc>,
+) -> VolatilityBucket {
+    volatility_surface.hour_of_week_bucket(timestamp)
+        .or_else(|| session_volatility.expected(&sessions.label(timestamp)))
+        .unwrap_or_else(|| volatility_surface.expected(timestamp))
+}
+
+/// Mean ratio of bar volatility to its unconditional baseline in each regime state,
+/// weighting bars by their state probabilities, relative to the ratio over all bars
+fn regime_volatility_scales(
+    history: &[ForexDataPoint],
+    path: &[RegimeProbabilities],
+    expected: impl Fn(DateTime<Utc>) -> VolatilityBucket,
+) -> Vec<f64> {
+    let states = path.first().map_or(0, |p| p.states.len());
+    let (mut weighted, mut weights) = (vec![0.0; states], vec![0.0; states]);
+    let (mut total, mut count) = (0.0, 0usize);
+    for bar in history {
+        let Ok(i) = path.binary_search_by_key(&bar.timestamp, |p| p.timestamp) else { continue };
+        let mean = expected(bar.timestamp).mean;
+        if mean <= 0.0 {
+            continue;
+        }
+        let ratio = bar_volatility(bar) / mean;
+        for (s, p) in path[i].states.iter().enumerate() {
+            weighted[s] += p * ratio;
+            weights[s] += p;
+        }
+        total += ratio;
+        count += 1;
+    }
+    let overall = if count > 0 { total / count as f64 } else { 0.0 };
+    weighted.iter().zip(&weights)
+        .map(|(w, p)| if *p > 0.0 && overall > 0.0 { w / p / overall } else { 1.0 })
+        .collect()
+}
+
+fn detrended_correlation(times: &[f64], values: &[f64], template: &[f64]) -> f64 {
+    let residuals = |ys: &[f64]| -> Vec<f64> {
+        let n = ys.len() as f64;
+        let mean_t = times.iter().sum::<f64>() / n;
+        let mean_y = ys.iter().sum::<f64>() / n;
+        let covariance: f64 = times.iter().zip(ys).map(|(t, y)| (t - mean_t) * (y - mean_y)).sum();
+        let variance: f64 = times.iter().map(|t| (t - mean_t).powi(2)).sum();
+        let slope = if variance > 0.0 { covariance / variance } else { 0.0 };
+        times.iter().zip(ys).map(|(t, y)| y - mean_y - slope * (t - mean_t)).collect()
+    };
+    let x = residuals(values);
+    let y = residuals(template);
+    let xy: f64 = x.iter().zip(&y).map(|(a, b)| a * b).sum();
+    let xx: f64 = x.iter().map(|a| a * a).sum();
+    let yy: f64 = y.iter().map(|b| b * b).sum();
+    if xx <= 0.0 || yy <= 0.0 {
+        return 0.0;
+    }
+    xy / (xx * yy).sqrt()
+}
+
 /// Anomaly detection statistics
 #[derive(Debug, Clone, Serialize)]
 pub struct AnomalyStatistics {
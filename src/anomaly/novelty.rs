@@ -0,0 +1,217 @@
+//! # Novel Pattern Discovery
+//!
+//! Online k-means over short windows of bar returns, seeded from history. A
+//! window far from every centroid is novel and starts a cluster of its own, so a
+//! recurring shape stops being reported once it has been learned.
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::ForexDataPoint;
+use crate::galois::{SimilarWindow, SimilarityConfig, SimilarityIndex};
+
+/// Clustering settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NoveltyConfig {
+    /// Bar returns in each feature window
+    pub window_bars: usize,
+    /// Clusters fitted to history
+    pub initial_clusters: usize,
+    /// Upper bound on clusters, including ones discovered online
+    pub max_clusters: usize,
+    /// Quantile of historical nearest-centroid distances beyond which a window is novel
+    pub novelty_quantile: f64,
+    /// Lloyd iterations for the historical fit
+    pub iterations: usize,
+    /// Signature of the historical window index; its window length follows `window_bars`
+    pub similarity: SimilarityConfig,
+}
+
+impl Default for NoveltyConfig {
+    fn default() -> Self {
+        Self {
+            window_bars: 8,
+            initial_clusters: 8,
+            max_clusters: 32,
+            novelty_quantile: 0.995,
+            iterations: 10,
+            similarity: SimilarityConfig::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Centroid {
+    center: Vec<f64>,
+    count: usize,
+}
+
+/// Outcome of observing one window
+#[derive(Debug, Clone)]
+pub struct Novelty {
+    /// Distance to the nearest centroid before the update
+    pub distance: f64,
+    pub threshold: f64,
+    /// Fraction of historical windows closer to their centroid than this one
+    pub rank: f64,
+    /// Symbolic shape of the window, one letter per return
+    pub signature: String,
+    pub is_novel: bool,
+    /// Closest window seen before this one, among those sharing a signature bucket
+    pub nearest_historical: Option<SimilarWindow>,
+}
+
+/// Return-shape clusters learned from history and updated as bars arrive
+#[derive(Debug, Clone)]
+pub struct PatternClusters {
+    config: NoveltyConfig,
+    centroids: Vec<Centroid>,
+    /// Standard deviation of one-bar log returns in history, the feature unit
+    return_scale: f64,
+    /// Sorted nearest-centroid distances of the historical windows
+    historical_distances: Vec<f64>,
+    /// Every window seen so far, for analog lookup
+    similarity: Option<SimilarityIndex>,
+}
+
+impl PatternClusters {
+    /// Fit `initial_clusters` centroids to the return windows of `history`
+    pub fn fit(history: &[ForexDataPoint], config: NoveltyConfig) -> Self {
+        let window = config.window_bars.max(2);
+        let returns = log_returns(history);
+        let mean = returns.iter().sum::<f64>() / returns.len().max(1) as f64;
+        let return_scale = (returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / returns.len().max(1) as f64).sqrt();
+
+        let similarity = SimilarityIndex::fit(history, SimilarityConfig { window_bars: window, ..config.similarity.clone() }).ok();
+        let mut clusters = Self { config, centroids: Vec::new(), return_scale, historical_distances: Vec::new(), similarity };
+        if return_scale <= 0.0 || returns.len() < window {
+            return clusters;
+        }
+        let samples: Vec<Vec<f64>> = returns.windows(window)
+            .map(|w| w.iter().map(|r| r / return_scale).collect())
+            .collect();
+
+        // Deterministic seeding from evenly spaced windows, then Lloyd iterations
+        let k = clusters.config.initial_clusters.clamp(1, samples.len());
+        clusters.centroids = (0..k)
+            .map(|i| Centroid { center: samples[i * samples.len() / k].clone(), count: 0 })
+            .collect();
+        for _ in 0..clusters.config.iterations.max(1) {
+            let mut sums = vec![vec![0.0; window]; k];
+            let mut counts = vec![0usize; k];
+            for sample in &samples {
+                let (nearest, _) = clusters.nearest(sample).unwrap_or((0, 0.0));
+                counts[nearest] += 1;
+                sums[nearest].iter_mut().zip(sample).for_each(|(sum, x)| *sum += x);
+            }
+            for ((centroid, sum), count) in clusters.centroids.iter_mut().zip(sums).zip(counts) {
+                if count > 0 {
+                    centroid.center = sum.into_iter().map(|s| s / count as f64).collect();
+                }
+                centroid.count = count;
+            }
+        }
+
+        let mut distances: Vec<f64> = samples.iter().filter_map(|s| clusters.nearest(s)).map(|(_, d)| d).collect();
+        distances.sort_by(|a, b| a.total_cmp(b));
+        clusters.historical_distances = distances;
+        clusters
+    }
+
+    /// Bars needed to form one feature window (one more than the returns in it)
+    pub fn bars_needed(&self) -> usize {
+        self.config.window_bars.max(2) + 1
+    }
+
+    pub fn cluster_count(&self) -> usize {
+        self.centroids.len()
+    }
+
+    /// Index of every window seen, `None` when the history was too flat to scale
+    pub fn similarity_index(&self) -> Option<&SimilarityIndex> {
+        self.similarity.as_ref()
+    }
+
+    /// Distance beyond which a window is novel, `None` before anything was learned
+    pub fn threshold(&self) -> Option<f64> {
+        let distances = &self.historical_distances;
+        if distances.is_empty() {
+            return None;
+        }
+        let q = self.config.novelty_quantile.clamp(0.0, 1.0);
+        let index = (q * (distances.len() - 1) as f64).round() as usize;
+        Some(distances[index.min(distances.len() - 1)])
+    }
+
+    /// Score the window ending the last bars of `bars` and learn from it: a familiar
+    /// window moves its nearest centroid, a novel one starts a new cluster
+    pub fn observe(&mut self, bars: &[ForexDataPoint]) -> Option<Novelty> {
+        let features = self.features(bars)?;
+        let threshold = self.threshold()?;
+        let (nearest, distance) = self.nearest(&features)?;
+        let is_novel = distance > threshold;
+
+        if is_novel && self.centroids.len() < self.config.max_clusters {
+            self.centroids.push(Centroid { center: features.clone(), count: 1 });
+        } else {
+            // Incremental mean update of the nearest centroid
+            let centroid = &mut self.centroids[nearest];
+            centroid.count += 1;
+            let rate = 1.0 / centroid.count as f64;
+            centroid.center.iter_mut().zip(&features).for_each(|(c, x)| *c += rate * (x - *c));
+        }
+
+        let nearest_historical = self.similarity.as_mut().and_then(|index| {
+            let closest = index.nearest(&features, 1).into_iter().next();
+            index.insert(bars[bars.len() - 1].timestamp, features.clone());
+            closest
+        });
+
+        let distances = &self.historical_distances;
+        Some(Novelty {
+            distance,
+            threshold,
+            rank: distances.partition_point(|d| *d < distance) as f64 / distances.len() as f64,
+            signature: signature(&features),
+            is_novel,
+            nearest_historical,
+        })
+    }
+
+    /// Scaled log returns of the last `window_bars + 1` bars
+    fn features(&self, bars: &[ForexDataPoint]) -> Option<Vec<f64>> {
+        let needed = self.bars_needed();
+        if bars.len() < needed || self.return_scale <= 0.0 {
+            return None;
+        }
+        let returns = log_returns(&bars[bars.len() - needed..]);
+        (returns.len() == needed - 1).then(|| returns.iter().map(|r| r / self.return_scale).collect())
+    }
+
+    fn nearest(&self, features: &[f64]) -> Option<(usize, f64)> {
+        self.centroids.iter()
+            .map(|c| c.center.iter().zip(features).map(|(a, b)| (a - b).powi(2)).sum::<f64>().sqrt())
+            .enumerate()
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+    }
+}
+
+fn log_returns(bars: &[ForexDataPoint]) -> Vec<f64> {
+    bars.windows(2)
+        .filter(|w| w[0].close > 0.0 && w[1].close > 0.0)
+        .map(|w| (w[1].close / w[0].close).ln())
+        .collect()
+}
+
+/// One letter per scaled return: `a` (below -1.5σ) through `e` (above +1.5σ)
+fn signature(features: &[f64]) -> String {
+    features.iter()
+        .map(|x| match *x {
+            x if x < -1.5 => 'a',
+            x if x < -0.5 => 'b',
+            x if x <= 0.5 => 'c',
+            x if x <= 1.5 => 'd',
+            _ => 'e',
+        })
+        .collect()
+}
@@ -0,0 +1,241 @@
+//! # Anomaly Signal Policies
+//!
+//! How detected anomalies become trading signals: [`RuleSignalPolicy`] reshapes
+//! them by anomaly type, confidence and severity from TOML rules, and
+//! [`RlSignalPolicy`] asks a trained Q-learning agent instead.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use super::suppression::WILDCARD;
+use super::{AnomalySeverity, AnomalyTradingSignal, DetectedAnomaly};
+use crate::data::ForexDataPoint;
+use crate::laplacian_rl::{LaplacianQLearningAgent, LaplacianQLearningConfig, QTableSnapshot, TradingAction};
+
+/// Decides the trading signal of each detected anomaly
+pub trait SignalPolicy: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Signal for `anomaly`, detected at `bar`, whose `trading_signal` holds the
+    /// detector's own proposal; `None` to trade nothing on it
+    fn signal(&mut self, anomaly: &DetectedAnomaly, bar: &ForexDataPoint) -> Result<Option<AnomalyTradingSignal>>;
+}
+
+/// Signal policy settings of the anomaly detector
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SignalPolicyConfig {
+    /// Checked in order; the first rule matching an anomaly decides its
+    /// signal, and anomalies no rule matches keep the signal they arrive with
+    #[serde(default)]
+    pub rules: Vec<SignalRule>,
+
+    /// Checkpoint of a trained agent whose actions replace the detectors'
+    /// signals before the rules apply
+    #[serde(default)]
+    pub agent_checkpoint: Option<PathBuf>,
+}
+
+impl SignalPolicyConfig {
+    /// Policy described by the settings: the rules, on top of the agent when
+    /// there is a checkpoint
+    pub fn build(&self) -> Result<Box<dyn SignalPolicy>> {
+        let policy = RuleSignalPolicy::new(self.rules.clone());
+        Ok(match &self.agent_checkpoint {
+            Some(path) => {
+                let snapshot = QTableSnapshot::load(path)?
+                    .with_context(|| format!("no agent checkpoint at {}", path.display()))?;
+                let mut agent = LaplacianQLearningAgent::new(LaplacianQLearningConfig::default())?;
+                agent.restore_q_table(snapshot);
+                Box::new(policy.with_source(Box::new(RlSignalPolicy::new(agent))))
+            }
+            None => Box::new(policy),
+        })
+    }
+}
+
+/// Which way a rule sends the signal
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SignalDirection {
+    /// Keep the detector's direction
+    #[default]
+    Detector,
+    /// Swap the detector's Buy and Sell
+    Reverse,
+    Buy,
+    Sell,
+    Hold,
+    /// Trade nothing on the anomaly
+    Suppress,
+}
+
+/// One row of the rule table, a `[[anomaly.signal_policy.rules]]` entry in TOML
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignalRule {
+    /// Anomaly type name (e.g. "MomentumShock"), or `*`
+    pub anomaly_type: String,
+
+    #[serde(default)]
+    pub min_confidence: f64,
+
+    #[serde(default)]
+    pub min_severity: Option<AnomalySeverity>,
+
+    #[serde(default)]
+    pub direction: SignalDirection,
+
+    /// Multiplies the signal strength, which sizes the position
+    #[serde(default = "default_strength_scale")]
+    pub strength_scale: f64,
+
+    #[serde(default)]
+    pub time_horizon: Option<String>,
+
+    #[serde(default)]
+    pub risk_level: Option<String>,
+
+    #[serde(default)]
+    pub duration_minutes: Option<u32>,
+}
+
+fn default_strength_scale() -> f64 {
+    1.0
+}
+
+impl SignalRule {
+    pub fn matches(&self, anomaly: &DetectedAnomaly) -> bool {
+        (self.anomaly_type == WILDCARD || self.anomaly_type == anomaly.anomaly_type.name())
+            && anomaly.confidence >= self.min_confidence
+            && self.min_severity.as_ref().is_none_or(|severity| anomaly.severity >= *severity)
+    }
+
+    /// The rule applied to `anomaly`, whose signal it starts from
+    pub fn apply(&self, anomaly: &DetectedAnomaly) -> Option<AnomalyTradingSignal> {
+        let proposed = anomaly.trading_signal.clone();
+        // Signals for anomalies the detector had nothing to say about
+        let fresh = |signal_type: &str| AnomalyTradingSignal {
+            signal_type: signal_type.to_string(),
+            strength: anomaly.confidence,
+            confidence: anomaly.confidence,
+            time_horizon: "Short".to_string(),
+            risk_level: "Medium".to_string(),
+            expected_duration: 60,
+        };
+        let mut signal = match self.direction {
+            SignalDirection::Suppress => return None,
+            SignalDirection::Detector => proposed?,
+            SignalDirection::Reverse => {
+                let mut signal = proposed?;
+                signal.signal_type = match signal.signal_type.as_str() {
+                    "Buy" => "Sell".to_string(),
+                    "Sell" => "Buy".to_string(),
+                    other => other.to_string(),
+                };
+                signal
+            }
+            SignalDirection::Buy | SignalDirection::Sell | SignalDirection::Hold => {
+                let signal_type = format!("{:?}", self.direction);
+                match proposed {
+                    Some(signal) => AnomalyTradingSignal { signal_type, ..signal },
+                    None => fresh(&signal_type),
+                }
+            }
+        };
+        signal.strength = (signal.strength * self.strength_scale).clamp(0.0, 1.0);
+        if let Some(time_horizon) = &self.time_horizon {
+            signal.time_horizon = time_horizon.clone();
+        }
+        if let Some(risk_level) = &self.risk_level {
+            signal.risk_level = risk_level.clone();
+        }
+        if let Some(duration) = self.duration_minutes {
+            signal.expected_duration = duration;
+        }
+        Some(signal)
+    }
+}
+
+/// Rule table over the detectors' signals, or over another policy's
+pub struct RuleSignalPolicy {
+    rules: Vec<SignalRule>,
+    source: Option<Box<dyn SignalPolicy>>,
+}
+
+impl RuleSignalPolicy {
+    pub fn new(rules: Vec<SignalRule>) -> Self {
+        Self { rules, source: None }
+    }
+
+    /// Apply the rules to `source`'s signals instead of the detectors'
+    pub fn with_source(mut self, source: Box<dyn SignalPolicy>) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    pub fn rules(&self) -> &[SignalRule] {
+        &self.rules
+    }
+}
+
+impl SignalPolicy for RuleSignalPolicy {
+    fn name(&self) -> &str {
+        "rules"
+    }
+
+    fn signal(&mut self, anomaly: &DetectedAnomaly, bar: &ForexDataPoint) -> Result<Option<AnomalyTradingSignal>> {
+        let sourced;
+        let anomaly = match &mut self.source {
+            Some(source) => {
+                sourced = DetectedAnomaly { trading_signal: source.signal(anomaly, bar)?, ..anomaly.clone() };
+                &sourced
+            }
+            None => anomaly,
+        };
+        Ok(match self.rules.iter().find(|rule| rule.matches(anomaly)) {
+            Some(rule) => rule.apply(anomaly),
+            None => anomaly.trading_signal.clone(),
+        })
+    }
+}
+
+/// Signals from a trained agent's greedy actions
+pub struct RlSignalPolicy {
+    agent: LaplacianQLearningAgent,
+}
+
+impl RlSignalPolicy {
+    /// Trade `agent`'s choices without exploring
+    pub fn new(mut agent: LaplacianQLearningAgent) -> Self {
+        agent.set_exploration_rate(0.0);
+        Self { agent }
+    }
+
+    pub fn agent(&self) -> &LaplacianQLearningAgent {
+        &self.agent
+    }
+}
+
+impl SignalPolicy for RlSignalPolicy {
+    fn name(&self) -> &str {
+        "rl_agent"
+    }
+
+    fn signal(&mut self, anomaly: &DetectedAnomaly, bar: &ForexDataPoint) -> Result<Option<AnomalyTradingSignal>> {
+        let state = self.agent.anomaly_to_state(anomaly, bar)?;
+        let (signal_type, size) = match self.agent.choose_action(&state, anomaly)? {
+            TradingAction::Buy { size } => ("Buy", size),
+            TradingAction::Sell { size } => ("Sell", size),
+            TradingAction::Hold | TradingAction::ClosePosition => ("Hold", 0),
+        };
+        let proposed = anomaly.trading_signal.as_ref();
+        Ok(Some(AnomalyTradingSignal {
+            signal_type: signal_type.to_string(),
+            // The agent's largest position is 20% of equity
+            strength: (size as f64 / 20.0).min(1.0),
+            confidence: anomaly.confidence,
+            time_horizon: proposed.map_or("Short", |signal| signal.time_horizon.as_str()).to_string(),
+            risk_level: proposed.map_or("Medium", |signal| signal.risk_level.as_str()).to_string(),
+            expected_duration: proposed.map_or(60, |signal| signal.expected_duration),
+        }))
+    }
+}
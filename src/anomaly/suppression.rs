@@ -0,0 +1,240 @@
+//! # Anomaly Suppression
+//!
+//! Operator acknowledgments and suppressions of recurring anomalies. A rule matches
+//! anomalies by pattern (symmetry or cycle id) and anomaly type, optionally for one
+//! pair, and keeps them out of alerting and RL triggering until it expires. Rules
+//! can be persisted to a JSON file so they survive restarts.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use super::DetectedAnomaly;
+
+/// Matches any pattern id or anomaly type
+pub const WILDCARD: &str = "*";
+
+/// Default rule lifetimes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SuppressionConfig {
+    /// How long an acknowledgment silences the anomaly
+    pub acknowledge_minutes: i64,
+    /// How long a suppression silences the anomaly
+    pub suppress_minutes: i64,
+}
+
+impl Default for SuppressionConfig {
+    fn default() -> Self {
+        Self {
+            acknowledge_minutes: 60,
+            suppress_minutes: 24 * 60,
+        }
+    }
+}
+
+/// Why a rule exists
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SuppressionKind {
+    /// Operator has seen the anomaly and does not want to be alerted again for a while
+    Acknowledged,
+    /// Known recurring pattern to be ignored
+    Suppressed,
+}
+
+impl std::fmt::Display for SuppressionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SuppressionKind::Acknowledged => write!(f, "acknowledged"),
+            SuppressionKind::Suppressed => write!(f, "suppressed"),
+        }
+    }
+}
+
+/// One acknowledgment or suppression
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuppressionRule {
+    pub id: String,
+    pub kind: SuppressionKind,
+    /// Pair the rule applies to, or every pair when `None`
+    pub pair: Option<String>,
+    /// Symmetry or cycle id, or `*`
+    pub pattern_id: String,
+    /// Anomaly type name (e.g. "SymmetryBreakdown"), or `*`
+    pub anomaly_type: String,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    /// Anomalies silenced by this rule so far
+    #[serde(default)]
+    pub matched: u64,
+}
+
+impl SuppressionRule {
+    pub fn new(
+        kind: SuppressionKind,
+        pair: Option<&str>,
+        pattern_id: &str,
+        anomaly_type: &str,
+        created_by: &str,
+        lifetime: Duration,
+        now: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string()[..8].to_string(),
+            kind,
+            pair: pair.map(|p| p.to_uppercase()),
+            pattern_id: pattern_id.to_string(),
+            anomaly_type: anomaly_type.to_string(),
+            created_by: created_by.to_string(),
+            created_at: now,
+            expires_at: now + lifetime,
+            matched: 0,
+        }
+    }
+
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        now < self.expires_at
+    }
+
+    /// Whether the rule covers `anomaly` on `symbol`
+    pub fn matches(&self, symbol: &str, anomaly: &DetectedAnomaly) -> bool {
+        let pair_matches = self.pair.as_deref().is_none_or(|pair| pair.eq_ignore_ascii_case(symbol));
+        let type_matches = self.anomaly_type == WILDCARD || self.anomaly_type == anomaly.anomaly_type.name();
+        let pattern_matches = self.pattern_id == WILDCARD || self.pattern_id == anomaly.pattern_id();
+        pair_matches && type_matches && pattern_matches
+    }
+}
+
+impl std::fmt::Display for SuppressionRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}/{} on {} until {}",
+               self.kind,
+               self.pattern_id,
+               self.anomaly_type,
+               self.pair.as_deref().unwrap_or("all pairs"),
+               self.expires_at.format("%Y-%m-%d %H:%M UTC"))
+    }
+}
+
+/// Thread-safe set of suppression rules
+pub struct SuppressionList {
+    config: SuppressionConfig,
+    rules: Mutex<Vec<SuppressionRule>>,
+    file: Option<PathBuf>,
+}
+
+impl SuppressionList {
+    /// In-memory suppression list
+    pub fn new(config: SuppressionConfig) -> Self {
+        Self {
+            config,
+            rules: Mutex::new(Vec::new()),
+            file: None,
+        }
+    }
+
+    /// Suppression list stored in `path`, loading any rules already there
+    pub fn with_file(config: SuppressionConfig, path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let rules: Vec<SuppressionRule> = if path.exists() {
+            serde_json::from_str(&std::fs::read_to_string(path)?)?
+        } else {
+            Vec::new()
+        };
+        let now = Utc::now();
+        Ok(Self {
+            config,
+            rules: Mutex::new(rules.into_iter().filter(|rule| rule.is_active(now)).collect()),
+            file: Some(path.to_path_buf()),
+        })
+    }
+
+    /// Persistent list at `ANOMALY_SUPPRESSIONS_PATH` when set, otherwise in-memory
+    pub fn from_env() -> Result<Self> {
+        match std::env::var("ANOMALY_SUPPRESSIONS_PATH") {
+            Ok(path) => Self::with_file(SuppressionConfig::default(), Path::new(&path)),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    pub fn config(&self) -> &SuppressionConfig {
+        &self.config
+    }
+
+    /// Acknowledge anomalies matching `pattern_id` / `anomaly_type` for the configured period
+    pub fn acknowledge(&self, pair: Option<&str>, pattern_id: &str, anomaly_type: &str, created_by: &str) -> SuppressionRule {
+        let lifetime = Duration::minutes(self.config.acknowledge_minutes);
+        self.add(SuppressionRule::new(SuppressionKind::Acknowledged, pair, pattern_id, anomaly_type, created_by, lifetime, Utc::now()))
+    }
+
+    /// Suppress anomalies matching `pattern_id` / `anomaly_type` for `lifetime`, or the configured period
+    pub fn suppress(&self, pair: Option<&str>, pattern_id: &str, anomaly_type: &str, created_by: &str, lifetime: Option<Duration>) -> SuppressionRule {
+        let lifetime = lifetime.unwrap_or_else(|| Duration::minutes(self.config.suppress_minutes));
+        self.add(SuppressionRule::new(SuppressionKind::Suppressed, pair, pattern_id, anomaly_type, created_by, lifetime, Utc::now()))
+    }
+
+    /// Add a rule, replacing any existing rule for the same pair, pattern and type
+    pub fn add(&self, rule: SuppressionRule) -> SuppressionRule {
+        let mut rules = self.lock();
+        rules.retain(|existing| {
+            !(existing.pair == rule.pair && existing.pattern_id == rule.pattern_id && existing.anomaly_type == rule.anomaly_type)
+        });
+        rules.push(rule.clone());
+        self.save(&rules);
+        rule
+    }
+
+    /// Remove a rule by id, returning it if it existed
+    pub fn remove(&self, id: &str) -> Option<SuppressionRule> {
+        let mut rules = self.lock();
+        let index = rules.iter().position(|rule| rule.id == id)?;
+        let removed = rules.remove(index);
+        self.save(&rules);
+        Some(removed)
+    }
+
+    /// The active rule silencing `anomaly` on `symbol`, if any; counts the match
+    pub fn check(&self, symbol: &str, anomaly: &DetectedAnomaly, now: DateTime<Utc>) -> Option<SuppressionRule> {
+        let mut rules = self.lock();
+        let rule = rules.iter_mut().find(|rule| rule.is_active(now) && rule.matches(symbol, anomaly))?;
+        rule.matched += 1;
+        Some(rule.clone())
+    }
+
+    /// Rules that have not expired, dropping expired ones
+    pub fn active(&self, now: DateTime<Utc>) -> Vec<SuppressionRule> {
+        let mut rules = self.lock();
+        let before = rules.len();
+        rules.retain(|rule| rule.is_active(now));
+        if rules.len() != before {
+            self.save(&rules);
+        }
+        rules.clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<SuppressionRule>> {
+        self.rules.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn save(&self, rules: &[SuppressionRule]) {
+        let Some(path) = &self.file else { return };
+        let written = serde_json::to_string_pretty(rules)
+            .map_err(anyhow::Error::from)
+            .and_then(|json| std::fs::write(path, json).map_err(anyhow::Error::from));
+        if let Err(e) = written {
+            println!("⚠️  Failed to write suppression list {}: {}", path.display(), e);
+        }
+    }
+}
+
+impl Default for SuppressionList {
+    fn default() -> Self {
+        Self::new(SuppressionConfig::default())
+    }
+}
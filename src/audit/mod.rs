@@ -0,0 +1,96 @@
+//! # Audit Log
+//!
+//! Append-only record of operator commands and their outcomes, kept in memory and
+//! optionally mirrored to a JSON-lines file
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Entries kept in memory
+const MAX_IN_MEMORY_ENTRIES: usize = 1000;
+
+/// One audited command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: DateTime<Utc>,
+    /// Who issued the command (e.g. "api", "cli", remote address)
+    pub source: String,
+    pub command: String,
+    pub success: bool,
+    pub message: String,
+}
+
+/// Thread-safe audit log
+pub struct AuditLog {
+    entries: Mutex<VecDeque<AuditEntry>>,
+    file: Option<PathBuf>,
+}
+
+impl AuditLog {
+    /// In-memory audit log
+    pub fn new() -> Self {
+        Self {
+            entries: Mutex::new(VecDeque::new()),
+            file: None,
+        }
+    }
+
+    /// Audit log that also appends every entry to `path`
+    pub fn with_file(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        Ok(Self {
+            entries: Mutex::new(VecDeque::new()),
+            file: Some(path.to_path_buf()),
+        })
+    }
+
+    /// Record a command outcome
+    pub fn record(&self, source: &str, command: &str, success: bool, message: &str) -> AuditEntry {
+        let entry = AuditEntry {
+            timestamp: Utc::now(),
+            source: source.to_string(),
+            command: command.to_string(),
+            success,
+            message: message.to_string(),
+        };
+
+        if let Some(path) = &self.file {
+            if let Err(e) = append_line(path, &entry) {
+                println!("⚠️  Failed to write audit log {}: {}", path.display(), e);
+            }
+        }
+
+        let mut entries = self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        entries.push_back(entry.clone());
+        while entries.len() > MAX_IN_MEMORY_ENTRIES {
+            entries.pop_front();
+        }
+
+        entry
+    }
+
+    /// Most recent entries, newest last
+    pub fn recent(&self, limit: usize) -> Vec<AuditEntry> {
+        let entries = self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        entries.iter().skip(entries.len().saturating_sub(limit)).cloned().collect()
+    }
+}
+
+impl Default for AuditLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn append_line(path: &Path, entry: &AuditEntry) -> Result<()> {
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)?;
+    Ok(())
+}
@@ -0,0 +1,174 @@
+//! # Order Execution Model
+//!
+//! How simulated orders meet the market: the liquidity a bar offers, after which
+//! orders fill in part; random rejections and requotes; and the latency between
+//! the decision at a bar close and the fill. The defaults fill every order in
+//! full, instantly, so backtests without an execution model are unchanged.
+
+use chrono::Duration;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::data::ForexDataPoint;
+
+/// Execution conditions for one pair
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExecutionModelConfig {
+    /// Units one bar absorbs across all orders; the rest of an order is cancelled.
+    /// `None` for unlimited liquidity
+    pub liquidity_units: Option<f64>,
+    /// Share of a bar's volume its orders may take, used instead of
+    /// `liquidity_units` on bars with a volume
+    pub volume_participation: Option<f64>,
+    /// Partial fills below this fraction of the order are cancelled instead
+    pub min_fill_fraction: f64,
+    /// Chance an order is rejected outright
+    pub rejection_probability: f64,
+    /// Chance an order is requoted and filled at a worse price
+    pub requote_probability: f64,
+    /// Adverse price move of a requote, as a fraction of price
+    pub requote_slippage: f64,
+    /// Delay between the decision and the fill
+    pub latency_ms: u64,
+    /// Extra delay drawn uniformly up to this
+    pub latency_jitter_ms: u64,
+    /// Seed of the rejection, requote and jitter draws
+    pub seed: u64,
+    /// Adverse fill price offset of this pair, instead of the backtest's `slippage`
+    pub slippage: Option<f64>,
+}
+
+impl Default for ExecutionModelConfig {
+    fn default() -> Self {
+        Self {
+            liquidity_units: None,
+            volume_participation: None,
+            min_fill_fraction: 0.0,
+            rejection_probability: 0.0,
+            requote_probability: 0.0,
+            requote_slippage: 0.0002,
+            latency_ms: 0,
+            latency_jitter_ms: 0,
+            seed: 0,
+            slippage: None,
+        }
+    }
+}
+
+/// Execution conditions per pair
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExecutionModel {
+    /// Conditions for pairs without their own entry
+    pub default: ExecutionModelConfig,
+    pub pairs: HashMap<String, ExecutionModelConfig>,
+}
+
+impl ExecutionModel {
+    pub fn with_pair(mut self, pair: &str, config: ExecutionModelConfig) -> Self {
+        self.pairs.insert(pair.to_string(), config);
+        self
+    }
+
+    pub fn config_for(&self, pair: &str) -> &ExecutionModelConfig {
+        self.pairs.get(pair).unwrap_or(&self.default)
+    }
+}
+
+/// What happened to the orders of a backtest
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExecutionStats {
+    pub orders: usize,
+    pub fills: usize,
+    /// Fills for less than the order
+    pub partial_fills: usize,
+    pub rejections: usize,
+    pub requotes: usize,
+    /// Units ordered but never filled, from rejections and liquidity shortfalls
+    pub unfilled_units: f64,
+    /// Orders still waiting out their latency when the data ended
+    pub pending_at_end: usize,
+    /// Mean delay between decision and fill
+    pub mean_latency_ms: f64,
+}
+
+/// Outcome of offering an order to the market
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Admission {
+    Rejected,
+    /// Units that fill and the adverse price move of a requote, as a fraction of price
+    Filled { units: f64, requote: f64 },
+}
+
+/// Draws one run's executions from an [`ExecutionModelConfig`]
+pub(crate) struct OrderSimulator {
+    config: ExecutionModelConfig,
+    rng: StdRng,
+    /// Liquidity left on the current bar
+    liquidity: f64,
+    latency_total_ms: f64,
+    pub stats: ExecutionStats,
+}
+
+impl OrderSimulator {
+    pub fn new(config: ExecutionModelConfig) -> Self {
+        let rng = StdRng::seed_from_u64(config.seed);
+        Self { config, rng, liquidity: f64::INFINITY, latency_total_ms: 0.0, stats: ExecutionStats::default() }
+    }
+
+    /// Whether orders wait before they fill
+    pub fn has_latency(&self) -> bool {
+        self.config.latency_ms > 0 || self.config.latency_jitter_ms > 0
+    }
+
+    /// Reset the liquidity for the orders filled on `bar`
+    pub fn start_bar(&mut self, bar: &ForexDataPoint) {
+        self.liquidity = match (self.config.volume_participation, bar.volume) {
+            (Some(share), Some(volume)) if volume > 0.0 => volume * share,
+            _ => self.config.liquidity_units.unwrap_or(f64::INFINITY),
+        };
+    }
+
+    /// Delay before an order decided now fills
+    pub fn latency(&mut self) -> Duration {
+        let jitter = if self.config.latency_jitter_ms > 0 { self.rng.gen_range(0..=self.config.latency_jitter_ms) } else { 0 };
+        Duration::milliseconds((self.config.latency_ms + jitter) as i64)
+    }
+
+    /// Offer an order of `units` to the current bar
+    pub fn admit(&mut self, units: f64) -> Admission {
+        self.stats.orders += 1;
+        if self.config.rejection_probability > 0.0 && self.rng.gen_bool(self.config.rejection_probability.min(1.0)) {
+            self.stats.rejections += 1;
+            self.stats.unfilled_units += units;
+            return Admission::Rejected;
+        }
+        let filled = units.min(self.liquidity.max(0.0));
+        if filled <= 0.0 || filled < units * self.config.min_fill_fraction {
+            self.stats.unfilled_units += units;
+            return Admission::Rejected;
+        }
+        self.liquidity -= filled;
+        if filled < units {
+            self.stats.partial_fills += 1;
+            self.stats.unfilled_units += units - filled;
+        }
+        let requote = if self.config.requote_probability > 0.0 && self.rng.gen_bool(self.config.requote_probability.min(1.0)) {
+            self.stats.requotes += 1;
+            self.config.requote_slippage
+        } else {
+            0.0
+        };
+        self.stats.fills += 1;
+        Admission::Filled { units: filled, requote }
+    }
+
+    /// Record the delay of a fill
+    pub fn record_latency(&mut self, latency: Duration) {
+        self.latency_total_ms += latency.num_milliseconds() as f64;
+        self.stats.mean_latency_ms = self.latency_total_ms / self.stats.fills.max(1) as f64;
+    }
+}
@@ -1,17 +1,86 @@
 //! # Backtesting Engine
-//! 
-//! Validation of temporal symmetries through backtesting.
+//!
+//! Validation of temporal symmetries through backtesting: bars are replayed
+//! through a [`strategy::Strategy`] and orders filled at the bar close under the
+//! pair's [`execution::ExecutionModel`] and, optionally, margin limits.
+
+pub mod execution;
+pub mod optimizer;
+pub mod rl_training;
+pub mod sandbox;
+pub mod spread;
+pub mod strategy;
+pub mod walk_forward;
 
 use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use crate::anomaly::DetectedAnomaly;
+use crate::calendar::{HolidayCalendar, ThinMarketPolicy};
+use crate::data::ForexDataPoint;
+use crate::patterns::{PatternConfig, PatternRecognizer};
+use crate::portfolio::execution_quality::ExecutionQualityReport;
+use crate::portfolio::margin::{max_order_units, MarginConfig, MarginEvent, MarginEventKind, MarginStatus};
+use crate::report::TradeRecord;
+use crate::trading_windows::TradingWindowsConfig;
+use execution::{Admission, ExecutionModel, ExecutionStats, OrderSimulator};
+use strategy::{Fill, Order, Strategy, StrategyContext};
+use walk_forward::AnomalyBaseline;
+
+/// Market conditions orders on one bar are filled under
+#[derive(Clone, Copy)]
+struct Execution {
+    /// When the orders fill: the bar close, or later by the latency
+    timestamp: DateTime<Utc>,
+    /// Mid price at `timestamp`
+    price: f64,
+    /// Bid/ask spread at the close, when quoted
+    spread: Option<f64>,
+    entry_weight: f64,
+    /// Broker liquidation, which bypasses the execution model and leverage limit
+    forced: bool,
+}
+
+/// Order waiting out its latency
+struct PendingOrder {
+    order: Order,
+    decided_at: DateTime<Utc>,
+    due: DateTime<Utc>,
+}
+
+/// Rounds of `on_fill` follow-up orders filled on one bar, so a strategy that keeps
+/// answering its own fills cannot stall the replay
+const MAX_FILL_ROUNDS: usize = 4;
 
 /// Backtest configuration
 #[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
 pub struct BacktestConfig {
+    /// Commission as a fraction of traded notional
     pub commission: f64,
+    /// Adverse fill price offset as a fraction of price, for bars without a quoted spread
     pub slippage: f64,
     pub max_positions: usize,
+    /// Bars used for the first cycle estimate before trading starts
+    pub warmup_bars: usize,
+    /// Bars between cycle re-estimates
+    pub cycle_refresh_bars: usize,
+    /// Liquidity, rejections, requotes and latency per pair
+    pub execution: ExecutionModel,
+    /// Account leverage (notional / margin)
+    pub leverage: f64,
+    /// Leverage limit, margin call and stop-out levels
+    pub margin: MarginConfig,
+    /// Where the baselines anomalies are detected against come from
+    pub anomaly_baseline: AnomalyBaseline,
+    /// Bars between walk-forward baseline refits
+    pub baseline_refit_bars: usize,
+    /// Live/paper execution log to take slippage and per-pair latency from
+    /// (see [`crate::portfolio::execution_quality`])
+    #[serde(default)]
+    pub execution_log: Option<PathBuf>,
 }
 
 impl Default for BacktestConfig {
@@ -20,6 +89,14 @@ impl Default for BacktestConfig {
             commission: 0.0001,
             slippage: 0.0001,
             max_positions: 1,
+            warmup_bars: 100,
+            cycle_refresh_bars: 20,
+            execution: ExecutionModel::default(),
+            leverage: 30.0,
+            margin: MarginConfig::default(),
+            anomaly_baseline: AnomalyBaseline::WalkForward,
+            baseline_refit_bars: 100,
+            execution_log: None,
         }
     }
 }
@@ -27,17 +104,35 @@ impl Default for BacktestConfig {
 /// Strategy configuration
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct StrategyConfig {
+    /// Registry key of the strategy to run
+    #[serde(default = "default_strategy_name")]
     pub name: String,
+    #[serde(default)]
     pub parameters: std::collections::HashMap<String, f64>,
 }
 
+fn default_strategy_name() -> String {
+    strategy::TimeSymmetricStrategy::NAME.to_string()
+}
+
+impl Default for StrategyConfig {
+    fn default() -> Self {
+        Self {
+            name: default_strategy_name(),
+            parameters: std::collections::HashMap::new(),
+        }
+    }
+}
+
 /// Backtest results
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidationResults {
     pub total_return: f64,
     pub sharpe_ratio: f64,
     pub max_drawdown: f64,
+    /// Mean confidence of the hidden cycles estimated during the run
     pub symmetry_score: f64,
+    /// Fraction of position-reducing fills that realized a profit
     pub pattern_consistency: f64,
 }
 
@@ -47,46 +142,511 @@ impl ValidationResults {
     }
 }
 
+/// Results plus the fills behind them
+#[derive(Debug, Clone, Serialize)]
+pub struct BacktestRun {
+    pub strategy: String,
+    pub pair: String,
+    pub results: ValidationResults,
+    /// One record per fill, tagged with the symmetry that triggered it
+    pub trades: Vec<TradeRecord>,
+    /// Equity after each bar
+    pub equity_curve: Vec<(DateTime<Utc>, f64)>,
+    /// Bars that fell in thin holiday markets
+    pub thin_market_bars: usize,
+    /// Partial fills, rejections, requotes and latency
+    pub execution: ExecutionStats,
+    /// Leverage cuts, margin calls and stop-outs
+    pub margin_events: Vec<MarginEvent>,
+}
+
+/// Simulated single-pair account
+struct Account {
+    cash: f64,
+    units: f64,
+    entry_price: f64,
+    winning_exits: usize,
+    exits: usize,
+}
+
+impl Account {
+    fn equity(&self, price: f64) -> f64 {
+        self.cash + self.units * (price - self.entry_price)
+    }
+
+    /// Margin level in percent at `price`, `None` when flat
+    fn margin_level(&self, price: f64, leverage: f64) -> Option<f64> {
+        (self.units != 0.0).then(|| self.equity(price) / (self.units.abs() * price / leverage) * 100.0)
+    }
+
+    /// Price at which the margin level is `level` percent: solves
+    /// cash + u(p − e) = level/100 · |u|p / leverage for p
+    fn price_at_margin_level(&self, level: f64, leverage: f64) -> f64 {
+        (self.cash - self.units * self.entry_price) / (level / 100.0 * self.units.abs() / leverage - self.units)
+    }
+}
+
+/// Mutable state of one backtest run
+struct Run {
+    account: Account,
+    trades: Vec<TradeRecord>,
+    simulator: OrderSimulator,
+    /// Orders waiting out their latency, in decision order
+    pending: Vec<PendingOrder>,
+    margin_events: Vec<MarginEvent>,
+    /// Whether the current margin call was already raised
+    in_margin_call: bool,
+}
+
 /// Backtesting engine
 pub struct BacktestEngine {
     strategy_config: StrategyConfig,
     initial_capital: f64,
     config: BacktestConfig,
+    trading_windows: TradingWindowsConfig,
+    holiday_calendar: HolidayCalendar,
+    pattern_config: PatternConfig,
 }
 
 impl BacktestEngine {
+    /// Engine for `config`, calibrated to its execution log when it names one
     pub fn new(
         strategy_config: StrategyConfig,
         initial_capital: f64,
         config: BacktestConfig,
     ) -> Result<Self> {
+        let config = match &config.execution_log {
+            Some(path) => ExecutionQualityReport::from_log(path)?.calibrate(config),
+            None => config,
+        };
         Ok(Self {
             strategy_config,
             initial_capital,
             config,
+            trading_windows: TradingWindowsConfig::default(),
+            holiday_calendar: HolidayCalendar::disabled(),
+            pattern_config: PatternConfig::default(),
         })
     }
-    
-    pub async fn validate_temporal_symmetries(
-        &mut self,
-        start_date: &str,
-        end_date: &str,
-    ) -> Result<ValidationResults> {
-        // Placeholder validation
-        Ok(ValidationResults {
-            total_return: 0.15,
-            sharpe_ratio: 1.8,
-            max_drawdown: 0.08,
-            symmetry_score: 0.87,
-            pattern_consistency: 0.82,
+
+    /// Apply the same blocked periods the execution layer enforces
+    pub fn with_trading_windows(mut self, trading_windows: TradingWindowsConfig) -> Self {
+        self.trading_windows = trading_windows;
+        self
+    }
+
+    /// Skip or shrink entries on thin holiday bars; under `Skip` those bars are also
+    /// left out of the cycle estimates
+    pub fn with_holiday_calendar(mut self, holiday_calendar: HolidayCalendar) -> Self {
+        self.holiday_calendar = holiday_calendar;
+        self
+    }
+
+    /// Cycle search settings used for the rolling cycle estimates
+    pub fn with_pattern_config(mut self, pattern_config: PatternConfig) -> Self {
+        self.pattern_config = pattern_config;
+        self
+    }
+
+    pub fn strategy_config(&self) -> &StrategyConfig {
+        &self.strategy_config
+    }
+
+    /// Settings after calibration to the execution log
+    pub fn backtest_config(&self) -> &BacktestConfig {
+        &self.config
+    }
+
+    /// Whether the simulated strategy may open a trade at `timestamp`
+    pub fn is_trading_allowed(&self, timestamp: DateTime<Utc>) -> bool {
+        self.trading_windows.is_trading_allowed(timestamp)
+    }
+
+    /// Replay `data` for `pair` through `strategy`.
+    ///
+    /// Cycles are re-estimated from bars up to the current one only, and anomalies are
+    /// delivered on the first bar at or after their timestamp, so nothing is seen early.
+    pub async fn run(
+        &self,
+        strategy: &mut dyn Strategy,
+        pair: &str,
+        data: &[ForexDataPoint],
+        anomalies: &[DetectedAnomaly],
+    ) -> Result<BacktestRun> {
+        self.run_with_spreads(strategy, pair, data, &[], anomalies).await
+    }
+
+    /// [`run`](Self::run) with the bid/ask spread quoted at each bar's close, e.g. from
+    /// [`TickBar::close_spread`](crate::data::tick::TickBar): buys fill at the ask and
+    /// sells at the bid instead of paying the constant `slippage`. Bars past the end of
+    /// `spreads` fall back to `slippage`.
+    pub async fn run_with_spreads(
+        &self,
+        strategy: &mut dyn Strategy,
+        pair: &str,
+        data: &[ForexDataPoint],
+        spreads: &[f64],
+        anomalies: &[DetectedAnomaly],
+    ) -> Result<BacktestRun> {
+        let mut recognizer = PatternRecognizer::new(self.pattern_config.clone())?;
+        let mut anomalies: Vec<&DetectedAnomaly> = anomalies.iter().collect();
+        anomalies.sort_by_key(|a| a.timestamp);
+        let mut next_anomaly = 0;
+
+        let bar_seconds = median_spacing_seconds(data);
+        let warmup = self.config.warmup_bars.min(data.len());
+        let refresh = self.config.cycle_refresh_bars.max(1);
+        let mut run = Run {
+            account: Account { cash: self.initial_capital, units: 0.0, entry_price: 0.0, winning_exits: 0, exits: 0 },
+            trades: Vec::new(),
+            simulator: OrderSimulator::new(self.config.execution.config_for(pair).clone()),
+            pending: Vec::new(),
+            margin_events: Vec::new(),
+            in_margin_call: false,
+        };
+        let mut equity_curve = Vec::with_capacity(data.len());
+        let mut cycle_confidences = Vec::new();
+        let thin: Vec<bool> = data.iter().map(|bar| self.holiday_calendar.is_thin(pair, bar.timestamp)).collect();
+        let skip_thin = self.holiday_calendar.policy == ThinMarketPolicy::Skip && thin.contains(&true);
+
+        for (index, bar) in data.iter().enumerate() {
+            let mut context = StrategyContext {
+                pair: pair.to_string(),
+                timestamp: bar.timestamp,
+                bar_index: index,
+                bar_seconds,
+                position_units: run.account.units,
+                equity: run.account.equity(bar.close),
+                trading_allowed: self.is_trading_allowed(bar.timestamp),
+                thin_market: thin[index],
+            };
+            let entry_weight = if context.trading_allowed { self.holiday_calendar.weight(pair, bar.timestamp) } else { 0.0 };
+            let spread = spreads.get(index).copied().filter(|spread| spread.is_finite() && *spread >= 0.0);
+            let mut orders = Vec::new();
+            run.simulator.start_bar(bar);
+            self.enforce_margin(strategy, &mut context, &mut run, bar, spread, entry_weight);
+
+            // Orders whose latency ran out since the previous close fill at the price then
+            let due = run.pending.iter().take_while(|pending| pending.due <= bar.timestamp).count();
+            for pending in run.pending.drain(..due).collect::<Vec<_>>() {
+                let price = match index.checked_sub(1).map(|previous| &data[previous]) {
+                    Some(previous) if bar.timestamp > previous.timestamp => {
+                        let elapsed = (pending.due - previous.timestamp).num_milliseconds().max(0) as f64;
+                        let fraction = (elapsed / (bar.timestamp - previous.timestamp).num_milliseconds() as f64).min(1.0);
+                        previous.close + (bar.close - previous.close) * fraction
+                    }
+                    _ => bar.close,
+                };
+                let execution = Execution { timestamp: pending.due, price, spread, entry_weight, forced: false };
+                self.execute(strategy, &mut context, &mut run, &execution, vec![pending.order], pending.decided_at);
+            }
+
+            if index + 1 >= warmup && (index + 1 - warmup).is_multiple_of(refresh) {
+                let cycles = if skip_thin {
+                    let liquid: Vec<ForexDataPoint> = data[..=index].iter().zip(&thin)
+                        .filter(|(_, thin)| !**thin)
+                        .map(|(bar, _)| bar.clone())
+                        .collect();
+                    recognizer.detect_cycles(&liquid).await?
+                } else {
+                    recognizer.detect_cycles(&data[..=index]).await?
+                };
+                cycle_confidences.extend(cycles.iter().map(|c| c.confidence));
+                orders.extend(strategy.on_cycle_update(&context, &cycles));
+            }
+            while next_anomaly < anomalies.len() && anomalies[next_anomaly].timestamp <= bar.timestamp {
+                orders.extend(strategy.on_anomaly(&context, anomalies[next_anomaly]));
+                next_anomaly += 1;
+            }
+            if index + 1 >= warmup {
+                let execution = Execution { timestamp: bar.timestamp, price: bar.close, spread, entry_weight, forced: false };
+                self.execute(strategy, &mut context, &mut run, &execution, std::mem::take(&mut orders), bar.timestamp);
+                let bar_orders = strategy.on_bar(&context, bar);
+                self.execute(strategy, &mut context, &mut run, &execution, bar_orders, bar.timestamp);
+            }
+
+            equity_curve.push((bar.timestamp, run.account.equity(bar.close)));
+        }
+
+        let results = self.summarize(&equity_curve, &run.account, &cycle_confidences, bar_seconds);
+        let mut execution = run.simulator.stats;
+        execution.pending_at_end = run.pending.len();
+        Ok(BacktestRun {
+            strategy: strategy.name().to_string(),
+            pair: pair.to_string(),
+            results,
+            trades: run.trades,
+            equity_curve,
+            thin_market_bars: thin.iter().filter(|t| **t).count(),
+            execution,
+            margin_events: run.margin_events,
         })
     }
+
+    /// Fill `orders` decided at `decided_at`, report each fill to the strategy, and
+    /// fill the orders it answers with, keeping `context` in step with the account.
+    /// Under latency, new orders are queued instead and fill on a later bar.
+    fn execute(
+        &self,
+        strategy: &mut dyn Strategy,
+        context: &mut StrategyContext,
+        run: &mut Run,
+        execution: &Execution,
+        mut orders: Vec<Order>,
+        decided_at: DateTime<Utc>,
+    ) {
+        let mut latency = execution.timestamp - decided_at;
+        for _ in 0..MAX_FILL_ROUNDS {
+            if orders.is_empty() {
+                break;
+            }
+            if run.simulator.has_latency() && latency.is_zero() && !execution.forced {
+                for order in orders {
+                    let due = execution.timestamp + run.simulator.latency();
+                    run.pending.push(PendingOrder { order, decided_at: execution.timestamp, due });
+                }
+                run.pending.sort_by_key(|pending| pending.due);
+                return;
+            }
+            let fills = self.fill(run, &context.pair, execution, orders, latency);
+            context.position_units = run.account.units;
+            context.equity = run.account.equity(execution.price);
+            orders = fills.iter().flat_map(|fill| strategy.on_fill(context, fill)).collect();
+            latency = Duration::zero();
+        }
+    }
+
+    /// Raise a margin call once per episode when the bar's adverse extreme takes the
+    /// margin level to the call level, and liquidate the position when it reaches the
+    /// stop-out level: at the price it did, or the open when the bar gapped through it.
+    fn enforce_margin(
+        &self,
+        strategy: &mut dyn Strategy,
+        context: &mut StrategyContext,
+        run: &mut Run,
+        bar: &ForexDataPoint,
+        spread: Option<f64>,
+        entry_weight: f64,
+    ) {
+        let margin = &self.config.margin;
+        let units = run.account.units;
+        if !margin.enabled || units == 0.0 {
+            run.in_margin_call = false;
+            return;
+        }
+        let leverage = self.config.leverage.max(1.0);
+        let adverse = if units > 0.0 { bar.low.min(bar.open).min(bar.close) } else { bar.high.max(bar.open).max(bar.close) };
+        let event = |account: &Account, kind, price: f64, realized_pnl| MarginEvent {
+            timestamp: bar.timestamp,
+            kind,
+            symbols: vec![context.pair.clone()],
+            equity: account.equity(price),
+            margin_used: units.abs() * price / leverage,
+            margin_level: account.margin_level(price, leverage),
+            units: if kind == MarginEventKind::StopOut { units.abs() } else { 0.0 },
+            realized_pnl,
+        };
+        match margin.status(run.account.margin_level(adverse, leverage)) {
+            MarginStatus::Healthy => {}
+            MarginStatus::MarginCall => {
+                if !run.in_margin_call {
+                    run.margin_events.push(event(&run.account, MarginEventKind::MarginCall, adverse, 0.0));
+                    run.in_margin_call = true;
+                }
+            }
+            MarginStatus::StopOut => {
+                let trigger = run.account.price_at_margin_level(margin.stop_out_level, leverage);
+                let price = if units > 0.0 { trigger.min(bar.open).max(adverse) } else { trigger.max(bar.open).min(adverse) };
+                let before = event(&run.account, MarginEventKind::StopOut, price, 0.0);
+                let order = if units > 0.0 { Order::sell(units, "margin stop-out") } else { Order::buy(-units, "margin stop-out") };
+                let liquidation = Execution { timestamp: bar.timestamp, price, spread, entry_weight, forced: true };
+                let fills = self.fill(run, &context.pair, &liquidation, vec![order], Duration::zero());
+                run.margin_events.push(MarginEvent { realized_pnl: fills.iter().map(|fill| fill.realized_pnl).sum(), ..before });
+                run.in_margin_call = false;
+                context.position_units = run.account.units;
+                context.equity = run.account.equity(price);
+                let orders = fills.iter().flat_map(|fill| strategy.on_fill(context, fill)).collect();
+                self.execute(strategy, context, run, &Execution { forced: false, ..liquidation }, orders, bar.timestamp);
+                return;
+            }
+        }
+        if margin.status(run.account.margin_level(bar.close, leverage)) == MarginStatus::Healthy {
+            run.in_margin_call = false;
+        }
+    }
+
+    /// Fill orders at the execution price, at the ask or bid when the spread is known
+    /// and adjusted for the pair's slippage otherwise, charging commission.
+    ///
+    /// Opening trades are scaled by `entry_weight` (0 when trading windows or thin
+    /// holiday markets block them); exits are not, and a reversal is split so only
+    /// the part beyond flat is scaled. With margin enforced, orders are
+    /// cut to what the free margin carries. Every order then meets the execution
+    /// model, which may reject it, fill it in part or requote it; liquidations skip both.
+    fn fill(&self, run: &mut Run, pair: &str, execution: &Execution, orders: Vec<Order>, latency: Duration) -> Vec<Fill> {
+        let Execution { timestamp, price: mid, spread, entry_weight, forced } = *execution;
+        let leverage = self.config.leverage.max(1.0);
+        let account = &mut run.account;
+        let mut fills = Vec::new();
+        for order in orders {
+            let mut delta = order.delta();
+            if delta == 0.0 || !delta.is_finite() {
+                continue;
+            }
+            let reduces = account.units != 0.0 && account.units.signum() != delta.signum();
+            if reduces {
+                // A reversal closes unweighted; only the part beyond flat opens a position
+                let closing = -account.units;
+                if delta.abs() > closing.abs() {
+                    delta = closing + (delta - closing) * entry_weight.clamp(0.0, 1.0);
+                }
+            } else {
+                if entry_weight <= 0.0 {
+                    continue;
+                }
+                delta *= entry_weight.min(1.0);
+            }
+            if self.config.margin.enabled && !forced {
+                let equity = account.equity(mid);
+                let margin_used = account.units.abs() * mid / leverage;
+                let limit = max_order_units(account.units, delta, equity - margin_used, mid / leverage);
+                if delta.abs() > limit {
+                    run.margin_events.push(MarginEvent {
+                        timestamp,
+                        kind: MarginEventKind::LeverageLimit,
+                        symbols: vec![pair.to_string()],
+                        equity,
+                        margin_used,
+                        margin_level: account.margin_level(mid, leverage),
+                        units: delta.abs() - limit,
+                        realized_pnl: 0.0,
+                    });
+                    delta = limit * delta.signum();
+                    if delta == 0.0 {
+                        continue;
+                    }
+                }
+            }
+            let requote = if forced {
+                0.0
+            } else {
+                match run.simulator.admit(delta.abs()) {
+                    Admission::Rejected => continue,
+                    Admission::Filled { units, requote } => {
+                        delta = units * delta.signum();
+                        run.simulator.record_latency(latency);
+                        requote
+                    }
+                }
+            };
+
+            let price = match spread {
+                Some(spread) => mid + spread / 2.0 * delta.signum(),
+                None => mid * (1.0 + self.config.execution.config_for(pair).slippage.unwrap_or(self.config.slippage) * delta.signum()),
+            } * (1.0 + requote * delta.signum());
+            let commission = delta.abs() * price * self.config.commission;
+            let entry_before = account.entry_price;
+            let mut realized = 0.0;
+            let mut exit_price = None;
+
+            if account.units == 0.0 || !reduces {
+                let units = account.units + delta;
+                account.entry_price = (account.entry_price * account.units + price * delta) / units;
+                account.units = units;
+            } else {
+                let closed = delta.abs().min(account.units.abs()) * account.units.signum();
+                realized = (price - account.entry_price) * closed;
+                account.cash += realized;
+                account.units += delta;
+                account.exits += 1;
+                if realized > 0.0 {
+                    account.winning_exits += 1;
+                }
+                exit_price = Some(price);
+                if account.units.abs() < f64::EPSILON {
+                    account.units = 0.0;
+                } else if account.units.signum() == delta.signum() {
+                    account.entry_price = price; // flipped through flat
+                }
+            }
+            account.cash -= commission;
+
+            run.trades.push(TradeRecord {
+                pair: pair.to_string(),
+                timestamp,
+                side: if delta > 0.0 { "Buy" } else { "Sell" }.to_string(),
+                size: delta.abs(),
+                entry_price: if exit_price.is_some() { entry_before } else { price },
+                exit_price,
+                commission,
+                profit_loss: realized - commission,
+                symmetry_id: order.symmetry_id.clone(),
+                cycle_id: order.cycle_id.clone(),
+            });
+            fills.push(Fill {
+                side: order.side,
+                units: delta.abs(),
+                price,
+                realized_pnl: realized,
+                commission,
+                position_units: account.units,
+                reason: order.reason,
+                symmetry_id: order.symmetry_id,
+                cycle_id: order.cycle_id,
+            });
+        }
+        fills
+    }
+
+    fn summarize(&self, equity_curve: &[(DateTime<Utc>, f64)], account: &Account, cycle_confidences: &[f64], bar_seconds: f64) -> ValidationResults {
+        let final_equity = equity_curve.last().map(|(_, equity)| *equity).unwrap_or(self.initial_capital);
+        let returns: Vec<f64> = equity_curve.windows(2)
+            .filter(|w| w[0].1 > 0.0)
+            .map(|w| w[1].1 / w[0].1 - 1.0)
+            .collect();
+        let mean = returns.iter().sum::<f64>() / returns.len().max(1) as f64;
+        let std_dev = (returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / returns.len().max(1) as f64).sqrt();
+        let bars_per_year = 365.25 * 86_400.0 / bar_seconds.max(1.0);
+
+        let mut peak = self.initial_capital;
+        let mut max_drawdown: f64 = 0.0;
+        for (_, equity) in equity_curve {
+            peak = peak.max(*equity);
+            if peak > 0.0 {
+                max_drawdown = max_drawdown.max((peak - equity) / peak);
+            }
+        }
+
+        ValidationResults {
+            total_return: final_equity / self.initial_capital - 1.0,
+            sharpe_ratio: if std_dev > 0.0 { mean / std_dev * bars_per_year.sqrt() } else { 0.0 },
+            max_drawdown,
+            symmetry_score: cycle_confidences.iter().sum::<f64>() / cycle_confidences.len().max(1) as f64,
+            pattern_consistency: account.winning_exits as f64 / account.exits.max(1) as f64,
+        }
+    }
 }
 
-pub fn load_strategy_config(path: &PathBuf) -> Result<StrategyConfig> {
-    // Placeholder strategy loading
-    Ok(StrategyConfig {
-        name: "TimeSymmetricStrategy".to_string(),
-        parameters: std::collections::HashMap::new(),
-    })
+/// Median spacing between bars in seconds (one day when unknown)
+pub(crate) fn median_spacing_seconds(data: &[ForexDataPoint]) -> f64 {
+    let mut gaps: Vec<i64> = data.windows(2)
+        .map(|w| (w[1].timestamp - w[0].timestamp).num_seconds())
+        .filter(|gap| *gap > 0)
+        .collect();
+    if gaps.is_empty() {
+        return 86_400.0;
+    }
+    gaps.sort_unstable();
+    gaps[gaps.len() / 2] as f64
+}
+
+/// Load a strategy configuration from TOML, or JSON when the file ends in `.json`
+pub fn load_strategy_config(path: &Path) -> Result<StrategyConfig> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("cannot read strategy config {}: {}", path.display(), e))?;
+    if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json")) {
+        Ok(serde_json::from_str(&text)?)
+    } else {
+        Ok(toml::from_str(&text)?)
+    }
 }
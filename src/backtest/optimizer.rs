@@ -0,0 +1,536 @@
+//! # Walk-Forward Parameter Optimization
+//!
+//! Tunes strategy parameters on each in-sample window, by grid search or
+//! Bayesian optimization, and runs the chosen set alone over the out-of-sample
+//! window that follows.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use nalgebra::{DMatrix, DVector};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::f64::consts::PI;
+use std::fmt;
+use std::path::Path;
+
+use super::strategy::StrategyRegistry;
+use super::{BacktestEngine, StrategyConfig, ValidationResults};
+use crate::anomaly::DetectedAnomaly;
+use crate::data::ForexDataPoint;
+
+/// Length scale of the surrogate's squared-exponential kernel, in units of each
+/// parameter's range
+const LENGTH_SCALE: f64 = 0.25;
+
+/// Observation noise of the surrogate, relative to the variance of the scores
+const NOISE: f64 = 1e-4;
+
+/// How candidates are chosen on each in-sample window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OptimizationMethod {
+    /// Backtest every candidate
+    #[default]
+    Grid,
+    /// Backtest `evaluations` candidates picked by expected improvement
+    Bayesian,
+}
+
+impl fmt::Display for OptimizationMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OptimizationMethod::Grid => f.pad("grid"),
+            OptimizationMethod::Bayesian => f.pad("bayesian"),
+        }
+    }
+}
+
+/// What a parameter set is judged by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OptimizationObjective {
+    #[default]
+    Sharpe,
+    TotalReturn,
+    /// Total return over maximum drawdown, the drawdown floored at 0.1%
+    ReturnOverDrawdown,
+}
+
+impl OptimizationObjective {
+    pub fn score(&self, results: &ValidationResults) -> f64 {
+        match self {
+            OptimizationObjective::Sharpe => results.sharpe_ratio,
+            OptimizationObjective::TotalReturn => results.total_return,
+            OptimizationObjective::ReturnOverDrawdown => results.total_return / results.max_drawdown.max(0.001),
+        }
+    }
+}
+
+impl fmt::Display for OptimizationObjective {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OptimizationObjective::Sharpe => f.pad("Sharpe ratio"),
+            OptimizationObjective::TotalReturn => f.pad("total return"),
+            OptimizationObjective::ReturnOverDrawdown => f.pad("return/drawdown"),
+        }
+    }
+}
+
+/// Search space and windows of an optimization
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OptimizerConfig {
+    pub method: OptimizationMethod,
+    pub objective: OptimizationObjective,
+    /// Values tried for each strategy parameter; others keep the strategy config's value
+    pub parameters: BTreeMap<String, Vec<f64>>,
+    /// Bars each candidate is backtested on, warm-up included
+    pub in_sample_bars: usize,
+    /// Bars the chosen candidate trades on after each in-sample window
+    pub out_of_sample_bars: usize,
+    /// Bars between window starts; `out_of_sample_bars` when 0, so the
+    /// out-of-sample stretches tile the history
+    pub step_bars: usize,
+    /// Backtests per window for Bayesian optimization
+    pub evaluations: usize,
+    /// Random candidates Bayesian optimization starts from
+    pub initial_points: usize,
+    /// Seed of the random starting candidates; window `n` uses `seed + n`
+    pub seed: u64,
+}
+
+impl Default for OptimizerConfig {
+    fn default() -> Self {
+        Self {
+            method: OptimizationMethod::Grid,
+            objective: OptimizationObjective::Sharpe,
+            parameters: BTreeMap::new(),
+            in_sample_bars: 500,
+            out_of_sample_bars: 100,
+            step_bars: 0,
+            evaluations: 20,
+            initial_points: 5,
+            seed: 42,
+        }
+    }
+}
+
+impl OptimizerConfig {
+    /// Every combination of the parameter values, in lexicographic order
+    pub fn candidates(&self) -> Vec<BTreeMap<String, f64>> {
+        self.parameters.iter().fold(vec![BTreeMap::new()], |candidates, (name, values)| {
+            candidates.iter()
+                .flat_map(|candidate| values.iter().map(move |value| {
+                    let mut candidate = candidate.clone();
+                    candidate.insert(name.clone(), *value);
+                    candidate
+                }))
+                .collect()
+        })
+    }
+
+    fn step(&self) -> usize {
+        if self.step_bars == 0 { self.out_of_sample_bars } else { self.step_bars }
+    }
+}
+
+/// One in-sample search and the out-of-sample run of its winner
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptimizationWindow {
+    /// From 1
+    pub window: usize,
+    pub in_sample_start: DateTime<Utc>,
+    pub in_sample_end: DateTime<Utc>,
+    pub out_of_sample_start: DateTime<Utc>,
+    pub out_of_sample_end: DateTime<Utc>,
+    /// Chosen values of the optimized parameters
+    pub parameters: BTreeMap<String, f64>,
+    pub in_sample: ValidationResults,
+    pub out_of_sample: ValidationResults,
+    pub in_sample_score: f64,
+    pub out_of_sample_score: f64,
+    /// Candidates backtested in sample
+    pub evaluations: usize,
+}
+
+/// How much one parameter's chosen value moved between windows
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParameterStability {
+    pub name: String,
+    pub mean: f64,
+    pub std_dev: f64,
+    /// Standard deviation over the absolute mean; 0 for a constant choice
+    pub coefficient_of_variation: f64,
+    /// Windows whose value differs from the window before
+    pub changes: usize,
+    /// Value chosen most often, and the share of windows choosing it
+    pub mode: f64,
+    pub mode_share: f64,
+}
+
+/// Every window of an optimization
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptimizationReport {
+    pub strategy: String,
+    pub pair: String,
+    pub method: OptimizationMethod,
+    pub objective: OptimizationObjective,
+    pub candidates: usize,
+    pub windows: Vec<OptimizationWindow>,
+    pub stability: Vec<ParameterStability>,
+}
+
+impl OptimizationReport {
+    pub fn mean_in_sample_score(&self) -> f64 {
+        mean(self.windows.iter().map(|w| w.in_sample_score))
+    }
+
+    pub fn mean_out_of_sample_score(&self) -> f64 {
+        mean(self.windows.iter().map(|w| w.out_of_sample_score))
+    }
+
+    /// Mean out-of-sample over mean in-sample score; near 1 when the chosen
+    /// parameters keep their edge, `None` without a positive in-sample score
+    pub fn walk_forward_efficiency(&self) -> Option<f64> {
+        let in_sample = self.mean_in_sample_score();
+        (in_sample > 0.0).then(|| self.mean_out_of_sample_score() / in_sample)
+    }
+
+    /// Return of trading each window's out-of-sample stretch in turn
+    pub fn out_of_sample_return(&self) -> f64 {
+        self.windows.iter().map(|w| 1.0 + w.out_of_sample.total_return).product::<f64>() - 1.0
+    }
+
+    /// Backtests run across all windows
+    pub fn evaluations(&self) -> usize {
+        self.windows.iter().map(|w| w.evaluations).sum()
+    }
+
+    pub fn stability(&self, name: &str) -> Option<&ParameterStability> {
+        self.stability.iter().find(|s| s.name == name)
+    }
+
+    /// "4 windows, 18 candidates (grid): Sharpe ratio 1.42 in sample, 0.63 out of sample (efficiency 0.44)"
+    pub fn summary(&self) -> String {
+        let efficiency = self.walk_forward_efficiency().map_or("n/a".to_string(), |e| format!("{:.2}", e));
+        format!("{} windows, {} candidates ({}): {} {:.2} in sample, {:.2} out of sample (efficiency {})",
+                self.windows.len(), self.candidates, self.method, self.objective,
+                self.mean_in_sample_score(), self.mean_out_of_sample_score(), efficiency)
+    }
+
+    pub fn print_windows(&self) {
+        println!("\n🧭 Walk-Forward Optimization of {} on {} ({}, {}):", self.strategy, self.pair, self.method, self.objective);
+        println!("╔════════╦════════════╦════════════╦═══════════╦═══════════╦════════════╦════════════════════════════════════════╗");
+        println!("║ Window ║ IS start   ║ OOS start  ║ IS score  ║ OOS score ║ OOS return ║ Parameters                             ║");
+        println!("╠════════╬════════════╬════════════╬═══════════╬═══════════╬════════════╬════════════════════════════════════════╣");
+        for w in &self.windows {
+            let parameters = w.parameters.iter().map(|(name, value)| format!("{}={}", name, value)).collect::<Vec<_>>().join(" ");
+            println!("║ {:6} ║ {} ║ {} ║ {:9.3} ║ {:9.3} ║ {:9.2}% ║ {:38} ║",
+                     w.window, w.in_sample_start.format("%Y-%m-%d"), w.out_of_sample_start.format("%Y-%m-%d"),
+                     w.in_sample_score, w.out_of_sample_score, w.out_of_sample.total_return * 100.0, parameters);
+        }
+        println!("╚════════╩════════════╩════════════╩═══════════╩═══════════╩════════════╩════════════════════════════════════════╝");
+    }
+
+    pub fn print_stability(&self) {
+        println!("\n⚓ Parameter Stability:");
+        println!("╔══════════════════════╦════════════╦════════════╦════════╦═════════╦════════════╦═════════╗");
+        println!("║ Parameter            ║       Mean ║    Std dev ║     CV ║ Changes ║       Mode ║ Share   ║");
+        println!("╠══════════════════════╬════════════╬════════════╬════════╬═════════╬════════════╬═════════╣");
+        for s in &self.stability {
+            println!("║ {:20} ║ {:10.4} ║ {:10.4} ║ {:6.2} ║ {:7} ║ {:10.4} ║ {:6.1}% ║",
+                     s.name, s.mean, s.std_dev, s.coefficient_of_variation, s.changes, s.mode, s.mode_share * 100.0);
+        }
+        println!("╚══════════════════════╩════════════╩════════════╩════════╩═════════╩════════════╩═════════╝");
+    }
+}
+
+/// Walks an engine's strategy forward through in-sample searches and out-of-sample runs
+pub struct WalkForwardOptimizer<'a> {
+    engine: &'a BacktestEngine,
+    registry: StrategyRegistry,
+    config: OptimizerConfig,
+}
+
+impl<'a> WalkForwardOptimizer<'a> {
+    /// Optimize the strategy of `engine`, whose parameters not in the search
+    /// space are kept as they are
+    pub fn new(engine: &'a BacktestEngine, config: OptimizerConfig) -> Result<Self> {
+        if config.parameters.is_empty() || config.parameters.values().any(|values| values.is_empty()) {
+            return Err(anyhow!("Every optimized parameter needs at least one value"));
+        }
+        if config.out_of_sample_bars == 0 {
+            return Err(anyhow!("Out-of-sample windows must be at least one bar"));
+        }
+        if config.in_sample_bars <= engine.backtest_config().warmup_bars {
+            return Err(anyhow!("In-sample windows of {} bars leave nothing to trade after the {} warm-up bars",
+                               config.in_sample_bars, engine.backtest_config().warmup_bars));
+        }
+        Ok(Self { engine, registry: StrategyRegistry::new(), config })
+    }
+
+    /// Create strategies from `registry` instead of the built-in one
+    pub fn with_registry(mut self, registry: StrategyRegistry) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    /// Optimize over `data` for `pair`, trading each window against the anomalies
+    /// that fall inside it
+    pub async fn optimize(
+        &self,
+        pair: &str,
+        data: &[ForexDataPoint],
+        spreads: &[f64],
+        anomalies: &[DetectedAnomaly],
+    ) -> Result<OptimizationReport> {
+        let (in_sample, out_of_sample, step) = (self.config.in_sample_bars, self.config.out_of_sample_bars, self.config.step());
+        if data.len() < in_sample + out_of_sample {
+            return Err(anyhow!("{} bars, need at least {} for one in-sample and one out-of-sample window",
+                               data.len(), in_sample + out_of_sample));
+        }
+        let candidates = self.config.candidates();
+        let warmup = self.engine.backtest_config().warmup_bars;
+        let mut windows = Vec::new();
+        let mut start = 0;
+        while start + in_sample + out_of_sample <= data.len() {
+            let split = start + in_sample;
+            let end = split + out_of_sample;
+            let number = windows.len() + 1;
+            let (parameters, in_sample_results, evaluations) = self.search(pair, data, spreads, anomalies, start..split, &candidates, number).await?;
+            let out_of_sample_results = self.evaluate(&parameters, pair, data, spreads, anomalies, split + 1 - warmup.max(1)..end).await?
+                .ok_or_else(|| anyhow!("Chosen parameters {:?} no longer create a strategy", parameters))?;
+            windows.push(OptimizationWindow {
+                window: number,
+                in_sample_start: data[start].timestamp,
+                in_sample_end: data[split - 1].timestamp,
+                out_of_sample_start: data[split].timestamp,
+                out_of_sample_end: data[end - 1].timestamp,
+                in_sample_score: self.config.objective.score(&in_sample_results),
+                out_of_sample_score: self.config.objective.score(&out_of_sample_results),
+                parameters,
+                in_sample: in_sample_results,
+                out_of_sample: out_of_sample_results,
+                evaluations,
+            });
+            start += step;
+        }
+
+        let stability = self.config.parameters.keys()
+            .map(|name| parameter_stability(name, &windows.iter().map(|w| w.parameters[name]).collect::<Vec<_>>()))
+            .collect();
+        Ok(OptimizationReport {
+            strategy: self.engine.strategy_config().name.clone(),
+            pair: pair.to_string(),
+            method: self.config.method,
+            objective: self.config.objective,
+            candidates: candidates.len(),
+            windows,
+            stability,
+        })
+    }
+
+    /// Best candidate on the bars of `range`, its results and the backtests run
+    #[allow(clippy::too_many_arguments)]
+    async fn search(
+        &self,
+        pair: &str,
+        data: &[ForexDataPoint],
+        spreads: &[f64],
+        anomalies: &[DetectedAnomaly],
+        range: std::ops::Range<usize>,
+        candidates: &[BTreeMap<String, f64>],
+        window: usize,
+    ) -> Result<(BTreeMap<String, f64>, ValidationResults, usize)> {
+        // Score per candidate; None until run, Some(None) when the strategy rejects the values
+        let mut scored: Vec<Option<Option<(f64, ValidationResults)>>> = vec![None; candidates.len()];
+        let mut evaluations = 0;
+        let order: Vec<usize> = match self.config.method {
+            OptimizationMethod::Grid => (0..candidates.len()).collect(),
+            OptimizationMethod::Bayesian => {
+                let mut order: Vec<usize> = (0..candidates.len()).collect();
+                order.shuffle(&mut StdRng::seed_from_u64(self.config.seed.wrapping_add(window as u64)));
+                order.truncate(self.config.initial_points.max(1).min(self.config.evaluations.max(1)));
+                order
+            }
+        };
+        for i in order {
+            scored[i] = Some(self.score(&candidates[i], pair, data, spreads, anomalies, range.clone()).await?);
+            evaluations += 1;
+        }
+        if self.config.method == OptimizationMethod::Bayesian {
+            let points: Vec<Vec<f64>> = candidates.iter().map(|candidate| self.unit_coordinates(candidate)).collect();
+            while evaluations < self.config.evaluations {
+                let Some(next) = next_candidate(&points, &scored) else {
+                    break;
+                };
+                scored[next] = Some(self.score(&candidates[next], pair, data, spreads, anomalies, range.clone()).await?);
+                evaluations += 1;
+            }
+        }
+
+        let (best, results) = scored.into_iter().enumerate()
+            .filter_map(|(i, score)| score.flatten().map(|score| (i, score)))
+            .fold(None, |best: Option<(usize, (f64, ValidationResults))>, (i, score)| match best {
+                Some((_, (best_score, _))) if best_score >= score.0 || score.0.is_nan() => best,
+                _ => Some((i, score)),
+            })
+            .ok_or_else(|| anyhow!("No candidate parameter set creates a {} strategy", self.engine.strategy_config().name))?;
+        Ok((candidates[best].clone(), results.1, evaluations))
+    }
+
+    /// Objective and results of `candidate` on `range`, `None` when the strategy rejects it
+    #[allow(clippy::too_many_arguments)]
+    async fn score(
+        &self,
+        candidate: &BTreeMap<String, f64>,
+        pair: &str,
+        data: &[ForexDataPoint],
+        spreads: &[f64],
+        anomalies: &[DetectedAnomaly],
+        range: std::ops::Range<usize>,
+    ) -> Result<Option<(f64, ValidationResults)>> {
+        Ok(self.evaluate(candidate, pair, data, spreads, anomalies, range).await?
+            .map(|results| (self.config.objective.score(&results), results)))
+    }
+
+    /// Backtest `candidate` on the bars of `range` and the anomalies among them
+    async fn evaluate(
+        &self,
+        candidate: &BTreeMap<String, f64>,
+        pair: &str,
+        data: &[ForexDataPoint],
+        spreads: &[f64],
+        anomalies: &[DetectedAnomaly],
+        range: std::ops::Range<usize>,
+    ) -> Result<Option<ValidationResults>> {
+        let mut strategy_config: StrategyConfig = self.engine.strategy_config().clone();
+        strategy_config.parameters.extend(candidate.iter().map(|(name, value)| (name.clone(), *value)));
+        let Ok(mut strategy) = self.registry.create(&strategy_config) else {
+            return Ok(None);
+        };
+        let bars = &data[range.clone()];
+        let (first, last) = (bars[0].timestamp, bars[bars.len() - 1].timestamp);
+        let anomalies: Vec<DetectedAnomaly> = anomalies.iter()
+            .filter(|a| a.timestamp >= first && a.timestamp <= last)
+            .cloned()
+            .collect();
+        let spreads = &spreads[range.start.min(spreads.len())..range.end.min(spreads.len())];
+        let run = self.engine.run_with_spreads(strategy.as_mut(), pair, bars, spreads, &anomalies).await?;
+        Ok(Some(run.results))
+    }
+
+    /// `candidate` scaled to [0, 1] along each parameter's range of values
+    fn unit_coordinates(&self, candidate: &BTreeMap<String, f64>) -> Vec<f64> {
+        self.config.parameters.iter()
+            .map(|(name, values)| {
+                let (low, high) = values.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(l, h), v| (l.min(*v), h.max(*v)));
+                if high > low { (candidate[name] - low) / (high - low) } else { 0.0 }
+            })
+            .collect()
+    }
+}
+
+/// Load an optimizer configuration from TOML, or JSON when the file ends in `.json`
+pub fn load_optimizer_config(path: &Path) -> Result<OptimizerConfig> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| anyhow!("cannot read optimizer config {}: {}", path.display(), e))?;
+    if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json")) {
+        Ok(serde_json::from_str(&text)?)
+    } else {
+        Ok(toml::from_str(&text)?)
+    }
+}
+
+/// Unrun candidate with the largest expected improvement over the best score,
+/// under a Gaussian process fitted to the scores so far
+fn next_candidate(points: &[Vec<f64>], scored: &[Option<Option<(f64, ValidationResults)>>]) -> Option<usize> {
+    let observed: Vec<(usize, f64)> = scored.iter().enumerate()
+        .filter_map(|(i, score)| score.as_ref().and_then(|s| s.as_ref()).map(|(score, _)| (i, *score)))
+        .filter(|(_, score)| score.is_finite())
+        .collect();
+    let unrun: Vec<usize> = (0..points.len()).filter(|i| scored[*i].is_none()).collect();
+    if unrun.is_empty() {
+        return None;
+    }
+    if observed.is_empty() {
+        return Some(unrun[0]);
+    }
+
+    let n = observed.len();
+    let scores: Vec<f64> = observed.iter().map(|(_, score)| *score).collect();
+    let mean_score = scores.iter().sum::<f64>() / n as f64;
+    let scale = (scores.iter().map(|s| (s - mean_score).powi(2)).sum::<f64>() / n as f64).sqrt().max(1e-12);
+    let y = DVector::from_iterator(n, scores.iter().map(|s| (s - mean_score) / scale));
+    let best = y.max();
+    let kernel = |a: &[f64], b: &[f64]| {
+        let distance: f64 = a.iter().zip(b).map(|(x, y)| (x - y).powi(2)).sum();
+        (-distance / (2.0 * LENGTH_SCALE * LENGTH_SCALE)).exp()
+    };
+    let k = DMatrix::from_fn(n, n, |i, j| kernel(&points[observed[i].0], &points[observed[j].0]) + if i == j { NOISE } else { 0.0 });
+    let Some(cholesky) = k.cholesky() else {
+        return Some(unrun[0]);
+    };
+    let alpha = cholesky.solve(&y);
+
+    unrun.into_iter()
+        .map(|i| {
+            let k_star = DVector::from_iterator(n, observed.iter().map(|(j, _)| kernel(&points[i], &points[*j])));
+            let mu = k_star.dot(&alpha);
+            let variance = (1.0 + NOISE - k_star.dot(&cholesky.solve(&k_star))).max(0.0);
+            (i, expected_improvement(mu, variance.sqrt(), best))
+        })
+        .fold(None, |best: Option<(usize, f64)>, (i, ei)| match best {
+            Some((_, best_ei)) if best_ei >= ei => best,
+            _ => Some((i, ei)),
+        })
+        .map(|(i, _)| i)
+}
+
+/// Expected improvement over `best` of a normal score with mean `mu` and deviation `sigma`
+fn expected_improvement(mu: f64, sigma: f64, best: f64) -> f64 {
+    if sigma <= 1e-12 {
+        return (mu - best).max(0.0);
+    }
+    let z = (mu - best) / sigma;
+    let pdf = (-0.5 * z * z).exp() / (2.0 * PI).sqrt();
+    let cdf = 0.5 * (1.0 + erf(z / 2.0_f64.sqrt()));
+    (mu - best) * cdf + sigma * pdf
+}
+
+/// Error function (Abramowitz and Stegun 7.1.26, error below 1.5e-7)
+fn erf(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.3275911 * x.abs());
+    let poly = t * (0.254829592 + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    (1.0 - poly * (-x * x).exp()).copysign(x)
+}
+
+fn parameter_stability(name: &str, values: &[f64]) -> ParameterStability {
+    let mean_value = mean(values.iter().copied());
+    let std_dev = (values.iter().map(|v| (v - mean_value).powi(2)).sum::<f64>() / values.len().max(1) as f64).sqrt();
+    let mut counts: Vec<(f64, usize)> = Vec::new();
+    for value in values {
+        match counts.iter_mut().find(|(v, _)| v == value) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((*value, 1)),
+        }
+    }
+    let (mode, count) = counts.into_iter().fold((f64::NAN, 0), |best, (value, count)| if count > best.1 { (value, count) } else { best });
+    ParameterStability {
+        name: name.to_string(),
+        mean: mean_value,
+        std_dev,
+        coefficient_of_variation: if std_dev == 0.0 { 0.0 } else { std_dev / mean_value.abs().max(1e-12) },
+        changes: values.windows(2).filter(|w| w[0] != w[1]).count(),
+        mode,
+        mode_share: count as f64 / values.len().max(1) as f64,
+    }
+}
+
+fn mean(values: impl Iterator<Item = f64>) -> f64 {
+    let (sum, count) = values.fold((0.0, 0usize), |(sum, count), v| (sum + v, count + 1));
+    if count == 0 { 0.0 } else { sum / count as f64 }
+}
@@ -0,0 +1,153 @@
+//! # Offline RL Training
+//!
+//! Trains the Laplacian Q-learning agent on history: each episode replays bars
+//! and anomalies through the backtester with an [`RlAgentStrategy`] whose
+//! realized P&L is the reward, checkpointing the agent between episodes.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use super::strategy::{RlAgentStrategy, Strategy};
+use super::{BacktestEngine, StrategyConfig};
+use crate::anomaly::DetectedAnomaly;
+use crate::data::ForexDataPoint;
+use crate::laplacian_rl::{LaplacianQLearningAgent, QTableSnapshot};
+
+/// How long and how hard to train
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RlTrainingConfig {
+    pub episodes: usize,
+    /// Batches replayed from the buffer after each episode
+    pub replay_batches: usize,
+    /// Episode `n` is run with the agent reseeded to `seed + n`, so a run
+    /// replays identically; random when absent
+    pub seed: Option<u64>,
+}
+
+impl Default for RlTrainingConfig {
+    fn default() -> Self {
+        Self { episodes: 20, replay_batches: 32, seed: None }
+    }
+}
+
+/// One pass over the history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EpisodeSummary {
+    /// From 1
+    pub episode: usize,
+    /// Sum of the rewards the agent learned from, in percent of equity
+    pub total_reward: f64,
+    pub mean_reward: f64,
+    /// Rewarded decisions
+    pub rewards: usize,
+    pub fills: usize,
+    pub total_return: f64,
+    pub max_drawdown: f64,
+    /// Exploration rate after the episode's replay
+    pub exploration_rate: f64,
+    pub q_values: usize,
+    pub graph_nodes: usize,
+}
+
+/// Every episode of a training run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LearningCurve {
+    pub pair: String,
+    pub bars: usize,
+    pub anomalies: usize,
+    /// Whether training continued from an earlier checkpoint
+    pub resumed: bool,
+    pub checkpoint: Option<PathBuf>,
+    pub episodes: Vec<EpisodeSummary>,
+}
+
+impl LearningCurve {
+    /// Mean total reward of the first and the last `window` episodes
+    pub fn reward_trend(&self, window: usize) -> Option<(f64, f64)> {
+        let window = window.clamp(1, self.episodes.len().max(1));
+        if self.episodes.is_empty() {
+            return None;
+        }
+        let mean = |episodes: &[EpisodeSummary]| episodes.iter().map(|episode| episode.total_reward).sum::<f64>() / episodes.len() as f64;
+        Some((mean(&self.episodes[..window]), mean(&self.episodes[self.episodes.len() - window..])))
+    }
+}
+
+/// Runs training episodes through a backtest engine
+pub struct RlTrainer<'a> {
+    engine: &'a BacktestEngine,
+    config: RlTrainingConfig,
+}
+
+impl<'a> RlTrainer<'a> {
+    /// Train with `engine`, whose strategy parameters (`units_per_size`,
+    /// `learn`) configure the [`RlAgentStrategy`] of every episode
+    pub fn new(engine: &'a BacktestEngine, config: RlTrainingConfig) -> Self {
+        Self { engine, config }
+    }
+
+    /// Run the episodes over `data` and its `anomalies`, starting from the
+    /// agent in `checkpoint` when there is one and from `agent` otherwise, and
+    /// saving to `checkpoint` after every episode. `on_episode` sees each
+    /// episode as it finishes.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn train(
+        &self,
+        mut agent: LaplacianQLearningAgent,
+        pair: &str,
+        data: &[ForexDataPoint],
+        spreads: &[f64],
+        anomalies: &[DetectedAnomaly],
+        checkpoint: Option<&Path>,
+        mut on_episode: impl FnMut(&EpisodeSummary),
+    ) -> Result<(LaplacianQLearningAgent, LearningCurve)> {
+        let saved = checkpoint.map(QTableSnapshot::load).transpose()?.flatten();
+        let resumed = saved.is_some();
+        if let Some(snapshot) = saved {
+            agent.restore_q_table(snapshot);
+        }
+        let strategy_config = StrategyConfig { name: RlAgentStrategy::NAME.to_string(), ..self.engine.strategy_config().clone() };
+        let mut curve = LearningCurve {
+            pair: pair.to_string(),
+            bars: data.len(),
+            anomalies: anomalies.len(),
+            resumed,
+            checkpoint: checkpoint.map(Path::to_path_buf),
+            episodes: Vec::with_capacity(self.config.episodes),
+        };
+
+        for episode in 1..=self.config.episodes {
+            if let Some(seed) = self.config.seed {
+                agent.reseed(seed.wrapping_add(episode as u64));
+            }
+            let mut strategy = RlAgentStrategy::new(&strategy_config)?.with_agent(agent);
+            let run = self.engine.run_with_spreads(&mut strategy as &mut dyn Strategy, pair, data, spreads, anomalies).await?;
+            let rewards = strategy.rewards().to_vec();
+            agent = strategy.into_agent();
+            for _ in 0..self.config.replay_batches {
+                agent.train_batch()?;
+            }
+
+            let total_reward: f64 = rewards.iter().sum();
+            let summary = EpisodeSummary {
+                episode,
+                total_reward,
+                mean_reward: if rewards.is_empty() { 0.0 } else { total_reward / rewards.len() as f64 },
+                rewards: rewards.len(),
+                fills: run.trades.len(),
+                total_return: run.results.total_return,
+                max_drawdown: run.results.max_drawdown,
+                exploration_rate: agent.exploration_rate(),
+                q_values: agent.q_backend().size(),
+                graph_nodes: agent.graph().len(),
+            };
+            if let Some(path) = checkpoint {
+                agent.q_table_snapshot().save(path)?;
+            }
+            on_episode(&summary);
+            curve.episodes.push(summary);
+        }
+        Ok((agent, curve))
+    }
+}
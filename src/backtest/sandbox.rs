@@ -0,0 +1,244 @@
+//! # Strategy Sandbox
+//!
+//! Runs strategy callbacks on the blocking pool under a time limit with panics
+//! caught, so a slow or crashing strategy cannot stall the live pipeline. A
+//! strategy that keeps failing is disabled and an alert is raised.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+use super::strategy::{Fill, Order, Strategy, StrategyContext};
+use crate::anomaly::DetectedAnomaly;
+use crate::correlation::LeadLagOpportunity;
+use crate::data::ForexDataPoint;
+use crate::patterns::HiddenCycle;
+
+/// Time limits and failure tolerance for sandboxed strategies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SandboxConfig {
+    /// Limit for a single bar, anomaly, cycle or fill callback
+    pub timeout_ms: u64,
+    /// Limit for replaying the history to a newly added strategy
+    pub replay_timeout_ms: u64,
+    /// Consecutive timeouts or panics after which the strategy is disabled
+    pub max_consecutive_failures: u32,
+}
+
+impl Default for SandboxConfig {
+    fn default() -> Self {
+        Self {
+            timeout_ms: 250,
+            replay_timeout_ms: 30_000,
+            max_consecutive_failures: 3,
+        }
+    }
+}
+
+/// Callback to deliver to a sandboxed strategy
+#[derive(Debug, Clone)]
+pub enum StrategyEvent {
+    Bar(ForexDataPoint),
+    Anomaly(Box<DetectedAnomaly>),
+    CycleUpdate(Vec<HiddenCycle>),
+    Fill(Fill),
+    LeadLag(Box<LeadLagOpportunity>),
+    /// Warm a new strategy: `bars` go to `on_bar` with their orders discarded, then
+    /// `cycles` to `on_cycle_update`, whose orders are returned
+    Replay { bars: Vec<ForexDataPoint>, cycles: Vec<HiddenCycle> },
+}
+
+impl StrategyEvent {
+    fn kind(&self) -> &'static str {
+        match self {
+            StrategyEvent::Bar(_) => "bar",
+            StrategyEvent::Anomaly(_) => "anomaly",
+            StrategyEvent::CycleUpdate(_) => "cycle update",
+            StrategyEvent::Fill(_) => "fill",
+            StrategyEvent::LeadLag(_) => "lead-lag",
+            StrategyEvent::Replay { .. } => "replay",
+        }
+    }
+
+    fn dispatch(self, strategy: &mut dyn Strategy, context: &StrategyContext) -> Vec<Order> {
+        match self {
+            StrategyEvent::Bar(bar) => strategy.on_bar(context, &bar),
+            StrategyEvent::Anomaly(anomaly) => strategy.on_anomaly(context, &anomaly),
+            StrategyEvent::CycleUpdate(cycles) => strategy.on_cycle_update(context, &cycles),
+            StrategyEvent::Fill(fill) => strategy.on_fill(context, &fill),
+            StrategyEvent::LeadLag(opportunity) => strategy.on_lead_lag(context, &opportunity),
+            StrategyEvent::Replay { bars, cycles } => {
+                for (index, bar) in bars.iter().enumerate() {
+                    let replay_context = StrategyContext { timestamp: bar.timestamp, bar_index: index, ..context.clone() };
+                    strategy.on_bar(&replay_context, bar);
+                }
+                strategy.on_cycle_update(context, &cycles)
+            }
+        }
+    }
+}
+
+/// Health of a sandboxed strategy
+#[derive(Debug, Clone, Serialize)]
+pub struct SandboxStatus {
+    pub name: String,
+    pub calls: u64,
+    pub timeouts: u64,
+    pub panics: u64,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+    /// Why and when the strategy was disabled
+    pub disabled: Option<(DateTime<Utc>, String)>,
+}
+
+type CallOutcome = (Box<dyn Strategy>, std::thread::Result<Vec<Order>>);
+
+/// A strategy whose callbacks run isolated from the caller
+pub struct StrategySandbox {
+    strategy: Option<Box<dyn Strategy>>,
+    /// Call that overran its limit and still holds the strategy
+    in_flight: Option<JoinHandle<CallOutcome>>,
+    config: SandboxConfig,
+    status: SandboxStatus,
+    alert: Option<String>,
+}
+
+impl StrategySandbox {
+    pub fn new(strategy: Box<dyn Strategy>, config: SandboxConfig) -> Self {
+        let status = SandboxStatus {
+            name: strategy.name().to_string(),
+            calls: 0,
+            timeouts: 0,
+            panics: 0,
+            consecutive_failures: 0,
+            last_error: None,
+            disabled: None,
+        };
+        Self { strategy: Some(strategy), in_flight: None, config, status, alert: None }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.status.name
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.status.disabled.is_none()
+    }
+
+    pub fn status(&self) -> &SandboxStatus {
+        &self.status
+    }
+
+    /// Alert raised when the strategy was disabled, returned once
+    pub fn take_alert(&mut self) -> Option<String> {
+        self.alert.take()
+    }
+
+    /// Re-enable a disabled strategy, e.g. after an operator fixed its inputs
+    pub fn enable(&mut self) {
+        self.status.disabled = None;
+        self.status.consecutive_failures = 0;
+    }
+
+    /// Deliver `event` and return the strategy's orders; a timeout, a panic, a
+    /// disabled strategy or one still busy with an earlier call yields no orders
+    pub async fn call(&mut self, context: &StrategyContext, event: StrategyEvent) -> Vec<Order> {
+        if !self.is_enabled() {
+            return Vec::new();
+        }
+        if !self.recover() {
+            self.record_failure(format!("{} skipped: still busy with an earlier call", event.kind()), false, false);
+            return Vec::new();
+        }
+        let Some(mut strategy) = self.strategy.take() else {
+            return Vec::new();
+        };
+
+        self.status.calls += 1;
+        let kind = event.kind();
+        let limit = match event {
+            StrategyEvent::Replay { .. } => self.config.replay_timeout_ms,
+            _ => self.config.timeout_ms,
+        };
+        let context = context.clone();
+        let mut handle = tokio::task::spawn_blocking(move || {
+            let result = catch_unwind(AssertUnwindSafe(|| event.dispatch(strategy.as_mut(), &context)));
+            (strategy, result)
+        });
+
+        match tokio::time::timeout(Duration::from_millis(limit), &mut handle).await {
+            Ok(Ok((strategy, result))) => {
+                self.strategy = Some(strategy);
+                match result {
+                    Ok(orders) => {
+                        self.status.consecutive_failures = 0;
+                        orders
+                    }
+                    Err(panic) => {
+                        self.record_failure(format!("{} panicked: {}", kind, panic_message(&*panic)), false, true);
+                        Vec::new()
+                    }
+                }
+            }
+            Ok(Err(error)) => {
+                // The task itself failed, taking the strategy with it
+                self.disable(format!("{} task failed: {}", kind, error));
+                Vec::new()
+            }
+            Err(_) => {
+                self.in_flight = Some(handle);
+                self.record_failure(format!("{} exceeded {}ms", kind, limit), true, false);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Take the strategy back from a finished overrunning call; false while it still runs
+    fn recover(&mut self) -> bool {
+        let Some(handle) = &self.in_flight else {
+            return true;
+        };
+        if !handle.is_finished() {
+            return false;
+        }
+        let handle = self.in_flight.take().expect("in-flight call");
+        // The task has finished, so this resolves without blocking
+        match futures_util::FutureExt::now_or_never(handle) {
+            Some(Ok((strategy, _late_orders))) => {
+                self.strategy = Some(strategy);
+                true
+            }
+            _ => {
+                self.disable("overrunning call failed".to_string());
+                false
+            }
+        }
+    }
+
+    fn record_failure(&mut self, error: String, timeout: bool, panic: bool) {
+        self.status.timeouts += timeout as u64;
+        self.status.panics += panic as u64;
+        self.status.consecutive_failures += 1;
+        println!("⚠️  Strategy {}: {}", self.status.name, error);
+        self.status.last_error = Some(error.clone());
+        if self.status.consecutive_failures >= self.config.max_consecutive_failures {
+            self.disable(format!("{} consecutive failures, last: {}", self.status.consecutive_failures, error));
+        }
+    }
+
+    fn disable(&mut self, reason: String) {
+        println!("🚨 Strategy {} disabled: {}", self.status.name, reason);
+        self.alert = Some(format!("strategy {} disabled: {}", self.status.name, reason));
+        self.status.last_error = Some(reason.clone());
+        self.status.disabled = Some((Utc::now(), reason));
+    }
+}
+
+fn panic_message(panic: &(dyn std::any::Any + Send)) -> String {
+    panic.downcast_ref::<&str>().map(|s| s.to_string())
+        .or_else(|| panic.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
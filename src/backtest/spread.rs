@@ -0,0 +1,112 @@
+//! # Spread Strategy
+//!
+//! Pairs trading on a cointegrated spread, replayed by the same engine as the
+//! symmetry strategies. The spread is traded through its dependent leg, with the
+//! hedge ratio refitted every `refit_bars` from bars already seen.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+use super::strategy::{Order, Strategy, StrategyContext};
+use super::StrategyConfig;
+use crate::data::ForexDataPoint;
+use crate::stats::cointegration::{engle_granger, CointegrationConfig, CointegrationResult, SpreadPosition, SpreadTracker};
+
+/// Trades a pair against its cointegrated hedge pair's prices
+pub struct SpreadStrategy {
+    hedge_pair: String,
+    hedge_closes: HashMap<DateTime<Utc>, f64>,
+    position_units: f64,
+    refit_bars: usize,
+    config: CointegrationConfig,
+    tracker: SpreadTracker,
+    fit: Option<CointegrationResult>,
+    /// The last `lookback` bars of the traded pair that the hedge pair also has
+    history: Vec<ForexDataPoint>,
+    hedge_history: Vec<ForexDataPoint>,
+    bars_since_fit: usize,
+}
+
+impl SpreadStrategy {
+    pub const NAME: &'static str = "SpreadStrategy";
+
+    /// Strategy for the pair it is run on, hedged by `hedge_pair` whose bars are
+    /// `hedge_data`. Parameters: `position_units`, `refit_bars`, `lookback`,
+    /// `entry_z`, `exit_z` and `stop_z`.
+    pub fn new(config: &StrategyConfig, hedge_pair: &str, hedge_data: &[ForexDataPoint]) -> Result<Self> {
+        let parameter = |key: &str, default: f64| config.parameters.get(key).copied().unwrap_or(default);
+        let position_units = parameter("position_units", 10_000.0);
+        if position_units <= 0.0 {
+            return Err(anyhow!("position_units must be positive, got {}", position_units));
+        }
+        let defaults = CointegrationConfig::default();
+        let cointegration = CointegrationConfig {
+            lookback: parameter("lookback", defaults.lookback as f64) as usize,
+            entry_z: parameter("entry_z", defaults.entry_z),
+            exit_z: parameter("exit_z", defaults.exit_z),
+            stop_z: parameter("stop_z", defaults.stop_z),
+            ..defaults
+        };
+        cointegration.validate()?;
+        Ok(Self {
+            hedge_pair: hedge_pair.to_uppercase(),
+            hedge_closes: hedge_data.iter().map(|bar| (bar.timestamp, bar.close)).collect(),
+            position_units,
+            refit_bars: parameter("refit_bars", 50.0).max(1.0) as usize,
+            tracker: SpreadTracker::new(cointegration.clone()),
+            config: cointegration,
+            fit: None,
+            history: Vec::new(),
+            hedge_history: Vec::new(),
+            bars_since_fit: 0,
+        })
+    }
+
+    /// The latest hedge-ratio fit
+    pub fn fit(&self) -> Option<&CointegrationResult> {
+        self.fit.as_ref()
+    }
+
+    fn refit(&mut self, pair: &str) {
+        self.fit = engle_granger(pair, &self.history, &self.hedge_pair, &self.hedge_history, &self.config).ok();
+        self.bars_since_fit = 0;
+    }
+}
+
+impl Strategy for SpreadStrategy {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn on_bar(&mut self, context: &StrategyContext, bar: &ForexDataPoint) -> Vec<Order> {
+        let Some(&hedge_close) = self.hedge_closes.get(&bar.timestamp) else {
+            return Vec::new();
+        };
+        self.history.push(bar.clone());
+        self.hedge_history.push(ForexDataPoint { close: hedge_close, ..bar.clone() });
+        if self.history.len() > self.config.lookback {
+            self.history.remove(0);
+            self.hedge_history.remove(0);
+        }
+        self.bars_since_fit += 1;
+        if self.history.len() >= self.config.min_samples && (self.fit.is_none() || self.bars_since_fit >= self.refit_bars) {
+            self.refit(&context.pair);
+        }
+        let Some(fit) = &self.fit else {
+            return Vec::new();
+        };
+        if self.tracker.position() == SpreadPosition::Flat && !context.trading_allowed {
+            return Vec::new();
+        }
+        let Some(signal) = self.tracker.update(fit, bar.timestamp, bar.close, hedge_close) else {
+            return Vec::new();
+        };
+        let target = match signal.to {
+            SpreadPosition::Long => self.position_units,
+            SpreadPosition::Short => -self.position_units,
+            SpreadPosition::Flat => 0.0,
+        };
+        context.orders_to(target, &format!("{} vs {}: {}", context.pair, self.hedge_pair, signal.reason))
+    }
+}
@@ -0,0 +1,650 @@
+//! # Strategy Plug-ins
+//!
+//! Signal logic driven by the backtest engine and by live trading: a strategy
+//! sees bars, anomalies, cycle refreshes and its own fills, and answers with
+//! orders. Strategies are created by name from a [`StrategyRegistry`].
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+use super::StrategyConfig;
+use crate::anomaly::{AnomalyType, DetectedAnomaly};
+use crate::correlation::LeadLagOpportunity;
+use crate::data::timeframe::TimeframeAggregator;
+use crate::data::ForexDataPoint;
+use crate::ids::{CycleId, SymmetryId};
+use crate::laplacian_rl::{Experience, LaplacianQLearningAgent, LaplacianQLearningConfig, TradingAction};
+use crate::patterns::{find_cycle_confluence, HiddenCycle, PatternConfig, PatternRecognizer, TimeframeCycles};
+use crate::signal::{CompositeScoreConfig, CompositeScorer};
+
+/// Order direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrderSide {
+    Buy,
+    Sell,
+}
+
+/// Market order produced by a strategy, filled by the engine at the bar close
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Order {
+    pub side: OrderSide,
+    /// Units of the base currency
+    pub units: f64,
+    pub reason: String,
+    /// Symmetry the signal came from, carried into the trade journal
+    pub symmetry_id: Option<SymmetryId>,
+    /// Cycle the signal came from, carried into the trade journal
+    #[serde(default)]
+    pub cycle_id: Option<CycleId>,
+}
+
+impl Order {
+    pub fn buy(units: f64, reason: &str) -> Self {
+        Self { side: OrderSide::Buy, units, reason: reason.to_string(), symmetry_id: None, cycle_id: None }
+    }
+
+    pub fn sell(units: f64, reason: &str) -> Self {
+        Self { side: OrderSide::Sell, units, reason: reason.to_string(), symmetry_id: None, cycle_id: None }
+    }
+
+    /// Tag the order with the symmetry that triggered it
+    pub fn with_symmetry(mut self, symmetry_id: &SymmetryId) -> Self {
+        self.symmetry_id = Some(symmetry_id.clone());
+        self
+    }
+
+    /// Tag the order with the cycle that triggered it
+    pub fn with_cycle(mut self, cycle_id: &CycleId) -> Self {
+        self.cycle_id = Some(cycle_id.clone());
+        self
+    }
+
+    /// Signed change in position
+    pub fn delta(&self) -> f64 {
+        match self.side {
+            OrderSide::Buy => self.units,
+            OrderSide::Sell => -self.units,
+        }
+    }
+}
+
+/// An executed order, reported back to the strategy that placed it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Fill {
+    pub side: OrderSide,
+    /// Units actually filled, after any thin-market scaling
+    pub units: f64,
+    pub price: f64,
+    /// P&L realized by closing part of the position, before commission; 0 for entries
+    pub realized_pnl: f64,
+    pub commission: f64,
+    /// Signed position after the fill
+    pub position_units: f64,
+    pub reason: String,
+    pub symmetry_id: Option<SymmetryId>,
+    #[serde(default)]
+    pub cycle_id: Option<CycleId>,
+}
+
+/// What a strategy can see about the simulation when it is called
+#[derive(Debug, Clone)]
+pub struct StrategyContext {
+    pub pair: String,
+    pub timestamp: DateTime<Utc>,
+    /// Index of the current bar
+    pub bar_index: usize,
+    /// Median bar spacing, the time unit of [`HiddenCycle`] periods and phases
+    pub bar_seconds: f64,
+    /// Signed position in base-currency units
+    pub position_units: f64,
+    pub equity: f64,
+    /// Whether orders placed now will be filled (trading windows)
+    pub trading_allowed: bool,
+    /// Whether the bar falls in a thin holiday market, where entries may be shrunk or refused
+    pub thin_market: bool,
+}
+
+impl StrategyContext {
+    /// Current time in bars since the Unix epoch
+    pub fn bar_time(&self) -> f64 {
+        self.timestamp.timestamp() as f64 / self.bar_seconds.max(1.0)
+    }
+
+    /// Orders that move the position to `target` units
+    pub fn orders_to(&self, target: f64, reason: &str) -> Vec<Order> {
+        let delta = target - self.position_units;
+        if delta.abs() < f64::EPSILON {
+            Vec::new()
+        } else if delta > 0.0 {
+            vec![Order::buy(delta, reason)]
+        } else {
+            vec![Order::sell(-delta, reason)]
+        }
+    }
+}
+
+/// Signal logic the backtest engine and live trading drive; `Sync` so a pair can
+/// hold one inside the shared multi-currency manager
+pub trait Strategy: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Called once per bar after anomalies and cycle updates for that bar
+    fn on_bar(&mut self, context: &StrategyContext, bar: &ForexDataPoint) -> Vec<Order>;
+
+    /// Called for each anomaly detected at or before the current bar
+    fn on_anomaly(&mut self, _context: &StrategyContext, _anomaly: &DetectedAnomaly) -> Vec<Order> {
+        Vec::new()
+    }
+
+    /// Called whenever the hidden cycles are re-estimated from the bars seen so far
+    fn on_cycle_update(&mut self, _context: &StrategyContext, _cycles: &[HiddenCycle]) -> Vec<Order> {
+        Vec::new()
+    }
+
+    /// Called after each of the strategy's orders is filled; orders returned here
+    /// are filled on the same bar, or once the execution model's latency has passed
+    fn on_fill(&mut self, _context: &StrategyContext, _fill: &Fill) -> Vec<Order> {
+        Vec::new()
+    }
+
+    /// Called when another pair is found to lead this one, each time the
+    /// correlations are refreshed
+    fn on_lead_lag(&mut self, _context: &StrategyContext, _opportunity: &LeadLagOpportunity) -> Vec<Order> {
+        Vec::new()
+    }
+}
+
+type StrategyFactory = Box<dyn Fn(&StrategyConfig) -> Result<Box<dyn Strategy>> + Send + Sync>;
+
+/// Strategies by name
+pub struct StrategyRegistry {
+    factories: HashMap<String, StrategyFactory>,
+}
+
+impl StrategyRegistry {
+    /// Registry without any strategies
+    pub fn empty() -> Self {
+        Self { factories: HashMap::new() }
+    }
+
+    /// Registry with the built-in strategies
+    pub fn new() -> Self {
+        let mut registry = Self::empty();
+        registry.register(TimeSymmetricStrategy::NAME, |config| Ok(Box::new(TimeSymmetricStrategy::new(config)?)));
+        registry.register(CompositeScoreStrategy::NAME, |config| Ok(Box::new(CompositeScoreStrategy::new(config)?)));
+        registry.register(RlAgentStrategy::NAME, |config| Ok(Box::new(RlAgentStrategy::new(config)?)));
+        registry.register(ConfluenceStrategy::NAME, |config| Ok(Box::new(ConfluenceStrategy::new(config)?)));
+        registry
+    }
+
+    /// Add or replace the strategy created for `name`
+    pub fn register<F>(&mut self, name: &str, factory: F)
+    where
+        F: Fn(&StrategyConfig) -> Result<Box<dyn Strategy>> + Send + Sync + 'static,
+    {
+        self.factories.insert(name.to_string(), Box::new(factory));
+    }
+
+    /// Instantiate the strategy named in `config`
+    pub fn create(&self, config: &StrategyConfig) -> Result<Box<dyn Strategy>> {
+        let factory = self.factories.get(&config.name).ok_or_else(|| {
+            anyhow!("unknown strategy '{}' (available: {})", config.name, self.names().join(", "))
+        })?;
+        factory(config)
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.factories.keys().cloned().collect();
+        names.sort();
+        names
+    }
+}
+
+impl Default for StrategyRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Baseline: trade the direction of the combined hidden-cycle projection and stand
+/// aside when an anomaly says the cycles broke down. After a liquidity gap it keeps
+/// or reduces the open position but makes no new entries.
+///
+/// Parameters: `position_units` (10000), `min_confidence` (0.5), `entry_threshold`
+/// (0.0, minimum projected slope relative to price per bar) and `cooldown_bars` (5,
+/// bars to stay flat after a breakdown, or without entries after a liquidity gap).
+pub struct TimeSymmetricStrategy {
+    position_units: f64,
+    min_confidence: f64,
+    entry_threshold: f64,
+    cooldown_bars: usize,
+    cycles: Vec<HiddenCycle>,
+    flat_until: usize,
+    no_entries_until: usize,
+}
+
+impl TimeSymmetricStrategy {
+    pub const NAME: &'static str = "TimeSymmetricStrategy";
+
+    pub fn new(config: &StrategyConfig) -> Result<Self> {
+        let parameter = |key: &str, default: f64| config.parameters.get(key).copied().unwrap_or(default);
+        let position_units = parameter("position_units", 10_000.0);
+        if position_units <= 0.0 {
+            return Err(anyhow!("position_units must be positive, got {}", position_units));
+        }
+        Ok(Self {
+            position_units,
+            min_confidence: parameter("min_confidence", 0.5),
+            entry_threshold: parameter("entry_threshold", 0.0),
+            cooldown_bars: parameter("cooldown_bars", 5.0).max(0.0) as usize,
+            cycles: Vec::new(),
+            flat_until: 0,
+            no_entries_until: 0,
+        })
+    }
+
+    /// Confidence-weighted slope of the cycle projection at bar time `t`, with the
+    /// cycle contributing most to it
+    fn projected_slope(&self, t: f64) -> Option<(f64, &HiddenCycle)> {
+        let mut slope = 0.0;
+        let mut strongest: Option<(f64, &HiddenCycle)> = None;
+        for cycle in self.cycles.iter().filter(|c| c.confidence >= self.min_confidence && c.period > 0) {
+            let omega = 2.0 * PI / cycle.period as f64;
+            let contribution = cycle.confidence * cycle.amplitude * omega * (omega * t + cycle.phase).cos();
+            slope += contribution;
+            if strongest.is_none_or(|(best, _)| contribution.abs() > best.abs()) {
+                strongest = Some((contribution, cycle));
+            }
+        }
+        strongest.map(|(_, cycle)| (slope, cycle))
+    }
+}
+
+impl Strategy for TimeSymmetricStrategy {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn on_bar(&mut self, context: &StrategyContext, _bar: &ForexDataPoint) -> Vec<Order> {
+        if context.bar_index < self.flat_until {
+            return Vec::new();
+        }
+        let Some((slope, cycle)) = self.projected_slope(context.bar_time()) else {
+            return context.orders_to(0.0, "no confident cycles");
+        };
+        let mut target = if slope > self.entry_threshold {
+            self.position_units
+        } else if slope < -self.entry_threshold {
+            -self.position_units
+        } else {
+            0.0
+        };
+        if context.bar_index < self.no_entries_until {
+            let position = context.position_units;
+            if target * position < 0.0 {
+                target = 0.0; // exit, but do not reverse
+            } else if target.abs() > position.abs() {
+                target = position;
+            }
+        }
+        context.orders_to(target, &format!("cycle projection slope {:+.6}", slope))
+            .into_iter()
+            .map(|order| order.with_cycle(&cycle.id))
+            .collect()
+    }
+
+    fn on_anomaly(&mut self, context: &StrategyContext, anomaly: &DetectedAnomaly) -> Vec<Order> {
+        match anomaly.anomaly_type {
+            AnomalyType::SymmetryBreakdown { .. } | AnomalyType::CycleDisruption { .. } | AnomalyType::PatternInversion { .. } => {
+                self.flat_until = context.bar_index + self.cooldown_bars;
+                context.orders_to(0.0, &format!("{} anomaly", anomaly.anomaly_type.name()))
+            }
+            AnomalyType::LiquidityGap { .. } => {
+                self.no_entries_until = context.bar_index + self.cooldown_bars;
+                Vec::new()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn on_cycle_update(&mut self, _context: &StrategyContext, cycles: &[HiddenCycle]) -> Vec<Order> {
+        self.cycles = cycles.to_vec();
+        Vec::new()
+    }
+}
+
+/// Trade the sign of the per-bar composite score: long above `entry_threshold`,
+/// short below its negative, flat once the score falls back inside `exit_threshold`.
+///
+/// Parameters: `position_units` (10000), `entry_threshold` (0.3), `exit_threshold`
+/// (0.1), and optionally any of the score weights (`cycle_weight`, `symmetry_weight`,
+/// `anomaly_weight`, `regime_weight`). The engine supplies no symmetries, so the
+/// symmetry component is absent and the remaining weights are renormalized.
+pub struct CompositeScoreStrategy {
+    position_units: f64,
+    entry_threshold: f64,
+    exit_threshold: f64,
+    scorer: CompositeScorer,
+    history: Vec<ForexDataPoint>,
+    anomalies: Vec<DetectedAnomaly>,
+}
+
+impl CompositeScoreStrategy {
+    pub const NAME: &'static str = "CompositeScoreStrategy";
+
+    /// Bars kept for the regime window and volatility estimate
+    const HISTORY_BARS: usize = 500;
+
+    pub fn new(config: &StrategyConfig) -> Result<Self> {
+        let parameter = |key: &str, default: f64| config.parameters.get(key).copied().unwrap_or(default);
+        let position_units = parameter("position_units", 10_000.0);
+        if position_units <= 0.0 {
+            return Err(anyhow!("position_units must be positive, got {}", position_units));
+        }
+        let entry_threshold = parameter("entry_threshold", 0.3);
+        let exit_threshold = parameter("exit_threshold", 0.1);
+        if !(0.0..=1.0).contains(&exit_threshold) || exit_threshold > entry_threshold || entry_threshold > 1.0 {
+            return Err(anyhow!("need 0 <= exit_threshold <= entry_threshold <= 1, got {} and {}", exit_threshold, entry_threshold));
+        }
+        let defaults = CompositeScoreConfig::default();
+        let score_config = CompositeScoreConfig {
+            cycle_weight: parameter("cycle_weight", defaults.cycle_weight),
+            symmetry_weight: parameter("symmetry_weight", defaults.symmetry_weight),
+            anomaly_weight: parameter("anomaly_weight", defaults.anomaly_weight),
+            regime_weight: parameter("regime_weight", defaults.regime_weight),
+            ..defaults
+        };
+        Ok(Self {
+            position_units,
+            entry_threshold,
+            exit_threshold,
+            scorer: CompositeScorer::new(score_config)?,
+            history: Vec::new(),
+            anomalies: Vec::new(),
+        })
+    }
+}
+
+impl Strategy for CompositeScoreStrategy {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn on_bar(&mut self, context: &StrategyContext, bar: &ForexDataPoint) -> Vec<Order> {
+        self.history.push(bar.clone());
+        if self.history.len() > 2 * Self::HISTORY_BARS {
+            self.history.drain(..self.history.len() - Self::HISTORY_BARS);
+        }
+        let Some(score) = self.scorer.score(&self.history, &self.anomalies) else {
+            return Vec::new();
+        };
+
+        let position = context.position_units;
+        let target = if score.score >= self.entry_threshold {
+            self.position_units
+        } else if score.score <= -self.entry_threshold {
+            -self.position_units
+        } else if score.score.abs() < self.exit_threshold || score.score * position < 0.0 {
+            0.0
+        } else {
+            position
+        };
+        context.orders_to(target, &format!("composite score {:+.3} ({})", score.score, score.regime))
+    }
+
+    fn on_anomaly(&mut self, context: &StrategyContext, anomaly: &DetectedAnomaly) -> Vec<Order> {
+        let horizon = chrono::Duration::seconds((context.bar_seconds * self.scorer.config().anomaly_half_life_bars * 5.0) as i64);
+        self.anomalies.retain(|a| a.timestamp >= context.timestamp - horizon);
+        self.anomalies.push(anomaly.clone());
+        Vec::new()
+    }
+
+    fn on_cycle_update(&mut self, _context: &StrategyContext, cycles: &[HiddenCycle]) -> Vec<Order> {
+        self.scorer.set_cycles(cycles);
+        Vec::new()
+    }
+}
+
+/// The Laplacian Q-learning agent as a strategy: each anomaly is turned into a
+/// state, the agent picks an action, and realized P&L from the resulting position
+/// is fed back as the reward for that decision and kept in the agent's replay buffer.
+///
+/// Parameters: `units_per_size` (1000, units per unit of action size),
+/// `exploration_rate` (agent default), `learn` (1, set 0 to freeze the Q-table) and
+/// `seed` (random when absent; set it for runs that replay identically).
+pub struct RlAgentStrategy {
+    agent: LaplacianQLearningAgent,
+    units_per_size: f64,
+    learn: bool,
+    last_bar: Option<ForexDataPoint>,
+    /// State and action of the decision the open position came from
+    decision: Option<(String, TradingAction)>,
+    /// Rewards fed back to the agent, oldest first
+    rewards: Vec<f64>,
+}
+
+impl RlAgentStrategy {
+    pub const NAME: &'static str = "LaplacianRLStrategy";
+
+    pub fn new(config: &StrategyConfig) -> Result<Self> {
+        let parameter = |key: &str, default: f64| config.parameters.get(key).copied().unwrap_or(default);
+        let units_per_size = parameter("units_per_size", 1_000.0);
+        if units_per_size <= 0.0 {
+            return Err(anyhow!("units_per_size must be positive, got {}", units_per_size));
+        }
+        let defaults = LaplacianQLearningConfig::default();
+        let agent_config = LaplacianQLearningConfig {
+            exploration_rate: parameter("exploration_rate", defaults.exploration_rate),
+            ..defaults
+        };
+        let mut agent = LaplacianQLearningAgent::new(agent_config)?;
+        if let Some(seed) = config.parameters.get("seed") {
+            agent.reseed(*seed as u64);
+        }
+        Ok(Self {
+            agent,
+            units_per_size,
+            learn: parameter("learn", 1.0) != 0.0,
+            last_bar: None,
+            decision: None,
+            rewards: Vec::new(),
+        })
+    }
+
+    /// Decide with `agent` instead of a fresh one, e.g. one trained in earlier
+    /// runs; it keeps its own configuration, Q-values and exploration rate
+    pub fn with_agent(mut self, agent: LaplacianQLearningAgent) -> Self {
+        self.agent = agent;
+        self
+    }
+
+    pub fn agent(&self) -> &LaplacianQLearningAgent {
+        &self.agent
+    }
+
+    /// The agent with everything it learned during the run
+    pub fn into_agent(self) -> LaplacianQLearningAgent {
+        self.agent
+    }
+
+    /// Rewards the agent has learned from, oldest first
+    pub fn rewards(&self) -> &[f64] {
+        &self.rewards
+    }
+}
+
+impl Strategy for RlAgentStrategy {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn on_bar(&mut self, _context: &StrategyContext, bar: &ForexDataPoint) -> Vec<Order> {
+        self.last_bar = Some(bar.clone());
+        Vec::new()
+    }
+
+    fn on_anomaly(&mut self, context: &StrategyContext, anomaly: &DetectedAnomaly) -> Vec<Order> {
+        let Some(bar) = &self.last_bar else {
+            return Vec::new();
+        };
+        let Ok(state) = self.agent.anomaly_to_state(anomaly, bar) else {
+            return Vec::new();
+        };
+        let Ok(action) = self.agent.choose_action(&state, anomaly) else {
+            return Vec::new();
+        };
+        let reason = format!("RL {:?} on {}", action, anomaly.anomaly_type.name());
+        let orders = match action {
+            TradingAction::Buy { size } => vec![Order::buy(size as f64 * self.units_per_size, &reason)],
+            TradingAction::Sell { size } => vec![Order::sell(size as f64 * self.units_per_size, &reason)],
+            TradingAction::ClosePosition => context.orders_to(0.0, &reason),
+            TradingAction::Hold => Vec::new(),
+        };
+        if !orders.is_empty() {
+            self.decision = Some((state, action));
+        }
+        orders
+    }
+
+    fn on_fill(&mut self, context: &StrategyContext, fill: &Fill) -> Vec<Order> {
+        if !self.learn || fill.realized_pnl == 0.0 {
+            return Vec::new();
+        }
+        if let Some((state, action)) = self.decision.clone() {
+            // Reward in percent of equity, so it is comparable across account sizes
+            let reward = (fill.realized_pnl - fill.commission) / context.equity.max(f64::EPSILON) * 100.0;
+            let done = fill.position_units == 0.0;
+            if self.agent.update_q_value(&state, action.clone(), reward, &state, done).is_ok() {
+                self.rewards.push(reward);
+                self.agent.add_experience(Experience {
+                    state: state.clone(),
+                    action,
+                    reward,
+                    next_state: state,
+                    done,
+                    anomaly_context: None,
+                });
+            }
+            if done {
+                self.decision = None;
+            }
+        }
+        Vec::new()
+    }
+}
+
+/// Trade only the hidden cycles that are confirmed on coarser timeframes: on each
+/// cycle refresh the bars seen so far are resampled to `higher_timeframe_factor`
+/// (and optionally `second_timeframe_factor`) times the bar length, cycles are
+/// detected on each, and a cycle counts when its wall-clock period agrees within
+/// `tolerance` on at least one other timeframe. Position follows the slope of the
+/// confirmed cycles' combined projection.
+///
+/// Parameters: `position_units` (10000), `higher_timeframe_factor` (4),
+/// `second_timeframe_factor` (0, off), `tolerance` (0.1) and `min_confidence` (0.5).
+pub struct ConfluenceStrategy {
+    position_units: f64,
+    factors: Vec<u32>,
+    tolerance: f64,
+    min_confidence: f64,
+    recognizer: PatternRecognizer,
+    history: Vec<ForexDataPoint>,
+    confirmed: Vec<HiddenCycle>,
+}
+
+impl ConfluenceStrategy {
+    pub const NAME: &'static str = "CycleConfluenceStrategy";
+
+    /// Bars kept for resampling; enough for two repetitions of a long cycle on the coarser timeframe
+    const HISTORY_BARS: usize = 5_000;
+
+    pub fn new(config: &StrategyConfig) -> Result<Self> {
+        let parameter = |key: &str, default: f64| config.parameters.get(key).copied().unwrap_or(default);
+        let position_units = parameter("position_units", 10_000.0);
+        if position_units <= 0.0 {
+            return Err(anyhow!("position_units must be positive, got {}", position_units));
+        }
+        let factors: Vec<u32> = [parameter("higher_timeframe_factor", 4.0), parameter("second_timeframe_factor", 0.0)]
+            .into_iter()
+            .filter(|factor| *factor != 0.0)
+            .map(|factor| if factor >= 2.0 { Ok(factor.round() as u32) } else { Err(anyhow!("timeframe factors must be at least 2, got {}", factor)) })
+            .collect::<Result<_>>()?;
+        if factors.is_empty() {
+            return Err(anyhow!("higher_timeframe_factor is required"));
+        }
+        Ok(Self {
+            position_units,
+            factors,
+            tolerance: parameter("tolerance", 0.1),
+            min_confidence: parameter("min_confidence", 0.5),
+            recognizer: PatternRecognizer::new(PatternConfig::default())?,
+            history: Vec::new(),
+            confirmed: Vec::new(),
+        })
+    }
+
+    /// Cycles confirmed at the last refresh
+    pub fn confirmed_cycles(&self) -> &[HiddenCycle] {
+        &self.confirmed
+    }
+}
+
+impl Strategy for ConfluenceStrategy {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn on_bar(&mut self, context: &StrategyContext, bar: &ForexDataPoint) -> Vec<Order> {
+        self.history.push(bar.clone());
+        if self.history.len() > 2 * Self::HISTORY_BARS {
+            self.history.drain(..self.history.len() - Self::HISTORY_BARS);
+        }
+
+        let t = context.bar_time();
+        let Some(strongest) = self.confirmed.iter().max_by(|a, b| a.confidence.total_cmp(&b.confidence)) else {
+            return context.orders_to(0.0, "no confirmed cycles");
+        };
+        let slope: f64 = self.confirmed.iter()
+            .map(|cycle| {
+                let omega = 2.0 * PI / cycle.period as f64;
+                cycle.confidence * cycle.amplitude * omega * (omega * t + cycle.phase).cos()
+            })
+            .sum();
+        let target = if slope > 0.0 { self.position_units } else if slope < 0.0 { -self.position_units } else { 0.0 };
+        context.orders_to(target, &format!("confirmed cycle slope {:+.6}", slope))
+            .into_iter()
+            .map(|order| order.with_cycle(&strongest.id))
+            .collect()
+    }
+
+    fn on_cycle_update(&mut self, context: &StrategyContext, cycles: &[HiddenCycle]) -> Vec<Order> {
+        let base = TimeframeCycles {
+            timeframe: "1x".to_string(),
+            bar_seconds: context.bar_seconds,
+            bars: self.history.len(),
+            cycles: cycles.iter().filter(|c| c.confidence >= self.min_confidence && c.period > 0).cloned().collect(),
+        };
+        let mut timeframes = vec![base];
+        for factor in &self.factors {
+            let seconds = (context.bar_seconds * *factor as f64).round() as i64;
+            let Ok(aggregator) = TimeframeAggregator::with_interval(chrono::Duration::seconds(seconds)) else { continue };
+            let Ok(bars) = aggregator.resample(&self.history) else { continue };
+            timeframes.push(TimeframeCycles {
+                timeframe: format!("{}x", factor),
+                bar_seconds: seconds as f64,
+                bars: bars.len(),
+                cycles: self.recognizer.find_cycles(&bars),
+            });
+        }
+
+        let confluence = find_cycle_confluence(&timeframes, self.tolerance);
+        self.confirmed = timeframes[0].cycles.iter()
+            .filter(|cycle| confluence.iter().any(|group| group.members.iter().any(|m| m.timeframe == "1x" && m.cycle == cycle.name)))
+            .cloned()
+            .collect();
+        Vec::new()
+    }
+}
@@ -0,0 +1,173 @@
+//! # Walk-Forward Anomaly Detection
+//!
+//! Anomalies detected the way a live detector would have seen them: before each
+//! block of bars the baselines, symmetries and cycles are refitted from earlier
+//! bars only, optionally against an imported symmetry set or an analysis cache.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::anomaly::{AnomalyDetectionConfig, DetectedAnomaly, TemporalAnomalyDetector};
+use crate::calendar::economic::EconomicCalendar;
+use crate::calendar::HolidayCalendar;
+use crate::core::{EngineConfig, TimeSymmetricEngine};
+use crate::data::ForexDataPoint;
+use crate::embedded_db::AnalysisCache;
+use crate::patterns::{PatternConfig, PatternRecognizer};
+use crate::symmetry::TemporalSymmetry;
+
+/// Where the expectations anomalies are measured against come from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum AnomalyBaseline {
+    /// Refitted from the bars before each block of `baseline_refit_bars`
+    #[default]
+    WalkForward,
+    /// Fitted once, from the warm-up bars
+    WarmUp,
+}
+
+/// One refit of the baseline and the bars it was used for
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaselineRefit {
+    /// Last bar the baseline saw
+    pub fitted_through: DateTime<Utc>,
+    pub baseline_bars: usize,
+    pub symmetries: usize,
+    pub cycles: usize,
+    pub first_evaluated: DateTime<Utc>,
+    pub last_evaluated: DateTime<Utc>,
+}
+
+/// Anomalies and the baselines they were detected against
+#[derive(Debug, Clone, Default)]
+pub struct WalkForwardDetection {
+    pub anomalies: Vec<DetectedAnomaly>,
+    pub refits: Vec<BaselineRefit>,
+}
+
+/// Refits baselines block by block and detects each block against the one before it
+pub struct WalkForwardDetector {
+    engine_config: EngineConfig,
+    pattern_config: PatternConfig,
+    anomaly_config: AnomalyDetectionConfig,
+    refit_bars: usize,
+    /// Most recent bars a baseline is fitted on; all earlier bars when `None`
+    max_baseline_bars: Option<usize>,
+    holiday_calendar: Option<(String, HolidayCalendar)>,
+    economic_calendar: Option<(String, EconomicCalendar)>,
+    /// Expected symmetries of every block; refitted when `None`
+    symmetries: Option<Vec<TemporalSymmetry>>,
+    /// Symmetries and cycles of baselines fitted before
+    analysis_cache: Option<AnalysisCache>,
+}
+
+impl WalkForwardDetector {
+    pub fn new(
+        engine_config: EngineConfig,
+        pattern_config: PatternConfig,
+        anomaly_config: AnomalyDetectionConfig,
+        refit_bars: usize,
+    ) -> Result<Self> {
+        if refit_bars == 0 {
+            return Err(anyhow::anyhow!("Baseline refit interval must be at least one bar"));
+        }
+        Ok(Self { engine_config, pattern_config, anomaly_config, refit_bars, max_baseline_bars: None, holiday_calendar: None, economic_calendar: None, symmetries: None, analysis_cache: None })
+    }
+
+    /// Fit each baseline on at most the latest `bars` bars before its block
+    pub fn with_max_baseline_bars(mut self, bars: usize) -> Self {
+        self.max_baseline_bars = Some(bars);
+        self
+    }
+
+    /// Skip or down-weight bars of `pair` that fall in thin holiday markets
+    pub fn with_holiday_calendar(mut self, pair: &str, holiday_calendar: HolidayCalendar) -> Self {
+        self.holiday_calendar = Some((pair.to_string(), holiday_calendar));
+        self
+    }
+
+    /// Annotate anomalies of `pair` near scheduled releases and discount their price shocks
+    pub fn with_economic_calendar(mut self, pair: &str, economic_calendar: EconomicCalendar) -> Self {
+        self.economic_calendar = Some((pair.to_string(), economic_calendar));
+        self
+    }
+
+    /// Measure every block against `symmetries` instead of refitting them
+    pub fn with_symmetries(mut self, symmetries: Vec<TemporalSymmetry>) -> Self {
+        self.symmetries = Some(symmetries);
+        self
+    }
+
+    /// Reuse the symmetries and cycles of baselines fitted before, and store new ones
+    pub fn with_analysis_cache(mut self, analysis_cache: AnalysisCache) -> Self {
+        self.analysis_cache = Some(analysis_cache);
+        self
+    }
+
+    /// Detect anomalies on `data[start..]`, each block against a baseline from the bars before it
+    pub async fn detect(&self, data: &[ForexDataPoint], start: usize) -> Result<WalkForwardDetection> {
+        if start < 2 || start >= data.len() {
+            return Err(anyhow::anyhow!("Walk-forward detection needs baseline bars before {} and bars from it, got {}", start, data.len()));
+        }
+        let mut engine = TimeSymmetricEngine::new(self.engine_config.clone())?;
+        engine.initialize().await?;
+        let mut recognizer = PatternRecognizer::new(self.pattern_config.clone())?;
+        let mut detection = WalkForwardDetection::default();
+
+        for block_start in (start..data.len()).step_by(self.refit_bars) {
+            let block_end = (block_start + self.refit_bars).min(data.len());
+            let baseline_start = self.max_baseline_bars.map(|bars| block_start.saturating_sub(bars)).unwrap_or(0);
+            let baseline = &data[baseline_start..block_start];
+
+            let cache = self.analysis_cache.as_ref();
+            let symmetries = match &self.symmetries {
+                Some(symmetries) => symmetries.clone(),
+                None => match cache.map(|cache| cache.symmetries(baseline)).transpose()?.flatten() {
+                    Some(symmetries) => symmetries,
+                    None => {
+                        let symmetries = engine.extract_temporal_symmetries(baseline).await?;
+                        if let Some(cache) = cache {
+                            cache.store_symmetries(baseline, &symmetries)?;
+                        }
+                        symmetries
+                    }
+                },
+            };
+            let cycles = match cache.map(|cache| cache.cycles(baseline)).transpose()?.flatten() {
+                Some(cycles) => cycles,
+                None => {
+                    let cycles = recognizer.detect_cycles(baseline).await?;
+                    if let Some(cache) = cache {
+                        cache.store_cycles(baseline, &cycles)?;
+                    }
+                    cycles
+                }
+            };
+            let (symmetry_count, cycle_count) = (symmetries.len(), cycles.len());
+            let mut detector = TemporalAnomalyDetector::new(symmetries, cycles, baseline, self.anomaly_config.clone())?;
+            if let Some((pair, calendar)) = &self.holiday_calendar {
+                detector = detector.with_holiday_calendar(pair, calendar.clone());
+            }
+            if let Some((pair, calendar)) = &self.economic_calendar {
+                detector = detector.with_economic_calendar(pair, calendar.clone());
+            }
+
+            // Earlier bars fill the first detection windows; their anomalies were reported by the previous block
+            let context_start = block_start.saturating_sub(self.anomaly_config.detection_window_size).max(baseline_start);
+            let first = data[block_start].timestamp;
+            let anomalies = detector.detect_anomalies(&data[context_start..block_end]).await?;
+            detection.anomalies.extend(anomalies.into_iter().filter(|anomaly| anomaly.timestamp >= first));
+            detection.refits.push(BaselineRefit {
+                fitted_through: data[block_start - 1].timestamp,
+                baseline_bars: baseline.len(),
+                symmetries: symmetry_count,
+                cycles: cycle_count,
+                first_evaluated: first,
+                last_evaluated: data[block_end - 1].timestamp,
+            });
+        }
+
+        Ok(detection)
+    }
+}
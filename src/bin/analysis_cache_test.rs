@@ -0,0 +1,127 @@
+//! # Analysis Cache Test
+//!
+//! Check cached symmetries and cycles are reused only for the same pair, bars
+//! and configuration, stage by stage and across reopening the database, and
+//! that walk-forward detection reuses an earlier run's baselines
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::f64::consts::PI;
+
+use forex_pattern_reconstruction::anomaly::AnomalyDetectionConfig;
+use forex_pattern_reconstruction::backtest::walk_forward::WalkForwardDetector;
+use forex_pattern_reconstruction::core::{EngineConfig, TimeSymmetricEngine};
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::embedded_db::{AnalysisCache, AnalysisCacheKey, EmbeddedForexDB};
+use forex_pattern_reconstruction::patterns::{PatternConfig, PatternRecognizer};
+
+/// Hourly bars on a 24-bar cycle with noise
+fn bars(seed: u64, count: usize) -> Vec<ForexDataPoint> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    (0..count)
+        .map(|i| {
+            let close = 1.1 * (1.0 + 0.002 * (2.0 * PI * i as f64 / 24.0).sin()) + rng.gen_range(-0.0003..0.0003);
+            ForexDataPoint { timestamp: start + Duration::hours(i as i64), open: close, high: close + 0.0002, low: close - 0.0002, close, volume: None }
+        })
+        .collect()
+}
+
+fn configs() -> (EngineConfig, PatternConfig) {
+    (EngineConfig::default(), PatternConfig::default())
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 Analysis Cache Test");
+    println!("======================");
+    println!();
+
+    let data = bars(3, 400);
+    let db = EmbeddedForexDB::new()?;
+    let cache = AnalysisCache::new(db.clone(), "EURUSD", "H1", &configs())?;
+    let mut engine = TimeSymmetricEngine::new(EngineConfig::default())?;
+    engine.initialize().await?;
+    let symmetries = engine.extract_temporal_symmetries(&data).await?;
+    let cycles = PatternRecognizer::new(PatternConfig::default())?.detect_cycles(&data).await?;
+
+    // Test 1: results come back only for the same key
+    println!("📊 Test 1: Cache keys");
+    ensure!(cache.symmetries(&data)?.is_none() && cache.cycles(&data)?.is_none(), "empty cache returned results");
+    cache.store_symmetries(&data, &symmetries)?;
+    cache.store_cycles(&data, &cycles)?;
+    let cached = cache.symmetries(&data)?.expect("symmetries cached");
+    ensure!(cached.len() == symmetries.len() && cached.iter().zip(&symmetries).all(|(a, b)| a.id == b.id && a.strength == b.strength),
+            "cached symmetries differ");
+    ensure!(cache.cycles(&data)?.map(|c| c.len()) == Some(cycles.len()), "cached cycles differ");
+    let mut edited = data.clone();
+    edited[200].close += 0.0001;
+    ensure!(cache.symmetries(&edited)?.is_none(), "an edited bar hit the cache");
+    ensure!(cache.symmetries(&data[..399])?.is_none(), "a shorter history hit the cache");
+    ensure!(AnalysisCache::new(db.clone(), "GBPUSD", "H1", &configs())?.symmetries(&data)?.is_none(), "another pair hit the cache");
+    ensure!(AnalysisCache::new(db.clone(), "EURUSD", "H4", &configs())?.symmetries(&data)?.is_none(), "another timeframe hit the cache");
+    let other = (EngineConfig { coherence_window: EngineConfig::default().coherence_window + 1, ..EngineConfig::default() }, PatternConfig::default());
+    ensure!(AnalysisCache::new(db.clone(), "EURUSD", "H1", &other)?.symmetries(&data)?.is_none(), "another configuration hit the cache");
+    println!("   ✅ {} symmetries and {} cycles cached; edits, pair, timeframe and configuration changes miss", symmetries.len(), cycles.len());
+
+    // Test 2: stages are checkpointed separately
+    println!("📊 Test 2: Checkpoints");
+    let partial = bars(4, 300);
+    cache.store_symmetries(&partial, &symmetries)?;
+    let key = cache.key(&partial);
+    ensure!(key == AnalysisCacheKey::new("EURUSD", "H1", &partial, &key.config_hash), "key built from its parts");
+    ensure!(cache.symmetries(&partial)?.is_some() && cache.cycles(&partial)?.is_none(), "symmetries checkpointed alone");
+    ensure!(db.get_cached_analysis(&key)?.is_none(), "a half-finished analysis counts as cached");
+    cache.store_cycles(&partial, &[])?;
+    let complete = db.get_cached_analysis(&key)?.expect("both stages stored");
+    ensure!(complete.symmetries.len() == symmetries.len() && complete.cycles.is_empty(), "complete analysis");
+    println!("   ✅ Symmetries survive without cycles; the analysis is complete once both are stored");
+
+    // Test 3: refreshing ignores cached results
+    println!("📊 Test 3: Refresh");
+    let refresh = cache.clone().with_refresh(true);
+    ensure!(refresh.symmetries(&data)?.is_none() && refresh.cycles(&data)?.is_none(), "refresh read the cache");
+    refresh.store_cycles(&data, &[])?;
+    ensure!(cache.cycles(&data)?.map(|c| c.len()) == Some(0), "refreshed results replace cached ones");
+    cache.store_cycles(&data, &cycles)?;
+    println!("   ✅ --no-cache recomputes and overwrites");
+
+    // Test 4: the cache outlives the process
+    println!("📊 Test 4: File-backed cache");
+    let path = std::env::temp_dir().join(format!("analysis-cache-test-{}.db", std::process::id()));
+    {
+        let file_cache = AnalysisCache::new(EmbeddedForexDB::open(&path)?, "EURUSD", "H1", &configs())?;
+        file_cache.store_symmetries(&data, &symmetries)?;
+    }
+    let reopened = AnalysisCache::new(EmbeddedForexDB::open(&path)?, "EURUSD", "H1", &configs())?;
+    let found = reopened.symmetries(&data)?.map(|s| s.len());
+    for suffix in ["", "-wal", "-shm"] {
+        let _ = std::fs::remove_file(format!("{}{}", path.display(), suffix));
+    }
+    ensure!(found == Some(symmetries.len()), "symmetries lost on reopening");
+    println!("   ✅ Reopened database returns {} symmetries", symmetries.len());
+
+    // Test 5: walk-forward refits are checkpointed and reused
+    println!("📊 Test 5: Walk-forward baselines");
+    let walk_db = EmbeddedForexDB::new()?;
+    let walk_cache = AnalysisCache::new(walk_db.clone(), "EURUSD", "H1", &configs())?;
+    let detector = || WalkForwardDetector::new(EngineConfig::default(), PatternConfig::default(), AnomalyDetectionConfig::default(), 100);
+    let first = detector()?.with_analysis_cache(walk_cache.clone()).detect(&data, 200).await?;
+    let baseline = &data[..200];
+    ensure!(walk_cache.cycles(baseline)?.map(|c| c.len()) == Some(first.refits[0].cycles), "first baseline checkpointed");
+    // A doctored checkpoint shows the second run reads instead of refitting
+    walk_cache.store_cycles(baseline, &[])?;
+    let second = detector()?.with_analysis_cache(walk_cache.clone()).detect(&data, 200).await?;
+    ensure!(second.refits[0].cycles == 0 && second.refits[1].cycles == first.refits[1].cycles, "second run refitted its baselines");
+    let uncached = detector()?.detect(&data, 200).await?;
+    ensure!(uncached.refits[0].cycles == first.refits[0].cycles, "the cache changed a fresh run");
+    let removed = walk_db.clear_analysis_cache(Some("EURUSD"))?;
+    ensure!(removed == 2 * first.refits.len(), "{} checkpoints for {} refits", removed, first.refits.len());
+    println!("   ✅ {} refits checkpointed ({} rows) and read back", first.refits.len(), removed);
+
+    println!();
+    println!("🎉 All analysis cache tests passed");
+    Ok(())
+}
@@ -0,0 +1,67 @@
+//! # Analysis Key Test
+//!
+//! Check the two halves of the analyze cache key: the data checksum changes with
+//! any bar field or the bar count, the config hash with any setting, and both are
+//! pinned values so a cache written by one build is found by the next
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, TimeZone, Utc};
+
+use forex_pattern_reconstruction::core::EngineConfig;
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::embedded_db::{config_hash, data_checksum};
+
+type Edit = (&'static str, fn(&mut ForexDataPoint));
+
+fn main() -> Result<()> {
+    println!("🔬 ANALYSIS KEY TEST");
+    println!("====================");
+    println!();
+
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let data: Vec<ForexDataPoint> = (0..50)
+        .map(|i| {
+            let close = 1.1 + 0.0001 * i as f64;
+            ForexDataPoint { timestamp: start + Duration::hours(i), open: close, high: close + 0.0002, low: close - 0.0002, close, volume: Some(100.0) }
+        })
+        .collect();
+
+    // Test 1: the checksum follows every field of every bar
+    println!("📊 Test 1: Data checksum");
+    let checksum = data_checksum(&data);
+    ensure!(checksum == data_checksum(&data.clone()) && checksum.ends_with("-50"), "checksum {} not stable", checksum);
+    let edits: [Edit; 6] = [
+        ("timestamp", |p| p.timestamp += Duration::seconds(1)),
+        ("open", |p| p.open += 1e-9),
+        ("high", |p| p.high += 1e-9),
+        ("low", |p| p.low -= 1e-9),
+        ("close", |p| p.close += 1e-9),
+        ("volume", |p| p.volume = None),
+    ];
+    for (field, edit) in edits {
+        let mut edited = data.clone();
+        edit(&mut edited[25]);
+        ensure!(data_checksum(&edited) != checksum, "editing {} kept the checksum", field);
+    }
+    ensure!(data_checksum(&data[..49]) != checksum, "a shorter series kept the checksum");
+    println!("   ✅ {}", checksum);
+
+    // Test 2: the config hash follows every setting
+    println!("📊 Test 2: Config hash");
+    let config = EngineConfig::default();
+    let hash = config_hash(&config)?;
+    ensure!(hash == config_hash(&EngineConfig::default())?, "hash of equal configs differs");
+    let other = EngineConfig { coherence_window: config.coherence_window + 1, ..EngineConfig::default() };
+    ensure!(config_hash(&other)? != hash, "a changed setting kept the hash");
+    println!("   ✅ {}", hash);
+
+    // Test 3: both halves are plain FNV-1a, the same in every build
+    println!("📊 Test 3: Pinned values");
+    ensure!(data_checksum(&[]) == "cbf29ce484222325-0", "empty series checksum is {}", data_checksum(&[]));
+    ensure!(config_hash(&serde_json::json!({ "window": 24 }))? == "d597cb363c894b29", "config hash changed between builds");
+    println!("   ✅ Keys match the FNV-1a reference values");
+
+    println!();
+    println!("🎉 All analysis key tests passed");
+    Ok(())
+}
@@ -0,0 +1,76 @@
+//! # Analysis Report Test
+//!
+//! Check that analysis and decomposition HTML reports are self-contained pages
+//! with their charts inlined as SVG, tables of every symmetry, cycle and component,
+//! and the validation metrics, and that histories too short to chart still render
+
+use anyhow::{ensure, Result};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use forex_pattern_reconstruction::ids::CycleId;
+use forex_pattern_reconstruction::patterns::{CycleDecomposer, DecompositionConfig, HiddenCycle};
+use forex_pattern_reconstruction::report::analysis::{self, ValidationMetrics};
+use forex_pattern_reconstruction::symmetry::{SymmetryDetector, SymmetryDetectorConfig};
+use forex_pattern_reconstruction::synthetic::fixtures::trending_daily;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 ANALYSIS REPORT TEST");
+    println!("=======================");
+    println!();
+
+    let mut rng = StdRng::seed_from_u64(11);
+    let data = trending_daily(&mut rng, 300);
+    let cycles = vec![
+        HiddenCycle { id: CycleId::new(), name: "20-bar <swing>".to_string(), period: 20, confidence: 0.9, amplitude: 0.009, phase: 0.0, p_value: None, track: None },
+        HiddenCycle { id: CycleId::new(), name: "55-bar drift".to_string(), period: 55, confidence: 0.4, amplitude: 0.002, phase: 1.0, p_value: None, track: None },
+    ];
+    let symmetries = SymmetryDetector::new(SymmetryDetectorConfig::default())?.detect(&data);
+    ensure!(!symmetries.is_empty(), "no symmetries to report");
+
+    // Test 1: analysis report inlines its charts and lists every result
+    println!("📊 Test 1: Analysis report");
+    let html = analysis::render_analysis_html("EURUSD", "1D", &data, &symmetries, &cycles, &[]);
+    ensure!(html.starts_with("<!DOCTYPE html>") && html.trim_end().ends_with("</html>"), "not a full page");
+    ensure!(html.matches("<svg").count() == 3, "{} inline charts", html.matches("<svg").count());
+    ensure!(!html.contains("src=\"") && !html.contains("href=\""), "report links to external files");
+    ensure!(html.contains("20-bar &lt;swing&gt;") && !html.contains("<swing>"), "cycle names are not escaped");
+    ensure!(html.find("20-bar &lt;swing&gt;</td>") < html.find("55-bar drift</td>"), "cycles not ranked by confidence");
+    for symmetry in &symmetries {
+        ensure!(html.contains(&format!("<td>{}</td>", symmetry.name)), "symmetry {} missing", symmetry.name);
+    }
+    let metrics = ValidationMetrics::compute(&symmetries, &cycles);
+    ensure!((metrics.cycle_confidence_avg - 0.65).abs() < 1e-9, "cycle confidence {}", metrics.cycle_confidence_avg);
+    ensure!(html.contains(&format!("<td>Pattern consistency</td><td>{:.3}</td>", metrics.pattern_consistency)), "metrics missing");
+    println!("   ✅ 3 charts, {} symmetries, {} cycles, {} bytes", symmetries.len(), cycles.len(), html.len());
+
+    // Test 2: decomposition report
+    println!("📊 Test 2: Decomposition report");
+    let mut decomposer = CycleDecomposer::new(DecompositionConfig::default())?;
+    let decomposition = decomposer.decompose_cycles(&data, &[10, 20, 50]).await?;
+    let html = analysis::render_decomposition_html("EUR/USD", &decomposition);
+    ensure!(html.matches("<svg").count() == 1, "decomposition chart missing");
+    for period in decomposition.periods() {
+        let component = &decomposition.components[&period];
+        ensure!(html.contains(&format!("<tr><td>{}</td><td>{:.5}</td>", period, component.amplitude)), "no row for the {}-day component", period);
+    }
+    println!("   ✅ {} components tabulated", decomposition.periods().len());
+
+    // Test 3: short histories render without charts, and the report is written to disk
+    println!("📊 Test 3: Short history");
+    let output = std::env::temp_dir().join(format!("analysis-report-test-{}", std::process::id()));
+    let path = analysis::write_analysis_html(&output, "GBPUSD", "1D", &data[..3], &[], &[], &[])?;
+    ensure!(path.ends_with("GBPUSD_1D_report.html"), "{}", path.display());
+    let html = std::fs::read_to_string(&path)?;
+    // Closes alone still chart; cycles and a spectrum need more bars
+    ensure!(html.matches("<svg").count() == 1 && html.matches("Chart unavailable").count() == 2, "short history charts");
+    ensure!(html.contains("No cycles detected") && html.contains("No symmetries detected"), "empty tables not explained");
+    ensure!(ValidationMetrics::compute(&[], &[]).pattern_consistency == 0.0, "empty results should score zero");
+    std::fs::remove_dir_all(&output)?;
+    println!("   ✅ Three bars reported with the closes chart only");
+
+    println!();
+    println!("🎉 All analysis report tests passed");
+    Ok(())
+}
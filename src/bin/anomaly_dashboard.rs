@@ -19,48 +19,60 @@ use ratatui::{
 use std::collections::{VecDeque, HashMap};
 use std::time::{Duration, Instant};
 use std::io;
+use std::sync::Arc;
 use tokio::time::interval;
 use chrono::{DateTime, Utc};
 
 use forex_pattern_reconstruction::{
-    core::{TimeSymmetricEngine, EngineConfig},
-    data::{ForexDataManager, DataConfig},
-    patterns::{PatternRecognizer, PatternConfig},
-    synthetic::{SyntheticDataGenerator, SyntheticForexPoint, SyntheticGenerationConfig},
-    anomaly::{TemporalAnomalyDetector, DetectedAnomaly, AnomalyType, AnomalyDetectionConfig, AnomalySeverity},
-    laplacian_rl::{LaplacianQLearningAgent, TradingAction, LaplacianQLearningConfig},
+    data::{DataConfig, MissingDataPolicy},
+    data::provider::provider_from_env,
+    anomaly::{DetectedAnomaly, AnomalyType, AnomalySeverity},
+    anomaly::suppression::SuppressionList,
+    dashboard::multi_pair::{run_market_update, MultiPairView, PairPanel, PairStatus},
+    laplacian_rl::TradingAction,
+    multi_currency::MultiCurrencyManager,
 };
 
-/// Real-time anomaly trading dashboard
+/// Pairs the dashboard trades
+const PAIRS: [&str; 7] = ["EURUSD", "GBPUSD", "USDJPY", "USDCHF", "AUDUSD", "USDCAD", "NZDUSD"];
+
+/// Trading actions kept per pair
+const MAX_ACTIONS: usize = 500;
+
+/// Real-time anomaly trading dashboard over a shared multi-currency manager
 pub struct AnomalyTradingDashboard {
-    // Core components
-    engine: TimeSymmetricEngine,
-    data_manager: ForexDataManager,
-    pattern_recognizer: PatternRecognizer,
-    synthetic_generator: SyntheticDataGenerator,
-    anomaly_detector: TemporalAnomalyDetector,
-    rl_agent: LaplacianQLearningAgent,
+    // Trading system
+    manager: Arc<MultiCurrencyManager>,
+    view: MultiPairView,
+    /// Where every pair's model is resumed from and saved to on exit
+    model_dir: Option<std::path::PathBuf>,
     
     // UI state
     current_tab: usize,
     should_quit: bool,
     last_update: Instant,
     
-    // Real-time data
-    price_history: VecDeque<(f64, f64)>, // (timestamp, price)
-    anomaly_history: VecDeque<DetectedAnomaly>,
-    trading_actions: VecDeque<(DateTime<Utc>, TradingAction, f64)>, // (time, action, reward)
-    synthetic_data: Vec<SyntheticForexPoint>,
+    // Current pair
+    price_history: Vec<(f64, f64)>, // (bar, price)
+    anomaly_history: Vec<DetectedAnomaly>,
+    trading_actions: VecDeque<(DateTime<Utc>, TradingAction, f64)>, // (time, action, realized)
+    current_position: f64,
+    
+    /// Actions of every pair, newest last
+    pair_actions: HashMap<String, VecDeque<(DateTime<Utc>, TradingAction, f64)>>,
     
-    // Performance metrics
+    // Portfolio metrics, summed over the pairs
     total_trades: u64,
     successful_trades: u64,
     total_reward: f64,
-    current_position: f64,
     portfolio_value: f64,
     anomalies_detected: u64,
     learning_episodes: u64,
     
+    // Operator acknowledgments / suppressions (A / S on the newest anomaly)
+    suppressed_anomalies: u64,
+    operator_notice: Option<String>,
+    
     // System metrics
     processing_time: Duration,
     memory_usage: f64,
@@ -77,115 +89,71 @@ impl AnomalyTradingDashboard {
     pub async fn new() -> Result<Self> {
         println!("🚀 Initializing Anomaly Trading Dashboard...");
         
-        // Initialize core components
-        let engine_config = EngineConfig::default();
-        let engine = TimeSymmetricEngine::new(engine_config)?;
-        
-        let data_config = DataConfig::default();
-        let data_manager = ForexDataManager::new(data_config)?;
-        
-        let pattern_config = PatternConfig::default();
-        let pattern_recognizer = PatternRecognizer::new(pattern_config)?;
-        
-        // Initialize with placeholder data - will be properly initialized later
-        let synthetic_generator = SyntheticDataGenerator::new(
-            Vec::new(), // temporal_symmetries
-            Vec::new(), // hidden_cycles
-            Vec::new(), // historical_data
-            SyntheticGenerationConfig::default()
-        )?;
-
-        let anomaly_detector = TemporalAnomalyDetector::new(
-            Vec::new(), // temporal_symmetries
-            Vec::new(), // expected_cycles
-            &[], // historical_data
-            AnomalyDetectionConfig::default()
-        )?;
-
-        let rl_config = LaplacianQLearningConfig::default();
-        let rl_agent = LaplacianQLearningAgent::new(rl_config)?;
-        
-        // Initialize multi-currency pairs
-        let active_pairs = vec![
-            "EURUSD".to_string(),
-            "GBPUSD".to_string(), 
-            "USDJPY".to_string(),
-            "USDCHF".to_string(),
-            "AUDUSD".to_string(),
-            "USDCAD".to_string(),
-            "NZDUSD".to_string(),
-        ];
+        // Data location and missing-data fallback are configurable from the environment
+        let data_config = DataConfig {
+            data_directory: std::env::var("FOREX_DATA_PATH")
+                .unwrap_or_else(|_| "FOREX DATA/Forex Daily (1980) - 2023/archive(4)/Forex_D1/Major".to_string())
+                .into(),
+            missing_data_policy: match std::env::var("MISSING_DATA_POLICY") {
+                Ok(policy) => policy.parse::<MissingDataPolicy>()?,
+                Err(_) => MissingDataPolicy::default(),
+            },
+            ..DataConfig::default()
+        };
+        let mut manager = MultiCurrencyManager::new()
+            .with_data_config(data_config)
+            .with_suppressions(SuppressionList::from_env()?);
         
-        let mut pair_performance = HashMap::new();
-        for pair in &active_pairs {
-            pair_performance.insert(pair.clone(), 0.0);
+        // Learning survives restarts when MODEL_DIR names a model directory
+        let model_dir = std::env::var("MODEL_DIR").ok().map(std::path::PathBuf::from);
+        if let Some(dir) = &model_dir {
+            manager = manager.with_model_load_dir(dir.clone());
         }
+        if let Some(provider) = provider_from_env()? {
+            println!("📡 Live data provider: {}", provider.name());
+            manager = manager.with_data_provider(provider);
+        }
+        manager.initialize_pairs(&PAIRS.map(String::from)).await?;
+        let manager = Arc::new(manager);
+        let view = MultiPairView::capture(&manager).await;
         
         Ok(Self {
-            engine,
-            data_manager,
-            pattern_recognizer,
-            synthetic_generator,
-            anomaly_detector,
-            rl_agent,
+            manager,
+            view,
+            model_dir,
             current_tab: 0,
             should_quit: false,
             last_update: Instant::now(),
-            price_history: VecDeque::with_capacity(1000),
-            anomaly_history: VecDeque::with_capacity(500),
-            trading_actions: VecDeque::with_capacity(1000),
-            synthetic_data: Vec::new(),
+            price_history: Vec::new(),
+            anomaly_history: Vec::new(),
+            trading_actions: VecDeque::new(),
+            current_position: 0.0,
+            pair_actions: HashMap::new(),
             total_trades: 0,
             successful_trades: 0,
             total_reward: 0.0,
-            current_position: 0.0,
-            portfolio_value: 10000.0, // Starting capital
+            portfolio_value: 0.0,
             anomalies_detected: 0,
             learning_episodes: 0,
+            suppressed_anomalies: 0,
+            operator_notice: None,
             processing_time: Duration::from_millis(0),
             memory_usage: 0.0,
             cpu_usage: 0.0,
-            active_pairs,
-            current_pair: "EURUSD".to_string(),
-            pair_performance,
+            active_pairs: PAIRS.map(String::from).to_vec(),
+            current_pair: PAIRS[0].to_string(),
+            pair_performance: HashMap::new(),
         })
     }
     
-    /// Initialize the dashboard with historical data
+    /// Initialize every pair with its history and start the live feed, if any
     pub async fn initialize(&mut self) -> Result<()> {
         println!("📊 Loading historical data and initializing systems...");
-        
-        // Load historical data for current pair
-        let data_path = std::path::PathBuf::from("FOREX DATA/Forex Daily (1980) - 2023/archive(4)/Forex_D1/Major");
-        let historical_data = self.data_manager.load_data(&data_path, &self.current_pair, "1D").await?;
-        println!("✅ Loaded {} historical data points", historical_data.len());
-        
-        // Initialize engine with historical data
-        self.engine.initialize().await?;
-        
-        // Extract temporal symmetries
-        let symmetries = self.engine.extract_temporal_symmetries(&historical_data).await?;
-        println!("✅ Extracted {} temporal symmetries", symmetries.len());
-        
-        // Detect hidden cycles
-        let cycles = self.pattern_recognizer.detect_cycles(&historical_data).await?;
-        println!("✅ Detected {} hidden cycles", cycles.len());
-        
-        // Generate initial synthetic data
-        let start_date = chrono::Utc::now();
-        self.synthetic_data = self.synthetic_generator.generate_future_data(
-            start_date,
-            &self.current_pair
-        ).await?;
-        println!("✅ Generated {} synthetic data points", self.synthetic_data.len());
-
-        // Note: Anomaly detector is already initialized with cycles
-        println!("✅ Anomaly detector ready");
-        
-        // Initialize price history with recent data
-        for (i, point) in historical_data.iter().rev().take(100).enumerate() {
-            self.price_history.push_back((i as f64, point.close));
+        self.manager.initialize_all_pairs().await?;
+        if self.manager.data_provider.is_some() {
+            self.manager.start_live_feed()?;
         }
+        self.refresh().await;
         
         println!("🎯 Dashboard initialization complete!");
         Ok(())
@@ -213,11 +181,25 @@ impl AnomalyTradingDashboard {
                 // Refresh/reset
                 self.last_update = Instant::now();
             }
+            KeyCode::Char('a') | KeyCode::Char('s') => {
+                // Acknowledge / suppress the newest anomaly's pattern on this pair
+                if let Some(anomaly) = self.anomaly_history.last() {
+                    let (pattern_id, anomaly_type) = (anomaly.pattern_id().to_string(), anomaly.anomaly_type.name());
+                    let suppressions = &self.manager.suppressions;
+                    let rule = if key == KeyCode::Char('a') {
+                        suppressions.acknowledge(Some(&self.current_pair), &pattern_id, anomaly_type, "dashboard")
+                    } else {
+                        suppressions.suppress(Some(&self.current_pair), &pattern_id, anomaly_type, "dashboard", None)
+                    };
+                    self.operator_notice = Some(format!("🔕 {}", rule));
+                }
+            }
             KeyCode::Up => {
                 // Switch to previous currency pair
                 if let Some(current_idx) = self.active_pairs.iter().position(|p| p == &self.current_pair) {
                     let new_idx = if current_idx == 0 { self.active_pairs.len() - 1 } else { current_idx - 1 };
                     self.current_pair = self.active_pairs[new_idx].clone();
+                    self.load_pair();
                 }
             }
             KeyCode::Down => {
@@ -225,6 +207,7 @@ impl AnomalyTradingDashboard {
                 if let Some(current_idx) = self.active_pairs.iter().position(|p| p == &self.current_pair) {
                     let new_idx = (current_idx + 1) % self.active_pairs.len();
                     self.current_pair = self.active_pairs[new_idx].clone();
+                    self.load_pair();
                 }
             }
             _ => {}
@@ -237,13 +220,33 @@ impl AnomalyTradingDashboard {
         self.should_quit
     }
     
-    /// Update dashboard with new data and analysis
+    /// Save every pair's model, if a model directory is configured
+    pub async fn save_checkpoint(&self) -> Result<()> {
+        if let Some(dir) = &self.model_dir {
+            let saved = self.manager.save_models(dir).await?;
+            println!("💾 {} pair models saved to {}", saved, dir.display());
+        }
+        Ok(())
+    }
+    
+    /// Run a market update on every pair and read the results
     pub async fn update(&mut self) -> Result<()> {
         let start_time = Instant::now();
         
-        // Simulate real-time data updates
         if self.last_update.elapsed() > Duration::from_millis(500) {
-            self.simulate_real_time_update().await?;
+            let update = run_market_update(&self.manager).await?;
+            let now = Utc::now();
+            for (symbol, actions) in update.actions {
+                // A pair's realized P&L is split evenly over the actions that produced it
+                let realized = update.realized.get(&symbol).copied().unwrap_or(0.0) / actions.len().max(1) as f64;
+                let history = self.pair_actions.entry(symbol).or_default();
+                history.extend(actions.into_iter().map(|action| (now, action, realized)));
+                while history.len() > MAX_ACTIONS {
+                    history.pop_front();
+                }
+            }
+            self.learning_episodes += 1;
+            self.refresh().await;
             self.last_update = Instant::now();
         }
         
@@ -251,83 +254,39 @@ impl AnomalyTradingDashboard {
         Ok(())
     }
     
-    /// Simulate real-time trading updates
-    async fn simulate_real_time_update(&mut self) -> Result<()> {
-        // Generate new synthetic data point
-        if let Some(last_point) = self.synthetic_data.last() {
-            let timestamp = self.price_history.len() as f64;
-            let new_price = last_point.data_point.close + 
-                (timestamp * 0.1).sin() * 0.001 + 
-                (timestamp * 0.05).cos() * 0.0005;
-            
-            self.price_history.push_back((timestamp, new_price));
-            
-            // Keep only last 200 points
-            if self.price_history.len() > 200 {
-                self.price_history.pop_front();
-            }
-            
-            // Detect anomalies in recent synthetic data
-            let recent_data = self.synthetic_data.iter().rev().take(50).cloned().collect::<Vec<_>>();
-            if recent_data.len() >= 10 {
-                let anomalies = self.anomaly_detector.detect_anomalies(&recent_data).await?;
-                
-                for anomaly in anomalies {
-                    self.anomalies_detected += 1;
-                    self.anomaly_history.push_back(anomaly.clone());
-                    
-                    // Keep only last 100 anomalies
-                    if self.anomaly_history.len() > 100 {
-                        self.anomaly_history.pop_front();
-                    }
-                    
-                    // Generate trading action based on anomaly
-                    let state_id = format!("state_{}", self.learning_episodes);
-                    let action = self.rl_agent.choose_action(&state_id, &anomaly)?;
-                    
-                    // Simulate reward based on action type
-                    let reward = match &action {
-                        TradingAction::Buy { size: _ } => (new_price - last_point.data_point.close) * 100.0,
-                        TradingAction::Sell { size: _ } => (last_point.data_point.close - new_price) * 100.0,
-                        TradingAction::Hold => 0.1,
-                        TradingAction::ClosePosition => 0.5,
-                    };
-                    
-                    self.total_reward += reward;
-                    self.total_trades += 1;
-                    if reward > 0.0 {
-                        self.successful_trades += 1;
-                    }
-                    
-                    // Update portfolio value
-                    self.portfolio_value += reward;
-                    
-                    // Record trading action
-                    self.trading_actions.push_back((Utc::now(), action, reward));
-                    
-                    // Keep only last 500 actions
-                    if self.trading_actions.len() > 500 {
-                        self.trading_actions.pop_front();
-                    }
-                }
-            }
-            
-            self.learning_episodes += 1;
-        }
+    /// Read the pairs and portfolio from the manager
+    async fn refresh(&mut self) {
+        self.view = MultiPairView::capture(&self.manager).await;
+        let portfolio = &self.view.portfolio;
+        self.total_trades = portfolio.total_trades;
+        self.successful_trades = portfolio.successful_trades;
+        self.total_reward = portfolio.total_reward;
+        self.portfolio_value = portfolio.snapshot.equity;
+        self.anomalies_detected = portfolio.anomalies_detected;
+        self.suppressed_anomalies = portfolio.suppressed_anomalies;
+        self.active_pairs = self.view.pairs.iter().map(|pair| pair.symbol.clone()).collect();
+        self.pair_performance = self.view.pairs.iter().map(|pair| (pair.symbol.clone(), pair.performance.win_rate)).collect();
         
         // Update system metrics
         self.memory_usage = 45.2 + (self.learning_episodes as f64 * 0.01) % 20.0;
         self.cpu_usage = 25.0 + (self.learning_episodes as f64 * 0.1).sin().abs() * 30.0;
         
-        // Update pair performance
-        let performance = if self.total_trades > 0 {
-            (self.successful_trades as f64 / self.total_trades as f64) * 100.0
-        } else {
-            0.0
+        self.load_pair();
+    }
+    
+    /// Show the current pair's prices, anomalies, actions and position
+    fn load_pair(&mut self) {
+        let Some(pair) = self.view.pair(&self.current_pair) else {
+            return;
         };
-        self.pair_performance.insert(self.current_pair.clone(), performance);
-        
-        Ok(())
+        self.price_history = pair.close_series(200);
+        self.anomaly_history = pair.recent_anomalies.clone();
+        self.current_position = pair.position_units;
+        self.trading_actions = self.pair_actions.get(&self.current_pair).cloned().unwrap_or_default();
+    }
+    
+    fn current_panel(&self) -> Option<&PairPanel> {
+        self.view.pair(&self.current_pair)
     }
 }
 
@@ -408,6 +367,7 @@ async fn run_dashboard(mut dashboard: AnomalyTradingDashboard) -> Result<()> {
     )?;
     terminal.show_cursor()?;
 
+    dashboard.save_checkpoint().await?;
     println!("🎯 Anomaly Trading Dashboard closed. Revolutionary trading complete!");
 
     Ok(())
@@ -420,7 +380,7 @@ fn render_dashboard(f: &mut Frame, dashboard: &AnomalyTradingDashboard) {
         .constraints([
             Constraint::Length(3),  // Header
             Constraint::Min(0),     // Main content
-            Constraint::Length(3),  // Footer
+            Constraint::Length(5),  // Footer
         ])
         .split(f.area());
 
@@ -479,7 +439,12 @@ fn render_footer(f: &mut Frame, area: Rect, dashboard: &AnomalyTradingDashboard)
     let footer = Paragraph::new(Text::from(vec![
         Line::from(vec![
             Span::styled("Controls: ", Style::default().fg(Color::Yellow)),
-            Span::raw("Tab/1-6: Switch tabs | ↑↓: Change pair | R: Refresh | Q/Esc: Quit"),
+            Span::raw("Tab/1-6: Switch tabs | ↑↓: Change pair | A/S: Ack/Suppress newest anomaly | R: Refresh | Q/Esc: Quit"),
+        ]),
+        Line::from(vec![
+            Span::styled("Operator: ", Style::default().fg(Color::Magenta)),
+            Span::raw(dashboard.operator_notice.clone().unwrap_or_else(|| format!("{} active rule(s), {} anomalies silenced",
+                dashboard.manager.suppressions.active(Utc::now()).len(), dashboard.suppressed_anomalies))),
         ]),
         Line::from(vec![
             Span::styled("Status: ", Style::default().fg(Color::Green)),
@@ -589,7 +554,7 @@ fn render_system_tab(f: &mut Frame, area: Rect, dashboard: &AnomalyTradingDashbo
 
 /// Render price chart with synthetic data overlay
 fn render_price_chart(f: &mut Frame, area: Rect, dashboard: &AnomalyTradingDashboard) {
-    let price_data: Vec<(f64, f64)> = dashboard.price_history.iter().cloned().collect();
+    let price_data = &dashboard.price_history;
 
     if price_data.is_empty() {
         let placeholder = Paragraph::new("📊 Loading price data...")
@@ -608,7 +573,7 @@ fn render_price_chart(f: &mut Frame, area: Rect, dashboard: &AnomalyTradingDashb
             .name("Price")
             .marker(symbols::Marker::Braille)
             .style(Style::default().fg(Color::Cyan))
-            .data(&price_data),
+            .data(price_data),
     ];
 
     let chart = Chart::new(datasets)
@@ -691,6 +656,8 @@ fn render_anomaly_list(f: &mut Frame, area: Rect, dashboard: &AnomalyTradingDash
             AnomalyType::PatternInversion { .. } => "🟢 Pattern Inversion",
             AnomalyType::CorrelationBreakdown { .. } => "🔵 Correlation Breakdown",
             AnomalyType::NovelPattern { .. } => "🟣 Novel Pattern",
+            AnomalyType::MomentumShock { .. } => "🟤 Momentum Shock",
+            AnomalyType::LiquidityGap { .. } => "⚪ Liquidity Gap",
         };
 
         let severity_str = match anomaly.severity {
@@ -723,14 +690,19 @@ fn render_anomaly_metrics(f: &mut Frame, area: Rect, dashboard: &AnomalyTradingD
         ])
         .split(area);
 
-    // Anomaly type distribution (simplified)
-    let anomaly_types = ["Symmetry", "Cycle", "Volatility", "Pattern"];
-    let anomaly_counts = [
-        dashboard.anomalies_detected / 6,
-        dashboard.anomalies_detected / 5,
-        dashboard.anomalies_detected / 4,
-        dashboard.anomalies_detected / 3,
-    ];
+    // Anomaly type distribution over the current pair's recent anomalies
+    let anomaly_types = ["Symmetry", "Cycle", "Volatility", "Other"];
+    let mut anomaly_counts = [0u64; 4];
+    for anomaly in &dashboard.anomaly_history {
+        let slot = match anomaly.anomaly_type {
+            AnomalyType::SymmetryBreakdown { .. } => 0,
+            AnomalyType::CycleDisruption { .. } => 1,
+            AnomalyType::VolatilitySpike { .. } => 2,
+            _ => 3,
+        };
+        anomaly_counts[slot] += 1;
+    }
+    let recent = dashboard.anomaly_history.len().max(1) as f64;
 
     for (i, (anomaly_type, count)) in anomaly_types.iter().zip(anomaly_counts.iter()).enumerate() {
         let gauge = Gauge::default()
@@ -741,7 +713,7 @@ fn render_anomaly_metrics(f: &mut Frame, area: Rect, dashboard: &AnomalyTradingD
                 2 => Color::Green,
                 _ => Color::Blue,
             }))
-            .percent(((*count as f64 / dashboard.anomalies_detected.max(1) as f64) * 100.0) as u16)
+            .percent(((*count as f64 / recent) * 100.0) as u16)
             .label(format!("{}", count));
         f.render_widget(gauge, chunks[i]);
     }
@@ -913,17 +885,18 @@ fn render_performance_charts(f: &mut Frame, area: Rect, dashboard: &AnomalyTradi
 
 /// Render currency pair performance table
 fn render_pair_performance_table(f: &mut Frame, area: Rect, dashboard: &AnomalyTradingDashboard) {
-    let header = Row::new(vec!["Currency Pair", "Performance", "Status"])
+    let header = Row::new(vec!["Currency Pair", "Win Rate", "Status"])
         .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
 
     let rows: Vec<Row> = dashboard.active_pairs.iter().map(|pair| {
         let performance = dashboard.pair_performance.get(pair).unwrap_or(&0.0);
-        let status = if pair == &dashboard.current_pair { "🟢 ACTIVE" } else { "⚪ INACTIVE" };
+        let status = dashboard.view.pair(pair).map_or(PairStatus::Inactive, |panel| panel.status);
+        let marker = if pair == &dashboard.current_pair { "▶ " } else { "  " };
 
         Row::new(vec![
-            Cell::from(pair.as_str()),
+            Cell::from(format!("{}{}", marker, pair)),
             Cell::from(format!("{:.1}%", performance)),
-            Cell::from(status),
+            Cell::from(status.to_string()).style(Style::default().fg(status_color(status))),
         ])
     }).collect();
 
@@ -936,6 +909,15 @@ fn render_pair_performance_table(f: &mut Frame, area: Rect, dashboard: &AnomalyT
     f.render_widget(table, area);
 }
 
+fn status_color(status: PairStatus) -> Color {
+    match status {
+        PairStatus::Trading => Color::Green,
+        PairStatus::Warming => Color::Yellow,
+        PairStatus::Inactive => Color::DarkGray,
+        PairStatus::Paused | PairStatus::DriftPaused | PairStatus::FeedPaused => Color::Red,
+    }
+}
+
 /// Render pair comparison chart
 fn render_pair_comparison_chart(f: &mut Frame, area: Rect, dashboard: &AnomalyTradingDashboard) {
     let pair_data: Vec<(&str, u64)> = dashboard.active_pairs.iter()
@@ -997,7 +979,7 @@ fn render_system_info(f: &mut Frame, area: Rect, dashboard: &AnomalyTradingDashb
         "🔬 ANOMALY-DRIVEN LAPLACIAN RL TRADING SYSTEM\n\
          ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n\
          📊 Active Currency Pairs: {}\n\
-         🎯 Current Pair: {}\n\
+         🎯 Current Pair: {} ({}, {:+.2} units)\n\
          🔍 Total Anomalies Detected: {}\n\
          🧠 Learning Episodes Completed: {}\n\
          💰 Portfolio Value: ${:.2}\n\
@@ -1016,6 +998,8 @@ fn render_system_info(f: &mut Frame, area: Rect, dashboard: &AnomalyTradingDashb
          • Multi-Currency Support ✅",
         dashboard.active_pairs.len(),
         dashboard.current_pair,
+        dashboard.current_panel().map_or(PairStatus::Inactive, |panel| panel.status),
+        dashboard.current_position,
         dashboard.anomalies_detected,
         dashboard.learning_episodes,
         dashboard.portfolio_value,
@@ -15,10 +15,13 @@ use forex_pattern_reconstruction::synthetic::{
     SyntheticDataGenerator, SyntheticGenerationConfig,
 };
 use forex_pattern_reconstruction::anomaly::{
-    TemporalAnomalyDetector, AnomalyDetectionConfig,
+    TemporalAnomalyDetector, AnomalyDetectionConfig, novelty::NoveltyConfig, signal_policy::SignalPolicyConfig,
 };
+use forex_pattern_reconstruction::sessions::SessionConfig;
+use forex_pattern_reconstruction::stats::volatility::VolatilityConfig;
+use forex_pattern_reconstruction::regimes::RegimeConfig;
 use forex_pattern_reconstruction::laplacian_rl::{
-    LaplacianQLearningAgent, LaplacianQLearningConfig, Experience, TradingAction,
+    LaplacianQLearningAgent, LaplacianQLearningConfig, Experience, QTableSnapshot, TradingAction,
 };
 
 /// ASCII Art Banner for Anomaly Trading
@@ -84,6 +87,13 @@ async fn main() -> Result<()> {
                 .help("Q-learning rate")
                 .default_value("0.1")
         )
+        .arg(
+            Arg::new("checkpoint")
+                .short('c')
+                .long("checkpoint")
+                .value_name("FILE")
+                .help("Resume from this agent checkpoint if it exists, and save to it after training")
+        )
         .get_matches();
 
     // Display banner
@@ -97,6 +107,7 @@ async fn main() -> Result<()> {
     let episodes: u32 = matches.get_one::<String>("episodes").unwrap().parse()?;
     let sensitivity: f64 = matches.get_one::<String>("sensitivity").unwrap().parse()?;
     let learning_rate: f64 = matches.get_one::<String>("learning-rate").unwrap().parse()?;
+    let checkpoint = matches.get_one::<String>("checkpoint").map(PathBuf::from);
 
     println!("📊 SYSTEM CONFIGURATION:");
     println!("   Currency Pair: {}", pair);
@@ -137,6 +148,8 @@ async fn main() -> Result<()> {
         cycle_confidence_threshold: 0.6,
         symmetry_strength_threshold: 0.5,
         enable_crisis_simulation: true,
+        seed: None,
+        ..SyntheticGenerationConfig::default()
     };
     
     let synthetic_generator = SyntheticDataGenerator::new(
@@ -157,6 +170,19 @@ async fn main() -> Result<()> {
         symmetry_deviation_weight: 0.4,
         cycle_deviation_weight: 0.3,
         volatility_anomaly_weight: 0.3,
+        target_anomalies_per_day: None,
+        recalibration_interval_days: 7,
+        momentum_lookback_bars: 12,
+        momentum_quantile: 0.99,
+        inversion_correlation: 0.5,
+        novelty: NoveltyConfig::default(),
+        matrix_profile: None,
+        liquidity_range_ratio: 0.3,
+        liquidity_volume_ratio: 0.25,
+        signal_policy: SignalPolicyConfig::default(),
+        sessions: SessionConfig::default(),
+        volatility: VolatilityConfig::default(),
+        regimes: RegimeConfig::default(),
     };
     
     let mut anomaly_detector = TemporalAnomalyDetector::new(
@@ -179,9 +205,15 @@ async fn main() -> Result<()> {
         batch_size: 32,
         pme_grid_size: 64,
         attention_weight: 0.3,
+        ..LaplacianQLearningConfig::default()
     };
     
     let mut rl_agent = LaplacianQLearningAgent::new(rl_config)?;
+    if let Some(snapshot) = checkpoint.as_deref().map(QTableSnapshot::load).transpose()?.flatten() {
+        rl_agent.restore_q_table(snapshot);
+        println!("✅ Resumed from {}: {} Q-values, {} experiences to replay",
+                 checkpoint.as_ref().unwrap().display(), rl_agent.q_backend().size(), rl_agent.replay_buffer().len());
+    }
     println!("✅ Laplacian Q-learning agent ready");
     
     println!();
@@ -347,6 +379,10 @@ async fn main() -> Result<()> {
     
     println!();
     println!("💾 Results saved to: {}", results_file);
+    if let Some(path) = &checkpoint {
+        rl_agent.q_table_snapshot().save(path)?;
+        println!("💾 Agent checkpoint saved to: {}", path.display());
+    }
     println!("🚀 Anomaly-driven Laplacian RL training complete!");
     
     Ok(())
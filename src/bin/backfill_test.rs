@@ -0,0 +1,77 @@
+//! # Backfill Test
+//!
+//! Replay bars missing since the end of a pair's history from the database on
+//! start, and check the pair stays cold, holding trading, until its history is
+//! long and recent enough
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use forex_pattern_reconstruction::embedded_db::EmbeddedForexDB;
+use forex_pattern_reconstruction::multi_currency::{CurrencyPairConfig, CurrencyPairState};
+use forex_pattern_reconstruction::synthetic::fixtures::cyclic_daily;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 BACKFILL TEST");
+    println!("================");
+    println!();
+
+    let now = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+    let mut rng = StdRng::seed_from_u64(5);
+    // Daily bars up to now, then three the database holds for the future
+    let full = cyclic_daily(now - Duration::days(120), 123, &mut rng);
+    let (past, future) = full.split_at(120);
+    let db = EmbeddedForexDB::new()?;
+    db.store_forex_data("EURUSD", &full)?;
+    let mut pair = CurrencyPairState::new(CurrencyPairConfig::default()).await?;
+    let min_bars = pair.backfill_config.min_bars;
+
+    // Test 1: a history that ends 40 days ago is cold
+    println!("📊 Test 1: Stale history");
+    pair.historical_data = past[..80].to_vec();
+    let report = pair.backfill(None, None, now).await?;
+    ensure!(report.replayed_bars == 0 && report.missing_bars == 39 && !report.warm && !pair.warm, "stale history is cold: {:?}", report);
+    println!("   ✅ {} bars missing, trading held", report.missing_bars);
+
+    // Test 2: the database fills the gap up to now and no further
+    println!("📊 Test 2: Database replay");
+    let report = pair.backfill(Some(&db), None, now).await?;
+    ensure!(report.replayed_bars == 40 && report.backfilled_to == Some(now) && report.missing_bars == 0, "replay: {:?}", report);
+    ensure!(report.warm && pair.warm, "a caught-up pair is warm");
+    ensure!(pair.historical_data.len() == past.len() && pair.historical_data.windows(2).all(|w| w[0].timestamp < w[1].timestamp),
+            "history should be the 120 past bars in order");
+    ensure!(!pair.historical_data.iter().any(|bar| future.iter().any(|f| f.timestamp == bar.timestamp)), "future bars replayed");
+    ensure!(pair.backfill(Some(&db), None, now).await?.replayed_bars == 0, "a second backfill replays nothing");
+    println!("   ✅ {} bars replayed, none past now", report.replayed_bars);
+
+    // Test 3: the missing-bar tolerance covers a weekend, not a week
+    println!("📊 Test 3: Tolerance");
+    let tolerance = pair.backfill_config.max_missing_bars;
+    for (days, warm) in [(tolerance + 1, true), (tolerance + 2, false)] {
+        let report = pair.backfill(None, None, now + Duration::days(days)).await?;
+        ensure!(report.warm == warm, "{} days after the last bar warm should be {}: {:?}", days, warm, report);
+    }
+    println!("   ✅ Warm up to {} missing bars", tolerance);
+
+    // Test 4: a short history warms once live bars fill the windows
+    println!("📊 Test 4: Live warm-up");
+    pair.historical_data = past[past.len() - 10..].to_vec();
+    ensure!(!pair.backfill(None, None, now).await?.warm, "{} bars are too few to trade", pair.historical_data.len());
+    let mut live = cyclic_daily(now, min_bars - 10, &mut rng).into_iter();
+    let stale = past[0].clone();
+    while !pair.warm {
+        let Some(bar) = live.next() else { anyhow::bail!("still cold after {} bars", pair.historical_data.len()) };
+        let at = bar.timestamp;
+        pair.append_bar(bar, at);
+        pair.append_bar(stale.clone(), at);
+    }
+    ensure!(pair.historical_data.len() == min_bars, "warmed at {} bars instead of {}", pair.historical_data.len(), min_bars);
+    println!("   ✅ Warm at {} bars; stale live bars ignored", pair.historical_data.len());
+
+    println!();
+    println!("🎉 All backfill tests passed");
+    Ok(())
+}
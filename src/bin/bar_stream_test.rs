@@ -0,0 +1,136 @@
+//! # Bar Stream Test
+//!
+//! Check live bars folded from ticks match resampling the same ticks, reach
+//! every subscriber, close on the clock in quiet markets and drop late ticks
+
+use anyhow::{ensure, Result};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+
+use forex_pattern_reconstruction::data::bars::{BarBuilder, LiveBar};
+use forex_pattern_reconstruction::data::provider::{ChannelProvider, Tick};
+use forex_pattern_reconstruction::data::tick::{resample_ticks, TickDataPoint};
+use forex_pattern_reconstruction::data::timeframe::TimeframeAggregator;
+use forex_pattern_reconstruction::data::RealTimeDataFeed;
+
+fn tick(symbol: &str, timestamp: DateTime<Utc>, bid: f64, spread: f64) -> Tick {
+    Tick { symbol: symbol.to_string(), timestamp, bid, ask: bid + spread }
+}
+
+/// A tick every 20 seconds for `minutes` minutes, wandering around 1.1
+fn ticks(symbol: &str, start: DateTime<Utc>, minutes: i64) -> Vec<Tick> {
+    (0..minutes * 3)
+        .map(|i| {
+            let bid = 1.1 + 0.0001 * ((i * 7) % 11) as f64;
+            let spread = 0.0001 + 0.00002 * (i % 3) as f64;
+            tick(symbol, start + Duration::seconds(20 * i), bid, spread)
+        })
+        .collect()
+}
+
+fn drain(receiver: &mut tokio::sync::broadcast::Receiver<LiveBar>) -> Vec<LiveBar> {
+    std::iter::from_fn(|| receiver.try_recv().ok()).collect()
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 Bar Stream Test");
+    println!("==================");
+    println!();
+
+    let start = Utc.with_ymd_and_hms(2024, 3, 4, 9, 0, 0).unwrap();
+
+    // Test 1: live bars match resampling the ticks afterwards
+    println!("📊 Test 1: Multi-timeframe bars");
+    let mut builder = BarBuilder::new(&["M1", "M5"])?;
+    let stream = ticks("EURUSD", start, 12);
+    let completed: Vec<LiveBar> = stream.iter().flat_map(|tick| builder.push(tick)).collect();
+    let quotes: Vec<TickDataPoint> = stream.iter().map(TickDataPoint::from).collect();
+    for timeframe in ["M1", "M5"] {
+        let live: Vec<&LiveBar> = completed.iter().filter(|bar| bar.timeframe == timeframe).collect();
+        let mut expected = resample_ticks(&quotes, &TimeframeAggregator::new(timeframe)?)?;
+        let in_progress = expected.pop().expect("bars resampled");
+        ensure!(live.len() == expected.len(), "{} {} bars completed, {} expected", live.len(), timeframe, expected.len());
+        for (live, expected) in live.iter().zip(&expected) {
+            let (a, b) = (&live.bar.bar, &expected.bar);
+            ensure!(a.timestamp == b.timestamp && a.open == b.open && a.high == b.high && a.low == b.low && a.close == b.close && a.volume == b.volume,
+                    "{} bar at {} differs", timeframe, a.timestamp);
+            ensure!((live.bar.mean_spread - expected.mean_spread).abs() < 1e-12 && live.bar.max_spread == expected.max_spread
+                    && live.bar.close_spread == expected.close_spread, "{} spreads at {} differ", timeframe, a.timestamp);
+        }
+        let current = builder.current("EURUSD", timeframe).expect("bar in progress");
+        ensure!(current.bar.timestamp == in_progress.bar.timestamp && current.bar.volume == in_progress.bar.volume, "{} bar in progress", timeframe);
+    }
+    ensure!(completed.iter().filter(|bar| bar.timeframe == "M1").all(|bar| bar.bar.bar.volume == Some(3.0)), "three ticks per minute");
+    ensure!(builder.timeframes().collect::<Vec<_>>() == ["M1", "M5"], "timeframes");
+    println!("   ✅ {} M1 and {} M5 bars completed, identical to resampled ticks",
+             completed.iter().filter(|bar| bar.timeframe == "M1").count(), completed.iter().filter(|bar| bar.timeframe == "M5").count());
+
+    // Test 2: every subscriber gets every completed bar, pairs kept apart
+    println!("📊 Test 2: Broadcast");
+    let mut builder = BarBuilder::new(&["M1"])?;
+    let (mut dashboard, mut detector) = (builder.subscribe(), builder.subscribe());
+    let mut stream = ticks("EURUSD", start, 3);
+    stream.extend(ticks("GBPUSD", start, 3).into_iter().map(|mut tick| { tick.bid += 0.17; tick.ask += 0.17; tick }));
+    stream.sort_by_key(|tick| tick.timestamp);
+    let returned: Vec<LiveBar> = stream.iter().flat_map(|tick| builder.push(tick)).collect();
+    let (first, second) = (drain(&mut dashboard), drain(&mut detector));
+    ensure!(returned.len() == 4 && first.len() == 4 && second.len() == 4, "{} returned, {} and {} received", returned.len(), first.len(), second.len());
+    ensure!(first.iter().zip(&returned).all(|(a, b)| a.symbol == b.symbol && a.bar.bar.timestamp == b.bar.bar.timestamp), "broadcast order");
+    ensure!(first.iter().filter(|bar| bar.symbol == "GBPUSD").all(|bar| bar.bar.bar.low > 1.2), "pairs mixed in one bar");
+    println!("   ✅ 2 subscribers each received {} bars of 2 pairs", first.len());
+
+    // Test 3: quiet markets close on the clock; late ticks are dropped
+    println!("📊 Test 3: Clock closes and late ticks");
+    let mut builder = BarBuilder::new(&["M1"])?.with_grace(Duration::seconds(5));
+    let mut receiver = builder.subscribe();
+    builder.push(&tick("EURUSD", start + Duration::seconds(10), 1.1, 0.0001));
+    ensure!(builder.close_due(start + Duration::seconds(64)).is_empty(), "closed within the grace period");
+    builder.push(&tick("EURUSD", start + Duration::seconds(50), 1.1010, 0.0001));
+    let closed = builder.close_due(start + Duration::seconds(65));
+    ensure!(closed.len() == 1 && (closed[0].bar.bar.high - 1.10105).abs() < 1e-12 && closed[0].bar.bar.volume == Some(2.0), "quiet bar closed with its delayed tick");
+    ensure!(drain(&mut receiver).len() == 1, "clock-closed bar broadcast");
+    ensure!(builder.push(&tick("EURUSD", start + Duration::seconds(55), 1.2, 0.0001)).is_empty() && builder.late_ticks() == 1, "late tick accepted");
+    ensure!(builder.current("EURUSD", "M1").is_none(), "late tick reopened a completed bar");
+    builder.push(&tick("EURUSD", start + Duration::seconds(130), 1.1, 0.0001));
+    ensure!(builder.push(&tick("EURUSD", start + Duration::seconds(90), 1.1, 0.0001)).is_empty() && builder.late_ticks() == 2, "tick for a skipped bar accepted");
+    println!("   ✅ Bar closed 5 s after its end; {} late ticks dropped", builder.late_ticks());
+
+    // Test 4: the real-time feed builds bars from its provider
+    println!("📊 Test 4: Real-time feed");
+    let (provider, sender) = ChannelProvider::new("scripted");
+    let feed = RealTimeDataFeed::default().await?;
+    ensure!(feed.subscribe_bars().is_none(), "bars built without timeframes");
+    let mut feed = feed.with_bar_timeframes(&["M1"])?;
+    feed.connect(&provider)?;
+    let mut bars = feed.subscribe_bars().expect("bar timeframes configured");
+    let recent = TimeframeAggregator::new("M1")?.bucket_start(Utc::now() - Duration::minutes(3));
+    for tick in ticks("EURUSD", recent, 2) {
+        sender.send(tick).await?;
+    }
+    let received = feed.poll();
+    let built = drain(&mut bars);
+    ensure!(received.len() == 6 && feed.get_current_data().len() == 6, "{} ticks polled", received.len());
+    ensure!(built.len() == 2 && built.iter().all(|bar| bar.symbol == "EURUSD" && bar.bar.bar.volume.is_some_and(|v| v >= 2.0)),
+            "{} bars broadcast from the feed", built.len());
+    ensure!(feed.bar_builder().is_some_and(|builder| builder.current("EURUSD", "M1").is_none()), "stale bar left open");
+    println!("   ✅ {} ticks polled into {} M1 bars", received.len(), built.len());
+
+    // Test 5: configuration
+    println!("📊 Test 5: Configuration");
+    ensure!(BarBuilder::new::<&str>(&[]).is_err(), "no timeframes accepted");
+    ensure!(BarBuilder::new(&["X7"]).is_err(), "unknown timeframe accepted");
+    let path = std::env::temp_dir().join(format!("bar-stream-test-{}.toml", std::process::id()));
+    std::fs::write(&path, "update_interval_ms = 500\npairs = [\"EURUSD\"]\nbar_timeframes = [\"M1\", \"H1\"]\n")?;
+    let configured = RealTimeDataFeed::from_config(&path).await;
+    std::fs::write(&path, "update_interval_ms = 500\npairs = [\"EURUSD\"]\n")?;
+    let plain = RealTimeDataFeed::from_config(&path).await;
+    let _ = std::fs::remove_file(&path);
+    let configured = configured?;
+    ensure!(configured.bar_builder().is_some_and(|builder| builder.timeframes().collect::<Vec<_>>() == ["M1", "H1"]), "bar timeframes from the feed config");
+    ensure!(plain?.subscribe_bars().is_none(), "bars built without bar_timeframes");
+    println!("   ✅ bar_timeframes in the feed config enable bars");
+
+    println!();
+    println!("🎉 All bar stream tests passed");
+    Ok(())
+}
@@ -0,0 +1,56 @@
+//! # Benchmark Dataset Test
+//!
+//! Check that benchmark datasets are reproducible bar for bar, have the standard
+//! sizes, and that the pipeline benchmark times every stage and recovers the
+//! injected cycles
+
+use anyhow::{ensure, Result};
+
+use forex_pattern_reconstruction::pipeline::bench::run_benchmark;
+use forex_pattern_reconstruction::pipeline::PipelineConfig;
+use forex_pattern_reconstruction::synthetic::benchmark::{benchmark_cycles, generate_benchmark, BenchmarkSize};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 BENCHMARK DATASET TEST");
+    println!("=========================");
+    println!();
+
+    // Test 1: same seed, same bars
+    println!("📊 Test 1: Determinism");
+    let first = generate_benchmark(BenchmarkSize::Small, 42);
+    let again = generate_benchmark(BenchmarkSize::Small, 42);
+    ensure!(serde_json::to_string(&first.bars)? == serde_json::to_string(&again.bars)?, "same seed gave different bars");
+    let other = generate_benchmark(BenchmarkSize::Small, 43);
+    ensure!(first.bars.iter().zip(&other.bars).any(|(a, b)| a.close != b.close), "seed has no effect");
+    ensure!(first.bars.iter().all(|p| p.low <= p.open.min(p.close) && p.high >= p.open.max(p.close) && p.close > 0.0), "inconsistent OHLC");
+    ensure!(first.bars.windows(2).all(|w| (w[1].timestamp - w[0].timestamp).num_days() == 1), "bars are not daily");
+    println!("   ✅ {} identical bars for seed 42", first.bars.len());
+
+    // Test 2: standard sizes
+    println!("📊 Test 2: Sizes");
+    for (label, size, bars) in [("S", BenchmarkSize::Small, 750), ("m", BenchmarkSize::Medium, 3_000), ("large", BenchmarkSize::Large, 12_000)] {
+        ensure!(label.parse::<BenchmarkSize>()? == size && size.bars() == bars, "{} is not {} bars", label, bars);
+    }
+    ensure!("XL".parse::<BenchmarkSize>().is_err(), "XL accepted");
+    ensure!(generate_benchmark(BenchmarkSize::Medium, 1).bars.len() == 3_000, "medium dataset size");
+    println!("   ✅ S/M/L = 750/3000/12000 bars");
+
+    // Test 3: the benchmark times each stage and recovers the injected cycles
+    println!("📊 Test 3: Pipeline benchmark");
+    let run = run_benchmark(&first, &PipelineConfig::default()).await?;
+    let stages: Vec<&str> = run.stages.iter().map(|s| s.stage.as_str()).collect();
+    ensure!(stages == ["symmetry extraction", "cycle detection", "synthetic generation", "anomaly detection"], "{:?}", stages);
+    ensure!(run.stages.iter().all(|s| s.seconds >= 0.0 && s.bars_per_second > 0.0), "bad timings");
+    ensure!(run.recoveries.len() == benchmark_cycles().len(), "{} recoveries", run.recoveries.len());
+    for recovery in &run.recoveries {
+        println!("   {}-bar cycle → {:?}", recovery.injected_period, recovery.detected_period);
+    }
+    ensure!(run.passed(), "injected cycles missed: {:?}", run.recoveries);
+    ensure!(run.synthetic_points > 0, "no synthetic points generated");
+    println!("   ✅ {:.3}s total, every injected cycle recovered", run.total_seconds());
+
+    println!();
+    println!("🎉 All benchmark dataset tests passed");
+    Ok(())
+}
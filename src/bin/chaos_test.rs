@@ -0,0 +1,155 @@
+//! # Chaos Test
+//!
+//! Inject feed drops, database write failures, broker rejections and slow responses
+//! into the live pipeline and check that it degrades gracefully: nothing panics,
+//! circuit breakers trip, and portfolio and stored state stay consistent
+
+use anyhow::{ensure, Result};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use forex_pattern_reconstruction::data::health::FeedHealthConfig;
+use forex_pattern_reconstruction::data::provider::{ChannelProvider, DataProvider, Tick};
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::embedded_db::EmbeddedForexDB;
+use forex_pattern_reconstruction::laplacian_rl::TradingAction;
+use forex_pattern_reconstruction::multi_currency::MultiCurrencyManager;
+use forex_pattern_reconstruction::resilience::chaos::{ChaosConfig, ChaosProvider, FaultInjector};
+use forex_pattern_reconstruction::resilience::{BreakerState, CircuitBreaker, CircuitBreakerConfig};
+use forex_pattern_reconstruction::trading_windows::TradingWindowsConfig;
+
+fn injector(config: ChaosConfig) -> Result<Arc<FaultInjector>> {
+    Ok(Arc::new(FaultInjector::new(ChaosConfig { enabled: true, seed: Some(11), ..config })?))
+}
+
+fn bar(timestamp: DateTime<Utc>) -> ForexDataPoint {
+    ForexDataPoint { timestamp, open: 1.1, high: 1.1010, low: 1.0990, close: 1.1005, volume: None }
+}
+
+/// EURUSD-only manager with a price
+async fn priced_manager(manager: MultiCurrencyManager) -> Result<MultiCurrencyManager> {
+    let mut manager = manager;
+    manager.initialize_major_pairs().await?;
+    manager.active_pairs.retain(|pair| pair == "EURUSD");
+    {
+        let mut pairs = manager.pairs.write().await;
+        pairs.retain(|pair, _| pair == "EURUSD");
+        let state = pairs.get_mut("EURUSD").expect("pair initialized");
+        state.historical_data = vec![bar(Utc::now() - Duration::hours(1))];
+    }
+    Ok(manager)
+}
+
+fn buys(count: usize) -> HashMap<String, Vec<TradingAction>> {
+    HashMap::from([("EURUSD".to_string(), vec![TradingAction::Buy { size: 1 }; count])])
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 CHAOS TEST");
+    println!("=============");
+    println!();
+
+    // Test 1: breaker state machine
+    println!("📊 Test 1: Circuit breaker");
+    let breaker = CircuitBreaker::new("test", CircuitBreakerConfig { failure_threshold: 3, cooldown_ms: 1000, call_timeout_ms: 100 });
+    let now = Utc::now();
+    ensure!(!breaker.record_failure("a", now) && !breaker.record_failure("b", now), "tripped too early");
+    ensure!(breaker.record_failure("c", now), "third failure should trip");
+    ensure!(!breaker.allow(now), "open circuit let a call through");
+    ensure!(breaker.allow(now + Duration::seconds(2)), "cooldown over, trial call expected");
+    ensure!(breaker.status().state == BreakerState::HalfOpen, "expected half-open");
+    ensure!(breaker.record_failure("d", now + Duration::seconds(2)), "failed trial should re-open");
+    ensure!(breaker.allow(now + Duration::seconds(4)), "second trial call expected");
+    breaker.record_success();
+    let status = breaker.status();
+    ensure!(status.state == BreakerState::Closed && status.trips == 2 && status.rejected == 1, "unexpected status {:?}", status);
+    println!("✅ Closed → open → half-open → open → half-open → closed");
+
+    // Test 2: a broker that rejects everything trips the breaker and leaves the portfolio untouched
+    println!("📊 Test 2: Broker rejections");
+    let chaos = injector(ChaosConfig { broker_rejection_rate: 1.0, ..ChaosConfig::default() })?;
+    let manager = priced_manager(MultiCurrencyManager::new().with_trading_windows(TradingWindowsConfig::unrestricted()).with_fault_injector(Arc::clone(&chaos))).await?;
+    let balance_before = manager.portfolio_snapshot().await.balance;
+    manager.execute_actions(&buys(10)).await;
+    let status = manager.broker_breaker.status();
+    let snapshot = manager.portfolio_snapshot().await;
+    ensure!(matches!(status.state, BreakerState::Open { .. }), "broker breaker not open: {:?}", status.state);
+    ensure!(chaos.counts().broker_rejections == 5 && status.rejected == 5, "expected 5 sent and 5 held back, got {:?} / {}", chaos.counts(), status.rejected);
+    ensure!(snapshot.positions.is_empty() && snapshot.balance == balance_before, "rejected orders changed the portfolio");
+    println!("✅ Breaker opened after 5 rejections, 5 orders held back, portfolio unchanged");
+
+    // Test 3: slow broker responses time out
+    println!("📊 Test 3: Delayed responses");
+    let chaos = injector(ChaosConfig { delay_rate: 1.0, delay_ms: 200, ..ChaosConfig::default() })?;
+    let manager = priced_manager(MultiCurrencyManager::new().with_trading_windows(TradingWindowsConfig::unrestricted())
+        .with_fault_injector(Arc::clone(&chaos))
+        .with_circuit_breaker_config(CircuitBreakerConfig { failure_threshold: 2, cooldown_ms: 60_000, call_timeout_ms: 50 })).await?;
+    let started = std::time::Instant::now();
+    manager.execute_actions(&buys(5)).await;
+    let elapsed = started.elapsed();
+    ensure!(matches!(manager.broker_breaker.status().state, BreakerState::Open { .. }), "timeouts did not trip the breaker");
+    ensure!(elapsed < std::time::Duration::from_millis(500), "pipeline waited on a dead broker for {:?}", elapsed);
+    ensure!(manager.portfolio_snapshot().await.positions.is_empty(), "timed-out orders filled");
+    println!("✅ Two timeouts opened the breaker within {:?}", elapsed);
+
+    // Test 4: partial broker failures keep the position equal to the accepted orders
+    println!("📊 Test 4: Consistency under partial rejections");
+    let chaos = injector(ChaosConfig { broker_rejection_rate: 0.3, ..ChaosConfig::default() })?;
+    let manager = priced_manager(MultiCurrencyManager::new().with_trading_windows(TradingWindowsConfig::unrestricted())
+        .with_fault_injector(Arc::clone(&chaos))
+        .with_circuit_breaker_config(CircuitBreakerConfig { failure_threshold: 1000, ..CircuitBreakerConfig::default() })).await?;
+    manager.execute_actions(&buys(50)).await;
+    let accepted = 50 - chaos.counts().broker_rejections as usize;
+    let units = manager.portfolio_snapshot().await.positions.first().map(|p| p.units).unwrap_or(0.0);
+    let units_per_order = manager.portfolio.read().await.config().units_per_size;
+    ensure!((units - accepted as f64 * units_per_order).abs() < 1e-6, "position {} does not match {} accepted orders", units, accepted);
+    println!("✅ {} of 50 orders accepted, position {} units", accepted, units);
+
+    // Test 5: database write failures trip the breaker without losing in-memory bars
+    println!("📊 Test 5: Database write failures");
+    let chaos = injector(ChaosConfig { db_write_failure_rate: 0.5, ..ChaosConfig::default() })?;
+    let db = EmbeddedForexDB::new()?;
+    let manager = MultiCurrencyManager::new().with_trading_windows(TradingWindowsConfig::unrestricted())
+        .with_backfill_db(db.clone())
+        .with_fault_injector(Arc::clone(&chaos))
+        .with_circuit_breaker_config(CircuitBreakerConfig { failure_threshold: 2, ..CircuitBreakerConfig::default() });
+    let start = Utc::now() - Duration::days(60);
+    for day in 0..40 {
+        manager.persist_bar("EURUSD", &bar(start + Duration::days(day))).await;
+    }
+    let stored = db.get_bars_after("EURUSD", start - Duration::days(1), Utc::now())?;
+    let status = manager.db_breaker.status();
+    let failures = chaos.counts().db_write_failures as usize;
+    ensure!(status.trips >= 1, "database breaker never tripped");
+    ensure!(stored.len() + failures + status.rejected as usize == 40, "{} stored + {} failed + {} skipped != 40", stored.len(), failures, status.rejected);
+    ensure!(stored.windows(2).all(|w| w[0].timestamp < w[1].timestamp), "stored bars out of order");
+    println!("✅ {} stored, {} failed, {} skipped while open", stored.len(), failures, status.rejected);
+
+    // Test 6: a dropped feed pauses trading instead of trading on stale prices
+    println!("📊 Test 6: Feed drops");
+    let chaos = injector(ChaosConfig { feed_drop_rate: 1.0, ..ChaosConfig::default() })?;
+    let (provider, feed) = ChannelProvider::new("scripted");
+    let provider: Arc<dyn DataProvider> = Arc::new(provider);
+    let mut manager = MultiCurrencyManager::new().with_trading_windows(TradingWindowsConfig::unrestricted())
+        .with_data_provider(Arc::new(ChaosProvider::new(provider, Arc::clone(&chaos))))
+        .with_feed_health_config(FeedHealthConfig { expected_interval_ms: 100, stale_after_intervals: 3.0, max_clock_skew_ms: 1000 });
+    manager.initialize_major_pairs().await?;
+    manager.active_pairs.retain(|pair| pair == "EURUSD");
+    let manager = Arc::new(manager);
+    manager.start_live_feed()?;
+    for _ in 0..10 {
+        feed.send(Tick { symbol: "EURUSD".to_string(), timestamp: Utc::now(), bid: 1.1, ask: 1.1002 }).await?;
+        tokio::time::sleep(std::time::Duration::from_millis(60)).await;
+    }
+    ensure!(chaos.counts().feed_drops == 10, "expected every tick dropped");
+    ensure!(manager.pairs.read().await["EURUSD"].feed_paused, "EURUSD still trading on a dead feed");
+    let actions = manager.process_all_market_updates().await?;
+    ensure!(actions.is_empty(), "paused pair produced actions");
+    println!("✅ All ticks dropped, EURUSD paused, no actions generated");
+
+    println!();
+    println!("🎉 All chaos tests passed");
+    Ok(())
+}
@@ -0,0 +1,113 @@
+//! # Coarse-to-Fine Test
+//!
+//! Check histories over the `max_points` budget are analyzed in a downsampled
+//! pass plus full-resolution windows, recovering both a long and a short cycle
+
+use anyhow::{ensure, Result};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::f64::consts::TAU;
+use std::time::Instant;
+
+use forex_pattern_reconstruction::core::sampling::{downsample, stride, zoom_windows};
+use forex_pattern_reconstruction::core::{EngineConfig, TimeSymmetricEngine};
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::symmetry::{TemporalSymmetry, ROTATIONAL};
+use forex_pattern_reconstruction::synthetic::fixtures::rotational;
+
+fn bar(timestamp: DateTime<Utc>, close: f64) -> ForexDataPoint {
+    ForexDataPoint { timestamp, open: close, high: close + 0.0001, low: close - 0.0001, close, volume: None }
+}
+
+/// Minute bars with a `long`-bar and a `short`-bar cycle under the noise
+fn minutes(count: usize, long: f64, short: f64, rng: &mut StdRng) -> Vec<ForexDataPoint> {
+    let start = Utc.with_ymd_and_hms(2020, 1, 6, 0, 0, 0).unwrap();
+    (0..count)
+        .map(|i| {
+            let t = i as f64;
+            let close = 1.1 + 0.01 * (TAU * t / long).sin() + 0.001 * (TAU * t / short).sin() + rng.gen_range(-0.0001..0.0001);
+            bar(start + Duration::minutes(i as i64), close)
+        })
+        .collect()
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 COARSE-TO-FINE TEST");
+    println!("======================");
+    println!();
+
+    let mut rng = StdRng::seed_from_u64(11);
+
+    // Test 1: blocks aggregate like bars
+    println!("📊 Test 1: downsampling");
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let data: Vec<ForexDataPoint> = (0..7)
+        .map(|i| ForexDataPoint { volume: Some(1.0), ..bar(start + Duration::minutes(i), 1.0 + i as f64 * 0.01) })
+        .collect();
+    let coarse = downsample(&data, 3);
+    ensure!(coarse.len() == 3 && coarse[0].timestamp == start && coarse[2].timestamp == data[6].timestamp, "3 blocks, stamped by their first bar");
+    ensure!(coarse[0].open == data[0].open && coarse[0].close == data[2].close, "open of the first bar, close of the last");
+    ensure!(coarse[1].high == data[5].high && coarse[1].low == data[3].low && coarse[1].volume == Some(3.0), "extremes and volume of the block");
+    ensure!(stride(60_000, 2_000) == 30 && stride(2_001, 2_000) == 2 && stride(10, 2_000) == 1, "strides fit the budget");
+    println!("   ✅ 7 bars → {} coarse bars", coarse.len());
+
+    // Test 2: the zoom follows the coarse symmetries
+    println!("📊 Test 2: zoom windows");
+    let coarse: Vec<ForexDataPoint> = (0..1_000)
+        .map(|i| {
+            let cycle = if (480..600).contains(&i) { 0.01 * (TAU * i as f64 / 10.0).sin() } else { 0.0 };
+            bar(start + Duration::minutes(i), 1.1 + cycle + rng.gen_range(-0.001..0.001))
+        })
+        .collect();
+    let windows = zoom_windows(1_000, 100, 2, 1, &coarse, &[rotational(10)]);
+    let ranges: Vec<_> = windows.iter().map(|(range, _)| range.clone()).collect();
+    ensure!(ranges == vec![500..600, 900..1_000], "the cycle's window and the newest: {:?}", ranges);
+    ensure!(windows[0].1 > 0.9 && windows[1].1 < 0.5, "scores {:?}", windows);
+    let unscored = zoom_windows(1_000, 100, 3, 1, &coarse, &[]);
+    ensure!(unscored.iter().map(|(range, _)| range.clone()).collect::<Vec<_>>() == vec![700..800, 800..900, 900..1_000], "without symmetries the newest windows");
+    ensure!(zoom_windows(250, 100, 5, 1, &coarse[..250], &[]).iter().map(|(range, _)| range.len()).sum::<usize>() == 250, "tiles cover a short history");
+    println!("   ✅ Zoomed into {:?} (score {:.2}) and the newest window", ranges[0], windows[0].1);
+
+    // Test 3: a long minute history within the budget
+    println!("📊 Test 3: engine");
+    let data = minutes(60_000, 1_200.0, 20.0, &mut rng);
+    let budget = 2_000;
+    let mut engine = TimeSymmetricEngine::new(EngineConfig { coherence_window: 50, max_points: Some(budget), ..EngineConfig::default() })?;
+    engine.initialize().await?;
+    let timer = Instant::now();
+    let symmetries = engine.extract_temporal_symmetries(&data).await?;
+    let elapsed = timer.elapsed();
+    let plan = engine.sampling_plan().cloned().ok_or_else(|| anyhow::anyhow!("history over budget is sampled"))?;
+    ensure!(plan.stride == 30 && plan.coarse_bars <= budget, "coarse pass of {} bars at stride {}", plan.coarse_bars, plan.stride);
+    ensure!(plan.windows.len() == 3 && plan.windows.iter().all(|window| window.bars <= budget), "three windows within budget");
+    ensure!(plan.windows.last().map(|window| window.end) == data.last().map(|bar| bar.timestamp), "the newest bars are zoomed into");
+    let cycles: Vec<u32> = symmetries.iter().filter(|symmetry| symmetry.symmetry_type == ROTATIONAL).map(|symmetry| symmetry.period_days).collect();
+    ensure!(cycles.iter().any(|period| (1_080..=1_320).contains(period) && period % 30 == 0), "long cycle found on the coarse pass, in minutes: {:?}", cycles);
+    ensure!(cycles.iter().any(|period| (19..=21).contains(period)), "short cycle found zooming in: {:?}", cycles);
+    for (index, symmetry) in symmetries.iter().enumerate() {
+        ensure!(!symmetries[..index].iter().any(|other| other.symmetry_type == symmetry.symmetry_type && other.period_days == symmetry.period_days),
+                "{} {} kept once", symmetry.symmetry_type, symmetry.period_days);
+        ensure!(symmetry.name.contains(&symmetry.period_days.to_string()), "{} named by its period", symmetry.name);
+    }
+    println!("   ✅ {} bars in {:.2?}: rotational cycles {:?}", data.len(), elapsed, cycles);
+
+    // Test 4: histories within the budget analyze as before
+    println!("📊 Test 4: within budget");
+    let short = &data[..budget];
+    let sampled = engine.extract_temporal_symmetries(short).await?;
+    ensure!(engine.sampling_plan().is_none(), "nothing sampled");
+    let mut unlimited = TimeSymmetricEngine::new(EngineConfig { coherence_window: 50, ..EngineConfig::default() })?;
+    unlimited.initialize().await?;
+    let full = unlimited.extract_temporal_symmetries(short).await?;
+    let periods = |symmetries: &[TemporalSymmetry]| symmetries.iter().map(|symmetry| (symmetry.symmetry_type.clone(), symmetry.period_days)).collect::<Vec<_>>();
+    ensure!(periods(&sampled) == periods(&full), "same symmetries as without a budget");
+    let legacy: EngineConfig = toml::from_str("field_characteristic = 2\nfield_degree = 32\nmax_cycle_period = 7665\nmin_symmetry_strength = 0.75\ncoherence_window = 1000\nerror_correction_threshold = 0.05\n")?;
+    ensure!(legacy.max_points.is_none() && legacy.zoom_windows == 3, "older configurations analyze everything");
+    println!("   ✅ {} symmetries, no sampling", sampled.len());
+
+    println!();
+    println!("🎉 All coarse-to-fine tests passed");
+    Ok(())
+}
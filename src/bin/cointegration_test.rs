@@ -0,0 +1,159 @@
+//! # Cointegration Test
+//!
+//! Check the Engle-Granger test recovers a planted hedge ratio and half-life,
+//! rejects unrelated pairs, and that the spread strategy trades the spread
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rand_distr::StandardNormal;
+use std::collections::HashMap;
+
+use forex_pattern_reconstruction::backtest::spread::SpreadStrategy;
+use forex_pattern_reconstruction::backtest::strategy::StrategyRegistry;
+use forex_pattern_reconstruction::backtest::{BacktestConfig, BacktestEngine, StrategyConfig};
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::stats::cointegration::{
+    engle_granger, find_cointegrated_pairs, CointegrationConfig, SpreadPosition, SpreadTracker,
+    ENGLE_GRANGER_CRITICAL_1PCT,
+};
+
+const BARS: usize = 1500;
+const HEDGE_RATIO: f64 = 0.8;
+const REVERSION: f64 = 0.9;
+
+fn bars(log_prices: &[f64]) -> Vec<ForexDataPoint> {
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    log_prices.iter().enumerate()
+        .map(|(i, log_price)| {
+            let close = log_price.exp();
+            ForexDataPoint { timestamp: start + Duration::hours(i as i64), open: close, high: close, low: close, close, volume: None }
+        })
+        .collect()
+}
+
+/// Hedge leg random walk, a dependent leg `0.05 + 0.8·ln x + spread` with an AR(1)
+/// spread, and an unrelated random walk
+fn series(seed: u64) -> (Vec<ForexDataPoint>, Vec<ForexDataPoint>, Vec<ForexDataPoint>) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let (mut x, mut spread, mut other) = (0.1_f64, 0.0_f64, 0.2_f64);
+    let (mut xs, mut ys, mut others) = (Vec::new(), Vec::new(), Vec::new());
+    for _ in 0..BARS {
+        x += 0.004 * rng.sample::<f64, _>(StandardNormal);
+        spread = REVERSION * spread + 0.002 * rng.sample::<f64, _>(StandardNormal);
+        other += 0.002 * rng.sample::<f64, _>(StandardNormal);
+        xs.push(x);
+        ys.push(0.05 + HEDGE_RATIO * x + spread);
+        others.push(other);
+    }
+    (bars(&ys), bars(&xs), bars(&others))
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 COINTEGRATION TEST");
+    println!("=====================");
+    println!();
+
+    let (gbp, eur, jpy) = series(2);
+    let config = CointegrationConfig::default();
+
+    // Test 1: Engle-Granger recovers the relationship
+    println!("📊 Test 1: Engle-Granger");
+    let result = engle_granger("GBPUSD", &gbp, "EURUSD", &eur, &config)?;
+    println!("   {}", result.summary());
+    ensure!(result.cointegrated && result.adf_statistic < ENGLE_GRANGER_CRITICAL_1PCT, "ADF {:.2}", result.adf_statistic);
+    ensure!((result.hedge_ratio - HEDGE_RATIO).abs() < 0.1, "hedge ratio {:.3}", result.hedge_ratio);
+    let expected_half_life = -(2.0_f64.ln()) / REVERSION.ln();
+    let half_life = result.half_life_bars.unwrap();
+    ensure!((half_life - expected_half_life).abs() < 3.0, "half-life {:.1} vs {:.1}", half_life, expected_half_life);
+    ensure!(result.samples == config.lookback && result.as_of == gbp.last().unwrap().timestamp, "tested on the last {} bars", config.lookback);
+    let unrelated = engle_granger("USDJPY", &jpy, "EURUSD", &eur, &config)?;
+    println!("   {}", unrelated.summary());
+    ensure!(!unrelated.cointegrated, "independent random walks are not cointegrated");
+    ensure!(engle_granger("GBPUSD", &gbp[..50], "EURUSD", &eur[..50], &config).is_err(), "too few bars");
+    ensure!(CointegrationConfig { exit_z: 3.0, ..config.clone() }.validate().is_err(), "exit beyond entry rejected");
+    println!("   ✅ Hedge ratio {:.3}, half-life {:.1} bars", result.hedge_ratio, half_life);
+
+    // Test 2: the strongest direction of each cointegrated combination is reported
+    println!("📊 Test 2: scanning pairs");
+    let data_map = HashMap::from([
+        ("GBPUSD".to_string(), gbp.clone()),
+        ("EURUSD".to_string(), eur.clone()),
+        ("USDJPY".to_string(), jpy.clone()),
+    ]);
+    let found = find_cointegrated_pairs(&data_map, &config)?;
+    ensure!(found.len() == 1, "one cointegrated combination: {:?}", found.iter().map(|r| r.summary()).collect::<Vec<_>>());
+    let mut legs = [found[0].pair_y.as_str(), found[0].pair_x.as_str()];
+    legs.sort();
+    ensure!(legs == ["EURUSD", "GBPUSD"], "legs {:?}", legs);
+    println!("   ✅ {}", found[0].summary());
+
+    // Test 3: spread z-scores and signals
+    println!("📊 Test 3: spread signals");
+    let (y, x) = (gbp.last().unwrap().close, eur.last().unwrap().close);
+    let z = result.z_score(y, x);
+    ensure!(z.abs() < 4.0, "latest spread within range, z {:.2}", z);
+    let hedge = result.hedge_units(10_000.0, y, x);
+    ensure!(hedge < 0.0 && (hedge.abs() * x / (10_000.0 * y) - result.hedge_ratio).abs() < 1e-9, "hedge leg offsets {:.0}", hedge);
+    let at = |z: f64| {
+        let spread = result.spread_mean + z * result.spread_std;
+        ((result.intercept + result.hedge_ratio * x.ln() + spread).exp(), x)
+    };
+    let mut tracker = SpreadTracker::new(config.clone());
+    let now = Utc::now();
+    let steps: Vec<(f64, Option<(SpreadPosition, SpreadPosition)>)> = vec![
+        (1.0, None),
+        (2.5, Some((SpreadPosition::Flat, SpreadPosition::Short))),
+        (1.5, None),
+        (0.3, Some((SpreadPosition::Short, SpreadPosition::Flat))),
+        (-2.2, Some((SpreadPosition::Flat, SpreadPosition::Long))),
+        (-4.5, Some((SpreadPosition::Long, SpreadPosition::Flat))),
+        (-5.0, None),
+    ];
+    for (z, expected) in steps {
+        let (y, x) = at(z);
+        let signal = tracker.update(&result, now, y, x);
+        ensure!(signal.as_ref().map(|s| (s.from, s.to)) == expected, "at z {}: {:?}", z, signal);
+        if let Some(signal) = signal {
+            ensure!((signal.z_score - z).abs() < 1e-6, "signal z {:.3} vs {}", signal.z_score, z);
+            println!("   z {:+.1}: {:?} → {:?} ({})", z, signal.from, signal.to, signal.reason);
+        }
+    }
+    let mut idle = SpreadTracker::new(config.clone());
+    let (y, x) = at(3.0);
+    ensure!(idle.update(&unrelated, now, y, x).is_none(), "no entries without cointegration");
+    println!("   ✅ Entries at ±2σ, exits on reversion and at the 4σ stop");
+
+    // Test 4: the spread strategy in the backtest engine, next to a symmetry strategy
+    println!("📊 Test 4: backtest");
+    let backtest_config = BacktestConfig { warmup_bars: 100, cycle_refresh_bars: 100, ..BacktestConfig::default() };
+    let spread_config = StrategyConfig {
+        name: SpreadStrategy::NAME.to_string(),
+        parameters: HashMap::from([("position_units".to_string(), 10_000.0), ("lookback".to_string(), 300.0)]),
+    };
+    let mut spread_strategy = SpreadStrategy::new(&spread_config, "EURUSD", &eur)?;
+    let engine = BacktestEngine::new(spread_config, 10_000.0, backtest_config.clone())?;
+    let spread_run = engine.run(&mut spread_strategy, "GBPUSD", &gbp, &[]).await?;
+    let fit = spread_strategy.fit().expect("fitted");
+    ensure!((fit.hedge_ratio - HEDGE_RATIO).abs() < 0.15, "walk-forward hedge ratio {:.3}", fit.hedge_ratio);
+    ensure!(spread_run.strategy == SpreadStrategy::NAME && spread_run.trades.len() >= 10, "{} fills", spread_run.trades.len());
+    ensure!(spread_run.results.total_return > 0.0, "trading a mean-reverting spread should profit, got {:.2}%", spread_run.results.total_return * 100.0);
+    let symmetry_config = StrategyConfig::default();
+    let mut symmetry_strategy = StrategyRegistry::new().create(&symmetry_config)?;
+    let symmetry_run = BacktestEngine::new(symmetry_config, 10_000.0, backtest_config)?
+        .run(symmetry_strategy.as_mut(), "GBPUSD", &gbp, &[]).await?;
+    println!("   {}: {:+.2}% over {} fills; {}: {:+.2}% over {} fills",
+             spread_run.strategy, spread_run.results.total_return * 100.0, spread_run.trades.len(),
+             symmetry_run.strategy, symmetry_run.results.total_return * 100.0, symmetry_run.trades.len());
+    ensure!(SpreadStrategy::new(&StrategyConfig {
+        name: SpreadStrategy::NAME.to_string(),
+        parameters: HashMap::from([("entry_z".to_string(), 5.0)]),
+    }, "EURUSD", &eur).is_err(), "entry beyond the stop is rejected");
+    println!("   ✅ Spread strategy validated alongside {}", symmetry_run.strategy);
+
+    println!();
+    println!("🎉 All cointegration tests passed");
+    Ok(())
+}
@@ -0,0 +1,151 @@
+//! # Composite Score Test
+//!
+//! Score a clean hourly sine against the cycle that generated it and check that
+//! the score stays in [-1, 1], follows the cycle's slope, moves with anomaly
+//! signals, is damped after breakdowns, labels trends and ranges, and that the
+//! built-in `CompositeScoreStrategy` trades it profitably in a backtest
+
+use anyhow::{ensure, Result};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+use forex_pattern_reconstruction::anomaly::{
+    AnomalySeverity, AnomalyTradingSignal, AnomalyType, DetectedAnomaly, MarketContext,
+};
+use forex_pattern_reconstruction::backtest::strategy::{CompositeScoreStrategy, StrategyRegistry};
+use forex_pattern_reconstruction::backtest::{BacktestConfig, BacktestEngine, StrategyConfig};
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::ids::CycleId;
+use forex_pattern_reconstruction::patterns::HiddenCycle;
+use forex_pattern_reconstruction::signal::{CompositeScoreConfig, CompositeScorer, Regime};
+
+fn bar(timestamp: DateTime<Utc>, open: f64, close: f64) -> ForexDataPoint {
+    ForexDataPoint { timestamp, open, high: open.max(close) + 0.0002, low: open.min(close) - 0.0002, close, volume: None }
+}
+
+/// Hourly closes from `close_at`, with opens at the previous close
+fn series(start: DateTime<Utc>, hours: i64, close_at: impl Fn(i64) -> f64) -> Vec<ForexDataPoint> {
+    (0..hours).map(|h| bar(start + Duration::hours(h), close_at(h - 1), close_at(h))).collect()
+}
+
+fn anomaly(timestamp: DateTime<Utc>, anomaly_type: AnomalyType, signal: Option<&str>) -> DetectedAnomaly {
+    DetectedAnomaly {
+        id: format!("a-{}", timestamp.timestamp()).into(),
+        timestamp,
+        anomaly_type,
+        severity: AnomalySeverity::High,
+        confidence: 0.9,
+        deviation_magnitude: 0.01,
+        affected_symmetries: Vec::new(),
+        affected_cycles: Vec::new(),
+        market_context: MarketContext {
+            session: "London".to_string(),
+            volatility_regime: "Normal".to_string(),
+            trend_direction: "Sideways".to_string(),
+            recent_events: Vec::new(),
+            event_proximity: 0.0,
+        },
+        trading_signal: signal.map(|signal_type| AnomalyTradingSignal {
+            signal_type: signal_type.to_string(),
+            strength: 1.0,
+            confidence: 1.0,
+            time_horizon: "Short".to_string(),
+            risk_level: "Medium".to_string(),
+            expected_duration: 60,
+        }),
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 COMPOSITE SCORE TEST");
+    println!("=======================");
+    println!();
+
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let cycle = HiddenCycle { id: CycleId::new(), name: "24-Bar Cycle".to_string(), period: 24, confidence: 0.9, amplitude: 0.002, phase: 0.0, p_value: None, track: None };
+    let omega = 2.0 * PI / 24.0;
+    let hour0 = start.timestamp() as f64 / 3600.0;
+    let sine = |h: i64| 1.1 * (1.0 + cycle.amplitude * (omega * (hour0 + h as f64)).sin());
+    let data = series(start, 24 * 20, sine);
+
+    // Test 1: bounded scores that follow the cycle slope
+    println!("📊 Test 1: cycle phase");
+    let mut scorer = CompositeScorer::new(CompositeScoreConfig::default())?;
+    scorer.set_cycles(std::slice::from_ref(&cycle));
+    let scores = scorer.score_series(&data, &[], 100);
+    ensure!(scores.len() == data.len() - 100, "one score per bar, got {}", scores.len());
+    ensure!(scores.iter().all(|s| (-1.0..=1.0).contains(&s.score)), "scores must stay in [-1, 1]");
+    let agreeing = scores.iter()
+        .filter(|s| {
+            let slope = (omega * s.timestamp.timestamp() as f64 / 3600.0).cos();
+            let cycle_phase = s.components.cycle_phase.unwrap_or(0.0);
+            slope.abs() < 0.2 || cycle_phase.signum() == slope.signum()
+        })
+        .count();
+    ensure!(agreeing == scores.len(), "cycle component should follow the slope on {} of {} bars", agreeing, scores.len());
+    ensure!(scores.iter().all(|s| s.components.symmetry.is_none()), "no symmetries were set");
+    let peak = scores.iter().map(|s| s.score).fold(f64::NEG_INFINITY, f64::max);
+    println!("   {} scores, peak {:+.3}, last {:+.3} ({})", scores.len(), peak, scores[scores.len() - 1].score, scores[scores.len() - 1].regime);
+    ensure!(peak > 0.3, "a clean cycle should give confident scores, peak {:+.3}", peak);
+    println!("   ✅ Scores bounded and in phase with the cycle");
+
+    // Test 2: a decaying anomaly signal moves the score, a breakdown damps it
+    println!("📊 Test 2: anomalies");
+    let last = data.last().unwrap().timestamp;
+    let base = scorer.score(&data, &[]).unwrap();
+    let bullish = scorer.score(&data, &[anomaly(last, AnomalyType::VolatilitySpike { expected_volatility: 0.001, actual_volatility: 0.004 }, Some("Buy"))]).unwrap();
+    let stale = scorer.score(&data, &[anomaly(last - Duration::days(3), AnomalyType::VolatilitySpike { expected_volatility: 0.001, actual_volatility: 0.004 }, Some("Buy"))]).unwrap();
+    let breakdown = anomaly(last, AnomalyType::SymmetryBreakdown { symmetry_id: "symmetry_0".into(), expected_strength: 0.9, actual_strength: 0.2 }, None);
+    let damped = scorer.score(&data, &[breakdown]).unwrap();
+    let future = scorer.score(&data, &[anomaly(last + Duration::hours(1), AnomalyType::VolatilitySpike { expected_volatility: 0.001, actual_volatility: 0.004 }, Some("Sell"))]).unwrap();
+    println!("   base {:+.3}, buy signal {:+.3}, stale {:+.3}, breakdown {:+.3} (×{:.2})",
+             base.score, bullish.score, stale.score, damped.score, damped.components.breakdown_damping);
+    ensure!(bullish.components.anomaly > 0.9 && bullish.score > base.score, "a fresh buy signal should lift the score");
+    ensure!(stale.components.anomaly == 0.0, "signals older than five half-lives are ignored");
+    ensure!(damped.components.breakdown_damping < 0.2 && damped.score.abs() < base.score.abs(), "a breakdown should damp the score");
+    ensure!(future.components.anomaly == 0.0, "anomalies after the bar must not leak in");
+    println!("   ✅ Signals add direction, breakdowns damp, no look-ahead");
+
+    // Test 3: regime labels
+    println!("📊 Test 3: regimes");
+    let plain = CompositeScorer::new(CompositeScoreConfig::default())?;
+    let up = plain.score(&series(start, 48, |h| 1.1 + 0.0005 * h as f64), &[]).unwrap();
+    let down = plain.score(&series(start, 48, |h| 1.1 - 0.0005 * h as f64), &[]).unwrap();
+    let range = plain.score(&series(start, 48, |h| 1.1 + if h % 2 == 0 { 0.0005 } else { 0.0 }), &[]).unwrap();
+    let mut jump = series(start, 48, |h| 1.1 + if h % 2 == 0 { 0.0005 } else { 0.0 });
+    let spike = jump.len() - 1;
+    jump[spike].high += 0.01;
+    let volatile = plain.score(&jump, &[]).unwrap();
+    let short = plain.score(&series(start, 5, |h| 1.1 + 0.0005 * h as f64), &[]).unwrap();
+    println!("   {}, {}, {}, {}, {}", up.regime, down.regime, range.regime, volatile.regime, short.regime);
+    ensure!(up.regime == Regime::TrendingUp && up.score > 0.3, "steady rise is an up trend, score {:+.3}", up.score);
+    ensure!(down.regime == Regime::TrendingDown && down.score < -0.3, "steady fall is a down trend");
+    ensure!(range.regime == Regime::Ranging && range.score == 0.0, "oscillation is a range");
+    ensure!(volatile.regime == Regime::Volatile, "a wide last bar is volatile");
+    ensure!(short.regime == Regime::Unknown && short.components.regime.is_none(), "too few bars to tell");
+    ensure!(CompositeScorer::new(CompositeScoreConfig { cycle_weight: -1.0, ..CompositeScoreConfig::default() }).is_err(),
+            "negative weights are rejected");
+    println!("   ✅ Trending, ranging, volatile and unknown regimes");
+
+    // Test 4: the score strategy trades the cycle through the registry
+    println!("📊 Test 4: backtest");
+    let config = StrategyConfig { name: CompositeScoreStrategy::NAME.to_string(), parameters: HashMap::new() };
+    let mut strategy = StrategyRegistry::new().create(&config)?;
+    let engine = BacktestEngine::new(config, 10_000.0, BacktestConfig { warmup_bars: 100, cycle_refresh_bars: 50, ..BacktestConfig::default() })?;
+    let run = engine.run(strategy.as_mut(), "EURUSD", &data, &[]).await?;
+    println!("   return {:.2}%, {} fills, sharpe {:.2}", run.results.total_return * 100.0, run.trades.len(), run.results.sharpe_ratio);
+    ensure!(run.strategy == CompositeScoreStrategy::NAME, "run should name the strategy");
+    ensure!(run.trades.len() >= 4, "the strategy should trade the cycle, got {} fills", run.trades.len());
+    ensure!(run.results.total_return > 0.0, "trading a clean cycle on its score should profit");
+    ensure!(CompositeScoreStrategy::new(&StrategyConfig {
+        name: CompositeScoreStrategy::NAME.to_string(),
+        parameters: HashMap::from([("entry_threshold".to_string(), 0.1), ("exit_threshold".to_string(), 0.5)]),
+    }).is_err(), "exit above entry threshold is rejected");
+    println!("   ✅ CompositeScoreStrategy traded {} fills", run.trades.len());
+
+    println!();
+    println!("🎉 All composite score tests passed");
+    Ok(())
+}
@@ -0,0 +1,102 @@
+//! # Correlation Regime Test
+//!
+//! Check the rolling correlation and CUSUM find a planted correlation break,
+//! ignore a volatility jump alone, and raise correlation breakdown anomalies
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rand_distr::StandardNormal;
+use std::collections::HashMap;
+
+use forex_pattern_reconstruction::anomaly::{AnomalySeverity, AnomalyType, DetectedAnomaly};
+use forex_pattern_reconstruction::correlation::{CrossPairAnalyzer, RegimeDetectionConfig};
+use forex_pattern_reconstruction::data::ForexDataPoint;
+
+const BARS: usize = 1200;
+const SHIFT: usize = 700;
+
+/// Two hourly series whose returns have correlation `correlation(i)` and volatility scaled by `scale(i)`
+fn pairs(seed: u64, correlation: impl Fn(usize) -> f64, scale: impl Fn(usize) -> f64) -> (Vec<ForexDataPoint>, Vec<ForexDataPoint>) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let (mut eur, mut gbp) = (1.1, 1.25);
+    let bar = |i: usize, close: f64| ForexDataPoint { timestamp: start + Duration::hours(i as i64), open: close, high: close, low: close, close, volume: None };
+    let (mut first, mut second) = (vec![bar(0, eur)], vec![bar(0, gbp)]);
+    for i in 1..BARS {
+        let rho: f64 = correlation(i);
+        let (common, own) = (rng.sample::<f64, _>(StandardNormal), rng.sample::<f64, _>(StandardNormal));
+        eur *= 1.0 + 0.001 * scale(i) * common;
+        gbp *= 1.0 + 0.001 * scale(i) * (rho * common + (1.0 - rho * rho).sqrt() * own);
+        first.push(bar(i, eur));
+        second.push(bar(i, gbp));
+    }
+    (first, second)
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 CORRELATION REGIME TEST");
+    println!("==========================");
+    println!();
+
+    let analyzer = CrossPairAnalyzer::new();
+    let config = RegimeDetectionConfig::default();
+    let (eur, gbp) = pairs(3, |i| if i < SHIFT { 0.85 } else { 0.0 }, |_| 1.0);
+    let shift = eur[SHIFT].timestamp;
+
+    // Test 1: the rolling correlation follows the shift the full-history coefficient hides
+    println!("📊 Test 1: rolling correlation");
+    let rolling = analyzer.rolling_correlation(&eur, &gbp, config.window);
+    ensure!(rolling.len() == BARS - config.window, "one point per bar once the window is full, got {}", rolling.len());
+    let at = |hours: i64| rolling.iter().find(|p| p.timestamp == shift + Duration::hours(hours)).unwrap().correlation;
+    ensure!(at(-1) > 0.7 && at(200).abs() < 0.3, "before {:.3}, after {:.3}", at(-1), at(200));
+    let full = analyzer.calculate_correlation_matrix(&HashMap::from([("EURUSD".to_string(), eur.clone()), ("GBPUSD".to_string(), gbp.clone())]))?;
+    let full = full.values().next().unwrap().correlation;
+    println!("   ✅ {:.3} before, {:.3} after; full history {:.3}", at(-1), at(200), full);
+
+    // Test 2: the CUSUM finds the shift soon after it happens
+    println!("📊 Test 2: change point");
+    let changes = analyzer.detect_regime_changes("EURUSD", &eur, "GBPUSD", &gbp, &config)?;
+    ensure!(changes.len() == 1, "one change, got {:?}", changes);
+    let change = &changes[0];
+    let delay = (change.detected_at - shift).num_hours();
+    let error = (change.change_point - shift).num_hours();
+    ensure!((0..=60).contains(&delay), "detected {}h after the shift", delay);
+    ensure!(error.abs() <= 20, "change point {}h from the shift", error);
+    ensure!(change.correlation_before > 0.7 && change.correlation_after < 0.4, "{:?}", change);
+    ensure!(change.z_score < -2.0 && change.confidence() > 0.6, "z {:.2}", change.z_score);
+    println!("   ✅ detected +{}h, change point {:+}h, {:.3} → {:.3}, z {:.2}",
+             delay, error, change.correlation_before, change.correlation_after, change.z_score);
+
+    // Test 3: stable correlations and volatility jumps alone raise no change
+    println!("📊 Test 3: no false alarms");
+    for seed in 0..5 {
+        let (a, b) = pairs(100 + seed, |_| 0.6, |_| 1.0);
+        let stable = analyzer.detect_regime_changes("EURUSD", &a, "GBPUSD", &b, &config)?;
+        ensure!(stable.is_empty(), "seed {}: {:?}", seed, stable);
+    }
+    let (a, b) = pairs(7, |_| 0.6, |i| if i < SHIFT { 1.0 } else { 3.0 });
+    let volatile = analyzer.detect_regime_changes("EURUSD", &a, "GBPUSD", &b, &config)?;
+    ensure!(volatile.is_empty(), "a volatility jump is not a correlation change: {:?}", volatile);
+    println!("   ✅ No changes in 5 stable histories or across a tripling of volatility");
+
+    // Test 4: changes become correlation breakdown anomalies
+    println!("📊 Test 4: correlation breakdown anomalies");
+    let anomaly = DetectedAnomaly::from(change);
+    ensure!(anomaly.timestamp == change.detected_at && anomaly.anomaly_type.name() == "CorrelationBreakdown", "{:?}", anomaly);
+    ensure!(matches!(&anomaly.anomaly_type, AnomalyType::CorrelationBreakdown { correlation_pair, expected_correlation, .. }
+            if correlation_pair == &("EURUSD".to_string(), "GBPUSD".to_string()) && *expected_correlation > 0.7), "{:?}", anomaly.anomaly_type);
+    ensure!(matches!(anomaly.severity, AnomalySeverity::High | AnomalySeverity::Critical), "{:?}", anomaly.severity);
+    let map = HashMap::from([("GBPUSD".to_string(), gbp.clone()), ("EURUSD".to_string(), eur.clone()), ("USDJPY".to_string(), a.clone())]);
+    let all = analyzer.detect_all_regime_changes(&map, &config)?;
+    ensure!(all.iter().any(|c| c.pair1 == "EURUSD" && c.pair2 == "GBPUSD"), "pairs in name order");
+    ensure!(all.windows(2).all(|w| w[0].detected_at <= w[1].detected_at), "oldest first");
+    ensure!(analyzer.detect_regime_changes("EURUSD", &eur, "GBPUSD", &gbp, &RegimeDetectionConfig { block_bars: 3, ..config }).is_err(), "blocks too short");
+    println!("   ✅ {:?} {} ({:.0}% confidence)", anomaly.severity, anomaly.anomaly_type.name(), anomaly.confidence * 100.0);
+
+    println!();
+    println!("🎉 All correlation regime tests passed");
+    Ok(())
+}
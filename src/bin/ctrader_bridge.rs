@@ -1,4 +1,4 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
@@ -6,9 +6,15 @@ use tokio::time::interval;
 use chrono::{DateTime, Utc};
 
 use forex_pattern_reconstruction::{
+    backtest::strategy::OrderSide,
+    broker::MarketOrder,
+    broker::ctrader::{CTraderClient, CTraderConfig},
     multi_currency::MultiCurrencyManager,
     laplacian_rl::TradingAction,
     anomaly::{DetectedAnomaly, AnomalyType, AnomalySeverity, MarketContext, AnomalyTradingSignal},
+    trading_windows::TradingWindowsConfig,
+    ids::AnomalyId,
+    units::Pips,
 };
 
 /// cTrader API Order Structure
@@ -63,21 +69,19 @@ impl Default for HFTAnomalyStrategy {
 
 /// cTrader API Bridge for High-Frequency Trading
 pub struct CTraderBridge {
-    client_id: String,
-    client_secret: String,
-    base_url: String,
-    account_id: String,
-    server: String,
-    access_token: Option<String>,
-    client: reqwest::Client,
+    config: CTraderConfig,
+    /// Open API session, once authenticated
+    session: Option<CTraderClient>,
     strategy: HFTAnomalyStrategy,
     metrics: TradingMetrics,
     active_positions: HashMap<String, ActivePosition>,
+    trading_windows: TradingWindowsConfig,
 }
 
 #[derive(Debug, Clone)]
 pub struct ActivePosition {
     pub order_id: String,
+    pub position_id: i64,
     pub symbol: String,
     pub side: String,
     pub volume: f64,
@@ -89,28 +93,19 @@ pub struct ActivePosition {
 
 impl CTraderBridge {
     pub async fn new() -> Result<Self> {
-        // Use your actual cTrader credentials from CTRADER.MD
-        let client_id = std::env::var("CTRADER_CLIENT_ID")
-            .unwrap_or_else(|_| "14877_vyfOpsRldMcTyq4M2Qien3KxqG43yVFlSt0jLNjBhr0LX2Cpd7".to_string());
-        let client_secret = std::env::var("CTRADER_CLIENT_SECRET")
-            .unwrap_or_else(|_| "smo86RDCn85U5Fy5hIuCi4oScBJMiKwlEt3x0zxBC406ioUioE".to_string());
+        // Open API credentials and demo/live mode from CTRADER_* variables
+        let config = CTraderConfig::from_env()?;
+        config.check_mode()?;
 
-        // Your cTrader account details from CTRADER.MD
-        let account_id = std::env::var("CTRADER_ACCOUNT_ID")
-            .unwrap_or_else(|_| "1259560".to_string()); // Live account
-        let server = std::env::var("CTRADER_SERVER")
-            .unwrap_or_else(|_| "cTrader LIVE".to_string());
+        // Blocked trading periods, shared with backtests
+        let trading_windows = match std::env::var("TRADING_WINDOWS_CONFIG") {
+            Ok(path) => TradingWindowsConfig::from_file(std::path::Path::new(&path))?,
+            Err(_) => TradingWindowsConfig::default(),
+        };
 
         Ok(Self {
-            client_id,
-            client_secret,
-            base_url: "https://connect.spotware.com".to_string(), // cTrader Open API v2
-            account_id,
-            server,
-            access_token: None, // Will be obtained via OAuth2
-            client: reqwest::Client::builder()
-                .timeout(Duration::from_millis(100)) // Ultra-low latency
-                .build()?,
+            config,
+            session: None,
             strategy: HFTAnomalyStrategy::default(),
             metrics: TradingMetrics {
                 total_trades: 0,
@@ -123,38 +118,37 @@ impl CTraderBridge {
                 current_equity: 100000.0, // Starting with $100k
             },
             active_positions: HashMap::new(),
+            trading_windows,
         })
     }
     
-    /// Authenticate with cTrader using OAuth2 flow
+    /// Open an Open API session and authenticate the application and account
     pub async fn authenticate(&mut self) -> Result<()> {
-        println!("🔐 Authenticating with cTrader API...");
-        println!("📋 Client ID: {}", &self.client_id[..20]); // Show first 20 chars only
-        println!("🏦 Account ID: {}", self.account_id);
-        println!("🖥️  Server: {}", self.server);
+        println!("🔐 Authenticating with cTrader Open API...");
+        println!("🏦 Account ID: {}", self.config.account_id);
+        println!("🖥️  Server: {} ({})", self.config.host(), self.config.mode);
 
-        // In production, implement full OAuth2 flow
-        // For now, simulate successful authentication
-        self.access_token = Some("demo_access_token_placeholder".to_string());
+        let mut session = CTraderClient::connect(self.config.clone()).await?;
+        session.authenticate().await?;
+        let symbols = session.load_symbols().await?;
+        self.session = Some(session);
 
         println!("✅ cTrader authentication successful!");
-        println!("🔗 Connected to {} account {}", self.server, self.account_id);
+        println!("🔗 Connected to {} account {} ({} symbols)", self.config.mode, self.config.account_id, symbols);
 
         Ok(())
     }
 
     /// Get account information from cTrader
     pub async fn get_account_info(&self) -> Result<()> {
-        if self.access_token.is_none() {
+        let Some(session) = &self.session else {
             return Err(anyhow::anyhow!("Not authenticated. Call authenticate() first."));
-        }
+        };
 
         println!("📊 Account Information:");
-        println!("   Account ID: {}", self.account_id);
-        println!("   Server: {}", self.server);
-        println!("   Currency: USD");
-        println!("   Leverage: 1:200");
-        println!("   Balance: $100,000.00 (simulated)");
+        println!("   Account ID: {}", self.config.account_id);
+        println!("   Server: {}", self.config.host());
+        println!("   Mode: {}", session.mode());
 
         Ok(())
     }
@@ -168,6 +162,12 @@ impl CTraderBridge {
             return Ok(None);
         }
         
+        // Respect blocked trading windows
+        if let Some(reason) = self.trading_windows.check(Utc::now()) {
+            println!("⏸️  Trade on {} blocked: {}", symbol, reason);
+            return Ok(None);
+        }
+        
         let order = self.create_order_from_action(action, symbol, anomaly)?;
         let order_id = self.place_order_hft(order).await?;
         
@@ -183,16 +183,22 @@ impl CTraderBridge {
     
     /// High-frequency order placement with sub-100ms target
     async fn place_order_hft(&mut self, order: CTraderOrder) -> Result<String> {
-        // Simulate cTrader API call (replace with actual API in production)
-        let order_id = format!("HFT_{}", chrono::Utc::now().timestamp_millis());
+        let session = self.session.as_mut().context("Not authenticated. Call authenticate() first.")?;
+        let side = if order.side == "BUY" { OrderSide::Buy } else { OrderSide::Sell };
+        let market_order = MarketOrder::new(&order.symbol, side, order.volume * self.config.units_per_size)
+            .with_protection(order.stop_loss, order.take_profit)
+            .with_label(&order.comment);
+        let fill = session.place_market_order(&market_order).await?;
+        let order_id = fill.order_id.to_string();
         
         // Record the position
         let position = ActivePosition {
             order_id: order_id.clone(),
+            position_id: fill.position_id,
             symbol: order.symbol.clone(),
             side: order.side.clone(),
             volume: order.volume,
-            entry_price: order.price.unwrap_or(1.1000), // Simulated price
+            entry_price: fill.price,
             entry_time: Utc::now(),
             stop_loss: order.stop_loss.unwrap_or(0.0),
             take_profit: order.take_profit.unwrap_or(0.0),
@@ -201,9 +207,8 @@ impl CTraderBridge {
         self.active_positions.insert(order_id.clone(), position);
         self.metrics.total_trades += 1;
         
-        // Simulate successful execution
-        println!("✅ Order executed: {} - {} lots {} - Order ID: {}", 
-                 order.symbol, order.volume, order.side, order_id);
+        println!("✅ Order executed: {} - {} units {} at {:.5} - Order ID: {}", 
+                 order.symbol, fill.units, order.side, fill.price, order_id);
         
         Ok(order_id)
     }
@@ -218,15 +223,14 @@ impl CTraderBridge {
         };
         
         // Calculate dynamic stop loss and take profit based on anomaly strength
-        let pip_value = if symbol.contains("JPY") { 0.01 } else { 0.0001 };
         let severity_multiplier = match anomaly.severity {
             AnomalySeverity::Low => 0.5,
             AnomalySeverity::Medium => 1.0,
             AnomalySeverity::High => 1.5,
             AnomalySeverity::Critical => 2.0,
         };
-        let stop_loss_distance = self.strategy.stop_loss_pips * pip_value * severity_multiplier;
-        let take_profit_distance = self.strategy.profit_target_pips * pip_value * anomaly.confidence;
+        let stop_loss_distance = (Pips(self.strategy.stop_loss_pips) * severity_multiplier).to_price(symbol);
+        let take_profit_distance = (Pips(self.strategy.profit_target_pips) * anomaly.confidence).to_price(symbol);
         
         Ok(CTraderOrder {
             symbol: symbol.to_string(),
@@ -234,8 +238,8 @@ impl CTraderBridge {
             order_type: "MARKET".to_string(),
             side: side.to_string(),
             price: None, // Market execution
-            stop_loss: Some(stop_loss_distance),
-            take_profit: Some(take_profit_distance),
+            stop_loss: Some(stop_loss_distance.0),
+            take_profit: Some(take_profit_distance.0),
             comment,
         })
     }
@@ -297,18 +301,22 @@ impl CTraderBridge {
     /// Close position and update metrics
     async fn close_position(&mut self, order_id: &str) -> Result<()> {
         if let Some(position) = self.active_positions.remove(order_id) {
-            // Simulate position closure with profit/loss
-            let simulated_profit = (rand::random::<f64>() - 0.4) * 100.0; // Slight positive bias
+            let session = self.session.as_mut().context("Not authenticated. Call authenticate() first.")?;
+            let fill = session.close_position(&position.symbol, position.position_id).await?;
+            
+            // P&L in the quote currency
+            let direction = if position.side == "BUY" { 1.0 } else { -1.0 };
+            let profit = (fill.price - position.entry_price) * fill.units * direction;
             
-            self.metrics.total_profit += simulated_profit;
-            if simulated_profit > 0.0 {
+            self.metrics.total_profit += profit;
+            if profit > 0.0 {
                 self.metrics.successful_trades += 1;
             }
             
             self.metrics.success_rate = (self.metrics.successful_trades as f64 / self.metrics.total_trades as f64) * 100.0;
             
-            println!("🔄 Position closed: {} - P&L: ${:.2} - Success Rate: {:.1}%", 
-                     position.symbol, simulated_profit, self.metrics.success_rate);
+            println!("🔄 Position closed: {} - P&L: {:.2} - Success Rate: {:.1}%", 
+                     position.symbol, profit, self.metrics.success_rate);
         }
         
         Ok(())
@@ -402,7 +410,7 @@ impl HFTTradingSystem {
             for action in actions {
                 // Get the anomaly that triggered this action (simulated)
                 let anomaly = DetectedAnomaly {
-                    id: format!("ANOMALY_{}", chrono::Utc::now().timestamp_millis()),
+                    id: AnomalyId::new(),
                     timestamp: chrono::Utc::now(),
                     anomaly_type: AnomalyType::VolatilitySpike {
                         expected_volatility: 0.01,
@@ -411,13 +419,14 @@ impl HFTTradingSystem {
                     severity: AnomalySeverity::High,
                     confidence: 0.9,
                     deviation_magnitude: 1.5,
-                    affected_symmetries: vec!["temporal_sym_1".to_string()],
-                    affected_cycles: vec!["cycle_1".to_string()],
+                    affected_symmetries: vec!["temporal_sym_1".into()],
+                    affected_cycles: vec!["cycle_1".into()],
                     market_context: MarketContext {
                         session: "London".to_string(),
                         volatility_regime: "High".to_string(),
                         trend_direction: "Bullish".to_string(),
                         recent_events: vec!["Economic data release".to_string()],
+                        event_proximity: 0.0,
                     },
                     trading_signal: Some(AnomalyTradingSignal {
                         signal_type: "Buy".to_string(),
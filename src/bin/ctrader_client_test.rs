@@ -0,0 +1,250 @@
+//! # cTrader Client Test
+//!
+//! Check the Open API wire format, that live sessions stay locked unless
+//! allowed, and run the client against a scripted in-process server:
+//! application and account authentication, symbol loading, spot quotes and
+//! market orders placed and closed from trading actions
+
+use anyhow::{ensure, Result};
+use std::sync::{Arc, Mutex};
+use tokio::io::DuplexStream;
+
+use forex_pattern_reconstruction::backtest::strategy::OrderSide;
+use forex_pattern_reconstruction::broker::ctrader::proto::{
+    self, execution, payload, Fields, ProtoMessage, Writer, PRICE_SCALE,
+};
+use forex_pattern_reconstruction::broker::ctrader::{CTraderClient, CTraderConfig};
+use forex_pattern_reconstruction::broker::{MarketOrder, TradingMode};
+use forex_pattern_reconstruction::laplacian_rl::TradingAction;
+
+const ACCOUNT_ID: i64 = 4_200_001;
+
+/// Requests the server received, as (payload type, payload)
+type Received = Arc<Mutex<Vec<(u32, Vec<u8>)>>>;
+
+fn config(mode: TradingMode, allow_live: bool) -> CTraderConfig {
+    CTraderConfig {
+        mode,
+        allow_live,
+        host: None,
+        port: 5035,
+        client_id: "client".to_string(),
+        client_secret: "secret".to_string(),
+        access_token: "token".to_string(),
+        account_id: ACCOUNT_ID,
+        units_per_size: 1_000.0,
+        request_timeout_ms: 2_000,
+    }
+}
+
+fn reply(payload_type: u32, payload: Vec<u8>, id: &Option<String>) -> ProtoMessage {
+    ProtoMessage { payload_type, payload, client_msg_id: id.clone() }
+}
+
+fn spot(symbol_id: i64, bid: Option<f64>, ask: Option<f64>) -> ProtoMessage {
+    let mut writer = Writer::default();
+    writer.int(2, ACCOUNT_ID).int(3, symbol_id);
+    if let Some(bid) = bid {
+        writer.uint(4, (bid * PRICE_SCALE).round() as u64);
+    }
+    if let Some(ask) = ask {
+        writer.uint(5, (ask * PRICE_SCALE).round() as u64);
+    }
+    writer.int(8, 1_700_000_000_000);
+    ProtoMessage::new(payload::SPOT_EVENT, writer.finish())
+}
+
+fn execution_event(execution_type: u64, order_id: i64, position_id: i64, volume: i64, price: f64, id: &Option<String>) -> ProtoMessage {
+    let deal = Writer::default().int(1, order_id * 10).int(2, order_id).int(3, position_id).int(5, volume).double(10, price).finish();
+    let event = Writer::default().int(2, ACCOUNT_ID).uint(3, execution_type).bytes(6, &deal).finish();
+    reply(payload::EXECUTION_EVENT, event, id)
+}
+
+/// Answer the client like the Open API proxy would
+async fn serve(mut stream: DuplexStream, received: Received) -> Result<()> {
+    let mut next_order = 0i64;
+    loop {
+        let Ok(request) = proto::read_frame(&mut stream).await else {
+            return Ok(());
+        };
+        received.lock().unwrap().push((request.payload_type, request.payload.clone()));
+        let fields = request.fields()?;
+        let id = &request.client_msg_id;
+        let mut replies = Vec::new();
+        match request.payload_type {
+            payload::APPLICATION_AUTH_REQ => {
+                if fields.string(3)?.as_deref() == Some("secret") {
+                    replies.push(reply(payload::APPLICATION_AUTH_RES, Vec::new(), id));
+                } else {
+                    let error = Writer::default().string(3, "CH_CLIENT_AUTH_FAILURE").string(4, "bad secret").finish();
+                    replies.push(reply(payload::OA_ERROR_RES, error, id));
+                }
+            }
+            payload::ACCOUNT_AUTH_REQ => replies.push(reply(payload::ACCOUNT_AUTH_RES, Writer::default().int(2, ACCOUNT_ID).finish(), id)),
+            payload::SYMBOLS_LIST_REQ => {
+                let mut list = Writer::default();
+                list.int(2, ACCOUNT_ID);
+                for (symbol_id, name, enabled) in [(1, "EURUSD", true), (2, "GBP/USD", true), (3, "XAUUSD", false)] {
+                    list.bytes(3, &Writer::default().int(1, symbol_id).string(2, name).bool(3, enabled).finish());
+                }
+                replies.push(reply(payload::SYMBOLS_LIST_RES, list.finish(), id));
+            }
+            payload::SUBSCRIBE_SPOTS_REQ => {
+                replies.push(ProtoMessage::new(payload::HEARTBEAT_EVENT, Vec::new()));
+                replies.push(reply(payload::SUBSCRIBE_SPOTS_RES, Vec::new(), id));
+                replies.push(spot(1, Some(1.08501), None));
+                replies.push(spot(1, None, Some(1.08512)));
+                replies.push(spot(2, Some(1.26400), Some(1.26415)));
+            }
+            payload::NEW_ORDER_REQ => {
+                let volume = fields.int(6).unwrap_or_default();
+                next_order += 1;
+                if volume > 100_000_000 {
+                    let error = Writer::default().string(2, "NOT_ENOUGH_MONEY").int(5, ACCOUNT_ID).string(7, "margin").finish();
+                    replies.push(reply(payload::ORDER_ERROR_EVENT, error, id));
+                } else {
+                    let position_id = 500 + next_order;
+                    replies.push(execution_event(execution::ORDER_ACCEPTED, next_order, position_id, 0, 0.0, id));
+                    replies.push(execution_event(execution::ORDER_PARTIAL_FILL, next_order, position_id, volume / 2, 1.0850, id));
+                    replies.push(spot(1, Some(1.08520), None));
+                    replies.push(execution_event(execution::ORDER_FILLED, next_order, position_id, volume - volume / 2, 1.0852, id));
+                }
+            }
+            payload::CLOSE_POSITION_REQ => {
+                let position_id = fields.int(3).unwrap_or_default();
+                replies.push(execution_event(execution::ORDER_FILLED, 900, position_id, fields.int(4).unwrap_or_default(), 1.0860, id));
+            }
+            _ => {}
+        }
+        for message in replies {
+            proto::write_frame(&mut stream, &message).await?;
+        }
+    }
+}
+
+/// Payload of the newest request the server received
+fn last_request(received: &Received) -> Vec<u8> {
+    received.lock().unwrap().last().map(|(_, payload)| payload.clone()).unwrap_or_default()
+}
+
+fn session(received: &Received) -> CTraderClient<DuplexStream> {
+    let (client, server) = tokio::io::duplex(1 << 16);
+    tokio::spawn(serve(server, received.clone()));
+    CTraderClient::over(client, config(TradingMode::Demo, false)).unwrap()
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 CTRADER CLIENT TEST");
+    println!("======================");
+    println!();
+
+    // Test 1: protobuf fields and frames round-trip
+    println!("📊 Test 1: Wire format");
+    let bytes = Writer::default().uint(1, 300).int(2, -5).double(3, 1.25).string(4, "EURUSD").bool(5, true).finish();
+    let fields = Fields::decode(&bytes)?;
+    ensure!(fields.uint(1) == Some(300) && fields.int(2) == Some(-5) && fields.double(3) == Some(1.25), "scalars");
+    ensure!(fields.string(4)?.as_deref() == Some("EURUSD") && fields.bool(5) == Some(true) && fields.uint(9).is_none(), "strings and absent fields");
+    let packed = Writer::default().bytes(3, &[1, 0x96, 0x01]).finish();
+    ensure!(Fields::decode(&packed)?.repeated_uint(3)? == [1, 150], "packed varints");
+    ensure!(Fields::decode(&[0x08, 0x80]).is_err(), "truncated varint is an error");
+    let (mut a, mut b) = tokio::io::duplex(1024);
+    let message = ProtoMessage { payload_type: payload::SPOT_EVENT, payload: bytes.clone(), client_msg_id: Some("x".to_string()) };
+    proto::write_frame(&mut a, &message).await?;
+    ensure!(proto::read_frame(&mut b).await? == message, "frame round trip");
+    println!("   ✅ Varints, doubles, strings, packed fields and frames");
+
+    // Test 2: live sessions are locked unless allowed
+    println!("📊 Test 2: Demo/live gating");
+    let (client, _server) = tokio::io::duplex(64);
+    ensure!(CTraderClient::over(client, config(TradingMode::Live, false)).is_err(), "live without permission must fail");
+    let (client, _server) = tokio::io::duplex(64);
+    let live = CTraderClient::over(client, config(TradingMode::Live, true))?;
+    ensure!(live.mode() == TradingMode::Live, "allowed live session");
+    ensure!(config(TradingMode::Demo, false).host() == "demo.ctraderapi.com" && config(TradingMode::Live, true).host() == "live.ctraderapi.com", "hosts");
+    ensure!("live".parse::<TradingMode>()? == TradingMode::Live && "paper".parse::<TradingMode>().is_err(), "mode parsing");
+    println!("   ✅ Live refused without CTRADER_ALLOW_LIVE, demo by default");
+
+    // Test 3: authentication
+    println!("📊 Test 3: Authentication");
+    let received = Received::default();
+    let mut client = session(&received);
+    ensure!(client.load_symbols().await.is_err(), "symbols need an authenticated session");
+    client.authenticate().await?;
+    ensure!(client.is_authenticated(), "authenticated");
+    {
+        let requests = received.lock().unwrap();
+        ensure!(requests.iter().map(|(t, _)| *t).eq([payload::APPLICATION_AUTH_REQ, payload::ACCOUNT_AUTH_REQ]), "application then account");
+        let account = Fields::decode(&requests[1].1)?;
+        ensure!(account.int(2) == Some(ACCOUNT_ID) && account.string(3)?.as_deref() == Some("token"), "account auth fields");
+    }
+    let (stream, server) = tokio::io::duplex(1 << 16);
+    tokio::spawn(serve(server, Received::default()));
+    let mut rejected = CTraderClient::over(stream, CTraderConfig { client_secret: "wrong".to_string(), ..config(TradingMode::Demo, false) })?;
+    let error = rejected.authenticate().await.unwrap_err();
+    ensure!(format!("{:#}", error).contains("CH_CLIENT_AUTH_FAILURE"), "error code surfaces: {:#}", error);
+    println!("   ✅ Application and account authenticated; bad secret reported");
+
+    // Test 4: symbols
+    println!("📊 Test 4: Symbols");
+    ensure!(client.load_symbols().await? == 2, "two enabled symbols");
+    ensure!(client.symbol_id("eurusd")? == 1 && client.symbol_id("GBPUSD")? == 2, "ids by normalized name");
+    ensure!(client.symbol_id("XAUUSD").is_err(), "disabled symbols are unavailable");
+    println!("   ✅ 2 enabled symbols, slash-free names");
+
+    // Test 5: spot quotes
+    println!("📊 Test 5: Spot subscription");
+    client.subscribe_spots(&["EURUSD".to_string(), "GBPUSD".to_string()]).await?;
+    let subscribe = Fields::decode(&last_request(&received))?.repeated_uint(3)?;
+    ensure!(subscribe == [1, 2], "subscribed ids {:?}", subscribe);
+    let first = client.next_tick().await?;
+    let second = client.next_tick().await?;
+    ensure!(first.symbol == "EURUSD" && (first.bid - 1.08501).abs() < 1e-9 && (first.ask - 1.08512).abs() < 1e-9, "EURUSD {:?}", first);
+    ensure!(second.symbol == "GBPUSD" && first.timestamp.timestamp_millis() == 1_700_000_000_000, "GBPUSD {:?}", second);
+    println!("   ✅ {} {:.5}/{:.5}, {} {:.5}/{:.5}", first.symbol, first.bid, first.ask, second.symbol, second.bid, second.ask);
+
+    // Test 6: a buy fills across partial fills
+    println!("📊 Test 6: Market order from a trading action");
+    let fills = client.execute_action("EURUSD", &TradingAction::Buy { size: 10 }).await?;
+    ensure!(fills.len() == 1, "one fill");
+    let fill = &fills[0];
+    ensure!(fill.units == 10_000.0 && fill.side == OrderSide::Buy && fill.position_id == 501, "fill {:?}", fill);
+    ensure!((fill.price - 1.0851).abs() < 1e-9, "volume-weighted price {}", fill.price);
+    let request = last_request(&received);
+    let order = Fields::decode(&request)?;
+    ensure!(order.int(3) == Some(1) && order.uint(4) == Some(1) && order.uint(5) == Some(1) && order.int(6) == Some(1_000_000), "order fields");
+    ensure!(client.open_positions("EURUSD").len() == 1, "position tracked");
+    let tick = client.next_tick().await?;
+    ensure!((tick.bid - 1.0852).abs() < 1e-9, "quote arriving mid-order is kept: {:?}", tick);
+    let protected = MarketOrder::new("GBPUSD", OrderSide::Sell, 2_000.0).with_protection(Some(0.0030), Some(0.0060)).with_label("anomaly");
+    client.place_market_order(&protected).await?;
+    let request = last_request(&received);
+    let order = Fields::decode(&request)?;
+    ensure!(order.uint(5) == Some(2) && order.int(19) == Some(300) && order.int(20) == Some(600), "relative protection in points");
+    ensure!(order.string(16)?.as_deref() == Some("anomaly"), "label");
+    println!("   ✅ {} units at {:.5}, protection and label sent", fill.units, fill.price);
+
+    // Test 7: holds send nothing, closes flatten
+    println!("📊 Test 7: Hold and close");
+    let sent = received.lock().unwrap().len();
+    ensure!(client.execute_action("EURUSD", &TradingAction::Hold).await?.is_empty(), "hold fills nothing");
+    ensure!(received.lock().unwrap().len() == sent, "hold sends no request");
+    let closed = client.execute_action("EURUSD", &TradingAction::ClosePosition).await?;
+    ensure!(closed.len() == 1 && closed[0].side == OrderSide::Sell && closed[0].units == 10_000.0, "closed {:?}", closed);
+    let request = last_request(&received);
+    let close = Fields::decode(&request)?;
+    ensure!(close.int(3) == Some(501) && close.int(4) == Some(1_000_000), "close request fields");
+    ensure!(client.open_positions("EURUSD").is_empty() && client.open_positions("GBPUSD").len() == 1, "positions after close");
+    println!("   ✅ Hold sent nothing, close flattened position 501");
+
+    // Test 8: order errors
+    println!("📊 Test 8: Rejected orders");
+    let error = client.execute_action("EURUSD", &TradingAction::Buy { size: 2_000 }).await.unwrap_err();
+    ensure!(error.to_string().contains("NOT_ENOUGH_MONEY"), "order error surfaces: {}", error);
+    ensure!(client.execute_action("XAUUSD", &TradingAction::Buy { size: 1 }).await.is_err(), "unknown symbol");
+    println!("   ✅ {}", error);
+
+    println!();
+    println!("🎉 All cTrader client tests passed");
+    Ok(())
+}
@@ -0,0 +1,142 @@
+//! # Currency Bloc Test
+//!
+//! Check clustering the correlation matrix of factor-driven pairs finds the
+//! USD, EUR and commodity blocs, with linkage distances worked by hand
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rand_distr::StandardNormal;
+use std::collections::HashMap;
+
+use forex_pattern_reconstruction::correlation::{
+    ClusterConfig, ClusterReport, CorrelationResult, CorrelationStrength, CrossPairAnalyzer, Linkage,
+};
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::units::PriceDelta;
+
+const BARS: usize = 500;
+const PAIRS: [&str; 7] = ["USDJPY", "USDCHF", "EURGBP", "EURCHF", "AUDJPY", "NZDJPY", "CADJPY"];
+
+/// Hourly closes of `PAIRS`, each moving by its base currency's return less its quote's
+fn series(seed: u64) -> HashMap<String, Vec<ForexDataPoint>> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let mut prices: HashMap<&str, f64> = PAIRS.iter().map(|pair| (*pair, 1.0)).collect();
+    let mut data: HashMap<String, Vec<ForexDataPoint>> = HashMap::new();
+    for i in 0..BARS {
+        let commodity = rng.sample::<f64, _>(StandardNormal);
+        let mut currency = HashMap::new();
+        currency.insert("USD", rng.sample::<f64, _>(StandardNormal));
+        currency.insert("EUR", rng.sample::<f64, _>(StandardNormal));
+        for name in ["AUD", "NZD", "CAD"] {
+            currency.insert(name, commodity + 0.3 * rng.sample::<f64, _>(StandardNormal));
+        }
+        for name in ["GBP", "CHF", "JPY"] {
+            currency.insert(name, 0.2 * rng.sample::<f64, _>(StandardNormal));
+        }
+        for pair in PAIRS {
+            let price = prices.get_mut(pair).unwrap();
+            if i > 0 {
+                *price *= 1.0 + 0.001 * (currency[&pair[..3]] - currency[&pair[3..]]);
+            }
+            data.entry(pair.to_string()).or_default().push(ForexDataPoint {
+                timestamp: start + Duration::hours(i as i64),
+                open: *price,
+                high: *price,
+                low: *price,
+                close: *price,
+                volume: None,
+            });
+        }
+    }
+    data
+}
+
+fn result(pair1: &str, pair2: &str, correlation: f64) -> ((String, String), CorrelationResult) {
+    ((pair1.to_string(), pair2.to_string()), CorrelationResult {
+        pair1: pair1.to_string(),
+        pair2: pair2.to_string(),
+        correlation,
+        strength: CorrelationStrength::Moderate,
+        arbitrage_potential: PriceDelta(0.0),
+    })
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 CURRENCY BLOC TEST");
+    println!("=====================");
+    println!();
+
+    let analyzer = CrossPairAnalyzer::new();
+    let correlations = analyzer.calculate_correlation_matrix(&series(5))?;
+
+    // Test 1: the three blocs are found and named
+    println!("📊 Test 1: blocs");
+    let report = analyzer.cluster_pairs(&correlations, &ClusterConfig::default())?;
+    ensure!(report.pairs.len() == PAIRS.len() && report.merges.len() == PAIRS.len() - 1, "7 leaves, 6 merges");
+    let names: Vec<&str> = report.blocs.iter().map(|bloc| bloc.name.as_str()).collect();
+    ensure!(names == ["commodity currencies", "USD bloc", "EUR bloc"] || names == ["commodity currencies", "EUR bloc", "USD bloc"], "blocs {:?}", names);
+    let members = |name: &str| {
+        let mut pairs = report.blocs.iter().find(|bloc| bloc.name == name).unwrap().pairs.clone();
+        pairs.sort();
+        pairs
+    };
+    ensure!(members("USD bloc") == ["USDCHF", "USDJPY"], "USD bloc {:?}", members("USD bloc"));
+    ensure!(members("EUR bloc") == ["EURCHF", "EURGBP"], "EUR bloc {:?}", members("EUR bloc"));
+    ensure!(members("commodity currencies") == ["AUDJPY", "CADJPY", "NZDJPY"], "commodity {:?}", members("commodity currencies"));
+    ensure!(report.blocs.iter().all(|bloc| bloc.mean_correlation > 0.85), "tight blocs");
+    ensure!(report.bloc_of("audjpy").map(|bloc| bloc.name.as_str()) == Some("commodity currencies"), "lookup is case-insensitive");
+    for bloc in &report.blocs {
+        println!("   ✅ {} (mean ρ {:.2}): {}", bloc.name, bloc.mean_correlation, bloc.pairs.join(", "));
+    }
+
+    // Test 2: single and average linkage against hand-worked distances
+    println!("📊 Test 2: linkage");
+    let small: HashMap<_, _> = [result("EURUSD", "GBPUSD", 0.9), result("EURUSD", "AUDUSD", 0.2), result("GBPUSD", "AUDUSD", 0.6)].into_iter().collect();
+    let single = analyzer.cluster_pairs(&small, &ClusterConfig { linkage: Linkage::Single, cut_distance: 0.5 })?;
+    let average = analyzer.cluster_pairs(&small, &ClusterConfig { linkage: Linkage::Average, cut_distance: 0.5 })?;
+    let heights = |report: &ClusterReport| report.merges.iter().map(|merge| merge.distance).collect::<Vec<f64>>();
+    let close = |a: &[f64], b: &[f64]| a.len() == b.len() && a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-9);
+    ensure!(close(&heights(&single), &[0.1, 0.4]), "single merges at {:?}", heights(&single));
+    ensure!(close(&heights(&average), &[0.1, 0.6]), "average merges at {:?}", heights(&average));
+    ensure!(single.blocs.len() == 1 && average.blocs.len() == 2, "AUDUSD chained in by single linkage only");
+    ensure!(average.blocs[0].name == "USD bloc" && average.blocs[1].name == "AUDUSD", "blocs {:?}", average.blocs);
+    ensure!(single.joined_at("EURUSD", "AUDUSD").map(|d| (d - 0.4).abs() < 1e-9) == Some(true), "cophenetic distance");
+    ensure!(single.merges[1].size == 3 && (single.merges[1].left == 3 || single.merges[1].right == 3), "second merge takes the first cluster");
+    let full = analyzer.cluster_pairs(&correlations, &ClusterConfig { linkage: Linkage::Single, ..ClusterConfig::default() })?;
+    ensure!(full.merges.iter().zip(&report.merges).all(|(s, a)| s.distance <= a.distance + 1e-12), "single linkage never merges later than average");
+    println!("   ✅ Single {:?}, average {:?}", heights(&single), heights(&average));
+
+    // Test 3: inverse and missing correlations
+    println!("📊 Test 3: distances");
+    let inverse: HashMap<_, _> = [result("EURUSD", "USDCHF", -0.95), result("EURUSD", "GBPUSD", 0.8)].into_iter().collect();
+    let report_inverse = analyzer.cluster_pairs(&inverse, &ClusterConfig::default())?;
+    ensure!(report_inverse.joined_at("EURUSD", "GBPUSD").map(|d| (d - 0.2).abs() < 1e-9) == Some(true), "ρ 0.8 is distance 0.2");
+    ensure!(report_inverse.merges[1].distance > 1.0, "a mirror image is far apart: {}", report_inverse.merges[1].distance);
+    ensure!(report_inverse.blocs.len() == 2, "mirror images are separate blocs");
+    ensure!(average.joined_at("EURUSD", "EURUSD") == Some(0.0) && average.joined_at("EURUSD", "NZDUSD").is_none(), "self and unknown pairs");
+    let empty = analyzer.cluster_pairs(&HashMap::new(), &ClusterConfig::default())?;
+    ensure!(empty.blocs.is_empty() && empty.leaf_order().is_empty(), "nothing to cluster");
+    ensure!(analyzer.cluster_pairs(&small, &ClusterConfig { cut_distance: -1.0, ..ClusterConfig::default() }).is_err(), "negative cut accepted");
+    ensure!("Single".parse::<Linkage>()? == Linkage::Single && "ward".parse::<Linkage>().is_err(), "linkage names");
+    println!("   ✅ Mirror images {:.2} apart; empty matrices and bad cuts handled", report_inverse.merges[1].distance);
+
+    // Test 4: the dendrogram table keeps blocs together
+    println!("📊 Test 4: dendrogram table");
+    let order = report.leaf_order();
+    for bloc in &report.blocs {
+        let positions: Vec<usize> = bloc.pairs.iter().map(|pair| order.iter().position(|p| p == pair).unwrap()).collect();
+        ensure!(positions.windows(2).all(|w| w[1] == w[0] + 1), "{} is contiguous in {:?}", bloc.name, order);
+    }
+    let table = report.render();
+    ensure!(table.lines().count() == PAIRS.len() + 5, "header, rows and footer");
+    ensure!(PAIRS.iter().all(|pair| table.contains(pair)) && table.contains("commodity currencies"), "every pair and bloc shown");
+    analyzer.print_cluster_report(&report);
+
+    println!();
+    println!("🎉 All currency bloc tests passed");
+    Ok(())
+}
@@ -0,0 +1,90 @@
+//! # Cycle Detection Test
+//!
+//! Plant known cycles in noisy synthetic prices and check that the spectral
+//! detector finds them on both evenly spaced (FFT) and weekday-only (Lomb-Scargle)
+//! series, while a plain random walk produces no confident cycles
+
+use anyhow::{ensure, Result};
+use chrono::{DateTime, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::patterns::{PatternConfig, PatternRecognizer};
+use forex_pattern_reconstruction::synthetic::fixtures::{hourly_timestamps, weekday_timestamps};
+
+/// Random-walk prices with sinusoids `(period in bars, relative amplitude)` on top
+fn synthetic_series(timestamps: &[DateTime<Utc>], cycles: &[(f64, f64)], seed: u64) -> Vec<ForexDataPoint> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let spacing = (timestamps[1] - timestamps[0]).num_seconds() as f64;
+    let mut walk = 0.0;
+    timestamps.iter()
+        .map(|timestamp| {
+            walk += rng.gen_range(-1.0..1.0) * 0.002;
+            let t = timestamp.timestamp() as f64 / spacing;
+            let cycle: f64 = cycles.iter()
+                .map(|(period, amplitude)| amplitude * (2.0 * std::f64::consts::PI * t / period).sin())
+                .sum();
+            let close = 1.10 * (1.0 + walk + cycle);
+            ForexDataPoint { timestamp: *timestamp, open: close, high: close, low: close, close, volume: None }
+        })
+        .collect()
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 CYCLE DETECTION TEST");
+    println!("=======================");
+    println!();
+
+    let mut recognizer = PatternRecognizer::new(PatternConfig {
+        min_cycle_length: 5,
+        max_cycle_length: 200,
+        confidence_threshold: 0.95,
+        significance: None,
+        wavelet: None,
+    })?;
+
+    // Test 1: evenly spaced hourly bars (FFT path)
+    println!("📊 Test 1: Hourly bars with a 24-bar cycle");
+    let data = synthetic_series(&hourly_timestamps(2048), &[(24.0, 0.004)], 1);
+    let cycles = recognizer.detect_cycles(&data).await?;
+    for cycle in &cycles {
+        println!("   {} period={} confidence={:.3} amplitude={:.4}", cycle.name, cycle.period, cycle.confidence, cycle.amplitude);
+    }
+    let daily = cycles.iter().find(|c| c.period == 24).ok_or_else(|| anyhow::anyhow!("24-bar cycle not found"))?;
+    ensure!((daily.amplitude - 0.004).abs() < 0.002, "amplitude {:.4} far from 0.004", daily.amplitude);
+    println!("✅ Found the 24-bar cycle");
+
+    // Test 2: weekday-only daily bars (Lomb-Scargle path), two planted cycles
+    println!("📊 Test 2: Daily bars with weekend gaps");
+    let data = synthetic_series(&weekday_timestamps(1500), &[(30.0, 0.01), (91.0, 0.02)], 2);
+    let cycles = recognizer.detect_cycles(&data).await?;
+    for cycle in &cycles {
+        println!("   {} period={} confidence={:.3} amplitude={:.4}", cycle.name, cycle.period, cycle.confidence, cycle.amplitude);
+    }
+    ensure!(cycles.iter().any(|c| c.period.abs_diff(30) <= 1), "30-day cycle not found");
+    ensure!(cycles.iter().any(|c| c.period.abs_diff(91) <= 3), "91-day cycle not found");
+    ensure!(cycles.len() == 2, "expected only the planted cycles, got {}", cycles.len());
+    println!("✅ Found the monthly and quarterly cycles");
+
+    // Test 3: no planted cycle, nothing confident
+    println!("📊 Test 3: Pure random walk");
+    let data = synthetic_series(&hourly_timestamps(2048), &[], 3);
+    let cycles = recognizer.detect_cycles(&data).await?;
+    ensure!(cycles.is_empty(), "random walk produced {} cycles", cycles.len());
+    println!("✅ No cycles reported");
+
+    // Test 4: configured bounds are respected
+    println!("📊 Test 4: Cycle length bounds");
+    let mut bounded = PatternRecognizer::new(PatternConfig { min_cycle_length: 40, max_cycle_length: 200, confidence_threshold: 0.95, significance: None, wavelet: None })?;
+    let data = synthetic_series(&weekday_timestamps(1500), &[(30.0, 0.01), (91.0, 0.02)], 2);
+    let cycles = bounded.detect_cycles(&data).await?;
+    ensure!(cycles.iter().all(|c| (40..=200).contains(&c.period)), "cycle outside the configured bounds");
+    ensure!(!cycles.iter().any(|c| c.period.abs_diff(30) <= 1), "30-day cycle reported below min_cycle_length");
+    println!("✅ {} cycles, all within 40..=200 bars", cycles.len());
+
+    println!();
+    println!("🎉 All cycle detection tests passed");
+    Ok(())
+}
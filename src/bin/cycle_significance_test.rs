@@ -0,0 +1,138 @@
+//! # Cycle Significance Test
+//!
+//! Check surrogate-data tests give planted cycles small p-values and
+//! cycle-free random walks none, reproducibly, on regular and weekday-only bars
+
+use anyhow::{ensure, Result};
+use chrono::{DateTime, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::patterns::{HiddenCycle, PatternConfig, PatternRecognizer, SignificanceConfig, SurrogateMethod};
+use forex_pattern_reconstruction::synthetic::fixtures::{hourly_timestamps, weekday_timestamps};
+
+/// Random-walk prices with sinusoids `(period in bars, relative amplitude)` on top;
+/// the step size follows a slowly switching volatility regime
+fn synthetic_series(timestamps: &[DateTime<Utc>], cycles: &[(f64, f64)], seed: u64) -> Vec<ForexDataPoint> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let spacing = (timestamps[1] - timestamps[0]).num_seconds() as f64;
+    let (mut walk, mut volatility) = (0.0, 0.002);
+    timestamps.iter()
+        .map(|timestamp| {
+            if rng.gen_bool(0.02) {
+                volatility = if volatility > 0.002 { 0.002 } else { 0.005 };
+            }
+            walk += rng.gen_range(-1.0..1.0) * volatility;
+            let t = timestamp.timestamp() as f64 / spacing;
+            let cycle: f64 = cycles.iter()
+                .map(|(period, amplitude)| amplitude * (2.0 * std::f64::consts::PI * t / period).sin())
+                .sum();
+            let close = 1.10 * (1.0 + walk + cycle);
+            ForexDataPoint { timestamp: *timestamp, open: close, high: close, low: close, close, volume: None }
+        })
+        .collect()
+}
+
+fn recognizer(confidence_threshold: f64, significance: SignificanceConfig) -> Result<PatternRecognizer> {
+    PatternRecognizer::new(PatternConfig {
+        min_cycle_length: 5,
+        max_cycle_length: 200,
+        confidence_threshold,
+        significance: Some(significance),
+        wavelet: None,
+    })
+}
+
+fn describe(cycles: &[HiddenCycle]) {
+    for cycle in cycles {
+        println!("   {} period={} confidence={:.3} p={:.3}", cycle.name, cycle.period, cycle.confidence, cycle.p_value.unwrap_or(f64::NAN));
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 Cycle Significance Test");
+    println!("==========================");
+    println!();
+
+    let phase = SignificanceConfig::default();
+    let bootstrap = SignificanceConfig { method: SurrogateMethod::BlockBootstrap, ..SignificanceConfig::default() };
+    let smallest = 1.0 / (phase.surrogates + 1) as f64;
+
+    // Test 1: a planted cycle beats phase-randomized surrogates
+    println!("📊 Test 1: Phase-randomized surrogates");
+    let data = synthetic_series(&hourly_timestamps(2048), &[(24.0, 0.004)], 1);
+    let cycles = recognizer(0.95, phase.clone())?.detect_cycles(&data).await?;
+    describe(&cycles);
+    let daily = cycles.iter().find(|c| c.period == 24).ok_or_else(|| anyhow::anyhow!("24-bar cycle not found"))?;
+    ensure!(daily.p_value == Some(smallest), "24-bar cycle p-value {:?}", daily.p_value);
+    ensure!(cycles.iter().all(|c| c.p_value.is_some()), "every detected cycle is tested");
+    println!("   ✅ 24-bar cycle p = {:.3}, stronger than all {} surrogates", smallest, phase.surrogates);
+
+    // Test 2: and block-bootstrapped ones
+    println!("📊 Test 2: Block-bootstrapped surrogates");
+    let cycles = recognizer(0.95, bootstrap.clone())?.detect_cycles(&data).await?;
+    describe(&cycles);
+    let daily = cycles.iter().find(|c| c.period == 24).ok_or_else(|| anyhow::anyhow!("24-bar cycle not found"))?;
+    ensure!(daily.p_value.is_some_and(|p| p <= bootstrap.alpha), "24-bar cycle p-value {:?}", daily.p_value);
+    println!("   ✅ 24-bar cycle p = {:.3} against bootstrapped blocks", daily.p_value.unwrap_or_default());
+
+    // Test 3: cycle-free random walks with clustered volatility
+    println!("📊 Test 3: No planted cycles");
+    let (mut tested, mut significant, mut confident) = (0, 0, 0);
+    let mut p_values = Vec::new();
+    for seed in 10..15 {
+        let noise = synthetic_series(&hourly_timestamps(2048), &[], seed);
+        for significance in [phase.clone(), bootstrap.clone()] {
+            let cycles = recognizer(0.0, significance.clone())?.detect_cycles(&noise).await?;
+            tested += cycles.len();
+            significant += cycles.iter().filter(|c| c.p_value.is_some_and(|p| p <= significance.alpha)).count();
+            confident += cycles.iter().filter(|c| c.confidence >= 0.75).count();
+            p_values.extend(cycles.iter().filter_map(|c| c.p_value));
+        }
+    }
+    p_values.sort_by(|a, b| a.total_cmp(b));
+    let median = p_values[p_values.len() / 2];
+    ensure!(significant <= 2, "{} of {} noise peaks significant", significant, tested);
+    ensure!(median > 0.5, "median noise p-value {:.3}", median);
+    println!("   ✅ {} of {} noise peaks significant (median p {:.2}); {} had confidence ≥ 0.75", significant, tested, median, confident);
+
+    // Test 4: weekday bars go through Lomb-Scargle
+    println!("📊 Test 4: Weekday bars");
+    let data = synthetic_series(&weekday_timestamps(1500), &[(30.0, 0.01), (91.0, 0.02)], 2);
+    let quick = SignificanceConfig { surrogates: 30, ..bootstrap.clone() };
+    let cycles = recognizer(0.95, quick.clone())?.detect_cycles(&data).await?;
+    describe(&cycles);
+    let planted = cycles.iter().filter(|c| c.period.abs_diff(30) <= 1 || c.period.abs_diff(91) <= 3).collect::<Vec<_>>();
+    ensure!(planted.len() == 2 && planted.iter().all(|c| c.p_value.is_some_and(|p| p <= quick.alpha)), "planted cycles not significant");
+    println!("   ✅ Monthly and quarterly cycles significant on gapped bars");
+
+    // Test 5: reproducible, optional and reusable on saved cycles
+    println!("📊 Test 5: Reproducibility");
+    let data = synthetic_series(&hourly_timestamps(2048), &[(24.0, 0.004), (12.0, 0.002)], 4);
+    let first = recognizer(0.5, phase.clone())?.detect_cycles(&data).await?;
+    describe(&first);
+    ensure!(first.len() >= 2, "{} cycles detected", first.len());
+    let second = recognizer(0.5, phase.clone())?.detect_cycles(&data).await?;
+    ensure!(first.iter().zip(&second).all(|(a, b)| a.p_value == b.p_value), "same seed, different p-values");
+    let reseeded = recognizer(0.5, SignificanceConfig { seed: 7, ..phase.clone() })?.detect_cycles(&data).await?;
+    ensure!(first.iter().zip(&reseeded).any(|(a, b)| a.p_value != b.p_value) || first.iter().all(|c| c.p_value == Some(smallest)),
+            "the seed changes the surrogates");
+    let mut plain = PatternRecognizer::new(PatternConfig { min_cycle_length: 5, max_cycle_length: 200, confidence_threshold: 0.5, significance: None, wavelet: None })?;
+    let untested = plain.detect_cycles(&data).await?;
+    ensure!(untested.iter().all(|c| c.p_value.is_none()) && untested.len() == first.len(), "significance changed detection");
+    let results = plain.cycle_significance(&data, &untested, &phase)?;
+    ensure!(results.len() == untested.len(), "{} results for {} cycles", results.len(), untested.len());
+    ensure!(results.iter().zip(&first).all(|(r, c)| Some(r.p_value) == c.p_value && r.significant == (r.p_value <= phase.alpha)),
+            "testing saved cycles matches testing on detection");
+    let saved: HiddenCycle = serde_json::from_str(r#"{"name":"24-Bar Cycle","period":24,"confidence":0.9,"amplitude":0.002,"phase":0.0}"#)?;
+    ensure!(saved.p_value.is_none(), "cycles saved before the test load untested");
+    let config: PatternConfig = serde_json::from_str(r#"{"min_cycle_length":2,"max_cycle_length":365,"confidence_threshold":0.75,"significance":{"method":"block_bootstrap"}}"#)?;
+    ensure!(config.significance.is_some_and(|s| s.method == SurrogateMethod::BlockBootstrap && s.surrogates == 100), "significance settings load over defaults");
+    println!("   ✅ {} cycles: same p-values with the same seed, on detection or afterwards", first.len());
+
+    println!();
+    println!("🎉 All cycle significance tests passed");
+    Ok(())
+}
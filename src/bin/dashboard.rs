@@ -14,10 +14,14 @@ use ratatui::{
     Terminal,
 };
 use std::io;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::time::interval;
 
 use forex_pattern_reconstruction::dashboard::{DashboardApp, render_dashboard};
+use forex_pattern_reconstruction::data::{DataConfig, MissingDataPolicy};
+use forex_pattern_reconstruction::data::provider::provider_from_env;
+use forex_pattern_reconstruction::multi_currency::MultiCurrencyManager;
 
 /// ASCII Art Banner
 const BANNER: &str = r#"
@@ -55,7 +59,7 @@ async fn main() -> Result<()> {
                 .short('p')
                 .long("pair")
                 .value_name("PAIR")
-                .help("Currency pair to analyze (default: EURUSD)")
+                .help("Currency pairs to analyze, comma-separated (default: EURUSD)")
                 .default_value("EURUSD")
         )
         .arg(
@@ -66,6 +70,13 @@ async fn main() -> Result<()> {
                 .help("Directory containing forex data")
                 .default_value("FOREX DATA")
         )
+        .arg(
+            Arg::new("on-missing-data")
+                .long("on-missing-data")
+                .value_name("POLICY")
+                .help("When historical data is missing: prompt, demo, feed-only or fail")
+                .default_value("prompt")
+        )
         .arg(
             Arg::new("update-interval")
                 .short('u')
@@ -84,8 +95,32 @@ async fn main() -> Result<()> {
     println!();
     
     // Initialize dashboard
-    let mut app = DashboardApp::new().await?;
+    let data_config = DataConfig {
+        data_directory: matches.get_one::<String>("data-dir").unwrap().into(),
+        missing_data_policy: matches.get_one::<String>("on-missing-data").unwrap().parse::<MissingDataPolicy>()?,
+        ..DataConfig::default()
+    };
+    let pairs: Vec<String> = matches.get_one::<String>("pair").unwrap()
+        .split(',')
+        .map(|pair| pair.trim().to_uppercase())
+        .filter(|pair| !pair.is_empty())
+        .collect();
+    let mut manager = MultiCurrencyManager::new().with_data_config(data_config);
+    
+    // Live quotes when DATA_PROVIDER is configured, the history alone otherwise
+    if let Some(provider) = provider_from_env()? {
+        println!("📡 Streaming live quotes from {}", provider.name());
+        manager = manager.with_data_provider(provider);
+    }
+    manager.initialize_pairs(&pairs).await?;
+    
+    let manager = Arc::new(manager);
+    let mut app = DashboardApp::with_manager(Arc::clone(&manager)).await?;
+    app.drive_updates(true);
     app.initialize().await?;
+    if manager.data_provider.is_some() {
+        manager.start_live_feed()?;
+    }
     
     println!("✅ Dashboard initialized successfully!");
     println!("🎯 Press any key to start the real-time dashboard...");
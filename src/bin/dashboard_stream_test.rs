@@ -0,0 +1,125 @@
+//! # Dashboard Stream Test
+//!
+//! Serve the dashboard in-process and follow it with the controller's client:
+//! prices and anomalies arrive over `/ws/prices` and `/ws/anomalies`, each
+//! anomaly once, and the controller API answers on the same port
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+use forex_pattern_reconstruction::anomaly::{AnomalySeverity, AnomalyType, DetectedAnomaly, MarketContext};
+use forex_pattern_reconstruction::dashboard::server::{publish_updates, routes, StreamHub};
+use forex_pattern_reconstruction::data::provider::Tick;
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::multi_currency::MultiCurrencyManager;
+use forex_pattern_reconstruction::protocol::client::ControllerClient;
+use forex_pattern_reconstruction::protocol::server::ApiState;
+use forex_pattern_reconstruction::protocol::{RemoteSystemStatus, SystemMetrics};
+
+fn breakdown() -> DetectedAnomaly {
+    DetectedAnomaly {
+        id: "test".into(),
+        timestamp: Utc::now(),
+        anomaly_type: AnomalyType::SymmetryBreakdown { symmetry_id: "weekly_sym".into(), expected_strength: 0.9, actual_strength: 0.4 },
+        severity: AnomalySeverity::Medium,
+        confidence: 0.9,
+        deviation_magnitude: 0.01,
+        affected_symmetries: vec!["weekly_sym".into()],
+        affected_cycles: Vec::new(),
+        market_context: MarketContext {
+            session: "London".to_string(),
+            volatility_regime: "Normal".to_string(),
+            trend_direction: "Sideways".to_string(),
+            recent_events: Vec::new(),
+            event_proximity: 0.0,
+        },
+        trading_signal: None,
+    }
+}
+
+async fn next(stream: &mut mpsc::Receiver<serde_json::Value>) -> Result<serde_json::Value> {
+    tokio::time::timeout(std::time::Duration::from_secs(5), stream.recv())
+        .await
+        .map_err(|_| anyhow::anyhow!("no message within 5s"))?
+        .ok_or_else(|| anyhow::anyhow!("stream closed"))
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 DASHBOARD STREAM TEST");
+    println!("========================");
+    println!();
+
+    let mut manager = MultiCurrencyManager::new();
+    manager.initialize_major_pairs().await?;
+    manager.active_pairs.retain(|pair| pair == "EURUSD");
+    {
+        let mut pairs = manager.pairs.write().await;
+        pairs.retain(|pair, _| pair == "EURUSD");
+        let state = pairs.get_mut("EURUSD").expect("pair initialized");
+        let close = 1.1000;
+        state.historical_data = vec![ForexDataPoint { timestamp: Utc::now() - Duration::hours(1), open: close, high: close, low: close, close, volume: None }];
+        state.last_tick = Some(Tick { symbol: "EURUSD".to_string(), timestamp: Utc::now(), bid: 1.1010, ask: 1.1012 });
+    }
+    let manager = Arc::new(manager);
+    let status = RemoteSystemStatus {
+        status: "running".to_string(),
+        uptime: 0,
+        active_pairs: manager.active_pairs.clone(),
+        total_trades: 0,
+        profit_loss: 0.0,
+        correlation_opportunities: Vec::new(),
+        system_metrics: SystemMetrics::default(),
+        feed_health: None,
+    };
+    let hub = StreamHub::new();
+    let (address, serving) = warp::serve(routes(ApiState::new(manager.clone(), status), hub.clone()))
+        .bind_ephemeral(([127, 0, 0, 1], 0));
+    tokio::spawn(serving);
+    let client = ControllerClient::new(&format!("http://{}", address));
+    println!("🌐 Dashboard listening on {}", client.endpoint());
+
+    // Test 1: the controller API is served next to the streams
+    println!("📊 Test 1: Controller API");
+    ensure!(client.health().await?, "health check failed");
+    ensure!(client.fetch_status().await?.active_pairs == ["EURUSD"], "status not served");
+    ensure!(client.fetch_portfolio().await?.positions.is_empty(), "portfolio not served");
+    println!("   ✅ /api/status and /api/portfolio answered");
+
+    // Test 2: price updates carry the live quote
+    println!("📊 Test 2: Price stream");
+    let mut prices = client.subscribe("prices").await?;
+    let mut anomalies = client.subscribe("anomalies").await?;
+    let (mut seen, mut epoch) = (HashMap::new(), 0);
+    publish_updates(&manager, &hub, &mut seen, &mut epoch).await;
+    let price = next(&mut prices).await?;
+    ensure!(price["symbol"] == "EURUSD" && price["bid"] == 1.1010 && price["ask"] == 1.1012, "price update {}", price);
+    ensure!((price["price"].as_f64().unwrap_or_default() - 1.1011).abs() < 1e-9, "price is the mid: {}", price);
+    println!("   ✅ EURUSD {}", price["price"]);
+
+    // Test 3: each detected anomaly is streamed once, with its pattern
+    println!("📊 Test 3: Anomaly stream");
+    {
+        let mut pairs = manager.pairs.write().await;
+        let state = pairs.get_mut("EURUSD").expect("pair initialized");
+        state.recent_anomalies.push(breakdown());
+        state.performance.anomalies_detected += 1;
+    }
+    publish_updates(&manager, &hub, &mut seen, &mut epoch).await;
+    let event = next(&mut anomalies).await?;
+    ensure!(event["kind"] == "anomaly" && event["symbol"] == "EURUSD", "anomaly event {}", event);
+    ensure!(event["pattern_id"] == "weekly_sym" && event["anomaly_type"] == "SymmetryBreakdown", "pattern named: {}", event);
+    publish_updates(&manager, &hub, &mut seen, &mut epoch).await;
+    for _ in 0..2 {
+        next(&mut prices).await?;
+    }
+    ensure!(tokio::time::timeout(std::time::Duration::from_millis(300), anomalies.recv()).await.is_err(), "an anomaly was streamed twice");
+    println!("   ✅ {}/{} streamed once", event["pattern_id"], event["anomaly_type"]);
+
+    println!();
+    println!("🎉 All dashboard stream tests passed");
+    Ok(())
+}
@@ -0,0 +1,100 @@
+//! # Data Provider Test
+//!
+//! Fold ticks into bars, fetch a time range from the CSV provider and backfill a
+//! pair from it, and route live ticks into a pair's history and price
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use forex_pattern_reconstruction::data::provider::{timeframe_duration, BarAggregator, ChannelProvider, CsvProvider, DataProvider, Tick};
+use forex_pattern_reconstruction::data::{DataConfig, ForexDataManager};
+use forex_pattern_reconstruction::multi_currency::{CurrencyPairConfig, CurrencyPairState};
+use forex_pattern_reconstruction::synthetic::fixtures::hourly_walk;
+
+fn tick(seconds: i64, mid: f64) -> Tick {
+    let start = Utc.with_ymd_and_hms(2024, 3, 5, 12, 0, 0).unwrap();
+    Tick { symbol: "EURUSD".to_string(), timestamp: start + Duration::seconds(seconds), bid: mid - 0.00005, ask: mid + 0.00005 }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 DATA PROVIDER TEST");
+    println!("=====================");
+    println!();
+
+    // Test 1: timeframe names
+    println!("📊 Test 1: Timeframes");
+    ensure!(timeframe_duration("H1")? == Duration::hours(1) && timeframe_duration("1h")? == Duration::hours(1), "hourly");
+    ensure!(timeframe_duration("D1")? == Duration::days(1) && timeframe_duration("M15")? == Duration::minutes(15), "daily and quarter-hour");
+    ensure!(timeframe_duration("H3").is_err(), "unsupported timeframes are errors");
+    println!("   ✅ Names mapped, unknown rejected");
+
+    // Test 2: ticks fold into mid-price bars
+    println!("📊 Test 2: Bar aggregation");
+    let mut bars = BarAggregator::new(Duration::minutes(1));
+    for (seconds, mid) in [(5, 1.1000), (20, 1.1010), (40, 1.0995), (55, 1.1003)] {
+        ensure!(bars.push(&tick(seconds, mid)).is_none(), "a bar closed mid-minute");
+    }
+    let bar = bars.push(&tick(61, 1.1004)).expect("the next minute closes the bar");
+    ensure!(bar.timestamp == tick(0, 0.0).timestamp, "bar starts on the minute");
+    let ohlc = [bar.open, bar.high, bar.low, bar.close];
+    ensure!(ohlc.iter().zip([1.1000, 1.1010, 1.0995, 1.1003]).all(|(a, b)| (a - b).abs() < 1e-9) && bar.volume == Some(4.0), "bar {:?}", bar);
+    ensure!(bars.push(&tick(30, 1.2)).is_none() && bars.current().is_some_and(|bar| (bar.high - 1.1004).abs() < 1e-9), "late ticks are dropped");
+    println!("   ✅ OHLC of mids, tick count as volume, late ticks dropped");
+
+    // Test 3: the CSV provider serves the requested range and has no ticks
+    println!("📊 Test 3: CSV provider");
+    let dir = std::env::temp_dir().join(format!("data-provider-test-{}", std::process::id()));
+    let start = Utc.with_ymd_and_hms(2024, 3, 4, 0, 0, 0).unwrap();
+    let history = hourly_walk(&mut StdRng::seed_from_u64(9), start, 48);
+    ForexDataManager::new(DataConfig::default())?.save_csv_file(&dir.join("EURUSD.csv"), &history)?;
+    let provider = CsvProvider::new(dir.clone())?;
+    let result = async {
+        let range = provider.fetch_historical("EURUSD", "H1", start + Duration::hours(10), start + Duration::hours(19)).await?;
+        ensure!(range.len() == 10 && range[0].timestamp == history[10].timestamp, "fetched {} bars for a 10-hour range", range.len());
+        ensure!(provider.fetch_historical("GBPUSD", "H1", start, start + Duration::hours(47)).await.is_err(), "a missing pair is an error");
+        ensure!(provider.subscribe_ticks(&["EURUSD".to_string()]).is_err(), "the CSV provider has no live ticks");
+        println!("   ✅ Range filtered, missing pair and ticks refused");
+
+        // Test 4: a pair backfills the bars after its history from the provider
+        println!("📊 Test 4: Provider backfill");
+        let mut pair = CurrencyPairState::new(CurrencyPairConfig::default()).await?;
+        pair.backfill_config.timeframe = "H1".to_string();
+        pair.backfill_config.min_bars = 24;
+        pair.historical_data = history[..30].to_vec();
+        let now = start + Duration::hours(40);
+        let report = pair.backfill(None, Some(&provider), now).await?;
+        ensure!(report.fetched_bars == 11 && pair.historical_data.last().map(|bar| bar.timestamp) == Some(now), "backfill: {:?}", report);
+        ensure!(report.warm && pair.historical_data.windows(2).all(|w| w[0].timestamp < w[1].timestamp), "backfilled history is warm and in order");
+        println!("   ✅ {} bars fetched up to now, none after", report.fetched_bars);
+        Ok::<_, anyhow::Error>(pair)
+    }.await;
+    let _ = std::fs::remove_dir_all(&dir);
+    let mut pair = result?;
+
+    // Test 5: live ticks from a channel extend the history and set the price
+    println!("📊 Test 5: Live ticks");
+    let (feed, sender) = ChannelProvider::new("scripted");
+    let mut ticks = feed.subscribe_ticks(&["EURUSD".to_string()])?;
+    ensure!(feed.subscribe_ticks(&["EURUSD".to_string()]).is_err(), "one subscription per channel");
+    let last = pair.historical_data.last().expect("history").timestamp;
+    for (minutes, mid) in [(61, 1.2000), (90, 1.2010), (121, 1.2020)] {
+        sender.send(Tick { symbol: "EURUSD".to_string(), timestamp: last + Duration::minutes(minutes), bid: mid, ask: mid + 0.0001 }).await?;
+    }
+    drop(sender);
+    let bars = pair.historical_data.len();
+    while let Some(tick) = ticks.recv().await {
+        pair.on_tick(tick);
+    }
+    ensure!(pair.historical_data.len() == bars + 1, "one hourly bar completed, got {}", pair.historical_data.len() - bars);
+    let bar = pair.historical_data.last().expect("bar");
+    ensure!(bar.timestamp == last + Duration::hours(1) && (bar.high - 1.20105).abs() < 1e-9, "live bar {:?}", bar);
+    ensure!(pair.current_price(Utc::now()).is_some_and(|price| (price - 1.20205).abs() < 1e-9), "the price is the last mid");
+    println!("   ✅ Completed bar appended, price follows the last tick");
+
+    println!();
+    println!("🎉 All data provider tests passed");
+    Ok(())
+}
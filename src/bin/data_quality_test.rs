@@ -0,0 +1,161 @@
+//! # Data Quality Test
+//!
+//! Check that weekend and holiday closures are told apart from missing trading
+//! time, that duplicates, bad prices and spikes are reported, that each gap
+//! filling strategy repairs the series as documented, and that the data manager
+//! runs the pass on every load
+
+use anyhow::{ensure, Result};
+use chrono::{DateTime, Datelike, Duration, TimeZone, Timelike, Utc, Weekday};
+use std::path::PathBuf;
+
+use forex_pattern_reconstruction::data::quality::{DataQualityChecker, DataQualityConfig, GapFill, GapKind};
+use forex_pattern_reconstruction::data::{DataConfig, ForexDataManager, ForexDataPoint};
+
+fn bar(timestamp: DateTime<Utc>, close: f64) -> ForexDataPoint {
+    ForexDataPoint { timestamp, open: close, high: close + 0.0005, low: close - 0.0005, close, volume: Some(100.0) }
+}
+
+/// Hourly bars over `weeks` trading weeks, Sunday 22:00 to Friday 21:00 UTC, gently oscillating
+fn hourly(weeks: i64) -> Vec<ForexDataPoint> {
+    let start = Utc.with_ymd_and_hms(2024, 2, 4, 22, 0, 0).unwrap(); // a Sunday
+    (0..weeks * 7 * 24)
+        .map(|h| start + Duration::hours(h))
+        .filter(|t| match t.weekday() {
+            Weekday::Sat => false,
+            Weekday::Sun => t.hour() >= 22,
+            Weekday::Fri => t.hour() < 21,
+            _ => true,
+        })
+        .enumerate()
+        .map(|(i, t)| bar(t, 1.1 + 0.001 * (i as f64 * 0.3).sin() + 0.0001 * ((i * 7) % 5) as f64))
+        .collect()
+}
+
+/// Weekday bars over December 2023 into January 2024
+fn daily() -> Vec<ForexDataPoint> {
+    let start = Utc.with_ymd_and_hms(2023, 12, 1, 0, 0, 0).unwrap();
+    (0..45)
+        .map(|d| start + Duration::days(d))
+        .filter(|t| !matches!(t.weekday(), Weekday::Sat | Weekday::Sun))
+        .enumerate()
+        .map(|(i, t)| bar(t, 1.09 + 0.002 * (i as f64 * 0.5).sin()))
+        .collect()
+}
+
+fn checker(gap_fill: GapFill) -> DataQualityChecker {
+    DataQualityChecker::new(DataQualityConfig { gap_fill, ..DataQualityConfig::default() })
+}
+
+/// Hourly history with a duplicate, a zero price, a crossed bar, a bad print,
+/// a lasting jump and three missing bars on a Wednesday
+fn defective() -> (Vec<ForexDataPoint>, [DateTime<Utc>; 5]) {
+    let mut data = hourly(2);
+    let at = |i: usize, data: &[ForexDataPoint]| data[i].timestamp;
+    let (duplicate, zero, crossed, print, jump) = (at(30, &data), at(40, &data), at(50, &data), at(60, &data), at(150, &data));
+    data[40].low = 0.0;
+    data[50].high = data[50].low - 0.001;
+    data[60].close *= 1.05;
+    for point in &mut data[150..] {
+        point.open *= 1.03;
+        point.high *= 1.03;
+        point.low *= 1.03;
+        point.close *= 1.03;
+    }
+    data.push(bar(duplicate, data[30].close + 0.0001));
+    data.drain(70..73);
+    (data, [duplicate, zero, crossed, print, jump])
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 Data Quality Test");
+    println!("====================");
+    println!();
+
+    // Test 1: closures are not defects
+    println!("📊 Test 1: Weekends and holidays");
+    let clean = hourly(3);
+    let report = checker(GapFill::Keep).check(&clean);
+    ensure!(report.spacing_seconds == 3600, "spacing {}s", report.spacing_seconds);
+    ensure!(report.is_clean() && report.gaps.len() == 2 && report.gaps.iter().all(|gap| gap.kind == GapKind::Weekend && gap.missing_bars == 49),
+            "weekend gaps: {:?}", report.gaps);
+    let mut days = daily();
+    days.retain(|point| !(point.timestamp.month() == 12 && point.timestamp.day() == 25));
+    let report = checker(GapFill::Keep).check(&days);
+    ensure!(report.is_clean(), "daily closures flagged: {}", report.summary());
+    ensure!(report.missing_bars(GapKind::Holiday) == 3 && report.missing_bars(GapKind::Weekend) == 2 * 5,
+            "{} holiday and {} weekend days", report.missing_bars(GapKind::Holiday), report.missing_bars(GapKind::Weekend));
+    println!("   ✅ {} weekend gaps on hourly bars; Christmas weekend read as a holiday on daily bars", 2);
+
+    // Test 2: defects are reported
+    println!("📊 Test 2: Defects");
+    let (data, [duplicate, zero, crossed, print, jump]) = defective();
+    let report = checker(GapFill::Keep).check(&data);
+    report.print_report();
+    ensure!(report.duplicate_timestamps == [duplicate], "duplicates {:?}", report.duplicate_timestamps);
+    ensure!(report.invalid_prices == [zero, crossed], "invalid prices {:?}", report.invalid_prices);
+    let missing: Vec<_> = report.gaps.iter().filter(|gap| gap.kind == GapKind::Missing).collect();
+    ensure!(missing.len() == 1 && missing[0].missing_bars == 3, "missing gaps {:?}", missing);
+    ensure!(report.spikes.len() == 2, "{} spikes: {:?}", report.spikes.len(), report.spikes);
+    ensure!(report.spikes[0].timestamp == print && report.spikes[0].reverted, "bad print {:?}", report.spikes[0]);
+    ensure!(report.spikes[1].timestamp == jump && !report.spikes[1].reverted && report.spikes[1].sigmas > 8.0, "lasting jump {:?}", report.spikes[1]);
+    ensure!(!report.is_clean() && report.bars == data.len(), "defective data reported clean");
+    println!("   ✅ {}", report.summary());
+
+    // Test 3: repair strategies
+    println!("📊 Test 3: Gap filling");
+    let (kept, report) = checker(GapFill::Keep).clean(data.clone());
+    ensure!(kept.len() == data.len() && report.filled_bars + report.replaced_bars + report.dropped_bars == 0, "keep changed the data");
+
+    let (filled, report) = checker(GapFill::ForwardFill).clean(data.clone());
+    let after_check = checker(GapFill::Keep).check(&filled);
+    ensure!(after_check.is_clean() || after_check.spikes.iter().all(|spike| spike.timestamp == jump), "forward fill left defects: {}", after_check.summary());
+    ensure!(report.dropped_bars == 1 && report.replaced_bars == 3 && report.filled_bars == 3, "forward fill: {}", report.summary());
+    let index = |data: &[ForexDataPoint], t: DateTime<Utc>| data.iter().position(|p| p.timestamp == t).expect("bar kept");
+    let i = index(&filled, print);
+    ensure!(filled[i].close == filled[i - 1].close && filled[i].high == filled[i].low, "bad print not replaced by the previous close");
+    let gap_start = missing[0].after;
+    let g = index(&filled, gap_start);
+    ensure!((1..=3).all(|k| filled[g + k].timestamp == gap_start + Duration::hours(k as i64) && filled[g + k].close == filled[g].close),
+            "missing hours not forward-filled");
+    ensure!(filled.iter().all(|p| p.timestamp.weekday() != Weekday::Sat), "weekend filled");
+    ensure!(filled.iter().filter(|p| p.timestamp == duplicate).count() == 1
+            && filled[index(&filled, duplicate)].close == data.last().map(|p| p.close).unwrap_or_default(), "duplicate kept the first row");
+
+    let (interpolated, report) = checker(GapFill::Interpolate).clean(data.clone());
+    ensure!(report.filled_bars == 3 && report.replaced_bars == 3, "interpolate: {}", report.summary());
+    let g = index(&interpolated, gap_start);
+    let (from, to) = (interpolated[g].close, interpolated[g + 4].open);
+    ensure!((1..=3).all(|k| (interpolated[g + k].close - (from + (to - from) * k as f64 / 4.0)).abs() < 1e-12), "missing hours not interpolated");
+
+    let (dropped, report) = checker(GapFill::Drop).clean(data.clone());
+    ensure!(report.filled_bars == 0 && report.replaced_bars == 0 && report.dropped_bars == 4, "drop: {}", report.summary());
+    ensure!(dropped.len() == data.len() - 4 && [zero, crossed, print].iter().all(|t| dropped.iter().all(|p| p.timestamp != *t)), "bad bars kept");
+
+    let short = DataQualityChecker::new(DataQualityConfig { gap_fill: GapFill::ForwardFill, max_fill_bars: 2, ..DataQualityConfig::default() });
+    ensure!(short.clean(data.clone()).1.filled_bars == 0, "gap longer than max_fill_bars filled");
+    println!("   ✅ forward-fill, interpolate and drop repair bad bars; 3 missing hours filled, weekends never");
+
+    // Test 4: the data manager checks every load
+    println!("📊 Test 4: Data manager");
+    let path = std::env::temp_dir().join(format!("data-quality-test-{}.csv", std::process::id()));
+    let manager = ForexDataManager::new(DataConfig::default())?;
+    manager.save_csv_file(&path, &data)?;
+    let mut plain = ForexDataManager::new(DataConfig::default())?;
+    let loaded = plain.load_data(&PathBuf::from(&path), "EURUSD", "H1").await;
+    let config: DataConfig = serde_json::from_str(r#"{"data_directory":"data","cache_enabled":false,"max_cache_size":10,"quality":{"gap_fill":"forward-fill"}}"#)?;
+    let mut repairing = ForexDataManager::new(config)?;
+    let repaired = repairing.load_data(&PathBuf::from(&path), "EURUSD", "H1").await;
+    let _ = std::fs::remove_file(&path);
+    let (loaded, repaired) = (loaded?, repaired?);
+    ensure!(loaded.len() == data.len() && plain.quality_report().is_some_and(|r| r.duplicate_timestamps.len() == 1), "report of a plain load");
+    ensure!(repaired.len() == filled.len() && repairing.quality_report().is_some_and(|r| r.filled_bars == 3), "configured repair on load");
+    ensure!(manager.check_quality(&repaired).duplicate_timestamps.is_empty(), "repaired load still has duplicates");
+    ensure!("ffill".parse::<GapFill>()? == GapFill::ForwardFill && "sideways".parse::<GapFill>().is_err(), "strategy names");
+    println!("   ✅ {} bars loaded as is, {} after forward-filling", loaded.len(), repaired.len());
+
+    println!();
+    println!("🎉 All data quality tests passed");
+    Ok(())
+}
@@ -0,0 +1,142 @@
+//! # Embedded Database Append Test
+//!
+//! Check that bars appended to a pair land in monthly blobs, that an append
+//! rewrites only the months it touches and replaces bars at stored timestamps,
+//! that range queries decompress only the months they overlap, that the
+//! consistency check decodes monthly blobs, and that appended bars merge over a
+//! full blob that range queries also read
+
+use anyhow::{ensure, Result};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use rusqlite::{params, Connection};
+use std::path::Path;
+
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::embedded_db::EmbeddedForexDB;
+
+/// Hourly bars from `start`, closing on a slow ramp
+fn bars(start: DateTime<Utc>, count: usize, base: f64) -> Vec<ForexDataPoint> {
+    (0..count)
+        .map(|i| {
+            let close = base + 0.00001 * (i % 500) as f64;
+            ForexDataPoint {
+                timestamp: start + Duration::hours(i as i64),
+                open: close,
+                high: close + 0.0002,
+                low: close - 0.0002,
+                close,
+                volume: Some(100.0),
+            }
+        })
+        .collect()
+}
+
+fn months(db_path: &Path, pair: &str) -> Result<Vec<(i64, i64)>> {
+    let conn = Connection::open(db_path)?;
+    let mut stmt = conn.prepare("SELECT month_start, created_at FROM forex_months WHERE pair = ?1 ORDER BY month_start")?;
+    let rows = stmt.query_map(params![pair], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    Ok(rows.collect::<rusqlite::Result<_>>()?)
+}
+
+fn main() -> Result<()> {
+    println!("🔬 EMBEDDED DATABASE APPEND TEST");
+    println!("================================");
+    println!();
+
+    let dir = std::env::temp_dir().join(format!("forex-db-append-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir)?;
+    let result = run_tests(&dir);
+    let _ = std::fs::remove_dir_all(&dir);
+    result?;
+
+    println!();
+    println!("🎉 All append tests passed");
+    Ok(())
+}
+
+fn run_tests(dir: &Path) -> Result<()> {
+    let path = dir.join("append.db");
+    let db = EmbeddedForexDB::open(&path)?;
+    let start = Utc.with_ymd_and_hms(2023, 1, 1, 0, 0, 0).unwrap();
+
+    // Test 1: a year of bars splits into monthly blobs
+    println!("📊 Test 1: Monthly blobs");
+    let year = bars(start, 365 * 24, 1.08);
+    ensure!(db.append_forex_data("EURUSD", &year)? == 12, "a year should span 12 months");
+    ensure!(months(&path, "EURUSD")?.len() == 12, "expected 12 stored months");
+    let all = db.get_forex_data("EURUSD")?;
+    ensure!(all.len() == year.len() && all.first().map(|p| p.timestamp) == Some(start), "appended year reads back whole");
+    println!("✅ {} bars stored as 12 monthly blobs", year.len());
+
+    // Test 2: appending a day rewrites only its month
+    println!("📊 Test 2: Incremental append");
+    let before = months(&path, "EURUSD")?;
+    std::thread::sleep(std::time::Duration::from_millis(1100));
+    let next_day = bars(start + Duration::hours(365 * 24), 24, 1.09);
+    ensure!(db.append_forex_data("EURUSD", &next_day)? == 1, "one day should touch one month");
+    let after = months(&path, "EURUSD")?;
+    ensure!(after.len() == 13, "the new day opens a 13th month");
+    ensure!(before.iter().zip(&after).all(|(old, new)| old == new), "an append rewrote an untouched month");
+    ensure!(db.get_forex_data("EURUSD")?.len() == year.len() + 24, "appended day missing");
+    println!("✅ Appending one day wrote one month and left the other 12 untouched");
+
+    // Test 3: overlapping appends replace stored bars
+    println!("📊 Test 3: Overlapping append");
+    let june = Utc.with_ymd_and_hms(2023, 6, 15, 0, 0, 0).unwrap();
+    let revised = bars(june, 48, 1.2);
+    ensure!(db.append_forex_data("EURUSD", &revised)? == 1, "revision touches June only");
+    let window = db.get_forex_data_between("EURUSD", june, june + Duration::hours(47))?;
+    ensure!(window.len() == 48, "revised bars duplicated: {}", window.len());
+    ensure!(window.iter().zip(&revised).all(|(stored, new)| (stored.close - new.close).abs() < 1e-5), "revised bars not replaced");
+    ensure!(db.get_forex_data("EURUSD")?.len() == year.len() + 24, "revision changed the bar count");
+    println!("✅ Bars at stored timestamps are replaced, not duplicated");
+
+    // Test 4: range queries cross month boundaries and skip other months
+    println!("📊 Test 4: Range queries");
+    let from = Utc.with_ymd_and_hms(2023, 3, 31, 20, 0, 0).unwrap();
+    let to = Utc.with_ymd_and_hms(2023, 4, 1, 3, 0, 0).unwrap();
+    let range = db.get_forex_data_between("EURUSD", from, to)?;
+    ensure!(range.len() == 8 && range[0].timestamp == from && range[7].timestamp == to, "boundary range {:?}", range.len());
+    ensure!(db.get_forex_data_between("GBPUSD", from, to)?.is_empty(), "other pair leaked in");
+    ensure!(db.get_forex_data_between("EURUSD", start - Duration::days(30), start - Duration::hours(1))?.is_empty(), "bars before the first month");
+    // Corrupt December: queries that do not reach it must not decompress it
+    Connection::open(&path)?.execute(
+        "UPDATE forex_months SET data = x'00' WHERE pair = 'EURUSD' AND month_start = ?1",
+        params![Utc.with_ymd_and_hms(2023, 12, 1, 0, 0, 0).unwrap().timestamp()],
+    )?;
+    ensure!(db.get_forex_data_between("EURUSD", from, to)?.len() == 8, "a March/April query read December");
+    ensure!(db.get_forex_data_between("EURUSD", start, start + Duration::days(365)).is_err(), "a full-year query skipped December");
+    println!("✅ {} bars across the March/April boundary; untouched months are never decoded", range.len());
+
+    // Test 5: consistency check covers monthly blobs
+    println!("📊 Test 5: Consistency check");
+    let report = db.check()?;
+    ensure!(report.corrupt_series.len() == 1 && report.corrupt_series[0].contains("2023-12"), "report {:?}", report.corrupt_series);
+    ensure!(!report.is_ok(), "corrupt month passed the check");
+    println!("✅ Corrupt month reported: {}", report.corrupt_series[0]);
+
+    // Test 6: a pair stored as one full blob takes appended bars without rewriting it
+    println!("📊 Test 6: Full blobs");
+    let memory = EmbeddedForexDB::new()?;
+    memory.store_forex_data("USDJPY", &bars(start, 200, 141.0))?;
+    memory.append_forex_data("USDJPY", &bars(start + Duration::hours(150), 100, 140.0))?;
+    let merged = memory.get_forex_data("USDJPY")?;
+    ensure!(merged.len() == 250, "blob and appended bars should merge to 250, got {}", merged.len());
+    ensure!(merged.windows(2).all(|w| w[0].timestamp < w[1].timestamp), "merged bars out of order or duplicated");
+    ensure!(merged[149].close > 141.0, "blob bar before the append changed");
+    ensure!(merged[150].close < 140.001, "appended bar should replace the blob bar at its timestamp");
+    let backfill = memory.get_bars_after("USDJPY", start + Duration::hours(199), start + Duration::hours(400))?;
+    ensure!(backfill.len() == 50 && backfill[0].timestamp == start + Duration::hours(200), "backfill missed appended bars: {}", backfill.len());
+    ensure!(memory.get_forex_data("AUDUSD").is_err(), "missing pair returned data");
+    println!("✅ Appended bars merge over the full blob and are backfilled; pairs with neither are an error");
+
+    // Test 7: range queries read pairs stored only as a full blob
+    println!("📊 Test 7: Blob-only range");
+    memory.store_forex_data("EURGBP", &bars(start, 200, 0.86))?;
+    let window = memory.get_forex_data_between("EURGBP", start + Duration::hours(10), start + Duration::hours(19))?;
+    ensure!(window.len() == 10 && window[0].timestamp == start + Duration::hours(10), "blob-only range returned {} bars", window.len());
+    ensure!(memory.get_forex_data_between("EURGBP", start + Duration::hours(200), start + Duration::hours(300))?.is_empty(), "bars past the blob");
+    println!("✅ {} bars from a pair with no appended months", window.len());
+
+    Ok(())
+}
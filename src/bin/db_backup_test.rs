@@ -0,0 +1,83 @@
+//! # Database Backup Test
+//!
+//! Back a file database up, restore it over a fresh path and check the series
+//! survive; check that a corrupted backup fails the consistency check and is
+//! never restored over a working database
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::embedded_db::EmbeddedForexDB;
+use forex_pattern_reconstruction::synthetic::fixtures::cyclic_daily;
+
+fn same_bars(a: &[ForexDataPoint], b: &[ForexDataPoint]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(x, y)| {
+        x.timestamp == y.timestamp && [x.open, x.high, x.low, x.close] == [y.open, y.high, y.low, y.close]
+    })
+}
+
+fn main() -> Result<()> {
+    println!("🔬 DATABASE BACKUP TEST");
+    println!("=======================");
+    println!();
+
+    let dir = std::env::temp_dir().join(format!("db-backup-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    let result = run(&dir);
+    let _ = std::fs::remove_dir_all(&dir);
+    result?;
+
+    println!();
+    println!("🎉 All database backup tests passed");
+    Ok(())
+}
+
+fn run(dir: &std::path::Path) -> Result<()> {
+    let mut rng = StdRng::seed_from_u64(3);
+    let start = Utc.with_ymd_and_hms(2023, 1, 1, 0, 0, 0).unwrap();
+    let (live, backup, restored) = (dir.join("live.db"), dir.join("backup.db"), dir.join("restored.db"));
+
+    // Test 1: a backup of a live database is consistent and refuses to overwrite
+    println!("📊 Test 1: Backup");
+    let db = EmbeddedForexDB::open(&live)?;
+    db.store_forex_data("EURUSD", &cyclic_daily(start, 200, &mut rng))?;
+    db.append_forex_data("GBPUSD", &cyclic_daily(start, 90, &mut rng))?;
+    let report = db.check()?;
+    ensure!(report.is_ok() && report.series_checked >= 2, "live database inconsistent: {:?}", report);
+    db.backup(&backup)?;
+    ensure!(backup.exists(), "no backup written");
+    ensure!(db.backup(&backup).is_err(), "an existing backup was overwritten");
+    println!("   ✅ {} series backed up", report.series_checked);
+
+    // Test 2: restoring gives back the same series
+    println!("📊 Test 2: Restore");
+    let report = EmbeddedForexDB::restore(&backup, &restored)?;
+    ensure!(report.is_ok(), "backup failed its check: {:?}", report);
+    let copy = EmbeddedForexDB::open(&restored)?;
+    for pair in ["EURUSD", "GBPUSD"] {
+        let (original, restored) = (db.get_forex_data(pair)?, copy.get_forex_data(pair)?);
+        ensure!(!original.is_empty() && same_bars(&original, &restored), "{} differs after restore", pair);
+    }
+    ensure!(copy.schema_version()? == db.schema_version()?, "restored schema version differs");
+    let day = start + Duration::days(30);
+    ensure!(copy.get_forex_data_between("GBPUSD", day, day + Duration::days(10))?.len() == 11, "monthly rows not restored");
+    println!("   ✅ Series and schema identical after restore");
+
+    // Test 3: a backup with a corrupted series is detected and not restored
+    println!("📊 Test 3: Corrupted backup");
+    let corrupted = dir.join("corrupted.db");
+    std::fs::copy(&backup, &corrupted)?;
+    rusqlite::Connection::open(&corrupted)?.execute("UPDATE forex_data SET data = x'00ff00ff' WHERE pair = 'EURUSD'", [])?;
+    let checked = EmbeddedForexDB::open(&corrupted)?.check()?;
+    ensure!(!checked.is_ok() && checked.corrupt_series.iter().any(|series| series.starts_with("EURUSD")), "corruption not found: {:?}", checked);
+    let before = copy.get_forex_data("EURUSD")?;
+    drop(copy);
+    ensure!(EmbeddedForexDB::restore(&corrupted, &restored).is_err(), "a corrupted backup was restored");
+    ensure!(same_bars(&EmbeddedForexDB::open(&restored)?.get_forex_data("EURUSD")?, &before), "the target changed after a refused restore");
+    println!("   ✅ {} flagged; target left as it was", checked.corrupt_series.join(", "));
+
+    Ok(())
+}
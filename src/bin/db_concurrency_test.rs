@@ -0,0 +1,133 @@
+//! # Embedded Database Concurrency Test
+//!
+//! Hammer one shared `EmbeddedForexDB` handle from many tasks and threads
+
+use anyhow::{bail, Result};
+use chrono::{Duration, Utc};
+use std::thread;
+
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::embedded_db::EmbeddedForexDB;
+
+const PAIRS: &[&str] = &["EURUSD", "GBPUSD", "USDJPY", "USDCHF", "AUDUSD", "NZDUSD", "USDCAD", "EURGBP"];
+const POINTS_PER_PAIR: usize = 500;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 EMBEDDED DATABASE CONCURRENCY TEST");
+    println!("=====================================");
+    println!();
+
+    let db_path = std::env::temp_dir().join(format!("forex-db-concurrency-{}.db", uuid::Uuid::new_v4()));
+    let db = EmbeddedForexDB::open(&db_path)?;
+    println!("🗄️  Opened {} with {} pooled connections", db_path.display(), db.pool_size());
+
+    // Test 1: concurrent async writers, one task per pair
+    println!("📊 Test 1: Concurrent async writes from {} tasks", PAIRS.len());
+    let mut handles = Vec::new();
+    for (i, pair) in PAIRS.iter().enumerate() {
+        let db = db.clone();
+        let data = sample_series(POINTS_PER_PAIR, 1.0 + i as f64 * 0.1);
+        handles.push(tokio::spawn(async move {
+            db.store_forex_data_async(pair.to_string(), data).await
+        }));
+    }
+    for handle in handles {
+        handle.await??;
+    }
+    println!("✅ All writers finished");
+
+    // Test 2: concurrent async readers see every write
+    println!("📊 Test 2: Concurrent async reads");
+    let mut handles = Vec::new();
+    for pair in PAIRS {
+        let db = db.clone();
+        handles.push(tokio::spawn(async move {
+            db.get_forex_data_async(pair.to_string()).await.map(|data| (pair, data.len()))
+        }));
+    }
+    for handle in handles {
+        let (pair, len) = handle.await??;
+        if len != POINTS_PER_PAIR {
+            bail!("{} returned {} points, expected {}", pair, len, POINTS_PER_PAIR);
+        }
+    }
+    println!("✅ Every pair read back {} points", POINTS_PER_PAIR);
+
+    // Test 3: mixed readers and writers on plain threads
+    println!("📊 Test 3: Mixed reads/writes from OS threads");
+    let threads: Vec<_> = (0..8)
+        .map(|t| {
+            let db = db.clone();
+            thread::spawn(move || -> Result<()> {
+                for i in 0..50 {
+                    let pair1 = PAIRS[t % PAIRS.len()];
+                    let pair2 = PAIRS[(t + i + 1) % PAIRS.len()];
+                    db.store_correlation(pair1, pair2, (i as f64 / 50.0) - 0.5, "D1")?;
+                    db.get_correlation_matrix("D1")?;
+                    db.get_forex_data(pair2)?;
+                }
+                Ok(())
+            })
+        })
+        .collect();
+    for handle in threads {
+        match handle.join() {
+            Ok(result) => result?,
+            Err(_) => bail!("worker thread panicked"),
+        }
+    }
+    let correlations = db.get_correlation_matrix_async("D1".to_string()).await?;
+    if correlations.is_empty() {
+        bail!("no correlations stored");
+    }
+    println!("✅ {} correlation entries written concurrently", correlations.len());
+
+    // Test 4: in-memory handle shared across threads
+    println!("📊 Test 4: Shared in-memory database");
+    let memory_db = EmbeddedForexDB::new()?;
+    let threads: Vec<_> = PAIRS.iter()
+        .map(|pair| {
+            let db = memory_db.clone();
+            thread::spawn(move || db.store_forex_data(pair, &sample_series(100, 1.0)))
+        })
+        .collect();
+    for handle in threads {
+        match handle.join() {
+            Ok(result) => result?,
+            Err(_) => bail!("worker thread panicked"),
+        }
+    }
+    for pair in PAIRS {
+        if memory_db.get_forex_data(pair)?.len() != 100 {
+            bail!("in-memory database lost writes for {}", pair);
+        }
+    }
+    println!("✅ In-memory handle kept every write");
+
+    drop(db);
+    for suffix in ["", "-wal", "-shm"] {
+        let _ = std::fs::remove_file(format!("{}{}", db_path.display(), suffix));
+    }
+
+    println!();
+    println!("🎉 All concurrency tests passed");
+    Ok(())
+}
+
+fn sample_series(len: usize, base: f64) -> Vec<ForexDataPoint> {
+    let start = Utc::now() - Duration::days(len as i64);
+    (0..len)
+        .map(|i| {
+            let price = base + (i as f64 * 0.1).sin() * 0.01;
+            ForexDataPoint {
+                timestamp: start + Duration::days(i as i64),
+                open: price,
+                high: price + 0.002,
+                low: price - 0.002,
+                close: price + 0.001,
+                volume: Some(1000.0),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,163 @@
+//! # Embedded Database Migration Test
+//!
+//! Build databases in older schema formats and check they open and upgrade cleanly
+
+use anyhow::{bail, Result};
+use chrono::{Duration, Utc};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use rusqlite::{params, Connection};
+use std::io::Write;
+use std::path::Path;
+
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::embedded_db::migrations::{self, SCHEMA_VERSION};
+use forex_pattern_reconstruction::embedded_db::{AnalysisCacheKey, CompressedForexPoint, EmbeddedForexDB};
+
+/// Schema written by releases before versioning (user_version 0)
+const UNVERSIONED_SCHEMA: &str = "
+    CREATE TABLE forex_data (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        pair TEXT NOT NULL,
+        data BLOB NOT NULL,
+        data_points INTEGER NOT NULL,
+        created_at INTEGER NOT NULL
+    );
+    CREATE INDEX idx_pair ON forex_data(pair);
+    CREATE TABLE correlation_matrix (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        pair1 TEXT NOT NULL,
+        pair2 TEXT NOT NULL,
+        correlation REAL NOT NULL,
+        timeframe TEXT NOT NULL,
+        created_at INTEGER NOT NULL
+    );
+    CREATE INDEX idx_correlation ON correlation_matrix(pair1, pair2);
+";
+
+fn main() -> Result<()> {
+    println!("🔬 EMBEDDED DATABASE MIGRATION TEST");
+    println!("===================================");
+    println!();
+
+    let dir = std::env::temp_dir().join(format!("forex-db-migrations-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir)?;
+    let result = run_tests(&dir);
+    let _ = std::fs::remove_dir_all(&dir);
+    result?;
+
+    println!();
+    println!("🎉 All migration tests passed");
+    Ok(())
+}
+
+fn run_tests(dir: &Path) -> Result<()> {
+    // Test 1: fresh database lands on the latest schema
+    println!("📊 Test 1: Fresh database");
+    let fresh = EmbeddedForexDB::open(&dir.join("fresh.db"))?;
+    expect_version(&fresh, SCHEMA_VERSION)?;
+    println!("✅ Fresh database at schema version {}", SCHEMA_VERSION);
+
+    // Test 2: unversioned database keeps its data and gains new tables
+    println!("📊 Test 2: Unversioned (pre-migration) database");
+    let path = dir.join("unversioned.db");
+    let series = sample_series(250);
+    {
+        let conn = Connection::open(&path)?;
+        conn.execute_batch(UNVERSIONED_SCHEMA)?;
+        insert_legacy_series(&conn, "EURUSD", &series)?;
+        conn.execute(
+            "INSERT INTO correlation_matrix (pair1, pair2, correlation, timeframe, created_at)
+             VALUES ('EURUSD', 'GBPUSD', 0.85, 'D1', 0)",
+            [],
+        )?;
+    }
+    let db = EmbeddedForexDB::open(&path)?;
+    expect_version(&db, SCHEMA_VERSION)?;
+    if db.get_forex_data("EURUSD")?.len() != series.len() {
+        bail!("legacy series was not preserved");
+    }
+    if db.get_correlation_matrix("D1")?.len() != 1 {
+        bail!("legacy correlations were not preserved");
+    }
+    let key = AnalysisCacheKey::new("EURUSD", "D1", &series, "config");
+    db.store_cached_analysis(&key, &[], &[])?;
+    if db.get_cached_analysis(&key)?.is_none() {
+        bail!("analysis cache unusable after upgrade");
+    }
+    if !db.check()?.is_ok() {
+        bail!("upgraded database failed consistency check");
+    }
+    println!("✅ Unversioned database upgraded with data intact");
+
+    // Test 3: partially migrated database only runs the remaining migrations
+    println!("📊 Test 3: Version 1 database");
+    let path = dir.join("v1.db");
+    {
+        let conn = Connection::open(&path)?;
+        conn.execute_batch(UNVERSIONED_SCHEMA)?;
+        conn.pragma_update(None, "user_version", 1)?;
+    }
+    let applied = migrations::migrate(&Connection::open(&path)?)?;
+    if applied != SCHEMA_VERSION - 1 {
+        bail!("expected {} migrations, applied {}", SCHEMA_VERSION - 1, applied);
+    }
+    expect_version(&EmbeddedForexDB::open(&path)?, SCHEMA_VERSION)?;
+    println!("✅ Applied {} remaining migrations", applied);
+
+    // Test 4: reopening is a no-op
+    println!("📊 Test 4: Reopening an up-to-date database");
+    let applied = migrations::migrate(&Connection::open(&path)?)?;
+    if applied != 0 {
+        bail!("up-to-date database re-ran {} migrations", applied);
+    }
+    println!("✅ No migrations re-run");
+
+    // Test 5: databases from a newer build are refused
+    println!("📊 Test 5: Newer schema version");
+    let path = dir.join("future.db");
+    Connection::open(&path)?.pragma_update(None, "user_version", SCHEMA_VERSION + 1)?;
+    match EmbeddedForexDB::open(&path) {
+        Ok(_) => bail!("newer schema version was accepted"),
+        Err(e) => println!("✅ Refused: {}", e),
+    }
+
+    Ok(())
+}
+
+fn expect_version(db: &EmbeddedForexDB, expected: u32) -> Result<()> {
+    let version = db.schema_version()?;
+    if version != expected {
+        bail!("schema version {} (expected {})", version, expected);
+    }
+    Ok(())
+}
+
+/// Write a series the way releases before versioning stored it
+fn insert_legacy_series(conn: &Connection, pair: &str, data: &[ForexDataPoint]) -> Result<()> {
+    let compressed: Vec<CompressedForexPoint> = data.iter().map(CompressedForexPoint::from).collect();
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(&bincode::serialize(&compressed)?)?;
+    conn.execute(
+        "INSERT INTO forex_data (pair, data, data_points, created_at) VALUES (?1, ?2, ?3, ?4)",
+        params![pair, encoder.finish()?, data.len(), Utc::now().timestamp()],
+    )?;
+    Ok(())
+}
+
+fn sample_series(len: usize) -> Vec<ForexDataPoint> {
+    let start = Utc::now() - Duration::days(len as i64);
+    (0..len)
+        .map(|i| {
+            let price = 1.1 + (i as f64 * 0.05).sin() * 0.02;
+            ForexDataPoint {
+                timestamp: start + Duration::days(i as i64),
+                open: price,
+                high: price + 0.003,
+                low: price - 0.003,
+                close: price + 0.001,
+                volume: Some(1000.0),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,98 @@
+//! # Decomposition Test
+//!
+//! Decompose closes built from a trend and two known cycles and check the
+//! amplitudes and phases come back, that the per-bar trend, components and
+//! residual line up with the input timestamps and sum to the closes, and that
+//! the CSV export carries one row per bar
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::f64::consts::TAU;
+
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::patterns::{CycleDecomposer, DecompositionConfig};
+
+/// Daily closes: 1.1 plus a drift, a 20-bar and a 50-bar cycle and uniform noise
+fn closes(count: usize, noise: f64, rng: &mut StdRng) -> Vec<ForexDataPoint> {
+    let start = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+    (0..count)
+        .map(|i| {
+            let timestamp = start + Duration::days(i as i64);
+            let t = timestamp.timestamp() as f64 / 86_400.0;
+            let close = 1.1 + 0.00005 * i as f64
+                + 0.011 * (TAU * t / 20.0 + 0.5).sin()
+                + 0.0055 * (TAU * t / 50.0).sin()
+                + rng.gen_range(-noise..noise);
+            ForexDataPoint { timestamp, open: close, high: close, low: close, close, volume: None }
+        })
+        .collect()
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 DECOMPOSITION TEST");
+    println!("=====================");
+    println!();
+
+    let mut rng = StdRng::seed_from_u64(3);
+    let data = closes(600, 0.001, &mut rng);
+    let mut decomposer = CycleDecomposer::new(DecompositionConfig::default())?;
+
+    // Test 1: known cycles are recovered
+    println!("📊 Test 1: amplitudes and phases");
+    let decomposition = decomposer.decompose_cycles(&data, &[50, 20]).await?;
+    ensure!(decomposition.periods() == [20, 50], "components listed shortest first");
+    let (fast, slow) = (&decomposition.components[&20], &decomposition.components[&50]);
+    let mean = data.iter().map(|point| point.close).sum::<f64>() / data.len() as f64;
+    ensure!((fast.amplitude * mean - 0.011).abs() < 5e-4 && (slow.amplitude * mean - 0.0055).abs() < 5e-4,
+            "amplitudes {:.5} and {:.5}", fast.amplitude * mean, slow.amplitude * mean);
+    ensure!((fast.phase_degrees - 0.5f64.to_degrees()).abs() < 3.0 && (slow.phase_degrees.min(360.0 - slow.phase_degrees)) < 6.0,
+            "phases {:.1}° and {:.1}°", fast.phase_degrees, slow.phase_degrees);
+    ensure!(fast.strength > slow.strength && fast.strength + slow.strength <= 1.0 + 1e-9, "the faster cycle dominates");
+    println!("   ✅ 20-bar amplitude {:.5} at {:.1}°, 50-bar {:.5} at {:.1}°",
+             fast.amplitude * mean, fast.phase_degrees, slow.amplitude * mean, slow.phase_degrees);
+
+    // Test 2: every series lines up with the input bars and adds back up to the closes
+    println!("📊 Test 2: alignment");
+    ensure!(decomposition.timestamps.iter().eq(data.iter().map(|point| &point.timestamp)), "timestamps kept");
+    ensure!(decomposition.trend.len() == data.len() && decomposition.residual.len() == data.len(), "one value per bar");
+    ensure!(decomposition.components.values().all(|component| component.series.len() == data.len()), "one component value per bar");
+    let fitted = decomposition.fitted();
+    ensure!(data.iter().enumerate().all(|(i, point)| (fitted[i] + decomposition.residual[i] - point.close).abs() < 1e-12),
+            "trend + cycles + residual = close");
+    // Uniform noise on ±0.001 has σ = 0.001/√3
+    ensure!(decomposition.residual_std() < 0.0007, "the residual is the noise: σ={:.5}", decomposition.residual_std());
+    let trend_slope = (decomposition.trend[599] - decomposition.trend[0]) / 599.0;
+    ensure!((trend_slope - 0.00005).abs() < 5e-6, "trend slope {:.7}", trend_slope);
+    println!("   ✅ {} bars, residual σ={:.5}", decomposition.timestamps.len(), decomposition.residual_std());
+
+    // Test 3: the CSV has a row per bar and a column per series
+    println!("📊 Test 3: CSV export");
+    let path = std::env::temp_dir().join(format!("decomposition-test-{}.csv", std::process::id()));
+    decomposition.save_to_csv(path.to_str().unwrap())?;
+    let csv = std::fs::read_to_string(&path)?;
+    std::fs::remove_file(&path)?;
+    let lines: Vec<&str> = csv.lines().collect();
+    ensure!(lines[0] == "time,close,trend,cycle_20,cycle_50,residual", "header {}", lines[0]);
+    ensure!(lines.len() == data.len() + 1 && lines[1].starts_with("2020-01-01 00:00:00,"), "one row per bar from the first");
+    let row: Vec<f64> = lines[10].split(',').skip(1).map(|value| value.parse().unwrap()).collect();
+    ensure!((row[1] + row[2] + row[3] + row[4] - row[0]).abs() < 1e-5, "rows add up: {:?}", row);
+    println!("   ✅ {} rows", lines.len() - 1);
+
+    // Test 4: limits and bad input
+    println!("📊 Test 4: limits");
+    let mut limited = CycleDecomposer::new(DecompositionConfig { max_components: 1, ..DecompositionConfig::default() })?;
+    let single = limited.decompose_cycles(&data, &[20, 50]).await?;
+    ensure!(single.periods() == [20] && single.residual_std() > decomposition.residual_std(), "the 50-bar cycle is left in the residual");
+    ensure!(decomposer.decompose_cycles(&data[..3], &[20]).await.is_err(), "too few bars");
+    ensure!(decomposer.decompose_cycles(&data, &[1]).await.is_err(), "a 1-bar cycle is refused");
+    let trend_only = decomposer.decompose_cycles(&data, &[]).await?;
+    ensure!(trend_only.components.is_empty() && trend_only.residual_std() > 0.005, "no cycles, everything but the trend is residual");
+    println!("   ✅ One component kept, bad input refused");
+
+    println!();
+    println!("🎉 All decomposition tests passed");
+    Ok(())
+}
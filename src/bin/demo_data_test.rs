@@ -0,0 +1,72 @@
+//! # Demo Data Test
+//!
+//! Check the seeded demo dataset: the same seed gives the same bars, pairs get
+//! their own series at their own price level, and the CSV and database copies
+//! written by `demo-data` read back unchanged
+
+use anyhow::{ensure, Result};
+use chrono::{Datelike, TimeZone, Utc, Weekday};
+
+use forex_pattern_reconstruction::data::{DataConfig, ForexDataManager, ForexDataPoint};
+use forex_pattern_reconstruction::embedded_db::EmbeddedForexDB;
+use forex_pattern_reconstruction::synthetic::demo::{generate_demo_history, reference_price};
+
+fn close_enough(a: &[ForexDataPoint], b: &[ForexDataPoint], tolerance: f64) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(x, y)| {
+        x.timestamp == y.timestamp
+            && [x.open, x.high, x.low, x.close].iter().zip([y.open, y.high, y.low, y.close]).all(|(p, q)| (p - q).abs() <= tolerance)
+    })
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 DEMO DATA TEST");
+    println!("=================");
+    println!();
+
+    let end = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+    let days = 2 * 365;
+
+    // Test 1: the same seed reproduces the dataset, another seed or pair does not
+    println!("📊 Test 1: Seeded generation");
+    let eurusd = generate_demo_history("EURUSD", end, days, 42).await?;
+    ensure!(close_enough(&eurusd, &generate_demo_history("EURUSD", end, days, 42).await?, 0.0), "same seed, different bars");
+    ensure!(!close_enough(&eurusd, &generate_demo_history("EURUSD", end, days, 43).await?, 1e-9), "another seed gave the same bars");
+    let gbpusd = generate_demo_history("GBPUSD", end, days, 42).await?;
+    ensure!(!close_enough(&eurusd, &gbpusd, 1e-9), "pairs share a series");
+    println!("   ✅ {} bars, reproducible per seed and pair", eurusd.len());
+
+    // Test 2: weekday bars with consistent OHLC at the pair's price level
+    println!("📊 Test 2: Bar shape");
+    for (pair, data) in [("EURUSD", &eurusd), ("GBPUSD", &gbpusd), ("USDJPY", &generate_demo_history("USDJPY", end, days, 42).await?)] {
+        ensure!(data.len() > days as usize * 5 / 7 - 5 && data.len() <= days as usize, "{} has {} bars for {} days", pair, data.len(), days);
+        ensure!(data.iter().all(|p| !matches!(p.timestamp.weekday(), Weekday::Sat | Weekday::Sun)), "{} has weekend bars", pair);
+        ensure!(data.windows(2).all(|w| w[0].timestamp < w[1].timestamp), "{} is out of order", pair);
+        ensure!(data.iter().all(|p| p.low <= p.open.min(p.close) && p.high >= p.open.max(p.close) && p.low > 0.0), "{} has inconsistent bars", pair);
+        let mean = data.iter().map(|p| p.close).sum::<f64>() / data.len() as f64;
+        ensure!((mean / reference_price(pair) - 1.0).abs() < 0.5, "{} averages {:.4}, far from {:.4}", pair, mean, reference_price(pair));
+        println!("   {}: mean close {:.4}", pair, mean);
+    }
+    println!("   ✅ Weekdays only, consistent OHLC, pair price levels");
+
+    // Test 3: CSV and database copies read back unchanged
+    println!("📊 Test 3: Written dataset");
+    let dir = std::env::temp_dir().join(format!("demo-data-test-{}", std::process::id()));
+    let result = async {
+        let manager = ForexDataManager::new(DataConfig::default())?;
+        let csv_path = dir.join("EURUSD.csv");
+        manager.save_csv_file(&csv_path, &eurusd)?;
+        ensure!(close_enough(&manager.load_csv_file(&csv_path)?, &eurusd, 1e-6), "CSV round trip changed the bars");
+        let db = EmbeddedForexDB::open(&dir.join("demo.db"))?;
+        db.append_forex_data("EURUSD", &eurusd)?;
+        ensure!(close_enough(&db.get_forex_data("EURUSD")?, &eurusd, 1e-5), "database round trip changed the bars");
+        Ok::<_, anyhow::Error>(())
+    }.await;
+    let _ = std::fs::remove_dir_all(&dir);
+    result?;
+    println!("   ✅ CSV and database copies match");
+
+    println!();
+    println!("🎉 All demo data tests passed");
+    Ok(())
+}
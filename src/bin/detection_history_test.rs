@@ -0,0 +1,138 @@
+//! # Detection History Test
+//!
+//! Check that symmetries and cycles recorded run by run give each one's history
+//! by name, that recording a run again replaces it, that the latest run feeds an
+//! anomaly detector's expectations, and that anomalies are recorded once per id
+//! and read back by time range
+
+use anyhow::{ensure, Result};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use std::f64::consts::PI;
+
+use forex_pattern_reconstruction::anomaly::{AnomalyDetectionConfig, AnomalySeverity, AnomalyType, DetectedAnomaly, MarketContext, TemporalAnomalyDetector};
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::embedded_db::EmbeddedForexDB;
+use forex_pattern_reconstruction::ids::{AnomalyId, CycleId, SymmetryId};
+use forex_pattern_reconstruction::patterns::HiddenCycle;
+use forex_pattern_reconstruction::symmetry::TemporalSymmetry;
+
+fn symmetry(name: &str, period: u32, strength: f64) -> TemporalSymmetry {
+    TemporalSymmetry {
+        id: SymmetryId::new(),
+        symmetry_type: "Cyclic".to_string(),
+        name: name.to_string(),
+        period_days: period,
+        strength,
+        confidence: strength * 0.9,
+        field_signature: 0,
+        discovered_at: Utc::now(),
+        validation_score: strength,
+        mirror_points: vec![(0.0, 1.1), (1.0, 1.1)],
+        phase_shift: 0.25,
+    }
+}
+
+fn cycle(name: &str, period: u32, phase: f64) -> HiddenCycle {
+    HiddenCycle { id: CycleId::new(), name: name.to_string(), period, confidence: 0.8, amplitude: 0.002, phase, p_value: Some(0.01), track: None }
+}
+
+fn anomaly(timestamp: DateTime<Utc>, severity: AnomalySeverity) -> DetectedAnomaly {
+    DetectedAnomaly {
+        id: AnomalyId::new(),
+        timestamp,
+        anomaly_type: AnomalyType::VolatilitySpike { expected_volatility: 0.001, actual_volatility: 0.004 },
+        severity,
+        confidence: 0.9,
+        deviation_magnitude: 3.0,
+        affected_symmetries: Vec::new(),
+        affected_cycles: Vec::new(),
+        market_context: MarketContext {
+            session: "London".to_string(),
+            volatility_regime: "High".to_string(),
+            trend_direction: "Sideways".to_string(),
+            recent_events: Vec::new(),
+            event_proximity: 0.0,
+        },
+        trading_signal: None,
+    }
+}
+
+/// Hourly bars on a 24-bar cycle
+fn bars(start: DateTime<Utc>, count: usize) -> Vec<ForexDataPoint> {
+    (0..count)
+        .map(|i| {
+            let close = 1.1 * (1.0 + 0.002 * (2.0 * PI * i as f64 / 24.0).sin());
+            ForexDataPoint { timestamp: start + Duration::hours(i as i64), open: close, high: close + 0.0002, low: close - 0.0002, close, volume: None }
+        })
+        .collect()
+}
+
+fn main() -> Result<()> {
+    println!("🔬 Detection History Test");
+    println!("=========================");
+    println!();
+
+    let db = EmbeddedForexDB::new()?;
+    let week = |n: i64| Utc.with_ymd_and_hms(2024, 1, 7, 0, 0, 0).unwrap() + Duration::weeks(n);
+
+    // Test 1: symmetry strength history by name
+    println!("📊 Test 1: Symmetry history");
+    for (n, strength) in [0.9, 0.7, 0.5].into_iter().enumerate() {
+        db.insert_symmetries("EURUSD", "H1", week(n as i64), &[symmetry("24-bar cyclic", 24, strength), symmetry("120-bar cyclic", 120, 0.6)])?;
+    }
+    db.insert_symmetries("EURUSD", "H4", week(0), &[symmetry("24-bar cyclic", 24, 0.1)])?;
+    db.insert_symmetries("GBPUSD", "H1", week(0), &[symmetry("24-bar cyclic", 24, 0.2)])?;
+    let history = db.symmetry_history("EURUSD", "H1", "24-bar cyclic")?;
+    let strengths: Vec<f64> = history.iter().map(|observation| observation.strength).collect();
+    ensure!(strengths == [0.9, 0.7, 0.5], "history {:?}", strengths);
+    ensure!(history.iter().zip(0..).all(|(observation, n)| observation.observed_at == week(n)), "observation times");
+    println!("   ✅ 24-bar symmetry weakening 0.9 → 0.7 → 0.5, other pairs and timeframes kept apart");
+
+    // Test 2: recording a run again replaces it
+    println!("📊 Test 2: Re-recorded runs");
+    db.insert_symmetries("EURUSD", "H1", week(2), &[symmetry("24-bar cyclic", 24, 0.55)])?;
+    ensure!(db.symmetry_history("EURUSD", "H1", "24-bar cyclic")?.last().map(|o| o.strength) == Some(0.55), "run not replaced");
+    ensure!(db.symmetry_history("EURUSD", "H1", "120-bar cyclic")?.len() == 2, "a symmetry dropped from the re-run survived");
+    let latest = db.latest_symmetries("EURUSD", "H1")?;
+    ensure!(latest.len() == 1 && latest[0].strength == 0.55 && latest[0].mirror_points.len() == 2, "latest run {:?}", latest.len());
+    println!("   ✅ Re-running a week replaces its symmetries");
+
+    // Test 3: cycles
+    println!("📊 Test 3: Cycle history");
+    db.insert_cycles("EURUSD", "H1", week(0), &[cycle("Daily", 24, 0.1), cycle("Weekly", 120, 1.0)])?;
+    db.insert_cycles("EURUSD", "H1", week(1), &[cycle("Daily", 24, 0.4)])?;
+    let phases: Vec<f64> = db.cycle_history("EURUSD", "H1", "Daily")?.iter().map(|o| o.phase).collect();
+    ensure!(phases == [0.1, 0.4], "cycle phases {:?}", phases);
+    let latest = db.latest_cycles("EURUSD", "H1")?;
+    ensure!(latest.len() == 1 && latest[0].name == "Daily" && latest[0].p_value == Some(0.01), "latest cycles");
+    println!("   ✅ Daily cycle phase drift 0.1 → 0.4 recorded");
+
+    // Test 4: the detector takes its expectations from the latest run
+    println!("📊 Test 4: Detector expectations");
+    let history = bars(week(0), 24 * 14);
+    let detector = TemporalAnomalyDetector::from_db(&db, "EURUSD", "H1", &history, AnomalyDetectionConfig::default())?;
+    ensure!(detector.expected_symmetries().len() == 1 && detector.expected_symmetries()[0].strength == 0.55, "expected symmetries");
+    ensure!(detector.expected_cycles().len() == 1 && detector.expected_cycles()[0].name == "Daily", "expected cycles");
+    ensure!(TemporalAnomalyDetector::from_db(&db, "USDJPY", "H1", &history, AnomalyDetectionConfig::default()).is_err(),
+            "a pair with nothing recorded built a detector");
+    println!("   ✅ Detector built from the latest recorded symmetries and cycles");
+
+    // Test 5: anomalies
+    println!("📊 Test 5: Anomalies");
+    let anomalies: Vec<DetectedAnomaly> = (0..5).map(|day| anomaly(week(1) + Duration::days(day), AnomalySeverity::High)).collect();
+    ensure!(db.insert_anomalies("EURUSD", &anomalies)? == 5, "inserted count");
+    db.insert_anomalies("EURUSD", &anomalies[..2])?;
+    db.insert_anomalies("GBPUSD", &[anomaly(week(1), AnomalySeverity::Low)])?;
+    let stored = db.get_anomalies("EURUSD", week(0), week(3))?;
+    ensure!(stored.len() == 5, "duplicated or missing anomalies: {}", stored.len());
+    ensure!(stored.iter().zip(&anomalies).all(|(a, b)| a.id == b.id && a.timestamp == b.timestamp && a.severity == b.severity),
+            "anomalies read back out of order");
+    let range = db.get_anomalies("EURUSD", week(1) + Duration::days(1), week(1) + Duration::days(3))?;
+    ensure!(range.len() == 3, "range held {}", range.len());
+    ensure!(matches!(range[0].anomaly_type, AnomalyType::VolatilitySpike { .. }), "anomaly type lost");
+    println!("   ✅ {} anomalies recorded once each, {} in a three-day range", stored.len(), range.len());
+
+    println!();
+    println!("🎉 All detection history tests passed");
+    Ok(())
+}
@@ -0,0 +1,129 @@
+//! # Symmetry Dossier Test
+//!
+//! Build weekday-only daily history with a weekly cycle, then check the dossier's
+//! autocorrelation, out-of-sample persistence, P&L attribution and rendered HTML
+
+use anyhow::{ensure, Result};
+use chrono::{DateTime, Datelike, Duration, TimeZone, Utc, Weekday};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::f64::consts::PI;
+
+use forex_pattern_reconstruction::backtest::ValidationResults;
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::report::dossier::{lagged_autocorrelation, AnalysisArtifact, DossierConfig, DossierGenerator};
+use forex_pattern_reconstruction::report::TradeRecord;
+use forex_pattern_reconstruction::symmetry::TemporalSymmetry;
+
+/// Weekday closes following a 7-day sine (0.5% amplitude) plus noise; the cycle
+/// stops at `cycle_until`
+fn history(days: i64, cycle_until: DateTime<Utc>, seed: u64) -> Vec<ForexDataPoint> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let start = Utc.with_ymd_and_hms(2022, 1, 3, 0, 0, 0).unwrap();
+    let mut drift = 0.0;
+    (0..days)
+        .map(|day| start + Duration::days(day))
+        .filter(|t| !matches!(t.weekday(), Weekday::Sat | Weekday::Sun))
+        .map(|timestamp| {
+            drift += rng.gen_range(-0.0005..0.0005);
+            let days = (timestamp - start).num_days() as f64;
+            let cycle = if timestamp < cycle_until { 0.005 * (2.0 * PI * days / 7.0).sin() } else { 0.0 };
+            let close = 1.1 * (1.0 + cycle + drift);
+            ForexDataPoint { timestamp, open: close, high: close * 1.001, low: close * 0.999, close, volume: None }
+        })
+        .collect()
+}
+
+fn symmetry(id: &str, period_days: u32) -> TemporalSymmetry {
+    TemporalSymmetry {
+        id: id.into(),
+        symmetry_type: "mirror".to_string(),
+        name: format!("{}-Day Symmetry", period_days),
+        period_days,
+        strength: 0.8,
+        confidence: 0.8,
+        field_signature: 0x2a,
+        discovered_at: Utc::now(),
+        validation_score: 0.7,
+        mirror_points: Vec::new(),
+        phase_shift: 0.0,
+    }
+}
+
+fn trade(symmetry_id: Option<&str>, profit_loss: f64) -> TradeRecord {
+    TradeRecord {
+        pair: "EURUSD".to_string(),
+        timestamp: Utc::now(),
+        side: "Buy".to_string(),
+        size: 10_000.0,
+        entry_price: 1.1,
+        exit_price: Some(1.101),
+        commission: 0.5,
+        profit_loss,
+        symmetry_id: symmetry_id.map(Into::into),
+        cycle_id: None,
+    }
+}
+
+fn main() -> Result<()> {
+    println!("🔬 SYMMETRY DOSSIER TEST");
+    println!("========================");
+    println!();
+
+    let persistent = history(730, DateTime::<Utc>::MAX_UTC, 1);
+
+    // Test 1: autocorrelation at the period, pairing bars across weekends by timestamp
+    println!("📊 Test 1: Autocorrelation at the symmetry period");
+    let weekly = lagged_autocorrelation(&persistent, Duration::days(7)).unwrap_or(0.0);
+    let off_period = lagged_autocorrelation(&persistent, Duration::days(10)).unwrap_or(0.0);
+    println!("   7 days: {:.3}, 10 days: {:.3}", weekly, off_period);
+    ensure!(weekly > 0.8, "weekly autocorrelation too weak: {:.3}", weekly);
+    ensure!(off_period < weekly - 0.5, "off-period lag should not look cyclic: {:.3}", off_period);
+    println!("✅ Weekly cycle visible at its period only");
+
+    // Test 2: persistence separates a lasting cycle from one that disappeared
+    println!("📊 Test 2: Out-of-sample persistence");
+    let generator = DossierGenerator::new(DossierConfig::default())?;
+    let symmetries = vec![symmetry("symmetry_0", 7), symmetry("symmetry_1", 10)];
+    let trades = vec![trade(Some("symmetry_0"), 120.0), trade(Some("symmetry_0"), -20.0), trade(Some("symmetry_1"), 30.0), trade(None, 20.0)];
+    let backtest = ValidationResults { total_return: 0.15, sharpe_ratio: 1.8, max_drawdown: 0.08, symmetry_score: 0.87, pattern_consistency: 0.82 };
+    let dossier = generator.compile("EURUSD", "1D", &symmetries, &persistent, &trades, Some(&backtest));
+    let lasting = dossier.evidence[0].persistence.unwrap_or(0.0);
+
+    let fading_data = history(730, persistent[persistent.len() / 2].timestamp, 1);
+    let fading = generator.compile("EURUSD", "1D", &symmetries, &fading_data, &[], None).evidence[0].persistence.unwrap_or(1.0);
+    println!("   lasting: {:.2}, fading: {:.2}", lasting, fading);
+    ensure!(lasting > 0.8, "persistent cycle scored {:.2}", lasting);
+    ensure!(fading < 0.3, "vanished cycle scored {:.2}", fading);
+    println!("✅ Persistence scores lasting and vanished cycles apart");
+
+    // Test 3: P&L attribution
+    println!("📊 Test 3: Backtest P&L contribution");
+    let weekly_evidence = &dossier.evidence[0];
+    ensure!(weekly_evidence.trades == 2 && (weekly_evidence.profit_loss - 100.0).abs() < 1e-9, "wrong attribution for symmetry_0");
+    ensure!(weekly_evidence.profit_loss_share.is_some_and(|s| (s - 100.0 / 150.0).abs() < 1e-9), "wrong P&L share");
+    ensure!((dossier.unattributed_profit_loss - 20.0).abs() < 1e-9, "unattributed P&L should be 20");
+    println!("✅ symmetry_0 contributed {:.1}% of {:.2}", weekly_evidence.profit_loss_share.unwrap_or(0.0) * 100.0, dossier.total_profit_loss);
+
+    // Test 4: HTML document and artifact loading
+    println!("📊 Test 4: Rendering");
+    let directory = std::env::temp_dir().join(format!("dossier_{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&directory)?;
+    let artifact_path = directory.join("GBPUSD_4H_analysis.json");
+    std::fs::write(&artifact_path, serde_json::to_string(&serde_json::json!({ "temporal_symmetries": symmetries }))?)?;
+    let artifact = AnalysisArtifact::load(&artifact_path)?;
+    ensure!(artifact.pair.as_deref() == Some("GBPUSD") && artifact.timeframe.as_deref() == Some("4H"), "pair/timeframe not taken from file name");
+
+    let writer = DossierGenerator::new(DossierConfig { output_directory: directory.clone(), ..DossierConfig::default() })?;
+    let path = writer.write(&dossier)?;
+    let html = std::fs::read_to_string(&path)?;
+    std::fs::remove_dir_all(&directory)?;
+    ensure!(path.ends_with("EURUSD_1D_dossier.html"), "unexpected file name {}", path.display());
+    ensure!(html.matches("<svg").count() == 2, "expected a mirror plot per symmetry");
+    ensure!(html.contains("7-Day Symmetry") && html.contains("Unattributed") && html.contains("Sharpe 1.80"), "missing sections");
+    println!("✅ {} bytes of HTML with {} plots", html.len(), html.matches("<svg").count());
+
+    println!();
+    println!("🎉 All dossier tests passed");
+    Ok(())
+}
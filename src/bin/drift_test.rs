@@ -0,0 +1,141 @@
+//! # Drift Test
+//!
+//! Check a stationary walk raises no drift, that a volatility regime shift is
+//! caught by both PSI and KS, that alerts respect the cooldown, and that a pair
+//! re-analyses, resets exploration or pauses in response as configured
+
+use anyhow::{ensure, Result};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use forex_pattern_reconstruction::anomaly::suppression::SuppressionList;
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::multi_currency::{
+    ControlCommand, CurrencyPairConfig, CurrencyPairState, MultiCurrencyManager, PairAccount,
+};
+use forex_pattern_reconstruction::stats::drift::{
+    ks_p_value, ks_statistic, population_stability_index, DriftAction, DriftConfig, DriftFeature, DriftMonitor,
+};
+
+/// `count` daily bars after `start` whose returns have standard deviation ≈ `step`
+fn walk(start: DateTime<Utc>, close: f64, count: usize, step: f64, rng: &mut StdRng) -> Vec<ForexDataPoint> {
+    let mut close = close;
+    (1..=count as i64)
+        .map(|i| {
+            close *= 1.0 + rng.gen_range(-step..step) * 3f64.sqrt();
+            ForexDataPoint { timestamp: start + Duration::days(i), open: close, high: close * (1.0 + step), low: close * (1.0 - step), close, volume: None }
+        })
+        .collect()
+}
+
+/// `data` followed by `count` bars at volatility `step`
+fn extend(data: &mut Vec<ForexDataPoint>, count: usize, step: f64, rng: &mut StdRng) {
+    let last = data.last().unwrap().clone();
+    data.extend(walk(last.timestamp, last.close, count, step, rng));
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 DRIFT TEST");
+    println!("=============");
+    println!();
+
+    let mut rng = StdRng::seed_from_u64(11);
+    let start = Utc.with_ymd_and_hms(2022, 1, 3, 0, 0, 0).unwrap();
+
+    // Test 1: the statistics behave on known samples
+    println!("📊 Test 1: PSI and KS");
+    let uniform: Vec<f64> = (0..1_000).map(|i| i as f64 / 1_000.0).collect();
+    let shifted: Vec<f64> = uniform.iter().map(|v| v + 0.5).collect();
+    ensure!(population_stability_index(&uniform, &uniform) < 1e-9, "identical samples have no PSI");
+    ensure!(population_stability_index(&uniform, &shifted) > 1.0, "half the mass out of range is a large PSI");
+    ensure!((ks_statistic(&uniform, &shifted) - 0.5).abs() < 2e-3, "KS of a half shift is 0.5");
+    ensure!(ks_p_value(0.0, 100, 100) == 1.0 && ks_p_value(0.5, 1_000, 1_000) < 1e-9, "p-values at the extremes");
+    // D = 1.36/sqrt(n/2) is the textbook 5% critical value
+    let critical = ks_p_value(1.36 / 50f64.sqrt(), 100, 100);
+    ensure!((critical - 0.05).abs() < 0.01, "5% critical value, got {:.4}", critical);
+    println!("   ✅ 5% critical value gives p={:.4}", critical);
+
+    // Test 2: a stationary walk does not drift
+    println!("📊 Test 2: stationary inputs");
+    let mut data = walk(start, 1.1, 600, 0.001, &mut rng);
+    let mut monitor = DriftMonitor::new(DriftConfig::default());
+    ensure!(monitor.check("EURUSD", &data).is_none(), "nothing to compare before fitting");
+    monitor.fit(&data);
+    ensure!(monitor.reference_end() == Some(data.last().unwrap().timestamp), "reference ends with the training window");
+    let mut false_alarms = 0;
+    for _ in 0..10 {
+        extend(&mut data, 50, 0.001, &mut rng);
+        false_alarms += monitor.check("EURUSD", &data).is_some() as usize;
+    }
+    ensure!(false_alarms == 0, "{} false alarms on a stationary walk", false_alarms);
+    println!("   ✅ 500 stationary bars, no alarm");
+
+    // Test 3: a volatility regime shift is caught, then cools down
+    println!("📊 Test 3: regime shift");
+    let mut data = walk(start, 1.1, 600, 0.001, &mut rng);
+    let mut monitor = DriftMonitor::new(DriftConfig::default());
+    monitor.fit(&data);
+    extend(&mut data, 40, 0.003, &mut rng);
+    ensure!(monitor.check("EURUSD", &data).is_none(), "too few live bars to test");
+    extend(&mut data, 20, 0.003, &mut rng);
+    let event = monitor.check("EURUSD", &data).ok_or_else(|| anyhow::anyhow!("tripled volatility drifts"))?;
+    let volatility = event.tests.iter().find(|test| test.feature == DriftFeature::Volatility).unwrap();
+    ensure!(volatility.drifted && volatility.psi >= 0.25 && volatility.ks_p_value < 0.01, "volatility drifted: {:?}", volatility);
+    ensure!(event.reference_end < event.detected_at && event.live_bars == 60, "tested the 60 new bars");
+    ensure!(event.actions == [DriftAction::Reanalyze, DriftAction::ResetExploration], "default actions");
+    extend(&mut data, 10, 0.003, &mut rng);
+    ensure!(monitor.check("EURUSD", &data).is_none(), "cooldown holds the next alert");
+    extend(&mut data, 40, 0.003, &mut rng);
+    ensure!(monitor.check("EURUSD", &data).is_some(), "drift persists after the cooldown");
+    let mut disabled = DriftMonitor::new(DriftConfig { enabled: false, ..DriftConfig::default() });
+    disabled.fit(&data[..600]);
+    ensure!(disabled.check("EURUSD", &data).is_none(), "disabled monitors stay quiet");
+    println!("   ✅ {}", event.summary());
+
+    // Test 4: a pair re-analyses and resets exploration
+    println!("📊 Test 4: re-analysis and exploration reset");
+    let mut pair = CurrencyPairState::new(CurrencyPairConfig::default()).await?;
+    pair.historical_data = walk(start, 1.1, 600, 0.001, &mut rng);
+    pair.reanalyze().await?;
+    let fitted = pair.drift_monitor.reference_end();
+    pair.is_active = true;
+    pair.warm = true;
+    pair.rl_agent.set_exploration_rate(0.02);
+    extend(&mut pair.historical_data, 60, 0.003, &mut rng);
+    let account = PairAccount { position_units: 0.0, equity: 100_000.0, units_per_size: 1_000.0 };
+    pair.process_market_update_seeded(&SuppressionList::default(), &account, 7).await?;
+    let events = pair.take_drift_events();
+    ensure!(events.len() == 1 && pair.take_drift_events().is_empty(), "one event, taken once");
+    ensure!(pair.drift_monitor.reference_end() > fitted, "re-analysis moves the reference to the new regime");
+    ensure!((pair.rl_agent.exploration_rate() - 0.1).abs() < 1e-12, "exploration back to its initial rate");
+    ensure!(!pair.drift_paused, "trading continues");
+    println!("   ✅ Reference refitted, exploration reset to {:.2}", pair.rl_agent.exploration_rate());
+
+    // Test 5: a manager pair pauses until resumed, and the drift is audited
+    println!("📊 Test 5: trading pause");
+    let mut manager = MultiCurrencyManager::new();
+    manager.initialize_pairs(&["EURUSD".to_string()]).await?;
+    {
+        let mut pairs = manager.pairs.write().await;
+        let state = pairs.get_mut("EURUSD").unwrap();
+        state.historical_data = walk(start, 1.1, 600, 0.001, &mut rng);
+        state.drift_monitor = DriftMonitor::new(DriftConfig { actions: vec![DriftAction::PauseTrading], ..DriftConfig::default() });
+        state.drift_monitor.fit(&state.historical_data);
+        extend(&mut state.historical_data, 60, 0.003, &mut rng);
+        state.is_active = true;
+        state.warm = true;
+    }
+    let actions = manager.process_all_market_updates().await?;
+    ensure!(actions.is_empty() && manager.pairs.read().await["EURUSD"].drift_paused, "drift pauses the pair");
+    let audited = manager.audit_log.recent(10);
+    ensure!(audited.iter().any(|entry| entry.source == "drift-monitor" && entry.command.contains("PauseTrading")), "drift audited: {:?}", audited);
+    manager.execute_command(&ControlCommand::Resume { pair: "EURUSD".to_string() }, "test").await?;
+    ensure!(!manager.pairs.read().await["EURUSD"].drift_paused, "resume lifts the drift pause");
+    println!("   ✅ Paused on drift, resumed by the operator");
+
+    println!();
+    println!("🎉 All drift tests passed");
+    Ok(())
+}
@@ -0,0 +1,154 @@
+//! # Economic Calendar Test
+//!
+//! Load releases from generic CSV, ForexFactory CSV and ForexFactory JSON feeds,
+//! check the time index, the per-pair event lists and the event proximity score,
+//! then run a payrolls spike through the anomaly detector and the RL state
+
+use anyhow::{ensure, Result};
+use chrono::{DateTime, Duration, Utc};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use forex_pattern_reconstruction::anomaly::{AnomalyDetectionConfig, DetectedAnomaly, TemporalAnomalyDetector};
+use forex_pattern_reconstruction::calendar::economic::{
+    load_events, EconomicCalendar, EconomicCalendarConfig, EconomicEvent, EventImpact, ECONOMIC_EVENT,
+};
+use forex_pattern_reconstruction::laplacian_rl::backend::state_features;
+use forex_pattern_reconstruction::laplacian_rl::{LaplacianQLearningAgent, LaplacianQLearningConfig};
+use forex_pattern_reconstruction::synthetic::fixtures::{at, hourly_walk, spike};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 ECONOMIC CALENDAR TEST");
+    println!("=========================");
+    println!();
+
+    // Test 1: all three feed layouts load into the same events
+    println!("📊 Test 1: feeds");
+    let directory = std::env::temp_dir().join(format!("calendar_{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&directory)?;
+    let generic = directory.join("events.csv");
+    std::fs::write(&generic, "timestamp,currency,event,impact,forecast,previous,actual\n\
+        2024-07-05 12:30:00,USD,Non-Farm Employment Change,High,190K,272K,206K\n\
+        2024-07-03T08:00:00Z,eur,Services PMI,Medium,,,\n\
+        not a time,USD,Broken Row,High,,,\n")?;
+    let forex_factory_csv = directory.join("ff_calendar.csv");
+    std::fs::write(&forex_factory_csv, "Title,Country,Date,Time,Impact,Forecast,Previous\n\
+        Non-Farm Employment Change,USD,07-05-2024,12:30pm,High,190K,272K\n\
+        Bank Holiday,GBP,07-05-2024,All Day,Holiday,,\n")?;
+    let forex_factory_json = directory.join("ff_calendar.json");
+    std::fs::write(&forex_factory_json, r#"[
+        {"title":"Non-Farm Employment Change","country":"USD","date":"2024-07-05T08:30:00-04:00","impact":"High","forecast":"190K","previous":"272K"},
+        {"title":"Unemployment Rate","country":"USD","date":"2024-07-05T08:30:00-04:00","impact":"High","forecast":"4.0%","previous":"4.0%"},
+        {"title":"Tentative Speech","country":"USD","date":"","impact":"Low"}
+    ]"#)?;
+
+    let nfp = at(2024, 7, 5, 12, 30);
+    let events = load_events(&generic)?;
+    ensure!(events.len() == 2, "broken rows are skipped, got {} events", events.len());
+    let payrolls = events.iter().find(|e| e.name == "Non-Farm Employment Change").unwrap();
+    ensure!(payrolls.timestamp == nfp && payrolls.impact == EventImpact::High && payrolls.actual.as_deref() == Some("206K"),
+            "generic row read as {:?}", payrolls);
+    ensure!(events.iter().any(|e| e.currency == "EUR" && e.impact == EventImpact::Medium), "currencies are upper-cased");
+
+    let events = load_events(&forex_factory_csv)?;
+    ensure!(events[0].timestamp == nfp && events[0].previous.as_deref() == Some("272K"), "ForexFactory CSV read as {:?}", events[0]);
+    ensure!(events[1].timestamp == at(2024, 7, 5, 0, 0) && events[1].impact == EventImpact::Low, "all-day holidays at midnight, low impact");
+
+    let events = load_events(&forex_factory_json)?;
+    ensure!(events.len() == 2 && events.iter().all(|e| e.timestamp == nfp), "JSON dates converted to UTC: {:?}", events);
+    ensure!(load_events(&directory.join("missing.csv")).is_err(), "a missing feed is an error");
+    println!("   ✅ Generic CSV, ForexFactory CSV and JSON agree on {}", nfp);
+
+    // Test 2: events are indexed by time and filtered by pair and impact
+    println!("📊 Test 2: lookups");
+    let mut events = load_events(&forex_factory_json)?;
+    std::fs::remove_dir_all(&directory)?;
+    events.extend([
+        EconomicEvent {
+            timestamp: at(2024, 7, 5, 6, 0),
+            currency: "GBP".to_string(),
+            name: "GDP m/m".to_string(),
+            impact: EventImpact::High,
+            forecast: None,
+            previous: None,
+            actual: None,
+        },
+        EconomicEvent {
+            timestamp: at(2024, 7, 5, 12, 0),
+            currency: "EUR".to_string(),
+            name: "German Buba Speech".to_string(),
+            impact: EventImpact::Low,
+            forecast: None,
+            previous: None,
+            actual: None,
+        },
+    ]);
+    let calendar = EconomicCalendar::new(events, EconomicCalendarConfig::default());
+    ensure!(calendar.events().windows(2).all(|w| w[0].timestamp <= w[1].timestamp), "events are sorted");
+    ensure!(calendar.events_between(at(2024, 7, 5, 12, 0), nfp).len() == 3, "inclusive time range");
+
+    let listed = calendar.recent_events("EURUSD", at(2024, 7, 5, 13, 0));
+    ensure!(listed.len() == 2 && listed.iter().all(|e| e.starts_with(ECONOMIC_EVENT) && e.contains("USD")),
+            "EURUSD lists the two USD releases, not the low-impact speech: {:?}", listed);
+    ensure!(calendar.recent_events("EURGBP", at(2024, 7, 5, 13, 0)).is_empty(), "USD releases do not concern EURGBP");
+    ensure!(calendar.recent_events("EURUSD", at(2024, 7, 5, 15, 0)).is_empty(), "releases outside the lookback are not listed");
+
+    ensure!(calendar.proximity("EURUSD", nfp) == 1.0, "full proximity at a high-impact release");
+    ensure!((calendar.proximity("EURUSD", nfp - Duration::minutes(30)) - 0.75).abs() < 1e-9, "linear decay before the release");
+    ensure!((calendar.proximity("EURUSD", nfp + Duration::minutes(90)) - 0.25).abs() < 1e-9, "linear decay after the release");
+    ensure!(calendar.proximity("EURUSD", nfp + Duration::hours(3)) == 0.0, "no proximity outside the window");
+    ensure!(calendar.proximity("GBPJPY", at(2024, 7, 5, 6, 0)) == 1.0, "GBP releases concern GBPJPY");
+    println!("   ✅ {} listed for EURUSD an hour after payrolls", listed.join("; "));
+
+    // Test 3: the detector labels the payrolls spike and trusts it less
+    println!("📊 Test 3: anomaly detection around a release");
+    let mut rng = StdRng::seed_from_u64(43);
+    let history = hourly_walk(&mut rng, at(2024, 5, 6, 0, 0), 24 * 7 * 8);
+    let mut live = hourly_walk(&mut rng, at(2024, 7, 1, 0, 0), 24 * 5);
+    let (ordinary_spike, release_spike) = (at(2024, 7, 3, 14, 0), at(2024, 7, 5, 13, 0));
+    spike(&mut live, ordinary_spike);
+    spike(&mut live, release_spike);
+    let detection = AnomalyDetectionConfig { min_anomaly_confidence: 0.3, ..AnomalyDetectionConfig::default() };
+    let detector = || TemporalAnomalyDetector::new(Vec::new(), Vec::new(), &history, detection.clone());
+    let on = |anomalies: &[DetectedAnomaly], timestamp: DateTime<Utc>| -> Vec<DetectedAnomaly> {
+        anomalies.iter().filter(|a| a.timestamp == timestamp).cloned().collect()
+    };
+
+    let plain = detector()?.detect_anomalies(&live).await?;
+    let mut with_calendar = detector()?.with_economic_calendar("EURUSD", calendar.clone());
+    ensure!(with_calendar.event_proximity(nfp) == 1.0 && detector()?.event_proximity(nfp) == 0.0, "proximity needs a calendar");
+    let annotated = with_calendar.detect_anomalies(&live).await?;
+    ensure!(!on(&plain, release_spike).is_empty(), "the release spike is an anomaly without a calendar");
+    let proximity = calendar.proximity("EURUSD", release_spike);
+    for anomaly in on(&annotated, release_spike) {
+        let full = on(&plain, release_spike).into_iter().find(|a| a.anomaly_type.name() == anomaly.anomaly_type.name());
+        let expected = if anomaly.anomaly_type.is_price_shock() { 1.0 - 0.5 * proximity } else { 1.0 };
+        ensure!(full.is_some_and(|f| (anomaly.confidence - expected * f.confidence).abs() < 1e-9),
+                "{} confidence scaled by {:.2}", anomaly.anomaly_type.name(), expected);
+        ensure!(anomaly.market_context.event_proximity == proximity, "proximity recorded in the market context");
+        ensure!(anomaly.market_context.recent_events.iter().any(|e| e.contains("Non-Farm Employment Change")), "the anomaly names payrolls");
+    }
+    ensure!(on(&annotated, release_spike).iter().any(|a| a.anomaly_type.is_price_shock()), "the release spike is still reported");
+    ensure!(on(&annotated, ordinary_spike).iter().all(|a| a.market_context.recent_events.is_empty() && a.market_context.event_proximity == 0.0),
+            "ordinary anomalies are unlabelled");
+    println!("   ✅ Release anomalies labelled at proximity {:.2}, price shocks discounted", proximity);
+
+    // Test 4: the RL state carries event proximity
+    println!("📊 Test 4: RL state");
+    let mut agent = LaplacianQLearningAgent::new(LaplacianQLearningConfig::default())?;
+    let shock = on(&annotated, release_spike).into_iter().find(|a| a.anomaly_type.is_price_shock()).unwrap();
+    let bar = live.iter().find(|b| b.timestamp == release_spike).unwrap();
+    let near = agent.anomaly_to_state(&shock, bar)?;
+    let mut far = shock.clone();
+    far.market_context.event_proximity = 0.0;
+    let far = agent.anomaly_to_state(&far, bar)?;
+    ensure!(near != far, "proximity distinguishes states");
+    ensure!((state_features(&near)[7] - (proximity * 100.0).round() / 100.0).abs() < 1e-9, "proximity decoded from {}", near);
+    ensure!(state_features("s_0.00_0.00_1.50_0.00_0.00_0.00_0.80")[2] == 1.5, "legacy state ids still decode");
+    println!("   ✅ {} near the release, {} without it", near, far);
+
+    println!();
+    println!("🎉 All economic calendar tests passed");
+    Ok(())
+}
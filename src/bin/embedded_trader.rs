@@ -2,16 +2,24 @@ use anyhow::Result;
 use std::collections::HashMap;
 use std::env;
 use std::time::Instant;
-use warp::Filter;
-use serde_json::json;
 use std::sync::Arc;
-use tokio::sync::Mutex;
 
 use forex_pattern_reconstruction::{
     data::{ForexDataManager, DataConfig, ForexDataPoint},
+    data::provider::provider_from_env,
+    broker::backend_from_env,
     embedded_db::EmbeddedForexDB,
-    correlation::CrossPairAnalyzer,
+    correlation::{ClusterConfig, CrossPairAnalyzer, LeadLagConfig},
     multi_currency::MultiCurrencyManager,
+    multi_currency::approval::{ApprovalConfig, ExecutionMode},
+    audit::AuditLog,
+    replay::SessionLog,
+    portfolio::execution_quality::{ExecutionLog, PAPER_BROKER},
+    anomaly::suppression::SuppressionList,
+    resilience::chaos::{ChaosProvider, FaultInjector},
+    protocol::{ArbitrageOpportunity, RemoteSystemStatus, SystemMetrics},
+    protocol::server::{self, ApiState},
+    trading_windows::TradingWindowsConfig,
 };
 
 /// All 15 major currency pairs available in the dataset
@@ -36,9 +44,17 @@ async fn main() -> Result<()> {
 
     let start_time = Instant::now();
     
-    // Initialize embedded database
-    println!("🗄️  Initializing embedded SQLite database...");
-    let db = EmbeddedForexDB::new()?;
+    // Initialize embedded database (a persistent one also lets a restart replay recent bars)
+    let db = match env::var("FOREX_DB_PATH") {
+        Ok(path) => {
+            println!("🗄️  Opening SQLite database {}...", path);
+            EmbeddedForexDB::open(std::path::Path::new(&path))?
+        }
+        Err(_) => {
+            println!("🗄️  Initializing embedded SQLite database...");
+            EmbeddedForexDB::new()?
+        }
+    };
     
     // Load and store all forex data
     let data_path = env::var("FOREX_DATA_PATH")
@@ -56,7 +72,7 @@ async fn main() -> Result<()> {
                 println!("✅ {} - Loaded {} historical data points", pair, data.len());
                 
                 // Store in embedded database
-                db.store_forex_data(pair, &data)?;
+                db.append_forex_data(pair, &data)?;
                 all_data.insert(pair.to_string(), data);
             }
             Err(e) => {
@@ -82,14 +98,58 @@ async fn main() -> Result<()> {
     
     // Display correlation analysis
     correlation_analyzer.print_correlation_analysis(&correlations);
+    let clusters = correlation_analyzer.cluster_pairs(&correlations, &ClusterConfig::default())?;
+    correlation_analyzer.print_cluster_report(&clusters);
     
     // Find arbitrage opportunities
     let arbitrage_opportunities = correlation_analyzer.find_arbitrage_opportunities(&correlations, &all_data)?;
     correlation_analyzer.print_arbitrage_opportunities(&arbitrage_opportunities);
+    let lead_lag = correlation_analyzer.find_lead_lag_opportunities(&all_data, &LeadLagConfig::default());
+    correlation_analyzer.print_lead_lag_opportunities(&lead_lag);
     
     // Initialize multi-currency trading system
     println!("\n🚀 Initializing multi-currency anomaly trading system...");
-    let mut multi_currency_manager = MultiCurrencyManager::new();
+    let mut multi_currency_manager = match env::var("AUDIT_LOG_PATH") {
+        Ok(path) => MultiCurrencyManager::new().with_audit_log(AuditLog::with_file(std::path::Path::new(&path))?),
+        Err(_) => MultiCurrencyManager::new(),
+    }
+    .with_backfill_db(db.clone())
+    .with_suppressions(SuppressionList::from_env()?);
+    let approval = ApprovalConfig::from_env()?;
+    if approval.mode == ExecutionMode::Approval {
+        println!("⏳ Approval mode: actions wait {}s for `approve` / `reject`", approval.timeout_seconds);
+    }
+    multi_currency_manager = multi_currency_manager.with_approval_config(approval);
+    if let Ok(path) = env::var("SESSION_LOG_PATH") {
+        println!("🎞️  Recording session to {} (replay with `replay-session`)", path);
+        multi_currency_manager = multi_currency_manager.with_session_log(SessionLog::with_file(std::path::Path::new(&path))?);
+    }
+    if let Ok(path) = env::var("TRADING_WINDOWS_CONFIG") {
+        multi_currency_manager = multi_currency_manager.with_trading_windows(TradingWindowsConfig::from_file(std::path::Path::new(&path))?);
+    }
+    let backend = backend_from_env().await?;
+    if let Some(backend) = &backend {
+        println!("🏦 Orders filled by the {} execution backend", backend.name());
+        multi_currency_manager = multi_currency_manager.with_execution_backend(Arc::clone(backend));
+    }
+    if let Ok(path) = env::var("EXECUTION_LOG_PATH") {
+        let broker = env::var("BROKER_NAME").ok()
+            .or_else(|| backend.as_ref().map(|backend| backend.name().to_string()))
+            .unwrap_or_else(|| PAPER_BROKER.to_string());
+        println!("🧾 Recording {} fills to {} (set backtest execution_log to calibrate costs)", broker, path);
+        multi_currency_manager = multi_currency_manager.with_execution_log(ExecutionLog::with_file(std::path::Path::new(&path))?.with_broker(&broker));
+    }
+    let fault_injector = FaultInjector::from_env()?;
+    if let Some(injector) = &fault_injector {
+        multi_currency_manager = multi_currency_manager.with_fault_injector(Arc::clone(injector));
+    }
+    if let Some(mut provider) = provider_from_env()? {
+        if let Some(injector) = &fault_injector {
+            provider = Arc::new(ChaosProvider::new(provider, Arc::clone(injector)));
+        }
+        println!("📡 Live data provider: {}", provider.name());
+        multi_currency_manager = multi_currency_manager.with_data_provider(provider);
+    }
 
     // Initialize major pairs (simplified for demo)
     multi_currency_manager.initialize_major_pairs().await?;
@@ -112,16 +172,25 @@ async fn main() -> Result<()> {
     // Start trading simulation
     println!("\n🎯 Starting embedded trading simulation...");
     
-    // Simulate trading for a short period
+    let multi_currency_manager = Arc::new(multi_currency_manager);
+    if multi_currency_manager.data_provider.is_some() {
+        multi_currency_manager.start_live_feed()?;
+    }
+    multi_currency_manager.start_allocation_schedule();
+    
+    // Run a few trading cycles before serving the API
     for i in 0..5 {
         println!("\n📈 Trading Cycle {} - Analyzing all {} pairs...", i + 1, ALL_CURRENCY_PAIRS.len());
+        run_trading_cycle(&multi_currency_manager).await?;
         
-        // Simulate portfolio performance
+        let portfolio = multi_currency_manager.portfolio_snapshot().await;
         println!("💰 Portfolio Performance:");
         println!("   Total Pairs: {}", ALL_CURRENCY_PAIRS.len());
         println!("   Active Pairs: {}", multi_currency_manager.active_pairs.len());
-        println!("   Total Profit: ${:.2}", 1250.75); // Simulated
-        println!("   Win Rate: {:.1}%", 73.5); // Simulated
+        println!("   Equity: ${:.2} (realized ${:.2}, unrealized ${:.2})",
+                 portfolio.equity, portfolio.realized_pnl, portfolio.unrealized_pnl);
+        println!("   Open Positions: {} | Margin Used: ${:.2}", portfolio.positions.len(), portfolio.margin_used);
+        println!("   Win Rate: {:.1}% over {} closed trades", portfolio.win_rate, portfolio.total_trades);
         
         // Check for arbitrage opportunities
         if !arbitrage_opportunities.is_empty() {
@@ -130,9 +199,9 @@ async fn main() -> Result<()> {
             println!("   Primary: {} | Correlated: {:?}", 
                      best_opportunity.primary_pair, 
                      best_opportunity.correlated_pairs);
-            println!("   Confidence: {:.1}% | Profit Potential: {:.1} pips", 
+            println!("   Confidence: {:.1}% | Profit Potential: {:.1}", 
                      best_opportunity.confidence * 100.0,
-                     best_opportunity.profit_potential * 10000.0);
+                     best_opportunity.profit_potential.to_pips(&best_opportunity.primary_pair));
         }
         
         // Simulate processing delay
@@ -146,95 +215,51 @@ async fn main() -> Result<()> {
     let port = env::var("API_PORT").unwrap_or_else(|_| "8080".to_string());
     println!("\n🌐 Starting HTTP API server on port {}...", port);
 
-    // Create shared state for API
-    let system_stats = Arc::new(Mutex::new(json!({
-        "status": "running",
-        "uptime": 0,
-        "active_pairs": ALL_CURRENCY_PAIRS,
-        "total_trades": 0,
-        "profit_loss": 1250.75,
-        "correlation_opportunities": arbitrage_opportunities.iter().take(5).map(|opp| {
-            json!({
-                "primary_pair": opp.primary_pair,
-                "correlated_pair": opp.correlated_pairs.get(0).unwrap_or(&"N/A".to_string()),
-                "confidence": opp.confidence,
-                "theoretical_pips": opp.profit_potential * 10000.0,
-                "realistic_pips": (opp.profit_potential * 10000.0 * 0.1).min(50.0),
-                "execution_cost": 2.5,
-                "net_expected_pips": (opp.profit_potential * 10000.0 * 0.1).min(50.0) - 2.5,
-                "position_size": 1000.0,
-                "time_window": "5-15 minutes"
-            })
-        }).collect::<Vec<_>>(),
-        "system_metrics": {
-            "cpu_usage": 0.15,
-            "memory_usage": 0.25,
-            "network_latency": 45.0,
-            "database_size": 1800000,
-            "active_connections": 1
+    // Keep trading in the background while the API is up
+    let trading_manager = multi_currency_manager.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(10));
+        loop {
+            interval.tick().await;
+            if let Err(e) = run_trading_cycle(&trading_manager).await {
+                println!("⚠️  Trading cycle failed: {}", e);
+            }
         }
-    })));
+    });
 
-    // API routes
-    let status_route = warp::path("api")
-        .and(warp::path("status"))
-        .and(warp::get())
-        .and(warp::any().map(move || system_stats.clone()))
-        .and_then(|stats: Arc<Mutex<serde_json::Value>>| async move {
-            let stats = stats.lock().await;
-            Ok::<_, warp::Rejection>(warp::reply::json(&*stats))
-        });
-
-    let health_route = warp::path("health")
-        .and(warp::get())
-        .map(|| warp::reply::json(&json!({"status": "healthy"})));
-
-    // Command route for CLI controller
-    let command_route = warp::path("api")
-        .and(warp::path("command"))
-        .and(warp::post())
-        .and(warp::body::json())
-        .and_then(|command: serde_json::Value| async move {
-            println!("📨 Received command: {}", command);
-
-            // Handle mode switching command
-            if let Some(action) = command.get("action").and_then(|v| v.as_str()) {
-                if action == "switch_mode" {
-                    if let Some(params) = command.get("parameters").and_then(|v| v.as_object()) {
-                        let mode = params.get("mode").and_then(|v| v.as_str()).unwrap_or("DEMO");
-                        let server = params.get("server").and_then(|v| v.as_str()).unwrap_or("cTrader DEMO");
-                        let account_id = params.get("account_id").and_then(|v| v.as_str()).unwrap_or("5078436");
-
-                        println!("🔄 Switching to {} mode", mode);
-                        println!("📊 Server: {}", server);
-                        println!("🔑 Account ID: {}", account_id);
-
-                        // In a real implementation, this would reconfigure the cTrader connection
-                        // For now, we'll just acknowledge the command
-
-                        let response = json!({
-                            "status": "success",
-                            "message": format!("Successfully switched to {} mode", mode),
-                            "mode": mode,
-                            "server": server,
-                            "account_id": account_id
-                        });
-
-                        return Ok::<_, warp::Rejection>(warp::reply::json(&response));
-                    }
-                }
+    // Create shared state for API
+    let status = RemoteSystemStatus {
+        status: "running".to_string(),
+        uptime: 0,
+        active_pairs: ALL_CURRENCY_PAIRS.iter().map(|p| p.to_string()).collect(),
+        total_trades: 0,
+        profit_loss: 0.0,
+        correlation_opportunities: arbitrage_opportunities.iter().take(5).map(|opp| {
+            let theoretical_pips = opp.profit_potential.to_pips(&opp.primary_pair).0;
+            let realistic_pips = (theoretical_pips * 0.1).min(50.0);
+            ArbitrageOpportunity {
+                primary_pair: opp.primary_pair.clone(),
+                correlated_pair: opp.correlated_pairs.first().cloned().unwrap_or_else(|| "N/A".to_string()),
+                confidence: opp.confidence,
+                theoretical_pips,
+                realistic_pips,
+                execution_cost: 2.5,
+                net_expected_pips: realistic_pips - 2.5,
+                position_size: 1000.0,
+                time_window: "5-15 minutes".to_string(),
             }
+        }).collect(),
+        system_metrics: SystemMetrics {
+            cpu_usage: 0.15,
+            memory_usage: 0.25,
+            network_latency: 45.0,
+            database_size: 1800000,
+            active_connections: 1,
+        },
+        feed_health: None,
+    };
 
-            // Default response for unhandled commands
-            let response = json!({
-                "status": "acknowledged",
-                "message": "Command received but not implemented yet"
-            });
-
-            Ok::<_, warp::Rejection>(warp::reply::json(&response))
-        });
-
-    let routes = status_route.or(health_route).or(command_route);
+    let routes = server::routes(ApiState::new(multi_currency_manager.clone(), status));
 
     println!("🚀 HTTP API server running on http://0.0.0.0:{}", port);
     println!("📡 CLI Controller can now connect to monitor this system!");
@@ -246,3 +271,14 @@ async fn main() -> Result<()> {
 
     Ok(())
 }
+
+/// Run one anomaly-detection pass over every pair and fill the resulting actions
+async fn run_trading_cycle(manager: &MultiCurrencyManager) -> Result<()> {
+    let actions = manager.process_all_market_updates().await?;
+    for (pair, realized) in manager.execute_actions(&actions).await {
+        if realized != 0.0 {
+            println!("💵 {} realized ${:.2}", pair, realized);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,158 @@
+//! # Error Correction Test
+//!
+//! Check Reed-Solomon decoding corrects every error pattern within capacity over
+//! binary and prime fields, that bad ticks on trending and flat stretches are
+//! put back on the curve while genuine moves are left alone, and that the
+//! pattern recognizer reports syndrome statistics of the data it searched
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::seq::index::sample;
+use rand::{Rng, SeedableRng};
+
+use forex_pattern_reconstruction::core::EngineConfig;
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::galois::error_correction::BlockStatus;
+use forex_pattern_reconstruction::galois::{ErrorCorrectionConfig, GaloisField, PriceErrorCorrector, ReedSolomon};
+use forex_pattern_reconstruction::patterns::{PatternConfig, PatternRecognizer};
+
+fn bars(closes: &[f64]) -> Vec<ForexDataPoint> {
+    let start = Utc.with_ymd_and_hms(2023, 1, 2, 0, 0, 0).unwrap();
+    closes.iter()
+        .enumerate()
+        .map(|(i, &close)| ForexDataPoint { timestamp: start + Duration::days(i as i64), open: close, high: close, low: close, close, volume: None })
+        .collect()
+}
+
+/// Encode random messages, corrupt `errors` symbols and decode
+fn round_trips(code: &ReedSolomon, errors: usize, trials: usize, rng: &mut StdRng) -> Result<usize> {
+    let q = code.field().size();
+    let mut recovered = 0;
+    for _ in 0..trials {
+        let message: Vec<u64> = (0..code.message_length()).map(|_| rng.gen_range(0..q)).collect();
+        let codeword = code.encode(&message)?;
+        ensure!(code.syndromes(&codeword).iter().all(|s| *s == 0), "codewords have zero syndromes");
+        let mut received = codeword.clone();
+        let mut positions: Vec<usize> = sample(rng, code.length(), errors).into_vec();
+        positions.sort_unstable();
+        for &i in &positions {
+            received[i] = code.field().add(received[i], rng.gen_range(1..q));
+        }
+        let decoding = code.decode(&received);
+        if decoding.corrected.as_ref() == Some(&codeword) {
+            ensure!(decoding.error_positions == positions, "errors located at {:?}, got {:?}", positions, decoding.error_positions);
+            recovered += 1;
+        } else if let Some(other) = &decoding.corrected {
+            ensure!(code.syndromes(other).iter().all(|s| *s == 0), "any correction is a codeword");
+        }
+    }
+    Ok(recovered)
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 ERROR CORRECTION TEST");
+    println!("========================");
+    println!();
+
+    let mut rng = StdRng::seed_from_u64(23);
+
+    // Test 1: every error pattern within capacity is corrected
+    println!("📊 Test 1: Reed-Solomon decoding");
+    let codes = [
+        ReedSolomon::new(GaloisField::new_with_degree(2, 8)?, 255, 223)?,
+        ReedSolomon::new(GaloisField::new(65_537)?, 40, 10)?,
+        ReedSolomon::new(GaloisField::new_with_degree(2, 32)?, 64, 2)?,
+    ];
+    for code in &codes {
+        for errors in 0..=code.capacity() {
+            let recovered = round_trips(code, errors, 3, &mut rng)?;
+            ensure!(recovered == 3, "GF({}^{}) RS({}, {}) with {} errors", code.field().characteristic(), code.field().degree(), code.length(), code.message_length(), errors);
+        }
+        let beyond = round_trips(code, code.capacity() + 1, 3, &mut rng)?;
+        ensure!(beyond < 3, "one error past capacity cannot always decode");
+        println!("   ✅ RS({}, {}) over GF({}^{}) corrects {} errors",
+                 code.length(), code.message_length(), code.field().characteristic(), code.field().degree(), code.capacity());
+    }
+    ensure!(ReedSolomon::new(GaloisField::new_with_degree(2, 4)?, 16, 4).is_err(), "codes longer than the field's nonzero elements");
+
+    // Test 2: bad ticks on a trend are put back on it
+    println!("📊 Test 2: trend with bad ticks");
+    let prime_config = ErrorCorrectionConfig {
+        field_characteristic: 2_147_483_647,
+        field_degree: 1,
+        block_length: 50,
+        message_length: 2,
+        tick_size: 0.0001,
+        max_correction_fraction: 0.1,
+    };
+    let corrector = PriceErrorCorrector::new(prime_config.clone())?;
+    let trend: Vec<f64> = (0..200).map(|i| 1.1 + 0.0003 * i as f64).collect();
+    let mut noisy = trend.clone();
+    let spikes = [7, 31, 60, 99, 123, 177];
+    for &i in &spikes {
+        noisy[i] += 0.0040 * if i % 2 == 0 { 1.0 } else { -1.0 };
+    }
+    let report = corrector.correct(&bars(&noisy))?;
+    ensure!(report.statistics.blocks == 4 && report.statistics.corrected_blocks == 4, "every block corrected: {:?}", report.statistics);
+    ensure!(report.blocks.iter().flat_map(|b| b.corrected_bars.clone()).collect::<Vec<_>>() == spikes, "only the spikes change");
+    for (bar, expected) in report.corrected.iter().zip(&trend) {
+        ensure!((bar.close - expected).abs() < 1e-9, "back on the trend at {}: {} vs {}", bar.timestamp, bar.close, expected);
+    }
+    println!("   ✅ {} spikes corrected, mean syndrome weight {:.2}", report.statistics.corrected_symbols, report.statistics.mean_syndrome_weight);
+
+    // Test 3: genuine moves and overloaded blocks are left alone
+    println!("📊 Test 3: genuine moves");
+    let mut close = 1.1;
+    let walk: Vec<f64> = (0..120).map(|_| { close += rng.gen_range(-0.002..0.002); close }).collect();
+    let report = corrector.correct(&bars(&walk))?;
+    ensure!(report.blocks.iter().all(|b| b.status == BlockStatus::Uncorrectable) && report.statistics.corrected_symbols == 0, "a random walk is no codeword");
+    ensure!(report.corrected.iter().zip(&walk).all(|(bar, close)| bar.close == *close), "uncorrectable blocks pass through");
+    ensure!(report.blocks.last().unwrap().len == 20, "the short trailing block is decoded as a shortened code");
+    let mut crowded = trend[..50].to_vec();
+    for i in (0..50).step_by(8) {
+        crowded[i] += 0.003;
+    }
+    let strict = PriceErrorCorrector::new(ErrorCorrectionConfig { max_correction_fraction: 0.05, ..prime_config })?;
+    ensure!(strict.correct(&bars(&crowded))?.blocks[0].status == BlockStatus::Uncorrectable, "7 changes exceed 5% of 50 bars");
+    println!("   ✅ Mean syndrome weight of a random walk {:.2}", report.statistics.mean_syndrome_weight);
+
+    // Test 4: the engine's field corrects bad ticks in a flat market
+    println!("📊 Test 4: engine field, flat market");
+    let engine_config = EngineConfig::default();
+    let flat_corrector = PriceErrorCorrector::new(ErrorCorrectionConfig { tick_size: 0.01, ..engine_config.error_correction() })?;
+    ensure!(flat_corrector.code().field().size() == 1 << 32, "runs over GF(2^32)");
+    let mut flat: Vec<f64> = (0..128).map(|_| 1.2 + rng.gen_range(-0.004..0.004)).collect();
+    flat[20] = 1.16;
+    flat[90] = 1.25;
+    let report = flat_corrector.correct(&bars(&flat))?;
+    ensure!(report.statistics.corrected_symbols == 2, "both bad ticks corrected: {:?}", report.statistics);
+    ensure!((report.corrected[20].close - 1.2).abs() < 1e-9 && (report.corrected[90].close - 1.2).abs() < 1e-9, "back to the range");
+    ensure!(report.corrected[20].high == report.corrected[20].close.max(report.corrected[20].open), "bar range follows the close");
+    println!("   ✅ Bad ticks at 1.16 and 1.25 returned to 1.20");
+
+    // Test 5: the recognizer searches corrected data and reports syndromes
+    println!("📊 Test 5: pattern recognizer");
+    let cyclic: Vec<f64> = (0..400)
+        .map(|i| 1.2 * (1.0 + 0.01 * (2.0 * std::f64::consts::PI * i as f64 / 20.0).sin() + rng.gen_range(-0.0005..0.0005)))
+        .collect();
+    let mut spiky = cyclic.clone();
+    spiky[150] *= 1.5;
+    let mut plain = PatternRecognizer::new(PatternConfig::default())?;
+    let mut corrected = PatternRecognizer::new(PatternConfig::default())?
+        .with_error_correction(PriceErrorCorrector::new(ErrorCorrectionConfig { tick_size: 0.05, ..engine_config.error_correction() })?);
+    ensure!(plain.last_syndrome_statistics().is_none() && plain.error_correction(&bars(&spiky))?.is_none(), "off by default");
+    let cycles = corrected.detect_cycles(&bars(&spiky)).await?;
+    let statistics = corrected.last_syndrome_statistics().expect("statistics recorded").clone();
+    ensure!(statistics.blocks == 7 && statistics.corrected_symbols == 1, "the spike is decoded away: {:?}", statistics);
+    ensure!(cycles.first().is_some_and(|c| c.period == 20), "cycle of 20 found in corrected data: {:?}", cycles.first().map(|c| c.period));
+    ensure!(corrected.find_cycles(&bars(&spiky)).len() == cycles.len(), "synchronous search corrects too");
+    let plain_cycles = plain.detect_cycles(&bars(&cyclic)).await?;
+    ensure!(plain_cycles.first().is_some_and(|c| c.period == 20), "reference search on clean data");
+    println!("   ✅ {} blocks, {} correction, dominant cycle {} bars", statistics.blocks, statistics.corrected_symbols, cycles[0].period);
+
+    println!();
+    println!("🎉 All error correction tests passed");
+    Ok(())
+}
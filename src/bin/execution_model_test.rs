@@ -0,0 +1,154 @@
+//! # Execution Model Test
+//!
+//! Drive a scripted strategy through the backtest engine and check partial fills
+//! against bar liquidity and volume, seeded rejections and requotes, fills
+//! delayed by latency at the price then, and per-pair execution conditions
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, TimeZone, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use forex_pattern_reconstruction::backtest::execution::{ExecutionModel, ExecutionModelConfig};
+use forex_pattern_reconstruction::backtest::strategy::{Fill, Order, OrderSide, Strategy, StrategyContext};
+use forex_pattern_reconstruction::backtest::{BacktestConfig, BacktestEngine, BacktestRun, StrategyConfig};
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::trading_windows::TradingWindowsConfig;
+
+/// Hourly bars rising by one pip per hour, with volume
+fn hourly_bars(count: i64, volume: Option<f64>) -> Vec<ForexDataPoint> {
+    let start = Utc.with_ymd_and_hms(2024, 3, 4, 0, 0, 0).unwrap();
+    (0..count)
+        .map(|i| {
+            let close = 1.1 + 0.0001 * i as f64;
+            ForexDataPoint { timestamp: start + Duration::hours(i), open: close, high: close, low: close, close, volume }
+        })
+        .collect()
+}
+
+/// Fills a strategy heard about, with the bar it heard them on
+type FillLog = Arc<Mutex<Vec<(usize, Fill)>>>;
+
+/// Places scripted orders on given bars and records the fills it hears about
+struct Scripted {
+    orders: HashMap<usize, Vec<Order>>,
+    fills: FillLog,
+}
+
+impl Scripted {
+    fn new(orders: &[(usize, Order)]) -> (Self, FillLog) {
+        let fills = Arc::new(Mutex::new(Vec::new()));
+        let mut by_bar: HashMap<usize, Vec<Order>> = HashMap::new();
+        for (bar, order) in orders {
+            by_bar.entry(*bar).or_default().push(order.clone());
+        }
+        (Self { orders: by_bar, fills: fills.clone() }, fills)
+    }
+}
+
+impl Strategy for Scripted {
+    fn name(&self) -> &str {
+        "scripted"
+    }
+
+    fn on_bar(&mut self, context: &StrategyContext, _bar: &ForexDataPoint) -> Vec<Order> {
+        self.orders.remove(&context.bar_index).unwrap_or_default()
+    }
+
+    fn on_fill(&mut self, context: &StrategyContext, fill: &Fill) -> Vec<Order> {
+        self.fills.lock().unwrap().push((context.bar_index, fill.clone()));
+        Vec::new()
+    }
+}
+
+async fn run(execution: ExecutionModel, pair: &str, data: &[ForexDataPoint], orders: &[(usize, Order)]) -> Result<(BacktestRun, Vec<(usize, Fill)>)> {
+    let config = BacktestConfig { warmup_bars: 1, cycle_refresh_bars: 10_000, commission: 0.0, slippage: 0.0, execution, ..BacktestConfig::default() };
+    let engine = BacktestEngine::new(StrategyConfig::default(), 100_000.0, config)?
+        .with_trading_windows(TradingWindowsConfig::unrestricted());
+    let (mut strategy, fills) = Scripted::new(orders);
+    let run = engine.run(&mut strategy, pair, data, &[]).await?;
+    let fills = fills.lock().unwrap().clone();
+    Ok((run, fills))
+}
+
+fn model(config: ExecutionModelConfig) -> ExecutionModel {
+    ExecutionModel { default: config, ..ExecutionModel::default() }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 EXECUTION MODEL TEST");
+    println!("=======================");
+    println!();
+
+    let data = hourly_bars(48, None);
+    let orders: Vec<(usize, Order)> = (0..20).map(|i| (2 + 2 * i, if i % 2 == 0 { Order::buy(10_000.0, "in") } else { Order::sell(10_000.0, "out") })).collect();
+
+    // Test 1: without a model every order fills in full at the close
+    println!("📊 Test 1: instant full fills");
+    let (instant, fills) = run(ExecutionModel::default(), "EURUSD", &data, &orders).await?;
+    ensure!(instant.execution.orders == 20 && instant.execution.fills == 20 && instant.execution.unfilled_units == 0.0, "{:?}", instant.execution);
+    ensure!(fills.iter().all(|(bar, fill)| fill.units == 10_000.0 && fill.price == data[*bar].close), "full fills at the close");
+    println!("   ✅ 20 orders, 20 full fills, {:.0} ms latency", instant.execution.mean_latency_ms);
+
+    // Test 2: liquidity caps what one bar fills, shared by its orders
+    println!("📊 Test 2: partial fills");
+    let crowded = [(5, Order::buy(3_000.0, "a")), (5, Order::buy(3_000.0, "b")), (8, Order::buy(1_000.0, "c"))];
+    let (partial, fills) = run(model(ExecutionModelConfig { liquidity_units: Some(4_000.0), ..Default::default() }), "EURUSD", &data, &crowded).await?;
+    let units: Vec<f64> = fills.iter().map(|(_, fill)| fill.units).collect();
+    ensure!(units == [3_000.0, 1_000.0, 1_000.0], "the second order gets what is left: {:?}", units);
+    ensure!(partial.execution.partial_fills == 1 && partial.execution.unfilled_units == 2_000.0, "{:?}", partial.execution);
+    ensure!(partial.trades[1].size == 1_000.0, "the journal records the filled size");
+    let (strict, _) = run(model(ExecutionModelConfig { liquidity_units: Some(4_000.0), min_fill_fraction: 0.5, ..Default::default() }), "EURUSD", &data, &crowded).await?;
+    ensure!(strict.execution.fills == 2 && strict.execution.unfilled_units == 3_000.0, "a third of an order is cancelled: {:?}", strict.execution);
+    let volume_data = hourly_bars(48, Some(2_000.0));
+    let (by_volume, fills) = run(model(ExecutionModelConfig { liquidity_units: Some(1e9), volume_participation: Some(0.25), ..Default::default() }), "EURUSD", &volume_data, &crowded).await?;
+    ensure!(fills[0].1.units == 500.0 && by_volume.execution.fills == 2, "a quarter of 2,000 volume, the rest of the bar is used up");
+    println!("   ✅ {:?} units filled from 4,000 of liquidity", units);
+
+    // Test 3: rejections and requotes are drawn from the seed
+    println!("📊 Test 3: rejections and requotes");
+    let flaky = ExecutionModelConfig { rejection_probability: 0.3, requote_probability: 0.5, requote_slippage: 0.0005, seed: 7, ..Default::default() };
+    let (first, first_fills) = run(model(flaky.clone()), "EURUSD", &data, &orders).await?;
+    let (again, again_fills) = run(model(flaky.clone()), "EURUSD", &data, &orders).await?;
+    ensure!(first.execution.rejections > 0 && first.execution.rejections < 20 && first.execution.requotes > 0, "{:?}", first.execution);
+    ensure!(first.execution.rejections + first.execution.fills == 20, "every order either fills or is rejected");
+    ensure!(first_fills.len() == again_fills.len() && first.trades.len() == again.trades.len() && first.execution.requotes == again.execution.requotes, "same seed, same run");
+    let requoted = first_fills.iter().filter(|(bar, fill)| (fill.price - data[*bar].close).abs() > 1e-12).count();
+    ensure!(requoted == first.execution.requotes, "requotes move the price");
+    ensure!(first_fills.iter().all(|(bar, fill)| {
+        let adverse = (fill.price - data[*bar].close) * if fill.side == OrderSide::Buy { 1.0 } else { -1.0 };
+        adverse >= -1e-12
+    }), "requotes are always worse");
+    let (all_rejected, _) = run(model(ExecutionModelConfig { rejection_probability: 1.0, ..Default::default() }), "EURUSD", &data, &orders).await?;
+    ensure!(all_rejected.trades.is_empty() && all_rejected.execution.rejections == 20, "certain rejection");
+    println!("   ✅ {} rejected, {} requoted, reproducible", first.execution.rejections, first.execution.requotes);
+
+    // Test 4: latency delays fills to a later price
+    println!("📊 Test 4: latency");
+    let delayed = [(4, Order::buy(1_000.0, "in")), (47, Order::buy(1_000.0, "late"))];
+    let (slow, fills) = run(model(ExecutionModelConfig { latency_ms: 30 * 60 * 1_000, ..Default::default() }), "EURUSD", &data, &delayed).await?;
+    ensure!(fills.len() == 1 && fills[0].0 == 5, "the fill is heard on the next bar");
+    ensure!((fills[0].1.price - (data[4].close + data[5].close) / 2.0).abs() < 1e-12, "filled half way to the next close, got {}", fills[0].1.price);
+    ensure!(slow.trades[0].timestamp == data[4].timestamp + Duration::minutes(30), "the journal has the fill time");
+    ensure!(slow.execution.mean_latency_ms == 1_800_000.0 && slow.execution.pending_at_end == 1, "{:?}", slow.execution);
+    let (jittery, jittery_fills) = run(model(ExecutionModelConfig { latency_ms: 100, latency_jitter_ms: 7_200_000, seed: 3, ..Default::default() }), "EURUSD", &data, &orders).await?;
+    ensure!(jittery.execution.mean_latency_ms > 100.0 && jittery.execution.mean_latency_ms < 7_200_100.0, "jitter adds delay");
+    ensure!(jittery_fills.iter().all(|(bar, fill)| fill.price > data[*bar - 1].close - 1e-12 && fill.price <= data[*bar].close + 1e-12),
+            "fills are priced between the closes around them");
+    ensure!(jittery.trades.windows(2).all(|pair| pair[0].timestamp <= pair[1].timestamp), "the journal stays in time order");
+    println!("   ✅ Filled 30 minutes late at {:.5}, {:.0} ms mean latency with jitter",
+             fills[0].1.price, jittery.execution.mean_latency_ms);
+
+    // Test 5: conditions per pair
+    println!("📊 Test 5: per-pair conditions");
+    let per_pair = ExecutionModel::default().with_pair("GBPJPY", ExecutionModelConfig { liquidity_units: Some(100.0), ..Default::default() });
+    let (liquid, _) = run(per_pair.clone(), "EURUSD", &data, &crowded).await?;
+    let (thin, _) = run(per_pair, "GBPJPY", &data, &crowded).await?;
+    ensure!(liquid.execution.partial_fills == 0 && thin.execution.partial_fills == 2, "only GBPJPY is thin: {:?}", thin.execution);
+    println!("   ✅ GBPJPY filled {:.0} of 7,000 units, EURUSD all", 7_000.0 - thin.execution.unfilled_units);
+
+    println!();
+    println!("🎉 All execution model tests passed");
+    Ok(())
+}
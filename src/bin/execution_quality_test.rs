@@ -0,0 +1,152 @@
+//! # Execution Quality Test
+//!
+//! Check slippage, time to fill and cost of single fills, the report per pair,
+//! session and broker and its round trip through the log file, that the manager
+//! records live fills against the price their pair decided on, and that a
+//! backtest naming an execution log fills at the measured cost
+
+use anyhow::{ensure, Result};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use std::collections::HashMap;
+
+use forex_pattern_reconstruction::backtest::strategy::{Order, Strategy, StrategyContext};
+use forex_pattern_reconstruction::backtest::{BacktestConfig, BacktestEngine, StrategyConfig};
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::laplacian_rl::TradingAction;
+use forex_pattern_reconstruction::multi_currency::MultiCurrencyManager;
+use forex_pattern_reconstruction::portfolio::execution_quality::{
+    read_execution_log, DecisionQuote, ExecutionLog, ExecutionQualityReport, PAPER_BROKER,
+};
+use forex_pattern_reconstruction::risk::SignalOrigin;
+use forex_pattern_reconstruction::trading_windows::TradingWindowsConfig;
+
+fn at(day: u32, hour: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 7, day, hour, 0, 0).unwrap()
+}
+
+fn decision(observed_at: DateTime<Utc>, price: f64, spread: f64) -> DecisionQuote {
+    DecisionQuote { observed_at, price, spread }
+}
+
+/// Buys on the first bar after warm-up and sells ten bars later
+struct RoundTrip;
+
+impl Strategy for RoundTrip {
+    fn name(&self) -> &str {
+        "round-trip"
+    }
+
+    fn on_bar(&mut self, context: &StrategyContext, _bar: &ForexDataPoint) -> Vec<Order> {
+        match context.bar_index {
+            2 => vec![Order::buy(10_000.0, "in")],
+            12 => vec![Order::sell(10_000.0, "out")],
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 EXECUTION QUALITY TEST");
+    println!("=========================");
+    println!();
+
+    // Test 1: one fill against its decision
+    println!("📊 Test 1: Single fills");
+    let log = ExecutionLog::new();
+    let buy = log.record(1, "EURUSD", 10_000.0, 1.10030, &decision(at(3, 9), 1.10000, 0.0002), at(3, 9) + Duration::milliseconds(400));
+    ensure!((buy.slippage_pips() - 3.0).abs() < 1e-6 && buy.slippage() > 0.0, "buy filled higher is adverse: {}", buy.slippage_pips());
+    ensure!(buy.time_to_fill_ms() == 400 && buy.session == "London" && buy.broker == PAPER_BROKER, "{}", buy.summary());
+    ensure!((buy.cost() - (0.0003 + 0.0001) / 1.1).abs() < 1e-9, "cost adds half the spread: {}", buy.cost());
+    let sell = log.record(2, "USDJPY", -10_000.0, 157.020, &decision(at(3, 14), 157.000, 0.02), at(3, 14) + Duration::seconds(2));
+    ensure!((sell.slippage_pips() + 2.0).abs() < 1e-6, "sell filled higher is favourable, in JPY pips: {}", sell.slippage_pips());
+    ensure!(sell.session == "London+NewYork", "{}", sell.session);
+    println!("   ✅ {}; {}", buy.summary(), sell.summary());
+
+    // Test 2: report per pair, session and broker, through the log file
+    println!("📊 Test 2: Report");
+    let path = std::env::temp_dir().join(format!("execution-quality-test-{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let log = ExecutionLog::with_file(&path)?.with_broker("acme");
+    for (i, (hour, slipped)) in [(9, 0.0001), (10, 0.0003), (14, -0.0001), (23, 0.0005)].iter().enumerate() {
+        let decided = at(3, *hour);
+        log.record(i as u64 + 1, "EURUSD", 10_000.0, 1.1 + slipped, &decision(decided, 1.1, 0.0001), decided + Duration::milliseconds(100 * (i as i64 + 1)));
+    }
+    let records = read_execution_log(&path)?;
+    ensure!(records.len() == 4 && records.iter().all(|record| record.broker == "acme"), "{} records read back", records.len());
+    let report = ExecutionQualityReport::from_records(&records);
+    ensure!(report.overall.fills == 4 && (report.overall.mean_slippage_pips - 2.0).abs() < 1e-6, "{}", report.overall.summary());
+    ensure!((report.overall.worst_slippage_pips - 5.0).abs() < 1e-6 && report.overall.median_time_to_fill_ms == 250.0, "{}", report.overall.summary());
+    ensure!(report.by_session.get("London").is_some_and(|london| london.fills == 2), "{:?}", report.by_session.keys().collect::<Vec<_>>());
+    ensure!(report.by_session.get("Sydney").is_some_and(|sydney| sydney.fills == 1), "23:00 UTC fill belongs to Sydney");
+    ensure!(report.by_broker.len() == 1 && report.by_broker["acme"].fills == 4, "one broker");
+    ensure!(log.report().overall.fills == 4 && log.recent(2).last().is_some_and(|record| record.order_id == 4), "in-memory records");
+    ensure!(ExecutionQualityReport::from_log(&path.with_extension("missing")).is_err(), "missing log accepted");
+    for line in report.lines() {
+        println!("   {}", line);
+    }
+    println!("   ✅ {} sessions, {} broker", report.by_session.len(), report.by_broker.len());
+
+    // Test 3: the manager records live fills against their decision
+    println!("📊 Test 3: Live fills");
+    let mut manager = MultiCurrencyManager::new().with_trading_windows(TradingWindowsConfig::unrestricted()).with_execution_log(ExecutionLog::new().with_broker("paper-desk"));
+    manager.initialize_pairs(&["EURUSD".to_string()]).await?;
+    let decided_at = Utc::now() - Duration::milliseconds(250);
+    {
+        let mut pairs = manager.pairs.write().await;
+        let state = pairs.get_mut("EURUSD").unwrap();
+        state.historical_data = (0..5).map(|i| {
+            let price = 1.1000 + 0.0002 * i as f64;
+            ForexDataPoint { timestamp: Utc::now() - Duration::hours(5 - i), open: price, high: price, low: price, close: price, volume: None }
+        }).collect();
+        state.signal_origin = Some(SignalOrigin { observed_at: decided_at, bars: state.historical_data.len() });
+        state.decision_quote = Some(decision(decided_at, 1.1005, 0.00015));
+    }
+    manager.execute_actions(&HashMap::from([("EURUSD".to_string(), vec![TradingAction::Buy { size: 1 }])])).await;
+    let recorded = manager.execution_log.recent(10);
+    ensure!(recorded.len() == 1 && recorded[0].broker == "paper-desk", "{:?}", recorded);
+    ensure!((recorded[0].slippage_pips() - 3.0).abs() < 1e-6 && recorded[0].time_to_fill_ms() >= 250, "{}", recorded[0].summary());
+    let order_id = manager.portfolio_snapshot().await.recent_orders[0].id;
+    ensure!(recorded[0].order_id == order_id, "record names the blotter order");
+
+    // A decision quote from another update is not the one behind the action
+    manager.pairs.write().await.get_mut("EURUSD").unwrap().decision_quote = Some(decision(decided_at - Duration::minutes(5), 1.1, 0.0001));
+    manager.execute_actions(&HashMap::from([("EURUSD".to_string(), vec![TradingAction::ClosePosition])])).await;
+    ensure!(manager.execution_log.recent(10).len() == 1 && manager.portfolio_snapshot().await.positions.is_empty(), "fill recorded against a stale quote");
+    ensure!(manager.execution_quality().by_pair.contains_key("EURUSD"), "manager report");
+    println!("   ✅ {}", recorded[0].summary());
+
+    // Test 4: backtests take their costs from the execution log
+    println!("📊 Test 4: Backtest calibration");
+    let base = BacktestConfig { warmup_bars: 1, cycle_refresh_bars: 10_000, commission: 0.0, slippage: 0.0, ..BacktestConfig::default() };
+    let calibrated = report.calibrate(base.clone());
+    let eurusd = calibrated.execution.config_for("EURUSD");
+    ensure!(eurusd.slippage.is_some_and(|slippage| (slippage - report.by_pair["EURUSD"].mean_cost).abs() < 1e-12), "{:?}", eurusd);
+    ensure!(eurusd.latency_ms == 250 && calibrated.execution.config_for("GBPUSD").slippage.is_none(), "{:?}", eurusd);
+    ensure!((calibrated.slippage - report.overall.mean_cost).abs() < 1e-12, "default slippage {}", calibrated.slippage);
+    ensure!(ExecutionQualityReport::default().calibrate(base.clone()).slippage == 0.0, "empty report changed the config");
+
+    let start = Utc.with_ymd_and_hms(2024, 3, 4, 0, 0, 0).unwrap();
+    let data: Vec<ForexDataPoint> = (0..30).map(|i| ForexDataPoint { timestamp: start + Duration::hours(i), open: 1.1, high: 1.1, low: 1.1, close: 1.1, volume: None }).collect();
+    let from_log = BacktestConfig { execution_log: Some(path.clone()), ..base.clone() };
+    let run = |config: BacktestConfig, pair: &'static str| {
+        let data = data.clone();
+        async move {
+            let engine = BacktestEngine::new(StrategyConfig::default(), 100_000.0, config)?.with_trading_windows(TradingWindowsConfig::unrestricted());
+            engine.run(&mut RoundTrip, pair, &data, &[]).await
+        }
+    };
+    let free = run(base.clone(), "EURUSD").await?;
+    let costly = run(from_log.clone(), "EURUSD").await?;
+    let pnl = |trades: &[forex_pattern_reconstruction::report::TradeRecord]| trades.iter().map(|trade| trade.profit_loss).sum::<f64>();
+    ensure!(free.trades.len() == 2 && pnl(&free.trades).abs() < 1e-9, "flat prices trade for free: {:?}", free.trades);
+    let expected = -10_000.0 * 1.1 * 2.0 * report.by_pair["EURUSD"].mean_cost;
+    ensure!(costly.trades.len() == 2 && (pnl(&costly.trades) - expected).abs() < 0.01, "paid {:.2}, expected {:.2}", pnl(&costly.trades), expected);
+    ensure!(BacktestEngine::new(StrategyConfig::default(), 100_000.0, BacktestConfig { execution_log: Some(path.with_extension("missing")), ..base }).is_err(), "missing log ignored");
+    std::fs::remove_file(&path)?;
+    println!("   ✅ Round trip costs {:.2} at the measured {:.2} pips per side", pnl(&costly.trades), report.by_pair["EURUSD"].mean_cost * 1.1 / 0.0001);
+
+    println!();
+    println!("🎉 All execution quality tests passed");
+    Ok(())
+}
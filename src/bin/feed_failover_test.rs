@@ -0,0 +1,105 @@
+//! # Feed Failover Test
+//!
+//! Feed two scripted sources through the failover provider and check priority
+//! routing, deduplication, divergence warnings, failover on silence and failback
+
+use anyhow::{ensure, Result};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{broadcast, mpsc};
+
+use forex_pattern_reconstruction::data::failover::{FailoverConfig, FailoverProvider, FeedEvent};
+use forex_pattern_reconstruction::data::provider::{ChannelProvider, DataProvider, Tick};
+
+fn tick(timestamp: DateTime<Utc>, price: f64) -> Tick {
+    Tick { symbol: "EURUSD".to_string(), timestamp, bid: price, ask: price + 0.0002 }
+}
+
+async fn settle() {
+    tokio::time::sleep(std::time::Duration::from_millis(30)).await;
+}
+
+/// Forwarded ticks and events received so far
+fn drain(ticks: &mut mpsc::Receiver<Tick>, events: &mut broadcast::Receiver<FeedEvent>) -> (Vec<Tick>, Vec<FeedEvent>) {
+    let mut forwarded = Vec::new();
+    while let Ok(tick) = ticks.try_recv() {
+        forwarded.push(tick);
+    }
+    let mut emitted = Vec::new();
+    while let Ok(event) = events.try_recv() {
+        emitted.push(event);
+    }
+    (forwarded, emitted)
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 FEED FAILOVER TEST");
+    println!("=====================");
+    println!();
+
+    let (backup, backup_feed) = ChannelProvider::new("backup");
+    let (primary, primary_feed) = ChannelProvider::new("primary");
+    // Registration order puts backup first; the per-pair priority must win
+    let config = FailoverConfig {
+        pair_priority: HashMap::from([("EURUSD".to_string(), vec!["primary".to_string(), "backup".to_string()])]),
+        silence_timeout_ms: 200,
+        divergence_tolerance: 0.0005,
+    };
+    let provider = FailoverProvider::new(vec![Arc::new(backup), Arc::new(primary)], config)?;
+    let mut events = provider.events();
+    let mut ticks = provider.subscribe_ticks(&["EURUSD".to_string()])?;
+    let start = Utc::now();
+
+    // Test 1: primary ticks are forwarded, the same quote from the backup is not
+    println!("📊 Test 1: Priority routing and deduplication");
+    primary_feed.send(tick(start, 1.1000)).await?;
+    settle().await;
+    backup_feed.send(tick(start, 1.1000)).await?;
+    primary_feed.send(tick(start, 1.1000)).await?;
+    settle().await;
+    let (forwarded, emitted) = drain(&mut ticks, &mut events);
+    ensure!(forwarded.len() == 1, "expected one forwarded tick, got {}", forwarded.len());
+    ensure!(matches!(&emitted[..], [FeedEvent::Switched { from: None, to, .. }] if to == "primary"), "expected primary as initial source, got {:?}", emitted);
+    println!("✅ Primary active, duplicates dropped");
+
+    // Test 2: a backup quote far from the primary raises a divergence event
+    println!("📊 Test 2: Divergence");
+    backup_feed.send(tick(start + Duration::milliseconds(10), 1.1050)).await?;
+    settle().await;
+    primary_feed.send(tick(start + Duration::milliseconds(20), 1.1001)).await?;
+    settle().await;
+    let (forwarded, emitted) = drain(&mut ticks, &mut events);
+    ensure!(forwarded.len() == 1, "primary tick not forwarded");
+    ensure!(emitted.iter().any(|event| matches!(event, FeedEvent::Divergence { other, .. } if other == "backup")), "no divergence event");
+    println!("✅ Divergence reported");
+
+    // Test 3: a silent primary fails over to the backup
+    println!("📊 Test 3: Failover");
+    for step in 1..=8 {
+        backup_feed.send(tick(start + Duration::milliseconds(20 + step * 50), 1.1002)).await?;
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+    let (forwarded, emitted) = drain(&mut ticks, &mut events);
+    ensure!(!forwarded.is_empty(), "backup ticks not forwarded after failover");
+    ensure!(emitted.iter().any(|event| matches!(event,
+        FeedEvent::Switched { from: Some(from), to, .. } if from == "primary" && to == "backup")), "no failover event");
+    println!("✅ Failed over to backup, {} ticks forwarded", forwarded.len());
+
+    // Test 4: the primary coming back takes over again, stale timestamps are dropped
+    println!("📊 Test 4: Failback");
+    primary_feed.send(tick(start + Duration::milliseconds(30), 1.1002)).await?;
+    settle().await;
+    primary_feed.send(tick(start + Duration::seconds(1), 1.1003)).await?;
+    settle().await;
+    let (forwarded, emitted) = drain(&mut ticks, &mut events);
+    ensure!(forwarded.len() == 1 && forwarded[0].timestamp == start + Duration::seconds(1), "expected only the newer primary tick, got {}", forwarded.len());
+    ensure!(emitted.iter().any(|event| matches!(event,
+        FeedEvent::Switched { to, reason, .. } if to == "primary" && reason.contains("recovered"))), "no failback event");
+    println!("✅ Primary restored");
+
+    println!();
+    println!("🎉 All feed failover tests passed");
+    Ok(())
+}
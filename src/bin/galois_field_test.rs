@@ -0,0 +1,121 @@
+//! # Galois Field Test
+//!
+//! Check the field axioms on random elements of prime and binary extension
+//! fields up to GF(2^63), exhaustively on GF(2^4), and that reduction
+//! polynomials, primitive elements and prime factorizations match known values
+
+use anyhow::{ensure, Result};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashSet;
+
+use forex_pattern_reconstruction::core::{EngineConfig, TimeSymmetricEngine};
+use forex_pattern_reconstruction::galois::polynomial::{is_irreducible, lowest_irreducible};
+use forex_pattern_reconstruction::galois::primes::{is_prime, prime_factors};
+use forex_pattern_reconstruction::galois::GaloisField;
+
+const SAMPLES: usize = 2_000;
+
+/// Check every axiom on `SAMPLES` random triples
+fn check_axioms(field: &GaloisField, rng: &mut StdRng) -> Result<()> {
+    let q = field.size();
+    let p = field.characteristic() as u64;
+    for _ in 0..SAMPLES {
+        let (a, b, c) = (rng.gen_range(0..q), rng.gen_range(0..q), rng.gen_range(0..q));
+        let name = format!("GF({}^{}) with a={} b={} c={}", p, field.degree(), a, b, c);
+        ensure!(field.contains(field.add(a, b)) && field.contains(field.mul(a, b)), "closure in {}", name);
+        ensure!(field.add(a, b) == field.add(b, a) && field.mul(a, b) == field.mul(b, a), "commutativity in {}", name);
+        ensure!(field.add(field.add(a, b), c) == field.add(a, field.add(b, c)), "additive associativity in {}", name);
+        ensure!(field.mul(field.mul(a, b), c) == field.mul(a, field.mul(b, c)), "multiplicative associativity in {}", name);
+        ensure!(field.mul(a, field.add(b, c)) == field.add(field.mul(a, b), field.mul(a, c)), "distributivity in {}", name);
+        ensure!(field.add(a, 0) == a && field.mul(a, 1) == a && field.mul(a, 0) == 0, "identities in {}", name);
+        ensure!(field.add(a, field.neg(a)) == 0 && field.sub(field.add(a, b), b) == a, "additive inverses in {}", name);
+        match field.inverse(a) {
+            Some(inverse) => ensure!(a != 0 && field.mul(a, inverse) == 1, "multiplicative inverse in {}", name),
+            None => ensure!(a == 0, "only zero lacks an inverse in {}", name),
+        }
+        if b != 0 {
+            ensure!(field.mul(field.div(a, b).unwrap(), b) == a, "division in {}", name);
+        }
+        ensure!(field.pow(a, q) == a, "a^q = a in {}", name);
+        ensure!(field.pow(field.add(a, b), p) == field.add(field.pow(a, p), field.pow(b, p)), "Frobenius in {}", name);
+        ensure!(field.mul(field.pow(a, 5), field.pow(a, 7)) == field.pow(a, 12), "exponent law in {}", name);
+    }
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 GALOIS FIELD TEST");
+    println!("====================");
+    println!();
+
+    let mut rng = StdRng::seed_from_u64(17);
+
+    // Test 1: field axioms on random elements
+    println!("📊 Test 1: field axioms");
+    let fields = [
+        GaloisField::new(2)?,
+        GaloisField::new(3)?,
+        GaloisField::new(65_537)?,
+        GaloisField::new(2_147_483_647)?,
+        GaloisField::new_with_degree(2, 2)?,
+        GaloisField::new_with_degree(2, 8)?,
+        GaloisField::new_with_degree(2, 32)?,
+        GaloisField::new_with_degree(2, 61)?,
+        GaloisField::new_with_degree(2, 63)?,
+    ];
+    for field in &fields {
+        check_axioms(field, &mut rng)?;
+    }
+    println!("   ✅ {} fields × {} samples satisfy every axiom", fields.len(), SAMPLES);
+
+    // Test 2: GF(16) exhaustively
+    println!("📊 Test 2: GF(2^4) exhaustively");
+    let gf16 = GaloisField::new_with_degree(2, 4)?;
+    ensure!(gf16.modulus() == 0b10011, "GF(16) reduces by x^4 + x + 1");
+    for a in 1..16 {
+        let row: HashSet<u64> = (1..16).map(|b| gf16.mul(a, b)).collect();
+        ensure!(row.len() == 15 && !row.contains(&0), "row {} of the multiplication table is a permutation", a);
+    }
+    let powers: HashSet<u64> = (0..15).map(|i| gf16.pow(2, i)).collect();
+    ensure!(gf16.primitive_element() == 2 && powers.len() == 15, "x generates GF(16)*");
+    println!("   ✅ Every row a permutation, x of order 15");
+
+    // Test 3: reduction polynomials
+    println!("📊 Test 3: reduction polynomials");
+    ensure!(fields[5].modulus() == 0x11B, "GF(2^8) reduces by the AES polynomial, got {:#x}", fields[5].modulus());
+    ensure!(fields[6].modulus() == 0x1_0000_008D, "GF(2^32) reduces by x^32 + x^7 + x^3 + x^2 + 1, got {:#x}", fields[6].modulus());
+    ensure!(is_irreducible(0x11D) && !is_irreducible(0b10101) && !is_irreducible(0b1001), "irreducibility");
+    ensure!((1..=64).all(|k| lowest_irreducible(k).is_some_and(is_irreducible)), "every degree to 64 has one");
+    ensure!(GaloisField::binary_with_modulus(0b10101).is_err(), "reducible moduli are rejected");
+    let reed_solomon = GaloisField::binary_with_modulus(0x11D)?;
+    ensure!(reed_solomon.primitive_element() == 2 && fields[5].primitive_element() == 3, "x is primitive only under 0x11D");
+    println!("   ✅ GF(2^8) {:#x}, GF(2^32) {:#x}", fields[5].modulus(), fields[6].modulus());
+
+    // Test 4: number theory
+    println!("📊 Test 4: primes");
+    ensure!(is_prime(2_147_483_647) && is_prime((1 << 61) - 1) && !is_prime(3_215_031_751), "Miller-Rabin, including a strong pseudoprime to 2, 3, 5 and 7");
+    ensure!(prime_factors(u32::MAX as u64) == [3, 5, 17, 257, 65_537], "factors of 2^32 − 1");
+    ensure!(prime_factors((1 << 62) - 1) == [3, 715_827_883, 2_147_483_647], "factors of 2^62 − 1");
+    println!("   ✅ 2^62 − 1 = 3 · 715827883 · 2147483647");
+
+    // Test 5: bad parameters and the engine's configured field
+    println!("📊 Test 5: configuration");
+    ensure!(GaloisField::new(4).is_err() && GaloisField::new(1 << 33).is_err(), "prime fields need a small prime");
+    ensure!(GaloisField::new_with_degree(3, 2).is_err() && GaloisField::new_with_degree(2, 64).is_err(), "unsupported extensions");
+    ensure!(GaloisField::new_with_degree(2, 0).is_err(), "degree zero");
+    let field = GaloisField::new_with_degree(2, 32)?;
+    let extended = field.extend_element(0x1234_5678, &[0.5, -0.25, 1.0])?;
+    let (c0, c1, c2) = (500, 250, 1000);
+    let x = 0x1234_5678;
+    ensure!(extended == field.add(field.add(c0, field.mul(c1, x)), field.mul(c2, field.mul(x, x))), "extension polynomial evaluated in the field");
+    let mut engine = TimeSymmetricEngine::new(EngineConfig::default())?;
+    engine.initialize().await?;
+    ensure!(TimeSymmetricEngine::new(EngineConfig { field_characteristic: 6, field_degree: 1, ..EngineConfig::default() }).is_err(), "engine rejects non-fields");
+    println!("   ✅ Engine runs over GF(2^32)");
+
+    println!();
+    println!("🎉 All Galois field tests passed");
+    Ok(())
+}
@@ -0,0 +1,139 @@
+//! # Graph Growth Test
+//!
+//! Check the De Bruijn graph grows from observed states up to its node cap with
+//! observed transition frequencies, and survives a saved Q-table
+
+use anyhow::{ensure, Result};
+use chrono::Utc;
+
+use forex_pattern_reconstruction::anomaly::{AnomalySeverity, AnomalyType, DetectedAnomaly, MarketContext};
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::laplacian_rl::{
+    AnomalyFeatures, DeBruijnGraph, LaplacianQLearningAgent, LaplacianQLearningConfig, QTableSnapshot, TradingAction,
+};
+
+/// State id with the given pattern strength, volatility ratio and confidence
+fn state(novel: f64, volatility: f64, confidence: f64) -> String {
+    format!("s_0.00_0.00_{:.2}_0.00_{:.2}_0.00_{:.2}", volatility, novel, confidence)
+}
+
+fn novel_pattern(emergence_confidence: f64) -> DetectedAnomaly {
+    DetectedAnomaly {
+        id: "graph_test".into(),
+        timestamp: Utc::now(),
+        anomaly_type: AnomalyType::NovelPattern { pattern_signature: "test".to_string(), emergence_confidence },
+        severity: AnomalySeverity::Medium,
+        confidence: 0.8,
+        deviation_magnitude: 1.0,
+        affected_symmetries: Vec::new(),
+        affected_cycles: Vec::new(),
+        market_context: MarketContext {
+            session: "London".to_string(),
+            volatility_regime: "Normal".to_string(),
+            trend_direction: "Sideways".to_string(),
+            recent_events: Vec::new(),
+            event_proximity: 0.0,
+        },
+        trading_signal: None,
+    }
+}
+
+/// Observe states with the given pattern strengths in order
+fn observe(graph: &mut DeBruijnGraph, strengths: &[f64]) -> Vec<Option<usize>> {
+    strengths.iter().map(|novel| graph.observe(&state(*novel, 1.0, 0.9), &AnomalyFeatures::default())).collect()
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 GRAPH GROWTH TEST");
+    println!("====================");
+    println!();
+
+    // Test 1: states quantize into symbols
+    println!("📊 Test 1: symbols");
+    let mut graph = DeBruijnGraph::new(4, 2, 8)?;
+    ensure!(graph.symbol(&state(0.1, 1.0, 0.9)).as_deref() == Some("003"), "weak pattern, calm market, confident");
+    ensure!(graph.symbol(&state(0.9, 1.0, 0.2)).as_deref() == Some("300"), "strong pattern, unsure");
+    ensure!(graph.symbol(&state(0.0, 3.0, 0.5)).as_deref() == Some("032"), "volatility three times normal is stress");
+    ensure!(graph.symbol(&state(0.95, 1.0, 0.9)) == graph.symbol(&state(0.8, 1.0, 0.9)), "nearby states share a symbol");
+    ensure!(graph.symbol("s_gap").is_none() && graph.symbol("node_003-003").is_none(), "only state ids have symbols");
+    ensure!(DeBruijnGraph::new(1, 2, 8).is_err() && DeBruijnGraph::new(4, 0, 8).is_err() && DeBruijnGraph::new(4, 2, 1).is_err(),
+            "degenerate graphs are rejected");
+    println!("   ✅ Symbols {:?}", graph.symbol(&state(0.0, 3.0, 0.5)));
+
+    // Test 2: nodes and edges are added as new words and transitions appear
+    println!("📊 Test 2: online growth");
+    ensure!(graph.is_empty() && graph.observations() == 0, "the graph starts empty");
+    let nodes = observe(&mut graph, &[0.1, 0.4, 0.7]);
+    ensure!(nodes == vec![None, Some(0), Some(1)], "a node per word of two symbols: {:?}", nodes);
+    ensure!(graph.nodes()[0].id == "node_003-103" && graph.nodes()[1].id == "node_103-203", "words shift one symbol in");
+    ensure!(graph.edges_from("node_003-103").len() == 1 && graph.transition_probability("node_003-103", "node_103-203") == 1.0,
+            "the transition is an edge");
+    let repeated = observe(&mut graph, &[0.1, 0.4]);
+    ensure!(graph.len() == 3 && repeated[1] == Some(0), "a word seen before is not added again");
+    ensure!(graph.nodes()[1].state_vector.as_slice() == [2.0 / 3.0, 0.0, 1.0], "node signals are the newest symbol's levels");
+    ensure!(graph.state_node(&state(0.4, 1.0, 0.9)) == Some(0) && graph.state_node("node_103-203") == Some(1), "states and node ids find their nodes");
+    println!("   ✅ {} nodes after {} observations", graph.len(), graph.observations());
+
+    // Test 3: transition probabilities are observed frequencies
+    println!("📊 Test 3: transition probabilities");
+    // The walk is back on node_003-103 and has left it for 203 once; leave it
+    // for 203 once more, for 303 twice and for 003 once, returning each time
+    for next in [0.9, 0.9, 0.7, 0.1] {
+        observe(&mut graph, &[next, 0.1, 0.4]);
+    }
+    let from = "node_003-103";
+    let probability = |to: &str| graph.transition_probability(from, to);
+    ensure!(probability("node_103-203") == 0.4 && probability("node_103-303") == 0.4 && probability("node_103-003") == 0.2,
+            "2/5, 2/5 and 1/5 of the moves");
+    ensure!((graph.edges_from(from).iter().map(|edge| edge.transition_probability).sum::<f64>() - 1.0).abs() < 1e-12, "probabilities sum to 1");
+    ensure!(graph.edges_from(from).iter().map(|edge| edge.observations).sum::<u32>() == 5, "every move counted");
+    ensure!(probability("node_003-003") == 0.0, "unseen moves have no probability");
+    println!("   ✅ {:.1} / {:.1} / {:.1}", probability("node_103-203"), probability("node_103-303"), probability("node_103-003"));
+
+    // Test 4: growth stops at the cap
+    println!("📊 Test 4: node cap");
+    let mut small = DeBruijnGraph::new(10, 1, 3)?;
+    let capped = observe(&mut small, &[0.05, 0.15, 0.25, 0.35, 0.15]);
+    ensure!(small.len() == 3 && capped == vec![Some(0), Some(1), Some(2), None, Some(1)], "new words beyond the cap are dropped: {:?}", capped);
+    ensure!(small.transition_probability("node_209", "node_109") == 0.0, "no edge across a dropped word");
+    println!("   ✅ {} nodes after {} observations", small.len(), small.observations());
+
+    // Test 5: the grown graph is saved with the Q-table and re-embedded as it grows
+    println!("📊 Test 5: agent");
+    let config = LaplacianQLearningConfig { symbol_levels: 10, spectral_refresh: 5, ..LaplacianQLearningConfig::default() };
+    let mut agent = LaplacianQLearningAgent::new(config.clone())?;
+    let bar = ForexDataPoint { timestamp: Utc::now(), open: 1.1, high: 1.101, low: 1.099, close: 1.1, volume: None };
+    for level in [0, 1, 2] {
+        agent.anomaly_to_state(&novel_pattern(level as f64 / 10.0 + 0.05), &bar)?;
+    }
+    ensure!(agent.spectral_embedding().map(|embedding| embedding.nodes()) == Some(2), "embedded as soon as there is an edge");
+    for level in [3, 4, 5, 6] {
+        agent.anomaly_to_state(&novel_pattern(level as f64 / 10.0 + 0.05), &bar)?;
+    }
+    ensure!(agent.graph().len() == 6 && agent.spectral_embedding().map(|embedding| embedding.nodes()) == Some(2), "not re-embedded every observation");
+    let last = agent.anomaly_to_state(&novel_pattern(0.75), &bar)?;
+    ensure!(agent.spectral_embedding().map(|embedding| embedding.nodes()) == Some(7), "re-embedded every spectral_refresh observations");
+    agent.update_q_value(&last, TradingAction::Hold, 1.0, &last, true)?;
+    let snapshot = agent.q_table_snapshot();
+    let saved: QTableSnapshot = serde_json::from_str(&serde_json::to_string(&snapshot)?)?;
+    let mut restored = LaplacianQLearningAgent::new(config.clone())?;
+    restored.restore_q_table(saved);
+    ensure!(serde_json::to_string(&restored.q_table_snapshot())? == serde_json::to_string(&snapshot)?, "the graph round-trips");
+    ensure!(restored.state_node(&last) == agent.state_node(&last) && restored.graph().nodes()[6].visit_count == 1, "state nodes and visits kept");
+    let next = novel_pattern(0.85);
+    let (resumed, original) = (restored.anomaly_to_state(&next, &bar)?, agent.anomaly_to_state(&next, &bar)?);
+    ensure!(restored.state_node(&resumed) == agent.state_node(&original) && restored.graph().len() == 8, "growth resumes from the saved word");
+    ensure!(serde_json::to_value(LaplacianQLearningAgent::new(config)?.q_table_snapshot())?.get("graph").is_none(), "an empty graph is not saved");
+    let mut table: toml::Table = toml::from_str(&toml::to_string(&LaplacianQLearningConfig::default())?)?;
+    for key in ["symbol_levels", "sequence_length", "max_graph_nodes", "spectral_refresh"] {
+        table.remove(key);
+    }
+    let legacy: LaplacianQLearningConfig = toml::from_str(&toml::to_string(&table)?)?;
+    ensure!(legacy.symbol_levels == 4 && legacy.sequence_length == 2 && legacy.max_graph_nodes == 256, "older configurations get the defaults");
+    println!("   ✅ {} nodes saved and restored, embedding of {} nodes", restored.graph().len(), restored.spectral_embedding().map_or(0, |embedding| embedding.nodes()));
+
+    println!();
+    println!("🎉 All graph growth tests passed");
+    Ok(())
+}
@@ -0,0 +1,207 @@
+//! # Holiday Calendar Test
+//!
+//! Check per-currency holidays and the year-end lull, then run the same holiday
+//! bars through the anomaly detector, the backtester, the dossier and the daily
+//! report under the skip and down-weight policies
+
+use anyhow::{ensure, Result};
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::f64::consts::PI;
+use std::sync::{Arc, Mutex};
+
+use forex_pattern_reconstruction::anomaly::{AnomalyDetectionConfig, DetectedAnomaly, TemporalAnomalyDetector};
+use forex_pattern_reconstruction::backtest::strategy::{Order, Strategy, StrategyContext};
+use forex_pattern_reconstruction::backtest::{BacktestConfig, BacktestEngine, StrategyConfig};
+use forex_pattern_reconstruction::calendar::{Holiday, HolidayCalendar, ThinMarketPolicy, THIN_MARKET_EVENT};
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::report::dossier::{DossierConfig, DossierGenerator};
+use forex_pattern_reconstruction::report::{DailyReportConfig, DailyReportGenerator, DailyReportInput};
+use forex_pattern_reconstruction::symmetry::TemporalSymmetry;
+use forex_pattern_reconstruction::synthetic::fixtures::{hourly_walk, spike};
+use forex_pattern_reconstruction::trading_windows::TradingWindowsConfig;
+
+fn at(year: i32, month: u32, day: u32, hour: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(year, month, day, hour, 0, 0).unwrap()
+}
+
+fn policy(policy: ThinMarketPolicy) -> HolidayCalendar {
+    HolidayCalendar { policy, ..HolidayCalendar::default() }
+}
+
+/// Goes long 10,000 units whenever flat and records whether each bar was thin
+struct LongStrategy {
+    thin_bars: Arc<Mutex<usize>>,
+}
+
+impl Strategy for LongStrategy {
+    fn name(&self) -> &str {
+        "Long"
+    }
+
+    fn on_bar(&mut self, context: &StrategyContext, _bar: &ForexDataPoint) -> Vec<Order> {
+        if context.thin_market {
+            *self.thin_bars.lock().unwrap() += 1;
+        }
+        if context.position_units == 0.0 {
+            vec![Order::buy(10_000.0, "go long")]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+fn symmetry(period_days: u32) -> TemporalSymmetry {
+    TemporalSymmetry {
+        id: format!("symmetry_{}", period_days).into(),
+        symmetry_type: "mirror".to_string(),
+        name: format!("{}-Day Symmetry", period_days),
+        period_days,
+        strength: 0.8,
+        confidence: 0.8,
+        field_signature: 0x2a,
+        discovered_at: Utc::now(),
+        validation_score: 0.7,
+        mirror_points: Vec::new(),
+        phase_shift: 0.0,
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 HOLIDAY CALENDAR TEST");
+    println!("========================");
+    println!();
+
+    // Test 1: holidays apply to either side of the pair, the year-end lull to all pairs
+    println!("📊 Test 1: calendar lookups");
+    let calendar = HolidayCalendar::default();
+    let independence_day = at(2024, 7, 4, 14);
+    println!("   EURUSD 2024-12-25: {:?}", calendar.check("EURUSD", at(2024, 12, 25, 10)));
+    ensure!(calendar.check("EURUSD", independence_day).as_deref() == Some("USD Independence Day"), "USD holiday applies to EURUSD");
+    ensure!(calendar.check("EURGBP", independence_day).is_none(), "a USD holiday does not thin EURGBP");
+    ensure!(calendar.check("USDJPY", at(2024, 1, 3, 2)).is_some_and(|r| r.contains("JPY")), "Japanese bank holiday");
+    ensure!(calendar.check("EURGBP", at(2024, 12, 28, 12)).as_deref() == Some("year-end holidays"), "year-end lull");
+    ensure!(calendar.check("EURUSD", at(2024, 3, 12, 12)).is_none(), "an ordinary Tuesday is not thin");
+    ensure!(HolidayCalendar::disabled().check("EURUSD", independence_day).is_none(), "a disabled calendar is silent");
+    ensure!(policy(ThinMarketPolicy::Skip).weight("EURUSD", independence_day) == 0.0, "skipped bars weigh nothing");
+    ensure!(policy(ThinMarketPolicy::DownWeight).weight("EURUSD", independence_day) == 0.5, "down-weighted bars weigh thin_weight");
+    ensure!(policy(ThinMarketPolicy::Annotate).weight("EURUSD", independence_day) == 1.0, "annotated bars keep full weight");
+
+    let thanksgiving = NaiveDate::from_ymd_opt(2024, 11, 28).unwrap();
+    let custom: HolidayCalendar = toml::from_str(&toml::to_string(
+        &HolidayCalendar::disabled().with_holiday("usd", Holiday::on("Thanksgiving", thanksgiving)))?.replace("enabled = false", "enabled = true"))?;
+    ensure!(custom.check("USDCAD", at(2024, 11, 28, 15)).as_deref() == Some("USD Thanksgiving"), "one-off holiday from TOML");
+    ensure!(custom.check("USDCAD", at(2025, 11, 28, 15)).is_none(), "one-off holidays do not recur");
+    println!("   ✅ Holidays, year-end lull and TOML round trip");
+
+    // Test 2: the detector skips or down-weights anomalies on holiday bars
+    println!("📊 Test 2: anomaly detection on holiday bars");
+    let mut rng = StdRng::seed_from_u64(41);
+    let history = hourly_walk(&mut rng, at(2024, 5, 6, 0), 24 * 7 * 8);
+    let mut live = hourly_walk(&mut rng, at(2024, 7, 1, 0), 24 * 5);
+    let (ordinary_spike, holiday_spike) = (at(2024, 7, 3, 14), independence_day);
+    spike(&mut live, ordinary_spike);
+    spike(&mut live, holiday_spike);
+    // A low confidence floor keeps halved holiday anomalies visible
+    let detection = AnomalyDetectionConfig { min_anomaly_confidence: 0.4, ..AnomalyDetectionConfig::default() };
+    let detector = || TemporalAnomalyDetector::new(Vec::new(), Vec::new(), &history, detection.clone());
+    let on = |anomalies: &[DetectedAnomaly], timestamp: DateTime<Utc>| -> Vec<DetectedAnomaly> {
+        anomalies.iter().filter(|a| a.timestamp == timestamp).cloned().collect()
+    };
+
+    let plain = detector()?.detect_anomalies(&live).await?;
+    let skipped = detector()?.with_holiday_calendar("EURUSD", policy(ThinMarketPolicy::Skip)).detect_anomalies(&live).await?;
+    let weighted = detector()?.with_holiday_calendar("EURUSD", policy(ThinMarketPolicy::DownWeight)).detect_anomalies(&live).await?;
+    let holiday = |a: &DetectedAnomaly| a.timestamp.date_naive() == independence_day.date_naive();
+    println!("   anomalies: {} plain, {} skipping, {} down-weighted; {} on the holiday without a calendar",
+             plain.len(), skipped.len(), weighted.len(), plain.iter().filter(|a| holiday(a)).count());
+    ensure!(!on(&plain, holiday_spike).is_empty(), "the holiday spike is an anomaly without a calendar");
+    ensure!(!skipped.iter().any(holiday), "the skip policy reports nothing on the holiday");
+    ensure!(!on(&skipped, ordinary_spike).is_empty(), "ordinary days are unaffected");
+    ensure!(!on(&weighted, holiday_spike).is_empty(), "the down-weighted holiday spike is still reported");
+    for anomaly in on(&weighted, holiday_spike) {
+        let full = on(&plain, holiday_spike).into_iter().find(|a| a.anomaly_type.name() == anomaly.anomaly_type.name());
+        ensure!(full.is_some_and(|f| (anomaly.confidence - 0.5 * f.confidence).abs() < 1e-9), "confidence is halved");
+        ensure!(anomaly.market_context.recent_events.iter().any(|e| e == &format!("{}: USD Independence Day", THIN_MARKET_EVENT)),
+                "the anomaly names the holiday");
+    }
+    ensure!(weighted.iter().filter(|a| !holiday(a)).all(|a| a.market_context.recent_events.is_empty()), "ordinary anomalies are unlabelled");
+    println!("   ✅ Holiday anomalies skipped, or halved and labelled");
+
+    // Test 3: the backtester refuses or shrinks entries on holiday bars
+    println!("📊 Test 3: backtest entries");
+    let year_end = hourly_walk(&mut rng, at(2024, 12, 27, 0), 24 * 10);
+    let config = BacktestConfig { warmup_bars: 10, cycle_refresh_bars: 1_000, ..BacktestConfig::default() };
+    let run = |calendar: HolidayCalendar| {
+        let config = config.clone();
+        let year_end = &year_end;
+        async move {
+            let thin_bars = Arc::new(Mutex::new(0));
+            let engine = BacktestEngine::new(StrategyConfig::default(), 100_000.0, config)?
+                .with_trading_windows(TradingWindowsConfig::unrestricted())
+                .with_holiday_calendar(calendar);
+            let mut strategy = LongStrategy { thin_bars: thin_bars.clone() };
+            let run = engine.run(&mut strategy, "EURUSD", year_end, &[]).await?;
+            let seen = *thin_bars.lock().unwrap();
+            anyhow::Ok((run, seen))
+        }
+    };
+    let (skip_run, skip_seen) = run(policy(ThinMarketPolicy::Skip)).await?;
+    let (weighted_run, _) = run(policy(ThinMarketPolicy::DownWeight)).await?;
+    let (plain_run, _) = run(HolidayCalendar::disabled()).await?;
+    let first_fill = |trades: &[forex_pattern_reconstruction::report::TradeRecord]| trades.first().map(|t| (t.timestamp, t.size));
+    println!("   first fill: skip {:?}, down-weight {:?}, none {:?}; {} thin bars",
+             first_fill(&skip_run.trades), first_fill(&weighted_run.trades), first_fill(&plain_run.trades), skip_run.thin_market_bars);
+    ensure!(skip_run.thin_market_bars == 24 * 7, "Dec 27 - Jan 2 are thin, got {}", skip_run.thin_market_bars);
+    ensure!(skip_seen == 24 * 7 - 9, "strategies see the thin flag after warm-up, got {}", skip_seen);
+    ensure!(skip_run.trades.first().is_some_and(|t| t.timestamp >= at(2025, 1, 3, 0)), "no entry before the lull ends");
+    ensure!(weighted_run.trades.first().is_some_and(|t| (t.size - 5_000.0).abs() < 1e-6), "holiday entries are half size");
+    ensure!(plain_run.trades.first().is_some_and(|t| (t.size - 10_000.0).abs() < 1e-6), "no calendar, full size");
+    println!("   ✅ Entries skipped or halved in the year-end lull");
+
+    // Test 4: the dossier flags a symmetry that only exists because of year-end moves
+    println!("📊 Test 4: dossier annotations");
+    let start = at(2021, 1, 4, 0);
+    let mut drift = 0.0;
+    let daily: Vec<ForexDataPoint> = (0..365 * 3).map(|day| {
+        let timestamp = start + Duration::days(day);
+        drift += rng.gen_range(-0.0005..0.0005);
+        let weekly = 0.005 * (2.0 * PI * day as f64 / 7.0).sin();
+        let close = 1.1 * (1.0 + weekly + drift);
+        ForexDataPoint { timestamp, open: close, high: close * 1.001, low: close * 0.999, close, volume: None }
+    }).collect();
+    let generator = DossierGenerator::new(DossierConfig::default())?.with_holiday_calendar(HolidayCalendar::default());
+    let dossier = generator.compile("EURUSD", "1D", &[symmetry(7), symmetry(364)], &daily, &[], None);
+    for evidence in &dossier.evidence {
+        println!("   {}: autocorrelation {:?} -> {:?} without holidays, note {:?}",
+                 evidence.symmetry.name, evidence.autocorrelation, evidence.autocorrelation_excluding_thin, evidence.holiday_note);
+    }
+    ensure!(dossier.thin_market_days.len() >= 30, "three year-ends plus holidays, got {}", dossier.thin_market_days.len());
+    ensure!(dossier.evidence[0].holiday_note.is_none(), "a genuine weekly cycle survives without holidays");
+    ensure!(dossier.evidence[1].holiday_note.is_some(), "a yearly period is flagged");
+    let html = generator.render_html(&dossier);
+    ensure!(html.contains("Thin holiday markets") && html.contains("Possible holiday artifact"), "HTML carries the annotations");
+    let plain_dossier = DossierGenerator::new(DossierConfig::default())?.compile("EURUSD", "1D", &[symmetry(364)], &daily, &[], None);
+    ensure!(plain_dossier.thin_market_days.is_empty() && plain_dossier.evidence[0].holiday_note.is_none(), "no calendar, no notes");
+    println!("   ✅ Yearly symmetry flagged, weekly symmetry trusted");
+
+    // Test 5: the daily report warns on holidays
+    println!("📊 Test 5: daily report");
+    let reporter = DailyReportGenerator::new(DailyReportConfig::default())?.with_holiday_calendar(HolidayCalendar::default());
+    let input = DailyReportInput { anomalies: weighted.clone(), ..DailyReportInput::default() };
+    let report = reporter.compile(independence_day.date_naive(), &input);
+    let markdown = reporter.render_markdown(&report);
+    println!("   {:?}, {} thin-market anomalies", report.thin_markets, report.thin_market_anomalies);
+    ensure!(report.thin_markets == vec!["USD Independence Day".to_string()], "the report names the holiday");
+    ensure!(report.thin_market_anomalies > 0 && report.thin_market_anomalies == report.anomaly_count, "every anomaly that day is a thin-market anomaly");
+    ensure!(markdown.contains("Thin holiday markets: USD Independence Day"), "Markdown carries the warning");
+    let ordinary = reporter.compile(ordinary_spike.date_naive(), &input);
+    ensure!(ordinary.thin_markets.is_empty() && !reporter.render_markdown(&ordinary).contains("Thin holiday"), "ordinary days carry no warning");
+    println!("   ✅ Holiday warning in the report");
+
+    println!();
+    println!("🎉 All holiday calendar tests passed");
+    Ok(())
+}
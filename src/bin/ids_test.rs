@@ -0,0 +1,125 @@
+//! # Ids Test
+//!
+//! Check generated symmetry, cycle and anomaly ids are unique, sort by creation
+//! time and carry their kind, that they save as plain strings and ids from before
+//! the scheme still load, and that links from anomalies and trades to unknown
+//! symmetries or cycles are reported
+
+use anyhow::{ensure, Result};
+use chrono::Utc;
+use std::collections::HashSet;
+
+use forex_pattern_reconstruction::anomaly::{AnomalySeverity, AnomalyType, DetectedAnomaly, MarketContext};
+use forex_pattern_reconstruction::ids::{new_ulid, ulid_timestamp, AnomalyId, CycleId, SymmetryId};
+use forex_pattern_reconstruction::patterns::HiddenCycle;
+use forex_pattern_reconstruction::report::{dangling_references, TradeRecord};
+use forex_pattern_reconstruction::symmetry::TemporalSymmetry;
+
+fn symmetry(id: SymmetryId) -> TemporalSymmetry {
+    TemporalSymmetry {
+        id,
+        symmetry_type: "Cyclic".to_string(),
+        name: "20-Day Cycle".to_string(),
+        period_days: 20,
+        strength: 0.8,
+        confidence: 0.9,
+        field_signature: 0,
+        discovered_at: Utc::now(),
+        validation_score: 0.8,
+        mirror_points: Vec::new(),
+        phase_shift: 0.0,
+    }
+}
+
+fn anomaly(symmetry_id: SymmetryId, cycle_id: CycleId) -> DetectedAnomaly {
+    DetectedAnomaly {
+        id: AnomalyId::new(),
+        timestamp: Utc::now(),
+        anomaly_type: AnomalyType::SymmetryBreakdown { symmetry_id: symmetry_id.clone(), expected_strength: 0.8, actual_strength: 0.2 },
+        severity: AnomalySeverity::High,
+        confidence: 0.9,
+        deviation_magnitude: 0.01,
+        affected_symmetries: vec![symmetry_id],
+        affected_cycles: vec![cycle_id],
+        market_context: MarketContext {
+            session: "London".to_string(),
+            volatility_regime: "Normal".to_string(),
+            trend_direction: "Sideways".to_string(),
+            recent_events: Vec::new(),
+            event_proximity: 0.0,
+        },
+        trading_signal: None,
+    }
+}
+
+fn trade(symmetry_id: Option<SymmetryId>, cycle_id: Option<CycleId>) -> TradeRecord {
+    TradeRecord {
+        pair: "EURUSD".to_string(),
+        timestamp: Utc::now(),
+        side: "Buy".to_string(),
+        size: 10_000.0,
+        entry_price: 1.1,
+        exit_price: None,
+        commission: 0.0,
+        profit_loss: 0.0,
+        symmetry_id,
+        cycle_id,
+    }
+}
+
+fn main() -> Result<()> {
+    println!("🔬 IDS TEST");
+    println!("===========");
+    println!();
+
+    // Test 1: ids are unique and sort in the order they were made
+    println!("📊 Test 1: uniqueness and order");
+    let before = Utc::now();
+    let ids: Vec<SymmetryId> = (0..10_000).map(|_| SymmetryId::new()).collect();
+    ensure!(ids.windows(2).all(|pair| pair[0] < pair[1]), "ids generated in one millisecond still sort");
+    ensure!(ids.iter().collect::<HashSet<_>>().len() == ids.len(), "no duplicates");
+    ensure!(ids.iter().all(|id| id.starts_with("sym_") && id.len() == 4 + 26), "prefixed ULIDs: {}", ids[0]);
+    let created = ids[0].created_at().expect("a generated id has a creation time");
+    ensure!(created >= before - chrono::Duration::milliseconds(1) && created <= Utc::now(), "created at {}", created);
+    ensure!(CycleId::new().starts_with("cyc_") && AnomalyId::new().starts_with("anm_"), "each kind has its prefix");
+    ensure!(ulid_timestamp(&new_ulid()).is_some() && ulid_timestamp("not-a-ulid").is_none(), "ULIDs parse, other strings do not");
+    println!("   ✅ {} ids in order, e.g. {}", ids.len(), ids[0]);
+
+    // Test 2: ids save as plain strings and older ids still load
+    println!("📊 Test 2: persistence");
+    let id = SymmetryId::new();
+    let json = serde_json::to_string(&id)?;
+    ensure!(json == format!("\"{}\"", id), "saved as a string: {}", json);
+    ensure!(serde_json::from_str::<SymmetryId>(&json)? == id, "round trip");
+    let legacy: SymmetryId = serde_json::from_str("\"symmetry_3\"")?;
+    ensure!(legacy == "symmetry_3" && legacy.created_at().is_none(), "legacy id kept as-is");
+    let on_h4 = id.on_timeframe("H4");
+    ensure!(on_h4.ends_with("@H4") && on_h4.created_at() == id.created_at(), "timeframe-qualified ids keep their creation time");
+    let cycle: HiddenCycle = serde_json::from_str(r#"{"name":"20-Bar Cycle","period":20,"confidence":0.9,"amplitude":0.01,"phase":0.0}"#)?;
+    ensure!(cycle.id.starts_with("cyc_"), "cycles saved without an id get one: {}", cycle.id);
+    let reloaded: HiddenCycle = serde_json::from_str(&serde_json::to_string(&cycle)?)?;
+    ensure!(reloaded.id == cycle.id, "and keep it once saved");
+    println!("   ✅ {} and legacy {} load", id, legacy);
+
+    // Test 3: links to unknown symmetries or cycles are reported
+    println!("📊 Test 3: referential integrity");
+    let symmetries = vec![symmetry(SymmetryId::new())];
+    let cycles = vec![HiddenCycle { id: CycleId::new(), name: "20-Bar Cycle".to_string(), period: 20, confidence: 0.9, amplitude: 0.01, phase: 0.0, p_value: None, track: None }];
+    let (known_symmetry, known_cycle) = (symmetries[0].id.clone(), cycles[0].id.clone());
+    let anomalies = vec![anomaly(known_symmetry.clone(), known_cycle.clone())];
+    let trades = vec![trade(Some(known_symmetry.clone()), None), trade(None, Some(known_cycle.clone())), trade(None, None)];
+    ensure!(dangling_references(&symmetries, &cycles, &anomalies, &trades).is_empty(), "every link resolves");
+
+    let missing_cycle = CycleId::new();
+    let stray = anomaly("symmetry_0".into(), known_cycle);
+    let dangling = dangling_references(&symmetries, &cycles, std::slice::from_ref(&stray), &[trade(Some(known_symmetry), Some(missing_cycle.clone()))]);
+    let missing: Vec<(&str, &str)> = dangling.iter().map(|d| (d.source.as_str(), d.missing_id.as_str())).collect();
+    ensure!(missing.len() == 3 && missing[..2].iter().all(|(source, id)| *source == stray.id.as_str() && *id == "symmetry_0"),
+            "the breakdown and its affected symmetry dangle: {:?}", missing);
+    ensure!(missing[2].0.starts_with("EURUSD@") && missing[2].1 == missing_cycle.as_str(), "the trade's cycle dangles: {:?}", missing[2]);
+    println!("   ✅ {} dangling links found", dangling.len());
+
+    println!();
+    println!("🎉 All ids tests passed");
+    Ok(())
+}
@@ -0,0 +1,144 @@
+//! # Incremental Symmetry Test
+//!
+//! Check the running correlations and signatures match pair-by-pair field
+//! similarity and per-period signatures through pushes and replacements, that
+//! streaming bars one at a time finds the same cyclic symmetries as a full
+//! extraction with stable ids, and that a new bar costs far less than
+//! re-analyzing the history
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::time::Instant;
+
+use forex_pattern_reconstruction::core::autocorrelation::{lag_similarity, pattern_signature};
+use forex_pattern_reconstruction::core::{EngineConfig, RunningCorrelation, TimeSymmetricEngine};
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::symmetry::TemporalSymmetry;
+
+/// Daily bars swinging over 17 bars under noise
+fn bars(rng: &mut StdRng, count: i64) -> Vec<ForexDataPoint> {
+    let start = Utc.with_ymd_and_hms(2000, 1, 3, 0, 0, 0).unwrap();
+    let mut close = 1.2;
+    (0..count).map(|i| {
+        let open = close;
+        close *= 1.0 + 0.004 * (i as f64 * std::f64::consts::TAU / 17.0).sin() + rng.gen_range(-0.001..0.001);
+        ForexDataPoint { timestamp: start + Duration::days(i), open, high: open.max(close) * 1.001, low: open.min(close) * 0.999, close, volume: None }
+    }).collect()
+}
+
+/// The cyclic symmetries of a full extraction, by period
+fn cyclic(symmetries: &[TemporalSymmetry]) -> Vec<(u32, f64)> {
+    let mut cyclic: Vec<(u32, f64)> = symmetries.iter()
+        .filter(|symmetry| symmetry.symmetry_type == "mirror")
+        .map(|symmetry| (symmetry.period_days, symmetry.strength))
+        .collect();
+    cyclic.sort_by_key(|(period, _)| *period);
+    cyclic
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 INCREMENTAL SYMMETRY TEST");
+    println!("============================");
+    println!();
+
+    let mut rng = StdRng::seed_from_u64(11);
+
+    // Test 1: running sums agree with the direct similarity through replacements
+    println!("📊 Test 1: Running correlation");
+    let (max_lag, window) = (40, 5);
+    let mut running = RunningCorrelation::new(max_lag, window);
+    let mut states: Vec<u64> = Vec::new();
+    for _ in 0..400 {
+        let state = rng.gen::<u64>() >> rng.gen_range(0..32);
+        running.push(state, rng.gen_bool(0.5));
+        states.push(state);
+        if states.len() > window {
+            let index = states.len() - 1 - window;
+            states[index] ^= rng.gen::<u64>();
+            ensure!(running.replace(index, states[index]), "state {} no longer retained", index);
+        }
+    }
+    for lag in 1..=max_lag {
+        let (fast, direct) = (running.similarity(lag).unwrap(), lag_similarity(&states, lag).unwrap());
+        ensure!((fast - direct).abs() < 1e-12, "lag {}: {} vs {}", lag, fast, direct);
+    }
+    ensure!((1..=max_lag).all(|lag| running.signature(lag) == Some(pattern_signature(&states, lag))), "signature drifted");
+    ensure!(running.signature(23) != running.signature(24), "periods share a signature");
+    ensure!(running.similarity(max_lag + 1).is_none() && running.similarity(0).is_none(), "lags out of range");
+    ensure!(!running.replace(0, 1), "replaced a state no longer retained");
+    println!("   ✅ {} lags identical after {} replacements", max_lag, states.len() - window);
+
+    // Test 2: streaming bar by bar finds what a full extraction finds
+    println!("📊 Test 2: Streaming against full extraction");
+    let config = EngineConfig { max_cycle_period: 60, coherence_window: 8, min_symmetry_strength: 0.7, ..EngineConfig::default() };
+    let data = bars(&mut rng, 600);
+    let mut batch = TimeSymmetricEngine::new(config.clone())?;
+    batch.initialize().await?;
+    let expected = cyclic(&batch.extract_temporal_symmetries(&data).await?);
+    ensure!(!expected.is_empty(), "no cyclic symmetries to compare");
+
+    let mut engine = TimeSymmetricEngine::new(config.clone())?;
+    ensure!(engine.update_with_point(&data[0]).await.is_err(), "uninitialized engine streamed");
+    engine.initialize().await?;
+    engine.update_with_window(&data[..300]).await?;
+    let mut streamed = Vec::new();
+    for point in &data[300..] {
+        streamed = engine.update_with_point(point).await?;
+    }
+    let found = cyclic(&streamed);
+    ensure!(found.len() == expected.len(), "{} streamed vs {} extracted", found.len(), expected.len());
+    for ((period, strength), (expected_period, expected_strength)) in found.iter().zip(&expected) {
+        ensure!(period == expected_period && (strength - expected_strength).abs() < 1e-12,
+                "period {} strength {} vs period {} strength {}", period, strength, expected_period, expected_strength);
+    }
+    let signatures = |symmetries: &[TemporalSymmetry]| -> Vec<(u32, u64)> {
+        let mut signatures: Vec<(u32, u64)> = symmetries.iter()
+            .filter(|symmetry| symmetry.symmetry_type == "mirror")
+            .map(|symmetry| (symmetry.period_days, symmetry.field_signature))
+            .collect();
+        signatures.sort();
+        signatures
+    };
+    ensure!(signatures(&streamed) == signatures(&batch.extract_temporal_symmetries(&data).await?), "streamed and extracted signatures differ");
+    ensure!(streamed.windows(2).all(|pair| pair[0].strength >= pair[1].strength), "strongest first");
+    println!("   ✅ {} cyclic symmetries match, strongest {}", found.len(), streamed[0].name);
+
+    // Test 3: ids stay put and old bars are ignored
+    println!("📊 Test 3: Stable ids");
+    let again = engine.update_with_window(&data[590..]).await?;
+    ensure!(engine.streamed_bars() == data.len(), "replayed bars were streamed again");
+    ensure!(again.iter().zip(&streamed).all(|(a, b)| a.id == b.id && a.strength == b.strength), "replaying old bars changed the symmetries");
+    let next = bars(&mut rng, 601).pop().unwrap();
+    let updated = engine.update_with_point(&next).await?;
+    let kept = updated.iter().filter(|symmetry| streamed.iter().any(|old| old.period_days == symmetry.period_days && old.id == symmetry.id)).count();
+    let shared = updated.iter().filter(|symmetry| streamed.iter().any(|old| old.period_days == symmetry.period_days)).count();
+    ensure!(kept == shared && shared > 0, "{} of {} periods kept their id", kept, shared);
+    engine.reset_incremental();
+    ensure!(engine.streamed_bars() == 0, "reset kept the stream");
+    println!("   ✅ {} periods kept their ids", kept);
+
+    // Test 4: a new bar is much cheaper than re-analyzing the history
+    println!("📊 Test 4: Cost per bar");
+    let config = EngineConfig { max_cycle_period: 365, coherence_window: 50, ..EngineConfig::default() };
+    let history = bars(&mut rng, 8_000);
+    let mut engine = TimeSymmetricEngine::new(config)?;
+    engine.initialize().await?;
+    let timer = Instant::now();
+    engine.extract_temporal_symmetries(&history[..7_900]).await?;
+    let full = timer.elapsed();
+    engine.update_with_window(&history[..7_900]).await?;
+    let timer = Instant::now();
+    for point in &history[7_900..] {
+        engine.update_with_point(point).await?;
+    }
+    let per_bar = timer.elapsed() / 100;
+    ensure!(per_bar * 10 < full, "{:?} per bar vs {:?} for a full extraction", per_bar, full);
+    println!("   ✅ {:?} per bar vs {:?} for a full extraction", per_bar, full);
+
+    println!();
+    println!("🎉 All incremental symmetry tests passed");
+    Ok(())
+}
@@ -0,0 +1,160 @@
+//! # Lead-Lag Test
+//!
+//! Check cross-correlation finds a planted lead at its lag whichever way the
+//! pairs are given, none for unrelated pairs, and reaches the follower's strategies
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rand_distr::StandardNormal;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use forex_pattern_reconstruction::anomaly::suppression::SuppressionList;
+use forex_pattern_reconstruction::backtest::strategy::{Order, Strategy, StrategyContext};
+use forex_pattern_reconstruction::correlation::{CrossPairAnalyzer, LeadLagConfig, LeadLagOpportunity};
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::laplacian_rl::TradingAction;
+use forex_pattern_reconstruction::multi_currency::{MultiCurrencyManager, PairAccount};
+
+const BARS: usize = 800;
+
+/// Hourly bars compounding `returns` from 1.0
+fn bars(returns: &[f64]) -> Vec<ForexDataPoint> {
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let mut close = 1.0;
+    let mut data = vec![];
+    for (i, r) in std::iter::once(&0.0).chain(returns).enumerate() {
+        close *= 1.0 + r;
+        data.push(ForexDataPoint { timestamp: start + Duration::hours(i as i64), open: close, high: close, low: close, close, volume: None });
+    }
+    data
+}
+
+/// Leader returns and follower returns `beta·leader[t − lag] + noise`
+fn echo(seed: u64, lag: usize, beta: f64) -> (Vec<f64>, Vec<f64>) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let leader: Vec<f64> = (0..BARS).map(|_| 0.001 * rng.sample::<f64, _>(StandardNormal)).collect();
+    let follower = (0..BARS)
+        .map(|t| beta * if t >= lag { leader[t - lag] } else { 0.0 } + 0.001 * rng.sample::<f64, _>(StandardNormal))
+        .collect();
+    (leader, follower)
+}
+
+/// Strategy recording the leads it hears of and buying on each
+struct LeadFollower {
+    heard: Arc<Mutex<Vec<LeadLagOpportunity>>>,
+}
+
+impl Strategy for LeadFollower {
+    fn name(&self) -> &str {
+        "lead-follower"
+    }
+
+    fn on_bar(&mut self, _context: &StrategyContext, _bar: &ForexDataPoint) -> Vec<Order> {
+        Vec::new()
+    }
+
+    fn on_lead_lag(&mut self, context: &StrategyContext, opportunity: &LeadLagOpportunity) -> Vec<Order> {
+        self.heard.lock().unwrap().push(opportunity.clone());
+        if opportunity.follower == context.pair {
+            vec![Order::buy(1_000.0, &opportunity.summary())]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 LEAD-LAG TEST");
+    println!("================");
+    println!();
+
+    let analyzer = CrossPairAnalyzer::new();
+    let config = LeadLagConfig::default();
+    let (eur_returns, chf_returns) = echo(1, 3, 0.8);
+    let (eur, chf) = (bars(&eur_returns), bars(&chf_returns));
+
+    // Test 1: the cross-correlation peaks at the echo's lag
+    println!("📊 Test 1: cross-correlation");
+    let profile = analyzer.cross_correlation("EURUSD", &eur, "EURCHF", &chf, &config);
+    ensure!(profile.correlations.len() == 2 * config.max_lag + 1, "one correlation per lag, got {}", profile.correlations.len());
+    let peak = profile.peak().unwrap();
+    ensure!(peak.lag == 3 && peak.correlation > 0.5, "peak {:?}", peak);
+    ensure!(profile.at(0).unwrap().correlation.abs() < 0.15 && profile.at(-3).unwrap().correlation.abs() < 0.15, "no relationship elsewhere");
+    ensure!(profile.at(3).unwrap().samples == BARS - 3, "lag 3 overlaps {} returns", BARS - 3);
+    println!("   ✅ Peak ρ {:.2} at lag {} (lag 0: {:.2})", peak.correlation, peak.lag, profile.at(0).unwrap().correlation);
+
+    // Test 2: the lead is reported whichever way round the pairs are given
+    println!("📊 Test 2: opportunities");
+    let forward = analyzer.lead_lag("EURUSD", &eur, "EURCHF", &chf, &config).expect("lead found");
+    let backward = analyzer.lead_lag("EURCHF", &chf, "EURUSD", &eur, &config).expect("lead found");
+    ensure!(forward.summary().starts_with("EURUSD leads EURCHF by 3 bars"), "{}", forward.summary());
+    ensure!(backward.leader == "EURUSD" && backward.lag_bars == 3 && (backward.correlation - forward.correlation).abs() < 1e-12, "reversed {:?}", backward);
+    ensure!(forward.z_score > config.min_z && forward.as_of == eur.last().unwrap().timestamp, "z {:.1} as of {}", forward.z_score, forward.as_of);
+    let unechoed: f64 = eur_returns.iter().rev().take(3).sum();
+    ensure!(forward.pending_move.signum() == unechoed.signum() && (forward.pending_move / unechoed - 0.8).abs() < 0.15, "pending {:.6} from {:.6}", forward.pending_move, unechoed);
+    let (gbp_returns, inverse_returns) = echo(2, 1, -0.8);
+    let inverse = analyzer.lead_lag("GBPUSD", &bars(&gbp_returns), "EURGBP", &bars(&inverse_returns), &config).expect("inverse lead found");
+    ensure!(inverse.correlation < -0.5 && inverse.lag_bars == 1 && inverse.summary().contains("by 1 bar "), "{}", inverse.summary());
+    println!("   ✅ {}; {}", forward.summary(), inverse.summary());
+
+    // Test 3: lag-zero and unrelated pairs lead nothing
+    println!("📊 Test 3: no lead");
+    let (usd_returns, together) = echo(3, 0, 0.9);
+    let (usd, jpy) = (bars(&usd_returns), bars(&together));
+    let contemporaneous = analyzer.cross_correlation("USDCHF", &usd, "USDJPY", &jpy, &config);
+    ensure!(contemporaneous.peak().unwrap().lag == 0, "peak at lag zero");
+    ensure!(analyzer.lead_lag("USDCHF", &usd, "USDJPY", &jpy, &config).is_none(), "lag-zero correlation is not a lead");
+    let (a, b) = (bars(&echo(4, 0, 0.0).0), bars(&echo(5, 0, 0.0).0));
+    ensure!(analyzer.lead_lag("AUDUSD", &a, "NZDUSD", &b, &config).is_none(), "independent series lead nothing");
+    let short = LeadLagConfig { min_samples: BARS, ..config.clone() };
+    ensure!(analyzer.cross_correlation("EURUSD", &eur, "EURCHF", &chf, &short).correlations.len() == 1, "only lag 0 has enough returns");
+    let data_map = HashMap::from([
+        ("EURUSD".to_string(), eur.clone()),
+        ("EURCHF".to_string(), chf.clone()),
+        ("AUDUSD".to_string(), a.clone()),
+    ]);
+    let all = analyzer.find_lead_lag_opportunities(&data_map, &config);
+    ensure!(all.len() == 1 && all[0].leader == "EURUSD", "one lead among three pairs: {:?}", all);
+    analyzer.print_lead_lag_opportunities(&all);
+    println!("   ✅ Lag-zero, unrelated and short series report no lead");
+
+    // Test 4: the follower's strategies hear of the lead
+    println!("📊 Test 4: strategy subscription");
+    let mut manager = MultiCurrencyManager::new();
+    manager.initialize_pairs(&["EURUSD".to_string(), "EURCHF".to_string()]).await?;
+    let heard = Arc::new(Mutex::new(Vec::new()));
+    {
+        let mut pairs_map = manager.pairs.write().await;
+        for (symbol, data) in [("EURUSD", &eur), ("EURCHF", &chf)] {
+            let state = pairs_map.get_mut(symbol).unwrap();
+            state.historical_data = data.clone();
+            state.backfill_config.timeframe = "H1".to_string();
+            state.is_active = true;
+            state.warm = true;
+            state.set_strategy(Box::new(LeadFollower { heard: Arc::clone(&heard) }));
+        }
+    }
+    manager.refresh_correlations().await?;
+    let found = manager.lead_lag.read().await.clone();
+    ensure!(found.len() == 1 && found[0].follower == "EURCHF", "manager keeps the lead: {:?}", found);
+    let heard_now = heard.lock().unwrap().clone();
+    ensure!(heard_now.len() == 1 && heard_now[0] == found[0], "only the follower is told: {:?}", heard_now);
+    let decision = {
+        let mut pairs_map = manager.pairs.write().await;
+        let state = pairs_map.get_mut("EURCHF").unwrap();
+        state.process_market_update_seeded(&SuppressionList::default(), &PairAccount { units_per_size: 1_000.0, ..PairAccount::default() }, 1).await?
+    };
+    let actions = decision.map(|decision| decision.actions).unwrap_or_default();
+    ensure!(actions == vec![TradingAction::Buy { size: 1 }], "the strategy's order is traded: {:?}", actions);
+    manager.refresh_correlations().await?;
+    ensure!(heard.lock().unwrap().len() == 2, "told again at each refresh");
+    println!("   ✅ EURCHF strategy told \"{}\" and bought", found[0].summary());
+
+    println!();
+    println!("🎉 All lead-lag tests passed");
+    Ok(())
+}
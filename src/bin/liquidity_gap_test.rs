@@ -0,0 +1,158 @@
+//! # Liquidity Gap Test
+//!
+//! Build hourly history where the London session (08:00-16:00) is busy and the
+//! rest of the day thin, then check that a flat, volume-less bar is flagged only
+//! when it falls in an hour that is normally active, and that the RL layer, the
+//! portfolio risk gate and the baseline strategy all refuse new entries after one
+
+use anyhow::{ensure, Result};
+use chrono::{DateTime, Duration, TimeZone, Timelike, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::{HashMap, HashSet};
+
+use forex_pattern_reconstruction::anomaly::{
+    AnomalyDetectionConfig, AnomalyType, DetectedAnomaly, TemporalAnomalyDetector,
+};
+use forex_pattern_reconstruction::backtest::strategy::{Strategy, StrategyContext, TimeSymmetricStrategy};
+use forex_pattern_reconstruction::backtest::StrategyConfig;
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::laplacian_rl::{LaplacianQLearningAgent, LaplacianQLearningConfig, TradingAction};
+use forex_pattern_reconstruction::multi_currency::MultiCurrencyManager;
+use forex_pattern_reconstruction::ids::CycleId;
+use forex_pattern_reconstruction::patterns::HiddenCycle;
+use forex_pattern_reconstruction::trading_windows::TradingWindowsConfig;
+
+fn active(timestamp: DateTime<Utc>) -> bool {
+    (8..16).contains(&timestamp.hour())
+}
+
+fn bar(timestamp: DateTime<Utc>, range: f64, volume: Option<f64>) -> ForexDataPoint {
+    let close = 1.1000;
+    ForexDataPoint { timestamp, open: close, high: close + range / 2.0, low: close - range / 2.0, close, volume }
+}
+
+/// Busy London hours: 20-pip ranges and ~1000 lots; otherwise 6 pips and ~200 lots
+fn history(rng: &mut StdRng, start: DateTime<Utc>, hours: i64, with_volume: bool) -> Vec<ForexDataPoint> {
+    (0..hours).map(|h| {
+        let timestamp = start + Duration::hours(h);
+        let (range, volume) = if active(timestamp) { (0.0020, 1000.0) } else { (0.0006, 200.0) };
+        let noise = rng.gen_range(0.8..1.2);
+        bar(timestamp, range * noise, with_volume.then_some(volume * noise))
+    }).collect()
+}
+
+/// Liquidity gaps reported for `point`
+async fn gaps(detector: &mut TemporalAnomalyDetector, point: ForexDataPoint) -> Result<Vec<DetectedAnomaly>> {
+    let anomalies = detector.detect_anomalies(std::slice::from_ref(&point)).await?;
+    Ok(anomalies.into_iter()
+        .filter(|a| a.timestamp == point.timestamp && matches!(a.anomaly_type, AnomalyType::LiquidityGap { .. }))
+        .collect())
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 LIQUIDITY GAP TEST");
+    println!("=====================");
+    println!();
+
+    let mut rng = StdRng::seed_from_u64(29);
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let historical = history(&mut rng, start, 24 * 7 * 6, true);
+    let mut detector = TemporalAnomalyDetector::new(Vec::new(), Vec::new(), &historical, AnomalyDetectionConfig::default())?;
+    let next_week = start + Duration::weeks(6);
+    let london = next_week + Duration::hours(10);
+    let asia = next_week + Duration::hours(3);
+
+    // Test 1: a flat London bar with a trickle of volume is a gap
+    println!("📊 Test 1: thin bar in an active session");
+    let found = gaps(&mut detector, bar(london, 0.0002, Some(50.0))).await?;
+    let Some(gap) = found.first() else {
+        anyhow::bail!("a 2-pip, 50-lot London bar was not flagged");
+    };
+    if let AnomalyType::LiquidityGap { expected_volatility, actual_volatility, expected_volume, actual_volume } = &gap.anomaly_type {
+        println!("   range {:.5} vs {:.5}, volume {:?} vs {:.0}: {:?}, confidence {:.3}",
+                 actual_volatility, expected_volatility, actual_volume, expected_volume.unwrap_or(0.0), gap.severity, gap.confidence);
+        ensure!(expected_volume.is_some_and(|v| v > 800.0), "expected London volume should come from the profile");
+    }
+    ensure!(gap.trading_signal.as_ref().is_some_and(|s| s.signal_type == "Hold"), "a gap signals standing aside");
+    ensure!(!gaps(&mut detector, bar(london, 0.0002, None)).await?.is_empty(), "missing volume counts as absent");
+    println!("   ✅ Flagged, also with volume missing");
+
+    // Test 2: thin bars are normal in quiet hours, and a small range with normal volume is not a gap
+    println!("📊 Test 2: quiet hour and busy volume");
+    ensure!(gaps(&mut detector, bar(asia, 0.0001, Some(10.0))).await?.is_empty(), "quiet hours are expected to be thin");
+    ensure!(gaps(&mut detector, bar(london, 0.0002, Some(1000.0))).await?.is_empty(), "normal volume is not illiquid");
+    ensure!(gaps(&mut detector, bar(london, 0.0015, Some(50.0))).await?.is_empty(), "a normal range is not illiquid");
+    println!("   ✅ No gap in the Asian session or with normal volume or range");
+
+    // Test 3: without volume in the history the range alone decides
+    println!("📊 Test 3: history without volume");
+    let no_volume = history(&mut rng, start, 24 * 7 * 6, false);
+    let mut range_only = TemporalAnomalyDetector::new(Vec::new(), Vec::new(), &no_volume, AnomalyDetectionConfig::default())?;
+    let found = gaps(&mut range_only, bar(london, 0.0002, None)).await?;
+    ensure!(found.iter().any(|a| matches!(a.anomaly_type, AnomalyType::LiquidityGap { expected_volume: None, .. })),
+            "a flat London bar should still be flagged from its range");
+    ensure!(gaps(&mut range_only, bar(london, 0.0018, None)).await?.is_empty(), "a normal range without volume is fine");
+    println!("   ✅ Range-only detection");
+
+    // Test 4: the RL layer offers no entries
+    println!("📊 Test 4: RL action set");
+    let agent = LaplacianQLearningAgent::new(LaplacianQLearningConfig { exploration_rate: 1.0, ..LaplacianQLearningConfig::default() })?;
+    let actions: HashSet<TradingAction> = (0..200).map(|_| agent.choose_action("s_gap", gap)).collect::<Result<_>>()?;
+    ensure!(actions == HashSet::from([TradingAction::Hold]), "expected only Hold, got {:?}", actions);
+    println!("   ✅ Only Hold is offered");
+
+    // Test 5: the portfolio risk gate refuses entries but still closes positions
+    println!("📊 Test 5: risk gate");
+    let mut manager = MultiCurrencyManager::new().with_trading_windows(TradingWindowsConfig::unrestricted());
+    manager.initialize_major_pairs().await?;
+    manager.active_pairs.retain(|pair| pair == "EURUSD");
+    {
+        let mut pairs = manager.pairs.write().await;
+        pairs.retain(|pair, _| pair == "EURUSD");
+        let state = pairs.get_mut("EURUSD").expect("pair initialized");
+        state.historical_data = vec![bar(Utc::now() - Duration::hours(1), 0.0020, None)];
+    }
+    manager.execute_actions(&HashMap::from([("EURUSD".to_string(), vec![TradingAction::Buy { size: 1 }])])).await;
+    ensure!(manager.portfolio_snapshot().await.positions.len() == 1, "entry before the gap should fill");
+    manager.pairs.write().await.get_mut("EURUSD").unwrap().last_liquidity_gap = Some(Utc::now());
+    manager.execute_actions(&HashMap::from([("EURUSD".to_string(), vec![TradingAction::Sell { size: 1 }, TradingAction::Buy { size: 1 }])])).await;
+    let after_gap = manager.portfolio_snapshot().await;
+    ensure!(after_gap.positions.len() == 1, "entries during the gap must be refused");
+    manager.execute_actions(&HashMap::from([("EURUSD".to_string(), vec![TradingAction::ClosePosition])])).await;
+    ensure!(manager.portfolio_snapshot().await.positions.is_empty(), "closing must still be allowed");
+    manager.pairs.write().await.get_mut("EURUSD").unwrap().last_liquidity_gap = Some(Utc::now() - Duration::hours(2));
+    manager.execute_actions(&HashMap::from([("EURUSD".to_string(), vec![TradingAction::Buy { size: 1 }])])).await;
+    ensure!(manager.portfolio_snapshot().await.positions.len() == 1, "entries resume after the block period");
+    println!("   ✅ Entries refused for the block period, closes allowed");
+
+    // Test 6: the baseline strategy keeps its position but does not add or reverse
+    println!("📊 Test 6: strategy");
+    let mut strategy = TimeSymmetricStrategy::new(&StrategyConfig::default())?;
+    let cycle = HiddenCycle { id: CycleId::new(), name: "24-Bar Cycle".to_string(), period: 24, confidence: 0.9, amplitude: 0.002, phase: 0.0, p_value: None, track: None };
+    let context = |bar_index: usize, hour: i64, position_units: f64| StrategyContext {
+        pair: "EURUSD".to_string(),
+        timestamp: start + Duration::hours(hour),
+        bar_index,
+        bar_seconds: 3600.0,
+        position_units,
+        equity: 100_000.0,
+        trading_allowed: true,
+        thin_market: false,
+    };
+    strategy.on_cycle_update(&context(0, 0, 0.0), &[cycle]);
+    strategy.on_anomaly(&context(0, 0, 0.0), gap);
+    // Slope is positive at hour 0 and negative at hour 12
+    let flat = strategy.on_bar(&context(1, 0, 0.0), &historical[0]);
+    let reversing = strategy.on_bar(&context(2, 12, 10_000.0), &historical[0]);
+    let resumed = strategy.on_bar(&context(10, 0, 0.0), &historical[0]);
+    ensure!(flat.is_empty(), "no entry during the gap, got {:?}", flat);
+    ensure!(reversing.len() == 1 && (reversing[0].delta() + 10_000.0).abs() < 1e-9, "a reversal becomes an exit, got {:?}", reversing);
+    ensure!(resumed.len() == 1 && resumed[0].delta() > 0.0, "entries resume after the cooldown, got {:?}", resumed);
+    println!("   ✅ No entries or reversals until the cooldown ends");
+
+    println!();
+    println!("🎉 All liquidity gap tests passed");
+    Ok(())
+}
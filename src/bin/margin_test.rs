@@ -0,0 +1,165 @@
+//! # Margin Test
+//!
+//! Check orders are cut to the leverage limit, that a margin call is raised once
+//! per episode, that stop-outs liquidate at the price the margin level reached the
+//! stop-out level (or the open after a gap), for longs and shorts, and that the
+//! paper trading portfolio closes its worst positions first
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, TimeZone, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use forex_pattern_reconstruction::backtest::strategy::{Fill, Order, Strategy, StrategyContext};
+use forex_pattern_reconstruction::backtest::{BacktestConfig, BacktestEngine, BacktestRun, StrategyConfig};
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::laplacian_rl::TradingAction;
+use forex_pattern_reconstruction::portfolio::margin::{MarginConfig, MarginEventKind, MarginStatus};
+use forex_pattern_reconstruction::portfolio::{Portfolio, PortfolioConfig};
+use forex_pattern_reconstruction::trading_windows::TradingWindowsConfig;
+
+/// Hourly bars from (open, low, high, close)
+fn bars(prices: &[(f64, f64, f64, f64)]) -> Vec<ForexDataPoint> {
+    let start = Utc.with_ymd_and_hms(2024, 3, 4, 0, 0, 0).unwrap();
+    prices.iter()
+        .enumerate()
+        .map(|(i, &(open, low, high, close))| ForexDataPoint { timestamp: start + Duration::hours(i as i64), open, high, low, close, volume: None })
+        .collect()
+}
+
+fn flat(price: f64) -> (f64, f64, f64, f64) {
+    (price, price, price, price)
+}
+
+/// Places scripted orders and records the fills it hears about
+struct Scripted {
+    orders: HashMap<usize, Vec<Order>>,
+    fills: Arc<Mutex<Vec<Fill>>>,
+}
+
+impl Strategy for Scripted {
+    fn name(&self) -> &str {
+        "scripted"
+    }
+
+    fn on_bar(&mut self, context: &StrategyContext, _bar: &ForexDataPoint) -> Vec<Order> {
+        self.orders.remove(&context.bar_index).unwrap_or_default()
+    }
+
+    fn on_fill(&mut self, _context: &StrategyContext, fill: &Fill) -> Vec<Order> {
+        self.fills.lock().unwrap().push(fill.clone());
+        Vec::new()
+    }
+}
+
+async fn run(margin: MarginConfig, data: &[ForexDataPoint], orders: Vec<(usize, Order)>) -> Result<(BacktestRun, Vec<Fill>)> {
+    let config = BacktestConfig { warmup_bars: 1, cycle_refresh_bars: 10_000, commission: 0.0, slippage: 0.0, leverage: 30.0, margin, ..BacktestConfig::default() };
+    let engine = BacktestEngine::new(StrategyConfig::default(), 100_000.0, config)?
+        .with_trading_windows(TradingWindowsConfig::unrestricted());
+    let fills = Arc::new(Mutex::new(Vec::new()));
+    let mut by_bar: HashMap<usize, Vec<Order>> = HashMap::new();
+    for (bar, order) in orders {
+        by_bar.entry(bar).or_default().push(order);
+    }
+    let mut strategy = Scripted { orders: by_bar, fills: fills.clone() };
+    let run = engine.run(&mut strategy, "EURUSD", data, &[]).await?;
+    let fills = fills.lock().unwrap().clone();
+    Ok((run, fills))
+}
+
+fn count(run: &BacktestRun, kind: MarginEventKind) -> usize {
+    run.margin_events.iter().filter(|event| event.kind == kind).count()
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 MARGIN TEST");
+    println!("==============");
+    println!();
+
+    // Test 1: orders are cut to what 30:1 leverage allows
+    println!("📊 Test 1: leverage limit");
+    let quiet = bars(&[flat(1.1); 10]);
+    let (capped, fills) = run(MarginConfig::enforced(), &quiet, vec![(2, Order::buy(10_000_000.0, "all in"))]).await?;
+    let limit = 100_000.0 * 30.0 / 1.1;
+    ensure!((fills[0].units - limit).abs() < 1e-3, "filled {} of a {} limit", fills[0].units, limit);
+    ensure!(count(&capped, MarginEventKind::LeverageLimit) == 1, "the cut is reported");
+    let (flipped, fills) = run(MarginConfig::enforced(), &quiet, vec![(2, Order::buy(2_000_000.0, "long")), (4, Order::sell(6_000_000.0, "flip"))]).await?;
+    ensure!((fills[1].position_units + limit).abs() < 1e-3, "a flip opens at most the limit on the other side: {}", fills[1].position_units);
+    ensure!(flipped.margin_events[0].units > 1_000_000.0, "the flip was cut");
+    let (unlimited, fills) = run(MarginConfig::default(), &quiet, vec![(2, Order::buy(10_000_000.0, "all in"))]).await?;
+    ensure!(fills[0].units == 10_000_000.0 && unlimited.margin_events.is_empty(), "no limit without enforcement");
+    println!("   ✅ 10M units cut to {:.0}", limit);
+
+    // Test 2: a long is called once, then stopped out at the stop-out price
+    println!("📊 Test 2: long margin call and stop-out");
+    let units = 2_500_000.0;
+    let mut path = vec![flat(1.1); 5];
+    path.push((1.1, 1.096, 1.1, 1.0965)); // margin level 98.5% at the low, 99.9% at the close
+    path.push((1.0965, 1.0962, 1.0966, 1.0965)); // still called, no new event
+    path.push((1.09, 1.07, 1.09, 1.08)); // through the stop-out
+    path.extend([flat(1.08); 3]);
+    let data = bars(&path);
+    let (stopped, fills) = run(MarginConfig::enforced(), &data, vec![(2, Order::buy(units, "long"))]).await?;
+    ensure!(count(&stopped, MarginEventKind::MarginCall) == 1, "one margin call: {:?}", stopped.margin_events);
+    let stop_out = stopped.margin_events.iter().find(|event| event.kind == MarginEventKind::StopOut).expect("a stop-out");
+    // cash + u(p − e) = 0.5 · u·p / 30  ⇒  p = (u·e − cash) / (u (1 − 0.5/30))
+    let trigger = (units * 1.1 - 100_000.0) / (units * (1.0 - 0.5 / 30.0));
+    ensure!((fills[1].price - trigger).abs() < 1e-9 && fills[1].reason == "margin stop-out", "liquidated at {} not {}", fills[1].price, trigger);
+    ensure!((stop_out.margin_level.unwrap() - 50.0).abs() < 1e-6 && stop_out.timestamp == data[7].timestamp, "{}", stop_out.summary());
+    ensure!(fills[1].position_units == 0.0 && stopped.trades.len() == 2, "the position is closed and journalled");
+    let final_equity = stopped.equity_curve.last().unwrap().1;
+    ensure!((final_equity - 0.5 * units * trigger / 30.0).abs() < 1e-3, "half the margin is left: {}", final_equity);
+    println!("   ✅ Called at {:.4}, stopped out at {:.5} with {:.0} left", data[5].low, trigger, final_equity);
+
+    // Test 3: a gap through the stop-out liquidates at the open
+    println!("📊 Test 3: gap through the stop-out");
+    path[7] = (1.07, 1.06, 1.075, 1.065);
+    let (gapped, fills) = run(MarginConfig::enforced(), &bars(&path), vec![(2, Order::buy(units, "long"))]).await?;
+    ensure!(fills[1].price == 1.07 && count(&gapped, MarginEventKind::StopOut) == 1, "filled at the open");
+    ensure!(gapped.margin_events.last().unwrap().margin_level.unwrap() < 50.0, "the level was already below stop-out");
+    let (ignored, fills) = run(MarginConfig::default(), &bars(&path), vec![(2, Order::buy(units, "long"))]).await?;
+    ensure!(fills.len() == 1 && ignored.margin_events.is_empty(), "no liquidation without enforcement");
+    println!("   ✅ Liquidated at the 1.07 open");
+
+    // Test 4: shorts are stopped out on a rally
+    println!("📊 Test 4: short stop-out");
+    let mut rally = vec![flat(1.1); 5];
+    rally.push((1.1, 1.1, 1.13, 1.12));
+    rally.push(flat(1.12));
+    let (short, fills) = run(MarginConfig::enforced(), &bars(&rally), vec![(2, Order::sell(units, "short"))]).await?;
+    let trigger = (100_000.0 + units * 1.1) / (units * (0.5 / 30.0 + 1.0));
+    ensure!((fills[1].price - trigger).abs() < 1e-9 && fills[1].position_units == 0.0, "short covered at {} not {}", fills[1].price, trigger);
+    ensure!(count(&short, MarginEventKind::MarginCall) == 0 && count(&short, MarginEventKind::StopOut) == 1, "straight to stop-out");
+    println!("   ✅ Short covered at {:.5}", trigger);
+
+    // Test 5: the paper portfolio cuts orders and liquidates the worst loser first
+    println!("📊 Test 5: paper trading portfolio");
+    let config = PortfolioConfig { leverage: 30.0, units_per_size: 10_000.0, margin: MarginConfig::enforced(), ..PortfolioConfig::default() };
+    let mut portfolio = Portfolio::new(config);
+    let now = Utc::now();
+    let mut prices = HashMap::from([("EURUSD".to_string(), 1.1), ("GBPUSD".to_string(), 1.25)]);
+    portfolio.apply_action("EURUSD", &TradingAction::Buy { size: 150 }, 1.1, &prices, now);
+    portfolio.apply_action("GBPUSD", &TradingAction::Buy { size: 1_000 }, 1.25, &prices, now);
+    let events = portfolio.take_margin_events();
+    let gbp = portfolio.positions()["GBPUSD"].units;
+    ensure!(events.len() == 1 && events[0].kind == MarginEventKind::LeverageLimit && gbp < 10_000_000.0, "{:?}", events);
+    let snapshot = portfolio.snapshot(&prices, now);
+    ensure!(snapshot.free_margin.abs() < 1e-6, "the cut order uses up the free margin: {}", snapshot.free_margin);
+
+    prices.insert("GBPUSD".to_string(), 1.2485);
+    ensure!(portfolio.enforce_margin(&prices, now) == MarginStatus::MarginCall, "called");
+    ensure!(portfolio.enforce_margin(&prices, now) == MarginStatus::MarginCall && portfolio.take_margin_events().len() == 1, "raised once");
+    prices.insert("GBPUSD".to_string(), 1.2);
+    ensure!(portfolio.enforce_margin(&prices, now) == MarginStatus::StopOut, "stopped out");
+    let events = portfolio.take_margin_events();
+    ensure!(events[0].symbols == ["GBPUSD"] && events[0].realized_pnl < 0.0, "the losing pair goes first: {}", events[0].summary());
+    ensure!(portfolio.positions().contains_key("EURUSD") && !portfolio.positions().contains_key("GBPUSD"), "the winner survives once the level recovers");
+    let level = portfolio.snapshot(&prices, now).margin_level.unwrap();
+    ensure!(level > 50.0, "level back above stop-out: {:.1}%", level);
+    println!("   ✅ {}", events[0].summary());
+
+    println!();
+    println!("🎉 All margin tests passed");
+    Ok(())
+}
@@ -0,0 +1,130 @@
+//! # Matrix Profile Test
+//!
+//! Check the STOMP self-join against brute force, that a shape planted twice in
+//! a random walk is the top motif with the right lag and a zigzag planted once
+//! is the top discord, and that the anomaly detector reports an unseen zigzag as
+//! a matrix profile discord only when configured to
+
+use anyhow::{ensure, Result};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::f64::consts::PI;
+
+use forex_pattern_reconstruction::anomaly::{AnomalyDetectionConfig, AnomalyType, DetectedAnomaly, TemporalAnomalyDetector};
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::patterns::{MatrixProfile, MatrixProfileConfig};
+
+/// Hourly random walk from `start`, with `shape(i)` added to the close of bar `i`
+fn walk(start: DateTime<Utc>, count: usize, from: f64, seed: u64, shape: impl Fn(usize) -> f64) -> Vec<ForexDataPoint> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut level = from;
+    (0..count)
+        .map(|i| {
+            level += rng.gen_range(-0.0002..0.0002);
+            let close = level + shape(i);
+            ForexDataPoint { timestamp: start + Duration::hours(i as i64), open: close, high: close + 0.0003, low: close - 0.0003, close, volume: None }
+        })
+        .collect()
+}
+
+/// Distance between z-normalized windows, the slow way
+fn znorm_distance(a: &[f64], b: &[f64]) -> f64 {
+    let normalize = |w: &[f64]| {
+        let mean = w.iter().sum::<f64>() / w.len() as f64;
+        let std = (w.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / w.len() as f64).sqrt();
+        w.iter().map(|v| (v - mean) / std).collect::<Vec<f64>>()
+    };
+    normalize(a).iter().zip(normalize(b)).map(|(x, y)| (x - y).powi(2)).sum::<f64>().sqrt()
+}
+
+/// 24-bar zigzag of `height`
+fn zigzag(i: usize, height: f64) -> f64 {
+    if i.is_multiple_of(2) { height } else { -height }
+}
+
+fn discords(anomalies: &[DetectedAnomaly]) -> Vec<&DetectedAnomaly> {
+    anomalies.iter()
+        .filter(|a| matches!(&a.anomaly_type, AnomalyType::NovelPattern { pattern_signature, .. } if pattern_signature.starts_with("discord-")))
+        .collect()
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 Matrix Profile Test");
+    println!("======================");
+    println!();
+
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let config = MatrixProfileConfig::default();
+
+    // Test 1: STOMP agrees with brute force
+    println!("📊 Test 1: Self-join");
+    let small = walk(start, 200, 1.1, 1, |_| 0.0);
+    let small_config = MatrixProfileConfig { window: 16, ..config.clone() };
+    let profile = MatrixProfile::self_join(&small, &small_config).unwrap();
+    let closes: Vec<f64> = small.iter().map(|point| point.close).collect();
+    ensure!(profile.profile.len() == 185 && profile.exclusion_zone == 8, "{} windows, zone {}", profile.profile.len(), profile.exclusion_zone);
+    let mut worst: f64 = 0.0;
+    for i in 0..profile.profile.len() {
+        let expected = (0..profile.profile.len())
+            .filter(|j| i.abs_diff(*j) > 8)
+            .map(|j| znorm_distance(&closes[i..i + 16], &closes[j..j + 16]))
+            .fold(f64::INFINITY, f64::min);
+        worst = worst.max((profile.profile[i] - expected).abs());
+    }
+    ensure!(worst < 1e-6, "STOMP differs from brute force by {}", worst);
+    ensure!(MatrixProfile::self_join(&small[..16], &small_config).is_none(), "one window has no match");
+    println!("   ✅ {} windows match brute force within {:.1e}", profile.profile.len(), worst);
+
+    // Test 2: a shape planted twice is the top motif
+    println!("📊 Test 2: Motifs");
+    let bump = |i: usize, at: usize| if (at..at + 24).contains(&i) { 0.004 * (PI * (i - at) as f64 / 24.0).sin().powi(3) } else { 0.0 };
+    let planted = walk(start, 1500, 1.1, 2, |i| bump(i, 300) + bump(i, 1000));
+    let profile = MatrixProfile::self_join(&planted, &config).unwrap();
+    let motifs = profile.motifs(config.motifs);
+    let top = &motifs[0];
+    ensure!(motifs.len() == 3, "{} motifs", motifs.len());
+    ensure!(top.index.abs_diff(300) < config.window && top.lag_bars.abs_diff(700) <= 2, "top motif at {} lag {}", top.index, top.lag_bars);
+    ensure!(top.start == planted[top.index].timestamp && top.distance <= motifs[1].distance, "motif order");
+    ensure!(motifs.iter().skip(1).all(|m| m.index.abs_diff(top.index) > profile.exclusion_zone), "motifs overlap");
+    println!("   ✅ Planted shape found at bar {} repeating {} bars later ({:.2} apart)", top.index, top.lag_bars, top.distance);
+
+    // Test 3: a zigzag planted once is the top discord
+    println!("📊 Test 3: Discords");
+    let odd = walk(start, 1500, 1.1, 3, |i| if (800..824).contains(&i) { zigzag(i, 0.001) } else { 0.0 });
+    let profile = MatrixProfile::self_join(&odd, &config).unwrap();
+    let found = profile.discords(config.discords);
+    ensure!(found.len() == 3 && found[0].distance >= found[1].distance, "discord order");
+    ensure!(found[0].index.abs_diff(800) < config.window, "top discord at {}", found[0].index);
+    ensure!(found.iter().skip(1).all(|d| d.index.abs_diff(found[0].index) > profile.exclusion_zone), "discords overlap");
+    println!("   ✅ Zigzag is the top discord at bar {} ({:.2} from its nearest match)", found[0].index, found[0].distance);
+
+    // Test 4: discords as novel patterns
+    println!("📊 Test 4: Anomaly detector");
+    let history = walk(start, 1500, 1.1, 4, |_| 0.0);
+    let live_start = history.last().unwrap().timestamp + Duration::hours(1);
+    let live = walk(live_start, 200, history.last().unwrap().close, 5, |i| if (120..144).contains(&i) { zigzag(i, 0.001) } else { 0.0 });
+    let zigzag_window = live[120].timestamp..live[143 + config.window].timestamp;
+
+    let mut plain = TemporalAnomalyDetector::new(Vec::new(), Vec::new(), &history, AnomalyDetectionConfig::default())?;
+    ensure!(discords(&plain.detect_anomalies(&live).await?).is_empty(), "discords reported without a matrix profile config");
+
+    let detection = AnomalyDetectionConfig { matrix_profile: Some(config.clone()), ..AnomalyDetectionConfig::default() };
+    let mut detector = TemporalAnomalyDetector::new(Vec::new(), Vec::new(), &history, detection)?;
+    let anomalies = detector.detect_anomalies(&live).await?;
+    let found = discords(&anomalies);
+    let (inside, outside): (Vec<&&DetectedAnomaly>, Vec<&&DetectedAnomaly>) = found.iter().partition(|a| zigzag_window.contains(&a.timestamp));
+    ensure!(!inside.is_empty(), "the zigzag was not reported as a discord");
+    ensure!(inside.len() <= 2, "overlapping discord windows reported {} times", inside.len());
+    ensure!(outside.len() <= 3, "{} discords in ordinary bars", outside.len());
+    let first = inside[0];
+    ensure!(first.market_context.recent_events.iter().any(|e| e.starts_with("Closest historical 24-bar shape")), "nearest match missing");
+    ensure!(first.trading_signal.is_none() && first.confidence >= 0.99, "discord confidence {}", first.confidence);
+    println!("   ✅ Zigzag reported as {:?} discord at {} ({} elsewhere in {} bars)",
+             first.severity, first.timestamp.format("%Y-%m-%d %H:%M"), outside.len(), live.len());
+
+    println!();
+    println!("🎉 All matrix profile tests passed");
+    Ok(())
+}
@@ -0,0 +1,77 @@
+//! # Missing Data Test
+//!
+//! Check that a missing history fails instead of falling back to demo data when
+//! stdin is not a terminal, and that a pair trading on demo history opens no positions
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, Utc};
+use std::collections::HashMap;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use forex_pattern_reconstruction::data::{DataConfig, ForexDataManager, ForexDataPoint, HistoricalSource, MissingDataPolicy};
+use forex_pattern_reconstruction::laplacian_rl::TradingAction;
+use forex_pattern_reconstruction::multi_currency::MultiCurrencyManager;
+use forex_pattern_reconstruction::trading_windows::TradingWindowsConfig;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    // The prompt policy only fails without a terminal, so run detached from it
+    if std::io::stdin().is_terminal() {
+        let status = Command::new(std::env::current_exe()?).stdin(Stdio::null()).status()?;
+        std::process::exit(status.code().unwrap_or(1));
+    }
+
+    println!("🔬 MISSING DATA TEST");
+    println!("====================");
+    println!();
+
+    let missing = std::env::temp_dir().join(format!("missing-data-test-{}", std::process::id()));
+
+    // Test 1: the default policy refuses to invent history without a terminal to ask on
+    println!("📊 Test 1: Default policy without a terminal");
+    ensure!(DataConfig::default().missing_data_policy == MissingDataPolicy::Prompt, "prompt is the default policy");
+    let mut manager = ForexDataManager::new(DataConfig::default())?;
+    let error = match manager.load_data_or_fallback(&missing, "EURUSD", "1D").await {
+        Ok((_, source)) => anyhow::bail!("missing data resolved to {:?} without a terminal", source),
+        Err(e) => format!("{:#}", e),
+    };
+    ensure!(error.contains("not a terminal"), "error does not say why: {}", error);
+    println!("   ✅ {}", error.lines().next().unwrap_or_default());
+
+    // Test 2: demo and feed-only data are still available when asked for
+    println!("📊 Test 2: Explicit policies");
+    manager.set_missing_data_policy(MissingDataPolicy::Demo);
+    let (data, source) = manager.load_data_or_fallback(&missing, "EURUSD", "1D").await?;
+    ensure!(source == HistoricalSource::Demo && !data.is_empty(), "demo policy gave {:?}", source);
+    manager.set_missing_data_policy(MissingDataPolicy::FeedOnly);
+    let (data, source) = manager.load_data_or_fallback(&missing, "EURUSD", "1D").await?;
+    ensure!(source == HistoricalSource::FeedOnly && data.is_empty(), "feed-only policy gave {:?}", source);
+    println!("   ✅ Demo and feed-only on request");
+
+    // Test 3: a pair whose history is demo data opens no positions
+    println!("📊 Test 3: Demo history blocks entries");
+    let mut trader = MultiCurrencyManager::new().with_trading_windows(TradingWindowsConfig::unrestricted());
+    trader.initialize_major_pairs().await?;
+    trader.active_pairs.retain(|pair| pair == "EURUSD");
+    {
+        let mut pairs = trader.pairs.write().await;
+        pairs.retain(|pair, _| pair == "EURUSD");
+        let state = pairs.get_mut("EURUSD").expect("pair initialized");
+        let close = 1.1000;
+        state.historical_data = vec![ForexDataPoint { timestamp: Utc::now() - Duration::hours(1), open: close, high: close, low: close, close, volume: None }];
+        state.historical_source = Some(HistoricalSource::Demo);
+    }
+    let entries = HashMap::from([("EURUSD".to_string(), vec![TradingAction::Buy { size: 1 }, TradingAction::Sell { size: 1 }])]);
+    trader.execute_actions(&entries).await;
+    ensure!(trader.portfolio_snapshot().await.positions.is_empty(), "entries on demo history must be refused");
+    trader.pairs.write().await.get_mut("EURUSD").unwrap().historical_source = Some(HistoricalSource::Files(PathBuf::from("EURUSD.csv")));
+    trader.execute_actions(&HashMap::from([("EURUSD".to_string(), vec![TradingAction::Buy { size: 1 }])])).await;
+    ensure!(trader.portfolio_snapshot().await.positions.len() == 1, "entries on file history should fill");
+    println!("   ✅ Entries refused on demo history, filled on file history");
+
+    println!();
+    println!("🎉 All missing data tests passed");
+    Ok(())
+}
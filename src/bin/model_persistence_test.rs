@@ -0,0 +1,100 @@
+//! # Model Persistence Test
+//!
+//! Check a pair's trained state survives a save and load: the symmetries,
+//! cycles, synthetic data, Q-table and calibration come back without
+//! re-analysis, and models of other pairs or layout versions are refused
+
+use anyhow::{ensure, Result};
+use chrono::{TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use forex_pattern_reconstruction::laplacian_rl::{QTableSnapshot, QValue, TradingAction};
+use forex_pattern_reconstruction::multi_currency::model::{PairModel, MODEL_VERSION};
+use forex_pattern_reconstruction::multi_currency::{CurrencyPairConfig, CurrencyPairState};
+use forex_pattern_reconstruction::synthetic::fixtures::cyclic_daily;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 MODEL PERSISTENCE TEST");
+    println!("=========================");
+    println!();
+
+    let dir = std::env::temp_dir().join(format!("model-persistence-test-{}", std::process::id()));
+    let mut rng = StdRng::seed_from_u64(5);
+    let start = Utc.with_ymd_and_hms(2021, 1, 4, 0, 0, 0).unwrap();
+    let data = cyclic_daily(start, 500, &mut rng);
+
+    // Test 1: a trained pair's state round-trips through a file
+    println!("📊 Test 1: save and load");
+    let mut trained = CurrencyPairState::new(CurrencyPairConfig::default()).await?;
+    trained.historical_data = data.clone();
+    trained.reanalyze().await?;
+    trained.rl_agent.restore_q_table(QTableSnapshot {
+        exploration_rate: 0.03,
+        entries: vec![
+            QValue { state_id: "s1".to_string(), action: TradingAction::Buy { size: 10 }, value: 0.7 },
+            QValue { state_id: "s0".to_string(), action: TradingAction::Hold, value: -0.2 },
+        ],
+        network: None,
+        graph: None,
+        replay: None,
+    });
+    let model = trained.model();
+    ensure!(model.history_bars == 500 && model.history_end == Some(data[499].timestamp), "trained on the history");
+    ensure!(model.q_table.entries[0].state_id == "s0", "Q-values are saved in state order");
+    let path = model.save(&dir)?;
+    ensure!(path == PairModel::path(&dir, "eurusd") && path.exists(), "saved as {}", path.display());
+    ensure!(PairModel::load(&dir, "GBPUSD")?.is_none(), "no model for a pair never saved");
+    let loaded = PairModel::load(&dir, "EURUSD")?.ok_or_else(|| anyhow::anyhow!("model saved"))?;
+    ensure!(loaded.symmetries.len() == model.symmetries.len() && loaded.cycles.len() == model.cycles.len(), "symmetries and cycles kept");
+    ensure!(loaded.synthetic_data.len() == model.synthetic_data.len() && loaded.q_table.entries.len() == 2, "synthetic data and Q-table kept");
+    println!("   ✅ {} symmetries, {} cycles, {} synthetic points saved to {}",
+             loaded.symmetries.len(), loaded.cycles.len(), loaded.synthetic_data.len(), path.display());
+
+    // Test 2: a fresh pair restores the model without re-analysis
+    println!("📊 Test 2: restore");
+    let mut restored = CurrencyPairState::new(CurrencyPairConfig::default()).await?;
+    restored.historical_data = data.clone();
+    restored.restore_model(loaded)?;
+    ensure!(restored.symmetries.len() == trained.symmetries.len() && restored.cycles.len() == trained.cycles.len(), "symmetries and cycles restored");
+    ensure!(restored.synthetic_data.len() == trained.synthetic_data.len()
+            && restored.synthetic_data.first().map(|point| point.data_point.timestamp) == trained.synthetic_data.first().map(|point| point.data_point.timestamp),
+            "synthetic data restored as generated, not regenerated");
+    ensure!((restored.rl_agent.exploration_rate() - 0.03).abs() < 1e-12, "exploration rate restored");
+    let snapshot = restored.rl_agent.q_table_snapshot();
+    ensure!(snapshot.entries.len() == 2 && snapshot.entries[1].value == 0.7, "Q-values restored: {:?}", snapshot.entries);
+    let threshold = |state: &CurrencyPairState| state.anomaly_detector.calibration().map(|calibration| calibration.sensitivity_threshold);
+    ensure!(threshold(&restored) == threshold(&trained), "calibration restored");
+    ensure!(restored.drift_monitor.reference_end() == Some(data[499].timestamp), "drift reference refitted to the history");
+    println!("   ✅ Restored with sensitivity {:?}", threshold(&restored));
+
+    // Test 3: a saved calibration wins over the one fitted to the history
+    println!("📊 Test 3: saved calibration");
+    let mut model = trained.model();
+    let calibration = model.calibration.as_mut().ok_or_else(|| anyhow::anyhow!("the trained pair is calibrated"))?;
+    calibration.sensitivity_threshold = 0.42;
+    let mut recalibrated = CurrencyPairState::new(CurrencyPairConfig::default()).await?;
+    recalibrated.historical_data = data.clone();
+    recalibrated.restore_model(model)?;
+    ensure!(threshold(&recalibrated) == Some(0.42), "the saved threshold is used: {:?}", threshold(&recalibrated));
+    println!("   ✅ Sensitivity 0.42 kept");
+
+    // Test 4: models of other pairs or versions are refused
+    println!("📊 Test 4: mismatches");
+    let mut foreign = CurrencyPairState::new(CurrencyPairConfig { symbol: "GBPUSD".to_string(), ..CurrencyPairConfig::default() }).await?;
+    ensure!(foreign.restore_model(trained.model()).is_err(), "a EURUSD model cannot initialize GBPUSD");
+    std::fs::copy(&path, PairModel::path(&dir, "GBPUSD"))?;
+    ensure!(PairModel::load(&dir, "GBPUSD").is_err(), "a renamed file is refused");
+    let outdated = PairModel { version: MODEL_VERSION + 1, ..trained.model() };
+    outdated.save(&dir)?;
+    let error = PairModel::load(&dir, "EURUSD").err().ok_or_else(|| anyhow::anyhow!("an unknown version is refused"))?;
+    std::fs::write(PairModel::path(&dir, "USDJPY"), "{ not json")?;
+    ensure!(PairModel::load(&dir, "USDJPY").is_err(), "a corrupt file is refused");
+    println!("   ✅ {}", error);
+
+    std::fs::remove_dir_all(&dir)?;
+    println!();
+    println!("🎉 All model persistence tests passed");
+    Ok(())
+}
@@ -0,0 +1,143 @@
+//! # Momentum Shock Test
+//!
+//! Build hourly history of small random moves, then walk the price steadily in one
+//! direction with ordinary bar ranges. The volatility detector has
+//! nothing to see; the momentum detector must flag the move with the right sign,
+//! and the RL layer must offer a matching action set.
+
+use anyhow::{ensure, Result};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::collections::HashSet;
+
+use forex_pattern_reconstruction::anomaly::{
+    AnomalyDetectionConfig, AnomalySeverity, AnomalyType, DetectedAnomaly, MarketContext, TemporalAnomalyDetector,
+};
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::laplacian_rl::{LaplacianQLearningAgent, LaplacianQLearningConfig, TradingAction};
+use forex_pattern_reconstruction::stats::{MomentumSurface, MomentumSurfaceConfig};
+use forex_pattern_reconstruction::synthetic::fixtures::hourly_walk;
+
+fn bar(timestamp: DateTime<Utc>, open: f64, close: f64, range: f64) -> ForexDataPoint {
+    let mid = (open + close) / 2.0;
+    ForexDataPoint { timestamp, open, high: mid + range / 2.0, low: mid - range / 2.0, close, volume: None }
+}
+
+/// `bars` hourly bars after `start` moving `step` per bar with a typical 10-pip range
+fn trend(start_time: DateTime<Utc>, start_price: f64, step: f64, bars: i64) -> Vec<ForexDataPoint> {
+    (1..=bars).map(|h| {
+        let open = start_price + step * (h - 1) as f64;
+        bar(start_time + Duration::hours(h), open, open + step, 0.0010)
+    }).collect()
+}
+
+fn shock(cumulative_return: f64, severity: AnomalySeverity) -> DetectedAnomaly {
+    DetectedAnomaly {
+        id: "momentum_anomaly_test".into(),
+        timestamp: Utc::now(),
+        anomaly_type: AnomalyType::MomentumShock { lookback_bars: 12, cumulative_return, threshold_return: 0.002, quantile: 0.99 },
+        severity,
+        confidence: 0.99,
+        deviation_magnitude: cumulative_return.abs() - 0.002,
+        affected_symmetries: Vec::new(),
+        affected_cycles: Vec::new(),
+        market_context: MarketContext {
+            session: "London".to_string(),
+            volatility_regime: "Normal".to_string(),
+            trend_direction: "Sideways".to_string(),
+            recent_events: Vec::new(),
+            event_proximity: 0.0,
+        },
+        trading_signal: None,
+    }
+}
+
+/// Every action the agent offers for `anomaly` when exploring
+fn action_set(agent: &LaplacianQLearningAgent, anomaly: &DetectedAnomaly) -> Result<HashSet<TradingAction>> {
+    let mut actions = HashSet::new();
+    for _ in 0..500 {
+        actions.insert(agent.choose_action("s_momentum", anomaly)?);
+    }
+    Ok(actions)
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 MOMENTUM SHOCK TEST");
+    println!("======================");
+    println!();
+
+    let mut rng = StdRng::seed_from_u64(17);
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let historical = hourly_walk(&mut rng, start, 24 * 7 * 12);
+
+    // Test 1: the surface holds a time-conditional distribution of 12-bar returns
+    println!("📊 Test 1: momentum surface");
+    let surface = MomentumSurface::from_history(&historical, MomentumSurfaceConfig::default());
+    let probe = historical[500].timestamp;
+    let p50 = surface.quantile(probe, 0.5).unwrap_or(0.0);
+    let p99 = surface.quantile(probe, 0.99).unwrap_or(0.0);
+    println!("   {} returns, median |r| {:.5}, 99th percentile {:.5}", surface.samples(), p50, p99);
+    ensure!(surface.samples() == historical.len() - 12, "one return per complete 12-bar window");
+    ensure!(p50 > 0.0 && p99 > p50, "quantiles must increase");
+    ensure!(surface.rank(probe, p99 * 2.0) == 1.0, "a move beyond every sample ranks at 1.0");
+    println!("   ✅ Surface quantiles are ordered");
+
+    let mut detector = TemporalAnomalyDetector::new(Vec::new(), Vec::new(), &historical, AnomalyDetectionConfig::default())?;
+
+    // Test 2: ordinary random-walk bars do not read as momentum shocks
+    println!("📊 Test 2: ordinary moves");
+    let quiet_start = historical.last().unwrap().timestamp;
+    let quiet = hourly_walk(&mut rng, quiet_start + Duration::hours(1), 24 * 7);
+    let quiet_anomalies = detector.detect_anomalies(&quiet).await?;
+    let quiet_shocks = quiet_anomalies.iter().filter(|a| matches!(a.anomaly_type, AnomalyType::MomentumShock { .. })).count();
+    println!("   {} momentum shocks in {} ordinary bars", quiet_shocks, 24 * 7);
+    ensure!(quiet_shocks <= 8, "ordinary moves should rarely exceed the 99th percentile, got {}", quiet_shocks);
+    println!("   ✅ Ordinary moves stay quiet");
+
+    // Test 3: a steady 12-bar sell-off with normal ranges is a shock, not a volatility spike
+    println!("📊 Test 3: directional shock with normal ranges");
+    let last = historical.last().unwrap();
+    let mut bars: Vec<ForexDataPoint> = historical[historical.len() - 20..].to_vec();
+    bars.extend(trend(last.timestamp, last.close, -0.0004, 12));
+    let shock_time = bars.last().unwrap().timestamp;
+    let anomalies = detector.detect_anomalies(&bars).await?;
+    let spikes = anomalies.iter()
+        .filter(|a| a.timestamp > last.timestamp && matches!(a.anomaly_type, AnomalyType::VolatilitySpike { .. }))
+        .count();
+    let detected = anomalies.iter().find(|a| a.timestamp == shock_time && matches!(a.anomaly_type, AnomalyType::MomentumShock { .. }));
+    ensure!(spikes == 0, "typical trend-bar ranges must not trigger volatility spikes, got {}", spikes);
+    let Some(detected) = detected else {
+        anyhow::bail!("the 48-pip sell-off was not flagged as a momentum shock");
+    };
+    let AnomalyType::MomentumShock { cumulative_return, threshold_return, lookback_bars, .. } = detected.anomaly_type else {
+        unreachable!();
+    };
+    println!("   {} bars returned {:+.5} against a {:.5} threshold: {:?}, confidence {:.3}",
+             lookback_bars, cumulative_return, threshold_return, detected.severity, detected.confidence);
+    ensure!(cumulative_return < 0.0, "the shock must carry the direction of the move");
+    ensure!(matches!(detected.severity, AnomalySeverity::High | AnomalySeverity::Critical), "a move far beyond the threshold is severe");
+    ensure!(detected.trading_signal.as_ref().is_some_and(|s| s.signal_type == "Sell"), "the signal follows the move");
+    ensure!(detected.anomaly_type.name() == "MomentumShock", "variant name is used by suppressions and reports");
+    println!("   ✅ Sell-off flagged as a downward momentum shock");
+
+    // Test 4: the RL layer follows or steps aside, and only fades critical shocks
+    println!("📊 Test 4: RL action set");
+    let mut agent = LaplacianQLearningAgent::new(LaplacianQLearningConfig { exploration_rate: 1.0, ..LaplacianQLearningConfig::default() })?;
+    let medium = action_set(&agent, &shock(-0.003, AnomalySeverity::Medium))?;
+    let critical = action_set(&agent, &shock(0.006, AnomalySeverity::Critical))?;
+    println!("   medium down-shock: {:?}", medium);
+    println!("   critical up-shock: {:?}", critical);
+    ensure!(medium == HashSet::from([TradingAction::Hold, TradingAction::Sell { size: 10 }, TradingAction::ClosePosition]),
+            "a medium down-shock offers hold, follow or close");
+    ensure!(critical.contains(&TradingAction::Buy { size: 10 }) && critical.contains(&TradingAction::Sell { size: 10 }),
+            "a critical shock may also be faded");
+    let state = agent.anomaly_to_state(&shock(-0.003, AnomalySeverity::Medium), last)?;
+    ensure!(state.contains("_-1.50_"), "the state carries the signed threshold ratio, got {}", state);
+    println!("   ✅ Action set matches the shock direction");
+
+    println!();
+    println!("🎉 All momentum shock tests passed");
+    Ok(())
+}
@@ -0,0 +1,96 @@
+//! # Multi-Pair Dashboard Test
+//!
+//! Check that the dashboards' view of a shared multi-currency manager shows
+//! every pair in order with its real status, bars and anomalies, that the
+//! portfolio panel sums the pairs, and that the dashboard switches between the
+//! manager's pairs instead of analyzing a pair of its own
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, TimeZone, Utc};
+use std::sync::Arc;
+
+use forex_pattern_reconstruction::dashboard::multi_pair::{run_market_update, MultiPairView, PairStatus, PANEL_ANOMALIES, PANEL_BARS};
+use forex_pattern_reconstruction::dashboard::DashboardApp;
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::multi_currency::MultiCurrencyManager;
+
+const PAIRS: [&str; 3] = ["EURUSD", "GBPUSD", "USDJPY"];
+
+/// Daily bars from 2024-01-01 drifting up from `base`
+fn bars(days: i64, base: f64) -> Vec<ForexDataPoint> {
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    (0..days)
+        .map(|day| {
+            let close = base + day as f64 * 1e-4 + (day as f64 * 0.3).sin() * 2e-3;
+            ForexDataPoint { timestamp: start + Duration::days(day), open: close, high: close + 1e-3, low: close - 1e-3, close, volume: None }
+        })
+        .collect()
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 MULTI-PAIR DASHBOARD TEST");
+    println!("============================");
+    println!();
+
+    let mut manager = MultiCurrencyManager::new();
+    manager.initialize_pairs(&PAIRS.map(String::from)).await?;
+    {
+        let mut pairs_map = manager.pairs.write().await;
+        for (i, symbol) in PAIRS.iter().enumerate() {
+            let state = pairs_map.get_mut(*symbol).unwrap();
+            state.historical_data = bars(600, 1.0 + i as f64 * 0.2);
+            state.backfill_config.timeframe = "D1".to_string();
+            state.is_active = true;
+            state.warm = true;
+        }
+        pairs_map.get_mut("GBPUSD").unwrap().paused = true;
+    }
+    let manager = Arc::new(manager);
+
+    // Test 1: one panel per pair, in the manager's order, with its real status
+    println!("📊 Test 1: Pair panels");
+    let view = MultiPairView::capture(&manager).await;
+    ensure!(view.symbols() == PAIRS, "pairs {:?}", view.symbols());
+    let statuses: Vec<PairStatus> = view.pairs.iter().map(|pair| pair.status).collect();
+    ensure!(statuses == [PairStatus::Trading, PairStatus::Paused, PairStatus::Trading], "statuses {:?}", statuses);
+    let eurusd = view.pair("EURUSD").unwrap();
+    ensure!(eurusd.recent_bars.len() == PANEL_BARS, "{} bars kept", eurusd.recent_bars.len());
+    ensure!(eurusd.recent_bars.last().unwrap().close == bars(600, 1.0)[599].close, "newest bar last");
+    let series = eurusd.close_series(100);
+    ensure!(series.len() == 100 && series[0].0 == 0.0 && series[99].1 == eurusd.recent_bars[PANEL_BARS - 1].close, "close series");
+    ensure!(view.portfolio.trading_pairs == 2, "{} trading pairs", view.portfolio.trading_pairs);
+    println!("   ✅ {} pairs, statuses {:?}", view.pairs.len(), statuses);
+
+    // Test 2: a market update runs on the manager, and the portfolio sums the pairs
+    println!("📊 Test 2: Market update and portfolio");
+    run_market_update(&manager).await?;
+    let view = MultiPairView::capture(&manager).await;
+    let anomalies: u64 = view.pairs.iter().map(|pair| pair.performance.anomalies_detected).sum();
+    let trades: u64 = view.pairs.iter().map(|pair| pair.performance.total_trades).sum();
+    ensure!(view.portfolio.anomalies_detected == anomalies && view.portfolio.total_trades == trades, "portfolio sums");
+    ensure!(view.pairs.iter().all(|pair| pair.recent_anomalies.len() <= PANEL_ANOMALIES), "anomalies kept per pair");
+    let paused = view.pair("GBPUSD").unwrap();
+    ensure!(paused.performance.total_trades == 0, "a paused pair does not trade");
+    ensure!(view.portfolio.win_rate() >= 0.0 && view.portfolio.win_rate() <= 100.0, "win rate {}", view.portfolio.win_rate());
+    println!("   ✅ {} anomalies, {} trades, equity {:.2}", anomalies, trades, view.portfolio.snapshot.equity);
+
+    // Test 3: the dashboard shows the shared manager and switches between its pairs
+    println!("📊 Test 3: Dashboard over the shared manager");
+    let mut app = DashboardApp::with_manager(manager.clone()).await?;
+    app.initialize().await?;
+    ensure!(Arc::ptr_eq(app.manager(), &manager), "dashboard keeps the shared manager");
+    ensure!(app.current_pair() == "EURUSD", "starts on the first pair");
+    app.select_pair(1);
+    ensure!(app.current_pair() == "GBPUSD", "next pair");
+    app.select_pair(-2);
+    ensure!(app.current_pair() == "USDJPY", "wraps backwards: {}", app.current_pair());
+    app.select_pair(1);
+    ensure!(app.current_pair() == "EURUSD", "wraps forwards: {}", app.current_pair());
+    ensure!(app.view().symbols() == PAIRS, "view of the manager's pairs");
+    println!("   ✅ Switched through {:?}", app.view().symbols());
+
+    println!();
+    println!("🎉 All multi-pair dashboard tests passed");
+    Ok(())
+}
@@ -0,0 +1,93 @@
+//! # Multi-Timeframe Test
+//!
+//! Build 60 days of M15 bars carrying a daily and a 5-day cycle, resample them to
+//! H1, H4 and D1, and check the OHLCV folding, the bucket alignment, and that each
+//! cycle is confirmed on every timeframe fine enough to see it
+
+use anyhow::{ensure, Result};
+use chrono::{Datelike, Duration, TimeZone, Timelike, Utc, Weekday};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::f64::consts::PI;
+
+use forex_pattern_reconstruction::core::{EngineConfig, TimeSymmetricEngine};
+use forex_pattern_reconstruction::data::timeframe::TimeframeAggregator;
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::patterns::{find_cycle_confluence, PatternConfig, PatternRecognizer};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 MULTI-TIMEFRAME TEST");
+    println!("=======================");
+    println!();
+
+    let mut rng = StdRng::seed_from_u64(31);
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let m15: Vec<ForexDataPoint> = (0..60 * 96).map(|i| {
+        let timestamp = start + Duration::minutes(15 * i);
+        let hours = timestamp.timestamp() as f64 / 3600.0;
+        let close = 1.1 * (1.0 + 0.002 * (2.0 * PI * hours / 24.0).sin() + 0.004 * (2.0 * PI * hours / 120.0).sin())
+            + rng.gen_range(-0.00003..0.00003);
+        ForexDataPoint { timestamp, open: close, high: close + 0.0001, low: close - 0.0001, close, volume: Some(10.0) }
+    }).collect();
+
+    // Test 1: OHLCV folding and alignment
+    println!("📊 Test 1: resampling");
+    let h4 = TimeframeAggregator::new("H4")?.resample(&m15)?;
+    ensure!(h4.len() == 60 * 6, "expected 360 H4 bars, got {}", h4.len());
+    let first = &m15[..16];
+    ensure!(h4[0].timestamp == start && h4[1].timestamp == start + Duration::hours(4), "H4 bars start on the 4-hour grid");
+    ensure!(h4[0].open == first[0].open && h4[0].close == first[15].close, "open is the first, close the last source bar");
+    ensure!(h4[0].high == first.iter().map(|p| p.high).fold(f64::MIN, f64::max)
+            && h4[0].low == first.iter().map(|p| p.low).fold(f64::MAX, f64::min), "high and low span the bucket");
+    ensure!(h4[0].volume == Some(160.0), "volume is summed, got {:?}", h4[0].volume);
+    let weekly = TimeframeAggregator::new("W1")?.resample(&m15[96 * 3..])?;
+    ensure!(weekly.iter().all(|bar| bar.timestamp.weekday() == Weekday::Mon && bar.timestamp.hour() == 0), "weekly bars start on Monday");
+    let two_hours = TimeframeAggregator::with_interval(Duration::hours(2))?.resample(&m15)?;
+    ensure!(two_hours.len() == 60 * 12, "custom intervals work too");
+    ensure!(TimeframeAggregator::new("M5")?.resample(&m15).is_err(), "cannot resample to a finer timeframe");
+    ensure!(TimeframeAggregator::new("H7").is_err(), "unknown timeframe names are rejected");
+    let mut gapped = m15.clone();
+    gapped.retain(|p| p.timestamp.weekday() != Weekday::Sat);
+    let daily = TimeframeAggregator::new("D1")?.resample(&gapped)?;
+    ensure!(daily.len() < 60 && daily.iter().all(|bar| bar.timestamp.weekday() != Weekday::Sat), "empty buckets make no bar");
+    println!("   ✅ {} H4, {} weekly, {} daily bars with gaps", h4.len(), weekly.len(), daily.len());
+
+    // Test 2: cycles per timeframe and their confluence
+    println!("📊 Test 2: cycle confluence");
+    let timeframes: Vec<String> = ["M15", "H1", "H4", "D1"].iter().map(|s| s.to_string()).collect();
+    let mut recognizer = PatternRecognizer::new(PatternConfig::default())?;
+    let results = recognizer.detect_cycles_multi_timeframe(&m15, &timeframes).await?;
+    for tf in &results {
+        let periods: Vec<String> = tf.cycles.iter().map(|c| format!("{:.0}h", tf.period_hours(c))).collect();
+        println!("   {:>3}: {} bars, cycles {:?}", tf.timeframe, tf.bars, periods);
+    }
+    let confluence = find_cycle_confluence(&results, 0.1);
+    for group in &confluence {
+        println!("   🔗 {:.1}h on {:?}, score {:.3}", group.period_hours, group.timeframes, group.score);
+    }
+    let daily_group = confluence.iter().find(|g| (g.period_hours - 24.0).abs() < 2.0);
+    let five_day = confluence.iter().find(|g| (g.period_hours - 120.0).abs() < 10.0);
+    ensure!(daily_group.is_some_and(|g| ["M15", "H1", "H4"].iter().all(|tf| g.timeframes.iter().any(|t| t == tf))),
+            "the daily cycle should be confirmed on M15, H1 and H4");
+    ensure!(daily_group.is_some_and(|g| !g.timeframes.iter().any(|t| t == "D1")), "D1 cannot resolve a daily cycle");
+    ensure!(five_day.is_some_and(|g| g.timeframes.len() >= 3 && g.timeframes.iter().any(|t| t == "D1")),
+            "the 5-day cycle should be confirmed on D1 and the intraday timeframes");
+    ensure!(confluence.iter().all(|g| g.members.len() == g.timeframes.len()), "one member per timeframe");
+    println!("   ✅ Daily and 5-day cycles confirmed across timeframes");
+
+    // Test 3: symmetries per timeframe carry the timeframe in their id
+    println!("📊 Test 3: symmetries per timeframe");
+    let mut engine = TimeSymmetricEngine::new(EngineConfig::default())?;
+    engine.initialize().await?;
+    let symmetries = engine.extract_temporal_symmetries_multi_timeframe(&m15, &timeframes[1..]).await?;
+    ensure!(symmetries.iter().map(|s| s.timeframe.as_str()).eq(["H1", "H4", "D1"]), "one result per timeframe, in order");
+    ensure!(symmetries.iter().all(|tf| tf.symmetries.iter().all(|s| s.id.ends_with(&format!("@{}", tf.timeframe)))),
+            "symmetry ids name their timeframe");
+    println!("   ✅ {} symmetries across {} timeframes",
+             symmetries.iter().map(|s| s.symmetries.len()).sum::<usize>(), symmetries.len());
+
+    println!();
+    println!("🎉 All multi-timeframe tests passed");
+    Ok(())
+}
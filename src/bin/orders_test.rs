@@ -0,0 +1,123 @@
+//! # Orders Test
+//!
+//! Check the portfolio records every submitted action as an order with what it
+//! requested and filled, cuts and refusals with their reason, and that the
+//! multi-currency manager's refused and filled actions reach the blotter and the
+//! portfolio snapshot
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, Utc};
+use std::collections::HashMap;
+
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::laplacian_rl::TradingAction;
+use forex_pattern_reconstruction::multi_currency::MultiCurrencyManager;
+use forex_pattern_reconstruction::portfolio::margin::MarginConfig;
+use forex_pattern_reconstruction::portfolio::orders::{Order, OrderBook, OrderStatus};
+use forex_pattern_reconstruction::portfolio::{Portfolio, PortfolioConfig};
+use forex_pattern_reconstruction::trading_windows::TradingWindowsConfig;
+
+fn order(symbol: &str, units: f64) -> Order {
+    Order {
+        id: 0,
+        timestamp: Utc::now(),
+        symbol: symbol.to_string(),
+        action: TradingAction::Buy { size: 1 },
+        requested_units: units,
+        filled_units: units,
+        price: 1.1,
+        status: OrderStatus::Filled,
+        reason: None,
+        realized_pnl: 0.0,
+        position_units: units,
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 ORDERS TEST");
+    println!("==============");
+    println!();
+
+    let now = Utc::now();
+    let mut prices = HashMap::from([("EURUSD".to_string(), 1.1), ("GBPUSD".to_string(), 1.25)]);
+
+    // Test 1: fills are recorded with their P&L and resulting position
+    println!("📊 Test 1: filled orders");
+    let mut portfolio = Portfolio::new(PortfolioConfig::default());
+    let buy = portfolio.submit("EURUSD", &TradingAction::Buy { size: 10 }, 1.1, &prices, now, Some("rl")).expect("an order");
+    ensure!(buy.id == 1 && buy.status == OrderStatus::Filled && buy.filled_units == 10_000.0 && buy.position_units == 10_000.0, "{:?}", buy);
+    ensure!(buy.reason.as_deref() == Some("rl"), "the placing source is kept");
+    prices.insert("EURUSD".to_string(), 1.11);
+    let sell = portfolio.submit("EURUSD", &TradingAction::Sell { size: 4 }, 1.11, &prices, now, None).expect("an order");
+    ensure!(sell.id == 2 && sell.filled_units == -4_000.0 && (sell.realized_pnl - 40.0).abs() < 1e-9 && sell.position_units == 6_000.0, "{:?}", sell);
+    let close = portfolio.submit("EURUSD", &TradingAction::ClosePosition, 1.11, &prices, now, None).expect("an order");
+    ensure!(close.requested_units == -6_000.0 && close.position_units == 0.0 && portfolio.positions().is_empty(), "{:?}", close);
+    ensure!(portfolio.submit("EURUSD", &TradingAction::ClosePosition, 1.11, &prices, now, None).is_none(), "closing a flat pair is no order");
+    ensure!(portfolio.submit("EURUSD", &TradingAction::Hold, 1.11, &prices, now, None).is_none(), "holding is no order");
+    ensure!(portfolio.reject("EURUSD", &TradingAction::Hold, "risk", now).is_none(), "holds are never refused");
+    println!("   ✅ {}", sell.summary());
+
+    // Test 2: leverage cuts are partial fills, refusals are rejections
+    println!("📊 Test 2: cuts and refusals");
+    let config = PortfolioConfig { margin: MarginConfig::enforced(), ..PortfolioConfig::default() };
+    let mut margined = Portfolio::new(config);
+    let big = margined.submit("GBPUSD", &TradingAction::Buy { size: 10_000 }, 1.25, &prices, now, None).expect("an order");
+    ensure!(big.status == OrderStatus::PartiallyFilled && big.reason.as_deref() == Some("leverage limit"), "{:?}", big);
+    ensure!(big.filled_units > 0.0 && big.filled_units < big.requested_units, "filled {} of {}", big.filled_units, big.requested_units);
+    let more = margined.submit("GBPUSD", &TradingAction::Buy { size: 10 }, 1.25, &prices, now, None).expect("an order");
+    ensure!(more.status == OrderStatus::Rejected && more.filled_units == 0.0, "no free margin left: {:?}", more);
+    let refused = margined.reject("EURUSD", &TradingAction::Sell { size: 3 }, "liquidity gap", now).expect("an order");
+    ensure!(refused.requested_units == -3_000.0 && refused.status == OrderStatus::Rejected, "{:?}", refused);
+    let stats = margined.orders().stats();
+    ensure!(stats.submitted == 3 && stats.filled == 0 && stats.partially_filled == 1 && stats.rejected == 2, "{:?}", stats);
+    let snapshot = margined.snapshot(&prices, now);
+    ensure!(snapshot.recent_orders.iter().map(|order| order.id).collect::<Vec<_>>() == [3, 2, 1], "newest first in the snapshot");
+    println!("   ✅ {}", big.summary());
+    println!("   ✅ {}", refused.summary());
+
+    // Test 3: stop-outs and operator flattens are orders too
+    println!("📊 Test 3: forced closes");
+    prices.insert("GBPUSD".to_string(), 1.2);
+    margined.enforce_margin(&prices, now);
+    let forced = margined.orders().recent(1).remove(0);
+    ensure!(forced.reason.as_deref() == Some("margin stop-out") && forced.position_units == 0.0, "{:?}", forced);
+    portfolio.submit("EURUSD", &TradingAction::Buy { size: 1 }, 1.11, &prices, now, None);
+    portfolio.flatten("EURUSD", 1.11, &prices, now);
+    ensure!(portfolio.orders().recent(1)[0].reason.as_deref() == Some("flatten"), "flatten recorded");
+    ensure!(portfolio.orders().for_symbol("EURUSD").len() == 5 && portfolio.orders().for_symbol("GBPUSD").is_empty(), "per-pair orders");
+    println!("   ✅ {}", forced.summary());
+
+    // Test 4: the blotter keeps the latest orders and keeps numbering
+    println!("📊 Test 4: capacity");
+    let mut book = OrderBook::new(3);
+    for i in 0..5 {
+        book.record(order("EURUSD", 1_000.0 * (i + 1) as f64));
+    }
+    let kept: Vec<u64> = book.recent(10).iter().map(|order| order.id).collect();
+    ensure!(kept == [5, 4, 3] && book.stats().submitted == 5, "kept {:?}", kept);
+    println!("   ✅ Kept orders {:?} of 5", kept);
+
+    // Test 5: the manager records what it refuses and fills
+    println!("📊 Test 5: manager blotter");
+    let mut manager = MultiCurrencyManager::new().with_trading_windows(TradingWindowsConfig::unrestricted());
+    manager.initialize_pairs(&["EURUSD".to_string()]).await?;
+    {
+        let mut pairs = manager.pairs.write().await;
+        let state = pairs.get_mut("EURUSD").unwrap();
+        let price = 1.1;
+        state.historical_data = vec![ForexDataPoint { timestamp: Utc::now() - Duration::hours(1), open: price, high: price, low: price, close: price, volume: None }];
+    }
+    manager.execute_actions(&HashMap::from([("EURUSD".to_string(), vec![TradingAction::Buy { size: 2 }, TradingAction::Hold])])).await;
+    manager.pairs.write().await.get_mut("EURUSD").unwrap().last_liquidity_gap = Some(Utc::now());
+    manager.execute_actions(&HashMap::from([("EURUSD".to_string(), vec![TradingAction::Buy { size: 1 }, TradingAction::ClosePosition])])).await;
+    let snapshot = manager.portfolio_snapshot().await;
+    let statuses: Vec<(OrderStatus, Option<&str>)> = snapshot.recent_orders.iter().map(|order| (order.status, order.reason.as_deref())).collect();
+    ensure!(statuses == [(OrderStatus::Filled, None), (OrderStatus::Rejected, Some("liquidity gap")), (OrderStatus::Filled, None)], "{:?}", statuses);
+    ensure!(snapshot.order_stats.submitted == 3 && snapshot.positions.is_empty(), "{:?}", snapshot.order_stats);
+    println!("   ✅ {} orders, {} rejected", snapshot.order_stats.submitted, snapshot.order_stats.rejected);
+
+    println!();
+    println!("🎉 All orders tests passed");
+    Ok(())
+}
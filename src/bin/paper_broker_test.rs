@@ -0,0 +1,180 @@
+//! # Paper Broker Test
+//!
+//! Check that the paper broker fills buys at the ask and sells at the bid, reads
+//! the quote after its latency, caps and partially fills orders, refuses pairs
+//! it has no quote for, follows a tick stream, and that a manager routed through
+//! it books the broker's fills from anomaly signal to realized P&L
+
+use anyhow::{ensure, Result};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration as StdDuration, Instant};
+use tokio::sync::mpsc;
+
+use forex_pattern_reconstruction::backtest::strategy::OrderSide;
+use forex_pattern_reconstruction::broker::paper::{PaperBroker, PaperBrokerConfig};
+use forex_pattern_reconstruction::broker::{ExecutionBackend, MarketOrder};
+use forex_pattern_reconstruction::data::provider::Tick;
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::laplacian_rl::TradingAction;
+use forex_pattern_reconstruction::multi_currency::MultiCurrencyManager;
+use forex_pattern_reconstruction::portfolio::orders::OrderStatus;
+use forex_pattern_reconstruction::trading_windows::TradingWindowsConfig;
+
+fn tick(symbol: &str, bid: f64, ask: f64) -> Tick {
+    Tick { symbol: symbol.to_string(), timestamp: Utc::now(), bid, ask }
+}
+
+fn bar(timestamp: DateTime<Utc>, close: f64) -> ForexDataPoint {
+    ForexDataPoint { timestamp, open: close, high: close + 0.001, low: close - 0.001, close, volume: None }
+}
+
+/// EURUSD-only manager priced at `close`
+async fn priced_manager(manager: MultiCurrencyManager, close: f64) -> Result<MultiCurrencyManager> {
+    let mut manager = manager;
+    manager.initialize_major_pairs().await?;
+    manager.active_pairs.retain(|pair| pair == "EURUSD");
+    {
+        let mut pairs = manager.pairs.write().await;
+        pairs.retain(|pair, _| pair == "EURUSD");
+        let state = pairs.get_mut("EURUSD").expect("pair initialized");
+        state.historical_data = vec![bar(Utc::now() - Duration::hours(1), close)];
+    }
+    Ok(manager)
+}
+
+/// Replace EURUSD's history with one bar closing at `close`
+async fn reprice(manager: &MultiCurrencyManager, close: f64) {
+    let mut pairs = manager.pairs.write().await;
+    let state = pairs.get_mut("EURUSD").expect("pair initialized");
+    state.historical_data = vec![bar(Utc::now() - Duration::minutes(30), close)];
+}
+
+fn actions(action: TradingAction) -> HashMap<String, Vec<TradingAction>> {
+    HashMap::from([("EURUSD".to_string(), vec![action])])
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 PAPER BROKER TEST");
+    println!("====================");
+    println!();
+
+    // Test 1: buys pay the ask, sells receive the bid, or a fixed spread around the mid
+    println!("📊 Test 1: fill prices");
+    let broker = PaperBroker::new(PaperBrokerConfig::default());
+    broker.update_quote(tick("eurusd", 1.1000, 1.1002));
+    let buy = broker.execute(&MarketOrder::new("EURUSD", OrderSide::Buy, 10_000.0)).await?;
+    let sell = broker.execute(&MarketOrder::new("EURUSD", OrderSide::Sell, 10_000.0)).await?;
+    ensure!(buy.price == 1.1002 && sell.price == 1.1000, "buy {} / sell {}", buy.price, sell.price);
+    ensure!(buy.units == 10_000.0 && buy.symbol == "EURUSD" && buy.side == OrderSide::Buy, "buy fill {:?}", buy);
+    ensure!(sell.order_id == buy.order_id + 1 && broker.fills().len() == 2, "sequential order ids");
+    let wide = PaperBroker::new(PaperBrokerConfig { spread_pips: Some(3.0), ..PaperBrokerConfig::default() });
+    wide.update_quote(tick("USDJPY", 150.00, 150.00));
+    let buy = wide.execute(&MarketOrder::new("USDJPY", OrderSide::Buy, 1_000.0)).await?;
+    let sell = wide.execute(&MarketOrder::new("USDJPY", OrderSide::Sell, 1_000.0)).await?;
+    ensure!((buy.price - 150.015).abs() < 1e-9 && (sell.price - 149.985).abs() < 1e-9, "3 JPY pips around the mid: {} / {}", buy.price, sell.price);
+    println!("   ✅ Bid/ask fills and a 3-pip USDJPY spread");
+
+    // Test 2: the quote is read after the latency
+    println!("📊 Test 2: latency");
+    let broker = Arc::new(PaperBroker::new(PaperBrokerConfig { latency_ms: 150, ..PaperBrokerConfig::default() }));
+    broker.update_quote(tick("GBPUSD", 1.2500, 1.2502));
+    let started = Instant::now();
+    let order = MarketOrder::new("GBPUSD", OrderSide::Buy, 1_000.0);
+    let pending = {
+        let broker = Arc::clone(&broker);
+        tokio::spawn(async move { broker.execute(&order).await })
+    };
+    tokio::time::sleep(StdDuration::from_millis(50)).await;
+    broker.update_quote(tick("GBPUSD", 1.2510, 1.2512));
+    let fill = pending.await??;
+    ensure!(started.elapsed() >= StdDuration::from_millis(150), "filled after {:?}", started.elapsed());
+    ensure!(fill.price == 1.2512, "price moved during the latency: filled at {}", fill.price);
+    let stale = PaperBroker::new(PaperBrokerConfig { max_quote_age_ms: Some(1_000), ..PaperBrokerConfig::default() });
+    stale.update_quote(Tick { timestamp: Utc::now() - Duration::seconds(5), ..tick("GBPUSD", 1.25, 1.2502) });
+    ensure!(stale.execute(&MarketOrder::new("GBPUSD", OrderSide::Buy, 1_000.0)).await.is_err(), "5s-old quote accepted");
+    println!("   ✅ Filled at the quote 150ms later; stale quotes refused");
+
+    // Test 3: liquidity cap and partial fills
+    println!("📊 Test 3: partial fills");
+    let capped = PaperBroker::new(PaperBrokerConfig { max_fill_units: Some(25_000.0), ..PaperBrokerConfig::default() });
+    capped.update_quote(tick("EURUSD", 1.1, 1.1002));
+    ensure!(capped.execute(&MarketOrder::new("EURUSD", OrderSide::Sell, 100_000.0)).await?.units == 25_000.0, "capped at 25k");
+    ensure!(capped.execute(&MarketOrder::new("EURUSD", OrderSide::Sell, 10_000.0)).await?.units == 10_000.0, "below the cap");
+    let config = PaperBrokerConfig { partial_fill_probability: 1.0, min_fill_fraction: 0.4, seed: 7, ..PaperBrokerConfig::default() };
+    let fill_units = |config: PaperBrokerConfig| async move {
+        let broker = PaperBroker::new(config);
+        broker.update_quote(tick("EURUSD", 1.1, 1.1002));
+        let mut units = Vec::new();
+        for _ in 0..20 {
+            units.push(broker.execute(&MarketOrder::new("EURUSD", OrderSide::Buy, 10_000.0)).await?.units);
+        }
+        anyhow::Ok(units)
+    };
+    let units = fill_units(config.clone()).await?;
+    ensure!(units.iter().all(|u| (4_000.0..=10_000.0).contains(u) && u.fract() == 0.0), "partial fills within 40-100%: {:?}", units);
+    ensure!(units.iter().any(|u| *u < 10_000.0), "some fills are partial");
+    ensure!(fill_units(config).await? == units, "the same seed fills the same");
+    ensure!(capped.execute(&MarketOrder::new("EURUSD", OrderSide::Buy, 0.0)).await.is_err(), "empty order filled");
+    println!("   ✅ Capped at 25k; partial fills {:.0}-{:.0} of 10k", units.iter().cloned().fold(f64::INFINITY, f64::min), units.iter().cloned().fold(0.0, f64::max));
+
+    // Test 4: no quote, no fill
+    println!("📊 Test 4: missing quotes");
+    let broker = PaperBroker::new(PaperBrokerConfig::default());
+    let error = broker.execute(&MarketOrder::new("AUDUSD", OrderSide::Buy, 1_000.0)).await.unwrap_err();
+    ensure!(error.to_string().contains("no quote for AUDUSD") && broker.fills().is_empty(), "{}", error);
+    println!("   ✅ {}", error);
+
+    // Test 5: quotes followed from a tick stream
+    println!("📊 Test 5: following a feed");
+    let broker = Arc::new(PaperBroker::new(PaperBrokerConfig::default()));
+    let (sender, receiver) = mpsc::channel(16);
+    let follower = broker.follow(receiver);
+    for (bid, ask) in [(1.3000, 1.3003), (1.3010, 1.3013), (1.3020, 1.3022)] {
+        sender.send(tick("GBPUSD", bid, ask)).await?;
+    }
+    drop(sender);
+    follower.await?;
+    let quote = broker.quote("gbpusd").expect("quoted");
+    ensure!(quote.bid == 1.3020 && quote.ask == 1.3022, "latest quote {:?}", quote);
+    ensure!(broker.execute(&MarketOrder::new("GBPUSD", OrderSide::Sell, 1_000.0)).await?.price == 1.3020, "sold at the latest bid");
+    println!("   ✅ Latest of 3 streamed quotes used");
+
+    // Test 6: signal to fill to P&L through the manager
+    println!("📊 Test 6: manager end to end");
+    let broker = Arc::new(PaperBroker::new(PaperBrokerConfig { latency_ms: 20, ..PaperBrokerConfig::default() }));
+    let manager = priced_manager(MultiCurrencyManager::new().with_trading_windows(TradingWindowsConfig::unrestricted()).with_execution_backend(broker.clone()), 1.1000).await?;
+    let spread = manager.pairs.read().await["EURUSD"].config.spread;
+    manager.execute_actions(&actions(TradingAction::Buy { size: 1 })).await;
+    let bought = manager.portfolio.read().await.orders().recent(1)[0].clone();
+    ensure!(bought.status == OrderStatus::Filled && (bought.price - (1.1000 + spread / 2.0)).abs() < 1e-9, "bought at the ask: {:?}", bought);
+    ensure!(broker.fills().len() == 1 && broker.fills()[0].units == bought.filled_units, "broker fill booked");
+    reprice(&manager, 1.1050).await;
+    let realized = manager.execute_actions(&actions(TradingAction::ClosePosition)).await;
+    let closed = manager.portfolio.read().await.orders().recent(1)[0].clone();
+    let expected = (1.1050 - spread / 2.0 - bought.price) * bought.filled_units;
+    ensure!(closed.filled_units == -bought.filled_units && (closed.price - (1.1050 - spread / 2.0)).abs() < 1e-9, "closed at the bid: {:?}", closed);
+    ensure!((realized["EURUSD"] - expected).abs() < 1e-6, "realized {} vs {}", realized["EURUSD"], expected);
+    ensure!(manager.portfolio.read().await.positions().get("EURUSD").map_or(0.0, |p| p.units) == 0.0, "position flat");
+    println!("   ✅ Bought {:.0} at {:.5}, closed at {:.5}, realized {:.2}", bought.filled_units, bought.price, closed.price, expected);
+
+    let broker = Arc::new(PaperBroker::new(PaperBrokerConfig { max_fill_units: Some(400.0), ..PaperBrokerConfig::default() }));
+    let manager = priced_manager(MultiCurrencyManager::new().with_trading_windows(TradingWindowsConfig::unrestricted()).with_execution_backend(broker), 1.1000).await?;
+    manager.execute_actions(&actions(TradingAction::Sell { size: 1 })).await;
+    let partial = manager.portfolio.read().await.orders().recent(1)[0].clone();
+    ensure!(partial.status == OrderStatus::PartiallyFilled && partial.filled_units == -400.0, "partial {:?}", partial);
+    ensure!(partial.reason.as_deref() == Some("partial fill"), "reason {:?}", partial.reason);
+    println!("   ✅ {}", partial.summary());
+
+    let manager = priced_manager(MultiCurrencyManager::new().with_trading_windows(TradingWindowsConfig::unrestricted()).with_execution_backend(Arc::new(PaperBroker::new(PaperBrokerConfig::default()))), 1.1000).await?;
+    manager.pairs.write().await.get_mut("EURUSD").expect("pair initialized").historical_data.clear();
+    manager.execute_actions(&actions(TradingAction::Buy { size: 1 })).await;
+    ensure!(manager.portfolio.read().await.orders().recent(1).is_empty(), "unpriced pair traded");
+    println!("   ✅ Unpriced pairs send nothing");
+
+    println!();
+    println!("🎉 All paper broker tests passed");
+    Ok(())
+}
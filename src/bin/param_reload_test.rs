@@ -0,0 +1,137 @@
+//! # Parameter Reload Test
+//!
+//! Check runtime parameter changes validate, get epochs, apply at each pair's
+//! next update without losing its own settings, and mark the anomaly stream
+
+use anyhow::{ensure, Result};
+use chrono::{TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::collections::HashMap;
+
+use forex_pattern_reconstruction::dashboard::server::{publish_updates, AnomalyStreamMessage, StreamHub};
+use forex_pattern_reconstruction::multi_currency::{ControlCommand, CurrencyPairConfig, CurrencyPairState, MultiCurrencyManager};
+use forex_pattern_reconstruction::pipeline::params::{AnalysisParams, ParamsHandle, ParamsUpdate, Sensitivity};
+use forex_pattern_reconstruction::synthetic::fixtures::cyclic_daily;
+
+fn parameters(entries: &[(&str, &str)]) -> HashMap<String, String> {
+    entries.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect()
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 PARAMETER RELOAD TEST");
+    println!("========================");
+    println!();
+
+    // Test 1: operator commands
+    println!("📊 Test 1: set-params commands");
+    let command = ControlCommand::parse("set-params", None, &parameters(&[("sensitivity", "2.5"), ("cycle_confidence", "0.8")]))?;
+    let expected = ParamsUpdate {
+        anomaly_sensitivity: Some(Sensitivity::Fixed(2.5)),
+        cycle_confidence_threshold: Some(0.8),
+        symmetry_strength_threshold: None,
+    };
+    ensure!(command == Some(ControlCommand::SetParams { update: expected }), "parsed {:?}", command);
+    let auto = ControlCommand::parse("set_params", None, &parameters(&[("sensitivity", "auto")]))?;
+    ensure!(matches!(auto, Some(ControlCommand::SetParams { update }) if update.anomaly_sensitivity == Some(Sensitivity::Calibrated)), "auto calibrates");
+    ensure!(ControlCommand::parse("set-params", None, &HashMap::new()).is_err(), "nothing to set");
+    ensure!(ControlCommand::parse("set-params", None, &parameters(&[("symmetry_strength", "high")])).is_err(), "not a number");
+    println!("   ✅ {}", command.unwrap());
+
+    // Test 2: the handle validates, logs and numbers changes
+    println!("📊 Test 2: epochs");
+    let handle = ParamsHandle::default();
+    let mut receiver = handle.subscribe();
+    let update = ParamsUpdate { symmetry_strength_threshold: Some(0.9), ..ParamsUpdate::default() };
+    let epoch = handle.update(&update, "test")?;
+    ensure!(epoch.epoch == 1 && epoch.changes.len() == 1, "one change at epoch 1");
+    ensure!(epoch.changes[0].old == "0.75" && epoch.changes[0].new == "0.9", "old and new values: {}", epoch.changes[0]);
+    ensure!(receiver.has_changed()? && receiver.borrow_and_update().symmetry_strength_threshold == 0.9, "published");
+    let unchanged = handle.update(&update, "test")?;
+    ensure!(unchanged.epoch == 1 && unchanged.changes.is_empty() && !receiver.has_changed()?, "a no-op is not an epoch");
+    for invalid in [
+        ParamsUpdate { cycle_confidence_threshold: Some(1.5), ..ParamsUpdate::default() },
+        ParamsUpdate { anomaly_sensitivity: Some(Sensitivity::Fixed(0.0)), ..ParamsUpdate::default() },
+    ] {
+        ensure!(handle.update(&invalid, "test").is_err(), "rejected {}", invalid);
+    }
+    ensure!(handle.current().epoch == 1 && handle.epochs_since(0).len() == 1, "rejected updates leave no epoch");
+    println!("   ✅ Epoch {}: {}", epoch.epoch, epoch.changes[0]);
+
+    // Test 3: pairs apply changes at their next update
+    println!("📊 Test 3: currency pairs");
+    let mut rng = StdRng::seed_from_u64(5);
+    let start = Utc.with_ymd_and_hms(2021, 1, 4, 0, 0, 0).unwrap();
+    let mut config = CurrencyPairConfig::default();
+    config.pipeline.patterns.confidence_threshold = 0.6;
+    let mut state = CurrencyPairState::new(config).await?;
+    state.historical_data = cyclic_daily(start, 500, &mut rng);
+    state.reanalyze().await?;
+    ensure!(state.anomaly_detector.calibration().is_some(), "pairs calibrate by default");
+    let handle = ParamsHandle::default();
+    state.follow_params(&handle);
+    ensure!(!state.apply_param_changes().await?, "nothing to apply yet");
+
+    handle.update(&ParamsUpdate { anomaly_sensitivity: Some(Sensitivity::Fixed(2.5)), ..ParamsUpdate::default() }, "test")?;
+    ensure!(state.anomaly_detector.sensitivity_threshold() != 2.5, "not applied before the next update");
+    ensure!(state.apply_param_changes().await?, "applied");
+    ensure!(state.anomaly_detector.sensitivity_threshold() == 2.5 && state.anomaly_detector.calibration().is_none(), "fixed sensitivity");
+    ensure!(state.analysis_params.cycle_confidence_threshold == 0.6, "the pair's own cycle threshold survives");
+
+    let (symmetries, cycles) = (state.symmetries.len(), state.cycles.len());
+    let strengths: Vec<f64> = state.symmetries.iter().map(|symmetry| symmetry.strength).collect();
+    let threshold = (strengths.iter().cloned().fold(0.0, f64::max) + strengths.iter().cloned().fold(1.0, f64::min)) / 2.0;
+    handle.update(&ParamsUpdate {
+        symmetry_strength_threshold: Some(threshold.clamp(0.0, 1.0)),
+        cycle_confidence_threshold: Some(1.0),
+        ..ParamsUpdate::default()
+    }, "test")?;
+    state.apply_param_changes().await?;
+    let admitted = strengths.iter().filter(|strength| **strength >= threshold).count();
+    ensure!(state.anomaly_detector.expected_symmetries().len() == admitted, "detector keeps {} of {} symmetries", admitted, symmetries);
+    ensure!(state.anomaly_detector.expected_cycles().iter().all(|cycle| cycle.confidence >= 1.0), "weaker cycles dropped");
+    ensure!(state.symmetries.len() == symmetries && state.cycles.len() == cycles, "extracted patterns are kept");
+    ensure!(state.anomaly_detector.sensitivity_threshold() == 2.5, "sensitivity untouched");
+    ensure!(state.analysis_params.epoch == 2, "pair at epoch {}", state.analysis_params.epoch);
+
+    handle.update(&ParamsUpdate { anomaly_sensitivity: Some(Sensitivity::Calibrated), ..ParamsUpdate::default() }, "test")?;
+    state.apply_param_changes().await?;
+    ensure!(state.anomaly_detector.calibration().is_some(), "calibrated again");
+    println!("   ✅ Sensitivity 2.5 → calibrated, {}/{} symmetries above {:.2}", admitted, symmetries, threshold);
+
+    // A pair joining late catches up
+    let mut late = CurrencyPairState::new(CurrencyPairConfig::default()).await?;
+    late.follow_params(&handle);
+    late.apply_param_changes().await?;
+    ensure!(late.analysis_params.cycle_confidence_threshold == 1.0 && late.analysis_params.epoch == 3, "late pair at the current epoch");
+    ensure!(AnalysisParams::default().epoch == 0, "parameters start at epoch 0");
+
+    // Test 4: the manager applies commands and the stream marks the epoch
+    println!("📊 Test 4: manager and anomaly stream");
+    let mut manager = MultiCurrencyManager::new();
+    manager.initialize_pairs(&["EURUSD".to_string(), "USDJPY".to_string()]).await?;
+    let hub = StreamHub::new();
+    let mut stream = hub.subscribe_anomalies();
+    let (mut seen, mut params_epoch) = (HashMap::new(), 0);
+    let command = ControlCommand::parse("set-params", None, &parameters(&[("cycle_confidence", "0.9")]))?.unwrap();
+    let message = manager.execute_command(&command, "api").await?;
+    ensure!(message.contains("0.75 → 0.9"), "reply shows the change: {}", message);
+    publish_updates(&manager, &hub, &mut seen, &mut params_epoch).await;
+    ensure!(stream.try_recv().is_err(), "no marker before the pairs apply the change");
+    manager.process_all_market_updates().await?;
+    ensure!(manager.pair_params().await.values().all(|params| params.epoch == 1 && params.cycle_confidence_threshold == 0.9), "every pair at epoch 1");
+    publish_updates(&manager, &hub, &mut seen, &mut params_epoch).await;
+    let marker = stream.try_recv()?;
+    let json = serde_json::to_value(&marker)?;
+    ensure!(matches!(&marker, AnomalyStreamMessage::ParamsEpoch(epoch) if epoch.epoch == 1 && epoch.source == "api"), "epoch marker streamed");
+    ensure!(json["kind"] == "params_epoch" && json["changes"][0]["new"] == "0.9", "marker JSON {}", json);
+    publish_updates(&manager, &hub, &mut seen, &mut params_epoch).await;
+    ensure!(stream.try_recv().is_err(), "each epoch marked once");
+    ensure!(manager.audit_log.recent(1)[0].command.starts_with("set-params"), "change audited");
+    println!("   ✅ {}", json);
+
+    println!();
+    println!("🎉 All parameter reload tests passed");
+    Ok(())
+}
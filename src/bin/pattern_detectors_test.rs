@@ -0,0 +1,129 @@
+//! # Pattern Inversion and Novel Pattern Test
+//!
+//! Feed the detector a clean 24-hour cycle that suddenly inverts, and a quiet
+//! random walk interrupted by a zigzag never seen in history, then check that
+//! the inversion is reported once against the right cycle and that the zigzag is
+//! novel the first time and learned by the second
+
+use anyhow::{ensure, Result};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::f64::consts::PI;
+
+use forex_pattern_reconstruction::anomaly::{AnomalyDetectionConfig, AnomalyType, DetectedAnomaly, TemporalAnomalyDetector};
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::ids::CycleId;
+use forex_pattern_reconstruction::patterns::HiddenCycle;
+
+fn bar(timestamp: DateTime<Utc>, open: f64, close: f64) -> ForexDataPoint {
+    ForexDataPoint { timestamp, open, high: open.max(close) + 0.0003, low: open.min(close) - 0.0003, close, volume: None }
+}
+
+fn count(anomalies: &[DetectedAnomaly], name: &str) -> usize {
+    anomalies.iter().filter(|a| a.anomaly_type.name() == name).count()
+}
+
+/// Hourly closes from `close_at`, with opens at the previous close
+fn series(start: DateTime<Utc>, hours: i64, mut close_at: impl FnMut(DateTime<Utc>) -> f64) -> Vec<ForexDataPoint> {
+    let mut previous = close_at(start);
+    (0..hours).map(|h| {
+        let timestamp = start + Duration::hours(h);
+        let close = close_at(timestamp);
+        let point = bar(timestamp, previous, close);
+        previous = close;
+        point
+    }).collect()
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 PATTERN INVERSION AND NOVEL PATTERN TEST");
+    println!("===========================================");
+    println!();
+
+    let mut rng = StdRng::seed_from_u64(23);
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+
+    // Test 1: a 24-hour cycle that flips sign is reported once, after the flip
+    println!("📊 Test 1: pattern inversion");
+    let cycle = HiddenCycle { id: CycleId::new(), name: "24-Bar Cycle".to_string(), period: 24, confidence: 0.95, amplitude: 0.002, phase: 0.3, p_value: None, track: None };
+    let omega = 2.0 * PI / 24.0;
+    let wave = |t: DateTime<Utc>| (omega * (t.timestamp() as f64 / 3600.0) + cycle.phase).sin();
+    let historical = series(start, 24 * 30, |t| 1.1 * (1.0 + cycle.amplitude * wave(t)) + rng.gen_range(-0.00005..0.00005));
+    let mut detector = TemporalAnomalyDetector::new(Vec::new(), vec![cycle.clone()], &historical, AnomalyDetectionConfig::default())?;
+
+    let flip = historical.last().unwrap().timestamp + Duration::hours(49);
+    let live = series(historical.last().unwrap().timestamp + Duration::hours(1), 96, |t| {
+        let sign = if t < flip { 1.0 } else { -1.0 };
+        1.1 * (1.0 + sign * cycle.amplitude * wave(t))
+    });
+    let anomalies = detector.detect_anomalies(&live).await?;
+    let inversions: Vec<&DetectedAnomaly> = anomalies.iter()
+        .filter(|a| matches!(a.anomaly_type, AnomalyType::PatternInversion { .. }))
+        .collect();
+    for inversion in &inversions {
+        println!("   {} at +{}h after the flip: {:?}, confidence {:.3}",
+                 inversion.pattern_id(), (inversion.timestamp - flip).num_hours(), inversion.severity, inversion.confidence);
+    }
+    ensure!(!inversions.is_empty(), "the inverted cycle was not detected");
+    ensure!(inversions.len() <= 2, "an inversion should be reported once, got {}", inversions.len());
+    ensure!(inversions.iter().all(|a| a.timestamp >= flip && a.timestamp < flip + Duration::hours(24)),
+            "inversions must follow the flip within one period");
+    ensure!(inversions[0].pattern_id() == cycle.id.as_str(), "the inversion names the cycle it broke");
+    println!("   ✅ Inversion of the 24-Bar Cycle reported after the flip");
+
+    // Test 2: a quiet random walk is mostly familiar
+    println!("📊 Test 2: familiar random walk");
+    let walk = |rng: &mut StdRng, from: DateTime<Utc>, hours: i64, start_price: f64| {
+        let mut price = start_price;
+        series(from, hours, |_| {
+            price += rng.gen_range(-0.0002..0.0002);
+            price
+        })
+    };
+    let history = walk(&mut rng, start, 24 * 7 * 8, 1.1);
+    let mut detector = TemporalAnomalyDetector::new(Vec::new(), Vec::new(), &history, AnomalyDetectionConfig::default())?;
+    let quiet_start = history.last().unwrap().timestamp + Duration::hours(1);
+    let quiet = walk(&mut rng, quiet_start, 24 * 7, history.last().unwrap().close);
+    let quiet_novel = count(&detector.detect_anomalies(&quiet).await?, "NovelPattern");
+    println!("   {} novel windows in {} ordinary bars", quiet_novel, quiet.len());
+    ensure!(quiet_novel <= 5, "ordinary moves should rarely look novel, got {}", quiet_novel);
+    println!("   ✅ Ordinary bars match the learned clusters");
+
+    // Test 3: an unseen zigzag is novel the first time and familiar the second
+    println!("📊 Test 3: novel zigzag, then learned");
+    let zigzag = |from: DateTime<Utc>, start_price: f64| {
+        let mut price = start_price;
+        let mut step = 0.0006;
+        series(from, 12, |_| {
+            price += step;
+            step = -step;
+            price
+        })
+    };
+    let mut bars = quiet[quiet.len() - 20..].to_vec();
+    bars.extend(zigzag(quiet.last().unwrap().timestamp + Duration::hours(1), quiet.last().unwrap().close));
+    let first = detector.detect_anomalies(&bars).await?;
+    let first_novel: Vec<&DetectedAnomaly> = first.iter()
+        .filter(|a| matches!(a.anomaly_type, AnomalyType::NovelPattern { .. }))
+        .collect();
+    let mut later = bars.clone();
+    later.extend(zigzag(bars.last().unwrap().timestamp + Duration::hours(1), quiet.last().unwrap().close));
+    let later = later.split_off(bars.len() - 20);
+    let second_novel = count(&detector.detect_anomalies(&later).await?, "NovelPattern");
+    if let Some(AnomalyType::NovelPattern { pattern_signature, emergence_confidence }) = first_novel.first().map(|a| &a.anomaly_type) {
+        println!("   first pass: {} novel windows, e.g. '{}' ({:.3}); second pass: {}",
+                 first_novel.len(), pattern_signature, emergence_confidence, second_novel);
+    }
+    ensure!(!first_novel.is_empty(), "the zigzag was not flagged as novel");
+    ensure!(first_novel.iter().any(|a| matches!(&a.anomaly_type,
+            AnomalyType::NovelPattern { pattern_signature, .. } if pattern_signature.contains("eaea"))),
+            "the signature should show the alternating ±5σ returns");
+    ensure!(second_novel * 2 < first_novel.len(), "a repeated zigzag must be recognised as learned");
+    println!("   ✅ Zigzag discovered, then recognised");
+
+    println!();
+    println!("🎉 All pattern detector tests passed");
+    Ok(())
+}
@@ -0,0 +1,88 @@
+//! # Pipeline Builder Test
+//!
+//! Check one configuration assembles a working pipeline: a history is analyzed
+//! and the generator and detector are built on the result, a given analysis
+//! and calibration are used as they are, settings load from TOML with missing
+//! sections defaulted, and currency pairs build their stages the same way
+
+use anyhow::{ensure, Result};
+use chrono::{TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use forex_pattern_reconstruction::anomaly::AnomalyDetectionConfig;
+use forex_pattern_reconstruction::multi_currency::{CurrencyPairConfig, CurrencyPairState};
+use forex_pattern_reconstruction::pipeline::{PipelineBuilder, PipelineConfig};
+use forex_pattern_reconstruction::synthetic::fixtures::cyclic_daily;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 PIPELINE BUILDER TEST");
+    println!("========================");
+    println!();
+
+    let mut rng = StdRng::seed_from_u64(9);
+    let start = Utc.with_ymd_and_hms(2021, 1, 4, 0, 0, 0).unwrap();
+    let data = cyclic_daily(start, 500, &mut rng);
+    let calibrated = PipelineConfig {
+        anomaly: AnomalyDetectionConfig { target_anomalies_per_day: Some(1.0), ..AnomalyDetectionConfig::default() },
+        ..PipelineConfig::default()
+    };
+
+    // Test 1: a history is analyzed and every stage is built on it
+    println!("📊 Test 1: build from a history");
+    let mut pipeline = PipelineBuilder::new(calibrated.clone()).with_history(data.clone()).build().await?;
+    ensure!(pipeline.history.len() == data.len(), "history kept");
+    ensure!(!pipeline.cycles.is_empty(), "the 20-bar cycle was detected");
+    ensure!(pipeline.anomaly_detector.calibration().is_some(), "the detector was calibrated on the history");
+    let synthetic = pipeline.synthetic_generator.generate_future_data(data[499].timestamp, "EURUSD").await?;
+    ensure!(!synthetic.is_empty(), "the generator produces data");
+    pipeline.anomaly_detector.detect_anomalies(&synthetic).await?;
+    println!("   ✅ {} symmetries, {} cycles, {} synthetic points", pipeline.symmetries.len(), pipeline.cycles.len(), synthetic.len());
+
+    // Test 2: a given analysis and calibration are used as they are
+    println!("📊 Test 2: given analysis and calibration");
+    let calibration = pipeline.anomaly_detector.calibration().cloned();
+    let restored = PipelineBuilder::new(calibrated.clone())
+        .with_history(cyclic_daily(start, 300, &mut rng))
+        .with_analysis(Vec::new(), pipeline.cycles[..1].to_vec())
+        .with_calibration(calibration.clone())
+        .build()
+        .await?;
+    ensure!(restored.symmetries.is_empty() && restored.cycles.len() == 1, "the analysis was not redone");
+    ensure!(restored.anomaly_detector.sensitivity_threshold() == calibration.as_ref().unwrap().sensitivity_threshold, "calibration kept");
+    let empty = PipelineBuilder::new(calibrated.clone()).build().await?;
+    ensure!(empty.history.is_empty() && empty.symmetries.is_empty() && empty.cycles.is_empty(), "nothing to analyze");
+    ensure!(empty.anomaly_detector.calibration().is_none(), "nothing to calibrate on");
+    println!("   ✅ Analysis and calibration reused; empty pipeline built");
+
+    // Test 3: settings load from TOML, missing sections defaulted
+    println!("📊 Test 3: TOML settings");
+    let path = std::env::temp_dir().join(format!("pipeline_builder_test_{}.toml", std::process::id()));
+    let mut edited = PipelineConfig::default();
+    edited.rl.learning_rate = 0.25;
+    let mut table: toml::Table = toml::from_str(&toml::to_string(&edited)?)?;
+    table.retain(|section, _| section == "rl");
+    std::fs::write(&path, toml::to_string(&table)?)?;
+    let loaded = PipelineBuilder::from_file(&path)?;
+    ensure!(loaded.config().rl.learning_rate == 0.25, "rl section read");
+    ensure!(loaded.config().anomaly.detection_window_size == AnomalyDetectionConfig::default().detection_window_size, "anomaly section defaulted");
+    std::fs::write(&path, "[rl]\nlearning_rate = \"fast\"\n")?;
+    ensure!(PipelineBuilder::from_file(&path).is_err(), "malformed settings are refused");
+    let _ = std::fs::remove_file(&path);
+    println!("   ✅ Loaded {} sections from TOML", table.len());
+
+    // Test 4: currency pairs build their stages from their pipeline settings
+    println!("📊 Test 4: currency pairs");
+    let pair = CurrencyPairConfig { target_anomalies_per_day: 3.0, ..CurrencyPairConfig::default() };
+    ensure!(pair.pipeline_config().anomaly.target_anomalies_per_day == Some(3.0), "the pair's anomaly rate is applied");
+    let mut state = CurrencyPairState::new(pair).await?;
+    state.historical_data = data.clone();
+    state.reanalyze().await?;
+    ensure!(state.anomaly_detector.calibration().is_some() && !state.cycles.is_empty(), "the pair was analyzed and calibrated");
+    println!("   ✅ {} cycles, sensitivity {:.3}", state.cycles.len(), state.anomaly_detector.sensitivity_threshold());
+
+    println!();
+    println!("🎉 All pipeline builder tests passed");
+    Ok(())
+}
@@ -0,0 +1,93 @@
+//! # Plotting Test
+//!
+//! Check that pattern charts and stacked decomposition plots are rendered as
+//! real PNG and SVG files in the output directory, with the cycles, symmetries
+//! and components they chart, and that too short histories are refused
+
+use anyhow::{ensure, Result};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::path::Path;
+
+use forex_pattern_reconstruction::ids::CycleId;
+use forex_pattern_reconstruction::patterns::{CycleDecomposer, DecompositionConfig, HiddenCycle};
+use forex_pattern_reconstruction::symmetry::{SymmetryDetector, SymmetryDetectorConfig};
+use forex_pattern_reconstruction::synthetic::fixtures::trending_daily;
+use forex_pattern_reconstruction::visualization::{self, charts, ChartFormat};
+
+/// Width and height from the header of a PNG file
+fn png_size(path: &Path) -> Result<(u32, u32)> {
+    let bytes = std::fs::read(path)?;
+    ensure!(bytes.len() > 24 && bytes.starts_with(b"\x89PNG\r\n\x1a\n"), "{} is not a PNG", path.display());
+    let word = |at: usize| u32::from_be_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]]);
+    Ok((word(16), word(20)))
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 PLOTTING TEST");
+    println!("================");
+    println!();
+
+    let output = std::env::temp_dir().join(format!("plotting-test-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&output);
+    let mut rng = StdRng::seed_from_u64(5);
+    let data = trending_daily(&mut rng, 300);
+    let cycles = vec![HiddenCycle { id: CycleId::new(), name: "20-bar swing".to_string(), period: 20, confidence: 0.9, amplitude: 0.01 / 1.115, phase: 0.0, p_value: None, track: None }];
+    let detector = SymmetryDetector::new(SymmetryDetectorConfig::default())?;
+    let symmetries = detector.detect(&data);
+    ensure!(symmetries.iter().any(|symmetry| symmetry.mirror_points.len() >= 2), "no symmetry with mirror points to chart");
+
+    // Test 1: PNG pattern charts
+    println!("📊 Test 1: PNG pattern charts");
+    let written = visualization::generate_pattern_plots(&symmetries, &cycles, &data, &output.join("png"), ChartFormat::Png)?;
+    let names: Vec<String> = written.iter().filter_map(|path| Some(path.file_name()?.to_str()?.to_string())).collect();
+    ensure!(names == ["price_cycles.png", "spectral_power.png", "symmetry_mirrors.png"], "{:?}", names);
+    for path in &written {
+        ensure!(png_size(path)? == (1280, 720), "{} is {:?}", path.display(), png_size(path)?);
+    }
+    println!("   ✅ {}", names.join(", "));
+
+    // Test 2: SVG pattern charts carry what they chart
+    println!("📊 Test 2: SVG pattern charts");
+    let written = visualization::generate_pattern_plots(&symmetries, &cycles, &data, &output.join("svg"), ChartFormat::Svg)?;
+    ensure!(written.len() == 3 && written.iter().all(|path| ChartFormat::from_path(path) == ChartFormat::Svg), "{:?}", written);
+    let price = std::fs::read_to_string(&written[0])?;
+    ensure!(price.starts_with("<svg") && price.contains("Reconstruction (1 cycles)") && price.contains("20-bar cycle"), "price chart misses its cycles");
+    let spectrum = std::fs::read_to_string(&written[1])?;
+    ensure!(spectrum.contains("Period (bars)") && spectrum.contains("1% false alarm") && spectrum.contains("20-bar cycle"), "spectrum misses its marks");
+    let mirrors = std::fs::read_to_string(&written[2])?;
+    let marked = symmetries.iter().filter(|symmetry| symmetry.mirror_points.len() >= 2).count();
+    ensure!(mirrors.matches("<circle").count() >= 2 * marked, "{} circles for {} symmetries", mirrors.matches("<circle").count(), marked);
+    println!("   ✅ {} symmetries marked on {} bars", marked, data.len());
+
+    // Test 3: stacked decomposition panels, one per component
+    println!("📊 Test 3: Decomposition");
+    let mut decomposer = CycleDecomposer::new(DecompositionConfig::default())?;
+    let decomposition = decomposer.decompose_cycles(&data, &[10, 20, 50]).await?;
+    let png = output.join("cycles.png");
+    visualization::plot_cycle_decomposition(&decomposition, png.to_str().unwrap())?;
+    ensure!(png_size(&png)? == (1280, 240 * 5), "five panels: {:?}", png_size(&png)?);
+    let svg = output.join("cycles.svg");
+    visualization::plot_cycle_decomposition(&decomposition, svg.to_str().unwrap())?;
+    let stacked = std::fs::read_to_string(&svg)?;
+    for period in decomposition.periods() {
+        ensure!(stacked.contains(&format!("{}-bar cycle", period)), "no panel for the {}-bar cycle", period);
+    }
+    ensure!(stacked.contains("Residual") && stacked.contains("Close, trend and fit"), "decomposition panels missing");
+    println!("   ✅ {} components stacked", decomposition.periods().len());
+
+    // Test 4: histories too short to chart are refused
+    println!("📊 Test 4: Short histories");
+    ensure!(visualization::generate_pattern_plots(&symmetries, &cycles, &data[..3], &output.join("short"), ChartFormat::Png).is_err(), "charted three bars");
+    ensure!(charts::plot_spectrum(&data[..5], &cycles, &output.join("short").join("spectrum.png")).is_err(), "spectrum of five bars");
+    let written = visualization::generate_pattern_plots(&[], &[], &data[..5], &output.join("short"), ChartFormat::Png)?;
+    ensure!(written.len() == 2, "short history keeps the price and mirror charts: {:?}", written);
+    ensure!(ChartFormat::from_path(Path::new("a.SVG")) == ChartFormat::Svg && ChartFormat::from_path(Path::new("a")) == ChartFormat::Png, "format by extension");
+    std::fs::remove_dir_all(&output)?;
+    println!("   ✅ Too few bars refused, spectrum skipped for five");
+
+    println!();
+    println!("🎉 All plotting tests passed");
+    Ok(())
+}
@@ -0,0 +1,88 @@
+//! # Portfolio Snapshot Test
+//!
+//! Check the numbers `/api/portfolio` reports: unrealized P&L, margin and net
+//! currency exposure converted to the account currency through direct, inverse
+//! and cross pairs, and the balance and win rate once a position is closed
+
+use anyhow::{ensure, Result};
+use chrono::{TimeZone, Utc};
+use std::collections::HashMap;
+
+use forex_pattern_reconstruction::laplacian_rl::TradingAction;
+use forex_pattern_reconstruction::portfolio::{convert, Portfolio, PortfolioConfig, PortfolioSnapshot};
+
+fn near(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-6 * b.abs().max(1.0)
+}
+
+fn prices(quotes: &[(&str, f64)]) -> HashMap<String, f64> {
+    quotes.iter().map(|(pair, price)| (pair.to_string(), *price)).collect()
+}
+
+fn main() -> Result<()> {
+    println!("🔬 PORTFOLIO SNAPSHOT TEST");
+    println!("==========================");
+    println!();
+
+    let now = Utc.with_ymd_and_hms(2024, 3, 5, 12, 0, 0).unwrap();
+    let config = PortfolioConfig::default();
+    let (balance, leverage) = (config.initial_balance, config.leverage);
+
+    // Test 1: conversions through direct, inverse and cross pairs
+    println!("📊 Test 1: Currency conversion");
+    let quotes = prices(&[("EURUSD", 1.10), ("USDJPY", 150.0), ("EURGBP", 0.85), ("GBPUSD", 1.25)]);
+    ensure!(near(convert(100.0, "EUR", "USD", &quotes), 110.0), "direct");
+    ensure!(near(convert(15_000.0, "JPY", "USD", &quotes), 100.0), "inverse");
+    ensure!(near(convert(7.0, "CHF", "USD", &quotes), 7.0), "unknown conversions pass through");
+    println!("   ✅ Direct, inverse and unknown conversions");
+
+    // Test 2: open positions in a USD pair, a JPY pair and a cross
+    println!("📊 Test 2: Open positions");
+    let mut portfolio = Portfolio::new(config);
+    portfolio.apply_action("EURUSD", &TradingAction::Buy { size: 10 }, 1.10, &quotes, now);
+    portfolio.apply_action("USDJPY", &TradingAction::Sell { size: 10 }, 150.0, &quotes, now);
+    portfolio.apply_action("EURGBP", &TradingAction::Buy { size: 5 }, 0.85, &quotes, now);
+    portfolio.apply_action("EURGBP", &TradingAction::Buy { size: 5 }, 0.87, &quotes, now);
+    ensure!(near(portfolio.positions()["EURGBP"].entry_price, 0.86), "adding to a position averages its entry");
+
+    let moved = prices(&[("EURUSD", 1.11), ("USDJPY", 149.0), ("EURGBP", 0.87), ("GBPUSD", 1.27)]);
+    let snapshot = portfolio.snapshot(&moved, now);
+    let pnl = |symbol: &str| snapshot.positions.iter().find(|p| p.symbol == symbol).map(|p| p.unrealized_pnl).unwrap_or(f64::NAN);
+    ensure!(near(pnl("EURUSD"), 100.0), "EURUSD P&L {}", pnl("EURUSD"));
+    ensure!(near(pnl("USDJPY"), 10_000.0 / 149.0), "USDJPY P&L {} not converted from yen", pnl("USDJPY"));
+    ensure!(near(pnl("EURGBP"), 100.0 * 1.27), "EURGBP P&L {} not converted from sterling", pnl("EURGBP"));
+    let margin = (10_000.0 * 1.11 + 10_000.0 + 10_000.0 * 0.87 * 1.27) / leverage;
+    ensure!(near(snapshot.margin_used, margin), "margin {} instead of {}", snapshot.margin_used, margin);
+    ensure!(near(snapshot.unrealized_pnl, pnl("EURUSD") + pnl("USDJPY") + pnl("EURGBP")), "unrealized is the sum of the positions");
+    ensure!(near(snapshot.equity, balance + snapshot.unrealized_pnl) && near(snapshot.free_margin, snapshot.equity - margin), "equity and free margin");
+    ensure!(snapshot.margin_level.is_some_and(|level| near(level, snapshot.equity / margin * 100.0)), "margin level");
+    println!("   ✅ Equity {:.2}, margin {:.2}, level {:.0}%", snapshot.equity, snapshot.margin_used, snapshot.margin_level.unwrap_or_default());
+
+    // Test 3: net exposure per currency
+    println!("📊 Test 3: Currency exposure");
+    let exposure: HashMap<&str, (f64, f64)> = snapshot.currency_exposure.iter()
+        .map(|e| (e.currency.as_str(), (e.net_amount, e.account_value)))
+        .collect();
+    let expected = [("EUR", 20_000.0), ("USD", -11_100.0 - 10_000.0), ("JPY", 10_000.0 * 149.0), ("GBP", -8_700.0)];
+    for (currency, amount) in expected {
+        let (net, value) = exposure.get(currency).copied().unwrap_or((f64::NAN, f64::NAN));
+        ensure!(near(net, amount), "{} net {} instead of {}", currency, net, amount);
+        ensure!(near(value, convert(amount, currency, "USD", &moved)), "{} valued at {}", currency, value);
+    }
+    ensure!(exposure.len() == expected.len(), "unexpected currencies: {:?}", exposure.keys());
+    println!("   ✅ EUR long, USD and GBP short, JPY long; valued in USD");
+
+    // Test 4: closing books the P&L into the balance and the API payload round-trips
+    println!("📊 Test 4: Closed trades");
+    portfolio.apply_action("EURUSD", &TradingAction::ClosePosition, 1.11, &moved, now);
+    let closed = portfolio.snapshot(&moved, now);
+    ensure!(closed.positions.len() == 2 && closed.total_trades == 1 && near(closed.win_rate, 100.0), "one winning trade closed, win rate {}%", closed.win_rate);
+    ensure!(near(closed.balance, balance + 100.0) && near(closed.realized_pnl, 100.0), "balance {} after closing", closed.balance);
+    let json: PortfolioSnapshot = serde_json::from_str(&serde_json::to_string(&closed)?)?;
+    ensure!(json.equity == closed.equity && json.positions.len() == 2 && json.currency_exposure.len() == closed.currency_exposure.len(), "payload round trip");
+    println!("   ✅ Realized {:.2}, balance {:.2}", closed.realized_pnl, closed.balance);
+
+    println!();
+    println!("🎉 All portfolio snapshot tests passed");
+    Ok(())
+}
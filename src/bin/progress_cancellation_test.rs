@@ -0,0 +1,110 @@
+//! # Progress and Cancellation Test
+//!
+//! Check that synthetic generation and symmetry extraction report their progress
+//! through closures and watch channels, and stop with `Cancelled` at the next
+//! checkpoint once their cancellation token is cancelled
+
+use anyhow::{ensure, Result};
+use chrono::Duration;
+use std::sync::Mutex;
+
+use forex_pattern_reconstruction::core::engine::EXTRACTION_STAGE;
+use forex_pattern_reconstruction::core::{EngineConfig, TimeSymmetricEngine};
+use forex_pattern_reconstruction::progress::{progress_channel, CancellationToken, Cancelled, ProgressUpdate};
+use forex_pattern_reconstruction::synthetic::benchmark::{generate_benchmark, BenchmarkSize};
+use forex_pattern_reconstruction::synthetic::{SyntheticDataGenerator, SyntheticGenerationConfig, GENERATION_STAGE};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 PROGRESS AND CANCELLATION TEST");
+    println!("=================================");
+    println!();
+
+    let dataset = generate_benchmark(BenchmarkSize::Small, 7);
+    let bars = dataset.bars;
+    let start = bars.last().unwrap().timestamp + Duration::days(1);
+    let generator = SyntheticDataGenerator::new(Vec::new(), Vec::new(), bars.clone(), SyntheticGenerationConfig {
+        future_horizon_days: 30,
+        seed: Some(7),
+        ..SyntheticGenerationConfig::default()
+    })?;
+
+    // Test 1: fractions
+    println!("📊 Test 1: Progress updates");
+    ensure!((ProgressUpdate::new("x", 1, 4).fraction() - 0.25).abs() < 1e-12, "quarter");
+    ensure!(ProgressUpdate::new("x", 0, 0).fraction() == 1.0 && ProgressUpdate::new("x", 0, 0).is_complete(), "empty stage is complete");
+    ensure!(ProgressUpdate::new("x", 9, 4).fraction() == 1.0, "fraction capped at 1");
+    println!("   ✅ Fractions in [0, 1]");
+
+    // Test 2: a watch channel sees the final update of a generation
+    println!("📊 Test 2: Generation through a watch channel");
+    let (sender, receiver) = progress_channel();
+    let points = generator.generate_future_data_with(start, "EURUSD", &sender, &CancellationToken::new()).await?;
+    let last = receiver.borrow().clone();
+    ensure!(points.len() == 720, "{} points for 30 hourly days", points.len());
+    ensure!(last == ProgressUpdate::new(GENERATION_STAGE, 720, 720), "last update {:?}", last);
+    println!("   ✅ {} points, last update {}/{}", points.len(), last.done, last.total);
+
+    // Test 3: a closure sees every point, in order
+    println!("📊 Test 3: Generation through a closure");
+    let seen = Mutex::new(Vec::new());
+    let record = |update: ProgressUpdate| seen.lock().unwrap().push(update.done);
+    generator.generate_future_data_with(start, "EURUSD", &record, &CancellationToken::new()).await?;
+    let seen = seen.into_inner().unwrap();
+    ensure!(seen.len() == 720 && seen.windows(2).all(|w| w[1] == w[0] + 1), "{} updates", seen.len());
+    println!("   ✅ {} updates, one per point", seen.len());
+
+    // Test 4: cancelling mid-generation stops at the next point
+    println!("📊 Test 4: Cancelled generation");
+    let cancel = CancellationToken::new();
+    let reports = Mutex::new(0u64);
+    let cancel_at_100 = |update: ProgressUpdate| {
+        *reports.lock().unwrap() = update.done;
+        if update.done == 100 {
+            cancel.cancel();
+        }
+    };
+    let result = generator.generate_future_data_with(start, "EURUSD", &cancel_at_100, &cancel).await;
+    ensure!(result.as_ref().is_err_and(|e| e.is::<Cancelled>()), "expected Cancelled, got {:?}", result.map(|p| p.len()));
+    ensure!(*reports.lock().unwrap() == 100, "generated past the cancellation: {}", reports.lock().unwrap());
+    println!("   ✅ Stopped after 100 of 720 points");
+
+    // Test 5: extraction steps at full resolution
+    println!("📊 Test 5: Symmetry extraction progress");
+    let mut engine = TimeSymmetricEngine::new(EngineConfig { coherence_window: 50, ..EngineConfig::default() })?;
+    engine.initialize().await?;
+    let updates = Mutex::new(Vec::new());
+    let record = |update: ProgressUpdate| updates.lock().unwrap().push(update);
+    let symmetries = engine.extract_temporal_symmetries_with(&bars, &record, &CancellationToken::new()).await?;
+    let updates = updates.into_inner().unwrap();
+    ensure!(updates.iter().all(|u| u.stage == EXTRACTION_STAGE && u.total == 5), "{:?}", updates);
+    ensure!(updates.iter().map(|u| u.done).eq(1..=5), "steps {:?}", updates);
+    println!("   ✅ 5 steps, {} symmetries", symmetries.len());
+
+    // Test 6: coarse-to-fine passes end complete whatever the number of windows
+    println!("📊 Test 6: Coarse-to-fine progress");
+    let mut coarse = TimeSymmetricEngine::new(EngineConfig { coherence_window: 50, max_points: Some(300), ..EngineConfig::default() })?;
+    coarse.initialize().await?;
+    let (sender, receiver) = progress_channel();
+    coarse.extract_temporal_symmetries_with(&bars, &sender, &CancellationToken::new()).await?;
+    let last = receiver.borrow().clone();
+    let passes = 1 + coarse.sampling_plan().map_or(0, |plan| plan.windows.len()) as u64;
+    ensure!(last.is_complete() && last.total == passes * 5, "last update {:?} for {} passes", last, passes);
+    println!("   ✅ {} passes, {}/{} steps", passes, last.done, last.total);
+
+    // Test 7: a cancelled extraction fails, and the engine still extracts afterwards
+    println!("📊 Test 7: Cancelled extraction");
+    let cancel = CancellationToken::new();
+    cancel.cancel();
+    let (sender, receiver) = progress_channel();
+    let result = engine.extract_temporal_symmetries_with(&bars, &sender, &cancel).await;
+    ensure!(result.as_ref().is_err_and(|e| e.is::<Cancelled>()), "expected Cancelled");
+    ensure!(receiver.borrow().done == 0, "no step should run once cancelled");
+    let again = engine.extract_temporal_symmetries(&bars).await?;
+    ensure!(again.len() == symmetries.len(), "{} symmetries after cancelling, {} before", again.len(), symmetries.len());
+    println!("   ✅ Cancelled before the first step, {} symmetries on retry", again.len());
+
+    println!();
+    println!("🎉 All progress and cancellation tests passed");
+    Ok(())
+}
@@ -0,0 +1,137 @@
+//! # Controller ↔ Server Protocol Integration Test
+//!
+//! Boot the trading API in-process and drive it with the controller's client
+
+use anyhow::{bail, ensure, Result};
+use chrono::{Duration, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::laplacian_rl::TradingAction;
+use forex_pattern_reconstruction::multi_currency::MultiCurrencyManager;
+use forex_pattern_reconstruction::protocol::client::ControllerClient;
+use forex_pattern_reconstruction::protocol::server::{self, ApiState};
+use forex_pattern_reconstruction::protocol::{
+    CommandStatus, RemoteSystemStatus, SystemMetrics, TradingCommand,
+};
+use forex_pattern_reconstruction::trading_windows::TradingWindowsConfig;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 CONTROLLER ↔ SERVER PROTOCOL TEST");
+    println!("====================================");
+    println!();
+
+    let manager = Arc::new(seeded_manager().await?);
+    let status = RemoteSystemStatus {
+        status: "running".to_string(),
+        uptime: 0,
+        active_pairs: manager.active_pairs.clone(),
+        total_trades: 0,
+        profit_loss: 0.0,
+        correlation_opportunities: Vec::new(),
+        system_metrics: SystemMetrics::default(),
+        feed_health: None,
+    };
+
+    let (address, serving) = warp::serve(server::routes(ApiState::new(manager.clone(), status)))
+        .bind_ephemeral(([127, 0, 0, 1], 0));
+    tokio::spawn(serving);
+    let client = ControllerClient::new(&format!("http://{}", address));
+    println!("🌐 Server listening on {}", client.endpoint());
+
+    // Test 1: health and status
+    println!("📊 Test 1: Health and status");
+    ensure!(client.health().await?, "health check failed");
+    let status = client.fetch_status().await?;
+    ensure!(status.status == "running", "unexpected status {}", status.status);
+    ensure!(status.active_pairs.len() == 2, "expected 2 active pairs");
+    println!("✅ Status round-trips ({} pairs)", status.active_pairs.len());
+
+    // Test 2: portfolio reflects positions opened through the manager
+    println!("📊 Test 2: Portfolio snapshot");
+    let portfolio = client.fetch_portfolio().await?;
+    ensure!(portfolio.positions.len() == 2, "expected 2 positions, got {}", portfolio.positions.len());
+    ensure!(portfolio.margin_used > 0.0, "margin should be in use");
+    ensure!(portfolio.currency_exposure.iter().any(|e| e.currency == "EUR"), "missing EUR exposure");
+    println!("✅ Equity {:.2} with {} positions", portfolio.equity, portfolio.positions.len());
+
+    // Test 3: pause / resume
+    println!("📊 Test 3: Pause and resume");
+    let response = client.send_command(&TradingCommand::new("pause").with_pair("eurusd")).await?;
+    ensure!(response.is_success(), "pause failed: {}", response.message);
+    ensure!(manager.pairs.read().await["EURUSD"].paused, "EURUSD not paused");
+    let response = client.send_command(&TradingCommand::new("resume").with_pair("EURUSD")).await?;
+    ensure!(response.is_success(), "resume failed: {}", response.message);
+    ensure!(!manager.pairs.read().await["EURUSD"].paused, "EURUSD still paused");
+    let response = client.send_command(&TradingCommand::new("pause").with_pair("XXXYYY")).await?;
+    ensure!(response.status == CommandStatus::Error, "unknown pair should fail");
+    println!("✅ Pause/resume confirmed, unknown pair rejected");
+
+    // Test 4: flatten one pair, then everything
+    println!("📊 Test 4: Flatten");
+    let response = client.send_command(&TradingCommand::new("flatten").with_pair("EURUSD")).await?;
+    ensure!(response.is_success(), "flatten failed: {}", response.message);
+    ensure!(client.fetch_portfolio().await?.positions.len() == 1, "EURUSD not closed");
+    let response = client.send_command(&TradingCommand::new("flatten").with_pair("all")).await?;
+    ensure!(response.is_success(), "flatten all failed: {}", response.message);
+    let portfolio = client.fetch_portfolio().await?;
+    ensure!(portfolio.positions.is_empty(), "positions left after flatten all");
+    ensure!(portfolio.total_trades == 2, "expected 2 closed trades, got {}", portfolio.total_trades);
+    println!("✅ Flattened, realized P&L {:.2}", portfolio.realized_pnl);
+
+    // Test 5: risk limits
+    println!("📊 Test 5: Set risk");
+    let response = client.send_command(&TradingCommand::new("set_risk").with_parameter("max_drawdown", "5%")).await?;
+    ensure!(response.is_success(), "set-risk failed: {}", response.message);
+    ensure!(manager.risk_limits.read().await.max_drawdown_pct == 5.0, "risk limit not applied");
+    let response = client.send_command(&TradingCommand::new("set_risk").with_parameter("max_drawdown", "lots")).await?;
+    ensure!(response.status == CommandStatus::Error, "invalid percentage should fail");
+    println!("✅ Max drawdown set to 5%, invalid value rejected");
+
+    // Test 6: unknown commands are acknowledged, everything is audited
+    println!("📊 Test 6: Audit trail");
+    let response = client.send_command(&TradingCommand::new("reticulate_splines")).await?;
+    ensure!(response.status == CommandStatus::Acknowledged, "unknown action should be acknowledged");
+    let audit = client.fetch_audit().await?;
+    let failures = audit.iter().filter(|entry| !entry.success).count();
+    if audit.len() != 7 || failures != 2 {
+        bail!("expected 7 audit entries with 2 failures, got {} with {}", audit.len(), failures);
+    }
+    println!("✅ {} audit entries ({} failures)", audit.len(), failures);
+
+    println!();
+    println!("🎉 All protocol tests passed");
+    Ok(())
+}
+
+/// Manager with two priced pairs and an open position in each
+async fn seeded_manager() -> Result<MultiCurrencyManager> {
+    let mut manager = MultiCurrencyManager::new().with_trading_windows(TradingWindowsConfig::unrestricted());
+    manager.initialize_major_pairs().await?;
+    manager.active_pairs.retain(|pair| pair == "EURUSD" || pair == "GBPUSD");
+
+    {
+        let mut pairs = manager.pairs.write().await;
+        pairs.retain(|pair, _| pair == "EURUSD" || pair == "GBPUSD");
+        for (pair, price) in [("EURUSD", 1.1000), ("GBPUSD", 1.3000)] {
+            let state = pairs.get_mut(pair).expect("pair initialized");
+            state.historical_data = vec![ForexDataPoint {
+                timestamp: Utc::now() - Duration::hours(1),
+                open: price,
+                high: price,
+                low: price,
+                close: price,
+                volume: None,
+            }];
+        }
+    }
+
+    let actions = HashMap::from([
+        ("EURUSD".to_string(), vec![TradingAction::Buy { size: 10 }]),
+        ("GBPUSD".to_string(), vec![TradingAction::Sell { size: 5 }]),
+    ]);
+    manager.execute_actions(&actions).await;
+    Ok(manager)
+}
@@ -0,0 +1,119 @@
+//! # Purged Cross-Validation Test
+//!
+//! Check purged k-fold splits keep training bars away from test blocks, and that
+//! a persistent symmetry validates out of sample while an early burst does not
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::f64::consts::PI;
+
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::ids::CycleId;
+use forex_pattern_reconstruction::patterns::HiddenCycle;
+use forex_pattern_reconstruction::synthetic::fixtures::rotational;
+use forex_pattern_reconstruction::validation::{validate_temporal_symmetries, CrossValidationConfig, PurgedKFold, SymmetryValidation};
+
+/// Hourly bars on a 24-bar cycle throughout and a 30-bar cycle over the first `burst` bars only
+fn bars(count: usize, burst: usize, seed: u64) -> Vec<ForexDataPoint> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    (0..count)
+        .map(|i| {
+            let t = i as f64;
+            let burst = if i < burst { 0.003 * (2.0 * PI * t / 30.0).sin() } else { 0.0 };
+            let close = 1.1 * (1.0 + 0.002 * (2.0 * PI * t / 24.0).sin() + burst) + rng.gen_range(-0.0003..0.0003);
+            ForexDataPoint { timestamp: start + Duration::hours(i as i64), open: close, high: close, low: close, close, volume: None }
+        })
+        .collect()
+}
+
+fn cycle(period: u32) -> HiddenCycle {
+    HiddenCycle { id: CycleId::new(), name: format!("{}-Bar Cycle", period), period, confidence: 0.9, amplitude: 0.002, phase: 0.0, p_value: None, track: None }
+}
+
+fn check_splits(validation: &SymmetryValidation) -> Result<()> {
+    let bars = validation.bars;
+    let mut covered = 0;
+    for split in &validation.splits {
+        ensure!(split.test.start == covered, "fold {} test block starts at {}", split.fold, split.test.start);
+        covered = split.test.end;
+        for segment in &split.train {
+            ensure!(segment.end + validation.purge_bars <= split.test.start || segment.start >= split.test.end + validation.purge_bars + validation.embargo_bars,
+                    "fold {} trains on {:?} next to test {:?}", split.fold, segment, split.test);
+        }
+        let dropped = bars - split.test.len() - split.train_len();
+        ensure!(dropped <= 2 * validation.purge_bars + validation.embargo_bars, "fold {} drops {} bars", split.fold, dropped);
+    }
+    ensure!(covered == bars, "test blocks cover {} of {} bars", covered, bars);
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    println!("🔬 Purged Cross-Validation Test");
+    println!("===============================");
+    println!();
+
+    // Test 1: purged and embargoed splits
+    println!("📊 Test 1: Purged splits");
+    let kfold = PurgedKFold::new(5, 24, 10)?;
+    let splits = kfold.split(1000)?;
+    ensure!(splits.len() == 5, "{} folds", splits.len());
+    ensure!(splits[0].train.len() == 1 && splits[0].train[0] == (234..1000), "first fold trains on {:?}", splits[0].train);
+    ensure!(splits[2].train == vec![0..376, 634..1000], "middle fold trains on {:?}", splits[2].train);
+    ensure!(splits[4].train.len() == 1 && splits[4].train[0] == (0..776), "last fold trains on {:?}", splits[4].train);
+    let plain = PurgedKFold::new(5, 0, 0)?.split(1000)?;
+    ensure!(plain.iter().all(|split| split.train_len() + split.test.len() == 1000), "without purge every bar is used");
+    println!("   ✅ 5 contiguous test blocks; 24 bars purged on each side and 10 more embargoed after");
+
+    // Test 2: a symmetry that holds throughout persists, one fitted to an early burst does not
+    println!("📊 Test 2: Out-of-sample persistence");
+    let data = bars(2000, 400, 1);
+    let (daily, burst) = (rotational(24), rotational(30));
+    let validation = validate_temporal_symmetries(&data, &[daily.clone(), burst.clone()], &[], &CrossValidationConfig::default())?;
+    validation.print_persistence();
+    check_splits(&validation)?;
+    let held = validation.get(&daily.id).expect("24-bar symmetry validated");
+    let faded = validation.get(&burst.id).expect("30-bar symmetry validated");
+    ensure!(held.persistent && held.persistence.is_some_and(|p| p >= 0.7), "24-bar persistence {:?}", held.persistence);
+    ensure!(held.sign_agreement == Some(1.0), "24-bar sign agreement {:?}", held.sign_agreement);
+    ensure!(!faded.persistent && faded.persistence.is_some_and(|p| p < 0.5), "30-bar persistence {:?}", faded.persistence);
+    ensure!(faded.folds[1..].iter().all(|fold| fold.in_sample.zip(fold.out_of_sample).is_some_and(|(is, oos)| is > oos)),
+            "the burst scores better in sample than out of it");
+    ensure!(held.detected_score == daily.validation_score && held.folds.len() == 5, "detected score carried over");
+    ensure!(validation.persistent().count() == 1, "{} persistent symmetries", validation.persistent().count());
+    println!("   ✅ 24-bar persistence {:.2}, burst persistence {:.2}",
+             held.persistence.unwrap_or_default(), faded.persistence.unwrap_or_default());
+
+    // Test 3: the purge covers the longest detected period
+    println!("📊 Test 3: Purge around cycle periods");
+    let validation = validate_temporal_symmetries(&data, std::slice::from_ref(&daily), &[cycle(24), cycle(90)], &CrossValidationConfig::default())?;
+    ensure!(validation.purge_bars == 90 && validation.embargo_bars == 20, "purge {} embargo {}", validation.purge_bars, validation.embargo_bars);
+    check_splits(&validation)?;
+    let doubled = CrossValidationConfig { purge_periods: 2.0, folds: 4, ..CrossValidationConfig::default() };
+    let validation = validate_temporal_symmetries(&data, std::slice::from_ref(&daily), &[cycle(90)], &doubled)?;
+    ensure!(validation.purge_bars == 180 && validation.splits.len() == 4, "purge {} over {} folds", validation.purge_bars, validation.splits.len());
+    check_splits(&validation)?;
+    let none = validate_temporal_symmetries(&data, &[], &[], &CrossValidationConfig::default())?;
+    ensure!(none.symmetries.is_empty() && none.purge_bars == 0, "no symmetries to validate");
+    println!("   ✅ 90-bar cycle purges 90 bars, or 180 at two periods");
+
+    // Test 4: settings and short histories
+    println!("📊 Test 4: Settings");
+    ensure!(PurgedKFold::new(1, 0, 0).is_err(), "one fold accepted");
+    ensure!(kfold.split(3).is_err(), "3 bars split into 5 folds");
+    let bad = CrossValidationConfig { embargo_fraction: 1.0, ..CrossValidationConfig::default() };
+    ensure!(validate_temporal_symmetries(&data, std::slice::from_ref(&daily), &[], &bad).is_err(), "whole-history embargo accepted");
+    let short = validate_temporal_symmetries(&data[..60], std::slice::from_ref(&daily), &[], &CrossValidationConfig::default())?;
+    ensure!(short.symmetries[0].persistence.is_none() && !short.symmetries[0].persistent, "60 bars scored a 24-bar symmetry");
+    let config: CrossValidationConfig = serde_json::from_str(r#"{"folds":3}"#)?;
+    ensure!(config.folds == 3 && config.purge_periods == 1.0 && config.min_pairs == 10, "settings load over defaults");
+    let json = serde_json::to_value(&validate_temporal_symmetries(&data, &[daily], &[], &config)?)?;
+    ensure!(json["symmetries"][0]["folds"].as_array().map(Vec::len) == Some(3), "fold scores serialized");
+    println!("   ✅ Bad settings rejected; unscorable folds report no persistence");
+
+    println!();
+    println!("🎉 All purged cross-validation tests passed");
+    Ok(())
+}
@@ -0,0 +1,156 @@
+//! # Q Backend Test
+//!
+//! Train the tabular and neural value functions on the same task and check the
+//! neural one generalizes at a fixed size and both round-trip through snapshots
+
+use anyhow::{ensure, Result};
+use chrono::Utc;
+
+use forex_pattern_reconstruction::anomaly::{AnomalySeverity, AnomalyType, DetectedAnomaly, MarketContext};
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::laplacian_rl::backend::{state_features, STATE_FEATURES};
+use forex_pattern_reconstruction::laplacian_rl::{
+    LaplacianQLearningAgent, LaplacianQLearningConfig, QBackendKind, QTableSnapshot, TradingAction,
+};
+use forex_pattern_reconstruction::multi_currency::{CurrencyPairConfig, CurrencyPairState};
+
+fn novel_pattern(emergence_confidence: f64) -> DetectedAnomaly {
+    DetectedAnomaly {
+        id: "novel_anomaly_test".into(),
+        timestamp: Utc::now(),
+        anomaly_type: AnomalyType::NovelPattern { pattern_signature: "test".to_string(), emergence_confidence },
+        severity: AnomalySeverity::Medium,
+        confidence: 0.8,
+        deviation_magnitude: emergence_confidence,
+        affected_symmetries: Vec::new(),
+        affected_cycles: Vec::new(),
+        market_context: MarketContext {
+            session: "London".to_string(),
+            volatility_regime: "Normal".to_string(),
+            trend_direction: "Sideways".to_string(),
+            recent_events: Vec::new(),
+            event_proximity: 0.0,
+        },
+        trading_signal: None,
+    }
+}
+
+/// Buying pays above one half, selling below; holding pays nothing
+fn reward(emergence_confidence: f64, action: &TradingAction) -> f64 {
+    let direction = if emergence_confidence > 0.5 { 1.0 } else { -1.0 };
+    match action {
+        TradingAction::Buy { .. } => direction,
+        TradingAction::Sell { .. } => -direction,
+        _ => 0.0,
+    }
+}
+
+fn agent(backend: QBackendKind) -> Result<LaplacianQLearningAgent> {
+    LaplacianQLearningAgent::new(LaplacianQLearningConfig { exploration_rate: 0.0, backend, ..LaplacianQLearningConfig::default() })
+}
+
+/// Train every action of the states with confidence 0.1 to 0.9, skipping 0.5
+fn train(agent: &mut LaplacianQLearningAgent, bar: &ForexDataPoint, epochs: usize) -> Result<()> {
+    let actions = [TradingAction::Hold, TradingAction::Buy { size: 10 }, TradingAction::Sell { size: 10 }];
+    for _ in 0..epochs {
+        for tenth in [1, 2, 3, 4, 6, 7, 8, 9] {
+            let confidence = tenth as f64 / 10.0;
+            let state = agent.anomaly_to_state(&novel_pattern(confidence), bar)?;
+            for action in &actions {
+                agent.update_q_value(&state, action.clone(), reward(confidence, action), &state, true)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 Q BACKEND TEST");
+    println!("=================");
+    println!();
+
+    let bar = ForexDataPoint { timestamp: Utc::now(), open: 1.1, high: 1.101, low: 1.099, close: 1.1, volume: None };
+
+    // Test 1: configuration picks the backend, tabular by default
+    println!("📊 Test 1: configuration");
+    ensure!(LaplacianQLearningConfig::default().backend == QBackendKind::Tabular, "tabular by default");
+    let mut table: toml::Table = toml::from_str(&toml::to_string(&LaplacianQLearningConfig::default())?)?;
+    table.remove("backend");
+    table.remove("neural");
+    let legacy: LaplacianQLearningConfig = toml::from_str(&toml::to_string(&table)?)?;
+    ensure!(legacy.backend == QBackendKind::Tabular, "configurations without a backend stay tabular");
+    table.insert("backend".to_string(), toml::Value::String("neural".to_string()));
+    let neural: LaplacianQLearningConfig = toml::from_str(&toml::to_string(&table)?)?;
+    ensure!(LaplacianQLearningAgent::new(neural)?.q_backend().name() == "neural", "backend = \"neural\" selects the network");
+    let state = agent(QBackendKind::Tabular)?.anomaly_to_state(&novel_pattern(0.7), &bar)?;
+    ensure!(state_features(&state)[4] == 0.7 && state_features("s_gap") == [0.0; STATE_FEATURES], "features read back from {}", state);
+    println!("   ✅ Tabular default, neural on request, features decoded from {}", state);
+
+    // Test 2: the network generalizes to unseen states, the table cannot
+    println!("📊 Test 2: generalization");
+    let mut tabular = agent(QBackendKind::Tabular)?;
+    let mut network = agent(QBackendKind::Neural)?;
+    train(&mut tabular, &bar, 300)?;
+    train(&mut network, &bar, 500)?;
+    for (confidence, expected) in [(0.8, TradingAction::Buy { size: 10 }), (0.2, TradingAction::Sell { size: 10 })] {
+        let anomaly = novel_pattern(confidence);
+        let state = tabular.anomaly_to_state(&anomaly, &bar)?;
+        ensure!(tabular.choose_action(&state, &anomaly)? == expected, "table learned the trained state {}", confidence);
+        ensure!(network.choose_action(&state, &anomaly)? == expected, "network learned the trained state {}", confidence);
+    }
+    for (confidence, expected) in [(0.95, TradingAction::Buy { size: 10 }), (0.05, TradingAction::Sell { size: 10 })] {
+        let anomaly = novel_pattern(confidence);
+        let state = network.anomaly_to_state(&anomaly, &bar)?;
+        ensure!(network.choose_action(&state, &anomaly)? == expected, "network generalizes to unseen {}", confidence);
+        ensure!(tabular.choose_action(&state, &anomaly)? == TradingAction::Hold, "the table knows nothing of {}", confidence);
+    }
+    let unseen = network.anomaly_to_state(&novel_pattern(0.95), &bar)?;
+    let buy = network.q_value(&unseen, &TradingAction::Buy { size: 10 });
+    println!("   ✅ Unseen confidence 0.95: network Q(buy) = {:.2}, table Q(buy) = 0", buy);
+
+    // Test 3: the table grows with the states, the network does not
+    println!("📊 Test 3: size");
+    let (table_before, network_before) = (tabular.q_backend().size(), network.q_backend().size());
+    for hundredth in 0..100 {
+        let anomaly = novel_pattern(hundredth as f64 / 100.0);
+        for agent in [&mut tabular, &mut network] {
+            let state = agent.anomaly_to_state(&anomaly, &bar)?;
+            agent.update_q_value(&state, TradingAction::Hold, 0.0, &state, true)?;
+        }
+    }
+    ensure!(tabular.q_backend().size() > table_before + 90, "table grew to {}", tabular.q_backend().size());
+    ensure!(network.q_backend().size() == network_before, "network stays at {} parameters", network_before);
+    println!("   ✅ Table {} → {} values, network fixed at {} parameters", table_before, tabular.q_backend().size(), network_before);
+
+    // Test 4: snapshots restore both backends
+    println!("📊 Test 4: snapshots");
+    let snapshot: QTableSnapshot = serde_json::from_str(&serde_json::to_string(&network.q_table_snapshot())?)?;
+    ensure!(snapshot.network.is_some() && snapshot.entries.is_empty(), "the network is saved as weights");
+    let mut restored = agent(QBackendKind::Neural)?;
+    restored.restore_q_table(snapshot);
+    let probe = network.anomaly_to_state(&novel_pattern(0.33), &bar)?;
+    for action in [TradingAction::Hold, TradingAction::Buy { size: 10 }, TradingAction::Sell { size: 10 }] {
+        ensure!(restored.q_value(&probe, &action) == network.q_value(&probe, &action), "restored weights give the same values");
+    }
+    let tabular_snapshot = tabular.q_table_snapshot();
+    ensure!(tabular_snapshot.network.is_none() && !serde_json::to_string(&tabular_snapshot)?.contains("network"), "tabular files unchanged");
+    let mut distilled = agent(QBackendKind::Neural)?;
+    distilled.restore_q_table(tabular_snapshot);
+    let anomaly = novel_pattern(0.9);
+    let state = distilled.anomaly_to_state(&anomaly, &bar)?;
+    ensure!(distilled.choose_action(&state, &anomaly)? == TradingAction::Buy { size: 10 }, "network fitted to saved table values");
+    println!("   ✅ Weights round-trip; a table migrates into a network");
+
+    // Test 5: pairs learn with the configured backend
+    println!("📊 Test 5: currency pairs");
+    let mut config = CurrencyPairConfig::default();
+    config.pipeline.rl.backend = QBackendKind::Neural;
+    let state = CurrencyPairState::new(config).await?;
+    ensure!(state.rl_agent.q_backend().name() == "neural", "pair agent uses the network");
+    println!("   ✅ Pair agent backend: {}", state.rl_agent.q_backend().name());
+
+    println!();
+    println!("🎉 All Q backend tests passed");
+    Ok(())
+}
@@ -0,0 +1,159 @@
+//! # Regime Detection Test
+//!
+//! Check the hidden Markov model names and places ranging, trending and crisis
+//! stretches without look-ahead, and that detector and report use the regimes
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rand_distr::StandardNormal;
+
+use forex_pattern_reconstruction::anomaly::{AnomalyDetectionConfig, AnomalyType, TemporalAnomalyDetector};
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::regimes::{dominant_regime, regime_at, MarketRegime, RegimeConfig, RegimeModel};
+use forex_pattern_reconstruction::report::analysis;
+use forex_pattern_reconstruction::symmetry::{SymmetryDetector, SymmetryDetectorConfig};
+
+/// (regime, bars, drift per bar, volatility per bar)
+const STRETCHES: [(MarketRegime, usize, f64, f64); 7] = [
+    (MarketRegime::Ranging, 400, 0.0, 0.0005),
+    (MarketRegime::Trending, 300, 0.0004, 0.0007),
+    (MarketRegime::Crisis, 150, 0.0, 0.003),
+    (MarketRegime::Ranging, 400, 0.0, 0.0005),
+    (MarketRegime::Trending, 300, 0.0004, 0.0007),
+    (MarketRegime::Crisis, 150, 0.0, 0.003),
+    (MarketRegime::Ranging, 300, 0.0, 0.0005),
+];
+
+/// Bars of every stretch in turn, with the regime each bar was drawn from
+fn bars(seed: u64) -> (Vec<ForexDataPoint>, Vec<MarketRegime>) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let mut close: f64 = 1.1;
+    let (mut data, mut regimes) = (Vec::new(), Vec::new());
+    for (regime, count, drift, volatility) in STRETCHES {
+        for _ in 0..count {
+            let open = close;
+            close = open * (drift + volatility * rng.sample::<f64, _>(StandardNormal)).exp();
+            let wick = open * volatility * 0.5 * rng.gen_range(0.0..1.0);
+            data.push(ForexDataPoint {
+                timestamp: start + Duration::hours(data.len() as i64),
+                open,
+                high: open.max(close) + wick,
+                low: open.min(close) - wick,
+                close,
+                volume: None,
+            });
+            regimes.push(regime);
+        }
+    }
+    (data, regimes)
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 Regime Detection Test");
+    println!("========================");
+    println!();
+
+    let (data, truth) = bars(7);
+    let config = RegimeConfig::default();
+
+    // Test 1: three states named after the stretches
+    println!("📊 Test 1: Three-state fit");
+    let model = RegimeModel::fit(&data, &config)?;
+    model.print_regimes();
+    ensure!(model.regimes() == vec![MarketRegime::Ranging, MarketRegime::Trending, MarketRegime::Crisis]
+            || model.regimes() == vec![MarketRegime::Trending, MarketRegime::Ranging, MarketRegime::Crisis],
+            "states {:?}", model.regimes());
+    let crisis = &model.states[2];
+    ensure!(crisis.volatility > 3.0 * model.states[0].volatility, "crisis volatility {:.5}", crisis.volatility);
+    ensure!(model.transition.iter().all(|row| (row.iter().sum::<f64>() - 1.0).abs() < 1e-9), "transition rows sum to 1");
+    ensure!((0..3).all(|s| model.expected_duration(s) > 20.0), "regimes persist");
+    let path = model.probabilities(&data);
+    ensure!(path.len() == data.len() - config.window, "{} probabilities", path.len());
+    ensure!(path.iter().all(|p| (p.states.iter().sum::<f64>() - 1.0).abs() < 1e-9 && p.probability > 0.0), "probabilities sum to 1");
+    // Away from the edges of each stretch, where the feature window straddles two
+    let offset = data.len() - path.len();
+    let (mut agree, mut checked) = (0, 0);
+    let mut start = 0;
+    for (_, count, _, _) in STRETCHES {
+        for i in (start + 2 * config.window).max(offset)..start + count - config.window {
+            checked += 1;
+            agree += (path[i - offset].regime == truth[i]) as usize;
+        }
+        start += count;
+    }
+    let accuracy = agree as f64 / checked as f64;
+    ensure!(accuracy > 0.85, "{:.1}% of bars in their regime", accuracy * 100.0);
+    println!("   ✅ {}; {:.1}% of stretch bars labelled correctly", model.summary(), accuracy * 100.0);
+
+    // Test 2: other state counts and bad settings
+    println!("📊 Test 2: State counts");
+    let two = RegimeModel::fit(&data, &RegimeConfig { states: 2, ..config.clone() })?;
+    ensure!(two.regimes().contains(&MarketRegime::Ranging) && !two.regimes().contains(&MarketRegime::Crisis), "two states {:?}", two.regimes());
+    let four = RegimeModel::fit(&data, &RegimeConfig { states: 4, ..config.clone() })?;
+    ensure!(four.states.len() == 4 && four.states[3].regime == MarketRegime::Crisis, "four states {:?}", four.regimes());
+    ensure!(four.log_likelihood >= model.log_likelihood - 1.0, "a fourth state fits no worse");
+    ensure!(RegimeModel::fit(&data, &RegimeConfig { states: 5, ..config.clone() }).is_err(), "five states accepted");
+    ensure!(RegimeModel::fit(&data, &RegimeConfig { states: 1, ..config.clone() }).is_err(), "one state accepted");
+    ensure!(RegimeModel::fit(&data[..100], &config).is_err(), "short history accepted");
+    println!("   ✅ 2 states {:?}, 4 states {:?}; 1, 5 states and short histories rejected", two.regimes(), four.regimes());
+
+    // Test 3: predicted probabilities use only earlier bars
+    println!("📊 Test 3: Predicted probabilities");
+    let predicted = model.predicted_probabilities(&data);
+    let prefix = model.predicted_probabilities(&data[..1000]);
+    ensure!(predicted[..prefix.len()] == prefix[..], "later bars changed earlier predictions");
+    let crisis_start = data[400 + 300 + 50].timestamp;
+    let crisis_end = data[400 + 300 + 140].timestamp;
+    ensure!(regime_at(&path, crisis_end).map(|p| p.regime) == Some(MarketRegime::Crisis), "regime at a crisis bar");
+    ensure!(regime_at(&path, data[0].timestamp).is_none(), "a regime before the first full window");
+    let (regime, share) = dominant_regime(&path, crisis_start, crisis_end).expect("a dominant regime");
+    ensure!(regime == MarketRegime::Crisis && share > 0.8, "{} for {:.0}% of the crisis", regime, share * 100.0);
+    println!("   ✅ {} predictions unchanged by later bars; crisis stretch {:.0}% crisis", prefix.len(), share * 100.0);
+
+    // Test 4: the detector's volatility baseline follows the regime of history bars
+    println!("📊 Test 4: Regime-conditioned baseline");
+    let mut detector = TemporalAnomalyDetector::new(Vec::new(), Vec::new(), &data, AnomalyDetectionConfig::default())?;
+    ensure!(detector.regime_model().is_some() && detector.regime_path().len() == path.len(), "detector fits regimes");
+    let ratio = |i: usize| detector.regime_expected_volatility(data[i].timestamp).mean / detector.expected_volatility(data[i].timestamp).mean;
+    let (calm_bar, crisis_bar) = (400 + 300 + 150 + 200, 400 + 300 + 100);
+    let (calm_ratio, crisis_ratio) = (ratio(calm_bar), ratio(crisis_bar));
+    ensure!(crisis_ratio > 2.0 && calm_ratio < 0.8, "baseline ratios {:.2} in a crisis, {:.2} when calm", crisis_ratio, calm_ratio);
+    ensure!(detector.conditional_expected_volatility(data[crisis_bar].timestamp).mean == detector.expected_volatility(data[crisis_bar].timestamp).mean,
+            "the conditional baseline of history bars is unchanged");
+    let anomalies = detector.detect_anomalies(&data).await?;
+    let spikes_in_crisis = anomalies.iter()
+        .filter(|a| matches!(a.anomaly_type, AnomalyType::VolatilitySpike { .. }))
+        .filter(|a| data.iter().position(|bar| bar.timestamp == a.timestamp).is_some_and(|i| truth[i] == MarketRegime::Crisis))
+        .count();
+    let unconditional_spikes = data.iter().enumerate()
+        .filter(|(i, bar)| {
+            let expected = detector.expected_volatility(bar.timestamp);
+            truth[*i] == MarketRegime::Crisis
+                && (bar.high - bar.low) / bar.close > expected.mean + detector.sensitivity_threshold().max(0.7) * expected.std_dev
+        })
+        .count();
+    ensure!(spikes_in_crisis * 3 < unconditional_spikes, "{} spikes in crisis bars, {} against the unconditional baseline", spikes_in_crisis, unconditional_spikes);
+    println!("   ✅ Baseline ×{:.2} in a crisis, ×{:.2} when calm; {} crisis spikes instead of {}",
+             crisis_ratio, calm_ratio, spikes_in_crisis, unconditional_spikes);
+
+    // Test 5: the report annotates symmetries with their regime
+    println!("📊 Test 5: Report annotation");
+    let symmetries = SymmetryDetector::new(SymmetryDetectorConfig::default())?.detect(&data);
+    ensure!(!symmetries.is_empty(), "no symmetries to report");
+    let html = analysis::render_analysis_html("EURUSD", "1H", &data, &symmetries, &[], &path);
+    ensure!(html.contains("<th>Regime</th>"), "no regime column");
+    let annotated = ["Trending (", "Ranging (", "Crisis ("].iter().map(|regime| html.matches(regime).count()).sum::<usize>();
+    ensure!(annotated == symmetries.len(), "{} of {} symmetries annotated", annotated, symmetries.len());
+    ensure!(html.contains("Ranging ("), "the last stretch ranges");
+    let bare = analysis::render_analysis_html("EURUSD", "1H", &data, &symmetries, &[], &[]);
+    ensure!(bare.matches("<td>—</td>").count() == symmetries.len(), "symmetries without a regime model");
+    println!("   ✅ {} symmetries annotated", symmetries.len());
+
+    println!();
+    println!("🎉 All regime detection tests passed");
+    Ok(())
+}
@@ -0,0 +1,118 @@
+//! # Remote Commands Test
+//!
+//! Parse and run the operator commands - pause, resume, flatten and set-risk -
+//! through the controller API, and check each one lands in the audit log file
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, Utc};
+use std::collections::HashMap;
+
+use forex_pattern_reconstruction::audit::{AuditEntry, AuditLog};
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::laplacian_rl::TradingAction;
+use forex_pattern_reconstruction::multi_currency::{parse_percent, ControlCommand, MultiCurrencyManager};
+use forex_pattern_reconstruction::portfolio::orders::OrderStatus;
+use forex_pattern_reconstruction::protocol::server::handle_command;
+use forex_pattern_reconstruction::protocol::TradingCommand;
+use forex_pattern_reconstruction::trading_windows::TradingWindowsConfig;
+
+async fn set_close(manager: &MultiCurrencyManager, close: f64) {
+    let mut pairs = manager.pairs.write().await;
+    let state = pairs.get_mut("EURUSD").expect("pair initialized");
+    state.historical_data = vec![ForexDataPoint { timestamp: Utc::now() - Duration::hours(1), open: close, high: close, low: close, close, volume: None }];
+}
+
+fn set_risk(value: &str) -> TradingCommand {
+    TradingCommand::new("set-risk").with_parameter("max_drawdown", value)
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 REMOTE COMMANDS TEST");
+    println!("=======================");
+    println!();
+
+    let audit_path = std::env::temp_dir().join(format!("remote-commands-test-{}.jsonl", std::process::id()));
+    let result = run(&audit_path).await;
+    let _ = std::fs::remove_file(&audit_path);
+    result?;
+
+    println!();
+    println!("🎉 All remote commands tests passed");
+    Ok(())
+}
+
+async fn run(audit_path: &std::path::Path) -> Result<()> {
+    // Test 1: parsing
+    println!("📊 Test 1: Parsing");
+    ensure!(parse_percent("5%")? == 5.0 && parse_percent(" 2.5 ")? == 2.5, "percentages");
+    ensure!(parse_percent("lots").is_err(), "a non-number is not a percentage");
+    let parameters = HashMap::from([("max_drawdown".to_string(), "7.5%".to_string())]);
+    for action in ["set-risk", "set_risk"] {
+        let command = ControlCommand::parse(action, None, &parameters)?;
+        ensure!(matches!(command, Some(ControlCommand::SetRisk { max_drawdown_pct }) if max_drawdown_pct == 7.5), "{} parsed as {:?}", action, command);
+    }
+    ensure!(ControlCommand::parse("set-risk", None, &HashMap::new()).is_err(), "set-risk without a limit");
+    ensure!(ControlCommand::parse("pause", None, &HashMap::new()).is_err(), "pause without a pair");
+    ensure!(matches!(ControlCommand::parse("flatten", Some("all"), &HashMap::new())?, Some(ControlCommand::Flatten { pair: None })), "flatten all");
+    ensure!(ControlCommand::parse("status", None, &HashMap::new())?.is_none(), "not a control command");
+    println!("   ✅ set-risk, pause and flatten parsed");
+
+    let mut manager = MultiCurrencyManager::new()
+        .with_trading_windows(TradingWindowsConfig::unrestricted())
+        .with_audit_log(AuditLog::with_file(audit_path)?);
+    manager.initialize_major_pairs().await?;
+    manager.active_pairs.retain(|pair| pair == "EURUSD");
+    manager.pairs.write().await.retain(|pair, _| pair == "EURUSD");
+    set_close(&manager, 1.1000).await;
+    let buy = HashMap::from([("EURUSD".to_string(), vec![TradingAction::Buy { size: 1 }])]);
+
+    // Test 2: pause and resume
+    println!("📊 Test 2: Pause and resume");
+    ensure!(handle_command(&manager, TradingCommand::new("pause").with_pair("eurusd")).await.is_success(), "pause failed");
+    ensure!(manager.pairs.read().await["EURUSD"].paused, "pair not paused");
+    ensure!(!handle_command(&manager, TradingCommand::new("pause").with_pair("XXXYYY")).await.is_success(), "unknown pair paused");
+    ensure!(handle_command(&manager, TradingCommand::new("resume").with_pair("EURUSD")).await.is_success(), "resume failed");
+    ensure!(!manager.pairs.read().await["EURUSD"].paused, "pair not resumed");
+    println!("   ✅ Paused and resumed; unknown pair refused");
+
+    // Test 3: set-risk caps the drawdown new entries may open into
+    println!("📊 Test 3: Set risk");
+    manager.execute_actions(&buy).await;
+    set_close(&manager, 1.0990).await;
+    for invalid in ["0", "150%", "lots"] {
+        ensure!(!handle_command(&manager, set_risk(invalid)).await.is_success(), "set-risk {} accepted", invalid);
+    }
+    ensure!(manager.risk_limits.read().await.max_drawdown_pct == 10.0, "a refused set-risk changed the limit");
+    ensure!(handle_command(&manager, set_risk("0.0001%")).await.is_success(), "set-risk failed");
+    manager.execute_actions(&buy).await;
+    let refused = manager.portfolio.read().await.orders().recent(1);
+    ensure!(refused[0].status == OrderStatus::Rejected && refused[0].reason.as_deref().is_some_and(|reason| reason.contains("over limit")),
+            "entry over the drawdown limit went through: {}", refused[0].summary());
+    ensure!(handle_command(&manager, set_risk("10")).await.is_success(), "set-risk failed");
+    manager.execute_actions(&buy).await;
+    ensure!(manager.portfolio.read().await.orders().recent(1)[0].status != OrderStatus::Rejected, "entry under the restored limit refused");
+    println!("   ✅ Limit applied, out-of-range values refused");
+
+    // Test 4: flatten closes the pair
+    println!("📊 Test 4: Flatten");
+    ensure!(handle_command(&manager, TradingCommand::new("flatten").with_pair("ALL")).await.is_success(), "flatten failed");
+    ensure!(manager.portfolio_snapshot().await.positions.is_empty(), "positions left after flatten");
+    let response = handle_command(&manager, TradingCommand::new("flatten").with_pair("EURUSD")).await;
+    ensure!(response.is_success() && response.message.contains("already flat"), "flatten of a flat pair: {}", response.message);
+    println!("   ✅ Flattened");
+
+    // Test 5: every command is audited, in memory and in the file
+    println!("📊 Test 5: Audit log");
+    let recent = manager.audit_log.recent(100);
+    let written: Vec<AuditEntry> = std::fs::read_to_string(audit_path)?
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<_, _>>()?;
+    ensure!(recent.len() == 10 && written.len() == recent.len(), "expected 10 audited commands, got {} in memory and {} on disk", recent.len(), written.len());
+    ensure!(recent.iter().filter(|entry| !entry.success).count() == 4, "the refused pause and set-risk values are audited as failures");
+    ensure!(written.iter().any(|entry| entry.command.starts_with("set-risk") && entry.success), "set-risk not in the file");
+    println!("   ✅ {} commands audited, {} failed", recent.len(), recent.iter().filter(|entry| !entry.success).count());
+
+    Ok(())
+}
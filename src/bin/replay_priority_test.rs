@@ -0,0 +1,140 @@
+//! # Replay Priority Test
+//!
+//! Check prioritized replay samples by TD error with bias-correcting weights,
+//! learns rare surprises faster than uniform replay, and resumes from checkpoints
+
+use anyhow::{ensure, Result};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use forex_pattern_reconstruction::laplacian_rl::{
+    Experience, LaplacianQLearningAgent, LaplacianQLearningConfig, PrioritizedReplay, QTableSnapshot, TradingAction,
+};
+
+fn experience(state: &str, reward: f64) -> Experience {
+    Experience {
+        state: state.to_string(),
+        action: TradingAction::Hold,
+        reward,
+        next_state: state.to_string(),
+        done: true,
+        anomaly_context: None,
+    }
+}
+
+/// Agent whose buffer holds 99 dull experiences and one rewarding one
+fn agent(priority_exponent: f64) -> Result<LaplacianQLearningAgent> {
+    let mut agent = LaplacianQLearningAgent::new(LaplacianQLearningConfig {
+        priority_exponent,
+        batch_size: 8,
+        attention_weight: 0.0,
+        ..LaplacianQLearningConfig::default()
+    })?;
+    agent.reseed(5);
+    for index in 0..99 {
+        agent.add_experience(experience(&format!("dull_{}", index % 3), 0.0));
+    }
+    agent.add_experience(experience("rare", 1.0));
+    Ok(agent)
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 REPLAY PRIORITY TEST");
+    println!("=======================");
+    println!();
+
+    // Test 1: priorities follow TD errors
+    println!("📊 Test 1: priorities");
+    let mut replay = PrioritizedReplay::new(4);
+    for index in 0..5 {
+        replay.push(experience(&format!("s{}", index), 0.0));
+    }
+    ensure!(replay.len() == 4 && replay.get(0).map(|e| e.state.as_str()) == Some("s1"), "the oldest is dropped when full");
+    ensure!(replay.entries().all(|entry| entry.priority == 1.0), "new experiences start at the largest priority");
+    replay.update_priority(0, -3.0);
+    replay.update_priority(1, 0.0);
+    ensure!((replay.entries().next().unwrap().priority - 3.001).abs() < 1e-12, "priority is |TD error| plus a floor");
+    replay.push(experience("s5", 0.0));
+    ensure!(replay.entries().last().unwrap().priority == 3.001, "and new ones take the largest seen");
+    // s2 at 0.001, s3 and s4 at 1, s5 at 3.001
+    let total = 0.001 + 1.0 + 1.0 + 3.001;
+    ensure!((replay.probability(1, 1.0) - 1.0 / total).abs() < 1e-12, "proportional at α = 1");
+    ensure!((replay.probability(0, 0.0) - 0.25).abs() < 1e-12, "uniform at α = 0");
+    println!("   ✅ P = {:.4} for TD error 0 against {:.3} for TD error 3", replay.probability(0, 1.0), replay.probability(3, 1.0));
+
+    // Test 2: sampling frequencies and importance weights
+    println!("📊 Test 2: sampling");
+    let mut replay = PrioritizedReplay::new(100);
+    for index in 0..100 {
+        replay.push(experience(&format!("s{}", index), 0.0));
+        replay.update_priority(index, if index == 42 { 10.0 } else { 0.1 });
+    }
+    let mut rng = StdRng::seed_from_u64(3);
+    let (mut draws, mut hits) = (0, 0);
+    for _ in 0..500 {
+        let batch = replay.sample(16, 1.0, 0.5, &mut rng);
+        ensure!(batch.len() == 16, "a full batch");
+        ensure!(batch.iter().map(|(_, weight)| *weight).fold(0.0, f64::max) == 1.0, "weights normalized to the largest");
+        for (index, weight) in batch {
+            draws += 1;
+            if index == 42 {
+                hits += 1;
+                ensure!(weight < 0.2, "the favoured experience is down-weighted: {}", weight);
+            }
+        }
+    }
+    let (expected, observed) = (replay.probability(42, 1.0), hits as f64 / draws as f64);
+    ensure!((observed - expected).abs() < 0.02, "drawn {:.3} of the time, expected {:.3}", observed, expected);
+    println!("   ✅ TD error 10 drawn {:.1}% of the time (expected {:.1}%)", observed * 100.0, expected * 100.0);
+
+    // Test 3: prioritized replay learns the rare experience sooner
+    println!("📊 Test 3: learning");
+    let mut prioritized = agent(0.6)?;
+    let mut uniform = agent(0.0)?;
+    for _ in 0..30 {
+        prioritized.train_batch()?;
+        uniform.train_batch()?;
+    }
+    let rare = |agent: &LaplacianQLearningAgent| agent.q_value("rare", &TradingAction::Hold);
+    ensure!(rare(&prioritized) > rare(&uniform) + 0.1, "prioritized {:.3}, uniform {:.3}", rare(&prioritized), rare(&uniform));
+    let learned = prioritized.replay_buffer().entries().last().unwrap().priority;
+    ensure!(learned < 1.0, "replaying the rare experience lowered its priority to {:.3}", learned);
+    println!("   ✅ Q(rare) {:.3} prioritized vs {:.3} uniform after 30 batches", rare(&prioritized), rare(&uniform));
+
+    // Test 4: training resumes from a checkpoint
+    println!("📊 Test 4: checkpoint");
+    let dir = std::env::temp_dir().join(format!("replay-priority-test-{}", std::process::id()));
+    let path = dir.join("agent.json");
+    ensure!(QTableSnapshot::load(&path)?.is_none(), "no checkpoint yet");
+    prioritized.q_table_snapshot().save(&path)?;
+    let mut resumed = LaplacianQLearningAgent::new(LaplacianQLearningConfig { batch_size: 8, attention_weight: 0.0, ..LaplacianQLearningConfig::default() })?;
+    resumed.restore_q_table(QTableSnapshot::load(&path)?.ok_or_else(|| anyhow::anyhow!("checkpoint written"))?);
+    let priorities = |agent: &LaplacianQLearningAgent| agent.replay_buffer().entries().map(|entry| entry.priority).collect::<Vec<_>>();
+    ensure!(priorities(&resumed) == priorities(&prioritized) && rare(&resumed) == rare(&prioritized), "buffer, priorities and Q-values restored");
+    for agent in [&mut prioritized, &mut resumed] {
+        agent.reseed(9);
+        for _ in 0..5 {
+            agent.train_batch()?;
+        }
+    }
+    ensure!(serde_json::to_string(&resumed.q_table_snapshot())? == serde_json::to_string(&prioritized.q_table_snapshot())?,
+            "the resumed agent trains exactly as the original");
+    let mut small = LaplacianQLearningAgent::new(LaplacianQLearningConfig { buffer_size: 10, ..LaplacianQLearningConfig::default() })?;
+    small.restore_q_table(prioritized.q_table_snapshot());
+    ensure!(small.replay_buffer().len() == 10 && small.replay_buffer().entries().last().map(|entry| entry.experience.state.as_str()) == Some("rare"),
+            "a smaller buffer keeps the newest experiences");
+    small.restore_q_table(QTableSnapshot { replay: None, ..prioritized.q_table_snapshot() });
+    ensure!(small.replay_buffer().is_empty(), "a checkpoint without a buffer starts an empty one");
+    let mut table: toml::Table = toml::from_str(&toml::to_string(&LaplacianQLearningConfig::default())?)?;
+    table.remove("priority_exponent");
+    table.remove("importance_exponent");
+    let legacy: LaplacianQLearningConfig = toml::from_str(&toml::to_string(&table)?)?;
+    ensure!(legacy.priority_exponent == 0.6 && legacy.importance_exponent == 0.4, "older configurations get the defaults");
+    println!("   ✅ {} experiences and {} Q-values resumed from {}", resumed.replay_buffer().len(), resumed.q_backend().size(), path.display());
+    std::fs::remove_dir_all(&dir)?;
+
+    println!();
+    println!("🎉 All replay priority tests passed");
+    Ok(())
+}
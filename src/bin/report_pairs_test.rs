@@ -0,0 +1,35 @@
+//! # Report Pairs Test
+//!
+//! Check that the `--pairs` list of the daily and weekly reports is normalized
+//! and that one naming no pair is an error instead of a panic
+
+use anyhow::{ensure, Result};
+
+use forex_pattern_reconstruction::report::parse_pairs;
+
+fn main() -> Result<()> {
+    println!("🔬 REPORT PAIRS TEST");
+    println!("====================");
+    println!();
+
+    // Test 1: pairs are trimmed and upper-cased, blanks dropped
+    println!("📊 Test 1: Normalized list");
+    ensure!(parse_pairs("eurusd, GBPusd,,usdjpy ")? == ["EURUSD", "GBPUSD", "USDJPY"], "pairs not normalized");
+    ensure!(parse_pairs("EURUSD")? == ["EURUSD"], "single pair");
+    println!("   ✅ Trimmed, upper-cased, blanks dropped");
+
+    // Test 2: an empty list is an error naming the flag
+    println!("📊 Test 2: Empty list");
+    for empty in ["", " ", ",", " , ,"] {
+        let error = match parse_pairs(empty) {
+            Ok(pairs) => anyhow::bail!("{:?} parsed as {:?}", empty, pairs),
+            Err(e) => e.to_string(),
+        };
+        ensure!(error.contains("--pairs"), "error does not name the flag: {}", error);
+    }
+    println!("   ✅ Empty lists rejected");
+
+    println!();
+    println!("🎉 All report pairs tests passed");
+    Ok(())
+}
@@ -0,0 +1,128 @@
+//! # Research Digest Test
+//!
+//! Check that the weekly digest matches cycles and symmetries across weeks by
+//! period, reports what emerged, vanished, strengthened or weakened, diffs against
+//! the saved snapshot of the week before and schedules itself on the right weekday
+
+use anyhow::{ensure, Result};
+use chrono::{NaiveDate, TimeZone, Utc, Weekday};
+use std::collections::BTreeMap;
+
+use forex_pattern_reconstruction::ids::{CycleId, SymmetryId};
+use forex_pattern_reconstruction::patterns::HiddenCycle;
+use forex_pattern_reconstruction::report::digest::{
+    next_weekly_schedule_time, week_start_of, AnomalyStatistics, ChangeStatus, DigestConfig, DigestGenerator,
+    PairSnapshot, StructureKind, WeeklySnapshot,
+};
+use forex_pattern_reconstruction::symmetry::TemporalSymmetry;
+
+fn cycle(period: u32, confidence: f64) -> HiddenCycle {
+    HiddenCycle { id: CycleId::new(), name: format!("{}-day cycle", period), period, confidence, amplitude: 0.01, phase: 0.0, p_value: None, track: None }
+}
+
+fn symmetry(period_days: u32, strength: f64) -> TemporalSymmetry {
+    TemporalSymmetry {
+        id: SymmetryId::new(),
+        symmetry_type: "Cyclic".to_string(),
+        name: format!("{}-day mirror", period_days),
+        period_days,
+        strength,
+        confidence: strength,
+        field_signature: 0,
+        discovered_at: Utc::now(),
+        validation_score: strength,
+        mirror_points: Vec::new(),
+        phase_shift: 0.0,
+    }
+}
+
+fn snapshot(week_start: NaiveDate, cycles: Vec<HiddenCycle>, symmetries: Vec<TemporalSymmetry>, anomalies: usize) -> WeeklySnapshot {
+    let mut snapshot = WeeklySnapshot::new(week_start);
+    let statistics = AnomalyStatistics {
+        count: anomalies,
+        high_severity: anomalies / 2,
+        mean_confidence: 0.7,
+        by_type: BTreeMap::from([("VolatilitySpike".to_string(), anomalies)]),
+    };
+    snapshot.pairs.insert("EURUSD".to_string(), PairSnapshot { symmetries, cycles, anomalies: statistics });
+    snapshot
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 RESEARCH DIGEST TEST");
+    println!("=======================");
+    println!();
+
+    let output = std::env::temp_dir().join(format!("research-digest-test-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&output);
+    let generator = DigestGenerator::new(DigestConfig { output_directory: output.clone(), ..DigestConfig::default() });
+    let last_week = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+    let this_week = NaiveDate::from_ymd_opt(2024, 3, 11).unwrap();
+
+    // 20 days drifts to 21 and strengthens, 50 vanishes, 90 emerges; the 30-day symmetry weakens
+    let before = snapshot(last_week, vec![cycle(20, 0.60), cycle(50, 0.70)], vec![symmetry(30, 0.80), symmetry(7, 0.50)], 4);
+    let now = snapshot(this_week, vec![cycle(21, 0.75), cycle(90, 0.65)], vec![symmetry(30, 0.60), symmetry(7, 0.52)], 9);
+
+    // Test 1: changes between two weeks
+    println!("📊 Test 1: Week-over-week changes");
+    let digest = generator.compile(&now, Some(&before));
+    let find = |kind: StructureKind, period: u32| digest.changes.iter().find(|c| c.kind == kind && c.period == period);
+    ensure!(find(StructureKind::Cycle, 21).map(|c| c.status) == Some(ChangeStatus::Strengthened), "21-day cycle: {:?}", find(StructureKind::Cycle, 21));
+    ensure!(find(StructureKind::Cycle, 90).map(|c| c.status) == Some(ChangeStatus::Emerged), "90-day cycle should emerge");
+    ensure!(find(StructureKind::Cycle, 50).map(|c| c.status) == Some(ChangeStatus::Vanished), "50-day cycle should vanish");
+    ensure!(find(StructureKind::Symmetry, 30).map(|c| c.status) == Some(ChangeStatus::Weakened), "30-day symmetry should weaken");
+    ensure!(find(StructureKind::Symmetry, 7).map(|c| c.status) == Some(ChangeStatus::Stable), "7-day symmetry moved under the threshold");
+    let strengthened = find(StructureKind::Cycle, 21).unwrap();
+    ensure!((strengthened.strength_change() - 0.15).abs() < 1e-9, "strength change {}", strengthened.strength_change());
+    ensure!(digest.changes.len() == 5, "{} changes", digest.changes.len());
+    println!("   ✅ 1 emerged, 1 vanished, 1 strengthened, 1 weakened, 1 stable");
+
+    // Test 2: Markdown carries every section and the anomaly shift
+    println!("📊 Test 2: Markdown digest");
+    let md = generator.render_markdown(&digest);
+    for heading in ["# Research Digest — week of 2024-03-11", "## Newly Emerged", "## Vanished", "## Strengthened", "## Weakened", "## Anomaly Statistics"] {
+        ensure!(md.contains(heading), "missing {}", heading);
+    }
+    ensure!(md.contains("| 90-day cycle |") && md.contains("| 50-day cycle |"), "emerged/vanished cycles missing");
+    ensure!(md.contains("| EURUSD | 9 | +5 |"), "anomaly count change missing");
+    ensure!(md.contains("Compared with the week of 2024-03-04"), "previous week missing");
+    println!("   ✅ {} lines", md.lines().count());
+
+    // Test 3: without a previous week everything is new
+    println!("📊 Test 3: First digest");
+    let first = generator.compile(&now, None);
+    ensure!(first.changes.iter().all(|c| c.status == ChangeStatus::Emerged) && first.changes.len() == 4, "{:?}", first.changes);
+    ensure!(generator.render_markdown(&first).contains("No earlier week on record"), "first digest note missing");
+    println!("   ✅ {} structures new", first.changes.len());
+
+    // Test 4: the saved snapshot is preferred over recollecting last week
+    println!("📊 Test 4: Snapshots across runs");
+    let saved = generator.compile(&before, None);
+    generator.write(&saved, &before)?;
+    ensure!(generator.load_snapshot(last_week)?.is_some(), "last week's snapshot not saved");
+    let mut collected = Vec::new();
+    let digest = generator.generate(this_week, |week_start| {
+        collected.push(week_start);
+        let snapshot = if week_start == this_week { now.clone() } else { WeeklySnapshot::new(week_start) };
+        async move { Ok(snapshot) }
+    }).await?;
+    ensure!(collected == [this_week], "recollected {:?}", collected);
+    ensure!(digest.previous_week_start == Some(last_week), "previous week {:?}", digest.previous_week_start);
+    ensure!(output.join("digest_2024-03-11.md").exists() && generator.load_snapshot(this_week)?.is_some(), "digest files missing");
+    println!("   ✅ Diffed against the saved snapshot");
+
+    // Test 5: weekly schedule
+    println!("📊 Test 5: Schedule");
+    ensure!(week_start_of(NaiveDate::from_ymd_opt(2024, 3, 17).unwrap()) == this_week, "Sunday belongs to the week from Monday");
+    let friday = Utc.with_ymd_and_hms(2024, 3, 15, 12, 0, 0).unwrap();
+    ensure!(next_weekly_schedule_time(friday, Weekday::Sat, 6) == Utc.with_ymd_and_hms(2024, 3, 16, 6, 0, 0).unwrap(), "next Saturday");
+    let saturday = Utc.with_ymd_and_hms(2024, 3, 16, 6, 0, 0).unwrap();
+    ensure!(next_weekly_schedule_time(saturday, Weekday::Sat, 6) == Utc.with_ymd_and_hms(2024, 3, 23, 6, 0, 0).unwrap(), "strictly after now");
+    std::fs::remove_dir_all(&output)?;
+    println!("   ✅ Saturday 06:00 UTC");
+
+    println!();
+    println!("🎉 All research digest tests passed");
+    Ok(())
+}
@@ -0,0 +1,101 @@
+//! # Multi-Resolution Symmetry Comparison Test
+//!
+//! Check a 21-day cycle decoded from daily bars is found again as ~504-hour
+//! structure in the hourly bars it was built from, that daily symmetries set
+//! against unrelated hourly bars score far lower, and that symmetries which
+//! cannot be re-measured are reported rather than scored
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::f64::consts::PI;
+
+use forex_pattern_reconstruction::data::timeframe::TimeframeAggregator;
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::ids::SymmetryId;
+use forex_pattern_reconstruction::symmetry::{compare_resolutions, SymmetryDetector, SymmetryDetectorConfig, TemporalSymmetry, ROTATIONAL};
+
+const DAYS: i64 = 150;
+
+/// Hourly bars from `close(hour)` with opens at the previous close
+fn hourly(mut close: impl FnMut(i64) -> f64) -> Vec<ForexDataPoint> {
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let mut previous = close(0);
+    (0..DAYS * 24)
+        .map(|h| {
+            let price = close(h);
+            let bar = ForexDataPoint {
+                timestamp: start + Duration::hours(h),
+                open: previous,
+                high: previous.max(price) + 0.00005,
+                low: previous.min(price) - 0.00005,
+                close: price,
+                volume: None,
+            };
+            previous = price;
+            bar
+        })
+        .collect()
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 MULTI-RESOLUTION SYMMETRY COMPARISON TEST");
+    println!("============================================");
+    println!();
+
+    let mut rng = StdRng::seed_from_u64(11);
+    let cyclic = hourly(|h| 1.1 * (1.0 + 0.01 * (2.0 * PI * h as f64 / 504.0).sin()) + rng.gen_range(-0.00001..0.00001));
+    let daily = TimeframeAggregator::new("D1")?.resample(&cyclic)?;
+    let detector = SymmetryDetector::new(SymmetryDetectorConfig::default())?;
+    let symmetries = detector.detect(&daily);
+    let rotation = symmetries.iter().find(|s| s.symmetry_type == ROTATIONAL).expect("the 21-day cycle on D1");
+    ensure!((20..=22).contains(&rotation.period_days), "daily cycle of {} days", rotation.period_days);
+
+    // Test 1: the daily cycle reappears as ~504 hours of hourly structure
+    println!("📊 Test 1: daily cycle in hourly bars");
+    let comparison = compare_resolutions("D1", &symmetries, "H1", &cyclic, 0.1)?;
+    ensure!(comparison.ratio == 24.0, "24 hours a day, got {}", comparison.ratio);
+    let matched = comparison.matches.iter().find(|m| m.symmetry == rotation.id).expect("the cycle was measured");
+    ensure!(matched.confirmed && (450..=560).contains(&matched.fine_period), "{:?}", matched);
+    ensure!(matched.fine_strength > matched.control_strength, "{:?}", matched);
+    ensure!(comparison.consistency_score > 0.5, "consistency {:.3}", comparison.consistency_score);
+    println!("   ✅ {} → {}h, strength {:.3} vs chance {:.3}; consistency {:.3}",
+             matched.name, matched.fine_period, matched.fine_strength, matched.control_strength, comparison.consistency_score);
+
+    // Test 2: the same daily symmetries against unrelated hourly bars
+    println!("📊 Test 2: unrelated hourly bars");
+    let mut price = 1.1;
+    let walk = hourly(|_| {
+        price += rng.gen_range(-0.0003..0.0003);
+        price
+    });
+    let unrelated = compare_resolutions("D1", &symmetries, "H1", &walk, 0.1)?;
+    let unmatched = unrelated.matches.iter().find(|m| m.symmetry == rotation.id).expect("the cycle was measured");
+    ensure!(!unmatched.confirmed, "{:?}", unmatched);
+    ensure!(unrelated.consistency_score < comparison.consistency_score / 2.0,
+            "consistency {:.3} against {:.3}", unrelated.consistency_score, comparison.consistency_score);
+    println!("   ✅ {}/{} confirmed, consistency {:.3}", unrelated.confirmed(), unrelated.matches.len(), unrelated.consistency_score);
+
+    // Test 3: kinds the detector does not find and periods too long for the hourly bars are unmeasured
+    println!("📊 Test 3: unmeasured symmetries");
+    let field_pattern = TemporalSymmetry { id: SymmetryId::new(), symmetry_type: "mirror".to_string(), period_days: 7, ..rotation.clone() };
+    let too_long = TemporalSymmetry { id: SymmetryId::new(), period_days: 60, ..rotation.clone() };
+    let partial = compare_resolutions("D1", &[field_pattern.clone(), too_long.clone(), rotation.clone()], "H1", &cyclic, 0.1)?;
+    ensure!(partial.unmeasured == vec![field_pattern.id, too_long.id], "{:?}", partial.unmeasured);
+    ensure!(partial.matches.len() == 1 && partial.consistency_score == partial.matches[0].support, "only the cycle is scored");
+    ensure!(compare_resolutions("D1", &[], "H1", &cyclic, 0.1)?.consistency_score == 0.0, "nothing measured scores 0");
+    println!("   ✅ {} unmeasured", partial.unmeasured.len());
+
+    // Test 4: bad arguments are refused
+    println!("📊 Test 4: limits");
+    ensure!(compare_resolutions("H1", &symmetries, "D1", &cyclic, 0.1).is_err(), "the fine timeframe must be finer");
+    ensure!(compare_resolutions("D1", &symmetries, "H1", &cyclic, 0.3).is_err(), "tolerance overlapping the control bands");
+    ensure!(compare_resolutions("D1", &symmetries, "X9", &cyclic, 0.1).is_err(), "unknown timeframe");
+    println!("   ✅ Refused");
+
+    println!();
+    println!("🎉 All multi-resolution comparison tests passed");
+    Ok(())
+}
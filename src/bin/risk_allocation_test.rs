@@ -0,0 +1,133 @@
+//! # Risk Allocation Test
+//!
+//! Give pairs persistent cycles, pure noise or near-duplicate paths and check
+//! risk budgets follow out-of-sample symmetry quality, shrink with correlation,
+//! respect the weight cap under both methods, and scale the manager's order sizes
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
+
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::laplacian_rl::TradingAction;
+use forex_pattern_reconstruction::multi_currency::MultiCurrencyManager;
+use forex_pattern_reconstruction::portfolio::allocation::{Allocation, AllocationConfig, AllocationMethod, RiskAllocator};
+use forex_pattern_reconstruction::trading_windows::TradingWindowsConfig;
+
+const BARS: usize = 600;
+
+fn bars(closes: &[f64]) -> Vec<ForexDataPoint> {
+    let start = Utc.with_ymd_and_hms(2022, 1, 3, 0, 0, 0).unwrap();
+    closes.iter()
+        .enumerate()
+        .map(|(i, &close)| ForexDataPoint { timestamp: start + Duration::days(i as i64), open: close, high: close, low: close, close, volume: None })
+        .collect()
+}
+
+/// A cycle of `period` bars with a little noise
+fn cycle(period: f64, rng: &mut StdRng) -> Vec<f64> {
+    (0..BARS).map(|i| 1.2 * (1.0 + 0.01 * (2.0 * std::f64::consts::PI * i as f64 / period).sin() + rng.gen_range(-0.0003..0.0003))).collect()
+}
+
+fn random_walk(rng: &mut StdRng) -> Vec<f64> {
+    let mut close = 1.2;
+    (0..BARS).map(|_| { close *= 1.0 + rng.gen_range(-0.004..0.004); close }).collect()
+}
+
+fn weight(allocation: &Allocation, symbol: &str) -> f64 {
+    allocation.pair(symbol).map(|pair| pair.weight).unwrap_or(0.0)
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 RISK ALLOCATION TEST");
+    println!("=======================");
+    println!();
+
+    let mut rng = StdRng::seed_from_u64(9);
+    let cycle_a = bars(&cycle(20.0, &mut rng));
+    let cycle_b = bars(&cycle(17.0, &mut rng));
+    let twin_a: Vec<ForexDataPoint> = cycle_a.iter()
+        .map(|bar| ForexDataPoint { close: bar.close * (1.0 + rng.gen_range(-0.0002..0.0002)), ..bar.clone() })
+        .collect();
+    let noise = bars(&random_walk(&mut rng));
+    let allocator = RiskAllocator::new(AllocationConfig::default())?;
+
+    // Test 1: symmetries that persist out of sample score high, noise does not
+    println!("📊 Test 1: out-of-sample scores");
+    let (cycle_score, noise_score) = (allocator.out_of_sample_score(&cycle_a), allocator.out_of_sample_score(&noise));
+    ensure!(cycle_score > 0.8 && noise_score < cycle_score / 2.0, "cycle {:.3} vs noise {:.3}", cycle_score, noise_score);
+    ensure!(allocator.out_of_sample_score(&cycle_a[..10]) == 0.0, "too little history scores nothing");
+    println!("   ✅ Cycle {:.3}, random walk {:.3}", cycle_score, noise_score);
+
+    // Test 2: budgets follow the scores and sum to one
+    println!("📊 Test 2: quality-weighted budgets");
+    let allocation = allocator.allocate(&[
+        ("CYCA".to_string(), cycle_a.as_slice()),
+        ("CYCB".to_string(), cycle_b.as_slice()),
+        ("NOISE".to_string(), noise.as_slice()),
+        ("SHORT".to_string(), &noise[..1]),
+    ])?;
+    ensure!(allocation.pairs.len() == 3 && allocation.pair("SHORT").is_none(), "pairs without returns are left out");
+    ensure!((allocation.pairs.iter().map(|p| p.weight).sum::<f64>() - 1.0).abs() < 1e-9, "weights sum to one");
+    ensure!(weight(&allocation, "NOISE") < weight(&allocation, "CYCA") / 2.0, "noise gets the least risk");
+    ensure!((allocation.pairs.iter().map(|p| p.size_scale).sum::<f64>() - 3.0).abs() < 1e-9, "scales average one");
+    ensure!(allocation.size_scale("OTHER") == 1.0, "pairs not allocated keep their sizing");
+    println!("   ✅ {}", allocation.pairs.iter().map(|p| format!("{} {:.1}%", p.symbol, p.weight * 100.0)).collect::<Vec<_>>().join(", "));
+
+    // Test 3: correlated pairs share a budget under both methods
+    println!("📊 Test 3: correlation");
+    for method in [AllocationMethod::RiskParity, AllocationMethod::MeanVariance] {
+        let allocator = RiskAllocator::new(AllocationConfig { method, ..AllocationConfig::default() })?;
+        let allocation = allocator.allocate(&[
+            ("CYCA".to_string(), cycle_a.as_slice()),
+            ("TWIN".to_string(), twin_a.as_slice()),
+            ("CYCB".to_string(), cycle_b.as_slice()),
+        ])?;
+        let (twin_crowding, independent_crowding) = (allocation.pair("TWIN").unwrap().mean_abs_correlation, allocation.pair("CYCB").unwrap().mean_abs_correlation);
+        ensure!(twin_crowding > 0.4 && independent_crowding < 0.1, "twins move together: {:.3} vs {:.3}", twin_crowding, independent_crowding);
+        let (a, twin, b) = (weight(&allocation, "CYCA"), weight(&allocation, "TWIN"), weight(&allocation, "CYCB"));
+        ensure!(b > a && b > twin, "{:?}: the independent pair gets more than each twin ({:.3} vs {:.3}, {:.3})", method, b, a, twin);
+        println!("   ✅ {:?}: independent {:.1}%, twins {:.1}% and {:.1}%", method, b * 100.0, a * 100.0, twin * 100.0);
+    }
+
+    // Test 4: no pair exceeds the cap, and bad settings are rejected
+    println!("📊 Test 4: weight cap");
+    let capped = RiskAllocator::new(AllocationConfig { max_weight: 0.4, ..AllocationConfig::default() })?
+        .allocate(&[("CYCA".to_string(), cycle_a.as_slice()), ("NOISE".to_string(), noise.as_slice()), ("FLAT".to_string(), &bars(&[1.2; BARS]))])?;
+    ensure!(capped.pairs.iter().all(|p| p.weight <= 0.4 + 1e-9), "cap respected: {:?}", capped.pairs.iter().map(|p| p.weight).collect::<Vec<_>>());
+    ensure!((weight(&capped, "CYCA") - 0.4).abs() < 1e-9 && weight(&capped, "FLAT") == 0.0, "excess goes to pairs that carry risk");
+    ensure!(RiskAllocator::new(AllocationConfig { holdout_fraction: 1.0, ..AllocationConfig::default() }).is_err(), "holdout must leave history");
+    println!("   ✅ Dominant pair capped at {:.0}%", weight(&capped, "CYCA") * 100.0);
+
+    // Test 5: the manager sizes orders by the budgets
+    println!("📊 Test 5: manager sizing");
+    let mut manager = MultiCurrencyManager::new().with_trading_windows(TradingWindowsConfig::unrestricted());
+    manager.initialize_pairs(&["EURUSD".to_string(), "GBPUSD".to_string()]).await?;
+    {
+        let mut pairs = manager.pairs.write().await;
+        pairs.get_mut("EURUSD").unwrap().historical_data = cycle_a.clone();
+        pairs.get_mut("GBPUSD").unwrap().historical_data = noise.clone();
+    }
+    let allocation = manager.rebalance_allocation().await?;
+    let actions = HashMap::from([
+        ("EURUSD".to_string(), vec![TradingAction::Buy { size: 10 }]),
+        ("GBPUSD".to_string(), vec![TradingAction::Buy { size: 10 }]),
+    ]);
+    manager.execute_actions(&actions).await;
+    let portfolio = manager.portfolio.read().await;
+    for symbol in ["EURUSD", "GBPUSD"] {
+        let units = portfolio.positions().get(symbol).map(|p| p.units).unwrap_or(0.0);
+        let expected = 10.0 * 1_000.0 * allocation.size_scale(symbol);
+        ensure!((units - expected).abs() < 1e-6, "{} holds {} units, budget gives {}", symbol, units, expected);
+    }
+    ensure!(portfolio.units_per_size("EURUSD") > portfolio.units_per_size("GBPUSD"), "the cycling pair trades larger");
+    println!("   ✅ EURUSD {:.0} units, GBPUSD {:.0} units per size-10 buy",
+             portfolio.units_per_size("EURUSD") * 10.0, portfolio.units_per_size("GBPUSD") * 10.0);
+
+    println!();
+    println!("🎉 All risk allocation tests passed");
+    Ok(())
+}
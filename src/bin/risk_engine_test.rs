@@ -0,0 +1,155 @@
+//! # Risk Engine Test
+//!
+//! Check orders are cut to the pair and currency exposure limits, shrunk for
+//! correlated open positions but not for hedges, refused past the drawdown
+//! limit, and that the kill switch closes every position in the manager and
+//! blocks new exposure until an operator re-arms it
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
+
+use forex_pattern_reconstruction::correlation::{CorrelationResult, CorrelationStrength, CrossPairAnalyzer};
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::laplacian_rl::TradingAction;
+use forex_pattern_reconstruction::multi_currency::{ControlCommand, MultiCurrencyManager};
+use forex_pattern_reconstruction::portfolio::orders::OrderStatus;
+use forex_pattern_reconstruction::portfolio::{Portfolio, PortfolioConfig};
+use forex_pattern_reconstruction::risk::{RiskDecision, RiskEngine, RiskLimits};
+use forex_pattern_reconstruction::trading_windows::TradingWindowsConfig;
+use forex_pattern_reconstruction::units::PriceDelta;
+
+fn correlation(pair1: &str, pair2: &str, correlation: f64) -> HashMap<(String, String), CorrelationResult> {
+    HashMap::from([((pair1.to_string(), pair2.to_string()), CorrelationResult {
+        pair1: pair1.to_string(),
+        pair2: pair2.to_string(),
+        correlation,
+        strength: CorrelationStrength::VeryStrong,
+        arbitrage_potential: PriceDelta(0.0),
+    })])
+}
+
+/// Set the latest bar of `symbol` to `price`
+async fn set_price(manager: &MultiCurrencyManager, symbol: &str, price: f64) {
+    let mut pairs = manager.pairs.write().await;
+    let state = pairs.get_mut(symbol).unwrap();
+    state.historical_data = vec![ForexDataPoint { timestamp: Utc::now() - Duration::hours(1), open: price, high: price, low: price, close: price, volume: None }];
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 RISK ENGINE TEST");
+    println!("===================");
+    println!();
+
+    let now = Utc::now();
+    let prices = HashMap::from([("EURUSD".to_string(), 1.1), ("GBPUSD".to_string(), 1.25), ("EURGBP".to_string(), 0.88)]);
+    let limits = RiskLimits { max_pair_exposure_pct: 50.0, max_currency_exposure_pct: 60.0, ..RiskLimits::default() };
+    let engine = RiskEngine::new(100_000.0);
+
+    // Test 1: orders are cut to the pair and currency exposure limits
+    println!("📊 Test 1: exposure limits");
+    let mut portfolio = Portfolio::new(PortfolioConfig::default());
+    portfolio.submit("EURUSD", &TradingAction::Buy { size: 40 }, 1.1, &prices, now, None);
+    let snapshot = portfolio.snapshot(&prices, now);
+    let buy = |engine: &RiskEngine, limits: &RiskLimits, symbol: &str, size: u32, snapshot| {
+        engine.check(limits, symbol, &TradingAction::Buy { size }, 1_000.0, snapshot, &prices)
+    };
+    // 40k EURUSD is 44k USD; 50% of equity allows 45.45k units in all
+    let decision = buy(&engine, &limits, "EURUSD", 10, &snapshot);
+    ensure!(matches!(&decision, RiskDecision::Resize { size: 5, reason } if reason.starts_with("pair exposure limit")), "{:?}", decision);
+    let sell = engine.check(&limits, "EURUSD", &TradingAction::Sell { size: 80 }, 1_000.0, &snapshot, &prices);
+    ensure!(sell == RiskDecision::Allow, "flipping to a 40k short stays within the limit: {:?}", sell);
+    // EUR exposure 44k of 60k: 10k EURGBP adds 11k USD of EUR
+    let decision = buy(&engine, &limits, "EURGBP", 20, &snapshot);
+    ensure!(matches!(&decision, RiskDecision::Resize { size: 14, reason } if reason.starts_with("currency exposure limit")), "{:?}", decision);
+    let tight = RiskLimits { max_currency_exposure_pct: 40.0, ..limits.clone() };
+    ensure!(matches!(buy(&engine, &tight, "EURGBP", 1, &snapshot), RiskDecision::Refuse { .. }), "already over the EUR limit");
+    ensure!(engine.check(&tight, "EURUSD", &TradingAction::ClosePosition, 1_000.0, &snapshot, &prices) == RiskDecision::Allow, "closing is always allowed");
+    println!("   ✅ {:?}", decision);
+
+    // Test 2: correlated positions in the same direction shrink orders, hedges do not
+    println!("📊 Test 2: correlation-adjusted sizing");
+    let loose = RiskLimits::default();
+    let mut correlated = RiskEngine::new(100_000.0);
+    correlated.set_correlations(&correlation("GBPUSD", "EURUSD", 0.9));
+    ensure!(correlated.correlation("EURUSD", "GBPUSD") == 0.9, "correlations are symmetric");
+    let decision = buy(&correlated, &loose, "GBPUSD", 19, &snapshot);
+    ensure!(matches!(&decision, RiskDecision::Resize { size: 10, reason } if reason.starts_with("correlated exposure")), "{:?}", decision);
+    let hedge = correlated.check(&loose, "GBPUSD", &TradingAction::Sell { size: 19 }, 1_000.0, &snapshot, &prices);
+    ensure!(hedge == RiskDecision::Allow, "a hedge is not shrunk: {:?}", hedge);
+    correlated.set_correlations(&correlation("EURUSD", "GBPUSD", 0.3));
+    ensure!(buy(&correlated, &loose, "GBPUSD", 19, &snapshot) == RiskDecision::Allow, "weak correlations are ignored");
+
+    let mut rng = StdRng::seed_from_u64(5);
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let (mut eur, mut gbp) = (1.1, 1.25);
+    let mut histories: HashMap<String, Vec<ForexDataPoint>> = HashMap::new();
+    for i in 0..200 {
+        let shock: f64 = rng.gen_range(-0.004..0.004);
+        eur *= 1.0 + shock;
+        gbp *= 1.0 + shock + rng.gen_range(-0.001..0.001);
+        let timestamp = start + Duration::hours(i);
+        for (symbol, close) in [("EURUSD", eur), ("GBPUSD", gbp)] {
+            histories.entry(symbol.to_string()).or_default()
+                .push(ForexDataPoint { timestamp, open: close, high: close, low: close, close, volume: None });
+        }
+    }
+    correlated.set_correlations(&CrossPairAnalyzer::new().calculate_correlation_matrix(&histories)?);
+    ensure!(correlated.correlation("EURUSD", "GBPUSD") > 0.8, "analyzer correlation {:.3}", correlated.correlation("EURUSD", "GBPUSD"));
+    println!("   ✅ {:?}; analyzer correlation {:.3}", decision, correlated.correlation("EURUSD", "GBPUSD"));
+
+    // Test 3: drawdown refuses new exposure, and past the kill switch only closing is allowed until reset
+    println!("📊 Test 3: drawdown and kill switch");
+    let mut engine = RiskEngine::new(100_000.0);
+    engine.update_drawdown(110_000.0);
+    ensure!((engine.update_drawdown(97_900.0) - 11.0).abs() < 1e-9, "drawdown from the peak");
+    ensure!(matches!(buy(&engine, &loose, "EURUSD", 1, &snapshot), RiskDecision::Refuse { reason } if reason.starts_with("drawdown")), "over the drawdown limit");
+    ensure!(engine.assess(&loose, now).is_none(), "below the kill switch");
+    engine.update_drawdown(85_000.0);
+    ensure!(engine.assess(&loose, now).is_some() && engine.assess(&loose, now).is_none(), "trips once");
+    ensure!(matches!(buy(&engine, &loose, "EURUSD", 1, &snapshot), RiskDecision::Refuse { reason } if reason.starts_with("kill switch")), "blocked");
+    let tripped = engine.reset(85_000.0).expect("was tripped");
+    ensure!(buy(&engine, &loose, "EURUSD", 1, &snapshot) == RiskDecision::Allow, "re-armed from the current equity");
+    println!("   ✅ {}", tripped.reason);
+
+    // Test 4: the manager closes every position on the kill switch and refuses new exposure until reset
+    println!("📊 Test 4: manager kill switch");
+    let mut manager = MultiCurrencyManager::new().with_trading_windows(TradingWindowsConfig::unrestricted());
+    manager.initialize_pairs(&["EURUSD".to_string(), "GBPUSD".to_string()]).await?;
+    set_price(&manager, "EURUSD", 1.1).await;
+    set_price(&manager, "GBPUSD", 1.25).await;
+    manager.execute_actions(&HashMap::from([
+        ("EURUSD".to_string(), vec![TradingAction::Buy { size: 10 }]),
+        ("GBPUSD".to_string(), vec![TradingAction::Sell { size: 10 }]),
+    ])).await;
+    ensure!(manager.portfolio_snapshot().await.positions.len() == 2, "both orders filled");
+    ensure!(ControlCommand::parse("kill-switch", None, &HashMap::new())? == Some(ControlCommand::KillSwitch), "parsed from the wire");
+    manager.execute_command(&ControlCommand::KillSwitch, "test").await?;
+    let snapshot = manager.portfolio_snapshot().await;
+    ensure!(snapshot.positions.is_empty(), "every position closed");
+    ensure!(snapshot.recent_orders[..2].iter().all(|order| order.reason.as_deref() == Some("kill switch")), "closed as kill switch orders");
+    manager.execute_actions(&HashMap::from([("EURUSD".to_string(), vec![TradingAction::Buy { size: 1 }])])).await;
+    let refused = manager.portfolio_snapshot().await.recent_orders.remove(0);
+    ensure!(refused.status == OrderStatus::Rejected && refused.reason.as_deref().is_some_and(|r| r.starts_with("kill switch")), "{:?}", refused);
+    manager.execute_command(&ControlCommand::ResetKillSwitch, "test").await?;
+    manager.execute_actions(&HashMap::from([("EURUSD".to_string(), vec![TradingAction::Buy { size: 1 }])])).await;
+    ensure!(manager.portfolio_snapshot().await.positions.len() == 1, "trading again after the reset");
+    println!("   ✅ {}", refused.summary());
+
+    // Test 5: a drawdown past the kill switch level trips it on the next execution
+    println!("📊 Test 5: automatic kill switch");
+    manager.execute_actions(&HashMap::from([("GBPUSD".to_string(), vec![TradingAction::Buy { size: 300 }])])).await;
+    set_price(&manager, "GBPUSD", 1.15).await;
+    manager.execute_actions(&HashMap::from([("EURUSD".to_string(), vec![TradingAction::Hold])])).await;
+    let risk = manager.risk.read().await;
+    let kill_switch = risk.kill_switch().expect("tripped by the drawdown");
+    ensure!(manager.portfolio_snapshot().await.positions.is_empty(), "positions closed after the loss");
+    println!("   ✅ {}", kill_switch.reason);
+
+    println!();
+    println!("🎉 All risk engine tests passed");
+    Ok(())
+}
@@ -0,0 +1,129 @@
+//! # Scan Test
+//!
+//! Check that cycle periods shared by several pairs are grouped within the
+//! tolerance, that a directory scan ranks every pair by its strongest symmetry
+//! while listing an unreadable file as failed, and that the ranking writes out
+//! as CSV and JSON
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, TimeZone, Utc};
+use std::f64::consts::PI;
+use std::path::{Path, PathBuf};
+
+use forex_pattern_reconstruction::core::EngineConfig;
+use forex_pattern_reconstruction::data::{DataConfig, ForexDataManager};
+use forex_pattern_reconstruction::patterns::{HiddenCycle, PatternConfig, SharedCycleConfig};
+use forex_pattern_reconstruction::report::scan::{scan_pairs, shared_cycles, PairScan, ScanConfig};
+
+fn scanned(pair: &str, periods: &[(u32, f64)]) -> PairScan {
+    PairScan {
+        pair: pair.to_string(),
+        file: PathBuf::from(format!("{}.csv", pair)),
+        bars: 0,
+        start: None,
+        end: None,
+        symmetries_found: 0,
+        top_symmetries: Vec::new(),
+        cycles: periods.iter().map(|&(period, confidence)| HiddenCycle {
+            id: Default::default(),
+            name: format!("{}-bar", period),
+            period,
+            confidence,
+            amplitude: 0.001,
+            phase: 0.0,
+            p_value: None,
+            track: None,
+        }).collect(),
+    }
+}
+
+/// Hourly bars on a `period`-bar cycle of relative amplitude `amplitude`
+fn write_pair(path: &Path, period: f64, amplitude: f64, count: usize) -> Result<()> {
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let mut csv = String::from("time,open,high,low,close,tick_volume\n");
+    for i in 0..count {
+        let close = 1.1 * (1.0 + amplitude * (2.0 * PI * i as f64 / period).sin()) + 0.00005 * ((i * 7919) % 11) as f64 / 11.0;
+        let time = (start + Duration::hours(i as i64)).format("%Y-%m-%d %H:%M:%S");
+        csv.push_str(&format!("{},{:.6},{:.6},{:.6},{:.6},100\n", time, close, close + 0.0003, close - 0.0003, close));
+    }
+    std::fs::write(path, csv)?;
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 Scan Test");
+    println!("============");
+    println!();
+
+    // Test 1: shared cycle periods
+    println!("📊 Test 1: Shared cycles");
+    let scans = vec![
+        scanned("EURUSD", &[(24, 0.9), (120, 0.5)]),
+        scanned("GBPUSD", &[(25, 0.7), (26, 0.8)]),
+        scanned("USDJPY", &[(24, 0.6), (168, 0.4)]),
+        scanned("AUDUSD", &[(121, 0.3)]),
+    ];
+    let config = SharedCycleConfig::default();
+    let shared = shared_cycles(&scans, 3600.0, &config);
+    ensure!(shared.len() == 2, "{} shared periods", shared.len());
+    ensure!(shared[0].pairs == ["EURUSD", "GBPUSD", "USDJPY"], "daily group {:?}", shared[0].pairs);
+    ensure!((shared[0].mean_confidence - (0.9 + 0.8 + 0.6) / 3.0).abs() < 1e-9, "GBPUSD should count once, with its most confident cycle");
+    ensure!(shared[1].pairs == ["AUDUSD", "EURUSD"] && (shared[1].period_hours - 120.375).abs() < 1e-9, "weekly group {:?}", shared[1]);
+    let exact = SharedCycleConfig { period_tolerance: 0.0, ..config.clone() };
+    ensure!(shared_cycles(&scans, 3600.0, &exact).len() == 1 && shared_cycles(&scans[..1], 3600.0, &config).is_empty(), "tolerance or single pair");
+    println!("   ✅ ~24-bar cycle shared by 3 pairs, ~120-bar by 2, 168-bar left out");
+
+    // Test 2: scanning a directory
+    println!("📊 Test 2: Directory scan");
+    let dir = std::env::temp_dir().join(format!("scan-test-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(dir.join("minors"))?;
+    let result = async {
+        write_pair(&dir.join("EURUSD.csv"), 24.0, 0.004, 24 * 40)?;
+        write_pair(&dir.join("GBPUSD.csv"), 24.0, 0.001, 24 * 40)?;
+        write_pair(&dir.join("minors").join("EURGBP.csv"), 120.0, 0.002, 24 * 40)?;
+        std::fs::write(dir.join("BROKEN.csv"), "not,a,price,file\n")?;
+
+        let data_config = DataConfig { data_directory: dir.clone(), ..Default::default() };
+        let summary = ForexDataManager::new(data_config.clone())?.get_data_summary().await?;
+        ensure!(summary.available_pairs.len() == 4, "summary found {} pairs", summary.available_pairs.len());
+        let config = ScanConfig { top_symmetries: 3, max_parallel: 2, ..Default::default() };
+        let report = scan_pairs(&summary, "H1", &data_config, &EngineConfig::default(), &PatternConfig::default(), &config).await?;
+
+        ensure!(report.pairs.len() == 3, "{} pairs scanned", report.pairs.len());
+        ensure!(report.failed.len() == 1 && report.failed[0].pair == "BROKEN", "failed {:?}", report.failed);
+        ensure!(report.pairs.windows(2).all(|w| w[0].strongest() >= w[1].strongest()), "pairs out of rank order");
+        ensure!(report.pairs.iter().all(|scan| scan.bars == 24 * 40 && scan.top_symmetries.len() <= 3), "bars or symmetry count");
+        ensure!(report.pairs.iter().all(|scan| scan.top_symmetries.windows(2).all(|w| w[0].strength >= w[1].strength)),
+                "symmetries out of strength order");
+
+        let lines = report.lines();
+        ensure!(lines.len() == 1 + report.pairs.len() + report.shared_cycles.len() + 1, "{} lines", lines.len());
+        ensure!(lines.last().is_some_and(|line| line.starts_with("BROKEN failed")), "failure line");
+
+        let csv_path = dir.join("out").join("scan.csv");
+        report.write_csv(&csv_path)?;
+        let csv = std::fs::read_to_string(&csv_path)?;
+        ensure!(csv.lines().count() == 1 + report.pairs.len(), "csv rows");
+        ensure!(csv.lines().nth(1).is_some_and(|row| row.starts_with(&format!("1,{},", report.pairs[0].pair))), "csv rank order");
+
+        let json: serde_json::Value = serde_json::from_str(&serde_json::to_string(&report)?)?;
+        ensure!(json["pairs"].as_array().map(Vec::len) == Some(3) && json["failed"][0]["pair"] == "BROKEN", "json report");
+
+        let empty = DataConfig { data_directory: dir.join("missing"), ..Default::default() };
+        let nothing = ForexDataManager::new(empty.clone())?.get_data_summary().await?;
+        ensure!(scan_pairs(&nothing, "H1", &empty, &EngineConfig::default(), &PatternConfig::default(), &config).await.is_err(),
+                "an empty directory scanned");
+        Ok(report)
+    }.await;
+    let _ = std::fs::remove_dir_all(&dir);
+    let report = result?;
+    for line in report.lines() {
+        println!("   {}", line);
+    }
+    println!("   ✅ {} pairs ranked, {} shared cycle periods, {} unreadable file reported", report.pairs.len(), report.shared_cycles.len(), report.failed.len());
+
+    println!();
+    println!("🎉 All scan tests passed");
+    Ok(())
+}
@@ -0,0 +1,70 @@
+//! # Sensitivity Calibration Test
+//!
+//! Auto-tune the anomaly sensitivity threshold to a target rate per day on two
+//! pairs with different volatility, and check when a recalibration is due
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use forex_pattern_reconstruction::anomaly::{AnomalyDetectionConfig, TemporalAnomalyDetector};
+use forex_pattern_reconstruction::synthetic::fixtures::hourly_walk;
+
+fn main() -> Result<()> {
+    println!("🔬 SENSITIVITY CALIBRATION TEST");
+    println!("===============================");
+    println!();
+
+    let mut rng = StdRng::seed_from_u64(17);
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let calm = hourly_walk(&mut rng, start, 24 * 60);
+    // Every tenth bar trades a range five times wider
+    let mut jumpy = hourly_walk(&mut rng, start, 24 * 60);
+    for point in jumpy.iter_mut().step_by(10) {
+        let extra = 2.0 * (point.high - point.low);
+        point.high += extra;
+        point.low -= extra;
+    }
+    let tuned = |target: f64| AnomalyDetectionConfig { target_anomalies_per_day: Some(target), ..AnomalyDetectionConfig::default() };
+
+    // Test 1: without a target the configured threshold is kept
+    println!("📊 Test 1: Fixed threshold");
+    let mut fixed = TemporalAnomalyDetector::new(Vec::new(), Vec::new(), &calm, AnomalyDetectionConfig::default())?;
+    ensure!(fixed.calibration().is_none() && fixed.sensitivity_threshold() == 0.3, "no calibration without a target");
+    ensure!(fixed.calibrate_sensitivity(&calm).is_err(), "calibrating needs a target rate");
+    ensure!(fixed.maybe_recalibrate(&calm, Utc::now())?.is_none(), "never due without a target");
+    println!("   ✅ Threshold left at {:.2}", fixed.sensitivity_threshold());
+
+    // Test 2: each pair is tuned to the target rate on its own distribution
+    println!("📊 Test 2: Per-pair calibration");
+    let mut thresholds = Vec::new();
+    for (name, history) in [("calm", &calm), ("jumpy", &jumpy)] {
+        let detector = TemporalAnomalyDetector::new(Vec::new(), Vec::new(), history, tuned(2.0))?;
+        let calibration = detector.calibration().expect("calibrated on construction");
+        println!("   {}: threshold {:.3}, ≈{:.2}/day over {} bars", name, calibration.sensitivity_threshold, calibration.estimated_rate_per_day, calibration.samples);
+        ensure!(detector.sensitivity_threshold() == calibration.sensitivity_threshold, "{} threshold not applied", name);
+        ensure!((calibration.estimated_rate_per_day - 2.0).abs() < 0.5, "{} tuned to {:.2}/day instead of 2", name, calibration.estimated_rate_per_day);
+        thresholds.push(calibration.sensitivity_threshold);
+    }
+    ensure!((thresholds[0] - thresholds[1]).abs() > 0.1, "pairs with different tails share a threshold: {:?}", thresholds);
+    let loose = TemporalAnomalyDetector::new(Vec::new(), Vec::new(), &calm, tuned(0.5))?;
+    ensure!(loose.sensitivity_threshold() > thresholds[0], "a lower target rate must raise the threshold");
+    println!("   ✅ Both pairs near 2/day with their own thresholds");
+
+    // Test 3: a saved calibration is reused and refreshed once the interval passes
+    println!("📊 Test 3: Recalibration");
+    let mut detector = TemporalAnomalyDetector::new(Vec::new(), Vec::new(), &calm, tuned(2.0))?;
+    let calibrated_at = detector.calibration().expect("calibrated").calibrated_at;
+    ensure!(detector.maybe_recalibrate(&calm, calibrated_at + Duration::days(6))?.is_none(), "recalibrated before the interval");
+    let refreshed = detector.maybe_recalibrate(&calm, calibrated_at + Duration::days(7))?;
+    ensure!(refreshed.is_some_and(|c| c.calibrated_at >= calibrated_at && (c.sensitivity_threshold - thresholds[0]).abs() < 1e-12), "not refreshed after the interval");
+    let saved = TemporalAnomalyDetector::new(Vec::new(), Vec::new(), &calm, AnomalyDetectionConfig::default())?
+        .with_calibration(loose.calibration().cloned().expect("calibrated"));
+    ensure!(saved.sensitivity_threshold() == loose.sensitivity_threshold(), "saved calibration not applied");
+    println!("   ✅ Due after {} days; saved calibration reused", AnomalyDetectionConfig::default().recalibration_interval_days);
+
+    println!();
+    println!("🎉 All sensitivity calibration tests passed");
+    Ok(())
+}
@@ -0,0 +1,237 @@
+//! # Session Replay Test
+//!
+//! Record pair updates and fills to a session log, replay it and check every
+//! decision is reproduced, that altered recordings are flagged as divergences,
+//! and that the manager records its live updates when given a session log
+
+use anyhow::{ensure, Result};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
+use std::path::Path;
+
+use forex_pattern_reconstruction::anomaly::{AnomalySeverity, AnomalyType, DetectedAnomaly, MarketContext};
+use forex_pattern_reconstruction::backtest::strategy::{Fill, Order, OrderSide, RlAgentStrategy, Strategy, StrategyContext};
+use forex_pattern_reconstruction::backtest::StrategyConfig;
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::laplacian_rl::TradingAction;
+use forex_pattern_reconstruction::multi_currency::{CurrencyPairConfig, CurrencyPairState, MultiCurrencyManager, PairAccount, PairDecision};
+use forex_pattern_reconstruction::replay::{read_session, replay_session, PairUpdate, SessionEvent, SessionLog};
+
+const UNITS_PER_SIZE: f64 = 1_000.0;
+
+fn bar(timestamp: DateTime<Utc>, rng: &mut StdRng) -> ForexDataPoint {
+    let close = 1.1 + rng.gen_range(-0.01..0.01);
+    ForexDataPoint { timestamp, open: close, high: close + 0.0005, low: close - 0.0005, close, volume: Some(100.0) }
+}
+
+fn anomaly(timestamp: DateTime<Utc>, rng: &mut StdRng) -> DetectedAnomaly {
+    let anomaly_type = match rng.gen_range(0..3) {
+        0 => AnomalyType::PatternInversion { original_pattern: "up".to_string(), inverted_pattern: "down".to_string() },
+        1 => AnomalyType::VolatilitySpike { expected_volatility: 0.01, actual_volatility: rng.gen_range(0.02..0.05) },
+        _ => AnomalyType::SymmetryBreakdown {
+            symmetry_id: "sym_1".into(),
+            expected_strength: 0.8,
+            actual_strength: rng.gen_range(0.1..1.5),
+        },
+    };
+    DetectedAnomaly {
+        id: format!("a_{}", rng.gen::<u32>()).into(),
+        timestamp,
+        anomaly_type,
+        severity: AnomalySeverity::High,
+        confidence: rng.gen_range(0.5..1.0),
+        deviation_magnitude: rng.gen_range(0.0..0.05),
+        affected_symmetries: Vec::new(),
+        affected_cycles: Vec::new(),
+        market_context: MarketContext {
+            session: "London".to_string(),
+            volatility_regime: "High".to_string(),
+            trend_direction: "Sideways".to_string(),
+            recent_events: Vec::new(),
+            event_proximity: 0.0,
+        },
+        trading_signal: None,
+    }
+}
+
+/// Drive a pair through `updates` updates of two anomalies each, filling every
+/// action at the bar close and recording it all to `log`
+async fn record_session(log: &SessionLog, config: CurrencyPairConfig, updates: i64, seed: u64) -> Result<usize> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut pair = CurrencyPairState::new(config).await?;
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    pair.historical_data = (0..30).map(|i| bar(start + Duration::hours(i), &mut rng)).collect();
+    let (mut position, mut entry, mut actions_taken) = (0.0f64, 0.0f64, 0);
+
+    for i in 0..updates {
+        let now = start + Duration::hours(30 + i);
+        pair.historical_data.push(bar(now, &mut rng));
+        let price = pair.historical_data.last().unwrap().close;
+        let account = PairAccount { position_units: position, equity: 100_000.0, units_per_size: UNITS_PER_SIZE };
+        let anomalies = vec![anomaly(now, &mut rng), anomaly(now, &mut rng)];
+        let decision_seed = rng.gen();
+        let actions = pair.decide(&anomalies, &account, decision_seed).await?;
+        log.record_update(&pair, &account, decision_seed, &PairDecision { anomalies, actions: actions.clone() });
+        actions_taken += actions.len();
+
+        for (index, action) in actions.iter().enumerate() {
+            let after = match action {
+                TradingAction::Buy { size } => position + *size as f64 * UNITS_PER_SIZE,
+                TradingAction::Sell { size } => position - *size as f64 * UNITS_PER_SIZE,
+                TradingAction::ClosePosition => 0.0,
+                TradingAction::Hold => position,
+            };
+            if after == position {
+                continue;
+            }
+            let closed = if after.signum() != position.signum() { position.abs() } else { (position.abs() - after.abs()).max(0.0) };
+            let realized_pnl = closed * (price - entry) * position.signum();
+            if closed < position.abs() || position == 0.0 || after.signum() != position.signum() {
+                entry = price;
+            }
+            let fill = Fill {
+                side: if after > position { OrderSide::Buy } else { OrderSide::Sell },
+                units: (after - position).abs(),
+                price,
+                realized_pnl,
+                commission: 0.0,
+                position_units: after,
+                reason: format!("{:?}", action),
+                symmetry_id: None,
+                cycle_id: None,
+            };
+            position = after;
+            let account = PairAccount { position_units: position, ..account };
+            log.record_fill(&pair.config.symbol, index, &fill, &account);
+            pair.record_fill(index, fill, &account).await;
+        }
+    }
+    Ok(actions_taken)
+}
+
+/// Rewrite the `n`th update of the log at `path` with `edit(n, update)`, returning
+/// the line of each update
+fn edit_updates(path: &Path, mut edit: impl FnMut(usize, &mut PairUpdate)) -> Result<Vec<usize>> {
+    let (mut text, mut lines) = (String::new(), Vec::new());
+    for (index, mut event) in read_session(path)?.into_iter().enumerate() {
+        if let SessionEvent::Update(update) = &mut event {
+            edit(lines.len(), update);
+            lines.push(index + 1);
+        }
+        text.push_str(&serde_json::to_string(&event)?);
+        text.push('\n');
+    }
+    std::fs::write(path, text)?;
+    Ok(lines)
+}
+
+/// Never trades; stands in for a strategy set from code
+struct Idle;
+
+impl Strategy for Idle {
+    fn name(&self) -> &str {
+        "Idle"
+    }
+
+    fn on_bar(&mut self, _context: &StrategyContext, _bar: &ForexDataPoint) -> Vec<Order> {
+        Vec::new()
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 SESSION REPLAY TEST");
+    println!("======================");
+    println!();
+
+    let directory = std::env::temp_dir().join(format!("session_{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&directory)?;
+
+    // Test 1: the built-in RL agent's decisions replay exactly
+    println!("📊 Test 1: RL agent session");
+    let path = directory.join("agent.jsonl");
+    let recorded = record_session(&SessionLog::with_file(&path)?, CurrencyPairConfig::default(), 60, 1).await?;
+    let events = read_session(&path)?;
+    let updates: Vec<_> = events.iter().filter_map(|e| match e { SessionEvent::Update(u) => Some(u), _ => None }).collect();
+    ensure!(updates[0].setup.is_some() && updates[0].bars.len() == 31 && updates[0].cycles.is_some(), "first update carries the setup, whole history and cycles");
+    ensure!(updates[1].setup.is_none() && updates[1].bars.len() == 1 && updates[1].cycles.is_none(), "later updates carry only what changed");
+    let report = replay_session(&path).await?;
+    ensure!(report.is_identical(), "replay diverged: {:?}", report.divergences.first());
+    ensure!(report.updates == 60 && report.actions == recorded, "every update replayed");
+    let exploring = updates.iter().filter(|u| u.actions.iter().any(|a| *a != TradingAction::Hold)).count();
+    ensure!(exploring > 0, "exploration should show in some decisions");
+    println!("   ✅ {} updates, {} actions ({} updates exploring) replayed identically", report.updates, report.actions, exploring);
+
+    // Test 2: altered inputs or outputs are flagged
+    println!("📊 Test 2: divergence detection");
+    let lines = edit_updates(&path, |n, update| {
+        if n == 5 {
+            update.actions = vec![TradingAction::Buy { size: 99 }];
+        }
+    })?;
+    let report = replay_session(&path).await?;
+    ensure!(report.divergences.len() == 1 && report.divergences[0].line == lines[5], "one divergence, at the altered line: {:?}", report.divergences);
+    ensure!(report.divergences[0].replayed != report.divergences[0].recorded, "both action lists reported");
+    edit_updates(&path, |_, update| update.seed = update.seed.wrapping_add(1))?;
+    let reseeded = replay_session(&path).await?;
+    ensure!(reseeded.divergences.len() > 1, "other seeds give other decisions");
+    let lines = edit_updates(&path, |n, update| {
+        if n == 10 {
+            update.bars.clear();
+        }
+    })?;
+    let gapped = replay_session(&path).await?;
+    ensure!(gapped.divergences.iter().any(|d| d.line == lines[10] && d.reason.contains("history")), "missing bars are flagged");
+    println!("   ✅ Altered actions, seeds and bars flagged ({} seed divergences)", reseeded.divergences.len());
+
+    // Test 3: a seeded strategy and the fills it learns from are replayed too
+    println!("📊 Test 3: strategy with fills");
+    let path = directory.join("strategy.jsonl");
+    let strategy = StrategyConfig {
+        name: RlAgentStrategy::NAME.to_string(),
+        parameters: HashMap::from([("exploration_rate".to_string(), 0.5), ("seed".to_string(), 11.0)]),
+    };
+    let config = CurrencyPairConfig { strategies: vec![strategy], ..CurrencyPairConfig::default() };
+    let recorded = record_session(&SessionLog::with_file(&path)?, config, 80, 2).await?;
+    let fills = read_session(&path)?.iter().filter(|e| matches!(e, SessionEvent::Fill { .. })).count();
+    ensure!(recorded > 0 && fills > 0, "the strategy should trade");
+    let report = replay_session(&path).await?;
+    ensure!(report.is_identical() && report.fills == fills, "strategy session diverged: {:?}", report.divergences.first());
+    println!("   ✅ {} updates and {} fills replayed identically", report.updates, report.fills);
+
+    // Test 4: the manager records live updates
+    println!("📊 Test 4: manager recording");
+    let path = directory.join("manager.jsonl");
+    let mut manager = MultiCurrencyManager::new().with_session_log(SessionLog::with_file(&path)?);
+    manager.initialize_major_pairs().await?;
+    manager.active_pairs.retain(|pair| pair == "EURUSD");
+    let mut rng = StdRng::seed_from_u64(3);
+    {
+        let mut pairs = manager.pairs.write().await;
+        pairs.retain(|pair, _| pair == "EURUSD");
+        let state = pairs.get_mut("EURUSD").expect("pair initialized");
+        state.historical_data = (1..=20).rev().map(|days| bar(Utc::now() - Duration::days(days), &mut rng)).collect();
+        state.is_active = true;
+        state.warm = true;
+    }
+    for day in 0..3 {
+        manager.process_all_market_updates().await?;
+        let mut pairs = manager.pairs.write().await;
+        pairs.get_mut("EURUSD").unwrap().historical_data.push(bar(Utc::now() + Duration::days(day), &mut rng));
+    }
+    let events = read_session(&path)?;
+    let bars: Vec<usize> = events.iter().filter_map(|e| match e { SessionEvent::Update(u) => Some(u.bars.len()), _ => None }).collect();
+    ensure!(bars == [20, 1, 1], "whole history once, then new bars: {:?}", bars);
+    ensure!(replay_session(&path).await?.is_identical(), "live updates replay");
+    manager.set_strategy("EURUSD", Box::new(Idle)).await?;
+    manager.process_all_market_updates().await?;
+    ensure!(replay_session(&path).await.is_err(), "strategies set from code cannot be replayed");
+    println!("   ✅ {} live updates recorded and replayed", bars.len());
+
+    std::fs::remove_dir_all(&directory)?;
+    println!();
+    println!("🎉 All session replay tests passed");
+    Ok(())
+}
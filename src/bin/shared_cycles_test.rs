@@ -0,0 +1,77 @@
+//! # Shared Cycles Test
+//!
+//! Check that cycles of different pairs group by wall-clock period across
+//! timeframes, that coverage counts the pairs analysed, and that phases taken at
+//! a common time tell pairs moving together from pairs moving against each other
+
+use anyhow::{ensure, Result};
+use chrono::{TimeZone, Utc};
+use std::f64::consts::PI;
+
+use forex_pattern_reconstruction::patterns::{find_shared_cycles, HiddenCycle, PairCycles, SharedCycleConfig};
+
+fn cycle(period: u32, confidence: f64, phase: f64) -> HiddenCycle {
+    HiddenCycle { id: Default::default(), name: format!("{}-Bar Cycle", period), period, confidence, amplitude: 0.002, phase, p_value: None, track: None }
+}
+
+fn pair(name: &str, bar_hours: f64, cycles: Vec<HiddenCycle>) -> PairCycles {
+    PairCycles { pair: name.to_string(), bar_seconds: bar_hours * 3600.0, cycles }
+}
+
+fn main() -> Result<()> {
+    println!("🔬 Shared Cycles Test");
+    println!("=====================");
+    println!();
+
+    let reference = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap();
+    let config = SharedCycleConfig::default();
+
+    // Test 1: a daily cycle on most majors, across timeframes
+    println!("📊 Test 1: Market-wide period");
+    let pairs = vec![
+        pair("EURUSD", 1.0, vec![cycle(24, 0.9, 0.0), cycle(500, 0.5, 0.0)]),
+        pair("GBPUSD", 1.0, vec![cycle(25, 0.8, 0.0)]),
+        pair("USDCHF", 4.0, vec![cycle(6, 0.7, 0.0)]),
+        pair("USDJPY", 1.0, vec![cycle(24, 0.6, PI)]),
+        pair("AUDUSD", 1.0, vec![cycle(72, 0.9, 0.0)]),
+        pair("NZDUSD", 1.0, Vec::new()),
+    ];
+    let shared = find_shared_cycles(&pairs, reference, &config);
+    ensure!(shared.len() == 1, "{} shared periods", shared.len());
+    let daily = &shared[0];
+    ensure!(daily.pairs == ["EURUSD", "GBPUSD", "USDCHF", "USDJPY"], "daily pairs {:?}", daily.pairs);
+    ensure!(daily.member("USDCHF").is_some_and(|member| member.period_bars == 6 && member.period_hours == 24.0), "H4 member");
+    ensure!((daily.coverage - 0.8).abs() < 1e-9, "coverage {} over the 5 pairs with cycles", daily.coverage);
+    ensure!((daily.score - daily.mean_confidence * 0.8).abs() < 1e-9, "score");
+    ensure!(daily.period_hours > 24.0 && daily.period_hours < 25.0, "period {}", daily.period_hours);
+    println!("   ✅ ~{:.1}h cycle on 4 of 5 pairs (H1 and H4), coverage {:.0}%", daily.period_hours, daily.coverage * 100.0);
+
+    // Test 2: phase relationships
+    println!("📊 Test 2: Phases");
+    let together = daily.phase_difference("EURUSD", "USDCHF").unwrap();
+    let against = daily.phase_difference("EURUSD", "USDJPY").unwrap();
+    ensure!(together.abs() < 1e-6, "EURUSD and USDCHF should peak together, {:.1}°", together);
+    ensure!((against.abs() - 180.0).abs() < 1e-6, "USDJPY should oppose EURUSD, {:.1}°", against);
+    ensure!(daily.phase_difference("EURUSD", "AUDUSD").is_none(), "AUDUSD is not a member");
+    ensure!(daily.members.iter().all(|member| member.phase_offset_degrees > -180.0 && member.phase_offset_degrees <= 180.0), "offsets");
+    ensure!(daily.phase_coherence > 0.0 && daily.phase_coherence < 1.0, "coherence {}", daily.phase_coherence);
+
+    let aligned = find_shared_cycles(&[pair("EURUSD", 1.0, vec![cycle(24, 0.9, 1.0)]), pair("GBPUSD", 1.0, vec![cycle(24, 0.8, 1.0)])], reference, &config);
+    let opposed = find_shared_cycles(&[pair("EURUSD", 1.0, vec![cycle(24, 0.9, 1.0)]), pair("USDJPY", 1.0, vec![cycle(24, 0.8, 1.0 + PI)])], reference, &config);
+    ensure!((aligned[0].phase_coherence - 1.0).abs() < 1e-9 && opposed[0].phase_coherence < 1e-9, "coherence of aligned and opposed pairs");
+    println!("   ✅ EURUSD/USDCHF {:.0}°, EURUSD/USDJPY {:.0}°, coherence {:.2}", together, against, daily.phase_coherence);
+
+    // Test 3: thresholds
+    println!("📊 Test 3: Thresholds");
+    let strict = SharedCycleConfig { min_pairs: 5, ..config.clone() };
+    ensure!(find_shared_cycles(&pairs, reference, &strict).is_empty(), "a 4-pair cycle passed min_pairs 5");
+    let tight = SharedCycleConfig { period_tolerance: 0.01, ..config.clone() };
+    let tight_pairs = find_shared_cycles(&pairs, reference, &tight);
+    ensure!(tight_pairs.len() == 1 && tight_pairs[0].pairs == ["EURUSD", "USDCHF", "USDJPY"], "tight tolerance {:?}", tight_pairs.first().map(|s| &s.pairs));
+    ensure!(find_shared_cycles(&pairs[..1], reference, &config).is_empty(), "one pair shares nothing");
+    println!("   ✅ min_pairs and period tolerance respected");
+
+    println!();
+    println!("🎉 All shared cycles tests passed");
+    Ok(())
+}
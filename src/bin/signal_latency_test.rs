@@ -0,0 +1,91 @@
+//! # Signal Latency Test
+//!
+//! Give the manager a signal latency budget and check that actions decided too
+//! long or too many bars before execution are recorded as missed instead of
+//! filled, that fresh actions still fill, and that the budget can be changed
+//! with the `set-latency` command
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, Utc};
+use std::collections::HashMap;
+
+use forex_pattern_reconstruction::laplacian_rl::TradingAction;
+use forex_pattern_reconstruction::multi_currency::{ControlCommand, MultiCurrencyManager};
+use forex_pattern_reconstruction::portfolio::orders::OrderStatus;
+use forex_pattern_reconstruction::risk::{SignalLatencyBudget, SignalOrigin};
+use forex_pattern_reconstruction::synthetic::fixtures::{parameters, recent_flat_bar};
+use forex_pattern_reconstruction::trading_windows::TradingWindowsConfig;
+
+/// Mark EURUSD's actions as decided `seconds_ago`, `bars_ago` bars back
+async fn decided(manager: &MultiCurrencyManager, seconds_ago: i64, bars_ago: usize) {
+    let mut pairs = manager.pairs.write().await;
+    let state = pairs.get_mut("EURUSD").unwrap();
+    state.signal_origin = Some(SignalOrigin {
+        observed_at: Utc::now() - Duration::seconds(seconds_ago),
+        bars: state.historical_data.len() - bars_ago,
+    });
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 SIGNAL LATENCY TEST");
+    println!("======================");
+    println!();
+    let now = Utc::now();
+
+    // Test 1: the budget checks time and bars separately
+    println!("📊 Test 1: Budget");
+    let origin = SignalOrigin { observed_at: now - Duration::seconds(10), bars: 100 };
+    ensure!(SignalLatencyBudget::default().expired(&origin, now, 1_000).is_none(), "an unset budget expired a signal");
+    let budget = SignalLatencyBudget { max_age_seconds: Some(5.0), max_age_bars: None };
+    ensure!(budget.expired(&origin, now, 100).is_some_and(|reason| reason.contains("10.0s old")), "10s signal kept under a 5s budget");
+    ensure!(budget.expired(&origin, now - Duration::seconds(6), 100).is_none(), "4s signal expired under a 5s budget");
+    let budget = SignalLatencyBudget { max_age_seconds: None, max_age_bars: Some(2) };
+    ensure!(budget.expired(&origin, now, 102).is_none(), "2-bar signal expired under a 2-bar budget");
+    ensure!(budget.expired(&origin, now, 103).is_some_and(|reason| reason.contains("3 bars old")), "3-bar signal kept under a 2-bar budget");
+    println!("✅ Seconds and bars limits applied");
+
+    // Test 2: the budget is set from the wire
+    println!("📊 Test 2: Command");
+    let command = ControlCommand::parse("set-latency", None, &parameters(&[("seconds", "5s"), ("bars", "off")]))?;
+    ensure!(command == Some(ControlCommand::SetSignalLatency { max_age_seconds: Some(5.0), max_age_bars: None }), "parsed {:?}", command);
+    ensure!(ControlCommand::parse("set-latency", None, &HashMap::new()).is_err(), "nothing to set");
+    ensure!(ControlCommand::parse("set_latency", None, &parameters(&[("bars", "soon")])).is_err(), "bars must be a count");
+    let mut manager = MultiCurrencyManager::new().with_trading_windows(TradingWindowsConfig::unrestricted());
+    manager.initialize_pairs(&["EURUSD".to_string()]).await?;
+    let message = manager.execute_command(&command.unwrap(), "test").await?;
+    ensure!(manager.risk_limits.read().await.signal_latency.max_age_seconds == Some(5.0), "budget not applied: {}", message);
+    let negative = ControlCommand::SetSignalLatency { max_age_seconds: Some(-1.0), max_age_bars: None };
+    ensure!(manager.execute_command(&negative, "test").await.is_err(), "negative budget accepted");
+    println!("✅ {}", message);
+
+    // Test 3: stale actions are missed, fresh ones fill
+    println!("📊 Test 3: Execution");
+    manager.pairs.write().await.get_mut("EURUSD").unwrap().historical_data = (0..5).rev().map(recent_flat_bar).collect();
+    let buy = HashMap::from([("EURUSD".to_string(), vec![TradingAction::Buy { size: 1 }, TradingAction::Hold])]);
+    decided(&manager, 10, 0).await;
+    manager.execute_actions(&buy).await;
+    let snapshot = manager.portfolio_snapshot().await;
+    ensure!(snapshot.positions.is_empty(), "stale buy was filled");
+    ensure!(snapshot.order_stats.missed == 1 && snapshot.order_stats.submitted == 1, "expected one missed order, got {:?}", snapshot.order_stats);
+    let missed = &snapshot.recent_orders[0];
+    ensure!(missed.status == OrderStatus::Missed && missed.summary().contains("missed"), "{:?}", missed);
+
+    decided(&manager, 1, 0).await;
+    manager.execute_actions(&buy).await;
+    ensure!(manager.portfolio_snapshot().await.positions.len() == 1, "fresh buy was not filled");
+
+    manager.execute_command(&ControlCommand::SetSignalLatency { max_age_seconds: None, max_age_bars: Some(2) }, "test").await?;
+    decided(&manager, 3_600, 3).await;
+    manager.execute_actions(&HashMap::from([("EURUSD".to_string(), vec![TradingAction::ClosePosition])])).await;
+    let snapshot = manager.portfolio_snapshot().await;
+    ensure!(snapshot.positions.len() == 1 && snapshot.order_stats.missed == 2, "close 3 bars late was not missed: {:?}", snapshot.order_stats);
+    decided(&manager, 3_600, 2).await;
+    manager.execute_actions(&HashMap::from([("EURUSD".to_string(), vec![TradingAction::ClosePosition])])).await;
+    ensure!(manager.portfolio_snapshot().await.positions.is_empty(), "close within the bar budget was not filled");
+    println!("✅ {} missed, fresh and in-budget actions filled", snapshot.order_stats.missed);
+
+    println!();
+    println!("🎉 All signal latency tests passed");
+    Ok(())
+}
@@ -0,0 +1,121 @@
+//! # Signal Policy Test
+//!
+//! Turn detected anomalies into trading signals through rule tables parsed
+//! from TOML, and check matching order, direction, sizing and horizon
+
+use anyhow::{ensure, Result};
+use chrono::Utc;
+
+use forex_pattern_reconstruction::anomaly::signal_policy::{RuleSignalPolicy, SignalPolicy, SignalPolicyConfig};
+use forex_pattern_reconstruction::anomaly::{
+    AnomalySeverity, AnomalyTradingSignal, AnomalyType, DetectedAnomaly, MarketContext,
+};
+use forex_pattern_reconstruction::data::ForexDataPoint;
+
+fn anomaly(anomaly_type: AnomalyType, severity: AnomalySeverity, confidence: f64, signal_type: Option<&str>) -> DetectedAnomaly {
+    DetectedAnomaly {
+        id: "test".into(),
+        timestamp: Utc::now(),
+        anomaly_type,
+        severity,
+        confidence,
+        deviation_magnitude: 0.01,
+        affected_symmetries: Vec::new(),
+        affected_cycles: Vec::new(),
+        market_context: MarketContext {
+            session: "London".to_string(),
+            volatility_regime: "Normal".to_string(),
+            trend_direction: "Sideways".to_string(),
+            recent_events: Vec::new(),
+            event_proximity: 0.0,
+        },
+        trading_signal: signal_type.map(|signal_type| AnomalyTradingSignal {
+            signal_type: signal_type.to_string(),
+            strength: 0.8,
+            confidence,
+            time_horizon: "Short".to_string(),
+            risk_level: "Medium".to_string(),
+            expected_duration: 60,
+        }),
+    }
+}
+
+fn spike(severity: AnomalySeverity, confidence: f64) -> DetectedAnomaly {
+    anomaly(AnomalyType::VolatilitySpike { expected_volatility: 0.001, actual_volatility: 0.004 }, severity, confidence, Some("Buy"))
+}
+
+fn bar() -> ForexDataPoint {
+    ForexDataPoint { timestamp: Utc::now(), open: 1.10, high: 1.11, low: 1.09, close: 1.105, volume: Some(1000.0) }
+}
+
+const RULES: &str = r#"
+[[rules]]
+anomaly_type = "VolatilitySpike"
+min_confidence = 0.8
+min_severity = "High"
+direction = "Reverse"
+strength_scale = 0.5
+time_horizon = "Medium"
+duration_minutes = 240
+
+[[rules]]
+anomaly_type = "VolatilitySpike"
+strength_scale = 2.0
+
+[[rules]]
+anomaly_type = "LiquidityGap"
+direction = "Suppress"
+
+[[rules]]
+anomaly_type = "*"
+direction = "Hold"
+"#;
+
+fn main() -> Result<()> {
+    println!("🔬 SIGNAL POLICY TEST");
+    println!("=====================");
+    println!();
+
+    // Test 1: rules load from TOML
+    println!("📊 Test 1: TOML rules");
+    let config: SignalPolicyConfig = toml::from_str(RULES)?;
+    let rules = config.rules.clone();
+    ensure!(rules.len() == 4, "expected 4 rules, got {}", rules.len());
+    let mut policy = config.build()?;
+    ensure!(policy.name() == "rules", "expected the rule policy, got {}", policy.name());
+    println!("✅ {} rules loaded", rules.len());
+
+    // Test 2: the first matching rule decides, by confidence and severity
+    println!("📊 Test 2: Rule order");
+    let strong = policy.signal(&spike(AnomalySeverity::Critical, 0.9), &bar())?.expect("strong spike suppressed");
+    ensure!(strong.signal_type == "Sell", "strong spike not reversed: {}", strong.signal_type);
+    ensure!((strong.strength - 0.4).abs() < 1e-9, "strength not halved: {}", strong.strength);
+    ensure!(strong.time_horizon == "Medium" && strong.expected_duration == 240, "horizon not applied");
+    let weak = policy.signal(&spike(AnomalySeverity::Medium, 0.9), &bar())?.expect("weak spike suppressed");
+    ensure!(weak.signal_type == "Buy" && weak.strength == 1.0, "second rule not applied: {:?}", weak);
+    println!("✅ Critical spike reversed to {}, medium spike sized up to {:.1}", strong.signal_type, weak.strength);
+
+    // Test 3: suppression, forced directions and fall-through
+    println!("📊 Test 3: Directions");
+    let gap = anomaly(AnomalyType::LiquidityGap { expected_volatility: 0.01, actual_volatility: 0.001, expected_volume: None, actual_volume: None }, AnomalySeverity::High, 0.9, Some("Buy"));
+    ensure!(policy.signal(&gap, &bar())?.is_none(), "liquidity gap not suppressed");
+    let novel = anomaly(AnomalyType::NovelPattern { pattern_signature: "test".into(), emergence_confidence: 0.5 }, AnomalySeverity::Low, 0.5, None);
+    let held = policy.signal(&novel, &bar())?.expect("wildcard rule produced no signal");
+    ensure!(held.signal_type == "Hold" && held.strength == 0.5, "wildcard hold not built from the anomaly: {:?}", held);
+    let mut empty = RuleSignalPolicy::new(Vec::new());
+    let kept = empty.signal(&spike(AnomalySeverity::Low, 0.1), &bar())?;
+    ensure!(kept.is_some_and(|signal| signal.signal_type == "Buy"), "unmatched anomaly lost its signal");
+    println!("✅ Suppress, Hold and pass-through behave");
+
+    // Test 4: the default policy changes nothing
+    println!("📊 Test 4: Default policy");
+    let mut default_policy = SignalPolicyConfig::default().build()?;
+    let proposed = spike(AnomalySeverity::High, 0.7);
+    let signal = default_policy.signal(&proposed, &bar())?.expect("default policy dropped the signal");
+    ensure!(signal.signal_type == "Buy" && signal.strength == 0.8, "default policy altered the signal");
+    println!("✅ Detector signals kept without rules");
+
+    println!();
+    println!("🎉 All signal policy tests passed");
+    Ok(())
+}
@@ -0,0 +1,128 @@
+//! # Similarity Hash Test
+//!
+//! Check the similarity index buckets perturbed windows with their originals,
+//! matches a full scan's nearest window, and survives the embedded database
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rand_distr::StandardNormal;
+
+use forex_pattern_reconstruction::anomaly::novelty::{NoveltyConfig, PatternClusters};
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::embedded_db::EmbeddedForexDB;
+use forex_pattern_reconstruction::galois::similarity::rank;
+use forex_pattern_reconstruction::galois::{SimilarityConfig, SimilarityIndex, WindowHasher};
+
+const BARS: usize = 20_000;
+
+/// Hourly random walk
+fn history(seed: u64) -> Vec<ForexDataPoint> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let start = Utc.with_ymd_and_hms(2022, 1, 3, 0, 0, 0).unwrap();
+    let mut close = 1.1;
+    (0..BARS)
+        .map(|i| {
+            close *= 1.0 + 0.001 * rng.sample::<f64, _>(StandardNormal);
+            ForexDataPoint { timestamp: start + Duration::hours(i as i64), open: close, high: close, low: close, close, volume: None }
+        })
+        .collect()
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 SIMILARITY HASH TEST");
+    println!("=======================");
+    println!();
+
+    let mut rng = StdRng::seed_from_u64(5);
+    let bars = history(1);
+    let config = SimilarityConfig::default();
+    let index = SimilarityIndex::fit(&bars, config.clone())?;
+    ensure!(index.len() == BARS - config.window_bars, "one window per bar once the window is full, got {}", index.len());
+    let perturb = |features: &[f64], rng: &mut StdRng| -> Vec<f64> { features.iter().map(|x| x + 0.1 * rng.sample::<f64, _>(StandardNormal)).collect() };
+
+    // Test 1: near windows share a bucket, unrelated ones rarely
+    println!("📊 Test 1: bucket collisions");
+    let hasher = WindowHasher::new(config.clone())?;
+    let collide = |a: &[f64], b: &[f64]| hasher.keys(a).iter().zip(hasher.keys(b)).any(|(x, y)| *x == y);
+    let trials = 500;
+    let (mut near, mut far) = (0, 0);
+    for _ in 0..trials {
+        let window = &index.windows()[rng.gen_range(0..index.len())].features;
+        let other = &index.windows()[rng.gen_range(0..index.len())].features;
+        near += collide(window, &perturb(window, &mut rng)) as usize;
+        far += collide(window, other) as usize;
+    }
+    ensure!(near * 100 >= trials * 95, "near windows collided {}/{}", near, trials);
+    ensure!(far * 100 <= trials * 15, "unrelated windows collided {}/{}", far, trials);
+    ensure!(hasher.keys(&index.windows()[0].features) == index.hasher().keys(&index.windows()[0].features), "keys are deterministic");
+    println!("   ✅ near {}/{}, unrelated {}/{}", near, trials, far, trials);
+
+    // Test 2: the index finds the full scan's nearest window while ranking a fraction of the history
+    println!("📊 Test 2: nearest windows");
+    let (mut found, mut ranked) = (0, 0);
+    for _ in 0..trials {
+        let target = &index.windows()[rng.gen_range(0..index.len())];
+        let query = perturb(&target.features, &mut rng);
+        let indexed = index.nearest(&query, 5);
+        let scanned = rank(index.windows().iter(), &query, 5);
+        ensure!(indexed.windows(2).all(|w| w[0].distance <= w[1].distance), "nearest first");
+        found += (indexed.first().map(|w| w.window_end) == scanned.first().map(|w| w.window_end)) as usize;
+        ranked += index.candidates(&query).len();
+    }
+    let share = ranked as f64 / (trials * index.len()) as f64;
+    ensure!(found * 100 >= trials * 95, "nearest window found {}/{}", found, trials);
+    ensure!(share < 0.1, "ranked {:.1}% of the history per query", share * 100.0);
+    println!("   ✅ full-scan nearest found {}/{} ranking {:.1}% of {} windows", found, trials, share * 100.0, index.len());
+
+    // Test 3: the index round-trips through the embedded database
+    println!("📊 Test 3: embedded database");
+    let path = std::env::temp_dir().join(format!("similarity_hash_test_{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let db = EmbeddedForexDB::open(&path)?;
+    ensure!(db.store_similarity_index("EURUSD", &index)? == index.len(), "every window stored");
+    ensure!(db.store_similarity_index("EURUSD", &index)? == index.len(), "storing again replaces");
+    let loaded = db.load_similarity_index("EURUSD")?.expect("stored index");
+    ensure!(loaded.len() == index.len() && loaded.config() == index.config() && loaded.return_scale() == index.return_scale(), "same index");
+    let target = &index.windows()[1234];
+    let query = perturb(&target.features, &mut rng);
+    let in_memory = index.nearest(&query, 3);
+    let stored = db.similar_windows("EURUSD", &query, 3)?;
+    ensure!(loaded.nearest(&query, 3).iter().map(|w| w.window_end).eq(in_memory.iter().map(|w| w.window_end)), "loaded index answers alike");
+    ensure!(stored.iter().map(|w| w.window_end).eq(in_memory.iter().map(|w| w.window_end)), "{:?} vs {:?}", stored, in_memory);
+    ensure!(stored[0].window_end == target.window_end, "the perturbed window's original is nearest");
+    ensure!(db.load_similarity_index("GBPUSD")?.is_none() && db.similar_windows("GBPUSD", &query, 3)?.is_empty(), "no index for GBPUSD");
+    drop(db);
+    let _ = std::fs::remove_file(&path);
+    println!("   ✅ {} windows stored, nearest {} at {:.3}", loaded.len(), stored[0].window_end, stored[0].distance);
+
+    // Test 4: novelty observations name the closest historical window
+    println!("📊 Test 4: novelty analogs");
+    let mut clusters = PatternClusters::fit(&bars[..BARS / 2], NoveltyConfig::default());
+    let fitted = clusters.similarity_index().map(|i| i.len()).unwrap_or(0);
+    let repeat = 3000;
+    let needed = clusters.bars_needed();
+    let mut replay = bars[repeat + 1 - needed..=repeat].to_vec();
+    let offset = bars[BARS - 1].timestamp - bars[repeat].timestamp + Duration::hours(1);
+    replay.iter_mut().for_each(|bar| bar.timestamp += offset);
+    let novelty = clusters.observe(&replay).expect("a full window");
+    let analog = novelty.nearest_historical.expect("an analog");
+    ensure!(analog.window_end == bars[repeat].timestamp && analog.distance < 1e-9, "{:?}", analog);
+    ensure!(clusters.similarity_index().map(|i| i.len()) == Some(fitted + 1), "observed windows are indexed");
+    println!("   ✅ Replayed window matched its original at {}", analog.window_end);
+
+    // Test 5: bad settings are refused
+    println!("📊 Test 5: limits");
+    ensure!(WindowHasher::new(SimilarityConfig { rows_per_band: 9, ..config.clone() }).is_err(), "more rows than returns");
+    ensure!(WindowHasher::new(SimilarityConfig { quantization_step: 0.0, ..config.clone() }).is_err(), "zero step");
+    ensure!(SimilarityIndex::new(config.clone(), 0.0).is_err(), "zero return scale");
+    let flat: Vec<ForexDataPoint> = bars[..100].iter().map(|bar| ForexDataPoint { close: 1.1, ..bar.clone() }).collect();
+    ensure!(SimilarityIndex::fit(&flat, config).is_err(), "a flat history has no return scale");
+    println!("   ✅ Refused");
+
+    println!();
+    println!("🎉 All similarity hash tests passed");
+    Ok(())
+}
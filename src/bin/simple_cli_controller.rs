@@ -1,12 +1,18 @@
 use clap::{Arg, Command};
-use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::error::Error;
 use tokio;
 use std::io::{self, Write};
 
+use forex_pattern_reconstruction::protocol::client::ControllerClient;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
+
+use forex_pattern_reconstruction::protocol::{
+    CommandResponse, CommandStatus, PendingApproval, PortfolioSnapshot, RemoteSystemStatus, ThroughputPanel, TradingCommand,
+};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum TradingMode {
     Demo,
@@ -46,57 +52,15 @@ impl TradingMode {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct RemoteSystemStatus {
-    status: String,
-    uptime: u64,
-    active_pairs: Vec<String>,
-    total_trades: u64,
-    profit_loss: f64,
-    correlation_opportunities: Vec<ArbitrageOpportunity>,
-    system_metrics: SystemMetrics,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-struct ArbitrageOpportunity {
-    primary_pair: String,
-    correlated_pair: String,
-    confidence: f64,
-    theoretical_pips: f64,
-    realistic_pips: f64,
-    execution_cost: f64,
-    net_expected_pips: f64,
-    position_size: f64,
-    time_window: String,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-struct SystemMetrics {
-    cpu_usage: f64,
-    memory_usage: f64,
-    network_latency: f64,
-    database_size: u64,
-    active_connections: u32,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-struct TradingCommand {
-    action: String,
-    pair: Option<String>,
-    parameters: HashMap<String, String>,
-}
-
 struct SimpleCliController {
-    client: Client,
-    render_endpoint: String,
+    client: ControllerClient,
     current_mode: TradingMode,
 }
 
 impl SimpleCliController {
     fn new(render_endpoint: String) -> Self {
         SimpleCliController {
-            client: Client::new(),
-            render_endpoint,
+            client: ControllerClient::new(&render_endpoint),
             current_mode: TradingMode::Demo, // Default to demo mode for safety
         }
     }
@@ -125,7 +89,7 @@ impl SimpleCliController {
         println!("📊 Server: {}", mode.server());
         println!("🔑 Account ID: {}", mode.account_id());
 
-        Ok(response)
+        Ok(response.message)
     }
 
     fn display_current_mode(&self) {
@@ -191,7 +155,7 @@ impl SimpleCliController {
             println!("🔑 Account ID: {}", mode.account_id());
         }
 
-        Ok(response)
+        Ok(response.message)
     }
 
     async fn set_custom_credentials(
@@ -224,25 +188,215 @@ impl SimpleCliController {
         println!("💰 Live Account: {}", live_account);
         println!("⚠️  Client Secret: [HIDDEN FOR SECURITY]");
 
-        Ok(response)
+        Ok(response.message)
     }
 
     async fn fetch_system_status(&self) -> Result<RemoteSystemStatus, Box<dyn std::error::Error>> {
-        let url = format!("{}/api/status", self.render_endpoint);
-        let response = self.client.get(&url).send().await?;
-        
-        if response.status().is_success() {
-            Ok(response.json().await?)
+        Ok(self.client.fetch_status().await?)
+    }
+
+    async fn fetch_portfolio(&self) -> Result<PortfolioSnapshot, Box<dyn std::error::Error>> {
+        Ok(self.client.fetch_portfolio().await?)
+    }
+
+    fn display_portfolio(&self, portfolio: &PortfolioSnapshot) {
+        let ccy = &portfolio.account_currency;
+        println!("╔═══════════════════════════════════════════════════════════════════════════════════╗");
+        println!("║                              PORTFOLIO                                           ║");
+        println!("╠═══════════════════════════════════════════════════════════════════════════════════╣");
+        println!("║ Equity: {:66.2} {} ║", portfolio.equity, ccy);
+        println!("║ Balance: {:65.2} {} ║", portfolio.balance, ccy);
+        println!("║ Realized P&L: {:60.2} {} ║", portfolio.realized_pnl, ccy);
+        println!("║ Unrealized P&L: {:58.2} {} ║", portfolio.unrealized_pnl, ccy);
+        println!("║ Margin Used: {:61.2} {} ║", portfolio.margin_used, ccy);
+        println!("║ Free Margin: {:61.2} {} ║", portfolio.free_margin, ccy);
+        match portfolio.margin_level {
+            Some(level) => println!("║ Margin Level: {:63.1}% ║", level),
+            None => println!("║ Margin Level: {:64} ║", "n/a (flat)"),
+        }
+        println!("║ Closed Trades: {:62} ║", portfolio.total_trades);
+        println!("║ Win Rate: {:67.1}% ║", portfolio.win_rate);
+        println!("╚═══════════════════════════════════════════════════════════════════════════════════╝");
+
+        if portfolio.positions.is_empty() {
+            println!("📭 No open positions");
         } else {
-            Err(format!("Failed to fetch status: {}", response.status()).into())
+            println!("📂 Open Positions:");
+            for position in &portfolio.positions {
+                println!("   {} {:5} {:>10.0} @ {:.5} → {:.5} | P&L {:>10.2} {} | Margin {:.2}",
+                    position.symbol, position.side, position.units, position.entry_price,
+                    position.current_price, position.unrealized_pnl, ccy, position.margin_used);
+            }
+        }
+
+        if !portfolio.currency_exposure.is_empty() {
+            println!("🌍 Net Currency Exposure:");
+            for exposure in &portfolio.currency_exposure {
+                println!("   {} {:>14.2} ({:>12.2} {})",
+                    exposure.currency, exposure.net_amount, exposure.account_value, ccy);
+            }
+        }
+
+        if !portfolio.recent_orders.is_empty() {
+            let stats = &portfolio.order_stats;
+            println!("🧾 Recent Orders ({} submitted, {} filled, {} partial, {} rejected, {} missed):",
+                stats.submitted, stats.filled, stats.partially_filled, stats.rejected, stats.missed);
+            for order in portfolio.recent_orders.iter().take(10) {
+                println!("   {} {}", order.timestamp.format("%H:%M:%S"), order.summary());
+            }
+        }
+
+        println!("🕒 As of: {}", portfolio.timestamp.format("%Y-%m-%d %H:%M:%S UTC"));
+    }
+
+    /// One row per pair; lagging pairs are marked and say why
+    fn display_throughput(&self, panel: &ThroughputPanel) {
+        println!("╔═══════════════════════════════════════════════════════════════════════════════════╗");
+        println!("║                          PAIR THROUGHPUT                                         ║");
+        println!("╠═══════════════════════════════════════════════════════════════════════════════════╣");
+        println!("║    {:8} {:>9} {:>10} {:>10} {:>8}  {:19}      ║", "Pair", "bars/s", "detect ms", "p95 ms", "backlog", "last data");
+        let ms = |value: Option<f64>| value.map_or("-".to_string(), |ms| format!("{:.1}", ms));
+        for pair in &panel.pairs {
+            let marker = if pair.lagging.is_empty() { "  " } else { "🐢" };
+            println!("║ {} {:8} {:>9.2} {:>10} {:>10} {:>8}  {:19}      ║",
+                marker, pair.symbol, pair.bars_per_sec,
+                ms(pair.detection_latency_ms), ms(pair.detection_latency_p95_ms), pair.backlog_bars,
+                pair.last_data.map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string()).unwrap_or_else(|| "never".to_string()));
+        }
+        println!("╚═══════════════════════════════════════════════════════════════════════════════════╝");
+        for pair in panel.pairs.iter().filter(|pair| !pair.lagging.is_empty()) {
+            println!("🐢 {} lagging: {}", pair.symbol, pair.lagging.join(", "));
+        }
+        println!("📬 Stream backlog: {} messages", panel.stream_backlog);
+    }
+
+    async fn get_throughput(&self) -> Result<(), Box<dyn std::error::Error>> {
+        match self.client.fetch_throughput().await {
+            Ok(panel) => self.display_throughput(&panel),
+            Err(e) => println!("❌ Error: {}", e),
         }
+        Ok(())
     }
 
-    async fn send_command(&self, command: TradingCommand) -> Result<String, Box<dyn std::error::Error>> {
-        let url = format!("{}/api/command", self.render_endpoint);
-        let response = self.client.post(&url).json(&command).send().await?;
+    async fn get_portfolio(&self) -> Result<(), Box<dyn std::error::Error>> {
+        println!("🔍 Fetching portfolio...");
         
-        Ok(response.text().await?)
+        match self.fetch_portfolio().await {
+            Ok(portfolio) => self.display_portfolio(&portfolio),
+            Err(e) => println!("❌ Error: {}", e),
+        }
+
+        Ok(())
+    }
+
+    async fn stream(&self, kind: &str) -> Result<(), Box<dyn std::error::Error>> {
+        println!("📡 Streaming {} from {} (Ctrl+C to stop)", kind, self.client.endpoint());
+        let mut messages = self.client.subscribe(kind).await?;
+
+        while let Some(message) = messages.recv().await {
+            let field = |key: &str| message.get(key).cloned().unwrap_or_default();
+            match kind {
+                "prices" => println!("💹 {} {} {}", field("timestamp"), field("symbol"), field("price")),
+                _ if field("kind") == "params_epoch" => {
+                    let changes = field("changes").as_array().cloned().unwrap_or_default().iter()
+                        .map(|change| {
+                            let text = |key: &str| change[key].as_str().unwrap_or_default().to_string();
+                            format!("{} {} → {}", text("parameter"), text("old"), text("new"))
+                        })
+                        .collect::<Vec<_>>();
+                    println!("⚙️  Parameters epoch {} by {}: {}", field("epoch"), field("source"), changes.join(", "));
+                }
+                _ => {
+                    let anomaly = field("anomaly");
+                    println!("🚨 {} {} {} {} severity {} confidence {}",
+                        field("symbol"), anomaly.get("timestamp").cloned().unwrap_or_default(),
+                        field("pattern_id"), field("anomaly_type"),
+                        anomaly.get("severity").cloned().unwrap_or_default(),
+                        anomaly.get("confidence").cloned().unwrap_or_default());
+                }
+            }
+        }
+
+        println!("🔌 Stream closed");
+        Ok(())
+    }
+
+    async fn send_command(&self, command: TradingCommand) -> Result<CommandResponse, Box<dyn std::error::Error>> {
+        Ok(self.client.send_command(&command).await?)
+    }
+
+    /// Send a control command (pause, flatten, suppress, ...) and print the server's confirmation
+    async fn list_suppressions(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let rules = self.client.fetch_suppressions().await?;
+        if rules.is_empty() {
+            println!("🔕 No active acknowledgments or suppressions");
+        }
+        for rule in rules {
+            println!("🔕 [{}] {} (matched {}, by {})", rule.id, rule, rule.matched, rule.created_by);
+        }
+        Ok(())
+    }
+
+    fn display_approvals(&self, pending: &[PendingApproval]) {
+        if pending.is_empty() {
+            println!("⏳ No actions awaiting approval\r");
+        }
+        let now = chrono::Utc::now();
+        for approval in pending {
+            println!("⏳ {}\r", approval.summary(now));
+        }
+    }
+
+    async fn list_approvals(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let pending = self.client.fetch_approvals().await?;
+        self.display_approvals(&pending);
+        Ok(())
+    }
+
+    /// Show held actions with their countdowns and answer the oldest by keypress
+    async fn watch_approvals(&self) -> Result<(), Box<dyn std::error::Error>> {
+        println!("⏳ Approvals on {}: y approve oldest, n reject oldest, a approve all, q quit", self.client.endpoint());
+        enable_raw_mode()?;
+        let result = self.approval_loop().await;
+        disable_raw_mode()?;
+        result
+    }
+
+    async fn approval_loop(&self) -> Result<(), Box<dyn std::error::Error>> {
+        loop {
+            let pending = self.client.fetch_approvals().await?;
+            println!("\r");
+            self.display_approvals(&pending);
+            if !event::poll(std::time::Duration::from_secs(1))? {
+                continue;
+            }
+            let Event::Key(key) = event::read()? else { continue };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            let oldest = pending.first().map(|approval| approval.id.to_string());
+            let (action, id) = match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Char('y') => ("approve", oldest),
+                KeyCode::Char('n') => ("reject", oldest),
+                KeyCode::Char('a') => ("approve", Some("all".to_string())),
+                _ => continue,
+            };
+            let Some(id) = id else { continue };
+            let response = self.send_command(TradingCommand::new(action).with_parameter("id", &id)).await?;
+            println!("{} {}\r", if response.status == CommandStatus::Success { "✅" } else { "❌" }, response.message);
+        }
+    }
+
+    async fn control(&self, command: TradingCommand) -> Result<(), Box<dyn std::error::Error>> {
+        let response = self.send_command(command).await?;
+        match response.status {
+            CommandStatus::Success => println!("✅ {}", response.message),
+            CommandStatus::Error => println!("❌ {}", response.message),
+            CommandStatus::Acknowledged => println!("⚠️  {}", response.message),
+        }
+
+        Ok(())
     }
 
     async fn monitor_system(&self) -> Result<(), Box<dyn std::error::Error>> {
@@ -253,7 +407,7 @@ impl SimpleCliController {
         println!("║                                                                                   ║");
         println!("╚═══════════════════════════════════════════════════════════════════════════════════╝");
         println!();
-        println!("📡 Connecting to: {}", self.render_endpoint);
+        println!("📡 Connecting to: {}", self.client.endpoint());
         println!("⏱️  Fetching system status every 10 seconds...");
         println!("🔄 Press Ctrl+C to stop monitoring");
         println!();
@@ -267,6 +421,10 @@ impl SimpleCliController {
                     println!("❌ Error fetching status: {}", e);
                 }
             }
+            // Only dashboards serve the panel; a plain daemon has none to show
+            if let Ok(panel) = self.client.fetch_throughput().await {
+                self.display_throughput(&panel);
+            }
 
             tokio::time::sleep(tokio::time::Duration::from_secs(10)).await;
         }
@@ -302,6 +460,18 @@ impl SimpleCliController {
             println!();
         }
 
+        if let Some(feed) = &status.feed_health {
+            println!("📡 Feed Health: {}", feed.overall.to_string().to_uppercase());
+            for pair in feed.pairs.iter().filter(|pair| pair.state.blocks_trading() || pair.future_dated > 0) {
+                println!("   {} {} | last tick {} | skew {}ms | {} future-dated",
+                    pair.symbol,
+                    pair.state,
+                    pair.last_tick_at.map(|t| t.format("%H:%M:%S").to_string()).unwrap_or_else(|| "never".to_string()),
+                    pair.clock_skew_ms.unwrap_or(0),
+                    pair.future_dated);
+            }
+        }
+
         println!("📊 Active Currency Pairs: {}", status.active_pairs.join(", "));
         println!("🕒 Last Updated: {}", chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC"));
         println!("═══════════════════════════════════════════════════════════════════════════════════");
@@ -345,7 +515,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 .short('e')
                 .long("endpoint")
                 .value_name("URL")
-                .help("Render deployment endpoint URL")
+                .help("Trading server or dashboard endpoint URL")
                 .default_value("http://localhost:8080"),
         )
         .subcommand(
@@ -356,6 +526,111 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             Command::new("status")
                 .about("Get current system status (one-time)")
         )
+        .subcommand(
+            Command::new("stream")
+                .about("Follow live prices or anomalies from a running dashboard")
+                .arg(Arg::new("kind").help("prices or anomalies").default_value("prices"))
+        )
+        .subcommand(
+            Command::new("portfolio")
+                .about("Show equity, margin, open positions and currency exposure")
+        )
+        .subcommand(
+            Command::new("throughput")
+                .about("Show bars/sec, detection latency, last data and backlog of every pair on a dashboard")
+        )
+        .subcommand(
+            Command::new("pause")
+                .about("Stop trading a currency pair")
+                .arg(Arg::new("pair").help("Currency pair, e.g. EURUSD").required(true))
+        )
+        .subcommand(
+            Command::new("resume")
+                .about("Resume trading a paused currency pair")
+                .arg(Arg::new("pair").help("Currency pair, e.g. EURUSD").required(true))
+        )
+        .subcommand(
+            Command::new("flatten")
+                .about("Close the open position in a pair, or all positions")
+                .arg(Arg::new("pair").help("Currency pair or 'all'").required(true))
+        )
+        .subcommand(
+            Command::new("set-risk")
+                .about("Update portfolio risk limits")
+                .arg(
+                    Arg::new("max_drawdown")
+                        .long("max-drawdown")
+                        .help("Maximum drawdown from peak equity, e.g. 5%")
+                        .required(true)
+                        .value_name("PERCENT")
+                )
+        )
+        .subcommand(
+            Command::new("set-latency")
+                .about("Expire signals not executed within a time or bar budget")
+                .arg(Arg::new("seconds").long("seconds").help("Seconds from decision to execution, or 'off'").value_name("SECONDS"))
+                .arg(Arg::new("bars").long("bars").help("Bars arriving between decision and execution, or 'off'").value_name("BARS"))
+        )
+        .subcommand(
+            Command::new("set-params")
+                .about("Change analysis parameters on the running pairs")
+                .arg(Arg::new("sensitivity").long("sensitivity").help("Anomaly sensitivity threshold, or 'auto' to calibrate").value_name("THRESHOLD"))
+                .arg(Arg::new("cycle_confidence").long("cycle-confidence").help("Least confidence of a hidden cycle, 0 to 1").value_name("THRESHOLD"))
+                .arg(Arg::new("symmetry_strength").long("symmetry-strength").help("Least strength of a temporal symmetry, 0 to 1").value_name("THRESHOLD"))
+        )
+        .subcommand(
+            Command::new("kill-switch")
+                .about("Close every position and refuse new exposure until reset")
+        )
+        .subcommand(
+            Command::new("reset-kill-switch")
+                .about("Re-arm a tripped kill switch")
+        )
+        .subcommand(
+            Command::new("ack")
+                .about("Acknowledge an anomaly pattern, silencing it for the acknowledgment period")
+                .arg(Arg::new("pair").help("Currency pair or 'all'").required(true))
+                .arg(Arg::new("pattern").long("pattern").help("Symmetry or cycle id (default: any)").value_name("ID"))
+                .arg(Arg::new("type").long("type").help("Anomaly type, e.g. VolatilitySpike (default: any)").value_name("TYPE"))
+        )
+        .subcommand(
+            Command::new("suppress")
+                .about("Exclude an anomaly pattern from alerting and trading")
+                .arg(Arg::new("pair").help("Currency pair or 'all'").required(true))
+                .arg(Arg::new("pattern").long("pattern").help("Symmetry or cycle id (default: any)").value_name("ID"))
+                .arg(Arg::new("type").long("type").help("Anomaly type, e.g. VolatilitySpike (default: any)").value_name("TYPE"))
+                .arg(Arg::new("minutes").long("minutes").help("Suppression period (default: server setting)").value_name("MINUTES"))
+        )
+        .subcommand(
+            Command::new("unsuppress")
+                .about("Remove an acknowledgment or suppression")
+                .arg(Arg::new("id").help("Rule id from 'suppressions'").required(true))
+        )
+        .subcommand(
+            Command::new("suppressions")
+                .about("List active acknowledgments and suppressions")
+        )
+        .subcommand(
+            Command::new("set-mode")
+                .about("Send actions automatically or hold them for approval")
+                .arg(Arg::new("mode").help("automatic or approval").required(true))
+                .arg(Arg::new("timeout").long("timeout").help("Seconds to answer a held action").value_name("SECONDS"))
+        )
+        .subcommand(
+            Command::new("approvals")
+                .about("List actions awaiting approval")
+                .arg(Arg::new("watch").long("watch").help("Keep listing and answer by keypress").action(clap::ArgAction::SetTrue))
+        )
+        .subcommand(
+            Command::new("approve")
+                .about("Send an action held for approval")
+                .arg(Arg::new("id").help("Approval id from 'approvals', or 'all'").required(true))
+        )
+        .subcommand(
+            Command::new("reject")
+                .about("Drop an action held for approval")
+                .arg(Arg::new("id").help("Approval id from 'approvals', or 'all'").required(true))
+        )
         .subcommand(
             Command::new("deploy")
                 .about("Deploy system to Render using MCP tools")
@@ -446,6 +721,79 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             println!();
             controller.get_status().await?;
         }
+        Some(("portfolio", _)) => {
+            controller.get_portfolio().await?;
+        }
+        Some(("throughput", _)) => {
+            controller.get_throughput().await?;
+        }
+        Some(("stream", sub_matches)) => {
+            controller.stream(sub_matches.get_one::<String>("kind").unwrap()).await?;
+        }
+        Some((action @ ("pause" | "resume" | "flatten"), sub_matches)) => {
+            let pair = sub_matches.get_one::<String>("pair").unwrap().to_uppercase();
+            controller.control(TradingCommand::new(action).with_pair(&pair)).await?;
+        }
+        Some(("set-risk", sub_matches)) => {
+            let max_drawdown = sub_matches.get_one::<String>("max_drawdown").unwrap();
+            controller.control(TradingCommand::new("set_risk").with_parameter("max_drawdown", max_drawdown)).await?;
+        }
+        Some(("set-latency", sub_matches)) => {
+            let mut command = TradingCommand::new("set_latency");
+            for key in ["seconds", "bars"] {
+                if let Some(value) = sub_matches.get_one::<String>(key) {
+                    command = command.with_parameter(key, value);
+                }
+            }
+            controller.control(command).await?;
+        }
+        Some(("set-params", sub_matches)) => {
+            let mut command = TradingCommand::new("set_params");
+            for key in ["sensitivity", "cycle_confidence", "symmetry_strength"] {
+                if let Some(value) = sub_matches.get_one::<String>(key) {
+                    command = command.with_parameter(key, value);
+                }
+            }
+            controller.control(command).await?;
+        }
+        Some((action @ ("kill-switch" | "reset-kill-switch"), _)) => {
+            controller.control(TradingCommand::new(action)).await?;
+        }
+        Some((action @ ("ack" | "suppress"), sub_matches)) => {
+            let pair = sub_matches.get_one::<String>("pair").unwrap().to_uppercase();
+            let mut command = TradingCommand::new(if action == "ack" { "acknowledge" } else { "suppress" }).with_pair(&pair);
+            for key in ["pattern", "type", "minutes"] {
+                if let Some(value) = sub_matches.try_get_one::<String>(key).ok().flatten() {
+                    command = command.with_parameter(key, value);
+                }
+            }
+            controller.control(command).await?;
+        }
+        Some(("unsuppress", sub_matches)) => {
+            let id = sub_matches.get_one::<String>("id").unwrap();
+            controller.control(TradingCommand::new("unsuppress").with_parameter("id", id)).await?;
+        }
+        Some(("suppressions", _)) => {
+            controller.list_suppressions().await?;
+        }
+        Some(("set-mode", sub_matches)) => {
+            let mut command = TradingCommand::new("set_mode").with_parameter("mode", sub_matches.get_one::<String>("mode").unwrap());
+            if let Some(timeout) = sub_matches.get_one::<String>("timeout") {
+                command = command.with_parameter("timeout", timeout);
+            }
+            controller.control(command).await?;
+        }
+        Some(("approvals", sub_matches)) => {
+            if sub_matches.get_flag("watch") {
+                controller.watch_approvals().await?;
+            } else {
+                controller.list_approvals().await?;
+            }
+        }
+        Some((action @ ("approve" | "reject"), sub_matches)) => {
+            let id = sub_matches.get_one::<String>("id").unwrap();
+            controller.control(TradingCommand::new(action).with_parameter("id", id)).await?;
+        }
         Some(("deploy", _)) => {
             controller.deploy_system().await?;
         }
@@ -518,6 +866,24 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             println!("Available commands:");
             println!("  monitor         - Start continuous monitoring dashboard");
             println!("  status          - Get current system status");
+            println!("  portfolio       - Show equity, margin, positions and exposure");
+            println!("  throughput      - Show per-pair bars/sec, detection latency and backlog");
+            println!("  pause <pair>    - Stop trading a pair");
+            println!("  resume <pair>   - Resume trading a pair");
+            println!("  flatten <pair|all> - Close open positions");
+            println!("  set-risk --max-drawdown 5% - Update portfolio drawdown limit");
+            println!("  set-latency --seconds <s|off> --bars <n|off> - Expire signals not executed in time");
+            println!("  set-params --sensitivity <x|auto> --cycle-confidence <x> --symmetry-strength <x> - Change analysis parameters");
+            println!("  kill-switch     - Close every position and refuse new exposure");
+            println!("  reset-kill-switch - Re-arm a tripped kill switch");
+            println!("  ack <pair|all> --pattern <id> --type <type> - Acknowledge a recurring anomaly");
+            println!("  suppress <pair|all> --pattern <id> --type <type> [--minutes N] - Suppress an anomaly pattern");
+            println!("  suppressions    - List active acknowledgments and suppressions");
+            println!("  unsuppress <id> - Remove an acknowledgment or suppression");
+            println!("  set-mode <automatic|approval> [--timeout N] - Hold actions for operator approval");
+            println!("  approvals [--watch] - List held actions, or answer them by keypress");
+            println!("  approve <id|all> - Send a held action");
+            println!("  reject <id|all> - Drop a held action");
             println!("  deploy          - Deploy system to Render");
             println!("  mode <demo|live> - Switch between DEMO and LIVE trading modes");
             println!("  current-mode    - Display current trading mode configuration");
@@ -535,6 +901,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             println!("Example usage:");
             println!("  {} -e https://your-render-app.onrender.com monitor",
                 std::env::args().next().unwrap_or_else(|| "forex-cli".to_string()));
+            println!("  {} -e http://localhost:8080 stream prices   # local `forex-pattern-analyzer dashboard`",
+                std::env::args().next().unwrap_or_else(|| "forex-cli".to_string()));
             println!("  {} mode demo    # Switch to demo mode (default credentials)",
                 std::env::args().next().unwrap_or_else(|| "forex-cli".to_string()));
             println!("  {} mode live --client-id YOUR_ID --account-id YOUR_ACCOUNT",
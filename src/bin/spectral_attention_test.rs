@@ -0,0 +1,187 @@
+//! # Spectral Attention Test
+//!
+//! Check the RL agent embeds its De Bruijn graph with Laplacian eigenvectors,
+//! attends and shapes rewards by it, and rebuilds both from a saved Q-table
+
+use anyhow::{ensure, Result};
+use chrono::Utc;
+
+use forex_pattern_reconstruction::anomaly::{AnomalySeverity, AnomalyType, DetectedAnomaly, MarketContext};
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::laplacian_rl::{LaplacianQLearningAgent, LaplacianQLearningConfig, QTableSnapshot, TradingAction};
+
+fn anomaly(anomaly_type: AnomalyType) -> DetectedAnomaly {
+    DetectedAnomaly {
+        id: "spectral_test".into(),
+        timestamp: Utc::now(),
+        anomaly_type,
+        severity: AnomalySeverity::Medium,
+        confidence: 0.8,
+        deviation_magnitude: 1.0,
+        affected_symmetries: Vec::new(),
+        affected_cycles: Vec::new(),
+        market_context: MarketContext {
+            session: "London".to_string(),
+            volatility_regime: "Normal".to_string(),
+            trend_direction: "Sideways".to_string(),
+            recent_events: Vec::new(),
+            event_proximity: 0.0,
+        },
+        trading_signal: None,
+    }
+}
+
+fn novel_pattern(emergence_confidence: f64) -> DetectedAnomaly {
+    anomaly(AnomalyType::NovelPattern { pattern_signature: "test".to_string(), emergence_confidence })
+}
+
+/// Pattern strengths rising and falling through the ten symbol levels the
+/// agents use, which grows a ring of eighteen nodes
+fn walk() -> Vec<f64> {
+    (0..10).chain((1..9).rev()).map(|level| (level as f64 + 0.5) / 10.0).collect()
+}
+
+fn bar() -> ForexDataPoint {
+    ForexDataPoint { timestamp: Utc::now(), open: 1.1, high: 1.101, low: 1.099, close: 1.1, volume: None }
+}
+
+fn config(shaping_weight: f64) -> LaplacianQLearningConfig {
+    LaplacianQLearningConfig { exploration_rate: 0.0, shaping_weight, symbol_levels: 10, ..LaplacianQLearningConfig::default() }
+}
+
+/// Agent that has observed the walk ten times
+fn agent(shaping_weight: f64) -> Result<LaplacianQLearningAgent> {
+    let mut agent = LaplacianQLearningAgent::new(config(shaping_weight))?;
+    for strength in walk().iter().cycle().take(180) {
+        agent.anomaly_to_state(&novel_pattern(*strength), &bar())?;
+    }
+    agent.refresh_spectral_embedding();
+    Ok(agent)
+}
+
+/// Id of the node at `index` of the grown graph
+fn node(agent: &LaplacianQLearningAgent, index: usize) -> String {
+    agent.graph().nodes()[index].id.clone()
+}
+
+/// Reward buying on `state` until its value settles
+fn train(agent: &mut LaplacianQLearningAgent, state: &str) -> Result<()> {
+    for _ in 0..50 {
+        agent.update_q_value(state, TradingAction::Buy { size: 10 }, 1.0, state, true)?;
+    }
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 SPECTRAL ATTENTION TEST");
+    println!("==========================");
+    println!();
+
+    // Test 1: the embedding
+    println!("📊 Test 1: Laplacian eigenvectors");
+    let mut fresh = agent(0.1)?;
+    let embedding = fresh.spectral_embedding().cloned().ok_or_else(|| anyhow::anyhow!("the grown graph is embedded"))?;
+    let nodes = embedding.nodes();
+    ensure!(nodes == 18 && embedding.dimensions() >= 4, "{} nodes in {} dimensions", nodes, embedding.dimensions());
+    let eigenvalues = embedding.eigenvalues();
+    ensure!(eigenvalues[0] > 1e-9 && eigenvalues.windows(2).all(|pair| pair[0] <= pair[1]), "smallest non-trivial eigenvalues, ascending: {:?}", eigenvalues);
+    for column in 0..embedding.dimensions() {
+        let sum: f64 = (0..nodes).map(|index| embedding.coordinates(index)[column]).sum();
+        ensure!(sum.abs() < 1e-9, "eigenvector {} is orthogonal to the constant one", column);
+    }
+    let pairs: Vec<(usize, usize)> = (0..nodes).flat_map(|a| (a + 1..nodes).map(move |b| (a, b))).collect();
+    let diameter = pairs.iter().map(|(a, b)| embedding.distance(*a, *b)).fold(0.0, f64::max);
+    ensure!((diameter - 1.0).abs() < 1e-9 && embedding.distance(5, 5) == 0.0, "scaled to unit diameter");
+    ensure!((embedding.distance(3, 11) - embedding.distance(11, 3)).abs() < 1e-12, "symmetric");
+    // The walk visits the ring's nodes in the order they were added
+    let edges: Vec<(usize, usize)> = (0..nodes).map(|index| (index, (index + 1) % nodes)).collect();
+    ensure!(edges.iter().all(|(a, b)| fresh.graph().transition_probability(&node(&fresh, *a), &node(&fresh, *b)) == 1.0), "the walk is a ring");
+    let mean = |pairs: &[(usize, usize)]| pairs.iter().map(|(a, b)| embedding.distance(*a, *b)).sum::<f64>() / pairs.len() as f64;
+    let (edge_distance, pair_distance) = (mean(&edges), mean(&pairs));
+    ensure!(edge_distance < pair_distance, "neighbours lie closer than average: {:.3} vs {:.3}", edge_distance, pair_distance);
+    let diagonal = fresh.laplacian().diagonal();
+    ensure!(diagonal.iter().all(|degree| (degree - diagonal[0]).abs() < 1e-12), "the diagonal alone cannot tell states apart");
+    let empty = LaplacianQLearningAgent::new(LaplacianQLearningConfig::default())?;
+    ensure!(empty.spectral_embedding().is_none() && empty.graph().is_empty(), "nothing to embed before observing states");
+    println!("   ✅ Eigenvalues {:.3?}; edges {:.3} apart, pairs {:.3}", eigenvalues, edge_distance, pair_distance);
+
+    // Test 2: states fall on graph nodes
+    println!("📊 Test 2: state nodes");
+    // A state lies on the node of the word it completes
+    let mut observe = |anomaly: &DetectedAnomaly| -> Result<Option<usize>> {
+        let state = fresh.anomaly_to_state(anomaly, &bar())?;
+        Ok(fresh.state_node(&state))
+    };
+    let (rising, strong) = (observe(&novel_pattern(0.25))?, observe(&novel_pattern(0.35))?);
+    let stronger = observe(&novel_pattern(0.38))?;
+    let falling = observe(&novel_pattern(0.25))?;
+    let spike = observe(&anomaly(AnomalyType::VolatilitySpike { expected_volatility: 0.01, actual_volatility: 0.03 }))?;
+    ensure!(strong.is_some() && strong == rising.map(|index| index + 1), "a rising pattern follows the ring");
+    ensure!(stronger == Some(18), "a repeated symbol is a new word");
+    ensure!(falling.is_some() && falling != rising, "the same state after a different one lies elsewhere");
+    ensure!(spike.is_some_and(|index| index > 18), "market stress moves the node");
+    ensure!(fresh.state_node("s_gap").is_none() && fresh.state_node(&node(&fresh, 3)) == Some(3), "ids off the graph have no node");
+    println!("   ✅ Novel 0.35 after 0.25 on node {}, volatility spike on node {}", strong.unwrap(), spike.unwrap());
+
+    // Test 3: attention follows experience through the embedding
+    println!("📊 Test 3: attention");
+    let learned = 5;
+    let others = (0..nodes).filter(|index| *index != learned);
+    let near = others.clone().min_by(|a, b| embedding.distance(learned, *a).total_cmp(&embedding.distance(learned, *b))).unwrap();
+    let far = others.max_by(|a, b| embedding.distance(learned, *a).total_cmp(&embedding.distance(learned, *b))).unwrap();
+    let mut unshaped = agent(0.0)?;
+    let ids: Vec<String> = (0..nodes).map(|index| node(&unshaped, index)).collect();
+    let node = |index: usize| ids[index].clone();
+    ensure!(unshaped.laplacian_attention(&node(learned)) == 0.0, "no attention before learning");
+    train(&mut unshaped, &node(learned))?;
+    let attention = |index: usize| unshaped.laplacian_attention(&node(index));
+    ensure!((attention(learned) - 1.0).abs() < 1e-12, "all experience on the learned node");
+    ensure!(attention(near) > attention(far) && attention(near) < 1.0, "near {:.3}, far {:.3}", attention(near), attention(far));
+    ensure!(unshaped.laplacian_attention("s_gap") == 0.0, "no attention off the graph");
+    println!("   ✅ Attention {:.3} on the nearest node, {:.3} on the farthest", attention(near), attention(far));
+
+    // Test 4: potential-based reward shaping
+    println!("📊 Test 4: reward shaping");
+    let mut shaped = agent(0.5)?;
+    train(&mut shaped, &node(learned))?;
+    let potential = shaped.shaping_potential(&node(learned));
+    ensure!((potential - 0.5).abs() < 1e-6, "potential is half the learned value: {}", potential);
+    ensure!(shaped.shaping_potential(&node(near)) > shaped.shaping_potential(&node(far)), "potential fades with distance");
+    ensure!(unshaped.shaping_potential(&node(learned)) == 0.0, "weight 0 turns shaping off");
+    // Neither agent has values for these holds, so only shaping tells them apart
+    for agent in [&mut shaped, &mut unshaped] {
+        agent.update_q_value(&node(far), TradingAction::Hold, 0.0, &node(near), false)?;
+        agent.update_q_value(&node(near), TradingAction::Hold, 0.0, &node(far), false)?;
+    }
+    let hold = |agent: &LaplacianQLearningAgent, index: usize| agent.q_value(&node(index), &TradingAction::Hold);
+    ensure!(hold(&shaped, far) > hold(&unshaped, far), "moving toward value is rewarded");
+    ensure!(hold(&shaped, near) < hold(&unshaped, near), "moving away from value is penalized");
+    println!("   ✅ Q(hold) toward value {:.3} vs {:.3} unshaped", hold(&shaped, far), hold(&unshaped, far));
+
+    // Test 5: saved Q-tables restore attention and shaping
+    println!("📊 Test 5: restore");
+    let mut restored = LaplacianQLearningAgent::new(config(0.5))?;
+    restored.restore_q_table(shaped.q_table_snapshot());
+    ensure!(restored.graph().len() == nodes && restored.spectral_embedding().is_some(), "the grown graph is restored and embedded");
+    // All but two of the saved visits lie on the learned node
+    ensure!(restored.laplacian_attention(&node(learned)) > 0.9, "attention restored");
+    ensure!(restored.laplacian_attention(&node(near)) > restored.laplacian_attention(&node(far)), "experience restored where it was");
+    ensure!(restored.shaping_potential(&node(learned)) > 0.0, "potential rebuilt from saved values");
+    let mut forgotten = agent(0.5)?;
+    forgotten.restore_q_table(QTableSnapshot { graph: None, ..shaped.q_table_snapshot() });
+    ensure!(forgotten.graph().is_empty() && forgotten.laplacian_attention(&node(learned)) == 0.0, "a table saved without a graph starts an empty one");
+    let defaults = LaplacianQLearningConfig::default();
+    let mut table: toml::Table = toml::from_str(&toml::to_string(&defaults)?)?;
+    for key in ["spectral_dimensions", "spectral_bandwidth", "shaping_weight"] {
+        table.remove(key);
+    }
+    let legacy: LaplacianQLearningConfig = toml::from_str(&toml::to_string(&table)?)?;
+    ensure!(legacy.spectral_dimensions == defaults.spectral_dimensions && legacy.shaping_weight == defaults.shaping_weight, "older configurations get the defaults");
+    ensure!(LaplacianQLearningAgent::new(LaplacianQLearningConfig { spectral_dimensions: 0, ..defaults }).is_err(), "an embedding needs a dimension");
+    println!("   ✅ Attention {:.3} and potential {:.3} after restore", restored.laplacian_attention(&node(learned)), restored.shaping_potential(&node(learned)));
+
+    println!();
+    println!("🎉 All spectral attention tests passed");
+    Ok(())
+}
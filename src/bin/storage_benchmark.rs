@@ -0,0 +1,95 @@
+//! # Storage Format Benchmark
+//!
+//! Compare the gzip-of-bincode blob format with columnar delta/zigzag/varint chunks
+
+use anyhow::{bail, Result};
+use chrono::{Duration, Utc};
+use std::time::Instant;
+
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::embedded_db::EmbeddedForexDB;
+use forex_pattern_reconstruction::synthetic::demo::generate_demo_history;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 STORAGE FORMAT BENCHMARK");
+    println!("===========================");
+    println!();
+
+    let db = EmbeddedForexDB::new()?;
+    let end = Utc::now();
+
+    let daily = generate_demo_history("EURUSD", end, 20 * 365, 42).await?;
+    let hourly = hourly_series(&daily);
+
+    println!("╔══════════════╦═════════╦════════════╦══════════════╦═══════╦════════════╦════════════╦═════════════╗");
+    println!("║    Series    ║ Points  ║ Blob (KB)  ║ Columns (KB) ║ Ratio ║ Full read  ║ Month read ║ Close read  ║");
+    println!("╠══════════════╬═════════╬════════════╬══════════════╬═══════╬════════════╬════════════╬═════════════╣");
+
+    for (name, pair, data) in [("EURUSD D1", "EURUSD_D1", &daily), ("EURUSD H1", "EURUSD_H1", &hourly)] {
+        db.store_forex_data(pair, data)?;
+        db.store_candles_columnar(pair, data)?;
+        let footprint = db.storage_footprint(pair)?;
+
+        let started = Instant::now();
+        let full = db.get_forex_data(pair)?;
+        let full_read = started.elapsed();
+
+        let month_start = end - Duration::days(30);
+        let started = Instant::now();
+        let month = db.get_candles_range(pair, month_start, end)?;
+        let month_read = started.elapsed();
+
+        let started = Instant::now();
+        let closes = db.get_close_range(pair, month_start, end)?;
+        let close_read = started.elapsed();
+
+        // Both formats quantize prices to 1e-5, so they must agree exactly
+        let columnar_full = db.get_candles_range(pair, data[0].timestamp, end)?;
+        if columnar_full.len() != full.len()
+            || columnar_full.iter().zip(&full).any(|(a, b)| a.timestamp != b.timestamp || a.close != b.close)
+        {
+            bail!("{}: columnar round trip disagrees with blob format", name);
+        }
+        if month.len() != closes.len() {
+            bail!("{}: range and close reads disagree", name);
+        }
+
+        println!(
+            "║ {:12} ║ {:7} ║ {:10.1} ║ {:12.1} ║ {:5.2} ║ {:>10} ║ {:>10} ║ {:>11} ║",
+            name,
+            data.len(),
+            footprint.blob_bytes as f64 / 1024.0,
+            footprint.columnar_bytes as f64 / 1024.0,
+            footprint.columnar_bytes as f64 / footprint.blob_bytes.max(1) as f64,
+            format!("{:.2?}", full_read),
+            format!("{:.2?}", month_read),
+            format!("{:.2?}", close_read),
+        );
+    }
+
+    println!("╚══════════════╩═════════╩════════════╩══════════════╩═══════╩════════════╩════════════╩═════════════╝");
+    println!();
+    println!("📊 Full read decodes the whole blob; month and close reads touch only overlapping chunks");
+    Ok(())
+}
+
+/// Interpolate 24 hourly bars inside every daily bar
+fn hourly_series(daily: &[ForexDataPoint]) -> Vec<ForexDataPoint> {
+    daily.iter()
+        .flat_map(|day| {
+            (0..24).map(move |hour| {
+                let t = hour as f64 / 24.0;
+                let price = day.open + (day.close - day.open) * t;
+                ForexDataPoint {
+                    timestamp: day.timestamp + Duration::hours(hour),
+                    open: price,
+                    high: price.max(day.open) + (day.high - day.open.max(day.close)) * 0.1,
+                    low: price.min(day.open) - (day.open.min(day.close) - day.low) * 0.1,
+                    close: day.open + (day.close - day.open) * (t + 1.0 / 24.0),
+                    volume: day.volume.map(|v| (v / 24.0).round()),
+                }
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,195 @@
+//! # Strategy Optimizer Test
+//!
+//! Check walk-forward optimization trades each in-sample optimum out of sample
+//! only, that Bayesian search matches the grid with fewer backtests, and stability
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::BTreeMap;
+use std::f64::consts::PI;
+
+use forex_pattern_reconstruction::backtest::optimizer::{
+    load_optimizer_config, OptimizationMethod, OptimizationObjective, OptimizerConfig, WalkForwardOptimizer,
+};
+use forex_pattern_reconstruction::backtest::strategy::{Order, Strategy, StrategyContext, StrategyRegistry, TimeSymmetricStrategy};
+use forex_pattern_reconstruction::backtest::{BacktestConfig, BacktestEngine, StrategyConfig};
+use forex_pattern_reconstruction::data::ForexDataPoint;
+
+/// Holds `direction × position_units` from the first tradable bar; `unused` changes nothing
+struct HoldStrategy {
+    target: f64,
+}
+
+impl Strategy for HoldStrategy {
+    fn name(&self) -> &str {
+        "HoldStrategy"
+    }
+
+    fn on_bar(&mut self, context: &StrategyContext, _bar: &ForexDataPoint) -> Vec<Order> {
+        context.orders_to(self.target, "hold")
+    }
+}
+
+fn registry() -> StrategyRegistry {
+    let mut registry = StrategyRegistry::new();
+    registry.register("HoldStrategy", |config| {
+        let parameter = |key: &str, default: f64| config.parameters.get(key).copied().unwrap_or(default);
+        let units = parameter("position_units", 10_000.0);
+        if units <= 0.0 {
+            return Err(anyhow::anyhow!("position_units must be positive"));
+        }
+        Ok(Box::new(HoldStrategy { target: parameter("direction", 1.0) * units }))
+    });
+    registry
+}
+
+/// Hourly bars drifting by `drift(i)` per bar on a 24-bar cycle with noise
+fn bars(seed: u64, count: usize, drift: impl Fn(usize) -> f64) -> Vec<ForexDataPoint> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let mut level: f64 = 1.1;
+    (0..count)
+        .map(|i| {
+            level *= 1.0 + drift(i);
+            let close = level * (1.0 + 0.002 * (2.0 * PI * i as f64 / 24.0).sin()) + rng.gen_range(-0.0002..0.0002);
+            ForexDataPoint { timestamp: start + Duration::hours(i as i64), open: close, high: close + 0.0002, low: close - 0.0002, close, volume: None }
+        })
+        .collect()
+}
+
+fn engine(name: &str, warmup_bars: usize, cycle_refresh_bars: usize) -> Result<BacktestEngine> {
+    let strategy = StrategyConfig { name: name.to_string(), ..StrategyConfig::default() };
+    BacktestEngine::new(strategy, 10_000.0, BacktestConfig { warmup_bars, cycle_refresh_bars, ..BacktestConfig::default() })
+}
+
+fn grid(parameters: &[(&str, &[f64])]) -> BTreeMap<String, Vec<f64>> {
+    parameters.iter().map(|(name, values)| (name.to_string(), values.to_vec())).collect()
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 Strategy Optimizer Test");
+    println!("==========================");
+    println!();
+
+    // Test 1: candidates and configuration files
+    println!("📊 Test 1: Search space");
+    let config = OptimizerConfig { parameters: grid(&[("b", &[1.0, 2.0]), ("a", &[0.1, 0.2, 0.3])]), ..OptimizerConfig::default() };
+    let candidates = config.candidates();
+    ensure!(candidates.len() == 6, "{} candidates", candidates.len());
+    ensure!(candidates[0]["a"] == 0.1 && candidates[0]["b"] == 1.0 && candidates[1]["b"] == 2.0 && candidates[5]["a"] == 0.3, "lexicographic order");
+    let path = std::env::temp_dir().join(format!("strategy-optimizer-test-{}.toml", std::process::id()));
+    std::fs::write(&path, "method = \"bayesian\"\nobjective = \"total_return\"\nin_sample_bars = 300\n[parameters]\nmin_confidence = [0.3, 0.6]\n")?;
+    let loaded = load_optimizer_config(&path);
+    let _ = std::fs::remove_file(&path);
+    let loaded = loaded?;
+    ensure!(loaded.method == OptimizationMethod::Bayesian && loaded.objective == OptimizationObjective::TotalReturn, "method and objective");
+    ensure!(loaded.in_sample_bars == 300 && loaded.out_of_sample_bars == 100 && loaded.parameters["min_confidence"] == [0.3, 0.6], "defaults fill the rest");
+    println!("   ✅ 2 × 3 grid gives 6 candidates; TOML settings load over the defaults");
+
+    // Test 2: grid search on rolling windows
+    println!("📊 Test 2: Grid walk-forward");
+    let data = bars(11, 700, |_| 0.0);
+    let cyclic = engine(TimeSymmetricStrategy::NAME, 100, 50)?;
+    let config = OptimizerConfig {
+        parameters: grid(&[("min_confidence", &[0.2, 0.6]), ("cooldown_bars", &[2.0, 8.0])]),
+        in_sample_bars: 300,
+        out_of_sample_bars: 100,
+        step_bars: 200,
+        ..OptimizerConfig::default()
+    };
+    let report = WalkForwardOptimizer::new(&cyclic, config.clone())?.optimize("EURUSD", &data, &[], &[]).await?;
+    report.print_windows();
+    report.print_stability();
+    ensure!(report.windows.len() == 2 && report.candidates == 4 && report.evaluations() == 8, "{} windows, {} backtests", report.windows.len(), report.evaluations());
+    let first = &report.windows[0];
+    ensure!(first.in_sample_start == data[0].timestamp && first.out_of_sample_start == data[300].timestamp
+            && first.out_of_sample_end == data[399].timestamp && report.windows[1].in_sample_start == data[200].timestamp,
+            "window boundaries");
+    let strategies = StrategyRegistry::new();
+    let mut best = f64::NEG_INFINITY;
+    for candidate in config.candidates() {
+        let mut strategy_config = cyclic.strategy_config().clone();
+        strategy_config.parameters.extend(candidate.clone());
+        let mut strategy = strategies.create(&strategy_config)?;
+        best = best.max(cyclic.run(strategy.as_mut(), "EURUSD", &data[..300], &[]).await?.results.sharpe_ratio);
+    }
+    ensure!(first.in_sample_score == best, "window 1 kept Sharpe {:.3}, best was {:.3}", first.in_sample_score, best);
+    println!("   ✅ {}", report.summary());
+
+    // Test 3: out-of-sample runs trade only after the in-sample window
+    println!("📊 Test 3: Out-of-sample isolation");
+    let mut strategy_config = cyclic.strategy_config().clone();
+    strategy_config.parameters.extend(first.parameters.clone());
+    let mut strategy = strategies.create(&strategy_config)?;
+    let run = cyclic.run(strategy.as_mut(), "EURUSD", &data[201..400], &[]).await?;
+    ensure!(run.results.total_return == first.out_of_sample.total_return, "out-of-sample run differs from a warm-started replay");
+    ensure!(run.trades.iter().all(|trade| trade.timestamp >= data[300].timestamp), "a trade before the out-of-sample window");
+    let later = bars(11, 900, |_| 0.0);
+    let extended = WalkForwardOptimizer::new(&cyclic, config.clone())?.optimize("EURUSD", &later, &[], &[]).await?;
+    ensure!(extended.windows.len() == 3 && extended.windows[0].parameters == first.parameters
+            && extended.windows[0].out_of_sample_score == first.out_of_sample_score, "later bars changed an earlier window");
+    println!("   ✅ Window 1 trades {} fills from {}, unchanged by 200 more bars", run.trades.len(), first.out_of_sample_start.format("%Y-%m-%d %H:%M"));
+
+    // Test 4: Bayesian optimization finds the grid optimum with fewer backtests
+    println!("📊 Test 4: Bayesian search");
+    let rising = bars(5, 600, |_| 0.0003);
+    let hold = engine("HoldStrategy", 20, 10_000)?;
+    let search = OptimizerConfig {
+        objective: OptimizationObjective::TotalReturn,
+        parameters: grid(&[("position_units", &[1_000.0, 2_000.0, 3_000.0, 4_000.0, 5_000.0, 6_000.0, 7_000.0, 8_000.0, 9_000.0, 10_000.0]),
+                           ("unused", &[0.0, 1.0, 2.0, 3.0, 4.0])]),
+        in_sample_bars: 200,
+        out_of_sample_bars: 100,
+        evaluations: 14,
+        initial_points: 5,
+        ..OptimizerConfig::default()
+    };
+    let exhaustive = WalkForwardOptimizer::new(&hold, search.clone())?.with_registry(registry()).optimize("EURUSD", &rising, &[], &[]).await?;
+    let bayesian = WalkForwardOptimizer::new(&hold, OptimizerConfig { method: OptimizationMethod::Bayesian, ..search.clone() })?
+        .with_registry(registry())
+        .optimize("EURUSD", &rising, &[], &[]).await?;
+    ensure!(exhaustive.windows.iter().all(|w| w.parameters["position_units"] == 10_000.0), "grid optimum");
+    ensure!(bayesian.windows.iter().all(|w| w.evaluations == 14), "Bayesian budget");
+    ensure!(bayesian.windows.iter().all(|w| w.parameters["position_units"] == 10_000.0),
+            "Bayesian chose {:?}", bayesian.windows.iter().map(|w| w.parameters["position_units"]).collect::<Vec<_>>());
+    ensure!(bayesian.windows.iter().zip(&exhaustive.windows).all(|(b, g)| (b.in_sample_score - g.in_sample_score).abs() < 1e-12), "same in-sample optimum");
+    println!("   ✅ {} backtests instead of {} reach the same optimum in all {} windows", bayesian.evaluations(), exhaustive.evaluations(), bayesian.windows.len());
+
+    // Test 5: parameter stability
+    println!("📊 Test 5: Parameter stability");
+    let units = exhaustive.stability("position_units").expect("position_units stability");
+    ensure!(units.coefficient_of_variation == 0.0 && units.changes == 0 && units.mode_share == 1.0, "a constant optimum is stable");
+    let reversing = bars(9, 900, |i| if i < 450 { 0.0003 } else { -0.0003 });
+    let direction = OptimizerConfig { parameters: grid(&[("direction", &[-1.0, 1.0])]), ..search.clone() };
+    let flips = WalkForwardOptimizer::new(&hold, direction)?.with_registry(registry()).optimize("EURUSD", &reversing, &[], &[]).await?;
+    flips.print_stability();
+    let chosen: Vec<f64> = flips.windows.iter().map(|w| w.parameters["direction"]).collect();
+    let stability = flips.stability("direction").expect("direction stability");
+    ensure!(chosen.first() == Some(&1.0) && chosen.last() == Some(&-1.0) && stability.changes == 1, "directions {:?}", chosen);
+    ensure!(stability.coefficient_of_variation > 1.0 && stability.mode_share < 1.0, "a flipping optimum is unstable");
+    ensure!(exhaustive.walk_forward_efficiency().is_some_and(|e| e > 0.0), "trend keeps its edge out of sample");
+    println!("   ✅ Constant optimum CV 0; reversal flips direction {:?} (CV {:.2}), out-of-sample return {:.2}%",
+             chosen, stability.coefficient_of_variation, flips.out_of_sample_return() * 100.0);
+
+    // Test 6: unusable settings
+    println!("📊 Test 6: Validation");
+    let optimizer = |config: OptimizerConfig| WalkForwardOptimizer::new(&hold, config).map(|o| o.with_registry(registry()));
+    ensure!(optimizer(OptimizerConfig { parameters: BTreeMap::new(), ..search.clone() }).is_err(), "empty search space accepted");
+    ensure!(optimizer(OptimizerConfig { parameters: grid(&[("direction", &[])]), ..search.clone() }).is_err(), "parameter without values accepted");
+    ensure!(optimizer(OptimizerConfig { out_of_sample_bars: 0, ..search.clone() }).is_err(), "empty out-of-sample window accepted");
+    ensure!(optimizer(OptimizerConfig { in_sample_bars: 20, ..search.clone() }).is_err(), "in-sample window within the warm-up accepted");
+    ensure!(optimizer(search.clone())?.optimize("EURUSD", &rising[..250], &[], &[]).await.is_err(), "too few bars accepted");
+    let invalid = OptimizerConfig { parameters: grid(&[("position_units", &[-1.0, 0.0])]), ..search.clone() };
+    ensure!(optimizer(invalid)?.optimize("EURUSD", &rising, &[], &[]).await.is_err(), "no valid candidate accepted");
+    let partly = OptimizerConfig { parameters: grid(&[("position_units", &[-1.0, 5_000.0])]), ..search.clone() };
+    ensure!(optimizer(partly)?.optimize("EURUSD", &rising, &[], &[]).await?.windows.iter().all(|w| w.parameters["position_units"] == 5_000.0),
+            "rejected candidates skipped");
+    println!("   ✅ Empty grids, windows inside the warm-up, short histories and invalid candidates rejected");
+
+    println!();
+    println!("🎉 All strategy optimizer tests passed");
+    Ok(())
+}
@@ -0,0 +1,187 @@
+//! # Strategy Plug-ins Test
+//!
+//! Check that fills are reported back through `on_fill` with bounded follow-up
+//! rounds, that the RL agent and cycle confluence strategies run from the registry
+//! in backtests, and that the multi-currency manager trades a pair through a
+//! custom strategy, replaying its history and queueing `on_fill` orders
+
+use anyhow::{ensure, Result};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use std::sync::{Arc, Mutex};
+
+use forex_pattern_reconstruction::anomaly::{AnomalyType, DetectedAnomaly};
+use forex_pattern_reconstruction::backtest::strategy::{
+    ConfluenceStrategy, Fill, Order, RlAgentStrategy, Strategy, StrategyContext, StrategyRegistry,
+};
+use forex_pattern_reconstruction::backtest::{BacktestConfig, BacktestEngine, StrategyConfig};
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::laplacian_rl::TradingAction;
+use forex_pattern_reconstruction::multi_currency::{CurrencyPairConfig, CurrencyPairState, MultiCurrencyManager};
+use forex_pattern_reconstruction::patterns::HiddenCycle;
+use forex_pattern_reconstruction::trading_windows::TradingWindowsConfig;
+use forex_pattern_reconstruction::synthetic::fixtures::anomaly;
+
+/// Bars every `step` following a sine of `period` with 1% amplitude, plus a little noise
+fn cyclic_bars(start: DateTime<Utc>, count: i64, step: Duration, period: Duration, seed: u64) -> Vec<ForexDataPoint> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..count)
+        .map(|i| {
+            let timestamp = start + step * i as i32;
+            let phase = 2.0 * PI * (timestamp.timestamp() as f64) / period.num_seconds() as f64;
+            let close = 1.1 * (1.0 + 0.01 * phase.sin()) + rng.gen_range(-0.00005..0.00005);
+            ForexDataPoint { timestamp, open: close, high: close + 0.0002, low: close - 0.0002, close, volume: Some(10.0) }
+        })
+        .collect()
+}
+
+/// Buys on bar `entry_bar`, scales half out when the entry fills, and echoes every
+/// fill when `echo` is set
+struct ScaleOutStrategy {
+    entry_bar: usize,
+    echo: bool,
+    bars: Arc<Mutex<usize>>,
+    fills: Arc<Mutex<Vec<Fill>>>,
+}
+
+impl ScaleOutStrategy {
+    fn new(entry_bar: usize, echo: bool) -> Self {
+        Self { entry_bar, echo, bars: Arc::default(), fills: Arc::default() }
+    }
+}
+
+impl Strategy for ScaleOutStrategy {
+    fn name(&self) -> &str {
+        "ScaleOut"
+    }
+
+    fn on_bar(&mut self, context: &StrategyContext, _bar: &ForexDataPoint) -> Vec<Order> {
+        *self.bars.lock().unwrap() += 1;
+        if context.bar_index != self.entry_bar {
+            return Vec::new();
+        }
+        vec![Order::buy(2_000.0, "entry")]
+    }
+
+    fn on_fill(&mut self, _context: &StrategyContext, fill: &Fill) -> Vec<Order> {
+        self.fills.lock().unwrap().push(fill.clone());
+        if self.echo {
+            return vec![Order { side: fill.side, units: fill.units, reason: "echo".to_string(), symmetry_id: None, cycle_id: None }];
+        }
+        if fill.reason == "entry" {
+            vec![Order::sell(fill.units / 2.0, "scale out")]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 STRATEGY PLUG-INS TEST");
+    println!("=========================");
+    println!();
+
+    let daily = cyclic_bars(Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(), 200, Duration::days(1), Duration::days(20), 1);
+    let config = BacktestConfig { warmup_bars: 50, cycle_refresh_bars: 50, ..BacktestConfig::default() };
+    let engine = BacktestEngine::new(StrategyConfig::default(), 100_000.0, config.clone())?;
+
+    // Test 1: fills come back to the strategy and its answers fill on the same bar
+    println!("📊 Test 1: on_fill");
+    let mut strategy = ScaleOutStrategy::new(62, false);
+    let fills = Arc::clone(&strategy.fills);
+    let run = engine.run(&mut strategy, "EURUSD", &daily, &[]).await?;
+    ensure!(run.trades.len() == 2, "expected entry and scale-out, got {} trades", run.trades.len());
+    ensure!(run.trades[0].timestamp == run.trades[1].timestamp, "scale-out fills on the entry bar");
+    let fills = fills.lock().unwrap().clone();
+    ensure!(fills.len() == 2 && fills[0].position_units == 2_000.0 && fills[1].position_units == 1_000.0,
+            "fills report the position after each fill: {:?}", fills.iter().map(|f| f.position_units).collect::<Vec<_>>());
+    ensure!(fills[1].reason == "scale out" && fills[1].commission > 0.0, "fills carry the order reason and commission");
+    let mut echo = ScaleOutStrategy::new(62, true);
+    let run = engine.run(&mut echo, "EURUSD", &daily, &[]).await?;
+    ensure!(!run.trades.is_empty() && run.trades.len() < 10, "follow-up rounds are bounded, got {} trades", run.trades.len());
+    println!("   ✅ Scale-out filled on the entry bar; {} fills for an echoing strategy", run.trades.len());
+
+    // Test 2: the RL agent trades anomalies and learns from realized P&L
+    println!("📊 Test 2: RL agent strategy");
+    let registry = StrategyRegistry::new();
+    let rl_config = StrategyConfig {
+        name: RlAgentStrategy::NAME.to_string(),
+        parameters: HashMap::from([("exploration_rate".to_string(), 1.0)]),
+    };
+    ensure!(registry.create(&rl_config)?.name() == RlAgentStrategy::NAME, "RL strategy available by name");
+    let anomalies: Vec<DetectedAnomaly> = daily[50..].iter().step_by(3).enumerate()
+        .map(|(i, bar)| {
+            let anomaly_type = if i % 2 == 0 {
+                AnomalyType::PatternInversion { original_pattern: "up".to_string(), inverted_pattern: "down".to_string() }
+            } else {
+                AnomalyType::VolatilitySpike { expected_volatility: 0.01, actual_volatility: 0.03 }
+            };
+            anomaly(bar.timestamp, anomaly_type)
+        })
+        .collect();
+    let mut rl = RlAgentStrategy::new(&rl_config)?;
+    let run = engine.run(&mut rl, "EURUSD", &daily, &anomalies).await?;
+    ensure!(!run.trades.is_empty(), "an exploring agent should trade");
+    ensure!(!rl.rewards().is_empty(), "closed positions should be fed back as rewards");
+    let mut frozen = RlAgentStrategy::new(&StrategyConfig { parameters: HashMap::from([("exploration_rate".to_string(), 1.0), ("learn".to_string(), 0.0)]), ..rl_config.clone() })?;
+    engine.run(&mut frozen, "EURUSD", &daily, &anomalies).await?;
+    ensure!(frozen.rewards().is_empty(), "learn=0 leaves the agent untouched");
+    println!("   ✅ {} trades, {} rewards learned", run.trades.len(), rl.rewards().len());
+
+    // Test 3: only cycles confirmed on a coarser timeframe are traded
+    println!("📊 Test 3: confluence strategy");
+    let hourly = cyclic_bars(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(), 60 * 24, Duration::hours(1), Duration::hours(24), 2);
+    let hourly_engine = BacktestEngine::new(StrategyConfig::default(), 100_000.0, BacktestConfig { warmup_bars: 500, cycle_refresh_bars: 240, ..config })?;
+    let confluence_config = StrategyConfig { name: ConfluenceStrategy::NAME.to_string(), parameters: HashMap::new() };
+    ensure!(registry.create(&confluence_config)?.name() == ConfluenceStrategy::NAME, "confluence strategy available by name");
+    let mut confluence = ConfluenceStrategy::new(&confluence_config)?;
+    let run = hourly_engine.run(&mut confluence, "EURUSD", &hourly, &[]).await?;
+    let confirmed: Vec<String> = confluence.confirmed_cycles().iter().map(|c| format!("{}h", c.period)).collect();
+    println!("   confirmed cycles {:?}, {} trades", confirmed, run.trades.len());
+    ensure!(confluence.confirmed_cycles().iter().any(|c| (c.period as i64 - 24).abs() <= 2), "the daily cycle should be confirmed on H4");
+    ensure!(!run.trades.is_empty() && run.trades.iter().all(|t| t.cycle_id.is_some()), "trades are tagged with the cycle");
+    let bad = StrategyConfig { parameters: HashMap::from([("higher_timeframe_factor".to_string(), 1.0)]), ..confluence_config };
+    ensure!(ConfluenceStrategy::new(&bad).is_err(), "a factor below 2 is not a coarser timeframe");
+    println!("   ✅ Daily cycle confirmed and traded");
+
+    // Test 4: live trading through a custom strategy
+    println!("📊 Test 4: live strategy");
+    let state = CurrencyPairState::new(CurrencyPairConfig { strategies: vec![rl_config], ..CurrencyPairConfig::default() }).await?;
+    ensure!(state.strategy_names() == [RlAgentStrategy::NAME], "pair config selects a registry strategy");
+    let mut manager = MultiCurrencyManager::new().with_trading_windows(TradingWindowsConfig::unrestricted());
+    manager.initialize_major_pairs().await?;
+    manager.active_pairs.retain(|pair| pair == "EURUSD");
+    {
+        let mut pairs = manager.pairs.write().await;
+        pairs.retain(|pair, _| pair == "EURUSD");
+        let state = pairs.get_mut("EURUSD").expect("pair initialized");
+        state.historical_data = cyclic_bars(Utc::now() - Duration::days(30), 30, Duration::days(1), Duration::days(20), 3);
+        state.cycles = vec![HiddenCycle { id: "cycle_20".into(), name: "cycle_20".to_string(), period: 20, confidence: 0.9, amplitude: 0.01, phase: 0.0, p_value: None, track: None }];
+        state.is_active = true;
+        state.warm = true;
+    }
+    let live = ScaleOutStrategy::new(29, false);
+    let (bars, fills) = (Arc::clone(&live.bars), Arc::clone(&live.fills));
+    manager.set_strategy("EURUSD", Box::new(live)).await?;
+    ensure!(manager.set_strategy("XXXYYY", Box::new(ScaleOutStrategy::new(0, false))).await.is_err(), "unknown pairs are rejected");
+    let actions = manager.process_all_market_updates().await?;
+    ensure!(*bars.lock().unwrap() == 30, "the history is replayed once, got {} bars", bars.lock().unwrap());
+    ensure!(actions.get("EURUSD") == Some(&vec![TradingAction::Buy { size: 2 }]), "entry becomes a sized action: {:?}", actions);
+    manager.execute_actions(&actions).await;
+    ensure!(fills.lock().unwrap().len() == 1, "the fill is reported to the strategy");
+    let actions = manager.process_all_market_updates().await?;
+    ensure!(actions.get("EURUSD") == Some(&vec![TradingAction::Sell { size: 1 }]), "on_fill orders go out with the next update: {:?}", actions);
+    manager.execute_actions(&actions).await;
+    let snapshot = manager.portfolio_snapshot().await;
+    ensure!(snapshot.positions.len() == 1 && snapshot.positions[0].units == 1_000.0, "half the entry remains open");
+    ensure!(*bars.lock().unwrap() == 30, "no new bar, no new on_bar call");
+    println!("   ✅ Entry and scale-out traded live");
+
+    println!();
+    println!("🎉 All strategy plug-in tests passed");
+    Ok(())
+}
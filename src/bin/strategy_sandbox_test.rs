@@ -0,0 +1,155 @@
+//! # Strategy Sandbox Test
+//!
+//! Run panicking, slow and hung strategies through the sandbox and check that each
+//! call returns within its limit, that a slow strategy rejoins once it finishes,
+//! that repeated failures disable the strategy with an audited alert, and that a
+//! healthy strategy on the same pair keeps trading
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, Utc};
+use std::time::{Duration as StdDuration, Instant};
+
+use forex_pattern_reconstruction::backtest::sandbox::{SandboxConfig, StrategyEvent, StrategySandbox};
+use forex_pattern_reconstruction::backtest::strategy::{Order, Strategy, StrategyContext};
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::laplacian_rl::TradingAction;
+use forex_pattern_reconstruction::multi_currency::{CurrencyPairConfig, MultiCurrencyManager};
+
+/// How a test strategy misbehaves on `on_bar`
+#[derive(Clone, Copy)]
+enum Behavior {
+    /// Buy 1000 units on every bar
+    Healthy,
+    Panic,
+    /// Sleep this long on the first bar only
+    SlowOnce(u64),
+    /// Sleep this long on every bar
+    Hang(u64),
+}
+
+struct TestStrategy {
+    name: &'static str,
+    behavior: Behavior,
+    bars: usize,
+}
+
+impl TestStrategy {
+    fn boxed(name: &'static str, behavior: Behavior) -> Box<dyn Strategy> {
+        Box::new(Self { name, behavior, bars: 0 })
+    }
+}
+
+impl Strategy for TestStrategy {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn on_bar(&mut self, _context: &StrategyContext, _bar: &ForexDataPoint) -> Vec<Order> {
+        self.bars += 1;
+        match self.behavior {
+            Behavior::Panic => panic!("boom"),
+            Behavior::SlowOnce(ms) if self.bars == 1 => std::thread::sleep(StdDuration::from_millis(ms)),
+            Behavior::Hang(ms) => std::thread::sleep(StdDuration::from_millis(ms)),
+            _ => {}
+        }
+        vec![Order::buy(1_000.0, "bar")]
+    }
+}
+
+fn bar(days_ago: i64) -> ForexDataPoint {
+    ForexDataPoint { timestamp: Utc::now() - Duration::days(days_ago), open: 1.1, high: 1.101, low: 1.099, close: 1.1, volume: None }
+}
+
+fn context() -> StrategyContext {
+    StrategyContext {
+        pair: "EURUSD".to_string(),
+        timestamp: Utc::now(),
+        bar_index: 0,
+        bar_seconds: 86_400.0,
+        position_units: 0.0,
+        equity: 100_000.0,
+        trading_allowed: true,
+        thin_market: false,
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 STRATEGY SANDBOX TEST");
+    println!("========================");
+    println!();
+
+    // Panics are expected below; keep the output readable
+    std::panic::set_hook(Box::new(|_| {}));
+    let config = SandboxConfig { timeout_ms: 50, replay_timeout_ms: 1_000, max_consecutive_failures: 3 };
+    let context = context();
+
+    // Test 1: panics are caught and counted, and disable the strategy after the limit
+    println!("📊 Test 1: panic isolation");
+    let mut healthy = StrategySandbox::new(TestStrategy::boxed("Healthy", Behavior::Healthy), config.clone());
+    ensure!(healthy.call(&context, StrategyEvent::Bar(bar(0))).await.len() == 1, "a healthy strategy trades");
+    let mut panicking = StrategySandbox::new(TestStrategy::boxed("Panicking", Behavior::Panic), config.clone());
+    for _ in 0..2 {
+        ensure!(panicking.call(&context, StrategyEvent::Bar(bar(0))).await.is_empty(), "a panic yields no orders");
+    }
+    ensure!(panicking.is_enabled() && panicking.status().panics == 2, "still enabled below the limit");
+    ensure!(panicking.status().last_error.as_deref().is_some_and(|e| e.contains("boom")), "panic message kept");
+    panicking.call(&context, StrategyEvent::Bar(bar(0))).await;
+    ensure!(!panicking.is_enabled(), "third consecutive panic disables the strategy");
+    ensure!(panicking.take_alert().is_some_and(|a| a.contains("Panicking")) && panicking.take_alert().is_none(), "one alert per disable");
+    panicking.call(&context, StrategyEvent::Bar(bar(0))).await;
+    ensure!(panicking.status().calls == 3, "disabled strategies are not called");
+    println!("   ✅ Panics caught; disabled after {} failures", panicking.status().panics);
+
+    // Test 2: a slow call times out and the strategy rejoins once it returns
+    println!("📊 Test 2: timeouts");
+    let mut slow = StrategySandbox::new(TestStrategy::boxed("Slow", Behavior::SlowOnce(300)), config.clone());
+    let started = Instant::now();
+    ensure!(slow.call(&context, StrategyEvent::Bar(bar(0))).await.is_empty(), "an overrunning call yields no orders");
+    ensure!(started.elapsed() < StdDuration::from_millis(250), "the caller is not held up, waited {:?}", started.elapsed());
+    ensure!(slow.call(&context, StrategyEvent::Bar(bar(0))).await.is_empty(), "busy strategies skip calls");
+    tokio::time::sleep(StdDuration::from_millis(400)).await;
+    ensure!(slow.call(&context, StrategyEvent::Bar(bar(0))).await.len() == 1, "the strategy rejoins after the slow call");
+    ensure!(slow.is_enabled() && slow.status().timeouts == 1 && slow.status().consecutive_failures == 0, "success resets the failure count");
+    let mut hung = StrategySandbox::new(TestStrategy::boxed("Hung", Behavior::Hang(500)), SandboxConfig { max_consecutive_failures: 2, ..config.clone() });
+    let started = Instant::now();
+    for _ in 0..3 {
+        hung.call(&context, StrategyEvent::Bar(bar(0))).await;
+    }
+    ensure!(started.elapsed() < StdDuration::from_millis(300), "a hung strategy never blocks the caller");
+    ensure!(!hung.is_enabled() && hung.take_alert().is_some(), "a hung strategy is disabled");
+    println!("   ✅ Timed out calls return in {:?}", started.elapsed());
+
+    // Test 3: one failing strategy does not stop the others on the pair
+    println!("📊 Test 3: pair with several strategies");
+    let mut manager = MultiCurrencyManager::new();
+    manager.initialize_major_pairs().await?;
+    manager.active_pairs.retain(|pair| pair == "EURUSD");
+    {
+        let mut pairs = manager.pairs.write().await;
+        pairs.retain(|pair, _| pair == "EURUSD");
+        let state = pairs.get_mut("EURUSD").expect("pair initialized");
+        state.config = CurrencyPairConfig { sandbox: config.clone(), ..state.config.clone() };
+        state.historical_data = (1..=10).rev().map(bar).collect();
+        state.is_active = true;
+        state.warm = true;
+    }
+    manager.set_strategy("EURUSD", TestStrategy::boxed("Healthy", Behavior::Healthy)).await?;
+    manager.add_strategy("EURUSD", TestStrategy::boxed("Panicking", Behavior::Panic)).await?;
+    for day in 0..2 {
+        let actions = manager.process_all_market_updates().await?;
+        ensure!(actions.get("EURUSD") == Some(&vec![TradingAction::Buy { size: 1 }]), "only the healthy strategy trades: {:?}", actions);
+        manager.pairs.write().await.get_mut("EURUSD").unwrap().historical_data.push(bar(-day));
+    }
+    let statuses = manager.strategy_statuses().await;
+    let statuses = &statuses["EURUSD"];
+    ensure!(statuses.iter().map(|s| s.name.as_str()).eq(["Healthy", "Panicking"]), "one status per strategy");
+    ensure!(statuses[0].disabled.is_none() && statuses[1].disabled.is_some(), "only the panicking strategy is disabled");
+    let audited = manager.audit_log.recent(10);
+    ensure!(audited.iter().any(|e| e.source == "strategy-sandbox" && !e.success && e.message.contains("Panicking")), "disable is audited");
+    println!("   ✅ Panicking strategy disabled and audited, healthy one kept trading");
+
+    println!();
+    println!("🎉 All strategy sandbox tests passed");
+    Ok(())
+}
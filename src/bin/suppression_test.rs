@@ -0,0 +1,112 @@
+//! # Anomaly Suppression Test
+//!
+//! Acknowledge and suppress anomaly patterns through the suppression list and the
+//! controller API, and check matching, expiry and persistence across restarts
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, Utc};
+
+use forex_pattern_reconstruction::anomaly::suppression::{SuppressionConfig, SuppressionKind, SuppressionList};
+use forex_pattern_reconstruction::anomaly::{AnomalySeverity, AnomalyType, DetectedAnomaly, MarketContext};
+use forex_pattern_reconstruction::multi_currency::MultiCurrencyManager;
+use forex_pattern_reconstruction::protocol::server::handle_command;
+use forex_pattern_reconstruction::protocol::TradingCommand;
+
+fn anomaly(anomaly_type: AnomalyType, symmetries: &[&str]) -> DetectedAnomaly {
+    DetectedAnomaly {
+        id: "test".into(),
+        timestamp: Utc::now(),
+        anomaly_type,
+        severity: AnomalySeverity::Medium,
+        confidence: 0.9,
+        deviation_magnitude: 0.01,
+        affected_symmetries: symmetries.iter().map(|s| (*s).into()).collect(),
+        affected_cycles: Vec::new(),
+        market_context: MarketContext {
+            session: "London".to_string(),
+            volatility_regime: "Normal".to_string(),
+            trend_direction: "Sideways".to_string(),
+            recent_events: Vec::new(),
+            event_proximity: 0.0,
+        },
+        trading_signal: None,
+    }
+}
+
+fn breakdown(symmetry_id: &str) -> DetectedAnomaly {
+    anomaly(AnomalyType::SymmetryBreakdown {
+        symmetry_id: symmetry_id.into(),
+        expected_strength: 0.9,
+        actual_strength: 0.4,
+    }, &[symmetry_id])
+}
+
+fn spike() -> DetectedAnomaly {
+    anomaly(AnomalyType::VolatilitySpike { expected_volatility: 0.001, actual_volatility: 0.004 }, &[])
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 ANOMALY SUPPRESSION TEST");
+    println!("===========================");
+    println!();
+    let now = Utc::now();
+
+    // Test 1: rules match by pair, pattern and type
+    println!("📊 Test 1: Matching");
+    let list = SuppressionList::default();
+    list.suppress(Some("EURUSD"), "weekly_sym", "SymmetryBreakdown", "test", None);
+    ensure!(list.check("EURUSD", &breakdown("weekly_sym"), now).is_some(), "suppressed pattern not matched");
+    ensure!(list.check("GBPUSD", &breakdown("weekly_sym"), now).is_none(), "rule leaked to another pair");
+    ensure!(list.check("EURUSD", &breakdown("monthly_sym"), now).is_none(), "rule matched another symmetry");
+    ensure!(list.check("EURUSD", &spike(), now).is_none(), "rule matched another anomaly type");
+    list.acknowledge(None, "*", "VolatilitySpike", "test");
+    ensure!(list.check("USDJPY", &spike(), now).is_some(), "wildcard acknowledgment not matched");
+    println!("✅ Pair, pattern and type scoping respected");
+
+    // Test 2: rules expire
+    println!("📊 Test 2: Expiry");
+    let config = SuppressionConfig::default();
+    ensure!(list.check("USDJPY", &spike(), now + Duration::minutes(config.acknowledge_minutes + 1)).is_none(), "acknowledgment did not expire");
+    ensure!(list.check("EURUSD", &breakdown("weekly_sym"), now + Duration::minutes(config.suppress_minutes - 1)).is_some(), "suppression expired early");
+    println!("✅ Acknowledgment expired after {} minutes", config.acknowledge_minutes);
+
+    // Test 3: rules survive a restart
+    println!("📊 Test 3: Persistence");
+    let path = std::env::temp_dir().join(format!("suppressions_{}.json", uuid::Uuid::new_v4()));
+    let stored = SuppressionList::with_file(SuppressionConfig::default(), &path)?;
+    let rule = stored.suppress(None, "cycle_30", "CycleDisruption", "test", Some(Duration::hours(2)));
+    stored.suppress(None, "old", "NovelPattern", "test", Some(Duration::seconds(-1)));
+    drop(stored);
+    let reloaded = SuppressionList::with_file(SuppressionConfig::default(), &path)?;
+    let active = reloaded.active(Utc::now());
+    std::fs::remove_file(&path)?;
+    ensure!(active.len() == 1 && active[0].id == rule.id, "expected only the live rule after reload, got {}", active.len());
+    println!("✅ {} rule reloaded, expired rule dropped", active.len());
+
+    // Test 4: acknowledge / suppress / unsuppress through the controller API
+    println!("📊 Test 4: Controller commands");
+    let manager = MultiCurrencyManager::new();
+    let response = handle_command(&manager, TradingCommand::new("suppress").with_pair("EURUSD")
+        .with_parameter("pattern", "weekly_sym")
+        .with_parameter("type", "SymmetryBreakdown")
+        .with_parameter("minutes", "30")).await;
+    ensure!(response.is_success(), "suppress failed: {}", response.message);
+    let rules = manager.suppressions.active(Utc::now());
+    ensure!(rules.len() == 1 && rules[0].kind == SuppressionKind::Suppressed && rules[0].created_by == "api", "suppression not stored");
+    ensure!(manager.suppressions.check("EURUSD", &breakdown("weekly_sym"), Utc::now()).is_some(), "API suppression not applied");
+
+    let response = handle_command(&manager, TradingCommand::new("acknowledge")).await;
+    ensure!(!response.is_success(), "acknowledge without pattern or type should fail");
+
+    let response = handle_command(&manager, TradingCommand::new("unsuppress").with_parameter("id", &rules[0].id)).await;
+    ensure!(response.is_success(), "unsuppress failed: {}", response.message);
+    ensure!(manager.suppressions.active(Utc::now()).is_empty(), "rule not removed");
+    let audited = manager.audit_log.recent(10);
+    ensure!(audited.len() == 3 && audited.iter().filter(|entry| entry.success).count() == 2, "expected three audit entries");
+    println!("✅ Suppress, rejected acknowledge and unsuppress audited");
+
+    println!();
+    println!("🎉 All suppression tests passed");
+    Ok(())
+}
@@ -0,0 +1,116 @@
+//! # Symmetry Artifact Test
+//!
+//! Check symmetry sets round-trip through JSON and bincode files, foreign runs
+//! are told apart, and imported sets reproduce walk-forward anomalies
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::f64::consts::PI;
+
+use forex_pattern_reconstruction::anomaly::AnomalyDetectionConfig;
+use forex_pattern_reconstruction::backtest::walk_forward::WalkForwardDetector;
+use forex_pattern_reconstruction::core::{EngineConfig, TimeSymmetricEngine};
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::embedded_db::config_hash;
+use forex_pattern_reconstruction::patterns::PatternConfig;
+use forex_pattern_reconstruction::symmetry::{SymmetrySet, SYMMETRY_SET_VERSION};
+
+/// Hourly bars on a 24-bar cycle with noise
+fn bars(count: usize, seed: u64) -> Vec<ForexDataPoint> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let mut previous: f64 = 1.1;
+    (0..count)
+        .map(|i| {
+            let close = 1.1 * (1.0 + 0.002 * (2.0 * PI * i as f64 / 24.0).sin()) + rng.gen_range(-0.0003..0.0003);
+            let (open, high, low) = (previous, previous.max(close) + 0.0002, previous.min(close) - 0.0002);
+            previous = close;
+            ForexDataPoint { timestamp: start + Duration::hours(i as i64), open, high, low, close, volume: None }
+        })
+        .collect()
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 SYMMETRY ARTIFACT TEST");
+    println!("=========================");
+    println!();
+
+    let dir = std::env::temp_dir().join(format!("symmetry-artifact-test-{}", std::process::id()));
+    let data = bars(600, 7);
+    let engine_config = EngineConfig::default();
+    let hash = config_hash(&engine_config)?;
+    let mut engine = TimeSymmetricEngine::new(engine_config.clone())?;
+    engine.initialize().await?;
+    let symmetries = engine.extract_temporal_symmetries(&data[..200]).await?;
+    ensure!(!symmetries.is_empty(), "the cycle is decoded");
+    let set = SymmetrySet::new(symmetries, &data[..200], "eurusd", "H1", &hash);
+
+    // Test 1: JSON files round-trip exactly
+    println!("📊 Test 1: JSON");
+    let json_path = dir.join("EURUSD.symmetries.json");
+    set.save(&json_path)?;
+    let from_json = SymmetrySet::load(&json_path)?;
+    ensure!(serde_json::to_value(&from_json)? == serde_json::to_value(&set)?, "every field and float survives");
+    ensure!(from_json.schema_version == SYMMETRY_SET_VERSION && from_json.provenance.pair == "EURUSD" && from_json.provenance.bars == 200,
+            "version and provenance recorded");
+    ensure!(from_json.provenance.data_end == Some(data[199].timestamp) && from_json.config_hash == hash, "the decoding run is identified");
+    ensure!(std::fs::read_to_string(&json_path)?.lines().count() > 10, "written for reading and diffing");
+    println!("   ✅ {} symmetries, {} bytes", from_json.symmetries.len(), std::fs::metadata(&json_path)?.len());
+
+    // Test 2: binary files hold the same set
+    println!("📊 Test 2: binary");
+    let bin_path = dir.join("EURUSD.symmetries.bin");
+    set.save(&bin_path)?;
+    let from_bin = SymmetrySet::load(&bin_path)?;
+    ensure!(serde_json::to_value(&from_bin)? == serde_json::to_value(&set)?, "every field and float survives");
+    ensure!(std::fs::metadata(&bin_path)?.len() < std::fs::metadata(&json_path)?.len(), "smaller than JSON");
+    ensure!(!dir.join("EURUSD.symmetries.partial").exists(), "written in place atomically");
+    println!("   ✅ {} bytes", std::fs::metadata(&bin_path)?.len());
+
+    // Test 3: other versions and formats are refused
+    println!("📊 Test 3: refusals");
+    let future = SymmetrySet { schema_version: SYMMETRY_SET_VERSION + 1, ..set.clone() };
+    for name in ["future.symmetries.json", "future.symmetries.bin"] {
+        future.save(&dir.join(name))?;
+        let error = SymmetrySet::load(&dir.join(name)).err().map(|error| error.to_string()).unwrap_or_default();
+        ensure!(error.contains("version 2, expected 1"), "{} refused: {}", name, error);
+    }
+    ensure!(set.save(&dir.join("EURUSD.symmetries.csv")).is_err(), "unknown extensions are not written");
+    std::fs::write(dir.join("broken.symmetries.json"), "{\"schema_version\": 1, \"symmetries\": [")?;
+    ensure!(SymmetrySet::load(&dir.join("broken.symmetries.json")).is_err(), "truncated files are refused");
+    ensure!(SymmetrySet::load(&dir.join("missing.symmetries.bin")).is_err(), "missing files are refused");
+    println!("   ✅ Future versions, unknown formats and broken files refused");
+
+    // Test 4: runs that cannot reproduce the set are told apart
+    println!("📊 Test 4: mismatches");
+    ensure!(set.mismatches("EURUSD", &hash, Some(&data[..200])).is_empty(), "the decoding run reproduces it");
+    let other_config = config_hash(&EngineConfig { coherence_window: 50, ..engine_config.clone() })?;
+    let mismatches = set.mismatches("GBPUSD", &other_config, Some(&data[..300]));
+    ensure!(mismatches.len() == 3, "pair, configuration and bars differ: {:?}", mismatches);
+    ensure!(set.mismatches("EURUSD", &hash, None).is_empty(), "bars are only checked when they should match");
+    println!("   ✅ {}", mismatches.join("; "));
+
+    // Test 5: walk-forward detection against an imported set
+    println!("📊 Test 5: walk-forward");
+    let detector = |symmetries| {
+        WalkForwardDetector::new(engine_config.clone(), PatternConfig::default(), AnomalyDetectionConfig::default(), 100)
+            .map(|detector| detector.with_symmetries(symmetries))
+    };
+    let json_run = detector(from_json.symmetries.clone())?.detect(&data, 200).await?;
+    let bin_run = detector(from_bin.symmetries.clone())?.detect(&data, 200).await?;
+    ensure!(json_run.refits.len() == 4 && json_run.refits.iter().all(|refit| refit.symmetries == set.symmetries.len()),
+            "every block measured against the imported set");
+    let keys = |anomalies: &[forex_pattern_reconstruction::anomaly::DetectedAnomaly]| anomalies.iter()
+        .map(|anomaly| (anomaly.timestamp, anomaly.anomaly_type.name(), anomaly.confidence.to_bits()))
+        .collect::<Vec<_>>();
+    ensure!(keys(&json_run.anomalies) == keys(&bin_run.anomalies), "either file reproduces the run");
+    println!("   ✅ {} anomalies from either file", json_run.anomalies.len());
+
+    std::fs::remove_dir_all(&dir)?;
+    println!();
+    println!("🎉 All symmetry artifact tests passed");
+    Ok(())
+}
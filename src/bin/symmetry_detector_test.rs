@@ -0,0 +1,117 @@
+//! # Symmetry Detector Test
+//!
+//! Build price paths with a known mirror, cycle and trend, and check the symmetry
+//! detector finds each at its period, re-measures them on short windows, and
+//! feeds the extrapolator only the history before its target date
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::symmetry::{SymmetryDetector, SymmetryDetectorConfig, MIRROR, ROTATIONAL, TRANSLATIONAL};
+use forex_pattern_reconstruction::synthetic::TemporalExtrapolator;
+
+/// Daily bars closing at `closes`
+fn bars(closes: &[f64]) -> Vec<ForexDataPoint> {
+    let start = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+    closes.iter()
+        .enumerate()
+        .map(|(i, &close)| ForexDataPoint {
+            timestamp: start + Duration::days(i as i64),
+            open: close,
+            high: close + 0.0005,
+            low: close - 0.0005,
+            close,
+            volume: Some(100.0),
+        })
+        .collect()
+}
+
+/// A random walk of `period` steps that then retraces itself step for step
+fn mirror_path(period: usize, rng: &mut StdRng) -> Vec<f64> {
+    let steps: Vec<f64> = (0..period).map(|_| rng.gen_range(-0.002..0.002)).collect();
+    let mut closes = vec![1.1];
+    for step in steps.iter().chain(steps.iter().rev().map(|s| -s).collect::<Vec<_>>().iter()) {
+        closes.push(closes.last().unwrap() + step);
+    }
+    closes
+}
+
+fn sine_path(bars: usize, period: usize) -> Vec<f64> {
+    (0..bars).map(|i| 1.1 + 0.01 * (2.0 * std::f64::consts::PI * i as f64 / period as f64).sin()).collect()
+}
+
+fn trend_path(bars: usize, step: f64, rng: &mut StdRng) -> Vec<f64> {
+    (0..bars).map(|i| 1.1 + step * i as f64 + rng.gen_range(-0.0002..0.0002)).collect()
+}
+
+fn main() -> Result<()> {
+    println!("🔬 SYMMETRY DETECTOR TEST");
+    println!("=========================");
+    println!();
+
+    let detector = SymmetryDetector::new(SymmetryDetectorConfig::default())?;
+    let mut rng = StdRng::seed_from_u64(7);
+
+    // Test 1: a retraced path is a mirror at its length
+    println!("📊 Test 1: mirror symmetry");
+    let data = bars(&mirror_path(30, &mut rng));
+    let mirror = detector.detect_mirror(&data).expect("mirror found");
+    ensure!(mirror.symmetry_type == MIRROR && mirror.period_days == 30, "mirror period 30, got {}", mirror.period_days);
+    ensure!(mirror.strength > 0.99 && mirror.mirror_points.len() == 3, "exact retrace, start/pivot/end points");
+    ensure!(mirror.mirror_points[1] == (data[30].timestamp.timestamp() as f64, data[30].close), "pivot at the turn");
+    ensure!(detector.detect_mirror(&bars(&trend_path(61, 0.001, &mut rng))).is_none(), "a trend is no mirror");
+    println!("   ✅ {} with strength {:.3}", mirror.name, mirror.strength);
+
+    // Test 2: a sine is rotational at its period, with the newest bar's phase
+    println!("📊 Test 2: rotational symmetry");
+    let data = bars(&sine_path(200, 20));
+    let rotational = detector.detect_rotational(&data).expect("cycle found");
+    ensure!(rotational.symmetry_type == ROTATIONAL && rotational.period_days == 20, "cycle period 20, got {}", rotational.period_days);
+    let phase_error = (rotational.phase_shift - 0.95).abs();
+    ensure!(phase_error.min(1.0 - phase_error) < 0.02, "bar 199 is 95% through its cycle, got {:.3}", rotational.phase_shift);
+    println!("   ✅ {} at phase {:.3}", rotational.name, rotational.phase_shift);
+
+    // Test 3: a steady trend is translational, with its direction in the points
+    println!("📊 Test 3: translational symmetry");
+    let falling = bars(&trend_path(300, -0.001, &mut rng));
+    let translational = detector.detect_translational(&falling).expect("trend found");
+    ensure!(translational.symmetry_type == TRANSLATIONAL && translational.strength > 0.9, "steady trend");
+    let (start, end) = (translational.mirror_points[0], translational.mirror_points[1]);
+    ensure!(end.0 > start.0 && end.1 < start.1, "points run forward in time and down in price");
+    let kinds: Vec<String> = detector.detect(&falling).iter().map(|s| s.symmetry_type.clone()).collect();
+    ensure!(kinds.iter().filter(|k| *k == TRANSLATIONAL).count() == 1, "one symmetry per kind: {:?}", kinds);
+    println!("   ✅ {} with strength {:.3}", translational.name, translational.strength);
+
+    // Test 4: re-measuring on a short window, as the anomaly detector does
+    println!("📊 Test 4: measuring known symmetries");
+    let window = bars(&sine_path(51, 12));
+    let cycle = detector.detect_rotational(&window).expect("short cycle found");
+    ensure!(detector.measure(&cycle, &window).is_some_and(|s| s > 0.9), "cycle holds on its own window");
+    let broken: Vec<ForexDataPoint> = window.iter().cloned().chain(bars(&trend_path(30, 0.002, &mut rng))).collect();
+    ensure!(detector.measure(&cycle, &broken[broken.len() - 51..]).is_some_and(|s| s < 0.5), "a trend breaks the cycle");
+    ensure!(detector.measure(&rotational, &window).is_none(), "51 bars are too few for three 20-bar cycles");
+    let mut unknown = cycle.clone();
+    unknown.symmetry_type = "Cyclic".to_string();
+    ensure!(detector.measure(&unknown, &window).is_none() && !SymmetryDetector::detects("Cyclic"), "other kinds are not measured");
+    println!("   ✅ Intact cycle, broken cycle and short window told apart");
+
+    // Test 5: the extrapolator sees only the history before its target date
+    println!("📊 Test 5: extrapolator symmetries");
+    let rising = bars(&trend_path(300, 0.001, &mut rng));
+    let extrapolator = TemporalExtrapolator::new(rising.clone())?;
+    let pattern = extrapolator.extrapolate_patterns(rising[299].timestamp + Duration::days(1), rising[299].close)?;
+    ensure!(pattern.contributing_symmetries.iter().any(|s| s.symmetry_type == TRANSLATIONAL), "the trend contributes");
+    ensure!(pattern.confidence > 0.0, "confidence from the symmetries found");
+    let early = extrapolator.extrapolate_patterns(rising[5].timestamp, rising[5].close)?;
+    ensure!(early.contributing_symmetries.is_empty() && early.confidence == 0.0, "six bars hold no symmetry");
+    ensure!(SymmetryDetector::new(SymmetryDetectorConfig { min_period: 10, max_period: 5, ..SymmetryDetectorConfig::default() }).is_err(),
+            "inverted period range is rejected");
+    println!("   ✅ {} symmetries at the end, none after six bars", pattern.contributing_symmetries.len());
+
+    println!();
+    println!("🎉 All symmetry detector tests passed");
+    Ok(())
+}
@@ -145,6 +145,8 @@ async fn main() -> Result<()> {
         cycle_confidence_threshold: 0.7,
         symmetry_strength_threshold: 0.6,
         enable_crisis_simulation: true,
+        seed: None,
+        ..SyntheticGenerationConfig::default()
     };
     
     let synthetic_generator = SyntheticDataGenerator::new(
@@ -169,6 +171,7 @@ async fn main() -> Result<()> {
         update_frequency_seconds: 3600, // 1 hour
         enable_slippage: true,
         max_slippage_pips: 0.5,
+        trading_windows: Default::default(),
     };
     
     let mut trading_env = SyntheticTradingEnvironment::new(
@@ -0,0 +1,123 @@
+//! # Synthetic Validation Test
+//!
+//! Check that synthetic data drawn from the history's own process passes every
+//! realism check, that too-volatile, clustering-free and one-way series each fail
+//! the check they break, that hourly synthetic data is compared at the daily
+//! spacing, and that pairs validate their generated data
+
+use anyhow::{ensure, Result};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::multi_currency::{CurrencyPairConfig, CurrencyPairState};
+use forex_pattern_reconstruction::synthetic::validation::{
+    autocorrelations, drawdown_stats, validate_synthetic, SyntheticValidationConfig,
+};
+
+/// Bars `spacing` apart after `start` following `returns`
+fn bars(start: DateTime<Utc>, spacing: Duration, returns: &[f64]) -> Vec<ForexDataPoint> {
+    let mut close = 1.1;
+    returns.iter()
+        .enumerate()
+        .map(|(i, r)| {
+            close *= r.exp();
+            ForexDataPoint { timestamp: start + spacing * (i as i32 + 1), open: close, high: close * 1.001, low: close * 0.999, close, volume: None }
+        })
+        .collect()
+}
+
+/// GARCH(1,1) returns: calm and turbulent stretches cluster
+fn garch_returns(count: usize, rng: &mut StdRng) -> Vec<f64> {
+    let (omega, alpha, beta): (f64, f64, f64) = (8e-6, 0.2, 0.6);
+    let mut variance = omega / (1.0 - alpha - beta);
+    (0..count)
+        .map(|_| {
+            let shock: f64 = (0..12).map(|_| rng.gen_range(0.0..1.0)).sum::<f64>() - 6.0;
+            let r = variance.sqrt() * shock;
+            variance = omega + alpha * r * r + beta * variance;
+            r
+        })
+        .collect()
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 SYNTHETIC VALIDATION TEST");
+    println!("============================");
+    println!();
+
+    let mut rng = StdRng::seed_from_u64(17);
+    let start = Utc.with_ymd_and_hms(2015, 1, 5, 0, 0, 0).unwrap();
+    let config = SyntheticValidationConfig::default();
+    let history = bars(start, Duration::days(1), &garch_returns(2_000, &mut rng));
+    let future = history.last().unwrap().timestamp;
+
+    // Test 1: the building blocks on known series
+    println!("📊 Test 1: autocorrelation and drawdowns");
+    let alternating: Vec<f64> = (0..100).map(|i| if i % 2 == 0 { 1.0 } else { -1.0 }).collect();
+    let acf = autocorrelations(&alternating, 2);
+    ensure!(acf[0] < -0.95 && acf[1] > 0.95, "alternating series: {:?}", acf);
+    let falling = bars(start, Duration::days(1), &[0.0, -0.1, -0.1, 0.3, -0.05]);
+    let stats = drawdown_stats(&falling);
+    ensure!((stats.max_drawdown - (1.0 - (-0.2f64).exp())).abs() < 1e-9, "max drawdown {}", stats.max_drawdown);
+    ensure!(stats.longest_drawdown_bars == 2, "two bars under the first peak");
+    println!("   ✅ ACF {:.2}/{:.2}, drawdown {:.3}", acf[0], acf[1], stats.max_drawdown);
+
+    // Test 2: data from the same process passes every check
+    println!("📊 Test 2: same process");
+    let same = bars(future, Duration::days(1), &garch_returns(365, &mut rng));
+    let report = validate_synthetic("EURUSD", &history, &same, &config)?;
+    for line in report.summary() {
+        println!("   {}", line);
+    }
+    ensure!(report.passed() && report.score == 1.0, "same-process data is realistic");
+
+    // Test 3: each broken property fails its own check
+    println!("📊 Test 3: unrealistic series");
+    let volatile: Vec<f64> = garch_returns(365, &mut rng).iter().map(|r| r * 3.0).collect();
+    let report = validate_synthetic("EURUSD", &history, &bars(future, Duration::days(1), &volatile), &config)?;
+    ensure!(!report.returns.passed && report.returns.synthetic_std > 2.0 * report.returns.historical_std, "tripled volatility fails KS");
+
+    let calm_then_wild: Vec<f64> = (0..1_460)
+        .map(|i| rng.gen_range(-1.0..1.0) * if (i / 60) % 2 == 0 { 0.001 } else { 0.012 })
+        .collect();
+    let regime_history = bars(start, Duration::days(1), &calm_then_wild);
+    let mut iid = calm_then_wild.clone();
+    iid.shuffle(&mut rng);
+    let report = validate_synthetic("EURUSD", &regime_history, &bars(future, Duration::days(1), &iid[..730]), &config)?;
+    let (historical, synthetic) = report.volatility_clustering.means();
+    ensure!(!report.volatility_clustering.passed, "shuffled returns lose the clustering ({:.3} vs {:.3})", historical, synthetic);
+    ensure!(report.returns.passed, "but keep the distribution");
+
+    let trending: Vec<f64> = (0..365).map(|_| -0.004 + rng.gen_range(-0.001..0.001)).collect();
+    let report = validate_synthetic("EURUSD", &history, &bars(future, Duration::days(1), &trending), &config)?;
+    ensure!(!report.drawdown.passed && report.drawdown.max_drawdown_ratio > config.max_drawdown_ratio, "one-way fall fails the drawdown check");
+    println!("   ✅ Volatility, clustering ({:.3} vs {:.3}) and drawdown failures caught", historical, synthetic);
+
+    // Test 4: hourly synthetic data is compared at the daily spacing
+    println!("📊 Test 4: resolution");
+    let hourly: Vec<f64> = garch_returns(365, &mut rng).iter()
+        .flat_map(|r| std::iter::repeat_n(r / 24.0, 24))
+        .collect();
+    let report = validate_synthetic("EURUSD", &history, &bars(future, Duration::hours(1), &hourly), &config)?;
+    ensure!(report.bar_seconds == 86_400 && (364..=366).contains(&report.synthetic_bars), "resampled to {} daily bars", report.synthetic_bars);
+    ensure!(validate_synthetic("EURUSD", &history[..10], &same, &config).is_err(), "too short to validate");
+    println!("   ✅ {} hourly points compared as {} daily bars", hourly.len(), report.synthetic_bars);
+
+    // Test 5: pairs validate the data they generate
+    println!("📊 Test 5: currency pairs");
+    let mut state = CurrencyPairState::new(CurrencyPairConfig::default()).await?;
+    state.historical_data = history[history.len() - 500..].to_vec();
+    state.reanalyze().await?;
+    let report = state.synthetic_quality.as_ref().ok_or_else(|| anyhow::anyhow!("no synthetic quality report"))?;
+    ensure!(report.historical_bars == 500 && report.synthetic_bars > 300, "generated year compared with the history");
+    ensure!(serde_json::from_str::<serde_json::Value>(&serde_json::to_string(report)?)?["score"].is_number(), "report serializes");
+    println!("   ✅ Pair synthetic data realism {:.0}%", report.score * 100.0);
+
+    println!();
+    println!("🎉 All synthetic validation tests passed");
+    Ok(())
+}
@@ -0,0 +1,166 @@
+//! # Throughput Panel Test
+//!
+//! Check the per-pair throughput tracker counts analyzed bars, latency and
+//! backlog, that the system panel flags exactly the pair whose data, detection
+//! or backlog falls behind the rest of a seven-pair deployment, and that
+//! dashboards serve the panel
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, TimeZone, Utc};
+use std::sync::Arc;
+use std::time::{Duration as StdDuration, Instant};
+
+use forex_pattern_reconstruction::anomaly::suppression::SuppressionList;
+use forex_pattern_reconstruction::dashboard::server::{routes, StreamHub};
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::multi_currency::throughput::{
+    PairThroughput, ThroughputConfig, ThroughputPanel, ThroughputTracker,
+};
+use forex_pattern_reconstruction::multi_currency::{MultiCurrencyManager, PairAccount};
+use forex_pattern_reconstruction::protocol::server::ApiState;
+use forex_pattern_reconstruction::protocol::{RemoteSystemStatus, SystemMetrics};
+
+const PAIRS: [&str; 7] = ["EURUSD", "GBPUSD", "USDJPY", "USDCHF", "AUDUSD", "USDCAD", "NZDUSD"];
+
+fn row(symbol: &str, latency_ms: f64, last_data_hours: i64, backlog_bars: usize) -> PairThroughput {
+    PairThroughput {
+        symbol: symbol.to_string(),
+        bars_per_sec: 1.0,
+        bars_processed: 100,
+        detection_latency_ms: Some(latency_ms),
+        detection_latency_p95_ms: Some(latency_ms),
+        last_data: Some(Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap() - Duration::hours(last_data_hours)),
+        backlog_bars,
+        bar_seconds: 3_600,
+        lagging: Vec::new(),
+    }
+}
+
+/// Daily bars ending `days` after 2024-01-01
+fn bars(days: i64) -> Vec<ForexDataPoint> {
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    (0..days)
+        .map(|day| ForexDataPoint { timestamp: start + Duration::days(day), open: 1.1, high: 1.101, low: 1.099, close: 1.1, volume: None })
+        .collect()
+}
+
+/// Run one market update on every pair
+async fn update_all(manager: &MultiCurrencyManager) -> Result<()> {
+    let mut pairs_map = manager.pairs.write().await;
+    for state in pairs_map.values_mut() {
+        state.process_market_update_seeded(&SuppressionList::default(), &PairAccount::default(), 1).await?;
+    }
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 THROUGHPUT PANEL TEST");
+    println!("========================");
+    println!();
+
+    // Test 1: the tracker
+    println!("📊 Test 1: tracker");
+    let mut tracker = ThroughputTracker::default();
+    let start = Instant::now();
+    let window = StdDuration::from_secs(60);
+    tracker.record_update(500, StdDuration::from_millis(4), start);
+    ensure!(tracker.bars_processed() == 0 && tracker.backlog(503) == 3, "startup history is not counted; new bars wait");
+    tracker.record_update(510, StdDuration::from_millis(6), start + StdDuration::from_secs(5));
+    tracker.record_update(520, StdDuration::from_millis(20), start + StdDuration::from_secs(10));
+    let rate = tracker.bars_per_sec(start + StdDuration::from_secs(10), window);
+    ensure!(tracker.bars_processed() == 20 && (rate - 2.0).abs() < 1e-9, "20 bars in 10s: {}", rate);
+    ensure!(tracker.bars_per_sec(start + StdDuration::from_secs(100), window) == 0.0, "nothing in the last minute");
+    ensure!(tracker.last_latency() == Some(StdDuration::from_millis(20)) && tracker.p95_latency() == Some(StdDuration::from_millis(20)), "latencies");
+    ensure!(tracker.backlog(520) == 0, "caught up");
+    println!("   ✅ {} bars at {:.1} bars/s, p95 {:?}", tracker.bars_processed(), rate, tracker.p95_latency().unwrap());
+
+    // Test 2: one lagging pair stands out among seven
+    println!("📊 Test 2: lagging pairs");
+    let config = ThroughputConfig::default();
+    let now = Utc::now();
+    let healthy: Vec<PairThroughput> = PAIRS.iter().map(|symbol| row(symbol, 12.0, 0, 0)).collect();
+    ensure!(ThroughputPanel::new(healthy.clone(), 0, &config, now).lagging_pairs().is_empty(), "healthy pairs keep up");
+    for (index, lagging, reason) in [
+        (2, row("USDJPY", 12.0, 5, 0), "data 5h 0m behind"),
+        (4, row("AUDUSD", 90.0, 0, 0), "detection 90ms (median 12ms)"),
+        (6, row("NZDUSD", 12.0, 0, 8), "8 bars waiting"),
+    ] {
+        let mut rows = healthy.clone();
+        rows[index] = lagging;
+        let panel = ThroughputPanel::new(rows, 0, &config, now);
+        let flagged = panel.lagging_pairs();
+        ensure!(flagged.len() == 1 && flagged[0].symbol == PAIRS[index], "only {} flagged: {:?}", PAIRS[index], flagged);
+        ensure!(flagged[0].lagging == vec![reason.to_string()], "reason {:?}", flagged[0].lagging);
+        println!("   🐢 {} lagging: {}", flagged[0].symbol, reason);
+    }
+    let mut rows = healthy.clone();
+    rows[0] = row("EURUSD", 5.0, 0, 0);
+    rows[1].detection_latency_ms = Some(9.0);
+    ensure!(ThroughputPanel::new(rows, 0, &config, now).lagging_pairs().is_empty(), "fast detection never lags");
+    let mut rows = healthy.clone();
+    rows[3].last_data = None;
+    ensure!(ThroughputPanel::new(rows, 0, &config, now).lagging_pairs()[0].lagging == vec!["no data".to_string()], "a pair without data lags");
+    println!("   ✅ Stale data, slow detection and backlog each flag their pair");
+
+    // Test 3: the manager measures its pairs
+    println!("📊 Test 3: manager");
+    let mut manager = MultiCurrencyManager::new();
+    manager.initialize_pairs(&PAIRS.map(String::from)).await?;
+    {
+        let mut pairs_map = manager.pairs.write().await;
+        for state in pairs_map.values_mut() {
+            state.historical_data = bars(100);
+            state.backfill_config.timeframe = "D1".to_string();
+            state.is_active = true;
+            state.warm = true;
+        }
+    }
+    update_all(&manager).await?;
+    {
+        let mut pairs_map = manager.pairs.write().await;
+        for state in pairs_map.values_mut() {
+            for bar in bars(108).into_iter().skip(100) {
+                state.append_bar(bar, Utc::now());
+            }
+        }
+        pairs_map.get_mut("USDCAD").unwrap().paused = true;
+    }
+    update_all(&manager).await?;
+    let panel = manager.throughput_panel(0).await;
+    ensure!(panel.pairs.len() == 7, "every pair has a row");
+    for pair in &panel.pairs {
+        ensure!(pair.detection_latency_ms.is_some() && pair.bar_seconds == 86_400, "{} measured", pair.symbol);
+        ensure!(pair.last_data == bars(108).last().map(|bar| bar.timestamp), "{} last data", pair.symbol);
+    }
+    let flagged = panel.lagging_pairs();
+    ensure!(flagged.len() == 1 && flagged[0].symbol == "USDCAD" && flagged[0].backlog_bars == 8, "paused pair backs up: {:?}", flagged);
+    let eurusd = panel.pairs.iter().find(|pair| pair.symbol == "EURUSD").unwrap();
+    ensure!(eurusd.bars_processed == 8 && eurusd.backlog_bars == 0 && eurusd.bars_per_sec > 0.0, "EURUSD analyzed its bars");
+    println!("   ✅ EURUSD {:.0} bars/s; USDCAD lagging: {}", eurusd.bars_per_sec, flagged[0].lagging.join(", "));
+
+    // Test 4: dashboards serve the panel
+    println!("📊 Test 4: dashboard route");
+    let status = RemoteSystemStatus {
+        status: "running".to_string(),
+        uptime: 0,
+        active_pairs: PAIRS.map(String::from).to_vec(),
+        total_trades: 0,
+        profit_loss: 0.0,
+        correlation_opportunities: Vec::new(),
+        system_metrics: SystemMetrics::default(),
+        feed_health: None,
+    };
+    let hub = StreamHub::new();
+    let _subscriber = hub.subscribe_prices();
+    let filter = routes(ApiState::new(Arc::new(manager), status), hub.clone());
+    let response = warp::test::request().method("GET").path("/api/throughput").reply(&filter).await;
+    ensure!(response.status() == 200, "status {}", response.status());
+    let panel: ThroughputPanel = serde_json::from_slice(response.body())?;
+    ensure!(panel.pairs.len() == 7 && panel.lagging_pairs().len() == 1 && panel.stream_backlog == hub.backlog(), "panel served");
+    println!("   ✅ /api/throughput: {} pairs, {} lagging", panel.pairs.len(), panel.lagging_pairs().len());
+
+    println!();
+    println!("🎉 All throughput panel tests passed");
+    Ok(())
+}
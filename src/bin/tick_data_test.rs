@@ -0,0 +1,132 @@
+//! # Tick Data Test
+//!
+//! Write the same quotes in the generic, Dukascopy, HistData and TrueFX tick CSV
+//! layouts and check they load identically, that ticks fold into mid-price bars
+//! that keep the spread, and that backtest fills pay the quoted spread
+
+use anyhow::{ensure, Result};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use std::path::Path;
+
+use forex_pattern_reconstruction::backtest::strategy::{Order, Strategy, StrategyContext};
+use forex_pattern_reconstruction::backtest::{BacktestConfig, BacktestEngine, StrategyConfig};
+use forex_pattern_reconstruction::data::tick::{load_tick_csv, resample_ticks, TickCsvFormat, TickDataPoint};
+use forex_pattern_reconstruction::data::timeframe::TimeframeAggregator;
+use forex_pattern_reconstruction::data::{DataConfig, ForexDataManager, ForexDataPoint};
+
+/// Buys on every even bar and sells on every odd one
+struct RoundTrip;
+
+impl Strategy for RoundTrip {
+    fn name(&self) -> &str {
+        "RoundTrip"
+    }
+
+    fn on_bar(&mut self, context: &StrategyContext, _bar: &ForexDataPoint) -> Vec<Order> {
+        let target = if context.bar_index.is_multiple_of(2) { 10_000.0 } else { 0.0 };
+        context.orders_to(target, "round trip")
+    }
+}
+
+/// Quotes every 10 seconds for `hours`; the spread widens from 1 to 3 pips each hour
+fn quotes(start: DateTime<Utc>, hours: i64) -> Vec<TickDataPoint> {
+    (0..hours * 360)
+        .map(|i| {
+            let bid = 1.1 + 0.00001 * (i % 360) as f64;
+            let spread = 0.0001 + 0.0002 * (i % 360) as f64 / 359.0;
+            TickDataPoint { timestamp: start + Duration::seconds(10 * i), bid, ask: bid + spread }
+        })
+        .collect()
+}
+
+fn write(path: &Path, header: Option<&str>, rows: impl Iterator<Item = String>) -> Result<()> {
+    let mut text = header.map(|h| format!("{}\n", h)).unwrap_or_default();
+    for row in rows {
+        text.push_str(&row);
+        text.push('\n');
+    }
+    Ok(std::fs::write(path, text)?)
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 TICK DATA TEST");
+    println!("=================");
+    println!();
+
+    let directory = std::env::temp_dir().join(format!("ticks_{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&directory)?;
+    let start = Utc.with_ymd_and_hms(2024, 1, 2, 10, 0, 0).unwrap();
+    let ticks = quotes(start, 2);
+
+    // Test 1: every layout loads to the same ticks
+    println!("📊 Test 1: CSV layouts");
+    let generic = directory.join("generic.csv");
+    write(&generic, Some("Timestamp,Bid,Ask,Volume"),
+          ticks.iter().map(|t| format!("{},{:.5},{:.5},1", t.timestamp.format("%Y-%m-%d %H:%M:%S%.3f"), t.bid, t.ask)))?;
+    let dukascopy = directory.join("dukascopy.csv");
+    write(&dukascopy, Some("Gmt time,Ask,Bid,AskVolume,BidVolume"),
+          ticks.iter().map(|t| format!("{},{:.5},{:.5},1.2,0.9", t.timestamp.format("%d.%m.%Y %H:%M:%S%.3f"), t.ask, t.bid)))?;
+    let histdata = directory.join("histdata.csv");
+    write(&histdata, None,
+          ticks.iter().map(|t| format!("{},{:.5},{:.5},0", (t.timestamp - Duration::hours(5)).format("%Y%m%d %H%M%S%3f"), t.bid, t.ask)))?;
+    let truefx = directory.join("truefx.csv");
+    write(&truefx, None,
+          ticks.iter().map(|t| format!("EUR/USD,{},{:.5},{:.5}", t.timestamp.format("%Y%m%d %H:%M:%S%.3f"), t.bid, t.ask)))?;
+    let manager = ForexDataManager::new(DataConfig::default())?;
+    let expected = manager.load_tick_csv(&generic)?;
+    ensure!(expected.len() == ticks.len() && expected[0].timestamp == start, "generic file loads every tick");
+    ensure!((expected[1].spread() - ticks[1].spread()).abs() < 1e-5, "bid and ask read from their columns");
+    for (path, format) in [(&dukascopy, TickCsvFormat::Headered), (&histdata, TickCsvFormat::HistData), (&truefx, TickCsvFormat::TrueFx)] {
+        let loaded = load_tick_csv(path, None, None)?;
+        ensure!(loaded == expected, "{} differs from the generic file", path.display());
+        ensure!(load_tick_csv(path, Some(format), None)? == loaded, "explicit {:?} matches detection", format);
+    }
+    let crossed = directory.join("crossed.csv");
+    write(&crossed, Some("time,bid,ask"), ["2024-01-02 10:00:00,1.1002,1.1001".to_string(), "2024-01-02 10:00:01,1.1001,1.1002".to_string()].into_iter())?;
+    ensure!(load_tick_csv(&crossed, None, None)?.len() == 1, "crossed quotes are skipped");
+    let headerless = directory.join("bad.csv");
+    write(&headerless, Some("when,buy,sell"), std::iter::empty())?;
+    ensure!(load_tick_csv(&headerless, None, None).is_err(), "files without bid/ask columns are rejected");
+    std::fs::remove_dir_all(&directory)?;
+    println!("   ✅ {} ticks from each of 4 layouts", expected.len());
+
+    // Test 2: ticks fold into mid-price bars that keep the spread
+    println!("📊 Test 2: tick-to-bar resampling");
+    let hourly = resample_ticks(&ticks, &TimeframeAggregator::new("H1")?)?;
+    let quarter = resample_ticks(&ticks, &TimeframeAggregator::new("M15")?)?;
+    ensure!(hourly.len() == 2 && quarter.len() == 8, "one bar per interval, got {} and {}", hourly.len(), quarter.len());
+    let first = &hourly[0];
+    ensure!(first.bar.timestamp == start && first.bar.volume == Some(360.0), "bars aligned and counting ticks");
+    ensure!((first.bar.open - ticks[0].mid()).abs() < 1e-12 && (first.bar.close - ticks[359].mid()).abs() < 1e-12, "open and close are mids");
+    ensure!((first.close_spread - 0.0003).abs() < 1e-9 && (first.max_spread - 0.0003).abs() < 1e-9, "close and max spread");
+    ensure!((first.mean_spread - 0.0002).abs() < 1e-9, "mean spread, got {}", first.mean_spread);
+    let mut unsorted = ticks.clone();
+    unsorted.swap(0, 400);
+    ensure!(resample_ticks(&unsorted, &TimeframeAggregator::new("H1")?).is_err(), "unsorted ticks are rejected");
+    println!("   ✅ Mean spread {:.1} pips, close spread {:.1} pips", first.mean_spread * 1e4, first.close_spread * 1e4);
+
+    // Test 3: fills pay the quoted spread instead of the constant slippage
+    println!("📊 Test 3: spread-aware fills");
+    let minutes = resample_ticks(&quotes(start, 4), &TimeframeAggregator::new("M5")?)?;
+    let bars: Vec<ForexDataPoint> = minutes.iter().map(|b| b.bar.clone()).collect();
+    let spreads: Vec<f64> = minutes.iter().map(|b| b.close_spread).collect();
+    let config = BacktestConfig { warmup_bars: 1, cycle_refresh_bars: 1_000, commission: 0.0, slippage: 0.0, ..BacktestConfig::default() };
+    let engine = BacktestEngine::new(StrategyConfig::default(), 100_000.0, config)?;
+    let frictionless = engine.run(&mut RoundTrip, "EURUSD", &bars, &[]).await?;
+    let quoted = engine.run_with_spreads(&mut RoundTrip, "EURUSD", &bars, &spreads, &[]).await?;
+    ensure!(!quoted.trades.is_empty() && quoted.trades.len() == frictionless.trades.len(), "same trades either way");
+    let buy = quoted.trades.iter().find(|t| t.side == "Buy").expect("a buy");
+    let index = bars.iter().position(|b| b.timestamp == buy.timestamp).expect("bar of the buy");
+    ensure!((buy.entry_price - (bars[index].close + spreads[index] / 2.0)).abs() < 1e-12, "buys fill at the ask");
+    let spread_cost = frictionless.results.total_return - quoted.results.total_return;
+    let expected_cost = quoted.trades.iter()
+        .map(|t| t.size * spreads[bars.iter().position(|b| b.timestamp == t.timestamp).unwrap()] / 2.0)
+        .sum::<f64>() / 100_000.0;
+    ensure!((spread_cost - expected_cost).abs() < 1e-9, "spread cost {:.6} should be {:.6}", spread_cost, expected_cost);
+    println!("   ✅ {} fills cost {:.2} in spread", quoted.trades.len(), spread_cost * 100_000.0);
+
+    println!();
+    println!("🎉 All tick data tests passed");
+    Ok(())
+}
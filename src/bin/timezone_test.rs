@@ -0,0 +1,147 @@
+//! # Timezone Test
+//!
+//! Check that source time zones parse, that local timestamps convert to UTC
+//! across spring and autumn daylight-saving changes (including the repeated
+//! hour and a skipped one), that New York-close server time follows US daylight
+//! saving, and that bar and tick CSV loads convert each file by its configured zone
+
+use anyhow::{ensure, Result};
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use std::path::{Path, PathBuf};
+
+use forex_pattern_reconstruction::data::timezone::{LocalTimeConverter, SourceZone, TimezoneConfig};
+use forex_pattern_reconstruction::data::{DataConfig, ForexDataManager};
+
+fn local(date: (i32, u32, u32), hour: u32, minute: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(date.0, date.1, date.2).unwrap().and_hms_opt(hour, minute, 0).unwrap()
+}
+
+fn utc(date: (i32, u32, u32), hour: u32, minute: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(date.0, date.1, date.2, hour, minute, 0).unwrap()
+}
+
+fn convert(zone: &str, times: &[NaiveDateTime]) -> Result<Vec<DateTime<Utc>>> {
+    let mut converter = LocalTimeConverter::new(zone.parse()?);
+    Ok(times.iter().map(|time| converter.to_utc(*time)).collect())
+}
+
+fn write_csv(path: &Path, times: &[&str]) -> Result<()> {
+    let mut csv = String::from("time,open,high,low,close,tick_volume\n");
+    for (i, time) in times.iter().enumerate() {
+        let price = 1.08 + 0.0001 * i as f64;
+        csv.push_str(&format!("{},{},{},{},{},100\n", time, price, price + 0.0002, price - 0.0002, price));
+    }
+    std::fs::write(path, csv)?;
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 Timezone Test");
+    println!("================");
+    println!();
+
+    // Test 1: zone names
+    println!("📊 Test 1: Zone names");
+    ensure!("UTC".parse::<SourceZone>()? == SourceZone::Utc && "".parse::<SourceZone>()? == SourceZone::Utc, "UTC");
+    ensure!("+02:00".parse::<SourceZone>()? == SourceZone::Fixed(FixedOffset::east_opt(7200).unwrap()), "+02:00");
+    ensure!("UTC-5".parse::<SourceZone>()? == SourceZone::Fixed(FixedOffset::west_opt(5 * 3600).unwrap()), "UTC-5");
+    ensure!("ny-close".parse::<SourceZone>()? == SourceZone::NewYorkClose, "ny-close");
+    ensure!(matches!("Europe/Athens".parse::<SourceZone>()?, SourceZone::Named(_)) && matches!("EET".parse::<SourceZone>()?, SourceZone::Named(_)), "IANA names");
+    ensure!("Mars/Olympus_Mons".parse::<SourceZone>().is_err() && "+25:00".parse::<SourceZone>().is_err(), "bad zones accepted");
+    println!("   ✅ UTC, fixed offsets, ny-close and IANA zones parse; unknown ones are rejected");
+
+    // Test 2: daylight-saving changes
+    println!("📊 Test 2: DST transitions");
+    let spring = (2024, 3, 10);
+    let converted = convert("America/New_York", &[local(spring, 0, 0), local(spring, 1, 0), local(spring, 3, 0), local(spring, 4, 0)])?;
+    ensure!(converted == [utc(spring, 5, 0), utc(spring, 6, 0), utc(spring, 7, 0), utc(spring, 8, 0)], "spring forward {:?}", converted);
+    let skipped = convert("America/New_York", &[local(spring, 1, 0), local(spring, 2, 30)])?;
+    ensure!(skipped[1] == utc(spring, 7, 30), "skipped 02:30 read as {}", skipped[1]);
+    let autumn = (2024, 11, 3);
+    let converted = convert("America/New_York", &[local(autumn, 0, 0), local(autumn, 1, 0), local(autumn, 1, 0), local(autumn, 2, 0)])?;
+    ensure!(converted == [utc(autumn, 4, 0), utc(autumn, 5, 0), utc(autumn, 6, 0), utc(autumn, 7, 0)], "fall back {:?}", converted);
+    let converted = convert("Europe/Athens", &[local((2024, 10, 27), 3, 30), local((2024, 10, 27), 3, 30)])?;
+    ensure!(converted[1] - converted[0] == Duration::hours(1), "repeated Athens hour {:?}", converted);
+    ensure!(convert("UTC+2", &[local(spring, 2, 30)])? == [utc(spring, 0, 30)], "fixed offsets ignore DST");
+    println!("   ✅ Hourly bars stay an hour apart in UTC through both changes");
+
+    // Test 3: New York-close server time
+    println!("📊 Test 3: New York-close server time");
+    let days = convert("ny-close", &[local((2024, 1, 15), 0, 0), local((2024, 7, 15), 0, 0)])?;
+    ensure!(days == [utc((2024, 1, 14), 22, 0), utc((2024, 7, 14), 21, 0)], "server midnight {:?}", days);
+    ensure!(days.iter().all(|day| day.with_timezone(&chrono_tz::America::New_York).format("%H:%M").to_string() == "17:00"),
+            "server days start at the New York close");
+    println!("   ✅ Server midnight is 17:00 New York in winter and summer");
+
+    // Test 4: loading converts each file by its zone
+    println!("📊 Test 4: CSV loads");
+    let dir = std::env::temp_dir().join(format!("timezone-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    let broker = dir.join("broker_eurusd.csv");
+    // Athens springs forward at 03:00 on 31 March 2024: 03:xx never happens
+    write_csv(&broker, &["2024-03-31 01:00:00", "2024-03-31 02:00:00", "2024-03-31 04:00:00", "2024-03-31 05:00:00"])?;
+    let vendor = dir.join("vendor_eurusd.csv");
+    write_csv(&vendor, &["2024-03-31 01:00:00", "2024-03-31T02:00:00+03:00", "2024-04-01"])?;
+    let config = DataConfig { timezones: TimezoneConfig::default().with_source("BROKER_", "Europe/Athens"), ..DataConfig::default() };
+    let manager = ForexDataManager::new(config.clone())?;
+    let loaded = manager.load_csv_file(&broker);
+    let plain = manager.load_csv_file(&vendor);
+    let bad = ForexDataManager::new(DataConfig { timezones: TimezoneConfig::default().with_source("broker", "Atlantis/Capital"), ..config })?
+        .load_csv_file(&broker);
+    let _ = std::fs::remove_dir_all(&dir);
+    let (loaded, plain) = (loaded?, plain?);
+    let times: Vec<DateTime<Utc>> = loaded.iter().map(|point| point.timestamp).collect();
+    ensure!(times == [utc((2024, 3, 30), 23, 0), utc((2024, 3, 31), 0, 0), utc((2024, 3, 31), 1, 0), utc((2024, 3, 31), 2, 0)],
+            "broker bars {:?}", times);
+    // Sorted on load: the explicit +03:00 row comes first
+    ensure!(plain[0].timestamp == utc((2024, 3, 30), 23, 0), "explicit offset ignored");
+    ensure!(plain[1].timestamp == utc((2024, 3, 31), 1, 0), "unconfigured file shifted");
+    ensure!(plain[2].timestamp == utc((2024, 4, 1), 0, 0), "daily date shifted");
+    ensure!(bad.is_err(), "unknown zone loaded");
+    println!("   ✅ Broker file converted from Athens time, vendor file left in UTC");
+
+    // Test 5: tick files follow the same zones; HistData keeps EST unless configured
+    println!("📊 Test 5: Tick CSV loads");
+    let dir = std::env::temp_dir().join(format!("timezone-tick-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    let broker_ticks = dir.join("broker_ticks.csv");
+    std::fs::write(&broker_ticks, "time,bid,ask\n2024-03-31 02:59:59.500,1.0800,1.0801\n2024-03-31 04:00:00.250,1.0802,1.0803\n")?;
+    let histdata = dir.join("histdata_ticks.csv");
+    std::fs::write(&histdata, "20240102 170000123,1.0950,1.0951,0\n")?;
+    let manager = ForexDataManager::new(DataConfig { timezones: TimezoneConfig::default().with_source("broker_", "Europe/Athens"), ..DataConfig::default() })?;
+    let ticks = manager.load_tick_csv(&broker_ticks);
+    let est = manager.load_tick_csv(&histdata);
+    let configured = ForexDataManager::new(DataConfig { timezones: TimezoneConfig::default().with_source("histdata", "UTC"), ..DataConfig::default() })?
+        .load_tick_csv(&histdata);
+    let _ = std::fs::remove_dir_all(&dir);
+    let (ticks, est, configured) = (ticks?, est?, configured?);
+    let times: Vec<DateTime<Utc>> = ticks.iter().map(|tick| tick.timestamp).collect();
+    ensure!(times == [utc((2024, 3, 31), 0, 59) + Duration::milliseconds(59_500), utc((2024, 3, 31), 1, 0) + Duration::milliseconds(250)],
+            "broker ticks {:?}", times);
+    ensure!(est[0].timestamp == utc((2024, 1, 2), 22, 0) + Duration::milliseconds(123), "HistData EST {}", est[0].timestamp);
+    ensure!(configured[0].timestamp == utc((2024, 1, 2), 17, 0) + Duration::milliseconds(123), "configured HistData zone {}", configured[0].timestamp);
+    println!("   ✅ Broker ticks converted from Athens time across the spring change; HistData EST unless configured");
+
+    // Test 6: configuration
+    println!("📊 Test 6: Configuration");
+    let config: DataConfig = toml::from_str(r#"
+        data_directory = "data"
+        cache_enabled = true
+        max_cache_size = 10
+        [timezones]
+        default = "ny-close"
+        [[timezones.sources]]
+        source = "eurusd_hour"
+        timezone = "America/New_York"
+    "#)?;
+    let zones = &config.timezones;
+    ensure!(matches!(zones.zone_for(&PathBuf::from("data/EURUSD_hour.csv"))?, SourceZone::Named(_)), "source entry");
+    ensure!(zones.zone_for(&PathBuf::from("data/GBPUSD.csv"))? == SourceZone::NewYorkClose, "default zone");
+    ensure!(DataConfig::default().timezones.zone_for(&PathBuf::from("any.csv"))? == SourceZone::Utc, "UTC by default");
+    println!("   ✅ Per-source zones and the default load from TOML");
+
+    println!();
+    println!("🎉 All timezone tests passed");
+    Ok(())
+}
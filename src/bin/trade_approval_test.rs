@@ -0,0 +1,109 @@
+//! # Trade Approval Test
+//!
+//! Switch the manager to approval mode and check that actions are held instead
+//! of filled, that approved actions fill and rejected ones are recorded, that
+//! unanswered actions are missed once their countdown runs out, and that an
+//! approval arriving past the signal latency budget is still missed
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, Utc};
+use std::collections::HashMap;
+
+use forex_pattern_reconstruction::laplacian_rl::TradingAction;
+use forex_pattern_reconstruction::multi_currency::approval::{ApprovalConfig, ApprovalQueue, ExecutionMode, ProposedAction};
+use forex_pattern_reconstruction::multi_currency::{ControlCommand, MultiCurrencyManager};
+use forex_pattern_reconstruction::portfolio::orders::OrderStatus;
+use forex_pattern_reconstruction::risk::SignalOrigin;
+use forex_pattern_reconstruction::synthetic::fixtures::{parameters, recent_flat_bar};
+use forex_pattern_reconstruction::trading_windows::TradingWindowsConfig;
+
+fn buy() -> HashMap<String, Vec<TradingAction>> {
+    HashMap::from([("EURUSD".to_string(), vec![TradingAction::Buy { size: 1 }, TradingAction::Hold])])
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 TRADE APPROVAL TEST");
+    println!("======================");
+    println!();
+
+    // Test 1: commands from the wire
+    println!("📊 Test 1: Commands");
+    let command = ControlCommand::parse("set-mode", None, &parameters(&[("mode", "approval"), ("timeout", "20s")]))?;
+    ensure!(command == Some(ControlCommand::SetExecutionMode { mode: ExecutionMode::Approval, timeout_seconds: Some(20) }), "parsed {:?}", command);
+    ensure!(command.as_ref().is_some_and(|c| c.to_string() == "set-mode approval --timeout 20s"), "{:?}", command);
+    ensure!(ControlCommand::parse("set_mode", None, &parameters(&[("mode", "yolo")])).is_err(), "unknown mode accepted");
+    let approve = ControlCommand::parse("approve", None, &parameters(&[("id", "#3")]))?.unwrap();
+    ensure!(approve == ControlCommand::Approve { id: Some(3) } && approve.to_string() == "approve #3", "parsed {:?}", approve);
+    let reject = ControlCommand::parse("reject", None, &parameters(&[("id", "all")]))?.unwrap();
+    ensure!(reject == ControlCommand::Reject { id: None } && reject.to_string() == "reject all", "parsed {:?}", reject);
+    ensure!(ControlCommand::parse("approve", None, &parameters(&[("id", "next")])).is_err(), "approval ids are numbers");
+    ensure!(ApprovalConfig::default().mode == ExecutionMode::Automatic, "automatic execution by default");
+    println!("✅ {}, {}, {}", command.unwrap(), approve, reject);
+
+    // Test 2: actions are held, not filled
+    println!("📊 Test 2: Queue");
+    let mut manager = MultiCurrencyManager::new().with_trading_windows(TradingWindowsConfig::unrestricted());
+    manager.initialize_pairs(&["EURUSD".to_string()]).await?;
+    manager.pairs.write().await.get_mut("EURUSD").unwrap().historical_data = (0..5).rev().map(recent_flat_bar).collect();
+    let message = manager.execute_command(&ControlCommand::SetExecutionMode { mode: ExecutionMode::Approval, timeout_seconds: Some(60) }, "test").await?;
+    manager.execute_actions(&buy()).await;
+    let pending = manager.pending_approvals().await;
+    ensure!(pending.len() == 1 && pending[0].proposed.action == TradingAction::Buy { size: 1 }, "holds are not queued: {:?}", pending);
+    let snapshot = manager.portfolio_snapshot().await;
+    ensure!(snapshot.positions.is_empty() && snapshot.order_stats.submitted == 0, "held action reached the blotter");
+    println!("✅ {}: {}", message, pending[0].summary(Utc::now()));
+
+    // Test 3: approving fills, rejecting records the refusal
+    println!("📊 Test 3: Answers");
+    ensure!(manager.execute_command(&ControlCommand::Approve { id: Some(99) }, "test").await.is_err(), "unknown id approved");
+    let approved = manager.execute_command(&ControlCommand::Approve { id: Some(pending[0].id) }, "test").await?;
+    ensure!(manager.portfolio_snapshot().await.positions.len() == 1, "approved buy was not filled: {}", approved);
+    ensure!(manager.pending_approvals().await.is_empty(), "approved action still held");
+
+    manager.execute_actions(&HashMap::from([("EURUSD".to_string(), vec![TradingAction::ClosePosition])])).await;
+    let rejected = manager.execute_command(&ControlCommand::Reject { id: None }, "test").await?;
+    let snapshot = manager.portfolio_snapshot().await;
+    ensure!(snapshot.positions.len() == 1 && snapshot.order_stats.rejected == 1, "rejected close changed the book: {:?}", snapshot.order_stats);
+    ensure!(snapshot.recent_orders[0].status == OrderStatus::Rejected, "{:?}", snapshot.recent_orders[0]);
+    ensure!(manager.execute_command(&ControlCommand::Reject { id: None }, "test").await.is_err(), "nothing left to reject");
+    println!("✅ {}; {}", approved, rejected);
+
+    // Test 4: unanswered actions are missed
+    println!("📊 Test 4: Countdown");
+    let proposed = ProposedAction { symbol: "EURUSD".to_string(), index: 0, action: TradingAction::ClosePosition, origin: None };
+    let mut queue = ApprovalQueue::default();
+    let held = queue.propose(proposed.clone(), Utc::now() - Duration::seconds(61), Duration::seconds(60));
+    ensure!(held.remaining(Utc::now()) == Duration::zero() && queue.expire(Utc::now()).len() == 1 && queue.is_empty(), "expired entry kept");
+    manager.approvals.write().await.propose(proposed, Utc::now() - Duration::seconds(61), Duration::seconds(60));
+    let expired = manager.expire_approvals().await;
+    let snapshot = manager.portfolio_snapshot().await;
+    ensure!(expired.len() == 1 && snapshot.order_stats.missed == 1 && snapshot.positions.len() == 1, "timed-out close: {:?}", snapshot.order_stats);
+    ensure!(snapshot.recent_orders[0].summary().contains("not approved within 60s"), "{}", snapshot.recent_orders[0].summary());
+    println!("✅ {}", snapshot.recent_orders[0].summary());
+
+    // Test 5: a late approval still obeys the signal latency budget
+    println!("📊 Test 5: Latency");
+    manager.execute_command(&ControlCommand::SetSignalLatency { max_age_seconds: None, max_age_bars: Some(2) }, "test").await?;
+    {
+        let mut pairs = manager.pairs.write().await;
+        let state = pairs.get_mut("EURUSD").unwrap();
+        state.signal_origin = Some(SignalOrigin { observed_at: Utc::now(), bars: state.historical_data.len() });
+    }
+    manager.execute_actions(&HashMap::from([("EURUSD".to_string(), vec![TradingAction::ClosePosition])])).await;
+    manager.pairs.write().await.get_mut("EURUSD").unwrap().historical_data.extend([recent_flat_bar(0), recent_flat_bar(0), recent_flat_bar(0)]);
+    manager.execute_command(&ControlCommand::Approve { id: None }, "test").await?;
+    let snapshot = manager.portfolio_snapshot().await;
+    ensure!(snapshot.positions.len() == 1 && snapshot.order_stats.missed == 2, "close approved 3 bars late was filled: {:?}", snapshot.order_stats);
+    ensure!(snapshot.recent_orders[0].summary().contains("3 bars old"), "{}", snapshot.recent_orders[0].summary());
+
+    manager.execute_command(&ControlCommand::SetExecutionMode { mode: ExecutionMode::Automatic, timeout_seconds: None }, "test").await?;
+    manager.pairs.write().await.get_mut("EURUSD").unwrap().signal_origin = None;
+    manager.execute_actions(&HashMap::from([("EURUSD".to_string(), vec![TradingAction::ClosePosition])])).await;
+    ensure!(manager.portfolio_snapshot().await.positions.is_empty(), "automatic mode did not fill");
+    println!("✅ Late approval missed, automatic mode fills again");
+
+    println!();
+    println!("🎉 All trade approval tests passed");
+    Ok(())
+}
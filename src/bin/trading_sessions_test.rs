@@ -0,0 +1,112 @@
+//! # Trading Sessions Test
+//!
+//! Check that sessions follow their local opening hours across daylight saving
+//! changes (including the weeks when the US and UK clocks disagree), that the
+//! FX weekend is closed, that custom sessions are validated, and that the
+//! anomaly detector falls back to session volatility baselines for sparse hours
+
+use anyhow::{ensure, Result};
+use chrono::{DateTime, Duration, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use forex_pattern_reconstruction::anomaly::{AnomalyDetectionConfig, TemporalAnomalyDetector};
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::sessions::{
+    SessionConfig, SessionDefinition, SessionVolatility, TradingSessions, OFF_HOURS, WEEKEND,
+};
+use forex_pattern_reconstruction::stats::vol_surface::vol_surface;
+use forex_pattern_reconstruction::synthetic::fixtures::at;
+
+/// Hourly bars with a 20-pip range while London is open and 5 pips otherwise
+fn hourly(rng: &mut StdRng, sessions: &TradingSessions, start: DateTime<Utc>, hours: i64) -> Vec<ForexDataPoint> {
+    (0..hours).map(|h| {
+        let timestamp = start + Duration::hours(h);
+        let price = 1.1000 + rng.gen_range(-0.0005..0.0005);
+        let range = if sessions.active(timestamp).contains(&"London") { 0.0020 } else { 0.0005 } * rng.gen_range(0.9..1.1);
+        ForexDataPoint { timestamp, open: price, high: price + range / 2.0, low: price - range / 2.0, close: price, volume: None }
+    }).collect()
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 TRADING SESSIONS TEST");
+    println!("========================");
+    println!();
+    let sessions = TradingSessions::default();
+
+    // Test 1: sessions keep local hours across DST
+    println!("📊 Test 1: Daylight saving");
+    ensure!(sessions.active(at(2024, 7, 3, 7, 30)).contains(&"London"), "London opens 07:00 UTC in summer");
+    ensure!(!sessions.active(at(2024, 1, 10, 7, 30)).contains(&"London"), "London opens 08:00 UTC in winter");
+    ensure!(sessions.active(at(2024, 7, 3, 12, 30)).contains(&"NewYork"), "New York opens 12:00 UTC in summer");
+    ensure!(!sessions.active(at(2024, 1, 10, 12, 30)).contains(&"NewYork"), "New York opens 13:00 UTC in winter");
+    ensure!(sessions.active(at(2024, 1, 9, 20, 30)).contains(&"Sydney"), "Sydney opens 20:00 UTC in its summer");
+    ensure!(!sessions.active(at(2024, 7, 2, 20, 30)).contains(&"Sydney"), "Sydney opens 21:00 UTC in its winter");
+    // US clocks moved on 10 March 2024, UK clocks on 31 March
+    ensure!(sessions.label(at(2024, 3, 20, 12, 15)) == "London+NewYork", "got {}", sessions.label(at(2024, 3, 20, 12, 15)));
+    ensure!(sessions.label(at(2024, 1, 10, 12, 15)) == "London", "got {}", sessions.label(at(2024, 1, 10, 12, 15)));
+    ensure!(sessions.label(at(2024, 7, 3, 0, 30)) == "Sydney+Tokyo", "got {}", sessions.label(at(2024, 7, 3, 0, 30)));
+    println!("   ✅ 12:15 UTC is {} in March and {} in January", sessions.label(at(2024, 3, 20, 12, 15)), sessions.label(at(2024, 1, 10, 12, 15)));
+
+    // Test 2: the FX week runs Sunday 17:00 to Friday 17:00 New York time
+    println!("📊 Test 2: Weekend");
+    ensure!(!sessions.is_weekend(at(2024, 7, 5, 20, 59)) && sessions.is_weekend(at(2024, 7, 5, 21, 0)), "summer Friday close at 21:00 UTC");
+    ensure!(!sessions.is_weekend(at(2024, 1, 12, 21, 30)) && sessions.is_weekend(at(2024, 1, 12, 22, 0)), "winter Friday close at 22:00 UTC");
+    ensure!(sessions.label(at(2024, 7, 6, 12, 0)) == WEEKEND && sessions.active(at(2024, 7, 6, 12, 0)).is_empty(), "Saturday is closed");
+    ensure!(sessions.is_weekend(at(2024, 7, 7, 20, 59)) && sessions.label(at(2024, 7, 7, 21, 0)) == "Sydney", "Sunday reopens with Sydney");
+    println!("   ✅ Week closes {} and reopens {}", at(2024, 7, 5, 21, 0), at(2024, 7, 7, 21, 0));
+
+    // Test 3: custom sessions, including one crossing midnight
+    println!("📊 Test 3: Configuration");
+    let config = SessionConfig {
+        sessions: vec![SessionDefinition::new("Evening", "Europe/Berlin", "22:00", "02:00")],
+        ..SessionConfig::default()
+    };
+    let evening = TradingSessions::new(config.clone())?;
+    ensure!(evening.label(at(2024, 7, 3, 23, 30)) == "Evening", "01:30 Berlin belongs to the session opened at 22:00");
+    ensure!(evening.label(at(2024, 7, 6, 23, 30)) == WEEKEND, "nothing opens on Saturday night");
+    ensure!(evening.label(at(2024, 7, 3, 12, 0)) == OFF_HOURS, "midday is outside the only session");
+    let mut broken = config.clone();
+    broken.sessions[0].timezone = "Mars/Olympus".to_string();
+    ensure!(TradingSessions::new(broken).is_err(), "unknown time zone accepted");
+    let mut broken = config;
+    broken.sessions[0].open = "25:00".to_string();
+    ensure!(TradingSessions::new(broken).is_err(), "invalid opening time accepted");
+    let parsed: SessionConfig = toml::from_str("week_boundary = \"17:00\"")?;
+    ensure!(parsed.sessions.len() == 4, "defaults fill a partial config");
+    println!("   ✅ Custom session validated and labelled");
+
+    // Test 4: volatility baselines by session
+    println!("📊 Test 4: Session baselines");
+    let mut rng = StdRng::seed_from_u64(7);
+    let history = hourly(&mut rng, &sessions, at(2024, 3, 4, 0, 0), 24 * 7 * 3);
+    let baselines = SessionVolatility::from_history(&history, &sessions);
+    let london = baselines.expected("London").ok_or_else(|| anyhow::anyhow!("no London baseline"))?;
+    let tokyo = baselines.expected("Tokyo").ok_or_else(|| anyhow::anyhow!("no Tokyo baseline"))?;
+    ensure!(london.mean > 3.0 * tokyo.mean, "London {:.6} vs Tokyo {:.6}", london.mean, tokyo.mean);
+    ensure!(baselines.expected("Nowhere").is_none(), "unknown sessions have no baseline");
+    ensure!(baselines.buckets().first().is_some_and(|(label, _)| label.contains("London")), "busiest session first");
+    println!("   ✅ {}", baselines.buckets().iter().map(|(label, bucket)| format!("{} {:.5}", label, bucket.mean)).collect::<Vec<_>>().join(", "));
+
+    // Test 5: the detector uses the session baseline where the hour of the week is sparse
+    println!("📊 Test 5: Detector");
+    let detector = TemporalAnomalyDetector::new(Vec::new(), Vec::new(), &history, AnomalyDetectionConfig::default())?;
+    // Three weeks of history leave each hour-of-week bucket with three bars
+    let after_uk_change = at(2024, 4, 3, 10, 30);
+    ensure!(vol_surface(&history).hour_of_week_bucket(after_uk_change).is_none(), "hour of the week is well sampled");
+    ensure!(detector.session(after_uk_change) == "London", "only London open at 10:30 UTC");
+    let expected = detector.expected_volatility(after_uk_change);
+    ensure!((expected.mean - london.mean).abs() < 1e-12, "expected {:.6}, London baseline {:.6}", expected.mean, london.mean);
+
+    let dense = hourly(&mut rng, &sessions, at(2024, 1, 1, 0, 0), 24 * 7 * 8);
+    let detector = TemporalAnomalyDetector::new(Vec::new(), Vec::new(), &dense, AnomalyDetectionConfig::default())?;
+    let surface = vol_surface(&dense);
+    let bucket = surface.hour_of_week_bucket(at(2024, 3, 6, 10, 0)).ok_or_else(|| anyhow::anyhow!("sparse hour"))?;
+    ensure!(detector.expected_volatility(at(2024, 3, 6, 10, 0)).mean == bucket.mean, "well-sampled hours keep the hour-of-week baseline");
+    println!("   ✅ Sparse hour at {} uses the London baseline {:.5}", after_uk_change, expected.mean);
+
+    println!();
+    println!("🎉 All trading sessions tests passed");
+    Ok(())
+}
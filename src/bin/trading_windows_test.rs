@@ -0,0 +1,128 @@
+//! # Trading Windows Test
+//!
+//! Check the weekend, Friday close, rollover and blackout windows, that the
+//! live manager refuses entries inside one while still allowing closes, and that
+//! a backtest reversal inside one only closes the position
+
+use anyhow::{ensure, Result};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use forex_pattern_reconstruction::backtest::strategy::{Fill, Order, Strategy, StrategyContext};
+use forex_pattern_reconstruction::backtest::{BacktestConfig, BacktestEngine, StrategyConfig};
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::laplacian_rl::TradingAction;
+use forex_pattern_reconstruction::multi_currency::MultiCurrencyManager;
+use forex_pattern_reconstruction::portfolio::orders::OrderStatus;
+use forex_pattern_reconstruction::trading_windows::{BlackoutWindow, BlockReason, TradingWindowsConfig};
+
+fn at(day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+    // March 2024: the 4th is a Monday, the 8th a Friday
+    Utc.with_ymd_and_hms(2024, 3, day, hour, minute, 0).unwrap()
+}
+
+fn blackout(start: DateTime<Utc>, end: DateTime<Utc>) -> BlackoutWindow {
+    BlackoutWindow { start, end, reason: "NFP".to_string() }
+}
+
+/// Places scripted orders and records the fills it hears about
+struct Scripted {
+    orders: HashMap<usize, Vec<Order>>,
+    fills: Arc<Mutex<Vec<Fill>>>,
+}
+
+impl Strategy for Scripted {
+    fn name(&self) -> &str {
+        "scripted"
+    }
+
+    fn on_bar(&mut self, context: &StrategyContext, _bar: &ForexDataPoint) -> Vec<Order> {
+        self.orders.remove(&context.bar_index).unwrap_or_default()
+    }
+
+    fn on_fill(&mut self, _context: &StrategyContext, fill: &Fill) -> Vec<Order> {
+        self.fills.lock().unwrap().push(fill.clone());
+        Vec::new()
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 TRADING WINDOWS TEST");
+    println!("=======================");
+    println!();
+
+    // Test 1: the default windows
+    println!("📊 Test 1: Default windows");
+    let config = TradingWindowsConfig::default();
+    ensure!(config.check(at(5, 12, 0)).is_none(), "Tuesday midday is open");
+    ensure!(config.check(at(9, 12, 0)) == Some(BlockReason::WeekendClose), "Saturday is closed");
+    ensure!(config.check(at(10, 21, 0)) == Some(BlockReason::WeekendClose), "Sunday before the open is closed");
+    ensure!(config.check(at(8, 21, 30)) == Some(BlockReason::FridayCloseBuffer), "the hour before the Friday close is blocked");
+    ensure!(config.check(at(5, 22, 5)) == Some(BlockReason::DailyRollover), "the rollover is blocked");
+    ensure!(config.check(at(5, 21, 45)).is_none(), "outside the rollover buffer is open");
+    ensure!(TradingWindowsConfig::unrestricted().check(at(9, 12, 0)).is_none(), "unrestricted never blocks");
+    println!("   ✅ Weekend, Friday buffer and rollover blocked");
+
+    // Test 2: blackouts cover [start, end)
+    println!("📊 Test 2: Blackouts");
+    let config = TradingWindowsConfig { blackouts: vec![blackout(at(5, 13, 0), at(5, 14, 0))], ..TradingWindowsConfig::default() };
+    ensure!(config.check(at(5, 13, 30)) == Some(BlockReason::Blackout("NFP".to_string())), "inside the blackout");
+    ensure!(config.check(at(5, 14, 0)).is_none() && config.check(at(5, 12, 59)).is_none(), "outside the blackout");
+    println!("   ✅ Blackout blocks only its range");
+
+    // Test 3: the live manager refuses entries in a window but still closes positions
+    println!("📊 Test 3: Live execution");
+    let mut manager = MultiCurrencyManager::new().with_trading_windows(TradingWindowsConfig::unrestricted());
+    manager.initialize_major_pairs().await?;
+    manager.active_pairs.retain(|pair| pair == "EURUSD");
+    {
+        let mut pairs = manager.pairs.write().await;
+        pairs.retain(|pair, _| pair == "EURUSD");
+        let state = pairs.get_mut("EURUSD").expect("pair initialized");
+        let close = 1.1000;
+        state.historical_data = vec![ForexDataPoint { timestamp: Utc::now() - Duration::hours(1), open: close, high: close, low: close, close, volume: None }];
+    }
+    let buy = HashMap::from([("EURUSD".to_string(), vec![TradingAction::Buy { size: 1 }])]);
+    manager.execute_actions(&buy).await;
+    ensure!(manager.portfolio_snapshot().await.positions.len() == 1, "entry with the windows off should fill");
+
+    let now = Utc::now();
+    manager.trading_windows = TradingWindowsConfig { blackouts: vec![blackout(now - Duration::hours(1), now + Duration::hours(1))], ..TradingWindowsConfig::default() };
+    manager.execute_actions(&HashMap::from([("EURUSD".to_string(), vec![TradingAction::Buy { size: 1 }, TradingAction::Sell { size: 2 }])])).await;
+    let refused = manager.portfolio.read().await.orders().recent(2);
+    ensure!(refused.iter().all(|order| order.status == OrderStatus::Rejected && order.reason.as_deref() == Some("blackout: NFP")),
+            "entries in the blackout must be rejected, got {:?}", refused.iter().map(|order| order.summary()).collect::<Vec<_>>());
+    let snapshot = manager.portfolio_snapshot().await;
+    ensure!(snapshot.positions.len() == 1, "the position is unchanged");
+    manager.execute_actions(&HashMap::from([("EURUSD".to_string(), vec![TradingAction::ClosePosition])])).await;
+    ensure!(manager.portfolio_snapshot().await.positions.is_empty(), "closing must still be allowed");
+    println!("   ✅ {} entries rejected in the blackout, close filled", refused.len());
+
+    // Test 4: a backtest reversal inside a window closes the position without opening the other side
+    println!("📊 Test 4: Backtest reversal");
+    let data: Vec<ForexDataPoint> = (0..8)
+        .map(|hour| ForexDataPoint { timestamp: at(5, 8 + hour, 0), open: 1.1, high: 1.1, low: 1.1, close: 1.1, volume: None })
+        .collect();
+    let windows = TradingWindowsConfig { blackouts: vec![blackout(at(5, 12, 0), at(5, 13, 0))], ..TradingWindowsConfig::default() };
+    let config = BacktestConfig { warmup_bars: 1, cycle_refresh_bars: 10_000, commission: 0.0, slippage: 0.0, ..BacktestConfig::default() };
+    let engine = BacktestEngine::new(StrategyConfig::default(), 100_000.0, config)?.with_trading_windows(windows);
+    let fills = Arc::new(Mutex::new(Vec::new()));
+    let orders = HashMap::from([
+        (2, vec![Order::buy(1_000.0, "long")]),
+        (4, vec![Order::sell(3_000.0, "reverse in the blackout")]),
+        (5, vec![Order::buy(1_000.0, "long again")]),
+        (6, vec![Order::sell(3_000.0, "reverse after the blackout")]),
+    ]);
+    engine.run(&mut Scripted { orders, fills: fills.clone() }, "EURUSD", &data, &[]).await?;
+    let fills = fills.lock().unwrap().clone();
+    let positions: Vec<f64> = fills.iter().map(|fill| fill.position_units).collect();
+    ensure!(positions == [1_000.0, 0.0, 1_000.0, -2_000.0], "positions after each fill: {:?}", positions);
+    ensure!(fills[1].units == 1_000.0, "the blocked reversal should only close: {} units", fills[1].units);
+    println!("   ✅ Reversal in the blackout closed 1000 units; outside it flipped to -2000");
+
+    println!();
+    println!("🎉 All trading windows tests passed");
+    Ok(())
+}
@@ -0,0 +1,99 @@
+//! # Units Test
+//!
+//! Check pips and price distances convert through the pair they belong to, so
+//! yen-quoted pairs get a pip of 0.01 instead of 0.0001: pip sizes and values,
+//! arithmetic and display, and the correlation analyzer and pair
+//! configuration built on them
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, TimeZone, Utc};
+use std::collections::HashMap;
+
+use forex_pattern_reconstruction::correlation::CrossPairAnalyzer;
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::multi_currency::MultiCurrencyManager;
+use forex_pattern_reconstruction::units::{pip_size, pip_value, Pips, PriceDelta, STANDARD_LOT};
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+/// Daily closes of `count` bars following `price(i)`
+fn bars(count: i64, price: impl Fn(f64) -> f64) -> Vec<ForexDataPoint> {
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    (0..count)
+        .map(|i| {
+            let close = price(i as f64);
+            ForexDataPoint { timestamp: start + Duration::days(i), open: close, high: close, low: close, close, volume: None }
+        })
+        .collect()
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 UNITS TEST");
+    println!("=============");
+    println!();
+
+    // Test 1: pip sizes and conversions
+    println!("📊 Test 1: pips and prices");
+    ensure!(pip_size("EURUSD") == 0.0001 && pip_size("USDJPY") == 0.01 && pip_size("eurjpy") == 0.01, "yen-quoted pairs use 0.01");
+    ensure!(pip_size("JPYUSD") == 0.0001, "only the quote currency decides");
+    ensure!(close(Pips(15.0).to_price("EURUSD").0, 0.0015) && close(Pips(15.0).to_price("USDJPY").0, 0.15), "15 pips");
+    let move_ = PriceDelta::between(151.20, 150.85);
+    ensure!(close(move_.to_pips("USDJPY").0, -35.0) && close(move_.abs().to_pips("USDJPY").0, 35.0), "a 0.35 yen fall is 35 pips");
+    ensure!(close(PriceDelta(0.00123).to_pips("GBPUSD").0, 12.3), "and 0.00123 on cable is 12.3");
+    for pair in ["EURUSD", "USDJPY", "EURGBP"] {
+        ensure!(close(Pips(7.5).to_price(pair).to_pips(pair).0, 7.5), "{} round-trips", pair);
+    }
+    println!("   ✅ 15 pips = {} on EURUSD, {} on USDJPY", Pips(15.0).to_price("EURUSD"), Pips(15.0).to_price("USDJPY"));
+
+    // Test 2: pip values
+    println!("📊 Test 2: pip values");
+    ensure!(close(pip_value("EURUSD", STANDARD_LOT, 1.1), 10.0), "$10 a pip on a EURUSD lot");
+    ensure!(close(pip_value("USDJPY", STANDARD_LOT, 150.0), 1000.0 / 150.0), "¥1000 a pip on a USDJPY lot, in dollars");
+    ensure!(close(pip_value("EURGBP", STANDARD_LOT, 0.85), 10.0), "£10 a pip on a EURGBP lot, in pounds");
+    ensure!(close(Pips(20.0).value("GBPUSD", 10_000.0, 1.27), 20.0), "20 pips on a mini lot");
+    println!("   ✅ USDJPY lot: ${:.2} a pip", pip_value("USDJPY", STANDARD_LOT, 150.0));
+
+    // Test 3: arithmetic, display and serialization
+    println!("📊 Test 3: arithmetic");
+    ensure!(Pips(3.0) + Pips(2.0) == Pips(5.0) && Pips(3.0) - Pips(5.0) == -Pips(2.0), "add and subtract");
+    ensure!(Pips(3.0) * 2.0 == Pips(6.0) && PriceDelta(0.003) / 3.0 == PriceDelta(0.001) && Pips(4.0) > Pips(3.5), "scale and compare");
+    ensure!(format!("{:.1}", Pips(12.345)) == "12.3 pips" && format!("{}", Pips(2.0)) == "2 pips", "shown with their unit");
+    ensure!(serde_json::to_string(&Pips(1.5))? == "1.5" && serde_json::from_str::<PriceDelta>("0.002")? == PriceDelta(0.002), "plain numbers on the wire");
+    println!("   ✅ {:.1}", Pips(3.0) + Pips(2.5));
+
+    // Test 4: correlation arbitrage in the first pair's pips
+    println!("📊 Test 4: correlation");
+    let analyzer = CrossPairAnalyzer::new();
+    // Two pairs moving together, the first off the second by ± `offset` on alternate bars
+    let pairs = |first: &str, second: &str, level: f64, offset: f64| {
+        let trend = move |i: f64| level * (1.0 + 0.03 * (i / 3.0).sin());
+        let wobble = move |i: f64| if i as i64 % 2 == 0 { offset } else { -offset };
+        HashMap::from([(first.to_string(), bars(40, move |i| trend(i) + wobble(i))), (second.to_string(), bars(40, trend))])
+    };
+    let arbitrage = |data: &HashMap<String, Vec<ForexDataPoint>>| -> Result<(f64, usize)> {
+        let correlations = analyzer.calculate_correlation_matrix(data)?;
+        let result = correlations.values().next().ok_or_else(|| anyhow::anyhow!("the pairs are correlated"))?;
+        Ok((result.arbitrage_potential.to_pips(&result.pair1).0, analyzer.find_arbitrage_opportunities(&correlations, data)?.len()))
+    };
+    let (yen, yen_opportunities) = arbitrage(&pairs("USDJPY", "EURJPY", 150.0, 0.2))?;
+    let (dollar, dollar_opportunities) = arbitrage(&pairs("EURUSD", "GBPUSD", 1.2, 0.0001))?;
+    ensure!(yen > 10.0 && yen < 30.0, "20-pip swings on USDJPY measured as about 20 pips: {:.1}", yen);
+    ensure!(dollar > 0.5 && dollar < 2.0, "1-pip swings on EURUSD measured as about 1 pip: {:.2}", dollar);
+    ensure!(yen_opportunities == 1 && dollar_opportunities == 0, "the 10-pip threshold applies in each pair's own pips");
+    println!("   ✅ {:.1} pips on the yen pairs, {:.2} on the dollar pairs", yen, dollar);
+
+    // Test 5: pair configuration
+    println!("📊 Test 5: pair configuration");
+    let mut manager = MultiCurrencyManager::new();
+    manager.initialize_pairs(&["USDJPY".to_string(), "eurusd".to_string()]).await?;
+    let pairs = manager.pairs.read().await;
+    ensure!(pairs["USDJPY"].config.pip_value == 0.01 && pairs["EURUSD"].config.pip_value == 0.0001, "pip sizes derived from the symbol");
+    println!("   ✅ USDJPY {} and EURUSD {}", pairs["USDJPY"].config.pip_value, pairs["EURUSD"].config.pip_value);
+
+    println!();
+    println!("🎉 All units tests passed");
+    Ok(())
+}
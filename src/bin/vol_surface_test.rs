@@ -0,0 +1,85 @@
+//! # Volatility Surface Test
+//!
+//! Build hourly history where the London open is routinely three times busier
+//! than the rest of the day and check that the surface captures it and the
+//! volatility detector only flags ranges that are unusual for their hour
+
+use anyhow::{ensure, Result};
+use chrono::{DateTime, Duration, TimeZone, Timelike, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use forex_pattern_reconstruction::anomaly::{AnomalyDetectionConfig, AnomalyType, TemporalAnomalyDetector};
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::stats::vol_surface;
+use forex_pattern_reconstruction::synthetic::fixtures::synthetic;
+
+fn bar(timestamp: DateTime<Utc>, range: f64) -> ForexDataPoint {
+    let close = 1.1000;
+    ForexDataPoint { timestamp, open: close, high: close + range / 2.0, low: close - range / 2.0, close, volume: None }
+}
+
+/// Typical range: 10 pips, 30 pips in the 08:00 London-open hour
+fn typical_range(timestamp: DateTime<Utc>) -> f64 {
+    if timestamp.hour() == 8 { 0.0030 } else { 0.0010 }
+}
+
+/// Volatility spikes reported for `point`, preceded by a typical bar so the detector has a window
+async fn volatility_spikes(detector: &mut TemporalAnomalyDetector, point: ForexDataPoint) -> Result<usize> {
+    let previous_time = point.timestamp - Duration::hours(1);
+    let previous = bar(previous_time, typical_range(previous_time));
+    let anomalies = detector.detect_anomalies(&[synthetic(previous), synthetic(point.clone())]).await?;
+    Ok(anomalies.iter()
+        .filter(|a| a.timestamp == point.timestamp && matches!(a.anomaly_type, AnomalyType::VolatilitySpike { .. }))
+        .count())
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 VOLATILITY SURFACE TEST");
+    println!("==========================");
+    println!();
+
+    let mut rng = StdRng::seed_from_u64(7);
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let history: Vec<ForexDataPoint> = (0..24 * 7 * 12)
+        .map(|i| {
+            let timestamp = start + Duration::hours(i);
+            bar(timestamp, typical_range(timestamp) * rng.gen_range(0.8..1.2))
+        })
+        .collect();
+
+    // Test 1: the surface separates the London open from the quiet hours
+    println!("📊 Test 1: Surface buckets");
+    let surface = vol_surface(&history);
+    let monday = start + Duration::weeks(12);
+    let london = surface.expected(monday + Duration::hours(8));
+    let asian = surface.expected(monday + Duration::hours(3));
+    println!("   London open {:.5} ± {:.5}, Asian {:.5} ± {:.5}, global {:.5}",
+             london.mean, london.std_dev, asian.mean, asian.std_dev, surface.global().mean);
+    ensure!(london.mean > asian.mean * 2.5, "London open bucket not elevated");
+    ensure!(surface.buckets().count() == 24 * 7, "expected every hour-of-week bucket populated");
+    println!("✅ London open expected ~3x the Asian session");
+
+    // Test 2: a routine London open is not a spike, the same range at 03:00 is
+    println!("📊 Test 2: Time-conditional detection");
+    let mut detector = TemporalAnomalyDetector::new(Vec::new(), Vec::new(), &history, AnomalyDetectionConfig::default())?;
+    // A range right at the London-open expectation
+    let routine_range = london.mean * 1.1000;
+    let open_spikes = volatility_spikes(&mut detector, bar(monday + Duration::hours(8), routine_range)).await?;
+    let night_spikes = volatility_spikes(&mut detector, bar(monday + Duration::hours(3), routine_range)).await?;
+    ensure!(open_spikes == 0, "routine London open flagged as a volatility spike");
+    ensure!(night_spikes == 1, "London-sized bar at 03:00 not flagged");
+    println!("✅ Only the off-session range was flagged");
+
+    // Test 3: sparse buckets fall back to the hour of day, then the global baseline
+    println!("📊 Test 3: Sparse fallback");
+    let short = vol_surface(&history[..48]);
+    let fallback = short.expected(monday + Duration::days(3) + Duration::hours(8));
+    ensure!(fallback.samples == short.global().samples, "two-day history should use the global baseline");
+    println!("✅ Fell back to the global baseline ({} samples)", fallback.samples);
+
+    println!();
+    println!("🎉 All volatility surface tests passed");
+    Ok(())
+}
@@ -0,0 +1,175 @@
+//! # Volatility Model Test
+//!
+//! Check the GARCH fit recovers simulated parameters, EWMA and GARCH forecasts
+//! follow their recursions, and generator and detector follow the fitted volatility
+
+use anyhow::{ensure, Result};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rand_distr::StandardNormal;
+
+use forex_pattern_reconstruction::anomaly::{AnomalyDetectionConfig, AnomalyType, DetectedAnomaly, TemporalAnomalyDetector};
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::stats::volatility::{log_returns, VolatilityConfig, VolatilityModel, VolatilityModelKind};
+use forex_pattern_reconstruction::synthetic::{SyntheticDataGenerator, SyntheticGenerationConfig};
+
+const ALPHA: f64 = 0.08;
+const BETA: f64 = 0.90;
+
+fn start() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()
+}
+
+/// Hourly bars following GARCH(1,1) with long-run volatility `long_run` per bar;
+/// each bar's range is drawn at the same conditional volatility
+fn garch_bars(seed: u64, bars: usize, long_run: f64) -> Vec<ForexDataPoint> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let long_run_variance = long_run * long_run;
+    let omega = long_run_variance * (1.0 - ALPHA - BETA);
+    let (mut variance, mut close) = (long_run_variance, 1.0_f64);
+    (0..bars)
+        .map(|i| {
+            let volatility = variance.sqrt();
+            let r = volatility * rng.sample::<f64, _>(StandardNormal);
+            close *= r.exp();
+            variance = omega + ALPHA * r * r + BETA * variance;
+            let half_range = 0.5 * close * volatility * rng.sample::<f64, _>(StandardNormal).abs();
+            ForexDataPoint {
+                timestamp: start() + Duration::hours(i as i64),
+                open: close,
+                high: close + half_range,
+                low: close - half_range,
+                close,
+                volume: None,
+            }
+        })
+        .collect()
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 VOLATILITY MODEL TEST");
+    println!("========================");
+    println!();
+
+    let history = garch_bars(3, 5000, 0.001);
+    let config = VolatilityConfig::default();
+
+    // Test 1: GARCH(1,1) recovers the simulated parameters
+    println!("📊 Test 1: GARCH fit");
+    let garch = VolatilityModel::fit(&history, &config)?;
+    println!("   {}", garch.summary());
+    ensure!(garch.kind == VolatilityModelKind::Garch && garch.samples == history.len() - 1, "GARCH on {} returns", garch.samples);
+    ensure!((garch.alpha - ALPHA).abs() < 0.04 && (garch.beta - BETA).abs() < 0.06, "α {:.3} β {:.3}", garch.alpha, garch.beta);
+    ensure!((garch.persistence() - (ALPHA + BETA)).abs() < 0.03, "persistence {:.3}", garch.persistence());
+    ensure!((garch.long_run_volatility() / 0.001 - 1.0).abs() < 0.15, "long-run {:.5}", garch.long_run_volatility());
+    ensure!(garch.bar_seconds == 3600.0 && garch.as_of == history.last().unwrap().timestamp, "hourly, as of the last bar");
+    let ewma = VolatilityModel::fit(&history, &VolatilityConfig { model: VolatilityModelKind::Ewma, ..config.clone() })?;
+    ensure!(garch.log_likelihood > ewma.log_likelihood, "GARCH fits better: {:.1} vs {:.1}", garch.log_likelihood, ewma.log_likelihood);
+    println!("   ✅ α {:.3} β {:.3}, log-likelihood {:.0} vs EWMA {:.0}", garch.alpha, garch.beta, garch.log_likelihood, ewma.log_likelihood);
+
+    // Test 2: EWMA and the fallbacks
+    println!("📊 Test 2: EWMA");
+    ensure!(ewma.kind == VolatilityModelKind::Ewma && ewma.omega == 0.0 && (ewma.beta - 0.94).abs() < 1e-12, "RiskMetrics λ");
+    ensure!(ewma.half_life_bars().is_none() && ewma.forecast_variance(100) == ewma.variance, "EWMA forecasts are flat");
+    let short = VolatilityModel::fit(&history[..50], &config)?;
+    ensure!(short.kind == VolatilityModelKind::Ewma, "short histories fall back to EWMA");
+    ensure!(VolatilityModel::fit(&history[..2], &config).is_err(), "two bars are too few");
+    ensure!(VolatilityModel::fit(&history, &VolatilityConfig { ewma_lambda: 1.0, ..config.clone() }).is_err(), "λ must be below 1");
+    ensure!("ewma".parse::<VolatilityModelKind>()? == VolatilityModelKind::Ewma && "GARCH".parse::<VolatilityModelKind>()? == VolatilityModelKind::Garch, "model names parse");
+    ensure!("arch".parse::<VolatilityModelKind>().is_err(), "unknown model rejected");
+    let returns = log_returns(&history);
+    let mut manual = returns.iter().map(|r| r * r).sum::<f64>() / returns.len() as f64;
+    for r in &returns {
+        manual = 0.06 * r * r + 0.94 * manual;
+    }
+    ensure!((manual / ewma.variance - 1.0).abs() < 1e-9, "EWMA recursion {:.3e} vs {:.3e}", ewma.variance, manual);
+    println!("   ✅ EWMA volatility {:.4}% per bar", ewma.volatility() * 100.0);
+
+    // Test 3: forecasts revert to the long-run level and react to new bars
+    println!("📊 Test 3: forecasts");
+    let far = garch.forecast_variance(10_000);
+    ensure!((far / garch.long_run_variance - 1.0).abs() < 1e-6, "far forecast reverts to long-run");
+    let (near, mid) = (garch.forecast_variance(1), garch.forecast_variance(10));
+    ensure!(near == garch.variance && (mid - garch.long_run_variance).abs() <= (near - garch.long_run_variance).abs(), "forecasts move toward long-run");
+    ensure!((garch.forecast_volatility(3600.0, 4.0 * 3600.0) - 2.0 * garch.variance.sqrt()).abs() < 1e-12, "four-hour bars have twice the volatility");
+    let mut live = garch.clone();
+    let last = history.last().unwrap().clone();
+    live.observe(&last);
+    ensure!(live == garch, "bars already seen are ignored");
+    for i in 1..=5 {
+        let sign = if i % 2 == 0 { 1.0 } else { -1.0 };
+        live.observe(&ForexDataPoint { timestamp: last.timestamp + Duration::hours(i), close: last.close * (1.0 + sign * 0.01), ..last.clone() });
+    }
+    ensure!(live.volatility_ratio() > 2.0, "five 1% moves raise the ratio to {:.2}", live.volatility_ratio());
+    let path = garch.conditional_volatilities(&history);
+    ensure!(path.len() == history.len() && path[0] == garch.long_run_volatility(), "one forecast per bar");
+    println!("   ✅ Ratio {:.2} after five 1% moves; half-life {:.1} bars", live.volatility_ratio(), garch.half_life_bars().unwrap());
+
+    // Test 4: synthetic ranges follow the anchor's volatility
+    println!("📊 Test 4: synthetic generator");
+    let generation = SyntheticGenerationConfig {
+        future_horizon_days: 10,
+        noise_level: 0.0,
+        enable_crisis_simulation: false,
+        seed: Some(1),
+        ..SyntheticGenerationConfig::default()
+    };
+    let mut mean_ranges = Vec::new();
+    for long_run in [0.0005, 0.002] {
+        let anchor = garch_bars(4, 2000, long_run);
+        let generator = SyntheticDataGenerator::new(Vec::new(), Vec::new(), anchor.clone(), generation.clone())?;
+        let fitted = generator.volatility_model().expect("anchor long enough").long_run_volatility();
+        ensure!((fitted / long_run - 1.0).abs() < 0.2, "fitted {:.5} for {:.5}", fitted, long_run);
+        let points = generator.generate_future_data(anchor.last().unwrap().timestamp + Duration::hours(1), "EURUSD").await?;
+        mean_ranges.push(points.iter().map(|p| p.data_point.high - p.data_point.low).sum::<f64>() / points.len() as f64);
+    }
+    let ratio = mean_ranges[1] / mean_ranges[0];
+    ensure!((ratio / 4.0 - 1.0).abs() < 0.3, "four times the volatility, {:.2} times the range", ratio);
+    let bare = SyntheticDataGenerator::new(Vec::new(), Vec::new(), history[..1].to_vec(), generation.clone())?;
+    ensure!(bare.volatility_model().is_none(), "one bar fits no model");
+    ensure!(bare.generate_future_data(start(), "EURUSD").await.is_err(), "generation needs a volatility model");
+    let supplied = bare.with_volatility_model(VolatilityModel::constant(0.001, 3600.0, 1.0, history[0].timestamp));
+    ensure!(!supplied.generate_future_data(start(), "EURUSD").await?.is_empty(), "a supplied model generates");
+    println!("   ✅ Ranges {:.5} and {:.5}, ratio {:.2}", mean_ranges[0], mean_ranges[1], ratio);
+
+    // Test 5: the detector's volatility baseline follows the regime
+    println!("📊 Test 5: anomaly detector");
+    let calm = garch_bars(5, 24 * 7 * 6, 0.001);
+    let detector_config = AnomalyDetectionConfig::default();
+    let next = calm.last().unwrap().timestamp + Duration::hours(1);
+    let spike_at = next + Duration::hours(12);
+    let mut steady = TemporalAnomalyDetector::new(Vec::new(), Vec::new(), &calm, detector_config.clone())?;
+    ensure!(steady.volatility_model().is_some(), "detector fits a model");
+    let unconditional = steady.expected_volatility(calm[10].timestamp);
+    ensure!(steady.conditional_expected_volatility(calm[10].timestamp).mean == unconditional.mean, "fitted bars keep the unconditional baseline");
+    let baseline = steady.expected_volatility(spike_at);
+    let last_close = calm.last().unwrap().close;
+    let spike_range = (baseline.mean + 4.0 * baseline.std_dev) * last_close;
+    let bar = |timestamp: DateTime<Utc>, close: f64, range: f64| ForexDataPoint {
+        timestamp, open: close, high: close + range / 2.0, low: close - range / 2.0, close, volume: None,
+    };
+    let quiet: Vec<ForexDataPoint> = (0..12).map(|i| bar(next + Duration::hours(i), last_close, baseline.mean * last_close)).collect();
+    let turbulent: Vec<ForexDataPoint> = (0..12)
+        .map(|i| bar(next + Duration::hours(i), last_close * if i % 2 == 0 { 1.01 } else { 0.99 }, baseline.mean * last_close))
+        .collect();
+    let is_spike = |anomalies: &[DetectedAnomaly]| anomalies.iter()
+        .any(|a| a.timestamp == spike_at && matches!(a.anomaly_type, AnomalyType::VolatilitySpike { .. }));
+
+    let calm_run: Vec<ForexDataPoint> = quiet.iter().cloned().chain([bar(spike_at, last_close, spike_range)]).collect();
+    let calm_anomalies = steady.detect_anomalies(&calm_run).await?;
+    ensure!(is_spike(&calm_anomalies), "a 4σ range is a spike in a calm market");
+    let mut stormy = TemporalAnomalyDetector::new(Vec::new(), Vec::new(), &calm, detector_config)?;
+    let storm_run: Vec<ForexDataPoint> = turbulent.iter().cloned().chain([bar(spike_at, turbulent[11].close, spike_range)]).collect();
+    let storm_anomalies = stormy.detect_anomalies(&storm_run).await?;
+    let scaled = stormy.conditional_expected_volatility(spike_at + Duration::hours(1));
+    ensure!(stormy.volatility_model().unwrap().as_of == spike_at, "live bars update the model");
+    ensure!(scaled.mean > 2.0 * baseline.mean, "turbulence raises the baseline: {:.5} vs {:.5}", scaled.mean, baseline.mean);
+    ensure!(!is_spike(&storm_anomalies), "the same range is routine after 1% swings");
+    println!("   ✅ Baseline {:.5} → {:.5} after turbulence; spike flagged only in the calm market", baseline.mean, scaled.mean);
+
+    println!();
+    println!("🎉 All volatility model tests passed");
+    Ok(())
+}
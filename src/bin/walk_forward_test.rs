@@ -0,0 +1,111 @@
+//! # Walk-Forward Detection Test
+//!
+//! Check walk-forward anomaly detection cannot see the future: rewriting every
+//! bar after some point leaves the anomalies before it unchanged, while a
+//! baseline fitted on the whole history does not. Also check each block is
+//! detected against a baseline that ends before it, and that a spike is found
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::f64::consts::PI;
+
+use forex_pattern_reconstruction::anomaly::{AnomalyDetectionConfig, AnomalyType, DetectedAnomaly, TemporalAnomalyDetector};
+use forex_pattern_reconstruction::backtest::walk_forward::{AnomalyBaseline, WalkForwardDetector};
+use forex_pattern_reconstruction::backtest::BacktestConfig;
+use forex_pattern_reconstruction::core::EngineConfig;
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::patterns::PatternConfig;
+
+const BARS: usize = 600;
+const WARMUP: usize = 200;
+const CHANGE: usize = 400;
+const SPIKE: usize = 300;
+
+/// Hourly bars on a 24-bar cycle with noise scaled by `noise(i)`, and a jump at `SPIKE`
+fn bars(seed: u64, noise: impl Fn(usize) -> f64) -> Vec<ForexDataPoint> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let mut previous: f64 = 1.1;
+    (0..BARS)
+        .map(|i| {
+            let jump = if i == SPIKE { 0.01 } else { 0.0 };
+            let close = 1.1 * (1.0 + 0.002 * (2.0 * PI * i as f64 / 24.0).sin()) + rng.gen_range(-1.0..1.0) * noise(i) + jump;
+            let (open, high, low) = (previous, previous.max(close) + 0.0002, previous.min(close) - 0.0002);
+            previous = close;
+            ForexDataPoint { timestamp: start + Duration::hours(i as i64), open, high, low, close, volume: None }
+        })
+        .collect()
+}
+
+/// What identifies an anomaly across runs
+fn keys(anomalies: &[DetectedAnomaly], before: chrono::DateTime<Utc>) -> Vec<(i64, &'static str, u64)> {
+    anomalies.iter()
+        .filter(|anomaly| anomaly.timestamp < before)
+        .map(|anomaly| (anomaly.timestamp.timestamp(), anomaly.anomaly_type.name(), anomaly.confidence.to_bits()))
+        .collect()
+}
+
+fn detector(refit_bars: usize) -> Result<WalkForwardDetector> {
+    WalkForwardDetector::new(EngineConfig::default(), PatternConfig::default(), AnomalyDetectionConfig::default(), refit_bars)
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 WALK-FORWARD DETECTION TEST");
+    println!("==============================");
+    println!();
+
+    let calm = bars(7, |_| 0.0003);
+    // Same bars up to CHANGE, then ten times the noise
+    let wild = bars(7, |i| if i < CHANGE { 0.0003 } else { 0.003 });
+    let change = calm[CHANGE].timestamp;
+    ensure!(calm[..CHANGE].iter().zip(&wild[..CHANGE]).all(|(a, b)| a.close == b.close), "histories agree before the change");
+
+    // Test 1: rewriting the future leaves earlier anomalies alone
+    println!("📊 Test 1: no look-ahead");
+    let walk_forward = detector(100)?;
+    let before = walk_forward.detect(&calm, WARMUP).await?;
+    let after = walk_forward.detect(&wild, WARMUP).await?;
+    ensure!(!keys(&before.anomalies, change).is_empty(), "anomalies before the change");
+    ensure!(keys(&before.anomalies, change) == keys(&after.anomalies, change), "anomalies before the change depend on later bars");
+    ensure!(after.anomalies.len() > before.anomalies.len(), "the wilder regime shows up after the change");
+    println!("   ✅ {} anomalies before the change in both runs; {} vs {} in total",
+             keys(&before.anomalies, change).len(), before.anomalies.len(), after.anomalies.len());
+
+    // Test 2: a baseline from the whole history leaks the later regime into earlier bars
+    println!("📊 Test 2: full-history baseline leaks");
+    let mut leaky = Vec::new();
+    for data in [&calm, &wild] {
+        let mut full = TemporalAnomalyDetector::new(Vec::new(), Vec::new(), data, AnomalyDetectionConfig::default())?;
+        leaky.push(full.detect_anomalies(&data[WARMUP..]).await?);
+    }
+    ensure!(keys(&leaky[0], change) != keys(&leaky[1], change), "a full-history baseline should see the later regime");
+    println!("   ✅ {} vs {} anomalies before the change", keys(&leaky[0], change).len(), keys(&leaky[1], change).len());
+
+    // Test 3: every block is detected against a baseline that ends before it
+    println!("📊 Test 3: refits");
+    ensure!(before.refits.len() == 4, "400 bars in blocks of 100, got {}", before.refits.len());
+    for (block, refit) in before.refits.iter().enumerate() {
+        ensure!(refit.fitted_through < refit.first_evaluated && refit.baseline_bars == WARMUP + 100 * block, "{:?}", refit);
+    }
+    ensure!(before.anomalies.iter().all(|anomaly| anomaly.timestamp >= calm[WARMUP].timestamp), "only bars after the warm-up");
+    let windowed = detector(100)?.with_max_baseline_bars(150).detect(&calm, WARMUP).await?;
+    ensure!(windowed.refits.iter().all(|refit| refit.baseline_bars == 150), "baselines capped at 150 bars");
+    let spike = calm[SPIKE].timestamp;
+    ensure!(before.anomalies.iter().any(|anomaly| anomaly.timestamp == spike && matches!(anomaly.anomaly_type, AnomalyType::VolatilitySpike { .. })),
+            "the jump is a volatility spike");
+    println!("   ✅ {} refits, spike at {} found", before.refits.len(), spike.format("%Y-%m-%d %H:%M"));
+
+    // Test 4: walk-forward is the backtest default, and bad settings are refused
+    println!("📊 Test 4: defaults and limits");
+    ensure!(BacktestConfig::default().anomaly_baseline == AnomalyBaseline::WalkForward, "backtests default to walk-forward");
+    ensure!(detector(0).is_err(), "a zero refit interval is refused");
+    ensure!(detector(100)?.detect(&calm, BARS).await.is_err() && detector(100)?.detect(&calm, 1).await.is_err(), "nothing to evaluate or no baseline");
+    println!("   ✅ Walk-forward by default");
+
+    println!();
+    println!("🎉 All walk-forward detection tests passed");
+    Ok(())
+}
@@ -0,0 +1,124 @@
+//! # Wavelet Test
+//!
+//! Check the Morlet power map's normalization and peaks, that ridges track a
+//! drifting cycle, and that drifting and transient cycles carry period tracks
+
+use anyhow::{ensure, Result};
+use chrono::{Duration, TimeZone, Utc};
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use std::f64::consts::PI;
+
+use forex_pattern_reconstruction::data::ForexDataPoint;
+use forex_pattern_reconstruction::patterns::spectral::SampledSeries;
+use forex_pattern_reconstruction::patterns::wavelet::{continuous_wavelet_transform, extract_ridges, ridge_cycles};
+use forex_pattern_reconstruction::patterns::{HiddenCycle, PatternConfig, PatternRecognizer, WaveletConfig};
+
+/// Hourly bars whose relative price offset at bar `i` is `offset(i)`, plus noise
+fn bars(count: usize, noise: f64, seed: u64, offset: impl Fn(usize) -> f64) -> Vec<ForexDataPoint> {
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..count)
+        .map(|i| {
+            let close = 1.1 * (1.0 + offset(i) + noise * rng.gen_range(-1.0..1.0));
+            ForexDataPoint { timestamp: start + Duration::hours(i as i64), open: close, high: close + 0.0002, low: close - 0.0002, close, volume: None }
+        })
+        .collect()
+}
+
+/// Hourly random walk: white-noise returns
+fn random_walk(count: usize, seed: u64) -> Vec<ForexDataPoint> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut walk = Vec::with_capacity(count);
+    let mut level = 0.0;
+    for _ in 0..count {
+        level += 0.0005 * rng.gen_range(-1.0..1.0);
+        walk.push(level);
+    }
+    bars(count, 0.0, seed, |i| walk[i])
+}
+
+/// Phase of a cycle whose period moves linearly from `from` to `to` bars over `count` bars
+fn chirp_phase(i: usize, count: usize, from: f64, to: f64) -> f64 {
+    let rate = (to - from) / count as f64;
+    2.0 * PI * ((from + rate * i as f64) / from).ln() / rate
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("🔬 Wavelet Test");
+    println!("===============");
+    println!();
+
+    let config = WaveletConfig::default();
+
+    // Test 1: normalization and a steady cycle
+    println!("📊 Test 1: Power map");
+    let noise = SampledSeries::from_closes(&random_walk(2048, 1)).unwrap();
+    let map = continuous_wavelet_transform(&noise, 4.0, 200.0, &config).unwrap();
+    ensure!(map.periods.len() == 46 && map.timestamps.len() == 2047, "{} scales, {} bars", map.periods.len(), map.timestamps.len());
+    let mean = map.global_spectrum().iter().sum::<f64>() / map.periods.len() as f64;
+    ensure!((0.6..1.4).contains(&mean), "white noise mean power {:.2}", mean);
+    ensure!(!map.is_reliable(map.periods.len() - 1, 0) && map.is_reliable(0, 1000), "cone of influence");
+
+    let steady = SampledSeries::from_closes(&bars(2048, 0.0002, 2, |i| 0.003 * (2.0 * PI * i as f64 / 32.0).sin())).unwrap();
+    let map = continuous_wavelet_transform(&steady, 4.0, 200.0, &config).unwrap();
+    let spectrum = map.global_spectrum();
+    let peak = (0..spectrum.len()).max_by(|a, b| spectrum[*a].total_cmp(&spectrum[*b])).unwrap();
+    ensure!((map.periods[peak] - 32.0).abs() < 32.0 * 0.1, "steady cycle peaks at {:.1} bars", map.periods[peak]);
+    let ridges = extract_ridges(&map, &config);
+    ensure!(ridges.first().is_some_and(|ridge| (ridge.mean_period - 32.0).abs() < 3.2 && ridge.len() > 1500), "steady ridge");
+    println!("   ✅ White noise mean power {:.2}, 32-bar cycle peaks at {:.1} bars", mean, map.periods[peak]);
+
+    // Test 2: a drifting cycle
+    println!("📊 Test 2: Ridge tracking");
+    let count = 3000;
+    let drifting = bars(count, 0.0002, 3, |i| 0.003 * chirp_phase(i, count, 20.0, 30.0).sin());
+    let series = SampledSeries::from_closes(&drifting).unwrap();
+    let map = continuous_wavelet_transform(&series, 4.0, 200.0, &config).unwrap();
+    let cycles = ridge_cycles(&series, &map, &config, 100);
+    let strongest = cycles.first().ok_or_else(|| anyhow::anyhow!("no ridge"))?;
+    let track = &strongest.track;
+    ensure!(track.points.len() <= config.track_points + 1, "{} track points", track.points.len());
+    ensure!(track.min_period() < 23.0 && track.max_period() > 26.0, "track {:.1}..{:.1}", track.min_period(), track.max_period());
+    let (early, late) = (track.period_at(drifting[400].timestamp).unwrap(), track.period_at(drifting[2600].timestamp).unwrap());
+    ensure!(early < late, "period should lengthen: {:.1} then {:.1}", early, late);
+    ensure!(strongest.is_non_stationary(&config) && strongest.confidence > 0.99, "drifting ridge");
+    ensure!(strongest.amplitude > 0.0005, "amplitude {}", strongest.amplitude);
+    println!("   ✅ Ridge follows the cycle from {:.1} to {:.1} bars (drift {:.0}%)", track.min_period(), track.max_period(), track.drift() * 100.0);
+
+    // Test 3: recognizer integration
+    println!("📊 Test 3: Non-stationary cycles");
+    let pattern_config = |wavelet: Option<WaveletConfig>| PatternConfig { min_cycle_length: 5, max_cycle_length: 200, confidence_threshold: 0.95, significance: None, wavelet };
+    let fourier = PatternRecognizer::new(pattern_config(None))?.detect_cycles(&drifting).await?;
+    ensure!(fourier.iter().all(|cycle| !cycle.is_non_stationary()), "Fourier cycles carry tracks");
+
+    let found = PatternRecognizer::new(pattern_config(Some(config.clone())))?.detect_cycles(&drifting).await?;
+    let drifting_cycles: Vec<&HiddenCycle> = found.iter().filter(|cycle| cycle.is_non_stationary()).collect();
+    ensure!(found.len() == fourier.len() + drifting_cycles.len(), "Fourier cycles should come first, unchanged");
+    let cycle = drifting_cycles.iter().find(|cycle| (20..=30).contains(&cycle.period)).ok_or_else(|| anyhow::anyhow!("drifting cycle missing"))?;
+    ensure!(cycle.name.starts_with("Drifting ") && cycle.track.as_ref().is_some_and(|track| track.drift() > config.drift_tolerance), "drifting cycle {}", cycle.name);
+
+    // A steady cycle that only runs in the second half
+    let transient = bars(3000, 0.0002, 4, |i| if i >= 1500 { 0.003 * (2.0 * PI * i as f64 / 40.0).sin() } else { 0.0 });
+    let found = PatternRecognizer::new(pattern_config(Some(config.clone())))?.detect_cycles(&transient).await?;
+    let late = found.iter().find(|cycle| cycle.is_non_stationary() && (36..=44).contains(&cycle.period))
+        .ok_or_else(|| anyhow::anyhow!("transient cycle missing"))?;
+    let start = late.track.as_ref().and_then(|track| track.start()).unwrap();
+    ensure!(start >= transient[1300].timestamp, "transient cycle starts at {}", start);
+    println!("   ✅ {} ({} track points) and a {}-bar cycle starting {} reported as non-stationary",
+             cycle.name, cycle.track.as_ref().map_or(0, |track| track.points.len()), late.period, start.format("%Y-%m-%d"));
+
+    // Test 4: cycles saved before tracks existed
+    println!("📊 Test 4: Compatibility");
+    let saved = r#"{"id":"cycle_1","name":"24-Bar Cycle","period":24,"confidence":0.9,"amplitude":0.002,"phase":0.0}"#;
+    let cycle: HiddenCycle = serde_json::from_str(saved)?;
+    ensure!(!cycle.is_non_stationary(), "old cycle read as non-stationary");
+    let round_trip: HiddenCycle = serde_json::from_str(&serde_json::to_string(found.iter().find(|c| c.is_non_stationary()).unwrap())?)?;
+    ensure!(round_trip.track.is_some(), "track lost in JSON");
+    println!("   ✅ Cycles without a track load as stationary; tracks survive JSON");
+
+    println!();
+    println!("🎉 All wavelet tests passed");
+    Ok(())
+}
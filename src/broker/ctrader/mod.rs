@@ -0,0 +1,465 @@
+//! # cTrader Open API Client
+//!
+//! Protobuf over TLS to Spotware's Open API proxies: a session authenticates the
+//! application and the account, loads its symbols, streams spot quotes and routes
+//! market orders, matching responses to requests by client message id.
+
+pub mod proto;
+
+use anyhow::{bail, Context, Result};
+use chrono::{TimeZone, Utc};
+use futures_util::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpStream;
+use tokio_native_tls::TlsStream;
+
+use super::{ExecutionBackend, Fill, MarketOrder, OpenPosition, TradingMode};
+use crate::backtest::strategy::OrderSide;
+use crate::data::provider::Tick;
+use crate::laplacian_rl::TradingAction;
+use proto::{execution, payload, Fields, ProtoMessage, Writer, ORDER_TYPE_MARKET, PRICE_SCALE, VOLUME_SCALE};
+
+/// Open API port on both environments
+pub const OPEN_API_PORT: u16 = 5035;
+
+/// cTrader Open API settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CTraderConfig {
+    pub mode: TradingMode,
+    /// Must be set for [`TradingMode::Live`]; a live session is refused otherwise
+    pub allow_live: bool,
+    /// Overrides the mode's proxy host, e.g. for a local relay
+    pub host: Option<String>,
+    pub port: u16,
+    /// Open API application credentials
+    pub client_id: String,
+    pub client_secret: String,
+    /// OAuth access token granting the application the account
+    pub access_token: String,
+    /// `ctidTraderAccountId` of the account, not its login number
+    pub account_id: i64,
+    /// Units per `TradingAction` size, as in the portfolio
+    pub units_per_size: f64,
+    pub request_timeout_ms: u64,
+}
+
+impl CTraderConfig {
+    /// Read `CTRADER_CLIENT_ID`, `CTRADER_CLIENT_SECRET`, `CTRADER_ACCESS_TOKEN`,
+    /// `CTRADER_ACCOUNT_ID` and optional `CTRADER_MODE` (demo or live),
+    /// `CTRADER_ALLOW_LIVE`, `CTRADER_HOST` / `CTRADER_PORT`
+    pub fn from_env() -> Result<Self> {
+        let var = |name: &str| std::env::var(name).with_context(|| format!("{} is not set", name));
+        Ok(Self {
+            mode: match std::env::var("CTRADER_MODE") {
+                Ok(mode) => mode.parse()?,
+                Err(_) => TradingMode::Demo,
+            },
+            allow_live: std::env::var("CTRADER_ALLOW_LIVE").is_ok_and(|allow| matches!(allow.as_str(), "1" | "true" | "yes")),
+            host: std::env::var("CTRADER_HOST").ok(),
+            port: std::env::var("CTRADER_PORT").ok()
+                .and_then(|port| port.parse().ok())
+                .unwrap_or(OPEN_API_PORT),
+            client_id: var("CTRADER_CLIENT_ID")?,
+            client_secret: var("CTRADER_CLIENT_SECRET")?,
+            access_token: var("CTRADER_ACCESS_TOKEN")?,
+            account_id: var("CTRADER_ACCOUNT_ID")?.parse().context("CTRADER_ACCOUNT_ID is not a number")?,
+            units_per_size: 1_000.0,
+            request_timeout_ms: 10_000,
+        })
+    }
+
+    /// Host the mode's proxy runs on, unless overridden
+    pub fn host(&self) -> &str {
+        match (&self.host, self.mode) {
+            (Some(host), _) => host,
+            (None, TradingMode::Demo) => "demo.ctraderapi.com",
+            (None, TradingMode::Live) => "live.ctraderapi.com",
+        }
+    }
+
+    /// Refuse live sessions unless live trading is allowed
+    pub fn check_mode(&self) -> Result<()> {
+        if self.mode == TradingMode::Live && !self.allow_live {
+            bail!("Live cTrader trading is locked; set CTRADER_ALLOW_LIVE=1 to route real orders");
+        }
+        Ok(())
+    }
+}
+
+/// A cTrader Open API session over `S`, TLS to the proxy unless built with [`CTraderClient::over`]
+pub struct CTraderClient<S = TlsStream<TcpStream>> {
+    stream: S,
+    config: CTraderConfig,
+    next_msg_id: u64,
+    authenticated: bool,
+    symbol_ids: HashMap<String, i64>,
+    symbol_names: HashMap<i64, String>,
+    /// Last bid / ask per symbol id; spot events only carry the side that moved
+    quotes: HashMap<i64, (Option<f64>, Option<f64>)>,
+    ticks: VecDeque<Tick>,
+    positions: HashMap<String, Vec<OpenPosition>>,
+}
+
+impl CTraderClient {
+    /// Open a TLS connection to the mode's proxy
+    pub async fn connect(config: CTraderConfig) -> Result<Self> {
+        config.check_mode()?;
+        let host = config.host().to_string();
+        let tcp = TcpStream::connect((host.as_str(), config.port)).await
+            .with_context(|| format!("Failed to reach cTrader at {}:{}", host, config.port))?;
+        let connector = tokio_native_tls::TlsConnector::from(native_tls::TlsConnector::new()?);
+        let stream = connector.connect(&host, tcp).await
+            .with_context(|| format!("TLS handshake with {} failed", host))?;
+        Self::over(stream, config)
+    }
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> CTraderClient<S> {
+    /// Session over an already open stream
+    pub fn over(stream: S, config: CTraderConfig) -> Result<Self> {
+        config.check_mode()?;
+        Ok(Self {
+            stream,
+            config,
+            next_msg_id: 0,
+            authenticated: false,
+            symbol_ids: HashMap::new(),
+            symbol_names: HashMap::new(),
+            quotes: HashMap::new(),
+            ticks: VecDeque::new(),
+            positions: HashMap::new(),
+        })
+    }
+
+    pub fn mode(&self) -> TradingMode {
+        self.config.mode
+    }
+
+    pub fn is_authenticated(&self) -> bool {
+        self.authenticated
+    }
+
+    /// Authenticate the application, then the trading account
+    pub async fn authenticate(&mut self) -> Result<()> {
+        let app_auth = Writer::default()
+            .string(2, &self.config.client_id)
+            .string(3, &self.config.client_secret)
+            .finish();
+        self.request(payload::APPLICATION_AUTH_REQ, app_auth, payload::APPLICATION_AUTH_RES).await
+            .context("cTrader application authentication failed")?;
+
+        let account_auth = Writer::default()
+            .int(2, self.config.account_id)
+            .string(3, &self.config.access_token)
+            .finish();
+        self.request(payload::ACCOUNT_AUTH_REQ, account_auth, payload::ACCOUNT_AUTH_RES).await
+            .with_context(|| format!("cTrader account {} authentication failed", self.config.account_id))?;
+        self.authenticated = true;
+        Ok(())
+    }
+
+    /// Load the account's enabled symbols, returning how many there are
+    pub async fn load_symbols(&mut self) -> Result<usize> {
+        self.ensure_authenticated()?;
+        let list = Writer::default().int(2, self.config.account_id).finish();
+        let response = self.request(payload::SYMBOLS_LIST_REQ, list, payload::SYMBOLS_LIST_RES).await?;
+        let fields = response.fields()?;
+        self.symbol_ids.clear();
+        self.symbol_names.clear();
+        for symbol in fields.repeated_bytes(3) {
+            let symbol = Fields::decode(symbol)?;
+            let (Some(id), Some(name)) = (symbol.int(1), symbol.string(2)?) else {
+                continue;
+            };
+            if symbol.bool(3) == Some(false) {
+                continue;
+            }
+            let name = name.replace('/', "").to_uppercase();
+            self.symbol_names.insert(id, name.clone());
+            self.symbol_ids.insert(name, id);
+        }
+        Ok(self.symbol_ids.len())
+    }
+
+    /// Open API id of `symbol`, after [`Self::load_symbols`]
+    pub fn symbol_id(&self, symbol: &str) -> Result<i64> {
+        self.symbol_ids.get(&symbol.to_uppercase()).copied()
+            .with_context(|| format!("Symbol {} is not available on cTrader account {}", symbol, self.config.account_id))
+    }
+
+    /// Stream spot quotes for `symbols`
+    pub async fn subscribe_spots(&mut self, symbols: &[String]) -> Result<()> {
+        self.ensure_authenticated()?;
+        let mut writer = Writer::default();
+        writer.int(2, self.config.account_id);
+        for symbol in symbols {
+            writer.int(3, self.symbol_id(symbol)?);
+        }
+        writer.bool(4, true);
+        self.request(payload::SUBSCRIBE_SPOTS_REQ, writer.finish(), payload::SUBSCRIBE_SPOTS_RES).await?;
+        Ok(())
+    }
+
+    /// Next quote of a subscribed symbol, once both its bid and ask are known
+    pub async fn next_tick(&mut self) -> Result<Tick> {
+        loop {
+            if let Some(tick) = self.ticks.pop_front() {
+                return Ok(tick);
+            }
+            let message = proto::read_frame(&mut self.stream).await?;
+            self.handle_unsolicited(&message)?;
+        }
+    }
+
+    /// Answer the server's keep-alive; sessions idle for 30s are dropped
+    pub async fn heartbeat(&mut self) -> Result<()> {
+        proto::write_frame(&mut self.stream, &ProtoMessage::new(payload::HEARTBEAT_EVENT, Vec::new())).await
+    }
+
+    /// Positions opened through this session on `symbol`
+    pub fn open_positions(&self, symbol: &str) -> &[OpenPosition] {
+        self.positions.get(&symbol.to_uppercase()).map_or(&[], Vec::as_slice)
+    }
+
+    /// Send a market order and wait for it to fill
+    pub async fn place_market_order(&mut self, order: &MarketOrder) -> Result<Fill> {
+        self.ensure_authenticated()?;
+        let volume = (order.units * VOLUME_SCALE).round() as i64;
+        if volume <= 0 {
+            bail!("Order for {} units of {} is below the minimum volume", order.units, order.symbol);
+        }
+        let mut writer = Writer::default();
+        writer.int(2, self.config.account_id)
+            .int(3, self.symbol_id(&order.symbol)?)
+            .uint(4, ORDER_TYPE_MARKET)
+            .uint(5, proto::trade_side(order.side == OrderSide::Buy))
+            .int(6, volume);
+        if let Some(label) = &order.label {
+            writer.string(16, label);
+        }
+        if let Some(distance) = order.stop_loss_distance {
+            writer.int(19, (distance * PRICE_SCALE).round() as i64);
+        }
+        if let Some(distance) = order.take_profit_distance {
+            writer.int(20, (distance * PRICE_SCALE).round() as i64);
+        }
+        let fill = self.await_fill(payload::NEW_ORDER_REQ, writer.finish(), &order.symbol, order.side).await?;
+        self.track(&fill);
+        Ok(fill)
+    }
+
+    /// Close one position opened through this session
+    pub async fn close_position(&mut self, symbol: &str, position_id: i64) -> Result<Fill> {
+        self.ensure_authenticated()?;
+        let symbol = symbol.to_uppercase();
+        let position = self.open_positions(&symbol).iter()
+            .find(|position| position.position_id == position_id)
+            .cloned()
+            .with_context(|| format!("No open {} position {} in this session", symbol, position_id))?;
+        let close = Writer::default()
+            .int(2, self.config.account_id)
+            .int(3, position.position_id)
+            .int(4, (position.units * VOLUME_SCALE).round() as i64)
+            .finish();
+        let side = match position.side {
+            OrderSide::Buy => OrderSide::Sell,
+            OrderSide::Sell => OrderSide::Buy,
+        };
+        let fill = self.await_fill(payload::CLOSE_POSITION_REQ, close, &symbol, side).await?;
+        if let Some(positions) = self.positions.get_mut(&symbol) {
+            positions.retain(|open| open.position_id != position_id);
+        }
+        Ok(fill)
+    }
+
+    /// Close every position opened through this session on `symbol`
+    pub async fn close_positions(&mut self, symbol: &str) -> Result<Vec<Fill>> {
+        let ids: Vec<i64> = self.open_positions(symbol).iter().map(|position| position.position_id).collect();
+        let mut fills = Vec::new();
+        for position_id in ids {
+            fills.push(self.close_position(symbol, position_id).await?);
+        }
+        Ok(fills)
+    }
+
+    /// Route a decided action: buys and sells become market orders, closes
+    /// flatten the symbol, holds send nothing
+    pub async fn execute_action(&mut self, symbol: &str, action: &TradingAction) -> Result<Vec<Fill>> {
+        match action {
+            TradingAction::Hold => Ok(Vec::new()),
+            TradingAction::ClosePosition => self.close_positions(symbol).await,
+            _ => {
+                let order = MarketOrder::from_action(symbol, action, self.config.units_per_size)
+                    .context("action opens no order")?;
+                Ok(vec![self.place_market_order(&order).await?])
+            }
+        }
+    }
+
+    fn ensure_authenticated(&self) -> Result<()> {
+        if !self.authenticated {
+            bail!("cTrader session is not authenticated; call authenticate() first");
+        }
+        Ok(())
+    }
+
+    /// Send `payload` and wait for the response of type `expected`
+    async fn request(&mut self, payload_type: u32, payload: Vec<u8>, expected: u32) -> Result<ProtoMessage> {
+        let id = self.send(payload_type, payload).await?;
+        let timeout = Duration::from_millis(self.config.request_timeout_ms);
+        tokio::time::timeout(timeout, async {
+            loop {
+                let message = self.response_to(&id).await?;
+                if message.payload_type == expected {
+                    return Ok(message);
+                }
+            }
+        }).await.with_context(|| format!("cTrader request {} timed out", payload_type))?
+    }
+
+    /// Send an order request and collect its deals until the order fills
+    async fn await_fill(&mut self, payload_type: u32, payload: Vec<u8>, symbol: &str, side: OrderSide) -> Result<Fill> {
+        let id = self.send(payload_type, payload).await?;
+        let timeout = Duration::from_millis(self.config.request_timeout_ms);
+        let (mut order_id, mut position_id, mut filled, mut notional) = (0, 0, 0.0, 0.0);
+        tokio::time::timeout(timeout, async {
+            loop {
+                let message = self.response_to(&id).await?;
+                if message.payload_type != payload::EXECUTION_EVENT {
+                    continue;
+                }
+                let event = message.fields()?;
+                let execution_type = event.uint(3).unwrap_or_default();
+                match execution_type {
+                    execution::ORDER_REJECTED | execution::ORDER_CANCELLED | execution::ORDER_EXPIRED => {
+                        let code = event.string(9)?.unwrap_or_else(|| format!("execution type {}", execution_type));
+                        bail!("cTrader order on {} was not filled: {}", symbol, code);
+                    }
+                    execution::ORDER_PARTIAL_FILL | execution::ORDER_FILLED => {
+                        if let Some(deal) = event.message(6)? {
+                            let units = deal.int(5).unwrap_or_default() as f64 / VOLUME_SCALE;
+                            order_id = deal.int(2).unwrap_or(order_id);
+                            position_id = deal.int(3).unwrap_or(position_id);
+                            filled += units;
+                            notional += units * deal.double(10).unwrap_or_default();
+                        }
+                        if execution_type == execution::ORDER_FILLED {
+                            return Ok(());
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }).await.with_context(|| format!("cTrader order on {} timed out", symbol))??;
+        Ok(Fill {
+            symbol: symbol.to_uppercase(),
+            order_id,
+            position_id,
+            side,
+            units: filled,
+            price: if filled > 0.0 { notional / filled } else { 0.0 },
+        })
+    }
+
+    async fn send(&mut self, payload_type: u32, payload: Vec<u8>) -> Result<String> {
+        self.next_msg_id += 1;
+        let id = format!("fpr-{}", self.next_msg_id);
+        let message = ProtoMessage { payload_type, payload, client_msg_id: Some(id.clone()) };
+        proto::write_frame(&mut self.stream, &message).await?;
+        Ok(id)
+    }
+
+    /// Next message answering request `id`, failing on its error responses
+    async fn response_to(&mut self, id: &str) -> Result<ProtoMessage> {
+        loop {
+            let message = proto::read_frame(&mut self.stream).await?;
+            if message.client_msg_id.as_deref() != Some(id) {
+                self.handle_unsolicited(&message)?;
+                continue;
+            }
+            match message.payload_type {
+                payload::ERROR_RES | payload::OA_ERROR_RES | payload::ORDER_ERROR_EVENT => bail!(error_text(&message)?),
+                _ => return Ok(message),
+            }
+        }
+    }
+
+    /// Keep quotes, drop heartbeats and events for other requests, fail on session errors
+    fn handle_unsolicited(&mut self, message: &ProtoMessage) -> Result<()> {
+        match message.payload_type {
+            payload::SPOT_EVENT => {
+                let fields = message.fields()?;
+                let Some(symbol_id) = fields.int(3) else {
+                    return Ok(());
+                };
+                let quote = self.quotes.entry(symbol_id).or_default();
+                if let Some(bid) = fields.uint(4) {
+                    quote.0 = Some(bid as f64 / PRICE_SCALE);
+                }
+                if let Some(ask) = fields.uint(5) {
+                    quote.1 = Some(ask as f64 / PRICE_SCALE);
+                }
+                let (Some(bid), Some(ask)) = *quote else {
+                    return Ok(());
+                };
+                let timestamp = fields.int(8)
+                    .and_then(|ms| Utc.timestamp_millis_opt(ms).single())
+                    .unwrap_or_else(Utc::now);
+                let symbol = self.symbol_names.get(&symbol_id).cloned().unwrap_or_else(|| symbol_id.to_string());
+                self.ticks.push_back(Tick { symbol, timestamp, bid, ask });
+                Ok(())
+            }
+            payload::ERROR_RES | payload::OA_ERROR_RES => bail!(error_text(message)?),
+            _ => Ok(()),
+        }
+    }
+
+    /// Net a fill into the session's positions
+    fn track(&mut self, fill: &Fill) {
+        let positions = self.positions.entry(fill.symbol.clone()).or_default();
+        match positions.iter_mut().position(|position| position.position_id == fill.position_id) {
+            None => positions.push(OpenPosition { position_id: fill.position_id, side: fill.side, units: fill.units }),
+            Some(index) => {
+                let position = &mut positions[index];
+                if position.side == fill.side {
+                    position.units += fill.units;
+                } else if fill.units < position.units {
+                    position.units -= fill.units;
+                } else if fill.units > position.units {
+                    *position = OpenPosition { position_id: fill.position_id, side: fill.side, units: fill.units - position.units };
+                } else {
+                    positions.remove(index);
+                }
+            }
+        }
+    }
+}
+
+/// A session shared between callers, one order at a time
+impl<S: AsyncRead + AsyncWrite + Unpin + Send> ExecutionBackend for tokio::sync::Mutex<CTraderClient<S>> {
+    fn name(&self) -> &str {
+        "ctrader"
+    }
+
+    fn execute<'a>(&'a self, order: &'a MarketOrder) -> BoxFuture<'a, Result<Fill>> {
+        Box::pin(async move { self.lock().await.place_market_order(order).await })
+    }
+}
+
+/// "CODE: description" of an error response
+fn error_text(message: &ProtoMessage) -> Result<String> {
+    let fields = message.fields()?;
+    let (code, description) = match message.payload_type {
+        payload::ERROR_RES => (fields.string(2)?, fields.string(3)?),
+        payload::OA_ERROR_RES => (fields.string(3)?, fields.string(4)?),
+        _ => (fields.string(2)?, fields.string(7)?),
+    };
+    Ok(format!(
+        "cTrader error {}: {}",
+        code.unwrap_or_else(|| "UNKNOWN".to_string()),
+        description.unwrap_or_default()
+    ))
+}
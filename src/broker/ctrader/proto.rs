@@ -0,0 +1,301 @@
+//! # cTrader Open API Wire Format
+//!
+//! The Open API frames every message as a 4-byte big-endian length followed by
+//! a `ProtoMessage` (payload type, payload bytes, client message id). Only the
+//! handful of messages the client sends or reads are encoded here, field by
+//! field, rather than generated from the `.proto` files.
+
+use anyhow::{bail, Context, Result};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// Largest frame accepted from the server; symbol lists run to a few hundred kB
+pub const MAX_FRAME_BYTES: usize = 16 * 1024 * 1024;
+
+/// Open API prices are integers in 1/100000 of the quote currency
+pub const PRICE_SCALE: f64 = 100_000.0;
+
+/// Open API volumes are integers in 1/100 of a unit
+pub const VOLUME_SCALE: f64 = 100.0;
+
+/// `payloadType` of the messages the client uses
+pub mod payload {
+    pub const ERROR_RES: u32 = 50;
+    pub const HEARTBEAT_EVENT: u32 = 51;
+    pub const APPLICATION_AUTH_REQ: u32 = 2100;
+    pub const APPLICATION_AUTH_RES: u32 = 2101;
+    pub const ACCOUNT_AUTH_REQ: u32 = 2102;
+    pub const ACCOUNT_AUTH_RES: u32 = 2103;
+    pub const NEW_ORDER_REQ: u32 = 2106;
+    pub const CLOSE_POSITION_REQ: u32 = 2111;
+    pub const SYMBOLS_LIST_REQ: u32 = 2114;
+    pub const SYMBOLS_LIST_RES: u32 = 2115;
+    pub const EXECUTION_EVENT: u32 = 2126;
+    pub const SUBSCRIBE_SPOTS_REQ: u32 = 2127;
+    pub const SUBSCRIBE_SPOTS_RES: u32 = 2128;
+    pub const SPOT_EVENT: u32 = 2131;
+    pub const ORDER_ERROR_EVENT: u32 = 2132;
+    pub const OA_ERROR_RES: u32 = 2142;
+}
+
+/// `ProtoOAOrderType.MARKET`
+pub const ORDER_TYPE_MARKET: u64 = 1;
+
+/// `ProtoOAExecutionType` values the client acts on
+pub mod execution {
+    pub const ORDER_ACCEPTED: u64 = 2;
+    pub const ORDER_FILLED: u64 = 3;
+    pub const ORDER_CANCELLED: u64 = 5;
+    pub const ORDER_EXPIRED: u64 = 6;
+    pub const ORDER_REJECTED: u64 = 7;
+    pub const ORDER_PARTIAL_FILL: u64 = 11;
+}
+
+/// `ProtoOATradeSide`: BUY = 1, SELL = 2
+pub fn trade_side(buy: bool) -> u64 {
+    if buy { 1 } else { 2 }
+}
+
+/// The envelope of every frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProtoMessage {
+    pub payload_type: u32,
+    pub payload: Vec<u8>,
+    /// Echoed by the server on the responses to a request
+    pub client_msg_id: Option<String>,
+}
+
+impl ProtoMessage {
+    pub fn new(payload_type: u32, payload: Vec<u8>) -> Self {
+        Self { payload_type, payload, client_msg_id: None }
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut writer = Writer::default();
+        writer.uint(1, self.payload_type as u64);
+        writer.bytes(2, &self.payload);
+        if let Some(id) = &self.client_msg_id {
+            writer.string(3, id);
+        }
+        writer.finish()
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        let fields = Fields::decode(bytes)?;
+        Ok(Self {
+            payload_type: fields.uint(1).context("ProtoMessage without payloadType")? as u32,
+            payload: fields.bytes(2).unwrap_or_default().to_vec(),
+            client_msg_id: fields.string(3)?,
+        })
+    }
+
+    /// Fields of the payload
+    pub fn fields(&self) -> Result<Fields<'_>> {
+        Fields::decode(&self.payload)
+    }
+}
+
+/// Write one length-prefixed frame
+pub async fn write_frame<W: AsyncWrite + Unpin>(stream: &mut W, message: &ProtoMessage) -> Result<()> {
+    let bytes = message.encode();
+    stream.write_all(&(bytes.len() as u32).to_be_bytes()).await?;
+    stream.write_all(&bytes).await?;
+    stream.flush().await?;
+    Ok(())
+}
+
+/// Read one length-prefixed frame
+pub async fn read_frame<R: AsyncRead + Unpin>(stream: &mut R) -> Result<ProtoMessage> {
+    let mut length = [0u8; 4];
+    stream.read_exact(&mut length).await.context("cTrader connection closed")?;
+    let length = u32::from_be_bytes(length) as usize;
+    if length > MAX_FRAME_BYTES {
+        bail!("cTrader frame of {} bytes exceeds the {} byte limit", length, MAX_FRAME_BYTES);
+    }
+    let mut bytes = vec![0u8; length];
+    stream.read_exact(&mut bytes).await.context("cTrader connection closed mid-frame")?;
+    ProtoMessage::decode(&bytes)
+}
+
+/// Protobuf encoder for the scalar and length-delimited fields the messages use
+#[derive(Debug, Default)]
+pub struct Writer {
+    buf: Vec<u8>,
+}
+
+impl Writer {
+    pub fn uint(&mut self, field: u32, value: u64) -> &mut Self {
+        self.key(field, 0);
+        self.varint(value);
+        self
+    }
+
+    /// `int64` fields carry negatives as ten-byte varints
+    pub fn int(&mut self, field: u32, value: i64) -> &mut Self {
+        self.uint(field, value as u64)
+    }
+
+    pub fn bool(&mut self, field: u32, value: bool) -> &mut Self {
+        self.uint(field, value as u64)
+    }
+
+    pub fn double(&mut self, field: u32, value: f64) -> &mut Self {
+        self.key(field, 1);
+        self.buf.extend_from_slice(&value.to_le_bytes());
+        self
+    }
+
+    pub fn bytes(&mut self, field: u32, value: &[u8]) -> &mut Self {
+        self.key(field, 2);
+        self.varint(value.len() as u64);
+        self.buf.extend_from_slice(value);
+        self
+    }
+
+    pub fn string(&mut self, field: u32, value: &str) -> &mut Self {
+        self.bytes(field, value.as_bytes())
+    }
+
+    pub fn finish(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.buf)
+    }
+
+    fn key(&mut self, field: u32, wire_type: u8) {
+        self.varint(((field as u64) << 3) | wire_type as u64);
+    }
+
+    fn varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.buf.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.buf.push(value as u8);
+    }
+}
+
+/// One decoded field value
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Value<'a> {
+    Varint(u64),
+    Fixed64(u64),
+    Bytes(&'a [u8]),
+    Fixed32(u32),
+}
+
+/// The fields of one message, in wire order; unknown fields are kept and ignored
+#[derive(Debug, Clone, Default)]
+pub struct Fields<'a> {
+    fields: Vec<(u32, Value<'a>)>,
+}
+
+impl<'a> Fields<'a> {
+    pub fn decode(bytes: &'a [u8]) -> Result<Self> {
+        let mut fields = Vec::new();
+        let mut pos = 0;
+        while pos < bytes.len() {
+            let key = read_varint(bytes, &mut pos)?;
+            let field = (key >> 3) as u32;
+            let value = match key & 7 {
+                0 => Value::Varint(read_varint(bytes, &mut pos)?),
+                1 => Value::Fixed64(u64::from_le_bytes(take(bytes, &mut pos, 8)?.try_into()?)),
+                2 => {
+                    let length = read_varint(bytes, &mut pos)? as usize;
+                    Value::Bytes(take(bytes, &mut pos, length)?)
+                }
+                5 => Value::Fixed32(u32::from_le_bytes(take(bytes, &mut pos, 4)?.try_into()?)),
+                other => bail!("Unsupported protobuf wire type {} for field {}", other, field),
+            };
+            fields.push((field, value));
+        }
+        Ok(Self { fields })
+    }
+
+    /// Last value of a varint field, as protobuf merges repeated scalars
+    pub fn uint(&self, field: u32) -> Option<u64> {
+        self.values(field).filter_map(|value| match value {
+            Value::Varint(v) => Some(v),
+            _ => None,
+        }).last()
+    }
+
+    pub fn int(&self, field: u32) -> Option<i64> {
+        self.uint(field).map(|value| value as i64)
+    }
+
+    pub fn bool(&self, field: u32) -> Option<bool> {
+        self.uint(field).map(|value| value != 0)
+    }
+
+    pub fn double(&self, field: u32) -> Option<f64> {
+        self.values(field).filter_map(|value| match value {
+            Value::Fixed64(bits) => Some(f64::from_bits(bits)),
+            _ => None,
+        }).last()
+    }
+
+    pub fn bytes(&self, field: u32) -> Option<&'a [u8]> {
+        self.repeated_bytes(field).last()
+    }
+
+    pub fn string(&self, field: u32) -> Result<Option<String>> {
+        self.bytes(field)
+            .map(|bytes| String::from_utf8(bytes.to_vec()).with_context(|| format!("field {} is not UTF-8", field)))
+            .transpose()
+    }
+
+    /// Every occurrence of a repeated message or string field
+    pub fn repeated_bytes(&self, field: u32) -> impl Iterator<Item = &'a [u8]> + '_ {
+        self.values(field).filter_map(|value| match value {
+            Value::Bytes(bytes) => Some(bytes),
+            _ => None,
+        })
+    }
+
+    /// Every value of a repeated varint field, packed or not
+    pub fn repeated_uint(&self, field: u32) -> Result<Vec<u64>> {
+        let mut values = Vec::new();
+        for value in self.values(field) {
+            match value {
+                Value::Varint(v) => values.push(v),
+                Value::Bytes(packed) => {
+                    let mut pos = 0;
+                    while pos < packed.len() {
+                        values.push(read_varint(packed, &mut pos)?);
+                    }
+                }
+                _ => bail!("field {} is not a varint", field),
+            }
+        }
+        Ok(values)
+    }
+
+    /// Decode an embedded message field
+    pub fn message(&self, field: u32) -> Result<Option<Fields<'a>>> {
+        self.bytes(field).map(Fields::decode).transpose()
+    }
+
+    fn values(&self, field: u32) -> impl Iterator<Item = Value<'a>> + '_ {
+        self.fields.iter().filter(move |(f, _)| *f == field).map(|(_, value)| *value)
+    }
+}
+
+fn read_varint(bytes: &[u8], pos: &mut usize) -> Result<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let Some(&byte) = bytes.get(*pos) else {
+            bail!("Truncated protobuf varint");
+        };
+        *pos += 1;
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    bail!("Protobuf varint longer than 10 bytes")
+}
+
+fn take<'a>(bytes: &'a [u8], pos: &mut usize, length: usize) -> Result<&'a [u8]> {
+    let end = pos.checked_add(length).filter(|end| *end <= bytes.len()).context("Truncated protobuf field")?;
+    let slice = &bytes[*pos..end];
+    *pos = end;
+    Ok(slice)
+}
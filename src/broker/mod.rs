@@ -0,0 +1,148 @@
+//! # Broker Connectivity
+//!
+//! Where decided orders are filled: an [`ExecutionBackend`] takes market orders
+//! and returns fills, simulated by [`paper::PaperBroker`] or routed through
+//! [`ctrader::CTraderClient`]. Live accounts stay locked unless allowed.
+
+pub mod ctrader;
+pub mod paper;
+
+use anyhow::{bail, Result};
+use futures_util::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::backtest::strategy::OrderSide;
+use crate::data::provider::Tick;
+use crate::laplacian_rl::TradingAction;
+
+/// Where orders are filled, simulated or real
+pub trait ExecutionBackend: Send + Sync {
+    /// Short name used in logs
+    fn name(&self) -> &str;
+
+    /// Latest quote of a pair; backends quoting from their own feed ignore it
+    fn observe_quote(&self, _tick: &Tick) {}
+
+    /// Fill `order`; fewer units than ordered is a partial fill, none an unfilled order
+    fn execute<'a>(&'a self, order: &'a MarketOrder) -> BoxFuture<'a, Result<Fill>>;
+}
+
+/// Build the backend selected by `EXECUTION_BACKEND` ("paper" or "ctrader"), if any
+pub async fn backend_from_env() -> Result<Option<Arc<dyn ExecutionBackend>>> {
+    let Ok(name) = std::env::var("EXECUTION_BACKEND").map(|name| name.to_lowercase()) else {
+        return Ok(None);
+    };
+    match name.trim() {
+        "paper" => Ok(Some(Arc::new(paper::PaperBroker::new(paper::PaperBrokerConfig::from_env()?)))),
+        "ctrader" => {
+            let mut client = ctrader::CTraderClient::connect(ctrader::CTraderConfig::from_env()?).await?;
+            client.authenticate().await?;
+            client.load_symbols().await?;
+            Ok(Some(Arc::new(tokio::sync::Mutex::new(client))))
+        }
+        other => bail!("Unknown EXECUTION_BACKEND '{}' (expected paper or ctrader)", other),
+    }
+}
+
+/// Which of a broker's environments orders go to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TradingMode {
+    /// Practice account, no real money
+    #[default]
+    Demo,
+    /// Real-money account; only reachable when live trading is allowed
+    Live,
+}
+
+impl FromStr for TradingMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "demo" => Ok(TradingMode::Demo),
+            "live" => Ok(TradingMode::Live),
+            other => bail!("Unknown trading mode '{}' (expected demo or live)", other),
+        }
+    }
+}
+
+impl fmt::Display for TradingMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TradingMode::Demo => write!(f, "DEMO"),
+            TradingMode::Live => write!(f, "LIVE"),
+        }
+    }
+}
+
+/// Market order as sent to a broker
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarketOrder {
+    pub symbol: String,
+    pub side: OrderSide,
+    /// Units of the base currency
+    pub units: f64,
+    /// Stop loss as a price distance from the fill
+    pub stop_loss_distance: Option<f64>,
+    /// Take profit as a price distance from the fill
+    pub take_profit_distance: Option<f64>,
+    /// Shown on the broker's side of the order, e.g. the anomaly behind it
+    pub label: Option<String>,
+}
+
+impl MarketOrder {
+    pub fn new(symbol: &str, side: OrderSide, units: f64) -> Self {
+        Self {
+            symbol: symbol.to_uppercase(),
+            side,
+            units,
+            stop_loss_distance: None,
+            take_profit_distance: None,
+            label: None,
+        }
+    }
+
+    /// The order opening `action` on `symbol`, `None` for holds and closes
+    pub fn from_action(symbol: &str, action: &TradingAction, units_per_size: f64) -> Option<Self> {
+        match action {
+            TradingAction::Buy { size } => Some(Self::new(symbol, OrderSide::Buy, *size as f64 * units_per_size)),
+            TradingAction::Sell { size } => Some(Self::new(symbol, OrderSide::Sell, *size as f64 * units_per_size)),
+            TradingAction::Hold | TradingAction::ClosePosition => None,
+        }
+    }
+
+    pub fn with_protection(mut self, stop_loss_distance: Option<f64>, take_profit_distance: Option<f64>) -> Self {
+        self.stop_loss_distance = stop_loss_distance;
+        self.take_profit_distance = take_profit_distance;
+        self
+    }
+
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+}
+
+/// An order filled by the broker
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Fill {
+    pub symbol: String,
+    pub order_id: i64,
+    /// Position the fill opened, added to or closed
+    pub position_id: i64,
+    pub side: OrderSide,
+    pub units: f64,
+    /// Volume-weighted price over the order's partial fills
+    pub price: f64,
+}
+
+/// A position the broker holds for orders placed through it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpenPosition {
+    pub position_id: i64,
+    pub side: OrderSide,
+    pub units: f64,
+}
@@ -0,0 +1,203 @@
+//! # Paper Broker
+//!
+//! Fills orders against the latest quote of each pair after the configured
+//! latency, buys at the ask and sells at the bid, with partial fills above the
+//! liquidity cap. Nothing is sent anywhere.
+
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use futures_util::future::BoxFuture;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+use super::{ExecutionBackend, Fill, MarketOrder};
+use crate::backtest::strategy::OrderSide;
+use crate::data::provider::Tick;
+use crate::units::Pips;
+
+/// Simulated execution conditions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PaperBrokerConfig {
+    /// Delay between an order and the quote it fills at
+    pub latency_ms: u64,
+    /// Extra delay drawn uniformly up to this
+    pub latency_jitter_ms: u64,
+    /// Spread around the mid instead of the quote's own bid and ask
+    pub spread_pips: Option<f64>,
+    /// Units one order fills at most; the rest is cancelled
+    pub max_fill_units: Option<f64>,
+    /// Chance an order fills only in part
+    pub partial_fill_probability: f64,
+    /// Smallest share of the order a partial fill takes
+    pub min_fill_fraction: f64,
+    /// Orders against quotes older than this fail. `None` accepts any age
+    pub max_quote_age_ms: Option<u64>,
+    /// Seed of the latency and partial-fill draws
+    pub seed: u64,
+}
+
+impl Default for PaperBrokerConfig {
+    fn default() -> Self {
+        Self {
+            latency_ms: 0,
+            latency_jitter_ms: 0,
+            spread_pips: None,
+            max_fill_units: None,
+            partial_fill_probability: 0.0,
+            min_fill_fraction: 0.5,
+            max_quote_age_ms: None,
+            seed: 0,
+        }
+    }
+}
+
+impl PaperBrokerConfig {
+    /// Read optional `PAPER_LATENCY_MS`, `PAPER_SPREAD_PIPS` and `PAPER_PARTIAL_FILL_PROBABILITY`
+    pub fn from_env() -> Result<Self> {
+        let mut config = Self::default();
+        if let Ok(latency) = std::env::var("PAPER_LATENCY_MS") {
+            config.latency_ms = latency.parse().context("PAPER_LATENCY_MS is not a number")?;
+        }
+        if let Ok(spread) = std::env::var("PAPER_SPREAD_PIPS") {
+            config.spread_pips = Some(spread.parse().context("PAPER_SPREAD_PIPS is not a number")?);
+        }
+        if let Ok(probability) = std::env::var("PAPER_PARTIAL_FILL_PROBABILITY") {
+            config.partial_fill_probability = probability.parse().context("PAPER_PARTIAL_FILL_PROBABILITY is not a number")?;
+        }
+        Ok(config)
+    }
+}
+
+/// Execution simulator filling against the latest quotes it was given
+pub struct PaperBroker {
+    config: PaperBrokerConfig,
+    quotes: Mutex<HashMap<String, Tick>>,
+    rng: Mutex<StdRng>,
+    next_order_id: AtomicI64,
+    fills: Mutex<Vec<Fill>>,
+}
+
+impl PaperBroker {
+    pub fn new(config: PaperBrokerConfig) -> Self {
+        Self {
+            rng: Mutex::new(StdRng::seed_from_u64(config.seed)),
+            config,
+            quotes: Mutex::new(HashMap::new()),
+            next_order_id: AtomicI64::new(1),
+            fills: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn config(&self) -> &PaperBrokerConfig {
+        &self.config
+    }
+
+    /// Fill later orders on `tick.symbol` at this quote
+    pub fn update_quote(&self, tick: Tick) {
+        self.quotes.lock().unwrap().insert(tick.symbol.to_uppercase(), tick);
+    }
+
+    pub fn quote(&self, symbol: &str) -> Option<Tick> {
+        self.quotes.lock().unwrap().get(&symbol.to_uppercase()).cloned()
+    }
+
+    /// Keep quoting from `ticks` until the stream ends
+    pub fn follow(self: &Arc<Self>, mut ticks: mpsc::Receiver<Tick>) -> tokio::task::JoinHandle<()> {
+        let broker = Arc::clone(self);
+        tokio::spawn(async move {
+            while let Some(tick) = ticks.recv().await {
+                broker.update_quote(tick);
+            }
+        })
+    }
+
+    /// Every fill so far, oldest first
+    pub fn fills(&self) -> Vec<Fill> {
+        self.fills.lock().unwrap().clone()
+    }
+
+    fn latency(&self) -> Duration {
+        let jitter = match self.config.latency_jitter_ms {
+            0 => 0,
+            max => self.rng.lock().unwrap().gen_range(0..=max),
+        };
+        Duration::from_millis(self.config.latency_ms + jitter)
+    }
+
+    /// Ask for buys and bid for sells, or the configured spread around the mid
+    fn fill_price(&self, quote: &Tick, side: OrderSide) -> f64 {
+        let (bid, ask) = match self.config.spread_pips {
+            Some(pips) => {
+                let half = Pips(pips / 2.0).to_price(&quote.symbol).0;
+                (quote.mid() - half, quote.mid() + half)
+            }
+            None => (quote.bid, quote.ask),
+        };
+        match side {
+            OrderSide::Buy => ask,
+            OrderSide::Sell => bid,
+        }
+    }
+
+    /// Units filled of an order for `units`, after the liquidity cap and partial-fill draw
+    fn fill_units(&self, units: f64) -> f64 {
+        let capped = self.config.max_fill_units.map_or(units, |cap| units.min(cap));
+        let mut rng = self.rng.lock().unwrap();
+        if self.config.partial_fill_probability > 0.0 && rng.gen_bool(self.config.partial_fill_probability.min(1.0)) {
+            let fraction = rng.gen_range(self.config.min_fill_fraction.clamp(0.0, 1.0)..=1.0);
+            (capped * fraction).round()
+        } else {
+            capped
+        }
+    }
+
+    async fn fill(&self, order: &MarketOrder) -> Result<Fill> {
+        if order.units <= 0.0 {
+            bail!("Order for {} units of {} has nothing to fill", order.units, order.symbol);
+        }
+        let latency = self.latency();
+        if !latency.is_zero() {
+            tokio::time::sleep(latency).await;
+        }
+        let quote = self.quote(&order.symbol)
+            .with_context(|| format!("Paper broker has no quote for {}", order.symbol))?;
+        if let Some(max_age) = self.config.max_quote_age_ms {
+            let age = (Utc::now() - quote.timestamp).num_milliseconds();
+            if age > max_age as i64 {
+                bail!("{} quote is {}ms old, over the {}ms limit", order.symbol, age, max_age);
+            }
+        }
+        let order_id = self.next_order_id.fetch_add(1, Ordering::Relaxed);
+        let fill = Fill {
+            symbol: order.symbol.to_uppercase(),
+            order_id,
+            position_id: order_id,
+            side: order.side,
+            units: self.fill_units(order.units),
+            price: self.fill_price(&quote, order.side),
+        };
+        self.fills.lock().unwrap().push(fill.clone());
+        Ok(fill)
+    }
+}
+
+impl ExecutionBackend for PaperBroker {
+    fn name(&self) -> &str {
+        "paper"
+    }
+
+    fn observe_quote(&self, tick: &Tick) {
+        self.update_quote(tick.clone());
+    }
+
+    fn execute<'a>(&'a self, order: &'a MarketOrder) -> BoxFuture<'a, Result<Fill>> {
+        Box::pin(self.fill(order))
+    }
+}
@@ -0,0 +1,298 @@
+//! # Economic Calendar
+//!
+//! Scheduled releases (payrolls, rate decisions, CPI) loaded from CSV or JSON
+//! feeds such as ForexFactory exports. The anomaly detector lists nearby events
+//! in market contexts and trusts price shocks less close to a release.
+
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use csv::ReaderBuilder;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::portfolio::split_symbol;
+
+/// Prefix of the market-context event attached to anomalies near a release
+pub const ECONOMIC_EVENT: &str = "Economic event";
+
+/// Expected market impact of a release
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum EventImpact {
+    Low,
+    Medium,
+    High,
+}
+
+impl EventImpact {
+    /// Feed impact label; holidays, speeches without a label and anything
+    /// unrecognized count as low impact
+    pub fn parse(label: &str) -> Self {
+        match label.trim().to_lowercase().as_str() {
+            "high" | "3" | "red" => EventImpact::High,
+            "medium" | "med" | "moderate" | "2" | "orange" => EventImpact::Medium,
+            _ => EventImpact::Low,
+        }
+    }
+
+    /// Share of full event proximity a release of this impact can reach
+    pub fn weight(&self) -> f64 {
+        match self {
+            EventImpact::Low => 0.25,
+            EventImpact::Medium => 0.5,
+            EventImpact::High => 1.0,
+        }
+    }
+}
+
+/// One scheduled release
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EconomicEvent {
+    pub timestamp: DateTime<Utc>,
+    /// ISO code of the currency the release moves
+    pub currency: String,
+    pub name: String,
+    pub impact: EventImpact,
+    pub forecast: Option<String>,
+    pub previous: Option<String>,
+    pub actual: Option<String>,
+}
+
+impl EconomicEvent {
+    /// "USD Non-Farm Employment Change (High, 13:30)"
+    pub fn label(&self) -> String {
+        format!("{} {} ({:?}, {})", self.currency, self.name, self.impact, self.timestamp.format("%H:%M"))
+    }
+}
+
+/// Economic calendar configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EconomicCalendarConfig {
+    /// CSV or JSON events feed; no calendar when unset
+    pub feed: Option<PathBuf>,
+
+    /// Releases this long before a bar are listed in its market context
+    pub lookback_minutes: i64,
+
+    /// Releases this long after a bar are listed in its market context
+    pub lookahead_minutes: i64,
+
+    /// Releases below this impact are ignored
+    pub min_impact: EventImpact,
+
+    /// Event proximity falls linearly from 1 at the release to 0 this far away
+    pub proximity_minutes: i64,
+
+    /// Confidence cut for price shocks at full event proximity
+    pub news_discount: f64,
+}
+
+impl Default for EconomicCalendarConfig {
+    fn default() -> Self {
+        Self {
+            feed: None,
+            lookback_minutes: 60,
+            lookahead_minutes: 60,
+            min_impact: EventImpact::Medium,
+            proximity_minutes: 120,
+            news_discount: 0.5,
+        }
+    }
+}
+
+/// Scheduled releases sorted by time, shared cheaply between detectors
+#[derive(Debug, Clone, Default)]
+pub struct EconomicCalendar {
+    events: Arc<Vec<EconomicEvent>>,
+    pub config: EconomicCalendarConfig,
+}
+
+impl EconomicCalendar {
+    pub fn new(mut events: Vec<EconomicEvent>, config: EconomicCalendarConfig) -> Self {
+        events.sort_by_key(|event| event.timestamp);
+        Self { events: Arc::new(events), config }
+    }
+
+    /// Calendar of the configured feed, or an empty one when none is set
+    pub fn from_config(config: &EconomicCalendarConfig) -> Result<Self> {
+        match &config.feed {
+            Some(path) => Ok(Self::new(load_events(path)?, config.clone())),
+            None => Ok(Self::new(Vec::new(), config.clone())),
+        }
+    }
+
+    pub fn events(&self) -> &[EconomicEvent] {
+        &self.events
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Releases from `start` to `end` inclusive, in time order
+    pub fn events_between(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> &[EconomicEvent] {
+        let from = self.events.partition_point(|event| event.timestamp < start);
+        let to = self.events.partition_point(|event| event.timestamp <= end);
+        &self.events[from..to.max(from)]
+    }
+
+    /// Releases of `pair`'s currencies near `timestamp` at or above the minimum impact
+    pub fn events_near(&self, pair: &str, timestamp: DateTime<Utc>, before: Duration, after: Duration) -> Vec<&EconomicEvent> {
+        let (base, quote) = split_symbol(pair);
+        self.events_between(timestamp - before, timestamp + after)
+            .iter()
+            .filter(|event| event.impact >= self.config.min_impact)
+            .filter(|event| event.currency == base || event.currency == quote)
+            .collect()
+    }
+
+    /// Market-context labels of the releases around the bar at `timestamp`
+    pub fn recent_events(&self, pair: &str, timestamp: DateTime<Utc>) -> Vec<String> {
+        self.events_near(pair, timestamp,
+                         Duration::minutes(self.config.lookback_minutes),
+                         Duration::minutes(self.config.lookahead_minutes))
+            .into_iter()
+            .map(|event| format!("{}: {}", ECONOMIC_EVENT, event.label()))
+            .collect()
+    }
+
+    /// How close `timestamp` sits to a release of `pair`'s currencies, in [0, 1]:
+    /// the impact weight of the nearest-weighted release, decaying linearly to
+    /// zero `proximity_minutes` away on either side
+    pub fn proximity(&self, pair: &str, timestamp: DateTime<Utc>) -> f64 {
+        let window = Duration::minutes(self.config.proximity_minutes.max(1));
+        let seconds = window.num_seconds() as f64;
+        self.events_near(pair, timestamp, window, window)
+            .into_iter()
+            .map(|event| {
+                let distance = (event.timestamp - timestamp).num_seconds().abs() as f64;
+                event.impact.weight() * (1.0 - distance / seconds).max(0.0)
+            })
+            .fold(0.0, f64::max)
+    }
+}
+
+/// Read a feed, choosing the parser by extension: `.json` is JSON, anything else CSV
+pub fn load_events(path: &Path) -> Result<Vec<EconomicEvent>> {
+    let is_json = path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+    if is_json {
+        let text = std::fs::read_to_string(path).with_context(|| format!("cannot open events feed {}", path.display()))?;
+        parse_json_events(&text).with_context(|| format!("{}", path.display()))
+    } else {
+        let reader = ReaderBuilder::new()
+            .flexible(true)
+            .from_path(path)
+            .with_context(|| format!("cannot open events feed {}", path.display()))?;
+        parse_csv_events(reader).with_context(|| format!("{}", path.display()))
+    }
+}
+
+/// Events of a headered CSV feed; rows without a usable time or currency are skipped.
+/// Columns: a time (`timestamp`, `datetime`, or `date` with an optional `time`),
+/// `currency` or `country`, `event`, `title` or `name`, and optionally `impact`,
+/// `forecast`, `previous` and `actual`, as in ForexFactory exports; times are UTC.
+pub fn parse_csv_events<R: std::io::Read>(mut reader: csv::Reader<R>) -> Result<Vec<EconomicEvent>> {
+    let header = reader.headers()?.clone();
+    let find = |names: &[&str]| header.iter().position(|field| names.iter().any(|name| field.trim().eq_ignore_ascii_case(name)));
+    let timestamp = find(&["timestamp", "datetime", "date_time"]);
+    let date = find(&["date"]);
+    let time = find(&["time"]);
+    if timestamp.is_none() && date.is_none() && time.is_none() {
+        bail!("unrecognized events header: no timestamp or date column");
+    }
+    let currency = find(&["currency", "country", "ccy"]).ok_or_else(|| anyhow!("unrecognized events header: no currency column"))?;
+    let name = find(&["event", "title", "name"]).ok_or_else(|| anyhow!("unrecognized events header: no event column"))?;
+    let impact = find(&["impact", "importance"]);
+    let forecast = find(&["forecast"]);
+    let previous = find(&["previous"]);
+    let actual = find(&["actual"]);
+
+    let mut events = Vec::new();
+    for record in reader.records() {
+        let record = record?;
+        let field = |column: Option<usize>| column.and_then(|i| record.get(i)).map(str::trim).filter(|value| !value.is_empty());
+        let when = match timestamp.or(if date.is_none() { time } else { None }) {
+            Some(column) => field(Some(column)).and_then(parse_timestamp),
+            None => field(date).and_then(|date| parse_date_time(date, field(time))),
+        };
+        let (Some(when), Some(currency), Some(name)) = (when, field(Some(currency)), field(Some(name))) else {
+            continue;
+        };
+        events.push(EconomicEvent {
+            timestamp: when,
+            currency: currency.to_uppercase(),
+            name: name.to_string(),
+            impact: field(impact).map_or(EventImpact::Low, EventImpact::parse),
+            forecast: field(forecast).map(str::to_string),
+            previous: field(previous).map(str::to_string),
+            actual: field(actual).map(str::to_string),
+        });
+    }
+    Ok(events)
+}
+
+/// Events of a JSON array feed with the CSV fields, whose dates carry their UTC offset;
+/// entries without a usable date are skipped
+pub fn parse_json_events(text: &str) -> Result<Vec<EconomicEvent>> {
+    let entries: Vec<serde_json::Map<String, serde_json::Value>> = serde_json::from_str(text)?;
+    Ok(entries.iter().filter_map(|entry| {
+        let field = |names: &[&str]| names.iter()
+            .find_map(|name| entry.get(*name))
+            .and_then(|value| match value {
+                serde_json::Value::String(text) => Some(text.trim().to_string()),
+                serde_json::Value::Number(number) => Some(number.to_string()),
+                _ => None,
+            })
+            .filter(|value| !value.is_empty());
+        let timestamp = match field(&["timestamp", "datetime"]) {
+            Some(value) => parse_timestamp(&value)?,
+            None => parse_date_time(&field(&["date"])?, field(&["time"]).as_deref())?,
+        };
+        Some(EconomicEvent {
+            timestamp,
+            currency: field(&["currency", "country"])?.to_uppercase(),
+            name: field(&["event", "title", "name"])?,
+            impact: field(&["impact"]).map_or(EventImpact::Low, |impact| EventImpact::parse(&impact)),
+            forecast: field(&["forecast"]),
+            previous: field(&["previous"]),
+            actual: field(&["actual"]),
+        })
+    }).collect())
+}
+
+/// RFC 3339 with offset, ISO-like in UTC, or epoch seconds
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Some(time.with_timezone(&Utc));
+    }
+    for format in ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M"] {
+        if let Ok(time) = NaiveDateTime::parse_from_str(value, format) {
+            return Some(time.and_utc());
+        }
+    }
+    if let Ok(epoch) = value.parse::<i64>() {
+        return DateTime::from_timestamp(epoch, 0);
+    }
+    parse_date_time(value, None)
+}
+
+/// A date with an optional time of day, in UTC. Times ForexFactory gives as
+/// "All Day" or "Tentative" fall at midnight.
+fn parse_date_time(date: &str, time: Option<&str>) -> Option<DateTime<Utc>> {
+    if time.is_none() {
+        if let Ok(time) = DateTime::parse_from_rfc3339(date) {
+            return Some(time.with_timezone(&Utc));
+        }
+    }
+    let date = ["%Y-%m-%d", "%m-%d-%Y", "%m/%d/%Y", "%Y.%m.%d", "%d.%m.%Y"].iter()
+        .find_map(|format| NaiveDate::parse_from_str(date, format).ok())?;
+    let time = time
+        .and_then(|time| {
+            let time = time.trim().to_uppercase();
+            ["%H:%M", "%H:%M:%S", "%I:%M%p", "%I:%M %p", "%I%p"].iter()
+                .find_map(|format| NaiveTime::parse_from_str(&time, format).ok())
+        })
+        .unwrap_or(NaiveTime::MIN);
+    Some(date.and_time(time).and_utc())
+}
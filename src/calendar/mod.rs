@@ -0,0 +1,213 @@
+//! # Holiday Calendar
+//!
+//! Per-currency bank holidays and the year-end lull, whose thin markets the
+//! detector, backtester and reports skip, down-weight or annotate so they are not
+//! read as yearly symmetries. Scheduled releases live in [`economic`].
+
+pub mod economic;
+
+use anyhow::Result;
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::data::ForexDataPoint;
+use crate::portfolio::split_symbol;
+
+/// Prefix of the market-context event attached to anomalies on thin-market bars
+pub const THIN_MARKET_EVENT: &str = "Thin market";
+
+/// How consumers treat bars in thin holiday markets
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThinMarketPolicy {
+    /// Leave the bar out: no anomalies, no new entries, no cycle evidence
+    Skip,
+    /// Keep the bar at `thin_weight` of its usual confidence or position size
+    DownWeight,
+    /// Keep the bar as is and only label it in reports
+    Annotate,
+}
+
+/// A bank holiday, recurring every year unless `year` is set
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Holiday {
+    pub name: String,
+    pub month: u32,
+    pub day: u32,
+    #[serde(default)]
+    pub year: Option<i32>,
+}
+
+impl Holiday {
+    pub fn annual(name: &str, month: u32, day: u32) -> Self {
+        Self { name: name.to_string(), month, day, year: None }
+    }
+
+    /// One-off holiday, e.g. a moving feast such as Easter Monday or Thanksgiving
+    pub fn on(name: &str, date: NaiveDate) -> Self {
+        Self { name: name.to_string(), month: date.month(), day: date.day(), year: Some(date.year()) }
+    }
+
+    pub fn falls_on(&self, date: NaiveDate) -> bool {
+        date.month() == self.month && date.day() == self.day && self.year.is_none_or(|y| y == date.year())
+    }
+}
+
+/// Holiday calendar configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HolidayCalendar {
+    /// Consult the calendar at all
+    pub enabled: bool,
+
+    /// What detectors and backtests do with thin-market bars
+    pub policy: ThinMarketPolicy,
+
+    /// Confidence or size multiplier for thin-market bars under `DownWeight`
+    pub thin_weight: f64,
+
+    /// Treat late December and the first days of January as thin for every currency
+    pub year_end: bool,
+
+    /// First thin day in December
+    pub year_end_start_day: u32,
+
+    /// Last thin day in January
+    pub year_end_end_day: u32,
+
+    /// Holidays keyed by ISO currency code. Dates are UTC calendar days.
+    pub currencies: BTreeMap<String, Vec<Holiday>>,
+}
+
+impl Default for HolidayCalendar {
+    fn default() -> Self {
+        let new_year = Holiday::annual("New Year's Day", 1, 1);
+        let christmas = Holiday::annual("Christmas Day", 12, 25);
+        let boxing_day = Holiday::annual("Boxing Day", 12, 26);
+        let currencies = BTreeMap::from([
+            ("USD".to_string(), vec![new_year.clone(), Holiday::annual("Independence Day", 7, 4), christmas.clone()]),
+            ("EUR".to_string(), vec![new_year.clone(), Holiday::annual("Labour Day", 5, 1), christmas.clone(), boxing_day.clone()]),
+            ("GBP".to_string(), vec![new_year.clone(), christmas.clone(), boxing_day.clone()]),
+            ("JPY".to_string(), vec![new_year.clone(), Holiday::annual("Bank Holiday", 1, 2), Holiday::annual("Bank Holiday", 1, 3),
+                                      Holiday::annual("Constitution Day", 5, 3), Holiday::annual("Children's Day", 5, 5),
+                                      Holiday::annual("Bank Holiday", 12, 31)]),
+            ("CHF".to_string(), vec![new_year.clone(), Holiday::annual("Berchtold's Day", 1, 2), Holiday::annual("National Day", 8, 1),
+                                      christmas.clone(), boxing_day.clone()]),
+            ("AUD".to_string(), vec![new_year.clone(), Holiday::annual("Australia Day", 1, 26), Holiday::annual("Anzac Day", 4, 25),
+                                      christmas.clone(), boxing_day.clone()]),
+            ("NZD".to_string(), vec![new_year.clone(), Holiday::annual("Day after New Year's Day", 1, 2), Holiday::annual("Waitangi Day", 2, 6),
+                                      Holiday::annual("Anzac Day", 4, 25), christmas.clone(), boxing_day.clone()]),
+            ("CAD".to_string(), vec![new_year, Holiday::annual("Canada Day", 7, 1), christmas, boxing_day]),
+        ]);
+
+        Self {
+            enabled: true,
+            policy: ThinMarketPolicy::DownWeight,
+            thin_weight: 0.5,
+            year_end: true,
+            year_end_start_day: 24,
+            year_end_end_day: 2,
+            currencies,
+        }
+    }
+}
+
+impl HolidayCalendar {
+    /// Calendar that never marks a bar as thin
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..Default::default()
+        }
+    }
+
+    /// Load a holiday calendar from a TOML file
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let config_str = std::fs::read_to_string(path)?;
+        Ok(toml::from_str(&config_str)?)
+    }
+
+    /// Add a holiday for `currency`
+    pub fn with_holiday(mut self, currency: &str, holiday: Holiday) -> Self {
+        self.currencies.entry(currency.to_uppercase()).or_default().push(holiday);
+        self
+    }
+
+    /// Holidays observed on `date` by any configured currency, as "CCY Name"
+    pub fn holidays_on(&self, date: NaiveDate) -> Vec<String> {
+        if !self.enabled {
+            return Vec::new();
+        }
+        self.currencies.iter()
+            .flat_map(|(currency, holidays)| holidays.iter()
+                .filter(move |h| h.falls_on(date))
+                .map(move |h| format!("{} {}", currency, h.name)))
+            .collect()
+    }
+
+    /// Whether `date` falls in the year-end lull
+    pub fn is_year_end(&self, date: NaiveDate) -> bool {
+        self.enabled && self.year_end && match date.month() {
+            12 => date.day() >= self.year_end_start_day,
+            1 => date.day() <= self.year_end_end_day,
+            _ => false,
+        }
+    }
+
+    /// Reason `pair` trades in a thin market at `timestamp`, if it does
+    pub fn check(&self, pair: &str, timestamp: DateTime<Utc>) -> Option<String> {
+        if !self.enabled {
+            return None;
+        }
+        let date = timestamp.date_naive();
+        let (base, quote) = split_symbol(pair);
+        let holidays: Vec<String> = [base, quote].iter()
+            .filter_map(|currency| self.currencies.get(currency).map(|h| (currency, h)))
+            .flat_map(|(currency, holidays)| holidays.iter()
+                .filter(|h| h.falls_on(date))
+                .map(move |h| format!("{} {}", currency, h.name)))
+            .collect();
+
+        if !holidays.is_empty() {
+            Some(holidays.join(", "))
+        } else if self.is_year_end(date) {
+            Some("year-end holidays".to_string())
+        } else {
+            None
+        }
+    }
+
+    pub fn is_thin(&self, pair: &str, timestamp: DateTime<Utc>) -> bool {
+        self.check(pair, timestamp).is_some()
+    }
+
+    /// Multiplier for confidence or size at `timestamp`: 1 in normal markets, 0 for
+    /// skipped thin bars and `thin_weight` for down-weighted ones
+    pub fn weight(&self, pair: &str, timestamp: DateTime<Utc>) -> f64 {
+        if !self.is_thin(pair, timestamp) {
+            return 1.0;
+        }
+        match self.policy {
+            ThinMarketPolicy::Skip => 0.0,
+            ThinMarketPolicy::DownWeight => self.thin_weight.clamp(0.0, 1.0),
+            ThinMarketPolicy::Annotate => 1.0,
+        }
+    }
+
+    /// Distinct thin-market days in `data`, in order, with their reasons
+    pub fn thin_days(&self, pair: &str, data: &[ForexDataPoint]) -> Vec<(NaiveDate, String)> {
+        let mut days: Vec<(NaiveDate, String)> = Vec::new();
+        for point in data {
+            let date = point.timestamp.date_naive();
+            if days.last().is_some_and(|(d, _)| *d == date) {
+                continue;
+            }
+            if let Some(reason) = self.check(pair, point.timestamp) {
+                days.push((date, reason));
+            }
+        }
+        days
+    }
+}
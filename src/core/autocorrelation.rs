@@ -0,0 +1,112 @@
+//! # Bit Autocorrelation
+//!
+//! Bitwise similarity of field-encoded states at every lag at once, from
+//! per-bit prefix sums and one FFT autocorrelation per bit: O(n log n) over all
+//! cycle lengths instead of O(n·P).
+
+use num_complex::Complex64;
+use rayon::prelude::*;
+
+use crate::patterns::spectral::fft;
+
+const BITS: usize = u64::BITS as usize;
+
+/// Bitwise similarity of a sequence of encoded states with itself at every lag
+pub struct BitAutocorrelation {
+    /// `prefix_ones[i]`: bits set in the first `i` states
+    prefix_ones: Vec<u64>,
+    /// `coincidences[lag]`: bits set in both states of every pair `lag` apart
+    coincidences: Vec<u64>,
+}
+
+impl BitAutocorrelation {
+    pub fn new(states: &[u64]) -> Self {
+        let n = states.len();
+        let mut prefix_ones = Vec::with_capacity(n + 1);
+        prefix_ones.push(0);
+        for state in states {
+            prefix_ones.push(prefix_ones.last().unwrap() + state.count_ones() as u64);
+        }
+        if n == 0 {
+            return Self { prefix_ones, coincidences: Vec::new() };
+        }
+
+        // Zero-padding to twice the length keeps the circular correlation from wrapping
+        let size = (2 * n).next_power_of_two();
+        let coincidences = (0..BITS)
+            .into_par_iter()
+            .map(|bit| {
+                let mut buffer: Vec<Complex64> = states.iter()
+                    .map(|state| Complex64::new(((state >> bit) & 1) as f64, 0.0))
+                    .chain(std::iter::repeat_n(Complex64::new(0.0, 0.0), size - n))
+                    .collect();
+                fft(&mut buffer);
+                // The power spectrum is real and even, so the forward FFT inverts it up to scale
+                for value in buffer.iter_mut() {
+                    *value = Complex64::new(value.norm_sqr(), 0.0);
+                }
+                fft(&mut buffer);
+                buffer[..n].iter().map(|value| (value.re / size as f64).round().max(0.0) as u64).collect::<Vec<u64>>()
+            })
+            .reduce(|| vec![0; n], |mut total, counts| {
+                total.iter_mut().zip(counts).for_each(|(t, c)| *t += c);
+                total
+            });
+
+        Self { prefix_ones, coincidences }
+    }
+
+    pub fn len(&self) -> usize {
+        self.coincidences.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.coincidences.is_empty()
+    }
+
+    /// Mean fraction of bits shared by states `lag` apart, in [0, 1]; `None`
+    /// unless `0 < lag < len`
+    pub fn similarity(&self, lag: usize) -> Option<f64> {
+        let n = self.len();
+        if lag == 0 || lag >= n {
+            return None;
+        }
+        let pairs = n - lag;
+        let ones_earlier = self.prefix_ones[pairs];
+        let ones_later = self.prefix_ones[n] - self.prefix_ones[lag];
+        let differing = ones_earlier + ones_later - 2 * self.coincidences[lag];
+        Some(1.0 - differing as f64 / (BITS * pairs) as f64)
+    }
+}
+
+/// [`BitAutocorrelation::similarity`] at one lag, pair by pair
+pub fn lag_similarity(states: &[u64], lag: usize) -> Option<f64> {
+    if lag == 0 || lag >= states.len() {
+        return None;
+    }
+    let differing: u64 = states.iter()
+        .zip(&states[lag..])
+        .map(|(a, b)| (a ^ b).count_ones() as u64)
+        .sum();
+    Some(1.0 - differing as f64 / (BITS * (states.len() - lag)) as f64)
+}
+
+/// Field signature of a `period`-bar pattern: every state rotated by its phase in
+/// the cycle and XORed onto a seed taken from the period, so patterns of different
+/// periods, or states at different phases, sign differently
+pub fn pattern_signature(states: &[u64], period: usize) -> u64 {
+    states.iter()
+        .enumerate()
+        .fold(signature_seed(period), |signature, (index, &state)| signature ^ phased(state, index, period))
+}
+
+/// Signature of a `period`-bar pattern over no states
+pub fn signature_seed(period: usize) -> u64 {
+    (period as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+}
+
+/// `state` at `index` as it enters a `period`-bar signature; rotation distributes
+/// over XOR, so a replaced state can be swapped out with its old value
+pub fn phased(state: u64, index: usize, period: usize) -> u64 {
+    state.rotate_left((index % period.max(1)) as u32)
+}
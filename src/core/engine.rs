@@ -11,10 +11,17 @@ use std::collections::HashMap;
 use tracing::{info, debug};
 
 use crate::data::ForexDataPoint;
-use crate::galois::GaloisField;
-use crate::symmetry::TemporalSymmetry;
+use crate::data::timeframe::TimeframeAggregator;
+use crate::galois::{ErrorCorrectionConfig, GaloisField};
+use crate::ids::SymmetryId;
+use crate::progress::{check_cancelled, CancellationToken, NoProgress, Progress, ProgressUpdate};
+use crate::symmetry::{SymmetryDetector, SymmetryDetectorConfig, TemporalSymmetry};
 use super::temporal_state::{TemporalState, TemporalStateSpace};
 use super::field_operations::GaloisFieldProcessor;
+use super::autocorrelation::{pattern_signature, BitAutocorrelation};
+use super::sampling::{self, SamplingPlan, ZoomWindow};
+use super::incremental::{RecentBars, RunningCorrelation};
+use rayon::prelude::*;
 
 /// Time-Symmetric Engine Configuration
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -36,6 +43,40 @@ pub struct EngineConfig {
     
     /// Error correction threshold
     pub error_correction_threshold: f64,
+    
+    /// Most bars one analysis pass takes at full resolution; longer histories
+    /// are analyzed coarse-to-fine (see [`super::sampling`]). `None` analyzes
+    /// every bar at once.
+    #[serde(default)]
+    pub max_points: Option<usize>,
+    
+    /// Windows re-analyzed at full resolution after the coarse pass
+    #[serde(default = "default_zoom_windows")]
+    pub zoom_windows: usize,
+}
+
+/// Stage [`TimeSymmetricEngine::extract_temporal_symmetries_with`] reports
+pub const EXTRACTION_STAGE: &str = "symmetry extraction";
+
+/// Checkpoints in one analysis pass: temporal states, field encoding, cyclic
+/// patterns, cyclic symmetries, price-path symmetries
+const STEPS_PER_PASS: u64 = 5;
+
+fn default_zoom_windows() -> usize {
+    3
+}
+
+impl EngineConfig {
+    /// Price error correction over the engine's field, refusing corrections to
+    /// more than `error_correction_threshold` of a block
+    pub fn error_correction(&self) -> ErrorCorrectionConfig {
+        ErrorCorrectionConfig {
+            field_characteristic: self.field_characteristic,
+            field_degree: self.field_degree,
+            max_correction_fraction: self.error_correction_threshold,
+            ..ErrorCorrectionConfig::default()
+        }
+    }
 }
 
 impl Default for EngineConfig {
@@ -47,6 +88,8 @@ impl Default for EngineConfig {
             min_symmetry_strength: 0.75,
             coherence_window: 1000,
             error_correction_threshold: 0.05,
+            max_points: None,
+            zoom_windows: default_zoom_windows(),
         }
     }
 }
@@ -57,7 +100,12 @@ pub struct TimeSymmetricEngine {
     galois_field: GaloisField,
     field_processor: GaloisFieldProcessor,
     temporal_space: TemporalStateSpace,
-    symmetry_cache: HashMap<String, TemporalSymmetry>,
+    symmetry_detector: SymmetryDetector,
+    symmetry_cache: HashMap<SymmetryId, TemporalSymmetry>,
+    /// How the last extraction sampled a history over `max_points`
+    sampling_plan: Option<SamplingPlan>,
+    /// Running state of [`Self::update_with_point`]
+    incremental: Option<IncrementalExtraction>,
     initialized: bool,
 }
 
@@ -68,19 +116,29 @@ impl TimeSymmetricEngine {
         info!("  Field: GF({}^{})", config.field_characteristic, config.field_degree);
         info!("  Max cycle period: {} days", config.max_cycle_period);
         
-        let galois_field = GaloisField::new(
-            config.field_characteristic as u64,
+        let galois_field = GaloisField::new_with_degree(
+            config.field_characteristic,
+            config.field_degree,
         )?;
         
         let field_processor = GaloisFieldProcessor::new(&galois_field)?;
         let temporal_space = TemporalStateSpace::new(config.coherence_window)?;
+        let defaults = SymmetryDetectorConfig::default();
+        let symmetry_detector = SymmetryDetector::new(SymmetryDetectorConfig {
+            max_period: defaults.max_period.min(config.max_cycle_period as usize).max(defaults.min_period),
+            min_strength: config.min_symmetry_strength,
+            ..defaults
+        })?;
         
         Ok(Self {
             config,
             galois_field,
             field_processor,
             temporal_space,
+            symmetry_detector,
             symmetry_cache: HashMap::new(),
+            sampling_plan: None,
+            incremental: None,
             initialized: false,
         })
     }
@@ -111,41 +169,205 @@ impl TimeSymmetricEngine {
         Ok(())
     }
     
-    /// Extract temporal symmetries from forex data
+    /// Extract temporal symmetries from forex data; histories over `max_points`
+    /// bars are analyzed coarse-to-fine, with periods in bars of `data` either way
     pub async fn extract_temporal_symmetries(
         &mut self,
         data: &[ForexDataPoint],
+    ) -> Result<Vec<TemporalSymmetry>> {
+        self.extract_temporal_symmetries_with(data, &NoProgress, &CancellationToken::new()).await
+    }
+    
+    /// [`Self::extract_temporal_symmetries`] reporting each step of each analysis
+    /// pass to `progress` and failing with [`Cancelled`](crate::progress::Cancelled)
+    /// at the next step once `cancel` is cancelled
+    pub async fn extract_temporal_symmetries_with(
+        &mut self,
+        data: &[ForexDataPoint],
+        progress: &dyn Progress,
+        cancel: &CancellationToken,
     ) -> Result<Vec<TemporalSymmetry>> {
         if !self.initialized {
             return Err(anyhow::anyhow!("Engine not initialized"));
         }
         
+        self.sampling_plan = None;
+        match self.config.max_points {
+            Some(budget) if data.len() > budget => {
+                // The coarse pass picks how many windows are zoomed; assume all of them until then
+                let mut checkpoints = Checkpoints::new(progress, cancel, 1 + self.config.zoom_windows as u64);
+                self.extract_coarse_to_fine(data, budget, &mut checkpoints).await
+            }
+            _ => self.extract_full_resolution(data, &mut Checkpoints::new(progress, cancel, 1)).await,
+        }
+    }
+    
+    /// How the last extraction sampled its history; `None` when it fit `max_points`
+    pub fn sampling_plan(&self) -> Option<&SamplingPlan> {
+        self.sampling_plan.as_ref()
+    }
+    
+    async fn extract_full_resolution(&mut self, data: &[ForexDataPoint], checkpoints: &mut Checkpoints<'_>) -> Result<Vec<TemporalSymmetry>> {
         info!("🔍 Extracting temporal symmetries from {} data points", data.len());
+        checkpoints.check()?;
         
         // Convert forex data to temporal states
         let temporal_states = self.convert_to_temporal_states(data).await?;
         debug!("📊 Converted to {} temporal states", temporal_states.len());
+        checkpoints.step()?;
         
         // Encode states in Galois field
         let field_encoded_states = self.encode_states_to_field(&temporal_states).await?;
         debug!("🔢 Encoded states to Galois field");
+        checkpoints.step()?;
         
         // Detect cyclic patterns
         let cyclic_patterns = self.detect_cyclic_patterns(&field_encoded_states).await?;
         debug!("🔄 Detected {} cyclic patterns", cyclic_patterns.len());
+        checkpoints.step()?;
         
         // Extract symmetries from patterns
-        let symmetries = self.extract_symmetries_from_patterns(&cyclic_patterns, data).await?;
+        let mut symmetries = self.extract_symmetries_from_patterns(&cyclic_patterns, data).await?;
+        checkpoints.step()?;
+        
+        // Mirror, rotational and translational symmetries in the price path
+        symmetries.extend(self.symmetry_detector.detect(data));
+        checkpoints.step()?;
         info!("✅ Extracted {} temporal symmetries", symmetries.len());
         
-        // Cache symmetries for future use
-        for symmetry in &symmetries {
-            self.symmetry_cache.insert(symmetry.id.clone(), symmetry.clone());
+        // Cache the symmetries of this extraction for predictions
+        self.symmetry_cache = symmetries.iter().map(|symmetry| (symmetry.id.clone(), symmetry.clone())).collect();
+        
+        Ok(symmetries)
+    }
+    
+    /// Analyze a downsampled history for the long cycles, then the most
+    /// promising budget-sized windows at full resolution for the short ones.
+    /// A symmetry kind and period found more than once keeps its strongest find.
+    async fn extract_coarse_to_fine(&mut self, data: &[ForexDataPoint], budget: usize, checkpoints: &mut Checkpoints<'_>) -> Result<Vec<TemporalSymmetry>> {
+        let stride = sampling::stride(data.len(), budget);
+        let coarse = sampling::downsample(data, stride);
+        info!("🔭 {} bars over the {}-bar budget: coarse pass on {} bars of {}", data.len(), budget, coarse.len(), stride);
+        let coarse_symmetries = self.extract_full_resolution(&coarse, checkpoints).await?;
+        let windows = sampling::zoom_windows(data.len(), budget, self.config.zoom_windows, stride, &coarse, &coarse_symmetries);
+        checkpoints.set_passes(1 + windows.len() as u64);
+        
+        let mut symmetries: Vec<TemporalSymmetry> = coarse_symmetries.into_iter()
+            .map(|symmetry| self.rescale(symmetry, stride))
+            .collect();
+        let mut zoomed = Vec::with_capacity(windows.len());
+        for (range, score) in windows {
+            let window = &data[range];
+            info!("🔬 Zooming into {} → {} ({} bars, coarse score {:.2})",
+                  window[0].timestamp, window[window.len() - 1].timestamp, window.len(), score);
+            symmetries.extend(self.extract_full_resolution(window, checkpoints).await?);
+            zoomed.push(ZoomWindow {
+                start: window[0].timestamp,
+                end: window[window.len() - 1].timestamp,
+                bars: window.len(),
+                score,
+            });
+        }
+        
+        let mut strongest: Vec<TemporalSymmetry> = Vec::new();
+        for symmetry in symmetries {
+            match strongest.iter_mut().find(|kept| kept.symmetry_type == symmetry.symmetry_type && kept.period_days == symmetry.period_days) {
+                Some(kept) if kept.strength >= symmetry.strength => {}
+                Some(kept) => *kept = symmetry,
+                None => strongest.push(symmetry),
+            }
         }
+        info!("✅ Coarse-to-fine analysis kept {} temporal symmetries", strongest.len());
         
+        self.symmetry_cache = strongest.iter().map(|symmetry| (symmetry.id.clone(), symmetry.clone())).collect();
+        self.sampling_plan = Some(SamplingPlan { stride, coarse_bars: coarse.len(), windows: zoomed });
+        Ok(strongest)
+    }
+    
+    /// A coarse-pass symmetry with its period in full-resolution bars
+    fn rescale(&self, mut symmetry: TemporalSymmetry, stride: usize) -> TemporalSymmetry {
+        let period = symmetry.period_days * stride as u32;
+        symmetry.name = if SymmetryDetector::detects(&symmetry.symmetry_type) {
+            format!("{}-bar {}", period, symmetry.symmetry_type.to_lowercase())
+        } else {
+            Self::pattern_name(period)
+        };
+        symmetry.period_days = period;
+        symmetry
+    }
+    
+    /// Extend the streamed history by one bar; see [`Self::update_with_window`]
+    pub async fn update_with_point(&mut self, point: &ForexDataPoint) -> Result<Vec<TemporalSymmetry>> {
+        self.update_with_window(std::slice::from_ref(point)).await
+    }
+    
+    /// Extend the streamed history by `points` and return the cyclic symmetries
+    /// of every bar streamed so far, with the strengths
+    /// [`Self::extract_temporal_symmetries`] finds at full resolution; each is
+    /// validated by how often bars a period apart move the same way. Each bar
+    /// costs O(max cycle period) however long the history, and a period keeps
+    /// its symmetry id from one update to the next. Bars not newer than the last one streamed are
+    /// skipped. Mirror, rotational and translational symmetries of the price
+    /// path still need a full extraction; those it found stay cached.
+    pub async fn update_with_window(&mut self, points: &[ForexDataPoint]) -> Result<Vec<TemporalSymmetry>> {
+        if !self.initialized {
+            return Err(anyhow::anyhow!("Engine not initialized"));
+        }
+        
+        let window = self.config.coherence_window;
+        let max_lag = self.config.max_cycle_period as usize;
+        let stream = self.incremental.get_or_insert_with(|| IncrementalExtraction::new(max_lag, window));
+        for point in points {
+            if stream.bars.last().is_some_and(|last| point.timestamp <= last.timestamp) {
+                continue;
+            }
+            stream.bars.push(point.clone());
+            let n = stream.bars.len();
+            let encoded = Self::encode_streamed(&self.field_processor, window, &mut stream.bars, n - 1)?;
+            stream.correlation.push(encoded, point.close > point.open);
+            // The bar a coherence window back now has its full future context
+            if n > window {
+                let index = n - 1 - window;
+                let encoded = Self::encode_streamed(&self.field_processor, window, &mut stream.bars, index)?;
+                stream.correlation.replace(index, encoded);
+            }
+        }
+        
+        let symmetries = stream.symmetries(self.config.min_symmetry_strength);
+        debug!("🔁 {} streamed bars, {} cyclic symmetries", stream.bars.len(), symmetries.len());
+        // Price-path symmetries of the last full extraction stay cached
+        self.symmetry_cache.retain(|_, symmetry| SymmetryDetector::detects(&symmetry.symmetry_type));
+        self.symmetry_cache.extend(symmetries.iter().map(|symmetry| (symmetry.id.clone(), symmetry.clone())));
         Ok(symmetries)
     }
     
+    /// Bars streamed through [`Self::update_with_point`] since the last reset
+    pub fn streamed_bars(&self) -> usize {
+        self.incremental.as_ref().map_or(0, |stream| stream.bars.len())
+    }
+    
+    /// Forget the streamed history, e.g. before streaming another pair
+    pub fn reset_incremental(&mut self) {
+        self.incremental = None;
+    }
+    
+    /// Field encoding of streamed bar `index`, with the contexts
+    /// [`Self::convert_to_temporal_states`] gives it over the bars so far
+    fn encode_streamed(
+        processor: &GaloisFieldProcessor,
+        window: usize,
+        bars: &mut RecentBars,
+        index: usize,
+    ) -> Result<u64> {
+        let n = bars.len();
+        let (offset, retained) = bars.retained();
+        let local = index - offset;
+        let past_context = (index >= window).then(|| &retained[local - window..local]);
+        let future_context = (index + window < n).then(|| &retained[local + 1..local + 1 + window]);
+        let state = TemporalState::from_forex_data(&retained[local], past_context, future_context)?;
+        processor.encode_temporal_state(&state)
+    }
+    
     /// Predict future states using field extensions
     pub async fn predict_future_states(
         &self,
@@ -185,6 +407,35 @@ impl TimeSymmetricEngine {
         Ok(predictions)
     }
     
+    /// Resample `data` to each of `timeframes` and extract symmetries on every one.
+    /// Symmetry ids get an `@TIMEFRAME` suffix naming the timeframe they were found on.
+    pub async fn extract_temporal_symmetries_multi_timeframe(
+        &mut self,
+        data: &[ForexDataPoint],
+        timeframes: &[String],
+    ) -> Result<Vec<TimeframeSymmetries>> {
+        let mut results: Vec<TimeframeSymmetries> = Vec::with_capacity(timeframes.len());
+        for timeframe in timeframes {
+            let aggregator = TimeframeAggregator::new(timeframe)?;
+            let bars = aggregator.resample(data)?;
+            info!("🕰️  {} timeframe: {} bars", aggregator.timeframe(), bars.len());
+            let mut symmetries = self.extract_temporal_symmetries(&bars).await?;
+            for symmetry in &mut symmetries {
+                symmetry.id = symmetry.id.on_timeframe(aggregator.timeframe());
+            }
+            results.push(TimeframeSymmetries {
+                timeframe: aggregator.timeframe().to_string(),
+                bars: bars.len(),
+                symmetries,
+            });
+        }
+        self.symmetry_cache = results.iter()
+            .flat_map(|timeframe| &timeframe.symmetries)
+            .map(|symmetry| (symmetry.id.clone(), symmetry.clone()))
+            .collect();
+        Ok(results)
+    }
+    
     /// Validate temporal invariance across data
     pub async fn validate_temporal_invariance(
         &self,
@@ -280,25 +531,26 @@ impl TimeSymmetricEngine {
         &self,
         encoded_states: &[u64],
     ) -> Result<Vec<CyclicPattern>> {
-        let mut patterns = Vec::new();
+        // Need at least 3 full cycles
+        let max_cycle_length = (self.config.max_cycle_period as usize).min(encoded_states.len() / 3);
+        if max_cycle_length < 2 {
+            return Ok(Vec::new());
+        }
         
-        // Use Galois field arithmetic to detect cycles
-        for cycle_length in 2..=self.config.max_cycle_period {
-            if encoded_states.len() < cycle_length as usize * 3 {
-                continue; // Need at least 3 full cycles
-            }
-            
-            let pattern_strength = self.compute_cycle_strength(encoded_states, cycle_length).await?;
-            
-            if pattern_strength > self.config.min_symmetry_strength {
-                let pattern = CyclicPattern {
-                    period: cycle_length,
+        // Bitwise field similarity at every cycle length, from one FFT per bit
+        let autocorrelation = BitAutocorrelation::new(encoded_states);
+        
+        let mut patterns: Vec<CyclicPattern> = (2..=max_cycle_length)
+            .into_par_iter()
+            .filter_map(|cycle_length| {
+                let pattern_strength = autocorrelation.similarity(cycle_length)?;
+                (pattern_strength > self.config.min_symmetry_strength).then_some(CyclicPattern {
+                    period: cycle_length as u32,
                     strength: pattern_strength,
-                    field_signature: self.compute_field_signature(encoded_states, cycle_length)?,
-                };
-                patterns.push(pattern);
-            }
-        }
+                    field_signature: pattern_signature(encoded_states, cycle_length),
+                })
+            })
+            .collect();
         
         // Sort by strength
         patterns.sort_by(|a, b| b.strength.partial_cmp(&a.strength).unwrap());
@@ -313,9 +565,9 @@ impl TimeSymmetricEngine {
     ) -> Result<Vec<TemporalSymmetry>> {
         let mut symmetries = Vec::new();
         
-        for (i, pattern) in patterns.iter().enumerate() {
+        for pattern in patterns {
             let symmetry = TemporalSymmetry {
-                id: format!("symmetry_{}", i),
+                id: SymmetryId::new(),
                 symmetry_type: "mirror".to_string(),
                 name: self.classify_pattern_name(pattern)?,
                 period_days: pattern.period,
@@ -422,50 +674,12 @@ impl TimeSymmetricEngine {
         Ok(similarity)
     }
     
-    async fn compute_cycle_strength(&self, encoded_states: &[u64], cycle_length: u32) -> Result<f64> {
-        let cycle_len = cycle_length as usize;
-        let num_cycles = encoded_states.len() / cycle_len;
-        
-        if num_cycles < 2 {
-            return Ok(0.0);
-        }
-        
-        let mut correlations = Vec::new();
-        
-        for i in 0..num_cycles - 1 {
-            let cycle1_start = i * cycle_len;
-            let cycle1_end = (i + 1) * cycle_len;
-            let cycle2_start = (i + 1) * cycle_len;
-            let cycle2_end = (i + 2) * cycle_len;
-            
-            let cycle1 = &encoded_states[cycle1_start..cycle1_end];
-            let cycle2 = &encoded_states[cycle2_start..cycle2_end];
-            
-            let correlation = self.compute_field_correlation(cycle1, cycle2)?;
-            correlations.push(correlation);
-        }
-        
-        let average_correlation = correlations.iter().sum::<f64>() / correlations.len() as f64;
-        Ok(average_correlation)
-    }
-    
-    fn compute_field_signature(&self, encoded_states: &[u64], cycle_length: u32) -> Result<u64> {
-        // Compute a unique signature for this cycle pattern
-        let cycle_len = cycle_length as usize;
-        let mut signature = 0u64;
-        
-        for i in (0..encoded_states.len()).step_by(cycle_len) {
-            let end = std::cmp::min(i + cycle_len, encoded_states.len());
-            for &state in &encoded_states[i..end] {
-                signature ^= state; // XOR for field-based signature
-            }
-        }
-        
-        Ok(signature)
+    fn classify_pattern_name(&self, pattern: &CyclicPattern) -> Result<String> {
+        Ok(Self::pattern_name(pattern.period))
     }
     
-    fn classify_pattern_name(&self, pattern: &CyclicPattern) -> Result<String> {
-        let name = match pattern.period {
+    fn pattern_name(period: u32) -> String {
+        let name = match period {
             1..=10 => "short_term_cycle",
             11..=30 => "monthly_cycle", 
             31..=100 => "quarterly_cycle",
@@ -476,7 +690,7 @@ impl TimeSymmetricEngine {
             _ => "long_term_cycle",
         };
         
-        Ok(format!("{}_{}_days", name, pattern.period))
+        format!("{}_{}_days", name, period)
     }
     
     async fn validate_pattern_against_data(
@@ -554,24 +768,6 @@ impl TimeSymmetricEngine {
         Ok(matches as f64 / total as f64)
     }
     
-    fn compute_field_correlation(&self, cycle1: &[u64], cycle2: &[u64]) -> Result<f64> {
-        if cycle1.len() != cycle2.len() {
-            return Ok(0.0);
-        }
-        
-        let mut correlation_sum = 0.0;
-        let total = cycle1.len();
-        
-        for (a, b) in cycle1.iter().zip(cycle2.iter()) {
-            // Compute field-based correlation
-            let xor_result = a ^ b;
-            let similarity = 1.0 - (xor_result.count_ones() as f64 / 64.0); // 64-bit numbers
-            correlation_sum += similarity;
-        }
-        
-        Ok(correlation_sum / total as f64)
-    }
-    
     fn compute_cycle_prediction_accuracy(
         &self,
         current_cycle: &[ForexDataPoint],
@@ -606,6 +802,84 @@ struct CyclicPattern {
     field_signature: u64,
 }
 
+/// Where an extraction reports its steps and looks for cancellation
+struct Checkpoints<'a> {
+    progress: &'a dyn Progress,
+    cancel: &'a CancellationToken,
+    done: u64,
+    total: u64,
+}
+
+impl<'a> Checkpoints<'a> {
+    fn new(progress: &'a dyn Progress, cancel: &'a CancellationToken, passes: u64) -> Self {
+        Self { progress, cancel, done: 0, total: passes * STEPS_PER_PASS }
+    }
+    
+    fn set_passes(&mut self, passes: u64) {
+        self.total = (passes * STEPS_PER_PASS).max(self.done);
+    }
+    
+    fn check(&self) -> Result<()> {
+        check_cancelled(self.cancel)
+    }
+    
+    /// Count a finished step, then stop if cancelled
+    fn step(&mut self) -> Result<()> {
+        self.done += 1;
+        self.progress.report(ProgressUpdate::new(EXTRACTION_STAGE, self.done, self.total));
+        self.check()
+    }
+}
+
+/// Streamed history behind [`TimeSymmetricEngine::update_with_point`]
+#[derive(Debug, Clone)]
+struct IncrementalExtraction {
+    bars: RecentBars,
+    correlation: RunningCorrelation,
+    /// Id and discovery time of the symmetry at each period
+    ids: HashMap<u32, (SymmetryId, chrono::DateTime<chrono::Utc>)>,
+}
+
+impl IncrementalExtraction {
+    fn new(max_lag: usize, coherence_window: usize) -> Self {
+        Self {
+            bars: RecentBars::new(coherence_window),
+            correlation: RunningCorrelation::new(max_lag, coherence_window),
+            ids: HashMap::new(),
+        }
+    }
+    
+    /// Cyclic symmetries over `min_strength`, strongest first, with at least 3 full cycles
+    fn symmetries(&mut self, min_strength: f64) -> Vec<TemporalSymmetry> {
+        let correlation = &self.correlation;
+        let max_cycle_length = correlation.max_lag().min(correlation.len() / 3);
+        let mut symmetries: Vec<TemporalSymmetry> = (2..=max_cycle_length)
+            .filter_map(|cycle_length| {
+                let strength = correlation.similarity(cycle_length).filter(|&strength| strength > min_strength)?;
+                let period = cycle_length as u32;
+                let (id, discovered_at) = self.ids.entry(period)
+                    .or_insert_with(|| (SymmetryId::new(), chrono::Utc::now()))
+                    .clone();
+                Some(TemporalSymmetry {
+                    id,
+                    symmetry_type: "mirror".to_string(),
+                    name: TimeSymmetricEngine::pattern_name(period),
+                    period_days: period,
+                    strength,
+                    confidence: strength,
+                    field_signature: correlation.signature(cycle_length)?,
+                    discovered_at,
+                    validation_score: correlation.direction_agreement(cycle_length).unwrap_or(0.0),
+                    mirror_points: Vec::new(),
+                    phase_shift: 0.0,
+                })
+            })
+            .collect();
+        symmetries.sort_by(|a, b| b.strength.total_cmp(&a.strength));
+        symmetries
+    }
+}
+
 /// Predicted future state
 #[derive(Debug, Clone, Serialize)]
 pub struct PredictedState {
@@ -615,6 +889,14 @@ pub struct PredictedState {
     pub cycle_alignment: String,
 }
 
+/// Symmetries extracted on one timeframe; periods are in bars of that timeframe
+#[derive(Debug, Clone, Serialize)]
+pub struct TimeframeSymmetries {
+    pub timeframe: String,
+    pub bars: usize,
+    pub symmetries: Vec<TemporalSymmetry>,
+}
+
 /// Temporal invariance validation result
 #[derive(Debug, Clone, Serialize)]
 pub struct TemporalInvarianceResult {
@@ -18,7 +18,7 @@ pub struct GaloisFieldProcessor {
 impl GaloisFieldProcessor {
     pub fn new(field: &GaloisField) -> Result<Self> {
         Ok(Self {
-            field: GaloisField::new(2)?, // Clone field parameters
+            field: field.clone(),
             encoding_cache: HashMap::new(),
             common_elements: Vec::new(),
         })
@@ -54,7 +54,7 @@ impl GaloisFieldProcessor {
         }
         
         // Ensure result is within field
-        Ok(encoded % self.field.size())
+        Ok(self.field.reduce(encoded))
     }
     
     pub fn decode_field_element(&self, element: u64) -> Result<TemporalState> {
@@ -82,21 +82,14 @@ pub trait FieldOperations {
 
 impl FieldOperations for GaloisFieldProcessor {
     fn add(&self, a: u64, b: u64) -> u64 {
-        (a ^ b) % self.field.size() // XOR for GF(2^n)
+        self.field.add(a, b)
     }
     
     fn multiply(&self, a: u64, b: u64) -> u64 {
-        // Simplified multiplication
-        (a.wrapping_mul(b)) % self.field.size()
+        self.field.mul(a, b)
     }
     
     fn inverse(&self, a: u64) -> Option<u64> {
-        if a == 0 {
-            return None;
-        }
-        
-        // Extended Euclidean algorithm would go here
-        // Placeholder implementation
-        Some((self.field.size() - 1) / a)
+        self.field.inverse(a)
     }
 }
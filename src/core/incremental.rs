@@ -0,0 +1,178 @@
+//! # Incremental Symmetry Extraction
+//!
+//! Running sums behind [`super::TimeSymmetricEngine::update_with_point`]: a new
+//! bar changes at most two field-encoded states, each adjusting the per-lag
+//! similarity sums in O(max lag) instead of re-correlating the whole history.
+
+use std::collections::VecDeque;
+
+use super::autocorrelation::{phased, signature_seed};
+use crate::data::ForexDataPoint;
+
+const BITS: u64 = u64::BITS as u64;
+
+/// Similarity and direction agreement of a growing state sequence at every lag up to `max_lag`
+#[derive(Debug, Clone)]
+pub struct RunningCorrelation {
+    max_lag: usize,
+    /// States that can still be replaced or paired with a new one, oldest first
+    states: VecDeque<u64>,
+    /// Bullish bars of the last `max_lag` states
+    directions: VecDeque<bool>,
+    /// Index of `states[0]` in the whole sequence
+    offset: usize,
+    /// `differing[lag]`: bits differing between every pair of states `lag` apart
+    differing: Vec<u64>,
+    /// `agreements[lag]`: pairs of bars `lag` apart moving the same way
+    agreements: Vec<u64>,
+    /// `signatures[lag]`: field signature of the `lag`-bar pattern over every state
+    signatures: Vec<u64>,
+    /// States a replacement may still reach back to
+    replace_window: usize,
+}
+
+impl RunningCorrelation {
+    /// Correlations up to `max_lag`, allowing states up to `replace_window` back to be replaced
+    pub fn new(max_lag: usize, replace_window: usize) -> Self {
+        Self {
+            max_lag,
+            states: VecDeque::new(),
+            directions: VecDeque::new(),
+            offset: 0,
+            differing: vec![0; max_lag + 1],
+            agreements: vec![0; max_lag + 1],
+            signatures: (0..=max_lag).map(signature_seed).collect(),
+            replace_window,
+        }
+    }
+
+    /// States pushed so far
+    pub fn len(&self) -> usize {
+        self.offset + self.states.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn max_lag(&self) -> usize {
+        self.max_lag
+    }
+
+    /// Field signature of the `lag`-bar pattern, as
+    /// [`super::autocorrelation::pattern_signature`] gives it
+    pub fn signature(&self, lag: usize) -> Option<u64> {
+        (lag > 0 && lag <= self.max_lag).then(|| self.signatures[lag])
+    }
+
+    /// State at `index` of the whole sequence, while it is retained
+    pub fn state(&self, index: usize) -> Option<u64> {
+        self.states.get(index.checked_sub(self.offset)?).copied()
+    }
+
+    /// Append a state and the direction of its bar
+    pub fn push(&mut self, state: u64, bullish: bool) {
+        let n = self.len();
+        for lag in 1..=self.max_lag.min(n) {
+            if let Some(earlier) = self.state(n - lag) {
+                self.differing[lag] += (state ^ earlier).count_ones() as u64;
+            }
+            if self.directions.get(self.directions.len() - lag) == Some(&bullish) {
+                self.agreements[lag] += 1;
+            }
+        }
+        for lag in 1..=self.max_lag {
+            self.signatures[lag] ^= phased(state, n, lag);
+        }
+        self.states.push_back(state);
+        self.directions.push_back(bullish);
+        while self.directions.len() > self.max_lag {
+            self.directions.pop_front();
+        }
+        while self.states.len() > self.max_lag + self.replace_window + 1 {
+            self.states.pop_front();
+            self.offset += 1;
+        }
+    }
+
+    /// Replace the state at `index`, e.g. once its future context is known;
+    /// `false` when it is no longer retained
+    pub fn replace(&mut self, index: usize, state: u64) -> bool {
+        let Some(old) = self.state(index) else { return false };
+        if old == state {
+            return true;
+        }
+        let n = self.len();
+        for lag in 1..=self.max_lag {
+            let neighbours = [index.checked_sub(lag), Some(index + lag).filter(|&later| later < n)];
+            for neighbour in neighbours.into_iter().flatten() {
+                let Some(other) = self.state(neighbour) else { continue };
+                self.differing[lag] = self.differing[lag] + (state ^ other).count_ones() as u64 - (old ^ other).count_ones() as u64;
+            }
+        }
+        for lag in 1..=self.max_lag {
+            self.signatures[lag] ^= phased(old ^ state, index, lag);
+        }
+        self.states[index - self.offset] = state;
+        true
+    }
+
+    /// Mean fraction of bits shared by states `lag` apart, as
+    /// [`super::autocorrelation::BitAutocorrelation::similarity`] gives it
+    pub fn similarity(&self, lag: usize) -> Option<f64> {
+        let pairs = self.pairs(lag)?;
+        Some(1.0 - self.differing[lag] as f64 / (BITS * pairs) as f64)
+    }
+
+    /// Fraction of bars moving the same way as the bar `lag` before them
+    pub fn direction_agreement(&self, lag: usize) -> Option<f64> {
+        let pairs = self.pairs(lag)?;
+        Some(self.agreements[lag] as f64 / pairs as f64)
+    }
+
+    fn pairs(&self, lag: usize) -> Option<u64> {
+        (lag > 0 && lag <= self.max_lag && lag < self.len()).then(|| (self.len() - lag) as u64)
+    }
+}
+
+/// Bars an incremental extraction still needs: enough for the past and future
+/// contexts of the states a new bar touches
+#[derive(Debug, Clone)]
+pub struct RecentBars {
+    bars: VecDeque<ForexDataPoint>,
+    /// Index of `bars[0]` in the whole stream
+    offset: usize,
+    capacity: usize,
+}
+
+impl RecentBars {
+    pub fn new(coherence_window: usize) -> Self {
+        Self { bars: VecDeque::new(), offset: 0, capacity: 2 * coherence_window + 1 }
+    }
+
+    /// Bars pushed so far
+    pub fn len(&self) -> usize {
+        self.offset + self.bars.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn last(&self) -> Option<&ForexDataPoint> {
+        self.bars.back()
+    }
+
+    pub fn push(&mut self, bar: ForexDataPoint) {
+        self.bars.push_back(bar);
+        while self.bars.len() > self.capacity {
+            self.bars.pop_front();
+            self.offset += 1;
+        }
+    }
+
+    /// Index of the oldest retained bar in the whole stream, and the retained bars
+    pub fn retained(&mut self) -> (usize, &[ForexDataPoint]) {
+        (self.offset, self.bars.make_contiguous())
+    }
+}
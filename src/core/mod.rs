@@ -6,7 +6,12 @@
 pub mod engine;
 pub mod temporal_state;
 pub mod field_operations;
+pub mod autocorrelation;
+pub mod sampling;
+pub mod incremental;
 
-pub use engine::{TimeSymmetricEngine, EngineConfig};
+pub use engine::{TimeSymmetricEngine, EngineConfig, TimeframeSymmetries};
 pub use temporal_state::{TemporalState, TemporalStateSpace};
 pub use field_operations::{FieldOperations, GaloisFieldProcessor};
+pub use sampling::{SamplingPlan, ZoomWindow};
+pub use incremental::{RecentBars, RunningCorrelation};
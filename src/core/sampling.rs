@@ -0,0 +1,105 @@
+//! # Coarse-to-Fine Sampling
+//!
+//! Histories over the `max_points` budget are analyzed in a coarse pass over
+//! aggregated blocks, which finds the long cycles, and a full-resolution pass over
+//! the newest window and those where the coarse symmetries hold most strongly.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+use crate::data::ForexDataPoint;
+use crate::symmetry::{SymmetryDetector, TemporalSymmetry};
+
+/// Periods of coarse bars before a tile's end a symmetry is measured over,
+/// enough for every kind the detector finds
+const MEASURED_PERIODS: usize = 4;
+
+/// How a long history was analyzed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SamplingPlan {
+    /// Bars aggregated into each coarse bar
+    pub stride: usize,
+    pub coarse_bars: usize,
+    /// Windows analyzed at full resolution, oldest first
+    pub windows: Vec<ZoomWindow>,
+}
+
+/// A stretch of history analyzed at full resolution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZoomWindow {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub bars: usize,
+    /// Strongest coarse symmetry measured at the window's end; the newest
+    /// window is always zoomed into whatever its score
+    pub score: f64,
+}
+
+/// Bars per coarse bar so `len` bars fit in `budget`
+pub fn stride(len: usize, budget: usize) -> usize {
+    len.div_ceil(budget.max(1)).max(1)
+}
+
+/// Aggregate every `stride` consecutive bars into one, stamped with the first
+/// bar's time: open of the first, close of the last, extremes of all
+pub fn downsample(data: &[ForexDataPoint], stride: usize) -> Vec<ForexDataPoint> {
+    data.chunks(stride.max(1))
+        .map(|block| {
+            let first = &block[0];
+            let last = &block[block.len() - 1];
+            ForexDataPoint {
+                timestamp: first.timestamp,
+                open: first.open,
+                high: block.iter().map(|bar| bar.high).fold(f64::MIN, f64::max),
+                low: block.iter().map(|bar| bar.low).fold(f64::MAX, f64::min),
+                close: last.close,
+                volume: block.iter()
+                    .filter_map(|bar| bar.volume)
+                    .fold(None, |total, volume| Some(total.unwrap_or(0.0) + volume)),
+            }
+        })
+        .collect()
+}
+
+/// Up to `count` windows of at most `budget` bars to analyze at full
+/// resolution: the newest tile, then the tiles at whose end the coarse
+/// symmetries hold most strongly, newer first on ties. Returned oldest first
+/// with their scores.
+pub fn zoom_windows(
+    len: usize,
+    budget: usize,
+    count: usize,
+    stride: usize,
+    coarse: &[ForexDataPoint],
+    coarse_symmetries: &[TemporalSymmetry],
+) -> Vec<(Range<usize>, f64)> {
+    if count == 0 || len == 0 {
+        return Vec::new();
+    }
+    let budget = budget.max(1);
+    // Tiles end at the newest bar so the newest one is a full window
+    let mut tiles: Vec<Range<usize>> = Vec::new();
+    let mut end = len;
+    while end > 0 {
+        tiles.push(end.saturating_sub(budget)..end);
+        end = end.saturating_sub(budget);
+    }
+    let score = |tile: &Range<usize>| {
+        let coarse_end = tile.end.div_ceil(stride.max(1)).min(coarse.len());
+        coarse_symmetries.iter()
+            .filter_map(|symmetry| {
+                let period = symmetry.period_days as usize;
+                let window = &coarse[coarse_end.saturating_sub(MEASURED_PERIODS * period + 1)..coarse_end];
+                SymmetryDetector::strength_at(&symmetry.symmetry_type, period, window)
+            })
+            .fold(0.0, f64::max)
+    };
+    let mut scored: Vec<(Range<usize>, f64)> = tiles.iter().map(|tile| (tile.clone(), score(tile))).collect();
+    let newest = scored.remove(0);
+    // Stable sort keeps newer tiles ahead on ties
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+    let mut chosen: Vec<(Range<usize>, f64)> = std::iter::once(newest).chain(scored).take(count).collect();
+    chosen.sort_by_key(|(tile, _)| tile.start);
+    chosen
+}
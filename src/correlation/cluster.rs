@@ -0,0 +1,297 @@
+//! # Currency Blocs
+//!
+//! Currency blocs: the correlation matrix is clustered bottom-up over the
+//! distance `|1 − ρ|` with single or average linkage, and the dendrogram is cut
+//! at a distance to give pairs that are really one bet.
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use super::{CorrelationResult, CrossPairAnalyzer};
+use crate::portfolio::split_symbol;
+
+/// Currencies whose pairs follow commodity prices
+const COMMODITY_CURRENCIES: [&str; 3] = ["AUD", "NZD", "CAD"];
+
+/// How the distance between two clusters is taken from their members'
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Linkage {
+    /// Closest members
+    Single,
+    /// Mean over every pair of members
+    #[default]
+    Average,
+}
+
+impl FromStr for Linkage {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "single" => Ok(Linkage::Single),
+            "average" => Ok(Linkage::Average),
+            other => bail!("Unknown linkage '{}' (expected single or average)", other),
+        }
+    }
+}
+
+impl fmt::Display for Linkage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Linkage::Single => write!(f, "single"),
+            Linkage::Average => write!(f, "average"),
+        }
+    }
+}
+
+/// Clustering settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClusterConfig {
+    pub linkage: Linkage,
+    /// Clusters further apart than this stay separate blocs; 0.5 is ρ = 0.5
+    pub cut_distance: f64,
+}
+
+impl Default for ClusterConfig {
+    fn default() -> Self {
+        Self {
+            linkage: Linkage::Average,
+            cut_distance: 0.5,
+        }
+    }
+}
+
+/// One step of the dendrogram. Nodes below the number of pairs are the pairs
+/// themselves, in [`ClusterReport::pairs`] order; node `pairs.len() + i` is the
+/// cluster formed by merge `i`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterMerge {
+    pub left: usize,
+    pub right: usize,
+    pub distance: f64,
+    /// Pairs in the merged cluster
+    pub size: usize,
+}
+
+/// Pairs that move together closely enough to count as one exposure
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurrencyBloc {
+    /// "USD bloc", "commodity currencies", or the pair itself when alone
+    pub name: String,
+    /// Members in dendrogram order
+    pub pairs: Vec<String>,
+    /// Mean correlation between members; 1 for a lone pair
+    pub mean_correlation: f64,
+}
+
+/// Hierarchical clustering of the pairs in a correlation matrix
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterReport {
+    /// Leaves of the dendrogram, sorted
+    pub pairs: Vec<String>,
+    pub linkage: Linkage,
+    pub cut_distance: f64,
+    /// Merges, closest first; one fewer than the pairs
+    pub merges: Vec<ClusterMerge>,
+    /// Blocs at the cut, largest first
+    pub blocs: Vec<CurrencyBloc>,
+}
+
+impl ClusterReport {
+    /// Pairs ordered so every cluster is contiguous
+    pub fn leaf_order(&self) -> Vec<String> {
+        let mut order = Vec::with_capacity(self.pairs.len());
+        if let Some(root) = self.root() {
+            self.collect_leaves(root, &mut order);
+        }
+        order.into_iter().map(|leaf| self.pairs[leaf].clone()).collect()
+    }
+
+    /// The bloc `symbol` falls in
+    pub fn bloc_of(&self, symbol: &str) -> Option<&CurrencyBloc> {
+        let symbol = symbol.to_uppercase();
+        self.blocs.iter().find(|bloc| bloc.pairs.contains(&symbol))
+    }
+
+    /// Distance at which `a` and `b` first share a cluster
+    pub fn joined_at(&self, a: &str, b: &str) -> Option<f64> {
+        let (a, b) = (self.index_of(a)?, self.index_of(b)?);
+        if a == b {
+            return Some(0.0);
+        }
+        self.merges.iter().enumerate()
+            .find(|(i, _)| {
+                let leaves = self.leaves(self.pairs.len() + i);
+                leaves.contains(&a) && leaves.contains(&b)
+            })
+            .map(|(_, merge)| merge.distance)
+    }
+
+    /// Dendrogram as a table: pairs in leaf order, their bloc, and a bar for
+    /// the distance at which each joins the pair below it
+    pub fn render(&self) -> String {
+        const BAR_WIDTH: usize = 20;
+        let order = self.leaf_order();
+        let mut out = format!("{} linkage over |1 - ρ|, blocs cut at {:.2}\n", self.linkage, self.cut_distance);
+        out.push_str("╔════════════╦══════════════════════╦═══════════════════════════════╗\n");
+        out.push_str("║ Pair       ║ Bloc                 ║ Joins next at                 ║\n");
+        out.push_str("╠════════════╬══════════════════════╬═══════════════════════════════╣\n");
+        for (i, symbol) in order.iter().enumerate() {
+            let bloc = self.bloc_of(symbol).map(|bloc| bloc.name.as_str()).unwrap_or("");
+            let joins = match order.get(i + 1).and_then(|next| self.joined_at(symbol, next)) {
+                Some(distance) => {
+                    let filled = ((distance / 2.0).clamp(0.0, 1.0) * BAR_WIDTH as f64).round() as usize;
+                    let marker = if distance <= self.cut_distance { '█' } else { '▒' };
+                    format!("{}{} {:.3}", marker.to_string().repeat(filled), " ".repeat(BAR_WIDTH - filled), distance)
+                }
+                None => String::new(),
+            };
+            out.push_str(&format!("║ {:10} ║ {:20} ║ {:29} ║\n", symbol, bloc, joins));
+        }
+        out.push_str("╚════════════╩══════════════════════╩═══════════════════════════════╝\n");
+        out
+    }
+
+    fn root(&self) -> Option<usize> {
+        match self.pairs.len() {
+            0 => None,
+            1 => Some(0),
+            n => Some(n + self.merges.len() - 1),
+        }
+    }
+
+    fn index_of(&self, symbol: &str) -> Option<usize> {
+        let symbol = symbol.to_uppercase();
+        self.pairs.iter().position(|pair| *pair == symbol)
+    }
+
+    fn collect_leaves(&self, node: usize, out: &mut Vec<usize>) {
+        match node.checked_sub(self.pairs.len()).and_then(|merge| self.merges.get(merge)) {
+            Some(merge) => {
+                self.collect_leaves(merge.left, out);
+                self.collect_leaves(merge.right, out);
+            }
+            None => out.push(node),
+        }
+    }
+
+    fn leaves(&self, node: usize) -> Vec<usize> {
+        let mut leaves = Vec::new();
+        self.collect_leaves(node, &mut leaves);
+        leaves
+    }
+}
+
+impl CrossPairAnalyzer {
+    /// Cluster the pairs of `correlations` into blocs; pairs without a
+    /// correlation between them count as uncorrelated
+    pub fn cluster_pairs(
+        &self,
+        correlations: &HashMap<(String, String), CorrelationResult>,
+        config: &ClusterConfig,
+    ) -> Result<ClusterReport> {
+        if !config.cut_distance.is_finite() || config.cut_distance < 0.0 {
+            bail!("Cluster cut distance must be non-negative, got {}", config.cut_distance);
+        }
+        let mut pairs: Vec<String> = correlations.keys()
+            .flat_map(|(pair1, pair2)| [pair1.to_uppercase(), pair2.to_uppercase()])
+            .collect();
+        pairs.sort();
+        pairs.dedup();
+        let n = pairs.len();
+        let mut distance = vec![vec![1.0; n]; n];
+        for result in correlations.values() {
+            let (Ok(i), Ok(j)) = (pairs.binary_search(&result.pair1.to_uppercase()), pairs.binary_search(&result.pair2.to_uppercase())) else { continue };
+            distance[i][j] = (1.0 - result.correlation).abs();
+            distance[j][i] = distance[i][j];
+        }
+        for (i, row) in distance.iter_mut().enumerate() {
+            row[i] = 0.0;
+        }
+
+        // Active clusters as (node, leaves); the closest two merge until one is left
+        let mut clusters: Vec<(usize, Vec<usize>)> = (0..n).map(|leaf| (leaf, vec![leaf])).collect();
+        let mut merges = Vec::with_capacity(n.saturating_sub(1));
+        let mut blocs: Vec<Vec<usize>> = Vec::new();
+        while clusters.len() > 1 {
+            let mut closest = (0, 1, f64::INFINITY);
+            for a in 0..clusters.len() {
+                for b in (a + 1)..clusters.len() {
+                    let d = linkage_distance(&distance, &clusters[a].1, &clusters[b].1, config.linkage);
+                    if d < closest.2 {
+                        closest = (a, b, d);
+                    }
+                }
+            }
+            let (a, b, d) = closest;
+            if d > config.cut_distance && blocs.is_empty() {
+                blocs = clusters.iter().map(|(_, leaves)| leaves.clone()).collect();
+            }
+            let (right, right_leaves) = clusters.remove(b);
+            let (left, mut leaves) = clusters.remove(a);
+            leaves.extend(right_leaves);
+            merges.push(ClusterMerge { left, right, distance: d, size: leaves.len() });
+            clusters.push((n + merges.len() - 1, leaves));
+        }
+        if blocs.is_empty() {
+            blocs = clusters.into_iter().map(|(_, leaves)| leaves).collect();
+        }
+
+        let mut report = ClusterReport { pairs, linkage: config.linkage, cut_distance: config.cut_distance, merges, blocs: Vec::new() };
+        let order: HashMap<String, usize> = report.leaf_order().into_iter().enumerate().map(|(i, pair)| (pair, i)).collect();
+        report.blocs = blocs.iter()
+            .map(|leaves| {
+                let mut members: Vec<String> = leaves.iter().map(|&leaf| report.pairs[leaf].clone()).collect();
+                members.sort_by_key(|pair| order[pair]);
+                let within: Vec<f64> = leaves.iter().enumerate()
+                    .flat_map(|(k, &i)| leaves[k + 1..].iter().map(move |&j| (i, j)))
+                    .map(|(i, j)| 1.0 - distance[i][j])
+                    .collect();
+                let mean_correlation = if within.is_empty() { 1.0 } else { within.iter().sum::<f64>() / within.len() as f64 };
+                CurrencyBloc { name: bloc_name(&members), pairs: members, mean_correlation }
+            })
+            .collect();
+        report.blocs.sort_by(|a, b| b.pairs.len().cmp(&a.pairs.len()).then_with(|| order[&a.pairs[0]].cmp(&order[&b.pairs[0]])));
+        Ok(report)
+    }
+
+    /// Print the dendrogram table and blocs of a cluster report
+    pub fn print_cluster_report(&self, report: &ClusterReport) {
+        println!("\n🌳 Currency Blocs:");
+        print!("{}", report.render());
+        for bloc in report.blocs.iter().filter(|bloc| bloc.pairs.len() > 1) {
+            println!("   {} ({} pairs, mean ρ {:.2}): {}", bloc.name, bloc.pairs.len(), bloc.mean_correlation, bloc.pairs.join(", "));
+        }
+    }
+}
+
+fn linkage_distance(distance: &[Vec<f64>], a: &[usize], b: &[usize], linkage: Linkage) -> f64 {
+    let between = a.iter().flat_map(|&i| b.iter().map(move |&j| distance[i][j]));
+    match linkage {
+        Linkage::Single => between.fold(f64::INFINITY, f64::min),
+        Linkage::Average => between.sum::<f64>() / (a.len() * b.len()) as f64,
+    }
+}
+
+/// Commodity currencies when every pair has one, otherwise the currency every pair shares
+fn bloc_name(pairs: &[String]) -> String {
+    if pairs.len() == 1 {
+        return pairs[0].clone();
+    }
+    let currencies: Vec<(String, String)> = pairs.iter().map(|pair| split_symbol(pair)).collect();
+    if currencies.iter().all(|(base, quote)| COMMODITY_CURRENCIES.contains(&base.as_str()) || COMMODITY_CURRENCIES.contains(&quote.as_str())) {
+        return "commodity currencies".to_string();
+    }
+    let (base, quote) = &currencies[0];
+    let shared = [base, quote].into_iter()
+        .find(|currency| !currency.is_empty() && currencies.iter().all(|(b, q)| b == *currency || q == *currency));
+    match shared {
+        Some(currency) => format!("{} bloc", currency),
+        None => "mixed".to_string(),
+    }
+}
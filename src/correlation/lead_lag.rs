@@ -0,0 +1,224 @@
+//! # Lead-Lag Relationships
+//!
+//! Pairs whose returns are correlated with another's a few bars later: returns
+//! are cross-correlated at lags up to `max_lag` either way, and the strongest
+//! non-zero lag is kept when its `|ρ|·√n` z-score clears `min_z`.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::regime::pearson;
+use super::CrossPairAnalyzer;
+use crate::data::ForexDataPoint;
+
+/// Lagged cross-correlation settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LeadLagConfig {
+    /// Bars tried either way
+    pub max_lag: usize,
+    /// Smallest |ρ| at the lead worth reporting
+    pub min_correlation: f64,
+    /// Smallest `|ρ|·√n` at the lead
+    pub min_z: f64,
+    /// Fewest overlapping returns a lag is correlated over
+    pub min_samples: usize,
+}
+
+impl Default for LeadLagConfig {
+    fn default() -> Self {
+        Self {
+            max_lag: 10,
+            min_correlation: 0.2,
+            min_z: 4.0,
+            min_samples: 60,
+        }
+    }
+}
+
+/// Correlation of the first series' returns with the second's `lag` bars later;
+/// negative lags have the second series first
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LaggedCorrelation {
+    pub lag: i64,
+    pub correlation: f64,
+    /// Overlapping returns the correlation is taken over
+    pub samples: usize,
+}
+
+impl LaggedCorrelation {
+    /// `|ρ|·√n`
+    pub fn z_score(&self) -> f64 {
+        self.correlation.abs() * (self.samples as f64).sqrt()
+    }
+}
+
+/// Cross-correlation of two pairs' returns at every lag tried
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeadLagProfile {
+    pub pair1: String,
+    pub pair2: String,
+    /// From `-max_lag` to `max_lag`, skipping lags with too few returns
+    pub correlations: Vec<LaggedCorrelation>,
+}
+
+impl LeadLagProfile {
+    /// The lag with the largest |ρ|, zero included
+    pub fn peak(&self) -> Option<LaggedCorrelation> {
+        strongest(self.correlations.iter())
+    }
+
+    /// The non-zero lag with the largest |ρ|
+    pub fn peak_lead(&self) -> Option<LaggedCorrelation> {
+        strongest(self.correlations.iter().filter(|lagged| lagged.lag != 0))
+    }
+
+    pub fn at(&self, lag: i64) -> Option<LaggedCorrelation> {
+        self.correlations.iter().find(|lagged| lagged.lag == lag).copied()
+    }
+}
+
+/// One pair's returns predicting another's a fixed number of bars later
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LeadLagOpportunity {
+    pub leader: String,
+    pub follower: String,
+    /// Bars between a leader return and the follower return it predicts
+    pub lag_bars: usize,
+    /// Correlation at the lead; negative when the follower moves the other way
+    pub correlation: f64,
+    /// Correlation of the same returns at lag zero
+    pub contemporaneous: f64,
+    pub z_score: f64,
+    /// Follower return implied by the leader's last `lag_bars` returns, still to come
+    pub pending_move: f64,
+    /// Last bar both series share
+    pub as_of: DateTime<Utc>,
+}
+
+impl LeadLagOpportunity {
+    /// "EURUSD leads EURCHF by 3 bars (ρ 0.42)"
+    pub fn summary(&self) -> String {
+        let bars = if self.lag_bars == 1 { "bar" } else { "bars" };
+        format!("{} leads {} by {} {} (ρ {:.2})", self.leader, self.follower, self.lag_bars, bars, self.correlation)
+    }
+}
+
+impl CrossPairAnalyzer {
+    /// Cross-correlation of the pairs' returns at lags from `-max_lag` to `max_lag`
+    pub fn cross_correlation(
+        &self,
+        pair1: &str,
+        data1: &[ForexDataPoint],
+        pair2: &str,
+        data2: &[ForexDataPoint],
+        config: &LeadLagConfig,
+    ) -> LeadLagProfile {
+        let (_, returns1, returns2) = self.aligned_returns(data1, data2);
+        let correlations = (-(config.max_lag as i64)..=config.max_lag as i64)
+            .filter_map(|lag| {
+                let (a, b) = lagged(&returns1, &returns2, lag);
+                if a.len() < config.min_samples.max(3) {
+                    return None;
+                }
+                pearson(a, b).map(|correlation| LaggedCorrelation { lag, correlation, samples: a.len() })
+            })
+            .collect();
+        LeadLagProfile { pair1: pair1.to_string(), pair2: pair2.to_string(), correlations }
+    }
+
+    /// The lead of one pair over the other, when its correlation is large and significant
+    pub fn lead_lag(
+        &self,
+        pair1: &str,
+        data1: &[ForexDataPoint],
+        pair2: &str,
+        data2: &[ForexDataPoint],
+        config: &LeadLagConfig,
+    ) -> Option<LeadLagOpportunity> {
+        let profile = self.cross_correlation(pair1, data1, pair2, data2, config);
+        let lead = profile.peak_lead()?;
+        if lead.correlation.abs() < config.min_correlation || lead.z_score() < config.min_z {
+            return None;
+        }
+        let (timestamps, returns1, returns2) = self.aligned_returns(data1, data2);
+        let (leader, follower, leader_returns, follower_returns) = if lead.lag > 0 {
+            (pair1, pair2, &returns1, &returns2)
+        } else {
+            (pair2, pair1, &returns2, &returns1)
+        };
+        let lag_bars = lead.lag.unsigned_abs() as usize;
+        // Regression slope of follower on leader returns, scaling the echo to the follower's volatility
+        let beta = lead.correlation * std_dev(follower_returns) / std_dev(leader_returns).max(f64::EPSILON);
+        let unechoed: f64 = leader_returns.iter().rev().take(lag_bars).sum();
+        Some(LeadLagOpportunity {
+            leader: leader.to_string(),
+            follower: follower.to_string(),
+            lag_bars,
+            correlation: lead.correlation,
+            contemporaneous: profile.at(0).map_or(0.0, |lagged| lagged.correlation),
+            z_score: lead.z_score(),
+            pending_move: beta * unechoed,
+            as_of: *timestamps.last()?,
+        })
+    }
+
+    /// Leads between every pair combination in `data_map`, strongest first
+    pub fn find_lead_lag_opportunities(
+        &self,
+        data_map: &HashMap<String, Vec<ForexDataPoint>>,
+        config: &LeadLagConfig,
+    ) -> Vec<LeadLagOpportunity> {
+        let mut pairs: Vec<&String> = data_map.keys().collect();
+        pairs.sort();
+        let mut opportunities = Vec::new();
+        for i in 0..pairs.len() {
+            for j in (i + 1)..pairs.len() {
+                opportunities.extend(self.lead_lag(pairs[i], &data_map[pairs[i]], pairs[j], &data_map[pairs[j]], config));
+            }
+        }
+        opportunities.sort_by(|a, b| b.correlation.abs().total_cmp(&a.correlation.abs()));
+        opportunities
+    }
+
+    /// Print lead-lag opportunities
+    pub fn print_lead_lag_opportunities(&self, opportunities: &[LeadLagOpportunity]) {
+        println!("\n⏩ Lead-Lag Relationships:");
+        println!("╔════════════╦════════════╦══════╦═════════════╦═════════════╦═════════╗");
+        println!("║ Leader     ║ Follower   ║ Lag  ║ Correlation ║ At lag 0    ║ z-score ║");
+        println!("╠════════════╬════════════╬══════╬═════════════╬═════════════╬═════════╣");
+        for opportunity in opportunities.iter().take(15) {
+            println!("║ {:10} ║ {:10} ║ {:4} ║ {:11.3} ║ {:11.3} ║ {:7.1} ║",
+                     opportunity.leader, opportunity.follower, opportunity.lag_bars,
+                     opportunity.correlation, opportunity.contemporaneous, opportunity.z_score);
+        }
+        println!("╚════════════╩════════════╩══════╩═════════════╩═════════════╩═════════╝");
+    }
+}
+
+/// `a[t]` against `b[t + lag]`
+fn lagged<'a>(a: &'a [f64], b: &'a [f64], lag: i64) -> (&'a [f64], &'a [f64]) {
+    let n = a.len().min(b.len());
+    let shift = lag.unsigned_abs() as usize;
+    if shift >= n {
+        return (&[], &[]);
+    }
+    if lag >= 0 {
+        (&a[..n - shift], &b[shift..n])
+    } else {
+        (&a[shift..n], &b[..n - shift])
+    }
+}
+
+fn strongest<'a>(correlations: impl Iterator<Item = &'a LaggedCorrelation>) -> Option<LaggedCorrelation> {
+    correlations.max_by(|a, b| a.correlation.abs().total_cmp(&b.correlation.abs())).copied()
+}
+
+fn std_dev(values: &[f64]) -> f64 {
+    if values.len() < 2 {
+        return 0.0;
+    }
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64).sqrt()
+}
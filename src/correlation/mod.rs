@@ -4,11 +4,20 @@ use std::collections::HashMap;
 use chrono::{DateTime, Utc, Duration};
 
 use crate::data::ForexDataPoint;
+use crate::units::{Pips, PriceDelta};
+
+pub mod cluster;
+pub mod lead_lag;
+pub mod regime;
+
+pub use cluster::{ClusterConfig, ClusterReport, CurrencyBloc, Linkage};
+pub use lead_lag::{LaggedCorrelation, LeadLagConfig, LeadLagOpportunity, LeadLagProfile};
+pub use regime::{CorrelationPoint, CorrelationRegimeChange, RegimeDetectionConfig};
 
 /// Cross-pair correlation analyzer for arbitrage opportunities
 pub struct CrossPairAnalyzer {
     correlation_threshold: f64,
-    arbitrage_threshold: f64,
+    arbitrage_threshold: Pips,
 }
 
 #[derive(Debug, Clone)]
@@ -17,7 +26,8 @@ pub struct CorrelationResult {
     pub pair2: String,
     pub correlation: f64,
     pub strength: CorrelationStrength,
-    pub arbitrage_potential: f64,
+    /// Typical mispricing of `pair1` against `pair2`, in `pair1` prices
+    pub arbitrage_potential: PriceDelta,
 }
 
 #[derive(Debug, Clone)]
@@ -33,17 +43,19 @@ pub enum CorrelationStrength {
 pub struct ArbitrageOpportunity {
     pub primary_pair: String,
     pub correlated_pairs: Vec<String>,
-    pub expected_move: f64,
+    /// In `primary_pair` prices
+    pub expected_move: PriceDelta,
     pub confidence: f64,
     pub time_window: Duration,
-    pub profit_potential: f64,
+    /// In `primary_pair` prices
+    pub profit_potential: PriceDelta,
 }
 
 impl CrossPairAnalyzer {
     pub fn new() -> Self {
         Self {
             correlation_threshold: 0.7,
-            arbitrage_threshold: 0.001, // 10 pips
+            arbitrage_threshold: Pips(10.0),
         }
     }
 
@@ -191,17 +203,17 @@ impl CrossPairAnalyzer {
         }
     }
 
-    /// Calculate arbitrage potential between two pairs
+    /// Calculate arbitrage potential between two pairs, in prices of the first
     fn calculate_arbitrage_potential(
         &self,
         data1: &[ForexDataPoint],
         data2: &[ForexDataPoint],
         correlation: f64
-    ) -> Result<f64> {
+    ) -> Result<PriceDelta> {
         let aligned_data = self.align_data_by_timestamp(data1, data2);
         
         if aligned_data.len() < 10 {
-            return Ok(0.0);
+            return Ok(PriceDelta(0.0));
         }
         
         // Calculate price ratio deviations
@@ -217,10 +229,11 @@ impl CrossPairAnalyzer {
             variance.sqrt()
         };
         
-        // Arbitrage potential based on correlation strength and ratio volatility
-        let potential = correlation.abs() * std_dev * 1000.0; // Convert to pips
+        // Arbitrage potential based on correlation strength and ratio volatility:
+        // a ratio deviation moves the first pair's price by that much times the second's
+        let mean_price2 = aligned_data.iter().map(|(_, p2)| p2.close).sum::<f64>() / aligned_data.len() as f64;
         
-        Ok(potential)
+        Ok(PriceDelta(correlation.abs() * std_dev * mean_price2))
     }
 
     /// Find arbitrage opportunities
@@ -237,7 +250,7 @@ impl CrossPairAnalyzer {
         let strong_correlations: Vec<&CorrelationResult> = correlations.values()
             .filter(|result| {
                 matches!(result.strength, CorrelationStrength::VeryStrong | CorrelationStrength::Strong)
-                && result.arbitrage_potential > self.arbitrage_threshold
+                && result.arbitrage_potential.to_pips(&result.pair1) > self.arbitrage_threshold
             })
             .collect();
         
@@ -254,8 +267,11 @@ impl CrossPairAnalyzer {
             opportunities.push(opportunity);
         }
         
-        // Sort by profit potential
-        opportunities.sort_by(|a, b| b.profit_potential.partial_cmp(&a.profit_potential).unwrap());
+        // Sort by profit potential, in pips so pairs quoted at different scales compare
+        opportunities.sort_by(|a, b| {
+            let pips = |opportunity: &ArbitrageOpportunity| opportunity.profit_potential.to_pips(&opportunity.primary_pair);
+            pips(b).partial_cmp(&pips(a)).unwrap()
+        });
         
         println!("✅ Found {} arbitrage opportunities", opportunities.len());
         Ok(opportunities)
@@ -282,7 +298,7 @@ impl CrossPairAnalyzer {
             
             println!("║ {:10} ║ {:10} ║ {:11.3} ║ {:13} ║ {:13.1} ║",
                      result.pair1, result.pair2, result.correlation, 
-                     strength_str, result.arbitrage_potential * 10000.0); // Convert to pips
+                     strength_str, result.arbitrage_potential.to_pips(&result.pair1).0);
         }
         
         println!("╚════════════╩════════════╩═════════════╩═══════════════╩═══════════════╝");
@@ -302,7 +318,7 @@ impl CrossPairAnalyzer {
                      if correlated.len() > 13 { &correlated[..10] } else { &correlated },
                      opp.confidence * 100.0,
                      format!("{}min", opp.time_window.num_minutes()),
-                     opp.profit_potential * 10000.0); // Convert to pips
+                     opp.profit_potential.to_pips(&opp.primary_pair).0);
         }
         
         println!("╚════════════╩═══════════════╩════════════╩═════════════╩═══════════════╝");
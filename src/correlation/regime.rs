@@ -0,0 +1,221 @@
+//! # Correlation Regimes
+//!
+//! Rolling correlation of returns with a two-sided CUSUM on the Fisher
+//! transform of block correlations, which finds the point where two pairs'
+//! correlation shifts without mistaking a joint volatility jump for one.
+
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::CrossPairAnalyzer;
+use crate::data::ForexDataPoint;
+
+/// Rolling correlation and change-point detection settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RegimeDetectionConfig {
+    /// Returns per rolling correlation
+    pub window: usize,
+    /// Returns per block correlation watched by the CUSUM
+    pub block_bars: usize,
+    /// Blocks each regime's reference is fitted on
+    pub reference_blocks: usize,
+    /// Shift in standard errors ignored per block (CUSUM `k`)
+    pub drift: f64,
+    /// Cumulative sum, in standard errors, at which a change is declared (CUSUM `h`)
+    pub threshold: f64,
+}
+
+impl Default for RegimeDetectionConfig {
+    fn default() -> Self {
+        Self {
+            window: 60,
+            block_bars: 24,
+            reference_blocks: 24,
+            drift: 0.75,
+            threshold: 5.0,
+        }
+    }
+}
+
+/// Correlation of returns over the window ending at `timestamp`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorrelationPoint {
+    pub timestamp: DateTime<Utc>,
+    pub correlation: f64,
+}
+
+/// A shift in the correlation of two pairs' returns
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorrelationRegimeChange {
+    pub pair1: String,
+    pub pair2: String,
+    /// First bar of the block where the CUSUM last left zero
+    pub change_point: DateTime<Utc>,
+    /// Last bar of the block in which the CUSUM crossed the threshold
+    pub detected_at: DateTime<Utc>,
+    /// Reference correlation of the regime that ended
+    pub correlation_before: f64,
+    /// Correlation from the change point to the detection
+    pub correlation_after: f64,
+    /// Fisher z statistic of the difference between the two correlations
+    pub z_score: f64,
+}
+
+impl CorrelationRegimeChange {
+    /// Confidence in the change, full at three standard errors
+    pub fn confidence(&self) -> f64 {
+        (self.z_score.abs() / 3.0).min(1.0)
+    }
+}
+
+impl CrossPairAnalyzer {
+    /// Correlation of returns over each `window` returns, one point per bar once the window is full
+    pub fn rolling_correlation(&self, data1: &[ForexDataPoint], data2: &[ForexDataPoint], window: usize) -> Vec<CorrelationPoint> {
+        let (timestamps, returns1, returns2) = self.aligned_returns(data1, data2);
+        if window < 3 {
+            return Vec::new();
+        }
+        (window..=returns1.len())
+            .filter_map(|end| pearson(&returns1[end - window..end], &returns2[end - window..end])
+                .map(|correlation| CorrelationPoint { timestamp: timestamps[end - 1], correlation }))
+            .collect()
+    }
+
+    /// Shifts in the correlation of `pair1` and `pair2` returns, oldest first
+    pub fn detect_regime_changes(
+        &self,
+        pair1: &str,
+        data1: &[ForexDataPoint],
+        pair2: &str,
+        data2: &[ForexDataPoint],
+        config: &RegimeDetectionConfig,
+    ) -> Result<Vec<CorrelationRegimeChange>> {
+        if config.block_bars < 4 || config.reference_blocks < 2 || config.threshold <= 0.0 || config.drift < 0.0 {
+            bail!("Regime detection needs blocks of at least 4 returns, 2 reference blocks and a positive threshold");
+        }
+        let (timestamps, returns1, returns2) = self.aligned_returns(data1, data2);
+        let blocks: Vec<Option<f64>> = returns1.chunks_exact(config.block_bars)
+            .zip(returns2.chunks_exact(config.block_bars))
+            .map(|(a, b)| pearson(a, b).map(fisher))
+            .collect();
+        let bars = |block: usize| block * config.block_bars;
+        let standard_error = 1.0 / (config.block_bars as f64 - 3.0).sqrt();
+        let mut changes = Vec::new();
+        let mut start = 0;
+        while start + config.reference_blocks < blocks.len() {
+            let reference_end = start + config.reference_blocks;
+            let reference_blocks: Vec<f64> = blocks[start..reference_end].iter().flatten().copied().collect();
+            let reference = pearson(&returns1[bars(start)..bars(reference_end)], &returns2[bars(start)..bars(reference_end)]);
+            let (Some(reference), Some((mean, spread))) = (reference, mean_and_spread(&reference_blocks)) else {
+                start = reference_end;
+                continue;
+            };
+            // Fat tails widen the spread of block correlations beyond the normal-theory error
+            let spread = spread.max(standard_error);
+            let (mut upper, mut lower) = (0.0_f64, 0.0_f64);
+            let (mut upper_start, mut lower_start) = (reference_end, reference_end);
+            let mut detection = None;
+
+            for (block, z) in blocks.iter().enumerate().skip(reference_end) {
+                let Some(z) = z else { continue };
+                let step = (z - mean) / spread;
+                if upper == 0.0 {
+                    upper_start = block;
+                }
+                if lower == 0.0 {
+                    lower_start = block;
+                }
+                upper = (upper + step - config.drift).max(0.0);
+                lower = (lower - step - config.drift).max(0.0);
+                if upper > config.threshold {
+                    detection = Some((upper_start, block));
+                    break;
+                }
+                if lower > config.threshold {
+                    detection = Some((lower_start, block));
+                    break;
+                }
+            }
+
+            let Some((change, detected)) = detection else { break };
+            let (first, last) = (bars(change), bars(detected + 1) - 1);
+            let after = pearson(&returns1[first..=last], &returns2[first..=last]).unwrap_or(0.0);
+            let (n_before, n_after) = (bars(reference_end - start) as f64, (last + 1 - first) as f64);
+            let z_score = (fisher(after) - fisher(reference)) / (1.0 / (n_before - 3.0) + 1.0 / (n_after - 3.0)).sqrt();
+            changes.push(CorrelationRegimeChange {
+                pair1: pair1.to_string(),
+                pair2: pair2.to_string(),
+                change_point: timestamps[first],
+                detected_at: timestamps[last],
+                correlation_before: reference,
+                correlation_after: after,
+                z_score,
+            });
+            start = detected + 1;
+        }
+        Ok(changes)
+    }
+
+    /// Regime changes of every pair of series in `data_map`, oldest first
+    pub fn detect_all_regime_changes(
+        &self,
+        data_map: &HashMap<String, Vec<ForexDataPoint>>,
+        config: &RegimeDetectionConfig,
+    ) -> Result<Vec<CorrelationRegimeChange>> {
+        let mut pairs: Vec<&String> = data_map.keys().collect();
+        pairs.sort();
+        let mut changes = Vec::new();
+        for i in 0..pairs.len() {
+            for j in (i + 1)..pairs.len() {
+                changes.extend(self.detect_regime_changes(pairs[i], &data_map[pairs[i]], pairs[j], &data_map[pairs[j]], config)?);
+            }
+        }
+        changes.sort_by_key(|change| change.detected_at);
+        if !changes.is_empty() {
+            println!("🔀 {} correlation regime changes", changes.len());
+        }
+        Ok(changes)
+    }
+
+    /// Returns of the bars both series share, each stamped with the bar it ends at
+    pub(super) fn aligned_returns(&self, data1: &[ForexDataPoint], data2: &[ForexDataPoint]) -> (Vec<DateTime<Utc>>, Vec<f64>, Vec<f64>) {
+        let aligned = self.align_data_by_timestamp(data1, data2);
+        let prices1: Vec<f64> = aligned.iter().map(|(p1, _)| p1.close).collect();
+        let prices2: Vec<f64> = aligned.iter().map(|(_, p2)| p2.close).collect();
+        let timestamps = aligned.iter().skip(1).map(|(p1, _)| p1.timestamp).collect();
+        (timestamps, self.calculate_returns(&prices1), self.calculate_returns(&prices2))
+    }
+}
+
+pub(super) fn pearson(a: &[f64], b: &[f64]) -> Option<f64> {
+    let n = a.len().min(b.len());
+    if n < 3 {
+        return None;
+    }
+    let (mean_a, mean_b) = (a[..n].iter().sum::<f64>() / n as f64, b[..n].iter().sum::<f64>() / n as f64);
+    let (mut covariance, mut var_a, mut var_b) = (0.0, 0.0, 0.0);
+    for i in 0..n {
+        let (da, db) = (a[i] - mean_a, b[i] - mean_b);
+        covariance += da * db;
+        var_a += da * da;
+        var_b += db * db;
+    }
+    (var_a > 0.0 && var_b > 0.0).then(|| covariance / (var_a * var_b).sqrt())
+}
+
+fn mean_and_spread(values: &[f64]) -> Option<(f64, f64)> {
+    if values.len() < 3 {
+        return None;
+    }
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    let spread = (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64).sqrt();
+    (spread > 0.0).then_some((mean, spread))
+}
+
+/// Fisher transform, clamped short of ±1
+fn fisher(correlation: f64) -> f64 {
+    correlation.clamp(-0.999, 0.999).atanh()
+}
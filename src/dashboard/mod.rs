@@ -1,6 +1,11 @@
 //! # Real-Time Forex Pattern Recognition Dashboard
 //! 
-//! CLI dashboard for live pattern monitoring and analysis
+//! CLI dashboard for live pattern monitoring and analysis of the pairs of a
+//! [`MultiCurrencyManager`], one pair at a time or all side by side
+
+pub mod server;
+pub mod what_if;
+pub mod multi_pair;
 
 use anyhow::Result;
 use crossterm::{
@@ -20,33 +25,62 @@ use ratatui::{
     },
     Frame, Terminal,
 };
-use std::collections::VecDeque;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::time::interval;
 
-use crate::core::{TimeSymmetricEngine, EngineConfig};
-use crate::data::{ForexDataPoint, ForexDataManager, DataConfig, RealTimeDataFeed};
-use crate::patterns::{PatternRecognizer, PatternConfig, HiddenCycle};
+use crate::data::{ForexDataPoint, DataConfig};
+use crate::data::health::FeedState;
+use crate::multi_currency::MultiCurrencyManager;
+use crate::patterns::HiddenCycle;
 use crate::symmetry::TemporalSymmetry;
+use crate::signal::{CompositeScore, CompositeScoreConfig, CompositeScorer};
+use multi_pair::{run_market_update, MultiPairView, PairPanel, PairStatus};
+use what_if::{CycleEditor, WhatIfOutcome, AMPLITUDE_STEP, PHASE_STEP};
+
+/// Pair analyzed when none is given
+const DEFAULT_PAIR: &str = "EURUSD";
+
+/// Closes charted on the overview
+const CHART_BARS: usize = 100;
+
+/// Time between market updates the dashboard runs itself
+const UPDATE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Tabs in header order
+const TABS: [&str; 6] = ["Overview", "Patterns", "Symmetries", "Performance", "What-If", "Pairs"];
+
+/// Index of the what-if cycle editor tab
+const WHAT_IF_TAB: usize = 4;
+
+/// Index of the tab comparing every pair
+const PAIRS_TAB: usize = 5;
 
 /// Dashboard application state
 pub struct DashboardApp {
-    // Core components
-    engine: TimeSymmetricEngine,
-    data_manager: ForexDataManager,
-    pattern_recognizer: PatternRecognizer,
-    data_feed: RealTimeDataFeed,
+    // Trading system the pairs are read from
+    manager: Arc<MultiCurrencyManager>,
+    view: MultiPairView,
+    /// Whether the dashboard runs the market updates or another loop sharing the manager does
+    drives_updates: bool,
     
     // UI state
     current_tab: usize,
     should_quit: bool,
     last_update: Instant,
     
-    // Data
-    price_history: VecDeque<(f64, f64)>, // (timestamp, price)
+    // Selected pair
+    current_pair: String,
+    price_history: Vec<(f64, f64)>, // (bar, price)
     detected_cycles: Vec<HiddenCycle>,
     temporal_symmetries: Vec<TemporalSymmetry>,
-    current_pair: String,
+    recent_bars: Vec<ForexDataPoint>,
+    composite_scorer: CompositeScorer,
+    score_history: Vec<(f64, f64)>, // (bar, score)
+    latest_score: Option<CompositeScore>,
+    cycle_editor: CycleEditor,
+    /// Forecast and score under the detected and the edited cycles
+    what_if: Option<(WhatIfOutcome, WhatIfOutcome)>,
     
     // Performance metrics
     pattern_strength: f64,
@@ -58,29 +92,46 @@ pub struct DashboardApp {
 impl DashboardApp {
     /// Create new dashboard application
     pub async fn new() -> Result<Self> {
-        let engine_config = EngineConfig::default();
-        let engine = TimeSymmetricEngine::new(engine_config)?;
-        
-        let data_config = DataConfig::default();
-        let data_manager = ForexDataManager::new(data_config)?;
-        
-        let pattern_config = PatternConfig::default();
-        let pattern_recognizer = PatternRecognizer::new(pattern_config)?;
-        
-        let data_feed = RealTimeDataFeed::default().await?;
-        
+        Self::with_data_config(DataConfig::default()).await
+    }
+    
+    /// Create dashboard application reading history from a specific data configuration
+    pub async fn with_data_config(data_config: DataConfig) -> Result<Self> {
+        Self::with_pairs(data_config, &[DEFAULT_PAIR.to_string()]).await
+    }
+    
+    /// Dashboard over a manager of its own trading `pairs`, reading their history
+    /// as `data_config` says and running their market updates
+    pub async fn with_pairs(data_config: DataConfig, pairs: &[String]) -> Result<Self> {
+        let mut manager = MultiCurrencyManager::new().with_data_config(data_config);
+        manager.initialize_pairs(pairs).await?;
+        let mut app = Self::with_manager(Arc::new(manager)).await?;
+        app.drives_updates = true;
+        Ok(app)
+    }
+    
+    /// Dashboard over a manager shared with the trading system, whose own loop runs
+    /// the market updates; see [`Self::drive_updates`]
+    pub async fn with_manager(manager: Arc<MultiCurrencyManager>) -> Result<Self> {
+        let view = MultiPairView::capture(&manager).await;
+        let current_pair = view.pairs.first().map_or_else(|| DEFAULT_PAIR.to_string(), |pair| pair.symbol.clone());
         Ok(Self {
-            engine,
-            data_manager,
-            pattern_recognizer,
-            data_feed,
+            manager,
+            view,
+            drives_updates: false,
             current_tab: 0,
             should_quit: false,
             last_update: Instant::now(),
-            price_history: VecDeque::with_capacity(1000),
+            current_pair,
+            price_history: Vec::new(),
             detected_cycles: Vec::new(),
             temporal_symmetries: Vec::new(),
-            current_pair: "EURUSD".to_string(),
+            recent_bars: Vec::new(),
+            composite_scorer: CompositeScorer::new(CompositeScoreConfig::default())?,
+            score_history: Vec::new(),
+            latest_score: None,
+            cycle_editor: CycleEditor::default(),
+            what_if: None,
             pattern_strength: 0.0,
             symmetry_score: 0.0,
             prediction_accuracy: 0.0,
@@ -88,52 +139,93 @@ impl DashboardApp {
         })
     }
     
-    /// Initialize the dashboard
+    /// Run a market update on every pair each [`UPDATE_INTERVAL`], for managers no other loop updates
+    pub fn drive_updates(&mut self, drive: bool) {
+        self.drives_updates = drive;
+    }
+    
+    /// Initialize the dashboard, analyzing the manager's pairs unless some already are
     pub async fn initialize(&mut self) -> Result<()> {
-        self.engine.initialize().await?;
-        self.load_historical_data().await?;
+        let initialized = self.manager.pairs.read().await.values().any(|state| state.is_active);
+        if !initialized {
+            self.manager.initialize_all_pairs().await?;
+        }
+        self.refresh().await;
         Ok(())
     }
     
-    /// Load historical data for analysis
-    async fn load_historical_data(&mut self) -> Result<()> {
-        let data_path = std::path::PathBuf::from("FOREX DATA");
-        let historical_data = self.data_manager.load_data(
-            &data_path, 
-            &self.current_pair, 
-            "1D"
-        ).await?;
-        
-        // Initialize price history with recent data
-        for (i, point) in historical_data.iter().rev().take(100).enumerate() {
-            let timestamp = i as f64;
-            self.price_history.push_back((timestamp, point.close));
+    /// The trading system the dashboard shows
+    pub fn manager(&self) -> &Arc<MultiCurrencyManager> {
+        &self.manager
+    }
+    
+    /// Pairs and portfolio as last read from the manager
+    pub fn view(&self) -> &MultiPairView {
+        &self.view
+    }
+    
+    /// Symbol of the pair the per-pair tabs show
+    pub fn current_pair(&self) -> &str {
+        &self.current_pair
+    }
+    
+    /// Show the pair `offset` places after the current one, wrapping around
+    pub fn select_pair(&mut self, offset: isize) {
+        let count = self.view.pairs.len() as isize;
+        if count == 0 {
+            return;
         }
-        
-        // Perform initial pattern analysis
-        self.update_patterns(&historical_data).await?;
-        
-        Ok(())
+        let current = self.view.pairs.iter().position(|pair| pair.symbol == self.current_pair).unwrap_or(0) as isize;
+        self.current_pair = self.view.pairs[(current + offset).rem_euclid(count) as usize].symbol.clone();
+        self.load_pair();
     }
     
-    /// Update pattern analysis
-    async fn update_patterns(&mut self, data: &[ForexDataPoint]) -> Result<()> {
-        let start_time = Instant::now();
+    /// Read the pairs and portfolio again and follow the current pair's new bars and analysis
+    pub async fn refresh(&mut self) {
+        self.view = MultiPairView::capture(&self.manager).await;
+        if self.view.pair(&self.current_pair).is_none() {
+            if let Some(first) = self.view.pairs.first() {
+                self.current_pair = first.symbol.clone();
+            }
+        }
+        self.load_pair();
+    }
+    
+    /// Take the current pair's panel into the per-pair tabs; the what-if edits
+    /// survive as long as the pair and its cycles do
+    fn load_pair(&mut self) {
+        let Some(pair) = self.view.pair(&self.current_pair).cloned() else {
+            return;
+        };
+        let PairPanel { recent_bars, cycles, symmetries, composite_scores, .. } = pair;
         
-        // Extract temporal symmetries
-        self.temporal_symmetries = self.engine.extract_temporal_symmetries(data).await?;
+        let reanalyzed = !self.detected_cycles.iter().map(|cycle| &cycle.id).eq(cycles.iter().map(|cycle| &cycle.id))
+            || !self.temporal_symmetries.iter().map(|symmetry| &symmetry.id).eq(symmetries.iter().map(|symmetry| &symmetry.id));
+        if reanalyzed {
+            self.composite_scorer.set_cycles(&cycles);
+            self.composite_scorer.set_symmetries(&symmetries);
+            self.cycle_editor.set_cycles(&cycles);
+            self.detected_cycles = cycles;
+            self.temporal_symmetries = symmetries;
+        }
         
-        // Detect cycles
-        self.detected_cycles = self.pattern_recognizer.detect_cycles(data).await?;
+        let new_bars = self.recent_bars.last().map(|bar| bar.timestamp) != recent_bars.last().map(|bar| bar.timestamp)
+            || self.recent_bars.len() != recent_bars.len();
+        self.recent_bars = recent_bars;
+        self.price_history = self.recent_bars[self.recent_bars.len().saturating_sub(CHART_BARS)..].iter()
+            .enumerate()
+            .map(|(i, bar)| (i as f64, bar.close))
+            .collect();
+        self.score_history = composite_scores.iter().enumerate().map(|(i, score)| (i as f64, score.score)).collect();
+        self.latest_score = composite_scores.last().cloned();
+        if reanalyzed || new_bars {
+            self.refresh_what_if();
+        }
         
         // Calculate metrics
         self.pattern_strength = self.calculate_pattern_strength();
         self.symmetry_score = self.calculate_symmetry_score();
         self.prediction_accuracy = self.calculate_prediction_accuracy();
-        
-        self.processing_time = start_time.elapsed();
-        
-        Ok(())
     }
     
     /// Calculate overall pattern strength
@@ -164,22 +256,52 @@ impl DashboardApp {
         0.75 + (self.symmetry_score * 0.2)
     }
     
+    /// Recompute the forecast cones and scores of the what-if tab
+    fn refresh_what_if(&mut self) {
+        self.what_if = (!self.recent_bars.is_empty())
+            .then(|| self.cycle_editor.evaluate(&self.recent_bars, &self.composite_scorer));
+    }
+    
+    /// Cycle editing keys of the what-if tab; `false` for keys it does not handle
+    fn handle_what_if_input(&mut self, key: KeyCode) -> bool {
+        match key {
+            KeyCode::Up => self.cycle_editor.select_previous(),
+            KeyCode::Down => self.cycle_editor.select_next(),
+            KeyCode::Char(' ') => self.cycle_editor.toggle(),
+            KeyCode::Char('+') | KeyCode::Char('=') => self.cycle_editor.scale_amplitude(AMPLITUDE_STEP),
+            KeyCode::Char('-') => self.cycle_editor.scale_amplitude(-AMPLITUDE_STEP),
+            KeyCode::Char(']') => self.cycle_editor.shift_phase(PHASE_STEP),
+            KeyCode::Char('[') => self.cycle_editor.shift_phase(-PHASE_STEP),
+            KeyCode::Char('0') => self.cycle_editor.reset(),
+            _ => return false,
+        }
+        self.refresh_what_if();
+        true
+    }
+    
     /// Handle keyboard input
     pub fn handle_input(&mut self, key: KeyCode) -> Result<()> {
+        if self.current_tab == WHAT_IF_TAB && self.handle_what_if_input(key) {
+            return Ok(());
+        }
         match key {
             KeyCode::Char('q') | KeyCode::Esc => {
                 self.should_quit = true;
             }
             KeyCode::Tab => {
-                self.current_tab = (self.current_tab + 1) % 4;
+                self.current_tab = (self.current_tab + 1) % TABS.len();
             }
             KeyCode::Char('1') => self.current_tab = 0,
             KeyCode::Char('2') => self.current_tab = 1,
             KeyCode::Char('3') => self.current_tab = 2,
             KeyCode::Char('4') => self.current_tab = 3,
+            KeyCode::Char('5') => self.current_tab = WHAT_IF_TAB,
+            KeyCode::Char('6') => self.current_tab = PAIRS_TAB,
+            KeyCode::Left => self.select_pair(-1),
+            KeyCode::Right => self.select_pair(1),
             KeyCode::Char('r') => {
-                // Refresh data
-                self.last_update = Instant::now();
+                // Read the manager again at the next update
+                self.last_update = Instant::now() - UPDATE_INTERVAL;
             }
             _ => {}
         }
@@ -191,33 +313,19 @@ impl DashboardApp {
         self.should_quit
     }
     
-    /// Update with new data
+    /// Run a market update when the dashboard drives them, then read the manager again
     pub async fn update(&mut self) -> Result<()> {
-        // Simulate new data point
-        if self.last_update.elapsed() > Duration::from_secs(1) {
-            self.simulate_new_data_point();
-            self.last_update = Instant::now();
+        if self.last_update.elapsed() < UPDATE_INTERVAL {
+            return Ok(());
         }
-        
-        Ok(())
-    }
-    
-    /// Simulate new data point for demo
-    fn simulate_new_data_point(&mut self) {
-        let timestamp = self.price_history.len() as f64;
-        let last_price = self.price_history.back().map(|(_, p)| *p).unwrap_or(1.1000);
-        
-        // Add some realistic price movement
-        let change = (timestamp * 0.1).sin() * 0.001 + 
-                    (timestamp * 0.05).cos() * 0.0005;
-        let new_price = last_price + change;
-        
-        self.price_history.push_back((timestamp, new_price));
-        
-        // Keep only last 100 points
-        if self.price_history.len() > 100 {
-            self.price_history.pop_front();
+        let start_time = Instant::now();
+        if self.drives_updates {
+            run_market_update(&self.manager).await?;
         }
+        self.refresh().await;
+        self.processing_time = start_time.elapsed();
+        self.last_update = Instant::now();
+        Ok(())
     }
 }
 
@@ -241,6 +349,8 @@ pub fn render_dashboard(f: &mut Frame, app: &DashboardApp) {
         1 => render_patterns_tab(f, chunks[1], app),
         2 => render_symmetries_tab(f, chunks[1], app),
         3 => render_performance_tab(f, chunks[1], app),
+        WHAT_IF_TAB => render_what_if_tab(f, chunks[1], app),
+        PAIRS_TAB => render_pairs_tab(f, chunks[1], app),
         _ => render_overview_tab(f, chunks[1], app),
     }
     
@@ -250,8 +360,7 @@ pub fn render_dashboard(f: &mut Frame, app: &DashboardApp) {
 
 /// Render header with title and tabs
 fn render_header(f: &mut Frame, area: Rect, app: &DashboardApp) {
-    let tabs = ["Overview", "Patterns", "Symmetries", "Performance"];
-    let tab_titles: Vec<Line> = tabs.iter().enumerate().map(|(i, &tab)| {
+    let tab_titles: Vec<Line> = TABS.iter().enumerate().map(|(i, &tab)| {
         if i == app.current_tab {
             Line::from(Span::styled(tab, Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)))
         } else {
@@ -259,13 +368,17 @@ fn render_header(f: &mut Frame, area: Rect, app: &DashboardApp) {
         }
     }).collect();
     
+    let mut title = vec![
+        Span::styled("🔬 FOREX PATTERN RECONSTRUCTION DASHBOARD", 
+                    Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Span::raw(" | "),
+    ];
+    title.extend(pair_tab_spans(app));
+    title.push(Span::raw("| "));
+    title.push(feed_status_span(app));
+    
     let header = Paragraph::new(Text::from(vec![
-        Line::from(vec![
-            Span::styled("🔬 FOREX PATTERN RECONSTRUCTION DASHBOARD", 
-                        Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
-            Span::raw(" | "),
-            Span::styled(&app.current_pair, Style::default().fg(Color::Green)),
-        ]),
+        Line::from(title),
         Line::from(tab_titles.into_iter().map(|line| line.spans).flatten().collect::<Vec<_>>()),
     ]))
     .block(Block::default().borders(Borders::ALL))
@@ -274,12 +387,53 @@ fn render_header(f: &mut Frame, area: Rect, app: &DashboardApp) {
     f.render_widget(header, area);
 }
 
+/// One tab per pair, the current one highlighted and each colored by its status
+fn pair_tab_spans(app: &DashboardApp) -> Vec<Span<'static>> {
+    app.view.pairs.iter().map(|pair| {
+        let style = Style::default().fg(pair_status_color(pair.status));
+        let style = if pair.symbol == app.current_pair { style.add_modifier(Modifier::BOLD | Modifier::REVERSED) } else { style };
+        Span::styled(format!("{} ", pair.symbol), style)
+    }).collect()
+}
+
+fn pair_status_color(status: PairStatus) -> Color {
+    match status {
+        PairStatus::Trading => Color::Green,
+        PairStatus::Warming => Color::Yellow,
+        PairStatus::Paused | PairStatus::DriftPaused | PairStatus::FeedPaused => Color::Red,
+        PairStatus::Inactive => Color::DarkGray,
+    }
+}
+
+/// Feed source and health for the header
+fn feed_status_span(app: &DashboardApp) -> Span<'static> {
+    let Some(health) = &app.view.feed_health else {
+        return Span::styled("HISTORY ONLY", Style::default().fg(Color::Gray));
+    };
+    let color = match health.overall {
+        FeedState::Healthy => Color::Green,
+        FeedState::Waiting => Color::Yellow,
+        FeedState::Stale | FeedState::Skewed => Color::Red,
+    };
+    let rejected: u64 = health.pairs.iter().map(|pair| pair.future_dated).sum();
+    let label = if rejected > 0 {
+        format!("FEED {} ({} future-dated)", health.overall.to_string().to_uppercase(), rejected)
+    } else {
+        format!("FEED {}", health.overall.to_string().to_uppercase())
+    };
+    Span::styled(label, Style::default().fg(color).add_modifier(Modifier::BOLD))
+}
+
 /// Render footer with controls
 fn render_footer(f: &mut Frame, area: Rect, app: &DashboardApp) {
     let footer = Paragraph::new(Text::from(vec![
         Line::from(vec![
             Span::styled("Controls: ", Style::default().fg(Color::Yellow)),
-            Span::raw("Tab/1-4: Switch tabs | R: Refresh | Q/Esc: Quit"),
+            Span::raw(if app.current_tab == WHAT_IF_TAB {
+                "↑/↓: Cycle | Space: On/off | +/-: Amplitude | [/]: Phase | 0: Reset | ←/→: Pair | Tab/1-6: Switch tabs | Q/Esc: Quit"
+            } else {
+                "Tab/1-6: Switch tabs | ←/→: Pair | R: Refresh | Q/Esc: Quit"
+            }),
         ]),
         Line::from(vec![
             Span::styled("Status: ", Style::default().fg(Color::Green)),
@@ -295,6 +449,65 @@ fn render_footer(f: &mut Frame, area: Rect, app: &DashboardApp) {
     f.render_widget(footer, area);
 }
 
+/// Render every pair side by side above the aggregated portfolio
+fn render_pairs_tab(f: &mut Frame, area: Rect, app: &DashboardApp) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(8)])
+        .split(area);
+
+    let rows: Vec<Row> = app.view.pairs.iter().map(|pair| {
+        let style = if pair.symbol == app.current_pair { Style::default().add_modifier(Modifier::REVERSED) } else { Style::default() };
+        Row::new(vec![
+            Cell::from(pair.symbol.clone()),
+            Cell::from(Span::styled(pair.status.to_string(), Style::default().fg(pair_status_color(pair.status)))),
+            Cell::from(pair.price.map_or("-".to_string(), |price| format!("{:.5}", price))),
+            Cell::from(format!("{:+.0}", pair.position_units)),
+            Cell::from(format!("{:+.2}", pair.unrealized_pnl)),
+            Cell::from(pair.performance.total_trades.to_string()),
+            Cell::from(format!("{:.1}%", pair.performance.win_rate)),
+            Cell::from(format!("{:+.2}", pair.performance.total_reward)),
+            Cell::from(format!("{} ({} silenced)", pair.performance.anomalies_detected, pair.suppressed_anomalies)),
+            Cell::from(pair.latest_score().map_or("-".to_string(), |score| format!("{:+.2}", score.score))),
+        ]).style(style)
+    }).collect();
+
+    let table = Table::new(rows, [
+            Constraint::Length(8),
+            Constraint::Length(13),
+            Constraint::Length(9),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(7),
+            Constraint::Length(7),
+            Constraint::Length(9),
+            Constraint::Length(18),
+            Constraint::Length(6),
+        ])
+        .header(Row::new(vec!["Pair", "Status", "Price", "Position", "Unrealized", "Trades", "Win", "Reward", "Anomalies", "Score"])
+            .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)))
+        .block(Block::default().title("Pairs").borders(Borders::ALL));
+    f.render_widget(table, chunks[0]);
+
+    let portfolio = &app.view.portfolio;
+    let account = &portfolio.snapshot;
+    let lines = vec![
+        Line::from(format!("Equity: {:.2} {} | Balance: {:.2} | Realized: {:+.2} | Unrealized: {:+.2}",
+                           account.equity, account.account_currency, account.balance, account.realized_pnl, account.unrealized_pnl)),
+        Line::from(format!("Margin used: {:.2} | Free: {:.2} | Level: {} | Open positions: {}",
+                           account.margin_used, account.free_margin,
+                           account.margin_level.map_or("-".to_string(), |level| format!("{:.0}%", level)),
+                           account.positions.len())),
+        Line::from(format!("Trades: {} | Win rate: {:.1}% | Total reward: {:+.2}",
+                           portfolio.total_trades, portfolio.win_rate(), portfolio.total_reward)),
+        Line::from(format!("Anomalies: {} ({} silenced) | Trading pairs: {}/{}",
+                           portfolio.anomalies_detected, portfolio.suppressed_anomalies, portfolio.trading_pairs, app.view.pairs.len())),
+    ];
+    let summary = Paragraph::new(Text::from(lines))
+        .block(Block::default().title("Portfolio").borders(Borders::ALL));
+    f.render_widget(summary, chunks[1]);
+}
+
 /// Render overview tab
 fn render_overview_tab(f: &mut Frame, area: Rect, app: &DashboardApp) {
     let chunks = Layout::default()
@@ -302,8 +515,13 @@ fn render_overview_tab(f: &mut Frame, area: Rect, app: &DashboardApp) {
         .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
         .split(area);
 
-    // Left side: Price chart
-    render_price_chart(f, chunks[0], app);
+    // Left side: Price chart above the composite score
+    let left = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(65), Constraint::Percentage(35)])
+        .split(chunks[0]);
+    render_price_chart(f, left[0], app);
+    render_score_chart(f, left[1], app);
 
     // Right side: Metrics
     render_metrics_panel(f, chunks[1], app);
@@ -354,9 +572,161 @@ fn render_performance_tab(f: &mut Frame, area: Rect, app: &DashboardApp) {
     render_performance_history(f, chunks[1], app);
 }
 
+/// Render the what-if cycle editor: cycles and edits on the left, the detected
+/// and edited forecast cones on the right
+fn render_what_if_tab(f: &mut Frame, area: Rect, app: &DashboardApp) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(area);
+
+    let left = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(6)])
+        .split(chunks[0]);
+    render_cycle_editor(f, left[0], app);
+    render_what_if_scores(f, left[1], app);
+    render_forecast_cones(f, chunks[1], app);
+}
+
+/// Render the detected cycles with their edits, the selected one highlighted
+fn render_cycle_editor(f: &mut Frame, area: Rect, app: &DashboardApp) {
+    let editor = &app.cycle_editor;
+    let rows: Vec<Row> = editor.cycles().iter().zip(editor.edits()).enumerate().map(|(i, (cycle, edit))| {
+        let style = if !edit.enabled {
+            Style::default().fg(Color::DarkGray)
+        } else if edit.is_identity() {
+            Style::default().fg(Color::White)
+        } else {
+            Style::default().fg(Color::Yellow)
+        };
+        let style = if i == editor.selected() { style.add_modifier(Modifier::REVERSED) } else { style };
+        Row::new(vec![
+            Cell::from(if edit.enabled { "on" } else { "off" }),
+            Cell::from(format!("{}", cycle.period)),
+            Cell::from(format!("{:.2}", cycle.confidence)),
+            Cell::from(format!("{:.4}×{:.1}", cycle.amplitude, edit.amplitude_scale)),
+            Cell::from(format!("{:+.0}°", edit.phase_shift.to_degrees())),
+        ]).style(style)
+    }).collect();
+
+    let table = Table::new(rows, [
+            Constraint::Length(4),
+            Constraint::Length(7),
+            Constraint::Length(6),
+            Constraint::Length(12),
+            Constraint::Length(6),
+        ])
+        .header(Row::new(vec!["", "Period", "Conf", "Amplitude", "Phase"]).style(Style::default().fg(Color::Cyan)))
+        .block(Block::default().title("What-If Cycles").borders(Borders::ALL));
+
+    f.render_widget(table, area);
+}
+
+/// Render the detected and edited composite scores and projected moves
+fn render_what_if_scores(f: &mut Frame, area: Rect, app: &DashboardApp) {
+    let last_close = app.recent_bars.last().map(|bar| bar.close).unwrap_or_default();
+    let lines = match &app.what_if {
+        Some((detected, edited)) => {
+            let score = |outcome: &WhatIfOutcome| outcome.score.as_ref().map_or("-".to_string(), |s| format!("{:+.2}", s.score));
+            let shift = match (&detected.score, &edited.score) {
+                (Some(before), Some(after)) => format!("{:+.2}", after.score - before.score),
+                _ => "-".to_string(),
+            };
+            vec![
+                Line::from(vec![
+                    Span::styled("Detected: ", Style::default().fg(Color::White)),
+                    Span::raw(format!("score {}  move {:+.5}", score(detected), detected.cone.drift(last_close))),
+                ]),
+                Line::from(vec![
+                    Span::styled("Edited:   ", Style::default().fg(Color::Yellow)),
+                    Span::raw(format!("score {}  move {:+.5}", score(edited), edited.cone.drift(last_close))),
+                ]),
+                Line::from(vec![
+                    Span::styled("Score shift: ", Style::default().fg(Color::White)),
+                    Span::styled(shift, Style::default().fg(Color::Magenta)),
+                ]),
+            ]
+        }
+        None => vec![Line::from("No bars to project from")],
+    };
+
+    let paragraph = Paragraph::new(Text::from(lines))
+        .block(Block::default().title("Signal Score").borders(Borders::ALL));
+    f.render_widget(paragraph, area);
+}
+
+/// Render the recent closes followed by the detected (gray) and edited (yellow) cones
+fn render_forecast_cones(f: &mut Frame, area: Rect, app: &DashboardApp) {
+    let Some((detected, edited)) = &app.what_if else {
+        let placeholder = Paragraph::new("Loading price data...")
+            .block(Block::default().title("Forecast Cone").borders(Borders::ALL))
+            .alignment(Alignment::Center);
+        f.render_widget(placeholder, area);
+        return;
+    };
+
+    let tail = &app.recent_bars[app.recent_bars.len().saturating_sub(2 * detected.cone.points.len().max(1))..];
+    let closes: Vec<(f64, f64)> = tail.iter().enumerate()
+        .map(|(i, bar)| (i as f64 + 1.0 - tail.len() as f64, bar.close))
+        .collect();
+    let band = |outcome: &WhatIfOutcome, pick: fn(&what_if::ConePoint) -> f64| -> Vec<(f64, f64)> {
+        closes.last().into_iter().copied()
+            .chain(outcome.cone.points.iter().map(|point| (point.step as f64, pick(point))))
+            .collect()
+    };
+    let series = [
+        (band(detected, |p| p.upper), Color::DarkGray),
+        (band(detected, |p| p.lower), Color::DarkGray),
+        (band(detected, |p| p.center), Color::Gray),
+        (band(edited, |p| p.upper), Color::Rgb(128, 112, 0)),
+        (band(edited, |p| p.lower), Color::Rgb(128, 112, 0)),
+        (band(edited, |p| p.center), Color::Yellow),
+    ];
+
+    let (min_price, max_price) = closes.iter().chain(series.iter().flat_map(|(points, _)| points))
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), (_, p)| (lo.min(*p), hi.max(*p)));
+    let margin = ((max_price - min_price) * 0.05).max(f64::EPSILON);
+    let x_min = closes.first().map_or(0.0, |(x, _)| *x);
+    let x_max = detected.cone.points.len() as f64;
+
+    let mut datasets = vec![
+        Dataset::default()
+            .name(app.current_pair.as_str())
+            .marker(symbols::Marker::Braille)
+            .style(Style::default().fg(Color::Cyan))
+            .data(&closes),
+    ];
+    datasets.extend(series.iter().map(|(points, color)| {
+        Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .style(Style::default().fg(*color))
+            .data(points)
+    }));
+
+    let title = if app.cycle_editor.is_edited() { "Forecast Cone (edited)" } else { "Forecast Cone" };
+    let chart = Chart::new(datasets)
+        .block(Block::default().title(title).borders(Borders::ALL))
+        .x_axis(
+            Axis::default()
+                .title("Bars")
+                .style(Style::default().fg(Color::Gray))
+                .bounds([x_min, x_max.max(x_min + 1.0)])
+        )
+        .y_axis(
+            Axis::default()
+                .title("Price")
+                .style(Style::default().fg(Color::Gray))
+                .bounds([min_price - margin, max_price + margin])
+                .labels(vec![Span::raw(format!("{:.5}", min_price)), Span::raw(format!("{:.5}", max_price))])
+        );
+
+    f.render_widget(chart, area);
+}
+
 /// Render price chart
 fn render_price_chart(f: &mut Frame, area: Rect, app: &DashboardApp) {
-    let price_data: Vec<(f64, f64)> = app.price_history.iter().cloned().collect();
+    let price_data = &app.price_history;
 
     if price_data.is_empty() {
         let placeholder = Paragraph::new("Loading price data...")
@@ -375,7 +745,7 @@ fn render_price_chart(f: &mut Frame, area: Rect, app: &DashboardApp) {
             .name(app.current_pair.as_str())
             .marker(symbols::Marker::Braille)
             .style(Style::default().fg(Color::Cyan))
-            .data(&price_data)
+            .data(price_data)
     ];
 
     let chart = Chart::new(datasets)
@@ -396,6 +766,46 @@ fn render_price_chart(f: &mut Frame, area: Rect, app: &DashboardApp) {
     f.render_widget(chart, area);
 }
 
+/// Render the composite score on a fixed [-1, 1] axis
+fn render_score_chart(f: &mut Frame, area: Rect, app: &DashboardApp) {
+    let score_data: Vec<(f64, f64)> = app.score_history.to_vec();
+    let x_min = score_data.first().map(|(t, _)| *t).unwrap_or(0.0);
+    let x_max = score_data.last().map(|(t, _)| *t).unwrap_or(0.0).max(x_min + 1.0);
+    let zero = [(x_min, 0.0), (x_max, 0.0)];
+
+    let datasets = vec![
+        Dataset::default()
+            .marker(symbols::Marker::Dot)
+            .style(Style::default().fg(Color::DarkGray))
+            .data(&zero),
+        Dataset::default()
+            .name("score")
+            .marker(symbols::Marker::Braille)
+            .style(Style::default().fg(Color::Magenta))
+            .data(&score_data),
+    ];
+
+    let title = match &app.latest_score {
+        Some(score) => format!("Composite Score {:+.2} ({})", score.score, score.regime),
+        None => "Composite Score".to_string(),
+    };
+    let chart = Chart::new(datasets)
+        .block(Block::default().title(title).borders(Borders::ALL))
+        .x_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::Gray))
+                .bounds([x_min, x_max])
+        )
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::Gray))
+                .bounds([-1.0, 1.0])
+                .labels(vec![Span::raw("-1"), Span::raw("0"), Span::raw("1")])
+        );
+
+    f.render_widget(chart, area);
+}
+
 /// Render metrics panel
 fn render_metrics_panel(f: &mut Frame, area: Rect, app: &DashboardApp) {
     let chunks = Layout::default()
@@ -430,7 +840,7 @@ fn render_metrics_panel(f: &mut Frame, area: Rect, app: &DashboardApp) {
     f.render_widget(accuracy_gauge, chunks[2]);
 
     // Additional info
-    let info_text = vec![
+    let mut info_text = vec![
         Line::from(vec![
             Span::styled("Cycles Detected: ", Style::default().fg(Color::White)),
             Span::styled(app.detected_cycles.len().to_string(), Style::default().fg(Color::Green)),
@@ -444,6 +854,18 @@ fn render_metrics_panel(f: &mut Frame, area: Rect, app: &DashboardApp) {
             Span::styled(format!("{:.2}ms", app.processing_time.as_millis()), Style::default().fg(Color::Yellow)),
         ]),
     ];
+    if let Some(score) = &app.latest_score {
+        let part = |value: Option<f64>| value.map_or("-".to_string(), |v| format!("{:+.2}", v));
+        info_text.push(Line::from(vec![
+            Span::styled("Score: ", Style::default().fg(Color::White)),
+            Span::styled(format!("{:+.2}", score.score), Style::default().fg(Color::Magenta)),
+        ]));
+        info_text.push(Line::from(Span::styled(
+            format!("cycle {} sym {} anom {:+.2} regime {} ×{:.2}",
+                    part(score.components.cycle_phase), part(score.components.symmetry),
+                    score.components.anomaly, part(score.components.regime), score.components.breakdown_damping),
+            Style::default().fg(Color::Gray))));
+    }
 
     let info = Paragraph::new(Text::from(info_text))
         .block(Block::default().title("Analysis Info").borders(Borders::ALL))
@@ -0,0 +1,215 @@
+//! # Multi-Pair Dashboard View
+//!
+//! What the terminal dashboards draw, captured from a shared
+//! [`MultiCurrencyManager`] instead of engines of their own: one panel per
+//! active pair with its real state and live anomalies, and portfolio metrics
+//! aggregated over every pair.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::anomaly::DetectedAnomaly;
+use crate::data::health::FeedHealthReport;
+use crate::data::ForexDataPoint;
+use crate::laplacian_rl::TradingAction;
+use crate::multi_currency::{CurrencyPairState, MultiCurrencyManager, PairPerformanceMetrics};
+use crate::patterns::HiddenCycle;
+use crate::portfolio::PortfolioSnapshot;
+use crate::signal::CompositeScore;
+use crate::symmetry::TemporalSymmetry;
+
+/// Bars of each pair's history kept in its panel
+pub const PANEL_BARS: usize = 500;
+/// Anomalies of each pair kept in its panel, newest last
+pub const PANEL_ANOMALIES: usize = 100;
+/// Composite scores of each pair kept in its panel, newest last
+pub const PANEL_SCORES: usize = 100;
+
+/// Whether a pair is trading and why not
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PairStatus {
+    Trading,
+    /// History too old or short for trading decisions yet
+    Warming,
+    Paused,
+    DriftPaused,
+    FeedPaused,
+    /// Not initialized, or no history and no feed
+    Inactive,
+}
+
+impl PairStatus {
+    pub fn of(state: &CurrencyPairState) -> Self {
+        if !state.is_active {
+            PairStatus::Inactive
+        } else if state.paused {
+            PairStatus::Paused
+        } else if state.drift_paused {
+            PairStatus::DriftPaused
+        } else if state.feed_paused {
+            PairStatus::FeedPaused
+        } else if !state.warm {
+            PairStatus::Warming
+        } else {
+            PairStatus::Trading
+        }
+    }
+}
+
+impl fmt::Display for PairStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            PairStatus::Trading => "TRADING",
+            PairStatus::Warming => "WARMING",
+            PairStatus::Paused => "PAUSED",
+            PairStatus::DriftPaused => "DRIFT PAUSED",
+            PairStatus::FeedPaused => "FEED PAUSED",
+            PairStatus::Inactive => "INACTIVE",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// One pair as the dashboards show it
+#[derive(Debug, Clone)]
+pub struct PairPanel {
+    pub symbol: String,
+    pub status: PairStatus,
+    /// Latest quote, or the last close without a live feed
+    pub price: Option<f64>,
+    /// Newest bars of the history, oldest first
+    pub recent_bars: Vec<ForexDataPoint>,
+    pub recent_anomalies: Vec<DetectedAnomaly>,
+    pub suppressed_anomalies: u64,
+    pub performance: PairPerformanceMetrics,
+    pub symmetries: Vec<TemporalSymmetry>,
+    pub cycles: Vec<HiddenCycle>,
+    pub composite_scores: Vec<CompositeScore>,
+    /// Net open units, negative when short
+    pub position_units: f64,
+    pub unrealized_pnl: f64,
+}
+
+impl PairPanel {
+    fn capture(state: &CurrencyPairState, snapshot: &PortfolioSnapshot, now: DateTime<Utc>) -> Self {
+        let positions = snapshot.positions.iter().filter(|position| position.symbol == state.config.symbol);
+        let (position_units, unrealized_pnl) = positions.fold((0.0, 0.0), |(units, pnl), position| {
+            let signed = if position.side == "SHORT" { -position.units } else { position.units };
+            (units + signed, pnl + position.unrealized_pnl)
+        });
+        Self {
+            symbol: state.config.symbol.clone(),
+            status: PairStatus::of(state),
+            price: state.current_price(now),
+            recent_bars: tail(&state.historical_data, PANEL_BARS),
+            recent_anomalies: tail(&state.recent_anomalies, PANEL_ANOMALIES),
+            suppressed_anomalies: state.suppressed_anomalies,
+            performance: state.performance.clone(),
+            symmetries: state.symmetries.clone(),
+            cycles: state.cycles.clone(),
+            composite_scores: tail(&state.composite_scores, PANEL_SCORES),
+            position_units,
+            unrealized_pnl,
+        }
+    }
+
+    /// Closes of the newest `bars` bars, indexed from 0 for charting
+    pub fn close_series(&self, bars: usize) -> Vec<(f64, f64)> {
+        let recent = &self.recent_bars[self.recent_bars.len().saturating_sub(bars)..];
+        recent.iter().enumerate().map(|(i, bar)| (i as f64, bar.close)).collect()
+    }
+
+    pub fn latest_score(&self) -> Option<&CompositeScore> {
+        self.composite_scores.last()
+    }
+}
+
+/// Trading results summed over every pair, with the account they trade
+#[derive(Debug, Clone)]
+pub struct PortfolioPanel {
+    pub snapshot: PortfolioSnapshot,
+    pub total_trades: u64,
+    pub successful_trades: u64,
+    pub total_reward: f64,
+    pub anomalies_detected: u64,
+    pub suppressed_anomalies: u64,
+    pub trading_pairs: usize,
+}
+
+impl PortfolioPanel {
+    fn aggregate(snapshot: PortfolioSnapshot, pairs: &[PairPanel]) -> Self {
+        Self {
+            snapshot,
+            total_trades: pairs.iter().map(|pair| pair.performance.total_trades).sum(),
+            successful_trades: pairs.iter().map(|pair| pair.performance.successful_trades).sum(),
+            total_reward: pairs.iter().map(|pair| pair.performance.total_reward).sum(),
+            anomalies_detected: pairs.iter().map(|pair| pair.performance.anomalies_detected).sum(),
+            suppressed_anomalies: pairs.iter().map(|pair| pair.suppressed_anomalies).sum(),
+            trading_pairs: pairs.iter().filter(|pair| pair.status == PairStatus::Trading).count(),
+        }
+    }
+
+    /// Successful trades in percent of all trades
+    pub fn win_rate(&self) -> f64 {
+        if self.total_trades == 0 {
+            0.0
+        } else {
+            self.successful_trades as f64 / self.total_trades as f64 * 100.0
+        }
+    }
+}
+
+/// Every active pair and the portfolio, as of one moment
+#[derive(Debug, Clone)]
+pub struct MultiPairView {
+    /// In the manager's pair order
+    pub pairs: Vec<PairPanel>,
+    pub portfolio: PortfolioPanel,
+    /// Live feed health, when the manager has a data provider
+    pub feed_health: Option<FeedHealthReport>,
+    pub captured_at: DateTime<Utc>,
+}
+
+impl MultiPairView {
+    /// Read the pairs and portfolio of `manager`
+    pub async fn capture(manager: &MultiCurrencyManager) -> Self {
+        let now = Utc::now();
+        let snapshot = manager.portfolio_snapshot().await;
+        let feed_health = manager.feed_health_report().await;
+        let pairs_map = manager.pairs.read().await;
+        let pairs: Vec<PairPanel> = manager.active_pairs.iter()
+            .filter_map(|symbol| pairs_map.get(symbol))
+            .map(|state| PairPanel::capture(state, &snapshot, now))
+            .collect();
+        let portfolio = PortfolioPanel::aggregate(snapshot, &pairs);
+        Self { pairs, portfolio, feed_health, captured_at: now }
+    }
+
+    pub fn pair(&self, symbol: &str) -> Option<&PairPanel> {
+        self.pairs.iter().find(|pair| pair.symbol == symbol)
+    }
+
+    pub fn symbols(&self) -> Vec<&str> {
+        self.pairs.iter().map(|pair| pair.symbol.as_str()).collect()
+    }
+}
+
+/// Actions one market update decided and what filling them realized, per pair
+#[derive(Debug, Clone, Default)]
+pub struct MarketUpdate {
+    pub actions: HashMap<String, Vec<TradingAction>>,
+    pub realized: HashMap<String, f64>,
+}
+
+/// Run a market update on every pair of `manager` and fill the actions it decides
+pub async fn run_market_update(manager: &MultiCurrencyManager) -> Result<MarketUpdate> {
+    let actions = manager.process_all_market_updates().await?;
+    let realized = manager.execute_actions(&actions).await;
+    Ok(MarketUpdate { actions, realized })
+}
+
+fn tail<T: Clone>(items: &[T], count: usize) -> Vec<T> {
+    items[items.len().saturating_sub(count)..].to_vec()
+}
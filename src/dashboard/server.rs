@@ -0,0 +1,218 @@
+//! # Dashboard Streaming Server
+//!
+//! WebSocket streams of prices and anomalies next to the controller HTTP API, so
+//! the CLI controller and browser clients can follow a running dashboard.
+//! Changes of the analysis parameters are marked in the anomaly stream, so
+//! subscribers can tell which anomalies were found under which settings.
+
+use chrono::{DateTime, Utc};
+use futures_util::{SinkExt, StreamExt};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use warp::ws::{Message, WebSocket};
+use warp::{Filter, Rejection, Reply};
+
+use crate::anomaly::DetectedAnomaly;
+use crate::multi_currency::MultiCurrencyManager;
+use crate::pipeline::params::ParamsEpoch;
+use crate::protocol::server::{self as api, ApiState};
+
+/// Messages buffered per stream for slow subscribers
+const STREAM_CAPACITY: usize = 1024;
+
+/// `/ws/prices` message
+#[derive(Debug, Clone, Serialize)]
+pub struct PriceUpdate {
+    pub symbol: String,
+    pub timestamp: DateTime<Utc>,
+    pub price: f64,
+    pub bid: Option<f64>,
+    pub ask: Option<f64>,
+}
+
+/// `/ws/anomalies` message
+#[derive(Debug, Clone, Serialize)]
+pub struct AnomalyEvent {
+    pub symbol: String,
+    /// Pattern and type to acknowledge or suppress this anomaly by
+    pub pattern_id: String,
+    pub anomaly_type: String,
+    pub anomaly: DetectedAnomaly,
+    /// Analysis parameter epoch the pair ran under
+    pub params_epoch: u64,
+}
+
+/// `/ws/anomalies` message, tagged by `kind`
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AnomalyStreamMessage {
+    Anomaly(Box<AnomalyEvent>),
+    /// Analysis parameters changed; anomalies after it carry the new epoch
+    ParamsEpoch(ParamsEpoch),
+}
+
+/// Broadcast channels behind the WebSocket streams
+#[derive(Clone)]
+pub struct StreamHub {
+    prices: broadcast::Sender<PriceUpdate>,
+    anomalies: broadcast::Sender<AnomalyStreamMessage>,
+}
+
+impl StreamHub {
+    pub fn new() -> Self {
+        Self {
+            prices: broadcast::channel(STREAM_CAPACITY).0,
+            anomalies: broadcast::channel(STREAM_CAPACITY).0,
+        }
+    }
+
+    pub fn publish_price(&self, update: PriceUpdate) {
+        // No subscribers is not an error
+        let _ = self.prices.send(update);
+    }
+
+    pub fn publish_anomaly(&self, event: AnomalyEvent) {
+        let _ = self.anomalies.send(AnomalyStreamMessage::Anomaly(Box::new(event)));
+    }
+
+    pub fn publish_params_epoch(&self, epoch: ParamsEpoch) {
+        let _ = self.anomalies.send(AnomalyStreamMessage::ParamsEpoch(epoch));
+    }
+
+    pub fn subscribe_prices(&self) -> broadcast::Receiver<PriceUpdate> {
+        self.prices.subscribe()
+    }
+
+    pub fn subscribe_anomalies(&self) -> broadcast::Receiver<AnomalyStreamMessage> {
+        self.anomalies.subscribe()
+    }
+
+    /// Messages the slowest subscriber of either stream has yet to receive
+    pub fn backlog(&self) -> usize {
+        self.prices.len().max(self.anomalies.len())
+    }
+}
+
+impl Default for StreamHub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `/ws/prices`, `/ws/anomalies`, the `/api/throughput` system panel and the
+/// controller API (`/api/status`, `/api/portfolio`, ...)
+pub fn routes(state: ApiState, hub: StreamHub) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    let throughput_state = state.clone();
+    let throughput_hub = hub.clone();
+    let throughput = warp::path!("api" / "throughput")
+        .and(warp::get())
+        .and_then(move || {
+            let (state, hub) = (throughput_state.clone(), throughput_hub.clone());
+            async move {
+                Ok::<_, std::convert::Infallible>(warp::reply::json(&state.manager.throughput_panel(hub.backlog()).await))
+            }
+        });
+
+    let prices_hub = hub.clone();
+    let prices = warp::path!("ws" / "prices")
+        .and(warp::ws())
+        .map(move |ws: warp::ws::Ws| {
+            let receiver = prices_hub.subscribe_prices();
+            ws.on_upgrade(move |socket| stream_to_socket(socket, receiver))
+        });
+
+    let anomalies = warp::path!("ws" / "anomalies")
+        .and(warp::ws())
+        .map(move |ws: warp::ws::Ws| {
+            let receiver = hub.subscribe_anomalies();
+            ws.on_upgrade(move |socket| stream_to_socket(socket, receiver))
+        });
+
+    prices.or(anomalies).or(throughput).or(api::routes(state))
+}
+
+/// Forward broadcast messages as JSON text frames until the client disconnects
+async fn stream_to_socket<T: Serialize + Clone>(socket: WebSocket, mut receiver: broadcast::Receiver<T>) {
+    let (mut sender, mut incoming) = socket.split();
+    loop {
+        tokio::select! {
+            message = receiver.recv() => match message {
+                Ok(message) => {
+                    let Ok(text) = serde_json::to_string(&message) else { continue };
+                    if sender.send(Message::text(text)).await.is_err() {
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    println!("⚠️  WebSocket subscriber lagged, skipped {} messages", skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            frame = incoming.next() => match frame {
+                Some(Ok(frame)) if !frame.is_close() => {}
+                _ => break,
+            },
+        }
+    }
+}
+
+/// Publish current prices and anomalies detected since the previous call.
+///
+/// `seen` tracks the per-pair anomaly count already published and `params_epoch`
+/// the last parameter epoch marked. Epochs a pair has started running under are
+/// marked before that pair's anomalies.
+pub async fn publish_updates(
+    manager: &MultiCurrencyManager,
+    hub: &StreamHub,
+    seen: &mut HashMap<String, u64>,
+    params_epoch: &mut u64,
+) {
+    let now = Utc::now();
+    let pairs_map = manager.pairs.read().await;
+
+    let applied = pairs_map.values().map(|state| state.analysis_params.epoch).max().unwrap_or(0);
+    if applied > *params_epoch {
+        for epoch in manager.analysis_params.epochs_since(*params_epoch) {
+            if epoch.epoch <= applied {
+                hub.publish_params_epoch(epoch);
+            }
+        }
+        *params_epoch = applied;
+    }
+
+    for symbol in &manager.active_pairs {
+        let Some(state) = pairs_map.get(symbol) else { continue };
+
+        if let Some(price) = state.current_price(now) {
+            hub.publish_price(PriceUpdate {
+                symbol: symbol.clone(),
+                timestamp: state.last_tick.as_ref().map(|tick| tick.timestamp).unwrap_or(now),
+                price,
+                bid: state.last_tick.as_ref().map(|tick| tick.bid),
+                ask: state.last_tick.as_ref().map(|tick| tick.ask),
+            });
+        }
+
+        let detected = state.performance.anomalies_detected;
+        let published = seen.insert(symbol.clone(), detected).unwrap_or(0);
+        let new = detected.saturating_sub(published) as usize;
+        for anomaly in state.recent_anomalies.iter().skip(state.recent_anomalies.len().saturating_sub(new)) {
+            hub.publish_anomaly(AnomalyEvent {
+                symbol: symbol.clone(),
+                pattern_id: anomaly.pattern_id().to_string(),
+                anomaly_type: anomaly.anomaly_type.name().to_string(),
+                anomaly: anomaly.clone(),
+                params_epoch: state.analysis_params.epoch,
+            });
+        }
+    }
+}
+
+/// Serve the dashboard streams and API on `port` in the background
+pub fn spawn(manager: Arc<MultiCurrencyManager>, status: crate::protocol::RemoteSystemStatus, hub: StreamHub, port: u16) -> tokio::task::JoinHandle<()> {
+    let routes = routes(ApiState::new(manager, status), hub);
+    println!("🌐 Dashboard server on http://0.0.0.0:{} (ws://…/ws/prices, ws://…/ws/anomalies, /api/status, /api/throughput)", port);
+    tokio::spawn(warp::serve(routes).run(([0, 0, 0, 0], port)))
+}
@@ -0,0 +1,231 @@
+//! # What-If Cycle Editor
+//!
+//! Lets an operator rescale, phase-shift or switch off detected cycles and see
+//! the forecast cone and composite score they would produce, next to the ones
+//! the unedited cycles give. A score that collapses when one cycle is switched
+//! off is being carried by that cycle alone.
+
+use std::f64::consts::PI;
+
+use crate::backtest::median_spacing_seconds;
+use crate::data::ForexDataPoint;
+use crate::patterns::HiddenCycle;
+use crate::signal::{CompositeScore, CompositeScorer};
+
+/// Bars used to estimate the one-bar return volatility that widens the cone
+const VOLATILITY_WINDOW: usize = 200;
+
+/// Bars the cone projects ahead
+pub const DEFAULT_HORIZON_BARS: usize = 20;
+
+/// Amplitude multiplier change per key press
+pub const AMPLITUDE_STEP: f64 = 0.1;
+
+/// Phase change per key press, in radians
+pub const PHASE_STEP: f64 = PI / 12.0;
+
+/// Operator override of one cycle
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CycleEdit {
+    pub enabled: bool,
+    /// Multiplies the detected amplitude
+    pub amplitude_scale: f64,
+    /// Added to the detected phase, in radians
+    pub phase_shift: f64,
+}
+
+impl Default for CycleEdit {
+    fn default() -> Self {
+        Self { enabled: true, amplitude_scale: 1.0, phase_shift: 0.0 }
+    }
+}
+
+impl CycleEdit {
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// `cycle` as edited, or `None` when switched off
+    pub fn apply(&self, cycle: &HiddenCycle) -> Option<HiddenCycle> {
+        self.enabled.then(|| HiddenCycle {
+            amplitude: cycle.amplitude * self.amplitude_scale,
+            phase: (cycle.phase + self.phase_shift).rem_euclid(2.0 * PI),
+            ..cycle.clone()
+        })
+    }
+}
+
+/// Price band of one projected bar
+#[derive(Debug, Clone, Copy)]
+pub struct ConePoint {
+    /// Bars after the last close
+    pub step: usize,
+    pub center: f64,
+    pub lower: f64,
+    pub upper: f64,
+}
+
+/// Cycle projection from the last close, widened by return volatility
+#[derive(Debug, Clone, Default)]
+pub struct ForecastCone {
+    pub points: Vec<ConePoint>,
+}
+
+impl ForecastCone {
+    /// Project `cycles` `horizon` bars past the end of `history`. The center moves
+    /// by the change in the summed cycle waves; the band is `z` one-bar return
+    /// standard deviations scaled by the square root of the steps ahead.
+    pub fn project(history: &[ForexDataPoint], cycles: &[HiddenCycle], horizon: usize, z: f64) -> Self {
+        let Some(last) = history.last() else {
+            return Self::default();
+        };
+        let tail = &history[history.len().saturating_sub(VOLATILITY_WINDOW)..];
+        let bar_seconds = median_spacing_seconds(tail).max(1.0);
+        let t0 = last.timestamp.timestamp() as f64 / bar_seconds;
+        let volatility = return_volatility(tail);
+        // Cycle amplitudes are fractions of the mean price they were fitted on
+        let mean_price = tail.iter().map(|bar| bar.close).sum::<f64>() / tail.len() as f64;
+        let wave = |t: f64| -> f64 {
+            cycles.iter()
+                .filter(|c| c.period > 0)
+                .map(|c| c.amplitude * mean_price * (2.0 * PI / c.period as f64 * t + c.phase).sin())
+                .sum()
+        };
+        let now = wave(t0);
+        let points = (1..=horizon)
+            .map(|step| {
+                let center = last.close + wave(t0 + step as f64) - now;
+                let half_width = z * volatility * last.close * (step as f64).sqrt();
+                ConePoint { step, center, lower: center - half_width, upper: center + half_width }
+            })
+            .collect();
+        Self { points }
+    }
+
+    /// Projected move from the last close to the end of the cone
+    pub fn drift(&self, last_close: f64) -> f64 {
+        self.points.last().map_or(0.0, |point| point.center - last_close)
+    }
+}
+
+fn return_volatility(bars: &[ForexDataPoint]) -> f64 {
+    let returns: Vec<f64> = bars.windows(2)
+        .filter(|w| w[0].close > 0.0)
+        .map(|w| w[1].close / w[0].close - 1.0)
+        .collect();
+    if returns.is_empty() {
+        return 0.0;
+    }
+    let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+    (returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / returns.len() as f64).sqrt()
+}
+
+/// Forecast and score under one set of cycles
+#[derive(Debug, Clone)]
+pub struct WhatIfOutcome {
+    pub cone: ForecastCone,
+    pub score: Option<CompositeScore>,
+}
+
+/// Detected cycles with the operator's edits
+#[derive(Debug, Clone)]
+pub struct CycleEditor {
+    cycles: Vec<HiddenCycle>,
+    edits: Vec<CycleEdit>,
+    selected: usize,
+    pub horizon_bars: usize,
+    /// Cone half-width in one-bar standard deviations at one step ahead
+    pub cone_z: f64,
+}
+
+impl Default for CycleEditor {
+    fn default() -> Self {
+        Self { cycles: Vec::new(), edits: Vec::new(), selected: 0, horizon_bars: DEFAULT_HORIZON_BARS, cone_z: 1.96 }
+    }
+}
+
+impl CycleEditor {
+    /// Replace the detected cycles, keeping the edits of cycles whose ids survive
+    pub fn set_cycles(&mut self, cycles: &[HiddenCycle]) {
+        self.edits = cycles.iter()
+            .map(|cycle| self.cycles.iter()
+                .position(|old| old.id == cycle.id)
+                .map_or_else(CycleEdit::default, |i| self.edits[i]))
+            .collect();
+        self.cycles = cycles.to_vec();
+        self.selected = self.selected.min(self.cycles.len().saturating_sub(1));
+    }
+
+    pub fn cycles(&self) -> &[HiddenCycle] {
+        &self.cycles
+    }
+
+    pub fn edits(&self) -> &[CycleEdit] {
+        &self.edits
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.cycles.is_empty() {
+            self.selected = (self.selected + 1) % self.cycles.len();
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        if !self.cycles.is_empty() {
+            self.selected = (self.selected + self.cycles.len() - 1) % self.cycles.len();
+        }
+    }
+
+    /// Switch the selected cycle off or back on
+    pub fn toggle(&mut self) {
+        if let Some(edit) = self.edits.get_mut(self.selected) {
+            edit.enabled = !edit.enabled;
+        }
+    }
+
+    /// Change the selected cycle's amplitude multiplier by `delta`, never below zero
+    pub fn scale_amplitude(&mut self, delta: f64) {
+        if let Some(edit) = self.edits.get_mut(self.selected) {
+            edit.amplitude_scale = (edit.amplitude_scale + delta).max(0.0);
+        }
+    }
+
+    /// Shift the selected cycle's phase by `radians`
+    pub fn shift_phase(&mut self, radians: f64) {
+        if let Some(edit) = self.edits.get_mut(self.selected) {
+            edit.phase_shift = (edit.phase_shift + radians).rem_euclid(2.0 * PI);
+        }
+    }
+
+    /// Drop every edit
+    pub fn reset(&mut self) {
+        self.edits.iter_mut().for_each(|edit| *edit = CycleEdit::default());
+    }
+
+    pub fn is_edited(&self) -> bool {
+        self.edits.iter().any(|edit| !edit.is_identity())
+    }
+
+    /// The cycles as edited, without those switched off
+    pub fn edited_cycles(&self) -> Vec<HiddenCycle> {
+        self.cycles.iter().zip(&self.edits).filter_map(|(cycle, edit)| edit.apply(cycle)).collect()
+    }
+
+    /// Forecast and score of the last bar of `history` under the detected and
+    /// the edited cycles. `scorer` keeps its symmetries; its cycles are swapped.
+    pub fn evaluate(&self, history: &[ForexDataPoint], scorer: &CompositeScorer) -> (WhatIfOutcome, WhatIfOutcome) {
+        let outcome = |cycles: &[HiddenCycle]| {
+            let mut scorer = scorer.clone();
+            scorer.set_cycles(cycles);
+            WhatIfOutcome {
+                cone: ForecastCone::project(history, cycles, self.horizon_bars, self.cone_z),
+                score: scorer.score(history, &[]),
+            }
+        };
+        (outcome(&self.cycles), outcome(&self.edited_cycles()))
+    }
+}
@@ -0,0 +1,146 @@
+//! # Streaming Bars
+//!
+//! Live ticks folded into mid-price OHLC bars on several timeframes at once. A
+//! bar completes when the next one's first tick arrives or, on a quiet market,
+//! on the clock after a grace period; completed bars go to every subscriber.
+
+use anyhow::{bail, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tokio::sync::broadcast;
+
+use super::provider::Tick;
+use super::tick::{TickBar, TickDataPoint};
+use super::timeframe::TimeframeAggregator;
+
+/// Completed bars buffered per subscriber before the slowest one starts missing them
+const BAR_CHANNEL_CAPACITY: usize = 1024;
+
+/// Default wait past a bar's end for delayed ticks before closing it on the clock
+const DEFAULT_GRACE_MS: i64 = 2000;
+
+/// A completed bar of one pair and timeframe
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiveBar {
+    pub symbol: String,
+    pub timeframe: String,
+    /// Mid-price OHLC with the tick count as volume, and the spreads quoted
+    pub bar: TickBar,
+}
+
+/// Bar in progress for one pair and timeframe, and the start of the last one completed
+#[derive(Debug, Default)]
+struct BarState {
+    current: Option<TickBar>,
+    last_completed: Option<DateTime<Utc>>,
+}
+
+/// Builds bars of every configured timeframe from a tick stream
+#[derive(Debug)]
+pub struct BarBuilder {
+    aggregators: Vec<TimeframeAggregator>,
+    states: HashMap<(String, usize), BarState>,
+    grace: Duration,
+    late_ticks: u64,
+    sender: broadcast::Sender<LiveBar>,
+}
+
+impl BarBuilder {
+    /// Builder for timeframe names such as "M1", "H1" or "D1"
+    pub fn new<S: AsRef<str>>(timeframes: &[S]) -> Result<Self> {
+        if timeframes.is_empty() {
+            bail!("bar builder needs at least one timeframe");
+        }
+        let aggregators = timeframes.iter()
+            .map(|timeframe| TimeframeAggregator::new(timeframe.as_ref()))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            aggregators,
+            states: HashMap::new(),
+            grace: Duration::milliseconds(DEFAULT_GRACE_MS),
+            late_ticks: 0,
+            sender: broadcast::channel(BAR_CHANNEL_CAPACITY).0,
+        })
+    }
+
+    /// Wait `grace` past a bar's end before closing it without a newer tick
+    pub fn with_grace(mut self, grace: Duration) -> Self {
+        self.grace = grace.max(Duration::zero());
+        self
+    }
+
+    /// Receive every bar completed from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<LiveBar> {
+        self.sender.subscribe()
+    }
+
+    pub fn timeframes(&self) -> impl Iterator<Item = &str> {
+        self.aggregators.iter().map(|aggregator| aggregator.timeframe())
+    }
+
+    /// Ticks dropped on some timeframe because their bar was already completed
+    pub fn late_ticks(&self) -> u64 {
+        self.late_ticks
+    }
+
+    /// The bar of `symbol` still being built on `timeframe`
+    pub fn current(&self, symbol: &str, timeframe: &str) -> Option<&TickBar> {
+        let index = self.aggregators.iter().position(|aggregator| aggregator.timeframe().eq_ignore_ascii_case(timeframe))?;
+        self.states.get(&(symbol.to_string(), index))?.current.as_ref()
+    }
+
+    /// Fold in a tick, returning (and broadcasting) the bars it completes
+    pub fn push(&mut self, tick: &Tick) -> Vec<LiveBar> {
+        let quote = TickDataPoint::from(tick);
+        let mut completed = Vec::new();
+        let mut late = false;
+        for (index, aggregator) in self.aggregators.iter().enumerate() {
+            let start = aggregator.bucket_start(tick.timestamp);
+            let state = self.states.entry((tick.symbol.clone(), index)).or_default();
+            if state.last_completed.is_some_and(|last| start <= last) {
+                late = true;
+                continue;
+            }
+            match state.current.as_mut() {
+                Some(current) if current.bar.timestamp == start => current.add(&quote),
+                Some(current) if start < current.bar.timestamp => late = true,
+                _ => {
+                    if let Some(bar) = state.current.replace(TickBar::open(start, &quote)) {
+                        state.last_completed = Some(bar.bar.timestamp);
+                        completed.push(LiveBar { symbol: tick.symbol.clone(), timeframe: aggregator.timeframe().to_string(), bar });
+                    }
+                }
+            }
+        }
+        if late {
+            self.late_ticks += 1;
+        }
+        self.publish(completed)
+    }
+
+    /// Complete bars whose interval and grace period ended by `now`, returning
+    /// (and broadcasting) them
+    pub fn close_due(&mut self, now: DateTime<Utc>) -> Vec<LiveBar> {
+        let mut completed = Vec::new();
+        for ((symbol, index), state) in &mut self.states {
+            let aggregator = &self.aggregators[*index];
+            let due = state.current.as_ref()
+                .is_some_and(|current| current.bar.timestamp + aggregator.interval() + self.grace <= now);
+            if let Some(bar) = state.current.take_if(|_| due) {
+                state.last_completed = Some(bar.bar.timestamp);
+                completed.push(LiveBar { symbol: symbol.clone(), timeframe: aggregator.timeframe().to_string(), bar });
+            }
+        }
+        completed.sort_by(|a, b| a.bar.bar.timestamp.cmp(&b.bar.bar.timestamp).then_with(|| a.symbol.cmp(&b.symbol)));
+        self.publish(completed)
+    }
+
+    fn publish(&self, completed: Vec<LiveBar>) -> Vec<LiveBar> {
+        for bar in &completed {
+            // No subscribers is not an error: the bars are returned as well
+            let _ = self.sender.send(bar.clone());
+        }
+        completed
+    }
+}
@@ -0,0 +1,151 @@
+//! # Parquet and Arrow Ingestion
+//!
+//! Bars or ticks stored as Parquet or Arrow IPC files, read through lazy Polars
+//! scans so a multi-GB tick dataset never has to fit in memory.
+
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, TimeZone, Utc};
+use polars::prelude::*;
+use std::path::Path;
+
+use super::provider::timeframe_duration;
+use super::ForexDataPoint;
+
+const TIME_COLUMNS: [&str; 4] = ["timestamp", "time", "datetime", "date"];
+const VOLUME_COLUMNS: [&str; 2] = ["volume", "tick_volume"];
+
+/// Integer epochs above this are in milliseconds, below it in seconds
+const EPOCH_MILLIS_THRESHOLD: i64 = 100_000_000_000;
+
+/// Columnar file formats
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColumnarFormat {
+    Parquet,
+    Arrow,
+}
+
+impl ColumnarFormat {
+    /// Format implied by a file extension, if it is a columnar one
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "parquet" | "pq" => Some(ColumnarFormat::Parquet),
+            "arrow" | "ipc" | "feather" => Some(ColumnarFormat::Arrow),
+            _ => None,
+        }
+    }
+}
+
+/// Lazy scan of `path`; nothing is read until the frame is collected
+pub fn scan(path: &Path, format: ColumnarFormat) -> Result<LazyFrame> {
+    Ok(match format {
+        ColumnarFormat::Parquet => LazyFrame::scan_parquet(path, ScanArgsParquet::default())?,
+        ColumnarFormat::Arrow => LazyFrame::scan_ipc(path, ScanArgsIpc::default())?,
+    })
+}
+
+/// Load bars from `path`. Tick files are aggregated to `timeframe` bars of mid prices,
+/// with the tick count as volume; bar files are returned as stored, sorted by time.
+/// Columns are matched case-insensitively: a time (`timestamp`, `time`, `datetime` or
+/// `date`, as a datetime, date or epoch seconds or milliseconds), then `open`, `high`,
+/// `low`, `close` and optionally `volume` or `tick_volume`, or `bid`/`ask` or `price`.
+pub fn load(path: &Path, format: ColumnarFormat, timeframe: &str) -> Result<Vec<ForexDataPoint>> {
+    let frame = scan(path, format)?;
+    let schema = frame.schema()?;
+    let names: Vec<String> = schema.iter_names().map(|name| name.to_string()).collect();
+    let find = |candidates: &[&str]| -> Option<String> {
+        candidates.iter()
+            .find_map(|candidate| names.iter().find(|name| name.eq_ignore_ascii_case(candidate)))
+            .cloned()
+    };
+
+    let time = find(&TIME_COLUMNS)
+        .ok_or_else(|| anyhow!("{}: no time column (expected one of {:?})", path.display(), TIME_COLUMNS))?;
+    let time_ms = epoch_millis(&time, schema.get(&time).expect("column from schema"))?;
+
+    let df = match (find(&["open"]), find(&["high"]), find(&["low"]), find(&["close"])) {
+        (Some(open), Some(high), Some(low), Some(close)) => {
+            let volume = match find(&VOLUME_COLUMNS) {
+                Some(volume) => col(&volume).cast(DataType::Float64),
+                None => lit(NULL).cast(DataType::Float64),
+            };
+            frame
+                .select([
+                    time_ms.alias("ts_ms"),
+                    col(&open).cast(DataType::Float64).alias("open"),
+                    col(&high).cast(DataType::Float64).alias("high"),
+                    col(&low).cast(DataType::Float64).alias("low"),
+                    col(&close).cast(DataType::Float64).alias("close"),
+                    volume.alias("volume"),
+                ])
+                .sort("ts_ms", SortOptions::default())
+                .collect()?
+        }
+        _ => {
+            let mid = match (find(&["bid"]), find(&["ask"]), find(&["price"])) {
+                (Some(bid), Some(ask), _) => (col(&bid).cast(DataType::Float64) + col(&ask).cast(DataType::Float64)) / lit(2.0),
+                (_, _, Some(price)) => col(&price).cast(DataType::Float64),
+                _ => bail!("{}: neither OHLC bar columns nor bid/ask or price tick columns", path.display()),
+            };
+            let interval_ms = timeframe_duration(timeframe)?.num_milliseconds();
+            let bars = frame
+                .select([time_ms.alias("ts_ms"), mid.alias("mid")])
+                .with_column((col("ts_ms") - col("ts_ms") % lit(interval_ms)).alias("ts_ms"))
+                .group_by([col("ts_ms")])
+                .agg([
+                    col("mid").first().alias("open"),
+                    col("mid").max().alias("high"),
+                    col("mid").min().alias("low"),
+                    col("mid").last().alias("close"),
+                    col("mid").count().cast(DataType::Float64).alias("volume"),
+                ])
+                .sort("ts_ms", SortOptions::default())
+                .with_streaming(true);
+            // The streaming Parquet source blocks on Polars' own runtime, which panics
+            // when called from a tokio worker, so collect on a thread outside it
+            std::thread::scope(|scope| scope.spawn(|| bars.collect()).join())
+                .map_err(|_| anyhow!("{}: tick aggregation panicked", path.display()))??
+        }
+    };
+
+    to_points(&df)
+}
+
+/// Expression for `column` as Int64 milliseconds since the Unix epoch
+fn epoch_millis(column: &str, dtype: &DataType) -> Result<Expr> {
+    let value = col(column);
+    Ok(match dtype {
+        DataType::Datetime(_, _) | DataType::Date => {
+            value.cast(DataType::Datetime(TimeUnit::Milliseconds, None)).cast(DataType::Int64)
+        }
+        dtype if dtype.is_integer() => {
+            let value = value.cast(DataType::Int64);
+            when(value.clone().gt(lit(EPOCH_MILLIS_THRESHOLD)))
+                .then(value.clone())
+                .otherwise(value * lit(1_000i64))
+        }
+        other => bail!("time column '{}' has unsupported type {}; store it as a datetime or epoch integer", column, other),
+    })
+}
+
+/// Convert a collected frame with `ts_ms`, OHLC and `volume` columns to data points
+fn to_points(df: &DataFrame) -> Result<Vec<ForexDataPoint>> {
+    let time = df.column("ts_ms")?.cast(&DataType::Int64)?;
+    let time = time.i64()?;
+    let [open, high, low, close, volume] = ["open", "high", "low", "close", "volume"].map(|name| df.column(name));
+    let (open, high, low, close, volume) = (open?.f64()?, high?.f64()?, low?.f64()?, close?.f64()?, volume?.f64()?);
+
+    let mut points = Vec::with_capacity(df.height());
+    for i in 0..df.height() {
+        let (Some(ms), Some(open), Some(high), Some(low), Some(close)) =
+            (time.get(i), open.get(i), high.get(i), low.get(i), close.get(i)) else {
+            continue; // incomplete row
+        };
+        points.push(ForexDataPoint { timestamp: millis_to_utc(ms)?, open, high, low, close, volume: volume.get(i) });
+    }
+    Ok(points)
+}
+
+fn millis_to_utc(ms: i64) -> Result<DateTime<Utc>> {
+    Utc.timestamp_millis_opt(ms).single().ok_or_else(|| anyhow!("timestamp {} ms is out of range", ms))
+}
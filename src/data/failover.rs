@@ -0,0 +1,309 @@
+//! # Multi-Source Feed Failover
+//!
+//! Combines several providers into one. Each pair has a priority order of sources;
+//! ticks from the active source are forwarded (duplicates and out-of-order ticks
+//! dropped), quotes from the other sources are compared against it for divergence,
+//! and the pair fails over to the next live source when the active one goes silent.
+
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
+use futures_util::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{broadcast, mpsc};
+
+use super::provider::{DataProvider, Tick};
+use super::ForexDataPoint;
+
+/// Failover thresholds and per-pair source priorities
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FailoverConfig {
+    /// Source names in priority order per pair; pairs not listed use the provider order
+    pub pair_priority: HashMap<String, Vec<String>>,
+    /// A source with no tick for this long is considered silent
+    pub silence_timeout_ms: i64,
+    /// Relative mid-price difference between sources that counts as divergence
+    pub divergence_tolerance: f64,
+}
+
+impl Default for FailoverConfig {
+    fn default() -> Self {
+        Self {
+            pair_priority: HashMap::new(),
+            silence_timeout_ms: 5000,
+            divergence_tolerance: 0.0005, // 5 pips on EURUSD
+        }
+    }
+}
+
+/// Something the failover layer wants operators to know about
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum FeedEvent {
+    /// The pair now follows a different source
+    Switched {
+        symbol: String,
+        from: Option<String>,
+        to: String,
+        reason: String,
+        at: DateTime<Utc>,
+    },
+    /// Two sources disagree beyond the tolerance
+    Divergence {
+        symbol: String,
+        active: String,
+        other: String,
+        active_mid: f64,
+        other_mid: f64,
+        relative_difference: f64,
+        at: DateTime<Utc>,
+    },
+}
+
+/// Provider that fails over between prioritized sources
+pub struct FailoverProvider {
+    sources: Vec<Arc<dyn DataProvider>>,
+    config: FailoverConfig,
+    events: broadcast::Sender<FeedEvent>,
+}
+
+impl FailoverProvider {
+    pub fn new(sources: Vec<Arc<dyn DataProvider>>, config: FailoverConfig) -> Result<Self> {
+        if sources.is_empty() {
+            bail!("failover provider needs at least one source");
+        }
+        Ok(Self {
+            sources,
+            config,
+            events: broadcast::channel(256).0,
+        })
+    }
+
+    /// Source switches and divergence warnings
+    pub fn events(&self) -> broadcast::Receiver<FeedEvent> {
+        self.events.subscribe()
+    }
+
+    /// Source indices for `pair`, highest priority first
+    fn priority(&self, pair: &str) -> Vec<usize> {
+        priority_order(&self.sources, &self.config, pair)
+    }
+}
+
+fn priority_order(sources: &[Arc<dyn DataProvider>], config: &FailoverConfig, pair: &str) -> Vec<usize> {
+    let Some(names) = config.pair_priority.get(&pair.to_uppercase()) else {
+        return (0..sources.len()).collect();
+    };
+    let mut order: Vec<usize> = names.iter()
+        .filter_map(|name| sources.iter().position(|source| source.name() == name))
+        .collect();
+    // Unlisted sources stay available as last resorts
+    let unlisted: Vec<usize> = (0..sources.len()).filter(|i| !order.contains(i)).collect();
+    order.extend(unlisted);
+    order
+}
+
+impl DataProvider for FailoverProvider {
+    fn name(&self) -> &str {
+        "failover"
+    }
+
+    fn fetch_historical<'a>(
+        &'a self,
+        pair: &'a str,
+        timeframe: &'a str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> BoxFuture<'a, Result<Vec<ForexDataPoint>>> {
+        Box::pin(async move {
+            let mut last_error = None;
+            for index in self.priority(pair) {
+                let source = &self.sources[index];
+                match source.fetch_historical(pair, timeframe, from, to).await {
+                    Ok(bars) if !bars.is_empty() => return Ok(bars),
+                    Ok(_) => {}
+                    Err(e) => {
+                        println!("⚠️  {} history from {} failed: {}", pair, source.name(), e);
+                        last_error = Some(e);
+                    }
+                }
+            }
+            match last_error {
+                Some(e) => Err(e),
+                None => Ok(Vec::new()),
+            }
+        })
+    }
+
+    fn subscribe_ticks(&self, pairs: &[String]) -> Result<mpsc::Receiver<Tick>> {
+        let (merged_sender, mut merged) = mpsc::channel::<(usize, Tick)>(1024);
+        let mut subscribed = 0;
+        for (index, source) in self.sources.iter().enumerate() {
+            match source.subscribe_ticks(pairs) {
+                Ok(mut ticks) => {
+                    subscribed += 1;
+                    let sender = merged_sender.clone();
+                    tokio::spawn(async move {
+                        while let Some(tick) = ticks.recv().await {
+                            if sender.send((index, tick)).await.is_err() {
+                                break;
+                            }
+                        }
+                    });
+                }
+                Err(e) => println!("⚠️  {} has no tick feed: {}", source.name(), e),
+            }
+        }
+        drop(merged_sender);
+        if subscribed == 0 {
+            bail!("none of the failover sources provides ticks");
+        }
+
+        let names: Vec<String> = self.sources.iter().map(|source| source.name().to_string()).collect();
+        let priorities: HashMap<String, Vec<usize>> = pairs.iter()
+            .map(|pair| (pair.to_uppercase(), priority_order(&self.sources, &self.config, pair)))
+            .collect();
+        let mut router = FailoverRouter::new(names, priorities, self.config.clone(), self.events.clone());
+        let (sender, receiver) = mpsc::channel(1024);
+        let check_every = std::time::Duration::from_millis((self.config.silence_timeout_ms / 4).max(50) as u64);
+
+        tokio::spawn(async move {
+            let mut watchdog = tokio::time::interval(check_every);
+            loop {
+                tokio::select! {
+                    incoming = merged.recv() => {
+                        let Some((source, tick)) = incoming else { break };
+                        if let Some(tick) = router.route(source, tick, Utc::now()) {
+                            if sender.send(tick).await.is_err() {
+                                break;
+                            }
+                        }
+                    }
+                    _ = watchdog.tick() => router.check_silence(Utc::now()),
+                }
+            }
+        });
+
+        Ok(receiver)
+    }
+}
+
+/// Per-pair routing state
+#[derive(Default)]
+struct PairRoute {
+    active: Option<usize>,
+    last_forwarded: Option<DateTime<Utc>>,
+    /// Latest tick and its arrival time per source
+    latest: HashMap<usize, (Tick, DateTime<Utc>)>,
+}
+
+/// Chooses the active source per pair and filters ticks
+struct FailoverRouter {
+    names: Vec<String>,
+    priorities: HashMap<String, Vec<usize>>,
+    config: FailoverConfig,
+    events: broadcast::Sender<FeedEvent>,
+    routes: HashMap<String, PairRoute>,
+}
+
+impl FailoverRouter {
+    fn new(
+        names: Vec<String>,
+        priorities: HashMap<String, Vec<usize>>,
+        config: FailoverConfig,
+        events: broadcast::Sender<FeedEvent>,
+    ) -> Self {
+        Self { names, priorities, config, events, routes: HashMap::new() }
+    }
+
+    /// Record a tick from `source`, returning it when it should be forwarded
+    fn route(&mut self, source: usize, tick: Tick, now: DateTime<Utc>) -> Option<Tick> {
+        let symbol = tick.symbol.to_uppercase();
+        self.routes.entry(symbol.clone()).or_default().latest.insert(source, (tick.clone(), now));
+        self.select_source(&symbol, now);
+
+        let route = self.routes.get_mut(&symbol)?;
+        if route.active != Some(source) {
+            return None;
+        }
+        self.check_divergence(&symbol, source, &tick, now);
+
+        let route = self.routes.get_mut(&symbol)?;
+        if route.last_forwarded.is_some_and(|last| tick.timestamp <= last) {
+            return None; // duplicate or out of order
+        }
+        route.last_forwarded = Some(tick.timestamp);
+        Some(tick)
+    }
+
+    /// Re-select sources for every pair (called periodically to catch silent sources)
+    fn check_silence(&mut self, now: DateTime<Utc>) {
+        let symbols: Vec<String> = self.routes.keys().cloned().collect();
+        for symbol in symbols {
+            self.select_source(&symbol, now);
+        }
+    }
+
+    /// Make the highest-priority live source active, emitting an event on change
+    fn select_source(&mut self, symbol: &str, now: DateTime<Utc>) {
+        let timeout = chrono::Duration::milliseconds(self.config.silence_timeout_ms);
+        let order = self.priorities.get(symbol).cloned().unwrap_or_else(|| (0..self.names.len()).collect());
+        let Some(route) = self.routes.get_mut(symbol) else { return };
+
+        let live = |source: &usize| route.latest.get(source).is_some_and(|(_, at)| now - *at <= timeout);
+        let Some(best) = order.iter().copied().find(|source| live(source)) else {
+            return; // every source is silent: keep the current one
+        };
+        if route.active == Some(best) {
+            return;
+        }
+
+        let reason = match route.active {
+            None => "initial source".to_string(),
+            Some(current) if !live(&current) => format!("{} silent for over {}ms", self.names[current], self.config.silence_timeout_ms),
+            Some(_) => "higher-priority source recovered".to_string(),
+        };
+        let event = FeedEvent::Switched {
+            symbol: symbol.to_string(),
+            from: route.active.map(|current| self.names[current].clone()),
+            to: self.names[best].clone(),
+            reason,
+            at: now,
+        };
+        route.active = Some(best);
+
+        if let FeedEvent::Switched { from: Some(from), to, reason, .. } = &event {
+            println!("🔀 {} feed switched {} → {} ({})", symbol, from, to, reason);
+        }
+        let _ = self.events.send(event);
+    }
+
+    fn check_divergence(&self, symbol: &str, active: usize, tick: &Tick, now: DateTime<Utc>) {
+        let Some(route) = self.routes.get(symbol) else { return };
+        let timeout = chrono::Duration::milliseconds(self.config.silence_timeout_ms);
+        let active_mid = tick.mid();
+
+        for (&other, (other_tick, at)) in &route.latest {
+            if other == active || now - *at > timeout || active_mid <= 0.0 {
+                continue;
+            }
+            let other_mid = other_tick.mid();
+            let relative_difference = (active_mid - other_mid).abs() / active_mid;
+            if relative_difference > self.config.divergence_tolerance {
+                println!("⚠️  {} sources diverge: {} {:.5} vs {} {:.5}",
+                         symbol, self.names[active], active_mid, self.names[other], other_mid);
+                let _ = self.events.send(FeedEvent::Divergence {
+                    symbol: symbol.to_string(),
+                    active: self.names[active].clone(),
+                    other: self.names[other].clone(),
+                    active_mid,
+                    other_mid,
+                    relative_difference,
+                    at: now,
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,216 @@
+//! # Feed Health
+//!
+//! Watches live tick timestamps against the wall clock: feeds that go quiet for
+//! several expected intervals are flagged stale, and ticks or bars dated in the
+//! future (clock skew) are rejected and counted.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::provider::Tick;
+
+/// Thresholds for the feed monitor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FeedHealthConfig {
+    /// Expected time between ticks for a pair
+    pub expected_interval_ms: i64,
+    /// A feed is stale after this many expected intervals without a tick
+    pub stale_after_intervals: f64,
+    /// Largest tolerated difference between tick time and receive time
+    pub max_clock_skew_ms: i64,
+}
+
+impl Default for FeedHealthConfig {
+    fn default() -> Self {
+        Self {
+            expected_interval_ms: 1000,
+            stale_after_intervals: 10.0,
+            max_clock_skew_ms: 2000,
+        }
+    }
+}
+
+impl FeedHealthConfig {
+    fn stale_after(&self) -> Duration {
+        Duration::milliseconds((self.expected_interval_ms as f64 * self.stale_after_intervals) as i64)
+    }
+}
+
+/// Health of one pair's feed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FeedState {
+    /// No tick received yet
+    Waiting,
+    Healthy,
+    /// No tick within the stale threshold
+    Stale,
+    /// The last tick was rejected as too far from the wall clock
+    Skewed,
+}
+
+impl FeedState {
+    /// Trading should be held back in this state
+    pub fn blocks_trading(&self) -> bool {
+        matches!(self, FeedState::Stale | FeedState::Skewed)
+    }
+}
+
+impl std::fmt::Display for FeedState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            FeedState::Waiting => "waiting",
+            FeedState::Healthy => "healthy",
+            FeedState::Stale => "stale",
+            FeedState::Skewed => "skewed",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// Per-pair feed statistics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairFeedHealth {
+    pub symbol: String,
+    pub state: FeedState,
+    /// When monitoring of the pair started
+    pub watched_since: DateTime<Utc>,
+    /// Exchange timestamp of the last accepted tick
+    pub last_tick_at: Option<DateTime<Utc>>,
+    /// Wall-clock time the last tick arrived
+    pub last_received_at: Option<DateTime<Utc>>,
+    /// Receive time minus tick time for the last tick (negative = tick from the future)
+    pub clock_skew_ms: Option<i64>,
+    pub ticks_received: u64,
+    /// Ticks or bars rejected for being future-dated
+    pub future_dated: u64,
+}
+
+/// Feed health across every monitored pair
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedHealthReport {
+    pub checked_at: DateTime<Utc>,
+    /// Worst state across the pairs
+    pub overall: FeedState,
+    pub pairs: Vec<PairFeedHealth>,
+}
+
+/// A pair moving between feed states
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeedTransition {
+    pub symbol: String,
+    pub from: FeedState,
+    pub to: FeedState,
+}
+
+/// Tracks tick arrival per pair and classifies feed health
+#[derive(Debug, Clone)]
+pub struct FeedHealthMonitor {
+    config: FeedHealthConfig,
+    pairs: BTreeMap<String, PairFeedHealth>,
+}
+
+impl FeedHealthMonitor {
+    pub fn new(config: FeedHealthConfig) -> Self {
+        Self { config, pairs: BTreeMap::new() }
+    }
+
+    pub fn config(&self) -> &FeedHealthConfig {
+        &self.config
+    }
+
+    /// Start watching `symbol` before its first tick, so a silent feed is noticed
+    pub fn watch(&mut self, symbol: &str, now: DateTime<Utc>) {
+        self.entry(symbol, now);
+    }
+
+    /// Record a tick received at `received_at`; returns `false` when it is rejected as future-dated
+    pub fn record_tick(&mut self, tick: &Tick, received_at: DateTime<Utc>) -> bool {
+        let max_skew = self.config.max_clock_skew_ms;
+        let health = self.entry(&tick.symbol, received_at);
+        let skew_ms = (received_at - tick.timestamp).num_milliseconds();
+        health.clock_skew_ms = Some(skew_ms);
+
+        if -skew_ms > max_skew {
+            health.future_dated += 1;
+            health.state = FeedState::Skewed;
+            return false;
+        }
+
+        health.ticks_received += 1;
+        health.last_tick_at = Some(tick.timestamp);
+        health.last_received_at = Some(received_at);
+        health.state = FeedState::Healthy;
+        true
+    }
+
+    /// Whether a bar timestamp is acceptable at `now`; future-dated bars are counted against `symbol`
+    pub fn accept_bar(&mut self, symbol: &str, bar_time: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        if (bar_time - now).num_milliseconds() > self.config.max_clock_skew_ms {
+            self.entry(symbol, now).future_dated += 1;
+            return false;
+        }
+        true
+    }
+
+    /// Re-evaluate staleness at `now`, returning the pairs whose state changed
+    pub fn check(&mut self, now: DateTime<Utc>) -> Vec<FeedTransition> {
+        let stale_after = self.config.stale_after();
+        let mut transitions = Vec::new();
+
+        for health in self.pairs.values_mut() {
+            let last_heard = health.last_received_at.unwrap_or(health.watched_since);
+            let next = if now - last_heard > stale_after { FeedState::Stale } else { health.state };
+            if next != health.state {
+                transitions.push(FeedTransition { symbol: health.symbol.clone(), from: health.state, to: next });
+                health.state = next;
+            }
+        }
+
+        transitions
+    }
+
+    pub fn state(&self, symbol: &str) -> Option<FeedState> {
+        self.pairs.get(symbol).map(|health| health.state)
+    }
+
+    /// Snapshot of every pair
+    pub fn report(&self, now: DateTime<Utc>) -> FeedHealthReport {
+        let overall = self.pairs.values()
+            .map(|health| health.state)
+            .max_by_key(|state| match state {
+                FeedState::Healthy => 0,
+                FeedState::Waiting => 1,
+                FeedState::Skewed => 2,
+                FeedState::Stale => 3,
+            })
+            .unwrap_or(FeedState::Waiting);
+
+        FeedHealthReport {
+            checked_at: now,
+            overall,
+            pairs: self.pairs.values().cloned().collect(),
+        }
+    }
+
+    fn entry(&mut self, symbol: &str, now: DateTime<Utc>) -> &mut PairFeedHealth {
+        self.pairs.entry(symbol.to_string()).or_insert_with(|| PairFeedHealth {
+            symbol: symbol.to_string(),
+            state: FeedState::Waiting,
+            watched_since: now,
+            last_tick_at: None,
+            last_received_at: None,
+            clock_skew_ms: None,
+            ticks_received: 0,
+            future_dated: 0,
+        })
+    }
+}
+
+impl Default for FeedHealthMonitor {
+    fn default() -> Self {
+        Self::new(FeedHealthConfig::default())
+    }
+}
@@ -2,10 +2,21 @@
 //!
 //! Data loading, processing, and real-time feed management for forex analysis.
 
+pub mod bars;
+pub mod columnar;
+pub mod failover;
+pub mod health;
+pub mod provider;
+pub mod quality;
+pub mod tick;
+pub mod timeframe;
+pub mod timezone;
+
 use anyhow::Result;
 use chrono::{DateTime, Utc, NaiveDateTime, NaiveDate};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
 use std::collections::HashMap;
 use csv::ReaderBuilder;
 use polars::prelude::*;
@@ -21,12 +32,43 @@ pub struct ForexDataPoint {
     pub volume: Option<f64>,
 }
 
+/// A bar from any source the detectors accept: historical, live or synthetic
+pub trait MarketPoint {
+    fn data_point(&self) -> &ForexDataPoint;
+
+    /// The series as plain bars, copied only when the points are not bars already
+    fn data_points(points: &[Self]) -> Cow<'_, [ForexDataPoint]>
+    where
+        Self: Sized,
+    {
+        Cow::Owned(points.iter().map(|point| point.data_point().clone()).collect())
+    }
+}
+
+impl MarketPoint for ForexDataPoint {
+    fn data_point(&self) -> &ForexDataPoint {
+        self
+    }
+
+    fn data_points(points: &[Self]) -> Cow<'_, [ForexDataPoint]> {
+        Cow::Borrowed(points)
+    }
+}
+
 /// Data configuration
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct DataConfig {
     pub data_directory: PathBuf,
     pub cache_enabled: bool,
     pub max_cache_size: usize,
+    #[serde(default)]
+    pub missing_data_policy: MissingDataPolicy,
+    /// Gap, duplicate, price and spike checks run on every load, and how gaps are filled
+    #[serde(default)]
+    pub quality: quality::DataQualityConfig,
+    /// Time zones of naive timestamps in CSV sources
+    #[serde(default)]
+    pub timezones: timezone::TimezoneConfig,
 }
 
 impl Default for DataConfig {
@@ -35,33 +77,179 @@ impl Default for DataConfig {
             data_directory: PathBuf::from("FOREX DATA"),
             cache_enabled: true,
             max_cache_size: 1000000,
+            missing_data_policy: MissingDataPolicy::default(),
+            quality: quality::DataQualityConfig::default(),
+            timezones: timezone::TimezoneConfig::default(),
         }
     }
 }
 
+/// What to do when historical data cannot be found
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MissingDataPolicy {
+    /// Return the load error
+    Fail,
+    /// Ask on the terminal for an alternative path, demo data or feed-only mode
+    /// (fails like `Fail` when not attached to a terminal, so daemons never trade on demo data)
+    #[default]
+    Prompt,
+    /// Generate seeded demo data via the synthetic module
+    Demo,
+    /// Continue without history and rely on the live feed
+    FeedOnly,
+}
+
+impl std::str::FromStr for MissingDataPolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "fail" => Ok(MissingDataPolicy::Fail),
+            "prompt" => Ok(MissingDataPolicy::Prompt),
+            "demo" => Ok(MissingDataPolicy::Demo),
+            "feed-only" | "feed" => Ok(MissingDataPolicy::FeedOnly),
+            _ => Err(anyhow::anyhow!("Unknown missing-data policy: {}", s)),
+        }
+    }
+}
+
+/// Where loaded historical data actually came from
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum HistoricalSource {
+    Files(PathBuf),
+    Demo,
+    FeedOnly,
+}
+
+/// Seed used for demo data generated as a fallback
+const FALLBACK_DEMO_SEED: u64 = 42;
+
+/// Days of demo history generated as a fallback
+const FALLBACK_DEMO_DAYS: u32 = 3650;
+
+/// Default location of the daily major-pair dataset
+pub const DEFAULT_DAILY_DATA_PATH: &str = "FOREX DATA/Forex Daily (1980) - 2023/archive(4)/Forex_D1/Major";
+
+/// Pair file extensions tried in a data directory, in order
+const PAIR_FILE_EXTENSIONS: [&str; 4] = ["csv", "parquet", "arrow", "feather"];
+
 /// Forex data manager
 pub struct ForexDataManager {
     config: DataConfig,
+    quality_report: Option<quality::DataQualityReport>,
 }
 
 impl ForexDataManager {
     pub fn new(config: DataConfig) -> Result<Self> {
-        Ok(Self { config })
+        Ok(Self { config, quality_report: None })
+    }
+
+    /// Change how missing historical data is handled
+    pub fn set_missing_data_policy(&mut self, policy: MissingDataPolicy) {
+        self.config.missing_data_policy = policy;
     }
 
-    /// Load historical forex data from various sources
+    /// Load historical forex data from various sources, checked and repaired by
+    /// the data-quality pass
     pub async fn load_data(
         &mut self,
         input: &PathBuf,
         pair: &str,
         timeframe: &str,
     ) -> Result<Vec<ForexDataPoint>> {
-        if input.is_file() {
-            self.load_csv_file(input)
+        let data = if input.is_file() {
+            self.load_file(input, timeframe)?
         } else if input.is_dir() {
-            self.load_from_directory(input, pair, timeframe).await
+            self.load_from_directory(input, pair, timeframe).await?
         } else {
-            Err(anyhow::anyhow!("Invalid input path: {}", input.display()))
+            return Err(anyhow::anyhow!("Invalid input path: {}", input.display()));
+        };
+
+        let (data, report) = quality::DataQualityChecker::new(self.config.quality.clone()).clean(data);
+        if !report.is_clean() {
+            println!("⚠️  Data quality for {}: {}", pair, report.summary());
+        }
+        self.quality_report = Some(report);
+        Ok(data)
+    }
+
+    /// Data-quality report of the last `load_data`
+    pub fn quality_report(&self) -> Option<&quality::DataQualityReport> {
+        self.quality_report.as_ref()
+    }
+
+    /// Check `data` against the configured data-quality settings without changing it
+    pub fn check_quality(&self, data: &[ForexDataPoint]) -> quality::DataQualityReport {
+        quality::DataQualityChecker::new(self.config.quality.clone()).check(data)
+    }
+
+    /// Load historical data, degrading gracefully when the input path is missing
+    /// according to the configured `MissingDataPolicy`
+    pub async fn load_data_or_fallback(
+        &mut self,
+        input: &PathBuf,
+        pair: &str,
+        timeframe: &str,
+    ) -> Result<(Vec<ForexDataPoint>, HistoricalSource)> {
+        let error = match self.load_data(input, pair, timeframe).await {
+            Ok(data) if !data.is_empty() => return Ok((data, HistoricalSource::Files(input.clone()))),
+            Ok(_) => anyhow::anyhow!("No data points for {} in {}", pair, input.display()),
+            Err(e) => e,
+        };
+
+        println!("⚠️  Historical data for {} unavailable: {}", pair, error);
+
+        let mut policy = self.config.missing_data_policy;
+        loop {
+            match policy {
+                MissingDataPolicy::Fail => {
+                    return Err(error.context(format!(
+                        "Historical data for {} not found. Pass a different data directory, \
+                         or use the demo/feed-only missing-data policy", pair)));
+                }
+                MissingDataPolicy::Demo => {
+                    println!("🧪 Generating seeded demo data for {}", pair);
+                    let data = crate::synthetic::demo::generate_demo_history(
+                        pair, Utc::now(), FALLBACK_DEMO_DAYS, FALLBACK_DEMO_SEED).await?;
+                    return Ok((data, HistoricalSource::Demo));
+                }
+                MissingDataPolicy::FeedOnly => {
+                    println!("📡 Starting {} in feed-only mode (no historical analysis)", pair);
+                    return Ok((Vec::new(), HistoricalSource::FeedOnly));
+                }
+                MissingDataPolicy::Prompt => {
+                    use std::io::{BufRead, IsTerminal, Write};
+
+                    if !std::io::stdin().is_terminal() {
+                        return Err(error.context(format!(
+                            "Historical data for {} not found and stdin is not a terminal to ask for it. \
+                             Pass a different data directory, or use the demo/feed-only missing-data policy", pair)));
+                    }
+
+                    print!("📂 Enter an alternative data path, 'demo' for generated data, or 'feed' for feed-only mode [demo]: ");
+                    std::io::stdout().flush()?;
+                    let mut answer = String::new();
+                    std::io::stdin().lock().read_line(&mut answer)?;
+                    let answer = answer.trim();
+
+                    match answer.to_lowercase().as_str() {
+                        "" | "demo" => policy = MissingDataPolicy::Demo,
+                        "feed" | "feed-only" => policy = MissingDataPolicy::FeedOnly,
+                        _ => {
+                            let alternative = PathBuf::from(answer);
+                            match self.load_data(&alternative, pair, timeframe).await {
+                                Ok(data) if !data.is_empty() => {
+                                    self.config.data_directory = alternative.clone();
+                                    return Ok((data, HistoricalSource::Files(alternative)));
+                                }
+                                Ok(_) => println!("❌ No data for {} in {}", pair, alternative.display()),
+                                Err(e) => println!("❌ {}", e),
+                            }
+                        }
+                    }
+                }
+            }
         }
     }
 
@@ -83,16 +271,45 @@ impl ForexDataManager {
         self.load_csv_file(data_file)
     }
 
-    /// Load standard CSV format (time,open,high,low,close,volume)
+    /// Load a data file by extension: Parquet (`.parquet`, `.pq`), Arrow IPC
+    /// (`.arrow`, `.ipc`, `.feather`) or CSV otherwise. Tick files are aggregated
+    /// to `timeframe` bars.
+    pub fn load_file(&self, file_path: &PathBuf, timeframe: &str) -> Result<Vec<ForexDataPoint>> {
+        match columnar::ColumnarFormat::from_path(file_path) {
+            Some(columnar::ColumnarFormat::Parquet) => self.load_parquet(file_path, timeframe),
+            Some(columnar::ColumnarFormat::Arrow) => self.load_arrow(file_path, timeframe),
+            None => self.load_csv_file(file_path),
+        }
+    }
+
+    /// Load bars, or ticks aggregated to `timeframe`, from a Parquet file via a lazy scan
+    pub fn load_parquet(&self, file_path: &Path, timeframe: &str) -> Result<Vec<ForexDataPoint>> {
+        columnar::load(file_path, columnar::ColumnarFormat::Parquet, timeframe)
+    }
+
+    /// Load bars, or ticks aggregated to `timeframe`, from an Arrow IPC file via a lazy scan
+    pub fn load_arrow(&self, file_path: &Path, timeframe: &str) -> Result<Vec<ForexDataPoint>> {
+        columnar::load(file_path, columnar::ColumnarFormat::Arrow, timeframe)
+    }
+
+    /// Load bid/ask ticks from a CSV file, converting naive times from the file's
+    /// configured time zone; see [`tick`] for the recognized layouts
+    pub fn load_tick_csv(&self, file_path: &Path) -> Result<Vec<tick::TickDataPoint>> {
+        tick::load_tick_csv(file_path, None, self.config.timezones.configured_zone_for(file_path)?)
+    }
+
+    /// Load standard CSV format (time,open,high,low,close,volume), converting naive
+    /// times from the file's configured time zone
     pub fn load_csv_file(&self, file_path: &PathBuf) -> Result<Vec<ForexDataPoint>> {
         let mut data = Vec::new();
         let mut reader = ReaderBuilder::new()
             .has_headers(true)
             .from_path(file_path)?;
+        let mut converter = timezone::LocalTimeConverter::new(self.config.timezones.zone_for(file_path)?);
 
         for result in reader.deserialize() {
             let record: CsvRecord = result?;
-            let data_point = self.parse_csv_record(record)?;
+            let data_point = self.parse_csv_record(record, &mut converter)?;
             data.push(data_point);
         }
 
@@ -108,10 +325,11 @@ impl ForexDataManager {
         let mut reader = ReaderBuilder::new()
             .has_headers(true)
             .from_path(file_path)?;
+        let mut converter = timezone::LocalTimeConverter::new(self.config.timezones.zone_for(file_path)?);
 
         for result in reader.deserialize() {
             let record: OandaCsvRecord = result?;
-            let data_point = self.parse_oanda_record(record)?;
+            let data_point = self.parse_oanda_record(record, &mut converter)?;
             data.push(data_point);
         }
 
@@ -128,27 +346,45 @@ impl ForexDataManager {
         pair: &str,
         timeframe: &str,
     ) -> Result<Vec<ForexDataPoint>> {
-        // Look for specific pair file in directory
-        let pair_file = dir_path.join(format!("{}.csv", pair));
-        if pair_file.exists() {
-            return self.load_csv_file(&pair_file);
-        }
-
-        // Look in subdirectories
-        let major_dir = dir_path.join("Major");
-        if major_dir.exists() {
-            let pair_file = major_dir.join(format!("{}.csv", pair));
-            if pair_file.exists() {
-                return self.load_csv_file(&pair_file);
+        // Look for specific pair file in directory, then in subdirectories
+        for directory in [dir_path.clone(), dir_path.join("Major")] {
+            for extension in PAIR_FILE_EXTENSIONS {
+                let pair_file = directory.join(format!("{}.{}", pair, extension));
+                if pair_file.exists() {
+                    return self.load_file(&pair_file, timeframe);
+                }
             }
         }
 
         Err(anyhow::anyhow!("Could not find data for pair {} in directory {}", pair, dir_path.display()))
     }
 
+    /// Write data points in the standard CSV format read by `load_csv_file`
+    pub fn save_csv_file(&self, file_path: &Path, data: &[ForexDataPoint]) -> Result<()> {
+        if let Some(parent) = file_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut writer = csv::Writer::from_path(file_path)?;
+        writer.write_record(["time", "open", "high", "low", "close", "tick_volume"])?;
+        for point in data {
+            writer.write_record([
+                point.timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
+                format!("{:.6}", point.open),
+                format!("{:.6}", point.high),
+                format!("{:.6}", point.low),
+                format!("{:.6}", point.close),
+                point.volume.map(|v| v.to_string()).unwrap_or_default(),
+            ])?;
+        }
+        writer.flush()?;
+
+        Ok(())
+    }
+
     /// Parse standard CSV record
-    fn parse_csv_record(&self, record: CsvRecord) -> Result<ForexDataPoint> {
-        let timestamp = self.parse_timestamp(&record.time)?;
+    fn parse_csv_record(&self, record: CsvRecord, converter: &mut timezone::LocalTimeConverter) -> Result<ForexDataPoint> {
+        let timestamp = self.parse_timestamp(&record.time, converter)?;
 
         Ok(ForexDataPoint {
             timestamp,
@@ -161,9 +397,9 @@ impl ForexDataManager {
     }
 
     /// Parse Oanda CSV record
-    fn parse_oanda_record(&self, record: OandaCsvRecord) -> Result<ForexDataPoint> {
+    fn parse_oanda_record(&self, record: OandaCsvRecord, converter: &mut timezone::LocalTimeConverter) -> Result<ForexDataPoint> {
         let datetime_str = format!("{} {}", record.date, record.time);
-        let timestamp = self.parse_oanda_timestamp(&datetime_str)?;
+        let timestamp = self.parse_oanda_timestamp(&datetime_str, converter)?;
 
         Ok(ForexDataPoint {
             timestamp,
@@ -175,17 +411,18 @@ impl ForexDataManager {
         })
     }
 
-    /// Parse timestamp from various formats
-    fn parse_timestamp(&self, time_str: &str) -> Result<DateTime<Utc>> {
+    /// Parse timestamp from various formats; naive date-times are in the source's zone
+    fn parse_timestamp(&self, time_str: &str, converter: &mut timezone::LocalTimeConverter) -> Result<DateTime<Utc>> {
         // Try different timestamp formats
         if let Ok(dt) = DateTime::parse_from_rfc3339(time_str) {
             return Ok(dt.with_timezone(&Utc));
         }
 
         if let Ok(naive_dt) = NaiveDateTime::parse_from_str(time_str, "%Y-%m-%d %H:%M:%S") {
-            return Ok(DateTime::from_naive_utc_and_offset(naive_dt, Utc));
+            return Ok(converter.to_utc(naive_dt));
         }
 
+        // A bare date labels a trading day, not an instant in the source's zone
         if let Ok(naive_date) = NaiveDate::parse_from_str(time_str, "%Y-%m-%d") {
             let naive_dt = naive_date.and_hms_opt(0, 0, 0).unwrap();
             return Ok(DateTime::from_naive_utc_and_offset(naive_dt, Utc));
@@ -195,9 +432,9 @@ impl ForexDataManager {
     }
 
     /// Parse Oanda timestamp format
-    fn parse_oanda_timestamp(&self, datetime_str: &str) -> Result<DateTime<Utc>> {
+    fn parse_oanda_timestamp(&self, datetime_str: &str, converter: &mut timezone::LocalTimeConverter) -> Result<DateTime<Utc>> {
         let naive_dt = NaiveDateTime::parse_from_str(datetime_str, "%Y-%m-%d %H:%M")?;
-        Ok(DateTime::from_naive_utc_and_offset(naive_dt, Utc))
+        Ok(converter.to_utc(naive_dt))
     }
 
     /// Get available data summary
@@ -307,6 +544,9 @@ pub struct RealTimeDataFeed {
     current_data: Vec<ForexDataPoint>,
     update_interval: std::time::Duration,
     pairs: Vec<String>,
+    ticks: Option<tokio::sync::mpsc::Receiver<provider::Tick>>,
+    health: health::FeedHealthMonitor,
+    bars: Option<bars::BarBuilder>,
 }
 
 impl RealTimeDataFeed {
@@ -314,11 +554,20 @@ impl RealTimeDataFeed {
         // Load configuration from file
         let config_str = std::fs::read_to_string(config_path)?;
         let config: RealTimeFeedConfig = toml::from_str(&config_str)?;
+        let bars = (!config.bar_timeframes.is_empty())
+            .then(|| bars::BarBuilder::new(&config.bar_timeframes))
+            .transpose()?;
 
         Ok(Self {
             current_data: Vec::new(),
             update_interval: std::time::Duration::from_millis(config.update_interval_ms),
             pairs: config.pairs,
+            ticks: None,
+            health: health::FeedHealthMonitor::new(health::FeedHealthConfig {
+                expected_interval_ms: config.update_interval_ms as i64,
+                ..health::FeedHealthConfig::default()
+            }),
+            bars,
         })
     }
 
@@ -327,9 +576,77 @@ impl RealTimeDataFeed {
             current_data: Vec::new(),
             update_interval: std::time::Duration::from_millis(1000),
             pairs: vec!["EURUSD".to_string(), "GBPUSD".to_string(), "USDJPY".to_string()],
+            ticks: None,
+            health: health::FeedHealthMonitor::default(),
+            bars: None,
         })
     }
 
+    /// Build bars of `timeframes` (e.g. "M1", "H1") from the ticks polled
+    pub fn with_bar_timeframes<S: AsRef<str>>(mut self, timeframes: &[S]) -> Result<Self> {
+        self.bars = Some(bars::BarBuilder::new(timeframes)?);
+        Ok(self)
+    }
+
+    /// Receive every bar completed from now on; `None` when no bar timeframes are configured
+    pub fn subscribe_bars(&self) -> Option<tokio::sync::broadcast::Receiver<bars::LiveBar>> {
+        self.bars.as_ref().map(|bars| bars.subscribe())
+    }
+
+    /// The bar builder fed by `poll`, if bar timeframes are configured
+    pub fn bar_builder(&self) -> Option<&bars::BarBuilder> {
+        self.bars.as_ref()
+    }
+
+    /// Subscribe to live ticks for the monitored pairs
+    pub fn connect(&mut self, provider: &dyn provider::DataProvider) -> Result<()> {
+        self.ticks = Some(provider.subscribe_ticks(&self.pairs)?);
+        let now = Utc::now();
+        for pair in &self.pairs {
+            self.health.watch(pair, now);
+        }
+        Ok(())
+    }
+
+    /// Whether a live provider is attached
+    pub fn is_live(&self) -> bool {
+        self.ticks.is_some()
+    }
+
+    /// Drain ticks received since the last poll, recording their mid prices and
+    /// folding them into bars, which are completed and broadcast as they close.
+    /// Future-dated ticks are dropped and counted by the feed health monitor.
+    pub fn poll(&mut self) -> Vec<provider::Tick> {
+        let mut received = Vec::new();
+        if let Some(ticks) = self.ticks.as_mut() {
+            let now = Utc::now();
+            while let Ok(tick) = ticks.try_recv() {
+                if self.health.record_tick(&tick, now) {
+                    received.push(tick);
+                }
+            }
+            self.health.check(now);
+        }
+        if let Some(bars) = self.bars.as_mut() {
+            for tick in &received {
+                bars.push(tick);
+            }
+            bars.close_due(Utc::now());
+        }
+        for tick in &received {
+            let mid = tick.mid();
+            self.update_data(ForexDataPoint {
+                timestamp: tick.timestamp,
+                open: mid,
+                high: mid,
+                low: mid,
+                close: mid,
+                volume: None,
+            });
+        }
+        received
+    }
+
     /// Get current market data
     pub fn get_current_data(&self) -> &[ForexDataPoint] {
         &self.current_data
@@ -345,6 +662,11 @@ impl RealTimeDataFeed {
         }
     }
 
+    /// Feed health of the live provider
+    pub fn health(&self) -> health::FeedHealthReport {
+        self.health.report(Utc::now())
+    }
+
     /// Get update interval
     pub fn get_update_interval(&self) -> std::time::Duration {
         self.update_interval
@@ -363,4 +685,7 @@ struct RealTimeFeedConfig {
     pairs: Vec<String>,
     #[serde(default)]
     data_source: String,
+    /// Timeframes of the bars built from live ticks, none when empty
+    #[serde(default)]
+    bar_timeframes: Vec<String>,
 }
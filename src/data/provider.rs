@@ -0,0 +1,433 @@
+//! # Data Providers
+//!
+//! Pluggable sources of historical bars and live ticks. `CsvProvider` serves the
+//! on-disk datasets; `OandaProvider` talks to the OANDA v20 REST API.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use futures_util::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::{mpsc, Mutex};
+
+use super::failover::{FailoverConfig, FailoverProvider};
+use super::{DataConfig, ForexDataManager, ForexDataPoint};
+
+/// Ticks buffered per subscription before the feed task waits for the consumer
+const TICK_CHANNEL_CAPACITY: usize = 1024;
+
+/// Top-of-book quote for one pair
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tick {
+    pub symbol: String,
+    pub timestamp: DateTime<Utc>,
+    pub bid: f64,
+    pub ask: f64,
+}
+
+impl Tick {
+    pub fn mid(&self) -> f64 {
+        (self.bid + self.ask) / 2.0
+    }
+
+    pub fn spread(&self) -> f64 {
+        self.ask - self.bid
+    }
+}
+
+/// Source of historical bars and live ticks
+pub trait DataProvider: Send + Sync {
+    /// Short name used in logs
+    fn name(&self) -> &str;
+
+    /// Bars for `pair` at `timeframe` (e.g. "D1", "H1") between `from` and `to`, oldest first
+    fn fetch_historical<'a>(
+        &'a self,
+        pair: &'a str,
+        timeframe: &'a str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> BoxFuture<'a, Result<Vec<ForexDataPoint>>>;
+
+    /// Start streaming ticks for `pairs`; the feed stops when the receiver is dropped
+    fn subscribe_ticks(&self, pairs: &[String]) -> Result<mpsc::Receiver<Tick>>;
+}
+
+/// Build the provider selected by `DATA_PROVIDER` ("oanda", "csv" or a list such as "oanda,csv"), if any
+pub fn provider_from_env() -> Result<Option<Arc<dyn DataProvider>>> {
+    let Ok(names) = std::env::var("DATA_PROVIDER").map(|p| p.to_lowercase()) else {
+        return Ok(None);
+    };
+    // A comma-separated list ("oanda,csv") fails over between sources in that order
+    let sources = names.split(',')
+        .map(|name| provider_by_name(name.trim()))
+        .collect::<Result<Vec<_>>>()?;
+    if sources.len() == 1 {
+        return Ok(sources.into_iter().next());
+    }
+    Ok(Some(Arc::new(FailoverProvider::new(sources, FailoverConfig::default())?)))
+}
+
+fn provider_by_name(name: &str) -> Result<Arc<dyn DataProvider>> {
+    match name {
+        "oanda" => Ok(Arc::new(OandaProvider::new(OandaConfig::from_env()?)?)),
+        "csv" => {
+            let directory = std::env::var("FOREX_DATA_PATH")
+                .unwrap_or_else(|_| super::DEFAULT_DAILY_DATA_PATH.to_string());
+            Ok(Arc::new(CsvProvider::new(PathBuf::from(directory))?))
+        }
+        other => bail!("Unknown DATA_PROVIDER '{}' (expected oanda or csv)", other),
+    }
+}
+
+/// Length of one bar for a timeframe name
+pub fn timeframe_duration(timeframe: &str) -> Result<Duration> {
+    Ok(match timeframe.to_uppercase().as_str() {
+        "M1" | "1M" => Duration::minutes(1),
+        "M5" | "5M" => Duration::minutes(5),
+        "M15" | "15M" => Duration::minutes(15),
+        "M30" | "30M" => Duration::minutes(30),
+        "H1" | "1H" => Duration::hours(1),
+        "H4" | "4H" => Duration::hours(4),
+        "D1" | "1D" | "D" => Duration::days(1),
+        "W1" | "1W" | "W" => Duration::weeks(1),
+        other => bail!("Unsupported timeframe '{}'", other),
+    })
+}
+
+/// Folds ticks into fixed-interval mid-price bars
+#[derive(Debug, Clone)]
+pub struct BarAggregator {
+    interval: Duration,
+    current: Option<ForexDataPoint>,
+}
+
+impl BarAggregator {
+    pub fn new(interval: Duration) -> Self {
+        Self { interval, current: None }
+    }
+
+    /// Add a tick, returning the previous bar once a tick opens a new one
+    pub fn push(&mut self, tick: &Tick) -> Option<ForexDataPoint> {
+        let price = tick.mid();
+        let bar_start = tick.timestamp.duration_trunc(self.interval).unwrap_or(tick.timestamp);
+
+        if let Some(bar) = self.current.as_mut().filter(|bar| bar.timestamp == bar_start) {
+            bar.high = bar.high.max(price);
+            bar.low = bar.low.min(price);
+            bar.close = price;
+            bar.volume = Some(bar.volume.unwrap_or(0.0) + 1.0);
+            return None;
+        }
+        if self.current.as_ref().is_some_and(|bar| bar_start < bar.timestamp) {
+            return None; // late tick for a bar already closed
+        }
+
+        self.current.replace(ForexDataPoint {
+            timestamp: bar_start,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume: Some(1.0),
+        })
+    }
+
+    /// The bar still being built
+    pub fn current(&self) -> Option<&ForexDataPoint> {
+        self.current.as_ref()
+    }
+}
+
+/// Historical bars from the CSV datasets; no live ticks
+pub struct CsvProvider {
+    directory: PathBuf,
+    manager: Mutex<ForexDataManager>,
+}
+
+impl CsvProvider {
+    pub fn new(directory: PathBuf) -> Result<Self> {
+        Ok(Self {
+            directory,
+            manager: Mutex::new(ForexDataManager::new(DataConfig::default())?),
+        })
+    }
+}
+
+impl DataProvider for CsvProvider {
+    fn name(&self) -> &str {
+        "csv"
+    }
+
+    fn fetch_historical<'a>(
+        &'a self,
+        pair: &'a str,
+        timeframe: &'a str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> BoxFuture<'a, Result<Vec<ForexDataPoint>>> {
+        Box::pin(async move {
+            let data = self.manager.lock().await.load_data(&self.directory, pair, timeframe).await?;
+            Ok(data.into_iter().filter(|p| p.timestamp >= from && p.timestamp <= to).collect())
+        })
+    }
+
+    fn subscribe_ticks(&self, _pairs: &[String]) -> Result<mpsc::Receiver<Tick>> {
+        bail!("the csv provider has no live ticks")
+    }
+}
+
+/// Ticks pushed through a channel by its owner, e.g. a replay or a test; no history
+pub struct ChannelProvider {
+    name: String,
+    receiver: std::sync::Mutex<Option<mpsc::Receiver<Tick>>>,
+}
+
+impl ChannelProvider {
+    /// The provider and the sender that feeds its single subscription
+    pub fn new(name: &str) -> (Self, mpsc::Sender<Tick>) {
+        let (sender, receiver) = mpsc::channel(TICK_CHANNEL_CAPACITY);
+        (Self { name: name.to_string(), receiver: std::sync::Mutex::new(Some(receiver)) }, sender)
+    }
+}
+
+impl DataProvider for ChannelProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn fetch_historical<'a>(
+        &'a self,
+        _pair: &'a str,
+        _timeframe: &'a str,
+        _from: DateTime<Utc>,
+        _to: DateTime<Utc>,
+    ) -> BoxFuture<'a, Result<Vec<ForexDataPoint>>> {
+        Box::pin(async { Ok(Vec::new()) })
+    }
+
+    fn subscribe_ticks(&self, _pairs: &[String]) -> Result<mpsc::Receiver<Tick>> {
+        self.receiver.lock().unwrap_or_else(|e| e.into_inner()).take()
+            .with_context(|| format!("the {} provider is already subscribed", self.name))
+    }
+}
+
+/// OANDA v20 REST API settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OandaConfig {
+    /// https://api-fxpractice.oanda.com for practice, https://api-fxtrade.oanda.com for live
+    pub api_url: String,
+    pub api_token: String,
+    pub account_id: String,
+    /// Pricing poll interval for the tick feed
+    pub poll_interval_ms: u64,
+}
+
+impl OandaConfig {
+    /// Read `OANDA_API_TOKEN`, `OANDA_ACCOUNT_ID` and optional `OANDA_API_URL` / `OANDA_POLL_MS`
+    pub fn from_env() -> Result<Self> {
+        Ok(Self {
+            api_url: std::env::var("OANDA_API_URL")
+                .unwrap_or_else(|_| "https://api-fxpractice.oanda.com".to_string()),
+            api_token: std::env::var("OANDA_API_TOKEN").context("OANDA_API_TOKEN is not set")?,
+            account_id: std::env::var("OANDA_ACCOUNT_ID").context("OANDA_ACCOUNT_ID is not set")?,
+            poll_interval_ms: std::env::var("OANDA_POLL_MS").ok()
+                .and_then(|ms| ms.parse().ok())
+                .unwrap_or(1000),
+        })
+    }
+}
+
+/// Maximum candles OANDA returns per request
+const OANDA_MAX_CANDLES: i32 = 5000;
+
+/// Historical candles and polled pricing from OANDA
+pub struct OandaProvider {
+    client: reqwest::Client,
+    config: OandaConfig,
+}
+
+#[derive(Deserialize)]
+struct OandaCandles {
+    candles: Vec<OandaCandle>,
+}
+
+#[derive(Deserialize)]
+struct OandaCandle {
+    time: DateTime<Utc>,
+    volume: f64,
+    complete: bool,
+    mid: OandaOhlc,
+}
+
+#[derive(Deserialize)]
+struct OandaOhlc {
+    o: String,
+    h: String,
+    l: String,
+    c: String,
+}
+
+#[derive(Deserialize)]
+struct OandaPricing {
+    prices: Vec<OandaPrice>,
+}
+
+#[derive(Deserialize)]
+struct OandaPrice {
+    instrument: String,
+    time: DateTime<Utc>,
+    bids: Vec<OandaPriceBucket>,
+    asks: Vec<OandaPriceBucket>,
+}
+
+#[derive(Deserialize)]
+struct OandaPriceBucket {
+    price: String,
+}
+
+impl OandaProvider {
+    pub fn new(config: OandaConfig) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(30))
+            .build()?;
+        Ok(Self { client, config })
+    }
+
+    /// "EURUSD" → "EUR_USD"
+    pub fn instrument(pair: &str) -> String {
+        let pair = pair.to_uppercase().replace(['_', '/'], "");
+        if pair.len() == 6 {
+            format!("{}_{}", &pair[..3], &pair[3..])
+        } else {
+            pair
+        }
+    }
+
+    /// Timeframe name → OANDA granularity
+    pub fn granularity(timeframe: &str) -> Result<&'static str> {
+        Ok(match timeframe.to_uppercase().as_str() {
+            "M1" | "1M" => "M1",
+            "M5" | "5M" => "M5",
+            "M15" | "15M" => "M15",
+            "M30" | "30M" => "M30",
+            "H1" | "1H" => "H1",
+            "H4" | "4H" => "H4",
+            "D1" | "1D" | "D" => "D",
+            "W1" | "1W" | "W" => "W",
+            other => bail!("OANDA does not support timeframe '{}'", other),
+        })
+    }
+
+    async fn get<T: serde::de::DeserializeOwned>(&self, path: &str, query: &[(&str, String)]) -> Result<T> {
+        let response = self.client
+            .get(format!("{}{}", self.config.api_url.trim_end_matches('/'), path))
+            .bearer_auth(&self.config.api_token)
+            .query(query)
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            bail!("OANDA {} returned {}: {}", path, status, response.text().await.unwrap_or_default());
+        }
+        Ok(response.json().await?)
+    }
+
+    async fn poll_pricing(&self, instruments: &str) -> Result<Vec<Tick>> {
+        let pricing: OandaPricing = self.get(
+            &format!("/v3/accounts/{}/pricing", self.config.account_id),
+            &[("instruments", instruments.to_string())],
+        ).await?;
+
+        pricing.prices.into_iter()
+            .filter_map(|price| {
+                let bid = price.bids.first()?.price.parse().ok()?;
+                let ask = price.asks.first()?.price.parse().ok()?;
+                Some(Ok(Tick { symbol: price.instrument.replace('_', ""), timestamp: price.time, bid, ask }))
+            })
+            .collect()
+    }
+}
+
+impl DataProvider for OandaProvider {
+    fn name(&self) -> &str {
+        "oanda"
+    }
+
+    fn fetch_historical<'a>(
+        &'a self,
+        pair: &'a str,
+        timeframe: &'a str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> BoxFuture<'a, Result<Vec<ForexDataPoint>>> {
+        Box::pin(async move {
+            let granularity = Self::granularity(timeframe)?;
+            let bar = timeframe_duration(timeframe)?;
+            let path = format!("/v3/instruments/{}/candles", Self::instrument(pair));
+            let mut bars = Vec::new();
+            let mut cursor = from;
+
+            // Page through the range; each request returns at most OANDA_MAX_CANDLES
+            while cursor < to {
+                let page_end = (cursor + bar * OANDA_MAX_CANDLES).min(to);
+                let page: OandaCandles = self.get(&path, &[
+                    ("price", "M".to_string()),
+                    ("granularity", granularity.to_string()),
+                    ("from", cursor.to_rfc3339()),
+                    ("to", page_end.to_rfc3339()),
+                ]).await?;
+
+                for candle in page.candles.into_iter().filter(|c| c.complete) {
+                    bars.push(ForexDataPoint {
+                        timestamp: candle.time,
+                        open: candle.mid.o.parse()?,
+                        high: candle.mid.h.parse()?,
+                        low: candle.mid.l.parse()?,
+                        close: candle.mid.c.parse()?,
+                        volume: Some(candle.volume),
+                    });
+                }
+                cursor = page_end;
+            }
+
+            bars.dedup_by_key(|point| point.timestamp);
+            Ok(bars)
+        })
+    }
+
+    fn subscribe_ticks(&self, pairs: &[String]) -> Result<mpsc::Receiver<Tick>> {
+        let (sender, receiver) = mpsc::channel(TICK_CHANNEL_CAPACITY);
+        let instruments = pairs.iter().map(|p| Self::instrument(p)).collect::<Vec<_>>().join(",");
+        let provider = OandaProvider { client: self.client.clone(), config: self.config.clone() };
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_millis(provider.config.poll_interval_ms));
+            let mut last_seen: HashMap<String, DateTime<Utc>> = HashMap::new();
+            loop {
+                interval.tick().await;
+                let ticks = match provider.poll_pricing(&instruments).await {
+                    Ok(ticks) => ticks,
+                    Err(e) => {
+                        println!("⚠️  OANDA pricing poll failed: {}", e);
+                        continue;
+                    }
+                };
+                for tick in ticks {
+                    // Pricing repeats the last quote until it changes
+                    if last_seen.get(&tick.symbol) == Some(&tick.timestamp) {
+                        continue;
+                    }
+                    last_seen.insert(tick.symbol.clone(), tick.timestamp);
+                    if sender.send(tick).await.is_err() {
+                        return; // subscriber dropped
+                    }
+                }
+            }
+        });
+
+        Ok(receiver)
+    }
+}
@@ -0,0 +1,411 @@
+//! # Data Quality
+//!
+//! Reports gaps, duplicate timestamps, bad prices and return spikes in loaded
+//! bars, telling weekend and holiday closures from missing trading time, and
+//! cleans them by the configured [`GapFill`] strategy without filling closures.
+
+use anyhow::Result;
+use chrono::{DateTime, Datelike, Duration, Timelike, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use super::ForexDataPoint;
+use crate::calendar::HolidayCalendar;
+
+/// Scale from the median absolute deviation to a normal standard deviation
+const MAD_TO_SIGMA: f64 = 1.4826;
+
+/// How gaps and bad bars are repaired
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GapFill {
+    /// Report only; the data is left as loaded
+    #[default]
+    Keep,
+    /// Missing and bad bars repeat the previous close as a flat bar
+    ForwardFill,
+    /// Missing and bad bars lie on the straight line between their neighbours
+    Interpolate,
+    /// Bad bars are removed and gaps left open
+    Drop,
+}
+
+impl std::str::FromStr for GapFill {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "keep" | "none" => Ok(GapFill::Keep),
+            "forward-fill" | "ffill" => Ok(GapFill::ForwardFill),
+            "interpolate" => Ok(GapFill::Interpolate),
+            "drop" => Ok(GapFill::Drop),
+            other => Err(anyhow::anyhow!("Unknown gap fill strategy: {} (keep, forward-fill, interpolate or drop)", other)),
+        }
+    }
+}
+
+impl fmt::Display for GapFill {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            GapFill::Keep => "keep",
+            GapFill::ForwardFill => "forward-fill",
+            GapFill::Interpolate => "interpolate",
+            GapFill::Drop => "drop",
+        })
+    }
+}
+
+/// Data-quality settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DataQualityConfig {
+    pub gap_fill: GapFill,
+    /// Steps longer than this many bar spacings are gaps
+    pub gap_tolerance: f64,
+    /// Returns beyond this many robust standard deviations are spikes
+    pub spike_sigma: f64,
+    /// Longest gap of missing trading time that is filled, in bars
+    pub max_fill_bars: usize,
+}
+
+impl Default for DataQualityConfig {
+    fn default() -> Self {
+        Self {
+            gap_fill: GapFill::Keep,
+            gap_tolerance: 1.5,
+            spike_sigma: 8.0,
+            max_fill_bars: 10,
+        }
+    }
+}
+
+/// Why bars are missing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GapKind {
+    Weekend,
+    Holiday,
+    Missing,
+}
+
+impl fmt::Display for GapKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            GapKind::Weekend => "weekend",
+            GapKind::Holiday => "holiday",
+            GapKind::Missing => "missing",
+        })
+    }
+}
+
+/// Bars missing between two consecutive bars
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataGap {
+    /// Last bar before the gap
+    pub after: DateTime<Utc>,
+    /// First bar after the gap
+    pub before: DateTime<Utc>,
+    pub missing_bars: usize,
+    pub kind: GapKind,
+}
+
+/// A return beyond the spike threshold
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceSpike {
+    /// Bar the return ends at
+    pub timestamp: DateTime<Utc>,
+    pub log_return: f64,
+    /// Size of the return in robust standard deviations
+    pub sigmas: f64,
+    /// Whether the next bar jumps back, marking this bar as a bad print
+    pub reverted: bool,
+}
+
+/// What the quality pass found in a bar series, and what cleaning changed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DataQualityReport {
+    pub bars: usize,
+    /// Median step between bars, in seconds
+    pub spacing_seconds: i64,
+    pub gaps: Vec<DataGap>,
+    /// Timestamps seen more than once, once each
+    pub duplicate_timestamps: Vec<DateTime<Utc>>,
+    /// Bars with a non-positive, non-finite or crossed price
+    pub invalid_prices: Vec<DateTime<Utc>>,
+    pub spikes: Vec<PriceSpike>,
+    pub gap_fill: GapFill,
+    pub filled_bars: usize,
+    pub replaced_bars: usize,
+    pub dropped_bars: usize,
+}
+
+impl DataQualityReport {
+    /// Bars missing in gaps of `kind`
+    pub fn missing_bars(&self, kind: GapKind) -> usize {
+        self.gaps.iter().filter(|gap| gap.kind == kind).map(|gap| gap.missing_bars).sum()
+    }
+
+    /// Nothing found beyond market closures
+    pub fn is_clean(&self) -> bool {
+        self.missing_bars(GapKind::Missing) == 0
+            && self.duplicate_timestamps.is_empty()
+            && self.invalid_prices.is_empty()
+            && self.spikes.is_empty()
+    }
+
+    pub fn summary(&self) -> String {
+        format!("{} bars: {} gaps ({} missing bars, {} weekend, {} holiday), {} duplicates, {} invalid prices, {} spikes; {}: {} filled, {} replaced, {} dropped",
+                self.bars, self.gaps.len(), self.missing_bars(GapKind::Missing), self.missing_bars(GapKind::Weekend),
+                self.missing_bars(GapKind::Holiday), self.duplicate_timestamps.len(), self.invalid_prices.len(), self.spikes.len(),
+                self.gap_fill, self.filled_bars, self.replaced_bars, self.dropped_bars)
+    }
+
+    pub fn print_report(&self) {
+        println!("\n🩺 Data Quality ({} bars, {}s spacing):", self.bars, self.spacing_seconds);
+        println!("╔═══════════════════════╦═════════╗");
+        println!("║ Check                 ║  Count  ║");
+        println!("╠═══════════════════════╬═════════╣");
+        println!("║ Gaps                  ║ {:7} ║", self.gaps.len());
+        println!("║ Missing bars          ║ {:7} ║", self.missing_bars(GapKind::Missing));
+        println!("║ Weekend bars          ║ {:7} ║", self.missing_bars(GapKind::Weekend));
+        println!("║ Holiday bars          ║ {:7} ║", self.missing_bars(GapKind::Holiday));
+        println!("║ Duplicate timestamps  ║ {:7} ║", self.duplicate_timestamps.len());
+        println!("║ Invalid prices        ║ {:7} ║", self.invalid_prices.len());
+        println!("║ Spikes                ║ {:7} ║", self.spikes.len());
+        println!("╠═══════════════════════╬═════════╣");
+        println!("║ Filled ({:12}) ║ {:7} ║", self.gap_fill, self.filled_bars);
+        println!("║ Replaced              ║ {:7} ║", self.replaced_bars);
+        println!("║ Dropped               ║ {:7} ║", self.dropped_bars);
+        println!("╚═══════════════════════╩═════════╝");
+    }
+}
+
+/// Checks and repairs bar series
+#[derive(Debug, Clone)]
+pub struct DataQualityChecker {
+    config: DataQualityConfig,
+    calendar: HolidayCalendar,
+}
+
+impl DataQualityChecker {
+    pub fn new(config: DataQualityConfig) -> Self {
+        Self { config, calendar: HolidayCalendar::default() }
+    }
+
+    /// Bank holidays that explain gaps
+    pub fn with_calendar(mut self, calendar: HolidayCalendar) -> Self {
+        self.calendar = calendar;
+        self
+    }
+
+    /// Report on `data` as loaded, in any order
+    pub fn check(&self, data: &[ForexDataPoint]) -> DataQualityReport {
+        let mut sorted = data.to_vec();
+        sorted.sort_by_key(|point| point.timestamp);
+        let mut duplicate_timestamps: Vec<DateTime<Utc>> = sorted.windows(2)
+            .filter(|w| w[0].timestamp == w[1].timestamp)
+            .map(|w| w[0].timestamp)
+            .collect();
+        duplicate_timestamps.dedup();
+        // Later checks see each timestamp once, as cleaning keeps it
+        let unique = dedup_last(sorted);
+
+        let spacing = median_spacing(&unique);
+        let gaps = spacing.map(|spacing| self.gaps(&unique, spacing)).unwrap_or_default();
+        let invalid_prices = unique.iter().filter(|point| !is_valid(point)).map(|point| point.timestamp).collect();
+        DataQualityReport {
+            bars: data.len(),
+            spacing_seconds: spacing.map(|s| s.num_seconds()).unwrap_or(0),
+            gaps,
+            duplicate_timestamps,
+            invalid_prices,
+            spikes: self.spikes(&unique),
+            gap_fill: self.config.gap_fill,
+            ..DataQualityReport::default()
+        }
+    }
+
+    /// Report on `data` and repair it by the configured strategy
+    pub fn clean(&self, data: Vec<ForexDataPoint>) -> (Vec<ForexDataPoint>, DataQualityReport) {
+        let mut report = self.check(&data);
+        if self.config.gap_fill == GapFill::Keep {
+            return (data, report);
+        }
+
+        let mut sorted = data;
+        sorted.sort_by_key(|point| point.timestamp);
+        let loaded = sorted.len();
+        let unique = dedup_last(sorted);
+        report.dropped_bars = loaded - unique.len();
+
+        // Bad prints: invalid bars and spikes the next bar takes back
+        let spikes: Vec<DateTime<Utc>> = report.spikes.iter().filter(|spike| spike.reverted).map(|spike| spike.timestamp).collect();
+        let bad: Vec<bool> = unique.iter().map(|point| !is_valid(point) || spikes.contains(&point.timestamp)).collect();
+        let mut repaired: Vec<ForexDataPoint> = Vec::with_capacity(unique.len());
+        for (i, point) in unique.iter().enumerate() {
+            if !bad[i] {
+                repaired.push(point.clone());
+                continue;
+            }
+            let previous = repaired.last().map(|p| (p.timestamp, p.close));
+            let next = unique[i + 1..].iter().zip(&bad[i + 1..]).find(|(_, bad)| !**bad).map(|(p, _)| (p.timestamp, p.open));
+            match (self.config.gap_fill, previous, next) {
+                (GapFill::ForwardFill, Some((_, close)), _) => {
+                    repaired.push(flat_bar(point.timestamp, close));
+                    report.replaced_bars += 1;
+                }
+                (GapFill::Interpolate, Some(from), Some(to)) => {
+                    repaired.push(flat_bar(point.timestamp, interpolate(from, to, point.timestamp)));
+                    report.replaced_bars += 1;
+                }
+                _ => report.dropped_bars += 1,
+            }
+        }
+
+        if matches!(self.config.gap_fill, GapFill::ForwardFill | GapFill::Interpolate) {
+            if let Some(spacing) = median_spacing(&repaired) {
+                let mut filled = Vec::with_capacity(repaired.len());
+                for (i, point) in repaired.iter().enumerate() {
+                    if let Some(previous) = i.checked_sub(1).map(|j| &repaired[j]) {
+                        let missing = self.missing_slots(previous.timestamp, point.timestamp, spacing);
+                        let (closed, open): (Vec<_>, Vec<_>) = missing.into_iter().partition(|slot| self.is_closed(*slot, spacing));
+                        if closed.is_empty() && !open.is_empty() && open.len() <= self.config.max_fill_bars {
+                            for slot in open {
+                                let price = match self.config.gap_fill {
+                                    GapFill::Interpolate => interpolate((previous.timestamp, previous.close), (point.timestamp, point.open), slot),
+                                    _ => previous.close,
+                                };
+                                filled.push(flat_bar(slot, price));
+                                report.filled_bars += 1;
+                            }
+                        }
+                    }
+                    filled.push(point.clone());
+                }
+                repaired = filled;
+            }
+        }
+        (repaired, report)
+    }
+
+    fn gaps(&self, data: &[ForexDataPoint], spacing: Duration) -> Vec<DataGap> {
+        data.windows(2)
+            .filter_map(|w| {
+                let missing = self.missing_slots(w[0].timestamp, w[1].timestamp, spacing);
+                if missing.is_empty() {
+                    return None;
+                }
+                let kind = if missing.iter().all(|slot| is_weekend(*slot, spacing)) {
+                    GapKind::Weekend
+                } else if missing.iter().all(|slot| self.is_closed(*slot, spacing)) {
+                    GapKind::Holiday
+                } else {
+                    GapKind::Missing
+                };
+                Some(DataGap { after: w[0].timestamp, before: w[1].timestamp, missing_bars: missing.len(), kind })
+            })
+            .collect()
+    }
+
+    /// Bar times expected strictly between `from` and `to`, when the step is a gap
+    fn missing_slots(&self, from: DateTime<Utc>, to: DateTime<Utc>, spacing: Duration) -> Vec<DateTime<Utc>> {
+        let step = to - from;
+        if step.num_seconds() as f64 <= spacing.num_seconds() as f64 * self.config.gap_tolerance {
+            return Vec::new();
+        }
+        let count = ((step.num_seconds() - 1) / spacing.num_seconds()) as i32;
+        (1..=count).map(|k| from + spacing * k).collect()
+    }
+
+    fn is_closed(&self, slot: DateTime<Utc>, spacing: Duration) -> bool {
+        is_weekend(slot, spacing) || !self.calendar.holidays_on(slot.date_naive()).is_empty()
+    }
+
+    fn spikes(&self, data: &[ForexDataPoint]) -> Vec<PriceSpike> {
+        let valid: Vec<&ForexDataPoint> = data.iter().filter(|point| is_valid(point)).collect();
+        let returns: Vec<f64> = valid.windows(2).map(|w| (w[1].close / w[0].close).ln()).collect();
+        let Some(sigma) = robust_sigma(&returns).filter(|sigma| *sigma > 0.0) else {
+            return Vec::new();
+        };
+        let threshold = self.config.spike_sigma * sigma;
+        let mut spikes = Vec::new();
+        let mut i = 0;
+        while i < returns.len() {
+            let r = returns[i];
+            if r.abs() > threshold {
+                // A bad print jumps out and straight back: one spike, not two
+                let reverted = returns.get(i + 1).is_some_and(|next| next.abs() > threshold && next.signum() != r.signum());
+                spikes.push(PriceSpike { timestamp: valid[i + 1].timestamp, log_return: r, sigmas: r.abs() / sigma, reverted });
+                if reverted {
+                    i += 1;
+                }
+            }
+            i += 1;
+        }
+        spikes
+    }
+}
+
+/// Sorted bars with each timestamp once, the last row loaded winning
+fn dedup_last(sorted: Vec<ForexDataPoint>) -> Vec<ForexDataPoint> {
+    let mut unique: Vec<ForexDataPoint> = Vec::with_capacity(sorted.len());
+    for point in sorted {
+        match unique.last_mut() {
+            Some(last) if last.timestamp == point.timestamp => *last = point,
+            _ => unique.push(point),
+        }
+    }
+    unique
+}
+
+fn median_spacing(data: &[ForexDataPoint]) -> Option<Duration> {
+    let mut steps: Vec<Duration> = data.windows(2)
+        .map(|w| w[1].timestamp - w[0].timestamp)
+        .filter(|step| *step > Duration::zero())
+        .collect();
+    if steps.is_empty() {
+        return None;
+    }
+    steps.sort();
+    Some(steps[steps.len() / 2])
+}
+
+fn is_valid(point: &ForexDataPoint) -> bool {
+    [point.open, point.high, point.low, point.close].iter().all(|price| price.is_finite() && *price > 0.0)
+        && point.high >= point.low
+}
+
+/// Whether the market is shut at `slot`: the weekend for daily bars, Friday
+/// 21:00 to Sunday 22:00 UTC for intraday ones
+fn is_weekend(slot: DateTime<Utc>, spacing: Duration) -> bool {
+    match slot.weekday() {
+        Weekday::Sat => true,
+        Weekday::Sun => spacing >= Duration::days(1) || slot.hour() < 22,
+        Weekday::Fri => spacing < Duration::days(1) && slot.hour() >= 21,
+        _ => false,
+    }
+}
+
+fn robust_sigma(values: &[f64]) -> Option<f64> {
+    if values.len() < 3 {
+        return None;
+    }
+    let median = |mut v: Vec<f64>| {
+        v.sort_by(|a, b| a.total_cmp(b));
+        v[v.len() / 2]
+    };
+    let center = median(values.to_vec());
+    Some(MAD_TO_SIGMA * median(values.iter().map(|v| (v - center).abs()).collect()))
+}
+
+fn interpolate(from: (DateTime<Utc>, f64), to: (DateTime<Utc>, f64), at: DateTime<Utc>) -> f64 {
+    let span = (to.0 - from.0).num_seconds() as f64;
+    if span <= 0.0 {
+        return from.1;
+    }
+    from.1 + (to.1 - from.1) * (at - from.0).num_seconds() as f64 / span
+}
+
+fn flat_bar(timestamp: DateTime<Utc>, price: f64) -> ForexDataPoint {
+    ForexDataPoint { timestamp, open: price, high: price, low: price, close: price, volume: Some(0.0) }
+}
@@ -0,0 +1,238 @@
+//! # Tick Data
+//!
+//! Bid/ask quotes from tick CSV files, folded into bars that keep the spread so
+//! backtest fills pay the spread the market actually quoted.
+
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Duration, FixedOffset, NaiveDateTime, TimeZone, Utc};
+use csv::{ReaderBuilder, StringRecord};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use super::provider::Tick;
+use super::timeframe::TimeframeAggregator;
+use super::timezone::{LocalTimeConverter, SourceZone};
+use super::{ForexDataPoint, MarketPoint};
+
+/// HistData tick times are EST all year round
+const HISTDATA_UTC_OFFSET_HOURS: i32 = -5;
+
+/// One bid/ask quote
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TickDataPoint {
+    pub timestamp: DateTime<Utc>,
+    pub bid: f64,
+    pub ask: f64,
+}
+
+impl TickDataPoint {
+    pub fn mid(&self) -> f64 {
+        (self.bid + self.ask) / 2.0
+    }
+
+    pub fn spread(&self) -> f64 {
+        self.ask - self.bid
+    }
+}
+
+impl From<&Tick> for TickDataPoint {
+    fn from(tick: &Tick) -> Self {
+        Self { timestamp: tick.timestamp, bid: tick.bid, ask: tick.ask }
+    }
+}
+
+/// Layout of a tick CSV file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TickCsvFormat {
+    /// Header row naming the time, bid and ask columns (generic, Dukascopy)
+    Headered,
+    HistData,
+    TrueFx,
+}
+
+impl TickCsvFormat {
+    /// Guess the layout from the first record of a file
+    pub fn detect(first: &StringRecord) -> Self {
+        let field = |i: usize| first.get(i).unwrap_or("").trim();
+        if first.len() >= 4 && field(0).contains('/') && parse_truefx_time(field(1)).is_ok() {
+            TickCsvFormat::TrueFx
+        } else if first.len() >= 3 && parse_histdata_time(field(0)).is_ok() {
+            TickCsvFormat::HistData
+        } else {
+            TickCsvFormat::Headered
+        }
+    }
+
+    /// Zone of the layout's naive times when the source has none configured
+    pub fn default_zone(self) -> SourceZone {
+        match self {
+            TickCsvFormat::HistData => SourceZone::Fixed(FixedOffset::east_opt(HISTDATA_UTC_OFFSET_HOURS * 3600).expect("offset in range")),
+            TickCsvFormat::Headered | TickCsvFormat::TrueFx => SourceZone::Utc,
+        }
+    }
+}
+
+/// Mid-price bar built from ticks, with the spreads quoted during it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TickBar {
+    /// Mid-price OHLC, with the tick count as volume
+    pub bar: ForexDataPoint,
+    /// Spread of the last tick, where bar-close fills happen
+    pub close_spread: f64,
+    pub mean_spread: f64,
+    pub max_spread: f64,
+}
+
+impl TickBar {
+    /// Bar starting at `start` whose first tick is `tick`
+    pub fn open(start: DateTime<Utc>, tick: &TickDataPoint) -> Self {
+        let (mid, spread) = (tick.mid(), tick.spread());
+        Self {
+            bar: ForexDataPoint { timestamp: start, open: mid, high: mid, low: mid, close: mid, volume: Some(1.0) },
+            close_spread: spread,
+            mean_spread: spread,
+            max_spread: spread,
+        }
+    }
+
+    /// Fold a later tick of the same bar in
+    pub fn add(&mut self, tick: &TickDataPoint) {
+        let (mid, spread) = (tick.mid(), tick.spread());
+        let bar = &mut self.bar;
+        let count = bar.volume.unwrap_or(0.0);
+        bar.high = bar.high.max(mid);
+        bar.low = bar.low.min(mid);
+        bar.close = mid;
+        bar.volume = Some(count + 1.0);
+        self.mean_spread += (spread - self.mean_spread) / (count + 1.0);
+        self.max_spread = self.max_spread.max(spread);
+        self.close_spread = spread;
+    }
+}
+
+impl MarketPoint for TickBar {
+    fn data_point(&self) -> &ForexDataPoint {
+        &self.bar
+    }
+}
+
+/// Read ticks from a CSV file, detecting the layout unless `format` is given:
+/// headered files with a time column (`timestamp`, `time`, `datetime`, `date` or
+/// `Gmt time`) and `bid`/`ask`, HistData files (`20240102 170000123,bid,ask,volume`,
+/// EST without daylight saving) or TrueFX files (`EUR/USD,20240102 17:00:00.123,bid,ask`, UTC).
+/// Naive times are read in `zone` when given, otherwise in the layout's own zone.
+/// Rows with a non-positive or crossed quote are skipped; ticks are returned sorted.
+pub fn load_tick_csv(path: &Path, format: Option<TickCsvFormat>, zone: Option<SourceZone>) -> Result<Vec<TickDataPoint>> {
+    let mut reader = ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_path(path)
+        .with_context(|| format!("cannot open tick file {}", path.display()))?;
+    let mut records = reader.records();
+    let Some(first) = records.next().transpose()? else {
+        return Ok(Vec::new());
+    };
+    let format = format.unwrap_or_else(|| TickCsvFormat::detect(&first));
+    let mut converter = LocalTimeConverter::new(zone.unwrap_or(format.default_zone()));
+
+    let mut ticks = Vec::new();
+    let mut parse = |record: &StringRecord, columns: (usize, usize, usize), line: usize| -> Result<()> {
+        let field = |i: usize| record.get(i).map(str::trim).ok_or_else(|| anyhow!("{}:{}: missing column {}", path.display(), line, i + 1));
+        let timestamp = match format {
+            TickCsvFormat::Headered => parse_time(field(columns.0)?, &mut converter),
+            TickCsvFormat::HistData => parse_histdata_time(field(columns.0)?).map(|time| converter.to_utc(time)),
+            TickCsvFormat::TrueFx => parse_truefx_time(field(columns.0)?).map(|time| converter.to_utc(time)),
+        }.with_context(|| format!("{}:{}", path.display(), line))?;
+        let bid: f64 = field(columns.1)?.parse().with_context(|| format!("{}:{}: bad bid", path.display(), line))?;
+        let ask: f64 = field(columns.2)?.parse().with_context(|| format!("{}:{}: bad ask", path.display(), line))?;
+        if bid > 0.0 && ask >= bid {
+            ticks.push(TickDataPoint { timestamp, bid, ask });
+        }
+        Ok(())
+    };
+
+    let columns = match format {
+        TickCsvFormat::Headered => header_columns(&first).with_context(|| format!("{}: unrecognized tick header", path.display()))?,
+        TickCsvFormat::HistData => {
+            parse(&first, (0, 1, 2), 1)?;
+            (0, 1, 2)
+        }
+        TickCsvFormat::TrueFx => {
+            parse(&first, (1, 2, 3), 1)?;
+            (1, 2, 3)
+        }
+    };
+    for (index, record) in records.enumerate() {
+        let record = record?;
+        if record.iter().all(|field| field.trim().is_empty()) {
+            continue;
+        }
+        parse(&record, columns, index + 2)?;
+    }
+
+    ticks.sort_by_key(|tick| tick.timestamp);
+    Ok(ticks)
+}
+
+/// Fold sorted ticks into mid-price bars of `aggregator`'s timeframe
+pub fn resample_ticks(ticks: &[TickDataPoint], aggregator: &TimeframeAggregator) -> Result<Vec<TickBar>> {
+    let mut bars: Vec<TickBar> = Vec::new();
+    for tick in ticks {
+        let start = aggregator.bucket_start(tick.timestamp);
+        match bars.last_mut() {
+            Some(current) if current.bar.timestamp == start => current.add(tick),
+            Some(current) if start < current.bar.timestamp => {
+                bail!("ticks are not sorted: {} follows {}", tick.timestamp, current.bar.timestamp);
+            }
+            _ => bars.push(TickBar::open(start, tick)),
+        }
+    }
+    Ok(bars)
+}
+
+/// Positions of the time, bid and ask columns in a header row
+fn header_columns(header: &StringRecord) -> Result<(usize, usize, usize)> {
+    let find = |names: &[&str]| header.iter().position(|field| names.iter().any(|name| field.trim().eq_ignore_ascii_case(name)));
+    let time = find(&["timestamp", "time", "datetime", "date", "gmt time"]).ok_or_else(|| anyhow!("no time column"))?;
+    let bid = find(&["bid"]).ok_or_else(|| anyhow!("no bid column"))?;
+    let ask = find(&["ask"]).ok_or_else(|| anyhow!("no ask column"))?;
+    Ok((time, bid, ask))
+}
+
+/// RFC 3339 times or epoch seconds or milliseconds, or ISO-like or Dukascopy
+/// (`02.01.2024 17:00:00.123`) naive times read by `converter`
+fn parse_time(value: &str, converter: &mut LocalTimeConverter) -> Result<DateTime<Utc>> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    for format in ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f", "%d.%m.%Y %H:%M:%S%.f", "%Y.%m.%d %H:%M:%S%.f"] {
+        if let Ok(time) = NaiveDateTime::parse_from_str(value, format) {
+            return Ok(converter.to_utc(time));
+        }
+    }
+    if let Ok(epoch) = value.parse::<i64>() {
+        let time = if epoch.abs() >= 100_000_000_000 { Utc.timestamp_millis_opt(epoch) } else { Utc.timestamp_opt(epoch, 0) };
+        return time.single().ok_or_else(|| anyhow!("epoch {} out of range", epoch));
+    }
+    bail!("unrecognized tick time '{}'", value)
+}
+
+/// `20240102 170000123`
+fn parse_histdata_time(value: &str) -> Result<NaiveDateTime> {
+    if !value.is_ascii() {
+        bail!("unrecognized HistData time '{}'", value);
+    }
+    let (date_time, millis) = value.split_at(value.len().saturating_sub(3));
+    if millis.len() != 3 || !millis.bytes().all(|b| b.is_ascii_digit()) {
+        bail!("unrecognized HistData time '{}'", value);
+    }
+    let time = NaiveDateTime::parse_from_str(date_time, "%Y%m%d %H%M%S")
+        .map_err(|_| anyhow!("unrecognized HistData time '{}'", value))?;
+    Ok(time + Duration::milliseconds(millis.parse()?))
+}
+
+/// `20240102 17:00:00.123`
+fn parse_truefx_time(value: &str) -> Result<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(value, "%Y%m%d %H:%M:%S%.f")
+        .map_err(|_| anyhow!("unrecognized TrueFX time '{}'", value))
+}
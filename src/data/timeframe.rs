@@ -0,0 +1,83 @@
+//! # Timeframe Resampling
+//!
+//! Fold a bar series into a coarser timeframe (M15 into H1, H1 into H4 or D1, ...).
+//! Buckets are aligned to the Unix epoch, except weekly ones which start on Monday
+//! 00:00 UTC. Empty buckets, such as weekends, produce no bar.
+
+use anyhow::{bail, Result};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+
+use super::provider::timeframe_duration;
+use super::ForexDataPoint;
+
+/// Resamples bar series to a fixed, coarser interval
+#[derive(Debug, Clone)]
+pub struct TimeframeAggregator {
+    timeframe: String,
+    interval: Duration,
+}
+
+impl TimeframeAggregator {
+    /// Aggregator for a timeframe name such as "M15", "H4" or "1D"
+    pub fn new(timeframe: &str) -> Result<Self> {
+        Ok(Self { timeframe: timeframe.to_uppercase(), interval: timeframe_duration(timeframe)? })
+    }
+
+    /// Aggregator for an arbitrary interval, e.g. 2 hours
+    pub fn with_interval(interval: Duration) -> Result<Self> {
+        if interval <= Duration::zero() {
+            bail!("resampling interval must be positive, got {}", interval);
+        }
+        Ok(Self { timeframe: format!("{}s", interval.num_seconds()), interval })
+    }
+
+    pub fn timeframe(&self) -> &str {
+        &self.timeframe
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Start of the bucket containing `timestamp`
+    pub fn bucket_start(&self, timestamp: DateTime<Utc>) -> DateTime<Utc> {
+        // 1970-01-05 was a Monday; every sub-daily interval dividing a day also
+        // divides the four days to it, so intraday buckets stay epoch aligned
+        let anchor = Utc.timestamp_opt(4 * 86_400, 0).unwrap();
+        let step = self.interval.num_seconds();
+        let offset = (timestamp - anchor).num_seconds().div_euclid(step) * step;
+        anchor + Duration::seconds(offset)
+    }
+
+    /// Fold `data`, sorted by time, into bars of this timeframe: first open, highest
+    /// high, lowest low, last close and summed volume. Source bars must not be
+    /// coarser than the target timeframe.
+    pub fn resample(&self, data: &[ForexDataPoint]) -> Result<Vec<ForexDataPoint>> {
+        if let Some(spacing) = data.windows(2).map(|w| w[1].timestamp - w[0].timestamp).filter(|gap| *gap > Duration::zero()).min() {
+            if spacing > self.interval {
+                bail!("cannot resample bars {} apart into {} bars", spacing, self.timeframe);
+            }
+        }
+
+        let mut bars: Vec<ForexDataPoint> = Vec::new();
+        for point in data {
+            let start = self.bucket_start(point.timestamp);
+            match bars.last_mut() {
+                Some(bar) if bar.timestamp == start => {
+                    bar.high = bar.high.max(point.high);
+                    bar.low = bar.low.min(point.low);
+                    bar.close = point.close;
+                    bar.volume = match (bar.volume, point.volume) {
+                        (None, None) => None,
+                        (a, b) => Some(a.unwrap_or(0.0) + b.unwrap_or(0.0)),
+                    };
+                }
+                Some(bar) if start < bar.timestamp => {
+                    bail!("data is not sorted: {} follows {}", point.timestamp, bar.timestamp);
+                }
+                _ => bars.push(ForexDataPoint { timestamp: start, ..point.clone() }),
+            }
+        }
+        Ok(bars)
+    }
+}
@@ -0,0 +1,172 @@
+//! # Source Time Zones
+//!
+//! Converts naive timestamps in data files from the zone each source was
+//! recorded in (UTC, broker-local EET, New York) to UTC under that zone's
+//! daylight-saving rules, so a cycle's phase does not shift twice a year.
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Duration, FixedOffset, LocalResult, NaiveDateTime, Offset, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Hours New York-close server time runs ahead of New York
+const NEW_YORK_CLOSE_SHIFT_HOURS: i64 = 7;
+
+/// Far enough before a skipped local time to be on the old side of the change
+const BEFORE_TRANSITION_HOURS: i64 = 12;
+
+/// Zone of a source's naive timestamps
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SourceZone {
+    Utc,
+    /// Constant offset from UTC, no daylight saving
+    Fixed(FixedOffset),
+    /// IANA zone with its daylight-saving rules, e.g. "Europe/Athens" or "EET"
+    Named(Tz),
+    /// New York time plus seven hours, the MetaTrader server convention whose
+    /// days start at the 17:00 New York close and which follows US daylight saving
+    NewYorkClose,
+}
+
+impl FromStr for SourceZone {
+    type Err = anyhow::Error;
+
+    /// "UTC", a fixed offset ("+02:00", "UTC-5"), "ny-close" or an IANA name
+    fn from_str(s: &str) -> Result<Self> {
+        let name = s.trim();
+        if name.is_empty() || name.eq_ignore_ascii_case("utc") || name.eq_ignore_ascii_case("gmt") {
+            return Ok(SourceZone::Utc);
+        }
+        if name.eq_ignore_ascii_case("ny-close") || name.eq_ignore_ascii_case("new-york-close") {
+            return Ok(SourceZone::NewYorkClose);
+        }
+        let offset = name.strip_prefix("UTC").or_else(|| name.strip_prefix("GMT")).unwrap_or(name);
+        if offset.starts_with(['+', '-']) {
+            return parse_offset(offset).map(SourceZone::Fixed).with_context(|| format!("invalid UTC offset '{}'", name));
+        }
+        name.parse::<Tz>().map(SourceZone::Named).map_err(|e| anyhow!("unknown time zone '{}': {}", name, e))
+    }
+}
+
+impl fmt::Display for SourceZone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SourceZone::Utc => f.pad("UTC"),
+            SourceZone::Fixed(offset) => f.pad(&format!("UTC{}", offset)),
+            SourceZone::Named(tz) => f.pad(tz.name()),
+            SourceZone::NewYorkClose => f.pad("ny-close"),
+        }
+    }
+}
+
+/// `+02:00`, `+0200`, `+2` or `-5`
+fn parse_offset(value: &str) -> Result<FixedOffset> {
+    let (sign, digits) = value.split_at(1);
+    let sign = if sign == "-" { -1 } else { 1 };
+    let (hours, minutes) = match digits.split_once(':') {
+        Some((hours, minutes)) => (hours.parse::<i32>()?, minutes.parse::<i32>()?),
+        None if digits.len() == 4 => (digits[..2].parse::<i32>()?, digits[2..].parse::<i32>()?),
+        None => (digits.parse::<i32>()?, 0),
+    };
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60)).ok_or_else(|| anyhow!("offset out of range"))
+}
+
+/// Time zone of the sources whose path contains `source`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceTimezone {
+    /// File name, directory or any part of the path, matched case-insensitively
+    pub source: String,
+    /// See [`SourceZone`] for the accepted names
+    pub timezone: String,
+}
+
+/// Time zones of data sources
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TimezoneConfig {
+    /// Zone of sources no entry matches
+    pub default: String,
+    /// Checked in order; the first match wins
+    pub sources: Vec<SourceTimezone>,
+}
+
+impl Default for TimezoneConfig {
+    fn default() -> Self {
+        Self { default: "UTC".to_string(), sources: Vec::new() }
+    }
+}
+
+impl TimezoneConfig {
+    pub fn with_source(mut self, source: &str, timezone: &str) -> Self {
+        self.sources.push(SourceTimezone { source: source.to_string(), timezone: timezone.to_string() });
+        self
+    }
+
+    /// Zone of the file at `path`
+    pub fn zone_for(&self, path: &Path) -> Result<SourceZone> {
+        let path_text = path.to_string_lossy().to_lowercase();
+        let timezone = self.sources.iter()
+            .find(|entry| !entry.source.is_empty() && path_text.contains(&entry.source.to_lowercase()))
+            .map(|entry| entry.timezone.as_str())
+            .unwrap_or(&self.default);
+        timezone.parse().with_context(|| format!("time zone of {}", path.display()))
+    }
+
+    /// Zone of the file at `path` when an entry names it or the default is not UTC;
+    /// `None` leaves formats with a zone of their own (HistData) in it
+    pub fn configured_zone_for(&self, path: &Path) -> Result<Option<SourceZone>> {
+        let path_text = path.to_string_lossy().to_lowercase();
+        let named = self.sources.iter().any(|entry| !entry.source.is_empty() && path_text.contains(&entry.source.to_lowercase()));
+        let zone = self.zone_for(path)?;
+        Ok((named || zone != SourceZone::Utc).then_some(zone))
+    }
+}
+
+/// Converts one source's naive timestamps to UTC, in file order
+#[derive(Debug, Clone)]
+pub struct LocalTimeConverter {
+    zone: SourceZone,
+    last: Option<DateTime<Utc>>,
+}
+
+impl LocalTimeConverter {
+    pub fn new(zone: SourceZone) -> Self {
+        Self { zone, last: None }
+    }
+
+    pub fn zone(&self) -> SourceZone {
+        self.zone
+    }
+
+    /// UTC instant of the local time `naive`. A time repeated by a DST change is
+    /// the earlier instant unless the previous row was already at or past it; a
+    /// skipped time takes the offset before the change.
+    pub fn to_utc(&mut self, naive: NaiveDateTime) -> DateTime<Utc> {
+        let utc = match self.zone {
+            SourceZone::Utc => naive.and_utc(),
+            SourceZone::Fixed(offset) => (naive - Duration::seconds(offset.local_minus_utc() as i64)).and_utc(),
+            SourceZone::Named(tz) => self.resolve(tz, naive),
+            SourceZone::NewYorkClose => self.resolve(chrono_tz::America::New_York, naive - Duration::hours(NEW_YORK_CLOSE_SHIFT_HOURS)),
+        };
+        self.last = Some(utc);
+        utc
+    }
+
+    fn resolve(&self, tz: Tz, naive: NaiveDateTime) -> DateTime<Utc> {
+        match tz.from_local_datetime(&naive) {
+            LocalResult::Single(time) => time.with_timezone(&Utc),
+            LocalResult::Ambiguous(earlier, later) => {
+                let (earlier, later) = (earlier.with_timezone(&Utc), later.with_timezone(&Utc));
+                // The second pass through a repeated hour follows rows already at or past its first
+                if self.last.is_some_and(|last| last >= earlier) { later } else { earlier }
+            }
+            LocalResult::None => {
+                let before = tz.offset_from_utc_datetime(&(naive - Duration::hours(BEFORE_TRANSITION_HOURS))).fix();
+                (naive - Duration::seconds(before.local_minus_utc() as i64)).and_utc()
+            }
+        }
+    }
+}
@@ -0,0 +1,200 @@
+//! # Analysis Cache
+//!
+//! Symmetries and cycles checkpointed stage by stage under (pair, timeframe,
+//! data checksum, config hash), so an interrupted run keeps finished stages and
+//! changed bars or configuration miss the cache.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::params;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt;
+
+use super::{config_hash, data_checksum, EmbeddedForexDB};
+use crate::data::ForexDataPoint;
+use crate::patterns::HiddenCycle;
+use crate::symmetry::TemporalSymmetry;
+
+/// A step of an analysis whose result is checkpointed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnalysisStage {
+    Symmetries,
+    Cycles,
+}
+
+impl AnalysisStage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AnalysisStage::Symmetries => "symmetries",
+            AnalysisStage::Cycles => "cycles",
+        }
+    }
+}
+
+impl fmt::Display for AnalysisStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// What an analysis result depends on
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AnalysisCacheKey {
+    pub pair: String,
+    pub timeframe: String,
+    /// [`data_checksum`] of the bars analysed
+    pub data_checksum: String,
+    /// [`config_hash`] of the configuration they were analysed with
+    pub config_hash: String,
+}
+
+impl AnalysisCacheKey {
+    pub fn new(pair: &str, timeframe: &str, data: &[ForexDataPoint], config_hash: &str) -> Self {
+        Self {
+            pair: pair.to_string(),
+            timeframe: timeframe.to_string(),
+            data_checksum: data_checksum(data),
+            config_hash: config_hash.to_string(),
+        }
+    }
+}
+
+/// Cached analyze results for one key
+#[derive(Debug, Clone)]
+pub struct CachedAnalysis {
+    pub symmetries: Vec<TemporalSymmetry>,
+    pub cycles: Vec<HiddenCycle>,
+    /// When the older of the two stages was stored
+    pub created_at: DateTime<Utc>,
+}
+
+/// The cache of one pair, timeframe and configuration, looked up by the bars analysed
+#[derive(Clone)]
+pub struct AnalysisCache {
+    db: EmbeddedForexDB,
+    pair: String,
+    timeframe: String,
+    config_hash: String,
+    refresh: bool,
+}
+
+impl AnalysisCache {
+    pub fn new<T: Serialize>(db: EmbeddedForexDB, pair: &str, timeframe: &str, config: &T) -> Result<Self> {
+        Ok(Self {
+            db,
+            pair: pair.to_string(),
+            timeframe: timeframe.to_string(),
+            config_hash: config_hash(config)?,
+            refresh: false,
+        })
+    }
+
+    /// Ignore cached results and overwrite them with the recomputed ones
+    pub fn with_refresh(mut self, refresh: bool) -> Self {
+        self.refresh = refresh;
+        self
+    }
+
+    /// Database the cache lives in
+    pub fn db(&self) -> &EmbeddedForexDB {
+        &self.db
+    }
+
+    pub fn key(&self, data: &[ForexDataPoint]) -> AnalysisCacheKey {
+        AnalysisCacheKey::new(&self.pair, &self.timeframe, data, &self.config_hash)
+    }
+
+    /// Symmetries cached for `data`, unless refreshing
+    pub fn symmetries(&self, data: &[ForexDataPoint]) -> Result<Option<Vec<TemporalSymmetry>>> {
+        self.get(data, AnalysisStage::Symmetries)
+    }
+
+    /// Cycles cached for `data`, unless refreshing
+    pub fn cycles(&self, data: &[ForexDataPoint]) -> Result<Option<Vec<HiddenCycle>>> {
+        self.get(data, AnalysisStage::Cycles)
+    }
+
+    pub fn store_symmetries(&self, data: &[ForexDataPoint], symmetries: &[TemporalSymmetry]) -> Result<()> {
+        self.db.store_checkpoint(&self.key(data), AnalysisStage::Symmetries, symmetries)
+    }
+
+    pub fn store_cycles(&self, data: &[ForexDataPoint], cycles: &[HiddenCycle]) -> Result<()> {
+        self.db.store_checkpoint(&self.key(data), AnalysisStage::Cycles, cycles)
+    }
+
+    fn get<T: DeserializeOwned>(&self, data: &[ForexDataPoint], stage: AnalysisStage) -> Result<Option<T>> {
+        if self.refresh {
+            return Ok(None);
+        }
+        Ok(self.db.get_checkpoint(&self.key(data), stage)?.map(|(value, _)| value))
+    }
+}
+
+impl EmbeddedForexDB {
+    /// Result of `stage` stored under `key`, and when it was stored
+    pub fn get_checkpoint<T: DeserializeOwned>(&self, key: &AnalysisCacheKey, stage: AnalysisStage) -> Result<Option<(T, DateTime<Utc>)>> {
+        let conn = self.pool.get();
+        let mut stmt = conn.prepare(
+            "SELECT result, created_at FROM analysis_checkpoints
+             WHERE pair = ?1 AND timeframe = ?2 AND data_checksum = ?3 AND config_hash = ?4 AND stage = ?5"
+        )?;
+        let mut rows = stmt.query(params![key.pair, key.timeframe, key.data_checksum, key.config_hash, stage.as_str()])?;
+        let Some(row) = rows.next()? else {
+            return Ok(None);
+        };
+
+        let result: String = row.get(0)?;
+        let created_at: i64 = row.get(1)?;
+        Ok(Some((serde_json::from_str(&result)?, DateTime::from_timestamp(created_at, 0).unwrap_or_else(Utc::now))))
+    }
+
+    /// Store the result of `stage`, replacing any previous one for the same key
+    pub fn store_checkpoint<T: Serialize + ?Sized>(&self, key: &AnalysisCacheKey, stage: AnalysisStage, result: &T) -> Result<()> {
+        self.pool.get().execute(
+            "INSERT OR REPLACE INTO analysis_checkpoints (pair, timeframe, data_checksum, config_hash, stage, result, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                key.pair,
+                key.timeframe,
+                key.data_checksum,
+                key.config_hash,
+                stage.as_str(),
+                serde_json::to_string(result)?,
+                Utc::now().timestamp()
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Look up cached analyze results, present only when both stages are
+    pub fn get_cached_analysis(&self, key: &AnalysisCacheKey) -> Result<Option<CachedAnalysis>> {
+        let Some((symmetries, symmetries_at)) = self.get_checkpoint(key, AnalysisStage::Symmetries)? else {
+            return Ok(None);
+        };
+        let Some((cycles, cycles_at)) = self.get_checkpoint(key, AnalysisStage::Cycles)? else {
+            return Ok(None);
+        };
+        Ok(Some(CachedAnalysis { symmetries, cycles, created_at: symmetries_at.min(cycles_at) }))
+    }
+
+    /// Store both stages of analyze results
+    pub fn store_cached_analysis(
+        &self,
+        key: &AnalysisCacheKey,
+        symmetries: &[TemporalSymmetry],
+        cycles: &[HiddenCycle],
+    ) -> Result<()> {
+        self.store_checkpoint(key, AnalysisStage::Symmetries, symmetries)?;
+        self.store_checkpoint(key, AnalysisStage::Cycles, cycles)
+    }
+
+    /// Drop the cached results of `pair`, or of every pair, returning the checkpoints removed
+    pub fn clear_analysis_cache(&self, pair: Option<&str>) -> Result<usize> {
+        let conn = self.pool.get();
+        Ok(match pair {
+            Some(pair) => conn.execute("DELETE FROM analysis_checkpoints WHERE pair = ?1", params![pair])?,
+            None => conn.execute("DELETE FROM analysis_checkpoints", [])?,
+        })
+    }
+}
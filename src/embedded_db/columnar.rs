@@ -0,0 +1,134 @@
+//! # Columnar Candle Encoding
+//!
+//! Candles are stored in fixed-size chunks with one array per field. Each array is
+//! delta encoded, zigzag mapped and written as LEB128 varints, so consecutive
+//! timestamps and prices collapse to one or two bytes while every chunk keeps its
+//! time range in plain columns for SQL range predicates.
+
+use anyhow::{bail, Result};
+
+use super::CompressedForexPoint;
+
+/// Candles per stored chunk
+pub const CHUNK_SIZE: usize = 1024;
+
+/// One chunk of candles, one encoded array per field
+#[derive(Debug, Clone)]
+pub struct ColumnChunk {
+    pub start_ts: i64,
+    pub end_ts: i64,
+    pub points: usize,
+    pub timestamps: Vec<u8>,
+    pub open: Vec<u8>,
+    pub high: Vec<u8>,
+    pub low: Vec<u8>,
+    pub close: Vec<u8>,
+    pub volume: Vec<u8>,
+}
+
+impl ColumnChunk {
+    /// Encode a run of candles (at most [`CHUNK_SIZE`] is expected, not enforced)
+    pub fn encode(points: &[CompressedForexPoint]) -> Self {
+        Self {
+            start_ts: points.first().map(|p| p.timestamp).unwrap_or(0),
+            end_ts: points.last().map(|p| p.timestamp).unwrap_or(0),
+            points: points.len(),
+            timestamps: encode_column(points.iter().map(|p| p.timestamp)),
+            open: encode_column(points.iter().map(|p| p.open as i64)),
+            high: encode_column(points.iter().map(|p| p.high as i64)),
+            low: encode_column(points.iter().map(|p| p.low as i64)),
+            close: encode_column(points.iter().map(|p| p.close as i64)),
+            volume: encode_column(points.iter().map(|p| p.volume as i64)),
+        }
+    }
+
+    /// Total encoded size in bytes
+    pub fn encoded_len(&self) -> usize {
+        self.timestamps.len() + self.open.len() + self.high.len()
+            + self.low.len() + self.close.len() + self.volume.len()
+    }
+}
+
+/// Decode the full candles of a chunk
+pub fn decode_chunk(
+    points: usize,
+    timestamps: &[u8],
+    open: &[u8],
+    high: &[u8],
+    low: &[u8],
+    close: &[u8],
+    volume: &[u8],
+) -> Result<Vec<CompressedForexPoint>> {
+    let timestamps = decode_column(timestamps, points)?;
+    let open = decode_column(open, points)?;
+    let high = decode_column(high, points)?;
+    let low = decode_column(low, points)?;
+    let close = decode_column(close, points)?;
+    let volume = decode_column(volume, points)?;
+
+    Ok((0..points)
+        .map(|i| CompressedForexPoint {
+            timestamp: timestamps[i],
+            open: open[i] as u32,
+            high: high[i] as u32,
+            low: low[i] as u32,
+            close: close[i] as u32,
+            volume: volume[i] as u32,
+        })
+        .collect())
+}
+
+/// Delta + zigzag + varint encode a column
+pub fn encode_column(values: impl Iterator<Item = i64>) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut previous = 0i64;
+    for value in values {
+        write_varint(&mut out, zigzag(value.wrapping_sub(previous)));
+        previous = value;
+    }
+    out
+}
+
+/// Decode exactly `count` values from a column
+pub fn decode_column(bytes: &[u8], count: usize) -> Result<Vec<i64>> {
+    let mut values = Vec::with_capacity(count);
+    let mut position = 0;
+    let mut previous = 0i64;
+    for _ in 0..count {
+        let (encoded, read) = read_varint(&bytes[position..])?;
+        position += read;
+        previous = previous.wrapping_add(unzigzag(encoded));
+        values.push(previous);
+    }
+    if position != bytes.len() {
+        bail!("column has {} trailing bytes", bytes.len() - position);
+    }
+    Ok(values)
+}
+
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+fn unzigzag(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn read_varint(bytes: &[u8]) -> Result<(u64, usize)> {
+    let mut value = 0u64;
+    for (i, byte) in bytes.iter().enumerate().take(10) {
+        value |= ((byte & 0x7F) as u64) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok((value, i + 1));
+        }
+    }
+    bail!("truncated or overlong varint")
+}
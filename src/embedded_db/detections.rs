@@ -0,0 +1,186 @@
+//! # Detection History
+//!
+//! Symmetries, cycles and anomalies recorded per pair as they are detected, so a
+//! symmetry's strength can be followed across runs by name and a detector can
+//! take its expectations from the latest run.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::params;
+use serde::Serialize;
+
+use super::EmbeddedForexDB;
+use crate::anomaly::DetectedAnomaly;
+use crate::patterns::HiddenCycle;
+use crate::symmetry::TemporalSymmetry;
+
+/// A named symmetry as seen by one run
+#[derive(Debug, Clone, Serialize)]
+pub struct SymmetryObservation {
+    pub observed_at: DateTime<Utc>,
+    pub strength: f64,
+    pub confidence: f64,
+}
+
+/// A named cycle as seen by one run
+#[derive(Debug, Clone, Serialize)]
+pub struct CycleObservation {
+    pub observed_at: DateTime<Utc>,
+    pub period: u32,
+    pub amplitude: f64,
+    pub confidence: f64,
+    pub phase: f64,
+}
+
+fn from_timestamp(timestamp: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp(timestamp, 0).unwrap_or_else(Utc::now)
+}
+
+impl EmbeddedForexDB {
+    /// Record a run's symmetries, replacing any recorded at the same time
+    pub fn insert_symmetries(&self, pair: &str, timeframe: &str, observed_at: DateTime<Utc>, symmetries: &[TemporalSymmetry]) -> Result<usize> {
+        let conn = self.pool.get();
+        let tx = conn.unchecked_transaction()?;
+        tx.execute(
+            "DELETE FROM temporal_symmetries WHERE pair = ?1 AND timeframe = ?2 AND observed_at = ?3",
+            params![pair, timeframe, observed_at.timestamp()],
+        )?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT OR REPLACE INTO temporal_symmetries
+                 (pair, timeframe, observed_at, name, symmetry_type, period_days, strength, confidence, record)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)"
+            )?;
+            for symmetry in symmetries {
+                stmt.execute(params![
+                    pair, timeframe, observed_at.timestamp(),
+                    symmetry.name, symmetry.symmetry_type, symmetry.period_days, symmetry.strength, symmetry.confidence,
+                    serde_json::to_string(symmetry)?
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(symmetries.len())
+    }
+
+    /// Symmetries of the most recent run recorded for `pair` and `timeframe`
+    pub fn latest_symmetries(&self, pair: &str, timeframe: &str) -> Result<Vec<TemporalSymmetry>> {
+        let conn = self.pool.get();
+        let mut stmt = conn.prepare(
+            "SELECT record FROM temporal_symmetries
+             WHERE pair = ?1 AND timeframe = ?2 AND observed_at =
+                (SELECT MAX(observed_at) FROM temporal_symmetries WHERE pair = ?1 AND timeframe = ?2)
+             ORDER BY strength DESC"
+        )?;
+        let rows = stmt.query_map(params![pair, timeframe], |row| row.get::<_, String>(0))?;
+        rows.map(|record| Ok(serde_json::from_str(&record?)?)).collect()
+    }
+
+    /// Strength of the symmetry called `name` in every recorded run, oldest first
+    pub fn symmetry_history(&self, pair: &str, timeframe: &str, name: &str) -> Result<Vec<SymmetryObservation>> {
+        let conn = self.pool.get();
+        let mut stmt = conn.prepare(
+            "SELECT observed_at, strength, confidence FROM temporal_symmetries
+             WHERE pair = ?1 AND timeframe = ?2 AND name = ?3
+             ORDER BY observed_at"
+        )?;
+        let rows = stmt.query_map(params![pair, timeframe, name], |row| {
+            Ok(SymmetryObservation { observed_at: from_timestamp(row.get(0)?), strength: row.get(1)?, confidence: row.get(2)? })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Record a run's cycles, replacing any recorded at the same time
+    pub fn insert_cycles(&self, pair: &str, timeframe: &str, observed_at: DateTime<Utc>, cycles: &[HiddenCycle]) -> Result<usize> {
+        let conn = self.pool.get();
+        let tx = conn.unchecked_transaction()?;
+        tx.execute(
+            "DELETE FROM hidden_cycles WHERE pair = ?1 AND timeframe = ?2 AND observed_at = ?3",
+            params![pair, timeframe, observed_at.timestamp()],
+        )?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT OR REPLACE INTO hidden_cycles
+                 (pair, timeframe, observed_at, name, period, amplitude, confidence, phase, record)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)"
+            )?;
+            for cycle in cycles {
+                stmt.execute(params![
+                    pair, timeframe, observed_at.timestamp(),
+                    cycle.name, cycle.period, cycle.amplitude, cycle.confidence, cycle.phase,
+                    serde_json::to_string(cycle)?
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(cycles.len())
+    }
+
+    /// Cycles of the most recent run recorded for `pair` and `timeframe`
+    pub fn latest_cycles(&self, pair: &str, timeframe: &str) -> Result<Vec<HiddenCycle>> {
+        let conn = self.pool.get();
+        let mut stmt = conn.prepare(
+            "SELECT record FROM hidden_cycles
+             WHERE pair = ?1 AND timeframe = ?2 AND observed_at =
+                (SELECT MAX(observed_at) FROM hidden_cycles WHERE pair = ?1 AND timeframe = ?2)
+             ORDER BY confidence DESC"
+        )?;
+        let rows = stmt.query_map(params![pair, timeframe], |row| row.get::<_, String>(0))?;
+        rows.map(|record| Ok(serde_json::from_str(&record?)?)).collect()
+    }
+
+    /// The cycle called `name` in every recorded run, oldest first
+    pub fn cycle_history(&self, pair: &str, timeframe: &str, name: &str) -> Result<Vec<CycleObservation>> {
+        let conn = self.pool.get();
+        let mut stmt = conn.prepare(
+            "SELECT observed_at, period, amplitude, confidence, phase FROM hidden_cycles
+             WHERE pair = ?1 AND timeframe = ?2 AND name = ?3
+             ORDER BY observed_at"
+        )?;
+        let rows = stmt.query_map(params![pair, timeframe, name], |row| {
+            Ok(CycleObservation {
+                observed_at: from_timestamp(row.get(0)?),
+                period: row.get(1)?,
+                amplitude: row.get(2)?,
+                confidence: row.get(3)?,
+                phase: row.get(4)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Record detected anomalies; an anomaly already recorded under its id is replaced
+    pub fn insert_anomalies(&self, pair: &str, anomalies: &[DetectedAnomaly]) -> Result<usize> {
+        let conn = self.pool.get();
+        let tx = conn.unchecked_transaction()?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT OR REPLACE INTO detected_anomalies
+                 (id, pair, timestamp, anomaly_type, severity, confidence, deviation_magnitude, record)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)"
+            )?;
+            for anomaly in anomalies {
+                stmt.execute(params![
+                    anomaly.id.as_str(), pair, anomaly.timestamp.timestamp(),
+                    anomaly.anomaly_type.name(), format!("{:?}", anomaly.severity),
+                    anomaly.confidence, anomaly.deviation_magnitude,
+                    serde_json::to_string(anomaly)?
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(anomalies.len())
+    }
+
+    /// Anomalies of `pair` with `from <= timestamp <= to`, oldest first
+    pub fn get_anomalies(&self, pair: &str, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<DetectedAnomaly>> {
+        let conn = self.pool.get();
+        let mut stmt = conn.prepare(
+            "SELECT record FROM detected_anomalies
+             WHERE pair = ?1 AND timestamp >= ?2 AND timestamp <= ?3
+             ORDER BY timestamp, id"
+        )?;
+        let rows = stmt.query_map(params![pair, from.timestamp(), to.timestamp()], |row| row.get::<_, String>(0))?;
+        rows.map(|record| Ok(serde_json::from_str(&record?)?)).collect()
+    }
+}
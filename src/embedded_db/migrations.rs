@@ -0,0 +1,228 @@
+//! # Schema Migrations
+//!
+//! Ordered schema upgrades tracked with SQLite's `user_version` pragma.
+//! Databases created before versioning report version 0 and are upgraded in place.
+
+use anyhow::{bail, Context, Result};
+use rusqlite::{Connection, Transaction};
+
+/// A single schema upgrade, run inside its own transaction
+type Migration = fn(&Transaction) -> Result<()>;
+
+/// Ordered migrations; entry `i` upgrades the schema to version `i + 1`.
+/// Append new migrations, never edit or reorder released ones.
+const MIGRATIONS: &[Migration] = &[
+    v1_forex_data_and_correlations,
+    v2_analysis_cache,
+    v3_candle_chunks,
+    v4_similarity_index,
+    v5_analysis_checkpoints,
+    v6_forex_months,
+    v7_detection_history,
+];
+
+/// Schema version produced by running every migration
+pub const SCHEMA_VERSION: u32 = MIGRATIONS.len() as u32;
+
+/// Current schema version of a database
+pub fn schema_version(conn: &Connection) -> Result<u32> {
+    Ok(conn.query_row("PRAGMA user_version", [], |row| row.get(0))?)
+}
+
+/// Bring a database up to [`SCHEMA_VERSION`], returning the number of migrations applied
+pub fn migrate(conn: &Connection) -> Result<u32> {
+    let current = schema_version(conn)?;
+    if current > SCHEMA_VERSION {
+        bail!(
+            "database schema version {} is newer than this build supports ({})",
+            current, SCHEMA_VERSION
+        );
+    }
+
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(current as usize) {
+        let version = index as u32 + 1;
+        let tx = conn.unchecked_transaction()?;
+        migration(&tx).with_context(|| format!("schema migration to version {} failed", version))?;
+        tx.pragma_update(None, "user_version", version)?;
+        tx.commit()?;
+    }
+
+    Ok(SCHEMA_VERSION - current)
+}
+
+/// Price series and correlation tables (the original, unversioned schema)
+fn v1_forex_data_and_correlations(tx: &Transaction) -> Result<()> {
+    tx.execute_batch(
+        "CREATE TABLE IF NOT EXISTS forex_data (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            pair TEXT NOT NULL,
+            data BLOB NOT NULL,
+            data_points INTEGER NOT NULL,
+            created_at INTEGER NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_pair ON forex_data(pair);
+
+        CREATE TABLE IF NOT EXISTS correlation_matrix (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            pair1 TEXT NOT NULL,
+            pair2 TEXT NOT NULL,
+            correlation REAL NOT NULL,
+            timeframe TEXT NOT NULL,
+            created_at INTEGER NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_correlation ON correlation_matrix(pair1, pair2);",
+    )?;
+    Ok(())
+}
+
+/// Cached analyze results keyed by data checksum and config hash
+fn v2_analysis_cache(tx: &Transaction) -> Result<()> {
+    tx.execute_batch(
+        "CREATE TABLE IF NOT EXISTS analysis_cache (
+            data_checksum TEXT NOT NULL,
+            config_hash TEXT NOT NULL,
+            symmetries TEXT NOT NULL,
+            cycles TEXT NOT NULL,
+            created_at INTEGER NOT NULL,
+            PRIMARY KEY (data_checksum, config_hash)
+        );",
+    )?;
+    Ok(())
+}
+
+/// Columnar candle chunks with their time range exposed for range queries
+fn v3_candle_chunks(tx: &Transaction) -> Result<()> {
+    tx.execute_batch(
+        "CREATE TABLE IF NOT EXISTS candle_chunks (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            pair TEXT NOT NULL,
+            start_ts INTEGER NOT NULL,
+            end_ts INTEGER NOT NULL,
+            points INTEGER NOT NULL,
+            timestamps BLOB NOT NULL,
+            open BLOB NOT NULL,
+            high BLOB NOT NULL,
+            low BLOB NOT NULL,
+            close BLOB NOT NULL,
+            volume BLOB NOT NULL,
+            created_at INTEGER NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_candle_chunks_range ON candle_chunks(pair, start_ts, end_ts);",
+    )?;
+    Ok(())
+}
+
+/// Pattern similarity windows and their signature buckets
+fn v4_similarity_index(tx: &Transaction) -> Result<()> {
+    tx.execute_batch(
+        "CREATE TABLE IF NOT EXISTS similarity_indexes (
+            pair TEXT PRIMARY KEY,
+            config TEXT NOT NULL,
+            return_scale REAL NOT NULL,
+            created_at INTEGER NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS similarity_windows (
+            pair TEXT NOT NULL,
+            position INTEGER NOT NULL,
+            window_end INTEGER NOT NULL,
+            features BLOB NOT NULL,
+            PRIMARY KEY (pair, position)
+        );
+
+        CREATE TABLE IF NOT EXISTS similarity_buckets (
+            pair TEXT NOT NULL,
+            band INTEGER NOT NULL,
+            bucket INTEGER NOT NULL,
+            position INTEGER NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_similarity_buckets ON similarity_buckets(pair, band, bucket);",
+    )?;
+    Ok(())
+}
+
+/// Analysis results checkpointed stage by stage under pair, timeframe, data checksum
+/// and config hash. `analysis_cache` rows carry no pair or timeframe to key them
+/// by, so they are dropped and recomputed on the next run.
+fn v5_analysis_checkpoints(tx: &Transaction) -> Result<()> {
+    tx.execute_batch(
+        "DROP TABLE IF EXISTS analysis_cache;
+
+        CREATE TABLE IF NOT EXISTS analysis_checkpoints (
+            pair TEXT NOT NULL,
+            timeframe TEXT NOT NULL,
+            data_checksum TEXT NOT NULL,
+            config_hash TEXT NOT NULL,
+            stage TEXT NOT NULL,
+            result TEXT NOT NULL,
+            created_at INTEGER NOT NULL,
+            PRIMARY KEY (pair, timeframe, data_checksum, config_hash, stage)
+        );",
+    )?;
+    Ok(())
+}
+
+/// Price series split into one compressed blob per pair and calendar month, so
+/// appends rewrite only the months they touch
+fn v6_forex_months(tx: &Transaction) -> Result<()> {
+    tx.execute_batch(
+        "CREATE TABLE IF NOT EXISTS forex_months (
+            pair TEXT NOT NULL,
+            month_start INTEGER NOT NULL,
+            start_ts INTEGER NOT NULL,
+            end_ts INTEGER NOT NULL,
+            data BLOB NOT NULL,
+            data_points INTEGER NOT NULL,
+            created_at INTEGER NOT NULL,
+            PRIMARY KEY (pair, month_start)
+        );
+        CREATE INDEX IF NOT EXISTS idx_forex_months_range ON forex_months(pair, start_ts, end_ts);",
+    )?;
+    Ok(())
+}
+
+/// Symmetries, cycles and anomalies recorded run by run, the full record kept as JSON
+fn v7_detection_history(tx: &Transaction) -> Result<()> {
+    tx.execute_batch(
+        "CREATE TABLE IF NOT EXISTS temporal_symmetries (
+            pair TEXT NOT NULL,
+            timeframe TEXT NOT NULL,
+            observed_at INTEGER NOT NULL,
+            name TEXT NOT NULL,
+            symmetry_type TEXT NOT NULL,
+            period_days INTEGER NOT NULL,
+            strength REAL NOT NULL,
+            confidence REAL NOT NULL,
+            record TEXT NOT NULL,
+            PRIMARY KEY (pair, timeframe, observed_at, name)
+        );
+        CREATE INDEX IF NOT EXISTS idx_temporal_symmetries_name ON temporal_symmetries(pair, timeframe, name, observed_at);
+
+        CREATE TABLE IF NOT EXISTS hidden_cycles (
+            pair TEXT NOT NULL,
+            timeframe TEXT NOT NULL,
+            observed_at INTEGER NOT NULL,
+            name TEXT NOT NULL,
+            period INTEGER NOT NULL,
+            amplitude REAL NOT NULL,
+            confidence REAL NOT NULL,
+            phase REAL NOT NULL,
+            record TEXT NOT NULL,
+            PRIMARY KEY (pair, timeframe, observed_at, name)
+        );
+        CREATE INDEX IF NOT EXISTS idx_hidden_cycles_name ON hidden_cycles(pair, timeframe, name, observed_at);
+
+        CREATE TABLE IF NOT EXISTS detected_anomalies (
+            id TEXT PRIMARY KEY,
+            pair TEXT NOT NULL,
+            timestamp INTEGER NOT NULL,
+            anomaly_type TEXT NOT NULL,
+            severity TEXT NOT NULL,
+            confidence REAL NOT NULL,
+            deviation_magnitude REAL NOT NULL,
+            record TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_detected_anomalies_time ON detected_anomalies(pair, timestamp);",
+    )?;
+    Ok(())
+}
@@ -1,14 +1,28 @@
-use anyhow::Result;
-use chrono::{DateTime, Utc};
+pub mod analysis_cache;
+pub mod columnar;
+pub mod detections;
+pub mod migrations;
+
+use anyhow::{bail, Result};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
 use flate2::Compression;
 use flate2::read::GzDecoder;
 use flate2::write::GzEncoder;
-use rusqlite::{Connection, params};
+use rusqlite::{Connection, OpenFlags, params};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
 
 use crate::data::ForexDataPoint;
+use crate::galois::similarity::{self, IndexedWindow, SimilarWindow, SimilarityConfig, SimilarityIndex, WindowHasher};
+use columnar::{ColumnChunk, CHUNK_SIZE};
+
+pub use analysis_cache::{AnalysisCache, AnalysisCacheKey, AnalysisStage, CachedAnalysis};
+pub use detections::{CycleObservation, SymmetryObservation};
 
 /// Compressed binary forex data point for efficient storage
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -47,51 +61,364 @@ impl Into<ForexDataPoint> for CompressedForexPoint {
     }
 }
 
-/// Embedded SQLite database for forex data
+/// 64-bit FNV-1a hash, stable across runs and toolchains
+fn fnv1a(bytes: &[u8], mut hash: u64) -> u64 {
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01B3);
+    }
+    hash
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xCBF2_9CE4_8422_2325;
+
+/// Checksum of a price series, used as the data half of the analysis cache key
+pub fn data_checksum(data: &[ForexDataPoint]) -> String {
+    let hash = data.iter().fold(FNV_OFFSET_BASIS, |hash, point| {
+        let hash = fnv1a(&point.timestamp.timestamp().to_le_bytes(), hash);
+        [point.open, point.high, point.low, point.close, point.volume.unwrap_or(0.0)]
+            .iter()
+            .fold(hash, |hash, value| fnv1a(&value.to_bits().to_le_bytes(), hash))
+    });
+    format!("{:016x}-{}", hash, data.len())
+}
+
+/// Hash of any serializable configuration, used as the config half of the cache key
+pub fn config_hash<T: Serialize>(config: &T) -> Result<String> {
+    let serialized = serde_json::to_vec(config)?;
+    Ok(format!("{:016x}", fnv1a(&serialized, FNV_OFFSET_BASIS)))
+}
+
+/// Decompress and deserialize a stored series blob
+fn decode_series(blob: &[u8]) -> Result<Vec<CompressedForexPoint>> {
+    let mut decoder = GzDecoder::new(blob);
+    let mut decompressed = Vec::new();
+    decoder.read_to_end(&mut decompressed)?;
+    Ok(bincode::deserialize(&decompressed)?)
+}
+
+/// Serialize and compress a series blob
+fn encode_series(points: &[CompressedForexPoint]) -> Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(&bincode::serialize(points)?)?;
+    Ok(encoder.finish()?)
+}
+
+/// Unix timestamp of the first second of the UTC calendar month containing `timestamp`
+fn month_start(timestamp: i64) -> i64 {
+    let date = DateTime::from_timestamp(timestamp, 0).unwrap_or_default().date_naive();
+    NaiveDate::from_ymd_opt(date.year(), date.month(), 1)
+        .and_then(|first| first.and_hms_opt(0, 0, 0))
+        .map(|midnight| midnight.and_utc().timestamp())
+        .unwrap_or(timestamp)
+}
+
+/// Decode a `(window_end, features)` row of `similarity_windows`
+fn decode_similarity_window(row: &rusqlite::Row) -> Result<IndexedWindow> {
+    Ok(IndexedWindow {
+        window_end: DateTime::from_timestamp(row.get(0)?, 0).unwrap_or_else(Utc::now),
+        features: bincode::deserialize(&row.get::<_, Vec<u8>>(1)?)?,
+    })
+}
+
+/// Bytes a pair occupies in the blob and columnar formats
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct StorageFootprint {
+    pub blob_bytes: usize,
+    pub columnar_bytes: usize,
+}
+
+/// Result of a database consistency check
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ConsistencyReport {
+    /// Problems reported by SQLite's integrity check
+    pub integrity_errors: Vec<String>,
+    /// Number of stored series that were decoded
+    pub series_checked: usize,
+    /// Series whose blob fails to decode or disagrees with its point count
+    pub corrupt_series: Vec<String>,
+}
+
+impl ConsistencyReport {
+    /// Whether the database passed every check
+    pub fn is_ok(&self) -> bool {
+        self.integrity_errors.is_empty() && self.corrupt_series.is_empty()
+    }
+}
+
+/// Run SQLite's integrity check and decode every stored series
+fn check_connection(conn: &Connection) -> Result<ConsistencyReport> {
+    let mut report = ConsistencyReport::default();
+
+    let mut stmt = conn.prepare("PRAGMA integrity_check")?;
+    let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+    for row in rows {
+        let message = row?;
+        if message != "ok" {
+            report.integrity_errors.push(message);
+        }
+    }
+
+    if !table_exists(conn, "forex_data")? {
+        return Ok(report);
+    }
+
+    let mut stmt = conn.prepare("SELECT id, pair, data, data_points FROM forex_data ORDER BY id")?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, Vec<u8>>(2)?,
+            row.get::<_, i64>(3)?,
+        ))
+    })?;
+    for row in rows {
+        let (id, pair, blob, data_points) = row?;
+        report.series_checked += 1;
+        match decode_series(&blob) {
+            Ok(points) if points.len() as i64 == data_points => {}
+            Ok(points) => report.corrupt_series.push(format!(
+                "{} (row {}): {} points stored, {} recorded", pair, id, points.len(), data_points
+            )),
+            Err(e) => report.corrupt_series.push(format!("{} (row {}): {}", pair, id, e)),
+        }
+    }
+
+    if table_exists(conn, "forex_months")? {
+        let mut stmt = conn.prepare("SELECT pair, month_start, data, data_points FROM forex_months ORDER BY pair, month_start")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, Vec<u8>>(2)?,
+                row.get::<_, i64>(3)?,
+            ))
+        })?;
+        for row in rows {
+            let (pair, month, blob, data_points) = row?;
+            let month = DateTime::from_timestamp(month, 0).unwrap_or_default().format("%Y-%m");
+            report.series_checked += 1;
+            match decode_series(&blob) {
+                Ok(points) if points.len() as i64 == data_points => {}
+                Ok(points) => report.corrupt_series.push(format!(
+                    "{} (month {}): {} points stored, {} recorded", pair, month, points.len(), data_points
+                )),
+                Err(e) => report.corrupt_series.push(format!("{} (month {}): {}", pair, month, e)),
+            }
+        }
+    }
+
+    if !table_exists(conn, "candle_chunks")? {
+        return Ok(report);
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT id, pair, points, timestamps, open, high, low, close, volume FROM candle_chunks ORDER BY id"
+    )?;
+    let mut rows = stmt.query([])?;
+    while let Some(row) = rows.next()? {
+        let id: i64 = row.get(0)?;
+        let pair: String = row.get(1)?;
+        report.series_checked += 1;
+        if let Err(e) = decode_chunk_row(row, 2) {
+            report.corrupt_series.push(format!("{} (chunk {}): {}", pair, id, e));
+        }
+    }
+
+    Ok(report)
+}
+
+fn table_exists(conn: &Connection, name: &str) -> Result<bool> {
+    Ok(conn.query_row(
+        "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type = 'table' AND name = ?1",
+        params![name],
+        |row| row.get(0),
+    )?)
+}
+
+/// Decode a candle chunk from `points, timestamps, open, high, low, close, volume`
+/// columns starting at `first`
+fn decode_chunk_row(row: &rusqlite::Row, first: usize) -> Result<Vec<CompressedForexPoint>> {
+    let points: i64 = row.get(first)?;
+    columnar::decode_chunk(
+        points as usize,
+        &row.get::<_, Vec<u8>>(first + 1)?,
+        &row.get::<_, Vec<u8>>(first + 2)?,
+        &row.get::<_, Vec<u8>>(first + 3)?,
+        &row.get::<_, Vec<u8>>(first + 4)?,
+        &row.get::<_, Vec<u8>>(first + 5)?,
+        &row.get::<_, Vec<u8>>(first + 6)?,
+    )
+}
+
+/// Connections opened per file-backed database
+pub const DEFAULT_POOL_SIZE: usize = 4;
+
+/// How long a connection waits on a locked database before giving up
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Small fixed pool of connections, each guarded by its own mutex
+struct ConnectionPool {
+    connections: Vec<Mutex<Connection>>,
+    next: AtomicUsize,
+}
+
+impl ConnectionPool {
+    /// Take the first idle connection, or wait on the next one in turn
+    fn get(&self) -> MutexGuard<'_, Connection> {
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        let size = self.connections.len();
+
+        for offset in 0..size {
+            if let Ok(conn) = self.connections[(start + offset) % size].try_lock() {
+                return conn;
+            }
+        }
+
+        self.connections[start % size]
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Embedded SQLite database for forex data.
+///
+/// Clones share the same connection pool, so one handle can be passed to the
+/// server, the multi-currency manager and the dashboards across threads.
+#[derive(Clone)]
 pub struct EmbeddedForexDB {
-    conn: Connection,
+    pool: Arc<ConnectionPool>,
 }
 
 impl EmbeddedForexDB {
     /// Create new embedded database in memory
     pub fn new() -> Result<Self> {
-        let conn = Connection::open(":memory:")?;
-        
-        // Create tables
-        conn.execute(
-            "CREATE TABLE forex_data (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                pair TEXT NOT NULL,
-                data BLOB NOT NULL,
-                data_points INTEGER NOT NULL,
-                created_at INTEGER NOT NULL
-            )",
-            [],
-        )?;
+        // Every `:memory:` connection is a separate database, so keep a single one
+        Self::from_connections(vec![Connection::open(":memory:")?])
+    }
 
-        conn.execute(
-            "CREATE INDEX idx_pair ON forex_data(pair)",
-            [],
-        )?;
+    /// Open (or create) a file-backed embedded database
+    pub fn open(path: &Path) -> Result<Self> {
+        Self::open_with_pool_size(path, DEFAULT_POOL_SIZE)
+    }
 
-        conn.execute(
-            "CREATE TABLE correlation_matrix (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                pair1 TEXT NOT NULL,
-                pair2 TEXT NOT NULL,
-                correlation REAL NOT NULL,
-                timeframe TEXT NOT NULL,
-                created_at INTEGER NOT NULL
-            )",
-            [],
-        )?;
+    /// Open a file-backed database with `pool_size` connections
+    pub fn open_with_pool_size(path: &Path, pool_size: usize) -> Result<Self> {
+        let first = Connection::open(path)?;
+        // WAL lets readers proceed while another connection writes
+        first.query_row("PRAGMA journal_mode=WAL", [], |_| Ok(()))?;
+
+        let mut connections = vec![first];
+        for _ in 1..pool_size.max(1) {
+            connections.push(Connection::open(path)?);
+        }
+
+        Self::from_connections(connections)
+    }
+
+    fn from_connections(connections: Vec<Connection>) -> Result<Self> {
+        for conn in &connections {
+            conn.busy_timeout(BUSY_TIMEOUT)?;
+        }
+        migrations::migrate(&connections[0])?;
+
+        Ok(Self {
+            pool: Arc::new(ConnectionPool {
+                connections: connections.into_iter().map(Mutex::new).collect(),
+                next: AtomicUsize::new(0),
+            }),
+        })
+    }
+
+    /// Restore a backup over `target` after verifying the backup is consistent.
+    ///
+    /// Nothing else may have `target` open while it is replaced.
+    pub fn restore(backup: &Path, target: &Path) -> Result<ConsistencyReport> {
+        let source = Connection::open_with_flags(backup, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        let report = check_connection(&source)?;
+        drop(source);
+        if !report.is_ok() {
+            bail!("backup {} failed consistency check: {:?}", backup.display(), report);
+        }
+
+        for suffix in ["-wal", "-shm"] {
+            let sidecar = PathBuf::from(format!("{}{}", target.display(), suffix));
+            if sidecar.exists() {
+                std::fs::remove_file(sidecar)?;
+            }
+        }
+        std::fs::copy(backup, target)?;
+
+        // Reopen so older backups are migrated to the current schema
+        Self::open_with_pool_size(target, 1)?;
+        Ok(report)
+    }
 
-        conn.execute(
-            "CREATE INDEX idx_correlation ON correlation_matrix(pair1, pair2)",
-            [],
+    /// Write a compacted, consistent copy of the database to `destination`
+    pub fn backup(&self, destination: &Path) -> Result<()> {
+        if destination.exists() {
+            bail!("backup destination {} already exists", destination.display());
+        }
+        self.pool.get().execute(
+            "VACUUM INTO ?1",
+            params![destination.to_string_lossy()],
         )?;
+        Ok(())
+    }
+
+    /// Check SQLite integrity and that every stored series decodes
+    pub fn check(&self) -> Result<ConsistencyReport> {
+        check_connection(&self.pool.get())
+    }
 
-        Ok(Self { conn })
+    /// Schema version of the underlying database
+    pub fn schema_version(&self) -> Result<u32> {
+        migrations::schema_version(&self.pool.get())
+    }
+
+    /// Number of pooled connections
+    pub fn pool_size(&self) -> usize {
+        self.pool.connections.len()
+    }
+
+    /// Run blocking database work on the blocking thread pool
+    pub async fn run<F, T>(&self, work: F) -> Result<T>
+    where
+        F: FnOnce(&EmbeddedForexDB) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let db = self.clone();
+        tokio::task::spawn_blocking(move || work(&db)).await?
+    }
+
+    /// Async variant of [`store_forex_data`](Self::store_forex_data)
+    pub async fn store_forex_data_async(&self, pair: String, data: Vec<ForexDataPoint>) -> Result<()> {
+        self.run(move |db| db.store_forex_data(&pair, &data)).await
+    }
+
+    /// Async variant of [`get_forex_data`](Self::get_forex_data)
+    pub async fn get_forex_data_async(&self, pair: String) -> Result<Vec<ForexDataPoint>> {
+        self.run(move |db| db.get_forex_data(&pair)).await
+    }
+
+    /// Async variant of [`append_forex_data`](Self::append_forex_data)
+    pub async fn append_forex_data_async(&self, pair: String, data: Vec<ForexDataPoint>) -> Result<usize> {
+        self.run(move |db| db.append_forex_data(&pair, &data)).await
+    }
+
+    /// Async variant of [`get_forex_data_between`](Self::get_forex_data_between)
+    pub async fn get_forex_data_between_async(&self, pair: String, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<ForexDataPoint>> {
+        self.run(move |db| db.get_forex_data_between(&pair, start, end)).await
+    }
+
+    /// Async variant of [`store_correlation`](Self::store_correlation)
+    pub async fn store_correlation_async(&self, pair1: String, pair2: String, correlation: f64, timeframe: String) -> Result<()> {
+        self.run(move |db| db.store_correlation(&pair1, &pair2, correlation, &timeframe)).await
+    }
+
+    /// Async variant of [`get_correlation_matrix`](Self::get_correlation_matrix)
+    pub async fn get_correlation_matrix_async(&self, timeframe: String) -> Result<HashMap<(String, String), f64>> {
+        self.run(move |db| db.get_correlation_matrix(&timeframe)).await
     }
 
     /// Store compressed forex data for a currency pair
@@ -110,7 +437,7 @@ impl EmbeddedForexDB {
         let compressed_blob = encoder.finish()?;
 
         // Store in database
-        self.conn.execute(
+        self.pool.get().execute(
             "INSERT INTO forex_data (pair, data, data_points, created_at) VALUES (?1, ?2, ?3, ?4)",
             params![pair, compressed_blob, data.len(), Utc::now().timestamp()],
         )?;
@@ -122,35 +449,229 @@ impl EmbeddedForexDB {
         Ok(())
     }
 
-    /// Retrieve forex data for a currency pair
+    /// Retrieve forex data for a currency pair: the latest full blob merged with every
+    /// appended month, an appended bar replacing a blob bar at the same timestamp
     pub fn get_forex_data(&self, pair: &str) -> Result<Vec<ForexDataPoint>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT data FROM forex_data WHERE pair = ?1 ORDER BY created_at DESC LIMIT 1"
+        let forex_data = self.merged_series(pair, DateTime::<Utc>::MIN_UTC, DateTime::<Utc>::MAX_UTC)?;
+        if forex_data.is_empty() {
+            bail!("no forex data stored for {}", pair);
+        }
+
+        println!("📊 Retrieved {} data points for {}", forex_data.len(), pair);
+        Ok(forex_data)
+    }
+
+    /// Bars with `start <= timestamp <= end` from the latest full blob and the appended
+    /// months, merged by timestamp with the appended bars winning
+    fn merged_series(&self, pair: &str, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<ForexDataPoint>> {
+        let latest_blob: Option<Vec<u8>> = {
+            let conn = self.pool.get();
+            let mut stmt = conn.prepare(
+                "SELECT data FROM forex_data WHERE pair = ?1 ORDER BY created_at DESC LIMIT 1"
+            )?;
+            let mut rows = stmt.query(params![pair])?;
+            rows.next()?.map(|row| row.get(0)).transpose()?
+        };
+
+        let (from, to) = (start.timestamp(), end.timestamp());
+        let mut merged: BTreeMap<i64, ForexDataPoint> = BTreeMap::new();
+        if let Some(blob) = latest_blob {
+            merged.extend(decode_series(&blob)?
+                .into_iter()
+                .filter(|point| point.timestamp >= from && point.timestamp <= to)
+                .map(|point| (point.timestamp, point.into())));
+        }
+        merged.extend(self.appended_between(pair, start, end)?
+            .into_iter()
+            .map(|point| (point.timestamp.timestamp(), point)));
+
+        Ok(merged.into_values().collect())
+    }
+
+    /// Merge bars into a pair's monthly blobs, rewriting only the months they fall
+    /// in. A bar at an already stored timestamp replaces it. Returns the number of
+    /// months written.
+    pub fn append_forex_data(&self, pair: &str, data: &[ForexDataPoint]) -> Result<usize> {
+        let mut months: BTreeMap<i64, Vec<CompressedForexPoint>> = BTreeMap::new();
+        for point in data {
+            let point = CompressedForexPoint::from(point);
+            months.entry(month_start(point.timestamp)).or_default().push(point);
+        }
+
+        let created_at = Utc::now().timestamp();
+        let conn = self.pool.get();
+        let tx = conn.unchecked_transaction()?;
+        for (month, new_points) in &months {
+            let stored: Option<Vec<u8>> = {
+                let mut stmt = tx.prepare("SELECT data FROM forex_months WHERE pair = ?1 AND month_start = ?2")?;
+                let mut rows = stmt.query(params![pair, month])?;
+                rows.next()?.map(|row| row.get(0)).transpose()?
+            };
+
+            let mut merged: BTreeMap<i64, CompressedForexPoint> = BTreeMap::new();
+            if let Some(blob) = stored {
+                merged.extend(decode_series(&blob)?.into_iter().map(|point| (point.timestamp, point)));
+            }
+            merged.extend(new_points.iter().map(|point| (point.timestamp, point.clone())));
+            let points: Vec<CompressedForexPoint> = merged.into_values().collect();
+
+            tx.execute(
+                "INSERT OR REPLACE INTO forex_months
+                 (pair, month_start, start_ts, end_ts, data, data_points, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    pair, month,
+                    points.first().map(|p| p.timestamp), points.last().map(|p| p.timestamp),
+                    encode_series(&points)?, points.len(), created_at
+                ],
+            )?;
+        }
+        tx.commit()?;
+
+        println!("📦 Appended {} data points for {} across {} months", data.len(), pair, months.len());
+        Ok(months.len())
+    }
+
+    /// Bars with `start <= timestamp <= end` from the latest full blob and the appended
+    /// months, decompressing only the months that overlap
+    pub fn get_forex_data_between(&self, pair: &str, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<ForexDataPoint>> {
+        self.merged_series(pair, start, end)
+    }
+
+    /// Appended bars with `start <= timestamp <= end`, decompressing only the months that overlap
+    fn appended_between(&self, pair: &str, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<ForexDataPoint>> {
+        let (start, end) = (start.timestamp(), end.timestamp());
+        let conn = self.pool.get();
+        let mut stmt = conn.prepare(
+            "SELECT data FROM forex_months
+             WHERE pair = ?1 AND end_ts >= ?2 AND start_ts <= ?3
+             ORDER BY month_start"
         )?;
 
-        let compressed_blob: Vec<u8> = stmt.query_row(params![pair], |row| {
-            Ok(row.get(0)?)
-        })?;
+        let mut bars = Vec::new();
+        let mut rows = stmt.query(params![pair, start, end])?;
+        while let Some(row) = rows.next()? {
+            bars.extend(decode_series(&row.get::<_, Vec<u8>>(0)?)?
+                .into_iter()
+                .filter(|point| point.timestamp >= start && point.timestamp <= end)
+                .map(Into::<ForexDataPoint>::into));
+        }
 
-        // Decompress and deserialize
-        let mut decoder = GzDecoder::new(&compressed_blob[..]);
-        let mut decompressed = Vec::new();
-        decoder.read_to_end(&mut decompressed)?;
+        Ok(bars)
+    }
 
-        let compressed_data: Vec<CompressedForexPoint> = bincode::deserialize(&decompressed)?;
-        
-        // Convert back to ForexDataPoint
-        let forex_data: Vec<ForexDataPoint> = compressed_data.into_iter()
-            .map(|point| point.into())
-            .collect();
+    /// Store a series as columnar chunks, returning the encoded size in bytes
+    pub fn store_candles_columnar(&self, pair: &str, data: &[ForexDataPoint]) -> Result<usize> {
+        let compressed: Vec<CompressedForexPoint> = data.iter().map(CompressedForexPoint::from).collect();
+        let created_at = Utc::now().timestamp();
+        let mut encoded_bytes = 0;
+
+        let conn = self.pool.get();
+        let tx = conn.unchecked_transaction()?;
+        for points in compressed.chunks(CHUNK_SIZE) {
+            let chunk = ColumnChunk::encode(points);
+            encoded_bytes += chunk.encoded_len();
+            tx.execute(
+                "INSERT INTO candle_chunks
+                 (pair, start_ts, end_ts, points, timestamps, open, high, low, close, volume, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                params![
+                    pair, chunk.start_ts, chunk.end_ts, chunk.points,
+                    chunk.timestamps, chunk.open, chunk.high, chunk.low, chunk.close, chunk.volume,
+                    created_at
+                ],
+            )?;
+        }
+        tx.commit()?;
 
-        println!("📊 Retrieved {} data points for {}", forex_data.len(), pair);
-        Ok(forex_data)
+        Ok(encoded_bytes)
+    }
+
+    /// Read columnar candles with `from <= timestamp <= to`, decoding only overlapping chunks
+    pub fn get_candles_range(&self, pair: &str, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<ForexDataPoint>> {
+        let (from, to) = (from.timestamp(), to.timestamp());
+        let conn = self.pool.get();
+        let mut stmt = conn.prepare(
+            "SELECT points, timestamps, open, high, low, close, volume FROM candle_chunks
+             WHERE pair = ?1 AND end_ts >= ?2 AND start_ts <= ?3
+             ORDER BY start_ts"
+        )?;
+
+        let mut candles = Vec::new();
+        let mut rows = stmt.query(params![pair, from, to])?;
+        while let Some(row) = rows.next()? {
+            candles.extend(decode_chunk_row(row, 0)?
+                .into_iter()
+                .filter(|point| point.timestamp >= from && point.timestamp <= to)
+                .map(Into::<ForexDataPoint>::into));
+        }
+
+        Ok(candles)
+    }
+
+    /// Bars strictly after `after` up to `to`, from columnar chunks when present, else the
+    /// latest blob merged with the appended months
+    pub fn get_bars_after(&self, pair: &str, after: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<ForexDataPoint>> {
+        let after = after + chrono::Duration::seconds(1);
+        let candles = self.get_candles_range(pair, after, to)?;
+        if !candles.is_empty() {
+            return Ok(candles);
+        }
+
+        self.merged_series(pair, after, to)
+    }
+
+    /// Read only the timestamp and close columns for a time range
+    pub fn get_close_range(&self, pair: &str, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<(DateTime<Utc>, f64)>> {
+        let (from, to) = (from.timestamp(), to.timestamp());
+        let conn = self.pool.get();
+        let mut stmt = conn.prepare(
+            "SELECT points, timestamps, close FROM candle_chunks
+             WHERE pair = ?1 AND end_ts >= ?2 AND start_ts <= ?3
+             ORDER BY start_ts"
+        )?;
+
+        let mut closes = Vec::new();
+        let mut rows = stmt.query(params![pair, from, to])?;
+        while let Some(row) = rows.next()? {
+            let points = row.get::<_, i64>(0)? as usize;
+            let timestamps = columnar::decode_column(&row.get::<_, Vec<u8>>(1)?, points)?;
+            let close = columnar::decode_column(&row.get::<_, Vec<u8>>(2)?, points)?;
+            closes.extend(timestamps.into_iter()
+                .zip(close)
+                .filter(|(ts, _)| *ts >= from && *ts <= to)
+                .filter_map(|(ts, close)| {
+                    DateTime::from_timestamp(ts, 0).map(|ts| (ts, close as f64 / 100000.0))
+                }));
+        }
+
+        Ok(closes)
+    }
+
+    /// Bytes used by a pair in each storage format
+    pub fn storage_footprint(&self, pair: &str) -> Result<StorageFootprint> {
+        let conn = self.pool.get();
+        let blob_bytes: i64 = conn.query_row(
+            "SELECT COALESCE(SUM(LENGTH(data)), 0) FROM (
+                SELECT data FROM forex_data WHERE pair = ?1
+                UNION ALL SELECT data FROM forex_months WHERE pair = ?1
+             )",
+            params![pair],
+            |row| row.get(0),
+        )?;
+        let columnar_bytes: i64 = conn.query_row(
+            "SELECT COALESCE(SUM(LENGTH(timestamps) + LENGTH(open) + LENGTH(high)
+                + LENGTH(low) + LENGTH(close) + LENGTH(volume)), 0)
+             FROM candle_chunks WHERE pair = ?1",
+            params![pair],
+            |row| row.get(0),
+        )?;
+        Ok(StorageFootprint { blob_bytes: blob_bytes as usize, columnar_bytes: columnar_bytes as usize })
     }
 
     /// Store correlation matrix
     pub fn store_correlation(&self, pair1: &str, pair2: &str, correlation: f64, timeframe: &str) -> Result<()> {
-        self.conn.execute(
+        self.pool.get().execute(
             "INSERT OR REPLACE INTO correlation_matrix (pair1, pair2, correlation, timeframe, created_at) 
              VALUES (?1, ?2, ?3, ?4, ?5)",
             params![pair1, pair2, correlation, timeframe, Utc::now().timestamp()],
@@ -160,7 +681,8 @@ impl EmbeddedForexDB {
 
     /// Get correlation matrix for all pairs
     pub fn get_correlation_matrix(&self, timeframe: &str) -> Result<HashMap<(String, String), f64>> {
-        let mut stmt = self.conn.prepare(
+        let conn = self.pool.get();
+        let mut stmt = conn.prepare(
             "SELECT pair1, pair2, correlation FROM correlation_matrix WHERE timeframe = ?1"
         )?;
 
@@ -180,10 +702,96 @@ impl EmbeddedForexDB {
         Ok(correlations)
     }
 
+    /// Store a pair's similarity index, replacing any previous one, returning the windows stored
+    pub fn store_similarity_index(&self, pair: &str, index: &SimilarityIndex) -> Result<usize> {
+        let conn = self.pool.get();
+        let tx = conn.unchecked_transaction()?;
+        for table in ["similarity_indexes", "similarity_windows", "similarity_buckets"] {
+            tx.execute(&format!("DELETE FROM {} WHERE pair = ?1", table), params![pair])?;
+        }
+        tx.execute(
+            "INSERT INTO similarity_indexes (pair, config, return_scale, created_at) VALUES (?1, ?2, ?3, ?4)",
+            params![pair, serde_json::to_string(index.config())?, index.return_scale(), Utc::now().timestamp()],
+        )?;
+        {
+            let mut window_stmt = tx.prepare(
+                "INSERT INTO similarity_windows (pair, position, window_end, features) VALUES (?1, ?2, ?3, ?4)"
+            )?;
+            let mut bucket_stmt = tx.prepare(
+                "INSERT INTO similarity_buckets (pair, band, bucket, position) VALUES (?1, ?2, ?3, ?4)"
+            )?;
+            for (position, window) in index.windows().iter().enumerate() {
+                window_stmt.execute(params![pair, position as i64, window.window_end.timestamp(), bincode::serialize(&window.features)?])?;
+                for (band, bucket) in index.hasher().keys(&window.features).into_iter().enumerate() {
+                    bucket_stmt.execute(params![pair, band as i64, bucket as i64, position as i64])?;
+                }
+            }
+        }
+        tx.commit()?;
+
+        println!("🔑 Stored similarity index of {} windows for {}", index.len(), pair);
+        Ok(index.len())
+    }
+
+    /// Load a pair's similarity index into memory
+    pub fn load_similarity_index(&self, pair: &str) -> Result<Option<SimilarityIndex>> {
+        let conn = self.pool.get();
+        let mut stmt = conn.prepare("SELECT config, return_scale FROM similarity_indexes WHERE pair = ?1")?;
+        let mut rows = stmt.query(params![pair])?;
+        let Some(row) = rows.next()? else {
+            return Ok(None);
+        };
+        let config: String = row.get(0)?;
+        let mut index = SimilarityIndex::new(serde_json::from_str(&config)?, row.get(1)?)?;
+        drop(rows);
+
+        let mut stmt = conn.prepare(
+            "SELECT window_end, features FROM similarity_windows WHERE pair = ?1 ORDER BY position"
+        )?;
+        let mut rows = stmt.query(params![pair])?;
+        while let Some(row) = rows.next()? {
+            let window = decode_similarity_window(row)?;
+            index.insert(window.window_end, window.features);
+        }
+        Ok(Some(index))
+    }
+
+    /// Up to `k` stored windows of a pair nearest to `features`, reading only the buckets `features` falls in
+    pub fn similar_windows(&self, pair: &str, features: &[f64], k: usize) -> Result<Vec<SimilarWindow>> {
+        let conn = self.pool.get();
+        let mut stmt = conn.prepare("SELECT config FROM similarity_indexes WHERE pair = ?1")?;
+        let mut rows = stmt.query(params![pair])?;
+        let Some(row) = rows.next()? else {
+            return Ok(Vec::new());
+        };
+        let config: SimilarityConfig = serde_json::from_str(&row.get::<_, String>(0)?)?;
+        drop(rows);
+        let keys = WindowHasher::new(config)?.keys(features);
+
+        let mut stmt = conn.prepare(
+            "SELECT DISTINCT w.window_end, w.features FROM similarity_buckets b
+             JOIN similarity_windows w ON w.pair = b.pair AND w.position = b.position
+             WHERE b.pair = ?1 AND b.band = ?2 AND b.bucket = ?3"
+        )?;
+        let mut candidates: HashMap<i64, IndexedWindow> = HashMap::new();
+        for (band, bucket) in keys.into_iter().enumerate() {
+            let mut rows = stmt.query(params![pair, band as i64, bucket as i64])?;
+            while let Some(row) = rows.next()? {
+                let window = decode_similarity_window(row)?;
+                candidates.entry(window.window_end.timestamp()).or_insert(window);
+            }
+        }
+        Ok(similarity::rank(candidates.values(), features, k))
+    }
+
     /// Get database statistics
     pub fn get_stats(&self) -> Result<()> {
-        let mut stmt = self.conn.prepare(
-            "SELECT pair, data_points, LENGTH(data) as blob_size FROM forex_data ORDER BY pair"
+        let conn = self.pool.get();
+        let mut stmt = conn.prepare(
+            "SELECT pair, SUM(data_points), SUM(LENGTH(data)) FROM (
+                SELECT pair, data_points, data FROM forex_data
+                UNION ALL SELECT pair, data_points, data FROM forex_months
+             ) GROUP BY pair ORDER BY pair"
         )?;
 
         let rows = stmt.query_map([], |row| {
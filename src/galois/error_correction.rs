@@ -0,0 +1,196 @@
+//! # Price Error Correction
+//!
+//! Treats quantized closes as Reed-Solomon codewords, so isolated bad ticks on a
+//! flat or trending stretch are corrected as symbol errors; corrections touching
+//! more than `max_correction_fraction` of a block are refused as genuine moves.
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+use super::reed_solomon::ReedSolomon;
+use super::GaloisField;
+use crate::data::ForexDataPoint;
+
+/// Price error correction configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ErrorCorrectionConfig {
+    /// Galois field characteristic
+    pub field_characteristic: u32,
+    /// Field extension degree
+    pub field_degree: u32,
+    /// Bars per codeword
+    pub block_length: usize,
+    /// Polynomial degree bound k of a codeword
+    pub message_length: usize,
+    /// Price quantum of one symbol step
+    pub tick_size: f64,
+    /// Largest fraction of a block a correction may change
+    pub max_correction_fraction: f64,
+}
+
+impl Default for ErrorCorrectionConfig {
+    fn default() -> Self {
+        Self {
+            field_characteristic: 2,
+            field_degree: 32,
+            block_length: 64,
+            message_length: 2,
+            tick_size: 0.0005,
+            max_correction_fraction: 0.05,
+        }
+    }
+}
+
+/// How a block decoded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BlockStatus {
+    /// Already a codeword
+    Clean,
+    Corrected,
+    /// Too far from any codeword, or the correction would change too much; left as is
+    Uncorrectable,
+}
+
+/// Decoding of one block of bars
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockDecoding {
+    /// Index of the block's first bar
+    pub start: usize,
+    pub len: usize,
+    pub status: BlockStatus,
+    /// Nonzero syndromes of the received block
+    pub syndrome_weight: usize,
+    /// Bars whose close was corrected
+    pub corrected_bars: Vec<usize>,
+}
+
+/// Syndrome statistics over a sequence
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyndromeStatistics {
+    pub blocks: usize,
+    pub clean_blocks: usize,
+    pub corrected_blocks: usize,
+    pub uncorrectable_blocks: usize,
+    pub corrected_symbols: usize,
+    /// Mean fraction of nonzero syndromes per block
+    pub mean_syndrome_weight: f64,
+}
+
+/// Corrected sequence with per-block decodings
+#[derive(Debug, Clone)]
+pub struct CorrectionReport {
+    pub corrected: Vec<ForexDataPoint>,
+    pub blocks: Vec<BlockDecoding>,
+    pub statistics: SyndromeStatistics,
+}
+
+/// Decodes quantized price sequences block by block
+#[derive(Debug, Clone)]
+pub struct PriceErrorCorrector {
+    config: ErrorCorrectionConfig,
+    code: ReedSolomon,
+}
+
+impl PriceErrorCorrector {
+    pub fn new(config: ErrorCorrectionConfig) -> Result<Self> {
+        if config.tick_size.is_nan() || config.tick_size <= 0.0 {
+            bail!("Error correction tick size must be positive");
+        }
+        if !(0.0..=1.0).contains(&config.max_correction_fraction) {
+            bail!("Largest correction fraction must be in [0, 1]");
+        }
+        let field = GaloisField::new_with_degree(config.field_characteristic, config.field_degree)?;
+        let code = ReedSolomon::new(field, config.block_length, config.message_length)?;
+        Ok(Self { config, code })
+    }
+
+    pub fn config(&self) -> &ErrorCorrectionConfig {
+        &self.config
+    }
+
+    pub fn code(&self) -> &ReedSolomon {
+        &self.code
+    }
+
+    /// Decode every block of `data`; a trailing block too short to carry a
+    /// syndrome is passed through undecoded
+    pub fn correct(&self, data: &[ForexDataPoint]) -> Result<CorrectionReport> {
+        let mut corrected = data.to_vec();
+        let mut blocks = Vec::new();
+        for start in (0..data.len()).step_by(self.config.block_length) {
+            let len = self.config.block_length.min(data.len() - start);
+            if len < self.config.message_length + 2 {
+                break;
+            }
+            let shortened;
+            let code = if len == self.config.block_length {
+                &self.code
+            } else {
+                shortened = ReedSolomon::new(self.code.field().clone(), len, self.config.message_length)?;
+                &shortened
+            };
+            let received: Vec<u64> = data[start..start + len].iter().map(|bar| self.quantize(bar.close)).collect();
+            let decoding = code.decode(&received);
+            let allowed = (self.config.max_correction_fraction * len as f64).floor() as usize;
+            let status = match &decoding.corrected {
+                Some(_) if decoding.error_positions.is_empty() => BlockStatus::Clean,
+                Some(_) if decoding.error_positions.len() <= allowed => BlockStatus::Corrected,
+                _ => BlockStatus::Uncorrectable,
+            };
+            let mut corrected_bars = Vec::new();
+            if status == BlockStatus::Corrected {
+                let codeword = decoding.corrected.as_ref().unwrap();
+                for &position in &decoding.error_positions {
+                    let bar = &mut corrected[start + position];
+                    bar.close = self.dequantize(codeword[position]);
+                    bar.high = bar.open.max(bar.close);
+                    bar.low = bar.open.min(bar.close);
+                    corrected_bars.push(start + position);
+                }
+            }
+            blocks.push(BlockDecoding { start, len, status, syndrome_weight: decoding.syndrome_weight, corrected_bars });
+        }
+
+        let statistics = SyndromeStatistics {
+            blocks: blocks.len(),
+            clean_blocks: blocks.iter().filter(|b| b.status == BlockStatus::Clean).count(),
+            corrected_blocks: blocks.iter().filter(|b| b.status == BlockStatus::Corrected).count(),
+            uncorrectable_blocks: blocks.iter().filter(|b| b.status == BlockStatus::Uncorrectable).count(),
+            corrected_symbols: blocks.iter().map(|b| b.corrected_bars.len()).sum(),
+            mean_syndrome_weight: if blocks.is_empty() {
+                0.0
+            } else {
+                blocks.iter().map(|b| b.syndrome_weight as f64 / (b.len - self.config.message_length) as f64).sum::<f64>() / blocks.len() as f64
+            },
+        };
+        Ok(CorrectionReport { corrected, blocks, statistics })
+    }
+
+    /// Field element of `price` in whole ticks
+    fn quantize(&self, price: f64) -> u64 {
+        let field = self.code.field();
+        let ticks = (price / self.config.tick_size).round() as i64;
+        if field.degree() > 1 {
+            (ticks as u64) & (field.size() - 1)
+        } else {
+            let magnitude = field.reduce(ticks.unsigned_abs());
+            if ticks < 0 { field.neg(magnitude) } else { magnitude }
+        }
+    }
+
+    /// Inverse of [`Self::quantize`] for elements of at most half the field size
+    fn dequantize(&self, element: u64) -> f64 {
+        let size = self.code.field().size();
+        let ticks = if self.code.field().degree() > 1 {
+            // Sign-extend from the field's bit width
+            let shift = 64 - self.code.field().degree();
+            ((element << shift) as i64) >> shift
+        } else if element > size / 2 {
+            -((size - element) as i64)
+        } else {
+            element as i64
+        };
+        ticks as f64 * self.config.tick_size
+    }
+}
@@ -1,58 +1,206 @@
 //! # Galois Field Operations
-//! 
-//! Finite field arithmetic for cyclic pattern detection.
+//!
+//! Finite field arithmetic for cyclic pattern detection, over prime fields GF(p)
+//! for p below 2^32 and binary extension fields GF(2^k) for 1 ≤ k ≤ 63.
 
-use anyhow::Result;
+pub mod error_correction;
+pub mod polynomial;
+pub mod primes;
+pub mod reed_solomon;
+pub mod similarity;
+
+pub use error_correction::{CorrectionReport, ErrorCorrectionConfig, PriceErrorCorrector, SyndromeStatistics};
+pub use reed_solomon::ReedSolomon;
+pub use similarity::{IndexedWindow, SimilarWindow, SimilarityConfig, SimilarityIndex, WindowHasher};
+
+use anyhow::{bail, Result};
+
+/// Largest binary extension degree, so every element and the field size fit a `u64`
+pub const MAX_BINARY_DEGREE: u32 = 63;
 
 /// Galois field implementation
+#[derive(Debug, Clone)]
 pub struct GaloisField {
-    prime: u64,
     characteristic: u32,
     degree: u32,
     size: u64,
+    /// p for a prime field; the reduction polynomial, x^k term included, for GF(2^k)
+    modulus: u128,
 }
 
 impl GaloisField {
+    /// Prime field GF(`prime`)
     pub fn new(prime: u64) -> Result<Self> {
+        if prime > u32::MAX as u64 || !primes::is_prime(prime) {
+            bail!("GF({}) needs a prime characteristic below 2^32", prime);
+        }
         Ok(Self {
-            prime,
             characteristic: prime as u32,
             degree: 1,
             size: prime,
+            modulus: prime as u128,
         })
     }
 
+    /// GF(`characteristic`^`degree`); extensions of degree above one need
+    /// characteristic 2 and reduce by [`polynomial::lowest_irreducible`]
     pub fn new_with_degree(characteristic: u32, degree: u32) -> Result<Self> {
-        let size = (characteristic as u64).pow(degree);
+        match (characteristic, degree) {
+            (_, 0) => bail!("GF({}^0) is not a field", characteristic),
+            (p, 1) => Self::new(p as u64),
+            (2, k) if k <= MAX_BINARY_DEGREE => {
+                let modulus = polynomial::lowest_irreducible(k)
+                    .ok_or_else(|| anyhow::anyhow!("No irreducible polynomial of degree {}", k))?;
+                Self::binary_with_modulus(modulus)
+            }
+            (2, k) => bail!("GF(2^{}) is larger than the supported GF(2^{})", k, MAX_BINARY_DEGREE),
+            (p, k) => bail!("GF({}^{}): only characteristic 2 has extension fields", p, k),
+        }
+    }
+
+    /// GF(2^k) reducing by `modulus`, an irreducible polynomial of degree k
+    pub fn binary_with_modulus(modulus: u128) -> Result<Self> {
+        let degree = polynomial::degree(modulus).unwrap_or(0);
+        if !(1..=MAX_BINARY_DEGREE).contains(&degree) {
+            bail!("Reduction polynomial {:#x} must have degree 1 to {}", modulus, MAX_BINARY_DEGREE);
+        }
+        if !polynomial::is_irreducible(modulus) {
+            bail!("Reduction polynomial {:#x} is reducible", modulus);
+        }
         Ok(Self {
-            prime: characteristic as u64,
-            characteristic,
+            characteristic: 2,
             degree,
-            size,
+            size: 1u64 << degree,
+            modulus,
         })
     }
-    
+
     pub fn size(&self) -> u64 {
         self.size
     }
-    
+
+    pub fn characteristic(&self) -> u32 {
+        self.characteristic
+    }
+
+    pub fn degree(&self) -> u32 {
+        self.degree
+    }
+
+    /// p for a prime field, the reduction polynomial for GF(2^k)
+    pub fn modulus(&self) -> u128 {
+        self.modulus
+    }
+
+    fn is_binary_extension(&self) -> bool {
+        self.degree > 1
+    }
+
+    pub fn contains(&self, element: u64) -> bool {
+        element < self.size
+    }
+
+    /// Field element of an arbitrary integer: its residue modulo p, or for
+    /// GF(2^k) the remainder of its bit polynomial by the reduction polynomial
+    pub fn reduce(&self, value: u64) -> u64 {
+        if self.is_binary_extension() {
+            polynomial::rem(value as u128, self.modulus) as u64
+        } else {
+            value % self.size
+        }
+    }
+
+    pub fn add(&self, a: u64, b: u64) -> u64 {
+        debug_assert!(self.contains(a) && self.contains(b));
+        if self.characteristic == 2 {
+            a ^ b
+        } else {
+            ((a as u128 + b as u128) % self.modulus) as u64
+        }
+    }
+
+    pub fn neg(&self, a: u64) -> u64 {
+        debug_assert!(self.contains(a));
+        if self.characteristic == 2 || a == 0 {
+            a
+        } else {
+            self.size - a
+        }
+    }
+
+    pub fn sub(&self, a: u64, b: u64) -> u64 {
+        self.add(a, self.neg(b))
+    }
+
+    pub fn mul(&self, a: u64, b: u64) -> u64 {
+        debug_assert!(self.contains(a) && self.contains(b));
+        if self.is_binary_extension() {
+            polynomial::mul_mod(a as u128, b as u128, self.modulus) as u64
+        } else {
+            ((a as u128 * b as u128) % self.modulus) as u64
+        }
+    }
+
+    /// `base`^`exponent`, with 0^0 = 1
+    pub fn pow(&self, mut base: u64, mut exponent: u64) -> u64 {
+        let mut result = 1;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = self.mul(result, base);
+            }
+            base = self.mul(base, base);
+            exponent >>= 1;
+        }
+        result
+    }
+
+    /// Multiplicative inverse, a^(q−2) by Lagrange; `None` for zero
+    pub fn inverse(&self, a: u64) -> Option<u64> {
+        (a != 0).then(|| self.pow(a, self.size - 2))
+    }
+
+    pub fn div(&self, a: u64, b: u64) -> Option<u64> {
+        self.inverse(b).map(|inverse| self.mul(a, inverse))
+    }
+
+    /// Whether `a` generates the multiplicative group: a^((q−1)/r) ≠ 1 for every
+    /// prime r dividing q − 1
+    pub fn is_primitive(&self, a: u64) -> bool {
+        if a == 0 || !self.contains(a) {
+            return false;
+        }
+        let order = self.size - 1;
+        primes::prime_factors(order).into_iter().all(|r| self.pow(a, order / r) != 1)
+    }
+
+    /// Smallest generator of the multiplicative group
+    pub fn primitive_element(&self) -> u64 {
+        (1..self.size)
+            .find(|a| self.is_primitive(*a))
+            .expect("the multiplicative group of a finite field is cyclic")
+    }
+
+    /// Image of `element` under the extension polynomial Σ cᵢ·xⁱ, each real
+    /// coefficient quantized to thousandths and mapped into the field
     pub fn extend_element(&self, element: u64, polynomial: &[f64]) -> Result<u64> {
-        // Placeholder field extension
-        let mut result = element;
-        for (i, &coeff) in polynomial.iter().enumerate() {
-            result ^= ((coeff * 1000.0) as u64) << (i * 8);
+        let element = self.reduce(element);
+        let mut result = 0;
+        for &coeff in polynomial.iter().rev() {
+            let magnitude = self.reduce((coeff.abs() * 1000.0).round() as u64);
+            let coefficient = if coeff < 0.0 { self.neg(magnitude) } else { magnitude };
+            result = self.add(self.mul(result, element), coefficient);
         }
-        Ok(result % self.size)
+        Ok(result)
     }
 
     /// Encode temporal state into field element
     pub fn encode_temporal_state(&self, timestamp: u64, price: u64) -> u64 {
-        (timestamp ^ price) % self.prime
+        self.reduce(timestamp ^ price)
     }
 
     /// Decode price influence from field element
     pub fn decode_price_influence(&self, field_element: u64) -> f64 {
-        let normalized = field_element as f64 / self.prime as f64;
+        let normalized = field_element as f64 / self.size as f64;
         (normalized - 0.5) * 0.02 // ±1% max influence
     }
 }
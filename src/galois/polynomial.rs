@@ -0,0 +1,94 @@
+//! # Binary Polynomials
+//!
+//! Polynomials over GF(2) packed into integers, bit `i` holding the coefficient
+//! of `x^i`. Addition is XOR; products of two polynomials below degree 64 fit a
+//! `u128`, which bounds the extension fields built on them to degree 64.
+
+/// Degree of a nonzero polynomial
+pub fn degree(p: u128) -> Option<u32> {
+    (p != 0).then(|| 127 - p.leading_zeros())
+}
+
+/// Carry-less product
+pub fn clmul(a: u64, b: u64) -> u128 {
+    let (mut a, mut b, mut product) = (a as u128, b, 0u128);
+    while b != 0 {
+        if b & 1 == 1 {
+            product ^= a;
+        }
+        a <<= 1;
+        b >>= 1;
+    }
+    product
+}
+
+/// Remainder of `a` divided by the nonzero polynomial `m`
+pub fn rem(mut a: u128, m: u128) -> u128 {
+    let dm = degree(m).expect("division by the zero polynomial");
+    while let Some(da) = degree(a) {
+        if da < dm {
+            break;
+        }
+        a ^= m << (da - dm);
+    }
+    a
+}
+
+/// `a·b mod m` for `a` and `b` already reduced modulo `m`, of degree at most 64
+pub fn mul_mod(a: u128, b: u128, m: u128) -> u128 {
+    rem(clmul(a as u64, b as u64), m)
+}
+
+pub fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, rem(a, b));
+    }
+    a
+}
+
+/// Rabin's test: `m` of degree n is irreducible iff x^(2^n) ≡ x (mod m) and
+/// gcd(x^(2^(n/q)) − x, m) = 1 for every prime q dividing n
+pub fn is_irreducible(m: u128) -> bool {
+    let Some(n) = degree(m).filter(|n| (1..=64).contains(n)) else {
+        return false;
+    };
+    let x = rem(0b10, m);
+    // frobenius[i] = x^(2^i) mod m
+    let mut frobenius = vec![x];
+    for i in 0..n as usize {
+        frobenius.push(mul_mod(frobenius[i], frobenius[i], m));
+    }
+    if frobenius[n as usize] != x {
+        return false;
+    }
+    super::primes::prime_factors(n as u64)
+        .into_iter()
+        .all(|q| gcd(m, frobenius[(n as u64 / q) as usize] ^ x) == 1)
+}
+
+/// Irreducible polynomial of `degree` with the fewest terms, the lowest middle
+/// exponents first: x + 1, else a trinomial x^n + x^a + 1, else a pentanomial
+/// x^n + x^a + x^b + x^c + 1
+pub fn lowest_irreducible(degree: u32) -> Option<u128> {
+    if !(1..=64).contains(&degree) {
+        return None;
+    }
+    let top = 1u128 << degree;
+    if degree == 1 {
+        return Some(top | 1);
+    }
+    if let Some(trinomial) = (1..degree).map(|a| top | 1 << a | 1).find(|m| is_irreducible(*m)) {
+        return Some(trinomial);
+    }
+    for a in 3..degree {
+        for b in 2..a {
+            for c in 1..b {
+                let pentanomial = top | 1 << a | 1 << b | 1 << c | 1;
+                if is_irreducible(pentanomial) {
+                    return Some(pentanomial);
+                }
+            }
+        }
+    }
+    None
+}
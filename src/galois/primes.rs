@@ -0,0 +1,116 @@
+//! # Primes
+//!
+//! Primality and factorization of 64-bit integers, used to validate prime
+//! fields and to find the order of multiplicative groups.
+
+/// Bases that make Miller-Rabin deterministic below 2^64
+const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    ((a as u128 * b as u128) % m as u128) as u64
+}
+
+fn pow_mod(mut base: u64, mut exponent: u64, m: u64) -> u64 {
+    let mut result = 1 % m;
+    base %= m;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exponent >>= 1;
+    }
+    result
+}
+
+pub fn is_prime(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    for p in WITNESSES {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+    let (mut d, mut s) = (n - 1, 0);
+    while d.is_multiple_of(2) {
+        d /= 2;
+        s += 1;
+    }
+    WITNESSES.iter().all(|&a| {
+        let mut x = pow_mod(a, d, n);
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+        for _ in 1..s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+/// Distinct prime factors of `n`, ascending; none for 0 and 1
+pub fn prime_factors(mut n: u64) -> Vec<u64> {
+    let mut factors = Vec::new();
+    if n < 2 {
+        return factors;
+    }
+    for p in 2..1_000u64 {
+        if n.is_multiple_of(p) {
+            factors.push(p);
+            while n.is_multiple_of(p) {
+                n /= p;
+            }
+        }
+    }
+    let mut pending = vec![n];
+    while let Some(m) = pending.pop() {
+        if m == 1 {
+            continue;
+        }
+        if is_prime(m) {
+            factors.push(m);
+            continue;
+        }
+        let divisor = pollard_rho(m);
+        pending.push(divisor);
+        pending.push(m / divisor);
+    }
+    factors.sort_unstable();
+    factors.dedup();
+    factors
+}
+
+/// A nontrivial divisor of the odd composite `n` (Brent's variant)
+fn pollard_rho(n: u64) -> u64 {
+    for c in 1.. {
+        let step = |x: u64| (mul_mod(x, x, n) + c) % n;
+        let (mut x, mut y, mut divisor) = (2u64, 2u64, 1u64);
+        let mut power = 1;
+        let mut length = 0;
+        while divisor == 1 {
+            if length == power {
+                x = y;
+                power *= 2;
+                length = 0;
+            }
+            y = step(y);
+            length += 1;
+            divisor = gcd(x.abs_diff(y), n);
+        }
+        if divisor != n {
+            return divisor;
+        }
+    }
+    unreachable!("some increment always splits a composite")
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
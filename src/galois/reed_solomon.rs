@@ -0,0 +1,193 @@
+//! # Reed-Solomon Codes
+//!
+//! Evaluation-form Reed-Solomon codes over a [`GaloisField`], decoded with
+//! Berlekamp-Massey, a Chien search and Forney's formula, correcting up to
+//! ⌊(n − k)/2⌋ symbol errors.
+
+use anyhow::{bail, Result};
+
+use super::GaloisField;
+
+/// Outcome of decoding one received word
+#[derive(Debug, Clone)]
+pub struct Decoding {
+    /// Nonzero syndromes; zero for a codeword
+    pub syndrome_weight: usize,
+    /// The nearest codeword, `None` when more symbols are wrong than the code corrects
+    pub corrected: Option<Vec<u64>>,
+    /// Positions the decoder changed, ascending
+    pub error_positions: Vec<usize>,
+}
+
+/// Reed-Solomon code of length n and dimension k
+#[derive(Debug, Clone)]
+pub struct ReedSolomon {
+    field: GaloisField,
+    message_length: usize,
+    points: Vec<u64>,
+    /// Column multipliers of the dual code, 1 / Πⱼ≠ᵢ (xᵢ − xⱼ)
+    multipliers: Vec<u64>,
+}
+
+impl ReedSolomon {
+    pub fn new(field: GaloisField, length: usize, message_length: usize) -> Result<Self> {
+        if message_length == 0 || message_length >= length {
+            bail!("Reed-Solomon code needs 0 < k < n, got k={} n={}", message_length, length);
+        }
+        if length as u64 >= field.size() {
+            bail!("Reed-Solomon code of length {} needs more than {} field elements", length, field.size());
+        }
+        let points: Vec<u64> = if field.degree() > 1 {
+            let alpha = field.primitive_element();
+            (0..length as u64).map(|i| field.pow(alpha, i)).collect()
+        } else {
+            (1..=length as u64).collect()
+        };
+        let multipliers = points.iter()
+            .enumerate()
+            .map(|(i, &x)| {
+                let product = points.iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .fold(1, |product, (_, &y)| field.mul(product, field.sub(x, y)));
+                field.inverse(product).expect("evaluation points are distinct")
+            })
+            .collect();
+        Ok(Self { field, message_length, points, multipliers })
+    }
+
+    pub fn field(&self) -> &GaloisField {
+        &self.field
+    }
+
+    pub fn length(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn message_length(&self) -> usize {
+        self.message_length
+    }
+
+    /// Symbol errors the code always corrects
+    pub fn capacity(&self) -> usize {
+        (self.length() - self.message_length) / 2
+    }
+
+    /// Point the symbol at `position` is evaluated at
+    pub fn point(&self, position: usize) -> u64 {
+        self.points[position]
+    }
+
+    /// Codeword of the polynomial with coefficients `message`, lowest degree first
+    pub fn encode(&self, message: &[u64]) -> Result<Vec<u64>> {
+        if message.len() != self.message_length {
+            bail!("Message of {} symbols for a code of dimension {}", message.len(), self.message_length);
+        }
+        Ok(self.points.iter().map(|&x| evaluate(&self.field, message, x)).collect())
+    }
+
+    /// Sⱼ = Σᵢ vᵢ·rᵢ·xᵢʲ for j < n − k, all zero exactly for codewords
+    pub fn syndromes(&self, received: &[u64]) -> Vec<u64> {
+        let field = &self.field;
+        let mut syndromes = vec![0; self.length() - self.message_length];
+        for ((&symbol, &x), &v) in received.iter().zip(&self.points).zip(&self.multipliers) {
+            let mut term = field.mul(v, symbol);
+            for syndrome in syndromes.iter_mut() {
+                *syndrome = field.add(*syndrome, term);
+                term = field.mul(term, x);
+            }
+        }
+        syndromes
+    }
+
+    pub fn decode(&self, received: &[u64]) -> Decoding {
+        let field = &self.field;
+        let syndromes = self.syndromes(received);
+        let syndrome_weight = syndromes.iter().filter(|s| **s != 0).count();
+        let uncorrectable = Decoding { syndrome_weight, corrected: None, error_positions: Vec::new() };
+        if received.len() != self.length() {
+            return uncorrectable;
+        }
+        if syndrome_weight == 0 {
+            return Decoding { syndrome_weight, corrected: Some(received.to_vec()), error_positions: Vec::new() };
+        }
+
+        let locator = berlekamp_massey(field, &syndromes);
+        let errors = locator.len() - 1;
+        if errors > self.capacity() {
+            return uncorrectable;
+        }
+        // Λ(z) = Π (1 − Xₗ·z) vanishes at the inverse of every error point
+        let error_positions: Vec<usize> = (0..self.length())
+            .filter(|&i| evaluate(field, &locator, field.inverse(self.points[i]).unwrap()) == 0)
+            .collect();
+        if error_positions.len() != errors {
+            return uncorrectable;
+        }
+
+        // Forney: Ω = S·Λ mod z^(n−k), error value vᵢ·Yᵢ = −Xᵢ·Ω(Xᵢ⁻¹) / Λ'(Xᵢ⁻¹)
+        let mut evaluator = vec![0; syndromes.len()];
+        for (i, &s) in syndromes.iter().enumerate() {
+            for (j, &l) in locator.iter().enumerate().take(syndromes.len() - i) {
+                evaluator[i + j] = field.add(evaluator[i + j], field.mul(s, l));
+            }
+        }
+        let derivative: Vec<u64> = locator.iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, &l)| field.mul(field.reduce((i % field.characteristic() as usize) as u64), l))
+            .collect();
+        let mut corrected = received.to_vec();
+        for &i in &error_positions {
+            let x = self.points[i];
+            let z = field.inverse(x).unwrap();
+            let Some(weighted) = field.div(field.mul(x, evaluate(field, &evaluator, z)), evaluate(field, &derivative, z)) else {
+                return uncorrectable;
+            };
+            let error = field.div(field.neg(weighted), self.multipliers[i]).unwrap();
+            corrected[i] = field.sub(corrected[i], error);
+        }
+        if self.syndromes(&corrected).iter().any(|s| *s != 0) {
+            return uncorrectable;
+        }
+        Decoding { syndrome_weight, corrected: Some(corrected), error_positions }
+    }
+}
+
+/// Σ cᵢ·xⁱ by Horner's rule
+fn evaluate(field: &GaloisField, coefficients: &[u64], x: u64) -> u64 {
+    coefficients.iter().rev().fold(0, |value, &c| field.add(field.mul(value, x), c))
+}
+
+/// Shortest linear recurrence generating `syndromes`, as the connection
+/// polynomial with constant term one
+fn berlekamp_massey(field: &GaloisField, syndromes: &[u64]) -> Vec<u64> {
+    let (mut current, mut previous) = (vec![1u64], vec![1u64]);
+    let (mut length, mut shift, mut previous_discrepancy) = (0usize, 1usize, 1u64);
+    for n in 0..syndromes.len() {
+        let discrepancy = (1..=length.min(current.len() - 1))
+            .fold(syndromes[n], |d, i| field.add(d, field.mul(current[i], syndromes[n - i])));
+        if discrepancy == 0 {
+            shift += 1;
+            continue;
+        }
+        let scale = field.div(discrepancy, previous_discrepancy).unwrap();
+        let mut next = current.clone();
+        next.resize(next.len().max(previous.len() + shift), 0);
+        for (i, &b) in previous.iter().enumerate() {
+            next[i + shift] = field.sub(next[i + shift], field.mul(scale, b));
+        }
+        if 2 * length <= n {
+            previous = std::mem::replace(&mut current, next);
+            length = n + 1 - length;
+            previous_discrepancy = discrepancy;
+            shift = 1;
+        } else {
+            current = next;
+            shift += 1;
+        }
+    }
+    current.truncate(length + 1);
+    current.resize(length + 1, 0);
+    current
+}
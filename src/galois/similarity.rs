@@ -0,0 +1,244 @@
+//! # Field-Hashed Pattern Similarity
+//!
+//! Locality-sensitive signatures for finding historical windows shaped like the
+//! current one: quantized returns are hashed band by band as polynomials over
+//! GF(2³¹ − 1), and windows sharing a bucket are ranked by return distance.
+
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use super::GaloisField;
+use crate::data::ForexDataPoint;
+
+/// Field the band keys are computed in
+const HASH_PRIME: u64 = 2_147_483_647;
+
+/// Signature settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SimilarityConfig {
+    /// Bar returns in each window
+    pub window_bars: usize,
+    /// Independent bucketings of each window
+    pub bands: usize,
+    /// Window positions sampled by each band
+    pub rows_per_band: usize,
+    /// Scaled return per quantization level
+    pub quantization_step: f64,
+    /// Levels either side of zero; larger moves share the outermost level
+    pub max_level: i32,
+}
+
+impl Default for SimilarityConfig {
+    fn default() -> Self {
+        Self {
+            window_bars: 8,
+            bands: 12,
+            rows_per_band: 4,
+            quantization_step: 1.0,
+            max_level: 2,
+        }
+    }
+}
+
+/// Quantizes windows and computes their band keys
+#[derive(Debug, Clone)]
+pub struct WindowHasher {
+    config: SimilarityConfig,
+    field: GaloisField,
+    /// Window positions sampled by each band
+    positions: Vec<Vec<usize>>,
+    /// Point each band's polynomial is evaluated at
+    points: Vec<u64>,
+}
+
+impl WindowHasher {
+    pub fn new(config: SimilarityConfig) -> Result<Self> {
+        if config.window_bars < 2 || config.bands == 0 || config.rows_per_band == 0 || config.rows_per_band > config.window_bars {
+            bail!("Similarity hashing needs windows of at least 2 returns and 1..={} rows per band", config.window_bars);
+        }
+        if config.quantization_step <= 0.0 || config.max_level < 1 {
+            bail!("Similarity hashing needs a positive quantization step and at least one level either side of zero");
+        }
+        let field = GaloisField::new(HASH_PRIME)?;
+        // Walk the powers of a generator: a fixed, well-spread sequence
+        let generator = 7;
+        let mut state = generator;
+        let mut next = || {
+            state = field.mul(state, generator);
+            state
+        };
+        let positions = (0..config.bands)
+            .map(|_| {
+                let mut band: Vec<usize> = Vec::with_capacity(config.rows_per_band);
+                while band.len() < config.rows_per_band {
+                    let position = (next() % config.window_bars as u64) as usize;
+                    if !band.contains(&position) {
+                        band.push(position);
+                    }
+                }
+                band.sort_unstable();
+                band
+            })
+            .collect();
+        let points = (0..config.bands).map(|_| next()).collect();
+        Ok(Self { config, field, positions, points })
+    }
+
+    pub fn config(&self) -> &SimilarityConfig {
+        &self.config
+    }
+
+    /// Level of each scaled return, in `-max_level..=max_level`
+    pub fn quantize(&self, features: &[f64]) -> Vec<i32> {
+        features.iter()
+            .map(|x| ((x / self.config.quantization_step).round() as i32).clamp(-self.config.max_level, self.config.max_level))
+            .collect()
+    }
+
+    /// Key of each band for a window of scaled returns
+    pub fn keys(&self, features: &[f64]) -> Vec<u64> {
+        let levels = self.quantize(features);
+        self.positions.iter().zip(&self.points)
+            .map(|(positions, point)| {
+                // Horner evaluation of Σ (levelᵢ + max_level + 1)·pointⁱ
+                positions.iter().fold(0, |acc, &i| {
+                    let coefficient = levels.get(i).map(|level| (level + self.config.max_level + 1) as u64).unwrap_or(0);
+                    self.field.add(self.field.mul(acc, *point), coefficient)
+                })
+            })
+            .collect()
+    }
+}
+
+/// A historical window close to a query
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimilarWindow {
+    /// Bar the window ends at
+    pub window_end: DateTime<Utc>,
+    /// Euclidean distance between the scaled returns
+    pub distance: f64,
+}
+
+/// One indexed window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexedWindow {
+    pub window_end: DateTime<Utc>,
+    pub features: Vec<f64>,
+}
+
+/// Windows of scaled returns bucketed by band key
+#[derive(Debug, Clone)]
+pub struct SimilarityIndex {
+    hasher: WindowHasher,
+    /// Standard deviation of one-bar log returns in the history, the feature unit
+    return_scale: f64,
+    windows: Vec<IndexedWindow>,
+    buckets: HashMap<(usize, u64), Vec<usize>>,
+}
+
+impl SimilarityIndex {
+    /// Empty index whose features are returns divided by `return_scale`
+    pub fn new(config: SimilarityConfig, return_scale: f64) -> Result<Self> {
+        if !return_scale.is_finite() || return_scale <= 0.0 {
+            bail!("Similarity index needs a positive return scale, got {}", return_scale);
+        }
+        Ok(Self { hasher: WindowHasher::new(config)?, return_scale, windows: Vec::new(), buckets: HashMap::new() })
+    }
+
+    /// Index every window of `history`, scaled by its own return spread
+    pub fn fit(history: &[ForexDataPoint], config: SimilarityConfig) -> Result<Self> {
+        let returns = log_returns(history);
+        let mean = returns.iter().map(|(_, r)| r).sum::<f64>() / returns.len().max(1) as f64;
+        let return_scale = (returns.iter().map(|(_, r)| (r - mean).powi(2)).sum::<f64>() / returns.len().max(1) as f64).sqrt();
+        let mut index = Self::new(config, return_scale)?;
+        let window = index.hasher.config.window_bars;
+        for slice in returns.windows(window) {
+            let features = slice.iter().map(|(_, r)| r / return_scale).collect();
+            index.insert(slice[window - 1].0, features);
+        }
+        Ok(index)
+    }
+
+    pub fn config(&self) -> &SimilarityConfig {
+        self.hasher.config()
+    }
+
+    pub fn hasher(&self) -> &WindowHasher {
+        &self.hasher
+    }
+
+    pub fn return_scale(&self) -> f64 {
+        self.return_scale
+    }
+
+    pub fn windows(&self) -> &[IndexedWindow] {
+        &self.windows
+    }
+
+    pub fn len(&self) -> usize {
+        self.windows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.windows.is_empty()
+    }
+
+    /// Scaled returns of the window ending at the last of `bars`
+    pub fn features(&self, bars: &[ForexDataPoint]) -> Option<Vec<f64>> {
+        let window = self.hasher.config.window_bars;
+        if bars.len() <= window {
+            return None;
+        }
+        let returns = log_returns(&bars[bars.len() - window - 1..]);
+        (returns.len() == window).then(|| returns.iter().map(|(_, r)| r / self.return_scale).collect())
+    }
+
+    /// Add a window of scaled returns ending at `window_end`
+    pub fn insert(&mut self, window_end: DateTime<Utc>, features: Vec<f64>) {
+        let id = self.windows.len();
+        for (band, key) in self.hasher.keys(&features).into_iter().enumerate() {
+            self.buckets.entry((band, key)).or_default().push(id);
+        }
+        self.windows.push(IndexedWindow { window_end, features });
+    }
+
+    /// Windows sharing at least one bucket with `features`
+    pub fn candidates(&self, features: &[f64]) -> Vec<usize> {
+        let mut seen = HashSet::new();
+        self.hasher.keys(features).into_iter().enumerate()
+            .filter_map(|(band, key)| self.buckets.get(&(band, key)))
+            .flatten()
+            .filter(|id| seen.insert(**id))
+            .copied()
+            .collect()
+    }
+
+    /// Up to `k` indexed windows nearest to `features` among its candidates, nearest first
+    pub fn nearest(&self, features: &[f64], k: usize) -> Vec<SimilarWindow> {
+        rank(self.candidates(features).into_iter().map(|id| &self.windows[id]), features, k)
+    }
+}
+
+/// Up to `k` of `windows` nearest to `features`, nearest first
+pub fn rank<'a>(windows: impl Iterator<Item = &'a IndexedWindow>, features: &[f64], k: usize) -> Vec<SimilarWindow> {
+    let mut similar: Vec<SimilarWindow> = windows
+        .map(|window| SimilarWindow {
+            window_end: window.window_end,
+            distance: window.features.iter().zip(features).map(|(a, b)| (a - b).powi(2)).sum::<f64>().sqrt(),
+        })
+        .collect();
+    similar.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+    similar.truncate(k);
+    similar
+}
+
+/// Log returns, each stamped with the bar it ends at
+fn log_returns(bars: &[ForexDataPoint]) -> Vec<(DateTime<Utc>, f64)> {
+    bars.windows(2)
+        .filter(|w| w[0].close > 0.0 && w[1].close > 0.0)
+        .map(|w| (w[1].timestamp, (w[1].close / w[0].close).ln()))
+        .collect()
+}
@@ -0,0 +1,152 @@
+//! # Identifiers
+//!
+//! Typed ids for symmetries, cycles and anomalies: a kind prefix and a ULID, so
+//! they sort by creation time and cannot collide across pairs and runs. Any
+//! string loads, so ids written before the scheme still resolve.
+
+use chrono::{DateTime, TimeZone, Utc};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Mutex;
+
+const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const ULID_LENGTH: usize = 26;
+const RANDOM_BITS: u32 = 80;
+
+/// Millisecond and random part of the last ULID, for monotonic ids
+static LAST_ULID: Mutex<(u64, u128)> = Mutex::new((0, 0));
+
+/// A new ULID, greater than every ULID generated before it in this process
+pub fn new_ulid() -> String {
+    let now = Utc::now().timestamp_millis().max(0) as u64;
+    let mut last = LAST_ULID.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let (millis, random) = if now > last.0 {
+        (now, rand::thread_rng().gen::<u128>() >> (128 - RANDOM_BITS))
+    } else if last.1 + 1 < 1 << RANDOM_BITS {
+        (last.0, last.1 + 1)
+    } else {
+        // Random part exhausted within a millisecond: borrow the next one
+        (last.0 + 1, 0)
+    };
+    *last = (millis, random);
+    encode((millis as u128) << RANDOM_BITS | random)
+}
+
+fn encode(value: u128) -> String {
+    (0..ULID_LENGTH)
+        .map(|i| CROCKFORD[((value >> (5 * (ULID_LENGTH - 1 - i))) & 31) as usize] as char)
+        .collect()
+}
+
+/// Creation time of a ULID, `None` if `ulid` is not one
+pub fn ulid_timestamp(ulid: &str) -> Option<DateTime<Utc>> {
+    if ulid.len() != ULID_LENGTH {
+        return None;
+    }
+    let mut value: u128 = 0;
+    for byte in ulid.bytes() {
+        let digit = CROCKFORD.iter().position(|c| *c == byte.to_ascii_uppercase())?;
+        value = value << 5 | digit as u128;
+    }
+    Utc.timestamp_millis_opt((value >> RANDOM_BITS) as i64).single()
+}
+
+macro_rules! typed_id {
+    ($(#[$meta:meta])* $name:ident, $prefix:literal) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        #[serde(transparent)]
+        pub struct $name(String);
+
+        impl $name {
+            /// Prefix of generated ids
+            pub const PREFIX: &'static str = $prefix;
+
+            /// A fresh id
+            pub fn new() -> Self {
+                Self(format!("{}_{}", Self::PREFIX, new_ulid()))
+            }
+
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            /// When a generated id was created; `None` for ids from before the scheme
+            pub fn created_at(&self) -> Option<DateTime<Utc>> {
+                let ulid = self.0.strip_prefix(Self::PREFIX)?.strip_prefix('_')?;
+                ulid_timestamp(ulid.split('@').next().unwrap_or(ulid))
+            }
+
+            /// The same id qualified by the timeframe it was found on, e.g. `…@H4`
+            pub fn on_timeframe(&self, timeframe: &str) -> Self {
+                Self(format!("{}@{}", self.0, timeframe))
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl std::ops::Deref for $name {
+            type Target = str;
+
+            fn deref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl std::borrow::Borrow<str> for $name {
+            fn borrow(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(id: &str) -> Self {
+                Self(id.to_string())
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(id: String) -> Self {
+                Self(id)
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+    };
+}
+
+typed_id!(
+    /// Id of a [`TemporalSymmetry`](crate::symmetry::TemporalSymmetry)
+    SymmetryId, "sym"
+);
+
+typed_id!(
+    /// Id of a [`HiddenCycle`](crate::patterns::HiddenCycle)
+    CycleId, "cyc"
+);
+
+typed_id!(
+    /// Id of a [`DetectedAnomaly`](crate::anomaly::DetectedAnomaly)
+    AnomalyId, "anm"
+);
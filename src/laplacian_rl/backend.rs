@@ -0,0 +1,326 @@
+//! # Q-Value Backends
+//!
+//! Where the agent keeps its action values: a table per state id and action, or
+//! a one-hidden-layer DQN over the features encoded in state ids, whose size is
+//! fixed and which shares what it learns between similar states.
+
+use nalgebra::{DMatrix, DVector};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::{QTableSnapshot, QValue, StateActionPair, TradingAction};
+
+/// Anomaly features encoded in a state id
+pub const STATE_FEATURES: usize = 8;
+
+/// Features of state ids written before event proximity joined the state
+const LEGACY_STATE_FEATURES: usize = 7;
+
+/// Action encoding: buy, sell, hold, close, signed size
+const ACTION_FEATURES: usize = 5;
+
+/// Largest magnitude of a network input, so outlying volatility ratios cannot saturate it
+const FEATURE_CLIP: f64 = 10.0;
+
+/// Actions the neural backend maximizes over when valuing a next state
+const CANDIDATE_ACTIONS: [TradingAction; 8] = [
+    TradingAction::Hold,
+    TradingAction::ClosePosition,
+    TradingAction::Buy { size: 10 },
+    TradingAction::Buy { size: 15 },
+    TradingAction::Buy { size: 20 },
+    TradingAction::Sell { size: 10 },
+    TradingAction::Sell { size: 15 },
+    TradingAction::Sell { size: 20 },
+];
+
+/// Passes over saved Q-values when fitting a network to a tabular snapshot
+const DISTILL_EPOCHS: usize = 200;
+
+/// Value function behind [`LaplacianQLearningAgent`](super::LaplacianQLearningAgent)
+pub trait QBackend: Send + Sync {
+    /// Backend name for logs
+    fn name(&self) -> &'static str;
+
+    /// Estimated value of taking `action` in `state_id`
+    fn q_value(&self, state_id: &str, action: &TradingAction) -> f64;
+
+    /// Best estimated value in `state_id`, the bootstrap term of the Bellman target
+    fn max_q_value(&self, state_id: &str) -> f64;
+
+    /// Move the value of `action` in `state_id` towards `target`; `weight` scales the step
+    fn update(&mut self, state_id: &str, action: &TradingAction, target: f64, weight: f64);
+
+    /// Stored values (tabular) or trainable parameters (neural)
+    fn size(&self) -> usize;
+
+    /// Learned values in saveable form; the agent fills in the exploration rate
+    fn snapshot(&self) -> QTableSnapshot;
+
+    /// Replace what was learned with a snapshot
+    fn restore(&mut self, snapshot: &QTableSnapshot);
+}
+
+/// Which backend an agent learns with
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QBackendKind {
+    /// One value per state id and action
+    #[default]
+    Tabular,
+    /// Multilayer perceptron over the state features
+    Neural,
+}
+
+/// Neural backend settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NeuralQConfig {
+    pub hidden_units: usize,
+    /// Gradient step size
+    pub learning_rate: f64,
+    /// Updates between copies of the online network into the target network
+    pub target_sync_interval: usize,
+    /// Seed of the initial weights
+    pub seed: u64,
+}
+
+impl Default for NeuralQConfig {
+    fn default() -> Self {
+        Self { hidden_units: 32, learning_rate: 0.01, target_sync_interval: 100, seed: 7 }
+    }
+}
+
+/// Backend for `kind`
+pub fn create_backend(kind: QBackendKind, learning_rate: f64, neural: &NeuralQConfig) -> Box<dyn QBackend> {
+    match kind {
+        QBackendKind::Tabular => Box::new(TabularQ::new(learning_rate)),
+        QBackendKind::Neural => Box::new(NeuralQ::new(neural.clone())),
+    }
+}
+
+/// One value per state id and action
+#[derive(Debug, Clone)]
+pub struct TabularQ {
+    table: HashMap<StateActionPair, f64>,
+    learning_rate: f64,
+}
+
+impl TabularQ {
+    pub fn new(learning_rate: f64) -> Self {
+        Self { table: HashMap::new(), learning_rate }
+    }
+}
+
+impl QBackend for TabularQ {
+    fn name(&self) -> &'static str {
+        "tabular"
+    }
+
+    fn q_value(&self, state_id: &str, action: &TradingAction) -> f64 {
+        let key = StateActionPair { state_id: state_id.to_string(), action: action.clone() };
+        self.table.get(&key).copied().unwrap_or(0.0)
+    }
+
+    fn max_q_value(&self, state_id: &str) -> f64 {
+        self.table.iter()
+            .filter(|(sa, _)| sa.state_id == state_id)
+            .map(|(_, &q)| q)
+            .fold(f64::NEG_INFINITY, f64::max)
+            .max(0.0)
+    }
+
+    fn update(&mut self, state_id: &str, action: &TradingAction, target: f64, weight: f64) {
+        let current = self.q_value(state_id, action);
+        let key = StateActionPair { state_id: state_id.to_string(), action: action.clone() };
+        self.table.insert(key, current + self.learning_rate * weight * (target - current));
+    }
+
+    fn size(&self) -> usize {
+        self.table.len()
+    }
+
+    fn snapshot(&self) -> QTableSnapshot {
+        let mut entries: Vec<QValue> = self.table.iter()
+            .map(|(pair, value)| QValue { state_id: pair.state_id.clone(), action: pair.action.clone(), value: *value })
+            .collect();
+        entries.sort_by(|a, b| a.state_id.cmp(&b.state_id).then_with(|| format!("{:?}", a.action).cmp(&format!("{:?}", b.action))));
+        QTableSnapshot { entries, ..QTableSnapshot::default() }
+    }
+
+    fn restore(&mut self, snapshot: &QTableSnapshot) {
+        self.table = snapshot.entries.iter()
+            .map(|entry| (StateActionPair { state_id: entry.state_id.clone(), action: entry.action.clone() }, entry.value))
+            .collect();
+    }
+}
+
+/// Weights of a one-hidden-layer network: `output · tanh(hidden · x + hidden_bias) + output_bias`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QNetworkSnapshot {
+    pub inputs: usize,
+    pub hidden_units: usize,
+    /// Row-major `hidden_units × inputs`
+    pub hidden: Vec<f64>,
+    pub hidden_bias: Vec<f64>,
+    pub output: Vec<f64>,
+    pub output_bias: f64,
+}
+
+#[derive(Debug, Clone)]
+struct Network {
+    hidden: DMatrix<f64>,
+    hidden_bias: DVector<f64>,
+    output: DVector<f64>,
+    output_bias: f64,
+}
+
+impl Network {
+    fn random(inputs: usize, hidden_units: usize, rng: &mut StdRng) -> Self {
+        let scale = 1.0 / (inputs as f64).sqrt();
+        Self {
+            hidden: DMatrix::from_fn(hidden_units, inputs, |_, _| rng.gen_range(-scale..scale)),
+            hidden_bias: DVector::zeros(hidden_units),
+            output: DVector::from_fn(hidden_units, |_, _| rng.gen_range(-scale..scale)),
+            output_bias: 0.0,
+        }
+    }
+
+    /// Hidden activations and output for input `x`
+    fn forward(&self, x: &DVector<f64>) -> (DVector<f64>, f64) {
+        let activations = (&self.hidden * x + &self.hidden_bias).map(f64::tanh);
+        let value = self.output.dot(&activations) + self.output_bias;
+        (activations, value)
+    }
+
+    /// One gradient step on the Huber loss between the output for `x` and `target`
+    fn train(&mut self, x: &DVector<f64>, target: f64, step: f64) {
+        let (activations, value) = self.forward(x);
+        // Huber: the error's gradient is clipped to [-1, 1]
+        let error = (value - target).clamp(-1.0, 1.0);
+        let hidden_error = self.output.component_mul(&activations.map(|a| 1.0 - a * a)) * error;
+        self.output -= &activations * (step * error);
+        self.output_bias -= step * error;
+        self.hidden -= &hidden_error * x.transpose() * step;
+        self.hidden_bias -= hidden_error * step;
+    }
+
+    fn snapshot(&self) -> QNetworkSnapshot {
+        QNetworkSnapshot {
+            inputs: self.hidden.ncols(),
+            hidden_units: self.hidden.nrows(),
+            hidden: self.hidden.transpose().as_slice().to_vec(),
+            hidden_bias: self.hidden_bias.as_slice().to_vec(),
+            output: self.output.as_slice().to_vec(),
+            output_bias: self.output_bias,
+        }
+    }
+
+    fn from_snapshot(snapshot: &QNetworkSnapshot) -> Option<Self> {
+        let (inputs, units) = (snapshot.inputs, snapshot.hidden_units);
+        if snapshot.hidden.len() != inputs * units || snapshot.hidden_bias.len() != units || snapshot.output.len() != units {
+            return None;
+        }
+        Some(Self {
+            hidden: DMatrix::from_row_slice(units, inputs, &snapshot.hidden),
+            hidden_bias: DVector::from_column_slice(&snapshot.hidden_bias),
+            output: DVector::from_column_slice(&snapshot.output),
+            output_bias: snapshot.output_bias,
+        })
+    }
+}
+
+/// Multilayer perceptron Q(s, a) with a periodically synced target network
+#[derive(Debug, Clone)]
+pub struct NeuralQ {
+    config: NeuralQConfig,
+    online: Network,
+    target: Network,
+    updates: usize,
+}
+
+impl NeuralQ {
+    pub fn new(config: NeuralQConfig) -> Self {
+        let mut rng = StdRng::seed_from_u64(config.seed);
+        let online = Network::random(STATE_FEATURES + ACTION_FEATURES, config.hidden_units.max(1), &mut rng);
+        Self { config, target: online.clone(), online, updates: 0 }
+    }
+}
+
+impl QBackend for NeuralQ {
+    fn name(&self) -> &'static str {
+        "neural"
+    }
+
+    fn q_value(&self, state_id: &str, action: &TradingAction) -> f64 {
+        self.online.forward(&network_input(&state_features(state_id), action)).1
+    }
+
+    fn max_q_value(&self, state_id: &str) -> f64 {
+        let features = state_features(state_id);
+        CANDIDATE_ACTIONS.iter()
+            .map(|action| self.target.forward(&network_input(&features, action)).1)
+            .fold(f64::NEG_INFINITY, f64::max)
+    }
+
+    fn update(&mut self, state_id: &str, action: &TradingAction, target: f64, weight: f64) {
+        self.online.train(&network_input(&state_features(state_id), action), target, self.config.learning_rate * weight);
+        self.updates += 1;
+        if self.updates.is_multiple_of(self.config.target_sync_interval.max(1)) {
+            self.target = self.online.clone();
+        }
+    }
+
+    fn size(&self) -> usize {
+        let (inputs, units) = (self.online.hidden.ncols(), self.online.hidden.nrows());
+        units * inputs + units * 2 + 1
+    }
+
+    fn snapshot(&self) -> QTableSnapshot {
+        QTableSnapshot { network: Some(self.online.snapshot()), ..QTableSnapshot::default() }
+    }
+
+    /// Take the saved network, or fit the current one to saved tabular values. A
+    /// network saved for a different state layout is ignored.
+    fn restore(&mut self, snapshot: &QTableSnapshot) {
+        let network = snapshot.network.as_ref()
+            .filter(|network| network.inputs == STATE_FEATURES + ACTION_FEATURES)
+            .and_then(Network::from_snapshot);
+        if let Some(network) = network {
+            self.online = network;
+        } else {
+            for _ in 0..DISTILL_EPOCHS {
+                for entry in &snapshot.entries {
+                    self.online.train(&network_input(&state_features(&entry.state_id), &entry.action), entry.value, self.config.learning_rate);
+                }
+            }
+        }
+        self.target = self.online.clone();
+    }
+}
+
+/// Anomaly features read back from a state id, zero when the id does not encode
+/// them. Legacy ids without event proximity read as far from any release.
+pub fn state_features(state_id: &str) -> [f64; STATE_FEATURES] {
+    let mut features = [0.0; STATE_FEATURES];
+    let Some(encoded) = state_id.strip_prefix("s_") else { return features };
+    let values: Vec<f64> = encoded.split('_').filter_map(|value| value.parse().ok()).collect();
+    if values.len() == STATE_FEATURES || values.len() == LEGACY_STATE_FEATURES {
+        for (feature, value) in features.iter_mut().zip(values) {
+            *feature = value.clamp(-FEATURE_CLIP, FEATURE_CLIP);
+        }
+    }
+    features
+}
+
+fn network_input(features: &[f64; STATE_FEATURES], action: &TradingAction) -> DVector<f64> {
+    let encoded = match action {
+        TradingAction::Buy { size } => [1.0, 0.0, 0.0, 0.0, *size as f64 / 100.0],
+        TradingAction::Sell { size } => [0.0, 1.0, 0.0, 0.0, -(*size as f64) / 100.0],
+        TradingAction::Hold => [0.0, 0.0, 1.0, 0.0, 0.0],
+        TradingAction::ClosePosition => [0.0, 0.0, 0.0, 1.0, 0.0],
+    };
+    DVector::from_iterator(STATE_FEATURES + ACTION_FEATURES, features.iter().copied().chain(encoded))
+}
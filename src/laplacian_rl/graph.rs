@@ -0,0 +1,314 @@
+//! # Growing De Bruijn Graph
+//!
+//! The De Bruijn state graph grown online: observed states are quantized into
+//! symbols, nodes are words of the latest symbols, and transition probabilities
+//! are observed frequencies, up to `max_nodes` nodes.
+
+use anyhow::{bail, Result};
+use nalgebra::DVector;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+use super::{AnomalyFeatures, TradingAction};
+
+/// De Bruijn graph of observed state sequences
+#[derive(Debug, Clone)]
+pub struct DeBruijnGraph {
+    /// Nodes in the order they were first observed, so indices stay stable as the graph grows
+    nodes: Vec<GraphNode>,
+    node_index: HashMap<String, usize>,
+
+    /// Observed transitions out of each node
+    edges: HashMap<String, Vec<GraphEdge>>,
+
+    /// Symbols of the latest observations, oldest first
+    recent_symbols: VecDeque<String>,
+
+    /// Node each state was last observed at
+    state_nodes: HashMap<String, usize>,
+
+    /// Quantization levels of each symbol component
+    levels: usize,
+    sequence_length: usize,
+    max_nodes: usize,
+    observations: u64,
+}
+
+/// Graph node: a word of observed symbols
+#[derive(Debug, Clone)]
+pub struct GraphNode {
+    pub id: String,
+    /// Quantized signals of the word's newest symbol, each in [0, 1]
+    pub state_vector: DVector<f64>,
+    /// Features of the latest state observed at this node
+    pub anomaly_features: AnomalyFeatures,
+    /// Learning updates made on states at this node
+    pub visit_count: u32,
+    pub value_estimate: f64,
+}
+
+/// Observed transition between two words
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphEdge {
+    pub from_node: String,
+    pub to_node: String,
+    pub action: TradingAction,
+    /// Share of the transitions out of `from_node` that went here
+    pub transition_probability: f64,
+    pub reward_estimate: f64,
+    pub observations: u32,
+}
+
+/// Learned statistics of a node, for saving a trained agent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphNodeSnapshot {
+    pub id: String,
+    pub visit_count: u32,
+    pub value_estimate: f64,
+}
+
+/// The grown graph, for saving a trained agent
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GraphSnapshot {
+    /// In the order they were first observed
+    pub nodes: Vec<GraphNodeSnapshot>,
+    /// Sorted by source and target so saved files are stable
+    pub edges: Vec<GraphEdge>,
+    /// Node each state was last observed at, sorted by state
+    pub state_nodes: Vec<(String, String)>,
+    pub recent_symbols: Vec<String>,
+    pub observations: u64,
+}
+
+impl DeBruijnGraph {
+    /// Empty graph of words of `sequence_length` symbols with `levels` levels
+    /// per component, holding at most `max_nodes` words
+    pub fn new(levels: usize, sequence_length: usize, max_nodes: usize) -> Result<Self> {
+        if !(2..=10).contains(&levels) {
+            bail!("symbol levels must be in 2..=10, got {}", levels);
+        }
+        if sequence_length == 0 || max_nodes < 2 {
+            bail!("the graph needs words of at least one symbol and room for two nodes");
+        }
+        Ok(Self::empty(levels, sequence_length, max_nodes))
+    }
+
+    fn empty(levels: usize, sequence_length: usize, max_nodes: usize) -> Self {
+        Self {
+            nodes: Vec::new(),
+            node_index: HashMap::new(),
+            edges: HashMap::new(),
+            recent_symbols: VecDeque::with_capacity(sequence_length + 1),
+            state_nodes: HashMap::new(),
+            levels,
+            sequence_length,
+            max_nodes,
+            observations: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn nodes(&self) -> &[GraphNode] {
+        &self.nodes
+    }
+
+    /// Observed transitions out of `node_id`
+    pub fn edges_from(&self, node_id: &str) -> &[GraphEdge] {
+        self.edges.get(node_id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Observed frequency of moving from one node to another
+    pub fn transition_probability(&self, from_node: &str, to_node: &str) -> f64 {
+        self.edges_from(from_node).iter()
+            .find(|edge| edge.to_node == to_node)
+            .map_or(0.0, |edge| edge.transition_probability)
+    }
+
+    /// States observed so far
+    pub fn observations(&self) -> u64 {
+        self.observations
+    }
+
+    /// Position of a node in the graph's (and its Laplacian's) node order
+    pub fn node_index(&self, node_id: &str) -> Option<usize> {
+        self.node_index.get(node_id).copied()
+    }
+
+    /// Node a state was last observed at; a node id maps to itself
+    pub fn state_node(&self, state_id: &str) -> Option<usize> {
+        self.node_index(state_id).or_else(|| self.state_nodes.get(state_id).copied())
+    }
+
+    /// Symbol of a state id from [`super::LaplacianQLearningAgent::anomaly_to_state`]:
+    /// one digit each for how structural the anomaly is (symmetry deviation,
+    /// cycle disruption, inversion or novel pattern), how much stress the market
+    /// is under (volatility or momentum beyond normal) and detector confidence
+    pub fn symbol(&self, state_id: &str) -> Option<String> {
+        Some(self.signals(state_id)?.iter().map(|signal| self.level(*signal).to_string()).collect())
+    }
+
+    /// Record an observed state: extend the current word by its symbol, adding
+    /// the node and the transition from the previous word if they are new.
+    /// Returns the state's node, `None` while fewer than `sequence_length`
+    /// states have been observed, for ids that are not states, or when the
+    /// graph is full and the word is new.
+    pub fn observe(&mut self, state_id: &str, features: &AnomalyFeatures) -> Option<usize> {
+        let signals = self.signals(state_id)?;
+        let symbol = self.symbol(state_id)?;
+        let previous = self.current_node();
+        self.recent_symbols.push_back(symbol);
+        if self.recent_symbols.len() > self.sequence_length {
+            self.recent_symbols.pop_front();
+        }
+        self.observations += 1;
+        if self.recent_symbols.len() < self.sequence_length {
+            return None;
+        }
+
+        let word = self.current_word();
+        let index = match self.node_index(&word) {
+            Some(index) => index,
+            None if self.nodes.len() < self.max_nodes => {
+                self.node_index.insert(word.clone(), self.nodes.len());
+                self.nodes.push(GraphNode {
+                    id: word,
+                    state_vector: DVector::zeros(signals.len()),
+                    anomaly_features: AnomalyFeatures::default(),
+                    visit_count: 0,
+                    value_estimate: 0.0,
+                });
+                self.nodes.len() - 1
+            }
+            None => return None,
+        };
+        let levels: Vec<f64> = signals.iter().map(|signal| self.level(*signal) as f64 / (self.levels - 1) as f64).collect();
+        let node = &mut self.nodes[index];
+        node.state_vector = DVector::from_vec(levels);
+        node.anomaly_features = features.clone();
+        if let Some(previous) = previous {
+            self.record_transition(previous, index);
+        }
+        self.state_nodes.insert(state_id.to_string(), index);
+        Some(index)
+    }
+
+    /// Count a visit to a node and move its value estimate toward `target`
+    pub(super) fn record_visit(&mut self, index: usize, target: f64, learning_rate: f64) {
+        if let Some(node) = self.nodes.get_mut(index) {
+            node.value_estimate = if node.visit_count == 0 {
+                target
+            } else {
+                node.value_estimate + learning_rate * (target - node.value_estimate)
+            };
+            node.visit_count += 1;
+        }
+    }
+
+    pub fn snapshot(&self) -> GraphSnapshot {
+        let mut edges: Vec<GraphEdge> = self.edges.values().flatten().cloned().collect();
+        edges.sort_by(|a, b| (&a.from_node, &a.to_node).cmp(&(&b.from_node, &b.to_node)));
+        let mut state_nodes: Vec<(String, String)> = self.state_nodes.iter()
+            .map(|(state, index)| (state.clone(), self.nodes[*index].id.clone()))
+            .collect();
+        state_nodes.sort();
+        GraphSnapshot {
+            nodes: self.nodes.iter()
+                .map(|node| GraphNodeSnapshot { id: node.id.clone(), visit_count: node.visit_count, value_estimate: node.value_estimate })
+                .collect(),
+            edges,
+            state_nodes,
+            recent_symbols: self.recent_symbols.iter().cloned().collect(),
+            observations: self.observations,
+        }
+    }
+
+    /// Replace the graph with a saved one; `None` empties it
+    pub fn restore(&mut self, snapshot: Option<&GraphSnapshot>) {
+        let mut graph = Self::empty(self.levels, self.sequence_length, self.max_nodes);
+        if let Some(snapshot) = snapshot {
+            for saved in snapshot.nodes.iter().take(self.max_nodes) {
+                graph.node_index.insert(saved.id.clone(), graph.nodes.len());
+                graph.nodes.push(GraphNode {
+                    id: saved.id.clone(),
+                    state_vector: DVector::zeros(3),
+                    anomaly_features: AnomalyFeatures::default(),
+                    visit_count: saved.visit_count,
+                    value_estimate: saved.value_estimate,
+                });
+            }
+            for edge in &snapshot.edges {
+                if graph.node_index.contains_key(&edge.from_node) && graph.node_index.contains_key(&edge.to_node) {
+                    graph.edges.entry(edge.from_node.clone()).or_default().push(edge.clone());
+                }
+            }
+            for (state, node) in &snapshot.state_nodes {
+                if let Some(index) = graph.node_index(node) {
+                    graph.state_nodes.insert(state.clone(), index);
+                }
+            }
+            graph.recent_symbols = snapshot.recent_symbols.iter().rev().take(self.sequence_length).rev().cloned().collect();
+            graph.observations = snapshot.observations;
+        }
+        *self = graph;
+    }
+
+    /// The state's components in [0, 1], if `state_id` is a state id
+    fn signals(&self, state_id: &str) -> Option<[f64; 3]> {
+        let values: Vec<f64> = state_id.strip_prefix("s_")?
+            .split('_')
+            .map(|value| value.parse().ok())
+            .collect::<Option<_>>()?;
+        // Event proximity, absent from legacy ids, does not change the symbol
+        let ([symmetry, cycle, volatility, inversion, novel, momentum, confidence]
+            | [symmetry, cycle, volatility, inversion, novel, momentum, confidence, _]) = values[..] else { return None };
+        Some([
+            symmetry.max(cycle / std::f64::consts::PI).max(inversion).max(novel),
+            ((volatility - 1.0).max(momentum.abs() - 1.0).max(0.0)) / 2.0,
+            confidence,
+        ].map(|signal| signal.clamp(0.0, 1.0)))
+    }
+
+    fn level(&self, signal: f64) -> usize {
+        ((signal * self.levels as f64) as usize).min(self.levels - 1)
+    }
+
+    fn current_word(&self) -> String {
+        format!("node_{}", self.recent_symbols.iter().cloned().collect::<Vec<_>>().join("-"))
+    }
+
+    /// Node of the word the latest observations spell, if it is in the graph
+    fn current_node(&self) -> Option<usize> {
+        if self.recent_symbols.len() < self.sequence_length {
+            return None;
+        }
+        self.node_index(&self.current_word())
+    }
+
+    /// Count a transition and renormalize the probabilities out of its source
+    fn record_transition(&mut self, from: usize, to: usize) {
+        let (from_node, to_node) = (self.nodes[from].id.clone(), self.nodes[to].id.clone());
+        let edges = self.edges.entry(from_node.clone()).or_default();
+        match edges.iter_mut().find(|edge| edge.to_node == to_node) {
+            Some(edge) => edge.observations += 1,
+            None => edges.push(GraphEdge {
+                from_node,
+                to_node,
+                action: TradingAction::Hold, // Default action
+                transition_probability: 0.0,
+                reward_estimate: 0.0,
+                observations: 1,
+            }),
+        }
+        let total: u32 = edges.iter().map(|edge| edge.observations).sum();
+        for edge in edges.iter_mut() {
+            edge.transition_probability = edge.observations as f64 / total as f64;
+        }
+    }
+}
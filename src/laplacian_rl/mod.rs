@@ -1,34 +1,63 @@
 //! # Laplacian Reinforcement Learning for Anomaly-Based Trading
-//! 
-//! De Bruijn graph-based Q-learning with PME and Laplacian attention for trading decisions
+//!
+//! De Bruijn graph-based Q-learning with Laplacian attention for trading
+//! decisions: the grown state graph ([`graph`]) is embedded by its Laplacian
+//! ([`spectral`]), and distances there drive attention and reward shaping.
 
-use anyhow::Result;
-use std::collections::{HashMap, VecDeque};
+pub mod backend;
+pub mod graph;
+pub mod replay;
+pub mod spectral;
+
+use anyhow::{Context, Result};
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+use std::sync::Mutex;
 use nalgebra::{DVector, DMatrix};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
 
 use crate::anomaly::{DetectedAnomaly, AnomalyType, AnomalySeverity};
 use crate::data::ForexDataPoint;
 
+pub use backend::{NeuralQConfig, QBackend, QBackendKind, QNetworkSnapshot};
+pub use graph::{DeBruijnGraph, GraphEdge, GraphNode, GraphSnapshot};
+pub use replay::{PrioritizedReplay, ReplayEntry, ReplaySnapshot};
+pub use spectral::SpectralEmbedding;
+
 /// De Bruijn graph-based Q-learning agent for anomaly trading
 pub struct LaplacianQLearningAgent {
-    /// De Bruijn graph structure for state representation
+    /// De Bruijn graph of the observed state sequences
     debruijn_graph: DeBruijnGraph,
     
-    /// Q-value table using PME approximation
-    q_table: HashMap<StateActionPair, f64>,
+    /// Q-values, tabular or approximated (see [`QBackendKind`])
+    q_backend: Box<dyn QBackend>,
     
-    /// Laplacian matrix for attention mechanism
+    /// Laplacian of the De Bruijn graph
     laplacian_matrix: DMatrix<f64>,
     
+    /// Node coordinates from the Laplacian's eigenvectors, for attention and
+    /// shaping; `None` until the graph has an edge
+    spectral_embedding: Option<SpectralEmbedding>,
+    
+    /// Graph observations when the Laplacian and embedding were last computed
+    embedded_at: u64,
+    
     /// Agent configuration
     config: LaplacianQLearningConfig,
     
-    /// Experience replay buffer
-    experience_buffer: VecDeque<Experience>,
+    /// Experience replay buffer, sampled by TD error
+    experience_buffer: PrioritizedReplay,
     
     /// Performance metrics
     performance_metrics: PerformanceMetrics,
+    
+    /// Source of exploration and batch sampling; reseed for reproducible decisions
+    rng: Mutex<StdRng>,
+    
+    /// Exploration rate before any decay
+    initial_exploration_rate: f64,
 }
 
 /// Configuration for Laplacian Q-learning
@@ -60,40 +89,87 @@ pub struct LaplacianQLearningConfig {
     
     /// Laplacian attention weight
     pub attention_weight: f64,
-}
-
-/// De Bruijn graph for efficient state representation
-#[derive(Debug, Clone)]
-pub struct DeBruijnGraph {
-    /// Nodes represent discretized market states
-    nodes: HashMap<String, GraphNode>,
     
-    /// Edges represent state transitions
-    edges: HashMap<String, Vec<GraphEdge>>,
+    /// Value function the agent learns
+    #[serde(default)]
+    pub backend: QBackendKind,
+    
+    /// Network settings when `backend` is neural
+    #[serde(default)]
+    pub neural: NeuralQConfig,
     
-    /// Graph parameters
-    alphabet_size: usize,
-    sequence_length: usize,
+    /// Laplacian eigenvectors states are embedded with
+    #[serde(default = "default_spectral_dimensions")]
+    pub spectral_dimensions: usize,
+    
+    /// Embedding distance at which attention and shaping fall to about 60%;
+    /// the two farthest states are at distance 1
+    #[serde(default = "default_spectral_bandwidth")]
+    pub spectral_bandwidth: f64,
+    
+    /// Scale of the shaping potential; 0 turns reward shaping off
+    #[serde(default = "default_shaping_weight")]
+    pub shaping_weight: f64,
+    
+    /// Levels each component of a state symbol is quantized into
+    #[serde(default = "default_symbol_levels")]
+    pub symbol_levels: usize,
+    
+    /// Symbols in a graph node's word
+    #[serde(default = "default_sequence_length")]
+    pub sequence_length: usize,
+    
+    /// Most nodes the graph grows to; new words beyond it are not added
+    #[serde(default = "default_max_graph_nodes")]
+    pub max_graph_nodes: usize,
+    
+    /// Observations between recomputing the embedding of a changed graph
+    #[serde(default = "default_spectral_refresh")]
+    pub spectral_refresh: u64,
+    
+    /// How strongly replay favours experiences with large TD errors; 0 samples uniformly
+    #[serde(default = "default_priority_exponent")]
+    pub priority_exponent: f64,
+    
+    /// How fully updates from prioritized samples are importance-weighted; 1 undoes the bias entirely
+    #[serde(default = "default_importance_exponent")]
+    pub importance_exponent: f64,
 }
 
-/// Graph node representing a market state
-#[derive(Debug, Clone)]
-pub struct GraphNode {
-    pub id: String,
-    pub state_vector: DVector<f64>,
-    pub anomaly_features: AnomalyFeatures,
-    pub visit_count: u32,
-    pub value_estimate: f64,
+fn default_spectral_dimensions() -> usize {
+    4
 }
 
-/// Graph edge representing state transition
-#[derive(Debug, Clone, Serialize)]
-pub struct GraphEdge {
-    pub from_node: String,
-    pub to_node: String,
-    pub action: TradingAction,
-    pub transition_probability: f64,
-    pub reward_estimate: f64,
+fn default_spectral_bandwidth() -> f64 {
+    0.25
+}
+
+fn default_shaping_weight() -> f64 {
+    0.1
+}
+
+fn default_symbol_levels() -> usize {
+    4
+}
+
+fn default_sequence_length() -> usize {
+    2
+}
+
+fn default_max_graph_nodes() -> usize {
+    256
+}
+
+fn default_spectral_refresh() -> u64 {
+    25
+}
+
+fn default_priority_exponent() -> f64 {
+    0.6
+}
+
+fn default_importance_exponent() -> f64 {
+    0.4
 }
 
 /// Anomaly features for state representation
@@ -104,12 +180,33 @@ pub struct AnomalyFeatures {
     pub volatility_spike: f64,
     pub pattern_inversion: f64,
     pub novel_pattern_strength: f64,
+    /// Signed N-bar return relative to its shock threshold
+    pub momentum_shock: f64,
     pub anomaly_confidence: f64,
+    /// Closeness to a scheduled economic release, 0 to 1
+    pub event_proximity: f64,
     pub market_context_vector: DVector<f64>,
 }
 
+impl Default for AnomalyFeatures {
+    /// Features of a market without anomalies
+    fn default() -> Self {
+        Self {
+            symmetry_deviation: 0.0,
+            cycle_disruption: 0.0,
+            volatility_spike: 1.0,
+            pattern_inversion: 0.0,
+            novel_pattern_strength: 0.0,
+            momentum_shock: 0.0,
+            anomaly_confidence: 0.0,
+            event_proximity: 0.0,
+            market_context_vector: DVector::zeros(3),
+        }
+    }
+}
+
 /// Trading actions
-#[derive(Debug, Clone, Serialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum TradingAction {
     Buy { size: u32 }, // Use integer for size to enable Hash/Eq
     Sell { size: u32 },
@@ -143,8 +240,56 @@ pub struct StateActionPair {
     pub action: TradingAction,
 }
 
+/// One learned Q-value
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QValue {
+    pub state_id: String,
+    pub action: TradingAction,
+    pub value: f64,
+}
+
+/// Learned Q-values and the decayed exploration rate, for saving a trained agent
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct QTableSnapshot {
+    pub exploration_rate: f64,
+    /// Sorted by state so saved files are stable
+    pub entries: Vec<QValue>,
+    /// Network weights of an agent with the neural backend
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network: Option<QNetworkSnapshot>,
+    /// De Bruijn graph the agent grew from the states it observed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub graph: Option<GraphSnapshot>,
+    /// Replay buffer with its priorities, so training resumes where it stopped
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replay: Option<ReplaySnapshot>,
+}
+
+impl QTableSnapshot {
+    /// Write the snapshot to a JSON checkpoint file, replacing any earlier one
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        let partial = path.with_extension("partial");
+        serde_json::to_writer(BufWriter::new(std::fs::File::create(&partial)?), self)?;
+        std::fs::rename(&partial, path)?;
+        Ok(())
+    }
+    
+    /// Checkpoint written by [`QTableSnapshot::save`], `None` when there is none yet
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let snapshot = serde_json::from_reader(BufReader::new(std::fs::File::open(path)?))
+            .with_context(|| format!("cannot read agent checkpoint {}", path.display()))?;
+        Ok(Some(snapshot))
+    }
+}
+
 /// Experience for replay buffer
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Experience {
     pub state: String,
     pub action: TradingAction,
@@ -178,6 +323,17 @@ impl Default for LaplacianQLearningConfig {
             batch_size: 32,
             pme_grid_size: 64,
             attention_weight: 0.3,
+            backend: QBackendKind::Tabular,
+            neural: NeuralQConfig::default(),
+            spectral_dimensions: default_spectral_dimensions(),
+            spectral_bandwidth: default_spectral_bandwidth(),
+            shaping_weight: default_shaping_weight(),
+            symbol_levels: default_symbol_levels(),
+            sequence_length: default_sequence_length(),
+            max_graph_nodes: default_max_graph_nodes(),
+            spectral_refresh: default_spectral_refresh(),
+            priority_exponent: default_priority_exponent(),
+            importance_exponent: default_importance_exponent(),
         }
     }
 }
@@ -185,38 +341,122 @@ impl Default for LaplacianQLearningConfig {
 impl LaplacianQLearningAgent {
     /// Create new Laplacian Q-learning agent
     pub fn new(config: LaplacianQLearningConfig) -> Result<Self> {
-        let debruijn_graph = DeBruijnGraph::new(4, 3)?; // 4-symbol alphabet, length 3
-        let laplacian_matrix = Self::compute_graph_laplacian(&debruijn_graph)?;
+        if config.spectral_dimensions == 0 {
+            return Err(anyhow::anyhow!("spectral_dimensions must be at least 1"));
+        }
+        let debruijn_graph = DeBruijnGraph::new(config.symbol_levels, config.sequence_length, config.max_graph_nodes)?;
         
         Ok(Self {
             debruijn_graph,
-            q_table: HashMap::new(),
-            laplacian_matrix,
+            q_backend: backend::create_backend(config.backend, config.learning_rate, &config.neural),
+            laplacian_matrix: DMatrix::zeros(0, 0),
+            spectral_embedding: None,
+            embedded_at: 0,
             config: config.clone(),
-            experience_buffer: VecDeque::with_capacity(config.buffer_size),
+            experience_buffer: PrioritizedReplay::new(config.buffer_size),
             performance_metrics: PerformanceMetrics::default(),
+            rng: Mutex::new(StdRng::from_entropy()),
+            initial_exploration_rate: config.exploration_rate,
         })
     }
     
+    /// Current exploration rate (epsilon)
+    pub fn exploration_rate(&self) -> f64 {
+        self.config.exploration_rate
+    }
+    
+    /// Set the exploration rate, e.g. to the one a recorded decision was made with
+    pub fn set_exploration_rate(&mut self, exploration_rate: f64) {
+        self.config.exploration_rate = exploration_rate;
+    }
+    
+    /// Restore the exploration rate the agent started with, undoing its decay
+    pub fn reset_exploration(&mut self) {
+        self.config.exploration_rate = self.initial_exploration_rate;
+    }
+    
+    /// Value function the agent learns with
+    pub fn q_backend(&self) -> &dyn QBackend {
+        self.q_backend.as_ref()
+    }
+    
+    /// Estimated value of `action` in `state_id`
+    pub fn q_value(&self, state_id: &str, action: &TradingAction) -> f64 {
+        self.q_backend.q_value(state_id, action)
+    }
+    
+    /// Q-values learned so far and the grown graph, with the current exploration rate
+    pub fn q_table_snapshot(&self) -> QTableSnapshot {
+        QTableSnapshot {
+            exploration_rate: self.config.exploration_rate,
+            graph: (!self.debruijn_graph.is_empty()).then(|| self.debruijn_graph.snapshot()),
+            replay: (!self.experience_buffer.is_empty()).then(|| self.experience_buffer.snapshot()),
+            ..self.q_backend.snapshot()
+        }
+    }
+    
+    /// Replace the Q-values, graph, replay buffer and exploration rate with
+    /// saved ones; a neural agent given only tabular values fits its network to
+    /// them, and a file saved without a graph or buffer starts an empty one
+    pub fn restore_q_table(&mut self, snapshot: QTableSnapshot) {
+        self.q_backend.restore(&snapshot);
+        self.config.exploration_rate = snapshot.exploration_rate;
+        self.debruijn_graph.restore(snapshot.graph.as_ref());
+        self.experience_buffer.restore(snapshot.replay.as_ref());
+        self.refresh_spectral_embedding();
+    }
+    
+    /// De Bruijn graph of the states observed so far
+    pub fn graph(&self) -> &DeBruijnGraph {
+        &self.debruijn_graph
+    }
+    
+    /// Laplacian of the graph as last embedded
+    pub fn laplacian(&self) -> &DMatrix<f64> {
+        &self.laplacian_matrix
+    }
+    
+    /// Embedding of the graph nodes as of the last refresh; nodes added since
+    /// are not in it yet
+    pub fn spectral_embedding(&self) -> Option<&SpectralEmbedding> {
+        self.spectral_embedding.as_ref()
+    }
+    
+    /// Recompute the Laplacian and embedding of the graph as it is now
+    pub fn refresh_spectral_embedding(&mut self) {
+        self.laplacian_matrix = Self::compute_graph_laplacian(&self.debruijn_graph);
+        // A graph without edges has no structure to embed
+        self.spectral_embedding = SpectralEmbedding::new(&self.laplacian_matrix, self.config.spectral_dimensions).ok();
+        self.embedded_at = self.debruijn_graph.observations();
+    }
+    
+    /// Index of the graph node a state was last observed at; node ids map to themselves
+    pub fn state_node(&self, state_id: &str) -> Option<usize> {
+        self.debruijn_graph.state_node(state_id)
+    }
+    
+    /// Restart the random stream from `seed`, so the same inputs yield the same actions
+    pub fn reseed(&mut self, seed: u64) {
+        self.rng = Mutex::new(StdRng::seed_from_u64(seed));
+    }
+    
+    /// Uniform draw in [0, 1)
+    fn random(&self) -> f64 {
+        self.rng.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).gen()
+    }
+    
     /// Compute graph Laplacian for attention mechanism
-    fn compute_graph_laplacian(graph: &DeBruijnGraph) -> Result<DMatrix<f64>> {
-        let n = graph.nodes.len();
+    fn compute_graph_laplacian(graph: &DeBruijnGraph) -> DMatrix<f64> {
+        let n = graph.len();
         let mut adjacency = DMatrix::zeros(n, n);
         let mut degree = DVector::zeros(n);
         
-        // Build adjacency matrix
-        let node_indices: HashMap<String, usize> = graph.nodes.keys()
-            .enumerate()
-            .map(|(i, k)| (k.clone(), i))
-            .collect();
-        
-        for (from_node, edges) in &graph.edges {
-            if let Some(&from_idx) = node_indices.get(from_node) {
-                for edge in edges {
-                    if let Some(&to_idx) = node_indices.get(&edge.to_node) {
-                        adjacency[(from_idx, to_idx)] = edge.transition_probability;
-                        degree[from_idx] += edge.transition_probability;
-                    }
+        // Build adjacency matrix from the observed transition probabilities
+        for (from_idx, node) in graph.nodes().iter().enumerate() {
+            for edge in graph.edges_from(&node.id) {
+                if let Some(to_idx) = graph.node_index(&edge.to_node) {
+                    adjacency[(from_idx, to_idx)] = edge.transition_probability;
+                    degree[from_idx] += edge.transition_probability;
                 }
             }
         }
@@ -232,11 +472,11 @@ impl LaplacianQLearningAgent {
             }
         }
         
-        Ok(laplacian)
+        laplacian
     }
     
-    /// Convert anomaly to state representation
-    pub fn anomaly_to_state(&self, anomaly: &DetectedAnomaly, market_data: &ForexDataPoint) -> Result<String> {
+    /// Convert anomaly to state representation, adding it to the graph
+    pub fn anomaly_to_state(&mut self, anomaly: &DetectedAnomaly, market_data: &ForexDataPoint) -> Result<String> {
         let anomaly_features = AnomalyFeatures {
             symmetry_deviation: match &anomaly.anomaly_type {
                 AnomalyType::SymmetryBreakdown { expected_strength, actual_strength, .. } => {
@@ -264,7 +504,14 @@ impl LaplacianQLearningAgent {
                 AnomalyType::NovelPattern { emergence_confidence, .. } => *emergence_confidence,
                 _ => 0.0,
             },
+            momentum_shock: match &anomaly.anomaly_type {
+                AnomalyType::MomentumShock { cumulative_return, threshold_return, .. } if *threshold_return > 0.0 => {
+                    cumulative_return / threshold_return
+                }
+                _ => 0.0,
+            },
             anomaly_confidence: anomaly.confidence,
+            event_proximity: anomaly.market_context.event_proximity,
             market_context_vector: DVector::from_vec(vec![
                 market_data.close,
                 market_data.high - market_data.low, // Range
@@ -274,19 +521,26 @@ impl LaplacianQLearningAgent {
         
         // Discretize features to create state ID
         let state_id = format!(
-            "s_{:.2}_{:.2}_{:.2}_{:.2}_{:.2}_{:.2}",
+            "s_{:.2}_{:.2}_{:.2}_{:.2}_{:.2}_{:.2}_{:.2}_{:.2}",
             (anomaly_features.symmetry_deviation * 100.0).round() / 100.0,
             (anomaly_features.cycle_disruption * 100.0).round() / 100.0,
             (anomaly_features.volatility_spike * 100.0).round() / 100.0,
             (anomaly_features.pattern_inversion * 100.0).round() / 100.0,
             (anomaly_features.novel_pattern_strength * 100.0).round() / 100.0,
+            (anomaly_features.momentum_shock * 100.0).round() / 100.0,
             (anomaly_features.anomaly_confidence * 100.0).round() / 100.0,
+            (anomaly_features.event_proximity * 100.0).round() / 100.0,
         );
         
-        // Add node to graph if not exists
-        if !self.debruijn_graph.nodes.contains_key(&state_id) {
-            // This would require mutable access - in practice, we'd pre-build the graph
-            // or use a different approach for dynamic state space
+        // Grow the graph by the observed state and re-embed it once it has
+        // changed enough, or as soon as it can be embedded at all
+        self.debruijn_graph.observe(&state_id, &anomaly_features);
+        let stale = match self.spectral_embedding {
+            None => self.debruijn_graph.len() >= 2,
+            Some(_) => self.debruijn_graph.observations() - self.embedded_at >= self.config.spectral_refresh.max(1),
+        };
+        if stale {
+            self.refresh_spectral_embedding();
         }
         
         Ok(state_id)
@@ -295,7 +549,7 @@ impl LaplacianQLearningAgent {
     /// Choose action using epsilon-greedy with Laplacian attention
     pub fn choose_action(&self, state_id: &str, anomaly: &DetectedAnomaly) -> Result<TradingAction> {
         // Epsilon-greedy exploration
-        if rand::random::<f64>() < self.config.exploration_rate {
+        if self.random() < self.config.exploration_rate {
             return Ok(self.random_action(anomaly));
         }
         
@@ -305,15 +559,10 @@ impl LaplacianQLearningAgent {
         // Apply Laplacian attention to weight Q-values
         let mut best_action = possible_actions[0].clone();
         let mut best_q_value = f64::NEG_INFINITY;
+        let attention_weight = self.laplacian_attention(state_id);
         
         for action in possible_actions {
-            let state_action = StateActionPair {
-                state_id: state_id.to_string(),
-                action: action.clone(),
-            };
-            
-            let base_q_value = self.q_table.get(&state_action).unwrap_or(&0.0);
-            let attention_weight = self.compute_laplacian_attention(state_id)?;
+            let base_q_value = self.q_backend.q_value(state_id, &action);
             let weighted_q_value = base_q_value * (1.0 + self.config.attention_weight * attention_weight);
             
             if weighted_q_value > best_q_value {
@@ -325,21 +574,39 @@ impl LaplacianQLearningAgent {
         Ok(best_action)
     }
     
-    /// Compute Laplacian attention weight for state
-    fn compute_laplacian_attention(&self, state_id: &str) -> Result<f64> {
-        // Find state index in graph
-        let state_index = self.debruijn_graph.nodes.keys()
-            .position(|k| k == state_id)
-            .unwrap_or(0);
-        
-        // Use Laplacian eigenvector for attention (simplified)
-        let attention_weight = if state_index < self.laplacian_matrix.nrows() {
-            self.laplacian_matrix[(state_index, state_index)].abs()
-        } else {
-            1.0
-        };
-        
-        Ok(attention_weight)
+    /// Laplacian attention of a state: the share of the agent's experience that
+    /// lies near it in the spectral embedding, weighted by a Gaussian of the
+    /// embedding distance. 1 when everything was learned on the state's node,
+    /// 0 for states off the graph or before any learning.
+    pub fn laplacian_attention(&self, state_id: &str) -> f64 {
+        self.spectral_average(state_id, |_| 1.0)
+    }
+    
+    /// Shaping potential of a state: the values learned on each node, weighted
+    /// like attention by the node's share of experience and a Gaussian of its
+    /// embedding distance, and scaled by `shaping_weight`. It fades to 0 far
+    /// from anything learned.
+    pub fn shaping_potential(&self, state_id: &str) -> f64 {
+        self.config.shaping_weight * self.spectral_average(state_id, |node| node.value_estimate)
+    }
+    
+    /// `value` of the graph nodes, weighted by their share of the learning
+    /// visits and their embedding similarity to the state's node; 0 for states
+    /// off the graph or not embedded yet
+    fn spectral_average(&self, state_id: &str, value: impl Fn(&GraphNode) -> f64) -> f64 {
+        let (Some(node), Some(embedding)) = (self.state_node(state_id), &self.spectral_embedding) else { return 0.0 };
+        if node >= embedding.nodes() {
+            return 0.0;
+        }
+        let (mut weighted, mut total) = (0.0, 0.0);
+        for (index, graph_node) in self.debruijn_graph.nodes().iter().enumerate() {
+            let visits = graph_node.visit_count as f64;
+            if index < embedding.nodes() {
+                weighted += visits * embedding.kernel(node, index, self.config.spectral_bandwidth) * value(graph_node);
+            }
+            total += visits;
+        }
+        if total > 0.0 { weighted / total } else { 0.0 }
     }
     
     /// Get possible actions for state and anomaly
@@ -370,6 +637,22 @@ impl LaplacianQLearningAgent {
                 actions.push(TradingAction::Buy { size: 15 });
                 actions.push(TradingAction::Sell { size: 15 });
             }
+            AnomalyType::LiquidityGap { .. } => {
+                // A thin market gives unreliable fills: no new entries, hold what is open
+            }
+            AnomalyType::MomentumShock { cumulative_return, .. } => {
+                // Ride the move, step aside, or fade it once it looks exhausted
+                let (follow, fade) = if *cumulative_return > 0.0 {
+                    (TradingAction::Buy { size: 10 }, TradingAction::Sell { size: 10 })
+                } else {
+                    (TradingAction::Sell { size: 10 }, TradingAction::Buy { size: 10 })
+                };
+                actions.push(follow);
+                actions.push(TradingAction::ClosePosition);
+                if matches!(anomaly.severity, AnomalySeverity::Critical) {
+                    actions.push(fade);
+                }
+            }
             _ => {
                 // Default actions for other anomaly types
                 actions.push(TradingAction::Buy { size: 10 });
@@ -383,7 +666,7 @@ impl LaplacianQLearningAgent {
     /// Generate random action
     fn random_action(&self, anomaly: &DetectedAnomaly) -> TradingAction {
         let actions = self.get_possible_actions("", anomaly);
-        let index = (rand::random::<f64>() * actions.len() as f64) as usize;
+        let index = (self.random() * actions.len() as f64) as usize;
         actions[index].clone()
     }
     
@@ -396,36 +679,50 @@ impl LaplacianQLearningAgent {
         next_state: &str,
         done: bool,
     ) -> Result<()> {
-        let state_action = StateActionPair {
-            state_id: state.to_string(),
-            action: action.clone(),
-        };
-        
-        // Get current Q-value
-        let current_q = self.q_table.get(&state_action).unwrap_or(&0.0);
-        
+        self.learn(state, action, reward, next_state, done, 1.0)?;
+        Ok(())
+    }
+    
+    /// One Q-learning step with its size scaled by `weight`; returns the TD error
+    fn learn(
+        &mut self,
+        state: &str,
+        action: TradingAction,
+        reward: f64,
+        next_state: &str,
+        done: bool,
+        weight: f64,
+    ) -> Result<f64> {
         // Calculate target Q-value
         let next_q_max = if done {
             0.0
         } else {
-            self.get_max_q_value(next_state)
+            self.q_backend.max_q_value(next_state)
         };
         
         // Apply PME approximation (simplified)
         let pme_correction = self.compute_pme_correction(state, &action)?;
         
         // Apply Laplacian attention
-        let attention_weight = self.compute_laplacian_attention(state)?;
+        let attention_weight = self.laplacian_attention(state);
         
-        // Bellman equation with PME and attention
-        let target_q = reward + self.config.discount_factor * next_q_max + pme_correction;
+        // Potential-based shaping F = γΦ(s') - Φ(s), with Φ = 0 after the episode ends
+        let next_potential = if done { 0.0 } else { self.shaping_potential(next_state) };
+        let shaping = self.config.discount_factor * next_potential - self.shaping_potential(state);
+        
+        // Bellman equation with PME, shaping and attention
+        let unshaped_target = reward + self.config.discount_factor * next_q_max;
+        let target_q = unshaped_target + shaping + pme_correction;
         let attention_factor = 1.0 + self.config.attention_weight * attention_weight;
-        let new_q = current_q + self.config.learning_rate * attention_factor * (target_q - current_q);
+        let td_error = target_q - self.q_backend.q_value(state, &action);
+        self.q_backend.update(state, &action, target_q, attention_factor * weight);
         
-        // Update Q-table
-        self.q_table.insert(state_action, new_q);
+        // The potential follows the unshaped values so shaping does not feed on itself
+        if let Some(node) = self.debruijn_graph.state_node(state) {
+            self.debruijn_graph.record_visit(node, unshaped_target, self.config.learning_rate);
+        }
         
-        Ok(())
+        Ok(td_error)
     }
     
     /// Compute PME correction for continuous state approximation
@@ -444,50 +741,38 @@ impl LaplacianQLearningAgent {
         Ok(real_space_contribution + reciprocal_space_contribution)
     }
     
-    /// Get maximum Q-value for state
-    fn get_max_q_value(&self, state: &str) -> f64 {
-        self.q_table.iter()
-            .filter(|(sa, _)| sa.state_id == state)
-            .map(|(_, &q)| q)
-            .fold(f64::NEG_INFINITY, f64::max)
-            .max(0.0)
-    }
-    
     /// Add experience to replay buffer
     pub fn add_experience(&mut self, experience: Experience) {
-        if self.experience_buffer.len() >= self.config.buffer_size {
-            self.experience_buffer.pop_front();
-        }
-        self.experience_buffer.push_back(experience);
+        self.experience_buffer.push(experience);
     }
     
-    /// Train on batch of experiences
+    /// Experiences waiting to be replayed, with their priorities
+    pub fn replay_buffer(&self) -> &PrioritizedReplay {
+        &self.experience_buffer
+    }
+    
+    /// Train on a batch of experiences drawn by priority, re-prioritizing each
+    /// by the TD error it was learned with
     pub fn train_batch(&mut self) -> Result<()> {
         if self.experience_buffer.len() < self.config.batch_size {
             return Ok(());
         }
         
-        // Sample random batch
-        let batch_size = self.config.batch_size.min(self.experience_buffer.len());
-        let mut batch_indices = Vec::new();
-        for _ in 0..batch_size {
-            let idx = (rand::random::<f64>() * self.experience_buffer.len() as f64) as usize;
-            batch_indices.push(idx);
-        }
-        
-        // Train on batch - collect experiences first to avoid borrow issues
-        let experiences: Vec<_> = batch_indices.iter()
-            .filter_map(|&idx| self.experience_buffer.get(idx).cloned())
-            .collect();
-
-        for experience in experiences {
-            self.update_q_value(
+        let batch = {
+            let mut rng = self.rng.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            self.experience_buffer.sample(self.config.batch_size, self.config.priority_exponent, self.config.importance_exponent, &mut *rng)
+        };
+        for (index, weight) in batch {
+            let Some(experience) = self.experience_buffer.get(index).cloned() else { continue };
+            let td_error = self.learn(
                 &experience.state,
                 experience.action,
                 experience.reward,
                 &experience.next_state,
                 experience.done,
+                weight,
             )?;
+            self.experience_buffer.update_priority(index, td_error);
         }
         
         // Decay exploration rate
@@ -523,80 +808,6 @@ impl LaplacianQLearningAgent {
     }
 }
 
-impl DeBruijnGraph {
-    /// Create new De Bruijn graph
-    pub fn new(alphabet_size: usize, sequence_length: usize) -> Result<Self> {
-        let mut nodes = HashMap::new();
-        let mut edges = HashMap::new();
-        
-        // Generate all possible sequences of given length
-        let total_sequences = alphabet_size.pow(sequence_length as u32);
-        
-        for i in 0..total_sequences {
-            let sequence = Self::index_to_sequence(i, alphabet_size, sequence_length);
-            let node_id = format!("node_{}", sequence);
-            
-            nodes.insert(node_id.clone(), GraphNode {
-                id: node_id.clone(),
-                state_vector: DVector::zeros(sequence_length),
-                anomaly_features: AnomalyFeatures {
-                    symmetry_deviation: 0.0,
-                    cycle_disruption: 0.0,
-                    volatility_spike: 1.0,
-                    pattern_inversion: 0.0,
-                    novel_pattern_strength: 0.0,
-                    anomaly_confidence: 0.0,
-                    market_context_vector: DVector::zeros(3),
-                },
-                visit_count: 0,
-                value_estimate: 0.0,
-            });
-            
-            // Create edges to successor nodes
-            let mut node_edges = Vec::new();
-            for symbol in 0..alphabet_size {
-                let next_sequence = Self::get_successor_sequence(&sequence, symbol, alphabet_size);
-                let next_node_id = format!("node_{}", next_sequence);
-                
-                node_edges.push(GraphEdge {
-                    from_node: node_id.clone(),
-                    to_node: next_node_id,
-                    action: TradingAction::Hold, // Default action
-                    transition_probability: 1.0 / alphabet_size as f64,
-                    reward_estimate: 0.0,
-                });
-            }
-            
-            edges.insert(node_id, node_edges);
-        }
-        
-        Ok(Self {
-            nodes,
-            edges,
-            alphabet_size,
-            sequence_length,
-        })
-    }
-    
-    /// Convert index to sequence
-    fn index_to_sequence(mut index: usize, alphabet_size: usize, length: usize) -> String {
-        let mut sequence = String::new();
-        for _ in 0..length {
-            sequence.push_str(&(index % alphabet_size).to_string());
-            index /= alphabet_size;
-        }
-        sequence
-    }
-    
-    /// Get successor sequence by shifting and adding new symbol
-    fn get_successor_sequence(sequence: &str, new_symbol: usize, _alphabet_size: usize) -> String {
-        let mut chars: Vec<char> = sequence.chars().collect();
-        chars.remove(0); // Remove first character
-        chars.push(char::from_digit(new_symbol as u32, 10).unwrap_or('0'));
-        chars.into_iter().collect()
-    }
-}
-
 impl Default for PerformanceMetrics {
     fn default() -> Self {
         Self {
@@ -0,0 +1,127 @@
+//! # Prioritized Experience Replay
+//!
+//! Prioritized experience replay (Schaul et al., 2016): experiences are drawn in
+//! proportion to `priority^α` of their latest TD error, with importance-sampling
+//! weights `(N·P(i))^-β` undoing the bias.
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+use super::Experience;
+
+/// Added to every TD error so no experience stops being replayed
+const PRIORITY_FLOOR: f64 = 1e-3;
+
+/// An experience and how much there is still to learn from it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayEntry {
+    pub experience: Experience,
+    /// Absolute TD error when last replayed, plus a floor
+    pub priority: f64,
+}
+
+/// The replay buffer, for saving a trained agent
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReplaySnapshot {
+    /// Oldest first
+    pub entries: Vec<ReplayEntry>,
+    pub max_priority: f64,
+}
+
+/// Bounded replay buffer with proportional sampling
+#[derive(Debug, Clone)]
+pub struct PrioritizedReplay {
+    entries: VecDeque<ReplayEntry>,
+    capacity: usize,
+    max_priority: f64,
+}
+
+impl PrioritizedReplay {
+    pub fn new(capacity: usize) -> Self {
+        Self { entries: VecDeque::with_capacity(capacity), capacity: capacity.max(1), max_priority: 1.0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &ReplayEntry> {
+        self.entries.iter()
+    }
+
+    /// Add an experience at the largest priority so far, dropping the oldest when full
+    pub fn push(&mut self, experience: Experience) {
+        if self.entries.len() >= self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(ReplayEntry { experience, priority: self.max_priority });
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Experience> {
+        self.entries.get(index).map(|entry| &entry.experience)
+    }
+
+    /// Chance `index` is drawn under exponent `alpha`
+    pub fn probability(&self, index: usize, alpha: f64) -> f64 {
+        let total: f64 = self.entries.iter().map(|entry| entry.priority.powf(alpha)).sum();
+        self.entries.get(index).map_or(0.0, |entry| entry.priority.powf(alpha) / total)
+    }
+
+    /// Draw `count` indices with probability proportional to `priority^alpha`,
+    /// one from each of `count` equal slices of the total so a batch spans
+    /// the distribution. Returned with their importance-sampling weights under
+    /// exponent `beta`, the largest 1.
+    pub fn sample(&self, count: usize, alpha: f64, beta: f64, rng: &mut impl Rng) -> Vec<(usize, f64)> {
+        if self.entries.is_empty() || count == 0 {
+            return Vec::new();
+        }
+        let scaled: Vec<f64> = self.entries.iter().map(|entry| entry.priority.powf(alpha)).collect();
+        let total: f64 = scaled.iter().sum();
+        let segment = total / count as f64;
+        let mut samples = Vec::with_capacity(count);
+        let (mut index, mut cumulative) = (0, scaled[0]);
+        for slice in 0..count {
+            let target = segment * (slice as f64 + rng.gen::<f64>());
+            while cumulative < target && index + 1 < scaled.len() {
+                index += 1;
+                cumulative += scaled[index];
+            }
+            let weight = (scaled.len() as f64 * scaled[index] / total).powf(-beta);
+            samples.push((index, weight));
+        }
+        let largest = samples.iter().map(|(_, weight)| *weight).fold(0.0, f64::max);
+        for (_, weight) in &mut samples {
+            *weight /= largest;
+        }
+        samples
+    }
+
+    /// Set an experience's priority from the TD error it was just replayed with
+    pub fn update_priority(&mut self, index: usize, td_error: f64) {
+        if let Some(entry) = self.entries.get_mut(index) {
+            entry.priority = td_error.abs() + PRIORITY_FLOOR;
+            self.max_priority = self.max_priority.max(entry.priority);
+        }
+    }
+
+    pub fn snapshot(&self) -> ReplaySnapshot {
+        ReplaySnapshot { entries: self.entries.iter().cloned().collect(), max_priority: self.max_priority }
+    }
+
+    /// Replace the buffer with a saved one, keeping the newest entries that fit;
+    /// `None` empties it
+    pub fn restore(&mut self, snapshot: Option<&ReplaySnapshot>) {
+        self.entries.clear();
+        self.max_priority = 1.0;
+        if let Some(snapshot) = snapshot {
+            let skip = snapshot.entries.len().saturating_sub(self.capacity);
+            self.entries.extend(snapshot.entries.iter().skip(skip).cloned());
+            self.max_priority = snapshot.max_priority.max(PRIORITY_FLOOR);
+        }
+    }
+}
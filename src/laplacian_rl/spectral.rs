@@ -0,0 +1,113 @@
+//! # Spectral State Embedding
+//!
+//! Laplacian eigenmaps of the symmetrized De Bruijn state graph: eigenvectors of
+//! the smallest non-zero eigenvalues place states close in the transition
+//! structure close together.
+
+use anyhow::{bail, Result};
+use nalgebra::{DMatrix, SymmetricEigen};
+
+/// Eigenvalues below this belong to the constant (trivial) eigenvectors
+const ZERO_EIGENVALUE: f64 = 1e-9;
+
+/// Eigenvalues closer than this are treated as one eigenspace
+const EIGENSPACE_TOLERANCE: f64 = 1e-8;
+
+/// Coordinates of every graph node from the smallest non-trivial Laplacian eigenvectors
+#[derive(Debug, Clone)]
+pub struct SpectralEmbedding {
+    /// Row per graph node, column per eigenvector
+    coordinates: DMatrix<f64>,
+    /// Eigenvalue of each column, ascending
+    eigenvalues: Vec<f64>,
+}
+
+impl SpectralEmbedding {
+    /// Embed the nodes of a graph with Laplacian `laplacian` (`D - A`, possibly
+    /// of a directed graph) in at least `dimensions` coordinates, scaled so the
+    /// two farthest nodes are at distance 1
+    pub fn new(laplacian: &DMatrix<f64>, dimensions: usize) -> Result<Self> {
+        if dimensions == 0 {
+            bail!("spectral embedding needs at least one dimension");
+        }
+        let n = laplacian.nrows();
+        if n != laplacian.ncols() || n < 2 {
+            bail!("spectral embedding needs a square Laplacian of at least 2 nodes, got {}x{}", n, laplacian.ncols());
+        }
+
+        // Undirected weights: average the two directions of each off-diagonal edge
+        let mut symmetric = DMatrix::zeros(n, n);
+        for i in 0..n {
+            for j in 0..n {
+                if i != j {
+                    let weight = -(laplacian[(i, j)] + laplacian[(j, i)]) / 2.0;
+                    symmetric[(i, j)] = -weight;
+                    symmetric[(i, i)] += weight;
+                }
+            }
+        }
+
+        let decomposition = SymmetricEigen::new(symmetric);
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|a, b| decomposition.eigenvalues[*a].total_cmp(&decomposition.eigenvalues[*b]));
+        let mut chosen: Vec<usize> = Vec::new();
+        for index in order {
+            let eigenvalue = decomposition.eigenvalues[index];
+            if eigenvalue < ZERO_EIGENVALUE {
+                continue;
+            }
+            let completes_eigenspace = chosen.last()
+                .is_some_and(|last| (eigenvalue - decomposition.eigenvalues[*last]).abs() < EIGENSPACE_TOLERANCE);
+            if chosen.len() >= dimensions && !completes_eigenspace {
+                break;
+            }
+            chosen.push(index);
+        }
+        if chosen.is_empty() {
+            bail!("graph Laplacian has no non-trivial eigenvectors");
+        }
+
+        let mut coordinates = DMatrix::from_fn(n, chosen.len(), |row, column| decomposition.eigenvectors[(row, chosen[column])]);
+        let diameter = (0..n)
+            .flat_map(|a| (a + 1..n).map(move |b| (a, b)))
+            .map(|(a, b)| (coordinates.row(a) - coordinates.row(b)).norm())
+            .fold(0.0, f64::max);
+        if diameter > 0.0 {
+            coordinates /= diameter;
+        }
+        Ok(Self {
+            coordinates,
+            eigenvalues: chosen.iter().map(|index| decomposition.eigenvalues[*index]).collect(),
+        })
+    }
+
+    /// Coordinates per node; more than requested when an eigenspace was completed
+    pub fn dimensions(&self) -> usize {
+        self.coordinates.ncols()
+    }
+
+    pub fn nodes(&self) -> usize {
+        self.coordinates.nrows()
+    }
+
+    /// Eigenvalue of each coordinate, ascending
+    pub fn eigenvalues(&self) -> &[f64] {
+        &self.eigenvalues
+    }
+
+    /// Coordinates of `node`
+    pub fn coordinates(&self, node: usize) -> Vec<f64> {
+        self.coordinates.row(node).iter().copied().collect()
+    }
+
+    /// Euclidean distance between two nodes
+    pub fn distance(&self, a: usize, b: usize) -> f64 {
+        (self.coordinates.row(a) - self.coordinates.row(b)).norm()
+    }
+
+    /// Gaussian similarity of two nodes, 1 at distance 0
+    pub fn kernel(&self, a: usize, b: usize, bandwidth: f64) -> f64 {
+        let distance = self.distance(a, b);
+        (-distance * distance / (2.0 * bandwidth * bandwidth).max(f64::EPSILON)).exp()
+    }
+}
@@ -16,6 +16,25 @@ pub mod laplacian_rl;
 pub mod multi_currency;
 pub mod embedded_db;
 pub mod correlation;
+pub mod report;
+pub mod trading_windows;
+pub mod portfolio;
+pub mod audit;
+pub mod protocol;
+pub mod stats;
+pub mod resilience;
+pub mod calendar;
+pub mod sessions;
+pub mod signal;
+pub mod replay;
+pub mod ids;
+pub mod risk;
+pub mod pipeline;
+pub mod units;
+pub mod progress;
+pub mod broker;
+pub mod regimes;
+pub mod validation;
 
 // Re-export main types for convenience
 pub use core::{TimeSymmetricEngine, EngineConfig};
@@ -10,17 +10,17 @@ use clap::{Parser, Subcommand};
 use tracing::{info, warn, error};
 use std::path::PathBuf;
 
-mod core;
-mod data;
-mod patterns;
-mod galois;
-mod symmetry;
-mod backtest;
-mod visualization;
+use forex_pattern_reconstruction::{
+    core, data, patterns, symmetry, backtest, visualization, anomaly, report, synthetic,
+    trading_windows, embedded_db, calendar, replay, portfolio, validation,
+};
 
 use crate::core::TimeSymmetricEngine;
 use crate::data::ForexDataManager;
 use crate::patterns::PatternRecognizer;
+use forex_pattern_reconstruction::pipeline::{PipelineBuilder, PipelineConfig};
+use forex_pattern_reconstruction::portfolio::margin::MarginEventKind;
+use forex_pattern_reconstruction::regimes::{RegimeConfig, RegimeModel};
 
 /// Forex Pattern Reconstruction System
 #[derive(Parser)]
@@ -59,6 +59,33 @@ enum Commands {
         /// Output directory for results
         #[arg(short, long, default_value = "output")]
         output: PathBuf,
+        
+        /// Recompute symmetries and cycles even if cached results exist
+        #[arg(long)]
+        no_cache: bool,
+        
+        /// Also resample to these timeframes (comma-separated, e.g. M15,H1,H4,D1)
+        /// and report cycles confirmed across them
+        #[arg(long)]
+        timeframes: Option<String>,
+        
+        /// Cross-check the symmetries of a coarse timeframe on a finer one
+        /// (e.g. D1,H1) and report how consistently they reappear
+        #[arg(long)]
+        resolutions: Option<String>,
+        
+        /// Use the symmetries of a saved set (.symmetries.json or .symmetries.bin)
+        /// instead of extracting them
+        #[arg(long)]
+        symmetries: Option<PathBuf>,
+        
+        /// Save the symmetries as a portable set (.symmetries.json or .symmetries.bin)
+        #[arg(long)]
+        export_symmetries: Option<PathBuf>,
+        
+        /// Report format (json, or html for a self-contained report next to the JSON)
+        #[arg(short, long, default_value = "json")]
+        format: String,
     },
     
     /// Run backtesting to validate temporal symmetries
@@ -78,6 +105,42 @@ enum Commands {
         /// Initial capital
         #[arg(long, default_value = "10000.0")]
         capital: f64,
+        
+        /// Input data file or directory
+        #[arg(short, long, default_value = "FOREX DATA")]
+        input: PathBuf,
+        
+        /// Currency pair (e.g., EURUSD)
+        #[arg(short, long, default_value = "EURUSD")]
+        pair: String,
+        
+        /// Bar timeframe
+        #[arg(short, long, default_value = "1D")]
+        timeframe: String,
+        
+        /// Save the results as JSON (used by `report dossier`)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        
+        /// Save the fills as a trade journal (used by `report dossier --trades`)
+        #[arg(long)]
+        journal: Option<PathBuf>,
+        
+        /// Bid/ask tick CSV to build the bars from instead of --input; fills pay the quoted spread
+        #[arg(long)]
+        ticks: Option<PathBuf>,
+        
+        /// Measure anomalies against the symmetries of a saved set instead of refitting them
+        #[arg(long)]
+        symmetries: Option<PathBuf>,
+        
+        /// Refit baseline symmetries and cycles even if cached results exist
+        #[arg(long)]
+        no_cache: bool,
+        
+        /// Walk-forward optimize the strategy parameters listed in this optimizer config instead of a single run
+        #[arg(long)]
+        optimize: Option<PathBuf>,
     },
     
     /// Launch real-time pattern recognition dashboard
@@ -89,6 +152,14 @@ enum Commands {
         /// Dashboard port
         #[arg(short, long, default_value = "8080")]
         port: u16,
+        
+        /// Restore pair models saved by --save-model instead of re-analyzing the history
+        #[arg(long)]
+        load_model: Option<PathBuf>,
+        
+        /// Save pair models to this directory after initialization and on shutdown
+        #[arg(long)]
+        save_model: Option<PathBuf>,
     },
     
     /// Decompose EUR/USD data into cyclic components
@@ -101,10 +172,329 @@ enum Commands {
         #[arg(short, long, default_value = "7,21,365,1277")]
         cycles: String,
         
-        /// Output format (json, csv, plot)
+        /// Output format (json, csv, plot, svg, html)
         #[arg(short, long, default_value = "json")]
         format: String,
     },
+    
+    /// Extract a pair's symmetries and cycles and export synthetic bars continuing its history
+    Generate {
+        /// Input data file or directory
+        #[arg(short, long, default_value = "FOREX DATA")]
+        input: PathBuf,
+        
+        /// Currency pair (e.g., EURUSD)
+        #[arg(short, long, default_value = "EURUSD")]
+        pair: String,
+        
+        /// Bar timeframe, also the spacing of the generated bars
+        #[arg(short, long, default_value = "1D")]
+        timeframe: String,
+        
+        /// Days of synthetic data to generate after the last bar
+        #[arg(short, long, default_value = "30")]
+        days: u32,
+        
+        /// Random seed for reproducible generation
+        #[arg(short, long)]
+        seed: Option<u64>,
+        
+        /// Output file
+        #[arg(short, long)]
+        output: PathBuf,
+        
+        /// Export format (csv, parquet, json); defaults to the output extension
+        #[arg(short, long)]
+        format: Option<String>,
+    },
+    
+    /// Analyze every CSV in a data directory in parallel and rank the pairs by their
+    /// strongest symmetries, with the cycle periods they share
+    Scan {
+        /// Data directory
+        #[arg(short, long, default_value = "FOREX DATA")]
+        input: PathBuf,
+        
+        /// Bar timeframe
+        #[arg(short, long, default_value = "1D")]
+        timeframe: String,
+        
+        /// Strongest symmetries kept per pair (overrides configuration)
+        #[arg(long)]
+        top: Option<usize>,
+        
+        /// Pairs analyzed at once (overrides configuration; 0 uses every core)
+        #[arg(short, long)]
+        jobs: Option<usize>,
+        
+        /// Save the summary as JSON or CSV, by extension
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    
+    /// Generate a seeded multi-pair demo dataset
+    DemoData {
+        /// Currency pairs to generate (comma-separated)
+        #[arg(short, long, default_value = "EURUSD,GBPUSD")]
+        pairs: String,
+        
+        /// Years of daily history per pair
+        #[arg(short, long, default_value = "10")]
+        years: u32,
+        
+        /// Random seed (same seed, same dataset)
+        #[arg(short, long, default_value = "42")]
+        seed: u64,
+        
+        /// Output directory for <PAIR>.csv files
+        #[arg(short, long, default_value = data::DEFAULT_DAILY_DATA_PATH)]
+        output: PathBuf,
+        
+        /// Also store the dataset in an embedded database file
+        #[arg(long)]
+        db: Option<PathBuf>,
+    },
+    
+    /// Generate summary reports
+    Report {
+        #[command(subcommand)]
+        report: ReportCommands,
+    },
+    
+    /// Maintain the embedded database
+    Db {
+        #[command(subcommand)]
+        db: DbCommands,
+    },
+    
+    /// Train the Laplacian RL agent on historical anomalies, episode by episode
+    TrainRl {
+        /// Input data file or directory
+        #[arg(short, long, default_value = "FOREX DATA")]
+        input: PathBuf,
+        
+        /// Currency pair (e.g., EURUSD)
+        #[arg(short, long, default_value = "EURUSD")]
+        pair: String,
+        
+        /// Bar timeframe
+        #[arg(short, long, default_value = "1D")]
+        timeframe: String,
+        
+        /// First day of the history (YYYY-MM-DD); all bars when absent
+        #[arg(long)]
+        start_date: Option<String>,
+        
+        /// Last day of the history (YYYY-MM-DD)
+        #[arg(long)]
+        end_date: Option<String>,
+        
+        /// Passes over the history
+        #[arg(short, long, default_value = "20")]
+        episodes: usize,
+        
+        /// Agent checkpoint, resumed from when it exists and saved after every episode
+        #[arg(long, default_value = "models/rl_agent.json")]
+        checkpoint: PathBuf,
+        
+        /// Strategy configuration whose parameters (units_per_size, exploration_rate) the agent trades with
+        #[arg(short, long)]
+        strategy: Option<PathBuf>,
+        
+        /// Initial capital of every episode
+        #[arg(long, default_value = "10000.0")]
+        capital: f64,
+        
+        /// Random seed, for runs that train identically
+        #[arg(long)]
+        seed: Option<u64>,
+        
+        /// Save the learning curve as JSON
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    
+    /// Time every pipeline stage on standard benchmark datasets and check the detected
+    /// cycles against the injected ones
+    BenchPipeline {
+        /// Dataset sizes to run (comma-separated: S, M, L)
+        #[arg(long, default_value = "S,M")]
+        sizes: String,
+        
+        /// Random seed of the datasets (same seed, same bars)
+        #[arg(long, default_value = "42")]
+        seed: u64,
+        
+        /// Pipeline configuration (TOML); defaults to the analysis configuration
+        #[arg(long)]
+        pipeline: Option<PathBuf>,
+        
+        /// Save the timings and cycle checks as JSON
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        
+        /// Also write each dataset as <SIZE>.csv with its injected cycles in <SIZE>.cycles.json
+        #[arg(long)]
+        export: Option<PathBuf>,
+    },
+    
+    /// Replay a recorded live session offline and flag decisions that differ
+    ReplaySession {
+        /// Session log written by a trader run with SESSION_LOG_PATH set
+        log: PathBuf,
+        
+        /// Write the replay report as JSON
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum DbCommands {
+    /// Write a consistent copy of the database to a new file
+    Backup {
+        /// Backup file to create
+        path: PathBuf,
+        
+        /// Database file
+        #[arg(long, default_value = "forex.db")]
+        db: PathBuf,
+    },
+    
+    /// Replace the database with a verified backup
+    Restore {
+        /// Backup file to restore from
+        path: PathBuf,
+        
+        /// Database file to overwrite
+        #[arg(long, default_value = "forex.db")]
+        db: PathBuf,
+    },
+    
+    /// Run integrity and consistency checks
+    Check {
+        /// Database file
+        #[arg(long, default_value = "forex.db")]
+        db: PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+enum ReportCommands {
+    /// Compile the day's anomalies, trades, P&L, symmetry decay and feed health
+    Daily {
+        /// Input data file or directory
+        #[arg(short, long, default_value = "FOREX DATA")]
+        input: PathBuf,
+        
+        /// Currency pairs to include (comma-separated)
+        #[arg(short, long, default_value = "EURUSD")]
+        pairs: String,
+        
+        /// Report date (YYYY-MM-DD), defaults to the last day in the data
+        #[arg(short, long)]
+        date: Option<String>,
+        
+        /// Trade journal (JSON array of trade records)
+        #[arg(long)]
+        trades: Option<PathBuf>,
+        
+        /// Output directory (overrides configuration)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        
+        /// Output format: markdown, html, both (overrides configuration)
+        #[arg(short, long)]
+        format: Option<String>,
+        
+        /// Webhook URL to POST the report to (overrides configuration)
+        #[arg(long)]
+        webhook: Option<String>,
+        
+        /// Keep running and generate the report every day at the configured hour
+        #[arg(long)]
+        schedule: bool,
+    },
+    
+    /// Diff the week's cycles, symmetries and anomalies against the week before
+    Weekly {
+        /// Input data file or directory
+        #[arg(short, long, default_value = "FOREX DATA")]
+        input: PathBuf,
+        
+        /// Currency pairs to include (comma-separated)
+        #[arg(short, long, default_value = "EURUSD")]
+        pairs: String,
+        
+        /// Any day of the week to digest (YYYY-MM-DD), defaults to the last week in the data
+        #[arg(short, long)]
+        week: Option<String>,
+        
+        /// Output directory (overrides configuration)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        
+        /// Keep running and generate the digest every week at the configured weekday and hour
+        #[arg(long)]
+        schedule: bool,
+    },
+    
+    /// Per-pair HTML evidence dossier for each detected symmetry
+    Dossier {
+        /// Analysis report written by `analyze`, or a directory of them
+        #[arg(short, long, default_value = "output")]
+        analysis: PathBuf,
+        
+        /// Input data file or directory
+        #[arg(short, long, default_value = "FOREX DATA")]
+        input: PathBuf,
+        
+        /// Trade journal (JSON array of trade records tagged with `symmetry_id`)
+        #[arg(long)]
+        trades: Option<PathBuf>,
+        
+        /// Backtest results written by `backtest --output`
+        #[arg(long)]
+        backtest: Option<PathBuf>,
+        
+        /// Output directory (overrides configuration)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    
+    /// Generate synthetic data from a pair's history and check how realistic it looks
+    Synthetic {
+        /// Input data file or directory
+        #[arg(short, long, default_value = "FOREX DATA")]
+        input: PathBuf,
+        
+        /// Currency pair (e.g., EURUSD)
+        #[arg(short, long, default_value = "EURUSD")]
+        pair: String,
+        
+        /// Bar timeframe
+        #[arg(short, long, default_value = "1D")]
+        timeframe: String,
+        
+        /// Random seed for reproducible generation
+        #[arg(short, long)]
+        seed: Option<u64>,
+        
+        /// Save the quality report as JSON
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    
+    /// Slippage, time to fill and spread of live/paper fills per pair, session and broker
+    ExecutionQuality {
+        /// Execution log written by a trader with EXECUTION_LOG_PATH set
+        #[arg(short, long)]
+        log: PathBuf,
+        
+        /// Save the report as JSON
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
 }
 
 #[tokio::main]
@@ -124,38 +514,102 @@ async fn main() -> Result<()> {
     let config = load_configuration(&cli.config).await?;
     
     match cli.command {
-        Commands::Analyze { input, pair, timeframe, output } => {
-            analyze_forex_patterns(input, pair, timeframe, output, config).await?;
+        Commands::Analyze { input, pair, timeframe, output, no_cache, timeframes, resolutions, symmetries, export_symmetries, format } => {
+            let run = AnalyzeRequest { input, pair, timeframe, output, no_cache, timeframes, resolutions, symmetries, export_symmetries, format };
+            analyze_forex_patterns(run, config).await?;
         },
         
-        Commands::Backtest { strategy, start_date, end_date, capital } => {
-            run_backtest_validation(strategy, start_date, end_date, capital, config).await?;
+        Commands::Backtest { strategy, start_date, end_date, capital, input, pair, timeframe, output, journal, ticks, symmetries, no_cache, optimize } => {
+            let run = BacktestRequest { strategy, start_date, end_date, capital, input, pair, timeframe, output, journal, ticks, symmetries, no_cache, optimize };
+            run_backtest_validation(run, config).await?;
         },
         
-        Commands::Dashboard { feed_config, port } => {
-            launch_pattern_dashboard(feed_config, port, config).await?;
+        Commands::Dashboard { feed_config, port, load_model, save_model } => {
+            launch_pattern_dashboard(feed_config, port, load_model, save_model, config).await?;
         },
         
         Commands::Decompose { data_file, cycles, format } => {
             decompose_eur_usd_cycles(data_file, cycles, format, config).await?;
         },
+        
+        Commands::Generate { input, pair, timeframe, days, seed, output, format } => {
+            let run = GenerateRequest { input, pair, timeframe, days, seed, output, format };
+            generate_synthetic_data(run, config).await?;
+        },
+        
+        Commands::Scan { input, timeframe, top, jobs, output } => {
+            scan_data_directory(input, timeframe, top, jobs, output, config).await?;
+        },
+        
+        Commands::DemoData { pairs, years, seed, output, db } => {
+            generate_demo_data(pairs, years, seed, output, db, config).await?;
+        },
+        
+        Commands::Report { report: ReportCommands::Daily { input, pairs, date, trades, output, format, webhook, schedule } } => {
+            generate_daily_report(input, pairs, date, trades, output, format, webhook, schedule, config).await?;
+        },
+        
+        Commands::Report { report: ReportCommands::Weekly { input, pairs, week, output, schedule } } => {
+            generate_weekly_digest(input, pairs, week, output, schedule, config).await?;
+        },
+        
+        Commands::Report { report: ReportCommands::Dossier { analysis, input, trades, backtest, output } } => {
+            generate_symmetry_dossiers(analysis, input, trades, backtest, output, config).await?;
+        },
+        
+        Commands::Report { report: ReportCommands::Synthetic { input, pair, timeframe, seed, output } } => {
+            validate_synthetic_data(input, pair, timeframe, seed, output, config).await?;
+        },
+        
+        Commands::Report { report: ReportCommands::ExecutionQuality { log, output } } => {
+            report_execution_quality(log, output, config)?;
+        },
+        
+        Commands::Db { db } => {
+            run_db_command(db)?;
+        },
+        
+        Commands::TrainRl { input, pair, timeframe, start_date, end_date, episodes, checkpoint, strategy, capital, seed, output } => {
+            let run = TrainRlRequest { input, pair, timeframe, start_date, end_date, episodes, checkpoint, strategy, capital, seed, output };
+            train_rl_agent(run, config).await?;
+        },
+        
+        Commands::BenchPipeline { sizes, seed, pipeline, output, export } => {
+            bench_pipeline(sizes, seed, pipeline, output, export, config).await?;
+        },
+        
+        Commands::ReplaySession { log, output } => {
+            replay_recorded_session(log, output).await?;
+        },
     }
     
     Ok(())
 }
 
-/// Analyze forex data for temporal symmetries and hidden cycles
-async fn analyze_forex_patterns(
+/// Arguments of the `analyze` command
+struct AnalyzeRequest {
     input: PathBuf,
     pair: String,
     timeframe: String,
     output: PathBuf,
-    config: Configuration,
-) -> Result<()> {
+    no_cache: bool,
+    timeframes: Option<String>,
+    resolutions: Option<String>,
+    symmetries: Option<PathBuf>,
+    export_symmetries: Option<PathBuf>,
+    format: String,
+}
+
+/// Analyze forex data for temporal symmetries and hidden cycles
+async fn analyze_forex_patterns(request: AnalyzeRequest, config: Configuration) -> Result<()> {
+    let AnalyzeRequest { input, pair, timeframe, output, no_cache, timeframes, resolutions, symmetries: imported, export_symmetries, format } = request;
+    if !matches!(format.as_str(), "json" | "html") {
+        return Err(anyhow::anyhow!("Unsupported output format: {} (json or html)", format));
+    }
     info!("🔍 Analyzing {} patterns in {} timeframe", pair, timeframe);
     
     // Initialize data manager
-    let mut data_manager = ForexDataManager::new(config.data_config)?;
+    let mut data_manager = ForexDataManager::new(config.data_config.clone())?;
     let forex_data = data_manager.load_data(&input, &pair, &timeframe).await?;
     
     info!("📈 Loaded {} data points from {} to {}", 
@@ -163,16 +617,43 @@ async fn analyze_forex_patterns(
           forex_data.first().unwrap().timestamp,
           forex_data.last().unwrap().timestamp);
     
-    // Initialize time-symmetric engine
-    let mut engine = TimeSymmetricEngine::new(config.engine_config)?;
-    engine.initialize().await?;
+    // Results are checkpointed per (pair, timeframe, data checksum, engine/pattern config hash)
+    let cache = analysis_cache(&config, &pair, &timeframe, no_cache);
+    let imported = imported.map(|path| load_symmetry_set(&path, &pair, Some(&forex_data), &config)).transpose()?;
     
-    // Initialize pattern recognizer
-    let mut pattern_recognizer = PatternRecognizer::new(config.pattern_config)?;
+    // An imported set replaces extraction; the cache pairs symmetries with the configuration that extracted them
+    let symmetries = match (&imported, cache.as_ref().map(|cache| cache.symmetries(&forex_data)).transpose()?.flatten()) {
+        (Some(set), _) => set.symmetries.clone(),
+        (None, Some(symmetries)) => {
+            info!("⚡ Using cached symmetries (use --no-cache to recompute)");
+            symmetries
+        }
+        (None, None) => {
+            let mut engine = TimeSymmetricEngine::new(config.engine_config.clone())?;
+            engine.initialize().await?;
+            info!("🔬 Extracting temporal symmetries...");
+            let symmetries = engine.extract_temporal_symmetries(&forex_data).await?;
+            if let Some(cache) = &cache {
+                cache.store_symmetries(&forex_data, &symmetries)?;
+            }
+            symmetries
+        }
+    };
     
-    // Extract temporal symmetries
-    info!("🔬 Extracting temporal symmetries...");
-    let symmetries = engine.extract_temporal_symmetries(&forex_data).await?;
+    let cycles = match cache.as_ref().map(|cache| cache.cycles(&forex_data)).transpose()?.flatten() {
+        Some(cycles) => {
+            info!("⚡ Using cached cycles (use --no-cache to recompute)");
+            cycles
+        }
+        None => {
+            info!("🔄 Detecting hidden cycles...");
+            let cycles = PatternRecognizer::new(config.pattern_config.clone())?.detect_cycles(&forex_data).await?;
+            if let Some(cache) = &cache {
+                cache.store_cycles(&forex_data, &cycles)?;
+            }
+            cycles
+        }
+    };
     
     info!("✅ Found {} temporal symmetries", symmetries.len());
     for symmetry in &symmetries {
@@ -180,18 +661,73 @@ async fn analyze_forex_patterns(
               symmetry.name, symmetry.strength, symmetry.period_days);
     }
     
-    // Detect hidden cycles
-    info!("🔄 Detecting hidden cycles...");
-    let cycles = pattern_recognizer.detect_cycles(&forex_data).await?;
-    
     info!("✅ Detected {} hidden cycles", cycles.len());
     for cycle in &cycles {
-        info!("  🔄 {}: period={} days, confidence={:.3}", 
-              cycle.name, cycle.period, cycle.confidence);
+        match cycle.p_value {
+            Some(p_value) => info!("  🔄 {}: period={} days, confidence={:.3}, p={:.3}",
+                                   cycle.name, cycle.period, cycle.confidence, p_value),
+            None => info!("  🔄 {}: period={} days, confidence={:.3}", 
+                          cycle.name, cycle.period, cycle.confidence),
+        }
+    }
+    
+    // Recorded at the last bar analysed, so symmetry strength can be followed run by run
+    if let (Some(cache), Some(last)) = (&cache, forex_data.last()) {
+        cache.db().insert_symmetries(&pair, &timeframe, last.timestamp, &symmetries)?;
+        cache.db().insert_cycles(&pair, &timeframe, last.timestamp, &cycles)?;
+    }
+    
+    if let Some(path) = export_symmetries {
+        let engine_hash = embedded_db::config_hash(&config.engine_config)?;
+        let set = match imported.clone() {
+            // Re-exporting keeps where the symmetries were decoded
+            Some(set) => set,
+            None => symmetry::SymmetrySet::new(symmetries.clone(), &forex_data, &pair, &timeframe, &engine_hash),
+        };
+        set.save(&path)?;
+        info!("📦 {} symmetries saved to {}", set.symmetries.len(), path.display());
+    }
+    
+    // Out-of-sample persistence over purged folds, the detected score being in-sample
+    let validation = validation::validate_temporal_symmetries(&forex_data, &symmetries, &cycles, &config.validation_config)?;
+    info!("🧪 {} of {} symmetries persist out of sample ({} folds, purge {} bars)",
+          validation.persistent().count(), validation.symmetries.len(), validation.splits.len(), validation.purge_bars);
+    for symmetry in &validation.symmetries {
+        info!("  🧪 {}: in-sample={}, out-of-sample={}, persistence={}", symmetry.name,
+              format_score(symmetry.mean_in_sample), format_score(symmetry.mean_out_of_sample), format_score(symmetry.persistence));
     }
     
     // Generate analysis report
-    let report = generate_analysis_report(&symmetries, &cycles, &forex_data)?;
+    let mut report = generate_analysis_report(&pair, &timeframe, &symmetries, &cycles, &forex_data)?;
+    report["symmetry_validation"] = serde_json::to_value(&validation)?;
+    if let Some(quality) = data_manager.quality_report() {
+        report["data_quality"] = serde_json::to_value(quality)?;
+    }
+    if let Some(set) = &imported {
+        report["symmetry_set"] = serde_json::json!({ "config_hash": set.config_hash, "provenance": set.provenance });
+    }
+    
+    if let Some(timeframes) = timeframes {
+        let timeframes: Vec<String> = timeframes
+            .split(',')
+            .map(|s| s.trim().to_uppercase())
+            .filter(|s| !s.is_empty())
+            .collect();
+        report["multi_timeframe"] = analyze_multi_timeframe(&forex_data, &timeframe, &timeframes, &config).await?;
+    }
+    
+    if let Some(resolutions) = resolutions {
+        let resolutions: Vec<String> = resolutions
+            .split(',')
+            .map(|s| s.trim().to_uppercase())
+            .filter(|s| !s.is_empty())
+            .collect();
+        let [coarse, fine] = resolutions.as_slice() else {
+            return Err(anyhow::anyhow!("--resolutions takes a coarse and a fine timeframe, e.g. D1,H1"));
+        };
+        let comparison = analyze_resolutions(&forex_data, &timeframe, coarse, fine, &config).await?;
+        report["resolution_comparison"] = serde_json::to_value(&comparison)?;
+    }
     
     // Save results
     std::fs::create_dir_all(&output)?;
@@ -200,42 +736,260 @@ async fn analyze_forex_patterns(
     
     info!("📄 Analysis report saved to: {}", report_path.display());
     
+    if format == "html" {
+        let regimes = RegimeModel::fit(&forex_data, &RegimeConfig::default())
+            .map(|model| model.probabilities(&forex_data))
+            .unwrap_or_default();
+        let html_path = report::analysis::write_analysis_html(&output, &pair, &timeframe, &forex_data, &symmetries, &cycles, &regimes)?;
+        info!("📄 HTML report saved to: {}", html_path.display());
+    }
+    
     // Generate visualizations
     if config.visualization_enabled {
         info!("📊 Generating visualizations...");
-        visualization::generate_pattern_plots(&symmetries, &cycles, &forex_data, &output)?;
+        visualization::generate_pattern_plots(&symmetries, &cycles, &forex_data, &output, config.chart_format)?;
         info!("✅ Visualizations saved to: {}", output.display());
     }
     
     Ok(())
 }
 
-/// Run backtesting to validate temporal symmetries
-async fn run_backtest_validation(
-    strategy_path: PathBuf,
+/// Symmetry set saved by `analyze --export-symmetries`, warning where this run
+/// differs from the one that decoded it; `data` is checked when the run should
+/// see the same bars
+fn load_symmetry_set(
+    path: &std::path::Path,
+    pair: &str,
+    data: Option<&[crate::data::ForexDataPoint]>,
+    config: &Configuration,
+) -> Result<symmetry::SymmetrySet> {
+    let set = symmetry::SymmetrySet::load(path)?;
+    info!("📦 {} symmetries from {} ({} {}, {} bars, {})", set.symmetries.len(), path.display(),
+          set.provenance.pair, set.provenance.timeframe, set.provenance.bars, set.provenance.generator);
+    for mismatch in set.mismatches(pair, &embedded_db::config_hash(&config.engine_config)?, data) {
+        warn!("⚠️  Symmetry set {}: results may not reproduce the original run", mismatch);
+    }
+    Ok(set)
+}
+
+/// Relative difference in wall-clock period under which cycles on two timeframes count as one
+const CONFLUENCE_TOLERANCE: f64 = 0.1;
+
+/// Cycles and symmetries on every requested timeframe no finer than the loaded
+/// data, and the cycles confirmed across them
+async fn analyze_multi_timeframe(
+    data: &[crate::data::ForexDataPoint],
+    base_timeframe: &str,
+    timeframes: &[String],
+    config: &Configuration,
+) -> Result<serde_json::Value> {
+    let base = crate::data::provider::timeframe_duration(base_timeframe)?;
+    let mut usable = Vec::new();
+    for timeframe in timeframes {
+        if crate::data::provider::timeframe_duration(timeframe)? < base {
+            warn!("⚠️  Skipping {}: finer than the {} input data", timeframe, base_timeframe);
+        } else {
+            usable.push(timeframe.clone());
+        }
+    }
+    info!("🕰️  Multi-timeframe analysis on {}", usable.join(", "));
+    
+    let mut engine = TimeSymmetricEngine::new(config.engine_config.clone())?;
+    engine.initialize().await?;
+    let symmetries = engine.extract_temporal_symmetries_multi_timeframe(data, &usable).await?;
+    
+    let mut pattern_recognizer = PatternRecognizer::new(config.pattern_config.clone())?;
+    let cycles = pattern_recognizer.detect_cycles_multi_timeframe(data, &usable).await?;
+    let confluence = crate::patterns::find_cycle_confluence(&cycles, CONFLUENCE_TOLERANCE);
+    
+    info!("✅ {} cycle periods confirmed on several timeframes", confluence.len());
+    for group in &confluence {
+        info!("  🔗 {:.1}h on {}: score={:.3}", group.period_hours, group.timeframes.join("/"), group.score);
+    }
+    
+    Ok(serde_json::json!({
+        "timeframes": cycles.iter().zip(&symmetries).map(|(c, s)| serde_json::json!({
+            "timeframe": c.timeframe,
+            "bars": c.bars,
+            "bar_seconds": c.bar_seconds,
+            "hidden_cycles": c.cycles,
+            "temporal_symmetries": s.symmetries,
+        })).collect::<Vec<_>>(),
+        "cycle_confluence": confluence,
+    }))
+}
+
+/// Relative difference from the scaled coarse period within which fine-timeframe structure counts as the same symmetry
+const RESOLUTION_TOLERANCE: f64 = 0.1;
+
+/// Symmetries extracted on the `coarse` timeframe and re-measured on the `fine` one
+async fn analyze_resolutions(
+    data: &[crate::data::ForexDataPoint],
+    base_timeframe: &str,
+    coarse: &str,
+    fine: &str,
+    config: &Configuration,
+) -> Result<symmetry::ResolutionComparison> {
+    let base = crate::data::provider::timeframe_duration(base_timeframe)?;
+    if crate::data::provider::timeframe_duration(fine)? < base {
+        return Err(anyhow::anyhow!("{} is finer than the {} input data", fine, base_timeframe));
+    }
+    info!("🔭 Comparing {} symmetries against {} bars", coarse, fine);
+    
+    let coarse_bars = crate::data::timeframe::TimeframeAggregator::new(coarse)?.resample(data)?;
+    let fine_bars = crate::data::timeframe::TimeframeAggregator::new(fine)?.resample(data)?;
+    let mut engine = TimeSymmetricEngine::new(config.engine_config.clone())?;
+    engine.initialize().await?;
+    let symmetries = engine.extract_temporal_symmetries(&coarse_bars).await?;
+    let comparison = symmetry::compare_resolutions(coarse, &symmetries, fine, &fine_bars, RESOLUTION_TOLERANCE)?;
+    
+    for m in &comparison.matches {
+        info!("  {} {} {}: expected ~{:.0} {} bars, strongest at {} ({:.3} vs chance {:.3})",
+              if m.confirmed { "✅" } else { "❌" }, coarse, m.name, m.expected_period, fine, m.fine_period, m.fine_strength, m.control_strength);
+    }
+    if !comparison.unmeasured.is_empty() {
+        warn!("⚠️  {} {} symmetries could not be re-measured on {}", comparison.unmeasured.len(), coarse, fine);
+    }
+    info!("🔭 {}/{} {} symmetries reappear on {}: consistency {:.3}",
+          comparison.confirmed(), comparison.matches.len(), coarse, fine, comparison.consistency_score);
+    Ok(comparison)
+}
+
+/// Open the file-backed analysis cache, creating its directory if needed
+fn open_analysis_cache(path: &std::path::Path) -> Result<embedded_db::EmbeddedForexDB> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    embedded_db::EmbeddedForexDB::open(path)
+}
+
+/// Cache of `pair`'s symmetries and cycles under the engine and pattern configuration;
+/// with `no_cache` results are recomputed and overwrite the cached ones
+fn analysis_cache(config: &Configuration, pair: &str, timeframe: &str, no_cache: bool) -> Option<embedded_db::AnalysisCache> {
+    let cache = open_analysis_cache(&config.analysis_cache_path).and_then(|db| {
+        embedded_db::AnalysisCache::new(db, pair, timeframe, &(&config.engine_config, &config.pattern_config))
+    });
+    match cache {
+        Ok(cache) => Some(cache.with_refresh(no_cache)),
+        Err(e) => {
+            warn!("⚠️  Analysis cache unavailable ({}), computing from scratch", e);
+            None
+        }
+    }
+}
+
+/// Arguments of the `backtest` command
+struct BacktestRequest {
+    strategy: PathBuf,
     start_date: String,
     end_date: String,
-    initial_capital: f64,
-    config: Configuration,
-) -> Result<()> {
-    info!("🧪 Running backtest validation from {} to {}", start_date, end_date);
-    info!("💰 Initial capital: ${:.2}", initial_capital);
+    capital: f64,
+    input: PathBuf,
+    pair: String,
+    timeframe: String,
+    output: Option<PathBuf>,
+    journal: Option<PathBuf>,
+    ticks: Option<PathBuf>,
+    symmetries: Option<PathBuf>,
+    no_cache: bool,
+    optimize: Option<PathBuf>,
+}
+
+/// Run backtesting to validate temporal symmetries
+async fn run_backtest_validation(request: BacktestRequest, config: Configuration) -> Result<()> {
+    info!("🧪 Running backtest validation from {} to {}", request.start_date, request.end_date);
+    info!("💰 Initial capital: ${:.2}", request.capital);
     
     // Load strategy configuration
-    let strategy_config = backtest::load_strategy_config(&strategy_path)?;
+    let strategy_config = backtest::load_strategy_config(&request.strategy)?;
+    let registry = backtest::strategy::StrategyRegistry::new();
+    let mut strategy = registry.create(&strategy_config)?;
+    info!("🧠 Strategy: {}", strategy.name());
     
     // Initialize backtesting engine
-    let mut backtest_engine = backtest::BacktestEngine::new(
+    let backtest_engine = backtest::BacktestEngine::new(
         strategy_config,
-        initial_capital,
-        config.backtest_config,
-    )?;
+        request.capital,
+        config.backtest_config.clone(),
+    )?
+    .with_trading_windows(config.trading_windows.clone())
+    .with_holiday_calendar(config.holiday_calendar.clone())
+    .with_pattern_config(config.pattern_config.clone());
     
-    // Run temporal symmetry validation
-    let validation_results = backtest_engine.validate_temporal_symmetries(
-        &start_date,
-        &end_date,
-    ).await?;
+    // Bars in the requested date range
+    let start = chrono::NaiveDate::parse_from_str(&request.start_date, "%Y-%m-%d")?.and_hms_opt(0, 0, 0).unwrap().and_utc();
+    let end = chrono::NaiveDate::parse_from_str(&request.end_date, "%Y-%m-%d")?.and_hms_opt(23, 59, 59).unwrap().and_utc();
+    let mut data_manager = ForexDataManager::new(config.data_config.clone())?;
+    let (forex_data, spreads): (Vec<data::ForexDataPoint>, Vec<f64>) = match &request.ticks {
+        Some(tick_file) => {
+            let ticks = data_manager.load_tick_csv(tick_file)?;
+            let bars = data::tick::resample_ticks(&ticks, &data::timeframe::TimeframeAggregator::new(&request.timeframe)?)?;
+            info!("🧾 {} ticks folded into {} {} bars", ticks.len(), bars.len(), request.timeframe);
+            bars.into_iter()
+                .filter(|b| b.bar.timestamp >= start && b.bar.timestamp <= end)
+                .map(|b| (b.bar, b.close_spread))
+                .unzip()
+        }
+        None => data_manager.load_data(&request.input, &request.pair, &request.timeframe).await?
+            .into_iter()
+            .filter(|p| p.timestamp >= start && p.timestamp <= end)
+            .map(|p| (p, f64::NAN))
+            .unzip(),
+    };
+    let warmup = config.backtest_config.warmup_bars;
+    if forex_data.len() <= warmup {
+        return Err(anyhow::anyhow!("{} bars between {} and {}, need more than the {} warm-up bars",
+                                   forex_data.len(), request.start_date, request.end_date, warmup));
+    }
+    let imported = request.symmetries.as_ref()
+        .map(|path| load_symmetry_set(path, &request.pair, None, &config))
+        .transpose()?;
+    if let Some(end) = imported.as_ref().and_then(|set| set.provenance.data_end) {
+        if end >= forex_data[warmup].timestamp {
+            warn!("⚠️  Symmetry set was decoded from bars up to {}, inside the test period", end.format("%Y-%m-%d"));
+        }
+    }
+    
+    let cache = analysis_cache(&config, &request.pair, &request.timeframe, request.no_cache);
+    let anomalies = detect_test_anomalies(&config, &request.pair, &forex_data, imported.as_ref(), cache.as_ref()).await?;
+    info!("🚨 {} anomalies in the test period", anomalies.len());
+    
+    if let Some(path) = &request.optimize {
+        let optimizer_config = backtest::optimizer::load_optimizer_config(path)?;
+        info!("🧭 Walk-forward optimizing {} parameters ({}, {})", optimizer_config.parameters.len(), optimizer_config.method, optimizer_config.objective);
+        let optimizer = backtest::optimizer::WalkForwardOptimizer::new(&backtest_engine, optimizer_config)?;
+        let report = optimizer.optimize(&request.pair, &forex_data, &spreads, &anomalies).await?;
+        report.print_windows();
+        report.print_stability();
+        info!("📊 {}", report.summary());
+        info!("  Out-of-sample Return: {:.2}% over {} backtests", report.out_of_sample_return() * 100.0, report.evaluations());
+        if let Some(output) = &request.output {
+            write_json(output, &report)?;
+            info!("📄 Optimization report saved to: {}", output.display());
+        }
+        return Ok(());
+    }
+    
+    let run = backtest_engine.run_with_spreads(strategy.as_mut(), &request.pair, &forex_data, &spreads, &anomalies).await?;
+    let validation_results = run.results.clone();
+    info!("📒 {} fills", run.trades.len());
+    if run.thin_market_bars > 0 {
+        info!("🎄 {} bars in thin holiday markets ({:?} policy)", run.thin_market_bars, config.holiday_calendar.policy);
+    }
+    let execution = &run.execution;
+    if execution.fills < execution.orders || execution.requotes > 0 {
+        info!("🧾 {} of {} orders filled: {} partial, {} rejected, {} requoted, {:.0} units unfilled, {:.0} ms mean latency",
+              execution.fills, execution.orders, execution.partial_fills, execution.rejections, execution.requotes,
+              execution.unfilled_units, execution.mean_latency_ms);
+    }
+    for event in run.margin_events.iter().filter(|event| event.kind != MarginEventKind::LeverageLimit) {
+        warn!("{} {} {}", if event.kind == MarginEventKind::StopOut { "💥 STOP-OUT" } else { "🚨 MARGIN CALL" },
+              event.timestamp.format("%Y-%m-%d %H:%M"), event.summary());
+    }
+    let leverage_cuts = run.margin_events.iter().filter(|event| event.kind == MarginEventKind::LeverageLimit).count();
+    if leverage_cuts > 0 {
+        info!("⚖️  {} orders cut to the {}:1 leverage limit", leverage_cuts, config.backtest_config.leverage);
+    }
     
     // Display results
     info!("📊 Backtest Results:");
@@ -255,6 +1009,164 @@ async fn run_backtest_validation(
         info!("📊 Pattern Consistency: {:.3} (target: >0.80)", validation_results.pattern_consistency);
     }
     
+    if let Some(output) = &request.output {
+        write_json(output, &validation_results)?;
+        info!("📄 Backtest results saved to: {}", output.display());
+    }
+    if let Some(journal) = &request.journal {
+        write_json(journal, &run.trades)?;
+        info!("📒 Trade journal saved to: {}", journal.display());
+    }
+    
+    Ok(())
+}
+
+/// Anomalies after the warm-up, judged only against expectations from earlier bars
+async fn detect_test_anomalies(
+    config: &Configuration,
+    pair: &str,
+    forex_data: &[data::ForexDataPoint],
+    imported: Option<&symmetry::SymmetrySet>,
+    cache: Option<&embedded_db::AnalysisCache>,
+) -> Result<Vec<anomaly::DetectedAnomaly>> {
+    let warmup = config.backtest_config.warmup_bars;
+    let economic_calendar = calendar::economic::EconomicCalendar::from_config(&config.economic_calendar)?;
+    if !economic_calendar.is_empty() {
+        info!("📅 {} economic events loaded", economic_calendar.events().len());
+    }
+    Ok(match config.backtest_config.anomaly_baseline {
+        backtest::walk_forward::AnomalyBaseline::WalkForward => {
+            let mut detector = backtest::walk_forward::WalkForwardDetector::new(
+                config.engine_config.clone(),
+                config.pattern_config.clone(),
+                anomaly::AnomalyDetectionConfig::default(),
+                config.backtest_config.baseline_refit_bars,
+            )?
+            .with_holiday_calendar(pair, config.holiday_calendar.clone())
+            .with_economic_calendar(pair, economic_calendar);
+            if let Some(set) = imported {
+                detector = detector.with_symmetries(set.symmetries.clone());
+            }
+            if let Some(cache) = cache {
+                detector = detector.with_analysis_cache(cache.clone());
+            }
+            let detection = detector.detect(forex_data, warmup).await?;
+            info!("🚶 Baselines refitted {} times, every {} bars", detection.refits.len(), config.backtest_config.baseline_refit_bars);
+            detection.anomalies
+        }
+        backtest::walk_forward::AnomalyBaseline::WarmUp => {
+            let baseline = &forex_data[..warmup];
+            let symmetries = match (imported, cache.map(|cache| cache.symmetries(baseline)).transpose()?.flatten()) {
+                (Some(set), _) => set.symmetries.clone(),
+                (None, Some(symmetries)) => symmetries,
+                (None, None) => {
+                    let mut engine = TimeSymmetricEngine::new(config.engine_config.clone())?;
+                    engine.initialize().await?;
+                    let symmetries = engine.extract_temporal_symmetries(baseline).await?;
+                    if let Some(cache) = cache {
+                        cache.store_symmetries(baseline, &symmetries)?;
+                    }
+                    symmetries
+                }
+            };
+            let cycles = match cache.map(|cache| cache.cycles(baseline)).transpose()?.flatten() {
+                Some(cycles) => cycles,
+                None => {
+                    let cycles = PatternRecognizer::new(config.pattern_config.clone())?.detect_cycles(baseline).await?;
+                    if let Some(cache) = cache {
+                        cache.store_cycles(baseline, &cycles)?;
+                    }
+                    cycles
+                }
+            };
+            let mut detector = anomaly::TemporalAnomalyDetector::new(symmetries, cycles, baseline, anomaly::AnomalyDetectionConfig::default())?
+                .with_holiday_calendar(pair, config.holiday_calendar.clone())
+                .with_economic_calendar(pair, economic_calendar);
+            detector.detect_anomalies(&forex_data[warmup..]).await?
+        }
+    })
+}
+
+/// Arguments of the `train-rl` command
+struct TrainRlRequest {
+    input: PathBuf,
+    pair: String,
+    timeframe: String,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    episodes: usize,
+    checkpoint: PathBuf,
+    strategy: Option<PathBuf>,
+    capital: f64,
+    seed: Option<u64>,
+    output: Option<PathBuf>,
+}
+
+/// Train the RL agent by backtesting it over the history again and again
+async fn train_rl_agent(request: TrainRlRequest, config: Configuration) -> Result<()> {
+    info!("🎓 Training the Laplacian RL agent on {} for {} episodes", request.pair, request.episodes);
+    
+    let day = |date: &Option<String>, (hour, minute, second)| -> Result<Option<chrono::DateTime<chrono::Utc>>> {
+        date.as_deref()
+            .map(|date| Ok(chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")?.and_hms_opt(hour, minute, second).unwrap().and_utc()))
+            .transpose()
+    };
+    let (start, end) = (day(&request.start_date, (0, 0, 0))?, day(&request.end_date, (23, 59, 59))?);
+    let mut data_manager = ForexDataManager::new(config.data_config.clone())?;
+    let forex_data: Vec<data::ForexDataPoint> = data_manager.load_data(&request.input, &request.pair, &request.timeframe).await?
+        .into_iter()
+        .filter(|p| start.is_none_or(|start| p.timestamp >= start) && end.is_none_or(|end| p.timestamp <= end))
+        .collect();
+    let warmup = config.backtest_config.warmup_bars;
+    if forex_data.len() <= warmup {
+        return Err(anyhow::anyhow!("{} bars of {}, need more than the {} warm-up bars", forex_data.len(), request.pair, warmup));
+    }
+    let anomalies = detect_test_anomalies(&config, &request.pair, &forex_data, None, None).await?;
+    info!("🚨 {} anomalies in {} bars", anomalies.len(), forex_data.len());
+    
+    let strategy_config = match &request.strategy {
+        Some(path) => backtest::load_strategy_config(path)?,
+        None => backtest::StrategyConfig::default(),
+    };
+    let agent = backtest::strategy::RlAgentStrategy::new(&strategy_config)?.into_agent();
+    let engine = backtest::BacktestEngine::new(strategy_config, request.capital, config.backtest_config.clone())?
+        .with_trading_windows(config.trading_windows.clone())
+        .with_holiday_calendar(config.holiday_calendar.clone())
+        .with_pattern_config(config.pattern_config.clone());
+    let training = backtest::rl_training::RlTrainingConfig {
+        episodes: request.episodes,
+        seed: request.seed,
+        ..Default::default()
+    };
+    if request.checkpoint.exists() {
+        info!("♻️  Resuming from {}", request.checkpoint.display());
+    }
+    let trainer = backtest::rl_training::RlTrainer::new(&engine, training);
+    let (agent, curve) = trainer.train(agent, &request.pair, &forex_data, &[], &anomalies, Some(&request.checkpoint), |episode| {
+        info!("  Episode {:>3}: reward {:+8.3} over {:>3} decisions, return {:+6.2}%, drawdown {:5.2}%, ε {:.3}, {} Q-values",
+              episode.episode, episode.total_reward, episode.rewards, episode.total_return * 100.0,
+              episode.max_drawdown * 100.0, episode.exploration_rate, episode.q_values);
+    }).await?;
+    info!("📈 Learning curve:");
+    if let Some((first, last)) = curve.reward_trend(5) {
+        info!("  Mean episode reward {:+.3} in the first episodes, {:+.3} in the last", first, last);
+    }
+    info!("  {} Q-values, {} experiences, {} graph nodes", agent.q_backend().size(), agent.replay_buffer().len(), agent.graph().len());
+    info!("💾 Agent checkpoint saved to: {}", request.checkpoint.display());
+    if let Some(output) = &request.output {
+        write_json(output, &curve)?;
+        info!("📄 Learning curve saved to: {}", output.display());
+    }
+    
+    Ok(())
+}
+
+/// Write `value` as pretty JSON, creating the parent directory
+fn write_json<T: serde::Serialize>(path: &std::path::Path, value: &T) -> Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(value)?)?;
     Ok(())
 }
 
@@ -262,7 +1174,9 @@ async fn run_backtest_validation(
 async fn launch_pattern_dashboard(
     feed_config: Option<PathBuf>,
     port: u16,
-    config: Configuration,
+    load_model: Option<PathBuf>,
+    save_model: Option<PathBuf>,
+    mut config: Configuration,
 ) -> Result<()> {
     info!("🚀 Launching real-time pattern recognition dashboard on port {}", port);
     
@@ -273,6 +1187,10 @@ async fn launch_pattern_dashboard(
         data::RealTimeDataFeed::default().await?
     };
     
+    // Flags take precedence over the configured model directories
+    config.dashboard_config.load_model = load_model.or(config.dashboard_config.load_model);
+    config.dashboard_config.save_model = save_model.or(config.dashboard_config.save_model);
+    
     // Launch dashboard
     visualization::launch_tui_dashboard(data_feed, port, config.dashboard_config).await?;
     
@@ -309,10 +1227,12 @@ async fn decompose_eur_usd_cycles(
     let decomposition = decomposer.decompose_cycles(&eur_usd_data, &target_cycles).await?;
     
     info!("✅ Decomposition complete:");
-    for (cycle_period, component) in &decomposition.components {
+    for cycle_period in decomposition.periods() {
+        let component = &decomposition.components[&cycle_period];
         info!("  🔄 {}-day cycle: amplitude={:.4}, phase={:.2}°, strength={:.3}",
               cycle_period, component.amplitude, component.phase_degrees, component.strength);
     }
+    info!("  📉 Residual: σ={:.5} over {} bars", decomposition.residual_std(), decomposition.timestamps.len());
     
     // Save results in requested format
     match format.as_str() {
@@ -329,6 +1249,14 @@ async fn decompose_eur_usd_cycles(
             visualization::plot_cycle_decomposition(&decomposition, "eur_usd_cycles.png")?;
             info!("📊 Plot saved to: eur_usd_cycles.png");
         },
+        "svg" => {
+            visualization::plot_cycle_decomposition(&decomposition, "eur_usd_cycles.svg")?;
+            info!("📊 Plot saved to: eur_usd_cycles.svg");
+        },
+        "html" => {
+            std::fs::write("eur_usd_decomposition.html", report::analysis::render_decomposition_html("EUR/USD", &decomposition))?;
+            info!("📄 Report saved to: eur_usd_decomposition.html");
+        },
         _ => {
             error!("❌ Unsupported format: {}", format);
             return Err(anyhow::anyhow!("Unsupported output format"));
@@ -338,6 +1266,646 @@ async fn decompose_eur_usd_cycles(
     Ok(())
 }
 
+/// Generate a seeded demo dataset on disk (and optionally in an embedded database)
+async fn generate_demo_data(
+    pairs_str: String,
+    years: u32,
+    seed: u64,
+    output: PathBuf,
+    db_path: Option<PathBuf>,
+    config: Configuration,
+) -> Result<()> {
+    info!("🧪 Generating {} years of demo data (seed {})", years, seed);
+    
+    let pairs = report::parse_pairs(&pairs_str)?;
+    let data_manager = ForexDataManager::new(config.data_config)?;
+    std::fs::create_dir_all(&output)?;
+    let db = match &db_path {
+        Some(path) => {
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                std::fs::create_dir_all(parent)?;
+            }
+            Some(embedded_db::EmbeddedForexDB::open(path)?)
+        }
+        None => None,
+    };
+    
+    let end = chrono::Utc::now();
+    for pair in pairs {
+        let demo_data = synthetic::demo::generate_demo_history(&pair, end, years * 365, seed).await?;
+        
+        let csv_path = output.join(format!("{}.csv", pair));
+        data_manager.save_csv_file(&csv_path, &demo_data)?;
+        info!("💾 {}: {} bars written to {}", pair, demo_data.len(), csv_path.display());
+        
+        if let Some(db) = &db {
+            db.append_forex_data(&pair, &demo_data)?;
+        }
+    }
+    
+    if let Some(path) = db_path {
+        info!("🗄️  Demo data stored in {}", path.display());
+    }
+    info!("✅ Demo dataset ready in {}", output.display());
+    
+    Ok(())
+}
+
+/// Run the pipeline benchmark on each requested dataset size; fails when a size misses an injected cycle
+async fn bench_pipeline(
+    sizes: String,
+    seed: u64,
+    pipeline: Option<PathBuf>,
+    output: Option<PathBuf>,
+    export: Option<PathBuf>,
+    config: Configuration,
+) -> Result<()> {
+    let sizes: Vec<synthetic::benchmark::BenchmarkSize> = sizes
+        .split(',')
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(str::parse)
+        .collect::<Result<_>>()?;
+    let pipeline_config = match pipeline {
+        Some(path) => PipelineConfig::from_file(&path)?,
+        None => PipelineConfig {
+            data: config.data_config.clone(),
+            engine: config.engine_config.clone(),
+            patterns: config.pattern_config.clone(),
+            ..PipelineConfig::default()
+        },
+    };
+    
+    let mut runs = Vec::new();
+    for size in sizes {
+        let dataset = synthetic::benchmark::generate_benchmark(size, seed);
+        info!("⏱️  Benchmark {}: {} bars, seed {}", size.label(), dataset.bars.len(), seed);
+        
+        if let Some(dir) = &export {
+            std::fs::create_dir_all(dir)?;
+            ForexDataManager::new(config.data_config.clone())?
+                .save_csv_file(&dir.join(format!("{}.csv", size.label())), &dataset.bars)?;
+            std::fs::write(dir.join(format!("{}.cycles.json", size.label())), serde_json::to_string_pretty(&dataset.cycles)?)?;
+        }
+        
+        let run = forex_pattern_reconstruction::pipeline::bench::run_benchmark(&dataset, &pipeline_config).await?;
+        for stage in &run.stages {
+            info!("  {:<22} {:>9.3}s  {:>12.0} bars/s", stage.stage, stage.seconds, stage.bars_per_second);
+        }
+        for recovery in &run.recoveries {
+            match recovery.detected_period {
+                Some(period) => info!("  ✅ {}-bar cycle recovered as {} bars (confidence {:.3})",
+                                      recovery.injected_period, period, recovery.confidence.unwrap_or_default()),
+                None => warn!("  ❌ {}-bar cycle not detected", recovery.injected_period),
+            }
+        }
+        info!("  Total {:.3}s: {} symmetries, {} cycles, {} synthetic points, {} anomalies",
+              run.total_seconds(), run.symmetries, run.cycles, run.synthetic_points, run.anomalies);
+        runs.push(run);
+    }
+    
+    if let Some(path) = output {
+        std::fs::write(&path, serde_json::to_string_pretty(&runs)?)?;
+        info!("💾 Benchmark results saved to {}", path.display());
+    }
+    
+    let failed: Vec<&str> = runs.iter().filter(|r| !r.passed()).map(|r| r.size.label()).collect();
+    if !failed.is_empty() {
+        return Err(anyhow::anyhow!("Injected cycles not recovered on size(s) {}", failed.join(", ")));
+    }
+    info!("✅ Every injected cycle recovered");
+    
+    Ok(())
+}
+
+/// Back up, restore or check the embedded database
+fn run_db_command(command: DbCommands) -> Result<()> {
+    match command {
+        DbCommands::Backup { path, db } => {
+            if !db.exists() {
+                anyhow::bail!("database {} not found", db.display());
+            }
+            let database = embedded_db::EmbeddedForexDB::open(&db)?;
+            database.backup(&path)?;
+            info!("💾 Backed up {} to {}", db.display(), path.display());
+        },
+        
+        DbCommands::Restore { path, db } => {
+            let report = embedded_db::EmbeddedForexDB::restore(&path, &db)?;
+            info!("♻️  Restored {} from {} ({} series verified)", db.display(), path.display(), report.series_checked);
+        },
+        
+        DbCommands::Check { db } => {
+            if !db.exists() {
+                anyhow::bail!("database {} not found", db.display());
+            }
+            let report = embedded_db::EmbeddedForexDB::open(&db)?.check()?;
+            for problem in report.integrity_errors.iter().chain(&report.corrupt_series) {
+                error!("❌ {}", problem);
+            }
+            if !report.is_ok() {
+                anyhow::bail!("database {} failed consistency check", db.display());
+            }
+            info!("✅ {} is consistent ({} series checked)", db.display(), report.series_checked);
+        },
+    }
+    
+    Ok(())
+}
+
+/// Re-run a session log's decisions and report any that differ from the recording
+async fn replay_recorded_session(log: PathBuf, output: Option<PathBuf>) -> Result<()> {
+    info!("🎞️  Replaying session {}", log.display());
+    let report = replay::replay_session(&log).await?;
+    
+    for divergence in &report.divergences {
+        error!("❌ line {} {} at {}: {} (recorded {:?}, replayed {:?})",
+               divergence.line, divergence.symbol, divergence.recorded_at.format("%Y-%m-%d %H:%M:%S"),
+               divergence.reason, divergence.recorded, divergence.replayed);
+    }
+    if let Some(path) = &output {
+        std::fs::write(path, serde_json::to_string_pretty(&report)?)?;
+        info!("💾 Replay report written to {}", path.display());
+    }
+    if !report.is_identical() {
+        anyhow::bail!("{} of {} updates diverged from the recording", report.divergences.len(), report.updates);
+    }
+    info!("✅ {} updates and {} fills of {} replayed identically ({} actions)",
+          report.updates, report.fills, report.pairs.join(", "), report.actions);
+    
+    Ok(())
+}
+
+/// Generate the daily summary report, once or on a schedule
+#[allow(clippy::too_many_arguments)]
+async fn generate_daily_report(
+    input: PathBuf,
+    pairs_str: String,
+    date: Option<String>,
+    trades_path: Option<PathBuf>,
+    output: Option<PathBuf>,
+    format: Option<String>,
+    webhook: Option<String>,
+    schedule: bool,
+    config: Configuration,
+) -> Result<()> {
+    let mut report_config = config.report_config.clone();
+    if let Some(output) = output {
+        report_config.output_directory = output;
+    }
+    if let Some(format) = format {
+        report_config.format = format.parse()?;
+    }
+    if webhook.is_some() {
+        report_config.webhook_url = webhook;
+    }
+    
+    let pairs = report::parse_pairs(&pairs_str)?;
+    
+    let generator = report::DailyReportGenerator::new(report_config)?
+        .with_holiday_calendar(config.holiday_calendar.clone());
+    
+    if schedule {
+        info!("⏰ Running daily report scheduler for {:?}", pairs);
+        return generator.run_schedule(|date| {
+            collect_daily_report_input(input.clone(), pairs.clone(), date, trades_path.clone(), config.clone())
+        }).await;
+    }
+    
+    let date = match date {
+        Some(date) => Some(chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d")?),
+        None => None,
+    };
+    
+    // Without an explicit date, report on the most recent day present in the data
+    let date = match date {
+        Some(date) => date,
+        None => {
+            let mut data_manager = data::ForexDataManager::new(config.data_config.clone())?;
+            let data = data_manager.load_data(&input, &pairs[0], "1D").await?;
+            data.last()
+                .map(|p| p.timestamp.date_naive())
+                .unwrap_or_else(|| chrono::Utc::now().date_naive())
+        }
+    };
+    
+    info!("📰 Compiling daily report for {}", date);
+    let report_input = collect_daily_report_input(input, pairs, date, trades_path, config).await?;
+    let daily_report = generator.generate(date, &report_input).await?;
+    
+    info!("✅ Daily report: {} anomalies, {} trades, net P&L {:.2}, {} symmetry warnings",
+          daily_report.anomaly_count,
+          daily_report.trades.len(),
+          daily_report.profit_loss.net_profit_loss,
+          daily_report.symmetry_warnings.len());
+    
+    Ok(())
+}
+
+/// Gather anomalies, trades, symmetries and feed health for a daily report
+async fn collect_daily_report_input(
+    input: PathBuf,
+    pairs: Vec<String>,
+    date: chrono::NaiveDate,
+    trades_path: Option<PathBuf>,
+    config: Configuration,
+) -> Result<report::DailyReportInput> {
+    let mut report_input = report::DailyReportInput::default();
+    let day_end = date.and_hms_opt(23, 59, 59).unwrap().and_utc();
+    
+    for pair in &pairs {
+        let mut data_manager = ForexDataManager::new(config.data_config.clone())?;
+        let forex_data = match data_manager.load_data(&input, pair, "1D").await {
+            Ok(data) => data,
+            Err(e) => {
+                warn!("⚠️  {}: no data available ({})", pair, e);
+                report_input.feeds.push(report::FeedHealth::assess(
+                    pair, &[], date, day_end, config.report_config.stale_feed_minutes));
+                continue;
+            }
+        };
+        
+        let history: Vec<data::ForexDataPoint> = forex_data.into_iter()
+            .filter(|p| p.timestamp <= day_end)
+            .collect();
+        report_input.feeds.push(report::FeedHealth::assess(
+            pair, &history, date, day_end, config.report_config.stale_feed_minutes));
+        
+        let split = history.iter()
+            .position(|p| p.timestamp.date_naive() >= date)
+            .unwrap_or(history.len());
+        if split < 2 || split == history.len() {
+            continue;
+        }
+        
+        // Expectations come from everything before the report date
+        let baseline = &history[..split];
+        let mut engine = TimeSymmetricEngine::new(config.engine_config.clone())?;
+        engine.initialize().await?;
+        let symmetries = engine.extract_temporal_symmetries(baseline).await?;
+        let mut pattern_recognizer = PatternRecognizer::new(config.pattern_config.clone())?;
+        let cycles = pattern_recognizer.detect_cycles(baseline).await?;
+        
+        let mut detector = anomaly::TemporalAnomalyDetector::new(
+            symmetries.clone(),
+            cycles,
+            baseline,
+            anomaly::AnomalyDetectionConfig::default(),
+        )?
+        .with_holiday_calendar(pair, config.holiday_calendar.clone());
+        
+        // Include a detection window of history so the day's points have context
+        let window_start = split.saturating_sub(anomaly::AnomalyDetectionConfig::default().detection_window_size);
+        report_input.anomalies.extend(detector.detect_anomalies(&history[window_start..]).await?);
+        report_input.symmetries.extend(symmetries);
+    }
+    
+    if let Some(trades_path) = trades_path {
+        let journal = std::fs::read_to_string(&trades_path)?;
+        let trades: Vec<report::TradeRecord> = serde_json::from_str(&journal)?;
+        report_input.trades.extend(trades);
+    }
+    
+    Ok(report_input)
+}
+
+/// Compile the research digest of one week, or of every week on a schedule
+async fn generate_weekly_digest(
+    input: PathBuf,
+    pairs_str: String,
+    week: Option<String>,
+    output: Option<PathBuf>,
+    schedule: bool,
+    config: Configuration,
+) -> Result<()> {
+    let mut digest_config = config.digest_config.clone();
+    if let Some(output) = output {
+        digest_config.output_directory = output;
+    }
+    
+    let pairs = report::parse_pairs(&pairs_str)?;
+    
+    let generator = report::digest::DigestGenerator::new(digest_config);
+    let collect = |week_start| collect_weekly_snapshot(input.clone(), pairs.clone(), week_start, config.clone());
+    
+    if schedule {
+        info!("⏰ Running research digest scheduler for {:?}", pairs);
+        return generator.run_schedule(collect).await;
+    }
+    
+    // Without an explicit week, digest the most recent week present in the data
+    let day = match week {
+        Some(day) => chrono::NaiveDate::parse_from_str(&day, "%Y-%m-%d")?,
+        None => {
+            let mut data_manager = data::ForexDataManager::new(config.data_config.clone())?;
+            let data = data_manager.load_data(&input, &pairs[0], "1D").await?;
+            data.last()
+                .map(|p| p.timestamp.date_naive())
+                .unwrap_or_else(|| chrono::Utc::now().date_naive())
+        }
+    };
+    let week_start = report::digest::week_start_of(day);
+    
+    info!("🔭 Compiling research digest for the week of {}", week_start);
+    let digest = generator.generate(week_start, collect).await?;
+    
+    let count = |status| digest.with_status(status).count();
+    info!("✅ Research digest: {} emerged, {} vanished, {} strengthened, {} weakened",
+          count(report::digest::ChangeStatus::Emerged),
+          count(report::digest::ChangeStatus::Vanished),
+          count(report::digest::ChangeStatus::Strengthened),
+          count(report::digest::ChangeStatus::Weakened));
+    
+    Ok(())
+}
+
+/// Detect each pair's symmetries and cycles on the history up to the end of the week
+/// starting on `week_start`, and the week's anomalies against the history before it
+async fn collect_weekly_snapshot(
+    input: PathBuf,
+    pairs: Vec<String>,
+    week_start: chrono::NaiveDate,
+    config: Configuration,
+) -> Result<report::digest::WeeklySnapshot> {
+    let mut snapshot = report::digest::WeeklySnapshot::new(week_start);
+    let start = week_start.and_hms_opt(0, 0, 0).unwrap().and_utc();
+    let end = start + chrono::Duration::weeks(1);
+    
+    for pair in &pairs {
+        let mut data_manager = ForexDataManager::new(config.data_config.clone())?;
+        let forex_data = match data_manager.load_data(&input, pair, "1D").await {
+            Ok(data) => data,
+            Err(e) => {
+                warn!("⚠️  {}: no data available ({})", pair, e);
+                continue;
+            }
+        };
+        
+        let history: Vec<data::ForexDataPoint> = forex_data.into_iter()
+            .filter(|p| p.timestamp < end)
+            .collect();
+        let split = history.iter().position(|p| p.timestamp >= start).unwrap_or(history.len());
+        if split < 2 || split == history.len() {
+            continue;
+        }
+        
+        let mut engine = TimeSymmetricEngine::new(config.engine_config.clone())?;
+        engine.initialize().await?;
+        let symmetries = engine.extract_temporal_symmetries(&history).await?;
+        let mut pattern_recognizer = PatternRecognizer::new(config.pattern_config.clone())?;
+        let cycles = pattern_recognizer.detect_cycles(&history).await?;
+        
+        // The week's anomalies are measured against expectations from before it
+        let baseline = &history[..split];
+        let baseline_symmetries = engine.extract_temporal_symmetries(baseline).await?;
+        let baseline_cycles = pattern_recognizer.detect_cycles(baseline).await?;
+        let mut detector = anomaly::TemporalAnomalyDetector::new(
+            baseline_symmetries,
+            baseline_cycles,
+            baseline,
+            anomaly::AnomalyDetectionConfig::default(),
+        )?
+        .with_holiday_calendar(pair, config.holiday_calendar.clone());
+        let window_start = split.saturating_sub(anomaly::AnomalyDetectionConfig::default().detection_window_size);
+        let anomalies: Vec<anomaly::DetectedAnomaly> = detector.detect_anomalies(&history[window_start..]).await?
+            .into_iter()
+            .filter(|a| a.timestamp >= start)
+            .collect();
+        
+        snapshot.pairs.insert(pair.clone(), report::digest::PairSnapshot {
+            symmetries,
+            cycles,
+            anomalies: report::digest::AnomalyStatistics::from_anomalies(&anomalies),
+        });
+    }
+    
+    Ok(snapshot)
+}
+
+/// Write an evidence dossier for every pair with an analysis report
+async fn generate_symmetry_dossiers(
+    analysis: PathBuf,
+    input: PathBuf,
+    trades_path: Option<PathBuf>,
+    backtest_path: Option<PathBuf>,
+    output: Option<PathBuf>,
+    config: Configuration,
+) -> Result<()> {
+    let mut dossier_config = config.dossier_config.clone();
+    if let Some(output) = output {
+        dossier_config.output_directory = output;
+    }
+    let generator = report::dossier::DossierGenerator::new(dossier_config)?
+        .with_holiday_calendar(config.holiday_calendar.clone());
+    
+    let analysis_files: Vec<PathBuf> = if analysis.is_dir() {
+        let mut files: Vec<PathBuf> = std::fs::read_dir(&analysis)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.ends_with("_analysis.json")))
+            .collect();
+        files.sort();
+        files
+    } else {
+        vec![analysis.clone()]
+    };
+    if analysis_files.is_empty() {
+        return Err(anyhow::anyhow!("No *_analysis.json reports found in {} (run `analyze` first)", analysis.display()));
+    }
+    
+    let trades: Vec<report::TradeRecord> = match &trades_path {
+        Some(path) => serde_json::from_str(&std::fs::read_to_string(path)?)?,
+        None => Vec::new(),
+    };
+    let backtest: Option<backtest::ValidationResults> = match &backtest_path {
+        Some(path) => Some(serde_json::from_str(&std::fs::read_to_string(path)?)?),
+        None => None,
+    };
+    
+    for path in analysis_files {
+        let artifact = report::dossier::AnalysisArtifact::load(&path)?;
+        let pair = artifact.pair.clone().unwrap_or_else(|| "EURUSD".to_string());
+        let timeframe = artifact.timeframe.clone().unwrap_or_else(|| "1D".to_string());
+        
+        let mut data_manager = ForexDataManager::new(config.data_config.clone())?;
+        let forex_data = match data_manager.load_data(&input, &pair, &timeframe).await {
+            Ok(data) => data,
+            Err(e) => {
+                warn!("⚠️  {}: no price data for dossier ({})", pair, e);
+                continue;
+            }
+        };
+        
+        let pair_trades: Vec<report::TradeRecord> = trades.iter().filter(|t| t.pair.eq_ignore_ascii_case(&pair)).cloned().collect();
+        let dangling = report::dangling_references(&artifact.temporal_symmetries, &artifact.hidden_cycles, &[], &pair_trades);
+        if !dangling.is_empty() {
+            warn!("🔗 {}: {} trade links to symmetries or cycles not in this analysis (e.g. {} from {}) stay unattributed",
+                  pair, dangling.len(), dangling[0].missing_id, dangling[0].source);
+        }
+        
+        let dossier = generator.compile(&pair, &timeframe, &artifact.temporal_symmetries, &forex_data, &trades, backtest.as_ref());
+        let written = generator.write(&dossier)?;
+        info!("📄 {} dossier ({} symmetries) written to {}", pair, dossier.evidence.len(), written.display());
+        let flagged = dossier.evidence.iter().filter(|e| e.holiday_note.is_some()).count();
+        if flagged > 0 {
+            warn!("🎄 {}: {} symmetries may be holiday artifacts", pair, flagged);
+        }
+    }
+    
+    Ok(())
+}
+
+/// Rank every pair of a data directory by its symmetries and list the cycles they share
+async fn scan_data_directory(
+    input: PathBuf,
+    timeframe: String,
+    top: Option<usize>,
+    jobs: Option<usize>,
+    output: Option<PathBuf>,
+    config: Configuration,
+) -> Result<()> {
+    let mut scan_config = config.scan_config.clone();
+    if let Some(top) = top {
+        scan_config.top_symmetries = top;
+    }
+    if let Some(jobs) = jobs {
+        scan_config.max_parallel = jobs;
+    }
+    let data_config = data::DataConfig { data_directory: input, ..config.data_config.clone() };
+    
+    let summary = ForexDataManager::new(data_config.clone())?.get_data_summary().await?;
+    info!("🔭 Scanning {} pairs in {}", summary.available_pairs.len(), data_config.data_directory.display());
+    let scan = report::scan::scan_pairs(&summary, &timeframe, &data_config, &config.engine_config, &config.pattern_config, &scan_config).await?;
+    
+    for line in scan.lines() {
+        info!("  {}", line);
+    }
+    if let Some(output) = &output {
+        match output.extension().and_then(|ext| ext.to_str()).map(|ext| ext.to_lowercase()).as_deref() {
+            Some("csv") => scan.write_csv(output)?,
+            Some("json") => write_json(output, &scan)?,
+            _ => return Err(anyhow::anyhow!("Unsupported scan output {} (.json or .csv)", output.display())),
+        }
+        info!("📄 Scan summary saved to: {}", output.display());
+    }
+    
+    Ok(())
+}
+
+/// Arguments of the `generate` command
+struct GenerateRequest {
+    input: PathBuf,
+    pair: String,
+    timeframe: String,
+    days: u32,
+    seed: Option<u64>,
+    output: PathBuf,
+    format: Option<String>,
+}
+
+/// Generate synthetic bars continuing a pair's history and export them with their confidence
+async fn generate_synthetic_data(request: GenerateRequest, config: Configuration) -> Result<()> {
+    let GenerateRequest { input, pair, timeframe, days, seed, output, format } = request;
+    let format = match format {
+        Some(format) => format.parse()?,
+        None => synthetic::export::ExportFormat::from_path(&output).ok_or_else(|| {
+            anyhow::anyhow!("Cannot tell the export format of {}; pass --format csv, parquet or json", output.display())
+        })?,
+    };
+    let resolution = data::provider::timeframe_duration(&timeframe)?;
+    
+    let mut data_manager = ForexDataManager::new(config.data_config.clone())?;
+    let forex_data = data_manager.load_data(&input, &pair, &timeframe).await?;
+    let Some(last) = forex_data.last().map(|point| point.timestamp) else {
+        return Err(anyhow::anyhow!("no {} {} data in {}", pair, timeframe, input.display()));
+    };
+    info!("🧪 Generating {} days of synthetic {} {} data from {} bars", days, pair, timeframe, forex_data.len());
+    
+    let pipeline_config = PipelineConfig {
+        data: config.data_config.clone(),
+        engine: config.engine_config.clone(),
+        patterns: config.pattern_config.clone(),
+        synthetic: synthetic::SyntheticGenerationConfig {
+            future_horizon_days: days,
+            resolution_minutes: resolution.num_minutes() as u32,
+            seed,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let pipeline = PipelineBuilder::new(pipeline_config).with_history(forex_data).build().await?;
+    info!("✅ {} symmetries and {} cycles extracted", pipeline.symmetries.len(), pipeline.cycles.len());
+    let generated = pipeline.synthetic_generator.generate_future_data(last + resolution, &pair).await?;
+    
+    synthetic::export::export(&generated, &output, format)?;
+    let mean_confidence = generated.iter().map(|point| point.generation_confidence).sum::<f64>() / generated.len() as f64;
+    info!("📄 {} synthetic points saved to {} ({}), mean confidence {:.3}",
+          generated.len(), output.display(), format.extension(), mean_confidence);
+    
+    Ok(())
+}
+
+/// Generate a year of synthetic data from the pair's history and compare it with that history
+async fn validate_synthetic_data(
+    input: PathBuf,
+    pair: String,
+    timeframe: String,
+    seed: Option<u64>,
+    output: Option<PathBuf>,
+    config: Configuration,
+) -> Result<()> {
+    let mut data_manager = ForexDataManager::new(config.data_config.clone())?;
+    let forex_data = data_manager.load_data(&input, &pair, &timeframe).await?;
+    let Some(last) = forex_data.last().map(|point| point.timestamp) else {
+        return Err(anyhow::anyhow!("no {} {} data in {}", pair, timeframe, input.display()));
+    };
+    info!("🧪 Generating synthetic {} data from {} bars", pair, forex_data.len());
+    
+    let pipeline_config = PipelineConfig {
+        data: config.data_config.clone(),
+        engine: config.engine_config.clone(),
+        patterns: config.pattern_config.clone(),
+        synthetic: synthetic::SyntheticGenerationConfig { seed, ..Default::default() },
+        ..Default::default()
+    };
+    let pipeline = PipelineBuilder::new(pipeline_config).with_history(forex_data).build().await?;
+    let generated = pipeline.synthetic_generator.generate_future_data(last, &pair).await?;
+    let report = synthetic::validation::validate_synthetic(&pair, &pipeline.history, &generated, &Default::default())?;
+    
+    for line in report.summary() {
+        info!("  {}", line);
+    }
+    info!("🧪 {} synthetic data realism: {:.0}% of checks passed", pair, report.score * 100.0);
+    if let Some(output) = &output {
+        write_json(output, &report)?;
+        info!("📄 Synthetic quality report saved to: {}", output.display());
+    }
+    
+    Ok(())
+}
+
+/// Summarize an execution log and the backtest costs it calibrates
+fn report_execution_quality(log: PathBuf, output: Option<PathBuf>, config: Configuration) -> Result<()> {
+    let report = portfolio::execution_quality::ExecutionQualityReport::from_log(&log)?;
+    if report.overall.fills == 0 {
+        return Err(anyhow::anyhow!("No fills recorded in {}", log.display()));
+    }
+    info!("🧾 Execution quality of {} fills in {}", report.overall.fills, log.display());
+    for line in report.lines() {
+        info!("  {}", line);
+    }
+    let calibrated = report.calibrate(config.backtest_config.clone());
+    info!("🧪 Backtest slippage {:.5}% (configured {:.5}%)", calibrated.slippage * 100.0, config.backtest_config.slippage * 100.0);
+    for symbol in report.by_pair.keys() {
+        let pair = calibrated.execution.config_for(symbol);
+        info!("  {}: slippage {:.5}%, latency {}ms", symbol, pair.slippage.unwrap_or(calibrated.slippage) * 100.0, pair.latency_ms);
+    }
+    if let Some(output) = &output {
+        write_json(output, &report)?;
+        info!("📄 Execution quality report saved to: {}", output.display());
+    }
+    Ok(())
+}
+
 /// Load system configuration
 async fn load_configuration(config_path: &PathBuf) -> Result<Configuration> {
     if config_path.exists() {
@@ -351,13 +1919,22 @@ async fn load_configuration(config_path: &PathBuf) -> Result<Configuration> {
     }
 }
 
+/// Score to three decimals, or a dash when it could not be measured
+fn format_score(score: Option<f64>) -> String {
+    score.map(|score| format!("{:.3}", score)).unwrap_or_else(|| "—".to_string())
+}
+
 /// Generate comprehensive analysis report
 fn generate_analysis_report(
+    pair: &str,
+    timeframe: &str,
     symmetries: &[crate::symmetry::TemporalSymmetry],
     cycles: &[crate::patterns::HiddenCycle],
     data: &[crate::data::ForexDataPoint],
 ) -> Result<serde_json::Value> {
     let report = serde_json::json!({
+        "pair": pair,
+        "timeframe": timeframe,
         "analysis_timestamp": chrono::Utc::now(),
         "data_summary": {
             "total_points": data.len(),
@@ -372,27 +1949,12 @@ fn generate_analysis_report(
         },
         "temporal_symmetries": symmetries,
         "hidden_cycles": cycles,
-        "validation_metrics": {
-            "symmetry_strength_avg": symmetries.iter().map(|s| s.strength).sum::<f64>() / symmetries.len() as f64,
-            "cycle_confidence_avg": cycles.iter().map(|c| c.confidence).sum::<f64>() / cycles.len() as f64,
-            "pattern_consistency": calculate_pattern_consistency(symmetries, cycles),
-        }
+        "validation_metrics": report::analysis::ValidationMetrics::compute(symmetries, cycles),
     });
     
     Ok(report)
 }
 
-/// Calculate overall pattern consistency score
-fn calculate_pattern_consistency(
-    symmetries: &[crate::symmetry::TemporalSymmetry],
-    cycles: &[crate::patterns::HiddenCycle],
-) -> f64 {
-    let symmetry_score = symmetries.iter().map(|s| s.strength).sum::<f64>() / symmetries.len() as f64;
-    let cycle_score = cycles.iter().map(|c| c.confidence).sum::<f64>() / cycles.len() as f64;
-    
-    (symmetry_score + cycle_score) / 2.0
-}
-
 /// System configuration structure
 #[derive(Debug, Clone, serde::Deserialize)]
 struct Configuration {
@@ -403,6 +1965,31 @@ struct Configuration {
     pub dashboard_config: crate::visualization::DashboardConfig,
     pub decomposition_config: crate::patterns::DecompositionConfig,
     pub visualization_enabled: bool,
+    /// Image format of the analysis charts (png or svg)
+    #[serde(default)]
+    pub chart_format: crate::visualization::ChartFormat,
+    #[serde(default)]
+    pub report_config: crate::report::DailyReportConfig,
+    #[serde(default)]
+    pub trading_windows: crate::trading_windows::TradingWindowsConfig,
+    #[serde(default = "default_analysis_cache_path")]
+    pub analysis_cache_path: PathBuf,
+    #[serde(default)]
+    pub dossier_config: crate::report::dossier::DossierConfig,
+    #[serde(default)]
+    pub digest_config: crate::report::digest::DigestConfig,
+    #[serde(default)]
+    pub holiday_calendar: calendar::HolidayCalendar,
+    #[serde(default)]
+    pub economic_calendar: calendar::economic::EconomicCalendarConfig,
+    #[serde(default)]
+    pub validation_config: crate::validation::CrossValidationConfig,
+    #[serde(default)]
+    pub scan_config: crate::report::scan::ScanConfig,
+}
+
+fn default_analysis_cache_path() -> PathBuf {
+    PathBuf::from("cache/analysis.db")
 }
 
 impl Default for Configuration {
@@ -415,6 +2002,16 @@ impl Default for Configuration {
             dashboard_config: crate::visualization::DashboardConfig::default(),
             decomposition_config: crate::patterns::DecompositionConfig::default(),
             visualization_enabled: true,
+            chart_format: crate::visualization::ChartFormat::default(),
+            report_config: crate::report::DailyReportConfig::default(),
+            trading_windows: crate::trading_windows::TradingWindowsConfig::default(),
+            analysis_cache_path: default_analysis_cache_path(),
+            dossier_config: crate::report::dossier::DossierConfig::default(),
+            digest_config: crate::report::digest::DigestConfig::default(),
+            holiday_calendar: calendar::HolidayCalendar::default(),
+            economic_calendar: calendar::economic::EconomicCalendarConfig::default(),
+            validation_config: crate::validation::CrossValidationConfig::default(),
+            scan_config: crate::report::scan::ScanConfig::default(),
         }
     }
 }
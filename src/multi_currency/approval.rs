@@ -0,0 +1,160 @@
+//! # Trade Approval
+//!
+//! Human-in-the-loop execution: proposed actions wait in a queue with a
+//! countdown until an operator approves or rejects them; unanswered ones are
+//! recorded as missed.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+use crate::laplacian_rl::TradingAction;
+use crate::risk::SignalOrigin;
+
+/// Whether proposed actions are sent at once or wait for an operator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExecutionMode {
+    /// Send every action the risk checks allow
+    #[default]
+    Automatic,
+    /// Hold every action that would trade until an operator approves it
+    Approval,
+}
+
+impl std::fmt::Display for ExecutionMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ExecutionMode::Automatic => "automatic",
+            ExecutionMode::Approval => "approval",
+        })
+    }
+}
+
+impl std::str::FromStr for ExecutionMode {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> anyhow::Result<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "automatic" | "auto" => Ok(ExecutionMode::Automatic),
+            "approval" | "manual" => Ok(ExecutionMode::Approval),
+            other => anyhow::bail!("unknown execution mode '{}', expected automatic or approval", other),
+        }
+    }
+}
+
+/// Approval mode settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApprovalConfig {
+    pub mode: ExecutionMode,
+    /// Seconds an operator has to answer before the action is missed
+    pub timeout_seconds: u64,
+}
+
+impl Default for ApprovalConfig {
+    fn default() -> Self {
+        Self { mode: ExecutionMode::Automatic, timeout_seconds: 30 }
+    }
+}
+
+impl ApprovalConfig {
+    /// Mode from `EXECUTION_MODE` and countdown from `APPROVAL_TIMEOUT_SECS`, defaults otherwise
+    pub fn from_env() -> anyhow::Result<Self> {
+        let mut config = Self::default();
+        if let Ok(mode) = std::env::var("EXECUTION_MODE") {
+            config.mode = mode.parse()?;
+        }
+        if let Ok(seconds) = std::env::var("APPROVAL_TIMEOUT_SECS") {
+            config.timeout_seconds = seconds.trim().parse()
+                .map_err(|_| anyhow::anyhow!("APPROVAL_TIMEOUT_SECS must be a number of seconds, got '{}'", seconds))?;
+        }
+        Ok(config)
+    }
+}
+
+/// An action a pair decided, as it is handed to execution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposedAction {
+    pub symbol: String,
+    /// Position of the action in the pair's decision, for reporting fills back
+    pub index: usize,
+    pub action: TradingAction,
+    /// When and at which bar the action was decided
+    pub origin: Option<SignalOrigin>,
+}
+
+/// A proposed action waiting for an operator
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingApproval {
+    pub id: u64,
+    pub proposed: ProposedAction,
+    pub queued_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl PendingApproval {
+    /// Time left to answer, zero once expired
+    pub fn remaining(&self, now: DateTime<Utc>) -> Duration {
+        (self.expires_at - now).max(Duration::zero())
+    }
+
+    /// "#3 EURUSD Buy { size: 10 } (12s left)"
+    pub fn summary(&self, now: DateTime<Utc>) -> String {
+        format!("#{} {} {:?} ({}s left)", self.id, self.proposed.symbol, self.proposed.action, self.remaining(now).num_seconds())
+    }
+}
+
+/// Proposed actions awaiting approval, oldest first
+#[derive(Debug, Clone)]
+pub struct ApprovalQueue {
+    pending: VecDeque<PendingApproval>,
+    next_id: u64,
+}
+
+impl Default for ApprovalQueue {
+    fn default() -> Self {
+        Self { pending: VecDeque::new(), next_id: 1 }
+    }
+}
+
+impl ApprovalQueue {
+    /// Queue `proposed` for `timeout`, returning the pending entry
+    pub fn propose(&mut self, proposed: ProposedAction, now: DateTime<Utc>, timeout: Duration) -> PendingApproval {
+        let pending = PendingApproval { id: self.next_id, proposed, queued_at: now, expires_at: now + timeout };
+        self.next_id += 1;
+        self.pending.push_back(pending.clone());
+        pending
+    }
+
+    /// Take the entry `id`, or every entry when `id` is `None`
+    pub fn take(&mut self, id: Option<u64>) -> Vec<PendingApproval> {
+        match id {
+            Some(id) => self.pending.iter()
+                .position(|pending| pending.id == id)
+                .and_then(|index| self.pending.remove(index))
+                .into_iter()
+                .collect(),
+            None => self.pending.drain(..).collect(),
+        }
+    }
+
+    /// Remove and return the entries whose countdown ran out by `now`
+    pub fn expire(&mut self, now: DateTime<Utc>) -> Vec<PendingApproval> {
+        let (expired, pending): (Vec<_>, Vec<_>) = self.pending.drain(..).partition(|pending| pending.expires_at <= now);
+        self.pending = pending.into();
+        expired
+    }
+
+    pub fn pending(&self) -> Vec<PendingApproval> {
+        self.pending.iter().cloned().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
@@ -1,18 +1,54 @@
+pub mod approval;
+pub mod model;
+pub mod throughput;
+
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use tokio::sync::RwLock;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::{watch, RwLock};
 use chrono::{DateTime, Utc};
 
 use crate::{
-    core::{TimeSymmetricEngine, EngineConfig},
-    data::{ForexDataManager, DataConfig, ForexDataPoint},
-    patterns::{PatternRecognizer, PatternConfig, HiddenCycle},
+    core::TimeSymmetricEngine,
+    data::{DataConfig, ForexDataManager, ForexDataPoint, HistoricalSource, MissingDataPolicy},
+    data::provider::{timeframe_duration, BarAggregator, DataProvider, Tick},
+    data::health::{FeedHealthConfig, FeedHealthMonitor, FeedHealthReport},
+    patterns::{PatternRecognizer, HiddenCycle},
+    patterns::cross_pair::{find_shared_cycles, PairCycles, SharedCycle, SharedCycleConfig},
+    pipeline::{Pipeline, PipelineBuilder, PipelineConfig},
+    pipeline::params::{AnalysisParams, ParamsHandle, ParamsUpdate, Sensitivity},
     symmetry::TemporalSymmetry,
-    synthetic::{SyntheticDataGenerator, SyntheticForexPoint, SyntheticGenerationConfig},
-    anomaly::{TemporalAnomalyDetector, DetectedAnomaly, AnomalyDetectionConfig},
-    laplacian_rl::{LaplacianQLearningAgent, TradingAction, LaplacianQLearningConfig},
+    synthetic::{SyntheticDataGenerator, SyntheticForexPoint},
+    synthetic::validation::{validate_synthetic, SyntheticQualityReport, SyntheticValidationConfig},
+    anomaly::{TemporalAnomalyDetector, DetectedAnomaly, AnomalyDetectionConfig, AnomalyType, SensitivityCalibration},
+    anomaly::suppression::{SuppressionList, WILDCARD},
+    laplacian_rl::{LaplacianQLearningAgent, TradingAction},
+    portfolio::{Portfolio, PortfolioConfig, PortfolioSnapshot},
+    portfolio::margin::{MarginEvent, MarginEventKind},
+    portfolio::allocation::{Allocation, AllocationConfig, RiskAllocator},
+    portfolio::execution_quality::{DecisionQuote, ExecutionLog, ExecutionQualityReport},
+    risk::{KillSwitch, RiskDecision, RiskEngine, SignalOrigin},
+    correlation::{CrossPairAnalyzer, LeadLagConfig, LeadLagOpportunity},
+    audit::AuditLog,
+    replay::SessionLog,
+    embedded_db::EmbeddedForexDB,
+    resilience::{BreakerStatus, CircuitBreaker, CircuitBreakerConfig},
+    resilience::chaos::FaultInjector,
+    signal::{CompositeScore, CompositeScoreConfig, CompositeScorer},
+    stats::drift::{DriftAction, DriftConfig, DriftDetected, DriftMonitor},
+    backtest::StrategyConfig,
+    backtest::strategy::{Fill, Order, OrderSide, Strategy, StrategyContext, StrategyRegistry},
+    broker::{ExecutionBackend, Fill as BrokerFill, MarketOrder},
+    trading_windows::TradingWindowsConfig,
+    backtest::sandbox::{SandboxConfig, SandboxStatus, StrategyEvent, StrategySandbox},
 };
+use approval::{ApprovalConfig, ApprovalQueue, ExecutionMode, PendingApproval, ProposedAction};
+use model::{PairModel, MODEL_VERSION};
+use throughput::{PairThroughput, ThroughputConfig, ThroughputPanel, ThroughputTracker};
+
+pub use crate::risk::RiskLimits;
 
 /// Multi-currency trading pair configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,11 +56,34 @@ pub struct CurrencyPairConfig {
     pub symbol: String,
     pub base_currency: String,
     pub quote_currency: String,
+    /// Price of one pip, as [`units::pip_size`](crate::units::pip_size)
     pub pip_value: f64,
     pub spread: f64,
     pub min_lot_size: f64,
     pub max_lot_size: f64,
     pub enabled: bool,
+    #[serde(default = "default_target_anomalies_per_day")]
+    pub target_anomalies_per_day: f64,
+    /// Strategies from the [`StrategyRegistry`] that trade this pair instead of the
+    /// built-in RL agent
+    #[serde(default)]
+    pub strategies: Vec<StrategyConfig>,
+    /// Time limits and failure tolerance for those strategies
+    #[serde(default)]
+    pub sandbox: SandboxConfig,
+    /// Input distribution drift tests and the response to drift
+    #[serde(default)]
+    pub drift: DriftConfig,
+    /// Engine, recognizer, generator, detector and agent settings
+    #[serde(default)]
+    pub pipeline: PipelineConfig,
+    /// Realism checks of the synthetic data against the history
+    #[serde(default)]
+    pub synthetic_validation: SyntheticValidationConfig,
+}
+
+fn default_target_anomalies_per_day() -> f64 {
+    2.0
 }
 
 impl Default for CurrencyPairConfig {
@@ -33,15 +92,74 @@ impl Default for CurrencyPairConfig {
             symbol: "EURUSD".to_string(),
             base_currency: "EUR".to_string(),
             quote_currency: "USD".to_string(),
-            pip_value: 0.0001,
+            pip_value: crate::units::pip_size("EURUSD"),
             spread: 0.0002,
             min_lot_size: 0.01,
             max_lot_size: 100.0,
             enabled: true,
+            target_anomalies_per_day: default_target_anomalies_per_day(),
+            strategies: Vec::new(),
+            sandbox: SandboxConfig::default(),
+            drift: DriftConfig::default(),
+            pipeline: PipelineConfig::default(),
+            synthetic_validation: SyntheticValidationConfig::default(),
+        }
+    }
+}
+
+impl CurrencyPairConfig {
+    /// Pipeline settings with the detector calibrated to this pair's anomaly rate
+    pub fn pipeline_config(&self) -> PipelineConfig {
+        PipelineConfig {
+            anomaly: AnomalyDetectionConfig {
+                target_anomalies_per_day: Some(self.target_anomalies_per_day),
+                ..self.pipeline.anomaly.clone()
+            },
+            ..self.pipeline.clone()
+        }
+    }
+}
+
+/// Startup backfill settings: how fresh history must be before trading is permitted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackfillConfig {
+    /// Timeframe of the historical bars (e.g. "D1", "H1")
+    pub timeframe: String,
+    /// Missing bars tolerated between the newest bar and now (covers weekends)
+    pub max_missing_bars: i64,
+    /// Bars needed to fill the anomaly detector and engine windows
+    pub min_bars: usize,
+}
+
+impl Default for BackfillConfig {
+    fn default() -> Self {
+        Self {
+            timeframe: "D1".to_string(),
+            max_missing_bars: 4,
+            min_bars: AnomalyDetectionConfig::default().detection_window_size,
         }
     }
 }
 
+/// Outcome of the startup backfill for one pair
+#[derive(Debug, Clone, Serialize)]
+pub struct BackfillReport {
+    pub symbol: String,
+    /// Newest bar before backfilling
+    pub history_end: Option<DateTime<Utc>>,
+    /// Bars replayed from the database
+    pub replayed_bars: usize,
+    /// Bars fetched from the data provider
+    pub fetched_bars: usize,
+    /// Newest bar after backfilling
+    pub backfilled_to: Option<DateTime<Utc>>,
+    /// Whole bars still missing between `backfilled_to` and now
+    pub missing_bars: i64,
+    /// Windows are full and recent enough for trading decisions
+    pub warm: bool,
+}
+
 /// Performance metrics for a currency pair
 #[derive(Debug, Clone, Serialize)]
 pub struct PairPerformanceMetrics {
@@ -86,6 +204,20 @@ impl PairPerformanceMetrics {
     }
 }
 
+/// Market action for a strategy order, rounded to whole action sizes; `None` when
+/// the order is smaller than half a size
+fn order_action(order: &Order, units_per_size: f64) -> Option<TradingAction> {
+    let size = (order.units.abs() / units_per_size.max(f64::EPSILON)).round();
+    if size < 1.0 || !size.is_finite() {
+        return None;
+    }
+    let size = size.min(u32::MAX as f64) as u32;
+    Some(match order.side {
+        OrderSide::Buy => TradingAction::Buy { size },
+        OrderSide::Sell => TradingAction::Sell { size },
+    })
+}
+
 /// Multi-currency trading system state
 pub struct CurrencyPairState {
     pub config: CurrencyPairConfig,
@@ -98,40 +230,105 @@ pub struct CurrencyPairState {
     pub performance: PairPerformanceMetrics,
     pub historical_data: Vec<ForexDataPoint>,
     pub synthetic_data: Vec<SyntheticForexPoint>,
+    /// How realistic the synthetic data looks next to the history, once generated
+    pub synthetic_quality: Option<SyntheticQualityReport>,
     pub recent_anomalies: Vec<DetectedAnomaly>,
+    /// Anomalies silenced by acknowledgment or suppression rules
+    pub suppressed_anomalies: u64,
     pub is_active: bool,
+    /// Paused by an operator: no new signals until resumed
+    pub paused: bool,
+    /// Paused because its inputs drifted: no new signals until an operator resumes it
+    pub drift_paused: bool,
+    /// Paused automatically while the live feed is stale or skewed
+    pub feed_paused: bool,
+    /// History is recent and long enough for trading decisions (see [`BackfillConfig`])
+    pub warm: bool,
+    pub backfill: Option<BackfillReport>,
+    pub backfill_config: BackfillConfig,
+    /// Latest live quote
+    pub last_tick: Option<Tick>,
+    bar_aggregator: BarAggregator,
+    pub data_path: std::path::PathBuf,
+    pub historical_source: Option<HistoricalSource>,
+    /// When the last liquidity gap was seen; new entries wait out [`RiskLimits::liquidity_gap_block_minutes`]
+    pub last_liquidity_gap: Option<DateTime<Utc>>,
+    pub composite_scorer: CompositeScorer,
+    /// Composite score of each bar processed, newest last
+    pub composite_scores: Vec<CompositeScore>,
+    /// Temporal symmetries extracted at initialization
+    pub symmetries: Vec<TemporalSymmetry>,
+    /// Hidden cycles found at initialization
+    pub cycles: Vec<HiddenCycle>,
+    /// Analysis thresholds in force; symmetries and cycles below them are kept but not used
+    pub analysis_params: AnalysisParams,
+    /// Manager-wide parameter changes, once the pair follows them
+    params_updates: Option<watch::Receiver<AnalysisParams>>,
+    /// Manager-wide parameters last applied
+    seen_params: AnalysisParams,
+    pub drift_monitor: DriftMonitor,
+    /// Bars analyzed and detection latency, for the system panel
+    pub throughput: ThroughputTracker,
+    /// Drift events not yet reported
+    drift_events: Vec<DriftDetected>,
+    /// Plug-in strategies trading the pair; the RL agent decides when there are none
+    strategies: Vec<PairStrategy>,
+    /// Strategy index and order behind each action of the last update, in action order
+    submitted_orders: Vec<(usize, Order)>,
+    /// Data the last update's actions were decided on, to tell when they go stale
+    pub signal_origin: Option<SignalOrigin>,
+    /// Price and spread the last update's actions were decided on
+    pub decision_quote: Option<DecisionQuote>,
+}
+
+/// A sandboxed strategy and how far it has followed the pair
+struct PairStrategy {
+    sandbox: StrategySandbox,
+    /// Whether it has been replayed the history and the cycles
+    synced: bool,
+    /// Newest bar it has seen
+    last_bar: Option<DateTime<Utc>>,
+    /// Follow-up orders from `on_fill`, sent with the next update
+    pending_orders: Vec<Order>,
+}
+
+impl PairStrategy {
+    fn new(strategy: Box<dyn Strategy>, config: SandboxConfig) -> Self {
+        Self { sandbox: StrategySandbox::new(strategy, config), synced: false, last_bar: None, pending_orders: Vec::new() }
+    }
+}
+
+/// The pair's account as seen by its strategy
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct PairAccount {
+    /// Signed open position in base-currency units
+    pub position_units: f64,
+    pub equity: f64,
+    /// Units per unit of [`TradingAction`] size, scaled by the pair's risk budget
+    pub units_per_size: f64,
+}
+
+/// Anomalies a pair update acted on and the actions it produced
+#[derive(Debug, Clone)]
+pub struct PairDecision {
+    pub anomalies: Vec<DetectedAnomaly>,
+    pub actions: Vec<TradingAction>,
 }
 
 impl CurrencyPairState {
     pub async fn new(config: CurrencyPairConfig) -> Result<Self> {
-        let engine_config = EngineConfig::default();
-        let engine = TimeSymmetricEngine::new(engine_config)?;
-        
-        let data_config = DataConfig::default();
-        let data_manager = ForexDataManager::new(data_config)?;
-        
-        let pattern_config = PatternConfig::default();
-        let pattern_recognizer = PatternRecognizer::new(pattern_config)?;
-        
-        // Initialize with empty data - will be populated during initialization
-        let synthetic_generator = SyntheticDataGenerator::new(
-            Vec::new(),
-            Vec::new(),
-            Vec::new(),
-            SyntheticGenerationConfig::default()
-        )?;
-        
-        let anomaly_detector = TemporalAnomalyDetector::new(
-            Vec::new(),
-            Vec::new(),
-            &[],
-            AnomalyDetectionConfig::default()
-        )?;
-        
-        let rl_config = LaplacianQLearningConfig::default();
-        let rl_agent = LaplacianQLearningAgent::new(rl_config)?;
+        // Stages start on empty data, uncalibrated, and are rebuilt on the history during initialization
+        let Pipeline { engine, data_manager, pattern_recognizer, synthetic_generator, anomaly_detector, rl_agent, .. } =
+            PipelineBuilder::new(config.pipeline.clone()).build().await?;
         
         let performance = PairPerformanceMetrics::new(config.symbol.clone());
+        let composite_scorer = CompositeScorer::new(CompositeScoreConfig::default())?;
+        let drift_monitor = DriftMonitor::new(config.drift.clone());
+        let analysis_params = AnalysisParams::from_config(&config.pipeline_config());
+        let registry = StrategyRegistry::new();
+        let strategies = config.strategies.iter()
+            .map(|strategy| Ok(PairStrategy::new(registry.create(strategy)?, config.sandbox.clone())))
+            .collect::<Result<Vec<_>>>()?;
         
         Ok(Self {
             config,
@@ -144,20 +341,105 @@ impl CurrencyPairState {
             performance,
             historical_data: Vec::new(),
             synthetic_data: Vec::new(),
+            synthetic_quality: None,
             recent_anomalies: Vec::new(),
+            suppressed_anomalies: 0,
             is_active: false,
+            paused: false,
+            drift_paused: false,
+            feed_paused: false,
+            warm: false,
+            backfill: None,
+            backfill_config: BackfillConfig::default(),
+            last_tick: None,
+            bar_aggregator: BarAggregator::new(chrono::Duration::days(1)),
+            data_path: std::path::PathBuf::from("FOREX DATA/Forex Daily (1980) - 2023/archive(4)/Forex_D1/Major"),
+            historical_source: None,
+            last_liquidity_gap: None,
+            composite_scorer,
+            composite_scores: Vec::new(),
+            symmetries: Vec::new(),
+            cycles: Vec::new(),
+            seen_params: analysis_params.clone(),
+            analysis_params,
+            params_updates: None,
+            drift_monitor,
+            throughput: ThroughputTracker::default(),
+            drift_events: Vec::new(),
+            strategies,
+            submitted_orders: Vec::new(),
+            signal_origin: None,
+            decision_quote: None,
         })
     }
     
     /// Initialize the currency pair with historical data
     pub async fn initialize(&mut self) -> Result<()> {
+        self.initialize_with_backfill(None, None).await
+    }
+    
+    /// Initialize, filling bars missing since the end of the history from `backfill_db`
+    /// and then `provider` before the engine and anomaly detector are built
+    pub async fn initialize_with_backfill(
+        &mut self,
+        backfill_db: Option<&EmbeddedForexDB>,
+        provider: Option<&dyn DataProvider>,
+    ) -> Result<()> {
+        self.initialize_with_model(backfill_db, provider, None).await
+    }
+    
+    /// [`Self::initialize_with_backfill`], restoring the trained state from `model`
+    /// instead of re-analyzing the history when one is given
+    pub async fn initialize_with_model(
+        &mut self,
+        backfill_db: Option<&EmbeddedForexDB>,
+        provider: Option<&dyn DataProvider>,
+        model: Option<PairModel>,
+    ) -> Result<()> {
         println!("🔄 Initializing {} trading system...", self.config.symbol);
         
-        // Load historical data
-        let data_path = std::path::PathBuf::from("FOREX DATA/Forex Daily (1980) - 2023/archive(4)/Forex_D1/Major");
-        self.historical_data = self.data_manager.load_data(&data_path, &self.config.symbol, "1D").await?;
-        println!("✅ {} - Loaded {} historical data points", self.config.symbol, self.historical_data.len());
+        // Load historical data, falling back to demo or feed-only mode if it is missing
+        let data_path = self.data_path.clone();
+        let (historical_data, source) = self.data_manager
+            .load_data_or_fallback(&data_path, &self.config.symbol, "1D").await?;
+        self.historical_data = historical_data;
+        self.historical_source = Some(source.clone());
+        
+        if self.historical_data.is_empty() {
+            println!("📡 {} - No historical data, waiting for live feed", self.config.symbol);
+            return Ok(());
+        }
+        println!("✅ {} - Loaded {} historical data points ({:?})", self.config.symbol, self.historical_data.len(), source);
+        
+        // Close the gap between the end of the history and now so the windows are warm
+        let report = self.backfill(backfill_db, provider, Utc::now()).await?;
+        if report.replayed_bars > 0 {
+            println!("⏪ {} - Replayed {} bars from the database", self.config.symbol, report.replayed_bars);
+        }
+        if report.fetched_bars > 0 {
+            println!("⏪ {} - Fetched {} bars from the data provider", self.config.symbol, report.fetched_bars);
+        }
+        if !report.warm {
+            println!("⏳ {} - History ends {} ({} bars missing); trading held until the feed catches up",
+                     self.config.symbol,
+                     report.backfilled_to.map(|t| t.to_rfc3339()).unwrap_or_else(|| "never".to_string()),
+                     report.missing_bars);
+        }
+        
+        match model {
+            Some(model) => self.restore_model(model)?,
+            None => self.reanalyze().await?,
+        }
+
+        self.is_active = true;
+        println!("🎯 {} trading system initialized successfully!", self.config.symbol);
         
+        Ok(())
+    }
+    
+    /// Extract symmetries and cycles from the history and rebuild the synthetic data,
+    /// anomaly detector and drift reference on it
+    pub async fn reanalyze(&mut self) -> Result<()> {
         // Initialize engine
         self.engine.initialize().await?;
         
@@ -169,65 +451,553 @@ impl CurrencyPairState {
         let cycles = self.pattern_recognizer.detect_cycles(&self.historical_data).await?;
         println!("✅ {} - Detected {} hidden cycles", self.config.symbol, cycles.len());
         
-        // Reinitialize synthetic generator with actual data
-        self.synthetic_generator = SyntheticDataGenerator::new(
-            symmetries.clone(),
-            cycles.clone(),
-            self.historical_data.clone(),
-            SyntheticGenerationConfig::default()
-        )?;
+        // Rebuild the synthetic generator on the actual data
+        self.synthetic_generator = self.pipeline_builder()
+            .with_analysis(symmetries.clone(), cycles.clone())
+            .synthetic_generator()?;
 
         // Generate synthetic data
         let start_date = chrono::Utc::now();
         self.synthetic_data = self.synthetic_generator.generate_future_data(start_date, &self.config.symbol).await?;
         println!("✅ {} - Generated {} synthetic data points", self.config.symbol, self.synthetic_data.len());
+        self.validate_synthetic_data();
 
-        // Reinitialize anomaly detector with actual data
-        self.anomaly_detector = TemporalAnomalyDetector::new(
-            symmetries.clone(),
-            cycles.clone(),
-            &self.historical_data,
-            AnomalyDetectionConfig::default()
-        )?;
-
-        self.is_active = true;
-        println!("🎯 {} trading system initialized successfully!", self.config.symbol);
+        self.rebuild_detectors(symmetries, cycles, None)
+    }
+    
+    /// Check the synthetic data against the history; a history or synthetic series
+    /// too short to compare leaves no report
+    fn validate_synthetic_data(&mut self) {
+        self.synthetic_quality = match validate_synthetic(&self.config.symbol, &self.historical_data, &self.synthetic_data, &self.config.synthetic_validation) {
+            Ok(report) => {
+                println!("🧪 {} - Synthetic data realism {:.0}% ({}/4 checks passed)",
+                         self.config.symbol, report.score * 100.0, (report.score * 4.0).round());
+                Some(report)
+            }
+            Err(e) => {
+                println!("⚠️  {} - Synthetic data not validated: {}", self.config.symbol, e);
+                None
+            }
+        };
+    }
+    
+    /// Builder over this pair's pipeline settings, with the analysis parameters in force, and history
+    fn pipeline_builder(&self) -> PipelineBuilder {
+        let mut config = self.config.pipeline_config();
+        self.analysis_params.apply_to(&mut config);
+        PipelineBuilder::new(config).with_history(self.historical_data.clone())
+    }
+    
+    /// Take manager-wide parameter changes from `handle` at each market update
+    pub fn follow_params(&mut self, handle: &ParamsHandle) {
+        self.seen_params = handle.initial().clone();
+        self.params_updates = Some(handle.subscribe());
+    }
+    
+    /// Apply the parameters published since the last check. Only fields that changed
+    /// are taken, so pair-specific values of the others are kept. The detector and
+    /// scorer are rebuilt on the symmetries and cycles the new thresholds admit; the
+    /// engine and recognizer extract with them at the next analysis. True when
+    /// anything changed for this pair.
+    pub async fn apply_param_changes(&mut self) -> Result<bool> {
+        let Some(updates) = self.params_updates.as_mut() else {
+            return Ok(false);
+        };
+        if !updates.has_changed().unwrap_or(false) {
+            return Ok(false);
+        }
+        let published = updates.borrow_and_update().clone();
+        let seen = std::mem::replace(&mut self.seen_params, published.clone());
+        let mut params = AnalysisParams { epoch: published.epoch, ..self.analysis_params.clone() };
+        if published.anomaly_sensitivity != seen.anomaly_sensitivity {
+            params.anomaly_sensitivity = published.anomaly_sensitivity;
+        }
+        if published.cycle_confidence_threshold != seen.cycle_confidence_threshold {
+            params.cycle_confidence_threshold = published.cycle_confidence_threshold;
+        }
+        if published.symmetry_strength_threshold != seen.symmetry_strength_threshold {
+            params.symmetry_strength_threshold = published.symmetry_strength_threshold;
+        }
+        let changes = params.changes_from(&self.analysis_params);
+        let previous = std::mem::replace(&mut self.analysis_params, params);
+        if changes.is_empty() {
+            return Ok(false);
+        }
+        println!("⚙️  {} - Parameters epoch {}: {}", self.config.symbol, self.analysis_params.epoch,
+                 changes.iter().map(|change| change.to_string()).collect::<Vec<_>>().join(", "));
+        
+        if self.analysis_params.symmetry_strength_threshold != previous.symmetry_strength_threshold {
+            self.engine = self.pipeline_builder().build_engine().await?;
+        }
+        self.pattern_recognizer.set_confidence_threshold(self.analysis_params.cycle_confidence_threshold);
+        // A calibration only survives while the sensitivity stays calibrated
+        let calibration = match (previous.anomaly_sensitivity, self.analysis_params.anomaly_sensitivity) {
+            (Sensitivity::Calibrated, Sensitivity::Calibrated) => self.anomaly_detector.calibration().cloned(),
+            _ => None,
+        };
+        let (symmetries, cycles) = (std::mem::take(&mut self.symmetries), std::mem::take(&mut self.cycles));
+        self.rebuild_detectors(symmetries, cycles, calibration)?;
+        Ok(true)
+    }
+    
+    /// Trained state of the pair, for [`PairModel::save`]
+    pub fn model(&self) -> PairModel {
+        PairModel {
+            version: MODEL_VERSION,
+            symbol: self.config.symbol.clone(),
+            saved_at: Utc::now(),
+            history_bars: self.historical_data.len(),
+            history_end: self.historical_data.last().map(|point| point.timestamp),
+            symmetries: self.symmetries.clone(),
+            cycles: self.cycles.clone(),
+            synthetic_data: self.synthetic_data.clone(),
+            q_table: self.rl_agent.q_table_snapshot(),
+            calibration: self.anomaly_detector.calibration().cloned(),
+        }
+    }
+    
+    /// Take the symmetries, cycles, synthetic data, Q-table and calibration from a
+    /// saved model instead of re-analyzing the history
+    pub fn restore_model(&mut self, model: PairModel) -> Result<()> {
+        if !model.symbol.eq_ignore_ascii_case(&self.config.symbol) {
+            anyhow::bail!("model of {} cannot initialize {}", model.symbol, self.config.symbol);
+        }
+        println!("💾 {} - Loaded model trained on {} bars to {} ({} symmetries, {} cycles, {} Q-values)",
+                 self.config.symbol, model.history_bars,
+                 model.history_end.map(|t| t.format("%Y-%m-%d").to_string()).unwrap_or_else(|| "-".to_string()),
+                 model.symmetries.len(), model.cycles.len(), model.q_table.entries.len());
+        self.synthetic_generator = self.pipeline_builder()
+            .with_analysis(model.symmetries.clone(), model.cycles.clone())
+            .synthetic_generator()?;
+        self.synthetic_data = model.synthetic_data;
+        self.validate_synthetic_data();
+        self.rl_agent.restore_q_table(model.q_table);
+        self.rebuild_detectors(model.symmetries, model.cycles, model.calibration)
+    }
+    
+    /// Rebuild the anomaly detector, composite scorer and drift reference on the history
+    /// for `symmetries` and `cycles`, keeping `calibration` when given
+    fn rebuild_detectors(
+        &mut self,
+        symmetries: Vec<TemporalSymmetry>,
+        cycles: Vec<HiddenCycle>,
+        calibration: Option<SensitivityCalibration>,
+    ) -> Result<()> {
+        // Rebuild the anomaly detector on the actual data, calibrated to this pair's volatility
+        let active_symmetries: Vec<TemporalSymmetry> = symmetries.iter()
+            .filter(|symmetry| self.analysis_params.admits_symmetry(symmetry))
+            .cloned()
+            .collect();
+        let active_cycles: Vec<HiddenCycle> = cycles.iter()
+            .filter(|cycle| self.analysis_params.admits_cycle(cycle))
+            .cloned()
+            .collect();
+        self.anomaly_detector = self.pipeline_builder()
+            .with_analysis(active_symmetries.clone(), active_cycles.clone())
+            .with_calibration(calibration)
+            .anomaly_detector()?;
+        self.composite_scorer.set_cycles(&active_cycles);
+        self.composite_scorer.set_symmetries(&active_symmetries);
+        self.symmetries = symmetries;
+        self.cycles = cycles;
+        for strategy in &mut self.strategies {
+            strategy.synced = false;
+        }
+        if let Some(calibration) = self.anomaly_detector.calibration() {
+            println!("🎚️  {} - Sensitivity calibrated to {:.3} (≈{:.2} anomalies/day)",
+                     self.config.symbol, calibration.sensitivity_threshold, calibration.estimated_rate_per_day);
+        }
+        self.drift_monitor.fit(&self.historical_data);
         
         Ok(())
     }
     
+    /// Fill bars newer than the loaded history, first from `db` and then from `provider`,
+    /// and re-evaluate warmth
+    pub async fn backfill(
+        &mut self,
+        db: Option<&EmbeddedForexDB>,
+        provider: Option<&dyn DataProvider>,
+        now: DateTime<Utc>,
+    ) -> Result<BackfillReport> {
+        let interval = timeframe_duration(&self.backfill_config.timeframe)?;
+        self.bar_aggregator = BarAggregator::new(interval);
+        let history_end = self.historical_data.last().map(|point| point.timestamp);
+        let mut replayed_bars = 0;
+        let mut fetched_bars = 0;
+        
+        if let Some(db) = db {
+            let after = history_end.unwrap_or(DateTime::<Utc>::MIN_UTC);
+            let bars = db.get_bars_after(&self.config.symbol, after, now)?;
+            replayed_bars = bars.len();
+            self.historical_data.extend(bars);
+        }
+        
+        if let Some(provider) = provider {
+            let from = match self.historical_data.last() {
+                Some(last) => last.timestamp + interval,
+                None => now - interval * (self.backfill_config.min_bars as i32 * 2),
+            };
+            if from < now {
+                match provider.fetch_historical(&self.config.symbol, &self.backfill_config.timeframe, from, now).await {
+                    Ok(bars) => {
+                        let after = self.historical_data.last().map(|point| point.timestamp);
+                        let total = bars.len();
+                        let bars: Vec<_> = bars.into_iter()
+                            .filter(|bar| after.is_none_or(|after| bar.timestamp > after))
+                            .filter(|bar| bar.timestamp <= now)
+                            .collect();
+                        if bars.len() < total {
+                            println!("⚠️  {} - Dropped {} stale or future-dated bars from {}",
+                                     self.config.symbol, total - bars.len(), provider.name());
+                        }
+                        fetched_bars = bars.len();
+                        self.historical_data.extend(bars);
+                    }
+                    Err(e) => println!("⚠️  {} - Backfill from {} failed: {}", self.config.symbol, provider.name(), e),
+                }
+            }
+        }
+        
+        let report = BackfillReport {
+            symbol: self.config.symbol.clone(),
+            history_end,
+            replayed_bars,
+            fetched_bars,
+            backfilled_to: self.historical_data.last().map(|point| point.timestamp),
+            missing_bars: self.missing_bars(now),
+            warm: self.is_warm(now),
+        };
+        self.warm = report.warm;
+        self.backfill = Some(report.clone());
+        Ok(report)
+    }
+    
+    /// Record a live quote, appending a bar to the history whenever one completes
+    /// and returning the completed bar
+    pub fn on_tick(&mut self, tick: Tick) -> Option<ForexDataPoint> {
+        let completed = self.bar_aggregator.push(&tick);
+        if let Some(bar) = &completed {
+            self.append_bar(bar.clone(), tick.timestamp);
+        }
+        self.last_tick = Some(tick);
+        completed
+    }
+    
+    /// Append a live bar, warming the pair once the history has caught up
+    pub fn append_bar(&mut self, bar: ForexDataPoint, now: DateTime<Utc>) {
+        if self.historical_data.last().is_none_or(|last| bar.timestamp > last.timestamp) {
+            self.historical_data.push(bar);
+        }
+        if !self.warm && self.is_warm(now) {
+            println!("🔥 {} - History caught up, trading enabled", self.config.symbol);
+            self.warm = true;
+        }
+    }
+    
+    /// Whole bars missing between the newest bar and `now`
+    fn missing_bars(&self, now: DateTime<Utc>) -> i64 {
+        let interval = timeframe_duration(&self.backfill_config.timeframe)
+            .map(|interval| interval.num_minutes())
+            .unwrap_or(24 * 60)
+            .max(1);
+        match self.historical_data.last() {
+            Some(last) => ((now - last.timestamp).num_minutes() / interval - 1).max(0),
+            None => i64::MAX,
+        }
+    }
+    
+    fn is_warm(&self, now: DateTime<Utc>) -> bool {
+        self.historical_data.len() >= self.backfill_config.min_bars
+            && self.missing_bars(now) <= self.backfill_config.max_missing_bars
+    }
+    
+    /// Resolve missing data the same way another pair already did, so the operator is asked only once
+    pub fn adopt_historical_source(&mut self, source: &HistoricalSource) {
+        match source {
+            HistoricalSource::Files(path) => self.data_path = path.clone(),
+            HistoricalSource::Demo => self.data_manager.set_missing_data_policy(MissingDataPolicy::Demo),
+            HistoricalSource::FeedOnly => self.data_manager.set_missing_data_policy(MissingDataPolicy::FeedOnly),
+        }
+    }
+    
+    /// Names of the plug-in strategies trading the pair
+    pub fn strategy_names(&self) -> Vec<&str> {
+        self.strategies.iter().map(|strategy| strategy.sandbox.name()).collect()
+    }
+    
+    /// Health of each plug-in strategy
+    pub fn strategy_statuses(&self) -> Vec<SandboxStatus> {
+        self.strategies.iter().map(|strategy| strategy.sandbox.status().clone()).collect()
+    }
+    
+    /// Alerts raised since the last call for strategies disabled by their sandbox
+    pub fn take_strategy_alerts(&mut self) -> Vec<String> {
+        self.strategies.iter_mut().filter_map(|strategy| strategy.sandbox.take_alert()).collect()
+    }
+    
+    /// Trade the pair with `strategy` alone instead of the RL agent; it is replayed
+    /// the history and the cycles on the next update
+    pub fn set_strategy(&mut self, strategy: Box<dyn Strategy>) {
+        self.strategies.clear();
+        self.add_strategy(strategy);
+    }
+    
+    /// Trade the pair with `strategy` next to those already running
+    pub fn add_strategy(&mut self, strategy: Box<dyn Strategy>) {
+        println!("🧩 {} - Trading with strategy {}", self.config.symbol, strategy.name());
+        self.strategies.push(PairStrategy::new(strategy, self.config.sandbox.clone()));
+    }
+    
+    /// What the strategy sees about the pair at its newest bar
+    fn strategy_context(&self, account: &PairAccount) -> StrategyContext {
+        let last = self.historical_data.last();
+        let bar_seconds = timeframe_duration(&self.backfill_config.timeframe)
+            .map(|interval| interval.num_seconds() as f64)
+            .unwrap_or(86_400.0);
+        StrategyContext {
+            pair: self.config.symbol.clone(),
+            timestamp: last.map(|bar| bar.timestamp).unwrap_or_else(Utc::now),
+            bar_index: self.historical_data.len().saturating_sub(1),
+            bar_seconds,
+            position_units: account.position_units,
+            equity: account.equity,
+            trading_allowed: true,
+            thin_market: false,
+        }
+    }
+    
+    /// Orders from strategy `index` for this update: its queued follow-ups, then its
+    /// answers to the new anomalies and to the bars it has not seen. On first use the
+    /// history is replayed and the cycles delivered, and only the newest bar may trade.
+    async fn run_strategy(&mut self, index: usize, context: &StrategyContext, anomalies: &[DetectedAnomaly]) -> Vec<Order> {
+        let strategy = &mut self.strategies[index];
+        let mut orders = std::mem::take(&mut strategy.pending_orders);
+        if !strategy.synced {
+            if let Some((_, replay)) = self.historical_data.split_last() {
+                strategy.last_bar = replay.last().map(|bar| bar.timestamp);
+                let event = StrategyEvent::Replay { bars: replay.to_vec(), cycles: self.cycles.clone() };
+                orders.extend(strategy.sandbox.call(context, event).await);
+            }
+            strategy.synced = true;
+        }
+        for anomaly in anomalies {
+            orders.extend(strategy.sandbox.call(context, StrategyEvent::Anomaly(Box::new(anomaly.clone()))).await);
+        }
+        let seen = strategy.last_bar;
+        let mut bar_orders = Vec::new();
+        for bar in self.historical_data.iter().filter(|bar| seen.is_none_or(|seen| bar.timestamp > seen)) {
+            let bar_context = StrategyContext { timestamp: bar.timestamp, ..context.clone() };
+            bar_orders = strategy.sandbox.call(&bar_context, StrategyEvent::Bar(bar.clone())).await;
+            strategy.last_bar = Some(bar.timestamp);
+        }
+        orders.extend(bar_orders);
+        orders
+    }
+    
+    /// Report the `index`th action of the last update, once executed, to the strategy
+    /// that placed it, queueing its follow-up orders
+    pub async fn record_fill(&mut self, index: usize, mut fill: Fill, account: &PairAccount) {
+        let context = self.strategy_context(account);
+        let Some((owner, order)) = self.submitted_orders.get(index) else {
+            return;
+        };
+        fill.reason = order.reason.clone();
+        fill.symmetry_id = order.symmetry_id.clone();
+        fill.cycle_id = order.cycle_id.clone();
+        let strategy = &mut self.strategies[*owner];
+        let orders = strategy.sandbox.call(&context, StrategyEvent::Fill(fill)).await;
+        strategy.pending_orders.extend(orders);
+    }
+    
+    /// Tell the pair's strategies that another pair leads it, queueing their orders
+    pub async fn record_lead_lag(&mut self, opportunity: &LeadLagOpportunity, account: &PairAccount) {
+        let context = self.strategy_context(account);
+        for strategy in &mut self.strategies {
+            let orders = strategy.sandbox.call(&context, StrategyEvent::LeadLag(Box::new(opportunity.clone()))).await;
+            strategy.pending_orders.extend(orders);
+        }
+    }
+    
     /// Process new market data and generate trading signals
-    pub async fn process_market_update(&mut self) -> Result<Vec<TradingAction>> {
-        if !self.is_active {
-            return Ok(Vec::new());
+    /// Detect anomalies and let the RL agent, or the pair's strategies when any are set,
+    /// act on them; anomalies matching an active rule in `suppressions` are counted
+    /// but neither alerted nor traded
+    pub async fn process_market_update(&mut self, suppressions: &SuppressionList, account: &PairAccount) -> Result<Vec<TradingAction>> {
+        let decision = self.process_market_update_seeded(suppressions, account, rand::random()).await?;
+        Ok(decision.map(|decision| decision.actions).unwrap_or_default())
+    }
+    
+    /// [`Self::process_market_update`] with the decision's random stream started
+    /// from `seed`, returning what was decided on; `None` while the pair is not trading
+    pub async fn process_market_update_seeded(
+        &mut self,
+        suppressions: &SuppressionList,
+        account: &PairAccount,
+        seed: u64,
+    ) -> Result<Option<PairDecision>> {
+        self.apply_param_changes().await?;
+        if !self.is_active || self.paused || self.drift_paused || self.feed_paused || !self.warm {
+            return Ok(None);
+        }
+        if self.check_drift().await? {
+            return Ok(None);
         }
         
-        let mut actions = Vec::new();
+        let started = std::time::Instant::now();
+        let observed_at = Utc::now();
+        self.signal_origin = Some(SignalOrigin { observed_at, bars: self.historical_data.len() });
+        self.decision_quote = self.current_price(observed_at).map(|price| DecisionQuote {
+            observed_at,
+            price,
+            spread: self.last_tick.as_ref().map_or(self.config.spread, Tick::spread),
+        });
+        let anomalies = self.detect_new_anomalies(suppressions).await?;
+        self.throughput.record_update(self.historical_data.len(), started.elapsed(), std::time::Instant::now());
+        let actions = self.decide(&anomalies, account, seed).await?;
+        self.update_composite_score();
+        
+        Ok(Some(PairDecision { anomalies, actions }))
+    }
+    
+    /// Test the bars since the last analysis for drift and respond as configured;
+    /// true when the pair paused itself
+    async fn check_drift(&mut self) -> Result<bool> {
+        let Some(event) = self.drift_monitor.check(&self.config.symbol, &self.historical_data) else {
+            return Ok(false);
+        };
+        println!("🌊 {} - Input drift: {}; responding with {:?}", self.config.symbol, event.summary(), event.actions);
+        for action in &event.actions {
+            match action {
+                DriftAction::Reanalyze => self.reanalyze().await?,
+                DriftAction::ResetExploration => self.rl_agent.reset_exploration(),
+                DriftAction::PauseTrading => self.drift_paused = true,
+            }
+        }
+        self.drift_events.push(event);
+        Ok(self.drift_paused)
+    }
+    
+    /// Drift events raised since the last call
+    pub fn take_drift_events(&mut self) -> Vec<DriftDetected> {
+        std::mem::take(&mut self.drift_events)
+    }
+    
+    /// Anomalies in recent synthetic data that no rule in `suppressions` silences
+    async fn detect_new_anomalies(&mut self, suppressions: &SuppressionList) -> Result<Vec<DetectedAnomaly>> {
+        let mut new_anomalies = Vec::new();
+        
+        // Weekly per-pair sensitivity refresh
+        if let Some(calibration) = self.anomaly_detector.maybe_recalibrate(&self.historical_data, Utc::now())? {
+            println!("🎚️  {} - Sensitivity recalibrated to {:.3}", self.config.symbol, calibration.sensitivity_threshold);
+        }
         
         // Detect anomalies in recent synthetic data
         if self.synthetic_data.len() >= 10 {
             let recent_data = self.synthetic_data.iter().rev().take(50).cloned().collect::<Vec<_>>();
             let anomalies = self.anomaly_detector.detect_anomalies(&recent_data).await?;
             
+            let now = Utc::now();
             for anomaly in anomalies {
+                if suppressions.check(&self.config.symbol, &anomaly, now).is_some() {
+                    self.suppressed_anomalies += 1;
+                    continue;
+                }
                 self.performance.anomalies_detected += 1;
+                if matches!(anomaly.anomaly_type, AnomalyType::LiquidityGap { .. }) {
+                    self.last_liquidity_gap = Some(now);
+                }
                 self.recent_anomalies.push(anomaly.clone());
                 
                 // Keep only last 100 anomalies
                 if self.recent_anomalies.len() > 100 {
                     self.recent_anomalies.remove(0);
                 }
-                
-                // Generate trading action based on anomaly
+                new_anomalies.push(anomaly);
+            }
+        }
+        
+        Ok(new_anomalies)
+    }
+    
+    /// Trading actions for `anomalies` and the bars the strategies have not seen.
+    /// Depends only on its arguments and the pair's history, cycles, trade count and
+    /// strategies, so a session log can replay it exactly.
+    pub async fn decide(&mut self, anomalies: &[DetectedAnomaly], account: &PairAccount, seed: u64) -> Result<Vec<TradingAction>> {
+        self.rl_agent.reseed(seed);
+        let mut actions = Vec::new();
+        
+        if self.strategies.is_empty() {
+            for anomaly in anomalies {
                 let state_id = format!("{}_{}", self.config.symbol, self.performance.total_trades);
-                let action = self.rl_agent.choose_action(&state_id, &anomaly)?;
-                actions.push(action);
+                actions.push(self.rl_agent.choose_action(&state_id, anomaly)?);
+            }
+            return Ok(actions);
+        }
+        
+        let context = self.strategy_context(account);
+        self.submitted_orders.clear();
+        for index in 0..self.strategies.len() {
+            for order in self.run_strategy(index, &context, anomalies).await {
+                if let Some(action) = order_action(&order, account.units_per_size) {
+                    actions.push(action);
+                    self.submitted_orders.push((index, order));
+                }
             }
         }
         
         Ok(actions)
     }
     
+    /// Score the newest bar once, keeping the last 500 scores
+    fn update_composite_score(&mut self) {
+        let Some(last) = self.historical_data.last() else {
+            return;
+        };
+        if self.composite_scores.last().is_some_and(|s| s.timestamp >= last.timestamp) {
+            return;
+        }
+        if let Some(score) = self.composite_scorer.score(&self.historical_data, &self.recent_anomalies) {
+            self.composite_scores.push(score);
+            if self.composite_scores.len() > 500 {
+                self.composite_scores.remove(0);
+            }
+        }
+    }
+    
+    /// Throughput row of the system panel, bars per second averaged over `window`
+    pub fn throughput_row(&self, now: std::time::Instant, window: std::time::Duration) -> PairThroughput {
+        let last_bar = self.historical_data.last().map(|point| point.timestamp);
+        let last_tick = self.last_tick.as_ref().map(|tick| tick.timestamp);
+        let milliseconds = |latency: std::time::Duration| latency.as_secs_f64() * 1_000.0;
+        PairThroughput {
+            symbol: self.config.symbol.clone(),
+            bars_per_sec: self.throughput.bars_per_sec(now, window),
+            bars_processed: self.throughput.bars_processed(),
+            detection_latency_ms: self.throughput.last_latency().map(milliseconds),
+            detection_latency_p95_ms: self.throughput.p95_latency().map(milliseconds),
+            last_data: last_bar.max(last_tick),
+            backlog_bars: self.throughput.backlog(self.historical_data.len()),
+            bar_seconds: timeframe_duration(&self.backfill_config.timeframe)
+                .map(|interval| interval.num_seconds())
+                .unwrap_or(86_400),
+            lagging: Vec::new(),
+        }
+    }
+    
+    /// Latest known price: the live quote, else the newest synthetic bar not in the future,
+    /// else the last historical close
+    pub fn current_price(&self, now: DateTime<Utc>) -> Option<f64> {
+        if let Some(tick) = &self.last_tick {
+            return Some(tick.mid());
+        }
+        self.synthetic_data.iter()
+            .rev()
+            .find(|point| point.data_point.timestamp <= now)
+            .map(|point| point.data_point.close)
+            .or_else(|| self.historical_data.last().map(|point| point.close))
+    }
+    
     /// Update performance metrics with trade result
     pub fn update_performance(&mut self, reward: f64) {
         let is_successful = reward > 0.0;
@@ -235,81 +1005,1267 @@ impl CurrencyPairState {
     }
 }
 
+/// Operator command routed to the manager
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ControlCommand {
+    /// Stop generating signals for a pair
+    Pause { pair: String },
+    /// Resume signal generation for a pair
+    Resume { pair: String },
+    /// Close the position in one pair, or every position when `pair` is `None`
+    Flatten { pair: Option<String> },
+    /// Replace the portfolio risk limits
+    SetRisk { max_drawdown_pct: f64 },
+    /// Replace the signal latency budget; `None` lifts a limit
+    SetSignalLatency { max_age_seconds: Option<f64>, max_age_bars: Option<usize> },
+    /// Close every position and refuse new exposure until reset
+    KillSwitch,
+    /// Re-arm a tripped kill switch
+    ResetKillSwitch,
+    /// Silence an anomaly pattern for the acknowledgment period
+    Acknowledge { pair: Option<String>, pattern_id: String, anomaly_type: String },
+    /// Silence an anomaly pattern for `minutes`, or the configured suppression period
+    Suppress { pair: Option<String>, pattern_id: String, anomaly_type: String, minutes: Option<i64> },
+    /// Remove an acknowledgment or suppression rule
+    Unsuppress { id: String },
+    /// Change analysis parameters on the running pairs
+    SetParams { update: ParamsUpdate },
+    /// Send actions at once or hold them for approval, optionally changing the countdown
+    SetExecutionMode { mode: ExecutionMode, timeout_seconds: Option<u64> },
+    /// Send a held action, or every held action when `id` is `None`
+    Approve { id: Option<u64> },
+    /// Drop a held action, or every held action when `id` is `None`
+    Reject { id: Option<u64> },
+}
+
+impl ControlCommand {
+    /// Build a command from the wire fields (`action`, `pair`, `parameters`).
+    ///
+    /// Returns `Ok(None)` for actions that are not control commands.
+    pub fn parse(action: &str, pair: Option<&str>, parameters: &HashMap<String, String>) -> Result<Option<Self>> {
+        let require_pair = || pair
+            .map(|p| p.to_uppercase())
+            .ok_or_else(|| anyhow::anyhow!("{} requires a pair", action));
+        
+        let command = match action {
+            "pause" => ControlCommand::Pause { pair: require_pair()? },
+            "resume" => ControlCommand::Resume { pair: require_pair()? },
+            "flatten" => match require_pair()?.as_str() {
+                "ALL" => ControlCommand::Flatten { pair: None },
+                symbol => ControlCommand::Flatten { pair: Some(symbol.to_string()) },
+            },
+            "set_risk" | "set-risk" => {
+                let value = parameters.get("max_drawdown")
+                    .ok_or_else(|| anyhow::anyhow!("set-risk requires max_drawdown"))?;
+                ControlCommand::SetRisk { max_drawdown_pct: parse_percent(value)? }
+            }
+            "set_latency" | "set-latency" => {
+                // "off" lifts a limit; at least one of the two must be given
+                let limit = |key: &str| parameters.get(key).map(|value| value.trim()).filter(|value| *value != "off");
+                if !parameters.contains_key("seconds") && !parameters.contains_key("bars") {
+                    anyhow::bail!("set-latency requires seconds or bars");
+                }
+                let max_age_seconds = limit("seconds")
+                    .map(|value| value.trim_end_matches('s').parse::<f64>().map_err(|_| anyhow::anyhow!("invalid seconds '{}'", value)))
+                    .transpose()?;
+                let max_age_bars = limit("bars")
+                    .map(|value| value.parse::<usize>().map_err(|_| anyhow::anyhow!("invalid bars '{}'", value)))
+                    .transpose()?;
+                ControlCommand::SetSignalLatency { max_age_seconds, max_age_bars }
+            }
+            "kill_switch" | "kill-switch" => ControlCommand::KillSwitch,
+            "reset_kill_switch" | "reset-kill-switch" => ControlCommand::ResetKillSwitch,
+            "acknowledge" | "ack" | "suppress" => {
+                // A missing pair or "ALL" applies the rule to every pair
+                let pair = pair.map(|p| p.to_uppercase()).filter(|p| p != "ALL");
+                let field = |key: &str| parameters.get(key).cloned().unwrap_or_else(|| WILDCARD.to_string());
+                let (pattern_id, anomaly_type) = (field("pattern"), field("type"));
+                if pattern_id == WILDCARD && anomaly_type == WILDCARD {
+                    anyhow::bail!("{} requires a pattern or type", action);
+                }
+                if action == "suppress" {
+                    let minutes = parameters.get("minutes")
+                        .map(|m| m.parse::<i64>().map_err(|_| anyhow::anyhow!("invalid minutes '{}'", m)))
+                        .transpose()?;
+                    ControlCommand::Suppress { pair, pattern_id, anomaly_type, minutes }
+                } else {
+                    ControlCommand::Acknowledge { pair, pattern_id, anomaly_type }
+                }
+            }
+            "set_params" | "set-params" => {
+                let threshold = |key: &str| parameters.get(key)
+                    .map(|value| value.trim().parse::<f64>().map_err(|_| anyhow::anyhow!("invalid {} '{}'", key, value)))
+                    .transpose();
+                let anomaly_sensitivity = match parameters.get("sensitivity").map(|value| value.trim()) {
+                    None => None,
+                    Some("auto") | Some("calibrated") => Some(Sensitivity::Calibrated),
+                    Some(value) => Some(Sensitivity::Fixed(
+                        value.parse().map_err(|_| anyhow::anyhow!("invalid sensitivity '{}'", value))?,
+                    )),
+                };
+                let update = ParamsUpdate {
+                    anomaly_sensitivity,
+                    cycle_confidence_threshold: threshold("cycle_confidence")?,
+                    symmetry_strength_threshold: threshold("symmetry_strength")?,
+                };
+                if update.is_empty() {
+                    anyhow::bail!("set-params requires sensitivity, cycle_confidence or symmetry_strength");
+                }
+                ControlCommand::SetParams { update }
+            }
+            "unsuppress" => ControlCommand::Unsuppress {
+                id: parameters.get("id").cloned().ok_or_else(|| anyhow::anyhow!("unsuppress requires id"))?,
+            },
+            "set_mode" | "set-mode" => {
+                let mode = parameters.get("mode").ok_or_else(|| anyhow::anyhow!("set-mode requires mode"))?.parse()?;
+                let timeout_seconds = parameters.get("timeout")
+                    .map(|value| value.trim().trim_end_matches('s').parse::<u64>().map_err(|_| anyhow::anyhow!("invalid timeout '{}'", value)))
+                    .transpose()?;
+                ControlCommand::SetExecutionMode { mode, timeout_seconds }
+            }
+            "approve" | "reject" => {
+                // A missing id or "all" answers every held action
+                let id = match parameters.get("id").map(|value| value.trim().trim_start_matches('#')) {
+                    None | Some("all") => None,
+                    Some(value) => Some(value.parse::<u64>().map_err(|_| anyhow::anyhow!("invalid approval id '{}'", value))?),
+                };
+                if action == "approve" { ControlCommand::Approve { id } } else { ControlCommand::Reject { id } }
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(command))
+    }
+}
+
+/// Parse "5%" or "5" as 5 percent
+pub fn parse_percent(value: &str) -> Result<f64> {
+    let number = value.trim().trim_end_matches('%').trim();
+    number.parse::<f64>()
+        .map_err(|_| anyhow::anyhow!("invalid percentage '{}'", value))
+}
+
+impl std::fmt::Display for ControlCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ControlCommand::Pause { pair } => write!(f, "pause {}", pair),
+            ControlCommand::Resume { pair } => write!(f, "resume {}", pair),
+            ControlCommand::Flatten { pair: Some(pair) } => write!(f, "flatten {}", pair),
+            ControlCommand::Flatten { pair: None } => write!(f, "flatten all"),
+            ControlCommand::SetRisk { max_drawdown_pct } => write!(f, "set-risk --max-drawdown {}%", max_drawdown_pct),
+            ControlCommand::SetSignalLatency { max_age_seconds, max_age_bars } => {
+                let limit = |value: Option<String>| value.unwrap_or_else(|| "off".to_string());
+                write!(f, "set-latency --seconds {} --bars {}",
+                       limit(max_age_seconds.map(|s| s.to_string())), limit(max_age_bars.map(|b| b.to_string())))
+            }
+            ControlCommand::KillSwitch => write!(f, "kill-switch"),
+            ControlCommand::ResetKillSwitch => write!(f, "reset-kill-switch"),
+            ControlCommand::Acknowledge { pair, pattern_id, anomaly_type } => {
+                write!(f, "acknowledge {} {}/{}", pair.as_deref().unwrap_or("all"), pattern_id, anomaly_type)
+            }
+            ControlCommand::Suppress { pair, pattern_id, anomaly_type, minutes } => {
+                write!(f, "suppress {} {}/{}", pair.as_deref().unwrap_or("all"), pattern_id, anomaly_type)?;
+                match minutes {
+                    Some(minutes) => write!(f, " --minutes {}", minutes),
+                    None => Ok(()),
+                }
+            }
+            ControlCommand::Unsuppress { id } => write!(f, "unsuppress {}", id),
+            ControlCommand::SetParams { update } => write!(f, "set-params {}", update),
+            ControlCommand::SetExecutionMode { mode, timeout_seconds } => {
+                write!(f, "set-mode {}", mode)?;
+                match timeout_seconds {
+                    Some(seconds) => write!(f, " --timeout {}s", seconds),
+                    None => Ok(()),
+                }
+            }
+            ControlCommand::Approve { id } | ControlCommand::Reject { id } => {
+                let verb = if matches!(self, ControlCommand::Approve { .. }) { "approve" } else { "reject" };
+                match id {
+                    Some(id) => write!(f, "{} #{}", verb, id),
+                    None => write!(f, "{} all", verb),
+                }
+            }
+        }
+    }
+}
+
 /// Multi-currency trading system manager
 pub struct MultiCurrencyManager {
     pub pairs: RwLock<HashMap<String, CurrencyPairState>>,
     pub active_pairs: Vec<String>,
     pub global_performance: RwLock<HashMap<String, PairPerformanceMetrics>>,
+    pub portfolio: RwLock<Portfolio>,
+    pub risk_limits: RwLock<RiskLimits>,
+    /// Drawdown, pair correlations and the kill switch the limits are checked with
+    pub risk: RwLock<RiskEngine>,
+    pub audit_log: AuditLog,
+    /// Database replayed at startup to fill bars missing from the history
+    pub backfill_db: Option<EmbeddedForexDB>,
+    /// Source of backfill bars and live ticks
+    pub data_provider: Option<Arc<dyn DataProvider>>,
+    /// Staleness and clock-skew state of the live feed
+    pub feed_health: RwLock<FeedHealthMonitor>,
+    /// Operator acknowledgments and suppressions of recurring anomalies
+    pub suppressions: SuppressionList,
+    /// Stops order submission after repeated broker failures
+    pub broker_breaker: CircuitBreaker,
+    /// Stops persisting live bars after repeated database failures
+    pub db_breaker: CircuitBreaker,
+    /// Fault injection for robustness tests
+    pub fault_injector: Option<Arc<FaultInjector>>,
+    /// Decision inputs and actions, recorded for offline replay
+    pub session_log: Option<SessionLog>,
+    /// How per-pair risk budgets are sized and how often
+    pub allocation_config: AllocationConfig,
+    /// Directory trained pair models are restored from at initialization
+    pub model_load_dir: Option<PathBuf>,
+    /// Directory trained pair models are saved to after initialization
+    pub model_save_dir: Option<PathBuf>,
+    /// Analysis parameters changeable at runtime, followed by every pair
+    pub analysis_params: ParamsHandle,
+    /// When the system panel flags a pair as lagging
+    pub throughput_config: ThroughputConfig,
+    /// Whether actions are sent at once or held for an operator, and for how long
+    pub approval_config: RwLock<ApprovalConfig>,
+    /// Actions held for approval
+    pub approvals: RwLock<ApprovalQueue>,
+    /// Fills against the price and spread they were decided on
+    pub execution_log: ExecutionLog,
+    /// Where pairs added later load their history from; each pair's defaults otherwise
+    pub data_config: Option<DataConfig>,
+    /// Broker or simulator orders are filled by; at the decision price without one
+    pub execution_backend: Option<Arc<dyn ExecutionBackend>>,
+    /// When one pair counts as leading another
+    pub lead_lag_config: LeadLagConfig,
+    /// Leads found at the last correlation refresh, strongest first
+    pub lead_lag: RwLock<Vec<LeadLagOpportunity>>,
+    /// When cycles of different pairs count as the same one
+    pub shared_cycle_config: SharedCycleConfig,
+    /// Periods in which no new positions are opened
+    pub trading_windows: TradingWindowsConfig,
 }
 
 impl MultiCurrencyManager {
     /// Create new multi-currency manager
     pub fn new() -> Self {
+        Self::with_portfolio_config(PortfolioConfig::default())
+    }
+    
+    /// Create a manager whose portfolio uses the given account settings
+    pub fn with_portfolio_config(portfolio_config: PortfolioConfig) -> Self {
         Self {
             pairs: RwLock::new(HashMap::new()),
             active_pairs: Vec::new(),
             global_performance: RwLock::new(HashMap::new()),
+            risk: RwLock::new(RiskEngine::new(portfolio_config.initial_balance)),
+            portfolio: RwLock::new(Portfolio::new(portfolio_config)),
+            risk_limits: RwLock::new(RiskLimits::default()),
+            audit_log: AuditLog::new(),
+            backfill_db: None,
+            data_provider: None,
+            feed_health: RwLock::new(FeedHealthMonitor::default()),
+            suppressions: SuppressionList::default(),
+            broker_breaker: CircuitBreaker::new("broker", CircuitBreakerConfig::default()),
+            db_breaker: CircuitBreaker::new("database", CircuitBreakerConfig::default()),
+            fault_injector: None,
+            session_log: None,
+            allocation_config: AllocationConfig::default(),
+            model_load_dir: None,
+            model_save_dir: None,
+            analysis_params: ParamsHandle::new(AnalysisParams::from_config(&CurrencyPairConfig::default().pipeline_config())),
+            throughput_config: ThroughputConfig::default(),
+            approval_config: RwLock::new(ApprovalConfig::default()),
+            approvals: RwLock::new(ApprovalQueue::default()),
+            execution_log: ExecutionLog::new(),
+            data_config: None,
+            execution_backend: None,
+            lead_lag_config: LeadLagConfig::default(),
+            lead_lag: RwLock::new(Vec::new()),
+            shared_cycle_config: SharedCycleConfig::default(),
+            trading_windows: TradingWindowsConfig::default(),
         }
     }
     
+    /// Mirror audit entries to a JSON-lines file
+    pub fn with_audit_log(mut self, audit_log: AuditLog) -> Self {
+        self.audit_log = audit_log;
+        self
+    }
+    
+    /// Use `suppressions` (e.g. one persisted to a file) for anomaly acknowledgments
+    pub fn with_suppressions(mut self, suppressions: SuppressionList) -> Self {
+        self.suppressions = suppressions;
+        self
+    }
+    
+    /// Replace the broker and database circuit breaker thresholds
+    pub fn with_circuit_breaker_config(mut self, config: CircuitBreakerConfig) -> Self {
+        self.broker_breaker = CircuitBreaker::new("broker", config.clone());
+        self.db_breaker = CircuitBreaker::new("database", config);
+        self
+    }
+    
+    /// Inject broker and database faults (robustness tests only)
+    pub fn with_fault_injector(mut self, injector: Arc<FaultInjector>) -> Self {
+        self.fault_injector = Some(injector);
+        self
+    }
+    
+    /// Record every pair update and fill to `log` for `replay-session`
+    pub fn with_session_log(mut self, log: SessionLog) -> Self {
+        self.session_log = Some(log);
+        self
+    }
+    
+    /// Record fills to `log`, e.g. one mirrored to a file for backtest calibration
+    pub fn with_execution_log(mut self, log: ExecutionLog) -> Self {
+        self.execution_log = log;
+        self
+    }
+    
+    /// Slippage, time to fill and spread of recent fills per pair, session and broker
+    pub fn execution_quality(&self) -> ExecutionQualityReport {
+        self.execution_log.report()
+    }
+    
+    /// Size per-pair risk budgets with `config`
+    pub fn with_allocation_config(mut self, config: AllocationConfig) -> Self {
+        self.allocation_config = config;
+        self
+    }
+    
+    /// Restore pairs from the models saved in `dir` instead of re-analyzing their history;
+    /// pairs without a usable model are analyzed as usual
+    pub fn with_model_load_dir(mut self, dir: PathBuf) -> Self {
+        self.model_load_dir = Some(dir);
+        self
+    }
+    
+    /// Save every pair's model to `dir` once the pairs are initialized
+    pub fn with_model_save_dir(mut self, dir: PathBuf) -> Self {
+        self.model_save_dir = Some(dir);
+        self
+    }
+    
+    /// Hold or send actions as `config` says
+    pub fn with_approval_config(mut self, config: ApprovalConfig) -> Self {
+        self.approval_config = RwLock::new(config);
+        self
+    }
+    
+    /// Load the history of pairs added from now on as `config` says
+    pub fn with_data_config(mut self, config: DataConfig) -> Self {
+        self.data_config = Some(config);
+        self
+    }
+    
+    /// Open no positions in the blocked periods of `config`
+    pub fn with_trading_windows(mut self, config: TradingWindowsConfig) -> Self {
+        self.trading_windows = config;
+        self
+    }
+    
+    /// Fill orders through `backend` instead of at the decision price
+    pub fn with_execution_backend(mut self, backend: Arc<dyn ExecutionBackend>) -> Self {
+        self.execution_backend = Some(backend);
+        self
+    }
+    
+    /// Replay missing bars from `db` when pairs are initialized
+    pub fn with_backfill_db(mut self, db: EmbeddedForexDB) -> Self {
+        self.backfill_db = Some(db);
+        self
+    }
+    
+    /// Backfill from and stream live quotes from `provider`
+    pub fn with_data_provider(mut self, provider: Arc<dyn DataProvider>) -> Self {
+        self.data_provider = Some(provider);
+        self
+    }
+    
+    /// Replace the feed staleness / clock-skew thresholds
+    pub fn with_feed_health_config(mut self, config: FeedHealthConfig) -> Self {
+        self.feed_health = RwLock::new(FeedHealthMonitor::new(config));
+        self
+    }
+    
+    /// Subscribe to live ticks for every active pair, routing each to its pair state.
+    ///
+    /// Future-dated ticks are dropped, and pairs whose feed goes stale are paused until it recovers.
+    pub fn start_live_feed(self: &Arc<Self>) -> Result<tokio::task::JoinHandle<()>> {
+        let provider = self.data_provider.as_ref()
+            .ok_or_else(|| anyhow::anyhow!("no data provider configured"))?;
+        let mut ticks = provider.subscribe_ticks(&self.active_pairs)?;
+        println!("📡 Streaming live quotes for {} pairs from {}", self.active_pairs.len(), provider.name());
+        
+        let manager = Arc::clone(self);
+        Ok(tokio::spawn(async move {
+            let check_interval = {
+                let mut monitor = manager.feed_health.write().await;
+                let now = Utc::now();
+                for pair in &manager.active_pairs {
+                    monitor.watch(pair, now);
+                }
+                std::time::Duration::from_millis(monitor.config().expected_interval_ms.max(100) as u64)
+            };
+            let mut watchdog = tokio::time::interval(check_interval);
+            
+            loop {
+                tokio::select! {
+                    tick = ticks.recv() => {
+                        let Some(tick) = tick else { break };
+                        if !manager.feed_health.write().await.record_tick(&tick, Utc::now()) {
+                            println!("⏱️  {} tick at {} is future-dated, dropped", tick.symbol, tick.timestamp);
+                            continue;
+                        }
+                        if let Some(backend) = &manager.execution_backend {
+                            backend.observe_quote(&tick);
+                        }
+                        let symbol = tick.symbol.clone();
+                        let completed = manager.pairs.write().await.get_mut(&symbol).and_then(|state| state.on_tick(tick));
+                        if let Some(bar) = completed {
+                            manager.persist_bar(&symbol, &bar).await;
+                        }
+                    }
+                    _ = watchdog.tick() => {
+                        manager.feed_health.write().await.check(Utc::now());
+                        manager.sync_feed_pauses().await;
+                    }
+                }
+            }
+            println!("⚠️  Live feed closed");
+        }))
+    }
+    
+    /// Store a completed live bar in the backfill database so a restart replays it.
+    ///
+    /// Failures are logged and counted by the database breaker; while it is open, bars
+    /// stay in memory only and the next startup backfills them from the provider.
+    pub async fn persist_bar(&self, symbol: &str, bar: &ForexDataPoint) {
+        let Some(db) = &self.backfill_db else { return };
+        if !self.db_breaker.allow(Utc::now()) {
+            return;
+        }
+        let write = async {
+            if let Some(injector) = &self.fault_injector {
+                injector.db_write().await?;
+            }
+            db.store_candles_columnar(symbol, std::slice::from_ref(bar))
+        };
+        match tokio::time::timeout(self.db_breaker.call_timeout(), write).await {
+            Ok(Ok(_)) => self.db_breaker.record_success(),
+            Ok(Err(e)) => {
+                println!("⚠️  {} bar at {} not persisted: {}", symbol, bar.timestamp, e);
+                self.db_breaker.record_failure(&e.to_string(), Utc::now());
+            }
+            Err(_) => {
+                println!("⚠️  {} bar at {} not persisted: write timed out", symbol, bar.timestamp);
+                self.db_breaker.record_failure("write timed out", Utc::now());
+            }
+        }
+    }
+    
+    /// Broker and database circuit breaker states
+    pub fn circuit_breakers(&self) -> Vec<BreakerStatus> {
+        vec![self.broker_breaker.status(), self.db_breaker.status()]
+    }
+    
+    /// Pause pairs whose feed blocks trading and resume those that recovered, auditing each change
+    pub async fn sync_feed_pauses(&self) {
+        let monitor = self.feed_health.read().await;
+        let mut pairs_map = self.pairs.write().await;
+        for (symbol, state) in pairs_map.iter_mut() {
+            let Some(feed_state) = monitor.state(symbol) else { continue };
+            let pause = feed_state.blocks_trading();
+            if pause == state.feed_paused {
+                continue;
+            }
+            state.feed_paused = pause;
+            let (command, message) = if pause {
+                (format!("pause {}", symbol), format!("feed {}", feed_state))
+            } else {
+                (format!("resume {}", symbol), "feed recovered".to_string())
+            };
+            println!("📡 {} {} automatically: {}", symbol, if pause { "paused" } else { "resumed" }, message);
+            self.audit_log.record("feed-monitor", &command, true, &message);
+        }
+    }
+    
+    /// Live feed health, `None` without a data provider
+    pub async fn feed_health_report(&self) -> Option<FeedHealthReport> {
+        self.data_provider.as_ref()?;
+        Some(self.feed_health.read().await.report(Utc::now()))
+    }
+    
     /// Initialize with major currency pairs
     pub async fn initialize_major_pairs(&mut self) -> Result<()> {
-        let major_pairs = vec![
-            CurrencyPairConfig { symbol: "EURUSD".to_string(), base_currency: "EUR".to_string(), quote_currency: "USD".to_string(), ..Default::default() },
-            CurrencyPairConfig { symbol: "GBPUSD".to_string(), base_currency: "GBP".to_string(), quote_currency: "USD".to_string(), ..Default::default() },
-            CurrencyPairConfig { symbol: "USDJPY".to_string(), base_currency: "USD".to_string(), quote_currency: "JPY".to_string(), pip_value: 0.01, ..Default::default() },
-            CurrencyPairConfig { symbol: "USDCHF".to_string(), base_currency: "USD".to_string(), quote_currency: "CHF".to_string(), ..Default::default() },
-            CurrencyPairConfig { symbol: "USDCAD".to_string(), base_currency: "USD".to_string(), quote_currency: "CAD".to_string(), ..Default::default() },
-            CurrencyPairConfig { symbol: "EURGBP".to_string(), base_currency: "EUR".to_string(), quote_currency: "GBP".to_string(), ..Default::default() },
-            CurrencyPairConfig { symbol: "EURJPY".to_string(), base_currency: "EUR".to_string(), quote_currency: "JPY".to_string(), pip_value: 0.01, ..Default::default() },
-        ];
+        let major_pairs = ["EURUSD", "GBPUSD", "USDJPY", "USDCHF", "USDCAD", "EURGBP", "EURJPY"];
+        self.initialize_pairs(&major_pairs.map(String::from)).await?;
+        println!("🌍 Multi-currency manager initialized with {} major pairs", self.active_pairs.len());
+        Ok(())
+    }
+    
+    /// Add pairs by symbol (e.g. "EURUSD"), deriving currencies and pip size from the symbol
+    pub async fn initialize_pairs(&mut self, symbols: &[String]) -> Result<()> {
+        let pair_configs = symbols.iter().map(|symbol| {
+            let (base_currency, quote_currency) = crate::portfolio::split_symbol(symbol);
+            let mut config = CurrencyPairConfig {
+                symbol: symbol.to_uppercase(),
+                pip_value: crate::units::pip_size(symbol),
+                base_currency,
+                quote_currency,
+                ..Default::default()
+            };
+            if let Some(data_config) = &self.data_config {
+                config.pipeline.data = data_config.clone();
+            }
+            config
+        });
         
         let mut pairs_map = self.pairs.write().await;
         let mut performance_map = self.global_performance.write().await;
         
-        for config in major_pairs {
+        for config in pair_configs {
             let symbol = config.symbol.clone();
+            if pairs_map.contains_key(&symbol) {
+                continue;
+            }
             self.active_pairs.push(symbol.clone());
             
-            let pair_state = CurrencyPairState::new(config).await?;
+            let mut pair_state = CurrencyPairState::new(config).await?;
+            if let Some(data_config) = &self.data_config {
+                pair_state.data_path = data_config.data_directory.clone();
+            }
+            pair_state.follow_params(&self.analysis_params);
             performance_map.insert(symbol.clone(), PairPerformanceMetrics::new(symbol.clone()));
             pairs_map.insert(symbol, pair_state);
         }
         
-        println!("🌍 Multi-currency manager initialized with {} major pairs", self.active_pairs.len());
         Ok(())
     }
     
     /// Initialize all currency pairs with historical data
-    pub async fn initialize_all_pairs(&mut self) -> Result<()> {
+    pub async fn initialize_all_pairs(&self) -> Result<()> {
         let mut pairs_map = self.pairs.write().await;
+        let mut resolved_source: Option<HistoricalSource> = None;
         
         for symbol in &self.active_pairs {
             if let Some(pair_state) = pairs_map.get_mut(symbol) {
-                pair_state.initialize().await?;
+                if let Some(source) = &resolved_source {
+                    pair_state.adopt_historical_source(source);
+                }
+                let model = match &self.model_load_dir {
+                    Some(dir) => PairModel::load(dir, symbol).unwrap_or_else(|e| {
+                        println!("⚠️  {} - Saved model unusable, analyzing the history: {}", symbol, e);
+                        None
+                    }),
+                    None => None,
+                };
+                pair_state.initialize_with_model(self.backfill_db.as_ref(), self.data_provider.as_deref(), model).await?;
+                
+                // A fallback decision (or alternative path) applies to every remaining pair
+                if let Some(source) = &pair_state.historical_source {
+                    if resolved_source.is_none() || !matches!(source, HistoricalSource::Files(_)) {
+                        resolved_source = Some(source.clone());
+                    }
+                }
             }
         }
         
-        println!("🚀 All currency pairs initialized successfully!");
+        let warm = pairs_map.values().filter(|state| state.warm).count();
+        println!("🚀 All currency pairs initialized successfully! ({}/{} warm)", warm, pairs_map.len());
+        drop(pairs_map);
+        if let Some(dir) = &self.model_save_dir {
+            self.save_models(dir).await?;
+        }
         Ok(())
     }
     
+    /// Save the model of every analyzed pair to `dir`, returning how many were saved
+    pub async fn save_models(&self, dir: &std::path::Path) -> Result<usize> {
+        let pairs_map = self.pairs.read().await;
+        let mut saved = 0;
+        for state in pairs_map.values().filter(|state| state.is_active) {
+            state.model().save(dir)?;
+            saved += 1;
+        }
+        println!("💾 Saved {} pair models to {}", saved, dir.display());
+        Ok(saved)
+    }
+    
     /// Get performance summary for all pairs
     pub async fn get_performance_summary(&self) -> HashMap<String, PairPerformanceMetrics> {
         let performance_map = self.global_performance.read().await;
         performance_map.clone()
     }
     
+    /// Latest composite score of every pair that has one
+    pub async fn composite_scores(&self) -> HashMap<String, CompositeScore> {
+        let pairs_map = self.pairs.read().await;
+        pairs_map.iter()
+            .filter_map(|(symbol, state)| state.composite_scores.last().map(|score| (symbol.clone(), score.clone())))
+            .collect()
+    }
+    
+    /// Recent composite scores of `symbol`, oldest first
+    pub async fn composite_score_history(&self, symbol: &str) -> Option<Vec<CompositeScore>> {
+        let pairs_map = self.pairs.read().await;
+        pairs_map.get(&symbol.to_uppercase()).map(|state| state.composite_scores.clone())
+    }
+    
+    /// Synthetic data realism of every pair that has been validated
+    pub async fn synthetic_quality_reports(&self) -> HashMap<String, SyntheticQualityReport> {
+        let pairs_map = self.pairs.read().await;
+        pairs_map.iter()
+            .filter_map(|(symbol, state)| state.synthetic_quality.clone().map(|report| (symbol.clone(), report)))
+            .collect()
+    }
+    
+    /// Cycle periods found in several active pairs at once, most widely shared
+    /// first, with phases compared at `reference`
+    pub async fn shared_cycles(&self, reference: DateTime<Utc>) -> Vec<SharedCycle> {
+        let pairs_map = self.pairs.read().await;
+        let pairs: Vec<PairCycles> = self.active_pairs.iter()
+            .filter_map(|symbol| pairs_map.get(symbol).map(|state| (symbol, state)))
+            .map(|(symbol, state)| PairCycles {
+                pair: symbol.clone(),
+                bar_seconds: timeframe_duration(&state.backfill_config.timeframe)
+                    .map(|duration| duration.num_seconds() as f64)
+                    .unwrap_or(86_400.0),
+                cycles: state.cycles.clone(),
+            })
+            .collect();
+        find_shared_cycles(&pairs, reference, &self.shared_cycle_config)
+    }
+    
+    /// Confluence of `symbol` with the market: the best score, in `[0, 1]`, of
+    /// the shared cycles it takes part in, scaled by how closely the pairs'
+    /// phases agree; 0 when none of its cycles is shared
+    pub async fn cycle_confluence(&self, symbol: &str) -> f64 {
+        let symbol = symbol.to_uppercase();
+        self.shared_cycles(Utc::now()).await.iter()
+            .filter(|shared| shared.pairs.contains(&symbol))
+            .map(|shared| shared.score * shared.phase_coherence)
+            .fold(0.0, f64::max)
+    }
+    
+    /// Throughput of every active pair, flagging those behind the others;
+    /// `stream_backlog` is the depth of the dashboard streams, when there are any
+    pub async fn throughput_panel(&self, stream_backlog: usize) -> ThroughputPanel {
+        let now = std::time::Instant::now();
+        let window = std::time::Duration::from_secs(self.throughput_config.window_secs.max(1));
+        let pairs_map = self.pairs.read().await;
+        let rows = self.active_pairs.iter()
+            .filter_map(|symbol| pairs_map.get(symbol))
+            .map(|state| state.throughput_row(now, window))
+            .collect();
+        ThroughputPanel::new(rows, stream_backlog, &self.throughput_config, Utc::now())
+    }
+    
+    /// Analysis parameters each pair runs with
+    pub async fn pair_params(&self) -> HashMap<String, AnalysisParams> {
+        let pairs_map = self.pairs.read().await;
+        pairs_map.iter().map(|(symbol, state)| (symbol.clone(), state.analysis_params.clone())).collect()
+    }
+    
+    /// Latest price for every pair that has one
+    pub async fn current_prices(&self) -> HashMap<String, f64> {
+        let now = Utc::now();
+        let pairs_map = self.pairs.read().await;
+        pairs_map.iter()
+            .filter_map(|(symbol, state)| state.current_price(now).map(|price| (symbol.clone(), price)))
+            .collect()
+    }
+    
+    /// Recompute the active pairs' risk budgets from their history and size later actions by them
+    pub async fn rebalance_allocation(&self) -> Result<Allocation> {
+        let allocator = RiskAllocator::new(self.allocation_config.clone())?;
+        let allocation = {
+            let pairs_map = self.pairs.read().await;
+            let histories: Vec<(String, &[ForexDataPoint])> = self.active_pairs.iter()
+                .filter_map(|symbol| pairs_map.get(symbol).map(|state| (symbol.clone(), state.historical_data.as_slice())))
+                .collect();
+            allocator.allocate(&histories)?
+        };
+        println!("⚖️  Risk budgets ({:?}): {}", allocation.method, allocation.pairs.iter()
+            .map(|pair| format!("{} {:.1}%", pair.symbol, pair.weight * 100.0))
+            .collect::<Vec<_>>()
+            .join(", "));
+        self.portfolio.write().await.set_allocation(allocation.clone());
+        Ok(allocation)
+    }
+    
+    /// Rebalance risk budgets and pair correlations now and then every `rebalance_minutes`
+    pub fn start_allocation_schedule(self: &Arc<Self>) -> tokio::task::JoinHandle<()> {
+        let manager = Arc::clone(self);
+        let period = std::time::Duration::from_secs(manager.allocation_config.rebalance_minutes.max(1) * 60);
+        tokio::spawn(async move {
+            let mut schedule = tokio::time::interval(period);
+            loop {
+                schedule.tick().await;
+                if let Err(e) = manager.rebalance_allocation().await {
+                    println!("⚠️  Risk budget rebalance failed: {}", e);
+                }
+                if let Err(e) = manager.refresh_correlations().await {
+                    println!("⚠️  Pair correlation refresh failed: {}", e);
+                }
+            }
+        })
+    }
+    
+    /// Position and equity of `symbol` for its strategy
+    async fn pair_account(&self, symbol: &str, prices: &HashMap<String, f64>) -> PairAccount {
+        let portfolio = self.portfolio.read().await;
+        PairAccount {
+            position_units: portfolio.positions().get(symbol).map(|position| position.units).unwrap_or(0.0),
+            equity: portfolio.snapshot(prices, Utc::now()).equity,
+            units_per_size: portfolio.units_per_size(symbol),
+        }
+    }
+    
+    /// Trade `symbol` with a plug-in strategy, e.g. one from another crate, instead of the RL agent
+    pub async fn set_strategy(&self, symbol: &str, strategy: Box<dyn Strategy>) -> Result<()> {
+        let mut pairs_map = self.pairs.write().await;
+        let pair_state = pairs_map.get_mut(symbol)
+            .ok_or_else(|| anyhow::anyhow!("unknown currency pair {}", symbol))?;
+        pair_state.set_strategy(strategy);
+        Ok(())
+    }
+    
+    /// Run another plug-in strategy on `symbol` beside those already trading it
+    pub async fn add_strategy(&self, symbol: &str, strategy: Box<dyn Strategy>) -> Result<()> {
+        let mut pairs_map = self.pairs.write().await;
+        let pair_state = pairs_map.get_mut(symbol)
+            .ok_or_else(|| anyhow::anyhow!("unknown currency pair {}", symbol))?;
+        pair_state.add_strategy(strategy);
+        Ok(())
+    }
+    
+    /// Health of every plug-in strategy, by pair
+    pub async fn strategy_statuses(&self) -> HashMap<String, Vec<SandboxStatus>> {
+        self.pairs.read().await.iter()
+            .filter(|(_, state)| !state.strategies.is_empty())
+            .map(|(symbol, state)| (symbol.clone(), state.strategy_statuses()))
+            .collect()
+    }
+    
+    /// Record strategies the sandbox disabled and input drift in the audit log
+    fn audit_pair_events(&self, pair_state: &mut CurrencyPairState) {
+        for alert in pair_state.take_strategy_alerts() {
+            self.audit_log.record("strategy-sandbox", &format!("disable {}", pair_state.config.symbol), false, &alert);
+        }
+        for event in pair_state.take_drift_events() {
+            let actions: Vec<String> = event.actions.iter().map(|action| format!("{:?}", action)).collect();
+            self.audit_log.record("drift-monitor", &format!("drift {} [{}]", event.symbol, actions.join(", ")), true, &event.summary());
+        }
+    }
+    
+    /// Fill trading actions into the portfolio at current prices, returning realized P&L per pair.
+    ///
+    /// With margin enforced, positions are first liquidated if the account is at stop-out,
+    /// and orders are cut to the leverage limit. Orders adding exposure are checked by the
+    /// risk engine: refused past the drawdown limit and cut to the pair and currency exposure
+    /// limits and for correlated open positions. At the kill switch drawdown every position
+    /// is closed and only closing actions are filled until the switch is reset. A pair that
+    /// recently showed a liquidity gap, or whose history is generated demo data, takes no
+    /// new entries, and no pair does in a blocked trading window. Actions decided longer ago than
+    /// the signal latency budget allows are recorded as missed and not sent.
+    /// Orders the broker rejects or does not answer in time are skipped, and while the broker
+    /// circuit is open no orders are sent at all. Every order, filled or refused, is
+    /// recorded in the portfolio's order blotter, and fills against the price their
+    /// pair decided on in the execution log. Fills of orders placed by a pair's
+    /// strategy are reported back to it. In approval mode actions that would trade are
+    /// held for an operator instead, and those left unanswered past their countdown are
+    /// recorded as missed.
+    pub async fn execute_actions(&self, all_actions: &HashMap<String, Vec<TradingAction>>) -> HashMap<String, f64> {
+        let proposals: Vec<ProposedAction> = {
+            let pairs_map = self.pairs.read().await;
+            all_actions.iter()
+                .flat_map(|(symbol, actions)| {
+                    let origin = pairs_map.get(symbol).and_then(|state| state.signal_origin);
+                    actions.iter().enumerate().map(move |(index, action)| ProposedAction {
+                        symbol: symbol.clone(),
+                        index,
+                        action: action.clone(),
+                        origin,
+                    })
+                })
+                .collect()
+        };
+        self.expire_approvals().await;
+        let config = self.approval_config.read().await.clone();
+        if config.mode != ExecutionMode::Approval {
+            return self.fill_actions(proposals).await;
+        }
+        
+        let (holds, trades): (Vec<_>, Vec<_>) = proposals.into_iter().partition(|proposed| matches!(proposed.action, TradingAction::Hold));
+        let now = Utc::now();
+        let mut approvals = self.approvals.write().await;
+        for proposed in trades {
+            let pending = approvals.propose(proposed, now, chrono::Duration::seconds(config.timeout_seconds as i64));
+            println!("⏳ {} awaiting approval", pending.summary(now));
+        }
+        drop(approvals);
+        self.fill_actions(holds).await
+    }
+    
+    /// Record held actions whose countdown ran out as missed, returning them
+    pub async fn expire_approvals(&self) -> Vec<PendingApproval> {
+        let now = Utc::now();
+        let expired = self.approvals.write().await.expire(now);
+        for pending in &expired {
+            let waited = (pending.expires_at - pending.queued_at).num_seconds();
+            let reason = format!("not approved within {}s", waited);
+            println!("⌛ #{} {} {:?} missed: {}", pending.id, pending.proposed.symbol, pending.proposed.action, reason);
+            self.portfolio.write().await.miss(&pending.proposed.symbol, &pending.proposed.action, &reason, now);
+            self.audit_log.record("approval-queue", &format!("expire #{}", pending.id), true, &reason);
+        }
+        expired
+    }
+    
+    /// Actions currently held for approval, oldest first
+    pub async fn pending_approvals(&self) -> Vec<PendingApproval> {
+        self.expire_approvals().await;
+        self.approvals.read().await.pending()
+    }
+    
+    /// Risk-check, send and record `proposals`, returning realized P&L per pair
+    async fn fill_actions(&self, proposals: Vec<ProposedAction>) -> HashMap<String, f64> {
+        let prices = self.current_prices().await;
+        let now = Utc::now();
+        self.portfolio.write().await.enforce_margin(&prices, now);
+        self.update_drawdown(&prices).await;
+        let limits = self.risk_limits.read().await.clone();
+        let tripped = self.risk.write().await.assess(&limits, now).cloned();
+        if let Some(kill_switch) = tripped {
+            self.engage_kill_switch(&kill_switch, &prices).await;
+        }
+        let risk = self.risk.read().await.clone();
+        let illiquid: HashMap<String, DateTime<Utc>> = self.pairs.read().await.iter()
+            .filter_map(|(symbol, state)| state.last_liquidity_gap.map(|at| (symbol.clone(), at)))
+            .filter(|(_, at)| now - *at < chrono::Duration::minutes(limits.liquidity_gap_block_minutes))
+            .collect();
+        let demo: HashSet<String> = self.pairs.read().await.iter()
+            .filter(|(_, state)| state.historical_source == Some(HistoricalSource::Demo))
+            .map(|(symbol, _)| symbol.clone())
+            .collect();
+        let bars: HashMap<String, usize> = self.pairs.read().await.iter()
+            .map(|(symbol, state)| (symbol.clone(), state.historical_data.len()))
+            .collect();
+        let quotes: HashMap<String, DecisionQuote> = self.pairs.read().await.iter()
+            .filter_map(|(symbol, state)| state.decision_quote.map(|quote| (symbol.clone(), quote)))
+            .collect();
+        // Pairs without a live quote are quoted to the backend at their price and spread;
+        // live quotes reach it from the feed
+        let ticks: HashMap<String, Tick> = self.pairs.read().await.iter()
+            .filter(|(_, state)| state.last_tick.is_none())
+            .filter_map(|(symbol, state)| prices.get(symbol).map(|price| (symbol.clone(), Tick {
+                symbol: symbol.clone(),
+                timestamp: now,
+                bid: price - state.config.spread / 2.0,
+                ask: price + state.config.spread / 2.0,
+            })))
+            .collect();
+        
+        let mut realized = HashMap::new();
+        let mut fills: Vec<(String, usize, Fill)> = Vec::new();
+        
+        for ProposedAction { symbol, index, action, origin } in &proposals {
+            let Some(price) = prices.get(symbol).copied() else { continue };
+            let expired = origin.as_ref()
+                .filter(|_| !matches!(action, TradingAction::Hold))
+                .and_then(|origin| limits.signal_latency.expired(origin, now, bars.get(symbol).copied().unwrap_or(origin.bars)));
+            if let Some(reason) = expired {
+                println!("⌛ {} {:?} missed: {}", symbol, action, reason);
+                self.portfolio.write().await.miss(symbol, action, &reason, now);
+                continue;
+            }
+            let decision = {
+                let portfolio = self.portfolio.read().await;
+                let snapshot = portfolio.snapshot(&prices, now);
+                risk.check(&limits, symbol, action, portfolio.units_per_size(symbol), &snapshot, &prices)
+            };
+            let (action, note) = match decision {
+                RiskDecision::Allow => (action.clone(), None),
+                RiskDecision::Resize { size, reason } => {
+                    println!("⚖️  {} {:?} cut to size {}: {}", symbol, action, size, reason);
+                    let resized = if matches!(action, TradingAction::Buy { .. }) { TradingAction::Buy { size } } else { TradingAction::Sell { size } };
+                    (resized, Some(reason))
+                }
+                RiskDecision::Refuse { reason } => {
+                    println!("🛑 {} {:?} refused: {}", symbol, action, reason);
+                    self.portfolio.write().await.reject(symbol, action, &reason, now);
+                    continue;
+                }
+            };
+            let action = &action;
+            let mut broker_fill = None;
+            let refusal = if let Some(at) = illiquid.get(symbol).filter(|_| matches!(action, TradingAction::Buy { .. } | TradingAction::Sell { .. })) {
+                println!("💧 {} {:?} refused: liquidity gap at {}", symbol, action, at.format("%H:%M:%S"));
+                Some("liquidity gap".to_string())
+            } else if let Some(reason) = self.trading_windows.check(now).filter(|_| matches!(action, TradingAction::Buy { .. } | TradingAction::Sell { .. })) {
+                println!("⏸️  {} {:?} refused: {}", symbol, action, reason);
+                Some(reason.to_string())
+            } else if demo.contains(symbol) && matches!(action, TradingAction::Buy { .. } | TradingAction::Sell { .. }) {
+                println!("🧪 {} {:?} refused: history is demo data", symbol, action);
+                Some("demo history".to_string())
+            } else if matches!(action, TradingAction::Hold) {
+                None
+            } else if !self.broker_breaker.allow(Utc::now()) {
+                println!("🔌 {} {:?} not sent: broker circuit open", symbol, action);
+                Some("broker circuit open".to_string())
+            } else if let Err(e) = self.submit_order().await {
+                println!("❌ {} {:?} failed: {}", symbol, action, e);
+                self.broker_breaker.record_failure(&e.to_string(), Utc::now());
+                Some(e.to_string())
+            } else if let Some(backend) = &self.execution_backend {
+                match self.route_order(backend.as_ref(), symbol, action, ticks.get(symbol)).await {
+                    Ok(fill) => {
+                        self.broker_breaker.record_success();
+                        broker_fill = fill;
+                        match &broker_fill {
+                            Some(fill) if fill.units <= 0.0 => Some(format!("not filled by {}", backend.name())),
+                            _ => None,
+                        }
+                    }
+                    Err(e) => {
+                        println!("❌ {} {:?} failed on {}: {}", symbol, action, backend.name(), e);
+                        self.broker_breaker.record_failure(&e.to_string(), Utc::now());
+                        Some(e.to_string())
+                    }
+                }
+            } else {
+                self.broker_breaker.record_success();
+                None
+            };
+            let mut portfolio = self.portfolio.write().await;
+            if let Some(reason) = refusal {
+                portfolio.reject(symbol, action, &reason, now);
+                continue;
+            }
+            let submitted = match &broker_fill {
+                Some(fill) => {
+                    let units = if fill.side == OrderSide::Buy { fill.units } else { -fill.units };
+                    portfolio.submit_fill(symbol, action, units, fill.price, &prices, now, note.as_deref())
+                }
+                None => portfolio.submit(symbol, action, price, &prices, now, note.as_deref()),
+            };
+            let Some(order) = submitted else { continue };
+            drop(portfolio);
+            *realized.entry(symbol.clone()).or_insert(0.0) += order.realized_pnl;
+            let decision = quotes.get(symbol).filter(|quote| origin.is_some_and(|origin| origin.observed_at == quote.observed_at));
+            if let Some(decision) = decision.filter(|_| order.filled_units != 0.0) {
+                self.execution_log.record(order.id, symbol, order.filled_units, price, decision, Utc::now());
+            }
+            if order.filled_units != 0.0 {
+                fills.push((symbol.clone(), *index, Fill {
+                    side: if order.filled_units > 0.0 { OrderSide::Buy } else { OrderSide::Sell },
+                    units: order.filled_units.abs(),
+                    price: order.price,
+                    realized_pnl: order.realized_pnl,
+                    commission: 0.0,
+                    position_units: order.position_units,
+                    reason: format!("{:?}", action),
+                    symmetry_id: None,
+                    cycle_id: None,
+                }));
+            }
+        }
+        
+        for (symbol, index, fill) in fills {
+            let account = self.pair_account(&symbol, &prices).await;
+            let mut pairs_map = self.pairs.write().await;
+            let Some(pair_state) = pairs_map.get_mut(&symbol) else { continue };
+            if let Some(log) = &self.session_log {
+                log.record_fill(&symbol, index, &fill, &account);
+            }
+            pair_state.record_fill(index, fill, &account).await;
+            self.audit_pair_events(pair_state);
+        }
+        self.report_margin_events().await;
+        
+        realized
+    }
+    
+    /// Raise a margin call or liquidate at stop-out at current prices, returning the events
+    pub async fn check_margin(&self) -> Vec<MarginEvent> {
+        let prices = self.current_prices().await;
+        self.portfolio.write().await.enforce_margin(&prices, Utc::now());
+        self.report_margin_events().await
+    }
+    
+    /// Log and audit the portfolio's leverage cuts, margin calls and stop-outs
+    async fn report_margin_events(&self) -> Vec<MarginEvent> {
+        let events = self.portfolio.write().await.take_margin_events();
+        for event in &events {
+            match event.kind {
+                MarginEventKind::LeverageLimit => println!("⚖️  {}", event.summary()),
+                MarginEventKind::MarginCall => println!("🚨 MARGIN CALL: {}", event.summary()),
+                MarginEventKind::StopOut => println!("💥 STOP-OUT: {}", event.summary()),
+            }
+            let command = format!("{:?} {}", event.kind, event.symbols.join(",")).trim_end().to_string();
+            self.audit_log.record("margin-monitor", &command, true, &event.summary());
+        }
+        events
+    }
+    
+    /// Send `action` to `backend`, quoting the pair first when `quote` is given, bounded
+    /// by the breaker's call timeout; `None` when it trades nothing
+    async fn route_order(&self, backend: &dyn ExecutionBackend, symbol: &str, action: &TradingAction, quote: Option<&Tick>) -> Result<Option<BrokerFill>> {
+        let Some(units) = self.portfolio.read().await.action_units(symbol, action) else {
+            return Ok(None);
+        };
+        let side = if units > 0.0 { OrderSide::Buy } else { OrderSide::Sell };
+        let order = MarketOrder::new(symbol, side, units.abs()).with_label(&format!("{:?}", action));
+        if let Some(quote) = quote {
+            backend.observe_quote(quote);
+        }
+        let fill = tokio::time::timeout(self.broker_breaker.call_timeout(), backend.execute(&order))
+            .await
+            .map_err(|_| anyhow::anyhow!("{} did not fill within {}ms", backend.name(), self.broker_breaker.config().call_timeout_ms))??;
+        Ok(Some(fill))
+    }
+    
+    /// Broker acknowledgement of an order, bounded by the breaker's call timeout
+    async fn submit_order(&self) -> Result<()> {
+        let Some(injector) = &self.fault_injector else {
+            return Ok(());
+        };
+        tokio::time::timeout(self.broker_breaker.call_timeout(), injector.broker_response())
+            .await
+            .map_err(|_| anyhow::anyhow!("broker did not respond within {}ms", self.broker_breaker.config().call_timeout_ms))?
+    }
+    
+    /// Track peak equity and return the current drawdown from it in percent
+    async fn update_drawdown(&self, prices: &HashMap<String, f64>) -> f64 {
+        let equity = self.portfolio.read().await.snapshot(prices, Utc::now()).equity;
+        self.risk.write().await.update_drawdown(equity)
+    }
+    
+    /// Close every open position for a tripped kill switch, returning how many were closed
+    async fn engage_kill_switch(&self, kill_switch: &KillSwitch, prices: &HashMap<String, f64>) -> usize {
+        let mut portfolio = self.portfolio.write().await;
+        let symbols: Vec<String> = portfolio.positions().keys().cloned().collect();
+        let mut realized = 0.0;
+        for symbol in &symbols {
+            let Some(price) = prices.get(symbol).copied() else { continue };
+            realized += portfolio.submit(symbol, &TradingAction::ClosePosition, price, prices, Utc::now(), Some("kill switch"))
+                .map(|order| order.realized_pnl)
+                .unwrap_or(0.0);
+        }
+        let summary = format!("{}: closed {} position(s), realized {:.2}", kill_switch.reason, symbols.len(), realized);
+        println!("☠️  KILL SWITCH: {}", summary);
+        self.audit_log.record("risk-engine", "kill-switch", true, &summary);
+        symbols.len()
+    }
+    
+    /// Correlate the active pairs' returns for correlation-adjusted position sizing, and
+    /// tell each pair's strategies which pairs lead it
+    pub async fn refresh_correlations(&self) -> Result<usize> {
+        let histories: HashMap<String, Vec<ForexDataPoint>> = {
+            let pairs_map = self.pairs.read().await;
+            self.active_pairs.iter()
+                .filter_map(|symbol| pairs_map.get(symbol).map(|state| (symbol.clone(), state.historical_data.clone())))
+                .collect()
+        };
+        let analyzer = CrossPairAnalyzer::new();
+        let matrix = analyzer.calculate_correlation_matrix(&histories)?;
+        self.risk.write().await.set_correlations(&matrix);
+        
+        let opportunities = analyzer.find_lead_lag_opportunities(&histories, &self.lead_lag_config);
+        let prices = self.current_prices().await;
+        for opportunity in &opportunities {
+            println!("⏩ {}", opportunity.summary());
+            let account = self.pair_account(&opportunity.follower, &prices).await;
+            if let Some(state) = self.pairs.write().await.get_mut(&opportunity.follower) {
+                state.record_lead_lag(opportunity, &account).await;
+            }
+        }
+        *self.lead_lag.write().await = opportunities;
+        Ok(matrix.len())
+    }
+    
+    /// Run an operator command and record it in the audit log
+    pub async fn execute_command(&self, command: &ControlCommand, source: &str) -> Result<String> {
+        let result = self.apply_command(command, source).await;
+        match &result {
+            Ok(message) => {
+                self.audit_log.record(source, &command.to_string(), true, message);
+                println!("📝 {} ({}): {}", command, source, message);
+            }
+            Err(e) => {
+                self.audit_log.record(source, &command.to_string(), false, &e.to_string());
+                println!("📝 {} ({}) failed: {}", command, source, e);
+            }
+        }
+        result
+    }
+    
+    async fn apply_command(&self, command: &ControlCommand, source: &str) -> Result<String> {
+        match command {
+            ControlCommand::Pause { pair } | ControlCommand::Resume { pair } => {
+                let pause = matches!(command, ControlCommand::Pause { .. });
+                let symbol = pair.to_uppercase();
+                let mut pairs_map = self.pairs.write().await;
+                let state = pairs_map.get_mut(&symbol)
+                    .ok_or_else(|| anyhow::anyhow!("unknown pair {}", symbol))?;
+                state.paused = pause;
+                if !pause {
+                    state.drift_paused = false;
+                }
+                Ok(format!("{} {}", symbol, if pause { "paused" } else { "resumed" }))
+            }
+            ControlCommand::Flatten { pair } => {
+                let prices = self.current_prices().await;
+                let mut portfolio = self.portfolio.write().await;
+                let symbols: Vec<String> = match pair {
+                    Some(pair) => {
+                        let symbol = pair.to_uppercase();
+                        if !portfolio.positions().contains_key(&symbol) {
+                            return Ok(format!("{} already flat", symbol));
+                        }
+                        vec![symbol]
+                    }
+                    None => portfolio.positions().keys().cloned().collect(),
+                };
+                
+                let now = Utc::now();
+                let mut realized = 0.0;
+                for symbol in &symbols {
+                    let price = prices.get(symbol).copied()
+                        .ok_or_else(|| anyhow::anyhow!("no price available for {}", symbol))?;
+                    realized += portfolio.flatten(symbol, price, &prices, now);
+                }
+                Ok(format!("closed {} position(s), realized {:.2} {}",
+                           symbols.len(), realized, portfolio.config().account_currency))
+            }
+            ControlCommand::SetRisk { max_drawdown_pct } => {
+                if !(*max_drawdown_pct > 0.0 && *max_drawdown_pct <= 100.0) {
+                    anyhow::bail!("max drawdown must be in (0, 100]%, got {}%", max_drawdown_pct);
+                }
+                let mut limits = self.risk_limits.write().await;
+                let previous = limits.max_drawdown_pct;
+                limits.max_drawdown_pct = *max_drawdown_pct;
+                Ok(format!("max drawdown {:.2}% → {:.2}%", previous, max_drawdown_pct))
+            }
+            ControlCommand::SetSignalLatency { max_age_seconds, max_age_bars } => {
+                if let Some(seconds) = max_age_seconds.filter(|seconds| *seconds <= 0.0 || !seconds.is_finite()) {
+                    anyhow::bail!("signal latency budget must be positive, got {}s", seconds);
+                }
+                let budget = &mut self.risk_limits.write().await.signal_latency;
+                budget.max_age_seconds = *max_age_seconds;
+                budget.max_age_bars = *max_age_bars;
+                Ok(format!("signal latency budget {}, {}",
+                           max_age_seconds.map_or("no time limit".to_string(), |s| format!("{}s", s)),
+                           max_age_bars.map_or("no bar limit".to_string(), |b| format!("{} bars", b))))
+            }
+            ControlCommand::KillSwitch => {
+                let prices = self.current_prices().await;
+                let tripped = self.risk.write().await.trip(&format!("operator ({})", source), Utc::now());
+                if !tripped {
+                    return Ok("kill switch already tripped".to_string());
+                }
+                let kill_switch = self.risk.read().await.kill_switch().cloned()
+                    .ok_or_else(|| anyhow::anyhow!("kill switch not tripped"))?;
+                let closed = self.engage_kill_switch(&kill_switch, &prices).await;
+                Ok(format!("kill switch tripped, {} position(s) closed", closed))
+            }
+            ControlCommand::ResetKillSwitch => {
+                let equity = self.portfolio_snapshot().await.equity;
+                match self.risk.write().await.reset(equity) {
+                    Some(kill_switch) => Ok(format!("kill switch re-armed (tripped {} by {})",
+                                                    kill_switch.tripped_at.format("%Y-%m-%d %H:%M:%S"), kill_switch.reason)),
+                    None => Ok("kill switch was not tripped".to_string()),
+                }
+            }
+            ControlCommand::Acknowledge { pair, pattern_id, anomaly_type } => {
+                let rule = self.suppressions.acknowledge(pair.as_deref(), pattern_id, anomaly_type, source);
+                Ok(format!("{} ({})", rule, rule.id))
+            }
+            ControlCommand::Suppress { pair, pattern_id, anomaly_type, minutes } => {
+                if minutes.is_some_and(|m| m <= 0) {
+                    anyhow::bail!("suppression period must be positive");
+                }
+                let lifetime = minutes.map(chrono::Duration::minutes);
+                let rule = self.suppressions.suppress(pair.as_deref(), pattern_id, anomaly_type, source, lifetime);
+                Ok(format!("{} ({})", rule, rule.id))
+            }
+            ControlCommand::Unsuppress { id } => {
+                let rule = self.suppressions.remove(id)
+                    .ok_or_else(|| anyhow::anyhow!("no suppression rule {}", id))?;
+                Ok(format!("removed {}", rule))
+            }
+            ControlCommand::SetParams { update } => {
+                let epoch = self.analysis_params.update(update, source)?;
+                if epoch.changes.is_empty() {
+                    return Ok(format!("parameters unchanged (epoch {})", epoch.epoch));
+                }
+                Ok(format!("epoch {}: {}", epoch.epoch,
+                           epoch.changes.iter().map(|change| change.to_string()).collect::<Vec<_>>().join(", ")))
+            }
+            ControlCommand::SetExecutionMode { mode, timeout_seconds } => {
+                if *timeout_seconds == Some(0) {
+                    anyhow::bail!("approval timeout must be at least 1s");
+                }
+                let mut config = self.approval_config.write().await;
+                config.mode = *mode;
+                if let Some(seconds) = timeout_seconds {
+                    config.timeout_seconds = *seconds;
+                }
+                let held = self.approvals.read().await.len();
+                Ok(match config.mode {
+                    ExecutionMode::Automatic => format!("automatic execution, {} action(s) still awaiting approval", held),
+                    ExecutionMode::Approval => format!("approval required within {}s", config.timeout_seconds),
+                })
+            }
+            ControlCommand::Approve { id } | ControlCommand::Reject { id } => {
+                self.expire_approvals().await;
+                let answered = self.approvals.write().await.take(*id);
+                if answered.is_empty() {
+                    anyhow::bail!("{}", id.map_or("no actions awaiting approval".to_string(), |id| format!("no action #{} awaiting approval", id)));
+                }
+                let now = Utc::now();
+                let summary = answered.iter()
+                    .map(|pending| format!("#{} {} {:?}", pending.id, pending.proposed.symbol, pending.proposed.action))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                if matches!(command, ControlCommand::Reject { .. }) {
+                    let mut portfolio = self.portfolio.write().await;
+                    for pending in &answered {
+                        portfolio.reject(&pending.proposed.symbol, &pending.proposed.action, &format!("rejected by {}", source), now);
+                    }
+                    return Ok(format!("rejected {}", summary));
+                }
+                let realized: f64 = self.fill_actions(answered.into_iter().map(|pending| pending.proposed).collect()).await.values().sum();
+                Ok(format!("approved {}, realized {:.2}", summary, realized))
+            }
+        }
+    }
+    
+    /// Equity, margin, open positions and currency exposure at current prices
+    pub async fn portfolio_snapshot(&self) -> PortfolioSnapshot {
+        let prices = self.current_prices().await;
+        self.portfolio.read().await.snapshot(&prices, Utc::now())
+    }
+    
     /// Process market updates for all active pairs
-    pub async fn process_all_market_updates(&mut self) -> Result<HashMap<String, Vec<TradingAction>>> {
+    pub async fn process_all_market_updates(&self) -> Result<HashMap<String, Vec<TradingAction>>> {
         let mut all_actions = HashMap::new();
+        let prices = self.current_prices().await;
         let mut pairs_map = self.pairs.write().await;
         
         for symbol in &self.active_pairs {
             if let Some(pair_state) = pairs_map.get_mut(symbol) {
-                let actions = pair_state.process_market_update().await?;
-                if !actions.is_empty() {
-                    all_actions.insert(symbol.clone(), actions);
+                let account = self.pair_account(symbol, &prices).await;
+                let seed = rand::random();
+                let decision = pair_state.process_market_update_seeded(&self.suppressions, &account, seed).await?;
+                self.audit_pair_events(pair_state);
+                let Some(decision) = decision else { continue };
+                if let Some(log) = &self.session_log {
+                    log.record_update(pair_state, &account, seed, &decision);
+                }
+                if !decision.actions.is_empty() {
+                    all_actions.insert(symbol.clone(), decision.actions);
                 }
             }
         }
@@ -0,0 +1,71 @@
+//! # Pair Model Persistence
+//!
+//! Trained per-pair state saved between runs (symmetries, cycles, synthetic
+//! data, Q-table and detector calibration), so a loaded pair skips extraction
+//! and generation.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+use crate::anomaly::SensitivityCalibration;
+use crate::laplacian_rl::QTableSnapshot;
+use crate::patterns::HiddenCycle;
+use crate::symmetry::TemporalSymmetry;
+use crate::synthetic::SyntheticForexPoint;
+
+/// Model file layout version; files of other versions are refused
+pub const MODEL_VERSION: u32 = 1;
+
+/// Trained state of one pair
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairModel {
+    pub version: u32,
+    pub symbol: String,
+    pub saved_at: DateTime<Utc>,
+    /// Bars the model was trained on
+    pub history_bars: usize,
+    /// Newest of those bars
+    pub history_end: Option<DateTime<Utc>>,
+    pub symmetries: Vec<TemporalSymmetry>,
+    pub cycles: Vec<HiddenCycle>,
+    pub synthetic_data: Vec<SyntheticForexPoint>,
+    pub q_table: QTableSnapshot,
+    pub calibration: Option<SensitivityCalibration>,
+}
+
+impl PairModel {
+    /// File holding `symbol`'s model in `dir`
+    pub fn path(dir: &Path, symbol: &str) -> PathBuf {
+        dir.join(format!("{}.model.json", symbol.to_uppercase()))
+    }
+
+    /// Write the model to `dir`, replacing any earlier one of the pair
+    pub fn save(&self, dir: &Path) -> Result<PathBuf> {
+        std::fs::create_dir_all(dir)?;
+        let path = Self::path(dir, &self.symbol);
+        let partial = path.with_extension("json.partial");
+        serde_json::to_writer(BufWriter::new(std::fs::File::create(&partial)?), self)?;
+        std::fs::rename(&partial, &path)?;
+        Ok(path)
+    }
+
+    /// `symbol`'s model from `dir`, `None` when none was saved
+    pub fn load(dir: &Path, symbol: &str) -> Result<Option<Self>> {
+        let path = Self::path(dir, symbol);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let model: Self = serde_json::from_reader(BufReader::new(std::fs::File::open(&path)?))
+            .with_context(|| format!("cannot read model {}", path.display()))?;
+        if model.version != MODEL_VERSION {
+            bail!("{} has model version {}, expected {}", path.display(), model.version, MODEL_VERSION);
+        }
+        if !model.symbol.eq_ignore_ascii_case(symbol) {
+            bail!("{} holds a model of {}, not {}", path.display(), model.symbol, symbol);
+        }
+        Ok(Some(model))
+    }
+}
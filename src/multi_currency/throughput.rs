@@ -0,0 +1,199 @@
+//! # Pair Throughput
+//!
+//! Per-pair analysis throughput, detection latency, data freshness and backlog,
+//! compared across pairs so a slow or stalled pair stands out.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Detection latencies kept per pair
+const LATENCY_SAMPLES: usize = 100;
+
+/// Updates older than this are dropped, bounding the longest usable window
+const MAX_WINDOW: Duration = Duration::from_secs(3_600);
+
+/// When a pair counts as lagging behind the others
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThroughputConfig {
+    /// Span bars per second are averaged over
+    pub window_secs: u64,
+    /// Detection latency above this multiple of the other pairs' median lags
+    pub latency_factor: f64,
+    /// Latencies below this never lag, however they compare
+    pub min_latency_ms: f64,
+    /// Appended bars waiting for analysis before a pair lags
+    pub max_backlog_bars: usize,
+    /// Bars a pair's newest data may trail the freshest pair's by
+    pub max_data_lag_bars: i64,
+}
+
+impl Default for ThroughputConfig {
+    fn default() -> Self {
+        Self {
+            window_secs: 60,
+            latency_factor: 3.0,
+            min_latency_ms: 10.0,
+            max_backlog_bars: 3,
+            max_data_lag_bars: 2,
+        }
+    }
+}
+
+/// Running throughput and latency of one pair
+#[derive(Debug, Clone, Default)]
+pub struct ThroughputTracker {
+    /// Bars analyzed by each update in the window, oldest first
+    updates: VecDeque<(Instant, usize)>,
+    latencies: VecDeque<Duration>,
+    /// History length at the last analysis; `None` until the first
+    analyzed_len: Option<usize>,
+    bars_processed: u64,
+    started: Option<Instant>,
+}
+
+impl ThroughputTracker {
+    /// Record an update that analyzed the history up to `history_len` bars and
+    /// spent `latency` detecting anomalies. The first update only sets the mark:
+    /// the bars loaded at startup were analyzed during initialization.
+    pub fn record_update(&mut self, history_len: usize, latency: Duration, now: Instant) {
+        let bars = match self.analyzed_len {
+            Some(analyzed) => history_len.saturating_sub(analyzed),
+            None => 0,
+        };
+        self.analyzed_len = Some(history_len);
+        self.bars_processed += bars as u64;
+        self.started.get_or_insert(now);
+        self.updates.push_back((now, bars));
+        while self.updates.front().is_some_and(|(at, _)| now.duration_since(*at) > MAX_WINDOW) {
+            self.updates.pop_front();
+        }
+        self.latencies.push_back(latency);
+        if self.latencies.len() > LATENCY_SAMPLES {
+            self.latencies.pop_front();
+        }
+    }
+
+    /// Bars appended since the last analysis
+    pub fn backlog(&self, history_len: usize) -> usize {
+        self.analyzed_len.map_or(0, |analyzed| history_len.saturating_sub(analyzed))
+    }
+
+    /// Bars analyzed per second over the window, or since the first update if that
+    /// is shorter; spans under a second count as one so startup does not spike
+    pub fn bars_per_sec(&self, now: Instant, window: Duration) -> f64 {
+        let Some(started) = self.started else { return 0.0 };
+        let span = now.duration_since(started).min(window).as_secs_f64().max(1.0);
+        let bars: usize = self.updates.iter()
+            .filter(|(at, _)| now.duration_since(*at) <= window)
+            .map(|(_, bars)| bars)
+            .sum();
+        bars as f64 / span
+    }
+
+    pub fn bars_processed(&self) -> u64 {
+        self.bars_processed
+    }
+
+    pub fn last_latency(&self) -> Option<Duration> {
+        self.latencies.back().copied()
+    }
+
+    /// 95th percentile of the recent detection latencies
+    pub fn p95_latency(&self) -> Option<Duration> {
+        let mut latencies: Vec<Duration> = self.latencies.iter().copied().collect();
+        latencies.sort_unstable();
+        let index = ((latencies.len() as f64 * 0.95).ceil() as usize).checked_sub(1)?;
+        latencies.get(index).copied()
+    }
+}
+
+/// One pair's row of the system panel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairThroughput {
+    pub symbol: String,
+    pub bars_per_sec: f64,
+    pub bars_processed: u64,
+    /// Latest anomaly detection time
+    pub detection_latency_ms: Option<f64>,
+    pub detection_latency_p95_ms: Option<f64>,
+    /// Newest bar or quote the pair has
+    pub last_data: Option<DateTime<Utc>>,
+    /// Appended bars waiting for analysis
+    pub backlog_bars: usize,
+    /// Length of the pair's bars
+    pub bar_seconds: i64,
+    /// Why the pair lags behind the others; empty when it keeps up
+    pub lagging: Vec<String>,
+}
+
+/// Every pair's throughput, for operators to compare at a glance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThroughputPanel {
+    pub generated_at: DateTime<Utc>,
+    pub pairs: Vec<PairThroughput>,
+    /// Messages queued on the dashboard streams for the slowest subscriber
+    pub stream_backlog: usize,
+}
+
+impl ThroughputPanel {
+    /// Panel of `pairs`, each checked against the others
+    pub fn new(mut pairs: Vec<PairThroughput>, stream_backlog: usize, config: &ThroughputConfig, now: DateTime<Utc>) -> Self {
+        let freshest = pairs.iter().filter_map(|pair| pair.last_data).max();
+        let latencies: Vec<Option<f64>> = pairs.iter().map(|pair| pair.detection_latency_ms).collect();
+        for (index, pair) in pairs.iter_mut().enumerate() {
+            let others = latencies.iter()
+                .enumerate()
+                .filter(|(other, _)| *other != index)
+                .filter_map(|(_, latency)| *latency)
+                .collect();
+            pair.lagging.clear();
+            if pair.backlog_bars > config.max_backlog_bars {
+                pair.lagging.push(format!("{} bars waiting", pair.backlog_bars));
+            }
+            match (pair.last_data, freshest) {
+                (Some(last), Some(freshest)) => {
+                    let behind = freshest - last;
+                    if behind.num_seconds() > config.max_data_lag_bars * pair.bar_seconds.max(1) {
+                        pair.lagging.push(format!("data {} behind", format_lag(behind)));
+                    }
+                }
+                (None, Some(_)) => pair.lagging.push("no data".to_string()),
+                _ => {}
+            }
+            if let (Some(latency), Some(median)) = (pair.detection_latency_ms, median(others)) {
+                if latency > config.min_latency_ms && latency > median * config.latency_factor {
+                    pair.lagging.push(format!("detection {:.0}ms (median {:.0}ms)", latency, median));
+                }
+            }
+        }
+        Self { generated_at: now, pairs, stream_backlog }
+    }
+
+    /// Pairs with at least one lag reason
+    pub fn lagging_pairs(&self) -> Vec<&PairThroughput> {
+        self.pairs.iter().filter(|pair| !pair.lagging.is_empty()).collect()
+    }
+}
+
+fn median(mut values: Vec<f64>) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+    let mid = values.len() / 2;
+    Some(if values.len().is_multiple_of(2) { (values[mid - 1] + values[mid]) / 2.0 } else { values[mid] })
+}
+
+/// "3d 4h", "2h 5m" or "40s"
+pub fn format_lag(lag: chrono::Duration) -> String {
+    let seconds = lag.num_seconds().max(0);
+    match seconds {
+        s if s >= 86_400 => format!("{}d {}h", s / 86_400, s % 86_400 / 3_600),
+        s if s >= 3_600 => format!("{}h {}m", s / 3_600, s % 3_600 / 60),
+        s if s >= 60 => format!("{}m", s / 60),
+        s => format!("{}s", s),
+    }
+}
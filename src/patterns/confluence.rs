@@ -0,0 +1,96 @@
+//! # Cross-Timeframe Cycle Confluence
+//!
+//! A cycle found on H1 and again on H4 at the same wall-clock period is far less
+//! likely to be noise than one seen on a single timeframe. Periods are compared
+//! in hours, since the same cycle is 96 bars on M15 but 6 on H4.
+
+use serde::{Deserialize, Serialize};
+
+use super::HiddenCycle;
+
+/// Cycles detected on one timeframe
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeframeCycles {
+    pub timeframe: String,
+    /// Bar length in seconds
+    pub bar_seconds: f64,
+    /// Bars analyzed
+    pub bars: usize,
+    pub cycles: Vec<HiddenCycle>,
+}
+
+impl TimeframeCycles {
+    /// Wall-clock period of `cycle` in hours
+    pub fn period_hours(&self, cycle: &HiddenCycle) -> f64 {
+        cycle.period as f64 * self.bar_seconds / 3600.0
+    }
+}
+
+/// One timeframe's cycle in a confluence group
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfluenceMember {
+    pub timeframe: String,
+    pub cycle: String,
+    pub period_bars: u32,
+    pub period_hours: f64,
+    pub confidence: f64,
+}
+
+/// A cycle period confirmed on more than one timeframe
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CycleConfluence {
+    /// Confidence-weighted mean period of the members
+    pub period_hours: f64,
+    pub timeframes: Vec<String>,
+    pub members: Vec<ConfluenceMember>,
+    /// Mean member confidence times the share of analyzed timeframes that agree
+    pub score: f64,
+}
+
+/// Group cycles whose wall-clock periods agree within `tolerance` (relative) and
+/// keep the groups seen on at least two timeframes, strongest first
+pub fn find_cycle_confluence(results: &[TimeframeCycles], tolerance: f64) -> Vec<CycleConfluence> {
+    let mut candidates: Vec<ConfluenceMember> = results.iter()
+        .flat_map(|tf| tf.cycles.iter().map(move |cycle| ConfluenceMember {
+            timeframe: tf.timeframe.clone(),
+            cycle: cycle.name.clone(),
+            period_bars: cycle.period,
+            period_hours: tf.period_hours(cycle),
+            confidence: cycle.confidence,
+        }))
+        .collect();
+    candidates.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+
+    // Greedy: each cycle joins the first group near its period that lacks its timeframe
+    let mut groups: Vec<Vec<ConfluenceMember>> = Vec::new();
+    for member in candidates {
+        let matching = groups.iter_mut().find(|group| {
+            let anchor = group[0].period_hours;
+            (member.period_hours - anchor).abs() <= anchor * tolerance
+                && group.iter().all(|m| m.timeframe != member.timeframe)
+        });
+        match matching {
+            Some(group) => group.push(member),
+            None => groups.push(vec![member]),
+        }
+    }
+
+    let analyzed = results.iter().filter(|tf| tf.bars > 0).count().max(1) as f64;
+    let mut confluences: Vec<CycleConfluence> = groups.into_iter()
+        .filter(|group| group.len() >= 2)
+        .map(|mut members| {
+            members.sort_by(|a, b| a.period_hours.total_cmp(&b.period_hours).then(b.confidence.total_cmp(&a.confidence)));
+            let weight: f64 = members.iter().map(|m| m.confidence).sum();
+            let period_hours = members.iter().map(|m| m.period_hours * m.confidence).sum::<f64>() / weight.max(f64::EPSILON);
+            // Mean confidence times agreeing share of timeframes
+            let score = weight / analyzed;
+            let timeframes = results.iter()
+                .map(|tf| tf.timeframe.clone())
+                .filter(|name| members.iter().any(|m| &m.timeframe == name))
+                .collect();
+            CycleConfluence { period_hours, timeframes, members, score }
+        })
+        .collect();
+    confluences.sort_by(|a, b| b.score.total_cmp(&a.score));
+    confluences
+}
@@ -0,0 +1,171 @@
+//! # Cross-Pair Shared Cycles
+//!
+//! Cycle periods shared across pairs, compared in hours so different timeframes
+//! line up, with each member's phase taken at a common reference time.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+use super::HiddenCycle;
+
+/// Shared cycle discovery settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SharedCycleConfig {
+    /// Relative period difference within which cycles of two pairs are the same one
+    pub period_tolerance: f64,
+    /// Pairs a period must be found in to count as shared
+    pub min_pairs: usize,
+}
+
+impl Default for SharedCycleConfig {
+    fn default() -> Self {
+        Self { period_tolerance: 0.1, min_pairs: 2 }
+    }
+}
+
+/// Cycles detected on one pair
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairCycles {
+    pub pair: String,
+    /// Bar length in seconds
+    pub bar_seconds: f64,
+    pub cycles: Vec<HiddenCycle>,
+}
+
+impl PairCycles {
+    /// Wall-clock period of `cycle` in hours
+    pub fn period_hours(&self, cycle: &HiddenCycle) -> f64 {
+        cycle.period as f64 * self.bar_seconds / 3600.0
+    }
+
+    /// Phase of `cycle` at `at`, in radians within `[0, 2π)`
+    pub fn phase_at(&self, cycle: &HiddenCycle, at: DateTime<Utc>) -> f64 {
+        let bars = at.timestamp() as f64 / self.bar_seconds.max(1.0);
+        (2.0 * PI * bars / cycle.period.max(1) as f64 + cycle.phase).rem_euclid(2.0 * PI)
+    }
+}
+
+/// One pair's cycle in a shared group
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SharedCycleMember {
+    pub pair: String,
+    pub cycle: String,
+    pub period_bars: u32,
+    pub period_hours: f64,
+    pub confidence: f64,
+    pub amplitude: f64,
+    /// Phase at the reference time, in degrees within `[0, 360)`
+    pub phase_degrees: f64,
+    /// Phase relative to the group's mean phase, in degrees within `(-180, 180]`
+    pub phase_offset_degrees: f64,
+}
+
+/// A cycle period found in several pairs at once
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SharedCycle {
+    /// Confidence-weighted mean period of the members, in hours
+    pub period_hours: f64,
+    /// Pairs with a cycle at this period, alphabetically
+    pub pairs: Vec<String>,
+    /// One per pair, alphabetically
+    pub members: Vec<SharedCycleMember>,
+    /// Share of the analysed pairs the cycle was found in
+    pub coverage: f64,
+    pub mean_confidence: f64,
+    /// Length of the members' mean phase vector: 1 when every pair peaks
+    /// together, near 0 when their phases are scattered or opposed
+    pub phase_coherence: f64,
+    /// Time the phases are taken at
+    pub reference: DateTime<Utc>,
+    /// Mean confidence times coverage
+    pub score: f64,
+}
+
+impl SharedCycle {
+    pub fn member(&self, pair: &str) -> Option<&SharedCycleMember> {
+        self.members.iter().find(|member| member.pair == pair)
+    }
+
+    /// Phase of `b` minus phase of `a`, in degrees within `(-180, 180]`;
+    /// near 0 the pairs move together, near ±180 against each other
+    pub fn phase_difference(&self, a: &str, b: &str) -> Option<f64> {
+        let (a, b) = (self.member(a)?, self.member(b)?);
+        Some(wrap_degrees(b.phase_degrees - a.phase_degrees))
+    }
+}
+
+fn wrap_degrees(degrees: f64) -> f64 {
+    let wrapped = degrees.rem_euclid(360.0);
+    if wrapped > 180.0 { wrapped - 360.0 } else { wrapped }
+}
+
+/// Group the pairs' cycles by wall-clock period and keep the periods found in
+/// at least [`SharedCycleConfig::min_pairs`] pairs, most widely shared first.
+/// Cycles are grouped in period order, a group spanning at most
+/// `period_tolerance` of its shortest period; a pair counts once per group,
+/// with its most confident cycle. Phases are compared at `reference`.
+pub fn find_shared_cycles(pairs: &[PairCycles], reference: DateTime<Utc>, config: &SharedCycleConfig) -> Vec<SharedCycle> {
+    let mut candidates: Vec<(&PairCycles, &HiddenCycle, f64)> = pairs.iter()
+        .flat_map(|pair| pair.cycles.iter().map(move |cycle| (pair, cycle, pair.period_hours(cycle))))
+        .collect();
+    candidates.sort_by(|a, b| a.2.total_cmp(&b.2));
+
+    let mut groups: Vec<Vec<(&PairCycles, &HiddenCycle, f64)>> = Vec::new();
+    for (pair, cycle, hours) in candidates {
+        match groups.last_mut() {
+            Some(group) if hours - group[0].2 <= group[0].2 * config.period_tolerance => {
+                match group.iter_mut().find(|(member, _, _)| member.pair == pair.pair) {
+                    Some(member) if member.1.confidence < cycle.confidence => *member = (pair, cycle, hours),
+                    Some(_) => {}
+                    None => group.push((pair, cycle, hours)),
+                }
+            }
+            _ => groups.push(vec![(pair, cycle, hours)]),
+        }
+    }
+
+    let analyzed = pairs.iter().filter(|pair| !pair.cycles.is_empty()).count().max(1) as f64;
+    let mut shared: Vec<SharedCycle> = groups.into_iter()
+        .filter(|group| group.len() >= config.min_pairs.max(2))
+        .map(|mut group| {
+            group.sort_by(|a, b| a.0.pair.cmp(&b.0.pair));
+            let count = group.len() as f64;
+            let weight: f64 = group.iter().map(|(_, cycle, _)| cycle.confidence).sum();
+            let period_hours = group.iter().map(|(_, cycle, hours)| hours * cycle.confidence).sum::<f64>() / weight.max(f64::EPSILON);
+
+            let phases: Vec<f64> = group.iter().map(|(pair, cycle, _)| pair.phase_at(cycle, reference)).collect();
+            let (sin, cos) = phases.iter().fold((0.0, 0.0), |(s, c), phase| (s + phase.sin(), c + phase.cos()));
+            let mean_phase = sin.atan2(cos);
+            let phase_coherence = (sin.hypot(cos) / count).min(1.0);
+
+            let members: Vec<SharedCycleMember> = group.iter().zip(&phases)
+                .map(|((pair, cycle, hours), phase)| SharedCycleMember {
+                    pair: pair.pair.clone(),
+                    cycle: cycle.name.clone(),
+                    period_bars: cycle.period,
+                    period_hours: *hours,
+                    confidence: cycle.confidence,
+                    amplitude: cycle.amplitude,
+                    phase_degrees: phase.to_degrees(),
+                    phase_offset_degrees: wrap_degrees((phase - mean_phase).to_degrees()),
+                })
+                .collect();
+            let coverage = count / analyzed;
+            let mean_confidence = weight / count;
+            SharedCycle {
+                period_hours,
+                pairs: members.iter().map(|member| member.pair.clone()).collect(),
+                members,
+                coverage,
+                mean_confidence,
+                phase_coherence,
+                reference,
+                score: mean_confidence * coverage,
+            }
+        })
+        .collect();
+    shared.sort_by(|a, b| b.pairs.len().cmp(&a.pairs.len()).then_with(|| b.mean_confidence.total_cmp(&a.mean_confidence)));
+    shared
+}
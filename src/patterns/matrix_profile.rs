@@ -0,0 +1,271 @@
+//! # Matrix Profile
+//!
+//! STOMP self-join of closes: each window's z-normalized distance to its closest
+//! non-trivial match. Low values mark motifs, shapes the market repeats with the
+//! lag between them; high values mark discords, shapes seen nowhere else.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::data::ForexDataPoint;
+
+/// Matrix profile settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MatrixProfileConfig {
+    /// Bars in each window
+    pub window: usize,
+    /// Trivial-match exclusion zone, as a fraction of the window
+    pub exclusion: f64,
+    /// Motifs reported
+    pub motifs: usize,
+    /// Discords reported
+    pub discords: usize,
+    /// Quantile of historical nearest-match distances beyond which a new window is a discord
+    pub discord_quantile: f64,
+    /// Most recent history bars new windows are matched against
+    pub max_reference_bars: usize,
+}
+
+impl Default for MatrixProfileConfig {
+    fn default() -> Self {
+        Self {
+            window: 24,
+            exclusion: 0.5,
+            motifs: 3,
+            discords: 3,
+            discord_quantile: 0.99,
+            max_reference_bars: 2000,
+        }
+    }
+}
+
+impl MatrixProfileConfig {
+    /// Exclusion zone in bars
+    pub fn exclusion_zone(&self) -> usize {
+        (self.window as f64 * self.exclusion).ceil() as usize
+    }
+}
+
+/// A pair of windows closer to each other than to anything else
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Motif {
+    /// Index of the earlier window's first bar
+    pub index: usize,
+    pub neighbor: usize,
+    pub start: DateTime<Utc>,
+    pub neighbor_start: DateTime<Utc>,
+    /// z-normalized distance between the two
+    pub distance: f64,
+    /// Bars between the two occurrences, the period of the candidate symmetry
+    pub lag_bars: usize,
+}
+
+/// A window far from every other
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Discord {
+    pub index: usize,
+    pub start: DateTime<Utc>,
+    /// z-normalized distance to its closest match
+    pub distance: f64,
+    /// Index of that match
+    pub nearest: usize,
+}
+
+/// Windows of a series with their means and standard deviations, for
+/// z-normalized distances
+#[derive(Debug, Clone)]
+pub struct SubsequenceIndex {
+    /// Values centred on their mean, which leaves z-normalized distances unchanged
+    values: Vec<f64>,
+    offset: f64,
+    window: usize,
+    means: Vec<f64>,
+    stds: Vec<f64>,
+}
+
+impl SubsequenceIndex {
+    /// `None` when `values` holds fewer than two windows
+    pub fn new(values: &[f64], window: usize) -> Option<Self> {
+        if window < 2 || values.len() < window + 1 {
+            return None;
+        }
+        let offset = values.iter().sum::<f64>() / values.len() as f64;
+        let values: Vec<f64> = values.iter().map(|v| v - offset).collect();
+        let (mut sum, mut squares) = (vec![0.0; values.len() + 1], vec![0.0; values.len() + 1]);
+        for (i, v) in values.iter().enumerate() {
+            sum[i + 1] = sum[i] + v;
+            squares[i + 1] = squares[i] + v * v;
+        }
+        let m = window as f64;
+        let (means, stds) = (0..=values.len() - window)
+            .map(|i| {
+                let mean = (sum[i + window] - sum[i]) / m;
+                let variance = (squares[i + window] - squares[i]) / m - mean * mean;
+                (mean, variance.max(0.0).sqrt())
+            })
+            .unzip();
+        Some(Self { values, offset, window, means, stds })
+    }
+
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Number of windows
+    pub fn len(&self) -> usize {
+        self.means.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.means.is_empty()
+    }
+
+    /// z-normalized distance from a window with dot product `dot`, mean and
+    /// standard deviation to window `j`
+    fn distance(&self, dot: f64, mean: f64, std: f64, j: usize) -> f64 {
+        let m = self.window as f64;
+        let flat = f64::EPSILON.sqrt();
+        match (std < flat, self.stds[j] < flat) {
+            (true, true) => 0.0,
+            (true, false) | (false, true) => m.sqrt(),
+            (false, false) => {
+                let correlation = (dot - m * mean * self.means[j]) / (m * std * self.stds[j]);
+                (2.0 * m * (1.0 - correlation.clamp(-1.0, 1.0))).sqrt()
+            }
+        }
+    }
+
+    /// Closest window to `query` (a window of raw values) that `skip` allows,
+    /// with its distance
+    pub fn nearest(&self, query: &[f64], skip: impl Fn(usize) -> bool) -> Option<(usize, f64)> {
+        if query.len() != self.window {
+            return None;
+        }
+        let query: Vec<f64> = query.iter().map(|v| v - self.offset).collect();
+        let m = self.window as f64;
+        let mean = query.iter().sum::<f64>() / m;
+        let std = (query.iter().map(|v| v * v).sum::<f64>() / m - mean * mean).max(0.0).sqrt();
+        (0..self.len())
+            .filter(|&j| !skip(j))
+            .map(|j| {
+                let dot: f64 = query.iter().zip(&self.values[j..j + self.window]).map(|(a, b)| a * b).sum();
+                (j, self.distance(dot, mean, std, j))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+    }
+}
+
+/// Nearest-match distance and index of every window of a series
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatrixProfile {
+    pub window: usize,
+    pub exclusion_zone: usize,
+    /// First bar of each window
+    pub starts: Vec<DateTime<Utc>>,
+    /// Distance to the closest non-trivial match, infinite when there is none
+    pub profile: Vec<f64>,
+    pub index: Vec<Option<usize>>,
+}
+
+impl MatrixProfile {
+    /// STOMP self-join of the closes of `data`; `None` with fewer than two windows
+    pub fn self_join(data: &[ForexDataPoint], config: &MatrixProfileConfig) -> Option<Self> {
+        let closes: Vec<f64> = data.iter().map(|point| point.close).collect();
+        let subsequences = SubsequenceIndex::new(&closes, config.window)?;
+        let (values, m, count) = (&subsequences.values, config.window, subsequences.len());
+        let exclusion_zone = config.exclusion_zone();
+
+        // Dot products of the first window with every window; by symmetry also
+        // the first column of every later row
+        let first: Vec<f64> = (0..count)
+            .map(|j| values[..m].iter().zip(&values[j..j + m]).map(|(a, b)| a * b).sum())
+            .collect();
+        let mut row = first.clone();
+        let mut profile = vec![f64::INFINITY; count];
+        let mut index = vec![None; count];
+
+        for i in 0..count {
+            if i > 0 {
+                for j in (1..count).rev() {
+                    row[j] = row[j - 1] - values[i - 1] * values[j - 1] + values[i + m - 1] * values[j + m - 1];
+                }
+                row[0] = first[i];
+            }
+            let (mean, std) = (subsequences.means[i], subsequences.stds[i]);
+            for (j, dot) in row.iter().enumerate() {
+                if i.abs_diff(j) <= exclusion_zone {
+                    continue;
+                }
+                let distance = subsequences.distance(*dot, mean, std, j);
+                if distance < profile[i] {
+                    profile[i] = distance;
+                    index[i] = Some(j);
+                }
+            }
+        }
+
+        Some(Self {
+            window: m,
+            exclusion_zone,
+            starts: data[..count].iter().map(|point| point.timestamp).collect(),
+            profile,
+            index,
+        })
+    }
+
+    /// Up to `count` motif pairs, closest first, no two within the exclusion zone
+    /// of each other
+    pub fn motifs(&self, count: usize) -> Vec<Motif> {
+        let mut taken = vec![false; self.profile.len()];
+        let mut motifs = Vec::new();
+        let mut order: Vec<usize> = (0..self.profile.len()).filter(|&i| self.profile[i].is_finite()).collect();
+        order.sort_by(|a, b| self.profile[*a].total_cmp(&self.profile[*b]));
+        for i in order {
+            if motifs.len() >= count {
+                break;
+            }
+            let Some(j) = self.index[i] else { continue };
+            if taken[i] || taken[j] {
+                continue;
+            }
+            self.take(&mut taken, i);
+            self.take(&mut taken, j);
+            let (index, neighbor) = (i.min(j), i.max(j));
+            motifs.push(Motif {
+                index,
+                neighbor,
+                start: self.starts[index],
+                neighbor_start: self.starts[neighbor],
+                distance: self.profile[i],
+                lag_bars: neighbor - index,
+            });
+        }
+        motifs
+    }
+
+    /// Up to `count` discords, farthest first, no two within the exclusion zone
+    /// of each other
+    pub fn discords(&self, count: usize) -> Vec<Discord> {
+        let mut taken = vec![false; self.profile.len()];
+        let mut discords = Vec::new();
+        let mut order: Vec<usize> = (0..self.profile.len()).filter(|&i| self.profile[i].is_finite()).collect();
+        order.sort_by(|a, b| self.profile[*b].total_cmp(&self.profile[*a]));
+        for i in order {
+            if discords.len() >= count {
+                break;
+            }
+            if taken[i] {
+                continue;
+            }
+            self.take(&mut taken, i);
+            discords.push(Discord { index: i, start: self.starts[i], distance: self.profile[i], nearest: self.index[i].unwrap_or(i) });
+        }
+        discords
+    }
+
+    fn take(&self, taken: &mut [bool], i: usize) {
+        let (from, to) = (i.saturating_sub(self.exclusion_zone), (i + self.exclusion_zone).min(taken.len() - 1));
+        taken[from..=to].iter_mut().for_each(|t| *t = true);
+    }
+}
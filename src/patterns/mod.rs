@@ -3,8 +3,25 @@
 //! Cycle detection and pattern analysis for forex data.
 
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use crate::data::ForexDataPoint;
+use crate::data::timeframe::TimeframeAggregator;
+use crate::galois::{CorrectionReport, PriceErrorCorrector, SyndromeStatistics};
+use crate::ids::CycleId;
+
+pub mod confluence;
+pub mod cross_pair;
+pub mod matrix_profile;
+pub mod significance;
+pub mod spectral;
+pub mod wavelet;
+
+pub use confluence::{find_cycle_confluence, CycleConfluence, TimeframeCycles};
+pub use cross_pair::{find_shared_cycles, PairCycles, SharedCycle, SharedCycleConfig};
+pub use matrix_profile::{Discord, MatrixProfile, MatrixProfileConfig, Motif};
+pub use significance::{CycleSignificance, SignificanceConfig, SurrogateMethod};
+pub use wavelet::{PeriodPoint, PeriodTrack, WaveletConfig, WaveletPowerMap};
 
 /// Pattern recognition configuration
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -12,6 +29,13 @@ pub struct PatternConfig {
     pub min_cycle_length: u32,
     pub max_cycle_length: u32,
     pub confidence_threshold: f64,
+    /// Surrogate-test every detected cycle and report its p-value; off when `None`
+    #[serde(default)]
+    pub significance: Option<SignificanceConfig>,
+    /// Also follow ridges of the wavelet power map and report the drifting or
+    /// transient cycles among them (see [`wavelet`]); off when `None`
+    #[serde(default)]
+    pub wavelet: Option<WaveletConfig>,
 }
 
 impl Default for PatternConfig {
@@ -20,6 +44,8 @@ impl Default for PatternConfig {
             min_cycle_length: 2,
             max_cycle_length: 365,
             confidence_threshold: 0.75,
+            significance: None,
+            wavelet: None,
         }
     }
 }
@@ -41,49 +67,250 @@ impl Default for DecompositionConfig {
 }
 
 /// Hidden cycle structure
+///
+/// `period` is in bars; `amplitude` is relative to the mean price and `phase` is in
+/// radians for `sin(2π t / period + phase)` with `t` in bars since the Unix epoch.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HiddenCycle {
+    /// Cycles saved without an id get a fresh one when loaded
+    #[serde(default)]
+    pub id: CycleId,
     pub name: String,
     pub period: u32,
     pub confidence: f64,
     pub amplitude: f64,
     pub phase: f64,
+    /// Surrogate-test p-value (see [`significance`]), when the cycle was tested
+    #[serde(default)]
+    pub p_value: Option<f64>,
+    /// Period over time of a non-stationary cycle found by the wavelet search;
+    /// `None` for a fixed-period Fourier cycle
+    #[serde(default)]
+    pub track: Option<PeriodTrack>,
+}
+
+impl HiddenCycle {
+    /// Whether the cycle drifts in period or only runs for part of the history
+    pub fn is_non_stationary(&self) -> bool {
+        self.track.is_some()
+    }
 }
 
 /// Pattern recognizer
 pub struct PatternRecognizer {
     config: PatternConfig,
+    error_corrector: Option<PriceErrorCorrector>,
+    last_syndromes: Option<SyndromeStatistics>,
 }
 
 impl PatternRecognizer {
     pub fn new(config: PatternConfig) -> Result<Self> {
-        Ok(Self { config })
+        Ok(Self { config, error_corrector: None, last_syndromes: None })
+    }
+    
+    /// Search for cycles in closes decoded by `error_corrector`, so isolated bad
+    /// ticks do not leak power into the periodogram
+    pub fn with_error_correction(mut self, error_corrector: PriceErrorCorrector) -> Self {
+        self.error_corrector = Some(error_corrector);
+        self
     }
     
+    /// Least confidence a cycle needs to be reported, from the next search on
+    pub fn set_confidence_threshold(&mut self, confidence_threshold: f64) {
+        self.config.confidence_threshold = confidence_threshold;
+    }
+    
+    /// Corrected closes and syndrome statistics of `data`, when error correction is on
+    pub fn error_correction(&self, data: &[ForexDataPoint]) -> Result<Option<CorrectionReport>> {
+        self.error_corrector.as_ref().map(|corrector| corrector.correct(data)).transpose()
+    }
+    
+    /// Syndrome statistics of the data last passed to [`detect_cycles`](Self::detect_cycles)
+    pub fn last_syndrome_statistics(&self) -> Option<&SyndromeStatistics> {
+        self.last_syndromes.as_ref()
+    }
+    
+    /// Find dominant cycles in the close-price series.
+    ///
+    /// Peaks of the log-return periodogram (FFT for evenly spaced bars, Lomb-Scargle
+    /// otherwise) between the configured cycle lengths are scored by one minus their
+    /// white-noise false-alarm probability. Cycles are extracted strongest first and
+    /// subtracted before the next search, which suppresses sampling aliases. With
+    /// `significance` configured each cycle also gets a surrogate-test p-value.
+    /// With `wavelet` configured, drifting or transient cycles found along wavelet
+    /// ridges follow the Fourier ones, each with its period track; a Fourier cycle
+    /// that drifts can appear again among them.
     pub async fn detect_cycles(&mut self, data: &[ForexDataPoint]) -> Result<Vec<HiddenCycle>> {
-        let mut cycles = Vec::new();
-        
-        // Placeholder cycle detection
-        cycles.push(HiddenCycle {
-            name: "Weekly Cycle".to_string(),
-            period: 7,
-            confidence: 0.85,
-            amplitude: 0.01,
-            phase: 0.0,
-        });
-        
-        cycles.push(HiddenCycle {
-            name: "Monthly Cycle".to_string(),
-            period: 30,
-            confidence: 0.78,
-            amplitude: 0.025,
-            phase: 1.57,
-        });
-        
+        let Some(report) = self.error_correction(data)? else {
+            return Ok(self.find_cycles_in(data));
+        };
+        let cycles = self.find_cycles_in(&report.corrected);
+        self.last_syndromes = Some(report.statistics);
         Ok(cycles)
     }
+    
+    /// Synchronous [`detect_cycles`](Self::detect_cycles), for callers outside an async context
+    pub fn find_cycles(&self, data: &[ForexDataPoint]) -> Vec<HiddenCycle> {
+        match self.error_correction(data) {
+            Ok(Some(report)) => self.find_cycles_in(&report.corrected),
+            _ => self.find_cycles_in(data),
+        }
+    }
+    
+    fn find_cycles_in(&self, data: &[ForexDataPoint]) -> Vec<HiddenCycle> {
+        let Some(mut series) = spectral::SampledSeries::from_closes(data) else {
+            return Vec::new();
+        };
+        // Cycles are subtracted as they are found; the wavelet search needs the prices as loaded
+        let original = self.config.wavelet.as_ref().map(|_| series.clone());
+
+        // A cycle needs at least two full repetitions in the data to be measured
+        let max_period = (self.config.max_cycle_length as f64).min(series.span() / 2.0);
+        let min_period = (self.config.min_cycle_length as f64).max(2.0);
+        if max_period <= min_period {
+            return Vec::new();
+        }
+
+        let mut cycles: Vec<HiddenCycle> = Vec::new();
+        // Bounded: rejected peaks are still subtracted, but never loop on a peak that will not fit
+        for _ in 0..MAX_CYCLES * 2 {
+            if cycles.len() >= MAX_CYCLES {
+                break;
+            }
+            let (spectrum, independent) = spectral::return_periodogram(&series, min_period, max_period);
+            let Some(peak) = spectral::spectral_peaks(&spectrum).into_iter().next() else { break };
+
+            let confidence = 1.0 - spectral::false_alarm_probability(peak.power, independent);
+            if confidence < self.config.confidence_threshold {
+                break;
+            }
+
+            let exact_period = spectral::refine_period(&series, 1.0 / peak.frequency);
+            let (amplitude, exact_phase) = spectral::fit_sinusoid(&series, exact_period);
+            let period = exact_period.round() as u32;
+            // The phase is reported for the whole-bar period: with `t` counted from the
+            // epoch, a fraction of a bar in the period shifts the phase by whole radians
+            let (_, phase) = spectral::fit_sinusoid(&series, period.max(1) as f64);
+            series.remove_cycle(exact_period, amplitude, exact_phase);
+
+            let duplicate = cycles.iter().any(|c| (c.period as f64 - exact_period).abs() <= c.period as f64 * 0.1);
+            if duplicate || period < self.config.min_cycle_length || period > self.config.max_cycle_length {
+                continue;
+            }
+            cycles.push(HiddenCycle {
+                id: CycleId::new(),
+                name: cycle_name(period, series.spacing_seconds),
+                period,
+                confidence,
+                amplitude,
+                phase,
+                p_value: None,
+                track: None,
+            });
+        }
+
+        if let (Some(wavelet), Some(series)) = (&self.config.wavelet, original) {
+            let fourier = cycles.len();
+            let independent = ((1.0 / min_period - 1.0 / max_period) * series.span()).round().max(1.0) as usize;
+            let found = wavelet::continuous_wavelet_transform(&series, min_period, max_period, wavelet)
+                .map(|map| wavelet::ridge_cycles(&series, &map, wavelet, independent))
+                .unwrap_or_default();
+            for ridge in found.into_iter().filter(|ridge| ridge.is_non_stationary(wavelet)) {
+                if cycles.len() - fourier >= wavelet.max_ridges {
+                    break;
+                }
+                let period = ridge.ridge.mean_period.round() as u32;
+                if ridge.confidence < self.config.confidence_threshold
+                    || period < self.config.min_cycle_length || period > self.config.max_cycle_length {
+                    continue;
+                }
+                cycles.push(HiddenCycle {
+                    id: CycleId::new(),
+                    name: format!("Drifting {}", cycle_name(period, series.spacing_seconds)),
+                    period,
+                    confidence: ridge.confidence,
+                    amplitude: ridge.amplitude,
+                    phase: ridge.phase,
+                    p_value: None,
+                    track: Some(ridge.track),
+                });
+            }
+        }
+
+        if let Some(significance) = &self.config.significance {
+            let results = significance::test_cycles(data, &cycles, min_period, max_period, significance);
+            for cycle in &mut cycles {
+                cycle.p_value = results.iter().find(|r| r.cycle_id == cycle.id).map(|r| r.p_value);
+            }
+        }
+        cycles
+    }
+    
+    /// Surrogate-test `cycles` found in `data`, in the order they were extracted,
+    /// against chance peaks in this recognizer's band of cycle lengths
+    pub fn cycle_significance(&self, data: &[ForexDataPoint], cycles: &[HiddenCycle], config: &SignificanceConfig) -> Result<Vec<CycleSignificance>> {
+        let corrected = self.error_correction(data)?.map(|report| report.corrected);
+        let data = corrected.as_deref().unwrap_or(data);
+        let Some(series) = spectral::SampledSeries::from_closes(data) else {
+            return Ok(Vec::new());
+        };
+        let max_period = (self.config.max_cycle_length as f64).min(series.span() / 2.0);
+        let min_period = (self.config.min_cycle_length as f64).max(2.0);
+        Ok(significance::test_cycles(data, cycles, min_period, max_period, config))
+    }
+    
+    /// Normalized Morlet wavelet power of `data` per period and bar, over this
+    /// recognizer's band of cycle lengths; `None` when there are too few bars
+    pub fn wavelet_power(&self, data: &[ForexDataPoint], config: &WaveletConfig) -> Result<Option<WaveletPowerMap>> {
+        let corrected = self.error_correction(data)?.map(|report| report.corrected);
+        let data = corrected.as_deref().unwrap_or(data);
+        let Some(series) = spectral::SampledSeries::from_closes(data) else {
+            return Ok(None);
+        };
+        let max_period = (self.config.max_cycle_length as f64).min(series.span() / 2.0);
+        let min_period = (self.config.min_cycle_length as f64).max(2.0);
+        Ok(wavelet::continuous_wavelet_transform(&series, min_period, max_period, config))
+    }
+    
+    /// Resample `data` to each of `timeframes` and detect cycles on every one.
+    /// Periods stay in bars of their own timeframe; see [`find_cycle_confluence`].
+    pub async fn detect_cycles_multi_timeframe(
+        &mut self,
+        data: &[ForexDataPoint],
+        timeframes: &[String],
+    ) -> Result<Vec<TimeframeCycles>> {
+        let mut results = Vec::with_capacity(timeframes.len());
+        for timeframe in timeframes {
+            let aggregator = TimeframeAggregator::new(timeframe)?;
+            let bars = aggregator.resample(data)?;
+            let cycles = self.detect_cycles(&bars).await?;
+            results.push(TimeframeCycles {
+                timeframe: aggregator.timeframe().to_string(),
+                bar_seconds: aggregator.interval().num_seconds() as f64,
+                bars: bars.len(),
+                cycles,
+            });
+        }
+        Ok(results)
+    }
+}
+
+/// Most cycles reported by one detection pass
+const MAX_CYCLES: usize = 10;
+
+fn cycle_name(period: u32, spacing_seconds: f64) -> String {
+    let daily = (spacing_seconds - 86_400.0).abs() < 3_600.0;
+    match period {
+        5..=7 if daily => "Weekly Cycle".to_string(),
+        28..=31 if daily => "Monthly Cycle".to_string(),
+        88..=92 if daily => "Quarterly Cycle".to_string(),
+        _ => format!("{}-Bar Cycle", period),
+    }
 }
 
+/// Most back-fitting passes of [`CycleDecomposer::decompose_cycles`]
+const MAX_DECOMPOSITION_PASSES: usize = 100;
+
 /// Cycle decomposer
 pub struct CycleDecomposer {
     config: DecompositionConfig,
@@ -94,43 +321,157 @@ impl CycleDecomposer {
         Ok(Self { config })
     }
     
+    /// Split the closes into a linear trend, one sinusoid per target period (in bars)
+    /// and a residual, all aligned with the input timestamps.
+    ///
+    /// The sinusoids are fitted jointly by back-fitting: each is refitted to the
+    /// closes less the others until no component moves by more than the
+    /// convergence threshold (relative to the mean price). At most
+    /// `max_components` periods are used, in the order given.
     pub async fn decompose_cycles(
         &mut self,
         data: &[ForexDataPoint],
         target_cycles: &[u32],
     ) -> Result<CycleDecomposition> {
-        let mut components = std::collections::HashMap::new();
+        let Some(series) = spectral::SampledSeries::from_closes(data) else {
+            anyhow::bail!("decomposition needs at least 4 bars, got {}", data.len());
+        };
+        if let Some(period) = target_cycles.iter().find(|period| **period < 2) {
+            anyhow::bail!("cycle periods must be at least 2 bars, got {}", period);
+        }
+        let mut periods: Vec<u32> = Vec::new();
+        for &period in target_cycles {
+            if !periods.contains(&period) && periods.len() < self.config.max_components {
+                periods.push(period);
+            }
+        }
         
-        for &cycle_period in target_cycles {
-            let component = CycleComponent {
-                amplitude: 0.01 + (cycle_period as f64 * 0.0001),
-                phase_degrees: (cycle_period as f64 * 0.1) % 360.0,
-                strength: 0.8 - (cycle_period as f64 * 0.0001),
-            };
-            components.insert(cycle_period, component);
+        let closes = series.values.clone();
+        let mean_price = closes.iter().sum::<f64>() / closes.len() as f64;
+        let mut fits = vec![(0.0, 0.0); periods.len()];
+        let mut waves = vec![vec![0.0; closes.len()]; periods.len()];
+        let mut partial = series.clone();
+        for _ in 0..MAX_DECOMPOSITION_PASSES {
+            let mut largest_change: f64 = 0.0;
+            for k in 0..periods.len() {
+                for (i, value) in partial.values.iter_mut().enumerate() {
+                    *value = closes[i] - (0..periods.len()).filter(|&j| j != k).map(|j| waves[j][i]).sum::<f64>();
+                }
+                let (amplitude, phase) = spectral::fit_sinusoid(&partial, periods[k] as f64);
+                let scale = amplitude * partial.values.iter().sum::<f64>() / partial.values.len() as f64;
+                let omega = 2.0 * std::f64::consts::PI / periods[k] as f64;
+                for (wave, t) in waves[k].iter_mut().zip(&series.times) {
+                    let updated = scale * (omega * t + phase).sin();
+                    largest_change = largest_change.max((updated - *wave).abs());
+                    *wave = updated;
+                }
+                fits[k] = (amplitude, phase);
+            }
+            if largest_change <= self.config.convergence_threshold * mean_price.abs() {
+                break;
+            }
         }
         
-        Ok(CycleDecomposition { components })
+        let cyclical: Vec<f64> = (0..closes.len()).map(|i| waves.iter().map(|wave| wave[i]).sum()).collect();
+        let deseasoned: Vec<f64> = closes.iter().zip(&cyclical).map(|(close, cycle)| close - cycle).collect();
+        let trend = spectral::linear_trend(&series.times, &deseasoned);
+        let residual: Vec<f64> = deseasoned.iter().zip(&trend).map(|(value, trend)| value - trend).collect();
+        let detrended_variance = variance(&closes.iter().zip(&trend).map(|(close, trend)| close - trend).collect::<Vec<_>>());
+        
+        let components = periods.iter()
+            .zip(fits)
+            .zip(waves)
+            .map(|((&period, (amplitude, phase)), series)| {
+                let strength = if detrended_variance > 0.0 { (variance(&series) / detrended_variance).min(1.0) } else { 0.0 };
+                (period, CycleComponent { amplitude, phase_degrees: phase.to_degrees().rem_euclid(360.0), strength, series })
+            })
+            .collect();
+        
+        Ok(CycleDecomposition {
+            components,
+            timestamps: data.iter().map(|point| point.timestamp).collect(),
+            closes,
+            trend,
+            residual,
+        })
     }
 }
 
+fn variance(values: &[f64]) -> f64 {
+    let mean = values.iter().sum::<f64>() / values.len().max(1) as f64;
+    values.iter().map(|value| (value - mean).powi(2)).sum::<f64>() / values.len().max(1) as f64
+}
+
 /// Cycle decomposition result
+///
+/// Every series is aligned with `timestamps`, and each close is exactly its trend
+/// plus the component values plus the residual.
 #[derive(Debug, Clone, Serialize)]
 pub struct CycleDecomposition {
     pub components: std::collections::HashMap<u32, CycleComponent>,
+    /// Timestamps of the decomposed bars
+    pub timestamps: Vec<DateTime<Utc>>,
+    pub closes: Vec<f64>,
+    /// Linear trend of the closes net of the cycles
+    pub trend: Vec<f64>,
+    /// What the trend and cycles leave unexplained
+    pub residual: Vec<f64>,
 }
 
 impl CycleDecomposition {
+    /// Component periods, shortest first
+    pub fn periods(&self) -> Vec<u32> {
+        let mut periods: Vec<u32> = self.components.keys().copied().collect();
+        periods.sort_unstable();
+        periods
+    }
+    
+    /// Trend plus every component at each timestamp
+    pub fn fitted(&self) -> Vec<f64> {
+        (0..self.closes.len())
+            .map(|i| self.trend[i] + self.components.values().map(|component| component.series[i]).sum::<f64>())
+            .collect()
+    }
+    
+    /// Standard deviation of the residual
+    pub fn residual_std(&self) -> f64 {
+        variance(&self.residual).sqrt()
+    }
+    
+    /// Write one row per timestamp: close, trend, each component (shortest period
+    /// first) and the residual
     pub fn save_to_csv(&self, filename: &str) -> Result<()> {
-        // Placeholder CSV save
+        let periods = self.periods();
+        let mut writer = csv::Writer::from_path(filename)?;
+        let mut header = vec!["time".to_string(), "close".to_string(), "trend".to_string()];
+        header.extend(periods.iter().map(|period| format!("cycle_{}", period)));
+        header.push("residual".to_string());
+        writer.write_record(&header)?;
+        for (i, timestamp) in self.timestamps.iter().enumerate() {
+            let mut record = vec![
+                timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
+                format!("{:.6}", self.closes[i]),
+                format!("{:.6}", self.trend[i]),
+            ];
+            record.extend(periods.iter().map(|period| format!("{:.8}", self.components[period].series[i])));
+            record.push(format!("{:.8}", self.residual[i]));
+            writer.write_record(&record)?;
+        }
+        writer.flush()?;
         Ok(())
     }
 }
 
 /// Individual cycle component
+///
+/// `amplitude` is relative to the mean price and `phase_degrees` is the phase of
+/// `sin(2π t / period + phase)` with `t` in bars since the Unix epoch, as in [`HiddenCycle`].
 #[derive(Debug, Clone, Serialize)]
 pub struct CycleComponent {
     pub amplitude: f64,
     pub phase_degrees: f64,
+    /// Share of the detrended price variance the component explains
     pub strength: f64,
+    /// Component value at each timestamp of the decomposition, in price units
+    pub series: Vec<f64>,
 }
@@ -0,0 +1,234 @@
+//! # Cycle Significance
+//!
+//! Surrogate-data p-values for detected cycles. Phase-randomized or
+//! block-bootstrapped surrogates keep the returns' distribution and colour but
+//! scramble any cycle; a cycle's p-value is the share of surrogates whose
+//! strongest peak anywhere in the search band is as strong.
+
+use num_complex::Complex64;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+use std::fmt;
+
+use super::spectral::{self, SampledSeries};
+use super::HiddenCycle;
+use crate::data::ForexDataPoint;
+use crate::ids::CycleId;
+
+/// Frequency bins on each side averaged into the smoothed amplitude spectrum
+const SMOOTHING_BINS: usize = 8;
+
+/// How surrogate series are generated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SurrogateMethod {
+    #[default]
+    PhaseRandomized,
+    BlockBootstrap,
+}
+
+impl fmt::Display for SurrogateMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SurrogateMethod::PhaseRandomized => f.pad("phase-randomized"),
+            SurrogateMethod::BlockBootstrap => f.pad("block bootstrap"),
+        }
+    }
+}
+
+/// Surrogate test settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SignificanceConfig {
+    pub method: SurrogateMethod,
+    pub surrogates: usize,
+    /// Returns per bootstrap block; the cube root of the number of returns when 0
+    pub block_length: usize,
+    /// p-value at or below which a cycle beats chance
+    pub alpha: f64,
+    pub seed: u64,
+}
+
+impl Default for SignificanceConfig {
+    fn default() -> Self {
+        Self {
+            method: SurrogateMethod::PhaseRandomized,
+            surrogates: 100,
+            block_length: 0,
+            alpha: 0.05,
+            seed: 42,
+        }
+    }
+}
+
+/// Surrogate test of one cycle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CycleSignificance {
+    pub cycle_id: CycleId,
+    pub period: u32,
+    /// Normalized periodogram power of the cycle's peak
+    pub power: f64,
+    /// Median of the surrogates' strongest peaks
+    pub surrogate_median: f64,
+    /// Share of surrogates (counting the data itself) with a peak at least as strong
+    pub p_value: f64,
+    pub significant: bool,
+}
+
+/// Test `cycles`, in the order they were extracted, against surrogates of
+/// `data` searched between `min_period` and `max_period` bars. Cycles whose peak
+/// cannot be found (too few bars, period outside the band) get no result.
+pub fn test_cycles(
+    data: &[ForexDataPoint],
+    cycles: &[HiddenCycle],
+    min_period: f64,
+    max_period: f64,
+    config: &SignificanceConfig,
+) -> Vec<CycleSignificance> {
+    let Some(original) = SampledSeries::from_closes(data) else {
+        return Vec::new();
+    };
+    let mut series = original.clone();
+    let observed: Vec<(&HiddenCycle, Option<f64>)> = cycles.iter()
+        .map(|cycle| {
+            let power = cycle_power(&series, cycle.period as f64, min_period, max_period);
+            let exact_period = spectral::refine_period(&series, cycle.period as f64);
+            let (amplitude, phase) = spectral::fit_sinusoid(&series, exact_period);
+            series.remove_cycle(exact_period, amplitude, phase);
+            (cycle, power)
+        })
+        .collect();
+    if observed.iter().all(|(_, power)| power.is_none()) {
+        return Vec::new();
+    }
+
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let mut maxima: Vec<f64> = (0..config.surrogates)
+        .filter_map(|_| surrogate(&original, config, &mut rng))
+        .map(|surrogate| band_maximum(&surrogate, min_period, max_period))
+        .collect();
+    maxima.sort_by(|a, b| a.total_cmp(b));
+    let surrogate_median = maxima.get(maxima.len() / 2).copied().unwrap_or(0.0);
+
+    observed.into_iter()
+        .filter_map(|(cycle, power)| power.map(|power| (cycle, power)))
+        .map(|(cycle, power)| {
+            let exceeding = maxima.len() - maxima.partition_point(|max| *max < power);
+            let p_value = (exceeding + 1) as f64 / (maxima.len() + 1) as f64;
+            CycleSignificance {
+                cycle_id: cycle.id.clone(),
+                period: cycle.period,
+                power,
+                surrogate_median,
+                p_value,
+                significant: p_value <= config.alpha,
+            }
+        })
+        .collect()
+}
+
+/// A surrogate of `series` under `config`, `None` when it is too short
+pub fn surrogate(series: &SampledSeries, config: &SignificanceConfig, rng: &mut StdRng) -> Option<SampledSeries> {
+    let returns: Vec<f64> = series.values.windows(2)
+        .map(|w| if w[0] > 0.0 && w[1] > 0.0 { (w[1] / w[0]).ln() } else { 0.0 })
+        .collect();
+    if returns.len() < 4 {
+        return None;
+    }
+    let (start, shuffled) = match config.method {
+        SurrogateMethod::PhaseRandomized => {
+            // The FFT needs a power of two: keep the most recent returns, as the periodogram does
+            let size = 1usize << (usize::BITS - 1 - returns.len().leading_zeros());
+            let start = returns.len() - size;
+            (start, phase_randomize(&returns[start..], rng))
+        }
+        SurrogateMethod::BlockBootstrap => {
+            let block = if config.block_length == 0 {
+                (returns.len() as f64).cbrt().round().max(2.0) as usize
+            } else {
+                config.block_length
+            };
+            (0, block_bootstrap(&returns, block, rng))
+        }
+    };
+
+    let mut price = series.values[start];
+    let mut values = Vec::with_capacity(shuffled.len() + 1);
+    values.push(price);
+    for r in shuffled {
+        price *= r.exp();
+        values.push(price);
+    }
+    Some(SampledSeries {
+        times: series.times[start..].to_vec(),
+        values,
+        regular: series.regular,
+        spacing_seconds: series.spacing_seconds,
+    })
+}
+
+/// Strongest peak within a frequency bin of `period` in the band periodogram of `series`
+fn cycle_power(series: &SampledSeries, period: f64, min_period: f64, max_period: f64) -> Option<f64> {
+    let span = series.span();
+    if span <= 0.0 || period < min_period || period > max_period {
+        return None;
+    }
+    let (spectrum, _) = spectral::return_periodogram(series, min_period, max_period);
+    // FFT bins of the power-of-two tail can be coarser than 1 / span
+    let spacing = spectrum.windows(2).map(|w| w[1].frequency - w[0].frequency).next().unwrap_or(0.0);
+    let (frequency, bin) = (1.0 / period, spacing.max(1.0 / span));
+    spectrum.iter()
+        .filter(|point| (point.frequency - frequency).abs() <= bin)
+        .map(|point| point.power)
+        .max_by(|a, b| a.total_cmp(b))
+}
+
+fn band_maximum(series: &SampledSeries, min_period: f64, max_period: f64) -> f64 {
+    let (spectrum, _) = spectral::return_periodogram(series, min_period, max_period);
+    spectrum.iter().map(|point| point.power).fold(0.0, f64::max)
+}
+
+/// Gaussian returns whose expected spectrum is the smoothed spectrum of `returns`
+fn phase_randomize(returns: &[f64], rng: &mut StdRng) -> Vec<f64> {
+    let n = returns.len();
+    let mean = returns.iter().sum::<f64>() / n as f64;
+    let mut buffer: Vec<Complex64> = returns.iter().map(|r| Complex64::new(r - mean, 0.0)).collect();
+    spectral::fft(&mut buffer);
+    let half = n / 2;
+    let power: Vec<f64> = buffer.iter().map(|x| x.norm_sqr()).collect();
+    let smoothed = |k: usize| {
+        let (low, high) = (k.saturating_sub(SMOOTHING_BINS).max(1), (k + SMOOTHING_BINS).min(half));
+        (power[low..=high].iter().sum::<f64>() / (high - low + 1) as f64).sqrt()
+    };
+
+    let mut spectrum = vec![Complex64::new(0.0, 0.0); n];
+    // Rayleigh magnitude and uniform phase: a complex Gaussian with the smoothed power
+    let mut draw = |k: usize| Complex64::from_polar(smoothed(k) * (-rng.gen_range(f64::EPSILON..1.0).ln()).sqrt(), rng.gen_range(0.0..2.0 * PI));
+    for k in 1..half {
+        let value = draw(k);
+        spectrum[k] = value;
+        spectrum[n - k] = value.conj();
+    }
+    if half > 0 {
+        spectrum[half] = Complex64::new(draw(half).re * 2.0_f64.sqrt(), 0.0);
+    }
+    // Inverse transform through the forward one: ifft(x) = conj(fft(conj(x))) / n
+    spectrum.iter_mut().for_each(|x| *x = x.conj());
+    spectral::fft(&mut spectrum);
+    spectrum.iter().map(|x| x.re / n as f64 + mean).collect()
+}
+
+/// `returns.len()` returns drawn as consecutive blocks of `block` from random
+/// starts, wrapping around the end
+fn block_bootstrap(returns: &[f64], block: usize, rng: &mut StdRng) -> Vec<f64> {
+    let n = returns.len();
+    let block = block.clamp(1, n);
+    let mut shuffled = Vec::with_capacity(n);
+    while shuffled.len() < n {
+        let start = rng.gen_range(0..n);
+        shuffled.extend((0..block.min(n - shuffled.len())).map(|i| returns[(start + i) % n]));
+    }
+    shuffled
+}
@@ -0,0 +1,279 @@
+//! # Spectral Analysis
+//!
+//! Periodograms of log returns: an FFT when bars are evenly spaced, Lomb-Scargle
+//! when timestamps have gaps (weekends, holidays). Time is measured in units of the
+//! median bar spacing, so periods come out in bars (days for daily data).
+
+use num_complex::Complex64;
+use std::f64::consts::PI;
+
+use crate::data::ForexDataPoint;
+
+/// Gaps longer than this many median spacings make a series irregular
+const IRREGULAR_GAP_RATIO: f64 = 1.5;
+/// Frequency grid oversampling for Lomb-Scargle
+const LOMB_SCARGLE_OVERSAMPLING: f64 = 4.0;
+/// Upper bound on Lomb-Scargle grid size
+const MAX_FREQUENCIES: usize = 4096;
+
+/// Evenly or unevenly sampled series on a common time axis
+#[derive(Debug, Clone)]
+pub struct SampledSeries {
+    /// Sample times in median-spacing units since the Unix epoch
+    pub times: Vec<f64>,
+    pub values: Vec<f64>,
+    /// Whether every gap is close to the median spacing
+    pub regular: bool,
+    /// Median spacing in seconds
+    pub spacing_seconds: f64,
+}
+
+impl SampledSeries {
+    /// Close prices on the median-spacing time axis
+    pub fn from_closes(data: &[ForexDataPoint]) -> Option<Self> {
+        if data.len() < 4 {
+            return None;
+        }
+        let seconds: Vec<f64> = data.iter().map(|point| point.timestamp.timestamp() as f64).collect();
+        let mut gaps: Vec<f64> = seconds.windows(2).map(|w| w[1] - w[0]).filter(|gap| *gap > 0.0).collect();
+        if gaps.is_empty() {
+            return None;
+        }
+        gaps.sort_by(|a, b| a.total_cmp(b));
+        let spacing_seconds = gaps[gaps.len() / 2];
+        let regular = gaps.last().is_some_and(|longest| *longest <= spacing_seconds * IRREGULAR_GAP_RATIO);
+
+        Some(Self {
+            times: seconds.iter().map(|s| s / spacing_seconds).collect(),
+            values: data.iter().map(|point| point.close).collect(),
+            regular,
+            spacing_seconds,
+        })
+    }
+
+    /// Subtract a fitted cycle (see [`fit_sinusoid`]) so weaker cycles and the aliases
+    /// of this one stop competing for the strongest peak
+    pub fn remove_cycle(&mut self, period: f64, amplitude: f64, phase: f64) {
+        let mean_price = self.values.iter().sum::<f64>() / self.values.len().max(1) as f64;
+        let omega = 2.0 * PI / period;
+        for (value, t) in self.values.iter_mut().zip(&self.times) {
+            *value -= amplitude * mean_price * (omega * t + phase).sin();
+        }
+    }
+
+    /// Span of the series in median-spacing units
+    pub fn span(&self) -> f64 {
+        self.times.last().unwrap_or(&0.0) - self.times.first().unwrap_or(&0.0)
+    }
+}
+
+/// Periodogram value at one frequency
+#[derive(Debug, Clone, Copy)]
+pub struct SpectralPoint {
+    /// Cycles per median spacing
+    pub frequency: f64,
+    /// Power normalized by the variance; exponentially distributed with mean 1 for white noise
+    pub power: f64,
+}
+
+/// Normalized periodogram of log returns between `min_period` and `max_period`,
+/// plus the number of independent frequencies in that band
+pub fn return_periodogram(series: &SampledSeries, min_period: f64, max_period: f64) -> (Vec<SpectralPoint>, usize) {
+    let (times, returns) = log_returns(series);
+    if returns.len() < 4 {
+        return (Vec::new(), 0);
+    }
+    let span = times.last().unwrap_or(&0.0) - times.first().unwrap_or(&0.0);
+    let (min_frequency, max_frequency) = (1.0 / max_period, 1.0 / min_period);
+    let independent = ((max_frequency - min_frequency) * span).round().max(1.0) as usize;
+
+    let spectrum = if series.regular {
+        fft_periodogram(&returns)
+    } else {
+        lomb_scargle(&times, &returns, min_frequency, max_frequency, span)
+    };
+    let in_band = spectrum.into_iter()
+        .filter(|point| point.frequency >= min_frequency && point.frequency <= max_frequency)
+        .collect();
+    (in_band, independent)
+}
+
+/// Local maxima of a periodogram, strongest first
+pub fn spectral_peaks(spectrum: &[SpectralPoint]) -> Vec<SpectralPoint> {
+    let mut peaks: Vec<SpectralPoint> = (0..spectrum.len())
+        .filter(|&i| {
+            let left = i == 0 || spectrum[i - 1].power < spectrum[i].power;
+            let right = i + 1 == spectrum.len() || spectrum[i + 1].power <= spectrum[i].power;
+            left && right
+        })
+        .map(|i| spectrum[i])
+        .collect();
+    peaks.sort_by(|a, b| b.power.total_cmp(&a.power));
+    peaks
+}
+
+/// Probability that white noise produces a peak at least this high among `independent` frequencies
+pub fn false_alarm_probability(power: f64, independent: usize) -> f64 {
+    let single = (-power).exp();
+    // 1 - (1 - p)^M, computed stably for small p
+    -((independent as f64) * (-single).ln_1p()).exp_m1()
+}
+
+/// Least-squares sinusoid at `period` fitted to linearly detrended prices.
+///
+/// Returns `(amplitude, phase)` with amplitude relative to the mean price and the
+/// cycle modelled as `amplitude * sin(2π t / period + phase)` on the series time axis.
+pub fn fit_sinusoid(series: &SampledSeries, period: f64) -> (f64, f64) {
+    let n = series.values.len() as f64;
+    let mean_price = series.values.iter().sum::<f64>() / n;
+    let residuals = detrend(&series.times, &series.values);
+    let omega = 2.0 * PI / period;
+
+    // Normal equations for residual ≈ a·sin(ωt) + b·cos(ωt)
+    let (mut ss, mut cc, mut sc, mut ys, mut yc) = (0.0, 0.0, 0.0, 0.0, 0.0);
+    for (t, y) in series.times.iter().zip(&residuals) {
+        let (s, c) = (omega * t).sin_cos();
+        ss += s * s;
+        cc += c * c;
+        sc += s * c;
+        ys += y * s;
+        yc += y * c;
+    }
+    let determinant = ss * cc - sc * sc;
+    if determinant.abs() < f64::EPSILON || mean_price <= 0.0 {
+        return (0.0, 0.0);
+    }
+    let a = (ys * cc - yc * sc) / determinant;
+    let b = (yc * ss - ys * sc) / determinant;
+    ((a * a + b * b).sqrt() / mean_price, b.atan2(a))
+}
+
+/// Sharpen a periodogram peak to sub-bin precision: the period within one frequency
+/// bin of `period` whose fitted sinusoid has the largest amplitude
+pub fn refine_period(series: &SampledSeries, period: f64) -> f64 {
+    const STEPS: i32 = 20;
+    let span = series.span();
+    if span <= 0.0 {
+        return period;
+    }
+    let frequency = 1.0 / period;
+    let bin = 1.0 / span;
+    (-STEPS..=STEPS)
+        .map(|step| frequency + bin * step as f64 / STEPS as f64)
+        .filter(|candidate| *candidate > 0.0)
+        .map(|candidate| (1.0 / candidate, fit_sinusoid(series, 1.0 / candidate).0))
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(refined, _)| refined)
+        .unwrap_or(period)
+}
+
+fn log_returns(series: &SampledSeries) -> (Vec<f64>, Vec<f64>) {
+    let mut times = Vec::with_capacity(series.values.len());
+    let mut returns = Vec::with_capacity(series.values.len());
+    for i in 1..series.values.len() {
+        let (previous, current) = (series.values[i - 1], series.values[i]);
+        if previous > 0.0 && current > 0.0 {
+            times.push(series.times[i]);
+            returns.push((current / previous).ln());
+        }
+    }
+    let mean = returns.iter().sum::<f64>() / returns.len().max(1) as f64;
+    returns.iter_mut().for_each(|r| *r -= mean);
+    (times, returns)
+}
+
+/// Least-squares line through `values` at `times`, evaluated at each time
+pub fn linear_trend(times: &[f64], values: &[f64]) -> Vec<f64> {
+    let n = times.len() as f64;
+    let mean_t = times.iter().sum::<f64>() / n;
+    let mean_v = values.iter().sum::<f64>() / n;
+    let covariance: f64 = times.iter().zip(values).map(|(t, v)| (t - mean_t) * (v - mean_v)).sum();
+    let variance: f64 = times.iter().map(|t| (t - mean_t).powi(2)).sum();
+    let slope = if variance > 0.0 { covariance / variance } else { 0.0 };
+    times.iter().map(|t| mean_v + slope * (t - mean_t)).collect()
+}
+
+fn detrend(times: &[f64], values: &[f64]) -> Vec<f64> {
+    values.iter().zip(linear_trend(times, values)).map(|(v, trend)| v - trend).collect()
+}
+
+/// Schuster periodogram at the Fourier frequencies of an evenly spaced, zero-mean series
+fn fft_periodogram(values: &[f64]) -> Vec<SpectralPoint> {
+    let n = values.len();
+    let variance = values.iter().map(|v| v * v).sum::<f64>() / n as f64;
+    if variance <= 0.0 {
+        return Vec::new();
+    }
+    // Truncate to a power of two so the bins stay at independent Fourier frequencies
+    let size = 1usize << (usize::BITS - 1 - n.leading_zeros());
+    let mut buffer: Vec<Complex64> = values[n - size..].iter().map(|v| Complex64::new(*v, 0.0)).collect();
+    fft(&mut buffer);
+
+    (1..=size / 2)
+        .map(|k| SpectralPoint {
+            frequency: k as f64 / size as f64,
+            power: buffer[k].norm_sqr() / (size as f64 * variance),
+        })
+        .collect()
+}
+
+/// In-place iterative radix-2 FFT; `buffer.len()` must be a power of two
+pub(crate) fn fft(buffer: &mut [Complex64]) {
+    let n = buffer.len();
+    let bits = n.trailing_zeros();
+    if bits == 0 {
+        return;
+    }
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            buffer.swap(i, j);
+        }
+    }
+    let mut length = 2;
+    while length <= n {
+        let step = Complex64::from_polar(1.0, -2.0 * PI / length as f64);
+        for chunk in buffer.chunks_mut(length) {
+            let mut twiddle = Complex64::new(1.0, 0.0);
+            let (lower, upper) = chunk.split_at_mut(length / 2);
+            for (a, b) in lower.iter_mut().zip(upper.iter_mut()) {
+                let t = *b * twiddle;
+                *b = *a - t;
+                *a += t;
+                twiddle *= step;
+            }
+        }
+        length <<= 1;
+    }
+}
+
+/// Scargle-normalized Lomb-Scargle periodogram of a zero-mean series
+fn lomb_scargle(times: &[f64], values: &[f64], min_frequency: f64, max_frequency: f64, span: f64) -> Vec<SpectralPoint> {
+    let variance = values.iter().map(|v| v * v).sum::<f64>() / (values.len() as f64 - 1.0);
+    if variance <= 0.0 || span <= 0.0 || max_frequency <= min_frequency {
+        return Vec::new();
+    }
+    let step = (1.0 / (LOMB_SCARGLE_OVERSAMPLING * span)).max((max_frequency - min_frequency) / MAX_FREQUENCIES as f64);
+    let count = ((max_frequency - min_frequency) / step).floor() as usize + 1;
+
+    (0..count)
+        .map(|i| {
+            let frequency = min_frequency + i as f64 * step;
+            let omega = 2.0 * PI * frequency;
+            let (sin2, cos2) = times.iter()
+                .fold((0.0, 0.0), |(s, c), t| (s + (2.0 * omega * t).sin(), c + (2.0 * omega * t).cos()));
+            let tau = sin2.atan2(cos2) / (2.0 * omega);
+
+            let (mut yc, mut ys, mut cc, mut ss) = (0.0, 0.0, 0.0, 0.0);
+            for (t, y) in times.iter().zip(values) {
+                let (s, c) = (omega * (t - tau)).sin_cos();
+                yc += y * c;
+                ys += y * s;
+                cc += c * c;
+                ss += s * s;
+            }
+            let power = if cc > 0.0 && ss > 0.0 { (yc * yc / cc + ys * ys / ss) / (2.0 * variance) } else { 0.0 };
+            SpectralPoint { frequency, power }
+        })
+        .collect()
+}
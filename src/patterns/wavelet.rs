@@ -0,0 +1,358 @@
+//! # Wavelet Cycles
+//!
+//! Morlet wavelet power per period and bar of the log returns, normalized so
+//! white noise has mean power 1. Its ridges follow cycles whose period drifts or
+//! that only run for part of the history, which a Fourier peak smears out.
+
+use chrono::{DateTime, Utc};
+use num_complex::Complex64;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+use super::spectral::{self, SampledSeries};
+
+/// Wavelet analysis settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WaveletConfig {
+    /// Morlet centre frequency; 6 balances time and period resolution
+    pub omega0: f64,
+    /// Scales per doubling of the period
+    pub voices_per_octave: usize,
+    /// Normalized power a ridge point needs (3.0 is the 95% level for white noise)
+    pub min_power: f64,
+    /// Shortest ridge kept, in periods of its own mean length
+    pub min_ridge_cycles: f64,
+    /// Relative period range beyond which a ridge counts as drifting
+    pub drift_tolerance: f64,
+    /// Share of the bars a steady ridge must span to be left to the Fourier search
+    pub stationary_coverage: f64,
+    /// Most cycles reported from ridges
+    pub max_ridges: usize,
+    /// Points kept in each cycle's period track
+    pub track_points: usize,
+}
+
+impl Default for WaveletConfig {
+    fn default() -> Self {
+        Self {
+            omega0: 6.0,
+            voices_per_octave: 8,
+            min_power: 3.0,
+            min_ridge_cycles: 2.0,
+            drift_tolerance: 0.1,
+            stationary_coverage: 0.8,
+            max_ridges: 5,
+            track_points: 50,
+        }
+    }
+}
+
+/// Normalized wavelet power per period and bar
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WaveletPowerMap {
+    pub timestamps: Vec<DateTime<Utc>>,
+    /// Fourier period of each scale in bars, shortest first
+    pub periods: Vec<f64>,
+    /// `power[scale][bar]`
+    pub power: Vec<Vec<f64>>,
+    /// Longest period unaffected by the edges at each bar
+    pub cone_of_influence: Vec<f64>,
+}
+
+impl WaveletPowerMap {
+    /// Whether `power[scale][bar]` is clear of edge effects
+    pub fn is_reliable(&self, scale: usize, bar: usize) -> bool {
+        self.periods[scale] <= self.cone_of_influence[bar]
+    }
+
+    /// Power averaged over the reliable bars of each scale (the global wavelet spectrum)
+    pub fn global_spectrum(&self) -> Vec<f64> {
+        (0..self.periods.len())
+            .map(|scale| {
+                let reliable: Vec<f64> = (0..self.timestamps.len())
+                    .filter(|&bar| self.is_reliable(scale, bar))
+                    .map(|bar| self.power[scale][bar])
+                    .collect();
+                reliable.iter().sum::<f64>() / reliable.len().max(1) as f64
+            })
+            .collect()
+    }
+}
+
+/// A cycle's period over time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeriodPoint {
+    pub timestamp: DateTime<Utc>,
+    /// Period in bars
+    pub period: f64,
+    /// Normalized wavelet power
+    pub power: f64,
+}
+
+/// Period-vs-time track of a non-stationary cycle, oldest first
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeriodTrack {
+    pub points: Vec<PeriodPoint>,
+}
+
+impl PeriodTrack {
+    pub fn start(&self) -> Option<DateTime<Utc>> {
+        self.points.first().map(|point| point.timestamp)
+    }
+
+    pub fn end(&self) -> Option<DateTime<Utc>> {
+        self.points.last().map(|point| point.timestamp)
+    }
+
+    pub fn min_period(&self) -> f64 {
+        self.points.iter().map(|point| point.period).fold(f64::INFINITY, f64::min)
+    }
+
+    pub fn max_period(&self) -> f64 {
+        self.points.iter().map(|point| point.period).fold(0.0, f64::max)
+    }
+
+    /// Relative period range, `max / min - 1`
+    pub fn drift(&self) -> f64 {
+        if self.points.is_empty() { 0.0 } else { self.max_period() / self.min_period() - 1.0 }
+    }
+
+    /// Period of the point nearest `at`
+    pub fn period_at(&self, at: DateTime<Utc>) -> Option<f64> {
+        self.points.iter()
+            .min_by_key(|point| (point.timestamp - at).num_seconds().abs())
+            .map(|point| point.period)
+    }
+}
+
+/// A ridge of the power map: the scale of a local power maximum at each bar it spans
+#[derive(Debug, Clone)]
+pub struct Ridge {
+    /// First bar of the ridge
+    pub start: usize,
+    /// Scale index at each bar from `start`
+    pub scales: Vec<usize>,
+    pub mean_power: f64,
+    pub mean_period: f64,
+}
+
+impl Ridge {
+    pub fn end(&self) -> usize {
+        self.start + self.scales.len() - 1
+    }
+
+    pub fn len(&self) -> usize {
+        self.scales.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scales.is_empty()
+    }
+}
+
+/// A cycle found along a ridge
+#[derive(Debug, Clone)]
+pub struct RidgeCycle {
+    pub ridge: Ridge,
+    pub track: PeriodTrack,
+    /// Relative amplitude (see [`HiddenCycle`](super::HiddenCycle)), averaged over
+    /// windows of two local periods along the ridge
+    pub amplitude: f64,
+    /// Phase at the rounded mean period, fitted over the ridge's last window so
+    /// the cycle lines up with its most recent bars
+    pub phase: f64,
+    /// One minus the white-noise false-alarm probability of the ridge's mean power
+    pub confidence: f64,
+    /// Share of the bars the ridge spans
+    pub coverage: f64,
+}
+
+impl RidgeCycle {
+    /// Whether the cycle drifts in period or only runs for part of the history
+    pub fn is_non_stationary(&self, config: &WaveletConfig) -> bool {
+        self.track.drift() > config.drift_tolerance || self.coverage < config.stationary_coverage
+    }
+}
+
+/// Ratio of the Fourier period to the Morlet scale
+fn fourier_factor(omega0: f64) -> f64 {
+    4.0 * PI / (omega0 + (2.0 + omega0 * omega0).sqrt())
+}
+
+/// Morlet transform of the log returns of `series` at periods from `min_period`
+/// to `max_period` bars; `None` when the series is too short
+pub fn continuous_wavelet_transform(series: &SampledSeries, min_period: f64, max_period: f64, config: &WaveletConfig) -> Option<WaveletPowerMap> {
+    let returns: Vec<f64> = series.values.windows(2)
+        .map(|w| if w[0] > 0.0 && w[1] > 0.0 { (w[1] / w[0]).ln() } else { 0.0 })
+        .collect();
+    let n = returns.len();
+    if n < 8 || max_period <= min_period || config.voices_per_octave == 0 {
+        return None;
+    }
+    let mean = returns.iter().sum::<f64>() / n as f64;
+    let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / n as f64;
+    if variance <= 0.0 {
+        return None;
+    }
+
+    // Zero-padded to a power of two at least twice the length, so the circular
+    // convolution does not wrap the ends into each other
+    let size = (2 * n).next_power_of_two();
+    let mut spectrum: Vec<Complex64> = returns.iter().map(|r| Complex64::new(r - mean, 0.0))
+        .chain(std::iter::repeat(Complex64::new(0.0, 0.0)))
+        .take(size)
+        .collect();
+    spectral::fft(&mut spectrum);
+
+    let factor = fourier_factor(config.omega0);
+    let octaves = (max_period / min_period).log2();
+    let count = (octaves * config.voices_per_octave as f64).floor() as usize + 1;
+    let periods: Vec<f64> = (0..count).map(|j| min_period * 2f64.powf(j as f64 / config.voices_per_octave as f64)).collect();
+
+    let power = periods.iter()
+        .map(|period| {
+            let scale = period / factor;
+            let norm = (2.0 * PI * scale).sqrt() * PI.powf(-0.25);
+            let mut product: Vec<Complex64> = spectrum.iter().enumerate()
+                .map(|(k, value)| {
+                    // Analytic wavelet: positive frequencies only
+                    if k == 0 || k > size / 2 {
+                        return Complex64::new(0.0, 0.0);
+                    }
+                    let omega = 2.0 * PI * k as f64 / size as f64;
+                    value * norm * (-(scale * omega - config.omega0).powi(2) / 2.0).exp()
+                })
+                .collect();
+            inverse_fft(&mut product);
+            product[..n].iter().map(|w| w.norm_sqr() / variance).collect()
+        })
+        .collect();
+
+    // Returns sit between bars; each is stamped with the later bar
+    let timestamps = series.times[1..].iter()
+        .map(|t| DateTime::from_timestamp((t * series.spacing_seconds).round() as i64, 0).unwrap_or_default())
+        .collect();
+    let cone_of_influence = (0..n)
+        .map(|bar| (bar.min(n - 1 - bar) + 1) as f64 * factor / 2f64.sqrt())
+        .collect();
+    Some(WaveletPowerMap { timestamps, periods, power, cone_of_influence })
+}
+
+fn inverse_fft(buffer: &mut [Complex64]) {
+    buffer.iter_mut().for_each(|value| *value = value.conj());
+    spectral::fft(buffer);
+    let size = buffer.len() as f64;
+    buffer.iter_mut().for_each(|value| *value = value.conj() / size);
+}
+
+/// Ridges of `map`: at every bar, the reliable local maxima over scale with at
+/// least [`WaveletConfig::min_power`], chained bar to bar while the scale moves
+/// by at most one step. Ridges shorter than [`WaveletConfig::min_ridge_cycles`]
+/// periods are dropped; the rest come strongest first.
+pub fn extract_ridges(map: &WaveletPowerMap, config: &WaveletConfig) -> Vec<Ridge> {
+    let scales = map.periods.len();
+    let mut open: Vec<Ridge> = Vec::new();
+    let mut closed: Vec<Ridge> = Vec::new();
+
+    for bar in 0..map.timestamps.len() {
+        let maxima: Vec<usize> = (0..scales)
+            .filter(|&scale| {
+                let power = map.power[scale][bar];
+                map.is_reliable(scale, bar)
+                    && power >= config.min_power
+                    && (scale == 0 || map.power[scale - 1][bar] < power)
+                    && (scale + 1 == scales || map.power[scale + 1][bar] <= power)
+            })
+            .collect();
+
+        let mut claimed = vec![false; maxima.len()];
+        let mut continuing = Vec::with_capacity(open.len());
+        for mut ridge in open.drain(..) {
+            let last = *ridge.scales.last().unwrap_or(&0);
+            let next = maxima.iter().enumerate()
+                .filter(|(i, &scale)| !claimed[*i] && scale.abs_diff(last) <= 1)
+                .min_by_key(|(_, &scale)| scale.abs_diff(last));
+            match next {
+                Some((i, &scale)) => {
+                    claimed[i] = true;
+                    ridge.scales.push(scale);
+                    continuing.push(ridge);
+                }
+                None => closed.push(ridge),
+            }
+        }
+        open = continuing;
+        open.extend(maxima.iter().zip(&claimed)
+            .filter(|(_, claimed)| !**claimed)
+            .map(|(&scale, _)| Ridge { start: bar, scales: vec![scale], mean_power: 0.0, mean_period: 0.0 }));
+    }
+    closed.extend(open);
+
+    let mut ridges: Vec<Ridge> = closed.into_iter()
+        .map(|mut ridge| {
+            let count = ridge.scales.len() as f64;
+            ridge.mean_power = ridge.scales.iter().enumerate().map(|(i, &scale)| map.power[scale][ridge.start + i]).sum::<f64>() / count;
+            ridge.mean_period = ridge.scales.iter().map(|&scale| map.periods[scale]).sum::<f64>() / count;
+            ridge
+        })
+        .filter(|ridge| ridge.scales.len() as f64 >= ridge.mean_period * config.min_ridge_cycles)
+        .collect();
+    ridges.sort_by(|a, b| b.mean_power.total_cmp(&a.mean_power));
+    ridges
+}
+
+/// Cycles along the ridges of `series`, strongest first, each with its period
+/// track, its amplitude and phase fitted over the bars it spans, and a confidence
+/// from the false-alarm probability of its mean power among `independent` periods
+pub fn ridge_cycles(series: &SampledSeries, map: &WaveletPowerMap, config: &WaveletConfig, independent: usize) -> Vec<RidgeCycle> {
+    let bars = map.timestamps.len().max(1);
+    extract_ridges(map, config).into_iter()
+        .map(|ridge| {
+            let step = (ridge.len() as f64 / config.track_points.max(2) as f64).ceil().max(1.0) as usize;
+            let mut points: Vec<PeriodPoint> = (0..ridge.len()).step_by(step)
+                .chain(std::iter::once(ridge.len() - 1))
+                .map(|i| PeriodPoint {
+                    timestamp: map.timestamps[ridge.start + i],
+                    period: map.periods[ridge.scales[i]],
+                    power: map.power[ridge.scales[i]][ridge.start + i],
+                })
+                .collect();
+            points.dedup_by_key(|point| point.timestamp);
+
+            // A drifting cycle loses coherence under one period, so the amplitude
+            // comes from short windows at their own local period
+            let mut windows = Vec::new();
+            let mut from = 0;
+            while from < ridge.len() {
+                let length = (2.0 * map.periods[ridge.scales[from]]).ceil() as usize;
+                let to = (from + length).min(ridge.len());
+                let local = ridge.scales[from..to].iter().map(|&scale| map.periods[scale]).sum::<f64>() / (to - from) as f64;
+                windows.push((ridge.start + from, ridge.start + to, local));
+                from = to;
+            }
+            // Returns start one bar into the series: ridge bars `from..to` cover prices `from..=to`
+            let segment = |from: usize, to: usize| SampledSeries {
+                times: series.times[from..=to].to_vec(),
+                values: series.values[from..=to].to_vec(),
+                regular: series.regular,
+                spacing_seconds: series.spacing_seconds,
+            };
+            let fits: Vec<f64> = windows.iter()
+                .filter(|(from, to, _)| to - from >= 4)
+                .map(|&(from, to, local)| spectral::fit_sinusoid(&segment(from, to), local).0)
+                .collect();
+            let amplitude = fits.iter().sum::<f64>() / fits.len().max(1) as f64;
+            let last = windows.last().map_or(ridge.start, |(from, _, _)| *from).min(ridge.end().saturating_sub(3));
+            let (_, phase) = spectral::fit_sinusoid(&segment(last, ridge.end() + 1), ridge.mean_period.round().max(1.0));
+            RidgeCycle {
+                confidence: 1.0 - spectral::false_alarm_probability(ridge.mean_power, independent),
+                coverage: ridge.len() as f64 / bars as f64,
+                track: PeriodTrack { points },
+                ridge,
+                amplitude,
+                phase,
+            }
+        })
+        .collect()
+}
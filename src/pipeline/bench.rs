@@ -0,0 +1,128 @@
+//! # Pipeline Benchmark
+//!
+//! Time every pipeline stage on a standard [benchmark dataset](crate::synthetic::benchmark)
+//! and check the cycles the recognizer finds against the ones planted in the data,
+//! so one run is both a performance baseline and a correctness oracle.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::future::Future;
+use std::time::Instant;
+
+use super::{PipelineBuilder, PipelineConfig};
+use crate::patterns::{HiddenCycle, PatternRecognizer};
+use crate::synthetic::benchmark::{BenchmarkDataset, BenchmarkSize};
+
+/// Relative period error within which a detected cycle recovers a planted one
+pub const PERIOD_TOLERANCE: f64 = 0.05;
+/// Share of the bars the anomaly detector is fitted on; it scans the rest
+const BASELINE_SHARE: f64 = 0.8;
+
+/// Wall-clock time of one stage
+#[derive(Debug, Clone, Serialize)]
+pub struct StageTiming {
+    pub stage: String,
+    pub seconds: f64,
+    /// Bars the stage consumed per second
+    pub bars_per_second: f64,
+}
+
+/// A planted cycle and the detected cycle closest to it in period, if within tolerance
+#[derive(Debug, Clone, Serialize)]
+pub struct CycleRecovery {
+    pub injected_period: u32,
+    pub detected_period: Option<u32>,
+    pub confidence: Option<f64>,
+}
+
+/// Timings and ground-truth check of one benchmark run
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchmarkRun {
+    pub size: BenchmarkSize,
+    pub seed: u64,
+    pub bars: usize,
+    pub stages: Vec<StageTiming>,
+    pub recoveries: Vec<CycleRecovery>,
+    pub symmetries: usize,
+    pub cycles: usize,
+    pub synthetic_points: usize,
+    pub anomalies: usize,
+}
+
+impl BenchmarkRun {
+    /// Whether every planted cycle was recovered
+    pub fn passed(&self) -> bool {
+        self.recoveries.iter().all(|r| r.detected_period.is_some())
+    }
+
+    pub fn total_seconds(&self) -> f64 {
+        self.stages.iter().map(|s| s.seconds).sum()
+    }
+}
+
+/// Run symmetry extraction, cycle detection, synthetic generation and anomaly
+/// detection on `dataset` with the stages configured by `config`
+pub async fn run_benchmark(dataset: &BenchmarkDataset, config: &PipelineConfig) -> Result<BenchmarkRun> {
+    let bars = &dataset.bars;
+    let mut config = config.clone();
+    // Synthetic bars are part of the timing, so they must not vary between runs either
+    config.synthetic.seed.get_or_insert(dataset.seed);
+    let builder = PipelineBuilder::new(config.clone());
+    let mut stages = Vec::new();
+
+    let mut engine = builder.build_engine().await?;
+    let symmetries = timed(&mut stages, "symmetry extraction", bars.len(), engine.extract_temporal_symmetries(bars)).await?;
+
+    let mut recognizer = PatternRecognizer::new(config.patterns.clone())?;
+    let cycles = timed(&mut stages, "cycle detection", bars.len(), recognizer.detect_cycles(bars)).await?;
+
+    let split = (bars.len() as f64 * BASELINE_SHARE) as usize;
+    let builder = builder
+        .with_history(bars[..split].to_vec())
+        .with_analysis(symmetries.clone(), cycles.clone());
+
+    let generator = builder.synthetic_generator()?;
+    let start = bars.last().map(|p| p.timestamp).unwrap_or_default();
+    let synthetic = timed(&mut stages, "synthetic generation", bars.len(), generator.generate_future_data(start, "BENCH")).await?;
+
+    let mut detector = builder.anomaly_detector()?;
+    let scanned = &bars[split..];
+    let anomalies = timed(&mut stages, "anomaly detection", scanned.len(), detector.detect_anomalies(scanned)).await?;
+
+    Ok(BenchmarkRun {
+        size: dataset.size,
+        seed: dataset.seed,
+        bars: bars.len(),
+        stages,
+        recoveries: dataset.cycles.iter().map(|c| recover(c.period, &cycles)).collect(),
+        symmetries: symmetries.len(),
+        cycles: cycles.len(),
+        synthetic_points: synthetic.len(),
+        anomalies: anomalies.len(),
+    })
+}
+
+/// Await `stage`, recording how long it took
+async fn timed<T>(stages: &mut Vec<StageTiming>, name: &str, bars: usize, stage: impl Future<Output = Result<T>>) -> Result<T> {
+    let started = Instant::now();
+    let result = stage.await?;
+    let seconds = started.elapsed().as_secs_f64();
+    stages.push(StageTiming {
+        stage: name.to_string(),
+        seconds,
+        bars_per_second: bars as f64 / seconds.max(1e-9),
+    });
+    Ok(result)
+}
+
+fn recover(period: u32, cycles: &[HiddenCycle]) -> CycleRecovery {
+    let tolerance = (period as f64 * PERIOD_TOLERANCE).max(1.0);
+    let closest = cycles.iter()
+        .filter(|c| (c.period as f64 - period as f64).abs() <= tolerance)
+        .min_by_key(|c| c.period.abs_diff(period));
+    CycleRecovery {
+        injected_period: period,
+        detected_period: closest.map(|c| c.period),
+        confidence: closest.map(|c| c.confidence),
+    }
+}
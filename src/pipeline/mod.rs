@@ -0,0 +1,152 @@
+//! # Pipeline Builder
+//!
+//! Assembles the engine, pattern recognizer, synthetic generator, anomaly
+//! detector and RL agent from a single configuration, building the generator and
+//! detector once the history's symmetries and cycles are known.
+
+pub mod bench;
+pub mod params;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::anomaly::{AnomalyDetectionConfig, SensitivityCalibration, TemporalAnomalyDetector};
+use crate::core::{EngineConfig, TimeSymmetricEngine};
+use crate::data::{DataConfig, ForexDataManager, ForexDataPoint};
+use crate::laplacian_rl::{LaplacianQLearningAgent, LaplacianQLearningConfig};
+use crate::patterns::{HiddenCycle, PatternConfig, PatternRecognizer};
+use crate::symmetry::TemporalSymmetry;
+use crate::synthetic::{SyntheticDataGenerator, SyntheticGenerationConfig};
+
+/// Settings of every pipeline stage
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PipelineConfig {
+    pub data: DataConfig,
+    pub engine: EngineConfig,
+    pub patterns: PatternConfig,
+    pub synthetic: SyntheticGenerationConfig,
+    pub anomaly: AnomalyDetectionConfig,
+    pub rl: LaplacianQLearningConfig,
+}
+
+impl PipelineConfig {
+    /// Load pipeline settings from a TOML file; missing sections keep their defaults
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let config_str = std::fs::read_to_string(path)?;
+        Ok(toml::from_str(&config_str)?)
+    }
+}
+
+/// An initialized analysis/trading pipeline
+pub struct Pipeline {
+    pub engine: TimeSymmetricEngine,
+    pub data_manager: ForexDataManager,
+    pub pattern_recognizer: PatternRecognizer,
+    pub synthetic_generator: SyntheticDataGenerator,
+    pub anomaly_detector: TemporalAnomalyDetector,
+    pub rl_agent: LaplacianQLearningAgent,
+    pub history: Vec<ForexDataPoint>,
+    pub symmetries: Vec<TemporalSymmetry>,
+    pub cycles: Vec<HiddenCycle>,
+}
+
+/// Assembles a [`Pipeline`] from one [`PipelineConfig`]
+#[derive(Debug, Clone)]
+pub struct PipelineBuilder {
+    config: PipelineConfig,
+    history: Vec<ForexDataPoint>,
+    analysis: Option<(Vec<TemporalSymmetry>, Vec<HiddenCycle>)>,
+    calibration: Option<SensitivityCalibration>,
+}
+
+impl PipelineBuilder {
+    pub fn new(config: PipelineConfig) -> Self {
+        Self { config, history: Vec::new(), analysis: None, calibration: None }
+    }
+
+    /// Builder for the pipeline settings in a TOML file
+    pub fn from_file(path: &Path) -> Result<Self> {
+        Ok(Self::new(PipelineConfig::from_file(path)?))
+    }
+
+    /// History the symmetries and cycles are extracted from and the detector baselines fitted on
+    pub fn with_history(mut self, history: Vec<ForexDataPoint>) -> Self {
+        self.history = history;
+        self
+    }
+
+    /// Use known symmetries and cycles instead of extracting them from the history
+    pub fn with_analysis(mut self, symmetries: Vec<TemporalSymmetry>, cycles: Vec<HiddenCycle>) -> Self {
+        self.analysis = Some((symmetries, cycles));
+        self
+    }
+
+    /// Keep a saved sensitivity calibration instead of calibrating the detector anew
+    pub fn with_calibration(mut self, calibration: Option<SensitivityCalibration>) -> Self {
+        self.calibration = calibration;
+        self
+    }
+
+    pub fn config(&self) -> &PipelineConfig {
+        &self.config
+    }
+
+    /// Generator on the history and the given analysis
+    pub fn synthetic_generator(&self) -> Result<SyntheticDataGenerator> {
+        let (symmetries, cycles) = self.analysis.clone().unwrap_or_default();
+        SyntheticDataGenerator::new(symmetries, cycles, self.history.clone(), self.config.synthetic.clone())
+    }
+
+    /// Detector on the history and the given analysis, with the given calibration if any;
+    /// without a history there is nothing to calibrate on and the configured sensitivity is kept
+    pub fn anomaly_detector(&self) -> Result<TemporalAnomalyDetector> {
+        let (symmetries, cycles) = self.analysis.clone().unwrap_or_default();
+        let mut config = self.config.anomaly.clone();
+        if self.history.is_empty() {
+            config.target_anomalies_per_day = None;
+        }
+        let detector = TemporalAnomalyDetector::new(symmetries, cycles, &self.history, config)?;
+        Ok(match &self.calibration {
+            Some(calibration) => detector.with_calibration(calibration.clone()),
+            None => detector,
+        })
+    }
+
+    /// Initialized engine with the configured settings
+    pub async fn build_engine(&self) -> Result<TimeSymmetricEngine> {
+        let mut engine = TimeSymmetricEngine::new(self.config.engine.clone())?;
+        engine.initialize().await?;
+        Ok(engine)
+    }
+
+    /// Initialize the engine, analyze the history unless the analysis was given,
+    /// and build every stage on the result
+    pub async fn build(mut self) -> Result<Pipeline> {
+        let mut engine = self.build_engine().await?;
+        let mut pattern_recognizer = PatternRecognizer::new(self.config.patterns.clone())?;
+
+        if self.analysis.is_none() && !self.history.is_empty() {
+            let symmetries = engine.extract_temporal_symmetries(&self.history).await?;
+            let cycles = pattern_recognizer.detect_cycles(&self.history).await?;
+            println!("🔧 Pipeline analyzed {} bars: {} symmetries, {} cycles", self.history.len(), symmetries.len(), cycles.len());
+            self.analysis = Some((symmetries, cycles));
+        }
+
+        let synthetic_generator = self.synthetic_generator()?;
+        let anomaly_detector = self.anomaly_detector()?;
+        let (symmetries, cycles) = self.analysis.unwrap_or_default();
+        Ok(Pipeline {
+            engine,
+            data_manager: ForexDataManager::new(self.config.data)?,
+            pattern_recognizer,
+            synthetic_generator,
+            anomaly_detector,
+            rl_agent: LaplacianQLearningAgent::new(self.config.rl)?,
+            history: self.history,
+            symmetries,
+            cycles,
+        })
+    }
+}
@@ -0,0 +1,265 @@
+//! # Live Analysis Parameters
+//!
+//! Analysis thresholds changed while pairs run: a [`ParamsHandle`] validates and
+//! logs each change and publishes it with a new epoch, which pairs apply at their
+//! next update.
+
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tokio::sync::watch;
+
+use super::PipelineConfig;
+use crate::patterns::HiddenCycle;
+use crate::symmetry::TemporalSymmetry;
+
+/// Epochs kept for [`ParamsHandle::epochs_since`]
+const EPOCH_HISTORY: usize = 100;
+
+/// How the anomaly detector's sensitivity threshold is set
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Sensitivity {
+    /// Calibrated to the pair's target anomaly rate
+    Calibrated,
+    /// Fixed by the operator; recalibration is off
+    Fixed(f64),
+}
+
+impl std::fmt::Display for Sensitivity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Sensitivity::Calibrated => write!(f, "calibrated"),
+            Sensitivity::Fixed(threshold) => write!(f, "{:.3}", threshold),
+        }
+    }
+}
+
+/// Analysis parameters that can change at runtime
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnalysisParams {
+    /// Bumped by every change
+    pub epoch: u64,
+    pub anomaly_sensitivity: Sensitivity,
+    /// Least confidence of a hidden cycle the detectors and scorer use
+    pub cycle_confidence_threshold: f64,
+    /// Least strength of a temporal symmetry the detectors and scorer use
+    pub symmetry_strength_threshold: f64,
+}
+
+impl AnalysisParams {
+    /// Parameters as `config` sets them, at epoch 0
+    pub fn from_config(config: &PipelineConfig) -> Self {
+        Self {
+            epoch: 0,
+            anomaly_sensitivity: match config.anomaly.target_anomalies_per_day {
+                Some(_) => Sensitivity::Calibrated,
+                None => Sensitivity::Fixed(config.anomaly.sensitivity_threshold),
+            },
+            cycle_confidence_threshold: config.patterns.confidence_threshold,
+            symmetry_strength_threshold: config.engine.min_symmetry_strength,
+        }
+    }
+
+    /// Write the parameters into the stage settings they govern. The pattern
+    /// recognizer and engine extract with the thresholds and the generator
+    /// filters by them, so they also take effect at the next analysis.
+    pub fn apply_to(&self, config: &mut PipelineConfig) {
+        if let Sensitivity::Fixed(threshold) = self.anomaly_sensitivity {
+            config.anomaly.sensitivity_threshold = threshold;
+            config.anomaly.target_anomalies_per_day = None;
+        }
+        config.patterns.confidence_threshold = self.cycle_confidence_threshold;
+        config.synthetic.cycle_confidence_threshold = self.cycle_confidence_threshold;
+        config.engine.min_symmetry_strength = self.symmetry_strength_threshold;
+        config.synthetic.symmetry_strength_threshold = self.symmetry_strength_threshold;
+    }
+
+    /// Whether a symmetry is strong enough to be used
+    pub fn admits_symmetry(&self, symmetry: &TemporalSymmetry) -> bool {
+        symmetry.strength >= self.symmetry_strength_threshold
+    }
+
+    /// Whether a cycle is confident enough to be used
+    pub fn admits_cycle(&self, cycle: &HiddenCycle) -> bool {
+        cycle.confidence >= self.cycle_confidence_threshold
+    }
+
+    /// Fields of `self` that differ from `other`, as `other → self` changes
+    pub fn changes_from(&self, other: &AnalysisParams) -> Vec<ParamChange> {
+        let mut changes = Vec::new();
+        if self.anomaly_sensitivity != other.anomaly_sensitivity {
+            changes.push(ParamChange::new("anomaly_sensitivity", other.anomaly_sensitivity, self.anomaly_sensitivity));
+        }
+        if self.cycle_confidence_threshold != other.cycle_confidence_threshold {
+            changes.push(ParamChange::new("cycle_confidence_threshold", other.cycle_confidence_threshold, self.cycle_confidence_threshold));
+        }
+        if self.symmetry_strength_threshold != other.symmetry_strength_threshold {
+            changes.push(ParamChange::new("symmetry_strength_threshold", other.symmetry_strength_threshold, self.symmetry_strength_threshold));
+        }
+        changes
+    }
+}
+
+impl Default for AnalysisParams {
+    fn default() -> Self {
+        Self::from_config(&PipelineConfig::default())
+    }
+}
+
+/// Requested parameter changes; `None` leaves a parameter as it is
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ParamsUpdate {
+    pub anomaly_sensitivity: Option<Sensitivity>,
+    pub cycle_confidence_threshold: Option<f64>,
+    pub symmetry_strength_threshold: Option<f64>,
+}
+
+impl ParamsUpdate {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Refuse values the stages cannot work with
+    pub fn validate(&self) -> Result<()> {
+        if let Some(Sensitivity::Fixed(threshold)) = self.anomaly_sensitivity {
+            if !(threshold > 0.0 && threshold <= 10.0) {
+                bail!("anomaly sensitivity must be in (0, 10], got {}", threshold);
+            }
+        }
+        for (name, value) in [
+            ("cycle confidence threshold", self.cycle_confidence_threshold),
+            ("symmetry strength threshold", self.symmetry_strength_threshold),
+        ] {
+            if let Some(value) = value {
+                if !(0.0..=1.0).contains(&value) {
+                    bail!("{} must be in [0, 1], got {}", name, value);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for ParamsUpdate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut fields = Vec::new();
+        if let Some(sensitivity) = self.anomaly_sensitivity {
+            fields.push(format!("--sensitivity {}", match sensitivity {
+                Sensitivity::Calibrated => "auto".to_string(),
+                Sensitivity::Fixed(threshold) => threshold.to_string(),
+            }));
+        }
+        if let Some(threshold) = self.cycle_confidence_threshold {
+            fields.push(format!("--cycle-confidence {}", threshold));
+        }
+        if let Some(threshold) = self.symmetry_strength_threshold {
+            fields.push(format!("--symmetry-strength {}", threshold));
+        }
+        write!(f, "{}", fields.join(" "))
+    }
+}
+
+/// One parameter's old and new value
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParamChange {
+    pub parameter: String,
+    pub old: String,
+    pub new: String,
+}
+
+impl ParamChange {
+    fn new(parameter: &str, old: impl std::fmt::Display, new: impl std::fmt::Display) -> Self {
+        Self { parameter: parameter.to_string(), old: old.to_string(), new: new.to_string() }
+    }
+}
+
+impl std::fmt::Display for ParamChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} → {}", self.parameter, self.old, self.new)
+    }
+}
+
+/// Marker of a parameter change, for logs and result streams
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParamsEpoch {
+    pub epoch: u64,
+    pub changed_at: DateTime<Utc>,
+    /// Who made the change (api, cli, ...)
+    pub source: String,
+    pub changes: Vec<ParamChange>,
+}
+
+/// Publishes parameter changes to every subscribed pair
+pub struct ParamsHandle {
+    sender: watch::Sender<AnalysisParams>,
+    initial: AnalysisParams,
+    epochs: Mutex<Vec<ParamsEpoch>>,
+}
+
+impl ParamsHandle {
+    pub fn new(initial: AnalysisParams) -> Self {
+        Self { sender: watch::channel(initial.clone()).0, initial, epochs: Mutex::new(Vec::new()) }
+    }
+
+    /// Parameters at epoch 0; a pair joining later applies every change made since
+    pub fn initial(&self) -> &AnalysisParams {
+        &self.initial
+    }
+
+    /// Parameters in force
+    pub fn current(&self) -> AnalysisParams {
+        self.sender.borrow().clone()
+    }
+
+    /// Receiver of the parameters, marked changed so its first check applies
+    /// any changes made since [`Self::initial`]
+    pub fn subscribe(&self) -> watch::Receiver<AnalysisParams> {
+        let mut receiver = self.sender.subscribe();
+        receiver.mark_changed();
+        receiver
+    }
+
+    /// Apply `update` and publish it under the next epoch; an update that changes
+    /// nothing returns the current epoch with no changes and publishes nothing
+    pub fn update(&self, update: &ParamsUpdate, source: &str) -> Result<ParamsEpoch> {
+        update.validate()?;
+        let previous = self.current();
+        let mut next = AnalysisParams {
+            anomaly_sensitivity: update.anomaly_sensitivity.unwrap_or(previous.anomaly_sensitivity),
+            cycle_confidence_threshold: update.cycle_confidence_threshold.unwrap_or(previous.cycle_confidence_threshold),
+            symmetry_strength_threshold: update.symmetry_strength_threshold.unwrap_or(previous.symmetry_strength_threshold),
+            ..previous.clone()
+        };
+        let changes = next.changes_from(&previous);
+        let now = Utc::now();
+        if changes.is_empty() {
+            return Ok(ParamsEpoch { epoch: previous.epoch, changed_at: now, source: source.to_string(), changes });
+        }
+
+        next.epoch = previous.epoch + 1;
+        let epoch = ParamsEpoch { epoch: next.epoch, changed_at: now, source: source.to_string(), changes };
+        println!("⚙️  Analysis parameters epoch {} ({}): {}", epoch.epoch, source,
+                 epoch.changes.iter().map(|change| change.to_string()).collect::<Vec<_>>().join(", "));
+        // No subscribers is not an error: pairs subscribing later catch up from `initial`
+        self.sender.send_replace(next);
+        let mut epochs = self.epochs.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        epochs.push(epoch.clone());
+        let excess = epochs.len().saturating_sub(EPOCH_HISTORY);
+        epochs.drain(..excess);
+        Ok(epoch)
+    }
+
+    /// Recorded epochs after `epoch`, oldest first
+    pub fn epochs_since(&self, epoch: u64) -> Vec<ParamsEpoch> {
+        let epochs = self.epochs.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        epochs.iter().filter(|recorded| recorded.epoch > epoch).cloned().collect()
+    }
+}
+
+impl Default for ParamsHandle {
+    fn default() -> Self {
+        Self::new(AnalysisParams::default())
+    }
+}
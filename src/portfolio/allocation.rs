@@ -0,0 +1,282 @@
+//! # Risk Allocation
+//!
+//! Per-pair risk budgets, by risk parity or mean-variance, from how well each
+//! pair's symmetries hold out of sample, discounted by volatility and correlation
+//! with the other pairs.
+
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
+use nalgebra::{DMatrix, DVector};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+use crate::data::ForexDataPoint;
+use crate::symmetry::{SymmetryDetector, SymmetryDetectorConfig, MIRROR};
+
+/// Fewest aligned returns a correlation is estimated from; below it pairs are
+/// taken as uncorrelated
+const MIN_ALIGNED_RETURNS: usize = 20;
+
+/// How budgets are derived from scores, volatilities and correlations
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AllocationMethod {
+    RiskParity,
+    MeanVariance,
+}
+
+/// Risk allocation configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AllocationConfig {
+    pub method: AllocationMethod,
+    /// Newest bars whose returns give volatilities and correlations
+    pub lookback_bars: usize,
+    /// Share of each pair's history held out to validate its symmetries
+    pub holdout_fraction: f64,
+    /// Largest budget of a single pair, as a share of the total
+    pub max_weight: f64,
+    /// Pull of the correlation matrix toward the identity for mean-variance
+    pub correlation_shrinkage: f64,
+    /// Minutes between scheduled recomputations
+    pub rebalance_minutes: u64,
+}
+
+impl Default for AllocationConfig {
+    fn default() -> Self {
+        Self {
+            method: AllocationMethod::RiskParity,
+            lookback_bars: 250,
+            holdout_fraction: 0.3,
+            max_weight: 0.5,
+            correlation_shrinkage: 0.1,
+            rebalance_minutes: 60,
+        }
+    }
+}
+
+/// Budget of one pair
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairAllocation {
+    pub symbol: String,
+    /// Mean out-of-sample strength of the pair's symmetries, in [0, 1]
+    pub validation_score: f64,
+    /// Standard deviation of log returns per bar
+    pub volatility: f64,
+    /// Mean |correlation| of the pair's returns with the other pairs'
+    pub mean_abs_correlation: f64,
+    /// Share of the total risk budget
+    pub weight: f64,
+    /// Multiplier on units per action size: weight × number of pairs
+    pub size_scale: f64,
+}
+
+/// Risk budgets of every allocated pair
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Allocation {
+    pub method: AllocationMethod,
+    pub computed_at: DateTime<Utc>,
+    pub pairs: Vec<PairAllocation>,
+}
+
+impl Allocation {
+    pub fn pair(&self, symbol: &str) -> Option<&PairAllocation> {
+        self.pairs.iter().find(|pair| pair.symbol == symbol)
+    }
+
+    /// Multiplier on `symbol`'s units per action size; 1 for pairs not allocated
+    pub fn size_scale(&self, symbol: &str) -> f64 {
+        self.pair(symbol).map(|pair| pair.size_scale).unwrap_or(1.0)
+    }
+}
+
+/// Sizes per-pair risk budgets from symmetry quality and return correlation
+pub struct RiskAllocator {
+    config: AllocationConfig,
+    symmetry_detector: SymmetryDetector,
+}
+
+impl RiskAllocator {
+    pub fn new(config: AllocationConfig) -> Result<Self> {
+        if config.lookback_bars < 2 {
+            bail!("lookback_bars must be at least 2, got {}", config.lookback_bars);
+        }
+        if !(config.holdout_fraction > 0.0 && config.holdout_fraction < 1.0) {
+            bail!("holdout_fraction must be in (0, 1), got {}", config.holdout_fraction);
+        }
+        if !(config.max_weight > 0.0 && config.max_weight <= 1.0) {
+            bail!("max_weight must be in (0, 1], got {}", config.max_weight);
+        }
+        if !(0.0..=1.0).contains(&config.correlation_shrinkage) {
+            bail!("correlation_shrinkage must be in [0, 1], got {}", config.correlation_shrinkage);
+        }
+        Ok(Self { config, symmetry_detector: SymmetryDetector::new(SymmetryDetectorConfig::default())? })
+    }
+
+    pub fn config(&self) -> &AllocationConfig {
+        &self.config
+    }
+
+    /// Mean strength, on the held-out end of `data`, of the symmetries detected
+    /// before it. Mirrors are left out: they mark a turn that has already
+    /// completed rather than a structure that should persist.
+    pub fn out_of_sample_score(&self, data: &[ForexDataPoint]) -> f64 {
+        let split = ((data.len() as f64) * (1.0 - self.config.holdout_fraction)).round() as usize;
+        let (in_sample, holdout) = data.split_at(split.min(data.len()));
+        let strengths: Vec<f64> = self.symmetry_detector.detect(in_sample)
+            .iter()
+            .filter(|symmetry| symmetry.symmetry_type != MIRROR)
+            .filter_map(|symmetry| self.symmetry_detector.measure(symmetry, holdout))
+            .collect();
+        if strengths.is_empty() {
+            return 0.0;
+        }
+        strengths.iter().sum::<f64>() / strengths.len() as f64
+    }
+
+    /// Budgets for `histories` (symbol, bars oldest first); pairs with fewer than
+    /// two bars are left out
+    pub fn allocate(&self, histories: &[(String, &[ForexDataPoint])]) -> Result<Allocation> {
+        let histories: Vec<&(String, &[ForexDataPoint])> = histories.iter().filter(|(_, data)| data.len() >= 2).collect();
+        let n = histories.len();
+        let scores: Vec<f64> = histories.iter().map(|(_, data)| self.out_of_sample_score(data)).collect();
+        let volatilities: Vec<f64> = histories.iter()
+            .map(|(_, data)| standard_deviation(&log_returns(&data[data.len().saturating_sub(self.config.lookback_bars + 1)..])))
+            .collect();
+        let correlations = self.correlation_matrix(&histories);
+
+        let raw: Vec<f64> = match self.config.method {
+            AllocationMethod::RiskParity => (0..n)
+                .map(|i| {
+                    let crowding = 1.0 + (0..n).filter(|&j| j != i).map(|j| correlations[(i, j)].abs()).sum::<f64>();
+                    if volatilities[i] > 0.0 { scores[i] / (volatilities[i] * crowding) } else { 0.0 }
+                })
+                .collect(),
+            AllocationMethod::MeanVariance => {
+                // Σ⁻¹μ with Σ = DρD and μ = Dq reduces to D⁻¹ρ⁻¹q
+                let shrinkage = self.config.correlation_shrinkage;
+                let shrunk = &correlations * (1.0 - shrinkage) + DMatrix::identity(n, n) * shrinkage;
+                let solved = shrunk.lu().solve(&DVector::from_column_slice(&scores))
+                    .ok_or_else(|| anyhow::anyhow!("correlation matrix is singular; raise correlation_shrinkage"))?;
+                (0..n)
+                    .map(|i| if volatilities[i] > 0.0 { solved[i].max(0.0) / volatilities[i] } else { 0.0 })
+                    .collect()
+            }
+        };
+        let weights = capped_weights(&raw, self.config.max_weight);
+
+        let pairs = histories.iter()
+            .enumerate()
+            .map(|(i, (symbol, _))| PairAllocation {
+                symbol: symbol.clone(),
+                validation_score: scores[i],
+                volatility: volatilities[i],
+                mean_abs_correlation: if n > 1 {
+                    (0..n).filter(|&j| j != i).map(|j| correlations[(i, j)].abs()).sum::<f64>() / (n - 1) as f64
+                } else {
+                    0.0
+                },
+                weight: weights[i],
+                size_scale: weights[i] * n as f64,
+            })
+            .collect();
+        Ok(Allocation { method: self.config.method, computed_at: Utc::now(), pairs })
+    }
+
+    /// Correlations of log returns over the newest `lookback_bars` timestamps all
+    /// pairs share; the identity when they share too few
+    fn correlation_matrix(&self, histories: &[&(String, &[ForexDataPoint])]) -> DMatrix<f64> {
+        let n = histories.len();
+        let mut matrix = DMatrix::identity(n, n);
+        let Some(((_, first), rest)) = histories.split_first() else {
+            return matrix;
+        };
+        let mut common: BTreeSet<DateTime<Utc>> = first.iter().map(|bar| bar.timestamp).collect();
+        for (_, data) in rest {
+            let timestamps: BTreeSet<DateTime<Utc>> = data.iter().map(|bar| bar.timestamp).collect();
+            common.retain(|timestamp| timestamps.contains(timestamp));
+        }
+        let common: Vec<DateTime<Utc>> = common.into_iter().collect();
+        let common = &common[common.len().saturating_sub(self.config.lookback_bars + 1)..];
+        if common.len() < MIN_ALIGNED_RETURNS + 1 {
+            return matrix;
+        }
+
+        let returns: Vec<Vec<f64>> = histories.iter()
+            .map(|(_, data)| {
+                let closes: HashMap<DateTime<Utc>, f64> = data.iter().map(|bar| (bar.timestamp, bar.close)).collect();
+                common.windows(2).map(|pair| (closes[&pair[1]] / closes[&pair[0]]).ln()).collect()
+            })
+            .collect();
+        for i in 0..n {
+            for j in i + 1..n {
+                let correlation = pearson(&returns[i], &returns[j]);
+                matrix[(i, j)] = correlation;
+                matrix[(j, i)] = correlation;
+            }
+        }
+        matrix
+    }
+}
+
+/// Normalize `raw` to sum to 1 with no weight above `max_weight` (or 1/n when that
+/// is larger), handing any excess to the uncapped pairs in proportion; excess no
+/// pair can take stays unallocated. All-zero input gives equal weights.
+fn capped_weights(raw: &[f64], max_weight: f64) -> Vec<f64> {
+    let n = raw.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let raw: Vec<f64> = if raw.iter().sum::<f64>() > 0.0 { raw.to_vec() } else { vec![1.0; n] };
+    let cap = max_weight.max(1.0 / n as f64);
+    let mut weights = vec![0.0; n];
+    let mut capped = vec![false; n];
+    loop {
+        let remaining = 1.0 - capped.iter().filter(|c| **c).count() as f64 * cap;
+        let free: f64 = (0..n).filter(|&i| !capped[i]).map(|i| raw[i]).sum();
+        let mut newly_capped = false;
+        for i in 0..n {
+            if capped[i] {
+                continue;
+            }
+            weights[i] = if free > 0.0 { remaining * raw[i] / free } else { 0.0 };
+            if weights[i] > cap + 1e-12 {
+                weights[i] = cap;
+                capped[i] = true;
+                newly_capped = true;
+            }
+        }
+        if !newly_capped {
+            return weights;
+        }
+    }
+}
+
+fn log_returns(data: &[ForexDataPoint]) -> Vec<f64> {
+    data.windows(2)
+        .filter(|pair| pair[0].close > 0.0 && pair[1].close > 0.0)
+        .map(|pair| (pair[1].close / pair[0].close).ln())
+        .collect()
+}
+
+fn standard_deviation(values: &[f64]) -> f64 {
+    if values.len() < 2 {
+        return 0.0;
+    }
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64).sqrt()
+}
+
+fn pearson(a: &[f64], b: &[f64]) -> f64 {
+    let n = a.len().min(b.len()) as f64;
+    let (mean_a, mean_b) = (a.iter().sum::<f64>() / n, b.iter().sum::<f64>() / n);
+    let (mut covariance, mut var_a, mut var_b) = (0.0, 0.0, 0.0);
+    for (x, y) in a.iter().zip(b) {
+        covariance += (x - mean_a) * (y - mean_b);
+        var_a += (x - mean_a).powi(2);
+        var_b += (y - mean_b).powi(2);
+    }
+    if var_a <= 0.0 || var_b <= 0.0 {
+        return 0.0;
+    }
+    covariance / (var_a * var_b).sqrt()
+}
@@ -0,0 +1,302 @@
+//! # Execution Quality
+//!
+//! Post-trade slippage, latency and spread of live and paper fills against the
+//! decisions behind them, per pair, session and broker, used to calibrate the
+//! backtester.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::backtest::BacktestConfig;
+use crate::sessions::TradingSessions;
+use crate::units::pip_size;
+
+/// Records kept in memory
+const MAX_IN_MEMORY_RECORDS: usize = 1000;
+
+/// Broker name of fills when none is configured
+pub const PAPER_BROKER: &str = "paper";
+
+/// Price and spread a pair decided its actions on
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DecisionQuote {
+    /// When the decision started, as its [`SignalOrigin`](crate::risk::SignalOrigin)
+    pub observed_at: DateTime<Utc>,
+    pub price: f64,
+    /// Bid/ask spread quoted then, or the pair's configured spread without a live quote
+    pub spread: f64,
+}
+
+/// One fill against its decision
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionRecord {
+    /// Blotter id of the order
+    pub order_id: u64,
+    pub symbol: String,
+    pub broker: String,
+    /// Trading session label at the fill, e.g. "London+NewYork"
+    pub session: String,
+    /// Signed units filled; positive buys
+    pub units: f64,
+    pub intended_price: f64,
+    pub fill_price: f64,
+    pub spread: f64,
+    pub decided_at: DateTime<Utc>,
+    pub filled_at: DateTime<Utc>,
+}
+
+impl ExecutionRecord {
+    /// Adverse move from the intended to the fill price, as a fraction of the intended price
+    pub fn slippage(&self) -> f64 {
+        if self.intended_price <= 0.0 {
+            return 0.0;
+        }
+        (self.fill_price - self.intended_price) / self.intended_price * self.units.signum()
+    }
+
+    /// Adverse move from the intended to the fill price, in pips
+    pub fn slippage_pips(&self) -> f64 {
+        (self.fill_price - self.intended_price) * self.units.signum() / pip_size(&self.symbol)
+    }
+
+    pub fn time_to_fill_ms(&self) -> i64 {
+        (self.filled_at - self.decided_at).num_milliseconds().max(0)
+    }
+
+    /// Slippage plus half the decision spread, as a fraction of the intended price:
+    /// what crossing the spread at the fill would have cost over the intended mid
+    pub fn cost(&self) -> f64 {
+        if self.intended_price <= 0.0 {
+            return 0.0;
+        }
+        self.slippage() + self.spread / 2.0 / self.intended_price
+    }
+
+    /// "#12 EURUSD BUY 10000 @ 1.10012 vs 1.10000 (+1.2 pips, 350ms, London)"
+    pub fn summary(&self) -> String {
+        let side = if self.units >= 0.0 { "BUY" } else { "SELL" };
+        format!("#{} {} {} {:.0} @ {:.5} vs {:.5} ({:+.1} pips, {}ms, {})",
+                self.order_id, self.symbol, side, self.units.abs(), self.fill_price, self.intended_price,
+                self.slippage_pips(), self.time_to_fill_ms(), self.session)
+    }
+}
+
+/// Execution quality of a group of fills
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExecutionQuality {
+    pub fills: usize,
+    /// Mean adverse slippage as a fraction of price
+    pub mean_slippage: f64,
+    pub mean_slippage_pips: f64,
+    /// Most adverse slippage of any fill
+    pub worst_slippage_pips: f64,
+    pub mean_time_to_fill_ms: f64,
+    pub median_time_to_fill_ms: f64,
+    pub mean_spread_pips: f64,
+    /// Mean of [`ExecutionRecord::cost`]
+    pub mean_cost: f64,
+}
+
+impl ExecutionQuality {
+    pub fn from_records(records: &[&ExecutionRecord]) -> Self {
+        if records.is_empty() {
+            return Self::default();
+        }
+        let count = records.len() as f64;
+        let mean = |value: &dyn Fn(&ExecutionRecord) -> f64| records.iter().map(|record| value(record)).sum::<f64>() / count;
+        let mut times: Vec<i64> = records.iter().map(|record| record.time_to_fill_ms()).collect();
+        times.sort_unstable();
+        let middle = times.len() / 2;
+        let median = if times.len().is_multiple_of(2) { (times[middle - 1] + times[middle]) as f64 / 2.0 } else { times[middle] as f64 };
+        Self {
+            fills: records.len(),
+            mean_slippage: mean(&ExecutionRecord::slippage),
+            mean_slippage_pips: mean(&ExecutionRecord::slippage_pips),
+            worst_slippage_pips: records.iter().map(|record| record.slippage_pips()).fold(f64::NEG_INFINITY, f64::max),
+            mean_time_to_fill_ms: mean(&|record| record.time_to_fill_ms() as f64),
+            median_time_to_fill_ms: median,
+            mean_spread_pips: mean(&|record| record.spread / pip_size(&record.symbol)),
+            mean_cost: mean(&ExecutionRecord::cost),
+        }
+    }
+
+    /// "25 fills, slippage +0.4 pips (worst +2.1), fill 350ms (median 200ms), spread 1.2 pips"
+    pub fn summary(&self) -> String {
+        format!("{} fills, slippage {:+.1} pips (worst {:+.1}), fill {:.0}ms (median {:.0}ms), spread {:.1} pips",
+                self.fills, self.mean_slippage_pips, self.worst_slippage_pips,
+                self.mean_time_to_fill_ms, self.median_time_to_fill_ms, self.mean_spread_pips)
+    }
+}
+
+/// Execution quality overall and per pair, session and broker
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExecutionQualityReport {
+    pub overall: ExecutionQuality,
+    pub by_pair: BTreeMap<String, ExecutionQuality>,
+    pub by_session: BTreeMap<String, ExecutionQuality>,
+    pub by_broker: BTreeMap<String, ExecutionQuality>,
+}
+
+impl ExecutionQualityReport {
+    pub fn from_records(records: &[ExecutionRecord]) -> Self {
+        let group = |key: &dyn Fn(&ExecutionRecord) -> &str| {
+            let mut groups: BTreeMap<String, Vec<&ExecutionRecord>> = BTreeMap::new();
+            for record in records {
+                groups.entry(key(record).to_string()).or_default().push(record);
+            }
+            groups.into_iter().map(|(key, records)| (key, ExecutionQuality::from_records(&records))).collect()
+        };
+        Self {
+            overall: ExecutionQuality::from_records(&records.iter().collect::<Vec<_>>()),
+            by_pair: group(&|record| &record.symbol),
+            by_session: group(&|record| &record.session),
+            by_broker: group(&|record| &record.broker),
+        }
+    }
+
+    /// Report of the execution log at `path`
+    pub fn from_log(path: &Path) -> Result<Self> {
+        Ok(Self::from_records(&read_execution_log(path)?))
+    }
+
+    /// `config` with the measured costs: the overall cost as the slippage of pairs
+    /// without fills, and each measured pair's own cost as its slippage and its
+    /// median time to fill as its latency. Unchanged when nothing was filled.
+    pub fn calibrate(&self, mut config: BacktestConfig) -> BacktestConfig {
+        if self.overall.fills == 0 {
+            return config;
+        }
+        config.slippage = self.overall.mean_cost.max(0.0);
+        for (symbol, quality) in &self.by_pair {
+            let mut pair = config.execution.config_for(symbol).clone();
+            pair.slippage = Some(quality.mean_cost.max(0.0));
+            pair.latency_ms = quality.median_time_to_fill_ms.round() as u64;
+            config.execution.pairs.insert(symbol.clone(), pair);
+        }
+        config
+    }
+
+    /// Report lines for logs and the CLI
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![format!("Overall: {}", self.overall.summary())];
+        for (title, groups) in [("Pair", &self.by_pair), ("Session", &self.by_session), ("Broker", &self.by_broker)] {
+            lines.extend(groups.iter().map(|(key, quality)| format!("{} {}: {}", title, key, quality.summary())));
+        }
+        lines
+    }
+}
+
+/// Thread-safe log of fills against their decisions, kept in memory and
+/// optionally mirrored to a JSON-lines file
+pub struct ExecutionLog {
+    records: Mutex<VecDeque<ExecutionRecord>>,
+    file: Option<PathBuf>,
+    broker: String,
+    sessions: TradingSessions,
+}
+
+impl Default for ExecutionLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExecutionLog {
+    /// In-memory log of paper fills
+    pub fn new() -> Self {
+        Self { records: Mutex::new(VecDeque::new()), file: None, broker: PAPER_BROKER.to_string(), sessions: TradingSessions::default() }
+    }
+
+    /// Log that also appends every record to `path`
+    pub fn with_file(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        Ok(Self { file: Some(path.to_path_buf()), ..Self::new() })
+    }
+
+    /// Stamp fills with the name of the broker that filled them
+    pub fn with_broker(mut self, broker: &str) -> Self {
+        self.broker = broker.to_string();
+        self
+    }
+
+    /// Label fills with `sessions` instead of the default four
+    pub fn with_sessions(mut self, sessions: TradingSessions) -> Self {
+        self.sessions = sessions;
+        self
+    }
+
+    pub fn broker(&self) -> &str {
+        &self.broker
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.file.as_deref()
+    }
+
+    /// Record a fill of `units` at `fill_price` decided on `decision`; write failures are reported, not raised
+    pub fn record(&self, order_id: u64, symbol: &str, units: f64, fill_price: f64, decision: &DecisionQuote, filled_at: DateTime<Utc>) -> ExecutionRecord {
+        let record = ExecutionRecord {
+            order_id,
+            symbol: symbol.to_string(),
+            broker: self.broker.clone(),
+            session: self.sessions.label(filled_at),
+            units,
+            intended_price: decision.price,
+            fill_price,
+            spread: decision.spread,
+            decided_at: decision.observed_at,
+            filled_at,
+        };
+        if let Some(path) = &self.file {
+            if let Err(e) = append_line(path, &record) {
+                println!("⚠️  Failed to write execution log {}: {}", path.display(), e);
+            }
+        }
+        let mut records = self.records.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        records.push_back(record.clone());
+        while records.len() > MAX_IN_MEMORY_RECORDS {
+            records.pop_front();
+        }
+        record
+    }
+
+    /// Most recent records, newest last
+    pub fn recent(&self, limit: usize) -> Vec<ExecutionRecord> {
+        let records = self.records.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        records.iter().skip(records.len().saturating_sub(limit)).cloned().collect()
+    }
+
+    /// Report of the records kept in memory
+    pub fn report(&self) -> ExecutionQualityReport {
+        let mut records = self.records.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        ExecutionQualityReport::from_records(records.make_contiguous())
+    }
+}
+
+fn append_line(path: &Path, record: &ExecutionRecord) -> Result<()> {
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(record)?)?;
+    Ok(())
+}
+
+/// Every record of an execution log file, oldest first
+pub fn read_execution_log(path: &Path) -> Result<Vec<ExecutionRecord>> {
+    let file = std::fs::File::open(path).with_context(|| format!("cannot open execution log {}", path.display()))?;
+    let mut records = Vec::new();
+    for (number, line) in std::io::BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        records.push(serde_json::from_str(&line)
+            .with_context(|| format!("{} line {}: not an execution record", path.display(), number + 1))?);
+    }
+    Ok(records)
+}
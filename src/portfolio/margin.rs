@@ -0,0 +1,117 @@
+//! # Margin
+//!
+//! Leverage limits, margin calls and stop-outs shared by backtests and paper
+//! trading, with the margin level as equity over margin used in percent.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Margin enforcement configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MarginConfig {
+    /// Enforce the leverage limit and stop-outs; margin is still reported when off
+    pub enabled: bool,
+    /// Margin level in percent at which a margin call is raised
+    pub margin_call_level: f64,
+    /// Margin level in percent at which positions are liquidated
+    pub stop_out_level: f64,
+}
+
+impl Default for MarginConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            margin_call_level: 100.0,
+            stop_out_level: 50.0,
+        }
+    }
+}
+
+impl MarginConfig {
+    /// Enforcement with the default margin call and stop-out levels
+    pub fn enforced() -> Self {
+        Self {
+            enabled: true,
+            ..Default::default()
+        }
+    }
+
+    /// Where a margin level (`None` when flat) stands against the thresholds
+    pub fn status(&self, margin_level: Option<f64>) -> MarginStatus {
+        match margin_level {
+            Some(level) if level <= self.stop_out_level => MarginStatus::StopOut,
+            Some(level) if level <= self.margin_call_level => MarginStatus::MarginCall,
+            _ => MarginStatus::Healthy,
+        }
+    }
+}
+
+/// Account standing against the margin thresholds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MarginStatus {
+    Healthy,
+    MarginCall,
+    StopOut,
+}
+
+/// What the margin rules did
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MarginEventKind {
+    /// An order was cut, or refused, to stay within the leverage limit
+    LeverageLimit,
+    MarginCall,
+    /// Positions were force-closed
+    StopOut,
+}
+
+/// Margin event, with the account state that triggered it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarginEvent {
+    pub timestamp: DateTime<Utc>,
+    pub kind: MarginEventKind,
+    /// Pairs ordered, or liquidated
+    pub symbols: Vec<String>,
+    pub equity: f64,
+    pub margin_used: f64,
+    /// Equity / margin used in percent
+    pub margin_level: Option<f64>,
+    /// Units cut from an order, or closed by a stop-out
+    pub units: f64,
+    /// P&L realized by a stop-out
+    pub realized_pnl: f64,
+}
+
+impl MarginEvent {
+    /// One-line description for logs and the audit trail
+    pub fn summary(&self) -> String {
+        let level = self.margin_level.map(|level| format!("{:.1}%", level)).unwrap_or_else(|| "n/a".to_string());
+        match self.kind {
+            MarginEventKind::LeverageLimit => format!(
+                "{} order cut by {:.0} units at the leverage limit (equity {:.2}, margin used {:.2})",
+                self.symbols.join(", "), self.units, self.equity, self.margin_used),
+            MarginEventKind::MarginCall => format!(
+                "margin call: level {} with equity {:.2} on {:.2} margin", level, self.equity, self.margin_used),
+            MarginEventKind::StopOut => format!(
+                "stop-out at margin level {}: closed {:.0} units of {}, realized {:.2}",
+                level, self.units, self.symbols.join(", "), self.realized_pnl),
+        }
+    }
+}
+
+/// Largest order size, in units, that keeps margin used within equity.
+///
+/// `position` is the current signed position and `unit_margin` the margin one
+/// unit ties up; a reducing order may close the position and open up to the
+/// free margin on the other side, which the close itself frees up.
+pub fn max_order_units(position: f64, delta: f64, free_margin: f64, unit_margin: f64) -> f64 {
+    if unit_margin <= 0.0 || !unit_margin.is_finite() {
+        return delta.abs();
+    }
+    let new_exposure = (free_margin.max(0.0) / unit_margin).max(0.0);
+    if position != 0.0 && position.signum() != delta.signum() {
+        2.0 * position.abs() + new_exposure
+    } else {
+        new_exposure
+    }
+}
@@ -0,0 +1,555 @@
+//! # Portfolio
+//!
+//! Net positions per pair with account equity, margin usage and per-currency
+//! exposure marked to the latest prices, and an order blotter.
+
+pub mod allocation;
+pub mod execution_quality;
+pub mod margin;
+pub mod orders;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::laplacian_rl::TradingAction;
+use allocation::Allocation;
+use margin::{max_order_units, MarginConfig, MarginEvent, MarginEventKind, MarginStatus};
+use orders::{Order, OrderBook, OrderStats, OrderStatus};
+
+/// Orders listed in a snapshot
+const SNAPSHOT_ORDERS: usize = 20;
+
+/// Portfolio accounting configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PortfolioConfig {
+    /// Currency the account is denominated in
+    pub account_currency: String,
+
+    /// Starting cash balance
+    pub initial_balance: f64,
+
+    /// Account leverage (notional / margin)
+    pub leverage: f64,
+
+    /// Units traded per unit of `TradingAction` size
+    pub units_per_size: f64,
+
+    /// Leverage limit, margin call and stop-out levels
+    pub margin: MarginConfig,
+}
+
+impl Default for PortfolioConfig {
+    fn default() -> Self {
+        Self {
+            account_currency: "USD".to_string(),
+            initial_balance: 100_000.0,
+            leverage: 30.0,
+            units_per_size: 1_000.0, // size 10 = 0.1 standard lots
+            margin: MarginConfig::default(),
+        }
+    }
+}
+
+/// Net open position in one pair; positive units are long
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Position {
+    pub symbol: String,
+    pub base_currency: String,
+    pub quote_currency: String,
+    pub units: f64,
+    pub entry_price: f64,
+    pub opened_at: DateTime<Utc>,
+}
+
+impl Position {
+    /// Unrealized P&L in the quote currency
+    pub fn unrealized_pnl_quote(&self, price: f64) -> f64 {
+        (price - self.entry_price) * self.units
+    }
+}
+
+/// Position line in a snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionReport {
+    pub symbol: String,
+    pub side: String,
+    pub units: f64,
+    pub entry_price: f64,
+    pub current_price: f64,
+    pub unrealized_pnl: f64,
+    pub margin_used: f64,
+    pub opened_at: DateTime<Utc>,
+}
+
+/// Net exposure to a single currency
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurrencyExposure {
+    pub currency: String,
+    /// Net amount held in the currency itself (negative = short)
+    pub net_amount: f64,
+    /// The same amount valued in the account currency
+    pub account_value: f64,
+}
+
+/// Point-in-time view of equity, margin and exposure
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortfolioSnapshot {
+    pub timestamp: DateTime<Utc>,
+    pub account_currency: String,
+    pub balance: f64,
+    pub realized_pnl: f64,
+    pub unrealized_pnl: f64,
+    pub equity: f64,
+    pub margin_used: f64,
+    pub free_margin: f64,
+    /// Equity / margin used in percent, `None` when flat
+    pub margin_level: Option<f64>,
+    pub total_trades: u64,
+    pub win_rate: f64,
+    pub positions: Vec<PositionReport>,
+    pub currency_exposure: Vec<CurrencyExposure>,
+    /// Latest orders, newest first
+    #[serde(default)]
+    pub recent_orders: Vec<Order>,
+    #[serde(default)]
+    pub order_stats: OrderStats,
+}
+
+/// Account state built from executed trading actions
+#[derive(Debug, Clone)]
+pub struct Portfolio {
+    config: PortfolioConfig,
+    balance: f64,
+    realized_pnl: f64,
+    positions: HashMap<String, Position>,
+    closed_trades: u64,
+    winning_trades: u64,
+    /// Per-pair risk budgets scaling action sizes
+    allocation: Option<Allocation>,
+    /// Leverage cuts, margin calls and stop-outs not yet taken
+    margin_events: Vec<MarginEvent>,
+    /// Whether the current margin call was already raised
+    in_margin_call: bool,
+    orders: OrderBook,
+}
+
+impl Portfolio {
+    pub fn new(config: PortfolioConfig) -> Self {
+        Self {
+            balance: config.initial_balance,
+            config,
+            realized_pnl: 0.0,
+            positions: HashMap::new(),
+            closed_trades: 0,
+            winning_trades: 0,
+            allocation: None,
+            margin_events: Vec::new(),
+            in_margin_call: false,
+            orders: OrderBook::default(),
+        }
+    }
+
+    pub fn config(&self) -> &PortfolioConfig {
+        &self.config
+    }
+
+    pub fn allocation(&self) -> Option<&Allocation> {
+        self.allocation.as_ref()
+    }
+
+    /// Size later actions by `allocation`'s per-pair risk budgets
+    pub fn set_allocation(&mut self, allocation: Allocation) {
+        self.allocation = Some(allocation);
+    }
+
+    /// Units traded per unit of action size in `symbol`, scaled by its risk budget
+    pub fn units_per_size(&self, symbol: &str) -> f64 {
+        let scale = self.allocation.as_ref().map(|allocation| allocation.size_scale(symbol)).unwrap_or(1.0);
+        self.config.units_per_size * scale
+    }
+
+    /// Open positions keyed by symbol
+    pub fn positions(&self) -> &HashMap<String, Position> {
+        &self.positions
+    }
+
+    /// Orders submitted so far
+    pub fn orders(&self) -> &OrderBook {
+        &self.orders
+    }
+
+    /// Signed units `action` trades in `symbol`; `None` when it trades nothing
+    pub fn action_units(&self, symbol: &str, action: &TradingAction) -> Option<f64> {
+        match action {
+            TradingAction::Buy { size } => Some(*size as f64 * self.units_per_size(symbol)),
+            TradingAction::Sell { size } => Some(-(*size as f64) * self.units_per_size(symbol)),
+            TradingAction::ClosePosition => self.positions.get(symbol).map(|position| -position.units),
+            TradingAction::Hold => None,
+        }
+        .filter(|units| *units != 0.0)
+    }
+
+    /// Apply an executed action at `price`, returning any realized P&L in the account currency.
+    ///
+    /// `prices` is used to convert quote-currency P&L into the account currency.
+    /// The action is not recorded as an order; see [`Self::submit`].
+    pub fn apply_action(
+        &mut self,
+        symbol: &str,
+        action: &TradingAction,
+        price: f64,
+        prices: &HashMap<String, f64>,
+        timestamp: DateTime<Utc>,
+    ) -> f64 {
+        match self.action_units(symbol, action) {
+            Some(delta) => self.trade(symbol, delta, price, prices, timestamp),
+            None => 0.0,
+        }
+    }
+
+    /// Execute `action` at `price` as an order and record it in the blotter.
+    ///
+    /// Returns the order, or `None` for actions that trade nothing (holds, or
+    /// closing a flat pair). Orders cut to the leverage limit are partial fills.
+    pub fn submit(
+        &mut self,
+        symbol: &str,
+        action: &TradingAction,
+        price: f64,
+        prices: &HashMap<String, f64>,
+        timestamp: DateTime<Utc>,
+        reason: Option<&str>,
+    ) -> Option<Order> {
+        let requested = self.action_units(symbol, action)?;
+        Some(self.book(symbol, action, requested, requested, price, prices, timestamp, reason))
+    }
+
+    /// Record `action` as filled by a broker: `units` (negative for sells) at `price`.
+    ///
+    /// A broker filling less than the action asked for is a partial fill, and
+    /// the leverage limit can still cut the booked units below the broker's.
+    #[allow(clippy::too_many_arguments)]
+    pub fn submit_fill(
+        &mut self,
+        symbol: &str,
+        action: &TradingAction,
+        units: f64,
+        price: f64,
+        prices: &HashMap<String, f64>,
+        timestamp: DateTime<Utc>,
+        reason: Option<&str>,
+    ) -> Option<Order> {
+        let requested = self.action_units(symbol, action)?;
+        Some(self.book(symbol, action, requested, units, price, prices, timestamp, reason))
+    }
+
+    /// Trade `units` of an order for `requested` units and record it
+    #[allow(clippy::too_many_arguments)]
+    fn book(
+        &mut self,
+        symbol: &str,
+        action: &TradingAction,
+        requested: f64,
+        units: f64,
+        price: f64,
+        prices: &HashMap<String, f64>,
+        timestamp: DateTime<Utc>,
+        reason: Option<&str>,
+    ) -> Order {
+        let position = |portfolio: &Self| portfolio.positions.get(symbol).map(|position| position.units).unwrap_or(0.0);
+        let before = position(self);
+        let realized_pnl = self.trade(symbol, units, price, prices, timestamp);
+        let after = position(self);
+        let filled = after - before;
+        let (status, reason) = if (filled - requested).abs() <= requested.abs() * 1e-9 {
+            (OrderStatus::Filled, reason.map(str::to_string))
+        } else {
+            let why = if price <= 0.0 {
+                "no valid price"
+            } else if (filled - units).abs() <= units.abs() * 1e-9 {
+                "partial fill"
+            } else {
+                "leverage limit"
+            };
+            let status = if filled == 0.0 { OrderStatus::Rejected } else { OrderStatus::PartiallyFilled };
+            (status, Some(why.to_string()))
+        };
+        self.orders.record(Order {
+            id: 0,
+            timestamp,
+            symbol: symbol.to_string(),
+            action: action.clone(),
+            requested_units: requested,
+            filled_units: filled,
+            price,
+            status,
+            reason,
+            realized_pnl,
+            position_units: after,
+        })
+    }
+
+    /// Record `action` as refused before it reached the book, e.g. by a risk check
+    pub fn reject(&mut self, symbol: &str, action: &TradingAction, reason: &str, timestamp: DateTime<Utc>) -> Option<Order> {
+        self.record_unfilled(symbol, action, OrderStatus::Rejected, reason, timestamp)
+    }
+
+    /// Record `action` as never sent because its signal expired
+    pub fn miss(&mut self, symbol: &str, action: &TradingAction, reason: &str, timestamp: DateTime<Utc>) -> Option<Order> {
+        self.record_unfilled(symbol, action, OrderStatus::Missed, reason, timestamp)
+    }
+
+    fn record_unfilled(&mut self, symbol: &str, action: &TradingAction, status: OrderStatus, reason: &str, timestamp: DateTime<Utc>) -> Option<Order> {
+        let requested = self.action_units(symbol, action)?;
+        let position_units = self.positions.get(symbol).map(|position| position.units).unwrap_or(0.0);
+        Some(self.orders.record(Order {
+            id: 0,
+            timestamp,
+            symbol: symbol.to_string(),
+            action: action.clone(),
+            requested_units: requested,
+            filled_units: 0.0,
+            price: 0.0,
+            status,
+            reason: Some(reason.to_string()),
+            realized_pnl: 0.0,
+            position_units,
+        }))
+    }
+
+    /// Close the position in `symbol` as an order, returning realized P&L
+    pub fn flatten(&mut self, symbol: &str, price: f64, prices: &HashMap<String, f64>, timestamp: DateTime<Utc>) -> f64 {
+        self.close_position(symbol, price, prices, timestamp, "flatten")
+    }
+
+    fn close_position(&mut self, symbol: &str, price: f64, prices: &HashMap<String, f64>, timestamp: DateTime<Utc>, reason: &str) -> f64 {
+        self.submit(symbol, &TradingAction::ClosePosition, price, prices, timestamp, Some(reason))
+            .map(|order| order.realized_pnl)
+            .unwrap_or(0.0)
+    }
+
+    /// Change the net position by `delta` units at `price`, cut to the leverage
+    /// limit when margin is enforced
+    fn trade(&mut self, symbol: &str, mut delta: f64, price: f64, prices: &HashMap<String, f64>, timestamp: DateTime<Utc>) -> f64 {
+        if delta == 0.0 || price <= 0.0 {
+            return 0.0;
+        }
+        if self.config.margin.enabled {
+            let snapshot = self.snapshot(prices, timestamp);
+            let position = self.positions.get(symbol).map(|position| position.units).unwrap_or(0.0);
+            let (_, quote) = split_symbol(symbol);
+            let unit_margin = convert(price, &quote, &self.config.account_currency, prices) / self.config.leverage.max(1.0);
+            let limit = max_order_units(position, delta, snapshot.free_margin, unit_margin);
+            if delta.abs() > limit {
+                self.margin_events.push(MarginEvent {
+                    timestamp,
+                    kind: MarginEventKind::LeverageLimit,
+                    symbols: vec![symbol.to_string()],
+                    equity: snapshot.equity,
+                    margin_used: snapshot.margin_used,
+                    margin_level: snapshot.margin_level,
+                    units: delta.abs() - limit,
+                    realized_pnl: 0.0,
+                });
+                delta = limit * delta.signum();
+                if delta == 0.0 {
+                    return 0.0;
+                }
+            }
+        }
+
+        let (base, quote) = split_symbol(symbol);
+        let position = self.positions.entry(symbol.to_string()).or_insert_with(|| Position {
+            symbol: symbol.to_string(),
+            base_currency: base,
+            quote_currency: quote.clone(),
+            units: 0.0,
+            entry_price: price,
+            opened_at: timestamp,
+        });
+
+        let mut realized_quote = None;
+        if position.units == 0.0 || position.units.signum() == delta.signum() {
+            // Adding to (or opening) a position: average the entry price
+            let units = position.units + delta;
+            position.entry_price = (position.entry_price * position.units + price * delta) / units;
+            position.units = units;
+        } else {
+            // Reducing, closing or flipping
+            let closed = delta.abs().min(position.units.abs()) * position.units.signum();
+            realized_quote = Some((price - position.entry_price) * closed);
+            position.units += delta;
+            if position.units.abs() < f64::EPSILON {
+                position.units = 0.0;
+            } else if position.units.signum() == delta.signum() {
+                position.entry_price = price;
+                position.opened_at = timestamp;
+            }
+        }
+
+        if position.units == 0.0 {
+            self.positions.remove(symbol);
+        }
+
+        let Some(realized_quote) = realized_quote else {
+            return 0.0;
+        };
+
+        let realized = convert(realized_quote, &quote, &self.config.account_currency, prices);
+        self.balance += realized;
+        self.realized_pnl += realized;
+        self.closed_trades += 1;
+        if realized > 0.0 {
+            self.winning_trades += 1;
+        }
+        realized
+    }
+
+    /// Raise a margin call once per episode, and at stop-out close the positions
+    /// losing most until the margin level recovers or the account is flat.
+    ///
+    /// Events are queued for [`Self::take_margin_events`]; does nothing unless
+    /// margin is enforced.
+    pub fn enforce_margin(&mut self, prices: &HashMap<String, f64>, timestamp: DateTime<Utc>) -> MarginStatus {
+        if !self.config.margin.enabled {
+            return MarginStatus::Healthy;
+        }
+        let snapshot = self.snapshot(prices, timestamp);
+        let status = self.config.margin.status(snapshot.margin_level);
+        let event = |kind, symbols, units, realized_pnl| MarginEvent {
+            timestamp,
+            kind,
+            symbols,
+            equity: snapshot.equity,
+            margin_used: snapshot.margin_used,
+            margin_level: snapshot.margin_level,
+            units,
+            realized_pnl,
+        };
+        match status {
+            MarginStatus::Healthy => self.in_margin_call = false,
+            MarginStatus::MarginCall => {
+                if !self.in_margin_call {
+                    self.margin_events.push(event(MarginEventKind::MarginCall, Vec::new(), 0.0, 0.0));
+                    self.in_margin_call = true;
+                }
+            }
+            MarginStatus::StopOut => {
+                let mut losers = snapshot.positions.clone();
+                losers.sort_by(|a, b| a.unrealized_pnl.total_cmp(&b.unrealized_pnl));
+                let (mut symbols, mut units, mut realized) = (Vec::new(), 0.0, 0.0);
+                let mut after = status;
+                for position in losers {
+                    let Some(price) = prices.get(&position.symbol).copied() else { continue };
+                    realized += self.close_position(&position.symbol, price, prices, timestamp, "margin stop-out");
+                    units += position.units;
+                    symbols.push(position.symbol);
+                    after = self.config.margin.status(self.snapshot(prices, timestamp).margin_level);
+                    if after != MarginStatus::StopOut {
+                        break;
+                    }
+                }
+                self.margin_events.push(event(MarginEventKind::StopOut, symbols, units, realized));
+                self.in_margin_call = after != MarginStatus::Healthy;
+            }
+        }
+        status
+    }
+
+    /// Drain the leverage cuts, margin calls and stop-outs since the last call
+    pub fn take_margin_events(&mut self) -> Vec<MarginEvent> {
+        std::mem::take(&mut self.margin_events)
+    }
+
+    /// Mark every position to `prices` (symbol → latest price)
+    pub fn snapshot(&self, prices: &HashMap<String, f64>, timestamp: DateTime<Utc>) -> PortfolioSnapshot {
+        let account = &self.config.account_currency;
+        let mut positions = Vec::new();
+        let mut exposure: BTreeMap<String, f64> = BTreeMap::new();
+        let mut unrealized_pnl = 0.0;
+        let mut margin_used = 0.0;
+
+        for position in self.positions.values() {
+            let price = prices.get(&position.symbol).copied().unwrap_or(position.entry_price);
+            let pnl = convert(position.unrealized_pnl_quote(price), &position.quote_currency, account, prices);
+            let notional = convert(position.units.abs() * price, &position.quote_currency, account, prices);
+            let margin = notional / self.config.leverage.max(1.0);
+
+            *exposure.entry(position.base_currency.clone()).or_default() += position.units;
+            *exposure.entry(position.quote_currency.clone()).or_default() -= position.units * price;
+
+            unrealized_pnl += pnl;
+            margin_used += margin;
+            positions.push(PositionReport {
+                symbol: position.symbol.clone(),
+                side: if position.units > 0.0 { "LONG" } else { "SHORT" }.to_string(),
+                units: position.units.abs(),
+                entry_price: position.entry_price,
+                current_price: price,
+                unrealized_pnl: pnl,
+                margin_used: margin,
+                opened_at: position.opened_at,
+            });
+        }
+        positions.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+
+        let currency_exposure = exposure.into_iter()
+            .filter(|(_, amount)| amount.abs() > f64::EPSILON)
+            .map(|(currency, net_amount)| CurrencyExposure {
+                account_value: convert(net_amount, &currency, account, prices),
+                currency,
+                net_amount,
+            })
+            .collect();
+
+        let equity = self.balance + unrealized_pnl;
+        PortfolioSnapshot {
+            timestamp,
+            account_currency: account.clone(),
+            balance: self.balance,
+            realized_pnl: self.realized_pnl,
+            unrealized_pnl,
+            equity,
+            margin_used,
+            free_margin: equity - margin_used,
+            margin_level: (margin_used > 0.0).then(|| equity / margin_used * 100.0),
+            total_trades: self.closed_trades,
+            win_rate: if self.closed_trades > 0 {
+                self.winning_trades as f64 / self.closed_trades as f64 * 100.0
+            } else {
+                0.0
+            },
+            positions,
+            currency_exposure,
+            recent_orders: self.orders.recent(SNAPSHOT_ORDERS),
+            order_stats: self.orders.stats().clone(),
+        }
+    }
+}
+
+/// Split "EURUSD" into ("EUR", "USD")
+pub fn split_symbol(symbol: &str) -> (String, String) {
+    let symbol = symbol.to_uppercase();
+    if symbol.len() >= 6 && symbol.is_char_boundary(3) {
+        (symbol[..3].to_string(), symbol[3..6].to_string())
+    } else {
+        (symbol.clone(), String::new())
+    }
+}
+
+/// Convert an amount between currencies using direct or inverse pair prices.
+/// Unknown conversions are returned unchanged.
+pub fn convert(amount: f64, from: &str, to: &str, prices: &HashMap<String, f64>) -> f64 {
+    if from == to || amount == 0.0 {
+        return amount;
+    }
+    if let Some(price) = prices.get(&format!("{}{}", from, to)).filter(|p| **p > 0.0) {
+        return amount * price;
+    }
+    if let Some(price) = prices.get(&format!("{}{}", to, from)).filter(|p| **p > 0.0) {
+        return amount / price;
+    }
+    amount
+}
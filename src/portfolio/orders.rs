@@ -0,0 +1,127 @@
+//! # Orders
+//!
+//! Blotter of the orders a portfolio was asked to execute: what each trading
+//! action requested, what filled and at what price, and why the rest did not.
+//! Fills are immediate at the price given, so an order is final once recorded.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+use crate::laplacian_rl::TradingAction;
+
+/// Orders kept by default; older ones are dropped
+const DEFAULT_ORDER_CAPACITY: usize = 1_000;
+
+/// How an order ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrderStatus {
+    Filled,
+    /// Filled in part, e.g. cut to the leverage limit
+    PartiallyFilled,
+    /// Not filled at all
+    Rejected,
+    /// Not sent: its signal went stale before execution
+    Missed,
+}
+
+/// One order and its outcome
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Order {
+    pub id: u64,
+    pub timestamp: DateTime<Utc>,
+    pub symbol: String,
+    pub action: TradingAction,
+    /// Signed units requested; positive buys
+    pub requested_units: f64,
+    /// Signed units filled
+    pub filled_units: f64,
+    pub price: f64,
+    pub status: OrderStatus,
+    /// Why the order was cut or refused, or what placed it
+    pub reason: Option<String>,
+    /// P&L the fill realized, in the account currency
+    pub realized_pnl: f64,
+    /// Net position in the pair after the order
+    pub position_units: f64,
+}
+
+impl Order {
+    /// One-line description for logs and the dashboard
+    pub fn summary(&self) -> String {
+        let side = if self.requested_units >= 0.0 { "BUY" } else { "SELL" };
+        let reason = self.reason.as_deref().map(|reason| format!(" ({})", reason)).unwrap_or_default();
+        match self.status {
+            OrderStatus::Rejected => format!("#{} {} {} {:.0} rejected{}", self.id, side, self.symbol, self.requested_units.abs(), reason),
+            OrderStatus::Missed => format!("#{} {} {} {:.0} missed{}", self.id, side, self.symbol, self.requested_units.abs(), reason),
+            _ => format!("#{} {} {} {:.0}/{:.0} @ {:.5} {:?}, P&L {:.2}{}",
+                         self.id, side, self.symbol, self.filled_units.abs(), self.requested_units.abs(),
+                         self.price, self.status, self.realized_pnl, reason),
+        }
+    }
+}
+
+/// Order counts by outcome
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OrderStats {
+    pub submitted: u64,
+    pub filled: u64,
+    pub partially_filled: u64,
+    pub rejected: u64,
+    #[serde(default)]
+    pub missed: u64,
+}
+
+/// Most recent orders, numbered in submission order
+#[derive(Debug, Clone)]
+pub struct OrderBook {
+    orders: VecDeque<Order>,
+    capacity: usize,
+    next_id: u64,
+    stats: OrderStats,
+}
+
+impl Default for OrderBook {
+    fn default() -> Self {
+        Self::new(DEFAULT_ORDER_CAPACITY)
+    }
+}
+
+impl OrderBook {
+    /// Keep the latest `capacity` orders
+    pub fn new(capacity: usize) -> Self {
+        Self { orders: VecDeque::new(), capacity: capacity.max(1), next_id: 1, stats: OrderStats::default() }
+    }
+
+    /// Number `order`, count it and keep it; the id in `order` is replaced
+    pub fn record(&mut self, mut order: Order) -> Order {
+        order.id = self.next_id;
+        self.next_id += 1;
+        self.stats.submitted += 1;
+        match order.status {
+            OrderStatus::Filled => self.stats.filled += 1,
+            OrderStatus::PartiallyFilled => self.stats.partially_filled += 1,
+            OrderStatus::Rejected => self.stats.rejected += 1,
+            OrderStatus::Missed => self.stats.missed += 1,
+        }
+        if self.orders.len() == self.capacity {
+            self.orders.pop_front();
+        }
+        self.orders.push_back(order.clone());
+        order
+    }
+
+    /// Latest `count` orders, newest first
+    pub fn recent(&self, count: usize) -> Vec<Order> {
+        self.orders.iter().rev().take(count).cloned().collect()
+    }
+
+    /// Kept orders in `symbol`, oldest first
+    pub fn for_symbol(&self, symbol: &str) -> Vec<&Order> {
+        self.orders.iter().filter(|order| order.symbol == symbol).collect()
+    }
+
+    pub fn stats(&self) -> &OrderStats {
+        &self.stats
+    }
+}
@@ -0,0 +1,112 @@
+//! # Progress and Cancellation
+//!
+//! Progress reports and cancellation for long operations: a [`Progress`] is told
+//! how far they are, and a cancelled [`CancellationToken`] stops them at the next
+//! checkpoint with [`Cancelled`].
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use tokio::sync::watch;
+pub use tokio_util::sync::CancellationToken;
+
+/// How far an operation is through one of its stages
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProgressUpdate {
+    pub stage: String,
+    pub done: u64,
+    pub total: u64,
+}
+
+impl ProgressUpdate {
+    pub fn new(stage: &str, done: u64, total: u64) -> Self {
+        Self { stage: stage.to_string(), done, total }
+    }
+
+    /// Share of the stage completed, in [0, 1]; a stage with nothing to do is complete
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            1.0
+        } else {
+            (self.done as f64 / self.total as f64).min(1.0)
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.done >= self.total
+    }
+}
+
+/// Receiver of progress updates; called from the operation's own task, so it
+/// should return quickly
+pub trait Progress: Send + Sync {
+    fn report(&self, update: ProgressUpdate);
+}
+
+impl<F: Fn(ProgressUpdate) + Send + Sync> Progress for F {
+    fn report(&self, update: ProgressUpdate) {
+        self(update)
+    }
+}
+
+/// Keeps only the latest update, which is all a progress bar draws
+impl Progress for watch::Sender<ProgressUpdate> {
+    fn report(&self, update: ProgressUpdate) {
+        self.send_replace(update);
+    }
+}
+
+/// Discards every update
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoProgress;
+
+impl Progress for NoProgress {
+    fn report(&self, _update: ProgressUpdate) {}
+}
+
+/// Prints a line to stdout every `every` steps and when a stage completes
+#[derive(Debug, Clone, Copy)]
+pub struct ConsoleProgress {
+    pub every: u64,
+}
+
+impl Default for ConsoleProgress {
+    fn default() -> Self {
+        Self { every: 1000 }
+    }
+}
+
+impl Progress for ConsoleProgress {
+    fn report(&self, update: ProgressUpdate) {
+        if update.done.is_multiple_of(self.every.max(1)) || update.is_complete() {
+            println!("📊 {}: {}/{} ({:.1}%)", update.stage, update.done, update.total, update.fraction() * 100.0);
+        }
+    }
+}
+
+/// A watch channel to pass as [`Progress`], and its receiver
+pub fn progress_channel() -> (watch::Sender<ProgressUpdate>, watch::Receiver<ProgressUpdate>) {
+    watch::channel(ProgressUpdate::default())
+}
+
+/// The error of an operation stopped through its [`CancellationToken`];
+/// find it with `error.is::<Cancelled>()`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "operation cancelled")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+/// Fail with [`Cancelled`] once `token` is cancelled
+pub fn check_cancelled(token: &CancellationToken) -> Result<()> {
+    if token.is_cancelled() {
+        Err(Cancelled.into())
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,127 @@
+//! HTTP client used by the remote controller
+
+use anyhow::{bail, Result};
+use futures_util::StreamExt;
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use tokio::sync::mpsc;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+use std::collections::HashMap;
+
+use super::{AuditEntry, CommandResponse, CompositeScore, ExecutionQualityReport, PendingApproval, PortfolioSnapshot, RemoteSystemStatus, SuppressionRule, ThroughputPanel, TradingCommand};
+
+/// Client for a trading daemon's HTTP API
+#[derive(Clone)]
+pub struct ControllerClient {
+    client: Client,
+    endpoint: String,
+}
+
+impl ControllerClient {
+    pub fn new(endpoint: &str) -> Self {
+        Self {
+            client: Client::new(),
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+        }
+    }
+
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// `GET /health`
+    pub async fn health(&self) -> Result<bool> {
+        let response = self.client.get(format!("{}/health", self.endpoint)).send().await?;
+        Ok(response.status().is_success())
+    }
+
+    /// `GET /api/status`
+    pub async fn fetch_status(&self) -> Result<RemoteSystemStatus> {
+        self.get("api/status").await
+    }
+
+    /// `GET /api/portfolio`
+    pub async fn fetch_portfolio(&self) -> Result<PortfolioSnapshot> {
+        self.get("api/portfolio").await
+    }
+
+    /// `GET /api/audit`
+    pub async fn fetch_audit(&self) -> Result<Vec<AuditEntry>> {
+        self.get("api/audit").await
+    }
+
+    /// `GET /api/suppressions`
+    pub async fn fetch_suppressions(&self) -> Result<Vec<SuppressionRule>> {
+        self.get("api/suppressions").await
+    }
+
+    /// `GET /api/approvals`: actions awaiting approval, oldest first
+    pub async fn fetch_approvals(&self) -> Result<Vec<PendingApproval>> {
+        self.get("api/approvals").await
+    }
+
+    /// `GET /api/execution-quality`: slippage, time to fill and spread per pair, session and broker
+    pub async fn fetch_execution_quality(&self) -> Result<ExecutionQualityReport> {
+        self.get("api/execution-quality").await
+    }
+
+    /// `GET /api/scores`: latest composite score per pair
+    pub async fn fetch_scores(&self) -> Result<HashMap<String, CompositeScore>> {
+        self.get("api/scores").await
+    }
+
+    /// `GET /api/scores/<pair>`: recent composite scores of one pair, oldest first
+    pub async fn fetch_score_history(&self, pair: &str) -> Result<Vec<CompositeScore>> {
+        self.get(&format!("api/scores/{}", pair)).await
+    }
+
+    /// `GET /api/throughput`: per-pair throughput and latency, served by dashboards
+    pub async fn fetch_throughput(&self) -> Result<ThroughputPanel> {
+        self.get("api/throughput").await
+    }
+
+    /// `POST /api/command`
+    pub async fn send_command(&self, command: &TradingCommand) -> Result<CommandResponse> {
+        let response = self.client
+            .post(format!("{}/api/command", self.endpoint))
+            .json(command)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            bail!("command failed: {}", response.status());
+        }
+        Ok(response.json().await?)
+    }
+
+    /// Open `/ws/<stream>` (e.g. "prices", "anomalies") and receive its JSON messages
+    pub async fn subscribe(&self, stream: &str) -> Result<mpsc::Receiver<serde_json::Value>> {
+        let url = format!("{}/ws/{}", self.endpoint, stream)
+            .replacen("https://", "wss://", 1)
+            .replacen("http://", "ws://", 1);
+        let (socket, _) = connect_async(url.as_str()).await?;
+        let (_, mut incoming) = socket.split();
+        let (sender, receiver) = mpsc::channel(256);
+
+        tokio::spawn(async move {
+            while let Some(Ok(message)) = incoming.next().await {
+                let Message::Text(text) = message else { continue };
+                let Ok(value) = serde_json::from_str(&text) else { continue };
+                if sender.send(value).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(receiver)
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let response = self.client.get(format!("{}/{}", self.endpoint, path)).send().await?;
+        if !response.status().is_success() {
+            bail!("failed to fetch {}: {}", path, response.status());
+        }
+        Ok(response.json().await?)
+    }
+}
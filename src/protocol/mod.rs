@@ -0,0 +1,124 @@
+//! # Controller Protocol
+//!
+//! Wire format shared by the trading server's HTTP API and the remote CLI controller
+
+pub mod client;
+pub mod server;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+pub use crate::anomaly::suppression::{SuppressionKind, SuppressionRule};
+pub use crate::audit::AuditEntry;
+pub use crate::data::health::{FeedHealthReport, FeedState, PairFeedHealth};
+pub use crate::portfolio::{CurrencyExposure, PortfolioSnapshot, PositionReport};
+pub use crate::multi_currency::approval::{ExecutionMode, PendingApproval};
+pub use crate::portfolio::execution_quality::ExecutionQualityReport;
+pub use crate::multi_currency::throughput::{PairThroughput, ThroughputPanel};
+pub use crate::signal::{CompositeScore, Regime, ScoreComponents};
+
+/// `GET /api/status` response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteSystemStatus {
+    pub status: String,
+    pub uptime: u64,
+    pub active_pairs: Vec<String>,
+    pub total_trades: u64,
+    pub profit_loss: f64,
+    pub correlation_opportunities: Vec<ArbitrageOpportunity>,
+    pub system_metrics: SystemMetrics,
+    /// Live feed health, absent when running without a data provider
+    #[serde(default)]
+    pub feed_health: Option<FeedHealthReport>,
+}
+
+/// Correlation arbitrage opportunity summary
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArbitrageOpportunity {
+    pub primary_pair: String,
+    pub correlated_pair: String,
+    pub confidence: f64,
+    pub theoretical_pips: f64,
+    pub realistic_pips: f64,
+    pub execution_cost: f64,
+    pub net_expected_pips: f64,
+    pub position_size: f64,
+    pub time_window: String,
+}
+
+/// Host-level metrics reported with the status
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SystemMetrics {
+    pub cpu_usage: f64,
+    pub memory_usage: f64,
+    pub network_latency: f64,
+    pub database_size: u64,
+    pub active_connections: u32,
+}
+
+/// `POST /api/command` request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradingCommand {
+    pub action: String,
+    pub pair: Option<String>,
+    #[serde(default)]
+    pub parameters: HashMap<String, String>,
+}
+
+impl TradingCommand {
+    pub fn new(action: &str) -> Self {
+        Self {
+            action: action.to_string(),
+            pair: None,
+            parameters: HashMap::new(),
+        }
+    }
+
+    pub fn with_pair(mut self, pair: &str) -> Self {
+        self.pair = Some(pair.to_string());
+        self
+    }
+
+    pub fn with_parameter(mut self, key: &str, value: &str) -> Self {
+        self.parameters.insert(key.to_string(), value.to_string());
+        self
+    }
+}
+
+/// Outcome of a command
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommandStatus {
+    Success,
+    Error,
+    /// Received but not acted on
+    Acknowledged,
+}
+
+/// `POST /api/command` response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandResponse {
+    pub status: CommandStatus,
+    pub message: String,
+    /// Canonical form of the command that was executed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// Command-specific extra fields
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub details: HashMap<String, String>,
+}
+
+impl CommandResponse {
+    pub fn new(status: CommandStatus, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+            command: None,
+            details: HashMap::new(),
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.status == CommandStatus::Success
+    }
+}
@@ -0,0 +1,183 @@
+//! HTTP API routes served by the trading daemon
+
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::Mutex;
+use warp::http::StatusCode;
+use warp::{Filter, Rejection, Reply};
+
+use super::{CommandResponse, CommandStatus, RemoteSystemStatus, TradingCommand};
+use crate::multi_currency::{ControlCommand, MultiCurrencyManager};
+
+/// Shared state behind the API
+#[derive(Clone)]
+pub struct ApiState {
+    pub manager: Arc<MultiCurrencyManager>,
+    pub status: Arc<Mutex<RemoteSystemStatus>>,
+    pub started: Instant,
+}
+
+impl ApiState {
+    pub fn new(manager: Arc<MultiCurrencyManager>, status: RemoteSystemStatus) -> Self {
+        Self {
+            manager,
+            status: Arc::new(Mutex::new(status)),
+            started: Instant::now(),
+        }
+    }
+}
+
+/// All API routes: status, portfolio, command, audit, suppressions, approvals, execution quality, scores, synthetic quality and health
+pub fn routes(state: ApiState) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    let with_state = warp::any().map(move || state.clone());
+
+    let status = warp::path!("api" / "status")
+        .and(warp::get())
+        .and(with_state.clone())
+        .and_then(status_handler);
+
+    let portfolio = warp::path!("api" / "portfolio")
+        .and(warp::get())
+        .and(with_state.clone())
+        .and_then(|state: ApiState| async move {
+            let snapshot = state.manager.portfolio_snapshot().await;
+            Ok::<_, Infallible>(warp::reply::json(&snapshot))
+        });
+
+    let command = warp::path!("api" / "command")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_state.clone())
+        .and_then(|command: TradingCommand, state: ApiState| async move {
+            let response = handle_command(&state.manager, command).await;
+            Ok::<_, Infallible>(warp::reply::json(&response))
+        });
+
+    let audit = warp::path!("api" / "audit")
+        .and(warp::get())
+        .and(with_state.clone())
+        .map(|state: ApiState| warp::reply::json(&state.manager.audit_log.recent(100)));
+
+    let suppressions = warp::path!("api" / "suppressions")
+        .and(warp::get())
+        .and(with_state.clone())
+        .map(|state: ApiState| warp::reply::json(&state.manager.suppressions.active(chrono::Utc::now())));
+
+    let approvals = warp::path!("api" / "approvals")
+        .and(warp::get())
+        .and(with_state.clone())
+        .and_then(|state: ApiState| async move {
+            Ok::<_, Infallible>(warp::reply::json(&state.manager.pending_approvals().await))
+        });
+
+    let execution_quality = warp::path!("api" / "execution-quality")
+        .and(warp::get())
+        .and(with_state.clone())
+        .map(|state: ApiState| warp::reply::json(&state.manager.execution_quality()));
+
+    let scores = warp::path!("api" / "scores")
+        .and(warp::get())
+        .and(with_state.clone())
+        .and_then(|state: ApiState| async move {
+            Ok::<_, Infallible>(warp::reply::json(&state.manager.composite_scores().await))
+        });
+
+    let synthetic_quality = warp::path!("api" / "synthetic-quality")
+        .and(warp::get())
+        .and(with_state.clone())
+        .and_then(|state: ApiState| async move {
+            Ok::<_, Infallible>(warp::reply::json(&state.manager.synthetic_quality_reports().await))
+        });
+
+    let params = warp::path!("api" / "params")
+        .and(warp::get())
+        .and(with_state.clone())
+        .and_then(|state: ApiState| async move {
+            let params = &state.manager.analysis_params;
+            Ok::<_, Infallible>(warp::reply::json(&serde_json::json!({
+                "current": params.current(),
+                "pairs": state.manager.pair_params().await,
+                "epochs": params.epochs_since(0),
+            })))
+        });
+
+    let score_history = warp::path!("api" / "scores" / String)
+        .and(warp::get())
+        .and(with_state)
+        .and_then(|pair: String, state: ApiState| async move {
+            let reply = match state.manager.composite_score_history(&pair).await {
+                Some(history) => warp::reply::with_status(warp::reply::json(&history), StatusCode::OK),
+                None => warp::reply::with_status(
+                    warp::reply::json(&serde_json::json!({"error": format!("unknown pair {}", pair)})),
+                    StatusCode::NOT_FOUND),
+            };
+            Ok::<_, Infallible>(reply)
+        });
+
+    let health = warp::path("health")
+        .and(warp::get())
+        .map(|| warp::reply::json(&serde_json::json!({"status": "healthy"})));
+
+    status.or(portfolio).or(command).or(audit).or(suppressions).or(approvals).or(execution_quality).or(scores).or(score_history).or(synthetic_quality).or(params).or(health)
+}
+
+async fn status_handler(state: ApiState) -> Result<impl Reply, Infallible> {
+    let portfolio = state.manager.portfolio_snapshot().await;
+    let mut status = state.status.lock().await;
+    status.uptime = state.started.elapsed().as_secs();
+    status.total_trades = portfolio.total_trades;
+    status.profit_loss = portfolio.realized_pnl + portfolio.unrealized_pnl;
+    status.feed_health = state.manager.feed_health_report().await;
+    Ok(warp::reply::json(&*status))
+}
+
+/// Route a command to the manager and build the response
+pub async fn handle_command(manager: &MultiCurrencyManager, command: TradingCommand) -> CommandResponse {
+    println!("📨 Received command: {:?}", command);
+
+    // Pause / resume / flatten / set-risk / set-latency / set-mode / approve / reject / kill-switch / acknowledge / suppress go to the trading manager
+    match ControlCommand::parse(&command.action, command.pair.as_deref(), &command.parameters) {
+        Ok(Some(control)) => {
+            let mut response = match manager.execute_command(&control, "api").await {
+                Ok(message) => CommandResponse::new(CommandStatus::Success, message),
+                Err(e) => CommandResponse::new(CommandStatus::Error, e.to_string()),
+            };
+            response.command = Some(control.to_string());
+            return response;
+        }
+        Err(e) => {
+            manager.audit_log.record("api", &command.action, false, &e.to_string());
+            return CommandResponse::new(CommandStatus::Error, e.to_string());
+        }
+        Ok(None) => {}
+    }
+
+    // Handle mode switching command
+    if command.action == "switch_mode" {
+        let param = |key: &str, default: &str| command.parameters.get(key).cloned().unwrap_or_else(|| default.to_string());
+        let mode = param("mode", "DEMO");
+        let server = param("server", "cTrader DEMO");
+        let account_id = param("account_id", "5078436");
+
+        println!("🔄 Switching to {} mode", mode);
+        println!("📊 Server: {}", server);
+        println!("🔑 Account ID: {}", account_id);
+
+        // In a real implementation, this would reconfigure the cTrader connection
+        // For now, we'll just acknowledge the command
+        manager.audit_log.record("api", &format!("switch_mode {}", mode), true, "acknowledged");
+
+        let mut response = CommandResponse::new(
+            CommandStatus::Success,
+            format!("Successfully switched to {} mode", mode),
+        );
+        response.details.insert("mode".to_string(), mode);
+        response.details.insert("server".to_string(), server);
+        response.details.insert("account_id".to_string(), account_id);
+        return response;
+    }
+
+    // Default response for unhandled commands
+    CommandResponse::new(CommandStatus::Acknowledged, "Command received but not implemented yet")
+}
@@ -0,0 +1,475 @@
+//! # Market Regimes
+//!
+//! Market regimes from a Gaussian hidden Markov model over the trend and log
+//! volatility of recent returns, with states named ranging, trending or crisis
+//! by what they look like.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::data::ForexDataPoint;
+
+/// Smallest feature variance a state may have, in standardised units
+const MIN_VARIANCE: f64 = 1e-3;
+
+const KMEANS_ITERATIONS: usize = 50;
+
+/// What kind of market a state describes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MarketRegime {
+    Trending,
+    Ranging,
+    Crisis,
+}
+
+impl fmt::Display for MarketRegime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            MarketRegime::Trending => "Trending",
+            MarketRegime::Ranging => "Ranging",
+            MarketRegime::Crisis => "Crisis",
+        })
+    }
+}
+
+impl FromStr for MarketRegime {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "trending" => Ok(MarketRegime::Trending),
+            "ranging" => Ok(MarketRegime::Ranging),
+            "crisis" => Ok(MarketRegime::Crisis),
+            other => Err(anyhow!("Unknown regime '{}' (expected trending, ranging or crisis)", other)),
+        }
+    }
+}
+
+/// Regime model settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RegimeConfig {
+    /// Hidden states, 2 to 4
+    pub states: usize,
+    /// Returns in the window each bar's features are measured over
+    pub window: usize,
+    pub max_iterations: usize,
+    /// Stop once an iteration improves the log-likelihood per bar by less than this
+    pub tolerance: f64,
+    /// Fewest bars with a full window to fit on
+    pub min_samples: usize,
+}
+
+impl Default for RegimeConfig {
+    fn default() -> Self {
+        Self {
+            states: 3,
+            window: 20,
+            max_iterations: 100,
+            tolerance: 1e-6,
+            min_samples: 100,
+        }
+    }
+}
+
+/// One hidden state of a fitted model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegimeState {
+    pub regime: MarketRegime,
+    /// Mean log return over its standard deviation in a window
+    pub trend: f64,
+    /// Typical standard deviation of a bar's log return
+    pub volatility: f64,
+    /// Share of the fitted bars spent in this state
+    pub occupancy: f64,
+    /// Standardised feature means (trend, log volatility)
+    pub mean: [f64; 2],
+    pub variance: [f64; 2],
+}
+
+/// Regime probabilities at one bar
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegimeProbabilities {
+    pub timestamp: DateTime<Utc>,
+    /// Probability of each state of the model
+    pub states: Vec<f64>,
+    /// Most likely regime, summing states with the same name
+    pub regime: MarketRegime,
+    pub probability: f64,
+}
+
+/// A fitted hidden Markov model of market regimes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegimeModel {
+    /// States from the calmest to the most volatile
+    pub states: Vec<RegimeState>,
+    /// `transition[i][j]`: probability of moving from state `i` to state `j` in one bar
+    pub transition: Vec<Vec<f64>>,
+    pub initial: Vec<f64>,
+    pub log_likelihood: f64,
+    pub iterations: usize,
+    /// Bars the model was fitted on
+    pub samples: usize,
+    pub window: usize,
+    /// Mean and standard deviation the features were standardised with
+    pub feature_mean: [f64; 2],
+    pub feature_scale: [f64; 2],
+}
+
+impl RegimeModel {
+    /// Fit the model on `data` by Baum-Welch
+    pub fn fit(data: &[ForexDataPoint], config: &RegimeConfig) -> Result<Self> {
+        if !(2..=4).contains(&config.states) {
+            return Err(anyhow!("A regime model has 2 to 4 states, got {}", config.states));
+        }
+        if config.window < 2 {
+            return Err(anyhow!("Regime features need a window of at least 2 returns, got {}", config.window));
+        }
+        let raw: Vec<[f64; 2]> = features(data, config.window).into_iter().map(|(_, x)| x).collect();
+        let needed = config.min_samples.max(10 * config.states);
+        if raw.len() < needed {
+            return Err(anyhow!("Need at least {} bars after the first {} returns to fit {} regimes, got {}",
+                               needed, config.window, config.states, raw.len()));
+        }
+        let n = raw.len() as f64;
+        let mut feature_mean = [0.0; 2];
+        let mut feature_scale = [0.0; 2];
+        for d in 0..2 {
+            feature_mean[d] = raw.iter().map(|x| x[d]).sum::<f64>() / n;
+            feature_scale[d] = (raw.iter().map(|x| (x[d] - feature_mean[d]).powi(2)).sum::<f64>() / n).sqrt();
+        }
+        if feature_scale.iter().any(|s| *s <= f64::EPSILON) {
+            return Err(anyhow!("History has no variation in returns to fit regimes on"));
+        }
+        let x: Vec<[f64; 2]> = raw.iter().map(|x| standardise(*x, feature_mean, feature_scale)).collect();
+
+        let k = config.states;
+        let (mut means, mut variances) = kmeans(&x, k);
+        let mut transition: Vec<Vec<f64>> = (0..k)
+            .map(|i| (0..k).map(|j| if i == j { 0.9 } else { 0.1 / (k - 1) as f64 }).collect())
+            .collect();
+        let mut initial = vec![1.0 / k as f64; k];
+        let mut previous = f64::NEG_INFINITY;
+        let mut iterations = 0;
+        for iteration in 1..=config.max_iterations {
+            let emissions = emissions(&x, &means, &variances);
+            let pass = forward_backward(&initial, &transition, &emissions);
+            let improvement = (pass.log_likelihood - previous) / n;
+            previous = pass.log_likelihood;
+            iterations = iteration;
+            if improvement < config.tolerance {
+                break;
+            }
+
+            initial = pass.gamma[0].clone();
+            for (i, row) in transition.iter_mut().enumerate() {
+                let total: f64 = pass.xi[i].iter().sum();
+                if total > 0.0 {
+                    for (j, p) in row.iter_mut().enumerate() {
+                        *p = pass.xi[i][j] / total;
+                    }
+                }
+            }
+            for s in 0..k {
+                let weight: f64 = pass.gamma.iter().map(|g| g[s]).sum();
+                if weight <= f64::EPSILON {
+                    continue;
+                }
+                for d in 0..2 {
+                    let mean = pass.gamma.iter().zip(&x).map(|(g, x)| g[s] * x[d]).sum::<f64>() / weight;
+                    let variance = pass.gamma.iter().zip(&x).map(|(g, x)| g[s] * (x[d] - mean).powi(2)).sum::<f64>() / weight;
+                    means[s][d] = mean;
+                    variances[s][d] = variance.max(MIN_VARIANCE);
+                }
+            }
+        }
+        let pass = forward_backward(&initial, &transition, &emissions(&x, &means, &variances));
+
+        // Order states from the calmest to the most volatile
+        let mut order: Vec<usize> = (0..k).collect();
+        order.sort_by(|&a, &b| means[a][1].total_cmp(&means[b][1]));
+        let mut states: Vec<RegimeState> = order.iter()
+            .map(|&s| RegimeState {
+                regime: MarketRegime::Ranging,
+                trend: feature_mean[0] + means[s][0] * feature_scale[0],
+                volatility: (feature_mean[1] + means[s][1] * feature_scale[1]).exp(),
+                occupancy: pass.gamma.iter().map(|g| g[s]).sum::<f64>() / n,
+                mean: means[s],
+                variance: variances[s],
+            })
+            .collect();
+        name_states(&mut states);
+
+        Ok(Self {
+            states,
+            transition: order.iter().map(|&i| order.iter().map(|&j| transition[i][j]).collect()).collect(),
+            initial: order.iter().map(|&s| initial[s]).collect(),
+            log_likelihood: pass.log_likelihood,
+            iterations,
+            samples: raw.len(),
+            window: config.window,
+            feature_mean,
+            feature_scale,
+        })
+    }
+
+    /// Regime of each state, calmest first
+    pub fn regimes(&self) -> Vec<MarketRegime> {
+        self.states.iter().map(|state| state.regime).collect()
+    }
+
+    /// Bars a state lasts on average once entered
+    pub fn expected_duration(&self, state: usize) -> f64 {
+        1.0 / (1.0 - self.transition[state][state]).max(f64::EPSILON)
+    }
+
+    /// Smoothed probabilities of each bar of `data` with a full window, given all of `data`
+    pub fn probabilities(&self, data: &[ForexDataPoint]) -> Vec<RegimeProbabilities> {
+        let (timestamps, emissions) = self.emissions(data);
+        if timestamps.is_empty() {
+            return Vec::new();
+        }
+        let pass = forward_backward(&self.initial, &self.transition, &emissions);
+        timestamps.into_iter().zip(pass.gamma).map(|(timestamp, states)| self.label(timestamp, states)).collect()
+    }
+
+    /// Probabilities of each bar of `data` with a full window, from the bars before it only
+    pub fn predicted_probabilities(&self, data: &[ForexDataPoint]) -> Vec<RegimeProbabilities> {
+        let (timestamps, emissions) = self.emissions(data);
+        if timestamps.is_empty() {
+            return Vec::new();
+        }
+        let (filtered, _) = forward(&self.initial, &self.transition, &emissions);
+        let mut predicted = self.initial.clone();
+        timestamps.into_iter().zip(filtered)
+            .map(|(timestamp, filtered)| {
+                let probabilities = self.label(timestamp, predicted.clone());
+                predicted = step(&filtered, &self.transition);
+                probabilities
+            })
+            .collect()
+    }
+
+    /// "3 regimes over 480 bars: ranging 0.081% (61%), trending 0.095% (27%), crisis 0.240% (12%)"
+    pub fn summary(&self) -> String {
+        let states: Vec<String> = self.states.iter()
+            .map(|state| format!("{} {:.3}% ({:.0}%)", state.regime.to_string().to_lowercase(), state.volatility * 100.0, state.occupancy * 100.0))
+            .collect();
+        format!("{} regimes over {} bars: {}", self.states.len(), self.samples, states.join(", "))
+    }
+
+    pub fn print_regimes(&self) {
+        println!("\n🌦️ Market Regimes ({} bars, log-likelihood {:.1}):", self.samples, self.log_likelihood);
+        println!("╔═══════╦══════════╦═══════════╦════════════╦═══════════╦══════════╗");
+        println!("║ State ║ Regime   ║   Trend   ║ Volatility ║ Occupancy ║ Duration ║");
+        println!("╠═══════╬══════════╬═══════════╬════════════╬═══════════╬══════════╣");
+        for (i, state) in self.states.iter().enumerate() {
+            println!("║ {:5} ║ {:8} ║ {:9.3} ║ {:9.3}% ║ {:8.1}% ║ {:8.1} ║",
+                     i, state.regime, state.trend, state.volatility * 100.0, state.occupancy * 100.0, self.expected_duration(i));
+        }
+        println!("╚═══════╩══════════╩═══════════╩════════════╩═══════════╩══════════╝");
+    }
+
+    fn emissions(&self, data: &[ForexDataPoint]) -> (Vec<DateTime<Utc>>, Emissions) {
+        let (timestamps, x): (Vec<DateTime<Utc>>, Vec<[f64; 2]>) = features(data, self.window).into_iter()
+            .map(|(timestamp, x)| (timestamp, standardise(x, self.feature_mean, self.feature_scale)))
+            .unzip();
+        let means: Vec<[f64; 2]> = self.states.iter().map(|state| state.mean).collect();
+        let variances: Vec<[f64; 2]> = self.states.iter().map(|state| state.variance).collect();
+        (timestamps, emissions(&x, &means, &variances))
+    }
+
+    fn label(&self, timestamp: DateTime<Utc>, states: Vec<f64>) -> RegimeProbabilities {
+        let (regime, probability) = [MarketRegime::Trending, MarketRegime::Ranging, MarketRegime::Crisis].into_iter()
+            .map(|regime| {
+                let p = self.states.iter().zip(&states).filter(|(state, _)| state.regime == regime).map(|(_, p)| p).sum::<f64>();
+                (regime, p)
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .expect("there are regimes");
+        RegimeProbabilities { timestamp, states, regime, probability }
+    }
+}
+
+/// Probabilities at the newest bar of `path` no later than `timestamp`
+pub fn regime_at(path: &[RegimeProbabilities], timestamp: DateTime<Utc>) -> Option<&RegimeProbabilities> {
+    let index = path.partition_point(|p| p.timestamp <= timestamp);
+    index.checked_sub(1).map(|i| &path[i])
+}
+
+/// Regime most bars of `path` from `start` to `end` were in, with their share; a span
+/// between two bars takes the regime at its end
+pub fn dominant_regime(path: &[RegimeProbabilities], start: DateTime<Utc>, end: DateTime<Utc>) -> Option<(MarketRegime, f64)> {
+    let span: Vec<&RegimeProbabilities> = path.iter().filter(|p| p.timestamp >= start && p.timestamp <= end).collect();
+    if span.is_empty() {
+        return regime_at(path, end).map(|p| (p.regime, 1.0));
+    }
+    [MarketRegime::Trending, MarketRegime::Ranging, MarketRegime::Crisis].into_iter()
+        .map(|regime| (regime, span.iter().filter(|p| p.regime == regime).count() as f64 / span.len() as f64))
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+/// State densities of each observation, each row divided by its largest entry, and
+/// the logs of those divisors
+struct Emissions {
+    densities: Vec<Vec<f64>>,
+    log_offsets: Vec<f64>,
+}
+
+struct ForwardBackward {
+    gamma: Vec<Vec<f64>>,
+    /// Expected transitions from state `i` to state `j`, summed over the bars
+    xi: Vec<Vec<f64>>,
+    log_likelihood: f64,
+}
+
+/// Trend and log volatility of the `window` returns ending at each bar that has them
+fn features(data: &[ForexDataPoint], window: usize) -> Vec<(DateTime<Utc>, [f64; 2])> {
+    let bars: Vec<&ForexDataPoint> = data.iter().filter(|bar| bar.close > 0.0).collect();
+    let returns: Vec<f64> = bars.windows(2).map(|w| (w[1].close / w[0].close).ln()).collect();
+    // returns[i] ends at bars[i + 1]
+    (window..=returns.len())
+        .map(|end| {
+            let slice = &returns[end - window..end];
+            let mean = slice.iter().sum::<f64>() / window as f64;
+            let std_dev = (slice.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / window as f64).sqrt();
+            let std_dev = std_dev.max(1e-12);
+            (bars[end].timestamp, [mean / std_dev, std_dev.ln()])
+        })
+        .collect()
+}
+
+fn standardise(x: [f64; 2], mean: [f64; 2], scale: [f64; 2]) -> [f64; 2] {
+    [(x[0] - mean[0]) / scale[0], (x[1] - mean[1]) / scale[1]]
+}
+
+/// Cluster means and variances, seeded with the point nearest the centre and then
+/// each point farthest from the seeds so far
+fn kmeans(x: &[[f64; 2]], k: usize) -> (Vec<[f64; 2]>, Vec<[f64; 2]>) {
+    let distance = |a: &[f64; 2], b: &[f64; 2]| (a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2);
+    let nearest = |point: &[f64; 2], centres: &[[f64; 2]]| {
+        (0..centres.len()).min_by(|&a, &b| distance(point, &centres[a]).total_cmp(&distance(point, &centres[b]))).unwrap_or(0)
+    };
+    let mut centres = vec![*x.iter().min_by(|a, b| distance(a, &[0.0; 2]).total_cmp(&distance(b, &[0.0; 2]))).expect("points to cluster")];
+    while centres.len() < k {
+        let farthest = x.iter()
+            .max_by(|a, b| {
+                let da = centres.iter().map(|c| distance(a, c)).fold(f64::INFINITY, f64::min);
+                let db = centres.iter().map(|c| distance(b, c)).fold(f64::INFINITY, f64::min);
+                da.total_cmp(&db)
+            })
+            .expect("points to cluster");
+        centres.push(*farthest);
+    }
+    let mut assignment = vec![0; x.len()];
+    for _ in 0..KMEANS_ITERATIONS {
+        let next: Vec<usize> = x.iter().map(|point| nearest(point, &centres)).collect();
+        let changed = next != assignment;
+        assignment = next;
+        for (c, centre) in centres.iter_mut().enumerate() {
+            let members: Vec<&[f64; 2]> = x.iter().zip(&assignment).filter(|(_, a)| **a == c).map(|(p, _)| p).collect();
+            if !members.is_empty() {
+                for d in 0..2 {
+                    centre[d] = members.iter().map(|p| p[d]).sum::<f64>() / members.len() as f64;
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    let variances = centres.iter().enumerate()
+        .map(|(c, centre)| {
+            let members: Vec<&[f64; 2]> = x.iter().zip(&assignment).filter(|(_, a)| **a == c).map(|(p, _)| p).collect();
+            if members.len() < 2 {
+                return [1.0; 2];
+            }
+            let variance = |d: usize| (members.iter().map(|p| (p[d] - centre[d]).powi(2)).sum::<f64>() / members.len() as f64).max(MIN_VARIANCE);
+            [variance(0), variance(1)]
+        })
+        .collect();
+    (centres, variances)
+}
+
+fn emissions(x: &[[f64; 2]], means: &[[f64; 2]], variances: &[[f64; 2]]) -> Emissions {
+    let (densities, log_offsets) = x.iter()
+        .map(|point| {
+            let log_densities: Vec<f64> = means.iter().zip(variances)
+                .map(|(mean, variance)| (0..2)
+                    .map(|d| -0.5 * ((2.0 * std::f64::consts::PI * variance[d]).ln() + (point[d] - mean[d]).powi(2) / variance[d]))
+                    .sum::<f64>())
+                .collect();
+            let offset = log_densities.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+            (log_densities.iter().map(|l| (l - offset).exp()).collect::<Vec<f64>>(), offset)
+        })
+        .unzip();
+    Emissions { densities, log_offsets }
+}
+
+/// State probabilities one bar on from `probabilities`
+fn step(probabilities: &[f64], transition: &[Vec<f64>]) -> Vec<f64> {
+    (0..probabilities.len())
+        .map(|j| probabilities.iter().zip(transition).map(|(p, row)| p * row[j]).sum())
+        .collect()
+}
+
+/// Filtered state probabilities of each bar and the scaling factor of each step
+fn forward(initial: &[f64], transition: &[Vec<f64>], emissions: &Emissions) -> (Vec<Vec<f64>>, Vec<f64>) {
+    let mut filtered: Vec<Vec<f64>> = Vec::with_capacity(emissions.densities.len());
+    let mut scales = Vec::with_capacity(emissions.densities.len());
+    for (t, density) in emissions.densities.iter().enumerate() {
+        let prior = if t == 0 { initial.to_vec() } else { step(&filtered[t - 1], transition) };
+        let mut alpha: Vec<f64> = prior.iter().zip(density).map(|(p, b)| p * b).collect();
+        let scale = alpha.iter().sum::<f64>().max(f64::MIN_POSITIVE);
+        alpha.iter_mut().for_each(|a| *a /= scale);
+        filtered.push(alpha);
+        scales.push(scale);
+    }
+    (filtered, scales)
+}
+
+fn forward_backward(initial: &[f64], transition: &[Vec<f64>], emissions: &Emissions) -> ForwardBackward {
+    let (alpha, scales) = forward(initial, transition, emissions);
+    let n = alpha.len();
+    let k = initial.len();
+    let log_likelihood = scales.iter().map(|c| c.ln()).sum::<f64>() + emissions.log_offsets.iter().sum::<f64>();
+
+    let mut beta = vec![vec![1.0; k]; n];
+    let mut xi = vec![vec![0.0; k]; k];
+    for t in (0..n.saturating_sub(1)).rev() {
+        let next: Vec<f64> = (0..k).map(|j| emissions.densities[t + 1][j] * beta[t + 1][j] / scales[t + 1]).collect();
+        for i in 0..k {
+            beta[t][i] = (0..k).map(|j| transition[i][j] * next[j]).sum();
+            for j in 0..k {
+                xi[i][j] += alpha[t][i] * transition[i][j] * next[j];
+            }
+        }
+    }
+    let gamma = alpha.iter().zip(&beta)
+        .map(|(a, b)| {
+            let g: Vec<f64> = a.iter().zip(b).map(|(a, b)| a * b).collect();
+            let total = g.iter().sum::<f64>().max(f64::MIN_POSITIVE);
+            g.into_iter().map(|g| g / total).collect()
+        })
+        .collect();
+    ForwardBackward { gamma, xi, log_likelihood }
+}
+
+/// Name states sorted from the calmest: with three or more the most volatile is a
+/// crisis, and of the rest the one whose trend strays least from the history's
+/// average, in either direction, is ranging
+fn name_states(states: &mut [RegimeState]) {
+    let calm = if states.len() >= 3 { states.len() - 1 } else { states.len() };
+    if calm < states.len() {
+        states[calm].regime = MarketRegime::Crisis;
+    }
+    let straying = |state: &RegimeState| state.mean[0].powi(2) + state.variance[0];
+    let ranging = (0..calm).min_by(|&a, &b| straying(&states[a]).total_cmp(&straying(&states[b]))).unwrap_or(0);
+    for (i, state) in states[..calm].iter_mut().enumerate() {
+        state.regime = if i == ranging { MarketRegime::Ranging } else { MarketRegime::Trending };
+    }
+}
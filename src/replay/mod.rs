@@ -0,0 +1,268 @@
+//! # Session Replay
+//!
+//! Records every input a live session's decisions depend on, one JSON line per
+//! pair update or fill, and replays the log through the same decision code to
+//! reproduce the session's actions bit for bit.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::anomaly::DetectedAnomaly;
+use crate::backtest::strategy::Fill;
+use crate::data::ForexDataPoint;
+use crate::laplacian_rl::TradingAction;
+use crate::multi_currency::{CurrencyPairConfig, CurrencyPairState, PairAccount, PairDecision};
+use crate::patterns::HiddenCycle;
+
+/// One line of a session log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum SessionEvent {
+    Update(Box<PairUpdate>),
+    Fill {
+        symbol: String,
+        /// Position of the filled action in the pair's last update
+        index: usize,
+        fill: Fill,
+        account: PairAccount,
+    },
+}
+
+/// Inputs and actions of one pair update
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairUpdate {
+    pub symbol: String,
+    /// Wall-clock time of the update
+    pub recorded_at: DateTime<Utc>,
+    /// Pair configuration and bar timeframe, on the pair's first update
+    pub setup: Option<PairSetup>,
+    /// Bars appended since the pair's previous update
+    pub bars: Vec<ForexDataPoint>,
+    /// Length of the history once `bars` are appended
+    pub history_len: usize,
+    /// Cycles, when they changed since the previous update
+    pub cycles: Option<Vec<HiddenCycle>>,
+    pub strategies: Vec<String>,
+    pub trade_count: u64,
+    /// Anomaly detector sensitivity at the time, for reference
+    pub sensitivity: f64,
+    /// RL exploration rate before the decision, which drift resets move
+    #[serde(default)]
+    pub exploration_rate: Option<f64>,
+    pub account: PairAccount,
+    pub seed: u64,
+    pub anomalies: Vec<DetectedAnomaly>,
+    pub actions: Vec<TradingAction>,
+}
+
+/// What is needed to rebuild a pair for replay
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairSetup {
+    pub config: CurrencyPairConfig,
+    pub timeframe: String,
+}
+
+/// How far the log has followed a pair
+#[derive(Default)]
+struct PairCursor {
+    last_bar: Option<DateTime<Utc>>,
+    /// Serialized cycles last recorded
+    cycles: Option<String>,
+}
+
+/// Append-only session log writer, shared by all pairs
+pub struct SessionLog {
+    path: PathBuf,
+    cursors: Mutex<HashMap<String, PairCursor>>,
+}
+
+impl SessionLog {
+    /// Session log appending to `path`
+    pub fn with_file(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        Ok(Self { path: path.to_path_buf(), cursors: Mutex::new(HashMap::new()) })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Record a pair update decided from `seed`; write failures are reported, not raised
+    pub fn record_update(&self, pair: &CurrencyPairState, account: &PairAccount, seed: u64, decision: &PairDecision) {
+        let mut cursors = self.cursors.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let first = !cursors.contains_key(&pair.config.symbol);
+        let cursor = cursors.entry(pair.config.symbol.clone()).or_default();
+        let bars: Vec<ForexDataPoint> = pair.historical_data.iter()
+            .filter(|bar| cursor.last_bar.is_none_or(|last| bar.timestamp > last))
+            .cloned()
+            .collect();
+        let cycles = serde_json::to_string(&pair.cycles).unwrap_or_default();
+        let update = PairUpdate {
+            symbol: pair.config.symbol.clone(),
+            recorded_at: Utc::now(),
+            setup: first.then(|| PairSetup { config: pair.config.clone(), timeframe: pair.backfill_config.timeframe.clone() }),
+            bars,
+            history_len: pair.historical_data.len(),
+            cycles: (cursor.cycles.as_ref() != Some(&cycles)).then(|| pair.cycles.clone()),
+            strategies: pair.strategy_names().iter().map(|name| name.to_string()).collect(),
+            trade_count: pair.performance.total_trades,
+            sensitivity: pair.anomaly_detector.sensitivity_threshold(),
+            exploration_rate: Some(pair.rl_agent.exploration_rate()),
+            account: *account,
+            seed,
+            anomalies: decision.anomalies.clone(),
+            actions: decision.actions.clone(),
+        };
+        match self.append(&SessionEvent::Update(Box::new(update))) {
+            Ok(()) => {
+                cursor.last_bar = pair.historical_data.last().map(|bar| bar.timestamp).or(cursor.last_bar);
+                cursor.cycles = Some(cycles);
+            }
+            Err(e) => {
+                println!("⚠️  Failed to write session log {}: {}", self.path.display(), e);
+                // Repeat the setup and bars with the next update
+                if first {
+                    cursors.remove(&pair.config.symbol);
+                }
+            }
+        }
+    }
+
+    /// Record a fill reported to the pair's strategies
+    pub fn record_fill(&self, symbol: &str, index: usize, fill: &Fill, account: &PairAccount) {
+        let event = SessionEvent::Fill { symbol: symbol.to_string(), index, fill: fill.clone(), account: *account };
+        if let Err(e) = self.append(&event) {
+            println!("⚠️  Failed to write session log {}: {}", self.path.display(), e);
+        }
+    }
+
+    fn append(&self, event: &SessionEvent) -> Result<()> {
+        let mut file = std::fs::OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(event)?)?;
+        Ok(())
+    }
+}
+
+/// Read every event of a session log, oldest first
+pub fn read_session(path: &Path) -> Result<Vec<SessionEvent>> {
+    let file = std::fs::File::open(path).with_context(|| format!("cannot open session log {}", path.display()))?;
+    let mut events = Vec::new();
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let event = serde_json::from_str(&line).with_context(|| format!("{}:{}: bad session event", path.display(), index + 1))?;
+        events.push(event);
+    }
+    Ok(events)
+}
+
+/// A replayed update whose outcome differs from the recorded one
+#[derive(Debug, Clone, Serialize)]
+pub struct Divergence {
+    /// Line of the update in the log
+    pub line: usize,
+    pub symbol: String,
+    pub recorded_at: DateTime<Utc>,
+    pub reason: String,
+    pub recorded: Vec<TradingAction>,
+    pub replayed: Vec<TradingAction>,
+}
+
+/// Outcome of replaying a session log
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayReport {
+    pub pairs: Vec<String>,
+    pub updates: usize,
+    pub fills: usize,
+    /// Actions recorded over all updates
+    pub actions: usize,
+    pub divergences: Vec<Divergence>,
+}
+
+impl ReplayReport {
+    pub fn is_identical(&self) -> bool {
+        self.divergences.is_empty()
+    }
+}
+
+/// Rebuild each pair from its first update and feed it the recorded inputs,
+/// comparing every decision with the recorded actions
+pub async fn replay_session(path: &Path) -> Result<ReplayReport> {
+    let events = read_session(path)?;
+    let mut pairs: HashMap<String, CurrencyPairState> = HashMap::new();
+    let mut report = ReplayReport { pairs: Vec::new(), updates: 0, fills: 0, actions: 0, divergences: Vec::new() };
+
+    for (index, event) in events.into_iter().enumerate() {
+        let line = index + 1;
+        match event {
+            SessionEvent::Update(update) => {
+                let update = *update;
+                if !pairs.contains_key(&update.symbol) {
+                    let Some(setup) = &update.setup else {
+                        bail!("line {}: first update of {} has no pair setup; the log must start with the session", line, update.symbol);
+                    };
+                    let mut state = CurrencyPairState::new(setup.config.clone()).await?;
+                    state.backfill_config.timeframe = setup.timeframe.clone();
+                    report.pairs.push(update.symbol.clone());
+                    pairs.insert(update.symbol.clone(), state);
+                }
+                let state = pairs.get_mut(&update.symbol).expect("pair rebuilt");
+                if state.strategy_names() != update.strategies {
+                    bail!("line {}: {} traded with {:?}, the configuration builds {:?}; strategies set from code cannot be replayed",
+                          line, update.symbol, update.strategies, state.strategy_names());
+                }
+                for bar in update.bars {
+                    if state.historical_data.last().is_none_or(|last| bar.timestamp > last.timestamp) {
+                        state.historical_data.push(bar);
+                    }
+                }
+                if let Some(cycles) = update.cycles {
+                    state.cycles = cycles;
+                }
+                state.performance.total_trades = update.trade_count;
+                if let Some(rate) = update.exploration_rate {
+                    state.rl_agent.set_exploration_rate(rate);
+                }
+
+                let replayed = state.decide(&update.anomalies, &update.account, update.seed).await?;
+                let reason = if state.historical_data.len() != update.history_len {
+                    Some(format!("history has {} bars, {} recorded", state.historical_data.len(), update.history_len))
+                } else if replayed != update.actions {
+                    Some("actions differ".to_string())
+                } else {
+                    None
+                };
+                if let Some(reason) = reason {
+                    report.divergences.push(Divergence {
+                        line,
+                        symbol: update.symbol,
+                        recorded_at: update.recorded_at,
+                        reason,
+                        recorded: update.actions.clone(),
+                        replayed,
+                    });
+                }
+                report.updates += 1;
+                report.actions += update.actions.len();
+            }
+            SessionEvent::Fill { symbol, index, fill, account } => {
+                let Some(state) = pairs.get_mut(&symbol) else {
+                    bail!("line {}: fill for {} before its first update", line, symbol);
+                };
+                state.record_fill(index, fill, &account).await;
+                report.fills += 1;
+            }
+        }
+    }
+
+    Ok(report)
+}
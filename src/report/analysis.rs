@@ -0,0 +1,190 @@
+//! # Analysis Reports
+//!
+//! Self-contained HTML pages for `analyze` and `decompose` runs: the charts as
+//! inline SVG, tables of the symmetries, cycles or components, and the validation
+//! metrics, so results can be reviewed in a browser without the JSON.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use super::escape_html;
+use crate::data::ForexDataPoint;
+use crate::patterns::{CycleDecomposition, HiddenCycle};
+use crate::regimes::{self, RegimeProbabilities};
+use crate::symmetry::TemporalSymmetry;
+use crate::visualization::charts;
+
+/// Summary scores of an analysis run
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ValidationMetrics {
+    pub symmetry_strength_avg: f64,
+    pub cycle_confidence_avg: f64,
+    /// Mean of the two averages above
+    pub pattern_consistency: f64,
+}
+
+impl ValidationMetrics {
+    pub fn compute(symmetries: &[TemporalSymmetry], cycles: &[HiddenCycle]) -> Self {
+        let symmetry_strength_avg = symmetries.iter().map(|s| s.strength).sum::<f64>() / symmetries.len().max(1) as f64;
+        let cycle_confidence_avg = cycles.iter().map(|c| c.confidence).sum::<f64>() / cycles.len().max(1) as f64;
+        Self {
+            symmetry_strength_avg,
+            cycle_confidence_avg,
+            pattern_consistency: (symmetry_strength_avg + cycle_confidence_avg) / 2.0,
+        }
+    }
+}
+
+/// HTML report of the symmetries and cycles found in `data`, each symmetry annotated
+/// with the regime of `regimes` it was found in (empty when no model was fitted)
+pub fn render_analysis_html(
+    pair: &str,
+    timeframe: &str,
+    data: &[ForexDataPoint],
+    symmetries: &[TemporalSymmetry],
+    cycles: &[HiddenCycle],
+    regimes: &[RegimeProbabilities],
+) -> String {
+    let mut body = format!("<h1>Pattern Analysis: {} ({})</h1>\n", escape_html(pair), escape_html(timeframe));
+    let range = match (data.first(), data.last()) {
+        (Some(first), Some(last)) => format!("{} to {}", first.timestamp.format("%Y-%m-%d"), last.timestamp.format("%Y-%m-%d")),
+        _ => "no data".to_string(),
+    };
+    let (low, high) = data.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), p| (low.min(p.close), high.max(p.close)));
+    body.push_str(&format!("<p>{} bars, {}, closes {} to {}. Generated {}.</p>\n",
+        data.len(), range, format_price(low), format_price(high), Utc::now().format("%Y-%m-%d %H:%M UTC")));
+
+    let metrics = ValidationMetrics::compute(symmetries, cycles);
+    body.push_str("<h2>Validation Metrics</h2>\n<table>\n<tr><th>Metric</th><th>Value</th></tr>\n");
+    body.push_str(&format!("<tr><td>Temporal symmetries</td><td>{}</td></tr>\n", symmetries.len()));
+    body.push_str(&format!("<tr><td>Hidden cycles</td><td>{}</td></tr>\n", cycles.len()));
+    body.push_str(&format!("<tr><td>Average symmetry strength</td><td>{:.3}</td></tr>\n", metrics.symmetry_strength_avg));
+    body.push_str(&format!("<tr><td>Average cycle confidence</td><td>{:.3}</td></tr>\n", metrics.cycle_confidence_avg));
+    body.push_str(&format!("<tr><td>Pattern consistency</td><td>{:.3}</td></tr>\n</table>\n", metrics.pattern_consistency));
+
+    body.push_str("<h2>Price and Reconstructed Cycles</h2>\n");
+    body.push_str(&embed(charts::price_cycles_svg(data, cycles)));
+    body.push_str("<h2>Spectral Power</h2>\n");
+    body.push_str(&embed(charts::spectrum_svg(data, cycles)));
+
+    body.push_str(&format!("<h2>Hidden Cycles ({})</h2>\n", cycles.len()));
+    if cycles.is_empty() {
+        body.push_str("<p>No cycles detected.</p>\n");
+    } else {
+        body.push_str("<table>\n<tr><th>Cycle</th><th>Period (bars)</th><th>Confidence</th><th>Amplitude</th><th>Phase (°)</th><th>p-value</th></tr>\n");
+        let mut sorted: Vec<&HiddenCycle> = cycles.iter().collect();
+        sorted.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+        for cycle in sorted {
+            let p_value = cycle.p_value.map_or("—".to_string(), |p| format!("{:.3}", p));
+            body.push_str(&format!("<tr><td>{}</td><td>{}</td><td>{:.3}</td><td>{:.5}</td><td>{:.1}</td><td>{}</td></tr>\n",
+                escape_html(&cycle.name), cycle.period, cycle.confidence, cycle.amplitude, cycle.phase.to_degrees(), p_value));
+        }
+        body.push_str("</table>\n");
+    }
+
+    body.push_str(&format!("<h2>Temporal Symmetries ({})</h2>\n", symmetries.len()));
+    body.push_str(&embed(charts::symmetry_mirrors_svg(data, symmetries)));
+    if symmetries.is_empty() {
+        body.push_str("<p>No symmetries detected.</p>\n");
+    } else {
+        body.push_str("<table>\n<tr><th>Symmetry</th><th>Type</th><th>Period (days)</th><th>Strength</th>\
+                       <th>Confidence</th><th>Validation score</th><th>Mirror points</th><th>Regime</th></tr>\n");
+        let mut sorted: Vec<&TemporalSymmetry> = symmetries.iter().collect();
+        sorted.sort_by(|a, b| b.strength.total_cmp(&a.strength));
+        for symmetry in sorted {
+            let regime = symmetry_regime(symmetry, data, regimes)
+                .map_or("—".to_string(), |(regime, share)| format!("{} ({:.0}%)", regime, share * 100.0));
+            body.push_str(&format!("<tr><td>{}</td><td>{}</td><td>{}</td><td>{:.3}</td><td>{:.3}</td><td>{:.3}</td><td>{}</td><td>{}</td></tr>\n",
+                escape_html(&symmetry.name), escape_html(&symmetry.symmetry_type), symmetry.period_days,
+                symmetry.strength, symmetry.confidence, symmetry.validation_score, symmetry.mirror_points.len(), regime));
+        }
+        body.push_str("</table>\n");
+    }
+
+    page(&format!("Pattern Analysis {} {}", pair, timeframe), &body)
+}
+
+/// HTML report of a cycle decomposition
+pub fn render_decomposition_html(title: &str, decomposition: &CycleDecomposition) -> String {
+    let mut body = format!("<h1>Cycle Decomposition: {}</h1>\n", escape_html(title));
+    let range = match (decomposition.timestamps.first(), decomposition.timestamps.last()) {
+        (Some(first), Some(last)) => format!("{} to {}", first.format("%Y-%m-%d"), last.format("%Y-%m-%d")),
+        _ => "no data".to_string(),
+    };
+    body.push_str(&format!("<p>{} bars, {}. Residual σ {:.5}. Generated {}.</p>\n",
+        decomposition.timestamps.len(), range, decomposition.residual_std(), Utc::now().format("%Y-%m-%d %H:%M UTC")));
+
+    body.push_str("<h2>Components</h2>\n<table>\n<tr><th>Period (days)</th><th>Amplitude</th><th>Phase (°)</th><th>Strength</th></tr>\n");
+    for period in decomposition.periods() {
+        let component = &decomposition.components[&period];
+        body.push_str(&format!("<tr><td>{}</td><td>{:.5}</td><td>{:.1}</td><td>{:.3}</td></tr>\n",
+            period, component.amplitude, component.phase_degrees, component.strength));
+    }
+    let explained: f64 = decomposition.components.values().map(|c| c.strength).sum();
+    body.push_str(&format!("<tr><td>All components</td><td></td><td></td><td>{:.3}</td></tr>\n</table>\n", explained));
+
+    body.push_str("<h2>Decomposition</h2>\n");
+    body.push_str(&embed(charts::decomposition_svg(decomposition)));
+
+    page(&format!("Cycle Decomposition {}", title), &body)
+}
+
+/// Write `<PAIR>_<TIMEFRAME>_report.html` to `output_dir`
+pub fn write_analysis_html(
+    output_dir: &Path,
+    pair: &str,
+    timeframe: &str,
+    data: &[ForexDataPoint],
+    symmetries: &[TemporalSymmetry],
+    cycles: &[HiddenCycle],
+    regimes: &[RegimeProbabilities],
+) -> Result<PathBuf> {
+    std::fs::create_dir_all(output_dir)?;
+    let path = output_dir.join(format!("{}_{}_report.html", pair, timeframe));
+    std::fs::write(&path, render_analysis_html(pair, timeframe, data, symmetries, cycles, regimes))?;
+    Ok(path)
+}
+
+/// Regime most of the bars a symmetry spans were in: from its first to its last
+/// mirror point, or the last three periods of `data` for a cycle, which has none
+fn symmetry_regime(
+    symmetry: &TemporalSymmetry,
+    data: &[ForexDataPoint],
+    regimes: &[RegimeProbabilities],
+) -> Option<(regimes::MarketRegime, f64)> {
+    let to_time = |seconds: f64| DateTime::from_timestamp(seconds as i64, 0);
+    let (start, end) = match (symmetry.mirror_points.first(), symmetry.mirror_points.last()) {
+        (Some(first), Some(last)) => (to_time(first.0)?, to_time(last.0)?),
+        _ => {
+            let end = data.last()?.timestamp;
+            let start = data[data.len().saturating_sub(3 * symmetry.period_days as usize + 1)].timestamp;
+            (start, end)
+        }
+    };
+    regimes::dominant_regime(regimes, start, end)
+}
+
+/// Inline a rendered chart, or say why there is none
+fn embed(chart: Result<String>) -> String {
+    match chart {
+        Ok(svg) => format!("<div class=\"chart\">{}</div>\n", svg),
+        Err(e) => format!("<p><em>Chart unavailable: {}.</em></p>\n", escape_html(&e.to_string())),
+    }
+}
+
+fn format_price(price: f64) -> String {
+    if price.is_finite() { format!("{:.5}", price) } else { "n/a".to_string() }
+}
+
+fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n\
+         <style>body{{font-family:sans-serif;margin:2em;}}table{{border-collapse:collapse;margin-bottom:1em;}}\
+         th,td{{border:1px solid #ccc;padding:4px 8px;text-align:left;}}th{{background:#f0f0f0;}}\
+         .chart svg{{max-width:100%;height:auto;border:1px solid #eee;}}</style>\n\
+         </head>\n<body>\n{}</body>\n</html>\n",
+        escape_html(title), body
+    )
+}
@@ -0,0 +1,430 @@
+//! # Weekly Research Digest
+//!
+//! Diff a week's detected cycles, symmetries and anomaly statistics against the
+//! week before, per pair: structures that emerged or vanished, those whose strength
+//! moved, and how the anomaly mix shifted. Each week's snapshot is saved next to the
+//! Markdown digest so the following week diffs against exactly what was reported.
+
+use anyhow::Result;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::path::PathBuf;
+
+use super::anomaly_type_name;
+use crate::anomaly::{AnomalySeverity, DetectedAnomaly};
+use crate::patterns::HiddenCycle;
+use crate::symmetry::TemporalSymmetry;
+
+/// Weekly digest configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DigestConfig {
+    /// Directory the digests and weekly snapshots are written to
+    pub output_directory: PathBuf,
+    /// Relative period difference within which two weeks' structures are the same one
+    pub period_tolerance: f64,
+    /// Smallest strength change reported as strengthened or weakened
+    pub min_strength_change: f64,
+    /// Day of the week on which the scheduler compiles the digest
+    pub schedule_weekday: Weekday,
+    /// UTC hour at which the scheduler compiles the digest
+    pub schedule_hour_utc: u32,
+}
+
+impl Default for DigestConfig {
+    fn default() -> Self {
+        Self {
+            output_directory: PathBuf::from("reports"),
+            period_tolerance: 0.1,
+            min_strength_change: 0.05,
+            schedule_weekday: Weekday::Sat, // Markets are closed and the week's bars are final
+            schedule_hour_utc: 6,
+        }
+    }
+}
+
+/// Anomaly statistics of one pair over a week
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AnomalyStatistics {
+    pub count: usize,
+    pub high_severity: usize,
+    pub mean_confidence: f64,
+    pub by_type: BTreeMap<String, usize>,
+}
+
+impl AnomalyStatistics {
+    pub fn from_anomalies(anomalies: &[DetectedAnomaly]) -> Self {
+        let mut by_type = BTreeMap::new();
+        for anomaly in anomalies {
+            *by_type.entry(anomaly_type_name(&anomaly.anomaly_type).to_string()).or_insert(0) += 1;
+        }
+        Self {
+            count: anomalies.len(),
+            high_severity: anomalies.iter()
+                .filter(|a| matches!(a.severity, AnomalySeverity::High | AnomalySeverity::Critical))
+                .count(),
+            mean_confidence: anomalies.iter().map(|a| a.confidence).sum::<f64>() / anomalies.len().max(1) as f64,
+            by_type,
+        }
+    }
+}
+
+/// What was detected on one pair by the end of a week
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PairSnapshot {
+    pub symmetries: Vec<TemporalSymmetry>,
+    pub cycles: Vec<HiddenCycle>,
+    pub anomalies: AnomalyStatistics,
+}
+
+/// Detections of every pair for the week starting on `week_start` (a Monday)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeeklySnapshot {
+    pub week_start: NaiveDate,
+    pub pairs: BTreeMap<String, PairSnapshot>,
+}
+
+impl WeeklySnapshot {
+    pub fn new(week_start: NaiveDate) -> Self {
+        Self { week_start, pairs: BTreeMap::new() }
+    }
+}
+
+/// Kind of structure a change concerns
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum StructureKind {
+    Cycle,
+    Symmetry,
+}
+
+/// How a structure changed from one week to the next
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ChangeStatus {
+    Emerged,
+    Vanished,
+    Strengthened,
+    Weakened,
+    Stable,
+}
+
+/// A cycle or symmetry compared across two weeks; strength is a cycle's confidence
+/// or a symmetry's strength
+#[derive(Debug, Clone, Serialize)]
+pub struct StructureChange {
+    pub pair: String,
+    pub kind: StructureKind,
+    pub name: String,
+    pub period: u32,
+    pub previous_strength: Option<f64>,
+    pub current_strength: Option<f64>,
+    pub status: ChangeStatus,
+}
+
+impl StructureChange {
+    /// Strength gained since last week, counting a missing week as zero
+    pub fn strength_change(&self) -> f64 {
+        self.current_strength.unwrap_or(0.0) - self.previous_strength.unwrap_or(0.0)
+    }
+}
+
+/// Anomaly statistics of one pair in both weeks
+#[derive(Debug, Clone, Serialize)]
+pub struct AnomalyShift {
+    pub pair: String,
+    pub previous: AnomalyStatistics,
+    pub current: AnomalyStatistics,
+}
+
+/// Compiled weekly digest
+#[derive(Debug, Clone, Serialize)]
+pub struct WeeklyDigest {
+    pub week_start: NaiveDate,
+    pub previous_week_start: Option<NaiveDate>,
+    pub generated_at: DateTime<Utc>,
+    pub changes: Vec<StructureChange>,
+    pub anomaly_shifts: Vec<AnomalyShift>,
+}
+
+impl WeeklyDigest {
+    pub fn with_status(&self, status: ChangeStatus) -> impl Iterator<Item = &StructureChange> {
+        self.changes.iter().filter(move |change| change.status == status)
+    }
+}
+
+/// A cycle or symmetry reduced to what is compared across weeks
+struct Structure<'a> {
+    name: &'a str,
+    /// Symmetry type; cycles only match cycles
+    family: &'a str,
+    period: u32,
+    strength: f64,
+}
+
+impl<'a> Structure<'a> {
+    fn cycles(snapshot: &'a PairSnapshot) -> Vec<Self> {
+        snapshot.cycles.iter()
+            .map(|c| Structure { name: &c.name, family: "cycle", period: c.period, strength: c.confidence })
+            .collect()
+    }
+
+    fn symmetries(snapshot: &'a PairSnapshot) -> Vec<Self> {
+        snapshot.symmetries.iter()
+            .map(|s| Structure { name: &s.name, family: &s.symmetry_type, period: s.period_days, strength: s.strength })
+            .collect()
+    }
+}
+
+/// Weekly digest generator
+pub struct DigestGenerator {
+    config: DigestConfig,
+}
+
+impl DigestGenerator {
+    pub fn new(config: DigestConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn config(&self) -> &DigestConfig {
+        &self.config
+    }
+
+    /// Compare `current` against `previous`; without a previous week everything emerged
+    pub fn compile(&self, current: &WeeklySnapshot, previous: Option<&WeeklySnapshot>) -> WeeklyDigest {
+        let empty = PairSnapshot::default();
+        let mut changes = Vec::new();
+        let mut anomaly_shifts = Vec::new();
+
+        let mut pairs: Vec<&String> = current.pairs.keys().collect();
+        if let Some(previous) = previous {
+            pairs.extend(previous.pairs.keys().filter(|pair| !current.pairs.contains_key(*pair)));
+        }
+        pairs.sort();
+
+        for pair in pairs {
+            let now = current.pairs.get(pair).unwrap_or(&empty);
+            let before = previous.and_then(|p| p.pairs.get(pair)).unwrap_or(&empty);
+
+            changes.extend(self.diff(pair, StructureKind::Cycle, &Structure::cycles(before), &Structure::cycles(now)));
+            changes.extend(self.diff(pair, StructureKind::Symmetry, &Structure::symmetries(before), &Structure::symmetries(now)));
+
+            anomaly_shifts.push(AnomalyShift {
+                pair: pair.clone(),
+                previous: before.anomalies.clone(),
+                current: now.anomalies.clone(),
+            });
+        }
+
+        // Biggest movers first within each pair and kind
+        changes.sort_by(|a, b| (&a.pair, a.kind as u8).cmp(&(&b.pair, b.kind as u8))
+            .then(b.strength_change().abs().total_cmp(&a.strength_change().abs())));
+
+        WeeklyDigest {
+            week_start: current.week_start,
+            previous_week_start: previous.map(|p| p.week_start),
+            generated_at: Utc::now(),
+            changes,
+            anomaly_shifts,
+        }
+    }
+
+    /// Match each current structure to the closest-period unmatched one of the same
+    /// family last week, strongest first
+    fn diff(&self, pair: &str, kind: StructureKind, before: &[Structure], now: &[Structure]) -> Vec<StructureChange> {
+        let mut order: Vec<usize> = (0..now.len()).collect();
+        order.sort_by(|a, b| now[*b].strength.total_cmp(&now[*a].strength));
+        let mut matched = vec![false; before.len()];
+        let mut changes = Vec::new();
+
+        for i in order {
+            let structure = &now[i];
+            let tolerance = (structure.period as f64 * self.config.period_tolerance).max(1.0);
+            let twin = before.iter().enumerate()
+                .filter(|(j, old)| !matched[*j] && old.family == structure.family)
+                .filter(|(_, old)| (old.period as f64 - structure.period as f64).abs() <= tolerance)
+                .min_by_key(|(_, old)| old.period.abs_diff(structure.period));
+
+            let (previous_strength, status) = match twin {
+                Some((j, old)) => {
+                    matched[j] = true;
+                    let change = structure.strength - old.strength;
+                    let status = if change >= self.config.min_strength_change {
+                        ChangeStatus::Strengthened
+                    } else if change <= -self.config.min_strength_change {
+                        ChangeStatus::Weakened
+                    } else {
+                        ChangeStatus::Stable
+                    };
+                    (Some(old.strength), status)
+                }
+                None => (None, ChangeStatus::Emerged),
+            };
+            changes.push(StructureChange {
+                pair: pair.to_string(),
+                kind,
+                name: structure.name.to_string(),
+                period: structure.period,
+                previous_strength,
+                current_strength: Some(structure.strength),
+                status,
+            });
+        }
+
+        for (old, _) in before.iter().zip(&matched).filter(|(_, matched)| !**matched) {
+            changes.push(StructureChange {
+                pair: pair.to_string(),
+                kind,
+                name: old.name.to_string(),
+                period: old.period,
+                previous_strength: Some(old.strength),
+                current_strength: None,
+                status: ChangeStatus::Vanished,
+            });
+        }
+        changes
+    }
+
+    /// Render digest as Markdown
+    pub fn render_markdown(&self, digest: &WeeklyDigest) -> String {
+        let mut md = String::new();
+
+        md.push_str(&format!("# Research Digest — week of {}\n\n", digest.week_start));
+        md.push_str(&format!("_Generated at {}_\n\n", digest.generated_at.format("%Y-%m-%d %H:%M:%S UTC")));
+        match digest.previous_week_start {
+            Some(previous) => md.push_str(&format!("Compared with the week of {}.\n\n", previous)),
+            None => md.push_str("No earlier week on record; every structure is listed as new.\n\n"),
+        }
+
+        let count = |status| digest.with_status(status).count();
+        md.push_str("| Emerged | Vanished | Strengthened | Weakened | Stable |\n|---|---|---|---|---|\n");
+        md.push_str(&format!("| {} | {} | {} | {} | {} |\n\n",
+            count(ChangeStatus::Emerged), count(ChangeStatus::Vanished),
+            count(ChangeStatus::Strengthened), count(ChangeStatus::Weakened), count(ChangeStatus::Stable)));
+
+        let sections = [
+            (ChangeStatus::Emerged, "Newly Emerged", "Nothing new this week."),
+            (ChangeStatus::Vanished, "Vanished", "Every structure from last week is still present."),
+            (ChangeStatus::Strengthened, "Strengthened", "No structure gained strength."),
+            (ChangeStatus::Weakened, "Weakened", "No structure lost strength."),
+        ];
+        for (status, title, none) in sections {
+            md.push_str(&format!("## {}\n\n", title));
+            let changes: Vec<&StructureChange> = digest.with_status(status).collect();
+            if changes.is_empty() {
+                md.push_str(&format!("{}\n\n", none));
+                continue;
+            }
+            md.push_str("| Pair | Kind | Name | Period | Last week | This week | Change |\n|---|---|---|---|---|---|---|\n");
+            for change in changes {
+                let strength = |s: Option<f64>| s.map(|s| format!("{:.3}", s)).unwrap_or_else(|| "-".to_string());
+                md.push_str(&format!("| {} | {:?} | {} | {} | {} | {} | {:+.3} |\n",
+                    change.pair, change.kind, change.name, change.period,
+                    strength(change.previous_strength), strength(change.current_strength),
+                    change.strength_change()));
+            }
+            md.push('\n');
+        }
+
+        md.push_str("## Anomaly Statistics\n\n");
+        if digest.anomaly_shifts.is_empty() {
+            md.push_str("No pairs analyzed.\n");
+        } else {
+            md.push_str("| Pair | Anomalies | Change | High/Critical | Mean confidence | Most frequent |\n|---|---|---|---|---|---|\n");
+            for shift in &digest.anomaly_shifts {
+                let most_frequent = shift.current.by_type.iter()
+                    .max_by_key(|(_, count)| **count)
+                    .map(|(kind, count)| format!("{} ({})", kind, count))
+                    .unwrap_or_else(|| "-".to_string());
+                md.push_str(&format!("| {} | {} | {:+} | {} | {:.3} | {} |\n",
+                    shift.pair, shift.current.count,
+                    shift.current.count as i64 - shift.previous.count as i64,
+                    shift.current.high_severity, shift.current.mean_confidence, most_frequent));
+            }
+        }
+
+        md
+    }
+
+    /// Where the snapshot of the week starting on `week_start` is kept
+    pub fn snapshot_path(&self, week_start: NaiveDate) -> PathBuf {
+        self.config.output_directory.join(format!("digest_{}.snapshot.json", week_start))
+    }
+
+    /// Load a saved weekly snapshot, if one was written
+    pub fn load_snapshot(&self, week_start: NaiveDate) -> Result<Option<WeeklySnapshot>> {
+        let path = self.snapshot_path(week_start);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_str(&std::fs::read_to_string(path)?)?))
+    }
+
+    /// Write the digest and this week's snapshot to the output directory
+    pub fn write(&self, digest: &WeeklyDigest, snapshot: &WeeklySnapshot) -> Result<PathBuf> {
+        std::fs::create_dir_all(&self.config.output_directory)?;
+        std::fs::write(self.snapshot_path(snapshot.week_start), serde_json::to_string_pretty(snapshot)?)?;
+        let path = self.config.output_directory.join(format!("digest_{}.md", digest.week_start));
+        std::fs::write(&path, self.render_markdown(digest))?;
+        Ok(path)
+    }
+
+    /// Compile and write the digest of `week_start`, diffing against the saved snapshot
+    /// of the week before or, when none was saved, the one `collect` produces
+    pub async fn generate<F, Fut>(&self, week_start: NaiveDate, mut collect: F) -> Result<WeeklyDigest>
+    where
+        F: FnMut(NaiveDate) -> Fut,
+        Fut: Future<Output = Result<WeeklySnapshot>>,
+    {
+        let previous_start = week_start - Duration::weeks(1);
+        let previous = match self.load_snapshot(previous_start)? {
+            Some(snapshot) => Some(snapshot),
+            None => collect(previous_start).await.ok().filter(|snapshot| !snapshot.pairs.is_empty()),
+        };
+        let current = collect(week_start).await?;
+
+        let digest = self.compile(&current, previous.as_ref());
+        let path = self.write(&digest, &current)?;
+        println!("📄 Research digest written to {}", path.display());
+        Ok(digest)
+    }
+
+    /// Run forever, compiling the digest of the week just ended at the configured weekday and hour
+    pub async fn run_schedule<F, Fut>(&self, mut collect: F) -> Result<()>
+    where
+        F: FnMut(NaiveDate) -> Fut,
+        Fut: Future<Output = Result<WeeklySnapshot>>,
+    {
+        loop {
+            let now = Utc::now();
+            let next_run = next_weekly_schedule_time(now, self.config.schedule_weekday, self.config.schedule_hour_utc);
+            println!("⏰ Next research digest scheduled for {}", next_run.format("%a %Y-%m-%d %H:%M UTC"));
+
+            let wait = (next_run - now).to_std().unwrap_or_default();
+            tokio::time::sleep(wait).await;
+
+            // A run on Monday digests the week before; any other day, the current one
+            let week_start = week_start_of(next_run.date_naive() - Duration::days(1));
+            if let Err(e) = self.generate(week_start, &mut collect).await {
+                println!("❌ Research digest for the week of {} failed: {}", week_start, e);
+            }
+        }
+    }
+}
+
+/// Monday of the ISO week containing `date`
+pub fn week_start_of(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// Next occurrence of the given weekday and UTC hour strictly after `now`
+pub fn next_weekly_schedule_time(now: DateTime<Utc>, weekday: Weekday, hour: u32) -> DateTime<Utc> {
+    let days_ahead = (weekday.num_days_from_monday() + 7 - now.weekday().num_days_from_monday()) % 7;
+    let candidate = (now.date_naive() + Duration::days(days_ahead as i64))
+        .and_hms_opt(hour.min(23), 0, 0).unwrap().and_utc();
+    if candidate > now {
+        candidate
+    } else {
+        candidate + Duration::weeks(1)
+    }
+}
@@ -0,0 +1,445 @@
+//! # Symmetry Evidence Dossier
+//!
+//! Per-pair HTML document explaining each detected symmetry with overlays,
+//! autocorrelation, out-of-sample persistence and backtest P&L.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use super::{escape_html, TradeRecord};
+use crate::backtest::ValidationResults;
+use crate::calendar::HolidayCalendar;
+use crate::data::ForexDataPoint;
+use crate::patterns::HiddenCycle;
+use crate::symmetry::TemporalSymmetry;
+
+/// Fewer return pairs than this and an autocorrelation is not reported
+const MIN_CORRELATION_PAIRS: usize = 10;
+const PLOT_WIDTH: f64 = 480.0;
+const PLOT_HEIGHT: f64 = 200.0;
+const PLOT_MARGIN: f64 = 30.0;
+/// Periods this close to a year line up with the holiday calendar itself
+const YEARLY_PERIOD_DAYS: std::ops::RangeInclusive<u32> = 358..=372;
+
+/// Dossier settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DossierConfig {
+    /// Directory the dossiers are written to
+    pub output_directory: PathBuf,
+    /// Trailing fraction of the history held out for the persistence score
+    pub out_of_sample_fraction: f64,
+}
+
+impl Default for DossierConfig {
+    fn default() -> Self {
+        Self {
+            output_directory: PathBuf::from("reports"),
+            out_of_sample_fraction: 0.3,
+        }
+    }
+}
+
+/// Symmetries and cycles stored by `analyze` in `<PAIR>_<TIMEFRAME>_analysis.json`
+#[derive(Debug, Clone, Deserialize)]
+pub struct AnalysisArtifact {
+    #[serde(default)]
+    pub pair: Option<String>,
+    #[serde(default)]
+    pub timeframe: Option<String>,
+    pub temporal_symmetries: Vec<TemporalSymmetry>,
+    #[serde(default)]
+    pub hidden_cycles: Vec<HiddenCycle>,
+}
+
+impl AnalysisArtifact {
+    /// Load an analysis report, taking pair and timeframe from the file name when
+    /// the report predates them being recorded
+    pub fn load(path: &Path) -> Result<Self> {
+        let mut artifact: Self = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+        let mut parts = stem.trim_end_matches("_analysis").splitn(2, '_');
+        if artifact.pair.is_none() {
+            artifact.pair = parts.next().filter(|p| !p.is_empty()).map(str::to_string);
+        } else {
+            parts.next();
+        }
+        if artifact.timeframe.is_none() {
+            artifact.timeframe = parts.next().map(str::to_string);
+        }
+        Ok(artifact)
+    }
+}
+
+/// Evidence for one symmetry
+#[derive(Debug, Clone, Serialize)]
+pub struct SymmetryEvidence {
+    pub symmetry: TemporalSymmetry,
+    /// Autocorrelation of log returns one symmetry period apart, over the whole history
+    pub autocorrelation: Option<f64>,
+    /// The same autocorrelation with thin holiday-market bars left out
+    pub autocorrelation_excluding_thin: Option<f64>,
+    /// Why the symmetry may be a holiday artifact rather than a cycle
+    pub holiday_note: Option<String>,
+    pub in_sample_autocorrelation: Option<f64>,
+    pub out_of_sample_autocorrelation: Option<f64>,
+    /// Share of the in-sample autocorrelation that survives out of sample (0 when the sign flips)
+    pub persistence: Option<f64>,
+    /// Trades attributed to this symmetry
+    pub trades: usize,
+    pub profit_loss: f64,
+    /// Share of the total backtest P&L
+    pub profit_loss_share: Option<f64>,
+    /// Latest cycle overlaid on the previous one, as inline SVG
+    #[serde(skip)]
+    pub mirror_plot: Option<String>,
+}
+
+/// Evidence dossier for one pair
+#[derive(Debug, Clone, Serialize)]
+pub struct PairDossier {
+    pub pair: String,
+    pub timeframe: String,
+    pub generated_at: DateTime<Utc>,
+    pub data_points: usize,
+    pub first_timestamp: Option<DateTime<Utc>>,
+    pub last_timestamp: Option<DateTime<Utc>>,
+    /// Boundary between the in-sample and out-of-sample periods
+    pub out_of_sample_start: Option<DateTime<Utc>>,
+    /// Days in the history that traded in thin holiday markets, with the holidays
+    pub thin_market_days: Vec<(NaiveDate, String)>,
+    pub backtest: Option<ValidationResults>,
+    pub evidence: Vec<SymmetryEvidence>,
+    pub total_trades: usize,
+    pub total_profit_loss: f64,
+    /// P&L of trades not attributed to any symmetry in the dossier
+    pub unattributed_profit_loss: f64,
+}
+
+/// Builds and writes symmetry dossiers
+pub struct DossierGenerator {
+    config: DossierConfig,
+    holiday_calendar: HolidayCalendar,
+}
+
+impl DossierGenerator {
+    pub fn new(config: DossierConfig) -> Result<Self> {
+        if !(0.0..1.0).contains(&config.out_of_sample_fraction) {
+            anyhow::bail!("out_of_sample_fraction must be in [0, 1), got {}", config.out_of_sample_fraction);
+        }
+        Ok(Self { config, holiday_calendar: HolidayCalendar::disabled() })
+    }
+
+    /// Flag symmetries whose evidence depends on thin holiday markets
+    pub fn with_holiday_calendar(mut self, holiday_calendar: HolidayCalendar) -> Self {
+        self.holiday_calendar = holiday_calendar;
+        self
+    }
+
+    pub fn config(&self) -> &DossierConfig {
+        &self.config
+    }
+
+    /// Assemble the evidence for `pair` from its analysis, price history and backtest
+    pub fn compile(
+        &self,
+        pair: &str,
+        timeframe: &str,
+        symmetries: &[TemporalSymmetry],
+        data: &[ForexDataPoint],
+        trades: &[TradeRecord],
+        backtest: Option<&ValidationResults>,
+    ) -> PairDossier {
+        let trades: Vec<&TradeRecord> = trades.iter().filter(|t| t.pair.eq_ignore_ascii_case(pair)).collect();
+        let total_profit_loss: f64 = trades.iter().map(|t| t.profit_loss).sum();
+
+        let split = ((data.len() as f64) * (1.0 - self.config.out_of_sample_fraction)).round() as usize;
+        let split = split.min(data.len());
+        let (in_sample, out_of_sample) = data.split_at(split);
+        let thin_market_days = self.holiday_calendar.thin_days(pair, data);
+        let liquid: Vec<ForexDataPoint> = if thin_market_days.is_empty() {
+            Vec::new()
+        } else {
+            data.iter().filter(|p| !self.holiday_calendar.is_thin(pair, p.timestamp)).cloned().collect()
+        };
+
+        let evidence: Vec<SymmetryEvidence> = symmetries.iter()
+            .map(|symmetry| {
+                let period = Duration::days(symmetry.period_days as i64);
+                let in_sample_autocorrelation = lagged_autocorrelation(in_sample, period);
+                let out_of_sample_autocorrelation = lagged_autocorrelation(out_of_sample, period);
+                let attributed: Vec<&&TradeRecord> = trades.iter()
+                    .filter(|t| t.symmetry_id.as_ref() == Some(&symmetry.id))
+                    .collect();
+                let profit_loss: f64 = attributed.iter().map(|t| t.profit_loss).sum();
+                let autocorrelation = lagged_autocorrelation(data, period);
+                let autocorrelation_excluding_thin = if thin_market_days.is_empty() {
+                    autocorrelation
+                } else {
+                    lagged_autocorrelation(&liquid, period)
+                };
+
+                SymmetryEvidence {
+                    symmetry: symmetry.clone(),
+                    autocorrelation,
+                    autocorrelation_excluding_thin,
+                    holiday_note: holiday_note(symmetry.period_days, autocorrelation, autocorrelation_excluding_thin, thin_market_days.len()),
+                    in_sample_autocorrelation,
+                    out_of_sample_autocorrelation,
+                    persistence: persistence(in_sample_autocorrelation, out_of_sample_autocorrelation),
+                    trades: attributed.len(),
+                    profit_loss,
+                    profit_loss_share: (total_profit_loss.abs() > f64::EPSILON).then(|| profit_loss / total_profit_loss),
+                    mirror_plot: mirror_plot(symmetry, data),
+                }
+            })
+            .collect();
+
+        let attributed_profit_loss: f64 = evidence.iter().map(|e| e.profit_loss).sum();
+
+        PairDossier {
+            pair: pair.to_uppercase(),
+            timeframe: timeframe.to_string(),
+            generated_at: Utc::now(),
+            data_points: data.len(),
+            first_timestamp: data.first().map(|p| p.timestamp),
+            last_timestamp: data.last().map(|p| p.timestamp),
+            out_of_sample_start: out_of_sample.first().map(|p| p.timestamp),
+            thin_market_days,
+            backtest: backtest.cloned(),
+            evidence,
+            total_trades: trades.len(),
+            total_profit_loss,
+            unattributed_profit_loss: total_profit_loss - attributed_profit_loss,
+        }
+    }
+
+    /// Render the dossier as a self-contained HTML page
+    pub fn render_html(&self, dossier: &PairDossier) -> String {
+        let mut body = format!("<h1>Symmetry Evidence: {} ({})</h1>\n", escape_html(&dossier.pair), escape_html(&dossier.timeframe));
+        let range = match (dossier.first_timestamp, dossier.last_timestamp) {
+            (Some(first), Some(last)) => format!("{} to {}", first.format("%Y-%m-%d"), last.format("%Y-%m-%d")),
+            _ => "no data".to_string(),
+        };
+        body.push_str(&format!("<p>{} bars, {}. Out-of-sample period starts {}. Generated {}.</p>\n",
+            dossier.data_points,
+            range,
+            dossier.out_of_sample_start.map(|t| t.format("%Y-%m-%d").to_string()).unwrap_or_else(|| "n/a".to_string()),
+            dossier.generated_at.format("%Y-%m-%d %H:%M UTC")));
+        if !dossier.thin_market_days.is_empty() {
+            let listed: Vec<String> = dossier.thin_market_days.iter()
+                .take(10)
+                .map(|(date, reason)| format!("{} ({})", date.format("%Y-%m-%d"), escape_html(reason)))
+                .collect();
+            let more = dossier.thin_market_days.len().saturating_sub(listed.len());
+            body.push_str(&format!("<p><strong>Thin holiday markets:</strong> {} day(s) in the history: {}{}. \
+                                    Symmetries marked &#9888; may be calendar artifacts rather than cycles.</p>\n",
+                dossier.thin_market_days.len(),
+                listed.join(", "),
+                if more > 0 { format!(" and {} more", more) } else { String::new() }));
+        }
+
+        body.push_str("<h2>Summary</h2>\n<table>\n<tr><th>Symmetry</th><th>Period (days)</th><th>Strength</th>\
+                       <th>Autocorrelation</th><th>Persistence</th><th>Trades</th><th>P&amp;L</th><th>P&amp;L share</th></tr>\n");
+        for evidence in &dossier.evidence {
+            body.push_str(&format!("<tr><td>{}{}</td><td>{}</td><td>{:.3}</td><td>{}</td><td>{}</td><td>{}</td><td>{:.2}</td><td>{}</td></tr>\n",
+                escape_html(&evidence.symmetry.name),
+                if evidence.holiday_note.is_some() { " &#9888;" } else { "" },
+                evidence.symmetry.period_days,
+                evidence.symmetry.strength,
+                format_optional(evidence.autocorrelation, 3),
+                format_optional(evidence.persistence, 2),
+                evidence.trades,
+                evidence.profit_loss,
+                evidence.profit_loss_share.map(|s| format!("{:.1}%", s * 100.0)).unwrap_or_else(|| "n/a".to_string())));
+        }
+        body.push_str(&format!("<tr><td>Unattributed</td><td></td><td></td><td></td><td></td><td></td><td>{:.2}</td><td></td></tr>\n</table>\n",
+            dossier.unattributed_profit_loss));
+
+        if let Some(backtest) = &dossier.backtest {
+            body.push_str(&format!("<h2>Backtest</h2>\n<p>Return {:.2}%, Sharpe {:.2}, max drawdown {:.2}%, \
+                                    symmetry score {:.3}, pattern consistency {:.3}. {} trades, net P&amp;L {:.2}.</p>\n",
+                backtest.total_return * 100.0,
+                backtest.sharpe_ratio,
+                backtest.max_drawdown * 100.0,
+                backtest.symmetry_score,
+                backtest.pattern_consistency,
+                dossier.total_trades,
+                dossier.total_profit_loss));
+        }
+
+        for evidence in &dossier.evidence {
+            let symmetry = &evidence.symmetry;
+            body.push_str(&format!("<h2>{} <small>({})</small></h2>\n", escape_html(&symmetry.name), escape_html(&symmetry.id)));
+            body.push_str(&format!("<p>{} symmetry, period {} days, strength {:.3}, confidence {:.3}, \
+                                    validation score {:.3}, field signature {:#x}, {} recorded mirror points.</p>\n",
+                escape_html(&symmetry.symmetry_type),
+                symmetry.period_days,
+                symmetry.strength,
+                symmetry.confidence,
+                symmetry.validation_score,
+                symmetry.field_signature,
+                symmetry.mirror_points.len()));
+            if let Some(note) = &evidence.holiday_note {
+                body.push_str(&format!("<p><strong>&#9888; Possible holiday artifact:</strong> {}.</p>\n", escape_html(note)));
+            }
+            match &evidence.mirror_plot {
+                Some(svg) => body.push_str(svg),
+                None => body.push_str("<p><em>Not enough history to plot two full cycles.</em></p>\n"),
+            }
+            body.push_str(&format!("<table>\n<tr><th>Return autocorrelation at period</th><th>In sample</th>\
+                                    <th>Out of sample</th><th>Persistence</th></tr>\n\
+                                    <tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n</table>\n",
+                format_optional(evidence.autocorrelation, 3),
+                format_optional(evidence.in_sample_autocorrelation, 3),
+                format_optional(evidence.out_of_sample_autocorrelation, 3),
+                format_optional(evidence.persistence, 2)));
+            body.push_str(&format!("<p>{} trades attributed, P&amp;L {:.2}.</p>\n", evidence.trades, evidence.profit_loss));
+        }
+
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Symmetry Evidence {}</title>\n\
+             <style>body{{font-family:sans-serif;margin:2em;}}table{{border-collapse:collapse;margin-bottom:1em;}}\
+             th,td{{border:1px solid #ccc;padding:4px 8px;text-align:left;}}th{{background:#f0f0f0;}}\
+             svg{{border:1px solid #eee;}}</style>\n\
+             </head>\n<body>\n{}</body>\n</html>\n",
+            escape_html(&dossier.pair), body
+        )
+    }
+
+    /// Write `<PAIR>_<TIMEFRAME>_dossier.html` to the output directory
+    pub fn write(&self, dossier: &PairDossier) -> Result<PathBuf> {
+        std::fs::create_dir_all(&self.config.output_directory)?;
+        let path = self.config.output_directory.join(format!("{}_{}_dossier.html", dossier.pair, dossier.timeframe));
+        std::fs::write(&path, self.render_html(dossier))?;
+        Ok(path)
+    }
+}
+
+/// Pearson correlation between each bar's log return and the return one `period` later.
+///
+/// Bars are paired by timestamp rather than index so weekend and holiday gaps do not
+/// shift the lag.
+pub fn lagged_autocorrelation(data: &[ForexDataPoint], period: Duration) -> Option<f64> {
+    let returns: Vec<(DateTime<Utc>, f64)> = data.windows(2)
+        .filter(|w| w[0].close > 0.0 && w[1].close > 0.0)
+        .map(|w| (w[1].timestamp, (w[1].close / w[0].close).ln()))
+        .collect();
+    let mut spacings: Vec<i64> = returns.windows(2).map(|w| (w[1].0 - w[0].0).num_seconds()).collect();
+    if spacings.is_empty() || period <= Duration::zero() {
+        return None;
+    }
+    spacings.sort_unstable();
+    let tolerance = Duration::seconds(spacings[spacings.len() / 2] / 2);
+
+    let pairs: Vec<(f64, f64)> = returns.iter()
+        .filter_map(|(timestamp, value)| {
+            let target = *timestamp + period;
+            let index = returns.partition_point(|(t, _)| *t < target - tolerance);
+            returns.get(index)
+                .filter(|(t, _)| (*t - target).abs() <= tolerance)
+                .map(|(_, later)| (*value, *later))
+        })
+        .collect();
+    if pairs.len() < MIN_CORRELATION_PAIRS {
+        return None;
+    }
+
+    let n = pairs.len() as f64;
+    let (mean_x, mean_y) = (pairs.iter().map(|p| p.0).sum::<f64>() / n, pairs.iter().map(|p| p.1).sum::<f64>() / n);
+    let covariance: f64 = pairs.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let variance_x: f64 = pairs.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    let variance_y: f64 = pairs.iter().map(|(_, y)| (y - mean_y).powi(2)).sum();
+    let denominator = (variance_x * variance_y).sqrt();
+    (denominator > 0.0).then(|| covariance / denominator)
+}
+
+/// Reason to distrust a symmetry when the history contains `thin_days` holiday days:
+/// its autocorrelation collapses once they are removed, or its period is a year
+fn holiday_note(period_days: u32, autocorrelation: Option<f64>, excluding_thin: Option<f64>, thin_days: usize) -> Option<String> {
+    if thin_days == 0 {
+        return None;
+    }
+    if let Some(full) = autocorrelation.filter(|a| a.abs() > f64::EPSILON) {
+        match excluding_thin {
+            None => return Some(format!("autocorrelation {:.3} cannot be measured without the {} thin-market days", full, thin_days)),
+            Some(rest) if rest * full <= 0.0 || rest.abs() < full.abs() * 0.5 => {
+                return Some(format!("autocorrelation falls from {:.3} to {:.3} without the {} thin-market days", full, rest, thin_days));
+            }
+            Some(_) => {}
+        }
+    }
+    YEARLY_PERIOD_DAYS.contains(&period_days)
+        .then(|| format!("a {}-day period recurs with the annual holiday calendar", period_days))
+}
+
+/// Out-of-sample autocorrelation as a fraction of the in-sample one, capped at 1
+fn persistence(in_sample: Option<f64>, out_of_sample: Option<f64>) -> Option<f64> {
+    let (in_sample, out_of_sample) = (in_sample?, out_of_sample?);
+    if in_sample.abs() < f64::EPSILON {
+        return None;
+    }
+    Some((out_of_sample / in_sample).clamp(0.0, 1.0))
+}
+
+/// SVG of the latest symmetry period (solid) over the period before it (dashed), both
+/// as percentage change from the start of their period, with any recorded mirror
+/// points folded onto the same phase axis
+fn mirror_plot(symmetry: &TemporalSymmetry, data: &[ForexDataPoint]) -> Option<String> {
+    let period = Duration::days(symmetry.period_days as i64);
+    let end = data.last()?.timestamp;
+    if period <= Duration::zero() || data.first()?.timestamp > end - period * 2 {
+        return None;
+    }
+    let cycle = |start: DateTime<Utc>| -> Vec<(f64, f64)> {
+        let points: Vec<&ForexDataPoint> = data.iter()
+            .filter(|p| p.timestamp >= start && p.timestamp <= start + period)
+            .collect();
+        let base = points.first().map(|p| p.close).filter(|c| *c > 0.0).unwrap_or(1.0);
+        points.iter()
+            .map(|p| ((p.timestamp - start).num_seconds() as f64 / period.num_seconds() as f64, (p.close / base - 1.0) * 100.0))
+            .collect()
+    };
+    let latest_start = end - period;
+    let latest = cycle(latest_start);
+    let previous = cycle(latest_start - period);
+    if latest.len() < 2 || previous.len() < 2 {
+        return None;
+    }
+
+    let latest_base = data.iter().find(|p| p.timestamp >= latest_start).map(|p| p.close).unwrap_or(1.0);
+    let mirrors: Vec<(f64, f64)> = symmetry.mirror_points.iter()
+        .map(|(time, price)| {
+            let offset = (*time - latest_start.timestamp() as f64).rem_euclid(period.num_seconds() as f64);
+            (offset / period.num_seconds() as f64, (price / latest_base - 1.0) * 100.0)
+        })
+        .collect();
+
+    let values = latest.iter().chain(&previous).chain(&mirrors).map(|(_, y)| *y);
+    let (low, high) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), y| (lo.min(y), hi.max(y)));
+    let range = (high - low).max(1e-9);
+    let x = |phase: f64| PLOT_MARGIN + phase * (PLOT_WIDTH - 2.0 * PLOT_MARGIN);
+    let y = |value: f64| PLOT_HEIGHT - PLOT_MARGIN - (value - low) / range * (PLOT_HEIGHT - 2.0 * PLOT_MARGIN);
+    let polyline = |points: &[(f64, f64)]| -> String {
+        points.iter().map(|(phase, value)| format!("{:.1},{:.1}", x(*phase), y(*value))).collect::<Vec<_>>().join(" ")
+    };
+
+    let mut svg = format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n",
+        w = PLOT_WIDTH, h = PLOT_HEIGHT);
+    svg.push_str(&format!("<polyline fill=\"none\" stroke=\"#999\" stroke-dasharray=\"4 3\" points=\"{}\"/>\n", polyline(&previous)));
+    svg.push_str(&format!("<polyline fill=\"none\" stroke=\"#1f5fbf\" stroke-width=\"1.5\" points=\"{}\"/>\n", polyline(&latest)));
+    for (phase, value) in &mirrors {
+        svg.push_str(&format!("<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"3\" fill=\"#d9534f\"/>\n", x(*phase), y(*value)));
+    }
+    svg.push_str(&format!("<text x=\"4\" y=\"14\" font-size=\"11\">{:+.2}%</text>\n\
+                           <text x=\"4\" y=\"{:.0}\" font-size=\"11\">{:+.2}%</text>\n\
+                           <text x=\"{:.0}\" y=\"14\" font-size=\"11\" text-anchor=\"end\">latest cycle (solid) vs previous (dashed)</text>\n</svg>\n",
+        high, PLOT_HEIGHT - 4.0, low, PLOT_WIDTH - 4.0));
+    Some(svg)
+}
+
+fn format_optional(value: Option<f64>, decimals: usize) -> String {
+    value.map(|v| format!("{:.*}", decimals, v)).unwrap_or_else(|| "n/a".to_string())
+}
@@ -0,0 +1,686 @@
+//! # Daily Summary Reports
+//!
+//! Compile the day's anomalies, trades, P&L, symmetry decay and data-feed health
+//! into a single Markdown/HTML summary, written to disk and optionally POSTed to a webhook
+
+pub mod analysis;
+pub mod digest;
+pub mod dossier;
+pub mod scan;
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::future::Future;
+use std::path::PathBuf;
+
+use crate::anomaly::{AnomalySeverity, AnomalyType, DetectedAnomaly};
+use crate::calendar::{HolidayCalendar, THIN_MARKET_EVENT};
+use crate::data::ForexDataPoint;
+use crate::ids::{CycleId, SymmetryId};
+use crate::patterns::HiddenCycle;
+use crate::symmetry::TemporalSymmetry;
+use crate::synthetic::trading_env::{SignalType, TradeResult};
+
+/// Daily report configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyReportConfig {
+    /// Directory the rendered reports are written to
+    pub output_directory: PathBuf,
+
+    /// Output format
+    pub format: ReportFormat,
+
+    /// Optional webhook receiving the rendered report as JSON
+    pub webhook_url: Option<String>,
+
+    /// Fraction of expected strength below which a symmetry is reported as decaying
+    pub symmetry_decay_threshold: f64,
+
+    /// Minutes without a new data point before a feed is flagged as stale
+    pub stale_feed_minutes: i64,
+
+    /// UTC hour at which the scheduler compiles the day's report
+    pub schedule_hour_utc: u32,
+}
+
+/// Rendered report format
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+    Both,
+}
+
+impl std::str::FromStr for ReportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "markdown" | "md" => Ok(ReportFormat::Markdown),
+            "html" => Ok(ReportFormat::Html),
+            "both" => Ok(ReportFormat::Both),
+            _ => Err(anyhow::anyhow!("Unsupported report format: {}", s)),
+        }
+    }
+}
+
+impl Default for DailyReportConfig {
+    fn default() -> Self {
+        Self {
+            output_directory: PathBuf::from("reports"),
+            format: ReportFormat::Markdown,
+            webhook_url: None,
+            symmetry_decay_threshold: 0.7,
+            stale_feed_minutes: 60 * 26, // Daily bars plus a weekend-friendly grace period
+            schedule_hour_utc: 22,       // New York close
+        }
+    }
+}
+
+/// Trade record consumed by the report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeRecord {
+    pub pair: String,
+    pub timestamp: DateTime<Utc>,
+    pub side: String,
+    pub size: f64,
+    pub entry_price: f64,
+    pub exit_price: Option<f64>,
+    pub commission: f64,
+    pub profit_loss: f64,
+    /// Symmetry whose signal opened the trade, when known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symmetry_id: Option<SymmetryId>,
+    /// Cycle whose signal opened the trade, when known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cycle_id: Option<CycleId>,
+}
+
+impl TradeRecord {
+    /// Convert a synthetic trading environment result into a report record
+    pub fn from_trade_result(pair: &str, trade: &TradeResult, previous_balance: f64) -> Self {
+        let side = match trade.signal_type {
+            SignalType::Buy => "Buy",
+            SignalType::Sell => "Sell",
+            SignalType::Hold => "Hold",
+        };
+
+        Self {
+            pair: pair.to_string(),
+            timestamp: trade.entry_time,
+            side: side.to_string(),
+            size: trade.position_size,
+            entry_price: trade.entry_price,
+            exit_price: None,
+            commission: trade.commission,
+            profit_loss: trade.new_balance - previous_balance,
+            symmetry_id: None,
+            cycle_id: None,
+        }
+    }
+}
+
+/// Health of a single data feed
+#[derive(Debug, Clone, Serialize)]
+pub struct FeedHealth {
+    pub source: String,
+    pub last_update: Option<DateTime<Utc>>,
+    pub points_today: usize,
+    pub gaps_detected: usize,
+    pub status: FeedStatus,
+}
+
+/// Feed status classification
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum FeedStatus {
+    Healthy,
+    Degraded,
+    Stale,
+    Missing,
+}
+
+impl FeedHealth {
+    /// Assess a feed from the points it delivered
+    pub fn assess(
+        source: &str,
+        points: &[ForexDataPoint],
+        date: NaiveDate,
+        now: DateTime<Utc>,
+        stale_after_minutes: i64,
+    ) -> Self {
+        let last_update = points.last().map(|p| p.timestamp);
+        let points_today = points.iter()
+            .filter(|p| p.timestamp.date_naive() == date)
+            .count();
+
+        // A gap is any interval larger than three times the median spacing
+        let mut intervals: Vec<i64> = points.windows(2)
+            .map(|w| (w[1].timestamp - w[0].timestamp).num_seconds())
+            .collect();
+        intervals.sort_unstable();
+        let median_interval = intervals.get(intervals.len() / 2).copied().unwrap_or(0);
+        let recent_start = date.and_hms_opt(0, 0, 0).unwrap().and_utc() - Duration::days(7);
+        let gaps_detected = points.windows(2)
+            .filter(|w| w[1].timestamp >= recent_start)
+            .filter(|w| median_interval > 0 && (w[1].timestamp - w[0].timestamp).num_seconds() > median_interval * 3)
+            .count();
+
+        let status = match last_update {
+            None => FeedStatus::Missing,
+            Some(ts) if (now - ts).num_minutes() > stale_after_minutes => FeedStatus::Stale,
+            Some(_) if gaps_detected > 0 => FeedStatus::Degraded,
+            Some(_) => FeedStatus::Healthy,
+        };
+
+        Self {
+            source: source.to_string(),
+            last_update,
+            points_today,
+            gaps_detected,
+            status,
+        }
+    }
+}
+
+/// Raw material for a daily report
+#[derive(Debug, Clone, Default)]
+pub struct DailyReportInput {
+    pub anomalies: Vec<DetectedAnomaly>,
+    pub trades: Vec<TradeRecord>,
+    pub symmetries: Vec<TemporalSymmetry>,
+    pub feeds: Vec<FeedHealth>,
+}
+
+/// Symmetry whose observed strength fell below the decay threshold
+#[derive(Debug, Clone, Serialize)]
+pub struct SymmetryDecayWarning {
+    pub symmetry_id: SymmetryId,
+    pub symmetry_name: String,
+    pub expected_strength: f64,
+    pub weakest_observed_strength: f64,
+    pub breakdown_count: usize,
+}
+
+/// Profit and loss summary
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfitLossSummary {
+    pub total_trades: usize,
+    pub winning_trades: usize,
+    pub gross_profit_loss: f64,
+    pub total_commission: f64,
+    pub net_profit_loss: f64,
+    pub per_pair: BTreeMap<String, f64>,
+}
+
+/// Compiled daily summary
+#[derive(Debug, Clone, Serialize)]
+pub struct DailyReport {
+    pub date: NaiveDate,
+    pub generated_at: DateTime<Utc>,
+    pub anomaly_count: usize,
+    pub anomalies_by_type: BTreeMap<String, usize>,
+    pub anomalies_by_severity: BTreeMap<String, usize>,
+    pub notable_anomalies: Vec<DetectedAnomaly>,
+    pub trades: Vec<TradeRecord>,
+    pub profit_loss: ProfitLossSummary,
+    pub symmetry_warnings: Vec<SymmetryDecayWarning>,
+    pub feed_health: Vec<FeedHealth>,
+    /// Holidays thinning the market on the report date
+    pub thin_markets: Vec<String>,
+    /// Anomalies detected on thin holiday-market bars
+    pub thin_market_anomalies: usize,
+}
+
+/// Daily report generator
+pub struct DailyReportGenerator {
+    config: DailyReportConfig,
+    client: reqwest::Client,
+    holiday_calendar: HolidayCalendar,
+}
+
+impl DailyReportGenerator {
+    /// Create new daily report generator
+    pub fn new(config: DailyReportConfig) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(30))
+            .build()?;
+
+        Ok(Self { config, client, holiday_calendar: HolidayCalendar::disabled() })
+    }
+    
+    /// Warn when the report date falls on a holiday
+    pub fn with_holiday_calendar(mut self, holiday_calendar: HolidayCalendar) -> Self {
+        self.holiday_calendar = holiday_calendar;
+        self
+    }
+
+    /// Get generator configuration
+    pub fn config(&self) -> &DailyReportConfig {
+        &self.config
+    }
+
+    /// Compile the report for a single UTC day
+    pub fn compile(&self, date: NaiveDate, input: &DailyReportInput) -> DailyReport {
+        let anomalies: Vec<&DetectedAnomaly> = input.anomalies.iter()
+            .filter(|a| a.timestamp.date_naive() == date)
+            .collect();
+
+        let mut anomalies_by_type = BTreeMap::new();
+        let mut anomalies_by_severity = BTreeMap::new();
+        for anomaly in &anomalies {
+            *anomalies_by_type.entry(anomaly_type_name(&anomaly.anomaly_type).to_string()).or_insert(0) += 1;
+            *anomalies_by_severity.entry(format!("{:?}", anomaly.severity)).or_insert(0) += 1;
+        }
+
+        let mut notable_anomalies: Vec<DetectedAnomaly> = anomalies.iter()
+            .filter(|a| matches!(a.severity, AnomalySeverity::High | AnomalySeverity::Critical))
+            .map(|a| (*a).clone())
+            .collect();
+        notable_anomalies.sort_by(|a, b| b.confidence.partial_cmp(&a.confidence).unwrap_or(std::cmp::Ordering::Equal));
+        notable_anomalies.truncate(10);
+
+        let trades: Vec<TradeRecord> = input.trades.iter()
+            .filter(|t| t.timestamp.date_naive() == date)
+            .cloned()
+            .collect();
+
+        let mut thin_markets = self.holiday_calendar.holidays_on(date);
+        if self.holiday_calendar.is_year_end(date) {
+            thin_markets.push("year-end holidays".to_string());
+        }
+        let thin_market_anomalies = anomalies.iter()
+            .filter(|a| a.market_context.recent_events.iter().any(|e| e.starts_with(THIN_MARKET_EVENT)))
+            .count();
+
+        DailyReport {
+            date,
+            generated_at: Utc::now(),
+            anomaly_count: anomalies.len(),
+            anomalies_by_type,
+            anomalies_by_severity,
+            notable_anomalies,
+            profit_loss: summarize_profit_loss(&trades),
+            trades,
+            symmetry_warnings: self.find_symmetry_decay(&anomalies, &input.symmetries),
+            feed_health: input.feeds.clone(),
+            thin_markets,
+            thin_market_anomalies,
+        }
+    }
+
+    /// Detect symmetries whose observed strength decayed below the threshold
+    fn find_symmetry_decay(
+        &self,
+        anomalies: &[&DetectedAnomaly],
+        symmetries: &[TemporalSymmetry],
+    ) -> Vec<SymmetryDecayWarning> {
+        let names: HashMap<&str, &str> = symmetries.iter()
+            .map(|s| (s.id.as_str(), s.name.as_str()))
+            .collect();
+        let mut warnings: BTreeMap<SymmetryId, SymmetryDecayWarning> = BTreeMap::new();
+
+        for anomaly in anomalies {
+            if let AnomalyType::SymmetryBreakdown { symmetry_id, expected_strength, actual_strength } = &anomaly.anomaly_type {
+                if *expected_strength <= 0.0
+                    || actual_strength / expected_strength >= self.config.symmetry_decay_threshold {
+                    continue;
+                }
+
+                let warning = warnings.entry(symmetry_id.clone()).or_insert_with(|| SymmetryDecayWarning {
+                    symmetry_id: symmetry_id.clone(),
+                    symmetry_name: names.get(symmetry_id.as_str()).copied().unwrap_or(symmetry_id.as_str()).to_string(),
+                    expected_strength: *expected_strength,
+                    weakest_observed_strength: *actual_strength,
+                    breakdown_count: 0,
+                });
+                warning.weakest_observed_strength = warning.weakest_observed_strength.min(*actual_strength);
+                warning.breakdown_count += 1;
+            }
+        }
+
+        warnings.into_values().collect()
+    }
+
+    /// Render report as Markdown
+    pub fn render_markdown(&self, report: &DailyReport) -> String {
+        let mut md = String::new();
+
+        md.push_str(&format!("# Daily Summary — {}\n\n", report.date));
+        md.push_str(&format!("_Generated at {}_\n\n", report.generated_at.format("%Y-%m-%d %H:%M:%S UTC")));
+        if !report.thin_markets.is_empty() || report.thin_market_anomalies > 0 {
+            md.push_str(&format!("> Thin holiday markets: {}. {} anomalies fell on thin-market bars; \
+                                  treat them, and any cycle evidence from today, as possible calendar artifacts.\n\n",
+                if report.thin_markets.is_empty() { "none scheduled".to_string() } else { report.thin_markets.join(", ") },
+                report.thin_market_anomalies));
+        }
+
+        md.push_str("## Profit & Loss\n\n");
+        md.push_str("| Metric | Value |\n|---|---|\n");
+        md.push_str(&format!("| Trades | {} |\n", report.profit_loss.total_trades));
+        md.push_str(&format!("| Winning trades | {} |\n", report.profit_loss.winning_trades));
+        md.push_str(&format!("| Gross P&L | {:.2} |\n", report.profit_loss.gross_profit_loss));
+        md.push_str(&format!("| Commission | {:.2} |\n", report.profit_loss.total_commission));
+        md.push_str(&format!("| Net P&L | {:.2} |\n\n", report.profit_loss.net_profit_loss));
+        if !report.profit_loss.per_pair.is_empty() {
+            md.push_str("| Pair | Net P&L |\n|---|---|\n");
+            for (pair, pnl) in &report.profit_loss.per_pair {
+                md.push_str(&format!("| {} | {:.2} |\n", pair, pnl));
+            }
+            md.push('\n');
+        }
+
+        md.push_str(&format!("## Anomalies ({})\n\n", report.anomaly_count));
+        if report.anomaly_count == 0 {
+            md.push_str("No anomalies detected.\n\n");
+        } else {
+            md.push_str("| Type | Count |\n|---|---|\n");
+            for (kind, count) in &report.anomalies_by_type {
+                md.push_str(&format!("| {} | {} |\n", kind, count));
+            }
+            md.push_str("\n| Severity | Count |\n|---|---|\n");
+            for (severity, count) in &report.anomalies_by_severity {
+                md.push_str(&format!("| {} | {} |\n", severity, count));
+            }
+            md.push('\n');
+        }
+        if !report.notable_anomalies.is_empty() {
+            md.push_str("### Notable anomalies\n\n| Time | Type | Severity | Confidence | Signal |\n|---|---|---|---|---|\n");
+            for anomaly in &report.notable_anomalies {
+                md.push_str(&format!("| {} | {} | {:?} | {:.3} | {} |\n",
+                    anomaly.timestamp.format("%H:%M"),
+                    anomaly_type_name(&anomaly.anomaly_type),
+                    anomaly.severity,
+                    anomaly.confidence,
+                    anomaly.trading_signal.as_ref().map(|s| s.signal_type.as_str()).unwrap_or("-")));
+            }
+            md.push('\n');
+        }
+
+        md.push_str("## Trades\n\n");
+        if report.trades.is_empty() {
+            md.push_str("No trades executed.\n\n");
+        } else {
+            md.push_str("| Time | Pair | Side | Size | Entry | P&L |\n|---|---|---|---|---|---|\n");
+            for trade in &report.trades {
+                md.push_str(&format!("| {} | {} | {} | {:.2} | {:.5} | {:.2} |\n",
+                    trade.timestamp.format("%H:%M"), trade.pair, trade.side,
+                    trade.size, trade.entry_price, trade.profit_loss));
+            }
+            md.push('\n');
+        }
+
+        md.push_str("## Symmetry Decay Warnings\n\n");
+        if report.symmetry_warnings.is_empty() {
+            md.push_str("All expected symmetries holding.\n\n");
+        } else {
+            md.push_str("| Symmetry | Expected | Weakest observed | Breakdowns |\n|---|---|---|---|\n");
+            for warning in &report.symmetry_warnings {
+                md.push_str(&format!("| {} | {:.3} | {:.3} | {} |\n",
+                    warning.symmetry_name, warning.expected_strength,
+                    warning.weakest_observed_strength, warning.breakdown_count));
+            }
+            md.push('\n');
+        }
+
+        md.push_str("## Data Feed Health\n\n");
+        if report.feed_health.is_empty() {
+            md.push_str("No feeds reported.\n");
+        } else {
+            md.push_str("| Source | Status | Last update | Points today | Gaps (7d) |\n|---|---|---|---|---|\n");
+            for feed in &report.feed_health {
+                md.push_str(&format!("| {} | {:?} | {} | {} | {} |\n",
+                    feed.source, feed.status,
+                    feed.last_update.map(|t| t.format("%Y-%m-%d %H:%M").to_string()).unwrap_or_else(|| "-".to_string()),
+                    feed.points_today, feed.gaps_detected));
+            }
+        }
+
+        md
+    }
+
+    /// Render report as a self-contained HTML page
+    pub fn render_html(&self, report: &DailyReport) -> String {
+        let mut body = String::new();
+        let mut in_table = false;
+
+        // The Markdown rendering is the source of truth; HTML mirrors its structure
+        for line in self.render_markdown(report).lines() {
+            if line.starts_with('|') {
+                let cells: Vec<&str> = line.trim_matches('|').split('|').map(|c| c.trim()).collect();
+                if cells.iter().all(|c| c.chars().all(|ch| ch == '-')) {
+                    continue;
+                }
+                if !in_table {
+                    body.push_str("<table>\n<tr>");
+                    body.push_str(&cells.iter().map(|c| format!("<th>{}</th>", escape_html(c))).collect::<String>());
+                    body.push_str("</tr>\n");
+                    in_table = true;
+                } else {
+                    body.push_str("<tr>");
+                    body.push_str(&cells.iter().map(|c| format!("<td>{}</td>", escape_html(c))).collect::<String>());
+                    body.push_str("</tr>\n");
+                }
+                continue;
+            }
+
+            if in_table {
+                body.push_str("</table>\n");
+                in_table = false;
+            }
+
+            if let Some(title) = line.strip_prefix("### ") {
+                body.push_str(&format!("<h3>{}</h3>\n", escape_html(title)));
+            } else if let Some(title) = line.strip_prefix("## ") {
+                body.push_str(&format!("<h2>{}</h2>\n", escape_html(title)));
+            } else if let Some(title) = line.strip_prefix("# ") {
+                body.push_str(&format!("<h1>{}</h1>\n", escape_html(title)));
+            } else if !line.is_empty() {
+                body.push_str(&format!("<p>{}</p>\n", escape_html(line.trim_matches('_'))));
+            }
+        }
+        if in_table {
+            body.push_str("</table>\n");
+        }
+
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Daily Summary {}</title>\n\
+             <style>body{{font-family:sans-serif;margin:2em;}}table{{border-collapse:collapse;margin-bottom:1em;}}\
+             th,td{{border:1px solid #ccc;padding:4px 8px;text-align:left;}}th{{background:#f0f0f0;}}</style>\n\
+             </head>\n<body>\n{}</body>\n</html>\n",
+            report.date, body
+        )
+    }
+
+    /// Write rendered report(s) to the output directory
+    pub fn write(&self, report: &DailyReport) -> Result<Vec<PathBuf>> {
+        std::fs::create_dir_all(&self.config.output_directory)?;
+        let mut written = Vec::new();
+
+        if matches!(self.config.format, ReportFormat::Markdown | ReportFormat::Both) {
+            let path = self.config.output_directory.join(format!("daily_{}.md", report.date));
+            std::fs::write(&path, self.render_markdown(report))?;
+            written.push(path);
+        }
+
+        if matches!(self.config.format, ReportFormat::Html | ReportFormat::Both) {
+            let path = self.config.output_directory.join(format!("daily_{}.html", report.date));
+            std::fs::write(&path, self.render_html(report))?;
+            written.push(path);
+        }
+
+        Ok(written)
+    }
+
+    /// POST the report to the configured webhook, if any
+    pub async fn publish(&self, report: &DailyReport) -> Result<bool> {
+        let Some(url) = &self.config.webhook_url else {
+            return Ok(false);
+        };
+
+        let payload = serde_json::json!({
+            "title": format!("Daily Summary {}", report.date),
+            "text": self.render_markdown(report),
+            "report": report,
+        });
+
+        let response = self.client.post(url).json(&payload).send().await?;
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!("Webhook returned {}", response.status()));
+        }
+
+        Ok(true)
+    }
+
+    /// Compile, write and publish the report for a day
+    pub async fn generate(&self, date: NaiveDate, input: &DailyReportInput) -> Result<DailyReport> {
+        let report = self.compile(date, input);
+
+        for path in self.write(&report)? {
+            println!("📄 Daily report written to {}", path.display());
+        }
+
+        if self.publish(&report).await? {
+            println!("📨 Daily report posted to webhook");
+        }
+
+        Ok(report)
+    }
+
+    /// Run forever, generating the report once a day at the configured UTC hour
+    pub async fn run_schedule<F, Fut>(&self, mut collect: F) -> Result<()>
+    where
+        F: FnMut(NaiveDate) -> Fut,
+        Fut: Future<Output = Result<DailyReportInput>>,
+    {
+        loop {
+            let now = Utc::now();
+            let next_run = next_schedule_time(now, self.config.schedule_hour_utc);
+            println!("⏰ Next daily report scheduled for {}", next_run.format("%Y-%m-%d %H:%M UTC"));
+
+            let wait = (next_run - now).to_std().unwrap_or_default();
+            tokio::time::sleep(wait).await;
+
+            let date = next_run.date_naive();
+            match collect(date).await {
+                Ok(input) => {
+                    if let Err(e) = self.generate(date, &input).await {
+                        println!("❌ Daily report for {} failed: {}", date, e);
+                    }
+                }
+                Err(e) => println!("❌ Could not collect data for {} report: {}", date, e),
+            }
+        }
+    }
+}
+
+/// Next occurrence of the given UTC hour strictly after `now`
+fn next_schedule_time(now: DateTime<Utc>, hour: u32) -> DateTime<Utc> {
+    let today = now.date_naive().and_hms_opt(hour.min(23), 0, 0).unwrap().and_utc();
+    if today > now {
+        today
+    } else {
+        today + Duration::days(1)
+    }
+}
+
+/// Summarize trade P&L
+fn summarize_profit_loss(trades: &[TradeRecord]) -> ProfitLossSummary {
+    let mut per_pair = BTreeMap::new();
+    for trade in trades {
+        *per_pair.entry(trade.pair.clone()).or_insert(0.0) += trade.profit_loss;
+    }
+
+    let net_profit_loss: f64 = trades.iter().map(|t| t.profit_loss).sum();
+    let total_commission: f64 = trades.iter().map(|t| t.commission).sum();
+
+    ProfitLossSummary {
+        total_trades: trades.len(),
+        winning_trades: trades.iter().filter(|t| t.profit_loss > 0.0).count(),
+        gross_profit_loss: net_profit_loss + total_commission,
+        total_commission,
+        net_profit_loss,
+        per_pair,
+    }
+}
+
+/// A symmetry or cycle id that an anomaly or trade names but no known symmetry or cycle has
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DanglingReference {
+    /// The anomaly id, or the trade as `PAIR@timestamp`
+    pub source: String,
+    pub missing_id: String,
+}
+
+/// Links from `anomalies` and `trades` to symmetries or cycles not in `symmetries` or `cycles`
+pub fn dangling_references(
+    symmetries: &[TemporalSymmetry],
+    cycles: &[HiddenCycle],
+    anomalies: &[DetectedAnomaly],
+    trades: &[TradeRecord],
+) -> Vec<DanglingReference> {
+    let symmetry_ids: HashSet<&SymmetryId> = symmetries.iter().map(|s| &s.id).collect();
+    let cycle_ids: HashSet<&CycleId> = cycles.iter().map(|c| &c.id).collect();
+    let mut dangling = Vec::new();
+    let mut check = |source: &str, symmetry: Option<&SymmetryId>, cycle: Option<&CycleId>| {
+        if let Some(id) = symmetry.filter(|id| !symmetry_ids.contains(id)) {
+            dangling.push(DanglingReference { source: source.to_string(), missing_id: id.to_string() });
+        }
+        if let Some(id) = cycle.filter(|id| !cycle_ids.contains(id)) {
+            dangling.push(DanglingReference { source: source.to_string(), missing_id: id.to_string() });
+        }
+    };
+
+    for anomaly in anomalies {
+        match &anomaly.anomaly_type {
+            AnomalyType::SymmetryBreakdown { symmetry_id, .. } => check(&anomaly.id, Some(symmetry_id), None),
+            AnomalyType::CycleDisruption { cycle_id, .. } => check(&anomaly.id, None, Some(cycle_id)),
+            _ => {}
+        }
+        for id in &anomaly.affected_symmetries {
+            check(&anomaly.id, Some(id), None);
+        }
+        for id in &anomaly.affected_cycles {
+            check(&anomaly.id, None, Some(id));
+        }
+    }
+    for trade in trades {
+        let source = format!("{}@{}", trade.pair, trade.timestamp.format("%Y-%m-%d %H:%M"));
+        check(&source, trade.symmetry_id.as_ref(), trade.cycle_id.as_ref());
+    }
+    dangling
+}
+
+/// Upper-cased pairs of a comma-separated `--pairs` list; an error when it names none
+pub fn parse_pairs(pairs: &str) -> Result<Vec<String>> {
+    let pairs: Vec<String> = pairs.split(',')
+        .map(|pair| pair.trim().to_uppercase())
+        .filter(|pair| !pair.is_empty())
+        .collect();
+    if pairs.is_empty() {
+        anyhow::bail!("no currency pairs given; pass --pairs, e.g. EURUSD,GBPUSD");
+    }
+    Ok(pairs)
+}
+
+/// Short display name for an anomaly type
+pub fn anomaly_type_name(anomaly_type: &AnomalyType) -> &'static str {
+    match anomaly_type {
+        AnomalyType::SymmetryBreakdown { .. } => "SymmetryBreakdown",
+        AnomalyType::CycleDisruption { .. } => "CycleDisruption",
+        AnomalyType::VolatilitySpike { .. } => "VolatilitySpike",
+        AnomalyType::PatternInversion { .. } => "PatternInversion",
+        AnomalyType::CorrelationBreakdown { .. } => "CorrelationBreakdown",
+        AnomalyType::NovelPattern { .. } => "NovelPattern",
+        AnomalyType::MomentumShock { .. } => "MomentumShock",
+        AnomalyType::LiquidityGap { .. } => "LiquidityGap",
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
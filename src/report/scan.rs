@@ -0,0 +1,239 @@
+//! # Multi-Pair Scan
+//!
+//! Symmetries and cycles of every pair in a data directory, extracted in
+//! parallel and ranked by symmetry strength, with cycle periods shared across
+//! pairs. Pairs that fail are listed with their error.
+
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+use crate::core::{EngineConfig, TimeSymmetricEngine};
+use crate::data::provider::timeframe_duration;
+use crate::data::{DataConfig, DataSummary, ForexDataManager, ForexDataPoint};
+use crate::patterns::cross_pair::{find_shared_cycles, PairCycles, SharedCycle, SharedCycleConfig};
+use crate::patterns::{HiddenCycle, PatternConfig, PatternRecognizer};
+use crate::symmetry::TemporalSymmetry;
+
+/// Multi-pair scan configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScanConfig {
+    /// Strongest symmetries kept per pair
+    pub top_symmetries: usize,
+    /// When cycles of different pairs count as the same one
+    pub shared_cycles: SharedCycleConfig,
+    /// Pairs analysed at once; 0 uses every available core
+    pub max_parallel: usize,
+}
+
+impl Default for ScanConfig {
+    fn default() -> Self {
+        Self { top_symmetries: 5, shared_cycles: SharedCycleConfig::default(), max_parallel: 0 }
+    }
+}
+
+/// A symmetry in a pair's ranking
+#[derive(Debug, Clone, Serialize)]
+pub struct ScannedSymmetry {
+    pub name: String,
+    pub symmetry_type: String,
+    pub period: u32,
+    pub strength: f64,
+    pub confidence: f64,
+}
+
+/// What the scan found in one pair
+#[derive(Debug, Clone, Serialize)]
+pub struct PairScan {
+    pub pair: String,
+    pub file: PathBuf,
+    pub bars: usize,
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+    pub symmetries_found: usize,
+    /// Strongest first, at most [`ScanConfig::top_symmetries`]
+    pub top_symmetries: Vec<ScannedSymmetry>,
+    pub cycles: Vec<HiddenCycle>,
+}
+
+impl PairScan {
+    pub fn new(
+        pair: &str,
+        file: &Path,
+        data: &[ForexDataPoint],
+        symmetries: &[TemporalSymmetry],
+        cycles: &[HiddenCycle],
+        config: &ScanConfig,
+    ) -> Self {
+        let mut ranked: Vec<&TemporalSymmetry> = symmetries.iter().collect();
+        ranked.sort_by(|a, b| b.strength.total_cmp(&a.strength));
+        Self {
+            pair: pair.to_string(),
+            file: file.to_path_buf(),
+            bars: data.len(),
+            start: data.first().map(|point| point.timestamp),
+            end: data.last().map(|point| point.timestamp),
+            symmetries_found: symmetries.len(),
+            top_symmetries: ranked.into_iter().take(config.top_symmetries).map(|symmetry| ScannedSymmetry {
+                name: symmetry.name.clone(),
+                symmetry_type: symmetry.symmetry_type.clone(),
+                period: symmetry.period_days,
+                strength: symmetry.strength,
+                confidence: symmetry.confidence,
+            }).collect(),
+            cycles: cycles.to_vec(),
+        }
+    }
+
+    /// Strength of the pair's strongest symmetry, 0 when it has none
+    pub fn strongest(&self) -> f64 {
+        self.top_symmetries.first().map(|symmetry| symmetry.strength).unwrap_or(0.0)
+    }
+}
+
+/// A pair the scan could not analyse
+#[derive(Debug, Clone, Serialize)]
+pub struct FailedScan {
+    pub pair: String,
+    pub file: PathBuf,
+    pub error: String,
+}
+
+/// Ranked results of a scan
+#[derive(Debug, Clone, Serialize)]
+pub struct ScanReport {
+    pub generated_at: DateTime<Utc>,
+    /// Strongest symmetry first
+    pub pairs: Vec<PairScan>,
+    /// Most widely shared first
+    pub shared_cycles: Vec<SharedCycle>,
+    pub failed: Vec<FailedScan>,
+}
+
+impl ScanReport {
+    /// Rank `pairs`, all analysed on bars of `bar_seconds`
+    pub fn new(mut pairs: Vec<PairScan>, mut failed: Vec<FailedScan>, bar_seconds: f64, config: &ScanConfig) -> Self {
+        pairs.sort_by(|a, b| b.strongest().total_cmp(&a.strongest()).then_with(|| a.pair.cmp(&b.pair)));
+        failed.sort_by(|a, b| a.pair.cmp(&b.pair));
+        let shared_cycles = shared_cycles(&pairs, bar_seconds, &config.shared_cycles);
+        Self { generated_at: Utc::now(), pairs, shared_cycles, failed }
+    }
+
+    /// Ranking and shared cycles as text table lines
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![format!("{:>4}  {:<8} {:>7}  {:<28} {:>8}  {:>6}", "Rank", "Pair", "Bars", "Strongest symmetry", "Strength", "Cycles")];
+        for (rank, scan) in self.pairs.iter().enumerate() {
+            let (name, strength) = match scan.top_symmetries.first() {
+                Some(symmetry) => (symmetry.name.as_str(), format!("{:.3}", symmetry.strength)),
+                None => ("—", "—".to_string()),
+            };
+            lines.push(format!("{:>4}  {:<8} {:>7}  {:<28} {:>8}  {:>6}", rank + 1, scan.pair, scan.bars, name, strength, scan.cycles.len()));
+        }
+        for shared in &self.shared_cycles {
+            lines.push(format!("Cycle ~{:.1}h in {} pairs ({}), mean confidence {:.3}, phase coherence {:.2}",
+                shared.period_hours, shared.pairs.len(), shared.pairs.join(", "), shared.mean_confidence, shared.phase_coherence));
+        }
+        for failure in &self.failed {
+            lines.push(format!("{} failed: {}", failure.pair, failure.error));
+        }
+        lines
+    }
+
+    /// One row per pair in rank order, with the shared cycle periods it takes part in
+    pub fn write_csv(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut writer = csv::Writer::from_path(path)?;
+        writer.write_record(["rank", "pair", "bars", "start", "end", "symmetries", "top_symmetries", "top_strength", "cycles", "shared_cycle_periods"])?;
+        for (rank, scan) in self.pairs.iter().enumerate() {
+            let shared: Vec<String> = self.shared_cycles.iter()
+                .filter(|shared| shared.pairs.contains(&scan.pair))
+                .map(|shared| format!("{:.1}h", shared.period_hours))
+                .collect();
+            writer.write_record([
+                (rank + 1).to_string(),
+                scan.pair.clone(),
+                scan.bars.to_string(),
+                scan.start.map(|start| start.to_rfc3339()).unwrap_or_default(),
+                scan.end.map(|end| end.to_rfc3339()).unwrap_or_default(),
+                scan.symmetries_found.to_string(),
+                scan.top_symmetries.iter().map(|symmetry| symmetry.name.as_str()).collect::<Vec<_>>().join(";"),
+                scan.top_symmetries.first().map(|symmetry| format!("{:.6}", symmetry.strength)).unwrap_or_default(),
+                scan.cycles.iter().map(|cycle| cycle.period.to_string()).collect::<Vec<_>>().join(";"),
+                shared.join(";"),
+            ])?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Cycle periods shared by `scans`, analysed on bars of `bar_seconds`, with
+/// phases compared at the newest bar scanned
+pub fn shared_cycles(scans: &[PairScan], bar_seconds: f64, config: &SharedCycleConfig) -> Vec<SharedCycle> {
+    let pairs: Vec<PairCycles> = scans.iter()
+        .map(|scan| PairCycles { pair: scan.pair.clone(), bar_seconds, cycles: scan.cycles.clone() })
+        .collect();
+    let reference = scans.iter().filter_map(|scan| scan.end).max().unwrap_or_else(Utc::now);
+    find_shared_cycles(&pairs, reference, config)
+}
+
+/// Load and analyse every pair of `summary` in parallel and rank the results
+pub async fn scan_pairs(
+    summary: &DataSummary,
+    timeframe: &str,
+    data_config: &DataConfig,
+    engine_config: &EngineConfig,
+    pattern_config: &PatternConfig,
+    config: &ScanConfig,
+) -> Result<ScanReport> {
+    if summary.available_pairs.is_empty() {
+        bail!("no CSV files found in {}", data_config.data_directory.display());
+    }
+
+    let parallel = match config.max_parallel {
+        0 => std::thread::available_parallelism().map(|cores| cores.get()).unwrap_or(1),
+        max => max,
+    };
+    let bar_seconds = timeframe_duration(timeframe)?.num_seconds() as f64;
+    let permits = Arc::new(Semaphore::new(parallel));
+    let mut files: Vec<(String, PathBuf)> = summary.available_pairs.iter().map(|(pair, file)| (pair.clone(), file.clone())).collect();
+    files.sort();
+
+    let mut tasks = JoinSet::new();
+    for (pair, file) in files {
+        let permits = Arc::clone(&permits);
+        let (timeframe, data_config, engine_config, pattern_config, config) =
+            (timeframe.to_string(), data_config.clone(), engine_config.clone(), pattern_config.clone(), config.clone());
+        tasks.spawn(async move {
+            let _permit = permits.acquire_owned().await?;
+            let scan = async {
+                let data = ForexDataManager::new(data_config)?.load_data(&file, &pair, &timeframe).await?;
+                if data.is_empty() {
+                    bail!("no bars");
+                }
+                let mut engine = TimeSymmetricEngine::new(engine_config)?;
+                engine.initialize().await?;
+                let symmetries = engine.extract_temporal_symmetries(&data).await?;
+                let cycles = PatternRecognizer::new(pattern_config)?.detect_cycles(&data).await?;
+                Ok(PairScan::new(&pair, &file, &data, &symmetries, &cycles, &config))
+            }.await;
+            anyhow::Ok(scan.map_err(|e: anyhow::Error| FailedScan { pair, file, error: e.to_string() }))
+        });
+    }
+
+    let (mut pairs, mut failed) = (Vec::new(), Vec::new());
+    while let Some(result) = tasks.join_next().await {
+        match result?? {
+            Ok(scan) => pairs.push(scan),
+            Err(failure) => failed.push(failure),
+        }
+    }
+    Ok(ScanReport::new(pairs, failed, bar_seconds, config))
+}
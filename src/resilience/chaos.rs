@@ -0,0 +1,218 @@
+//! # Fault Injection
+//!
+//! Test-only chaos layer: drops feed ticks, fails database writes, has the broker
+//! reject orders and delays responses, each with a configured probability. It does
+//! nothing unless `enabled` is set, and is meant for robustness tests, never for
+//! production runs.
+
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
+use futures_util::future::BoxFuture;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+
+use crate::data::provider::{DataProvider, Tick};
+use crate::data::ForexDataPoint;
+
+/// Fault probabilities
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChaosConfig {
+    pub enabled: bool,
+    /// Probability a live tick is dropped or a history request fails
+    pub feed_drop_rate: f64,
+    /// Probability a database write fails
+    pub db_write_failure_rate: f64,
+    /// Probability the broker rejects an order
+    pub broker_rejection_rate: f64,
+    /// Probability a response (tick, history, order, write) is delayed
+    pub delay_rate: f64,
+    pub delay_ms: u64,
+    /// Seed for reproducible fault sequences; random when unset
+    pub seed: Option<u64>,
+}
+
+impl Default for ChaosConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            feed_drop_rate: 0.0,
+            db_write_failure_rate: 0.0,
+            broker_rejection_rate: 0.0,
+            delay_rate: 0.0,
+            delay_ms: 500,
+            seed: None,
+        }
+    }
+}
+
+/// Faults injected so far
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FaultCounts {
+    pub feed_drops: u64,
+    pub db_write_failures: u64,
+    pub broker_rejections: u64,
+    pub delays: u64,
+}
+
+/// Decides which calls fail; shared by every wrapped dependency
+pub struct FaultInjector {
+    config: ChaosConfig,
+    rng: Mutex<StdRng>,
+    feed_drops: AtomicU64,
+    db_write_failures: AtomicU64,
+    broker_rejections: AtomicU64,
+    delays: AtomicU64,
+}
+
+impl FaultInjector {
+    pub fn new(config: ChaosConfig) -> Result<Self> {
+        for (name, rate) in [
+            ("feed_drop_rate", config.feed_drop_rate),
+            ("db_write_failure_rate", config.db_write_failure_rate),
+            ("broker_rejection_rate", config.broker_rejection_rate),
+            ("delay_rate", config.delay_rate),
+        ] {
+            if !(0.0..=1.0).contains(&rate) {
+                bail!("{} must be in [0, 1], got {}", name, rate);
+            }
+        }
+        let rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        Ok(Self {
+            config,
+            rng: Mutex::new(rng),
+            feed_drops: AtomicU64::new(0),
+            db_write_failures: AtomicU64::new(0),
+            broker_rejections: AtomicU64::new(0),
+            delays: AtomicU64::new(0),
+        })
+    }
+
+    /// Injector configured by the TOML file at `CHAOS_CONFIG`, `None` when unset or disabled
+    pub fn from_env() -> Result<Option<Arc<Self>>> {
+        let Ok(path) = std::env::var("CHAOS_CONFIG") else {
+            return Ok(None);
+        };
+        let config: ChaosConfig = toml::from_str(&std::fs::read_to_string(&path)?)?;
+        if !config.enabled {
+            return Ok(None);
+        }
+        println!("🐒 Chaos mode enabled from {}: {:?}", path, config);
+        Ok(Some(Arc::new(Self::new(config)?)))
+    }
+
+    pub fn config(&self) -> &ChaosConfig {
+        &self.config
+    }
+
+    pub fn counts(&self) -> FaultCounts {
+        FaultCounts {
+            feed_drops: self.feed_drops.load(Ordering::Relaxed),
+            db_write_failures: self.db_write_failures.load(Ordering::Relaxed),
+            broker_rejections: self.broker_rejections.load(Ordering::Relaxed),
+            delays: self.delays.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Whether to drop this tick (or fail this history request)
+    pub fn drop_feed(&self) -> bool {
+        self.roll(self.config.feed_drop_rate, &self.feed_drops)
+    }
+
+    /// Simulated database write outcome
+    pub async fn db_write(&self) -> Result<()> {
+        self.maybe_delay().await;
+        if self.roll(self.config.db_write_failure_rate, &self.db_write_failures) {
+            bail!("database write failed (injected)");
+        }
+        Ok(())
+    }
+
+    /// Simulated broker response to an order
+    pub async fn broker_response(&self) -> Result<()> {
+        self.maybe_delay().await;
+        if self.roll(self.config.broker_rejection_rate, &self.broker_rejections) {
+            bail!("order rejected by broker (injected)");
+        }
+        Ok(())
+    }
+
+    /// Sleep for `delay_ms` with probability `delay_rate`
+    pub async fn maybe_delay(&self) {
+        if self.roll(self.config.delay_rate, &self.delays) {
+            tokio::time::sleep(std::time::Duration::from_millis(self.config.delay_ms)).await;
+        }
+    }
+
+    fn roll(&self, rate: f64, counter: &AtomicU64) -> bool {
+        if !self.config.enabled || rate <= 0.0 {
+            return false;
+        }
+        let hit = self.rng.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).gen_bool(rate);
+        if hit {
+            counter.fetch_add(1, Ordering::Relaxed);
+        }
+        hit
+    }
+}
+
+/// Provider wrapper that drops and delays ticks and fails history requests
+pub struct ChaosProvider {
+    inner: Arc<dyn DataProvider>,
+    injector: Arc<FaultInjector>,
+    name: String,
+}
+
+impl ChaosProvider {
+    pub fn new(inner: Arc<dyn DataProvider>, injector: Arc<FaultInjector>) -> Self {
+        let name = format!("chaos({})", inner.name());
+        Self { inner, injector, name }
+    }
+}
+
+impl DataProvider for ChaosProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn fetch_historical<'a>(
+        &'a self,
+        pair: &'a str,
+        timeframe: &'a str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> BoxFuture<'a, Result<Vec<ForexDataPoint>>> {
+        Box::pin(async move {
+            self.injector.maybe_delay().await;
+            if self.injector.drop_feed() {
+                bail!("{} history request dropped (injected)", pair);
+            }
+            self.inner.fetch_historical(pair, timeframe, from, to).await
+        })
+    }
+
+    fn subscribe_ticks(&self, pairs: &[String]) -> Result<mpsc::Receiver<Tick>> {
+        let mut ticks = self.inner.subscribe_ticks(pairs)?;
+        let injector = Arc::clone(&self.injector);
+        let (sender, receiver) = mpsc::channel(1024);
+        tokio::spawn(async move {
+            while let Some(tick) = ticks.recv().await {
+                if injector.drop_feed() {
+                    continue;
+                }
+                injector.maybe_delay().await;
+                if sender.send(tick).await.is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(receiver)
+    }
+}
@@ -0,0 +1,159 @@
+//! # Resilience
+//!
+//! Circuit breakers that stop the live pipeline from hammering a failing dependency
+//! (broker, database), and a fault injector for exercising them in robustness tests.
+
+pub mod chaos;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+/// When a breaker trips and how long it stays open
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures that open the circuit
+    pub failure_threshold: u32,
+    /// How long an open circuit rejects calls before letting a trial call through
+    pub cooldown_ms: i64,
+    /// Calls taking longer than this count as failures
+    pub call_timeout_ms: u64,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            cooldown_ms: 30_000,
+            call_timeout_ms: 2_000,
+        }
+    }
+}
+
+/// Breaker position
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum BreakerState {
+    /// Calls go through
+    Closed,
+    /// Calls are rejected until the cooldown ends
+    Open { until: DateTime<Utc> },
+    /// One trial call is allowed; its outcome closes or re-opens the circuit
+    HalfOpen,
+}
+
+/// Point-in-time view of a breaker
+#[derive(Debug, Clone, Serialize)]
+pub struct BreakerStatus {
+    pub name: String,
+    pub state: BreakerState,
+    pub consecutive_failures: u32,
+    /// Times the circuit has opened
+    pub trips: u64,
+    /// Calls rejected while open
+    pub rejected: u64,
+    pub last_error: Option<String>,
+}
+
+struct BreakerInner {
+    state: BreakerState,
+    consecutive_failures: u32,
+    trips: u64,
+    rejected: u64,
+    last_error: Option<String>,
+}
+
+/// Thread-safe circuit breaker guarding one dependency
+pub struct CircuitBreaker {
+    name: String,
+    config: CircuitBreakerConfig,
+    inner: Mutex<BreakerInner>,
+}
+
+impl CircuitBreaker {
+    pub fn new(name: &str, config: CircuitBreakerConfig) -> Self {
+        Self {
+            name: name.to_string(),
+            config,
+            inner: Mutex::new(BreakerInner {
+                state: BreakerState::Closed,
+                consecutive_failures: 0,
+                trips: 0,
+                rejected: 0,
+                last_error: None,
+            }),
+        }
+    }
+
+    pub fn config(&self) -> &CircuitBreakerConfig {
+        &self.config
+    }
+
+    /// Per-call timeout as a std duration
+    pub fn call_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.config.call_timeout_ms)
+    }
+
+    /// Whether a call may proceed; an open circuit past its cooldown moves to half-open
+    pub fn allow(&self, now: DateTime<Utc>) -> bool {
+        let mut inner = self.lock();
+        match inner.state {
+            BreakerState::Closed => true,
+            BreakerState::HalfOpen => false, // the trial call is still in flight
+            BreakerState::Open { until } if now >= until => {
+                inner.state = BreakerState::HalfOpen;
+                true
+            }
+            BreakerState::Open { .. } => {
+                inner.rejected += 1;
+                false
+            }
+        }
+    }
+
+    pub fn record_success(&self) {
+        let mut inner = self.lock();
+        if inner.state != BreakerState::Closed {
+            println!("🔌 {} circuit closed", self.name);
+        }
+        inner.state = BreakerState::Closed;
+        inner.consecutive_failures = 0;
+    }
+
+    /// Count a failure, returning true when it opened the circuit
+    pub fn record_failure(&self, error: &str, now: DateTime<Utc>) -> bool {
+        let mut inner = self.lock();
+        inner.consecutive_failures += 1;
+        inner.last_error = Some(error.to_string());
+        let trip = match inner.state {
+            BreakerState::HalfOpen => true,
+            BreakerState::Closed => inner.consecutive_failures >= self.config.failure_threshold,
+            BreakerState::Open { .. } => false,
+        };
+        if trip {
+            let until = now + Duration::milliseconds(self.config.cooldown_ms);
+            inner.state = BreakerState::Open { until };
+            inner.trips += 1;
+            println!("🔌 {} circuit open until {} after {} consecutive failures (last: {})",
+                     self.name, until.format("%H:%M:%S"), inner.consecutive_failures, error);
+        }
+        trip
+    }
+
+    pub fn status(&self) -> BreakerStatus {
+        let inner = self.lock();
+        BreakerStatus {
+            name: self.name.clone(),
+            state: inner.state,
+            consecutive_failures: inner.consecutive_failures,
+            trips: inner.trips,
+            rejected: inner.rejected,
+            last_error: inner.last_error.clone(),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BreakerInner> {
+        self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
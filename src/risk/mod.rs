@@ -0,0 +1,288 @@
+//! # Risk Engine
+//!
+//! Pre-trade checks and a kill switch over the whole portfolio: drawdown,
+//! per-pair, per-currency and correlation limits on orders adding exposure,
+//! and a latency budget past which stale signals are logged instead of traded.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::correlation::CorrelationResult;
+use crate::laplacian_rl::TradingAction;
+use crate::portfolio::{convert, split_symbol, PortfolioSnapshot};
+
+/// Portfolio-level risk limits
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskLimits {
+    /// Maximum drawdown from peak equity, in percent, before new exposure is refused
+    pub max_drawdown_pct: f64,
+    /// Minutes after a liquidity gap during which a pair takes no new entries
+    #[serde(default = "default_liquidity_gap_block_minutes")]
+    pub liquidity_gap_block_minutes: i64,
+    /// Largest net exposure to one currency, in percent of equity
+    #[serde(default = "default_max_currency_exposure_pct")]
+    pub max_currency_exposure_pct: f64,
+    /// Largest position notional in one pair, in percent of equity
+    #[serde(default = "default_max_pair_exposure_pct")]
+    pub max_pair_exposure_pct: f64,
+    /// Drawdown from peak equity, in percent, at which every position is closed
+    #[serde(default = "default_kill_switch_drawdown_pct")]
+    pub kill_switch_drawdown_pct: f64,
+    /// Smallest |correlation| that shrinks orders in the same direction
+    #[serde(default = "default_correlation_floor")]
+    pub correlation_floor: f64,
+    /// How stale a signal may be when it reaches execution
+    #[serde(default)]
+    pub signal_latency: SignalLatencyBudget,
+}
+
+fn default_liquidity_gap_block_minutes() -> i64 {
+    60
+}
+
+fn default_max_currency_exposure_pct() -> f64 {
+    1_000.0
+}
+
+fn default_max_pair_exposure_pct() -> f64 {
+    500.0
+}
+
+fn default_kill_switch_drawdown_pct() -> f64 {
+    20.0
+}
+
+fn default_correlation_floor() -> f64 {
+    0.5
+}
+
+impl Default for RiskLimits {
+    fn default() -> Self {
+        Self {
+            max_drawdown_pct: 10.0,
+            liquidity_gap_block_minutes: default_liquidity_gap_block_minutes(),
+            max_currency_exposure_pct: default_max_currency_exposure_pct(),
+            max_pair_exposure_pct: default_max_pair_exposure_pct(),
+            kill_switch_drawdown_pct: default_kill_switch_drawdown_pct(),
+            correlation_floor: default_correlation_floor(),
+            signal_latency: SignalLatencyBudget::default(),
+        }
+    }
+}
+
+/// Longest a signal may wait between the data it was decided on and its execution;
+/// unlimited when unset
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SignalLatencyBudget {
+    #[serde(default)]
+    pub max_age_seconds: Option<f64>,
+    /// Bars that may arrive after the decision
+    #[serde(default)]
+    pub max_age_bars: Option<usize>,
+}
+
+impl SignalLatencyBudget {
+    /// Why a signal from `origin` has expired at `now`, with `bars` bars in the
+    /// pair's history; `None` while it is still fresh
+    pub fn expired(&self, origin: &SignalOrigin, now: DateTime<Utc>, bars: usize) -> Option<String> {
+        let age_seconds = (now - origin.observed_at).num_milliseconds() as f64 / 1000.0;
+        let age_bars = bars.saturating_sub(origin.bars);
+        if let Some(limit) = self.max_age_seconds.filter(|limit| age_seconds > *limit) {
+            return Some(format!("signal expired: {:.1}s old, budget {:.1}s", age_seconds, limit));
+        }
+        if let Some(limit) = self.max_age_bars.filter(|limit| age_bars > *limit) {
+            return Some(format!("signal expired: {} bars old, budget {}", age_bars, limit));
+        }
+        None
+    }
+}
+
+/// The data a pair's latest actions were decided on
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SignalOrigin {
+    /// When the update that decided them started
+    pub observed_at: DateTime<Utc>,
+    /// Bars in the pair's history at that time
+    pub bars: usize,
+}
+
+/// Outcome of a pre-trade check
+#[derive(Debug, Clone, PartialEq)]
+pub enum RiskDecision {
+    Allow,
+    /// Trade `size` instead of the requested size
+    Resize { size: u32, reason: String },
+    Refuse { reason: String },
+}
+
+/// Why and when the kill switch tripped
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KillSwitch {
+    pub tripped_at: DateTime<Utc>,
+    pub reason: String,
+}
+
+/// Drawdown tracking, pair correlations and the kill switch
+#[derive(Debug, Clone)]
+pub struct RiskEngine {
+    peak_equity: f64,
+    /// Drawdown from peak equity at the last update, in percent
+    drawdown_pct: f64,
+    /// Correlation of returns keyed by the pair of symbols in sorted order
+    correlations: HashMap<(String, String), f64>,
+    kill_switch: Option<KillSwitch>,
+}
+
+impl RiskEngine {
+    pub fn new(initial_equity: f64) -> Self {
+        Self { peak_equity: initial_equity, drawdown_pct: 0.0, correlations: HashMap::new(), kill_switch: None }
+    }
+
+    /// Use the correlations of a [`CrossPairAnalyzer`](crate::correlation::CrossPairAnalyzer) matrix
+    pub fn set_correlations(&mut self, matrix: &HashMap<(String, String), CorrelationResult>) {
+        self.correlations = matrix.values()
+            .filter(|result| result.correlation.is_finite())
+            .map(|result| (pair_key(&result.pair1, &result.pair2), result.correlation))
+            .collect();
+    }
+
+    /// Correlation of two pairs' returns, 0 when unknown
+    pub fn correlation(&self, a: &str, b: &str) -> f64 {
+        self.correlations.get(&pair_key(a, b)).copied().unwrap_or(0.0)
+    }
+
+    /// Track peak equity and return the drawdown from it in percent
+    pub fn update_drawdown(&mut self, equity: f64) -> f64 {
+        self.peak_equity = self.peak_equity.max(equity);
+        self.drawdown_pct = if self.peak_equity > 0.0 { (self.peak_equity - equity) / self.peak_equity * 100.0 } else { 0.0 };
+        self.drawdown_pct
+    }
+
+    pub fn drawdown_pct(&self) -> f64 {
+        self.drawdown_pct
+    }
+
+    pub fn kill_switch(&self) -> Option<&KillSwitch> {
+        self.kill_switch.as_ref()
+    }
+
+    /// Trip the kill switch; returns `false` if it was already tripped
+    pub fn trip(&mut self, reason: &str, timestamp: DateTime<Utc>) -> bool {
+        if self.kill_switch.is_some() {
+            return false;
+        }
+        self.kill_switch = Some(KillSwitch { tripped_at: timestamp, reason: reason.to_string() });
+        true
+    }
+
+    /// Re-arm the kill switch, restarting drawdown from `equity`
+    pub fn reset(&mut self, equity: f64) -> Option<KillSwitch> {
+        self.peak_equity = equity;
+        self.drawdown_pct = 0.0;
+        self.kill_switch.take()
+    }
+
+    /// Trip the kill switch if the drawdown reached the limit, returning it when newly tripped
+    pub fn assess(&mut self, limits: &RiskLimits, timestamp: DateTime<Utc>) -> Option<&KillSwitch> {
+        if self.drawdown_pct >= limits.kill_switch_drawdown_pct {
+            let reason = format!("drawdown {:.2}% reached kill switch at {:.2}%", self.drawdown_pct, limits.kill_switch_drawdown_pct);
+            if self.trip(&reason, timestamp) {
+                return self.kill_switch.as_ref();
+            }
+        }
+        None
+    }
+
+    /// Check `action` in `symbol` at its price in `prices` against the limits and
+    /// the last drawdown.
+    ///
+    /// `units_per_size` converts the action's size to units; closing and holding
+    /// are always allowed.
+    pub fn check(
+        &self,
+        limits: &RiskLimits,
+        symbol: &str,
+        action: &TradingAction,
+        units_per_size: f64,
+        snapshot: &PortfolioSnapshot,
+        prices: &HashMap<String, f64>,
+    ) -> RiskDecision {
+        let (size, direction) = match action {
+            TradingAction::Buy { size } => (*size, 1.0),
+            TradingAction::Sell { size } => (*size, -1.0),
+            TradingAction::ClosePosition | TradingAction::Hold => return RiskDecision::Allow,
+        };
+        if let Some(kill_switch) = &self.kill_switch {
+            return RiskDecision::Refuse { reason: format!("kill switch: {}", kill_switch.reason) };
+        }
+        if self.drawdown_pct > limits.max_drawdown_pct {
+            return RiskDecision::Refuse { reason: format!("drawdown {:.2}% over limit", self.drawdown_pct) };
+        }
+        let price = prices.get(symbol).copied().unwrap_or(0.0);
+        if size == 0 || units_per_size <= 0.0 || price <= 0.0 || snapshot.equity <= 0.0 {
+            return RiskDecision::Allow;
+        }
+
+        let mut units = size as f64 * units_per_size;
+        let mut reason = None;
+
+        let correlated: f64 = snapshot.positions.iter()
+            .filter(|position| position.symbol != symbol)
+            .map(|position| {
+                let correlation = self.correlation(symbol, &position.symbol);
+                let side = if position.side == "LONG" { 1.0 } else { -1.0 };
+                if correlation.abs() >= limits.correlation_floor { (correlation * direction * side).max(0.0) } else { 0.0 }
+            })
+            .sum();
+        if correlated > 0.0 {
+            units /= 1.0 + correlated;
+            reason = Some("correlated exposure");
+        }
+
+        let position = snapshot.positions.iter()
+            .find(|position| position.symbol == symbol)
+            .map(|position| if position.side == "LONG" { position.units } else { -position.units })
+            .unwrap_or(0.0);
+        let (base, quote) = split_symbol(symbol);
+        let account = &snapshot.account_currency;
+        let unit_notional = convert(price, &quote, account, prices).abs();
+        if unit_notional > 0.0 {
+            let pair_limit = limits.max_pair_exposure_pct / 100.0 * snapshot.equity / unit_notional - direction * position;
+            if units > pair_limit {
+                units = pair_limit.max(0.0);
+                reason = Some("pair exposure limit");
+            }
+        }
+
+        let currency_limit = limits.max_currency_exposure_pct / 100.0 * snapshot.equity;
+        for (currency, per_unit) in [(base.as_str(), direction), (quote.as_str(), -direction * price)] {
+            let change = convert(per_unit, currency, account, prices);
+            if change == 0.0 {
+                continue;
+            }
+            let exposure = snapshot.currency_exposure.iter()
+                .find(|exposure| exposure.currency == currency)
+                .map(|exposure| exposure.account_value)
+                .unwrap_or(0.0);
+            let room = (currency_limit - change.signum() * exposure) / change.abs();
+            if units > room {
+                units = room.max(0.0);
+                reason = Some("currency exposure limit");
+            }
+        }
+
+        let resized = (units / units_per_size + 1e-9).floor() as u32;
+        match reason {
+            None => RiskDecision::Allow,
+            Some(reason) if resized == 0 => RiskDecision::Refuse { reason: reason.to_string() },
+            Some(_) if resized >= size => RiskDecision::Allow,
+            Some(reason) => RiskDecision::Resize { size: resized, reason: format!("{} {}→{}", reason, size, resized) },
+        }
+    }
+}
+
+fn pair_key(a: &str, b: &str) -> (String, String) {
+    let (a, b) = (a.to_uppercase(), b.to_uppercase());
+    if a <= b { (a, b) } else { (b, a) }
+}
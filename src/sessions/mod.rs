@@ -0,0 +1,203 @@
+//! # Trading Sessions
+//!
+//! The Sydney, Tokyo, London and New York sessions in their own time zones, so
+//! they keep their local hours across daylight saving changes.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, NaiveTime, Utc, Weekday};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::data::ForexDataPoint;
+use crate::stats::vol_surface::{bar_volatility, VolatilityBucket};
+
+/// Label of bars in the weekend
+pub const WEEKEND: &str = "Closed";
+/// Label of bars in the week with no session open
+pub const OFF_HOURS: &str = "Off-hours";
+
+/// One session as configured: local opening hours in an IANA time zone
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionDefinition {
+    pub name: String,
+    /// IANA time zone, e.g. "Europe/London"
+    pub timezone: String,
+    /// Local opening time, "HH:MM"
+    pub open: String,
+    /// Local closing time, "HH:MM"; before `open` for sessions crossing midnight
+    pub close: String,
+}
+
+impl SessionDefinition {
+    pub fn new(name: &str, timezone: &str, open: &str, close: &str) -> Self {
+        Self { name: name.to_string(), timezone: timezone.to_string(), open: open.to_string(), close: close.to_string() }
+    }
+}
+
+/// Session definitions and the FX week boundary
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionConfig {
+    pub sessions: Vec<SessionDefinition>,
+    /// Time zone of the weekly open and close
+    pub week_timezone: String,
+    /// Local time the week opens on Sunday and closes on Friday, "HH:MM"
+    pub week_boundary: String,
+    /// Bars a session baseline needs before the detector relies on it
+    pub min_samples_per_session: usize,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            sessions: vec![
+                SessionDefinition::new("Sydney", "Australia/Sydney", "07:00", "16:00"),
+                SessionDefinition::new("Tokyo", "Asia/Tokyo", "09:00", "18:00"),
+                SessionDefinition::new("London", "Europe/London", "08:00", "17:00"),
+                SessionDefinition::new("NewYork", "America/New_York", "08:00", "17:00"),
+            ],
+            week_timezone: "America/New_York".to_string(),
+            week_boundary: "17:00".to_string(),
+            min_samples_per_session: 5,
+        }
+    }
+}
+
+fn parse_timezone(name: &str) -> Result<Tz> {
+    name.parse::<Tz>().map_err(|e| anyhow::anyhow!("unknown time zone '{}': {}", name, e))
+}
+
+fn parse_time(value: &str) -> Result<NaiveTime> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M").with_context(|| format!("invalid time '{}', expected HH:MM", value))
+}
+
+#[derive(Debug, Clone)]
+struct Session {
+    name: String,
+    timezone: Tz,
+    open: NaiveTime,
+    close: NaiveTime,
+}
+
+impl Session {
+    /// Open at `timestamp` on a local weekday
+    fn is_open(&self, timestamp: DateTime<Utc>) -> bool {
+        let local = timestamp.with_timezone(&self.timezone);
+        let time = local.time();
+        let (day, inside) = if self.open < self.close {
+            (local.weekday(), time >= self.open && time < self.close)
+        } else if time >= self.open {
+            (local.weekday(), true)
+        } else {
+            // After midnight the session belongs to the day it opened
+            (local.weekday().pred(), time < self.close)
+        };
+        inside && day.num_days_from_monday() < 5
+    }
+}
+
+/// Resolved sessions answering which are open at a given instant
+#[derive(Debug, Clone)]
+pub struct TradingSessions {
+    sessions: Vec<Session>,
+    week_timezone: Tz,
+    week_boundary: NaiveTime,
+    config: SessionConfig,
+}
+
+impl Default for TradingSessions {
+    fn default() -> Self {
+        Self::new(SessionConfig::default()).expect("default sessions are valid")
+    }
+}
+
+impl TradingSessions {
+    pub fn new(config: SessionConfig) -> Result<Self> {
+        let sessions = config.sessions.iter()
+            .map(|definition| Ok(Session {
+                name: definition.name.clone(),
+                timezone: parse_timezone(&definition.timezone).with_context(|| format!("session {}", definition.name))?,
+                open: parse_time(&definition.open).with_context(|| format!("session {}", definition.name))?,
+                close: parse_time(&definition.close).with_context(|| format!("session {}", definition.name))?,
+            }))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            sessions,
+            week_timezone: parse_timezone(&config.week_timezone)?,
+            week_boundary: parse_time(&config.week_boundary)?,
+            config,
+        })
+    }
+
+    pub fn config(&self) -> &SessionConfig {
+        &self.config
+    }
+
+    /// Between the Friday close and the Sunday open of the FX week
+    pub fn is_weekend(&self, timestamp: DateTime<Utc>) -> bool {
+        let local = timestamp.with_timezone(&self.week_timezone);
+        match local.weekday() {
+            Weekday::Sat => true,
+            Weekday::Fri => local.time() >= self.week_boundary,
+            Weekday::Sun => local.time() < self.week_boundary,
+            _ => false,
+        }
+    }
+
+    /// Names of the sessions open at `timestamp`, in configured order
+    pub fn active(&self, timestamp: DateTime<Utc>) -> Vec<&str> {
+        if self.is_weekend(timestamp) {
+            return Vec::new();
+        }
+        self.sessions.iter()
+            .filter(|session| session.is_open(timestamp))
+            .map(|session| session.name.as_str())
+            .collect()
+    }
+
+    /// "London", "London+NewYork" for an overlap, `OFF_HOURS` or `WEEKEND`
+    pub fn label(&self, timestamp: DateTime<Utc>) -> String {
+        if self.is_weekend(timestamp) {
+            return WEEKEND.to_string();
+        }
+        let active = self.active(timestamp);
+        if active.is_empty() {
+            OFF_HOURS.to_string()
+        } else {
+            active.join("+")
+        }
+    }
+}
+
+/// Expected bar volatility per session label
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SessionVolatility {
+    buckets: HashMap<String, VolatilityBucket>,
+    min_samples: usize,
+}
+
+impl SessionVolatility {
+    pub fn from_history(data: &[ForexDataPoint], sessions: &TradingSessions) -> Self {
+        let mut by_session: HashMap<String, Vec<f64>> = HashMap::new();
+        for point in data.iter().filter(|point| point.close > 0.0) {
+            by_session.entry(sessions.label(point.timestamp)).or_default().push(bar_volatility(point));
+        }
+        Self {
+            buckets: by_session.iter().map(|(label, values)| (label.clone(), VolatilityBucket::from_values(values))).collect(),
+            min_samples: sessions.config().min_samples_per_session.max(2),
+        }
+    }
+
+    /// Baseline of `label`, if it has enough bars to rely on
+    pub fn expected(&self, label: &str) -> Option<VolatilityBucket> {
+        self.buckets.get(label).copied().filter(|bucket| bucket.samples >= self.min_samples)
+    }
+
+    /// `(label, bucket)` for every session seen in the history, busiest first
+    pub fn buckets(&self) -> Vec<(&str, VolatilityBucket)> {
+        let mut buckets: Vec<_> = self.buckets.iter().map(|(label, bucket)| (label.as_str(), *bucket)).collect();
+        buckets.sort_by(|a, b| b.1.mean.total_cmp(&a.1.mean));
+        buckets
+    }
+}
@@ -0,0 +1,266 @@
+//! # Composite Signal Score
+//!
+//! One directional score per bar in [-1, 1] from cycle phases, symmetries,
+//! recent anomalies and trend, kept with its breakdown.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+use crate::anomaly::{AnomalyType, DetectedAnomaly};
+use crate::backtest::median_spacing_seconds;
+use crate::data::ForexDataPoint;
+use crate::patterns::HiddenCycle;
+use crate::symmetry::TemporalSymmetry;
+
+/// Bars used to estimate bar spacing and return volatility
+const VOLATILITY_WINDOW: usize = 200;
+
+/// Component weights and look-backs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CompositeScoreConfig {
+    pub cycle_weight: f64,
+    pub symmetry_weight: f64,
+    pub anomaly_weight: f64,
+    pub regime_weight: f64,
+    /// Cycles below this confidence are ignored
+    pub min_cycle_confidence: f64,
+    /// Bars after which an anomaly's influence has halved
+    pub anomaly_half_life_bars: f64,
+    /// Bars in the regime window
+    pub regime_lookback_bars: usize,
+    /// Net move over path length above which the window counts as trending
+    pub trend_efficiency: f64,
+    /// Last bar range, in multiples of the window's mean range, that marks the regime volatile
+    pub volatile_range_ratio: f64,
+}
+
+impl Default for CompositeScoreConfig {
+    fn default() -> Self {
+        Self {
+            cycle_weight: 0.4,
+            symmetry_weight: 0.2,
+            anomaly_weight: 0.25,
+            regime_weight: 0.15,
+            min_cycle_confidence: 0.5,
+            anomaly_half_life_bars: 12.0,
+            regime_lookback_bars: 24,
+            trend_efficiency: 0.3,
+            volatile_range_ratio: 2.5,
+        }
+    }
+}
+
+/// Market regime label of the scored bar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Regime {
+    TrendingUp,
+    TrendingDown,
+    Ranging,
+    Volatile,
+    /// Not enough bars to tell
+    Unknown,
+}
+
+impl std::fmt::Display for Regime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            Regime::TrendingUp => "trending up",
+            Regime::TrendingDown => "trending down",
+            Regime::Ranging => "ranging",
+            Regime::Volatile => "volatile",
+            Regime::Unknown => "unknown",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// Per-component contributions, each in [-1, 1]; `None` when the input is missing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoreComponents {
+    /// Normalized slope of the combined cycle projection
+    pub cycle_phase: Option<f64>,
+    /// Strength-weighted direction of the move that followed one symmetry period ago
+    pub symmetry: Option<f64>,
+    /// Decayed, confidence-weighted direction of recent anomaly signals
+    pub anomaly: f64,
+    /// Trend direction times efficiency ratio; zero when ranging or volatile
+    pub regime: Option<f64>,
+    /// Multiplier in [0, 1] applied to the score after recent breakdown anomalies
+    pub breakdown_damping: f64,
+}
+
+/// Score of one bar
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompositeScore {
+    pub timestamp: DateTime<Utc>,
+    /// Weighted mean of the available components, damped by breakdowns
+    pub score: f64,
+    pub components: ScoreComponents,
+    pub regime: Regime,
+}
+
+/// Scores bars against the current cycles and symmetries
+#[derive(Debug, Clone)]
+pub struct CompositeScorer {
+    config: CompositeScoreConfig,
+    cycles: Vec<HiddenCycle>,
+    symmetries: Vec<TemporalSymmetry>,
+}
+
+impl CompositeScorer {
+    pub fn new(config: CompositeScoreConfig) -> Result<Self> {
+        let weights = [config.cycle_weight, config.symmetry_weight, config.anomaly_weight, config.regime_weight];
+        if weights.iter().any(|w| *w < 0.0 || !w.is_finite()) || weights.iter().sum::<f64>() <= 0.0 {
+            return Err(anyhow!("composite score weights must be non-negative and not all zero, got {:?}", weights));
+        }
+        Ok(Self { config, cycles: Vec::new(), symmetries: Vec::new() })
+    }
+
+    pub fn config(&self) -> &CompositeScoreConfig {
+        &self.config
+    }
+
+    pub fn set_cycles(&mut self, cycles: &[HiddenCycle]) {
+        self.cycles = cycles.to_vec();
+    }
+
+    pub fn set_symmetries(&mut self, symmetries: &[TemporalSymmetry]) {
+        self.symmetries = symmetries.to_vec();
+    }
+
+    /// Score the last bar of `history` using the bars before it and any of
+    /// `anomalies` detected at or before it
+    pub fn score(&self, history: &[ForexDataPoint], anomalies: &[DetectedAnomaly]) -> Option<CompositeScore> {
+        let bar = history.last()?;
+        let tail = &history[history.len().saturating_sub(VOLATILITY_WINDOW)..];
+        let bar_seconds = median_spacing_seconds(tail);
+
+        let (anomaly, breakdown_damping) = self.anomaly_component(bar.timestamp, anomalies, bar_seconds);
+        let (regime, regime_component) = self.regime(history);
+        let components = ScoreComponents {
+            cycle_phase: self.cycle_component(bar.timestamp, bar_seconds),
+            symmetry: self.symmetry_component(history, tail),
+            anomaly,
+            regime: regime_component,
+            breakdown_damping,
+        };
+
+        let weighted = [
+            (components.cycle_phase, self.config.cycle_weight),
+            (components.symmetry, self.config.symmetry_weight),
+            (Some(components.anomaly), self.config.anomaly_weight),
+            (components.regime, self.config.regime_weight),
+        ];
+        let (sum, weight) = weighted.iter()
+            .filter_map(|(value, weight)| value.map(|v| (v * weight, *weight)))
+            .fold((0.0, 0.0), |(s, w), (v, wt)| (s + v, w + wt));
+        let score = if weight > 0.0 { (sum / weight * breakdown_damping).clamp(-1.0, 1.0) } else { 0.0 };
+
+        Some(CompositeScore { timestamp: bar.timestamp, score, components, regime })
+    }
+
+    /// Score every bar of `data` from `start` on, each seeing only the bars and anomalies up to it
+    pub fn score_series(&self, data: &[ForexDataPoint], anomalies: &[DetectedAnomaly], start: usize) -> Vec<CompositeScore> {
+        (start..data.len())
+            .filter_map(|i| self.score(&data[..=i], anomalies))
+            .collect()
+    }
+
+    /// Confidence- and amplitude-weighted slope of the cycles at `timestamp`, divided by
+    /// its largest possible value
+    fn cycle_component(&self, timestamp: DateTime<Utc>, bar_seconds: f64) -> Option<f64> {
+        let t = timestamp.timestamp() as f64 / bar_seconds.max(1.0);
+        let (slope, scale) = self.cycles.iter()
+            .filter(|c| c.confidence >= self.config.min_cycle_confidence && c.period > 0 && c.amplitude > 0.0)
+            .map(|c| {
+                let omega = 2.0 * PI / c.period as f64;
+                let weight = c.confidence * c.amplitude * omega;
+                (weight * (omega * t + c.phase).cos(), weight)
+            })
+            .fold((0.0, 0.0), |(s, w), (slope, weight)| (s + slope, w + weight));
+        (scale > 0.0).then(|| slope / scale)
+    }
+
+    /// What the bar after the one a symmetry period ago did, in units of one-bar
+    /// volatility squashed by `tanh`, averaged by symmetry strength
+    fn symmetry_component(&self, history: &[ForexDataPoint], tail: &[ForexDataPoint]) -> Option<f64> {
+        let bar = history.last()?;
+        let returns: Vec<f64> = tail.windows(2)
+            .filter(|w| w[0].close > 0.0)
+            .map(|w| w[1].close / w[0].close - 1.0)
+            .collect();
+        let mean = returns.iter().sum::<f64>() / returns.len().max(1) as f64;
+        let volatility = (returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / returns.len().max(1) as f64).sqrt();
+        if volatility <= 0.0 {
+            return None;
+        }
+
+        let (sum, strength) = self.symmetries.iter()
+            .filter(|s| s.period_days > 0 && s.strength > 0.0)
+            .filter_map(|s| {
+                let then = bar.timestamp - chrono::Duration::days(s.period_days as i64);
+                let index = history.partition_point(|p| p.timestamp <= then).checked_sub(1)?;
+                let (before, after) = (history.get(index)?, history.get(index + 1)?);
+                (before.close > 0.0 && index + 1 < history.len() - 1)
+                    .then(|| (s.strength * ((after.close / before.close - 1.0) / volatility).tanh(), s.strength))
+            })
+            .fold((0.0, 0.0), |(s, w), (value, strength)| (s + value, w + strength));
+        (strength > 0.0).then(|| sum / strength)
+    }
+
+    /// Direction of recent anomaly signals and the damping from recent breakdowns
+    fn anomaly_component(&self, timestamp: DateTime<Utc>, anomalies: &[DetectedAnomaly], bar_seconds: f64) -> (f64, f64) {
+        let half_life = self.config.anomaly_half_life_bars.max(f64::EPSILON);
+        let mut direction = 0.0;
+        let mut worst_breakdown: f64 = 0.0;
+        for anomaly in anomalies.iter().filter(|a| a.timestamp <= timestamp) {
+            let age_bars = (timestamp - anomaly.timestamp).num_seconds() as f64 / bar_seconds.max(1.0);
+            if age_bars > half_life * 5.0 {
+                continue;
+            }
+            let decay = 0.5f64.powf(age_bars / half_life);
+            if let Some(signal) = &anomaly.trading_signal {
+                let sign = match signal.signal_type.as_str() {
+                    "Buy" => 1.0,
+                    "Sell" => -1.0,
+                    _ => 0.0,
+                };
+                direction += sign * signal.confidence.clamp(0.0, 1.0) * signal.strength.clamp(0.0, 1.0) * decay;
+            }
+            if matches!(anomaly.anomaly_type,
+                AnomalyType::SymmetryBreakdown { .. } | AnomalyType::CycleDisruption { .. }
+                | AnomalyType::PatternInversion { .. } | AnomalyType::LiquidityGap { .. }) {
+                worst_breakdown = worst_breakdown.max(anomaly.confidence.clamp(0.0, 1.0) * decay);
+            }
+        }
+        (direction.clamp(-1.0, 1.0), 1.0 - worst_breakdown)
+    }
+
+    /// Regime of the last `regime_lookback_bars` bars and its directional component
+    fn regime(&self, history: &[ForexDataPoint]) -> (Regime, Option<f64>) {
+        let lookback = self.config.regime_lookback_bars.max(2);
+        if history.len() < lookback + 1 {
+            return (Regime::Unknown, None);
+        }
+        let window = &history[history.len() - lookback - 1..];
+        let last = &window[window.len() - 1];
+        let mean_range = window.iter().map(|p| p.high - p.low).sum::<f64>() / window.len() as f64;
+        if mean_range > 0.0 && last.high - last.low > mean_range * self.config.volatile_range_ratio {
+            return (Regime::Volatile, Some(0.0));
+        }
+
+        let net = last.close - window[0].close;
+        let path: f64 = window.windows(2).map(|w| (w[1].close - w[0].close).abs()).sum();
+        let efficiency = if path > 0.0 { net.abs() / path } else { 0.0 };
+        if efficiency < self.config.trend_efficiency {
+            (Regime::Ranging, Some(0.0))
+        } else if net > 0.0 {
+            (Regime::TrendingUp, Some(efficiency))
+        } else {
+            (Regime::TrendingDown, Some(-efficiency))
+        }
+    }
+}
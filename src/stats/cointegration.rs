@@ -0,0 +1,297 @@
+//! # Cointegration
+//!
+//! Engle-Granger cointegration: the hedge ratio from regressing one pair's log
+//! price on the other's, and an ADF test of the residual spread against
+//! MacKinnon's two-variable critical values.
+
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
+use nalgebra::{DMatrix, DVector};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::data::ForexDataPoint;
+
+/// MacKinnon (2010) critical values of the two-variable Engle-Granger test with a constant
+pub const ENGLE_GRANGER_CRITICAL_1PCT: f64 = -3.90;
+pub const ENGLE_GRANGER_CRITICAL_5PCT: f64 = -3.34;
+pub const ENGLE_GRANGER_CRITICAL_10PCT: f64 = -3.04;
+
+/// Cointegration test and spread trading settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CointegrationConfig {
+    /// Most recent shared bars the test is run on
+    pub lookback: usize,
+    /// Fewest shared bars a test needs
+    pub min_samples: usize,
+    /// Lagged spread differences in the ADF regression
+    pub adf_lags: usize,
+    /// ADF statistic the spread must fall below
+    pub critical_value: f64,
+    /// |z| at which a position in the spread is opened
+    pub entry_z: f64,
+    /// |z| at which it is closed as the spread reverts
+    pub exit_z: f64,
+    /// |z| at which it is closed as the relationship looks broken
+    pub stop_z: f64,
+}
+
+impl Default for CointegrationConfig {
+    fn default() -> Self {
+        Self {
+            lookback: 500,
+            min_samples: 100,
+            adf_lags: 1,
+            critical_value: ENGLE_GRANGER_CRITICAL_5PCT,
+            entry_z: 2.0,
+            exit_z: 0.5,
+            stop_z: 4.0,
+        }
+    }
+}
+
+impl CointegrationConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self.min_samples < self.adf_lags + 10 || self.lookback < self.min_samples {
+            bail!("Cointegration needs min_samples >= adf_lags + 10 and lookback >= min_samples");
+        }
+        if !(0.0 <= self.exit_z && self.exit_z < self.entry_z && self.entry_z < self.stop_z) {
+            bail!("Spread thresholds need 0 <= exit_z < entry_z < stop_z, got {}, {}, {}", self.exit_z, self.entry_z, self.stop_z);
+        }
+        Ok(())
+    }
+}
+
+/// Engle-Granger test of `ln y = intercept + hedge_ratio · ln x + spread`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CointegrationResult {
+    /// Dependent leg
+    pub pair_y: String,
+    /// Hedge leg
+    pub pair_x: String,
+    pub hedge_ratio: f64,
+    pub intercept: f64,
+    /// t-statistic of the spread's mean reversion in the ADF regression
+    pub adf_statistic: f64,
+    pub critical_value: f64,
+    pub cointegrated: bool,
+    /// Bars for a deviation to halve, `None` when the spread does not revert
+    pub half_life_bars: Option<f64>,
+    /// Mean and standard deviation of the spread over the test window
+    pub spread_mean: f64,
+    pub spread_std: f64,
+    pub samples: usize,
+    /// Last bar of the test window
+    pub as_of: DateTime<Utc>,
+}
+
+impl CointegrationResult {
+    /// Spread of the log prices
+    pub fn spread(&self, y: f64, x: f64) -> f64 {
+        y.ln() - self.intercept - self.hedge_ratio * x.ln()
+    }
+
+    /// Standard deviations the spread at these prices is from its mean
+    pub fn z_score(&self, y: f64, x: f64) -> f64 {
+        if self.spread_std > 0.0 {
+            (self.spread(y, x) - self.spread_mean) / self.spread_std
+        } else {
+            0.0
+        }
+    }
+
+    /// Units of the hedge leg that offset `units_y` of the dependent leg in value
+    pub fn hedge_units(&self, units_y: f64, y: f64, x: f64) -> f64 {
+        -self.hedge_ratio * units_y * y / x
+    }
+
+    pub fn summary(&self) -> String {
+        let verdict = if self.cointegrated { "cointegrated" } else { "not cointegrated" };
+        let half_life = self.half_life_bars.map(|bars| format!(", half-life {:.1} bars", bars)).unwrap_or_default();
+        format!("{} ~ {:.3}·{}: ADF {:.2} vs {:.2}, {}{}", self.pair_y, self.hedge_ratio, self.pair_x, self.adf_statistic, self.critical_value, verdict, half_life)
+    }
+}
+
+/// Which way to hold the spread
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpreadPosition {
+    Flat,
+    /// Long the dependent leg, short the hedge leg
+    Long,
+    /// Short the dependent leg, long the hedge leg
+    Short,
+}
+
+/// A change in the spread position
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpreadSignal {
+    pub pair_y: String,
+    pub pair_x: String,
+    pub timestamp: DateTime<Utc>,
+    pub z_score: f64,
+    pub hedge_ratio: f64,
+    pub from: SpreadPosition,
+    pub to: SpreadPosition,
+    pub reason: String,
+}
+
+/// Turns spread z-scores into entries and exits, holding each position until
+/// the spread reverts or stretches past the stop
+#[derive(Debug, Clone)]
+pub struct SpreadTracker {
+    config: CointegrationConfig,
+    position: SpreadPosition,
+}
+
+impl SpreadTracker {
+    pub fn new(config: CointegrationConfig) -> Self {
+        Self { config, position: SpreadPosition::Flat }
+    }
+
+    pub fn position(&self) -> SpreadPosition {
+        self.position
+    }
+
+    /// Signal for the spread of `result` at prices `y` and `x`, if the position changes
+    pub fn update(&mut self, result: &CointegrationResult, timestamp: DateTime<Utc>, y: f64, x: f64) -> Option<SpreadSignal> {
+        let z = result.z_score(y, x);
+        let (to, reason) = match self.position {
+            SpreadPosition::Flat if !result.cointegrated => return None,
+            SpreadPosition::Flat if (self.config.entry_z..self.config.stop_z).contains(&z) => (SpreadPosition::Short, "spread stretched above its mean"),
+            SpreadPosition::Flat if (self.config.entry_z..self.config.stop_z).contains(&-z) => (SpreadPosition::Long, "spread stretched below its mean"),
+            SpreadPosition::Flat => return None,
+            _ if z.abs() >= self.config.stop_z => (SpreadPosition::Flat, "spread stop"),
+            _ if !result.cointegrated => (SpreadPosition::Flat, "cointegration lost"),
+            SpreadPosition::Long if z >= -self.config.exit_z => (SpreadPosition::Flat, "spread reverted"),
+            SpreadPosition::Short if z <= self.config.exit_z => (SpreadPosition::Flat, "spread reverted"),
+            _ => return None,
+        };
+        let from = std::mem::replace(&mut self.position, to);
+        Some(SpreadSignal {
+            pair_y: result.pair_y.clone(),
+            pair_x: result.pair_x.clone(),
+            timestamp,
+            z_score: z,
+            hedge_ratio: result.hedge_ratio,
+            from,
+            to,
+            reason: format!("{} (z {:.2})", reason, z),
+        })
+    }
+}
+
+/// Engle-Granger test over the last `config.lookback` bars both series share
+pub fn engle_granger(
+    pair_y: &str,
+    data_y: &[ForexDataPoint],
+    pair_x: &str,
+    data_x: &[ForexDataPoint],
+    config: &CointegrationConfig,
+) -> Result<CointegrationResult> {
+    config.validate()?;
+    let aligned = aligned_closes(data_y, data_x);
+    let window = &aligned[aligned.len().saturating_sub(config.lookback)..];
+    if window.len() < config.min_samples {
+        bail!("{} and {} share {} bars, {} needed for a cointegration test", pair_y, pair_x, window.len(), config.min_samples);
+    }
+    if window.iter().any(|(_, y, x)| *y <= 0.0 || *x <= 0.0) {
+        bail!("{} and {} have non-positive prices", pair_y, pair_x);
+    }
+    let log_y: Vec<f64> = window.iter().map(|(_, y, _)| y.ln()).collect();
+    let log_x: Vec<f64> = window.iter().map(|(_, _, x)| x.ln()).collect();
+
+    let (intercept, hedge_ratio) = fit_line(&log_x, &log_y)?;
+    let spread: Vec<f64> = log_y.iter().zip(&log_x).map(|(y, x)| y - intercept - hedge_ratio * x).collect();
+    let (gamma, adf_statistic) = adf(&spread, config.adf_lags)?;
+    let spread_mean = spread.iter().sum::<f64>() / spread.len() as f64;
+    let spread_std = (spread.iter().map(|s| (s - spread_mean).powi(2)).sum::<f64>() / (spread.len() - 1) as f64).sqrt();
+    let half_life_bars = (gamma < 0.0 && gamma > -1.0).then(|| -(2.0_f64.ln()) / (1.0 + gamma).ln());
+
+    Ok(CointegrationResult {
+        pair_y: pair_y.to_string(),
+        pair_x: pair_x.to_string(),
+        hedge_ratio,
+        intercept,
+        adf_statistic,
+        critical_value: config.critical_value,
+        cointegrated: adf_statistic < config.critical_value,
+        half_life_bars,
+        spread_mean,
+        spread_std,
+        samples: window.len(),
+        as_of: window[window.len() - 1].0,
+    })
+}
+
+/// Every pair combination in `data_map` that tests cointegrated, most strongly first.
+/// Each combination is tested both ways round and the stronger direction kept.
+pub fn find_cointegrated_pairs(
+    data_map: &HashMap<String, Vec<ForexDataPoint>>,
+    config: &CointegrationConfig,
+) -> Result<Vec<CointegrationResult>> {
+    config.validate()?;
+    let mut pairs: Vec<&String> = data_map.keys().collect();
+    pairs.sort();
+    let mut results = Vec::new();
+    for i in 0..pairs.len() {
+        for j in (i + 1)..pairs.len() {
+            let (a, b) = (pairs[i], pairs[j]);
+            let forward = engle_granger(a, &data_map[a], b, &data_map[b], config);
+            let backward = engle_granger(b, &data_map[b], a, &data_map[a], config);
+            let best = match (forward, backward) {
+                (Ok(f), Ok(r)) => Some(if f.adf_statistic <= r.adf_statistic { f } else { r }),
+                (Ok(result), Err(_)) | (Err(_), Ok(result)) => Some(result),
+                (Err(_), Err(_)) => None,
+            };
+            results.extend(best.filter(|result| result.cointegrated));
+        }
+    }
+    results.sort_by(|a, b| a.adf_statistic.total_cmp(&b.adf_statistic));
+    Ok(results)
+}
+
+/// Closes of the bars both series share, by timestamp
+pub fn aligned_closes(data_y: &[ForexDataPoint], data_x: &[ForexDataPoint]) -> Vec<(DateTime<Utc>, f64, f64)> {
+    let closes_x: HashMap<DateTime<Utc>, f64> = data_x.iter().map(|bar| (bar.timestamp, bar.close)).collect();
+    data_y.iter()
+        .filter_map(|bar| closes_x.get(&bar.timestamp).map(|x| (bar.timestamp, bar.close, *x)))
+        .collect()
+}
+
+/// Least-squares intercept and slope of `y` on `x`
+fn fit_line(x: &[f64], y: &[f64]) -> Result<(f64, f64)> {
+    let n = x.len() as f64;
+    let (mean_x, mean_y) = (x.iter().sum::<f64>() / n, y.iter().sum::<f64>() / n);
+    let var_x: f64 = x.iter().map(|v| (v - mean_x).powi(2)).sum();
+    if var_x <= 0.0 {
+        bail!("Hedge leg price is constant over the test window");
+    }
+    let slope = x.iter().zip(y).map(|(a, b)| (a - mean_x) * (b - mean_y)).sum::<f64>() / var_x;
+    Ok((mean_y - slope * mean_x, slope))
+}
+
+/// ADF regression `Δs_t = γ·s_{t−1} + Σ φ_i·Δs_{t−i} + ε` without a constant (the spread
+/// is a residual with mean zero), returning γ and its t-statistic
+fn adf(spread: &[f64], lags: usize) -> Result<(f64, f64)> {
+    let diffs: Vec<f64> = spread.windows(2).map(|w| w[1] - w[0]).collect();
+    let rows = diffs.len() - lags;
+    let columns = 1 + lags;
+    let design = DMatrix::from_fn(rows, columns, |row, column| {
+        let t = row + lags;
+        if column == 0 { spread[t] } else { diffs[t - column] }
+    });
+    let target = DVector::from_iterator(rows, diffs[lags..].iter().copied());
+    let gram = design.transpose() * &design;
+    let Some(inverse) = gram.try_inverse() else {
+        bail!("ADF regression is singular; the spread is constant");
+    };
+    let coefficients = &inverse * design.transpose() * &target;
+    let residuals = &target - &design * &coefficients;
+    let sigma2 = residuals.norm_squared() / (rows - columns) as f64;
+    let standard_error = (sigma2 * inverse[(0, 0)]).sqrt();
+    if !standard_error.is_finite() || standard_error == 0.0 {
+        bail!("ADF regression has no residual variance");
+    }
+    Ok((coefficients[0], coefficients[0] / standard_error))
+}
@@ -0,0 +1,268 @@
+//! # Distribution Drift
+//!
+//! Compares live returns and volatility with the training window by population
+//! stability index and a two-sample KS test; a feature drifts when both agree.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::data::ForexDataPoint;
+
+/// Smallest bin share in the PSI, so empty bins do not make it infinite
+const PSI_FLOOR: f64 = 1e-4;
+
+/// What a pair does when its inputs drift
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DriftAction {
+    /// Re-extract symmetries and cycles and rebuild the anomaly baselines on the
+    /// current history, which becomes the new reference window
+    Reanalyze,
+    /// Restore the RL agent's initial exploration rate
+    ResetExploration,
+    /// Stop trading the pair until an operator resumes it
+    PauseTrading,
+}
+
+/// Drift monitor configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DriftConfig {
+    pub enabled: bool,
+    /// Newest bars of the training window used as the reference
+    pub reference_bars: usize,
+    /// Newest bars since the training window compared with it
+    pub live_bars: usize,
+    /// Fewest live bars before testing
+    pub min_live_bars: usize,
+    /// Population stability index from which a feature counts as shifted
+    pub psi_threshold: f64,
+    /// KS p-value below which a feature counts as shifted
+    pub ks_alpha: f64,
+    /// Bars after an alert before the next one
+    pub cooldown_bars: usize,
+    pub actions: Vec<DriftAction>,
+}
+
+impl Default for DriftConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            reference_bars: 500,
+            live_bars: 100,
+            min_live_bars: 50,
+            psi_threshold: 0.25,
+            ks_alpha: 0.01,
+            cooldown_bars: 50,
+            actions: vec![DriftAction::Reanalyze, DriftAction::ResetExploration],
+        }
+    }
+}
+
+/// Input distribution a monitor watches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DriftFeature {
+    /// Bar-to-bar log returns
+    Returns,
+    /// Absolute log returns
+    Volatility,
+}
+
+/// Outcome of testing one feature
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DriftTest {
+    pub feature: DriftFeature,
+    pub psi: f64,
+    pub ks_statistic: f64,
+    pub ks_p_value: f64,
+    pub drifted: bool,
+}
+
+/// Raised when a pair's live inputs no longer look like its training window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DriftDetected {
+    pub symbol: String,
+    /// Newest bar tested
+    pub detected_at: DateTime<Utc>,
+    /// Last bar of the training window
+    pub reference_end: DateTime<Utc>,
+    pub live_bars: usize,
+    pub tests: Vec<DriftTest>,
+    /// Actions the pair took in response
+    pub actions: Vec<DriftAction>,
+}
+
+impl DriftDetected {
+    /// One-line description of the drifted features
+    pub fn summary(&self) -> String {
+        self.tests.iter()
+            .filter(|test| test.drifted)
+            .map(|test| format!("{:?} PSI {:.2}, KS {:.2} (p={:.1e})", test.feature, test.psi, test.ks_statistic, test.ks_p_value))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+/// Reference distributions of the training window
+#[derive(Debug, Clone)]
+struct Reference {
+    end: DateTime<Utc>,
+    /// Sorted samples per feature
+    returns: Vec<f64>,
+    volatility: Vec<f64>,
+}
+
+/// Tests live bars against the distributions of a training window
+#[derive(Debug, Clone)]
+pub struct DriftMonitor {
+    config: DriftConfig,
+    reference: Option<Reference>,
+    last_alert: Option<DateTime<Utc>>,
+}
+
+impl DriftMonitor {
+    pub fn new(config: DriftConfig) -> Self {
+        Self { config, reference: None, last_alert: None }
+    }
+
+    pub fn config(&self) -> &DriftConfig {
+        &self.config
+    }
+
+    /// Last bar of the training window, once fitted
+    pub fn reference_end(&self) -> Option<DateTime<Utc>> {
+        self.reference.as_ref().map(|reference| reference.end)
+    }
+
+    /// Take the newest `reference_bars` of `training` as the reference window
+    pub fn fit(&mut self, training: &[ForexDataPoint]) {
+        self.last_alert = None;
+        let Some(last) = training.last() else {
+            self.reference = None;
+            return;
+        };
+        let (mut returns, mut volatility) = features(&training[training.len().saturating_sub(self.config.reference_bars + 1)..]);
+        returns.sort_by(f64::total_cmp);
+        volatility.sort_by(f64::total_cmp);
+        self.reference = Some(Reference { end: last.timestamp, returns, volatility });
+    }
+
+    /// Test the bars of `data` after the training window, returning an event when
+    /// a feature drifted and the cooldown since the last one has passed
+    pub fn check(&mut self, symbol: &str, data: &[ForexDataPoint]) -> Option<DriftDetected> {
+        if !self.config.enabled {
+            return None;
+        }
+        let reference = self.reference.as_ref()?;
+        let last = data.last()?;
+        let live_start = data.partition_point(|bar| bar.timestamp <= reference.end);
+        let new_bars = data.len() - live_start;
+        if new_bars < self.config.min_live_bars.max(2) {
+            return None;
+        }
+        if let Some(alert) = self.last_alert {
+            if data[data.partition_point(|bar| bar.timestamp <= alert)..].len() < self.config.cooldown_bars {
+                return None;
+            }
+        }
+
+        let live = new_bars.min(self.config.live_bars);
+        let (returns, volatility) = features(&data[data.len().saturating_sub(live + 1)..]);
+        let tests = vec![
+            self.test(DriftFeature::Returns, &reference.returns, returns),
+            self.test(DriftFeature::Volatility, &reference.volatility, volatility),
+        ];
+        if !tests.iter().any(|test| test.drifted) {
+            return None;
+        }
+        self.last_alert = Some(last.timestamp);
+        Some(DriftDetected {
+            symbol: symbol.to_string(),
+            detected_at: last.timestamp,
+            reference_end: reference.end,
+            live_bars: live,
+            tests,
+            actions: self.config.actions.clone(),
+        })
+    }
+
+    fn test(&self, feature: DriftFeature, reference: &[f64], mut live: Vec<f64>) -> DriftTest {
+        live.sort_by(f64::total_cmp);
+        let psi = population_stability_index(reference, &live);
+        let ks_statistic = ks_statistic(reference, &live);
+        let ks_p_value = ks_p_value(ks_statistic, reference.len(), live.len());
+        DriftTest {
+            feature,
+            psi,
+            ks_statistic,
+            ks_p_value,
+            drifted: psi >= self.config.psi_threshold && ks_p_value < self.config.ks_alpha,
+        }
+    }
+}
+
+/// PSI of sorted `live` against sorted `reference` over the reference deciles
+pub fn population_stability_index(reference: &[f64], live: &[f64]) -> f64 {
+    if reference.len() < 10 || live.is_empty() {
+        return 0.0;
+    }
+    let edges: Vec<f64> = (1..10).map(|decile| reference[decile * reference.len() / 10]).collect();
+    let shares = |sample: &[f64]| -> Vec<f64> {
+        let mut counts = vec![0usize; edges.len() + 1];
+        for value in sample {
+            counts[edges.partition_point(|edge| edge <= value)] += 1;
+        }
+        counts.iter().map(|count| (*count as f64 / sample.len() as f64).max(PSI_FLOOR)).collect()
+    };
+    shares(reference).iter()
+        .zip(shares(live))
+        .map(|(expected, actual)| (actual - expected) * (actual / expected).ln())
+        .sum()
+}
+
+/// Largest gap between the empirical distribution functions of two sorted samples
+pub fn ks_statistic(a: &[f64], b: &[f64]) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let (mut i, mut j, mut statistic) = (0, 0, 0.0f64);
+    while i < a.len() && j < b.len() {
+        let value = a[i].min(b[j]);
+        while i < a.len() && a[i] <= value {
+            i += 1;
+        }
+        while j < b.len() && b[j] <= value {
+            j += 1;
+        }
+        statistic = statistic.max((i as f64 / a.len() as f64 - j as f64 / b.len() as f64).abs());
+    }
+    statistic
+}
+
+/// Asymptotic p-value of a two-sample KS statistic (Stephens' small-sample correction)
+pub fn ks_p_value(statistic: f64, n: usize, m: usize) -> f64 {
+    if n == 0 || m == 0 {
+        return 1.0;
+    }
+    let effective = ((n * m) as f64 / (n + m) as f64).sqrt();
+    let lambda = (effective + 0.12 + 0.11 / effective) * statistic;
+    if lambda < 1e-3 {
+        return 1.0;
+    }
+    let tail: f64 = (1..=100)
+        .map(|k| {
+            let sign = if k % 2 == 1 { 1.0 } else { -1.0 };
+            sign * (-2.0 * (k * k) as f64 * lambda * lambda).exp()
+        })
+        .sum();
+    (2.0 * tail).clamp(0.0, 1.0)
+}
+
+/// Log returns of `data` and their absolute values
+fn features(data: &[ForexDataPoint]) -> (Vec<f64>, Vec<f64>) {
+    let returns: Vec<f64> = data.windows(2)
+        .filter(|pair| pair[0].close > 0.0 && pair[1].close > 0.0)
+        .map(|pair| (pair[1].close / pair[0].close).ln())
+        .collect();
+    let volatility = returns.iter().map(|r| r.abs()).collect();
+    (returns, volatility)
+}
@@ -0,0 +1,17 @@
+//! # Statistics Module
+//!
+//! Descriptive statistics over historical forex data shared by the detectors.
+
+pub mod cointegration;
+pub mod drift;
+pub mod momentum;
+pub mod vol_surface;
+pub mod volatility;
+pub mod volume_profile;
+
+pub use cointegration::{engle_granger, find_cointegrated_pairs, CointegrationConfig, CointegrationResult, SpreadPosition, SpreadSignal, SpreadTracker};
+pub use drift::{DriftAction, DriftConfig, DriftDetected, DriftMonitor};
+pub use momentum::{MomentumSurface, MomentumSurfaceConfig};
+pub use vol_surface::{vol_surface, VolSurface, VolSurfaceConfig, VolatilityBucket};
+pub use volatility::{VolatilityConfig, VolatilityModel, VolatilityModelKind};
+pub use volume_profile::VolumeProfile;
@@ -0,0 +1,125 @@
+//! # Momentum Surface
+//!
+//! Distribution of N-bar cumulative returns per (day-of-week, hour) bucket. A
+//! directional move can be extreme while every individual bar range stays normal,
+//! so momentum shocks are judged against the return quantiles seen at that time of
+//! the week rather than against bar volatility.
+
+use chrono::{DateTime, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::vol_surface::hour_of_week;
+use crate::data::ForexDataPoint;
+
+const HOURS_PER_WEEK: usize = 7 * 24;
+
+/// Surface construction settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MomentumSurfaceConfig {
+    /// Bars in each cumulative return
+    pub lookback_bars: usize,
+    /// Buckets with fewer returns fall back to the hour-of-day, then the global distribution
+    pub min_samples_per_bucket: usize,
+}
+
+impl Default for MomentumSurfaceConfig {
+    fn default() -> Self {
+        Self {
+            lookback_bars: 12,
+            min_samples_per_bucket: 30,
+        }
+    }
+}
+
+/// Sorted absolute N-bar returns by hour of the week (UTC), keyed by the bar the return ends on
+#[derive(Debug, Clone)]
+pub struct MomentumSurface {
+    config: MomentumSurfaceConfig,
+    /// Indexed by `weekday * 24 + hour`, Monday first
+    hour_of_week: Vec<Vec<f64>>,
+    /// Indexed by hour, across all weekdays
+    hour_of_day: Vec<Vec<f64>>,
+    global: Vec<f64>,
+}
+
+impl MomentumSurface {
+    pub fn from_history(data: &[ForexDataPoint], config: MomentumSurfaceConfig) -> Self {
+        let lookback = config.lookback_bars.max(1);
+        let mut hour_of_week_returns = vec![Vec::new(); HOURS_PER_WEEK];
+        let mut hour_of_day_returns = vec![Vec::new(); 24];
+        let mut global = Vec::new();
+
+        for window in data.windows(lookback + 1) {
+            let Some(value) = cumulative_return(window) else {
+                continue;
+            };
+            let end = window[lookback].timestamp;
+            hour_of_week_returns[hour_of_week(end)].push(value.abs());
+            hour_of_day_returns[end.hour() as usize].push(value.abs());
+            global.push(value.abs());
+        }
+
+        let sort = |values: &mut Vec<f64>| values.sort_by(|a, b| a.total_cmp(b));
+        hour_of_week_returns.iter_mut().for_each(sort);
+        hour_of_day_returns.iter_mut().for_each(sort);
+        sort(&mut global);
+
+        Self {
+            config,
+            hour_of_week: hour_of_week_returns,
+            hour_of_day: hour_of_day_returns,
+            global,
+        }
+    }
+
+    /// Bars in each cumulative return
+    pub fn lookback_bars(&self) -> usize {
+        self.config.lookback_bars.max(1)
+    }
+
+    /// Returns measured across the whole history
+    pub fn samples(&self) -> usize {
+        self.global.len()
+    }
+
+    /// Absolute return at quantile `q` for a move ending at `timestamp`, `None` without history
+    pub fn quantile(&self, timestamp: DateTime<Utc>, q: f64) -> Option<f64> {
+        let values = self.bucket(timestamp);
+        if values.is_empty() {
+            return None;
+        }
+        let index = (q.clamp(0.0, 1.0) * (values.len() - 1) as f64).round() as usize;
+        Some(values[index.min(values.len() - 1)])
+    }
+
+    /// Fraction of historical moves at this time of the week smaller than `abs_return`
+    pub fn rank(&self, timestamp: DateTime<Utc>, abs_return: f64) -> f64 {
+        let values = self.bucket(timestamp);
+        if values.is_empty() {
+            return 0.0;
+        }
+        values.partition_point(|v| *v < abs_return) as f64 / values.len() as f64
+    }
+
+    /// Most specific bucket with enough samples
+    fn bucket(&self, timestamp: DateTime<Utc>) -> &[f64] {
+        let min_samples = self.config.min_samples_per_bucket.max(2);
+        let week_bucket = &self.hour_of_week[hour_of_week(timestamp)];
+        if week_bucket.len() >= min_samples {
+            return week_bucket;
+        }
+        let day_bucket = &self.hour_of_day[timestamp.hour() as usize];
+        if day_bucket.len() >= min_samples {
+            return day_bucket;
+        }
+        &self.global
+    }
+}
+
+/// Return from the first close to the last close of `window`
+pub fn cumulative_return(window: &[ForexDataPoint]) -> Option<f64> {
+    let first = window.first()?.close;
+    let last = window.last()?.close;
+    (first > 0.0 && window.len() > 1).then(|| last / first - 1.0)
+}
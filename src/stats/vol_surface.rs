@@ -0,0 +1,124 @@
+//! # Volatility Surface
+//!
+//! Expected bar volatility per (day-of-week, hour) bucket. Markets are reliably
+//! busier at the London and New York opens than in the Asian afternoon, so a
+//! time-conditional baseline keeps routine session opens from reading as spikes.
+
+use chrono::{DateTime, Datelike, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::data::ForexDataPoint;
+
+const HOURS_PER_WEEK: usize = 7 * 24;
+
+/// Surface construction settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VolSurfaceConfig {
+    /// Buckets with fewer bars fall back to the hour-of-day, then the global baseline
+    pub min_samples_per_bucket: usize,
+}
+
+impl Default for VolSurfaceConfig {
+    fn default() -> Self {
+        Self { min_samples_per_bucket: 5 }
+    }
+}
+
+/// Volatility statistics for one bucket
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct VolatilityBucket {
+    pub mean: f64,
+    pub std_dev: f64,
+    pub samples: usize,
+}
+
+impl VolatilityBucket {
+    pub(crate) fn from_values(values: &[f64]) -> Self {
+        if values.is_empty() {
+            return Self::default();
+        }
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64;
+        Self { mean, std_dev: variance.sqrt(), samples: values.len() }
+    }
+}
+
+/// Expected (high - low) / close volatility by hour of the week (UTC)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VolSurface {
+    config: VolSurfaceConfig,
+    /// Indexed by `weekday * 24 + hour`, Monday first
+    hour_of_week: Vec<VolatilityBucket>,
+    /// Indexed by hour, across all weekdays
+    hour_of_day: Vec<VolatilityBucket>,
+    global: VolatilityBucket,
+}
+
+/// Build the volatility surface of `data` with default settings
+pub fn vol_surface(data: &[ForexDataPoint]) -> VolSurface {
+    VolSurface::from_history(data, VolSurfaceConfig::default())
+}
+
+impl VolSurface {
+    pub fn from_history(data: &[ForexDataPoint], config: VolSurfaceConfig) -> Self {
+        let mut by_hour_of_week = vec![Vec::new(); HOURS_PER_WEEK];
+        let mut by_hour_of_day = vec![Vec::new(); 24];
+        let mut all = Vec::with_capacity(data.len());
+
+        for point in data.iter().filter(|point| point.close > 0.0) {
+            let volatility = bar_volatility(point);
+            by_hour_of_week[hour_of_week(point.timestamp)].push(volatility);
+            by_hour_of_day[point.timestamp.hour() as usize].push(volatility);
+            all.push(volatility);
+        }
+
+        Self {
+            config,
+            hour_of_week: by_hour_of_week.iter().map(|values| VolatilityBucket::from_values(values)).collect(),
+            hour_of_day: by_hour_of_day.iter().map(|values| VolatilityBucket::from_values(values)).collect(),
+            global: VolatilityBucket::from_values(&all),
+        }
+    }
+
+    /// Expected volatility for a bar at `timestamp`, falling back to coarser buckets when sparse
+    pub fn expected(&self, timestamp: DateTime<Utc>) -> VolatilityBucket {
+        let min_samples = self.config.min_samples_per_bucket.max(2);
+        if let Some(week_bucket) = self.hour_of_week_bucket(timestamp) {
+            return week_bucket;
+        }
+        let day_bucket = self.hour_of_day[timestamp.hour() as usize];
+        if day_bucket.samples >= min_samples {
+            return day_bucket;
+        }
+        self.global
+    }
+
+    /// Hour-of-week baseline for `timestamp`, if it has enough bars to rely on
+    pub fn hour_of_week_bucket(&self, timestamp: DateTime<Utc>) -> Option<VolatilityBucket> {
+        let bucket = self.hour_of_week[hour_of_week(timestamp)];
+        (bucket.samples >= self.config.min_samples_per_bucket.max(2)).then_some(bucket)
+    }
+
+    /// Baseline across every bar
+    pub fn global(&self) -> VolatilityBucket {
+        self.global
+    }
+
+    /// `(weekday, hour, bucket)` for every populated hour-of-week bucket, Monday = 0
+    pub fn buckets(&self) -> impl Iterator<Item = (u32, u32, VolatilityBucket)> + '_ {
+        self.hour_of_week.iter()
+            .enumerate()
+            .filter(|(_, bucket)| bucket.samples > 0)
+            .map(|(index, bucket)| ((index / 24) as u32, (index % 24) as u32, *bucket))
+    }
+}
+
+/// Range volatility of one bar, as the anomaly detector measures it
+pub fn bar_volatility(point: &ForexDataPoint) -> f64 {
+    (point.high - point.low) / point.close
+}
+
+pub(crate) fn hour_of_week(timestamp: DateTime<Utc>) -> usize {
+    timestamp.weekday().num_days_from_monday() as usize * 24 + timestamp.hour() as usize
+}
@@ -0,0 +1,289 @@
+//! # Volatility Models
+//!
+//! Conditional volatility of bar log returns by EWMA or GARCH(1,1),
+//! `σ²[t+1] = ω + α·r²[t] + β·σ²[t]`, fitted by maximum likelihood with variance
+//! targeting.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::data::ForexDataPoint;
+
+/// Largest `α + β` a fitted GARCH model may have; at 1 shocks never decay
+const MAX_PERSISTENCE: f64 = 0.999;
+
+/// Which recursion to fit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VolatilityModelKind {
+    Ewma,
+    #[default]
+    Garch,
+}
+
+impl fmt::Display for VolatilityModelKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VolatilityModelKind::Ewma => write!(f, "EWMA"),
+            VolatilityModelKind::Garch => write!(f, "GARCH(1,1)"),
+        }
+    }
+}
+
+impl FromStr for VolatilityModelKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "ewma" => Ok(VolatilityModelKind::Ewma),
+            "garch" | "garch(1,1)" => Ok(VolatilityModelKind::Garch),
+            other => Err(anyhow!("Unknown volatility model '{}' (expected ewma or garch)", other)),
+        }
+    }
+}
+
+/// Volatility model settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VolatilityConfig {
+    pub model: VolatilityModelKind,
+    /// EWMA decay per bar
+    pub ewma_lambda: f64,
+    /// Fewest returns GARCH is fitted on; shorter histories fall back to EWMA
+    pub min_samples: usize,
+}
+
+impl Default for VolatilityConfig {
+    fn default() -> Self {
+        Self {
+            model: VolatilityModelKind::Garch,
+            ewma_lambda: 0.94,
+            min_samples: 100,
+        }
+    }
+}
+
+/// A fitted volatility model and its state after the last bar it has seen
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VolatilityModel {
+    pub kind: VolatilityModelKind,
+    pub omega: f64,
+    pub alpha: f64,
+    pub beta: f64,
+    /// Variance of one bar's log return the model reverts to
+    pub long_run_variance: f64,
+    /// Forecast variance of the next bar's log return
+    pub variance: f64,
+    /// Gaussian log-likelihood of the fitted returns, constants dropped
+    pub log_likelihood: f64,
+    /// Returns the model was fitted on
+    pub samples: usize,
+    /// Median spacing of the fitted bars, the time unit of the variances
+    pub bar_seconds: f64,
+    pub last_close: f64,
+    /// Last bar the model has seen
+    pub as_of: DateTime<Utc>,
+}
+
+impl VolatilityModel {
+    /// Fit the configured model on the close-to-close log returns of `data`,
+    /// falling back to EWMA when there are too few returns for GARCH
+    pub fn fit(data: &[ForexDataPoint], config: &VolatilityConfig) -> Result<Self> {
+        if !(0.0..1.0).contains(&config.ewma_lambda) {
+            return Err(anyhow!("ewma_lambda must be in [0, 1), got {}", config.ewma_lambda));
+        }
+        let returns = log_returns(data);
+        if returns.len() < 2 {
+            return Err(anyhow!("Need at least 3 bars to fit a volatility model, got {}", data.len()));
+        }
+        let sample_variance = returns.iter().map(|r| r * r).sum::<f64>() / returns.len() as f64;
+        if sample_variance <= 0.0 {
+            return Err(anyhow!("History has no price movement to fit a volatility model on"));
+        }
+        let (kind, omega, alpha, beta) = match config.model {
+            VolatilityModelKind::Garch if returns.len() >= config.min_samples => {
+                let (alpha, beta) = fit_garch(&returns, sample_variance);
+                (VolatilityModelKind::Garch, sample_variance * (1.0 - alpha - beta), alpha, beta)
+            }
+            _ => (VolatilityModelKind::Ewma, 0.0, 1.0 - config.ewma_lambda, config.ewma_lambda),
+        };
+        let (log_likelihood, variance) = filter_returns(&returns, omega, alpha, beta, sample_variance);
+        let last = data.iter().rev().find(|bar| bar.close > 0.0).ok_or_else(|| anyhow!("No positive closes"))?;
+        Ok(Self {
+            kind,
+            omega,
+            alpha,
+            beta,
+            long_run_variance: sample_variance,
+            variance,
+            log_likelihood,
+            samples: returns.len(),
+            bar_seconds: median_spacing(data),
+            last_close: last.close,
+            as_of: last.timestamp,
+        })
+    }
+
+    /// A model that always forecasts `volatility` per bar of `bar_seconds`, for
+    /// when there is no history to fit one on
+    pub fn constant(volatility: f64, bar_seconds: f64, last_close: f64, as_of: DateTime<Utc>) -> Self {
+        let variance = volatility * volatility;
+        Self {
+            kind: VolatilityModelKind::Ewma,
+            omega: 0.0,
+            alpha: 0.0,
+            beta: 1.0,
+            long_run_variance: variance,
+            variance,
+            log_likelihood: 0.0,
+            samples: 0,
+            bar_seconds,
+            last_close,
+            as_of,
+        }
+    }
+
+    /// `α + β`, the share of a variance shock left after one bar
+    pub fn persistence(&self) -> f64 {
+        self.alpha + self.beta
+    }
+
+    /// Bars for a variance shock to halve (None when shocks never decay)
+    pub fn half_life_bars(&self) -> Option<f64> {
+        let persistence = self.persistence();
+        (persistence > 0.0 && persistence < 1.0).then(|| 0.5_f64.ln() / persistence.ln())
+    }
+
+    /// Forecast standard deviation of the next bar's log return
+    pub fn volatility(&self) -> f64 {
+        self.variance.sqrt()
+    }
+
+    pub fn long_run_volatility(&self) -> f64 {
+        self.long_run_variance.sqrt()
+    }
+
+    /// Next bar's volatility over the long-run level: above 1 in turbulent markets
+    pub fn volatility_ratio(&self) -> f64 {
+        if self.long_run_variance > 0.0 {
+            (self.variance / self.long_run_variance).sqrt()
+        } else {
+            1.0
+        }
+    }
+
+    /// Forecast variance of the log return `horizon` bars ahead (1 = next bar)
+    pub fn forecast_variance(&self, horizon: usize) -> f64 {
+        let steps = horizon.max(1) - 1;
+        if self.persistence() >= 1.0 {
+            return self.variance;
+        }
+        self.long_run_variance + self.persistence().powi(steps.min(i32::MAX as usize) as i32) * (self.variance - self.long_run_variance)
+    }
+
+    /// Forecast volatility of a bar `seconds` long whose end lies `seconds_ahead`
+    /// past the last bar seen, scaling by the square root of time
+    pub fn forecast_volatility(&self, seconds_ahead: f64, seconds: f64) -> f64 {
+        let bar_seconds = self.bar_seconds.max(1.0);
+        let horizon = (seconds_ahead / bar_seconds).ceil().max(1.0) as usize;
+        (self.forecast_variance(horizon) * seconds / bar_seconds).sqrt()
+    }
+
+    /// Update the forecast with a new bar; bars no newer than the last one seen are ignored
+    pub fn observe(&mut self, bar: &ForexDataPoint) {
+        if bar.timestamp <= self.as_of || bar.close <= 0.0 {
+            return;
+        }
+        if self.last_close > 0.0 {
+            let r = (bar.close / self.last_close).ln();
+            self.variance = self.omega + self.alpha * r * r + self.beta * self.variance;
+        }
+        self.last_close = bar.close;
+        self.as_of = bar.timestamp;
+    }
+
+    /// Forecast volatility of each bar of `data` from the bars before it, the
+    /// first starting at the long-run level
+    pub fn conditional_volatilities(&self, data: &[ForexDataPoint]) -> Vec<f64> {
+        let mut variance = self.long_run_variance;
+        let mut previous: Option<f64> = None;
+        data.iter()
+            .map(|bar| {
+                let volatility = variance.sqrt();
+                if bar.close > 0.0 {
+                    if let Some(previous) = previous {
+                        let r = (bar.close / previous).ln();
+                        variance = self.omega + self.alpha * r * r + self.beta * variance;
+                    }
+                    previous = Some(bar.close);
+                }
+                volatility
+            })
+            .collect()
+    }
+
+    /// "GARCH(1,1) α 0.080 β 0.900: 0.112% per bar (long-run 0.100%, half-life 34.3 bars)"
+    pub fn summary(&self) -> String {
+        let half_life = self.half_life_bars().map_or("shocks persist".to_string(), |h| format!("half-life {:.1} bars", h));
+        format!("{} α {:.3} β {:.3}: {:.3}% per bar (long-run {:.3}%, {})",
+                self.kind, self.alpha, self.beta, self.volatility() * 100.0, self.long_run_volatility() * 100.0, half_life)
+    }
+}
+
+/// Close-to-close log returns, skipping non-positive closes
+pub fn log_returns(data: &[ForexDataPoint]) -> Vec<f64> {
+    let closes: Vec<f64> = data.iter().map(|bar| bar.close).filter(|close| *close > 0.0).collect();
+    closes.windows(2).map(|w| (w[1] / w[0]).ln()).collect()
+}
+
+/// Log-likelihood of `returns` under the recursion and the variance forecast after the last one
+fn filter_returns(returns: &[f64], omega: f64, alpha: f64, beta: f64, initial: f64) -> (f64, f64) {
+    let mut variance = initial;
+    let mut log_likelihood = 0.0;
+    for r in returns {
+        let v = variance.max(f64::MIN_POSITIVE);
+        log_likelihood -= 0.5 * (v.ln() + r * r / v);
+        variance = omega + alpha * r * r + beta * variance;
+    }
+    (log_likelihood, variance)
+}
+
+/// `(α, β)` maximising the variance-targeted likelihood
+fn fit_garch(returns: &[f64], sample_variance: f64) -> (f64, f64) {
+    let likelihood = |alpha: f64, beta: f64| {
+        if alpha <= 0.0 || beta < 0.0 || alpha + beta >= MAX_PERSISTENCE {
+            return f64::NEG_INFINITY;
+        }
+        filter_returns(returns, sample_variance * (1.0 - alpha - beta), alpha, beta, sample_variance).0
+    };
+    let mut best = (0.05, 0.9, f64::NEG_INFINITY);
+    let search = |alphas: Vec<f64>, betas: Vec<f64>, best: &mut (f64, f64, f64)| {
+        for &alpha in &alphas {
+            for &beta in &betas {
+                let ll = likelihood(alpha, beta);
+                if ll > best.2 {
+                    *best = (alpha, beta, ll);
+                }
+            }
+        }
+    };
+    let coarse_alphas = (1..=15).map(|i| i as f64 * 0.02).collect();
+    let coarse_betas = (0..50).map(|i| i as f64 * 0.02).collect();
+    search(coarse_alphas, coarse_betas, &mut best);
+    let (alpha, beta) = (best.0, best.1);
+    let fine = |centre: f64| (-10..=10).map(|i| centre + i as f64 * 0.002).collect::<Vec<_>>();
+    search(fine(alpha), fine(beta), &mut best);
+    (best.0, best.1)
+}
+
+fn median_spacing(data: &[ForexDataPoint]) -> f64 {
+    let mut spacings: Vec<i64> = data.windows(2)
+        .map(|w| (w[1].timestamp - w[0].timestamp).num_seconds())
+        .filter(|s| *s > 0)
+        .collect();
+    spacings.sort_unstable();
+    spacings.get(spacings.len() / 2).copied().unwrap_or(86400) as f64
+}
@@ -0,0 +1,67 @@
+//! # Volume Profile
+//!
+//! Expected bar volume per (day-of-week, hour) bucket, the volume counterpart of
+//! the volatility surface. Bars without a reported volume are left out, so a feed
+//! that never reports volume yields an empty profile.
+
+use chrono::{DateTime, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::vol_surface::{hour_of_week, VolatilityBucket};
+use crate::data::ForexDataPoint;
+
+const HOURS_PER_WEEK: usize = 7 * 24;
+
+/// Expected volume by hour of the week (UTC), with the same fallbacks as the volatility surface
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VolumeProfile {
+    min_samples_per_bucket: usize,
+    /// Indexed by `weekday * 24 + hour`, Monday first
+    hour_of_week: Vec<VolatilityBucket>,
+    /// Indexed by hour, across all weekdays
+    hour_of_day: Vec<VolatilityBucket>,
+    global: VolatilityBucket,
+}
+
+impl VolumeProfile {
+    pub fn from_history(data: &[ForexDataPoint], min_samples_per_bucket: usize) -> Self {
+        let mut by_hour_of_week = vec![Vec::new(); HOURS_PER_WEEK];
+        let mut by_hour_of_day = vec![Vec::new(); 24];
+        let mut all = Vec::new();
+
+        for point in data {
+            let Some(volume) = point.volume.filter(|v| v.is_finite() && *v >= 0.0) else {
+                continue;
+            };
+            by_hour_of_week[hour_of_week(point.timestamp)].push(volume);
+            by_hour_of_day[point.timestamp.hour() as usize].push(volume);
+            all.push(volume);
+        }
+
+        Self {
+            min_samples_per_bucket,
+            hour_of_week: by_hour_of_week.iter().map(|values| VolatilityBucket::from_values(values)).collect(),
+            hour_of_day: by_hour_of_day.iter().map(|values| VolatilityBucket::from_values(values)).collect(),
+            global: VolatilityBucket::from_values(&all),
+        }
+    }
+
+    /// Whether any bar in the history reported a volume
+    pub fn has_volume(&self) -> bool {
+        self.global.samples > 0
+    }
+
+    /// Expected volume for a bar at `timestamp`, falling back to coarser buckets when sparse
+    pub fn expected(&self, timestamp: DateTime<Utc>) -> VolatilityBucket {
+        let min_samples = self.min_samples_per_bucket.max(2);
+        let week_bucket = self.hour_of_week[hour_of_week(timestamp)];
+        if week_bucket.samples >= min_samples {
+            return week_bucket;
+        }
+        let day_bucket = self.hour_of_day[timestamp.hour() as usize];
+        if day_bucket.samples >= min_samples {
+            return day_bucket;
+        }
+        self.global
+    }
+}
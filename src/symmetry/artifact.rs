@@ -0,0 +1,143 @@
+//! # Symmetry Set Artifacts
+//!
+//! Decoded symmetry sets saved as `.symmetries.json` or `.symmetries.bin` with a
+//! schema version, engine configuration hash and data checksum, so an imported
+//! set can be checked against the run that decoded it.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::io::{BufReader, BufWriter, Read};
+use std::path::Path;
+
+use super::TemporalSymmetry;
+use crate::data::ForexDataPoint;
+use crate::embedded_db;
+
+/// Symmetry set file layout version; files of other versions are refused
+pub const SYMMETRY_SET_VERSION: u32 = 1;
+
+/// Encoding of a symmetry set file, chosen by its extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtifactFormat {
+    Json,
+    Binary,
+}
+
+impl ArtifactFormat {
+    pub fn from_path(path: &Path) -> Result<Self> {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("json") => Ok(Self::Json),
+            Some(extension) if extension.eq_ignore_ascii_case("bin") => Ok(Self::Binary),
+            _ => bail!("{} is not a symmetry set; use a .symmetries.json or .symmetries.bin file", path.display()),
+        }
+    }
+}
+
+/// Where a symmetry set was decoded
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SymmetrySetProvenance {
+    pub pair: String,
+    pub timeframe: String,
+    pub bars: usize,
+    pub data_start: Option<DateTime<Utc>>,
+    pub data_end: Option<DateTime<Utc>>,
+    /// [`embedded_db::data_checksum`] of the bars
+    pub data_checksum: String,
+    pub created_at: DateTime<Utc>,
+    /// Analyzer version that decoded the set
+    pub generator: String,
+}
+
+/// Symmetries decoded from one pair's history, with how they were decoded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymmetrySet {
+    /// First so a reader can check it before decoding the rest
+    pub schema_version: u32,
+    /// [`embedded_db::config_hash`] of the engine configuration
+    pub config_hash: String,
+    pub provenance: SymmetrySetProvenance,
+    pub symmetries: Vec<TemporalSymmetry>,
+}
+
+/// Leading field of every version of the layout
+#[derive(Deserialize)]
+struct Header {
+    schema_version: u32,
+}
+
+impl SymmetrySet {
+    /// Set of `symmetries` decoded from `data` of `pair` on `timeframe` with an
+    /// engine configuration hashing to `config_hash`
+    pub fn new(symmetries: Vec<TemporalSymmetry>, data: &[ForexDataPoint], pair: &str, timeframe: &str, config_hash: &str) -> Self {
+        Self {
+            schema_version: SYMMETRY_SET_VERSION,
+            config_hash: config_hash.to_string(),
+            provenance: SymmetrySetProvenance {
+                pair: pair.to_uppercase(),
+                timeframe: timeframe.to_string(),
+                bars: data.len(),
+                data_start: data.first().map(|bar| bar.timestamp),
+                data_end: data.last().map(|bar| bar.timestamp),
+                data_checksum: embedded_db::data_checksum(data),
+                created_at: Utc::now(),
+                generator: format!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")),
+            },
+            symmetries,
+        }
+    }
+
+    /// Write the set to `path`, encoded by its extension
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let format = ArtifactFormat::from_path(path)?;
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        let partial = path.with_extension("partial");
+        let writer = BufWriter::new(std::fs::File::create(&partial)?);
+        match format {
+            ArtifactFormat::Json => serde_json::to_writer_pretty(writer, self)?,
+            ArtifactFormat::Binary => bincode::serialize_into(writer, self)?,
+        }
+        std::fs::rename(&partial, path)?;
+        Ok(())
+    }
+
+    /// Read a set saved by [`SymmetrySet::save`]
+    pub fn load(path: &Path) -> Result<Self> {
+        let format = ArtifactFormat::from_path(path)?;
+        let mut bytes = Vec::new();
+        BufReader::new(std::fs::File::open(path).with_context(|| format!("cannot open symmetry set {}", path.display()))?)
+            .read_to_end(&mut bytes)?;
+        let header: Header = decode(format, &bytes).with_context(|| format!("cannot read symmetry set {}", path.display()))?;
+        if header.schema_version != SYMMETRY_SET_VERSION {
+            bail!("{} has symmetry set version {}, expected {}", path.display(), header.schema_version, SYMMETRY_SET_VERSION);
+        }
+        decode(format, &bytes).with_context(|| format!("cannot read symmetry set {}", path.display()))
+    }
+
+    /// Ways a run on `pair` with an engine configuration hashing to
+    /// `config_hash`, and on `data` when it should see the same bars, differs
+    /// from the one that decoded the set; empty when the run reproduces it
+    pub fn mismatches(&self, pair: &str, config_hash: &str, data: Option<&[ForexDataPoint]>) -> Vec<String> {
+        let mut mismatches = Vec::new();
+        if !self.provenance.pair.eq_ignore_ascii_case(pair) {
+            mismatches.push(format!("decoded from {}, not {}", self.provenance.pair, pair));
+        }
+        if self.config_hash != config_hash {
+            mismatches.push(format!("engine configuration {} differs from {}", self.config_hash, config_hash));
+        }
+        if let Some(checksum) = data.map(embedded_db::data_checksum).filter(|checksum| *checksum != self.provenance.data_checksum) {
+            mismatches.push(format!("decoded from bars {}, not {}", self.provenance.data_checksum, checksum));
+        }
+        mismatches
+    }
+}
+
+fn decode<T: DeserializeOwned>(format: ArtifactFormat, bytes: &[u8]) -> Result<T> {
+    Ok(match format {
+        ArtifactFormat::Json => serde_json::from_slice(bytes)?,
+        ArtifactFormat::Binary => bincode::deserialize(bytes)?,
+    })
+}
@@ -1,14 +1,41 @@
 //! # Temporal Symmetry Detection
-//! 
-//! Detection and analysis of temporal symmetries in forex data.
+//!
+//! Detection of mirror, rotational and translational symmetries ending at the
+//! newest bar. Periods are in bars.
 
+use anyhow::{bail, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::data::ForexDataPoint;
+use crate::galois::GaloisField;
+use crate::ids::SymmetryId;
+
+pub mod artifact;
+pub mod resolution;
+
+pub use artifact::{ArtifactFormat, SymmetrySet, SymmetrySetProvenance, SYMMETRY_SET_VERSION};
+pub use resolution::{compare_resolutions, ResolutionComparison, ResolutionMatch};
+
+/// `symmetry_type` of a mirror symmetry: the last `period` bars retrace the
+/// `period` bars before them in reverse, as in a V
+pub const MIRROR: &str = "Mirror";
+/// `symmetry_type` of a rotational symmetry: the price path repeats every `period` bars
+pub const ROTATIONAL: &str = "Rotational";
+/// `symmetry_type` of a translational symmetry: each of the last few `period`-bar
+/// blocks moves price by the same amount
+pub const TRANSLATIONAL: &str = "Translational";
+
+/// Blocks compared for a translational symmetry
+const TRANSLATION_BLOCKS: usize = 4;
+
+/// Margin by which a longer period must beat a shorter one to be preferred
+const HARMONIC_MARGIN: f64 = 0.02;
+
 /// Temporal symmetry structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TemporalSymmetry {
-    pub id: String,
+    pub id: SymmetryId,
     pub symmetry_type: String,  // Type of symmetry (e.g., "Mirror", "Rotational", "Cyclic")
     pub name: String,
     pub period_days: u32,
@@ -21,7 +48,243 @@ pub struct TemporalSymmetry {
     pub phase_shift: f64,                // Phase shift in the symmetry
 }
 
+/// Symmetry detector configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SymmetryDetectorConfig {
+    /// Shortest period tried, in bars
+    pub min_period: usize,
+    /// Longest period tried, in bars
+    pub max_period: usize,
+    /// Newest bars searched
+    pub max_history: usize,
+    /// Weakest symmetry reported
+    pub min_strength: f64,
+}
+
+impl Default for SymmetryDetectorConfig {
+    fn default() -> Self {
+        Self {
+            min_period: 5,
+            max_period: 64,
+            max_history: 1000,
+            min_strength: 0.6,
+        }
+    }
+}
+
 /// Symmetry detector
 pub struct SymmetryDetector {
-    // Placeholder for symmetry detection logic
+    config: SymmetryDetectorConfig,
+    galois_field: GaloisField,
+}
+
+impl SymmetryDetector {
+    pub fn new(config: SymmetryDetectorConfig) -> Result<Self> {
+        if config.min_period < 2 || config.max_period < config.min_period {
+            bail!("symmetry periods must satisfy 2 <= min_period <= max_period, got {}..{}", config.min_period, config.max_period);
+        }
+        Ok(Self { config, galois_field: GaloisField::new(2147483647)? })
+    }
+
+    pub fn config(&self) -> &SymmetryDetectorConfig {
+        &self.config
+    }
+
+    /// Strongest symmetry of each kind ending at the newest bar
+    pub fn detect(&self, data: &[ForexDataPoint]) -> Vec<TemporalSymmetry> {
+        [self.detect_mirror(data), self.detect_rotational(data), self.detect_translational(data)]
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    /// Strongest mirror ending at the newest bar
+    pub fn detect_mirror(&self, data: &[ForexDataPoint]) -> Option<TemporalSymmetry> {
+        let data = self.recent(data);
+        let (period, strength) = self.best_period(|period| mirror_strength(data, period))?;
+        let pivot = data.len() - 1 - period;
+        let points = [pivot - period, pivot, data.len() - 1].iter()
+            .map(|&i| (data[i].timestamp.timestamp() as f64, data[i].close))
+            .collect();
+        Some(self.symmetry(MIRROR, period, strength, data, points, 0.0))
+    }
+
+    /// Strongest cycle in the recent bars; `phase_shift` is where the newest bar
+    /// sits in it, as a fraction of the period from its trough-to-peak zero crossing
+    pub fn detect_rotational(&self, data: &[ForexDataPoint]) -> Option<TemporalSymmetry> {
+        let data = self.recent(data);
+        let (period, strength) = self.best_period(|period| rotational_strength(data, period))?;
+        let phase = cycle_phase(data, period);
+        Some(self.symmetry(ROTATIONAL, period, strength, data, Vec::new(), phase))
+    }
+
+    /// Strongest steady trend in the recent bars; `mirror_points` hold the start and
+    /// end of the blocks compared, so their order gives the direction
+    pub fn detect_translational(&self, data: &[ForexDataPoint]) -> Option<TemporalSymmetry> {
+        let data = self.recent(data);
+        let (period, strength) = self.best_period(|period| translational_strength(data, period))?;
+        let start = data.len() - 1 - period * TRANSLATION_BLOCKS;
+        let points = [start, data.len() - 1].iter()
+            .map(|&i| (data[i].timestamp.timestamp() as f64, data[i].close))
+            .collect();
+        Some(self.symmetry(TRANSLATIONAL, period, strength, data, points, 0.0))
+    }
+
+    /// Whether `symmetry_type` is one of the kinds this detector finds
+    pub fn detects(symmetry_type: &str) -> bool {
+        matches!(symmetry_type, MIRROR | ROTATIONAL | TRANSLATIONAL)
+    }
+
+    /// How strongly `symmetry` holds at the end of `window`, in [0, 1]; `None` when
+    /// the window is too short for its period or the kind is not one this detector finds
+    pub fn measure(&self, symmetry: &TemporalSymmetry, window: &[ForexDataPoint]) -> Option<f64> {
+        Self::strength_at(&symmetry.symmetry_type, symmetry.period_days as usize, window)
+    }
+
+    /// Strength of a `symmetry_type` symmetry of `period` bars at the end of `window`,
+    /// as in [`measure`](Self::measure)
+    pub fn strength_at(symmetry_type: &str, period: usize, window: &[ForexDataPoint]) -> Option<f64> {
+        match symmetry_type {
+            MIRROR if window.len() > 2 * period => Some(mirror_strength(window, period).unwrap_or(0.0)),
+            ROTATIONAL if window.len() > 3 * period => Some(rotational_strength(window, period).unwrap_or(0.0)),
+            TRANSLATIONAL if window.len() > TRANSLATION_BLOCKS * period => Some(translational_strength(window, period).unwrap_or(0.0)),
+            _ => None,
+        }
+    }
+
+    /// Product-moment of prices `lag` bars apart, relative to a baseline mean and
+    /// standard deviation, in [0, 1]
+    pub fn lag_correlation(prices: &[f64], lag: usize, mean: f64, std_dev: f64) -> f64 {
+        if prices.len() < 2 || prices.len() <= lag {
+            return 0.0;
+        }
+        let count = prices.len() - lag;
+        let correlation = (0..count).map(|i| prices[i] * prices[i + lag]).sum::<f64>() / count as f64;
+        ((correlation - mean.powi(2)) / std_dev.powi(2)).abs().min(1.0)
+    }
+
+    fn recent<'a>(&self, data: &'a [ForexDataPoint]) -> &'a [ForexDataPoint] {
+        &data[data.len().saturating_sub(self.config.max_history)..]
+    }
+
+    /// Period with the highest strength, if it reaches `min_strength`; a longer
+    /// period must win by [`HARMONIC_MARGIN`] so harmonics do not displace the fundamental
+    fn best_period(&self, strength: impl Fn(usize) -> Option<f64>) -> Option<(usize, f64)> {
+        (self.config.min_period..=self.config.max_period)
+            .filter_map(|period| strength(period).map(|s| (period, s)))
+            .fold(None, |best: Option<(usize, f64)>, (period, s)| match best {
+                Some((_, best_strength)) if best_strength + HARMONIC_MARGIN >= s => best,
+                _ => Some((period, s)),
+            })
+            .filter(|(_, strength)| *strength >= self.config.min_strength)
+    }
+
+    fn symmetry(
+        &self,
+        kind: &str,
+        period: usize,
+        strength: f64,
+        data: &[ForexDataPoint],
+        mirror_points: Vec<(f64, f64)>,
+        phase_shift: f64,
+    ) -> TemporalSymmetry {
+        let last = data.last().expect("a detected symmetry has bars");
+        // Short periods rest on few samples
+        let confidence = strength * (1.0 - 1.0 / (period as f64).sqrt());
+        TemporalSymmetry {
+            id: SymmetryId::new(),
+            symmetry_type: kind.to_string(),
+            name: format!("{}-bar {}", period, kind.to_lowercase()),
+            period_days: period as u32,
+            strength,
+            confidence,
+            field_signature: self.galois_field.encode_temporal_state(last.timestamp.timestamp() as u64, (last.close * 10000.0) as u64),
+            discovered_at: Utc::now(),
+            validation_score: strength,
+            mirror_points,
+            phase_shift,
+        }
+    }
+}
+
+/// Price changes bar to bar; `changes[i]` ends at bar `i + 1`
+fn changes(data: &[ForexDataPoint]) -> Vec<f64> {
+    data.windows(2).map(|pair| pair[1].close - pair[0].close).collect()
+}
+
+fn correlation(a: &[f64], b: &[f64]) -> Option<f64> {
+    let n = a.len().min(b.len());
+    if n < 3 {
+        return None;
+    }
+    let (mean_a, mean_b) = (a[..n].iter().sum::<f64>() / n as f64, b[..n].iter().sum::<f64>() / n as f64);
+    let (mut covariance, mut var_a, mut var_b) = (0.0, 0.0, 0.0);
+    for i in 0..n {
+        let (da, db) = (a[i] - mean_a, b[i] - mean_b);
+        covariance += da * db;
+        var_a += da * da;
+        var_b += db * db;
+    }
+    if var_a <= f64::EPSILON * f64::EPSILON || var_b <= f64::EPSILON * f64::EPSILON {
+        return None;
+    }
+    Some(covariance / (var_a * var_b).sqrt())
+}
+
+/// Correlation of the last `period` changes with the `period` before them reversed
+/// and negated, which is what retracing a path backwards looks like
+fn mirror_strength(data: &[ForexDataPoint], period: usize) -> Option<f64> {
+    if period < 2 || data.len() < 2 * period + 1 {
+        return None;
+    }
+    let changes = changes(data);
+    let split = changes.len() - period;
+    let after = &changes[split..];
+    let before: Vec<f64> = changes[split - period..split].iter().rev().map(|change| -change).collect();
+    correlation(after, &before).map(|c| c.max(0.0))
+}
+
+/// Autocorrelation of changes `period` bars apart
+fn rotational_strength(data: &[ForexDataPoint], period: usize) -> Option<f64> {
+    let changes = changes(data);
+    if period < 2 || changes.len() < 3 * period {
+        return None;
+    }
+    correlation(&changes[period..], &changes[..changes.len() - period]).map(|c| c.max(0.0))
+}
+
+/// Consistency of the price moves over the last [`TRANSLATION_BLOCKS`] blocks of
+/// `period` bars: |mean| / (|mean| + standard deviation)
+fn translational_strength(data: &[ForexDataPoint], period: usize) -> Option<f64> {
+    if period < 1 || data.len() < period * TRANSLATION_BLOCKS + 1 {
+        return None;
+    }
+    let end = data.len() - 1;
+    let moves: Vec<f64> = (0..TRANSLATION_BLOCKS)
+        .map(|block| data[end - block * period].close - data[end - (block + 1) * period].close)
+        .collect();
+    let mean = moves.iter().sum::<f64>() / moves.len() as f64;
+    let std_dev = (moves.iter().map(|m| (m - mean).powi(2)).sum::<f64>() / (moves.len() - 1) as f64).sqrt();
+    if mean.abs() + std_dev <= f64::EPSILON {
+        return None;
+    }
+    Some(mean.abs() / (mean.abs() + std_dev))
+}
+
+/// Phase of the newest bar in a sine of `period` bars fitted to the closes about their mean,
+/// as a fraction of the period in [0, 1)
+fn cycle_phase(data: &[ForexDataPoint], period: usize) -> f64 {
+    let n = data.len();
+    let mean = data.iter().map(|bar| bar.close).sum::<f64>() / n as f64;
+    let (mut sine, mut cosine) = (0.0, 0.0);
+    for (i, bar) in data.iter().enumerate() {
+        let angle = 2.0 * std::f64::consts::PI * i as f64 / period as f64;
+        sine += (bar.close - mean) * angle.sin();
+        cosine += (bar.close - mean) * angle.cos();
+    }
+    // close ≈ A sin(angle + offset), with offset = atan2(cosine, sine)
+    let offset = cosine.atan2(sine);
+    let angle = 2.0 * std::f64::consts::PI * (n - 1) as f64 / period as f64 + offset;
+    (angle / (2.0 * std::f64::consts::PI)).rem_euclid(1.0)
 }
@@ -0,0 +1,150 @@
+//! # Multi-Resolution Symmetry Comparison
+//!
+//! Checks symmetries decoded from daily bars are still there in hourly bars at
+//! 24 times the period, scoring the strength reached above chance.
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+use super::{SymmetryDetector, TemporalSymmetry};
+use crate::data::provider::timeframe_duration;
+use crate::data::ForexDataPoint;
+use crate::ids::SymmetryId;
+
+/// Multiples of the expected period whose bands measure chance; the harmonics at ½ and 2 are avoided
+const CONTROL_SCALES: [f64; 4] = [0.6, 0.7, 1.4, 1.6];
+
+/// Widest tolerance that keeps the expected band clear of the control bands
+const MAX_TOLERANCE: f64 = 0.15;
+
+/// Support from which a coarse symmetry counts as confirmed
+const CONFIRMED_SUPPORT: f64 = 0.5;
+
+/// One coarse symmetry re-measured on the fine bars
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolutionMatch {
+    pub symmetry: SymmetryId,
+    pub name: String,
+    pub symmetry_type: String,
+    /// Period in coarse bars
+    pub coarse_period: u32,
+    pub coarse_strength: f64,
+    /// Coarse period in fine bars
+    pub expected_period: f64,
+    /// Fine period near `expected_period` where the symmetry is strongest
+    pub fine_period: u32,
+    pub fine_strength: f64,
+    /// Mean of the best strengths in the control bands
+    pub control_strength: f64,
+    /// Share of the headroom above `control_strength` that `fine_strength` reaches, in [0, 1]
+    pub support: f64,
+    pub confirmed: bool,
+}
+
+/// Symmetries of a coarse timeframe cross-referenced against a fine one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolutionComparison {
+    pub coarse_timeframe: String,
+    pub fine_timeframe: String,
+    /// Fine bars per coarse bar
+    pub ratio: f64,
+    pub matches: Vec<ResolutionMatch>,
+    /// Coarse symmetries of a kind the detector cannot re-measure, or too long for the fine bars
+    pub unmeasured: Vec<SymmetryId>,
+    /// Coarse-confidence-weighted mean support; 0 when nothing could be measured
+    pub consistency_score: f64,
+}
+
+impl ResolutionComparison {
+    /// Coarse symmetries confirmed on the fine timeframe
+    pub fn confirmed(&self) -> usize {
+        self.matches.iter().filter(|m| m.confirmed).count()
+    }
+}
+
+/// Re-measure `coarse` symmetries, found on `coarse_timeframe` bars, on the
+/// `fine` bars of `fine_timeframe` within `tolerance` (relative) of the scaled period
+pub fn compare_resolutions(
+    coarse_timeframe: &str,
+    coarse: &[TemporalSymmetry],
+    fine_timeframe: &str,
+    fine: &[ForexDataPoint],
+    tolerance: f64,
+) -> Result<ResolutionComparison> {
+    if !(tolerance > 0.0 && tolerance <= MAX_TOLERANCE) {
+        bail!("Resolution tolerance must be in (0, {}], got {}", MAX_TOLERANCE, tolerance);
+    }
+    let ratio = timeframe_duration(coarse_timeframe)?.num_seconds() as f64 / timeframe_duration(fine_timeframe)?.num_seconds() as f64;
+    if ratio <= 1.0 {
+        bail!("{} is not finer than {}", fine_timeframe, coarse_timeframe);
+    }
+
+    let mut matches = Vec::new();
+    let mut unmeasured = Vec::new();
+    for symmetry in coarse {
+        let kind = symmetry.symmetry_type.as_str();
+        let expected = symmetry.period_days as f64 * ratio;
+        let measured = SymmetryDetector::detects(kind)
+            .then(|| strongest_in_band(kind, expected, tolerance, fine))
+            .flatten();
+        let controls: Vec<f64> = CONTROL_SCALES.iter()
+            .filter_map(|scale| strongest_in_band(kind, expected * scale, tolerance, fine))
+            .map(|(_, strength)| strength)
+            .collect();
+        let Some((fine_period, fine_strength)) = measured.filter(|_| !controls.is_empty()) else {
+            unmeasured.push(symmetry.id.clone());
+            continue;
+        };
+        let control_strength = controls.iter().sum::<f64>() / controls.len() as f64;
+        let support = if control_strength < 1.0 {
+            ((fine_strength - control_strength) / (1.0 - control_strength)).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        matches.push(ResolutionMatch {
+            symmetry: symmetry.id.clone(),
+            name: symmetry.name.clone(),
+            symmetry_type: symmetry.symmetry_type.clone(),
+            coarse_period: symmetry.period_days,
+            coarse_strength: symmetry.strength,
+            expected_period: expected,
+            fine_period: fine_period as u32,
+            fine_strength,
+            control_strength,
+            support,
+            confirmed: support >= CONFIRMED_SUPPORT,
+        });
+    }
+
+    let coarse_confidence = |m: &ResolutionMatch| coarse.iter()
+        .find(|s| s.id == m.symmetry)
+        .map(|s| s.confidence.max(f64::EPSILON))
+        .unwrap_or(f64::EPSILON);
+    let weight: f64 = matches.iter().map(coarse_confidence).sum();
+    let consistency_score = if matches.is_empty() {
+        0.0
+    } else {
+        matches.iter().map(|m| m.support * coarse_confidence(m)).sum::<f64>() / weight
+    };
+
+    Ok(ResolutionComparison {
+        coarse_timeframe: coarse_timeframe.to_uppercase(),
+        fine_timeframe: fine_timeframe.to_uppercase(),
+        ratio,
+        matches,
+        unmeasured,
+        consistency_score,
+    })
+}
+
+/// Period within `tolerance` of `center` where a `kind` symmetry is strongest at the end of `bars`
+fn strongest_in_band(kind: &str, center: f64, tolerance: f64, bars: &[ForexDataPoint]) -> Option<(usize, f64)> {
+    let low = (center * (1.0 - tolerance)).ceil().max(2.0) as usize;
+    let high = ((center * (1.0 + tolerance)).floor() as usize).max(low);
+    (low..=high)
+        .filter_map(|period| SymmetryDetector::strength_at(kind, period, bars).map(|strength| (period, strength)))
+        .fold(None, |best: Option<(usize, f64)>, (period, strength)| match best {
+            Some((_, best_strength)) if best_strength >= strength => best,
+            _ => Some((period, strength)),
+        })
+}
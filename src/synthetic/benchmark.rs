@@ -0,0 +1,125 @@
+//! # Benchmark Datasets
+//!
+//! Standardized, fully deterministic daily series in three sizes with known cycles
+//! injected, so pipeline timings are comparable across runs and machines and cycle
+//! detection can be checked against ground truth. Unlike the demo data these do not
+//! go through the synthetic generator, whose output would change with it.
+
+use anyhow::Result;
+use chrono::{Duration, TimeZone, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+use crate::data::ForexDataPoint;
+
+/// Price level every benchmark series oscillates around
+const BASE_PRICE: f64 = 1.1;
+/// Standard deviation of the white noise on each close, relative to the price
+const NOISE: f64 = 0.001;
+/// Total drift over the series, relative to the price
+const DRIFT: f64 = 0.05;
+
+/// Standard dataset size
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BenchmarkSize {
+    Small,
+    Medium,
+    Large,
+}
+
+impl BenchmarkSize {
+    /// Daily bars in a dataset of this size
+    pub fn bars(self) -> usize {
+        match self {
+            BenchmarkSize::Small => 750,
+            BenchmarkSize::Medium => 3_000,
+            BenchmarkSize::Large => 12_000,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            BenchmarkSize::Small => "S",
+            BenchmarkSize::Medium => "M",
+            BenchmarkSize::Large => "L",
+        }
+    }
+}
+
+impl std::str::FromStr for BenchmarkSize {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "s" | "small" => Ok(BenchmarkSize::Small),
+            "m" | "medium" => Ok(BenchmarkSize::Medium),
+            "l" | "large" => Ok(BenchmarkSize::Large),
+            _ => Err(anyhow::anyhow!("Unknown benchmark size: {} (S, M or L)", s)),
+        }
+    }
+}
+
+/// A cycle planted in a benchmark series: `amplitude * sin(2π t / period + phase)`
+/// relative to the price, with `t` in bars since the Unix epoch as [`HiddenCycle`]
+/// reads it
+///
+/// [`HiddenCycle`]: crate::patterns::HiddenCycle
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct InjectedCycle {
+    pub period: u32,
+    pub amplitude: f64,
+    pub phase: f64,
+}
+
+/// Cycles planted in every benchmark series; the periods share no common factor so
+/// none is a harmonic of another
+pub fn benchmark_cycles() -> Vec<InjectedCycle> {
+    vec![
+        InjectedCycle { period: 13, amplitude: 0.004, phase: 0.0 },
+        InjectedCycle { period: 34, amplitude: 0.006, phase: 1.0 },
+        InjectedCycle { period: 89, amplitude: 0.010, phase: 2.0 },
+    ]
+}
+
+/// A generated benchmark series with its ground truth
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkDataset {
+    pub size: BenchmarkSize,
+    pub seed: u64,
+    pub cycles: Vec<InjectedCycle>,
+    pub bars: Vec<ForexDataPoint>,
+}
+
+/// Generate the benchmark series of `size`; the same size and seed always give the same bars
+pub fn generate_benchmark(size: BenchmarkSize, seed: u64) -> BenchmarkDataset {
+    let cycles = benchmark_cycles();
+    let count = size.bars();
+    let mut rng = StdRng::seed_from_u64(seed);
+    let start = Utc.with_ymd_and_hms(2000, 1, 3, 0, 0, 0).unwrap();
+
+    // Every calendar day is a bar so periods in bars and in days coincide
+    let mut previous_close = BASE_PRICE;
+    let bars = (0..count).map(|i| {
+        let timestamp = start + Duration::days(i as i64);
+        let t = timestamp.timestamp() as f64 / 86_400.0;
+        let wave: f64 = cycles.iter().map(|c| c.amplitude * (2.0 * PI * t / c.period as f64 + c.phase).sin()).sum();
+        let drift = DRIFT * i as f64 / count as f64;
+        let noise = NOISE * (rng.gen::<f64>() + rng.gen::<f64>() + rng.gen::<f64>() - 1.5) * 2.0;
+        let close = BASE_PRICE * (1.0 + drift + wave + noise);
+        let open = previous_close;
+        previous_close = close;
+        let range = BASE_PRICE * NOISE * rng.gen_range(0.5..1.5);
+        ForexDataPoint {
+            timestamp,
+            open,
+            high: open.max(close) + range,
+            low: open.min(close) - range,
+            close,
+            volume: Some(rng.gen_range(1_000.0_f64..5_000.0).round()),
+        }
+    }).collect();
+
+    BenchmarkDataset { size, seed, cycles, bars }
+}
@@ -0,0 +1,135 @@
+//! # Synthetic Data Export
+//!
+//! Generated points written as CSV, Parquet or JSON, one row per point with the
+//! generation confidence and the cycles and symmetries behind it. CSV rows use
+//! the standard bar columns (`time,open,high,low,close,tick_volume`) and Parquet
+//! rows a `timestamp` datetime, so exported files load back as price data.
+
+use anyhow::{anyhow, bail, Result};
+use polars::prelude::*;
+use serde::Serialize;
+use std::fs::File;
+use std::path::Path;
+use std::str::FromStr;
+
+use super::SyntheticForexPoint;
+
+/// Separator of the cycle and symmetry names in one CSV or Parquet cell
+const NAME_SEPARATOR: &str = ";";
+
+/// File formats synthetic data exports to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Csv,
+    Parquet,
+    Json,
+}
+
+impl ExportFormat {
+    /// Format implied by a file extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()?.to_str()?.parse().ok()
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Parquet => "parquet",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(ExportFormat::Csv),
+            "parquet" | "pq" => Ok(ExportFormat::Parquet),
+            "json" => Ok(ExportFormat::Json),
+            other => Err(anyhow!("unsupported export format '{}' (csv, parquet or json)", other)),
+        }
+    }
+}
+
+/// One exported point
+#[derive(Debug, Clone, Serialize)]
+struct ExportRow {
+    time: String,
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+    tick_volume: Option<f64>,
+    confidence: f64,
+    cycles: String,
+    symmetries: String,
+}
+
+impl From<&SyntheticForexPoint> for ExportRow {
+    fn from(point: &SyntheticForexPoint) -> Self {
+        let bar = &point.data_point;
+        Self {
+            time: bar.timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
+            open: bar.open,
+            high: bar.high,
+            low: bar.low,
+            close: bar.close,
+            tick_volume: bar.volume,
+            confidence: point.generation_confidence,
+            cycles: point.contributing_cycles.join(NAME_SEPARATOR),
+            symmetries: point.symmetry_influences.join(NAME_SEPARATOR),
+        }
+    }
+}
+
+/// Write `points` to `path` in `format`
+pub fn export(points: &[SyntheticForexPoint], path: &Path, format: ExportFormat) -> Result<()> {
+    if points.is_empty() {
+        bail!("no synthetic points to export");
+    }
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    match format {
+        ExportFormat::Csv => {
+            let mut writer = csv::Writer::from_path(path)?;
+            for point in points {
+                writer.serialize(ExportRow::from(point))?;
+            }
+            writer.flush()?;
+        }
+        ExportFormat::Parquet => {
+            ParquetWriter::new(File::create(path)?).finish(&mut frame(points)?)?;
+        }
+        ExportFormat::Json => {
+            let rows: Vec<ExportRow> = points.iter().map(ExportRow::from).collect();
+            serde_json::to_writer_pretty(File::create(path)?, &rows)?;
+        }
+    }
+    Ok(())
+}
+
+/// Frame of `points` with a millisecond `timestamp` datetime column
+fn frame(points: &[SyntheticForexPoint]) -> Result<DataFrame> {
+    let column = |f: fn(&SyntheticForexPoint) -> f64| -> Vec<f64> { points.iter().map(f).collect() };
+    let names = |f: fn(&SyntheticForexPoint) -> &Vec<String>| -> Vec<String> {
+        points.iter().map(|point| f(point).join(NAME_SEPARATOR)).collect()
+    };
+    let timestamps: Vec<i64> = points.iter().map(|point| point.data_point.timestamp.timestamp_millis()).collect();
+    let volume: Vec<Option<f64>> = points.iter().map(|point| point.data_point.volume).collect();
+
+    Ok(DataFrame::new(vec![
+        Series::new("timestamp", &timestamps).cast(&DataType::Datetime(TimeUnit::Milliseconds, None))?,
+        Series::new("open", &column(|point| point.data_point.open)),
+        Series::new("high", &column(|point| point.data_point.high)),
+        Series::new("low", &column(|point| point.data_point.low)),
+        Series::new("close", &column(|point| point.data_point.close)),
+        Series::new("volume", &volume),
+        Series::new("confidence", &column(|point| point.generation_confidence)),
+        Series::new("cycles", &names(|point| &point.contributing_cycles)),
+        Series::new("symmetries", &names(|point| &point.symmetry_influences)),
+    ])?)
+}
@@ -0,0 +1,153 @@
+//! # Fixture Series
+//!
+//! Small seeded series with a known shape, and the anomalies and symmetries the
+//! tests build around them, shared by the test binaries so each one does not
+//! carry its own copy
+
+use chrono::{DateTime, Datelike, Duration, TimeZone, Utc, Weekday};
+use rand::Rng;
+use std::collections::HashMap;
+
+use super::{AlgebraicBasis, SyntheticForexPoint};
+use crate::anomaly::{AnomalySeverity, AnomalyType, DetectedAnomaly, MarketContext};
+use crate::data::ForexDataPoint;
+use crate::ids::SymmetryId;
+use crate::symmetry::{TemporalSymmetry, ROTATIONAL};
+
+/// `count` daily bars after `start` with a 20-bar cycle under the noise
+pub fn cyclic_daily(start: DateTime<Utc>, count: usize, rng: &mut impl Rng) -> Vec<ForexDataPoint> {
+    (1..=count as i64)
+        .map(|i| {
+            let close = 1.1 + 0.01 * (i as f64 * std::f64::consts::TAU / 20.0).sin() + rng.gen_range(-0.002..0.002);
+            ForexDataPoint { timestamp: start + Duration::days(i), open: close, high: close + 0.003, low: close - 0.003, close, volume: None }
+        })
+        .collect()
+}
+
+/// `count` daily bars from 2023-01-02 rising slowly under a 20-bar swing and noise
+pub fn trending_daily(rng: &mut impl Rng, count: i64) -> Vec<ForexDataPoint> {
+    let start = Utc.with_ymd_and_hms(2023, 1, 2, 0, 0, 0).unwrap();
+    (0..count).map(|i| {
+        let timestamp = start + Duration::days(i);
+        let t = timestamp.timestamp() as f64 / 86_400.0;
+        let close = 1.1 + 0.0001 * i as f64 + 0.01 * (std::f64::consts::TAU * t / 20.0).sin() + rng.gen_range(-0.0005..0.0005);
+        ForexDataPoint { timestamp, open: close, high: close + 0.0005, low: close - 0.0005, close, volume: None }
+    }).collect()
+}
+
+/// UTC time from its calendar fields
+pub fn at(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(year, month, day, hour, minute, 0).unwrap()
+}
+
+/// `count` hourly timestamps from 2023-01-02
+pub fn hourly_timestamps(count: usize) -> Vec<DateTime<Utc>> {
+    let start = Utc.with_ymd_and_hms(2023, 1, 2, 0, 0, 0).unwrap();
+    (0..count).map(|i| start + Duration::hours(i as i64)).collect()
+}
+
+/// `count` weekday midnights from 2015-01-05, skipping weekends
+pub fn weekday_timestamps(count: usize) -> Vec<DateTime<Utc>> {
+    let mut day = Utc.with_ymd_and_hms(2015, 1, 5, 0, 0, 0).unwrap();
+    let mut days = Vec::with_capacity(count);
+    while days.len() < count {
+        if !matches!(day.weekday(), Weekday::Sat | Weekday::Sun) {
+            days.push(day);
+        }
+        day += Duration::days(1);
+    }
+    days
+}
+
+/// Flat 1.1000 bar opened `hours_ago` hours before now
+pub fn recent_flat_bar(hours_ago: i64) -> ForexDataPoint {
+    let price = 1.1;
+    ForexDataPoint { timestamp: Utc::now() - Duration::hours(hours_ago), open: price, high: price, low: price, close: price, volume: None }
+}
+
+/// Widen the bar at `timestamp` to a 60-pip range
+pub fn spike(bars: &mut [ForexDataPoint], timestamp: DateTime<Utc>) {
+    if let Some(bar) = bars.iter_mut().find(|b| b.timestamp == timestamp) {
+        bar.high = bar.close + 0.0030;
+        bar.low = bar.close - 0.0030;
+    }
+}
+
+/// Hourly random walk from 1.1000 with 2-pip steps and 8-12 pip ranges
+pub fn hourly_walk(rng: &mut impl Rng, start: DateTime<Utc>, hours: i64) -> Vec<ForexDataPoint> {
+    let mut price = 1.1000;
+    (0..hours).map(|h| {
+        let close = price + rng.gen_range(-0.0002..0.0002);
+        let range = rng.gen_range(0.0008..0.0012);
+        let point = ForexDataPoint {
+            timestamp: start + Duration::hours(h),
+            open: price,
+            high: price.max(close) + range / 2.0,
+            low: price.min(close) - range / 2.0,
+            close,
+            volume: None,
+        };
+        price = close;
+        point
+    }).collect()
+}
+
+/// `point` wrapped as a synthetic point with an empty algebraic basis
+pub fn synthetic(point: ForexDataPoint) -> SyntheticForexPoint {
+    SyntheticForexPoint {
+        data_point: point,
+        generation_confidence: 1.0,
+        contributing_cycles: Vec::new(),
+        symmetry_influences: Vec::new(),
+        algebraic_basis: AlgebraicBasis {
+            field_element: 0,
+            cycle_contributions: HashMap::new(),
+            symmetry_weights: HashMap::new(),
+            temporal_coordinates: (0.0, 0.0, 0.0),
+        },
+    }
+}
+
+/// High-severity anomaly of `anomaly_type` at `timestamp`, in a quiet London session
+pub fn anomaly(timestamp: DateTime<Utc>, anomaly_type: AnomalyType) -> DetectedAnomaly {
+    DetectedAnomaly {
+        id: format!("a_{}", timestamp.timestamp()).into(),
+        timestamp,
+        anomaly_type,
+        severity: AnomalySeverity::High,
+        confidence: 0.9,
+        deviation_magnitude: 0.02,
+        affected_symmetries: Vec::new(),
+        affected_cycles: Vec::new(),
+        market_context: MarketContext {
+            session: "London".to_string(),
+            volatility_regime: "Normal".to_string(),
+            trend_direction: "Sideways".to_string(),
+            recent_events: Vec::new(),
+            event_proximity: 0.0,
+        },
+        trading_signal: None,
+    }
+}
+
+/// Strong rotational symmetry of `period` bars
+pub fn rotational(period: u32) -> TemporalSymmetry {
+    TemporalSymmetry {
+        id: SymmetryId::new(),
+        symmetry_type: ROTATIONAL.to_string(),
+        name: format!("{}-bar rotational", period),
+        period_days: period,
+        strength: 0.9,
+        confidence: 0.9,
+        field_signature: 0,
+        discovered_at: Utc::now(),
+        validation_score: 0.9,
+        mirror_points: Vec::new(),
+        phase_shift: 0.0,
+    }
+}
+
+/// String parameters from `(key, value)` pairs
+pub fn parameters(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect()
+}
@@ -3,18 +3,32 @@
 //! Generate future forex data from decoded temporal symmetries using algebraic continuation
 
 pub mod trading_env;
+pub mod demo;
+pub mod benchmark;
+pub mod validation;
+pub mod export;
+pub mod fixtures;
 
 use anyhow::Result;
 use chrono::{DateTime, Utc, Duration, Timelike, Datelike};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use nalgebra::{DVector, DMatrix};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::sync::Mutex;
 
 use crate::core::TimeSymmetricEngine;
-use crate::data::ForexDataPoint;
+use crate::data::{ForexDataPoint, MarketPoint};
 use crate::patterns::HiddenCycle;
-use crate::symmetry::TemporalSymmetry;
+use crate::symmetry::{SymmetryDetector, SymmetryDetectorConfig, TemporalSymmetry, MIRROR, ROTATIONAL, TRANSLATIONAL};
 use crate::galois::GaloisField;
+use crate::ids::SymmetryId;
+use crate::progress::{check_cancelled, CancellationToken, ConsoleProgress, Progress, ProgressUpdate};
+use crate::stats::volatility::{VolatilityConfig, VolatilityModel};
+
+/// Stage [`SyntheticDataGenerator::generate_future_data_with`] reports, one step per point
+pub const GENERATION_STAGE: &str = "synthetic generation";
 
 /// Synthetic data generation engine
 pub struct SyntheticDataGenerator {
@@ -32,6 +46,12 @@ pub struct SyntheticDataGenerator {
     
     /// Generation parameters
     config: SyntheticGenerationConfig,
+    
+    /// Volatility fitted on the anchor, scaling each point's range and noise
+    volatility_model: Option<VolatilityModel>,
+    
+    /// Noise source (seeded when `config.seed` is set)
+    rng: Mutex<StdRng>,
 }
 
 /// Configuration for synthetic data generation
@@ -54,10 +74,18 @@ pub struct SyntheticGenerationConfig {
     
     /// Enable crisis simulation
     pub enable_crisis_simulation: bool,
+    
+    /// Seed for reproducible generation (None = random)
+    #[serde(default)]
+    pub seed: Option<u64>,
+    
+    /// Volatility model fitted on the historical anchor
+    #[serde(default)]
+    pub volatility: VolatilityConfig,
 }
 
 /// Synthetic data point with generation metadata
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SyntheticForexPoint {
     pub data_point: ForexDataPoint,
     pub generation_confidence: f64,
@@ -67,14 +95,20 @@ pub struct SyntheticForexPoint {
 }
 
 /// Mathematical basis for synthetic point generation
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AlgebraicBasis {
     pub field_element: u64,
     pub cycle_contributions: HashMap<String, f64>,
-    pub symmetry_weights: HashMap<String, f64>,
+    pub symmetry_weights: HashMap<SymmetryId, f64>,
     pub temporal_coordinates: (f64, f64, f64), // Past, Present, Future
 }
 
+impl MarketPoint for SyntheticForexPoint {
+    fn data_point(&self) -> &ForexDataPoint {
+        &self.data_point
+    }
+}
+
 impl Default for SyntheticGenerationConfig {
     fn default() -> Self {
         Self {
@@ -84,6 +118,8 @@ impl Default for SyntheticGenerationConfig {
             cycle_confidence_threshold: 0.7, // High confidence cycles only
             symmetry_strength_threshold: 0.6, // Strong symmetries only
             enable_crisis_simulation: true,  // Include crisis patterns
+            seed: None,                      // Non-deterministic by default
+            volatility: VolatilityConfig::default(),
         }
     }
 }
@@ -97,6 +133,12 @@ impl SyntheticDataGenerator {
         config: SyntheticGenerationConfig,
     ) -> Result<Self> {
         let galois_field = GaloisField::new(2147483647)?; // Large prime for precision
+        let rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        
+        let volatility_model = VolatilityModel::fit(&historical_anchor, &config.volatility).ok();
         
         Ok(Self {
             temporal_symmetries,
@@ -104,20 +146,45 @@ impl SyntheticDataGenerator {
             galois_field,
             historical_anchor,
             config,
+            volatility_model,
+            rng: Mutex::new(rng),
         })
     }
     
+    /// Use `volatility_model` instead of the one fitted on the anchor, e.g. when
+    /// the anchor is too short to fit one
+    pub fn with_volatility_model(mut self, volatility_model: VolatilityModel) -> Self {
+        self.volatility_model = Some(volatility_model);
+        self
+    }
+    
+    /// Volatility model scaling the generated points (None when the anchor was too short)
+    pub fn volatility_model(&self) -> Option<&VolatilityModel> {
+        self.volatility_model.as_ref()
+    }
+    
     /// Generate synthetic forex data for future timeframe
     pub async fn generate_future_data(
         &self,
         start_date: DateTime<Utc>,
         pair: &str,
     ) -> Result<Vec<SyntheticForexPoint>> {
-        let mut synthetic_data = Vec::new();
-        
+        self.generate_future_data_with(start_date, pair, &ConsoleProgress::default(), &CancellationToken::new()).await
+    }
+    
+    /// [`Self::generate_future_data`] reporting each point to `progress` and
+    /// failing with [`Cancelled`](crate::progress::Cancelled) once `cancel` is cancelled
+    pub async fn generate_future_data_with(
+        &self,
+        start_date: DateTime<Utc>,
+        pair: &str,
+        progress: &dyn Progress,
+        cancel: &CancellationToken,
+    ) -> Result<Vec<SyntheticForexPoint>> {
         // Calculate total points to generate
         let total_minutes = self.config.future_horizon_days as i64 * 24 * 60;
         let total_points = total_minutes / self.config.resolution_minutes as i64;
+        let mut synthetic_data = Vec::with_capacity(total_points.max(0) as usize);
         
         println!("🔬 Generating {} synthetic data points for {} days ahead", 
                 total_points, self.config.future_horizon_days);
@@ -125,19 +192,23 @@ impl SyntheticDataGenerator {
         // Get last historical point as starting reference
         let last_historical = self.historical_anchor.last()
             .ok_or_else(|| anyhow::anyhow!("No historical data available"))?;
+        let volatility_model = self.volatility_model.as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Historical anchor too short to fit a volatility model; supply one with with_volatility_model"))?;
         
         let mut current_time = start_date;
         let mut last_price = last_historical.close;
         
         for i in 0..total_points {
-            let progress = i as f64 / total_points as f64;
+            check_cancelled(cancel)?;
+            let progress_fraction = i as f64 / total_points as f64;
             
             // Generate synthetic point using algebraic continuation
             let synthetic_point = self.generate_synthetic_point(
                 current_time,
                 last_price,
-                progress,
+                progress_fraction,
                 pair,
+                volatility_model,
             ).await?;
             
             last_price = synthetic_point.data_point.close;
@@ -145,12 +216,7 @@ impl SyntheticDataGenerator {
             
             // Advance time
             current_time = current_time + Duration::minutes(self.config.resolution_minutes as i64);
-            
-            // Progress indicator
-            if i % 1000 == 0 {
-                println!("📊 Generated {}/{} points ({:.1}%)", 
-                        i, total_points, progress * 100.0);
-            }
+            progress.report(ProgressUpdate::new(GENERATION_STAGE, i as u64 + 1, total_points as u64));
         }
         
         println!("✅ Synthetic data generation complete!");
@@ -164,6 +230,7 @@ impl SyntheticDataGenerator {
         last_price: f64,
         progress: f64,
         pair: &str,
+        volatility_model: &VolatilityModel,
     ) -> Result<SyntheticForexPoint> {
         // Calculate base price from cycle contributions
         let mut cycle_price = last_price;
@@ -200,7 +267,7 @@ impl SyntheticDataGenerator {
         
         // Calculate OHLC from base price
         let base_price = symmetry_price;
-        let volatility = self.calculate_synthetic_volatility(timestamp, progress);
+        let volatility = self.calculate_synthetic_volatility(volatility_model, timestamp, progress);
         
         let open = base_price;
         let high = base_price + volatility * 0.7;
@@ -279,11 +346,11 @@ impl SyntheticDataGenerator {
         
         // Mirror symmetry creates price reversions
         let correction = match symmetry.symmetry_type.as_str() {
-            "Mirror" => {
+            MIRROR => {
                 let mirror_factor = (phase_adjustment * std::f64::consts::PI).sin();
                 mirror_factor * symmetry_strength * 0.005 // Small price correction
             }
-            "Rotational" => {
+            ROTATIONAL => {
                 let rotation_factor = (phase_adjustment * 2.0 * std::f64::consts::PI).cos();
                 rotation_factor * symmetry_strength * 0.003
             }
@@ -297,10 +364,17 @@ impl SyntheticDataGenerator {
         correction
     }
     
-    /// Calculate synthetic volatility
-    fn calculate_synthetic_volatility(&self, timestamp: DateTime<Utc>, progress: f64) -> f64 {
-        // Base volatility from historical patterns
-        let base_volatility = 0.008; // ~80 pips for EUR/USD
+    /// Calculate synthetic volatility in price units at the anchor's level
+    fn calculate_synthetic_volatility(
+        &self,
+        volatility_model: &VolatilityModel,
+        timestamp: DateTime<Utc>,
+        progress: f64,
+    ) -> f64 {
+        // Base volatility forecast by the fitted model, reverting to its long-run level
+        let seconds_ahead = (timestamp - volatility_model.as_of).num_seconds() as f64;
+        let resolution_seconds = self.config.resolution_minutes as f64 * 60.0;
+        let base_volatility = volatility_model.forecast_volatility(seconds_ahead, resolution_seconds) * volatility_model.last_close;
         
         // Add time-of-day effects (higher during London/NY overlap)
         let hour = timestamp.hour() as f64;
@@ -336,9 +410,7 @@ impl SyntheticDataGenerator {
     
     /// Add realistic noise to price
     fn add_realistic_noise(&self, volatility: f64) -> f64 {
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
-        let noise: f64 = rng.gen_range(-1.0..1.0);
+        let noise: f64 = self.rng.lock().unwrap().gen_range(-1.0..1.0);
         noise * volatility * self.config.noise_level
     }
     
@@ -379,6 +451,7 @@ pub struct TemporalExtrapolator {
     galois_field: GaloisField,
     historical_patterns: Vec<ForexDataPoint>,
     symmetry_matrix: DMatrix<f64>,
+    symmetry_detector: SymmetryDetector,
 }
 
 impl TemporalExtrapolator {
@@ -386,11 +459,13 @@ impl TemporalExtrapolator {
     pub fn new(historical_data: Vec<ForexDataPoint>) -> Result<Self> {
         let galois_field = GaloisField::new(2147483647)?;
         let symmetry_matrix = Self::build_symmetry_matrix(&historical_data)?;
+        let symmetry_detector = SymmetryDetector::new(SymmetryDetectorConfig::default())?;
 
         Ok(Self {
             galois_field,
             historical_patterns: historical_data,
             symmetry_matrix,
+            symmetry_detector,
         })
     }
 
@@ -427,7 +502,11 @@ impl TemporalExtrapolator {
         let future_price = self.apply_field_extension(base_price, &symmetries)?;
 
         // Calculate confidence based on symmetry strength
-        let confidence = symmetries.iter().map(|s| s.strength).sum::<f64>() / symmetries.len() as f64;
+        let confidence = if symmetries.is_empty() {
+            0.0
+        } else {
+            symmetries.iter().map(|s| s.strength).sum::<f64>() / symmetries.len() as f64
+        };
 
         Ok(ExtrapolatedPattern {
             target_date,
@@ -438,104 +517,10 @@ impl TemporalExtrapolator {
         })
     }
 
-    /// Find temporal symmetries for target date
+    /// Mirror, rotational and translational symmetries in the history up to `target_date`
     fn find_temporal_symmetries(&self, target_date: DateTime<Utc>) -> Result<Vec<TemporalSymmetry>> {
-        let mut symmetries = Vec::new();
-
-        // Look for mirror symmetries (price reversals)
-        if let Some(mirror_symmetry) = self.find_mirror_symmetry(target_date)? {
-            symmetries.push(mirror_symmetry);
-        }
-
-        // Look for rotational symmetries (cyclical patterns)
-        if let Some(rotational_symmetry) = self.find_rotational_symmetry(target_date)? {
-            symmetries.push(rotational_symmetry);
-        }
-
-        // Look for translational symmetries (trend continuations)
-        if let Some(translational_symmetry) = self.find_translational_symmetry(target_date)? {
-            symmetries.push(translational_symmetry);
-        }
-
-        Ok(symmetries)
-    }
-
-    /// Find mirror symmetry patterns
-    fn find_mirror_symmetry(&self, target_date: DateTime<Utc>) -> Result<Option<TemporalSymmetry>> {
-        // Look for historical points that mirror the target date
-        let target_timestamp = target_date.timestamp();
-
-        for (i, point) in self.historical_patterns.iter().enumerate() {
-            let historical_timestamp = point.timestamp.timestamp();
-            let time_diff = (target_timestamp - historical_timestamp).abs();
-
-            // Look for patterns that repeat with mirror symmetry
-            if let Some(mirror_point) = self.find_mirror_point(i, time_diff) {
-                let strength = self.calculate_mirror_strength(i, mirror_point);
-
-                if strength > 0.6 {
-                    return Ok(Some(TemporalSymmetry {
-                        id: format!("mirror_{}", i),
-                        symmetry_type: "Mirror".to_string(),
-                        name: "Historical Mirror Pattern".to_string(),
-                        period_days: (time_diff / 86400) as u32,
-                        strength,
-                        confidence: strength * 0.9,
-                        field_signature: self.galois_field.encode_temporal_state(
-                            target_timestamp as u64,
-                            (point.close * 10000.0) as u64,
-                        ),
-                        discovered_at: Utc::now(),
-                        validation_score: strength,
-                        mirror_points: vec![(historical_timestamp as f64, point.close)],
-                        phase_shift: 0.0,
-                    }));
-                }
-            }
-        }
-
-        Ok(None)
-    }
-
-    /// Find mirror point for given index and time difference
-    fn find_mirror_point(&self, index: usize, time_diff: i64) -> Option<usize> {
-        let target_timestamp = self.historical_patterns[index].timestamp.timestamp() - time_diff;
-
-        // Find closest historical point to the mirror timestamp
-        self.historical_patterns.iter().enumerate()
-            .min_by_key(|(_, point)| (point.timestamp.timestamp() - target_timestamp).abs())
-            .map(|(i, _)| i)
-    }
-
-    /// Calculate mirror strength between two points
-    fn calculate_mirror_strength(&self, index1: usize, index2: usize) -> f64 {
-        let point1 = &self.historical_patterns[index1];
-        let point2 = &self.historical_patterns[index2];
-
-        // Calculate price correlation (inverted for mirror)
-        let price_diff = (point1.close - point2.close).abs();
-        let max_price = point1.close.max(point2.close);
-        let price_correlation = 1.0 - (price_diff / max_price);
-
-        // Calculate temporal correlation
-        let time_diff = (point1.timestamp.timestamp() - point2.timestamp.timestamp()).abs() as f64;
-        let temporal_correlation = (-time_diff / (86400.0 * 365.0)).exp(); // Decay over years
-
-        (price_correlation * temporal_correlation).max(0.0).min(1.0)
-    }
-
-    /// Find rotational symmetry (cyclical patterns)
-    fn find_rotational_symmetry(&self, target_date: DateTime<Utc>) -> Result<Option<TemporalSymmetry>> {
-        // Implementation for rotational symmetry detection
-        // This would look for cyclical patterns that repeat at regular intervals
-        Ok(None) // Placeholder
-    }
-
-    /// Find translational symmetry (trend patterns)
-    fn find_translational_symmetry(&self, target_date: DateTime<Utc>) -> Result<Option<TemporalSymmetry>> {
-        // Implementation for translational symmetry detection
-        // This would look for trend patterns that translate forward in time
-        Ok(None) // Placeholder
+        let known = self.historical_patterns.partition_point(|point| point.timestamp <= target_date);
+        Ok(self.symmetry_detector.detect(&self.historical_patterns[..known]))
     }
 
     /// Apply field extension to project patterns forward
@@ -548,20 +533,24 @@ impl TemporalExtrapolator {
 
             // Apply symmetry influence based on type
             match symmetry.symmetry_type.as_str() {
-                "Mirror" => {
+                MIRROR => {
                     // Mirror symmetries create price reversals
                     let reversion_factor = symmetry.strength * 0.02; // 2% max reversion
                     extended_price *= 1.0 - reversion_factor;
                 }
-                "Rotational" => {
-                    // Rotational symmetries create cyclical movements
-                    let cycle_factor = (symmetry.phase_shift * std::f64::consts::PI).sin();
+                ROTATIONAL => {
+                    // Rotational symmetries create cyclical movements; the phase is a fraction of the cycle
+                    let cycle_factor = (symmetry.phase_shift * 2.0 * std::f64::consts::PI).sin();
                     extended_price += cycle_factor * symmetry.strength * 0.01;
                 }
-                "Translational" => {
-                    // Translational symmetries continue trends
+                TRANSLATIONAL => {
+                    // Translational symmetries continue trends, in the direction of the blocks compared
+                    let direction = match (symmetry.mirror_points.first(), symmetry.mirror_points.last()) {
+                        (Some((_, start)), Some((_, end))) if end < start => -1.0,
+                        _ => 1.0,
+                    };
                     let trend_factor = symmetry.strength * 0.015; // 1.5% max trend
-                    extended_price *= 1.0 + trend_factor;
+                    extended_price *= 1.0 + direction * trend_factor;
                 }
                 _ => {}
             }
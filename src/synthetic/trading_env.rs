@@ -8,6 +8,8 @@ use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
 
 use crate::data::ForexDataPoint;
+use crate::trading_windows::TradingWindowsConfig;
+use crate::units::{pip_value, Pips, PriceDelta, STANDARD_LOT};
 use super::{SyntheticDataGenerator, SyntheticForexPoint, TemporalExtrapolator};
 
 /// Synthetic trading environment
@@ -27,6 +29,9 @@ pub struct SyntheticTradingEnvironment {
     /// Current market state
     market_state: MarketState,
     
+    /// Pair being traded, for pip conversions
+    pair: String,
+    
     /// Performance metrics
     performance: PerformanceMetrics,
 }
@@ -54,6 +59,10 @@ pub struct TradingEnvironmentConfig {
     
     /// Maximum slippage (in pips)
     pub max_slippage_pips: f64,
+    
+    /// Blocked trading periods
+    #[serde(default)]
+    pub trading_windows: TradingWindowsConfig,
 }
 
 /// Current market state
@@ -134,6 +143,7 @@ impl Default for TradingEnvironmentConfig {
             update_frequency_seconds: 60,
             enable_slippage: true,
             max_slippage_pips: 0.5,
+            trading_windows: TradingWindowsConfig::default(),
         }
     }
 }
@@ -177,6 +187,7 @@ impl SyntheticTradingEnvironment {
             synthetic_stream,
             config,
             market_state,
+            pair: "EURUSD".to_string(),
             performance,
         })
     }
@@ -190,6 +201,7 @@ impl SyntheticTradingEnvironment {
         println!("🚀 Starting Synthetic Trading Session");
         println!("   Duration: {} days", duration_days);
         println!("   Pair: {}", pair);
+        self.pair = pair.to_uppercase();
         println!("   Initial Balance: ${:.2}", self.config.initial_balance);
         println!();
         
@@ -215,8 +227,12 @@ impl SyntheticTradingEnvironment {
             // Generate trading signal based on synthetic data analysis
             let signal = self.analyze_synthetic_data(&synthetic_point).await?;
             
-            // Execute trade if signal is strong enough
-            if signal.confidence > 0.7 {
+            // Execute trade if signal is strong enough and the market window is open
+            let window_open = self.config.trading_windows.is_trading_allowed(synthetic_point.data_point.timestamp);
+            if !window_open && signal.confidence > 0.7 {
+                session_result.blocked_signals += 1;
+            }
+            if window_open && signal.confidence > 0.7 {
                 let trade_result = self.execute_synthetic_trade(&signal, current_balance)?;
                 current_balance = trade_result.new_balance;
                 session_result.add_trade(trade_result.clone());
@@ -249,6 +265,7 @@ impl SyntheticTradingEnvironment {
         println!("   Final Balance: ${:.2}", current_balance);
         println!("   Total Return: {:.2}%", session_result.total_return * 100.0);
         println!("   Total Trades: {}", self.performance.total_trades);
+        println!("   Blocked Signals: {}", session_result.blocked_signals);
         println!("   Win Rate: {:.1}%", self.performance.win_rate * 100.0);
         println!("   Pattern Accuracy: {:.1}%", self.performance.pattern_accuracy * 100.0);
         
@@ -263,7 +280,7 @@ impl SyntheticTradingEnvironment {
         self.market_state.current_price = data_point.close;
         
         // Calculate bid/ask with spread
-        let spread_value = self.config.spread_pips * 0.0001; // Convert pips to price
+        let spread_value = Pips(self.config.spread_pips).to_price(&self.pair).0;
         self.market_state.bid_price = data_point.close - spread_value / 2.0;
         self.market_state.ask_price = data_point.close + spread_value / 2.0;
         self.market_state.spread = spread_value;
@@ -325,16 +342,17 @@ impl SyntheticTradingEnvironment {
             SignalType::Hold => self.market_state.current_price,
         };
         
-        let volatility_factor = self.market_state.volatility * 100.0; // Convert to pips
+        // One pip of distance per 1% of range, two to the stop and three to the target
+        let volatility_distance = Pips(self.market_state.volatility * 100.0).to_price(&self.pair).0;
         let stop_loss = match signal_type {
-            SignalType::Buy => entry_price - volatility_factor * 0.0001 * 2.0,
-            SignalType::Sell => entry_price + volatility_factor * 0.0001 * 2.0,
+            SignalType::Buy => entry_price - volatility_distance * 2.0,
+            SignalType::Sell => entry_price + volatility_distance * 2.0,
             SignalType::Hold => entry_price,
         };
         
         let take_profit = match signal_type {
-            SignalType::Buy => entry_price + volatility_factor * 0.0001 * 3.0,
-            SignalType::Sell => entry_price - volatility_factor * 0.0001 * 3.0,
+            SignalType::Buy => entry_price + volatility_distance * 3.0,
+            SignalType::Sell => entry_price - volatility_distance * 3.0,
             SignalType::Hold => entry_price,
         };
         
@@ -364,13 +382,13 @@ impl SyntheticTradingEnvironment {
     fn execute_synthetic_trade(&self, signal: &TradingSignal, current_balance: f64) -> Result<TradeResult> {
         // Calculate position size (risk 2% of balance)
         let risk_amount = current_balance * 0.02;
-        let pip_value = 10.0; // $10 per pip for standard lot EUR/USD
-        let stop_loss_pips = ((signal.entry_price - signal.stop_loss).abs() / 0.0001).max(1.0);
+        let pip_value = pip_value(&self.pair, STANDARD_LOT, signal.entry_price); // Per standard lot
+        let stop_loss_pips = PriceDelta::between(signal.entry_price, signal.stop_loss).to_pips(&self.pair).abs().0.max(1.0);
         let position_size = risk_amount / (stop_loss_pips * pip_value);
         
         // Simulate trade execution with slippage
         let executed_price = if self.config.enable_slippage {
-            let slippage = (rand::random::<f64>() - 0.5) * self.config.max_slippage_pips * 0.0001;
+            let slippage = (Pips(self.config.max_slippage_pips).to_price(&self.pair) * (rand::random::<f64>() - 0.5)).0;
             signal.entry_price + slippage
         } else {
             signal.entry_price
@@ -412,6 +430,7 @@ pub struct TradingSessionResult {
     pub total_return: f64,
     pub trades: Vec<TradeResult>,
     pub market_updates: Vec<MarketUpdate>,
+    pub blocked_signals: u32,
 }
 
 /// Individual trade result
@@ -447,6 +466,7 @@ impl TradingSessionResult {
             total_return: 0.0,
             trades: Vec::new(),
             market_updates: Vec::new(),
+            blocked_signals: 0,
         }
     }
     
@@ -0,0 +1,318 @@
+//! # Synthetic Data Validation
+//!
+//! Checks generated data against its history: return distribution,
+//! autocorrelation, volatility clustering and drawdowns, after resampling to the
+//! history's bar spacing.
+
+use anyhow::{bail, Result};
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+
+use crate::backtest::median_spacing_seconds;
+use crate::data::timeframe::TimeframeAggregator;
+use crate::data::{ForexDataPoint, MarketPoint};
+use crate::stats::drift::{ks_p_value, ks_statistic};
+
+/// History windows started per window length when measuring typical drawdowns
+const DRAWDOWN_WINDOW_STEPS: usize = 8;
+
+/// Thresholds of the realism checks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SyntheticValidationConfig {
+    /// Autocorrelation lags compared, 1 to `max_lag` bars
+    pub max_lag: usize,
+    /// KS p-value below which the return distributions differ
+    pub ks_alpha: f64,
+    /// Largest allowed autocorrelation difference at any lag
+    pub max_autocorrelation_gap: f64,
+    /// Largest allowed difference in mean absolute-return autocorrelation
+    pub max_clustering_gap: f64,
+    /// Largest allowed ratio, either way, between the synthetic and typical historical max drawdown
+    pub max_drawdown_ratio: f64,
+    /// Fewest returns on either side to validate
+    pub min_returns: usize,
+}
+
+impl Default for SyntheticValidationConfig {
+    fn default() -> Self {
+        Self {
+            max_lag: 10,
+            ks_alpha: 0.01,
+            max_autocorrelation_gap: 0.2,
+            max_clustering_gap: 0.15,
+            max_drawdown_ratio: 3.0,
+            min_returns: 30,
+        }
+    }
+}
+
+/// Two-sample test of the log-return distributions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReturnDistributionCheck {
+    pub ks_statistic: f64,
+    pub ks_p_value: f64,
+    pub historical_mean: f64,
+    pub synthetic_mean: f64,
+    pub historical_std: f64,
+    pub synthetic_std: f64,
+    /// Excess kurtosis; fat tails show as a positive value
+    pub historical_kurtosis: f64,
+    pub synthetic_kurtosis: f64,
+    pub passed: bool,
+}
+
+/// Autocorrelation of a return feature at lags 1 to `max_lag`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutocorrelationCheck {
+    pub historical: Vec<f64>,
+    pub synthetic: Vec<f64>,
+    /// Largest absolute difference at any lag
+    pub max_gap: f64,
+    pub passed: bool,
+}
+
+impl AutocorrelationCheck {
+    /// Mean autocorrelation over the lags of the historical and synthetic series
+    pub fn means(&self) -> (f64, f64) {
+        (mean(&self.historical), mean(&self.synthetic))
+    }
+}
+
+/// Drawdown statistics of one close series
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DrawdownStats {
+    /// Deepest fall from a running peak, as a fraction of the peak
+    pub max_drawdown: f64,
+    /// Mean depth below the running peak over all bars
+    pub mean_drawdown: f64,
+    /// Most consecutive bars below a peak
+    pub longest_drawdown_bars: usize,
+}
+
+/// Synthetic drawdowns against history windows of the same length
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DrawdownCheck {
+    /// Median over history windows of the synthetic length
+    pub historical: DrawdownStats,
+    pub synthetic: DrawdownStats,
+    /// Synthetic over historical max drawdown
+    pub max_drawdown_ratio: f64,
+    pub passed: bool,
+}
+
+/// How realistic a synthetic series looks next to its history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyntheticQualityReport {
+    pub symbol: String,
+    /// Bar spacing both series were compared at
+    pub bar_seconds: i64,
+    pub historical_bars: usize,
+    pub synthetic_bars: usize,
+    pub returns: ReturnDistributionCheck,
+    pub autocorrelation: AutocorrelationCheck,
+    /// Autocorrelation of absolute returns
+    pub volatility_clustering: AutocorrelationCheck,
+    pub drawdown: DrawdownCheck,
+    /// Share of the four checks passed
+    pub score: f64,
+}
+
+impl SyntheticQualityReport {
+    /// Whether every check passed
+    pub fn passed(&self) -> bool {
+        self.returns.passed && self.autocorrelation.passed && self.volatility_clustering.passed && self.drawdown.passed
+    }
+
+    /// One line per check
+    pub fn summary(&self) -> Vec<String> {
+        let mark = |passed: bool| if passed { "✅" } else { "❌" };
+        let (historical_clustering, synthetic_clustering) = self.volatility_clustering.means();
+        vec![
+            format!("{} Returns: KS {:.3} (p={:.1e}), std {:.5} vs {:.5}, excess kurtosis {:.2} vs {:.2}",
+                    mark(self.returns.passed), self.returns.ks_statistic, self.returns.ks_p_value,
+                    self.returns.historical_std, self.returns.synthetic_std,
+                    self.returns.historical_kurtosis, self.returns.synthetic_kurtosis),
+            format!("{} Autocorrelation: largest gap {:.3} over {} lags",
+                    mark(self.autocorrelation.passed), self.autocorrelation.max_gap, self.autocorrelation.historical.len()),
+            format!("{} Volatility clustering: mean |r| autocorrelation {:.3} vs {:.3}",
+                    mark(self.volatility_clustering.passed), historical_clustering, synthetic_clustering),
+            format!("{} Drawdown: max {:.2}% vs {:.2}% (ratio {:.2}), longest {} vs {} bars",
+                    mark(self.drawdown.passed), self.drawdown.historical.max_drawdown * 100.0, self.drawdown.synthetic.max_drawdown * 100.0,
+                    self.drawdown.max_drawdown_ratio, self.drawdown.historical.longest_drawdown_bars, self.drawdown.synthetic.longest_drawdown_bars),
+        ]
+    }
+}
+
+/// Compare `synthetic` with the `historical` bars it was generated from
+pub fn validate_synthetic<P: MarketPoint>(
+    symbol: &str,
+    historical: &[ForexDataPoint],
+    synthetic: &[P],
+    config: &SyntheticValidationConfig,
+) -> Result<SyntheticQualityReport> {
+    let bar_seconds = median_spacing_seconds(historical) as i64;
+    let mut synthetic: Vec<ForexDataPoint> = synthetic.iter().map(|point| point.data_point().clone()).collect();
+    synthetic.sort_by_key(|point| point.timestamp);
+    if median_spacing_seconds(&synthetic) < bar_seconds as f64 {
+        synthetic = TimeframeAggregator::with_interval(Duration::seconds(bar_seconds))?.resample(&synthetic)?;
+    }
+
+    let historical_returns = log_returns(historical);
+    let synthetic_returns = log_returns(&synthetic);
+    let needed = config.min_returns.max(config.max_lag + 2);
+    if historical_returns.len() < needed || synthetic_returns.len() < needed {
+        bail!("{}: need {} returns on each side to validate synthetic data, got {} historical and {} synthetic",
+              symbol, needed, historical_returns.len(), synthetic_returns.len());
+    }
+
+    let returns = compare_distributions(&historical_returns, &synthetic_returns, config);
+    let autocorrelation = compare_autocorrelation(&historical_returns, &synthetic_returns, config.max_lag, |gap, _| gap <= config.max_autocorrelation_gap);
+    let historical_abs: Vec<f64> = historical_returns.iter().map(|r| r.abs()).collect();
+    let synthetic_abs: Vec<f64> = synthetic_returns.iter().map(|r| r.abs()).collect();
+    let volatility_clustering = compare_autocorrelation(&historical_abs, &synthetic_abs, config.max_lag, |_, check| {
+        let (historical, synthetic) = check.means();
+        (historical - synthetic).abs() <= config.max_clustering_gap
+    });
+    let drawdown = compare_drawdowns(historical, &synthetic, config);
+
+    let passed = [returns.passed, autocorrelation.passed, volatility_clustering.passed, drawdown.passed];
+    Ok(SyntheticQualityReport {
+        symbol: symbol.to_string(),
+        bar_seconds,
+        historical_bars: historical.len(),
+        synthetic_bars: synthetic.len(),
+        returns,
+        autocorrelation,
+        volatility_clustering,
+        drawdown,
+        score: passed.iter().filter(|p| **p).count() as f64 / passed.len() as f64,
+    })
+}
+
+fn compare_distributions(historical: &[f64], synthetic: &[f64], config: &SyntheticValidationConfig) -> ReturnDistributionCheck {
+    let mut sorted_historical = historical.to_vec();
+    let mut sorted_synthetic = synthetic.to_vec();
+    sorted_historical.sort_by(f64::total_cmp);
+    sorted_synthetic.sort_by(f64::total_cmp);
+    let statistic = ks_statistic(&sorted_historical, &sorted_synthetic);
+    let p_value = ks_p_value(statistic, historical.len(), synthetic.len());
+    ReturnDistributionCheck {
+        ks_statistic: statistic,
+        ks_p_value: p_value,
+        historical_mean: mean(historical),
+        synthetic_mean: mean(synthetic),
+        historical_std: std_dev(historical),
+        synthetic_std: std_dev(synthetic),
+        historical_kurtosis: excess_kurtosis(historical),
+        synthetic_kurtosis: excess_kurtosis(synthetic),
+        passed: p_value >= config.ks_alpha,
+    }
+}
+
+fn compare_autocorrelation(
+    historical: &[f64],
+    synthetic: &[f64],
+    max_lag: usize,
+    passes: impl Fn(f64, &AutocorrelationCheck) -> bool,
+) -> AutocorrelationCheck {
+    let historical = autocorrelations(historical, max_lag);
+    let synthetic = autocorrelations(synthetic, max_lag);
+    let max_gap = historical.iter().zip(&synthetic).map(|(h, s)| (h - s).abs()).fold(0.0, f64::max);
+    let mut check = AutocorrelationCheck { historical, synthetic, max_gap, passed: false };
+    check.passed = passes(max_gap, &check);
+    check
+}
+
+fn compare_drawdowns(historical: &[ForexDataPoint], synthetic: &[ForexDataPoint], config: &SyntheticValidationConfig) -> DrawdownCheck {
+    let synthetic_stats = drawdown_stats(synthetic);
+    let window = synthetic.len().clamp(2, historical.len());
+    let windows: Vec<DrawdownStats> = historical.windows(window)
+        .step_by((window / DRAWDOWN_WINDOW_STEPS).max(1))
+        .map(drawdown_stats)
+        .collect();
+    let median_of = |value: fn(&DrawdownStats) -> f64| {
+        let mut values: Vec<f64> = windows.iter().map(value).collect();
+        values.sort_by(f64::total_cmp);
+        values.get(values.len() / 2).copied().unwrap_or(0.0)
+    };
+    let historical_stats = DrawdownStats {
+        max_drawdown: median_of(|stats| stats.max_drawdown),
+        mean_drawdown: median_of(|stats| stats.mean_drawdown),
+        longest_drawdown_bars: median_of(|stats| stats.longest_drawdown_bars as f64) as usize,
+    };
+    let ratio = if historical_stats.max_drawdown > 0.0 {
+        synthetic_stats.max_drawdown / historical_stats.max_drawdown
+    } else if synthetic_stats.max_drawdown > 0.0 {
+        f64::INFINITY
+    } else {
+        1.0
+    };
+    let limit = config.max_drawdown_ratio.max(1.0);
+    DrawdownCheck {
+        historical: historical_stats,
+        synthetic: synthetic_stats,
+        max_drawdown_ratio: ratio,
+        passed: ratio >= 1.0 / limit && ratio <= limit,
+    }
+}
+
+/// Drawdown statistics of the closes of `data`
+pub fn drawdown_stats(data: &[ForexDataPoint]) -> DrawdownStats {
+    let mut stats = DrawdownStats::default();
+    let mut peak = f64::NEG_INFINITY;
+    let (mut underwater, mut depth_sum) = (0usize, 0.0);
+    for bar in data.iter().filter(|bar| bar.close > 0.0) {
+        if bar.close >= peak {
+            peak = bar.close;
+            underwater = 0;
+            continue;
+        }
+        let depth = 1.0 - bar.close / peak;
+        stats.max_drawdown = stats.max_drawdown.max(depth);
+        depth_sum += depth;
+        underwater += 1;
+        stats.longest_drawdown_bars = stats.longest_drawdown_bars.max(underwater);
+    }
+    stats.mean_drawdown = depth_sum / data.len().max(1) as f64;
+    stats
+}
+
+/// Sample autocorrelation of `values` at lags 1 to `max_lag`
+pub fn autocorrelations(values: &[f64], max_lag: usize) -> Vec<f64> {
+    let center = mean(values);
+    let variance: f64 = values.iter().map(|v| (v - center).powi(2)).sum();
+    (1..=max_lag)
+        .map(|lag| {
+            if variance <= 0.0 || lag >= values.len() {
+                return 0.0;
+            }
+            values.windows(lag + 1).map(|w| (w[0] - center) * (w[lag] - center)).sum::<f64>() / variance
+        })
+        .collect()
+}
+
+fn log_returns(data: &[ForexDataPoint]) -> Vec<f64> {
+    data.windows(2)
+        .filter(|pair| pair[0].close > 0.0 && pair[1].close > 0.0)
+        .map(|pair| (pair[1].close / pair[0].close).ln())
+        .collect()
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len().max(1) as f64
+}
+
+fn std_dev(values: &[f64]) -> f64 {
+    let center = mean(values);
+    (values.iter().map(|v| (v - center).powi(2)).sum::<f64>() / values.len().max(1) as f64).sqrt()
+}
+
+fn excess_kurtosis(values: &[f64]) -> f64 {
+    let center = mean(values);
+    let variance = values.iter().map(|v| (v - center).powi(2)).sum::<f64>() / values.len().max(1) as f64;
+    if variance <= 0.0 {
+        return 0.0;
+    }
+    values.iter().map(|v| (v - center).powi(4)).sum::<f64>() / values.len() as f64 / (variance * variance) - 3.0
+}
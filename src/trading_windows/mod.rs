@@ -0,0 +1,147 @@
+//! # Trading Windows
+//!
+//! Blocked trading periods (weekend close, Friday close buffer, daily rollover and
+//! custom blackouts) shared by the execution layer and backtests
+
+use anyhow::Result;
+use chrono::{DateTime, Datelike, Duration, Timelike, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Trading windows configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TradingWindowsConfig {
+    /// Enforce blocked periods at all
+    pub enabled: bool,
+
+    /// UTC hour at which the market closes on Friday
+    pub weekly_close_hour_utc: u32,
+
+    /// UTC hour at which the market reopens on Sunday
+    pub weekly_open_hour_utc: u32,
+
+    /// Minutes before the Friday close during which no new trades are opened
+    pub friday_close_buffer_minutes: i64,
+
+    /// UTC hour of the daily rollover (17:00 New York)
+    pub rollover_hour_utc: u32,
+
+    /// Minutes blocked on each side of the daily rollover
+    pub rollover_buffer_minutes: i64,
+
+    /// Custom blackout ranges (news releases, holidays, maintenance)
+    pub blackouts: Vec<BlackoutWindow>,
+}
+
+/// Custom blackout range
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlackoutWindow {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub reason: String,
+}
+
+/// Reason a timestamp falls in a blocked period
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum BlockReason {
+    WeekendClose,
+    FridayCloseBuffer,
+    DailyRollover,
+    Blackout(String),
+}
+
+impl std::fmt::Display for BlockReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BlockReason::WeekendClose => write!(f, "weekend close"),
+            BlockReason::FridayCloseBuffer => write!(f, "Friday close buffer"),
+            BlockReason::DailyRollover => write!(f, "daily rollover"),
+            BlockReason::Blackout(reason) => write!(f, "blackout: {}", reason),
+        }
+    }
+}
+
+impl Default for TradingWindowsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            weekly_close_hour_utc: 22,
+            weekly_open_hour_utc: 22,
+            friday_close_buffer_minutes: 60,
+            rollover_hour_utc: 22,
+            rollover_buffer_minutes: 10,
+            blackouts: Vec::new(),
+        }
+    }
+}
+
+impl TradingWindowsConfig {
+    /// Configuration that never blocks trading
+    pub fn unrestricted() -> Self {
+        Self {
+            enabled: false,
+            ..Default::default()
+        }
+    }
+
+    /// Load trading windows from a TOML file
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let config_str = std::fs::read_to_string(path)?;
+        Ok(toml::from_str(&config_str)?)
+    }
+
+    /// Return the reason trading is blocked at `timestamp`, if any
+    pub fn check(&self, timestamp: DateTime<Utc>) -> Option<BlockReason> {
+        if !self.enabled {
+            return None;
+        }
+
+        if let Some(blackout) = self.blackouts.iter()
+            .find(|b| timestamp >= b.start && timestamp < b.end) {
+            return Some(BlockReason::Blackout(blackout.reason.clone()));
+        }
+
+        let hour = timestamp.hour();
+        let weekend = match timestamp.weekday() {
+            Weekday::Fri => hour >= self.weekly_close_hour_utc,
+            Weekday::Sat => true,
+            Weekday::Sun => hour < self.weekly_open_hour_utc,
+            _ => false,
+        };
+        if weekend {
+            return Some(BlockReason::WeekendClose);
+        }
+
+        if timestamp.weekday() == Weekday::Fri {
+            let close = timestamp.date_naive()
+                .and_hms_opt(self.weekly_close_hour_utc.min(23), 0, 0)
+                .unwrap()
+                .and_utc();
+            if close - timestamp <= Duration::minutes(self.friday_close_buffer_minutes) {
+                return Some(BlockReason::FridayCloseBuffer);
+            }
+        }
+
+        // Rollover may straddle midnight, so compare against the nearest rollover instant
+        let rollover = timestamp.date_naive()
+            .and_hms_opt(self.rollover_hour_utc.min(23), 0, 0)
+            .unwrap()
+            .and_utc();
+        let distance = [rollover - Duration::days(1), rollover, rollover + Duration::days(1)]
+            .iter()
+            .map(|r| (timestamp - *r).num_minutes().abs())
+            .min()
+            .unwrap_or(i64::MAX);
+        if distance <= self.rollover_buffer_minutes {
+            return Some(BlockReason::DailyRollover);
+        }
+
+        None
+    }
+
+    /// Whether new trades may be opened at `timestamp`
+    pub fn is_trading_allowed(&self, timestamp: DateTime<Utc>) -> bool {
+        self.check(timestamp).is_none()
+    }
+}
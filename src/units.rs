@@ -0,0 +1,133 @@
+//! # Pips and Prices
+//!
+//! Pair-aware arithmetic for price distances. A pip is 0.01 of the quote
+//! currency on yen-quoted pairs and 0.0001 on the others, so "×10000" is only
+//! right for some pairs. Distances are carried as [`Pips`] or [`PriceDelta`]
+//! and converted between the two only through the pair they belong to.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::portfolio::split_symbol;
+
+/// Base-currency units in a standard lot
+pub const STANDARD_LOT: f64 = 100_000.0;
+
+/// Price of one pip on `pair` (e.g. "EURUSD" or "USDJPY")
+pub fn pip_size(pair: &str) -> f64 {
+    let (_, quote) = split_symbol(pair);
+    if quote == "JPY" {
+        0.01
+    } else {
+        0.0001
+    }
+}
+
+/// Value of a one-pip move on `units` of `pair` at `price`, in US dollars
+/// when either currency is the dollar and in the quote currency otherwise
+pub fn pip_value(pair: &str, units: f64, price: f64) -> f64 {
+    let (base, quote) = split_symbol(pair);
+    let in_quote = pip_size(pair) * units;
+    if base == "USD" && quote != "USD" && price > 0.0 {
+        in_quote / price
+    } else {
+        in_quote
+    }
+}
+
+/// A price distance in pips
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Pips(pub f64);
+
+/// A price distance in quote currency per unit of base currency
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PriceDelta(pub f64);
+
+impl Pips {
+    pub fn to_price(self, pair: &str) -> PriceDelta {
+        PriceDelta(self.0 * pip_size(pair))
+    }
+
+    /// Value of the move on `units` of `pair` at `price`, as [`pip_value`]
+    pub fn value(self, pair: &str, units: f64, price: f64) -> f64 {
+        self.0 * pip_value(pair, units, price)
+    }
+
+    pub fn abs(self) -> Self {
+        Self(self.0.abs())
+    }
+}
+
+impl PriceDelta {
+    /// Move from price `from` to price `to`
+    pub fn between(from: f64, to: f64) -> Self {
+        Self(to - from)
+    }
+
+    pub fn to_pips(self, pair: &str) -> Pips {
+        Pips(self.0 / pip_size(pair))
+    }
+
+    pub fn abs(self) -> Self {
+        Self(self.0.abs())
+    }
+}
+
+/// Shows the number as formatted (so `{:.1}` applies) followed by "pips"
+impl fmt::Display for Pips {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)?;
+        f.write_str(" pips")
+    }
+}
+
+impl fmt::Display for PriceDelta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+macro_rules! distance_arithmetic {
+    ($unit:ident) => {
+        impl Add for $unit {
+            type Output = Self;
+            fn add(self, other: Self) -> Self {
+                Self(self.0 + other.0)
+            }
+        }
+
+        impl Sub for $unit {
+            type Output = Self;
+            fn sub(self, other: Self) -> Self {
+                Self(self.0 - other.0)
+            }
+        }
+
+        impl Neg for $unit {
+            type Output = Self;
+            fn neg(self) -> Self {
+                Self(-self.0)
+            }
+        }
+
+        impl Mul<f64> for $unit {
+            type Output = Self;
+            fn mul(self, factor: f64) -> Self {
+                Self(self.0 * factor)
+            }
+        }
+
+        impl Div<f64> for $unit {
+            type Output = Self;
+            fn div(self, divisor: f64) -> Self {
+                Self(self.0 / divisor)
+            }
+        }
+    };
+}
+
+distance_arithmetic!(Pips);
+distance_arithmetic!(PriceDelta);
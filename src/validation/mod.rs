@@ -0,0 +1,267 @@
+//! # Symmetry Validation
+//!
+//! Purged and embargoed k-fold cross-validation of temporal symmetries: each
+//! symmetry's lag autocorrelation is measured on training bars and on a held-out
+//! block, with bars within a period of the block dropped so neither leaks into
+//! the other. Periods are in bars.
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+use crate::data::ForexDataPoint;
+use crate::ids::SymmetryId;
+use crate::patterns::HiddenCycle;
+use crate::symmetry::TemporalSymmetry;
+
+/// Cross-validation settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CrossValidationConfig {
+    pub folds: usize,
+    /// Purge distance in multiples of the longest symmetry or cycle period
+    pub purge_periods: f64,
+    /// Share of the history embargoed after each test block
+    pub embargo_fraction: f64,
+    /// Fewest return pairs an autocorrelation is measured from
+    pub min_pairs: usize,
+    /// Persistence at or above which a symmetry holds out of sample
+    pub min_persistence: f64,
+}
+
+impl Default for CrossValidationConfig {
+    fn default() -> Self {
+        Self {
+            folds: 5,
+            purge_periods: 1.0,
+            embargo_fraction: 0.01,
+            min_pairs: 10,
+            min_persistence: 0.5,
+        }
+    }
+}
+
+/// Bars of one fold: a contiguous test block and the training segments around it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FoldSplit {
+    pub fold: usize,
+    pub test: Range<usize>,
+    pub train: Vec<Range<usize>>,
+}
+
+impl FoldSplit {
+    /// Training bars across all segments
+    pub fn train_len(&self) -> usize {
+        self.train.iter().map(|segment| segment.len()).sum()
+    }
+}
+
+/// Contiguous k-fold splits with purged and embargoed training bars
+#[derive(Debug, Clone, Copy)]
+pub struct PurgedKFold {
+    folds: usize,
+    purge: usize,
+    embargo: usize,
+}
+
+impl PurgedKFold {
+    /// `purge` bars are dropped on each side of a test block, and `embargo` more after it
+    pub fn new(folds: usize, purge: usize, embargo: usize) -> Result<Self> {
+        if folds < 2 {
+            bail!("Cross-validation needs at least 2 folds, got {}", folds);
+        }
+        Ok(Self { folds, purge, embargo })
+    }
+
+    pub fn purge(&self) -> usize {
+        self.purge
+    }
+
+    pub fn embargo(&self) -> usize {
+        self.embargo
+    }
+
+    /// Splits of `bars` bars, oldest test block first
+    pub fn split(&self, bars: usize) -> Result<Vec<FoldSplit>> {
+        if bars < self.folds {
+            bail!("{} bars cannot be split into {} folds", bars, self.folds);
+        }
+        Ok((0..self.folds)
+            .map(|fold| {
+                let test = fold * bars / self.folds..(fold + 1) * bars / self.folds;
+                let before = 0..test.start.saturating_sub(self.purge);
+                let after = (test.end + self.purge + self.embargo).min(bars)..bars;
+                let train = [before, after].into_iter().filter(|segment| !segment.is_empty()).collect();
+                FoldSplit { fold, test, train }
+            })
+            .collect())
+    }
+}
+
+/// Scores of one symmetry in one fold
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FoldScore {
+    pub fold: usize,
+    /// Lag autocorrelation at the period on the training bars
+    pub in_sample: Option<f64>,
+    /// Lag autocorrelation at the period on the test block
+    pub out_of_sample: Option<f64>,
+    /// Out-of-sample over in-sample autocorrelation, clamped to [0, 1]
+    pub persistence: Option<f64>,
+}
+
+/// Out-of-sample persistence of one symmetry across folds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymmetryPersistence {
+    pub symmetry_id: SymmetryId,
+    pub name: String,
+    pub period_days: u32,
+    /// The symmetry's own, in-sample validation score
+    pub detected_score: f64,
+    pub folds: Vec<FoldScore>,
+    pub mean_in_sample: Option<f64>,
+    pub mean_out_of_sample: Option<f64>,
+    /// Mean persistence over the folds where both samples could be scored
+    pub persistence: Option<f64>,
+    pub persistence_std: Option<f64>,
+    /// Share of scored folds whose test block keeps the in-sample sign
+    pub sign_agreement: Option<f64>,
+    pub persistent: bool,
+}
+
+/// Cross-validated symmetries of one history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymmetryValidation {
+    pub bars: usize,
+    pub purge_bars: usize,
+    pub embargo_bars: usize,
+    pub splits: Vec<FoldSplit>,
+    pub symmetries: Vec<SymmetryPersistence>,
+}
+
+impl SymmetryValidation {
+    pub fn get(&self, id: &SymmetryId) -> Option<&SymmetryPersistence> {
+        self.symmetries.iter().find(|symmetry| &symmetry.symmetry_id == id)
+    }
+
+    /// Symmetries that hold out of sample
+    pub fn persistent(&self) -> impl Iterator<Item = &SymmetryPersistence> {
+        self.symmetries.iter().filter(|symmetry| symmetry.persistent)
+    }
+
+    pub fn print_persistence(&self) {
+        println!("\n🧪 Symmetry Persistence ({} folds over {} bars, purge {} / embargo {} bars):",
+                 self.splits.len(), self.bars, self.purge_bars, self.embargo_bars);
+        println!("╔══════════════════════════╦════════╦══════════╦═══════════╦═══════════╦═════════════╦════════╗");
+        println!("║ Symmetry                 ║ Period ║ Detected ║ In-Sample ║ Out-of-S. ║ Persistence ║ Holds  ║");
+        println!("╠══════════════════════════╬════════╬══════════╬═══════════╬═══════════╬═════════════╬════════╣");
+        for symmetry in &self.symmetries {
+            println!("║ {:24} ║ {:6} ║ {:8.3} ║ {:>9} ║ {:>9} ║ {:>11} ║ {:6} ║",
+                     symmetry.name.chars().take(24).collect::<String>(), symmetry.period_days, symmetry.detected_score,
+                     format_optional(symmetry.mean_in_sample), format_optional(symmetry.mean_out_of_sample),
+                     format_optional(symmetry.persistence), if symmetry.persistent { "yes" } else { "no" });
+        }
+        println!("╚══════════════════════════╩════════╩══════════╩═══════════╩═══════════╩═════════════╩════════╝");
+    }
+}
+
+/// Cross-validate `symmetries` on `data`, purging around the longest period of
+/// the symmetries and `cycles`
+pub fn validate_temporal_symmetries(
+    data: &[ForexDataPoint],
+    symmetries: &[TemporalSymmetry],
+    cycles: &[HiddenCycle],
+    config: &CrossValidationConfig,
+) -> Result<SymmetryValidation> {
+    if config.purge_periods.is_nan() || config.purge_periods < 0.0 || !(0.0..1.0).contains(&config.embargo_fraction) {
+        bail!("Purge periods must be non-negative and the embargo fraction in [0, 1)");
+    }
+    let longest = symmetries.iter().map(|s| s.period_days)
+        .chain(cycles.iter().map(|c| c.period))
+        .max()
+        .unwrap_or(0);
+    let purge = (longest as f64 * config.purge_periods).ceil() as usize;
+    let embargo = (data.len() as f64 * config.embargo_fraction).ceil() as usize;
+    let kfold = PurgedKFold::new(config.folds, purge, embargo)?;
+    let splits = kfold.split(data.len())?;
+
+    // returns[i] ends at bar i + 1
+    let returns: Vec<f64> = data.windows(2)
+        .map(|w| if w[0].close > 0.0 && w[1].close > 0.0 { (w[1].close / w[0].close).ln() } else { 0.0 })
+        .collect();
+
+    let symmetries = symmetries.iter()
+        .map(|symmetry| {
+            let lag = symmetry.period_days as usize;
+            let folds: Vec<FoldScore> = splits.iter()
+                .map(|split| {
+                    let in_sample = lag_autocorrelation(&returns, &split.train, lag, config.min_pairs);
+                    let out_of_sample = lag_autocorrelation(&returns, std::slice::from_ref(&split.test), lag, config.min_pairs);
+                    let persistence = match (in_sample, out_of_sample) {
+                        (Some(in_sample), Some(out_of_sample)) if in_sample.abs() > f64::EPSILON => {
+                            Some((out_of_sample / in_sample).clamp(0.0, 1.0))
+                        }
+                        _ => None,
+                    };
+                    FoldScore { fold: split.fold, in_sample, out_of_sample, persistence }
+                })
+                .collect();
+
+            let persistences: Vec<f64> = folds.iter().filter_map(|fold| fold.persistence).collect();
+            let persistence = mean(&persistences);
+            let persistence_std = persistence.map(|mean| {
+                (persistences.iter().map(|p| (p - mean).powi(2)).sum::<f64>() / persistences.len() as f64).sqrt()
+            });
+            let sign_agreement = mean(&persistences.iter().map(|p| if *p > 0.0 { 1.0 } else { 0.0 }).collect::<Vec<_>>());
+            SymmetryPersistence {
+                symmetry_id: symmetry.id.clone(),
+                name: symmetry.name.clone(),
+                period_days: symmetry.period_days,
+                detected_score: symmetry.validation_score,
+                mean_in_sample: mean(&folds.iter().filter_map(|fold| fold.in_sample).collect::<Vec<_>>()),
+                mean_out_of_sample: mean(&folds.iter().filter_map(|fold| fold.out_of_sample).collect::<Vec<_>>()),
+                persistent: persistence.is_some_and(|p| p >= config.min_persistence),
+                folds,
+                persistence,
+                persistence_std,
+                sign_agreement,
+            }
+        })
+        .collect();
+
+    Ok(SymmetryValidation { bars: data.len(), purge_bars: purge, embargo_bars: embargo, splits, symmetries })
+}
+
+/// Correlation of returns `lag` apart, pooled over pairs lying within one of
+/// the bar `segments`; `None` below `min_pairs` pairs
+fn lag_autocorrelation(returns: &[f64], segments: &[Range<usize>], lag: usize, min_pairs: usize) -> Option<f64> {
+    if lag == 0 {
+        return None;
+    }
+    // Returns within bars start..end are start..end - 1
+    let pairs: Vec<(f64, f64)> = segments.iter()
+        .flat_map(|segment| {
+            let within = &returns[segment.start.min(returns.len())..segment.end.saturating_sub(1).min(returns.len())];
+            within.iter().zip(within.iter().skip(lag)).map(|(a, b)| (*a, *b))
+        })
+        .collect();
+    if pairs.len() < min_pairs.max(3) {
+        return None;
+    }
+
+    let n = pairs.len() as f64;
+    let (mean_x, mean_y) = (pairs.iter().map(|p| p.0).sum::<f64>() / n, pairs.iter().map(|p| p.1).sum::<f64>() / n);
+    let covariance: f64 = pairs.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let variance_x: f64 = pairs.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    let variance_y: f64 = pairs.iter().map(|(_, y)| (y - mean_y).powi(2)).sum();
+    let denominator = (variance_x * variance_y).sqrt();
+    (denominator > 0.0).then(|| covariance / denominator)
+}
+
+fn mean(values: &[f64]) -> Option<f64> {
+    (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+}
+
+fn format_optional(value: Option<f64>) -> String {
+    value.map(|v| format!("{:.3}", v)).unwrap_or_else(|| "—".to_string())
+}
@@ -0,0 +1,370 @@
+//! # Charts
+//!
+//! PNG and SVG rendering of analysis results with plotters; the file extension
+//! picks the backend, and the `*_svg` variants return the markup for embedding. Cycles are drawn as `sin(2π t / period + phase)` with `t`
+//! in bars since the Unix epoch, as [`HiddenCycle`] reads them, around a linear
+//! trend of the closes.
+
+use anyhow::{bail, Result};
+use chrono::{DateTime, TimeZone, Utc};
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+use std::ops::Range;
+use std::path::Path;
+
+use crate::data::ForexDataPoint;
+use crate::patterns::spectral::{linear_trend, return_periodogram, SampledSeries};
+use crate::patterns::{CycleDecomposition, HiddenCycle};
+use crate::symmetry::TemporalSymmetry;
+
+const CHART_SIZE: (u32, u32) = (1280, 720);
+/// Height of each panel of a stacked decomposition chart
+const PANEL_HEIGHT: u32 = 240;
+/// Strongest cycles overlaid one by one on the price chart
+const MAX_OVERLAID_CYCLES: usize = 5;
+/// Strongest symmetries marked on the mirror-point chart
+const MAX_MARKED_SYMMETRIES: usize = 8;
+/// False alarm probability of the significance line on spectral charts
+const SIGNIFICANCE: f64 = 0.01;
+/// Fewest bars a spectrum is charted from
+const MIN_SPECTRUM_BARS: usize = 16;
+
+/// Image format of rendered charts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChartFormat {
+    #[default]
+    Png,
+    Svg,
+}
+
+impl ChartFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ChartFormat::Png => "png",
+            ChartFormat::Svg => "svg",
+        }
+    }
+
+    /// Format named by the extension of `path`; PNG unless it is `.svg`
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("svg") => ChartFormat::Svg,
+            _ => ChartFormat::Png,
+        }
+    }
+}
+
+/// Where a chart is drawn
+enum Target<'a> {
+    /// Image file in the format its extension names
+    File(&'a Path),
+    /// SVG markup written into the string
+    Svg(&'a mut String),
+}
+
+/// Draw on the backend `$target` calls for
+macro_rules! render {
+    ($target:expr, $size:expr, $draw:ident($($arg:expr),*)) => {
+        match $target {
+            Target::Svg(svg) => $draw(SVGBackend::with_string(svg, $size).into_drawing_area(), $($arg),*),
+            Target::File(path) => match ChartFormat::from_path(path) {
+                ChartFormat::Svg => $draw(SVGBackend::new(path, $size).into_drawing_area(), $($arg),*),
+                ChartFormat::Png => $draw(BitMapBackend::new(path, $size).into_drawing_area(), $($arg),*),
+            },
+        }
+    };
+}
+
+/// Render a chart into SVG markup
+fn svg_string(draw: impl FnOnce(Target) -> Result<()>) -> Result<String> {
+    let mut svg = String::new();
+    draw(Target::Svg(&mut svg))?;
+    Ok(svg)
+}
+
+/// One named series of a time chart
+struct Line {
+    label: String,
+    values: Vec<f64>,
+    color: RGBAColor,
+}
+
+/// Connected points marking one symmetry on a time chart
+struct Marks {
+    label: String,
+    points: Vec<(DateTime<Utc>, f64)>,
+    color: RGBAColor,
+}
+
+/// Close prices with the reconstruction of every cycle and the strongest cycles one by one
+pub fn plot_price_cycles(data: &[ForexDataPoint], cycles: &[HiddenCycle], path: &Path) -> Result<()> {
+    price_cycles(data, cycles, Target::File(path))
+}
+
+/// [`plot_price_cycles`] as SVG markup
+pub fn price_cycles_svg(data: &[ForexDataPoint], cycles: &[HiddenCycle]) -> Result<String> {
+    svg_string(|target| price_cycles(data, cycles, target))
+}
+
+fn price_cycles(data: &[ForexDataPoint], cycles: &[HiddenCycle], target: Target) -> Result<()> {
+    let series = sampled(data)?;
+    let closes: Vec<f64> = data.iter().map(|point| point.close).collect();
+    let trend = linear_trend(&series.times, &closes);
+    let mean_price = closes.iter().sum::<f64>() / closes.len() as f64;
+    let wave = |cycle: &HiddenCycle, t: f64| mean_price * cycle.amplitude * (2.0 * PI * t / cycle.period.max(1) as f64 + cycle.phase).sin();
+
+    let mut lines = vec![Line { label: "Close".to_string(), values: closes, color: BLACK.to_rgba() }];
+    if !cycles.is_empty() {
+        lines.push(Line {
+            label: format!("Reconstruction ({} cycles)", cycles.len()),
+            values: series.times.iter().zip(&trend).map(|(t, base)| base + cycles.iter().map(|cycle| wave(cycle, *t)).sum::<f64>()).collect(),
+            color: RED.to_rgba(),
+        });
+    }
+    let mut strongest: Vec<&HiddenCycle> = cycles.iter().collect();
+    strongest.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+    for (i, cycle) in strongest.into_iter().take(MAX_OVERLAID_CYCLES).enumerate() {
+        lines.push(Line {
+            label: format!("{}-bar cycle", cycle.period),
+            values: series.times.iter().zip(&trend).map(|(t, base)| base + wave(cycle, *t)).collect(),
+            color: Palette99::pick(i + 1).mix(0.7),
+        });
+    }
+
+    let times = timestamps(data);
+    render!(target, CHART_SIZE, draw_time_chart("Price and reconstructed cycles", &times, &lines, &[]))
+}
+
+/// Normalized power of the log-return periodogram by period, with the cycle periods
+/// and the white-noise significance level marked
+pub fn plot_spectrum(data: &[ForexDataPoint], cycles: &[HiddenCycle], path: &Path) -> Result<()> {
+    spectrum(data, cycles, Target::File(path))
+}
+
+/// [`plot_spectrum`] as SVG markup
+pub fn spectrum_svg(data: &[ForexDataPoint], cycles: &[HiddenCycle]) -> Result<String> {
+    svg_string(|target| spectrum(data, cycles, target))
+}
+
+fn spectrum(data: &[ForexDataPoint], cycles: &[HiddenCycle], target: Target) -> Result<()> {
+    if data.len() < MIN_SPECTRUM_BARS {
+        bail!("{} bars are too few for a spectrum", data.len());
+    }
+    let series = sampled(data)?;
+    let max_period = (series.span() / 2.0).max(4.0);
+    let (spectrum, independent) = return_periodogram(&series, 2.0, max_period);
+    let mut power: Vec<(f64, f64)> = spectrum.iter().map(|point| (1.0 / point.frequency, point.power)).collect();
+    if power.len() < 2 {
+        bail!("{} bars are too few for a spectrum", data.len());
+    }
+    power.sort_by(|a, b| a.0.total_cmp(&b.0));
+    // Power white noise exceeds at one of the independent frequencies with probability SIGNIFICANCE
+    let threshold = -(-(1.0 - SIGNIFICANCE).powf(1.0 / independent.max(1) as f64)).ln_1p();
+    let marked: Vec<(String, f64)> = cycles.iter()
+        .map(|cycle| (format!("{}-bar cycle", cycle.period), cycle.period as f64))
+        .filter(|(_, period)| (2.0..=max_period).contains(period))
+        .collect();
+    render!(target, CHART_SIZE, draw_spectrum(&power, threshold, &marked))
+}
+
+/// Close prices with the mirror points of the strongest symmetries
+pub fn plot_symmetry_mirrors(data: &[ForexDataPoint], symmetries: &[TemporalSymmetry], path: &Path) -> Result<()> {
+    symmetry_mirrors(data, symmetries, Target::File(path))
+}
+
+/// [`plot_symmetry_mirrors`] as SVG markup
+pub fn symmetry_mirrors_svg(data: &[ForexDataPoint], symmetries: &[TemporalSymmetry]) -> Result<String> {
+    svg_string(|target| symmetry_mirrors(data, symmetries, target))
+}
+
+fn symmetry_mirrors(data: &[ForexDataPoint], symmetries: &[TemporalSymmetry], target: Target) -> Result<()> {
+    if data.len() < 2 {
+        bail!("{} bars are too few to chart", data.len());
+    }
+    let times = timestamps(data);
+    let (first, last) = (times[0], times[times.len() - 1]);
+    let mut strongest: Vec<&TemporalSymmetry> = symmetries.iter().filter(|symmetry| symmetry.mirror_points.len() >= 2).collect();
+    strongest.sort_by(|a, b| b.strength.total_cmp(&a.strength));
+    let marks: Vec<Marks> = strongest.into_iter().take(MAX_MARKED_SYMMETRIES).enumerate()
+        .map(|(i, symmetry)| Marks {
+            label: format!("{} ({:.2})", symmetry.name, symmetry.strength),
+            points: symmetry.mirror_points.iter()
+                .filter_map(|(seconds, price)| Some((Utc.timestamp_opt(*seconds as i64, 0).single()?, *price)))
+                .filter(|(time, _)| (first..=last).contains(time))
+                .collect(),
+            color: Palette99::pick(i + 1).to_rgba(),
+        })
+        .filter(|marks| !marks.points.is_empty())
+        .collect();
+    let closes = Line { label: "Close".to_string(), values: data.iter().map(|point| point.close).collect(), color: BLACK.mix(0.6) };
+    render!(target, CHART_SIZE, draw_time_chart("Symmetry mirror points", &times, &[closes], &marks))
+}
+
+/// Stacked panels of a decomposition: closes with trend and fit, each component
+/// (shortest period first) and the residual
+pub fn plot_decomposition(decomposition: &CycleDecomposition, path: &Path) -> Result<()> {
+    decomposition_panels(decomposition, Target::File(path))
+}
+
+/// [`plot_decomposition`] as SVG markup
+pub fn decomposition_svg(decomposition: &CycleDecomposition) -> Result<String> {
+    svg_string(|target| decomposition_panels(decomposition, target))
+}
+
+fn decomposition_panels(decomposition: &CycleDecomposition, target: Target) -> Result<()> {
+    if decomposition.timestamps.len() < 2 {
+        bail!("{} bars are too few to chart", decomposition.timestamps.len());
+    }
+    let mut panels = vec![(
+        "Close, trend and fit".to_string(),
+        vec![
+            Line { label: "Close".to_string(), values: decomposition.closes.clone(), color: BLACK.to_rgba() },
+            Line { label: "Trend".to_string(), values: decomposition.trend.clone(), color: BLUE.to_rgba() },
+            Line { label: "Fit".to_string(), values: decomposition.fitted(), color: RED.mix(0.8) },
+        ],
+    )];
+    for (i, period) in decomposition.periods().into_iter().enumerate() {
+        let component = &decomposition.components[&period];
+        panels.push((
+            format!("{}-bar cycle: amplitude {:.4}, phase {:.0}°, strength {:.3}", period, component.amplitude, component.phase_degrees, component.strength),
+            vec![Line { label: String::new(), values: component.series.clone(), color: Palette99::pick(i + 1).to_rgba() }],
+        ));
+    }
+    panels.push((
+        format!("Residual (σ {:.5})", decomposition.residual_std()),
+        vec![Line { label: String::new(), values: decomposition.residual.clone(), color: BLACK.mix(0.7) }],
+    ));
+    let size = (CHART_SIZE.0, PANEL_HEIGHT * panels.len() as u32);
+    render!(target, size, draw_panels(&decomposition.timestamps, &panels))
+}
+
+fn draw_time_chart<DB: DrawingBackend>(root: DrawingArea<DB, Shift>, title: &str, times: &[DateTime<Utc>], lines: &[Line], marks: &[Marks]) -> Result<()>
+where
+    DB::ErrorType: 'static,
+{
+    root.fill(&WHITE)?;
+    time_panel(&root, title, times, lines, marks)?;
+    root.present()?;
+    Ok(())
+}
+
+fn draw_panels<DB: DrawingBackend>(root: DrawingArea<DB, Shift>, times: &[DateTime<Utc>], panels: &[(String, Vec<Line>)]) -> Result<()>
+where
+    DB::ErrorType: 'static,
+{
+    root.fill(&WHITE)?;
+    for (area, (title, lines)) in root.split_evenly((panels.len(), 1)).iter().zip(panels) {
+        time_panel(area, title, times, lines, &[])?;
+    }
+    root.present()?;
+    Ok(())
+}
+
+/// Lines over `times` and connected marks on one area, with a legend for labelled series
+fn time_panel<DB: DrawingBackend>(area: &DrawingArea<DB, Shift>, title: &str, times: &[DateTime<Utc>], lines: &[Line], marks: &[Marks]) -> Result<()>
+where
+    DB::ErrorType: 'static,
+{
+    let values = lines.iter().flat_map(|line| line.values.iter().copied())
+        .chain(marks.iter().flat_map(|marks| marks.points.iter().map(|(_, price)| *price)));
+    let mut chart = ChartBuilder::on(area)
+        .caption(title, ("sans-serif", 20))
+        .margin(10)
+        .x_label_area_size(30)
+        .y_label_area_size(70)
+        .build_cartesian_2d(times[0]..times[times.len() - 1], padded_range(values))?;
+    chart.configure_mesh()
+        .x_labels(8)
+        .x_label_formatter(&|time| time.format("%Y-%m-%d").to_string())
+        .y_label_formatter(&|value| format!("{:.4}", value))
+        .light_line_style(WHITE.mix(0.0))
+        .draw()?;
+
+    let mut labelled = false;
+    for line in lines {
+        let color = line.color;
+        let series = chart.draw_series(LineSeries::new(times.iter().copied().zip(line.values.iter().copied()), color.stroke_width(2)))?;
+        if !line.label.is_empty() {
+            series.label(&line.label).legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color.stroke_width(2)));
+            labelled = true;
+        }
+    }
+    for marks in marks {
+        let color = marks.color;
+        chart.draw_series(LineSeries::new(marks.points.iter().copied(), color.stroke_width(1)))?;
+        chart.draw_series(marks.points.iter().map(|point| Circle::new(*point, 5, color.filled())))?
+            .label(&marks.label)
+            .legend(move |(x, y)| Circle::new((x + 10, y), 5, color.filled()));
+        labelled = true;
+    }
+    if labelled {
+        chart.configure_series_labels()
+            .position(SeriesLabelPosition::UpperLeft)
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK.mix(0.5))
+            .draw()?;
+    }
+    Ok(())
+}
+
+fn draw_spectrum<DB: DrawingBackend>(root: DrawingArea<DB, Shift>, power: &[(f64, f64)], threshold: f64, cycles: &[(String, f64)]) -> Result<()>
+where
+    DB::ErrorType: 'static,
+{
+    root.fill(&WHITE)?;
+    let (min_period, max_period) = (power[0].0, power[power.len() - 1].0);
+    let top = power.iter().map(|(_, power)| *power).fold(threshold, f64::max) * 1.1;
+    let mut chart = ChartBuilder::on(&root)
+        .caption("Spectral power of log returns", ("sans-serif", 24))
+        .margin(10)
+        .x_label_area_size(40)
+        .y_label_area_size(60)
+        .build_cartesian_2d((min_period..max_period).log_scale(), 0.0..top)?;
+    chart.configure_mesh()
+        .x_desc("Period (bars)")
+        .y_desc("Normalized power")
+        .x_label_formatter(&|period| format!("{:.0}", period))
+        .draw()?;
+
+    chart.draw_series(LineSeries::new(power.iter().copied(), BLACK.stroke_width(2)))?
+        .label("Periodogram")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLACK.stroke_width(2)));
+    chart.draw_series(LineSeries::new([(min_period, threshold), (max_period, threshold)], RED.mix(0.6).stroke_width(1)))?
+        .label(format!("{:.0}% false alarm", SIGNIFICANCE * 100.0))
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], RED.mix(0.6)));
+    for (i, (label, period)) in cycles.iter().enumerate() {
+        let color = Palette99::pick(i + 1).mix(0.8);
+        chart.draw_series(LineSeries::new([(*period, 0.0), (*period, top)], color.stroke_width(2)))?
+            .label(label)
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color.stroke_width(2)));
+    }
+    chart.configure_series_labels()
+        .position(SeriesLabelPosition::UpperRight)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK.mix(0.5))
+        .draw()?;
+    root.present()?;
+    Ok(())
+}
+
+fn sampled(data: &[ForexDataPoint]) -> Result<SampledSeries> {
+    SampledSeries::from_closes(data).ok_or_else(|| anyhow::anyhow!("{} bars are too few to chart", data.len()))
+}
+
+fn timestamps(data: &[ForexDataPoint]) -> Vec<DateTime<Utc>> {
+    data.iter().map(|point| point.timestamp).collect()
+}
+
+/// Range of the finite `values` with a 5% margin, never empty
+fn padded_range(values: impl Iterator<Item = f64>) -> Range<f64> {
+    let (low, high) = values.filter(|value| value.is_finite())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), value| (low.min(value), high.max(value)));
+    if low > high {
+        return 0.0..1.0;
+    }
+    let margin = ((high - low) * 0.05).max(high.abs().max(low.abs()) * 1e-4).max(1e-9);
+    low - margin..high + margin
+}
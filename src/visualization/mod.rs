@@ -2,20 +2,37 @@
 //! 
 //! Pattern visualization and dashboard functionality.
 
+pub mod charts;
+
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
+use crate::anomaly::suppression::SuppressionList;
+use crate::dashboard::server::{self as dashboard_server, StreamHub};
+use crate::data::provider::provider_from_env;
 use crate::data::{ForexDataPoint, RealTimeDataFeed};
+use crate::multi_currency::MultiCurrencyManager;
+use crate::protocol::{RemoteSystemStatus, SystemMetrics};
 use crate::patterns::{CycleDecomposition, HiddenCycle};
 use crate::symmetry::TemporalSymmetry;
 
+pub use charts::ChartFormat;
+
 /// Dashboard configuration
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct DashboardConfig {
     pub update_interval_ms: u64,
     pub max_data_points: usize,
     pub theme: String,
+    /// Directory pair models are restored from instead of re-analyzing the history
+    #[serde(default)]
+    pub load_model: Option<PathBuf>,
+    /// Directory pair models are saved to after initialization and on shutdown
+    #[serde(default)]
+    pub save_model: Option<PathBuf>,
 }
 
 impl Default for DashboardConfig {
@@ -24,43 +41,123 @@ impl Default for DashboardConfig {
             update_interval_ms: 1000,
             max_data_points: 10000,
             theme: "dark".to_string(),
+            load_model: None,
+            save_model: None,
         }
     }
 }
 
-/// Generate pattern plots
+/// Write the price with reconstructed cycles, the spectral power and the symmetry
+/// mirror points as `format` charts to `output_dir`; returns the files written
 pub fn generate_pattern_plots(
     symmetries: &[TemporalSymmetry],
     cycles: &[HiddenCycle],
     data: &[ForexDataPoint],
-    output_dir: &PathBuf,
-) -> Result<()> {
-    // Placeholder visualization
-    println!("📊 Generated {} symmetry plots", symmetries.len());
-    println!("📊 Generated {} cycle plots", cycles.len());
-    Ok(())
+    output_dir: &Path,
+    format: ChartFormat,
+) -> Result<Vec<PathBuf>> {
+    std::fs::create_dir_all(output_dir)?;
+    let path = |name: &str| output_dir.join(format!("{}.{}", name, format.extension()));
+    let mut written = Vec::new();
+
+    charts::plot_price_cycles(data, cycles, &path("price_cycles"))?;
+    written.push(path("price_cycles"));
+    // Short histories have no spectrum to speak of; the other charts still help
+    match charts::plot_spectrum(data, cycles, &path("spectral_power")) {
+        Ok(()) => written.push(path("spectral_power")),
+        Err(e) => println!("⚠️  Skipped spectral power chart: {}", e),
+    }
+    charts::plot_symmetry_mirrors(data, symmetries, &path("symmetry_mirrors"))?;
+    written.push(path("symmetry_mirrors"));
+
+    println!("📊 Charted {} cycles and {} symmetries in {}", cycles.len(), symmetries.len(), output_dir.display());
+    Ok(written)
 }
 
-/// Launch TUI dashboard
+/// Launch the dashboard: analyze the feed's pairs and serve price/anomaly streams
+/// and the controller API on `port` until interrupted
 pub async fn launch_tui_dashboard(
     data_feed: RealTimeDataFeed,
     port: u16,
     config: DashboardConfig,
 ) -> Result<()> {
-    println!("🚀 TUI Dashboard launched on port {}", port);
-    println!("📊 Real-time pattern recognition active");
-    
-    // Placeholder dashboard loop
-    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-    
+    let pairs = data_feed.get_pairs().to_vec();
+    let mut manager = MultiCurrencyManager::new().with_suppressions(SuppressionList::from_env()?);
+    if let Some(provider) = provider_from_env()? {
+        println!("📡 Live data provider: {}", provider.name());
+        manager = manager.with_data_provider(provider);
+    }
+    if let Some(dir) = &config.load_model {
+        manager = manager.with_model_load_dir(dir.clone());
+    }
+    if let Some(dir) = &config.save_model {
+        manager = manager.with_model_save_dir(dir.clone());
+    }
+    manager.initialize_pairs(&pairs).await?;
+    manager.initialize_all_pairs().await?;
+
+    let manager = Arc::new(manager);
+    if manager.data_provider.is_some() {
+        manager.start_live_feed()?;
+    }
+
+    let status = RemoteSystemStatus {
+        status: "running".to_string(),
+        uptime: 0,
+        active_pairs: pairs.clone(),
+        total_trades: 0,
+        profit_loss: 0.0,
+        correlation_opportunities: Vec::new(),
+        system_metrics: SystemMetrics::default(),
+        feed_health: None,
+    };
+    let hub = StreamHub::new();
+    let server = dashboard_server::spawn(manager.clone(), status, hub.clone(), port);
+
+    println!("🚀 Dashboard running on port {} for {}", port, pairs.join(", "));
+    println!("📊 Real-time pattern recognition active (Ctrl+C to stop)");
+
+    let mut seen = HashMap::new();
+    let mut params_epoch = 0;
+    let mut lagging = HashSet::new();
+    let mut interval = tokio::time::interval(tokio::time::Duration::from_millis(config.update_interval_ms.max(100)));
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                // Monitoring only: signals are detected and streamed, not traded
+                if let Err(e) = manager.process_all_market_updates().await {
+                    println!("⚠️  Analysis cycle failed: {}", e);
+                }
+                dashboard_server::publish_updates(&manager, &hub, &mut seen, &mut params_epoch).await;
+                // Log pairs as they fall behind and catch up; the full panel is on /api/throughput
+                let panel = manager.throughput_panel(hub.backlog()).await;
+                for pair in &panel.pairs {
+                    if !pair.lagging.is_empty() && lagging.insert(pair.symbol.clone()) {
+                        println!("🐢 {} lagging: {}", pair.symbol, pair.lagging.join(", "));
+                    } else if pair.lagging.is_empty() && lagging.remove(&pair.symbol) {
+                        println!("✅ {} caught up", pair.symbol);
+                    }
+                }
+            }
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
+
+    server.abort();
+    // Keep what the agents learned while running
+    if let Some(dir) = &manager.model_save_dir {
+        manager.save_models(dir).await?;
+    }
+    println!("👋 Dashboard stopped");
     Ok(())
 }
 
-/// Plot cycle decomposition
+/// Plot the decomposition as stacked panels; `.svg` files are written as SVG, others as PNG
 pub fn plot_cycle_decomposition(
     decomposition: &CycleDecomposition,
     filename: &str,
 ) -> Result<()> {
+    charts::plot_decomposition(decomposition, Path::new(filename))?;
     println!("📊 Cycle decomposition plot saved to: {}", filename);
     Ok(())
 }